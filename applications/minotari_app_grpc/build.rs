@@ -1,16 +1,23 @@
 // Copyright 2022 The Tari Project
 // SPDX-License-Identifier: BSD-3-Clause
 
+use std::{env, path::PathBuf};
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let out_dir = PathBuf::from(env::var("OUT_DIR")?);
     tonic_build::configure()
         .build_client(true)
         .build_server(true)
         .format(false)
+        // Served by the reflection service
+        .file_descriptor_set_path(out_dir.join("tari_rpc_descriptor.bin"))
         .compile(
             &[
                 "proto/base_node.proto",
                 "proto/wallet.proto",
                 "proto/validator_node.proto",
+                "proto/health.proto",
+                "proto/reflection.proto",
             ],
             &["proto"],
         )?;
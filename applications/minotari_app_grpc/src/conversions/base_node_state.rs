@@ -20,11 +20,14 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::borrow::Borrow;
+use std::{borrow::Borrow, time::Duration};
 
-use tari_core::base_node::state_machine_service::states::{
-    StateInfo,
-    StateInfo::{BlockSync, Connecting, HeaderSync, HorizonSync, Listening, StartUp, SyncFailed},
+use tari_core::base_node::{
+    state_machine_service::states::{
+        StateInfo,
+        StateInfo::{BlockSync, Connecting, HeaderSync, HorizonSync, Listening, StartUp, SyncFailed},
+    },
+    sync::{HorizonSyncStatus, SyncPeer},
 };
 
 use crate::tari_rpc as grpc;
@@ -42,3 +45,66 @@ impl<T: Borrow<StateInfo>> From<T> for grpc::BaseNodeState {
         }
     }
 }
+
+/// Builds the sync progress from the state machine info. Peer latencies are not part of the state machine info and are
+/// left for the caller to fill in.
+impl<T: Borrow<StateInfo>> From<T> for grpc::SyncProgressResponse {
+    fn from(info: T) -> Self {
+        let info = info.borrow();
+        let (tip_height, local_height, state) = match info {
+            HeaderSync(None) => (0, 0, grpc::SyncState::HeaderStarting),
+            HeaderSync(Some(info)) => (info.tip_height, info.local_height, grpc::SyncState::Header),
+            Connecting(_) => (0, 0, grpc::SyncState::BlockStarting),
+            BlockSync(info) => (info.tip_height, info.local_height, grpc::SyncState::Block),
+            _ if info.is_synced() => (0, 0, grpc::SyncState::Done),
+            _ => (0, 0, grpc::SyncState::Startup),
+        };
+
+        let sync_peer = match info {
+            Connecting(sync_peer) => Some(sync_peer),
+            HeaderSync(Some(info)) | BlockSync(info) => Some(&info.sync_peer),
+            HorizonSync(info) => match &info.status {
+                HorizonSyncStatus::Kernels { sync_peer, .. } | HorizonSyncStatus::Outputs { sync_peer, .. } => {
+                    Some(sync_peer)
+                },
+                HorizonSyncStatus::Starting | HorizonSyncStatus::Finalizing => None,
+            },
+            StartUp | HeaderSync(None) | SyncFailed(_) | Listening(_) => None,
+        };
+
+        let items_remaining = match info {
+            HeaderSync(Some(info)) | BlockSync(info) => info.tip_height.saturating_sub(info.local_height),
+            HorizonSync(info) => match info.status {
+                HorizonSyncStatus::Kernels { current, total, .. } |
+                HorizonSyncStatus::Outputs { current, total, .. } => total.saturating_sub(current),
+                HorizonSyncStatus::Starting | HorizonSyncStatus::Finalizing => 0,
+            },
+            _ => 0,
+        };
+
+        let last_error = match info {
+            SyncFailed(details) => details.clone(),
+            _ => String::new(),
+        };
+
+        Self {
+            tip_height,
+            local_height,
+            state: state.into(),
+            phase: grpc::BaseNodeState::from(info).into(),
+            sync_peer_node_id: sync_peer.map(|p| p.node_id().to_vec()).unwrap_or_default(),
+            items_remaining,
+            items_per_second: sync_peer.and_then(SyncPeer::items_per_second).unwrap_or_default(),
+            sync_peer_avg_latency_ms: sync_peer
+                .and_then(SyncPeer::calc_avg_latency)
+                .map(duration_to_millis)
+                .unwrap_or_default(),
+            last_error,
+            peer_latencies: Vec::new(),
+        }
+    }
+}
+
+fn duration_to_millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
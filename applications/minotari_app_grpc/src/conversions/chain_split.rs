@@ -0,0 +1,63 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use tari_core::base_node::chain_split_monitor::{ChainSplit, ChainSplitEvent, ForkedPeer};
+use tari_utilities::ByteArray;
+
+use crate::tari_rpc as grpc;
+
+impl From<ForkedPeer> for grpc::ForkedPeer {
+    fn from(peer: ForkedPeer) -> Self {
+        Self {
+            node_id: peer.node_id.to_vec(),
+            metadata: Some(peer.chain_metadata.into()),
+            blocks_since_divergence: peer.blocks_since_divergence,
+        }
+    }
+}
+
+impl From<ChainSplit> for grpc::ChainSplit {
+    fn from(split: ChainSplit) -> Self {
+        let competing_fork_is_stronger = split.is_competing_fork_stronger();
+        Self {
+            local_metadata: Some(split.local_chain.into()),
+            competing_peers: split.competing_peers.into_iter().map(Into::into).collect(),
+            fork_depth: split.fork_depth,
+            competing_fork_is_stronger,
+        }
+    }
+}
+
+impl From<ChainSplitEvent> for grpc::ChainSplitEvent {
+    fn from(event: ChainSplitEvent) -> Self {
+        match event {
+            ChainSplitEvent::SplitDetected(split) => Self {
+                event_type: grpc::ChainSplitEventType::SplitDetected.into(),
+                split: Some(split.into()),
+            },
+            ChainSplitEvent::SplitResolved => Self {
+                event_type: grpc::ChainSplitEventType::SplitResolved.into(),
+                split: None,
+            },
+        }
+    }
+}
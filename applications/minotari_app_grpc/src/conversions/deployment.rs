@@ -0,0 +1,52 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+use tari_core::consensus::deployments::{Deployment, DeploymentState};
+
+use crate::tari_rpc as grpc;
+
+impl From<DeploymentState> for grpc::DeploymentState {
+    fn from(state: DeploymentState) -> Self {
+        match state {
+            DeploymentState::Defined => grpc::DeploymentState::Defined,
+            DeploymentState::Started => grpc::DeploymentState::Started,
+            DeploymentState::LockedIn => grpc::DeploymentState::LockedIn,
+            DeploymentState::Active => grpc::DeploymentState::Active,
+            DeploymentState::Failed => grpc::DeploymentState::Failed,
+        }
+    }
+}
+
+impl grpc::DeploymentStatus {
+    pub fn new(deployment: &Deployment, state: DeploymentState, signals_in_current_period: u64) -> Self {
+        let state: grpc::DeploymentState = state.into();
+        Self {
+            name: deployment.name.to_string(),
+            bit: u32::from(deployment.bit),
+            start_height: deployment.start_height,
+            timeout_height: deployment.timeout_height,
+            period: deployment.period,
+            threshold: deployment.threshold,
+            state: state.into(),
+            signals_in_current_period,
+        }
+    }
+}
@@ -25,21 +25,26 @@ mod base_node_state;
 mod block;
 mod block_header;
 mod chain_metadata;
+mod chain_split;
 mod com_and_pub_signature;
 mod commitment_signature;
 mod consensus_constants;
+mod deployment;
 mod historical_block;
 mod new_block_template;
 mod output_features;
+mod payment_proof;
 mod peer;
 mod proof_of_work;
 mod sidechain_feature;
 mod signature;
+mod sync_candidate;
 mod transaction;
 mod transaction_input;
 mod transaction_kernel;
 mod transaction_output;
 mod unblinded_output;
+mod validation_rejection;
 
 use prost_types::Timestamp;
 
@@ -49,18 +54,22 @@ pub use self::{
     block::*,
     block_header::*,
     chain_metadata::*,
+    chain_split::*,
     com_and_pub_signature::*,
     consensus_constants::*,
     historical_block::*,
     new_block_template::*,
     output_features::*,
+    payment_proof::*,
     peer::*,
     proof_of_work::*,
     signature::*,
+    sync_candidate::*,
     transaction::*,
     transaction_input::*,
     transaction_kernel::*,
     transaction_output::*,
+    validation_rejection::*,
 };
 use crate::{tari_rpc as grpc, tari_rpc::BlockGroupRequest};
 
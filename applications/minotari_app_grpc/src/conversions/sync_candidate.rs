@@ -0,0 +1,57 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::convert::TryFrom;
+
+use tari_core::base_node::state_machine_service::{SyncCandidate, SyncCandidateReason};
+use tari_utilities::ByteArray;
+
+use crate::tari_rpc as grpc;
+
+impl From<SyncCandidateReason> for grpc::SyncCandidateReason {
+    fn from(reason: SyncCandidateReason) -> Self {
+        match reason {
+            SyncCandidateReason::HigherAccumulatedDifficulty => grpc::SyncCandidateReason::HigherAccumulatedDifficulty,
+            SyncCandidateReason::SameHeightFork => grpc::SyncCandidateReason::SameHeightFork,
+            SyncCandidateReason::Behind => grpc::SyncCandidateReason::Behind,
+            SyncCandidateReason::InSync => grpc::SyncCandidateReason::InSync,
+            SyncCandidateReason::CannotSupplyBlocks => grpc::SyncCandidateReason::CannotSupplyBlocks,
+        }
+    }
+}
+
+impl From<SyncCandidate> for grpc::SyncCandidate {
+    fn from(candidate: SyncCandidate) -> Self {
+        let reason: grpc::SyncCandidateReason = candidate.reason.into();
+        Self {
+            node_id: candidate.peer.node_id().to_vec(),
+            metadata: Some(candidate.peer.claimed_chain_metadata().clone().into()),
+            latency_ms: candidate
+                .peer
+                .latency()
+                .map(|l| u64::try_from(l.as_millis()).unwrap_or(u64::MAX))
+                .unwrap_or_default(),
+            reason: reason.into(),
+            is_candidate: candidate.is_candidate,
+        }
+    }
+}
@@ -0,0 +1,44 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use tari_core::validation::{RejectionLocation, ValidationRejection};
+
+use crate::tari_rpc as grpc;
+
+impl From<ValidationRejection> for grpc::ValidationRejection {
+    fn from(rejection: ValidationRejection) -> Self {
+        let (location_type, index) = match rejection.location {
+            None => (grpc::RejectionLocationType::None, 0),
+            Some(RejectionLocation::Output(index)) => (grpc::RejectionLocationType::Output, index as u64),
+            Some(RejectionLocation::Kernel(index)) => (grpc::RejectionLocationType::Kernel, index as u64),
+        };
+        Self {
+            rule: rejection.rule.to_string(),
+            location_type: location_type as i32,
+            index,
+            consensus_constant: rejection.consensus_constant.unwrap_or_default().to_string(),
+            limit: rejection.limit.unwrap_or_default(),
+            actual: rejection.actual.unwrap_or_default(),
+            message: rejection.message,
+        }
+    }
+}
@@ -0,0 +1,157 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use futures::{channel::mpsc, future::BoxFuture, SinkExt};
+use log::*;
+use tonic::{Request, Response, Status};
+
+use crate::grpc_health_v1::{
+    health_check_response::ServingStatus,
+    health_server::Health,
+    HealthCheckRequest,
+    HealthCheckResponse,
+};
+
+const LOG_TARGET: &str = "minotari::grpc::health";
+
+/// How often the status of a watched service is checked for changes
+const WATCH_INTERVAL: Duration = Duration::from_secs(5);
+
+type HealthCheckFn = Box<dyn FnMut() -> BoxFuture<'static, bool> + Send>;
+
+/// Implements the standard `grpc.health.v1` health service. The server as a whole (the empty service name) and each
+/// of the given services are reported as `SERVING` while the application health check passes, and as `NOT_SERVING`
+/// otherwise.
+#[derive(Clone)]
+pub struct HealthService {
+    services: Arc<Vec<String>>,
+    health_check: Arc<Mutex<HealthCheckFn>>,
+}
+
+impl HealthService {
+    pub fn new<F>(services: &[&str], health_check: F) -> Self
+    where F: FnMut() -> BoxFuture<'static, bool> + Send + 'static {
+        Self {
+            services: Arc::new(services.iter().map(|s| s.to_string()).collect()),
+            health_check: Arc::new(Mutex::new(Box::new(health_check))),
+        }
+    }
+
+    async fn status_of(&self, service: &str) -> ServingStatus {
+        if !service.is_empty() && !self.services.iter().any(|s| s == service) {
+            return ServingStatus::ServiceUnknown;
+        }
+        let check = match self.health_check.lock() {
+            Ok(mut health_check) => (health_check)(),
+            Err(_) => {
+                error!(target: LOG_TARGET, "The health check lock is poisoned");
+                return ServingStatus::NotServing;
+            },
+        };
+        if check.await {
+            ServingStatus::Serving
+        } else {
+            ServingStatus::NotServing
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl Health for HealthService {
+    type WatchStream = mpsc::Receiver<Result<HealthCheckResponse, Status>>;
+
+    async fn check(&self, request: Request<HealthCheckRequest>) -> Result<Response<HealthCheckResponse>, Status> {
+        let service = request.into_inner().service;
+        match self.status_of(&service).await {
+            ServingStatus::ServiceUnknown => Err(Status::not_found(format!("Unknown service '{}'", service))),
+            status => Ok(Response::new(HealthCheckResponse { status: status as i32 })),
+        }
+    }
+
+    async fn watch(&self, request: Request<HealthCheckRequest>) -> Result<Response<Self::WatchStream>, Status> {
+        let service = request.into_inner().service;
+        let (mut tx, rx) = mpsc::channel(1);
+        let health = self.clone();
+        tokio::spawn(async move {
+            let mut last_status = None;
+            while !tx.is_closed() {
+                let status = health.status_of(&service).await;
+                if last_status != Some(status) {
+                    if tx
+                        .send(Ok(HealthCheckResponse { status: status as i32 }))
+                        .await
+                        .is_err()
+                    {
+                        break;
+                    }
+                    last_status = Some(status);
+                }
+                tokio::time::sleep(WATCH_INTERVAL).await;
+            }
+            debug!(target: LOG_TARGET, "Health watch for '{}' ended", service);
+        });
+        Ok(Response::new(rx))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use futures::FutureExt;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn it_reports_the_health_check_status() {
+        let mut healthy = true;
+        let health = HealthService::new(&["tari.rpc.BaseNode"], move || {
+            let status = healthy;
+            healthy = false;
+            async move { status }.boxed()
+        });
+
+        let response = health
+            .check(Request::new(HealthCheckRequest {
+                service: "tari.rpc.BaseNode".to_string(),
+            }))
+            .await
+            .unwrap();
+        assert_eq!(response.into_inner().status, ServingStatus::Serving as i32);
+        let response = health
+            .check(Request::new(HealthCheckRequest { service: String::new() }))
+            .await
+            .unwrap();
+        assert_eq!(response.into_inner().status, ServingStatus::NotServing as i32);
+
+        let status = health
+            .check(Request::new(HealthCheckRequest {
+                service: "tari.rpc.Unknown".to_string(),
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::NotFound);
+    }
+}
@@ -23,6 +23,21 @@ pub mod authentication;
 
 pub mod conversions;
 
+pub mod health;
+
+pub mod reflection;
+
 pub mod tari_rpc {
     tonic::include_proto!("tari.rpc");
 }
+
+pub mod grpc_health_v1 {
+    tonic::include_proto!("grpc.health.v1");
+}
+
+pub mod grpc_reflection_v1alpha {
+    tonic::include_proto!("grpc.reflection.v1alpha");
+}
+
+/// The encoded descriptors of all the protobuf files compiled into this crate
+pub const FILE_DESCRIPTOR_SET: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/tari_rpc_descriptor.bin"));
@@ -0,0 +1,289 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use futures::{channel::mpsc, SinkExt};
+use log::*;
+use prost::{DecodeError, Message};
+use prost_types::{DescriptorProto, FileDescriptorProto, FileDescriptorSet};
+use tonic::{Code, Request, Response, Status, Streaming};
+
+use crate::{
+    grpc_reflection_v1alpha::{
+        server_reflection_request::MessageRequest,
+        server_reflection_response::MessageResponse,
+        server_reflection_server::ServerReflection,
+        ErrorResponse,
+        ExtensionNumberResponse,
+        FileDescriptorResponse,
+        ListServiceResponse,
+        ServerReflectionRequest,
+        ServerReflectionResponse,
+        ServiceResponse,
+    },
+    FILE_DESCRIPTOR_SET,
+};
+
+const LOG_TARGET: &str = "minotari::grpc::reflection";
+
+/// The name of the reflection service, which is always listed
+const REFLECTION_SERVICE_NAME: &str = "grpc.reflection.v1alpha.ServerReflection";
+
+/// Implements the `grpc.reflection.v1alpha` server reflection service from the protobuf definitions compiled into
+/// this crate, so that tools like grpcurl and evans can discover the served services without a local copy of the
+/// proto files.
+#[derive(Clone)]
+pub struct ReflectionService {
+    state: Arc<ReflectionState>,
+}
+
+impl ReflectionService {
+    /// Creates a reflection service that lists the given fully qualified service names
+    pub fn new(services: &[&str]) -> Result<Self, DecodeError> {
+        let descriptor_set = FileDescriptorSet::decode(FILE_DESCRIPTOR_SET)?;
+        let mut services = services.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        if !services.iter().any(|s| s == REFLECTION_SERVICE_NAME) {
+            services.push(REFLECTION_SERVICE_NAME.to_string());
+        }
+        Ok(Self {
+            state: Arc::new(ReflectionState::new(services, descriptor_set)),
+        })
+    }
+}
+
+#[tonic::async_trait]
+impl ServerReflection for ReflectionService {
+    type ServerReflectionInfoStream = mpsc::Receiver<Result<ServerReflectionResponse, Status>>;
+
+    async fn server_reflection_info(
+        &self,
+        request: Request<Streaming<ServerReflectionRequest>>,
+    ) -> Result<Response<Self::ServerReflectionInfoStream>, Status> {
+        let mut requests = request.into_inner();
+        let (mut tx, rx) = mpsc::channel(1);
+        let state = self.state.clone();
+        tokio::spawn(async move {
+            loop {
+                let response = match requests.message().await {
+                    Ok(Some(request)) => Ok(state.respond(request)),
+                    Ok(None) => break,
+                    Err(status) => Err(status),
+                };
+                let is_err = response.is_err();
+                if tx.send(response).await.is_err() || is_err {
+                    break;
+                }
+            }
+            debug!(target: LOG_TARGET, "Reflection stream ended");
+        });
+        Ok(Response::new(rx))
+    }
+}
+
+struct ReflectionState {
+    services: Vec<String>,
+    /// Maps file name -> file descriptor
+    files: HashMap<String, FileDescriptorProto>,
+    /// Maps fully qualified symbol name -> the name of the file that declares it
+    symbols: HashMap<String, String>,
+}
+
+impl ReflectionState {
+    fn new(services: Vec<String>, descriptor_set: FileDescriptorSet) -> Self {
+        let mut files = HashMap::new();
+        let mut symbols = HashMap::new();
+        for file in descriptor_set.file {
+            let file_name = file.name().to_string();
+            let package = file.package();
+            for service in &file.service {
+                let service_name = qualify(package, service.name());
+                for method in &service.method {
+                    symbols.insert(format!("{}.{}", service_name, method.name()), file_name.clone());
+                }
+                symbols.insert(service_name, file_name.clone());
+            }
+            for message in &file.message_type {
+                insert_message_symbols(&mut symbols, &qualify(package, message.name()), message, &file_name);
+            }
+            for enum_type in &file.enum_type {
+                symbols.insert(qualify(package, enum_type.name()), file_name.clone());
+            }
+            files.insert(file_name, file);
+        }
+        Self {
+            services,
+            files,
+            symbols,
+        }
+    }
+
+    fn respond(&self, request: ServerReflectionRequest) -> ServerReflectionResponse {
+        let message_response = match &request.message_request {
+            Some(MessageRequest::FileByFilename(file_name)) => self.file_descriptors(file_name),
+            Some(MessageRequest::FileContainingSymbol(symbol)) => match self.symbols.get(symbol) {
+                Some(file_name) => self.file_descriptors(file_name),
+                None => error_response(Code::NotFound, format!("Symbol '{}' not found", symbol)),
+            },
+            Some(MessageRequest::FileContainingExtension(extension)) => error_response(
+                Code::NotFound,
+                format!("No extensions of '{}' are defined", extension.containing_type),
+            ),
+            Some(MessageRequest::AllExtensionNumbersOfType(type_name)) => {
+                if self.symbols.contains_key(type_name) {
+                    MessageResponse::AllExtensionNumbersResponse(ExtensionNumberResponse {
+                        base_type_name: type_name.clone(),
+                        extension_number: vec![],
+                    })
+                } else {
+                    error_response(Code::NotFound, format!("Type '{}' not found", type_name))
+                }
+            },
+            Some(MessageRequest::ListServices(_)) => MessageResponse::ListServicesResponse(ListServiceResponse {
+                service: self
+                    .services
+                    .iter()
+                    .map(|name| ServiceResponse { name: name.clone() })
+                    .collect(),
+            }),
+            None => error_response(Code::InvalidArgument, "No message request provided".to_string()),
+        };
+        ServerReflectionResponse {
+            valid_host: request.host.clone(),
+            original_request: Some(request),
+            message_response: Some(message_response),
+        }
+    }
+
+    /// Returns the encoded descriptor of the file and all of its transitive dependencies
+    fn file_descriptors(&self, file_name: &str) -> MessageResponse {
+        if !self.files.contains_key(file_name) {
+            return error_response(Code::NotFound, format!("File '{}' not found", file_name));
+        }
+        let mut seen = HashSet::new();
+        let mut pending = vec![file_name];
+        let mut file_descriptor_proto = vec![];
+        while let Some(name) = pending.pop() {
+            if !seen.insert(name) {
+                continue;
+            }
+            if let Some(file) = self.files.get(name) {
+                file_descriptor_proto.push(file.encode_to_vec());
+                pending.extend(file.dependency.iter().map(String::as_str));
+            }
+        }
+        MessageResponse::FileDescriptorResponse(FileDescriptorResponse { file_descriptor_proto })
+    }
+}
+
+fn qualify(package: &str, name: &str) -> String {
+    if package.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", package, name)
+    }
+}
+
+fn insert_message_symbols(
+    symbols: &mut HashMap<String, String>,
+    message_name: &str,
+    message: &DescriptorProto,
+    file_name: &str,
+) {
+    for nested in &message.nested_type {
+        insert_message_symbols(symbols, &qualify(message_name, nested.name()), nested, file_name);
+    }
+    for enum_type in &message.enum_type {
+        symbols.insert(qualify(message_name, enum_type.name()), file_name.to_string());
+    }
+    symbols.insert(message_name.to_string(), file_name.to_string());
+}
+
+fn error_response(code: Code, error_message: String) -> MessageResponse {
+    MessageResponse::ErrorResponse(ErrorResponse {
+        error_code: code as i32,
+        error_message,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn request(message_request: MessageRequest) -> ServerReflectionRequest {
+        ServerReflectionRequest {
+            host: String::new(),
+            message_request: Some(message_request),
+        }
+    }
+
+    #[test]
+    fn it_lists_the_served_services() {
+        let reflection = ReflectionService::new(&["tari.rpc.BaseNode"]).unwrap();
+        let response = reflection
+            .state
+            .respond(request(MessageRequest::ListServices(String::new())));
+        match response.message_response.unwrap() {
+            MessageResponse::ListServicesResponse(list) => {
+                let names = list.service.into_iter().map(|s| s.name).collect::<Vec<_>>();
+                assert_eq!(names, vec!["tari.rpc.BaseNode", REFLECTION_SERVICE_NAME]);
+            },
+            _ => panic!("Expected a list services response"),
+        }
+    }
+
+    #[test]
+    fn it_returns_the_file_containing_a_symbol_with_its_dependencies() {
+        let reflection = ReflectionService::new(&["tari.rpc.BaseNode"]).unwrap();
+        let response = reflection.state.respond(request(MessageRequest::FileContainingSymbol(
+            "tari.rpc.BaseNode.GetTipInfo".to_string(),
+        )));
+        match response.message_response.unwrap() {
+            MessageResponse::FileDescriptorResponse(files) => {
+                let names = files
+                    .file_descriptor_proto
+                    .iter()
+                    .map(|bytes| {
+                        FileDescriptorProto::decode(bytes.as_slice())
+                            .unwrap()
+                            .name()
+                            .to_string()
+                    })
+                    .collect::<Vec<_>>();
+                assert_eq!(names[0], "base_node.proto");
+                assert!(names.iter().any(|name| name == "types.proto"));
+            },
+            _ => panic!("Expected a file descriptor response"),
+        }
+
+        let response = reflection.state.respond(request(MessageRequest::FileContainingSymbol(
+            "tari.rpc.Unknown".to_string(),
+        )));
+        assert!(matches!(
+            response.message_response.unwrap(),
+            MessageResponse::ErrorResponse(ErrorResponse { error_code, .. }) if error_code == Code::NotFound as i32
+        ));
+    }
+}
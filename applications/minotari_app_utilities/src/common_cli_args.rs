@@ -53,6 +53,15 @@ pub struct CommonCliArgs {
     /// Overrides for properties in the config file, e.g. -p base_node.network=esmeralda
     #[clap(short = 'p', parse(try_from_str = parse_key_val), multiple_occurrences(true))]
     pub config_property_overrides: Vec<(String, String)>,
+
+    /// Print the effective configuration, after applying the config file, environment and command line overrides,
+    /// and exit
+    #[clap(long)]
+    pub print_config: bool,
+
+    /// With --print-config, show the source of each value: default, file, environment or command line
+    #[clap(long, requires = "print_config")]
+    pub explain: bool,
 }
 
 // Taken from clap examples
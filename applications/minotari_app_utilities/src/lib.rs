@@ -23,6 +23,7 @@
 pub mod common_cli_args;
 pub mod identity_management;
 pub mod network_check;
+pub mod service_manager;
 pub mod utilities;
 
 pub mod consts {
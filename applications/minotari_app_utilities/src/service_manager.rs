@@ -0,0 +1,248 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Integration with service managers, so that a supervisor can detect a hung application rather than only a crashed
+//! one.
+//!
+//! On Linux this implements the systemd notification protocol (`sd_notify`): `READY=1` is sent once the application
+//! has started, `WATCHDOG=1` keep-alives are sent for as long as the application's health check passes and
+//! `STOPPING=1` is sent on shutdown. Keep-alives are only sent when the unit sets `WatchdogSec=`, in which case systemd
+//! restarts the application once they stop arriving. Notifications are silently skipped when the application is not
+//! run under systemd (`NOTIFY_SOCKET` is not set) and on other platforms.
+//!
+//! [wait_for_stop_request] resolves when the service manager asks the application to stop: `SIGTERM` on unix, and the
+//! close and shutdown control events on Windows.
+
+#[cfg(target_os = "linux")]
+use std::sync::Arc;
+use std::{future::Future, io, time::Duration};
+
+use log::*;
+use tari_shutdown::ShutdownSignal;
+use tokio::time;
+
+const LOG_TARGET: &str = "minotari::application::service_manager";
+
+/// The result of an application health check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HealthStatus {
+    /// The application is making progress. The description is reported to the service manager as the status line.
+    Healthy(String),
+    /// The application is not making progress, watchdog keep-alives are withheld until it recovers
+    Unhealthy(String),
+}
+
+/// Sends notifications to the service manager that started this process
+#[derive(Clone, Default)]
+pub struct ServiceNotifier {
+    #[cfg(target_os = "linux")]
+    socket: Option<Arc<linux::NotifySocket>>,
+    watchdog_interval: Option<Duration>,
+}
+
+impl ServiceNotifier {
+    /// Connects to the notification socket given by the service manager in the environment. The notifier does nothing
+    /// if the service manager does not expect notifications.
+    pub fn from_env() -> Self {
+        #[cfg(target_os = "linux")]
+        {
+            let socket = match linux::NotifySocket::from_env() {
+                Ok(socket) => socket.map(Arc::new),
+                Err(err) => {
+                    warn!(target: LOG_TARGET, "Could not connect to the systemd notify socket: {}", err);
+                    None
+                },
+            };
+            let watchdog_interval = socket.as_ref().and_then(|_| linux::watchdog_interval_from_env());
+            Self {
+                socket,
+                watchdog_interval,
+            }
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            Self::default()
+        }
+    }
+
+    /// Returns true if the service manager expects notifications from this process
+    pub fn is_enabled(&self) -> bool {
+        #[cfg(target_os = "linux")]
+        {
+            self.socket.is_some()
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            false
+        }
+    }
+
+    /// The interval within which the service manager expects a watchdog keep-alive, if it monitors this process
+    pub fn watchdog_interval(&self) -> Option<Duration> {
+        self.watchdog_interval
+    }
+
+    /// Tells the service manager that the application has finished starting up
+    pub fn ready(&self) {
+        self.notify("READY=1");
+    }
+
+    /// Tells the service manager that the application is shutting down
+    pub fn stopping(&self) {
+        self.notify("STOPPING=1");
+    }
+
+    /// Sets the status line the service manager displays for this process
+    pub fn status(&self, status: &str) {
+        // The protocol is newline delimited
+        self.notify(&format!("STATUS={}", status.replace('\n', " ")));
+    }
+
+    /// Sends a watchdog keep-alive
+    pub fn watchdog(&self) {
+        self.notify("WATCHDOG=1");
+    }
+
+    #[allow(unused_variables)]
+    fn notify(&self, state: &str) {
+        #[cfg(target_os = "linux")]
+        if let Some(socket) = self.socket.as_ref() {
+            if let Err(err) = socket.send(state) {
+                debug!(target: LOG_TARGET, "Failed to send `{}` to the systemd notify socket: {}", state, err);
+            }
+        }
+    }
+}
+
+/// Runs the health check at half the watchdog interval until shutdown, sending a keep-alive each time it passes. A
+/// health check that does not complete within that time counts as failed. Returns immediately if the service manager
+/// does not monitor this process.
+pub async fn run_watchdog<F, Fut>(notifier: ServiceNotifier, mut health_check: F, mut shutdown_signal: ShutdownSignal)
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = HealthStatus>,
+{
+    let Some(watchdog_interval) = notifier.watchdog_interval() else {
+        return;
+    };
+    let check_interval = watchdog_interval / 2;
+    info!(
+        target: LOG_TARGET,
+        "Service manager watchdog enabled, checking application health every {:.0?}", check_interval
+    );
+    let mut interval = time::interval(check_interval);
+    interval.set_missed_tick_behavior(time::MissedTickBehavior::Delay);
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                match time::timeout(check_interval, health_check()).await {
+                    Ok(HealthStatus::Healthy(status)) => {
+                        notifier.status(&status);
+                        notifier.watchdog();
+                    },
+                    Ok(HealthStatus::Unhealthy(reason)) => {
+                        warn!(target: LOG_TARGET, "Health check failed: {}", reason);
+                        notifier.status(&format!("Unhealthy: {}", reason));
+                    },
+                    Err(_) => {
+                        warn!(target: LOG_TARGET, "Health check did not complete within {:.0?}", check_interval);
+                        notifier.status("Unhealthy: health check timed out");
+                    },
+                }
+            },
+            _ = shutdown_signal.wait() => break,
+        }
+    }
+}
+
+/// Resolves when the service manager asks the application to stop
+pub async fn wait_for_stop_request() -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        signal(SignalKind::terminate())?.recv().await;
+    }
+    #[cfg(windows)]
+    {
+        use tokio::signal::windows::{ctrl_close, ctrl_shutdown};
+        let mut close = ctrl_close()?;
+        let mut shutdown = ctrl_shutdown()?;
+        tokio::select! {
+            _ = close.recv() => {},
+            _ = shutdown.recv() => {},
+        }
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::{
+        env,
+        io,
+        os::{
+            linux::net::SocketAddrExt,
+            unix::{
+                ffi::OsStrExt,
+                net::{SocketAddr, UnixDatagram},
+            },
+        },
+        time::Duration,
+    };
+
+    pub struct NotifySocket {
+        socket: UnixDatagram,
+        addr: SocketAddr,
+    }
+
+    impl NotifySocket {
+        pub fn from_env() -> io::Result<Option<Self>> {
+            let Some(path) = env::var_os("NOTIFY_SOCKET") else {
+                return Ok(None);
+            };
+            // A leading '@' denotes a socket in the abstract namespace
+            let addr = match path.as_bytes().strip_prefix(b"@") {
+                Some(name) => SocketAddr::from_abstract_name(name)?,
+                None => SocketAddr::from_pathname(&path)?,
+            };
+            let socket = UnixDatagram::unbound()?;
+            Ok(Some(Self { socket, addr }))
+        }
+
+        pub fn send(&self, state: &str) -> io::Result<()> {
+            self.socket.send_to_addr(state.as_bytes(), &self.addr)?;
+            Ok(())
+        }
+    }
+
+    /// Reads the watchdog interval systemd sets in the environment when the unit has `WatchdogSec=` configured
+    pub fn watchdog_interval_from_env() -> Option<Duration> {
+        let usec = env::var("WATCHDOG_USEC").ok()?.parse::<u64>().ok()?;
+        // The watchdog is meant for a specific process if WATCHDOG_PID is set
+        if let Ok(pid) = env::var("WATCHDOG_PID") {
+            if pid.parse::<u32>().ok()? != std::process::id() {
+                return None;
+            }
+        }
+        Some(Duration::from_micros(usec)).filter(|d| !d.is_zero())
+    }
+}
@@ -27,6 +27,7 @@ use std::{
     io,
     io::{LineWriter, Write},
     path::{Path, PathBuf},
+    str::FromStr,
     time::{Duration, Instant},
 };
 
@@ -38,7 +39,13 @@ use minotari_app_grpc::authentication::salted_password::create_salted_hashed_pas
 use minotari_wallet::{
     connectivity_service::WalletConnectivityInterface,
     output_manager_service::{handle::OutputManagerHandle, UtxoSelectionCriteria},
-    transaction_service::handle::{TransactionEvent, TransactionServiceHandle},
+    transaction_service::handle::{
+        RecipientLivenessCheck,
+        SendTransactionOutcome,
+        TransactionEvent,
+        TransactionServiceHandle,
+    },
+    util::payment_request::PaymentRequest,
     TransactionStage,
     WalletConfig,
     WalletSqlite,
@@ -50,6 +57,7 @@ use tari_common_types::{
     burnt_proof::BurntProof,
     emoji::EmojiId,
     tari_address::TariAddress,
+    tari_uri::{TariUri, TariUriAction},
     transaction::TxId,
     types::{Commitment, FixedHash, PublicKey, Signature},
 };
@@ -59,6 +67,7 @@ use tari_comms::{
     types::CommsPublicKey,
 };
 use tari_comms_dht::{envelope::NodeDestination, DhtDiscoveryRequester};
+use tari_contacts::contacts_service::types::Contact;
 use tari_core::transactions::{
     tari_amount::{uT, MicroMinotari, Minotari},
     transaction_components::{OutputFeatures, TransactionOutput, WalletOutput},
@@ -72,8 +81,11 @@ use tokio::{
 
 use super::error::CommandError;
 use crate::{
-    cli::{CliCommands, MakeItRainTransactionType},
-    utils::db::{CUSTOM_BASE_NODE_ADDRESS_KEY, CUSTOM_BASE_NODE_PUBLIC_KEY_KEY},
+    cli::{CliCommands, MakeItRainTransactionType, RecipientCheck, SendMinotariInteractiveArgs},
+    utils::{
+        db::{CUSTOM_BASE_NODE_ADDRESS_KEY, CUSTOM_BASE_NODE_PUBLIC_KEY_KEY},
+        qr_code::{render_qr_code, write_qr_code_png},
+    },
 };
 
 pub const LOG_TARGET: &str = "wallet::automation::commands";
@@ -102,6 +114,7 @@ pub enum WalletCommand {
     MintTokens,
     CreateInitialCheckpoint,
     RevalidateWalletDb,
+    AuditOutputs,
 }
 
 #[derive(Debug)]
@@ -128,6 +141,32 @@ pub async fn send_tari(
         .map_err(CommandError::TransactionServiceError)
 }
 
+pub async fn send_tari_with_recipient_check(
+    mut wallet_transaction_service: TransactionServiceHandle,
+    fee_per_gram: u64,
+    amount: MicroMinotari,
+    destination: TariAddress,
+    message: String,
+    recipient_check: RecipientCheck,
+) -> Result<SendTransactionOutcome, CommandError> {
+    let recipient_check = match recipient_check {
+        RecipientCheck::Fallback => RecipientLivenessCheck::FallbackToOneSided,
+        RecipientCheck::Fail => RecipientLivenessCheck::Fail,
+    };
+    wallet_transaction_service
+        .send_transaction_with_recipient_check(
+            destination,
+            amount,
+            UtxoSelectionCriteria::default(),
+            OutputFeatures::default(),
+            fee_per_gram * uT,
+            message,
+            recipient_check,
+        )
+        .await
+        .map_err(CommandError::TransactionServiceError)
+}
+
 pub async fn burn_tari(
     mut wallet_transaction_service: TransactionServiceHandle,
     fee_per_gram: u64,
@@ -314,6 +353,62 @@ async fn set_base_node_peer(
     Ok((public_key, address))
 }
 
+/// Performs the action of a `tari://` deep link, returning the id of the transaction it sent, if any
+async fn open_tari_uri(
+    wallet: WalletSqlite,
+    wallet_transaction_service: TransactionServiceHandle,
+    fee_per_gram: u64,
+    uri: TariUri,
+) -> Result<Option<TxId>, CommandError> {
+    match uri.action {
+        TariUriAction::Send {
+            address,
+            amount,
+            message,
+        } => {
+            let amount = amount.ok_or_else(|| {
+                CommandError::InvalidArgument("The payment request does not specify an amount".to_string())
+            })?;
+            let tx_id = send_tari(
+                wallet_transaction_service,
+                fee_per_gram,
+                MicroMinotari::from(amount),
+                address,
+                message.unwrap_or_default(),
+            )
+            .await?;
+            Ok(Some(tx_id))
+        },
+        TariUriAction::AddContact { address, alias } => {
+            let mut contacts_service = wallet.contacts_service.clone();
+            contacts_service
+                .upsert_contact(Contact::new(
+                    alias.unwrap_or_default(),
+                    address.clone(),
+                    None,
+                    None,
+                    false,
+                ))
+                .await?;
+            println!("Added contact {}", address);
+            Ok(None)
+        },
+        TariUriAction::AddBaseNode {
+            public_key, addresses, ..
+        } => {
+            let address = addresses
+                .first()
+                .ok_or_else(|| CommandError::InvalidArgument("The base node has no address".to_string()))?;
+            let address = Multiaddr::from_str(address).map_err(|e| CommandError::InvalidArgument(e.to_string()))?;
+            set_base_node_peer(wallet, public_key, address).await?;
+            Ok(None)
+        },
+        TariUriAction::CallTemplate { .. } => Err(CommandError::InvalidArgument(
+            "Template calls are not supported by the console wallet".to_string(),
+        )),
+    }
+}
+
 pub async fn discover_peer(
     mut dht_service: DhtDiscoveryRequester,
     dest_public_key: PublicKey,
@@ -677,7 +772,35 @@ pub async fn command_runner(
                     Err(e) => eprintln!("BurnMinotari error! {}", e),
                 }
             },
-            SendMinotari(args) => {
+            SendMinotari(SendMinotariInteractiveArgs {
+                args,
+                recipient_check: Some(recipient_check),
+            }) => {
+                match send_tari_with_recipient_check(
+                    transaction_service.clone(),
+                    config.fee_per_gram,
+                    args.amount,
+                    args.destination,
+                    args.message,
+                    recipient_check,
+                )
+                .await
+                {
+                    Ok(SendTransactionOutcome::Interactive(tx_id)) => {
+                        debug!(target: LOG_TARGET, "send-minotari concluded with tx_id {}", tx_id);
+                        tx_ids.push(tx_id);
+                    },
+                    Ok(SendTransactionOutcome::OneSided(tx_id)) => {
+                        println!(
+                            "The recipient appears to be offline, sent one-sided instead (tx_id {})",
+                            tx_id
+                        );
+                        tx_ids.push(tx_id);
+                    },
+                    Err(e) => eprintln!("SendMinotari error! {}", e),
+                }
+            },
+            SendMinotari(SendMinotariInteractiveArgs { args, .. }) => {
                 match send_tari(
                     transaction_service.clone(),
                     config.fee_per_gram,
@@ -818,6 +941,49 @@ pub async fn command_runner(
                 },
                 Err(e) => eprintln!("ExportSpentUtxos error! {}", e),
             },
+            OpenUri(args) => {
+                match open_tari_uri(
+                    wallet.clone(),
+                    transaction_service.clone(),
+                    config.fee_per_gram,
+                    args.uri,
+                )
+                .await
+                {
+                    Ok(Some(tx_id)) => {
+                        debug!(target: LOG_TARGET, "open-uri concluded with tx_id {}", tx_id);
+                        tx_ids.push(tx_id);
+                    },
+                    Ok(None) => {},
+                    Err(e) => eprintln!("OpenUri error! {}", e),
+                }
+            },
+            ExportPaymentRequest(args) => {
+                let address = TariAddress::new(
+                    wallet.comms.node_identity().public_key().clone(),
+                    wallet.network.as_network(),
+                );
+                let mut request = PaymentRequest::new(address);
+                if let Some(amount) = args.amount {
+                    request = request.with_amount(amount);
+                }
+                if let Some(message) = args.message {
+                    request = request.with_message(message);
+                }
+                let uri = request.to_uri();
+                println!("Payment request: {}", uri);
+                if let Some(file) = args.output_file {
+                    match write_qr_code_png(&uri, &file) {
+                        Ok(_) => println!("Payment request QR code written to {}", file.display()),
+                        Err(e) => eprintln!("ExportPaymentRequest error! {}", e),
+                    }
+                } else {
+                    match render_qr_code(&uri) {
+                        Ok(qr_code) => println!("{}", qr_code),
+                        Err(e) => eprintln!("ExportPaymentRequest error! {}", e),
+                    }
+                }
+            },
             CountUtxos => match output_service.get_unspent_outputs().await {
                 Ok(utxos) => {
                     let utxos: Vec<WalletOutput> = utxos.into_iter().map(|v| v.wallet_output).collect();
@@ -962,6 +1128,39 @@ pub async fn command_runner(
                     eprintln!("RevalidateWalletDb error! {}", e);
                 }
             },
+            AuditOutputs(args) => match wallet.audit_outputs(args.repair).await {
+                Ok(report) => {
+                    println!(
+                        "Found {} orphaned encumbered output(s)",
+                        report.encumbrances.orphaned.len()
+                    );
+                    for orphan in &report.encumbrances.orphaned {
+                        let tx_id = orphan
+                            .tx_id
+                            .map(|tx_id| tx_id.to_string())
+                            .unwrap_or_else(|| "none".to_string());
+                        println!("  {} ({}, TxId: {})", orphan.commitment.to_hex(), orphan.status, tx_id);
+                    }
+                    println!(
+                        "Found {} commitment(s) spent by more than one unmined transaction",
+                        report.duplicate_commitments.len()
+                    );
+                    for (commitment, tx_ids) in &report.duplicate_commitments {
+                        let tx_ids = tx_ids.iter().map(|tx_id| tx_id.to_string()).collect::<Vec<_>>();
+                        println!("  {} (TxIds: {})", commitment.to_hex(), tx_ids.join(", "));
+                    }
+                    if args.repair {
+                        println!(
+                            "Released the orphaned encumbrances of {} transaction(s)",
+                            report.encumbrances.repaired_tx_ids.len()
+                        );
+                        if let Some(request_key) = report.encumbrances.validation_request_key {
+                            println!("Started output validation (request key {})", request_key);
+                        }
+                    }
+                },
+                Err(e) => eprintln!("AuditOutputs error! {}", e),
+            },
             HashGrpcPassword(args) => {
                 match config
                     .grpc_authentication
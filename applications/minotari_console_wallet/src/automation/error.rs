@@ -33,6 +33,7 @@ use minotari_wallet::{
 };
 use tari_common::exit_codes::{ExitCode, ExitError};
 use tari_common_types::types::FixedHashSizeError;
+use tari_contacts::contacts_service::error::ContactsServiceError;
 use tari_core::transactions::{tari_amount::MicroMinotariError, transaction_components::TransactionError};
 use tari_key_manager::key_manager_service::KeyManagerServiceError;
 use tari_utilities::{hex::HexError, ByteArrayError};
@@ -56,6 +57,8 @@ pub enum CommandError {
     TransactionServiceError(#[from] TransactionServiceError),
     #[error("Output manager error: `{0}`")]
     OutputManagerError(#[from] OutputManagerError),
+    #[error("Contacts service error: `{0}`")]
+    ContactsServiceError(#[from] ContactsServiceError),
     #[error("Key manager error: `{0}`")]
     KeyManagerError(#[from] KeyManagerServiceError),
     #[error("Tokio join error `{0}`")]
@@ -27,10 +27,10 @@ use std::{
 };
 
 use chrono::{DateTime, Utc};
-use clap::{Args, Parser, Subcommand};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use minotari_app_utilities::{common_cli_args::CommonCliArgs, utilities::UniPublicKey};
 use tari_common::configuration::{ConfigOverrideProvider, Network};
-use tari_common_types::tari_address::TariAddress;
+use tari_common_types::{tari_address::TariAddress, tari_uri::TariUri};
 use tari_comms::multiaddr::Multiaddr;
 use tari_core::transactions::{tari_amount, tari_amount::MicroMinotari};
 use tari_key_manager::SeedWords;
@@ -55,6 +55,11 @@ pub struct Cli {
     /// Change the password for the console wallet and exit
     #[clap(long, alias = "update-password")]
     pub change_password: bool,
+    /// Read the password for the console wallet from the OS keyring (Secret Service, macOS Keychain or Windows
+    /// Credential Manager). If no password is stored yet, it is stored once the wallet has been unlocked. Requires the
+    /// wallet to be built with the `keyring` feature.
+    #[clap(long, env = "MINOTARI_WALLET_USE_KEYRING", alias = "use_keyring")]
+    pub use_keyring: bool,
     /// Force wallet recovery
     #[clap(long, alias = "recover")]
     pub recovery: bool,
@@ -114,7 +119,7 @@ impl ConfigOverrideProvider for Cli {
 #[derive(Debug, Subcommand, Clone)]
 pub enum CliCommands {
     GetBalance,
-    SendMinotari(SendMinotariArgs),
+    SendMinotari(SendMinotariInteractiveArgs),
     BurnMinotari(BurnMinotariArgs),
     SendOneSided(SendMinotariArgs),
     SendOneSidedToStealthAddress(SendMinotariArgs),
@@ -124,6 +129,8 @@ pub enum CliCommands {
     Whois(WhoisArgs),
     ExportUtxos(ExportUtxosArgs),
     ExportSpentUtxos(ExportUtxosArgs),
+    ExportPaymentRequest(ExportPaymentRequestArgs),
+    OpenUri(OpenUriArgs),
     CountUtxos,
     SetBaseNode(SetBaseNodeArgs),
     SetCustomBaseNode(SetBaseNodeArgs),
@@ -132,6 +139,7 @@ pub enum CliCommands {
     FinaliseShaAtomicSwap(FinaliseShaAtomicSwapArgs),
     ClaimShaAtomicSwapRefund(ClaimShaAtomicSwapRefundArgs),
     RevalidateWalletDb,
+    AuditOutputs(AuditOutputsArgs),
     HashGrpcPassword(HashPasswordArgs),
     RegisterValidatorNode(RegisterValidatorNodeArgs),
 }
@@ -149,6 +157,22 @@ pub struct SendMinotariArgs {
     pub message: String,
 }
 
+#[derive(Debug, Args, Clone)]
+pub struct SendMinotariInteractiveArgs {
+    #[clap(flatten)]
+    pub args: SendMinotariArgs,
+    /// Ping the recipient before sending and, if they do not respond, send a one-sided payment instead (`fallback`)
+    /// or do not send at all (`fail`)
+    #[clap(long, value_enum)]
+    pub recipient_check: Option<RecipientCheck>,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum RecipientCheck {
+    Fallback,
+    Fail,
+}
+
 #[derive(Debug, Args, Clone)]
 pub struct BurnMinotariArgs {
     pub amount: MicroMinotari,
@@ -241,6 +265,23 @@ pub struct ExportUtxosArgs {
     pub output_file: Option<PathBuf>,
 }
 
+#[derive(Debug, Args, Clone)]
+pub struct ExportPaymentRequestArgs {
+    #[clap(short, long)]
+    pub amount: Option<MicroMinotari>,
+    #[clap(short, long)]
+    pub message: Option<String>,
+    /// Write the payment request QR code to this file as a PNG image instead of printing it
+    #[clap(short, long)]
+    pub output_file: Option<PathBuf>,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct OpenUriArgs {
+    /// A `tari://` link to pay a payment request, add a contact or set the base node
+    pub uri: TariUri,
+}
+
 #[derive(Debug, Args, Clone)]
 pub struct SetBaseNodeArgs {
     pub public_key: UniPublicKey,
@@ -281,6 +322,13 @@ pub struct ClaimShaAtomicSwapRefundArgs {
     pub message: String,
 }
 
+#[derive(Debug, Args, Clone)]
+pub struct AuditOutputsArgs {
+    /// Release encumbered outputs that do not belong to any live transaction
+    #[clap(long)]
+    pub repair: bool,
+}
+
 #[derive(Debug, Args, Clone)]
 pub struct HashPasswordArgs {
     /// If true, only output the hashed password and the salted password. Otherwise a usage explanation is output.
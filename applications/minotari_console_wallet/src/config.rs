@@ -25,7 +25,14 @@
 use config::Config;
 use minotari_app_utilities::consts;
 use minotari_wallet::WalletConfig;
-use tari_common::{configuration::CommonConfig, ConfigurationError, DefaultConfigLoader};
+use tari_common::{
+    configuration::{
+        explain::{ConfigOrigins, EffectiveConfig},
+        CommonConfig,
+    },
+    ConfigurationError,
+    DefaultConfigLoader,
+};
 use tari_p2p::{auto_update::AutoUpdateConfig, PeerSeedsConfig};
 
 #[derive(Clone, Debug)]
@@ -48,6 +55,21 @@ impl ApplicationConfig {
         config.wallet.p2p.user_agent = format!("tari/wallet/{}", consts::APP_VERSION_NUMBER);
 
         config.wallet.set_base_path(config.common.base_path());
+        config.auto_update.set_base_path(config.common.base_path());
         Ok(config)
     }
+
+    /// The effective configuration of every section, as printed by `--print-config`
+    pub fn effective_config<'a>(
+        &self,
+        cfg: &'a Config,
+        origins: &'a ConfigOrigins,
+    ) -> Result<EffectiveConfig<'a>, ConfigurationError> {
+        let mut effective = EffectiveConfig::new(cfg, origins);
+        effective.add_section(&self.common)?;
+        effective.add_section(&self.auto_update)?;
+        effective.add_section(&self.wallet)?;
+        effective.add_section(&self.peer_seeds)?;
+        Ok(effective)
+    }
 }
@@ -20,8 +20,12 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::convert::{TryFrom, TryInto};
+use std::{
+    convert::{TryFrom, TryInto},
+    time::Duration,
+};
 
+use chrono::{NaiveDateTime, Utc};
 use futures::{
     channel::mpsc::{self, Sender},
     future,
@@ -34,6 +38,10 @@ use minotari_app_grpc::{
         self,
         payment_recipient::PaymentType,
         wallet_server,
+        AddBaseNodeCandidateRequest,
+        AddBaseNodeCandidateResponse,
+        BalanceSnapshot,
+        BaseNodeCandidate,
         CheckConnectivityResponse,
         ClaimHtlcRefundRequest,
         ClaimHtlcRefundResponse,
@@ -44,9 +52,13 @@ use minotari_app_grpc::{
         CommitmentSignature,
         CreateBurnTransactionRequest,
         CreateBurnTransactionResponse,
+        CreatePaymentProofRequest,
+        CreatePaymentProofResponse,
         CreateTemplateRegistrationRequest,
         CreateTemplateRegistrationResponse,
         GetAddressResponse,
+        GetBalanceHistoryRequest,
+        GetBalanceHistoryResponse,
         GetBalanceRequest,
         GetBalanceResponse,
         GetCoinbaseRequest,
@@ -63,6 +75,9 @@ use minotari_app_grpc::{
         GetVersionResponse,
         ImportUtxosRequest,
         ImportUtxosResponse,
+        ListBaseNodesResponse,
+        PinBaseNodeRequest,
+        PinBaseNodeResponse,
         RegisterValidatorNodeRequest,
         RegisterValidatorNodeResponse,
         RevalidateRequest,
@@ -71,6 +86,8 @@ use minotari_app_grpc::{
         SendShaAtomicSwapResponse,
         SetBaseNodeRequest,
         SetBaseNodeResponse,
+        SubscribeTransactionEventsRequest,
+        SubscribeTransactionEventsResponse,
         TransactionDirection,
         TransactionEvent,
         TransactionEventRequest,
@@ -84,11 +101,16 @@ use minotari_app_grpc::{
 };
 use minotari_wallet::{
     connectivity_service::{OnlineStatus, WalletConnectivityInterface},
-    error::WalletStorageError,
-    output_manager_service::{handle::OutputManagerHandle, UtxoSelectionCriteria},
+    error::{WalletError, WalletStorageError},
+    output_manager_service::{
+        handle::OutputManagerHandle,
+        storage::models::BalanceHistoryResolution,
+        UtxoSelectionCriteria,
+        UtxoSelectionOrdering,
+    },
     transaction_service::{
         handle::TransactionServiceHandle,
-        storage::models::{self, WalletTransaction},
+        storage::models::{self, TransactionEventRecord, WalletTransaction},
     },
     WalletSqlite,
 };
@@ -122,6 +144,28 @@ use crate::{
 };
 
 const LOG_TARGET: &str = "wallet::ui::grpc";
+/// The number of recorded transaction events fetched from the wallet database at a time while replaying
+const TRANSACTION_EVENT_REPLAY_BATCH_SIZE: usize = 100;
+
+fn utxo_selection_criteria(strategy: i32) -> Result<UtxoSelectionCriteria, Status> {
+    let ordering = match tari_rpc::UtxoSelectionStrategy::from_i32(strategy) {
+        Some(tari_rpc::UtxoSelectionStrategy::Default) => UtxoSelectionOrdering::Default,
+        Some(tari_rpc::UtxoSelectionStrategy::SmallestFirst) => UtxoSelectionOrdering::SmallestFirst,
+        Some(tari_rpc::UtxoSelectionStrategy::LargestFirst) => UtxoSelectionOrdering::LargestFirst,
+        Some(tari_rpc::UtxoSelectionStrategy::BranchAndBound) => UtxoSelectionOrdering::BranchAndBound,
+        Some(tari_rpc::UtxoSelectionStrategy::PrivacyRandom) => UtxoSelectionOrdering::PrivacyRandom,
+        None => {
+            return Err(Status::invalid_argument(format!(
+                "Invalid UTXO selection strategy {}",
+                strategy
+            )))
+        },
+    };
+    Ok(UtxoSelectionCriteria {
+        ordering,
+        ..Default::default()
+    })
+}
 
 async fn send_transaction_event(
     transaction_event: TransactionEvent,
@@ -138,6 +182,62 @@ async fn send_transaction_event(
     }
 }
 
+fn transaction_event_record_to_response(record: &TransactionEventRecord) -> SubscribeTransactionEventsResponse {
+    SubscribeTransactionEventsResponse {
+        seq: record.seq,
+        tx_id: record.tx_id.as_u64(),
+        event: record.event.clone(),
+        status: record
+            .status
+            .clone()
+            .map(TransactionStatus::from)
+            .unwrap_or(TransactionStatus::NotFound) as i32,
+        recorded_at: Some(naive_datetime_to_timestamp(record.recorded_at)),
+    }
+}
+
+/// Sends the recorded transaction events with a sequence number greater than `last_seq` to the client, advancing
+/// `last_seq` past every event sent. Returns false if the stream should end because the client disconnected or the
+/// events could not be fetched.
+async fn replay_transaction_events(
+    transaction_service: &mut TransactionServiceHandle,
+    last_seq: &mut u64,
+    sender: &mut Sender<Result<SubscribeTransactionEventsResponse, Status>>,
+) -> bool {
+    loop {
+        let records = match transaction_service
+            .get_transaction_events_since(*last_seq, TRANSACTION_EVENT_REPLAY_BATCH_SIZE)
+            .await
+        {
+            Ok(records) => records,
+            Err(e) => {
+                warn!(target: LOG_TARGET, "Could not fetch recorded transaction events: {}", e);
+                let _result = sender
+                    .send(Err(Status::internal(format!(
+                        "Could not fetch recorded transaction events: {}",
+                        e
+                    ))))
+                    .await;
+                return false;
+            },
+        };
+        let is_last_batch = records.len() < TRANSACTION_EVENT_REPLAY_BATCH_SIZE;
+        for record in records {
+            *last_seq = record.seq;
+            if sender
+                .send(Ok(transaction_event_record_to_response(&record)))
+                .await
+                .is_err()
+            {
+                return false;
+            }
+        }
+        if is_last_batch {
+            return true;
+        }
+    }
+}
+
 pub struct WalletGrpcServer {
     wallet: WalletSqlite,
     rules: ConsensusManager,
@@ -178,6 +278,7 @@ impl WalletGrpcServer {
 impl wallet_server::Wallet for WalletGrpcServer {
     type GetCompletedTransactionsStream = mpsc::Receiver<Result<GetCompletedTransactionsResponse, Status>>;
     type StreamTransactionEventsStream = mpsc::Receiver<Result<TransactionEventResponse, Status>>;
+    type SubscribeTransactionEventsStream = mpsc::Receiver<Result<SubscribeTransactionEventsResponse, Status>>;
 
     async fn get_version(&self, _: Request<GetVersionRequest>) -> Result<Response<GetVersionResponse>, Status> {
         Ok(Response::new(GetVersionResponse {
@@ -260,6 +361,73 @@ impl wallet_server::Wallet for WalletGrpcServer {
         Ok(Response::new(SetBaseNodeResponse {}))
     }
 
+    async fn add_base_node_candidate(
+        &self,
+        request: Request<AddBaseNodeCandidateRequest>,
+    ) -> Result<Response<AddBaseNodeCandidateResponse>, Status> {
+        let message = request.into_inner();
+        let public_key = PublicKey::from_hex(&message.public_key_hex)
+            .map_err(|e| Status::invalid_argument(format!("Base node public key was not a valid pub key: {}", e)))?;
+        let net_address = message
+            .net_address
+            .parse::<Multiaddr>()
+            .map_err(|e| Status::invalid_argument(format!("Base node net address was not valid: {}", e)))?;
+
+        let mut wallet = self.wallet.clone();
+        wallet
+            .add_base_node_candidate(public_key, net_address)
+            .await
+            .map_err(|e| Status::internal(format!("{:?}", e)))?;
+
+        Ok(Response::new(AddBaseNodeCandidateResponse {}))
+    }
+
+    async fn list_base_nodes(&self, _: Request<tari_rpc::Empty>) -> Result<Response<ListBaseNodesResponse>, Status> {
+        let base_nodes = self
+            .wallet
+            .wallet_connectivity
+            .get_base_node_candidates()
+            .into_iter()
+            .map(|c| BaseNodeCandidate {
+                public_key: c.public_key.to_vec(),
+                latency_ms: c
+                    .latency
+                    .map(|d| u64::try_from(d.as_millis()).unwrap_or(u64::MAX))
+                    .unwrap_or_default(),
+                height: c.height.unwrap_or_default(),
+                consecutive_failures: c.consecutive_failures,
+                is_healthy: c.is_healthy,
+                is_pinned: c.is_pinned,
+                is_active: c.is_active,
+            })
+            .collect();
+
+        Ok(Response::new(ListBaseNodesResponse { base_nodes }))
+    }
+
+    async fn pin_base_node(
+        &self,
+        request: Request<PinBaseNodeRequest>,
+    ) -> Result<Response<PinBaseNodeResponse>, Status> {
+        let message = request.into_inner();
+        let mut wallet = self.wallet.clone();
+        if message.public_key_hex.is_empty() {
+            wallet
+                .unpin_base_node()
+                .map_err(|e| Status::internal(format!("{:?}", e)))?;
+        } else {
+            let public_key = PublicKey::from_hex(&message.public_key_hex).map_err(|e| {
+                Status::invalid_argument(format!("Base node public key was not a valid pub key: {}", e))
+            })?;
+            wallet.pin_base_node(&public_key).map_err(|e| match e {
+                WalletError::WalletConnectivityError(e) => Status::not_found(e.to_string()),
+                e => Status::internal(format!("{:?}", e)),
+            })?;
+        }
+
+        Ok(Response::new(PinBaseNodeResponse {}))
+    }
+
     async fn get_balance(&self, _request: Request<GetBalanceRequest>) -> Result<Response<GetBalanceResponse>, Status> {
         let mut output_service = self.get_output_manager_service();
         let balance = match output_service.get_balance().await {
@@ -277,6 +445,65 @@ impl wallet_server::Wallet for WalletGrpcServer {
         }))
     }
 
+    async fn get_balance_history(
+        &self,
+        request: Request<GetBalanceHistoryRequest>,
+    ) -> Result<Response<GetBalanceHistoryResponse>, Status> {
+        let message = request.into_inner();
+        let resolution = match tari_rpc::BalanceHistoryResolution::from_i32(message.resolution) {
+            Some(tari_rpc::BalanceHistoryResolution::Daily) => BalanceHistoryResolution::Daily,
+            Some(tari_rpc::BalanceHistoryResolution::PerBlock) => BalanceHistoryResolution::PerBlock,
+            None => {
+                return Err(Status::invalid_argument(format!(
+                    "Invalid balance history resolution {}",
+                    message.resolution
+                )))
+            },
+        };
+        let to_datetime = |timestamp: u64| {
+            i64::try_from(timestamp)
+                .ok()
+                .and_then(|secs| NaiveDateTime::from_timestamp_opt(secs, 0))
+                .ok_or_else(|| Status::invalid_argument(format!("Invalid timestamp {}", timestamp)))
+        };
+        let from = to_datetime(message.from_timestamp)?;
+        let to = if message.to_timestamp == 0 {
+            Utc::now().naive_utc()
+        } else {
+            to_datetime(message.to_timestamp)?
+        };
+        if from > to {
+            return Err(Status::invalid_argument(
+                "from_timestamp must not be after to_timestamp",
+            ));
+        }
+
+        let mut output_service = self.get_output_manager_service();
+        let history = output_service
+            .get_balance_history(resolution, from, to)
+            .await
+            .map_err(|e| Status::internal(format!("GetBalanceHistory error! {}", e)))?;
+        Ok(Response::new(GetBalanceHistoryResponse {
+            snapshots: history
+                .into_iter()
+                .map(|snapshot| {
+                    let balance = snapshot.balance;
+                    BalanceSnapshot {
+                        block_height: snapshot.block_height,
+                        recorded_at: Some(naive_datetime_to_timestamp(snapshot.recorded_at)),
+                        available_balance: balance
+                            .available_balance
+                            .saturating_sub(balance.time_locked_balance.unwrap_or_default())
+                            .0,
+                        pending_incoming_balance: balance.pending_incoming_balance.0,
+                        pending_outgoing_balance: balance.pending_outgoing_balance.0,
+                        timelocked_balance: balance.time_locked_balance.unwrap_or_default().0,
+                    }
+                })
+                .collect(),
+        }))
+    }
+
     async fn get_unspent_amounts(
         &self,
         _: Request<tari_rpc::Empty>,
@@ -494,6 +721,7 @@ impl wallet_server::Wallet for WalletGrpcServer {
 
     async fn transfer(&self, request: Request<TransferRequest>) -> Result<Response<TransferResponse>, Status> {
         let message = request.into_inner();
+        let selection_criteria = utxo_selection_criteria(message.utxo_selection_strategy)?;
         let recipients = message
             .recipients
             .into_iter()
@@ -516,6 +744,7 @@ impl wallet_server::Wallet for WalletGrpcServer {
         let mut transfers = Vec::new();
         for (hex_address, address, amount, fee_per_gram, message, payment_type) in recipients {
             let mut transaction_service = self.get_transaction_service();
+            let selection_criteria = selection_criteria.clone();
             transfers.push(async move {
                 (
                     hex_address,
@@ -524,7 +753,7 @@ impl wallet_server::Wallet for WalletGrpcServer {
                             .send_transaction(
                                 address,
                                 amount.into(),
-                                UtxoSelectionCriteria::default(),
+                                selection_criteria,
                                 OutputFeatures::default(),
                                 fee_per_gram.into(),
                                 message,
@@ -535,7 +764,7 @@ impl wallet_server::Wallet for WalletGrpcServer {
                             .send_one_sided_transaction(
                                 address,
                                 amount.into(),
-                                UtxoSelectionCriteria::default(),
+                                selection_criteria,
                                 OutputFeatures::default(),
                                 fee_per_gram.into(),
                                 message,
@@ -546,7 +775,7 @@ impl wallet_server::Wallet for WalletGrpcServer {
                             .send_one_sided_to_stealth_address_transaction(
                                 address,
                                 amount.into(),
-                                UtxoSelectionCriteria::default(),
+                                selection_criteria,
                                 OutputFeatures::default(),
                                 fee_per_gram.into(),
                                 message,
@@ -750,6 +979,35 @@ impl wallet_server::Wallet for WalletGrpcServer {
         Ok(Response::new(receiver))
     }
 
+    async fn subscribe_transaction_events(
+        &self,
+        request: Request<SubscribeTransactionEventsRequest>,
+    ) -> Result<Response<Self::SubscribeTransactionEventsStream>, Status> {
+        let since_seq = request.into_inner().since_seq;
+        let mut transaction_service = self.get_transaction_service();
+        // Subscribe before replaying so that changes journaled while the replay is in progress are not missed
+        let mut event_stream = transaction_service.get_event_stream();
+        let (mut sender, receiver) = mpsc::channel(TRANSACTION_EVENT_REPLAY_BATCH_SIZE);
+
+        task::spawn(async move {
+            let mut last_seq = since_seq;
+            loop {
+                if !replay_transaction_events(&mut transaction_service, &mut last_seq, &mut sender).await {
+                    return;
+                }
+                // The journal is written together with every transaction change, the transaction events that follow
+                // those changes only signal that there may be new journal entries to read. A lagged receiver missed
+                // signals, not journal entries.
+                match event_stream.recv().await {
+                    Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => {},
+                    Err(broadcast::error::RecvError::Closed) => return,
+                }
+            }
+        });
+
+        Ok(Response::new(receiver))
+    }
+
     async fn get_completed_transactions(
         &self,
         _request: Request<GetCompletedTransactionsRequest>,
@@ -942,6 +1200,35 @@ impl wallet_server::Wallet for WalletGrpcServer {
         }
     }
 
+    async fn cancel_stale_transactions(
+        &self,
+        request: Request<tari_rpc::CancelStaleTransactionsRequest>,
+    ) -> Result<Response<tari_rpc::CancelStaleTransactionsResponse>, Status> {
+        let message = request.into_inner();
+        debug!(
+            target: LOG_TARGET,
+            "Incoming gRPC request to Cancel Stale Transactions (older than {}s)", message.older_than_secs,
+        );
+        let mut transaction_service = self.get_transaction_service();
+
+        let summary = transaction_service
+            .cancel_all_stale(Duration::from_secs(message.older_than_secs))
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        Ok(Response::new(tari_rpc::CancelStaleTransactionsResponse {
+            cancelled_tx_ids: summary.cancelled.into_iter().map(|tx_id| tx_id.as_u64()).collect(),
+            failed: summary
+                .failed
+                .into_iter()
+                .map(|(tx_id, failure_message)| tari_rpc::FailedTransactionCancellation {
+                    tx_id: tx_id.as_u64(),
+                    failure_message,
+                })
+                .collect(),
+        }))
+    }
+
     async fn create_template_registration(
         &self,
         request: Request<CreateTemplateRegistrationRequest>,
@@ -1045,6 +1332,22 @@ impl wallet_server::Wallet for WalletGrpcServer {
         };
         Ok(Response::new(response))
     }
+
+    async fn create_payment_proof(
+        &self,
+        request: Request<CreatePaymentProofRequest>,
+    ) -> Result<Response<CreatePaymentProofResponse>, Status> {
+        let request = request.into_inner();
+        let mut wallet = self.wallet.clone();
+        let proof = wallet
+            .create_payment_proof(request.transaction_id.into(), request.message)
+            .await
+            .map_err(|e| Status::invalid_argument(format!("Failed to create payment proof: {}", e)))?;
+
+        Ok(Response::new(CreatePaymentProofResponse {
+            proof: Some(proof.into()),
+        }))
+    }
 }
 
 async fn handle_completed_tx(
@@ -69,6 +69,9 @@ use crate::{
     ApplicationConfig,
 };
 
+mod os_keyring;
+pub use os_keyring::WalletKeyring;
+
 pub const LOG_TARGET: &str = "wallet::console_wallet::init";
 const TARI_WALLET_PASSWORD: &str = "MINOTARI_WALLET_PASSWORD";
 // Maxmimum number of times we prompt for confirmation of a new passphrase, to avoid driving the user insane with an
@@ -234,13 +237,13 @@ fn prompt_password(prompt: &str) -> Result<SafePassword, ExitError> {
     Ok(SafePassword::from(password))
 }
 
-/// Allows the user to change the password of the wallet.
+/// Allows the user to change the password of the wallet. Returns the new password.
 pub async fn change_password(
     config: &ApplicationConfig,
     existing: SafePassword,
     shutdown_signal: ShutdownSignal,
     non_interactive_mode: bool,
-) -> Result<(), ExitError> {
+) -> Result<SafePassword, ExitError> {
     let mut wallet = init_wallet(
         config,
         existing.clone(),
@@ -260,7 +263,28 @@ pub async fn change_password(
             ExitError::new(ExitCode::IncorrectOrEmptyPassword, "Your password was not changed.")
         },
         _ => ExitError::new(ExitCode::DatabaseError, "Your password was not changed."),
-    })
+    })?;
+
+    Ok(new)
+}
+
+/// Stores the password in the OS keyring, unless it is already stored there. Failing to store the password is not
+/// fatal, as the wallet has already been unlocked.
+pub(crate) fn store_password_in_keyring(wallet_config: &WalletConfig, password: &SafePassword) {
+    let result = WalletKeyring::new(wallet_config).and_then(|keyring| {
+        let is_stored = keyring
+            .get_password()?
+            .map_or(false, |stored| stored.reveal() == password.reveal());
+        if !is_stored {
+            keyring.set_password(password)?;
+            println!("The wallet password has been stored in the OS keyring.");
+        }
+        Ok(())
+    });
+    if let Err(e) = result {
+        warn!(target: LOG_TARGET, "Could not store the wallet password in the OS keyring: {}", e);
+        println!("Could not store the wallet password in the OS keyring: {}", e);
+    }
 }
 
 /// Populates the PeerConfig struct from:
@@ -755,6 +779,19 @@ pub(crate) fn boot_with_password(
     if wallet_config.password.is_some() {
         return Ok((boot_mode, wallet_config.password.clone().unwrap()));
     }
+    if cli.use_keyring {
+        if let Some(password) = WalletKeyring::new(wallet_config)?.get_password()? {
+            debug!(target: LOG_TARGET, "Using passphrase from the OS keyring.");
+            return Ok((boot_mode, password));
+        }
+        if cli.non_interactive_mode {
+            return Err(ExitError::new(
+                ExitCode::IncorrectOrEmptyPassword,
+                "No wallet passphrase is stored in the OS keyring. Run the wallet interactively with --use-keyring \
+                 once to store it.",
+            ));
+        }
+    }
 
     let password = match boot_mode {
         WalletBoot::New => {
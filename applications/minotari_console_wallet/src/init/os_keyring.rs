@@ -0,0 +1,97 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+#[cfg(feature = "keyring")]
+use keyring::{Entry, Error as KeyringError};
+#[cfg(feature = "keyring")]
+use log::*;
+use minotari_wallet::WalletConfig;
+use tari_common::exit_codes::{ExitCode, ExitError};
+use tari_utilities::SafePassword;
+
+#[cfg(feature = "keyring")]
+const LOG_TARGET: &str = "wallet::console_wallet::init::keyring";
+#[cfg(feature = "keyring")]
+const KEYRING_SERVICE: &str = "minotari_console_wallet";
+
+/// The wallet passphrase stored in the OS keychain (Secret Service on Linux, Keychain on macOS and Credential Manager
+/// on Windows). Entries are keyed by network and wallet database path, so several wallets can be stored side by side.
+/// Without the `keyring` feature every operation fails.
+pub struct WalletKeyring {
+    #[cfg(feature = "keyring")]
+    entry: Entry,
+}
+
+#[cfg(feature = "keyring")]
+impl WalletKeyring {
+    pub fn new(config: &WalletConfig) -> Result<Self, ExitError> {
+        let user = format!("{}:{}", config.network, config.db_file.display());
+        let entry = Entry::new(KEYRING_SERVICE, &user).map_err(to_exit_error)?;
+        Ok(Self { entry })
+    }
+
+    /// Returns the stored passphrase, or None if no passphrase has been stored for this wallet
+    pub fn get_password(&self) -> Result<Option<SafePassword>, ExitError> {
+        match self.entry.get_password() {
+            Ok(password) => Ok(Some(SafePassword::from(password))),
+            Err(KeyringError::NoEntry) => Ok(None),
+            Err(e) => Err(to_exit_error(e)),
+        }
+    }
+
+    /// Stores the passphrase, replacing any previously stored passphrase for this wallet
+    pub fn set_password(&self, password: &SafePassword) -> Result<(), ExitError> {
+        let password = std::str::from_utf8(password.reveal())
+            .map_err(|_| ExitError::new(ExitCode::InputError, "The wallet passphrase is not valid UTF-8"))?;
+        self.entry.set_password(password).map_err(to_exit_error)?;
+        info!(target: LOG_TARGET, "Wallet passphrase stored in the OS keyring");
+        Ok(())
+    }
+}
+
+#[cfg(feature = "keyring")]
+fn to_exit_error(e: KeyringError) -> ExitError {
+    ExitError::new(ExitCode::IOError, format!("OS keyring error: {}", e))
+}
+
+#[cfg(not(feature = "keyring"))]
+impl WalletKeyring {
+    pub fn new(_config: &WalletConfig) -> Result<Self, ExitError> {
+        Err(unsupported())
+    }
+
+    pub fn get_password(&self) -> Result<Option<SafePassword>, ExitError> {
+        Err(unsupported())
+    }
+
+    pub fn set_password(&self, _password: &SafePassword) -> Result<(), ExitError> {
+        Err(unsupported())
+    }
+}
+
+#[cfg(not(feature = "keyring"))]
+fn unsupported() -> ExitError {
+    ExitError::new(
+        ExitCode::ConfigError,
+        "This wallet was built without OS keyring support, rebuild it with the `keyring` feature to use --use-keyring",
+    )
+}
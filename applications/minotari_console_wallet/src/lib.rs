@@ -42,13 +42,22 @@ pub use cli::{
     DiscoverPeerArgs,
     ExportUtxosArgs,
     MakeItRainArgs,
+    RecipientCheck,
     SendMinotariArgs,
+    SendMinotariInteractiveArgs,
     SetBaseNodeArgs,
     WhoisArgs,
 };
+use futures::{future::BoxFuture, FutureExt};
 use init::{change_password, get_base_node_peer_config, init_wallet, start_wallet, tari_splash_screen, WalletBoot};
 use log::*;
-use minotari_app_utilities::{common_cli_args::CommonCliArgs, consts, network_check::is_network_choice_valid};
+use minotari_app_utilities::{
+    common_cli_args::CommonCliArgs,
+    consts,
+    network_check::is_network_choice_valid,
+    service_manager::{self, HealthStatus, ServiceNotifier},
+};
+use minotari_wallet::WalletSqlite;
 use recovery::{get_seed_from_seed_words, prompt_private_key_from_seed_words};
 use tari_common::{
     configuration::bootstrap::ApplicationType,
@@ -63,7 +72,7 @@ use tokio::runtime::Runtime;
 use wallet_modes::{command_mode, grpc_mode, recovery_mode, script_mode, tui_mode, WalletMode};
 
 pub use crate::config::ApplicationConfig;
-use crate::init::{boot_with_password, confirm_seed_words, wallet_mode};
+use crate::init::{boot_with_password, confirm_seed_words, store_password_in_keyring, wallet_mode};
 
 pub const LOG_TARGET: &str = "wallet::console_wallet::main";
 
@@ -82,9 +91,12 @@ pub fn run_wallet(shutdown: &mut Shutdown, runtime: Runtime, config: &mut Applic
             log_level: None,
             network: None,
             config_property_overrides: vec![],
+            print_config: false,
+            explain: false,
         },
         password: None,
         change_password: false,
+        use_keyring: false,
         recovery: false,
         seed_words: None,
         seed_words_file_name: None,
@@ -135,12 +147,16 @@ pub fn run_wallet_with_cli(
 
     if cli.change_password {
         info!(target: LOG_TARGET, "Change password requested.");
-        return runtime.block_on(change_password(
+        let new_password = runtime.block_on(change_password(
             config,
             password,
             shutdown_signal,
             cli.non_interactive_mode,
-        ));
+        ))?;
+        if cli.use_keyring {
+            store_password_in_keyring(&config.wallet, &new_password);
+        }
+        return Ok(());
     }
 
     // Run our own Tor instance, if configured
@@ -159,6 +175,8 @@ pub fn run_wallet_with_cli(
     let on_init = matches!(boot_mode, WalletBoot::New);
     let not_recovery = recovery_seed.is_none();
 
+    let keyring_password = if cli.use_keyring { Some(password.clone()) } else { None };
+
     // initialize wallet
     let mut wallet = runtime.block_on(init_wallet(
         config,
@@ -169,6 +187,10 @@ pub fn run_wallet_with_cli(
         cli.non_interactive_mode,
     ))?;
 
+    if let Some(password) = keyring_password {
+        store_password_in_keyring(&config.wallet, &password);
+    }
+
     // if wallet is being set for the first time, wallet seed words are prompted on the screen
     if !cli.non_interactive_mode && not_recovery && on_init {
         match confirm_seed_words(&mut wallet) {
@@ -199,6 +221,14 @@ pub fn run_wallet_with_cli(
 
     debug!(target: LOG_TARGET, "Starting app");
 
+    let notifier = ServiceNotifier::from_env();
+    runtime.spawn(service_manager::run_watchdog(
+        notifier.clone(),
+        health_check(&wallet),
+        shutdown.to_signal(),
+    ));
+    notifier.ready();
+
     let handle = runtime.handle().clone();
 
     let result = match wallet_mode {
@@ -224,6 +254,7 @@ pub fn run_wallet_with_cli(
     };
 
     print!("\nShutting down wallet... ");
+    notifier.stopping();
     shutdown.trigger();
     runtime.block_on(wallet.wait_until_shutdown());
     println!("Done.");
@@ -231,6 +262,29 @@ pub fn run_wallet_with_cli(
     result
 }
 
+/// The wallet is healthy while the transaction and base node services respond
+pub(crate) fn health_check(wallet: &WalletSqlite) -> impl FnMut() -> BoxFuture<'static, HealthStatus> {
+    let transaction_service = wallet.transaction_service.clone();
+    let base_node_service = wallet.base_node_service.clone();
+    move || {
+        let mut transaction_service = transaction_service.clone();
+        let mut base_node_service = base_node_service.clone();
+        async move {
+            if let Err(err) = transaction_service.get_num_confirmations_required().await {
+                return HealthStatus::Unhealthy(format!("The transaction service is not responding: {}", err));
+            }
+            match base_node_service.get_chain_metadata().await {
+                Ok(Some(metadata)) => {
+                    HealthStatus::Healthy(format!("Base node tip #{}", metadata.height_of_longest_chain()))
+                },
+                Ok(None) => HealthStatus::Healthy("Waiting for the base node".to_string()),
+                Err(err) => HealthStatus::Unhealthy(format!("The base node service is not responding: {}", err)),
+            }
+        }
+        .boxed()
+    }
+}
+
 fn get_password(config: &ApplicationConfig, cli: &Cli) -> Option<SafePassword> {
     cli.password
         .as_ref()
@@ -29,7 +29,7 @@ use tari_common::{
     configuration::bootstrap::{grpc_default_port, ApplicationType},
     exit_codes::ExitError,
     initialize_logging,
-    load_configuration,
+    load_configuration_with_origins,
 };
 use tari_shutdown::Shutdown;
 
@@ -78,7 +78,7 @@ fn main() {
 fn main_inner() -> Result<(), ExitError> {
     let cli = Cli::parse();
 
-    let cfg = load_configuration(cli.common.config_path(), true, &cli)?;
+    let (cfg, config_origins) = load_configuration_with_origins(cli.common.config_path(), true, &cli)?;
     initialize_logging(
         &cli.common.log_config_path("wallet"),
         &cli.common.get_base_path(),
@@ -94,6 +94,12 @@ fn main_inner() -> Result<(), ExitError> {
 
     setup_grpc_config(&mut config);
 
+    if cli.common.print_config {
+        let effective = config.effective_config(&cfg, &config_origins)?;
+        print!("{}", effective.render(cli.common.explain));
+        return Ok(());
+    }
+
     let runtime = tokio::runtime::Builder::new_multi_thread()
         .enable_all()
         .build()
@@ -1,22 +1,155 @@
 // Copyright 2022 The Tari Project
 // SPDX-License-Identifier: BSD-3-Clause
 
+use minotari_wallet::util::payment_request::PaymentRequest;
+use tari_core::transactions::tari_amount::MicroMinotari;
 use tui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Span, Spans},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Paragraph, Wrap},
     Frame,
 };
+use unicode_width::UnicodeWidthStr;
 
-use crate::ui::{components::Component, state::AppState};
+use crate::{
+    ui::{
+        components::{Component, KeyHandled},
+        state::AppState,
+        widgets::draw_dialog,
+    },
+    utils::qr_code::render_qr_code,
+};
 
-pub struct ReceiveTab {}
+pub struct ReceiveTab {
+    request_input_mode: RequestInputMode,
+    amount_field: String,
+    message_field: String,
+    payment_request: Option<(String, String)>,
+    error_message: Option<String>,
+}
 
 impl ReceiveTab {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            request_input_mode: RequestInputMode::None,
+            amount_field: String::new(),
+            message_field: String::new(),
+            payment_request: None,
+            error_message: None,
+        }
+    }
+
+    /// Builds the payment request URI and QR code for the amount and message fields, or clears the payment request if
+    /// both are empty so that the plain address QR code is shown
+    fn update_payment_request(&mut self, app_state: &AppState) {
+        if self.amount_field.is_empty() && self.message_field.is_empty() {
+            self.payment_request = None;
+            return;
+        }
+
+        let mut request = PaymentRequest::new(app_state.get_identity().address.clone());
+        if !self.amount_field.is_empty() {
+            match self.amount_field.parse::<MicroMinotari>() {
+                Ok(amount) => request = request.with_amount(amount),
+                Err(e) => {
+                    self.error_message = Some(format!("Invalid amount: {}\nPress Enter to continue.", e));
+                    return;
+                },
+            }
+        }
+        if !self.message_field.is_empty() {
+            request = request.with_message(self.message_field.clone());
+        }
+
+        let uri = request.to_uri();
+        match render_qr_code(&uri) {
+            Ok(qr_code) => self.payment_request = Some((uri, qr_code)),
+            Err(e) => {
+                self.error_message = Some(format!(
+                    "Could not create a QR code for the payment request: {}\nPress Enter to continue.",
+                    e
+                ))
+            },
+        }
+    }
+
+    // casting here is okay as we only use it for draw widths
+    #[allow(clippy::cast_possible_truncation)]
+    fn draw_payment_request_form<B>(&self, f: &mut Frame<B>, area: Rect)
+    where B: Backend {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(Span::styled("Payment Request", Style::default().fg(Color::White)));
+        f.render_widget(block, area);
+
+        let vert_chunks = Layout::default()
+            .constraints([Constraint::Length(1), Constraint::Length(3), Constraint::Min(1)].as_ref())
+            .margin(1)
+            .split(area);
+
+        let instructions = Paragraph::new(Spans::from(vec![
+            Span::raw("Press "),
+            Span::styled("A", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" to edit "),
+            Span::styled("Amount", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(", "),
+            Span::styled("M", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" to edit "),
+            Span::styled("Message", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" and "),
+            Span::styled("C", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" to clear the payment request."),
+        ]))
+        .wrap(Wrap { trim: false })
+        .block(Block::default());
+        f.render_widget(instructions, vert_chunks[0]);
+
+        let field_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(30), Constraint::Percentage(70)].as_ref())
+            .split(vert_chunks[1]);
+
+        let amount_input = Paragraph::new(self.amount_field.as_ref())
+            .style(match self.request_input_mode {
+                RequestInputMode::Amount => Style::default().fg(Color::Magenta),
+                _ => Style::default(),
+            })
+            .block(Block::default().borders(Borders::ALL).title("(A)mount:"));
+        f.render_widget(amount_input, field_chunks[0]);
+
+        let message_input = Paragraph::new(self.message_field.as_ref())
+            .style(match self.request_input_mode {
+                RequestInputMode::Message => Style::default().fg(Color::Magenta),
+                _ => Style::default(),
+            })
+            .block(Block::default().borders(Borders::ALL).title("(M)essage:"));
+        f.render_widget(message_input, field_chunks[1]);
+
+        if let Some((uri, _)) = self.payment_request.as_ref() {
+            let uri_text = Paragraph::new(uri.as_str())
+                .style(Style::default().fg(Color::White))
+                .wrap(Wrap { trim: false })
+                .block(Block::default());
+            f.render_widget(uri_text, vert_chunks[2]);
+        }
+
+        match self.request_input_mode {
+            RequestInputMode::None => (),
+            RequestInputMode::Amount => f.set_cursor(
+                // Put cursor past the end of the input text
+                field_chunks[0].x + self.amount_field.width() as u16 + 1,
+                // Move one line down, from the border to the input line
+                field_chunks[0].y + 1,
+            ),
+            RequestInputMode::Message => f.set_cursor(
+                // Put cursor past the end of the input text
+                field_chunks[1].x + self.message_field.width() as u16 + 1,
+                // Move one line down, from the border to the input line
+                field_chunks[1].y + 1,
+            ),
+        }
     }
 
     fn draw_whoami<B>(&self, f: &mut Frame<B>, area: Rect, app_state: &AppState)
@@ -29,13 +162,21 @@ impl ReceiveTab {
 
         let chunks = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Length(6), Constraint::Length(23)].as_ref())
+            .constraints([Constraint::Length(6), Constraint::Length(8), Constraint::Min(23)].as_ref())
             .margin(1)
             .split(area);
 
-        // QR Code
-        let qr_code = Paragraph::new(app_state.get_identity().qr_code.as_str()).block(Block::default());
-        f.render_widget(qr_code, chunks[1]);
+        self.draw_payment_request_form(f, chunks[1]);
+
+        // QR Code, for the payment request if there is one and otherwise for the wallet address
+        let qr_code = self
+            .payment_request
+            .as_ref()
+            .map_or(app_state.get_identity().qr_code.as_str(), |(_, qr_code)| {
+                qr_code.as_str()
+            });
+        let qr_code = Paragraph::new(qr_code).block(Block::default());
+        f.render_widget(qr_code, chunks[2]);
 
         // Connection details
         let details_chunks = Layout::default()
@@ -115,15 +256,86 @@ impl<B: Backend> Component<B> for ReceiveTab {
             .split(area);
 
         self.draw_whoami(f, areas[0], app_state);
+
+        if let Some(msg) = self.error_message.clone() {
+            draw_dialog(f, area, "Error!".to_string(), msg, Color::Red, 120, 9);
+        }
     }
 
-    fn on_key(&mut self, _app_state: &mut AppState, _c: char) {}
+    fn on_key(&mut self, app_state: &mut AppState, c: char) {
+        if self.error_message.is_some() {
+            if '\n' == c {
+                self.error_message = None;
+            }
+            return;
+        }
+
+        if self.on_key_request_input(c, app_state) == KeyHandled::Handled {
+            return;
+        }
+
+        match c {
+            'a' => self.request_input_mode = RequestInputMode::Amount,
+            'm' => self.request_input_mode = RequestInputMode::Message,
+            'c' => {
+                self.amount_field = String::new();
+                self.message_field = String::new();
+                self.payment_request = None;
+            },
+            _ => {},
+        }
+    }
 
     fn on_up(&mut self, _app_state: &mut AppState) {}
 
     fn on_down(&mut self, _app_state: &mut AppState) {}
 
-    fn on_esc(&mut self, _: &mut AppState) {}
+    fn on_esc(&mut self, _: &mut AppState) {
+        self.request_input_mode = RequestInputMode::None;
+    }
+
+    fn on_backspace(&mut self, _app_state: &mut AppState) {
+        match self.request_input_mode {
+            RequestInputMode::Amount => {
+                let _ = self.amount_field.pop();
+            },
+            RequestInputMode::Message => {
+                let _ = self.message_field.pop();
+            },
+            RequestInputMode::None => {},
+        }
+    }
+}
+
+impl ReceiveTab {
+    fn on_key_request_input(&mut self, c: char, app_state: &AppState) -> KeyHandled {
+        match self.request_input_mode {
+            RequestInputMode::None => return KeyHandled::NotHandled,
+            RequestInputMode::Amount => match c {
+                '\n' => self.request_input_mode = RequestInputMode::Message,
+                c => {
+                    if c.is_numeric() || ['.', 't', 'T', 'u', 'U'].contains(&c) {
+                        self.amount_field.push(c);
+                    }
+                    return KeyHandled::Handled;
+                },
+            },
+            RequestInputMode::Message => match c {
+                '\n' => self.request_input_mode = RequestInputMode::None,
+                c => {
+                    self.message_field.push(c);
+                    return KeyHandled::Handled;
+                },
+            },
+        }
+        self.update_payment_request(app_state);
+        KeyHandled::Handled
+    }
+}
 
-    fn on_backspace(&mut self, _app_state: &mut AppState) {}
+#[derive(PartialEq, Debug)]
+pub enum RequestInputMode {
+    None,
+    Amount,
+    Message,
 }
@@ -38,13 +38,13 @@ use minotari_wallet::{
         handle::TransactionEventReceiver,
         storage::models::{CompletedTransaction, TxCancellationReason},
     },
-    util::wallet_identity::WalletIdentity,
+    util::{payment_request::PaymentRequest, wallet_identity::WalletIdentity},
     WalletConfig,
     WalletSqlite,
 };
-use qrcode::{render::unicode, QrCode};
 use tari_common::configuration::Network;
 use tari_common_types::{
+    address_utils::{check_emoji_id, EmojiIdCheck},
     tari_address::TariAddress,
     transaction::{TransactionDirection, TransactionStatus, TxId},
     types::PublicKey,
@@ -86,7 +86,10 @@ use crate::{
         ui_contact::UiContact,
         ui_error::UiError,
     },
-    utils::db::{CUSTOM_BASE_NODE_ADDRESS_KEY, CUSTOM_BASE_NODE_PUBLIC_KEY_KEY},
+    utils::{
+        db::{CUSTOM_BASE_NODE_ADDRESS_KEY, CUSTOM_BASE_NODE_PUBLIC_KEY_KEY},
+        qr_code::render_qr_code,
+    },
     wallet_modes::PeerConfig,
 };
 
@@ -228,11 +231,7 @@ impl AppState {
     pub async fn upsert_contact(&mut self, alias: String, tari_emoji: String) -> Result<(), UiError> {
         let mut inner = self.inner.write().await;
 
-        let address = match TariAddress::from_emoji_string(&tari_emoji) {
-            Ok(address) => address,
-            Err(_) => TariAddress::from_bytes(&from_hex(&tari_emoji).map_err(|_| UiError::PublicKeyParseError)?)
-                .map_err(|_| UiError::PublicKeyParseError)?,
-        };
+        let address = parse_address(&tari_emoji, inner.get_network())?;
 
         let contact = Contact::new(alias, address, None, None, false);
         inner.wallet.contacts_service.upsert_contact(contact).await?;
@@ -260,11 +259,7 @@ impl AppState {
 
     pub async fn delete_contact(&mut self, tari_emoji: String) -> Result<(), UiError> {
         let mut inner = self.inner.write().await;
-        let address = match TariAddress::from_emoji_string(&tari_emoji) {
-            Ok(address) => address,
-            Err(_) => TariAddress::from_bytes(&from_hex(&tari_emoji).map_err(|_| UiError::PublicKeyParseError)?)
-                .map_err(|_| UiError::PublicKeyParseError)?,
-        };
+        let address = parse_address(&tari_emoji, inner.get_network())?;
 
         inner.wallet.contacts_service.remove_contact(address).await?;
 
@@ -300,11 +295,7 @@ impl AppState {
         result_tx: watch::Sender<UiTransactionSendStatus>,
     ) -> Result<(), UiError> {
         let inner = self.inner.write().await;
-        let address = match TariAddress::from_emoji_string(&address) {
-            Ok(address) => address,
-            Err(_) => TariAddress::from_bytes(&from_hex(&address).map_err(|_| UiError::PublicKeyParseError)?)
-                .map_err(|_| UiError::PublicKeyParseError)?,
-        };
+        let address = parse_address(&address, inner.get_network())?;
 
         let output_features = OutputFeatures { ..Default::default() };
 
@@ -334,11 +325,7 @@ impl AppState {
         result_tx: watch::Sender<UiTransactionSendStatus>,
     ) -> Result<(), UiError> {
         let inner = self.inner.write().await;
-        let address = match TariAddress::from_emoji_string(&address) {
-            Ok(address) => address,
-            Err(_) => TariAddress::from_bytes(&from_hex(&address).map_err(|_| UiError::PublicKeyParseError)?)
-                .map_err(|_| UiError::PublicKeyParseError)?,
-        };
+        let address = parse_address(&address, inner.get_network())?;
         let output_features = OutputFeatures { ..Default::default() };
 
         let fee_per_gram = fee_per_gram * uT;
@@ -367,11 +354,7 @@ impl AppState {
         result_tx: watch::Sender<UiTransactionSendStatus>,
     ) -> Result<(), UiError> {
         let inner = self.inner.write().await;
-        let address = match TariAddress::from_emoji_string(&address) {
-            Ok(address) => address,
-            Err(_) => TariAddress::from_bytes(&from_hex(&address).map_err(|_| UiError::PublicKeyParseError)?)
-                .map_err(|_| UiError::PublicKeyParseError)?,
-        };
+        let address = parse_address(&address, inner.get_network())?;
 
         let output_features = OutputFeatures { ..Default::default() };
 
@@ -1245,22 +1228,11 @@ pub struct EventListItem {
 impl AppStateData {
     pub fn new(wallet_identity: &WalletIdentity, base_node_selected: Peer, base_node_config: PeerConfig) -> Self {
         let eid = wallet_identity.address.to_emoji_string();
-        let qr_link = format!(
-            "tari://{}/transactions/send?tariAddress={}",
-            wallet_identity.network,
-            wallet_identity.address.to_hex()
-        );
-        let code = QrCode::new(qr_link).unwrap();
-        let image = code
-            .render::<unicode::Dense1x2>()
-            .dark_color(unicode::Dense1x2::Dark)
-            .light_color(unicode::Dense1x2::Light)
-            .build()
-            .lines()
-            .skip(1)
-            .fold("".to_string(), |acc, l| format!("{}{}\n", acc, l));
+        let qr_link = PaymentRequest::new(wallet_identity.address.clone()).to_uri();
+        let image = render_qr_code(&qr_link).unwrap();
 
         let identity = MyIdentity {
+            address: wallet_identity.address.clone(),
             tari_address: wallet_identity.address.to_hex(),
             network_address: wallet_identity
                 .node_identity
@@ -1319,6 +1291,7 @@ impl AppStateData {
 
 #[derive(Clone)]
 pub struct MyIdentity {
+    pub address: TariAddress,
     pub tari_address: String,
     pub network_address: String,
     pub emoji_id: String,
@@ -1352,6 +1325,39 @@ bitflags! {
     }
 }
 
+/// Parses an address given as an emoji ID or in hex. Mistyped emoji IDs are checked for single-emoji errors, so that
+/// the user can be told where the typo is.
+fn parse_address(address: &str, network: Network) -> Result<TariAddress, UiError> {
+    if let Ok(address) = TariAddress::from_emoji_string(address) {
+        return Ok(address);
+    }
+    if let Ok(bytes) = from_hex(address) {
+        return TariAddress::from_bytes(&bytes).map_err(|_| UiError::PublicKeyParseError);
+    }
+    match check_emoji_id(address, network) {
+        Ok(EmojiIdCheck::Corrections(corrections)) => {
+            let hint = match corrections.as_slice() {
+                [correction] => format!(
+                    "emoji {} ({}) should probably be {}",
+                    correction.position + 1,
+                    correction.found,
+                    correction.replacement
+                ),
+                _ => format!(
+                    "one emoji is mistyped, at one of positions {}",
+                    corrections
+                        .iter()
+                        .map(|c| (c.position + 1).to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            };
+            Err(UiError::EmojiIdTypo(hint))
+        },
+        _ => Err(UiError::PublicKeyParseError),
+    }
+}
+
 #[derive(Clone)]
 struct AppStateConfig {
     pub cache_update_cooldown: Duration,
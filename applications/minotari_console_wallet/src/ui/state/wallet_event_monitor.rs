@@ -25,7 +25,7 @@ use std::{ops::Deref, sync::Arc};
 use log::*;
 use minotari_wallet::{
     base_node_service::{handle::BaseNodeEvent, service::BaseNodeState},
-    connectivity_service::WalletConnectivityInterface,
+    connectivity_service::{BaseNodeChangeReason, WalletConnectivityInterface},
     output_manager_service::handle::OutputManagerEvent,
     transaction_service::handle::TransactionEvent,
 };
@@ -72,6 +72,7 @@ impl WalletEventMonitor {
         let wallet_connectivity = self.app_state_inner.read().await.get_wallet_connectivity();
         let mut connectivity_status = wallet_connectivity.get_connectivity_status_watch();
         let mut base_node_changed = wallet_connectivity.get_current_base_node_watcher();
+        let mut base_node_changed_events = wallet_connectivity.get_base_node_changed_event_stream();
 
         let mut base_node_events = self.app_state_inner.read().await.get_base_node_event_stream();
         // let mut software_update_notif = self
@@ -147,6 +148,14 @@ impl WalletEventMonitor {
                                         format!("Transaction Reply Received - TxId: {}", tx_id)
                                     ).await;
                                 },
+                                TransactionEvent::TransactionUnmined(tx_id) => {
+                                    self.trigger_confirmations_cleanup(tx_id).await;
+                                    self.trigger_tx_state_refresh(tx_id).await;
+                                    self.trigger_balance_refresh();
+                                    self.add_notification(
+                                        format!("Transaction Reorged Out of the Chain - TxId: {}", tx_id)
+                                    ).await;
+                                },
                                 TransactionEvent::TransactionBroadcast(tx_id) => {
                                     self.trigger_tx_state_refresh(tx_id).await;
                                     self.trigger_balance_refresh();
@@ -227,6 +236,38 @@ impl WalletEventMonitor {
                         self.trigger_balance_refresh();
                     }
                 }
+                result = base_node_changed_events.recv() => {
+                    match result {
+                        Ok(event) => {
+                            trace!(target: LOG_TARGET, "Wallet Event Monitor received base node changed event {:?}", event);
+                            let desc = format!(
+                                "Base node changed to {} ({:?})",
+                                event.current.public_key,
+                                event.reason
+                            );
+                            self.app_state_inner.write().await.add_event(EventListItem{
+                                event_type: "BaseNodeChanged".to_string(),
+                                desc,
+                            });
+                            if event.reason == BaseNodeChangeReason::Failover {
+                                let previous = event
+                                    .previous
+                                    .as_ref()
+                                    .map(|p| p.public_key.to_string())
+                                    .unwrap_or_else(|| "none".to_string());
+                                self.add_notification(format!(
+                                    "Base node {} is unavailable, failed over to {}",
+                                    previous,
+                                    event.current.public_key
+                                )).await;
+                            }
+                        },
+                        Err(broadcast::error::RecvError::Lagged(n)) => {
+                            warn!(target: LOG_TARGET, "Missed {} from Base node changed events", n);
+                        }
+                        Err(broadcast::error::RecvError::Closed) => {}
+                    }
+                },
                 result = base_node_events.recv() => {
                     match result {
                         Ok(msg) => {
@@ -48,6 +48,8 @@ pub enum UiError {
     WalletStorageError(#[from] WalletStorageError),
     #[error("Could not convert string into Public Key")]
     PublicKeyParseError,
+    #[error("Invalid emoji ID: {0}")]
+    EmojiIdTypo(String),
     #[error("Could not convert string into Net Address")]
     AddressParseError,
     #[error("Peer did not include an address")]
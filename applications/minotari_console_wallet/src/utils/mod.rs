@@ -24,5 +24,6 @@ pub mod crossterm_events;
 pub mod db;
 pub mod events;
 pub mod formatting;
+pub mod qr_code;
 
 // pub mod termion_events;
@@ -0,0 +1,102 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    convert::TryFrom,
+    fs::File,
+    io,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use flate2::{write::ZlibEncoder, Compression};
+use qrcode::{render::unicode, types::QrError, Color, QrCode};
+
+/// The number of light modules around the code that scanners need to detect it
+const QUIET_ZONE_MODULES: usize = 4;
+/// The width and height in pixels of each module in an exported image
+const MODULE_PIXELS: usize = 8;
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+
+/// Renders `data` as a QR code made of unicode half blocks for display in the terminal
+pub fn render_qr_code(data: &str) -> Result<String, QrError> {
+    let code = QrCode::new(data)?;
+    Ok(code
+        .render::<unicode::Dense1x2>()
+        .dark_color(unicode::Dense1x2::Dark)
+        .light_color(unicode::Dense1x2::Light)
+        .build()
+        .lines()
+        .skip(1)
+        .fold("".to_string(), |acc, l| format!("{}{}\n", acc, l)))
+}
+
+/// Writes `data` as a QR code to a greyscale PNG image at `path`
+pub fn write_qr_code_png(data: &str, path: &Path) -> io::Result<()> {
+    let code = QrCode::new(data).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let modules = code.width();
+    let colors = code.to_colors();
+    let size = (modules + 2 * QUIET_ZONE_MODULES) * MODULE_PIXELS;
+
+    let mut scanlines = Vec::with_capacity((size + 1) * size);
+    for y in 0..size {
+        // Each scanline starts with its filter type, which is none
+        scanlines.push(0);
+        let module_y = (y / MODULE_PIXELS).checked_sub(QUIET_ZONE_MODULES);
+        for x in 0..size {
+            let module_x = (x / MODULE_PIXELS).checked_sub(QUIET_ZONE_MODULES);
+            let is_dark = match (module_x, module_y) {
+                (Some(mx), Some(my)) if mx < modules && my < modules => colors[my * modules + mx] == Color::Dark,
+                _ => false,
+            };
+            scanlines.push(if is_dark { 0x00 } else { 0xFF });
+        }
+    }
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&scanlines)?;
+    let image_data = encoder.finish()?;
+
+    let size = u32::try_from(size).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&size.to_be_bytes());
+    header.extend_from_slice(&size.to_be_bytes());
+    // 8-bit greyscale, deflate compression, adaptive filtering and no interlacing
+    header.extend_from_slice(&[8, 0, 0, 0, 0]);
+
+    let mut file = BufWriter::new(File::create(path)?);
+    file.write_all(&PNG_SIGNATURE)?;
+    write_png_chunk(&mut file, b"IHDR", &header)?;
+    write_png_chunk(&mut file, b"IDAT", &image_data)?;
+    write_png_chunk(&mut file, b"IEND", &[])?;
+    file.flush()
+}
+
+fn write_png_chunk<W: Write>(writer: &mut W, chunk_type: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    let length = u32::try_from(data.len()).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    writer.write_all(&length.to_be_bytes())?;
+    writer.write_all(chunk_type)?;
+    writer.write_all(data)?;
+    let mut crc = crc32fast::Hasher::new();
+    crc.update(chunk_type);
+    crc.update(data);
+    writer.write_all(&crc.finalize().to_be_bytes())
+}
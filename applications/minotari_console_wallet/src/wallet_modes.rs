@@ -25,15 +25,24 @@
 use std::{fs, io::Stdout, path::PathBuf};
 
 use clap::Parser;
+use futures::FutureExt;
 use log::*;
-use minotari_app_grpc::authentication::ServerAuthenticationInterceptor;
+use minotari_app_grpc::{
+    authentication::ServerAuthenticationInterceptor,
+    grpc_health_v1::health_server::HealthServer,
+    grpc_reflection_v1alpha::server_reflection_server::ServerReflectionServer,
+    health::HealthService,
+    reflection::ReflectionService,
+    tari_rpc::wallet_server::WalletServer,
+};
+use minotari_app_utilities::service_manager::{self, HealthStatus};
 use minotari_wallet::{WalletConfig, WalletSqlite};
 use rand::{rngs::OsRng, seq::SliceRandom};
 use tari_common::exit_codes::{ExitCode, ExitError};
 use tari_common_types::grpc_authentication::GrpcAuthentication;
 use tari_comms::{multiaddr::Multiaddr, peer_manager::Peer, utils::multiaddr::multiaddr_to_socketaddr};
 use tokio::{runtime::Handle, sync::broadcast};
-use tonic::transport::Server;
+use tonic::transport::{NamedService, Server};
 use tui::backend::CrosstermBackend;
 
 use crate::{
@@ -405,11 +414,30 @@ async fn run_grpc(
     info!(target: LOG_TARGET, "Starting GRPC on {}", grpc_listener_addr);
     let address = multiaddr_to_socketaddr(&grpc_listener_addr).map_err(|e| e.to_string())?;
     let auth = ServerAuthenticationInterceptor::new(auth_config);
-    let service = minotari_app_grpc::tari_rpc::wallet_server::WalletServer::with_interceptor(grpc, auth);
-
+    let service = WalletServer::with_interceptor(grpc, auth);
+    // The health and reflection services are served without authentication, so that load balancers and tools like
+    // grpcurl can use them
+    let wallet_service_name = <WalletServer<WalletGrpcServer> as NamedService>::NAME;
+    let mut health_check = crate::health_check(&wallet);
+    let health = HealthService::new(&[wallet_service_name], move || {
+        health_check()
+            .map(|status| matches!(status, HealthStatus::Healthy(_)))
+            .boxed()
+    });
+    let reflection =
+        ReflectionService::new(&[wallet_service_name, <HealthServer<HealthService> as NamedService>::NAME])
+            .map_err(|e| format!("Could not load the gRPC reflection descriptors: {}", e))?;
+
+    // Stop serving when the wallet shuts down or the service manager asks the wallet to stop
+    let shutdown = futures::future::select(
+        wallet.wait_until_shutdown().boxed(),
+        service_manager::wait_for_stop_request().boxed(),
+    );
     Server::builder()
         .add_service(service)
-        .serve_with_shutdown(address, wallet.wait_until_shutdown())
+        .add_service(HealthServer::new(health))
+        .add_service(ServerReflectionServer::new(reflection))
+        .serve_with_shutdown(address, shutdown.map(|_| ()))
         .await
         .map_err(|e| format!("GRPC server returned error:{}", e))?;
 
@@ -473,6 +501,8 @@ mod test {
                 CliCommands::Whois(_) => whois = true,
                 CliCommands::ExportUtxos(_) => {},
                 CliCommands::ExportSpentUtxos(_) => {},
+                CliCommands::ExportPaymentRequest(_) => {},
+                CliCommands::OpenUri(_) => {},
                 CliCommands::CountUtxos => {},
                 CliCommands::SetBaseNode(_) => {},
                 CliCommands::SetCustomBaseNode(_) => {},
@@ -481,6 +511,7 @@ mod test {
                 CliCommands::FinaliseShaAtomicSwap(_) => {},
                 CliCommands::ClaimShaAtomicSwapRefund(_) => {},
                 CliCommands::RevalidateWalletDb => {},
+                CliCommands::AuditOutputs(_) => {},
                 CliCommands::HashGrpcPassword(_) => {},
                 CliCommands::RegisterValidatorNode(_) => {},
             }
@@ -27,7 +27,13 @@ use std::{cmp, sync::Arc};
 use log::*;
 use minotari_node_grpc_client::{grpc, BaseNodeGrpcClient};
 use minotari_wallet_grpc_client::WalletGrpcClient;
-use tari_core::proof_of_work::{monero_rx, monero_rx::FixedByteArray, Difficulty};
+use serde_json as json;
+use tari_core::proof_of_work::{
+    aux_pow::{AuxChainAdapter, MoneroAdapter},
+    monero_rx::FixedByteArray,
+    Difficulty,
+};
+use tari_utilities::hex::Hex;
 
 use crate::{
     block_template_data::{BlockTemplateData, BlockTemplateDataBuilder},
@@ -231,16 +237,24 @@ impl BlockTemplateProtocol<'_> {
 
         // Deserialize the block template blob
         debug!(target: LOG_TARGET, "Deserializing Blocktemplate Blob into Monero Block",);
-        let mut monero_block = monero_rx::deserialize_monero_block_from_hex(&monero_mining_data.blocktemplate_blob)?;
+        let mut monero_block = MoneroAdapter.deserialize_block(&monero_mining_data.blocktemplate_blob)?;
+        let hard_fork = self.config.monero_version_policy().check_block(&monero_block)?;
+        debug!(
+            target: LOG_TARGET,
+            "Monero block template is version {}.{} ({} hard fork)",
+            monero_block.header.major_version.0,
+            monero_block.header.minor_version.0,
+            hard_fork
+        );
 
         debug!(target: LOG_TARGET, "Appending Merged Mining Tag",);
         // Add the Tari merge mining tag to the retrieved block template
-        monero_rx::append_merge_mining_tag(&mut monero_block, &tari_block.merge_mining_hash)?;
+        MoneroAdapter.append_merge_mining_tag(&mut monero_block, &tari_block.merge_mining_hash)?;
 
         debug!(target: LOG_TARGET, "Creating blockhashing blob from blocktemplate blob",);
         // Must be done after the tag is inserted since it will affect the hash of the miner tx
-        let blockhashing_blob = monero_rx::create_blockhashing_blob_from_block(&monero_block)?;
-        let blocktemplate_blob = monero_rx::serialize_monero_block_to_hex(&monero_block)?;
+        let blockhashing_blob = MoneroAdapter.hashing_blob(&monero_block)?;
+        let blocktemplate_blob = MoneroAdapter.serialize_block(&monero_block)?;
 
         let monero_difficulty = monero_mining_data.difficulty;
         let mining_difficulty = cmp::min(monero_difficulty, tari_difficulty);
@@ -290,3 +304,18 @@ pub struct MoneroMiningData {
     pub blocktemplate_blob: String,
     pub difficulty: u64,
 }
+
+impl MoneroMiningData {
+    /// Reads the mining data from the `result` of a monerod `get_block_template` response
+    pub fn from_block_template_result(result: &json::Value) -> Result<Self, MmProxyError> {
+        let seed_hash = FixedByteArray::from_hex(&result["seed_hash"].to_string().replace('\"', ""))
+            .map_err(|err| MmProxyError::InvalidMonerodResponse(format!("seed hash hex is invalid: {}", err)))?;
+        let blocktemplate_blob = result["blocktemplate_blob"].to_string().replace('\"', "");
+        let difficulty = result["difficulty"].as_u64().unwrap_or_default();
+        Ok(Self {
+            seed_hash,
+            blocktemplate_blob,
+            difficulty,
+        })
+    }
+}
@@ -27,6 +27,7 @@ use tari_common::{
     SubConfigPath,
 };
 use tari_comms::multiaddr::Multiaddr;
+use tari_core::proof_of_work::monero_rx::{MoneroVersionPolicy, RANDOMX_MIN_MAJOR_VERSION};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
@@ -41,6 +42,11 @@ pub struct MergeMiningProxyConfig {
     pub monerod_password: String,
     /// If authentication is being used for curl
     pub monerod_use_auth: bool,
+    /// A monerod server that is more than this many blocks behind the highest server in `monerod_url` is considered
+    /// stale and will not be selected
+    pub monerod_max_height_lag: u64,
+    /// The interval (in seconds) after which the health of all monerod servers is checked again
+    pub monerod_health_check_interval: u64,
     /// The Minotari base node's GRPC address
     pub base_node_grpc_address: Option<Multiaddr>,
     /// The Minotari wallet's GRPC address
@@ -49,6 +55,15 @@ pub struct MergeMiningProxyConfig {
     pub console_wallet_grpc_authentication: GrpcAuthentication,
     /// Address of the minotari_merge_mining_proxy application
     pub listener_address: Multiaddr,
+    /// Address of the stratum server for miners such as XMRig. If not set, the stratum server is not started.
+    pub stratum_listener_address: Option<Multiaddr>,
+    /// The Monero wallet address that block templates are requested for in stratum mode
+    pub stratum_monero_wallet_address: String,
+    /// The interval (in seconds) at which the Monero and Minotari tips are checked for a new stratum job
+    pub stratum_tip_poll_interval: u64,
+    /// The maximum age (in seconds) of a stratum job before a new one is created, so that new transactions are
+    /// included
+    pub stratum_max_job_age: u64,
     /// In sole merged mining, the block solution is usually submitted to the Monero blockchain (monerod) as well as to
     /// the Minotari blockchain, then this setting should be "true". With pool merged mining, there is no sense in
     /// submitting the solution to the Monero blockchain as thepool does that, then this setting should be "false".
@@ -67,6 +82,11 @@ pub struct MergeMiningProxyConfig {
     /// Note that this data is publicly readable, but it is suggested you populate it so that
     /// pool dominance can be seen before any one party has more than 51%.
     pub coinbase_extra: String,
+    /// The lowest Monero block major version accepted for merge mining
+    pub monero_min_major_version: u64,
+    /// The highest Monero block major version accepted for merge mining. Versions newer than the latest known Monero
+    /// hard fork are checked using the latest known rules if this is not set.
+    pub monero_max_major_version: Option<u64>,
     /// Selected network
     pub network: Network,
 }
@@ -79,20 +99,38 @@ impl Default for MergeMiningProxyConfig {
             monerod_username: String::new(),
             monerod_password: String::new(),
             monerod_use_auth: false,
+            monerod_max_height_lag: 2,
+            monerod_health_check_interval: 60,
             base_node_grpc_address: None,
             console_wallet_grpc_address: None,
             console_wallet_grpc_authentication: GrpcAuthentication::default(),
             listener_address: "/ip4/127.0.0.1/tcp/18081".parse().unwrap(),
+            stratum_listener_address: None,
+            stratum_monero_wallet_address: String::new(),
+            stratum_tip_poll_interval: 1,
+            stratum_max_job_age: 30,
             submit_to_origin: true,
             wait_for_initial_sync_at_startup: true,
             check_tari_difficulty_before_submit: true,
             max_randomx_vms: 5,
             coinbase_extra: "tari_merge_mining_proxy".to_string(),
+            monero_min_major_version: RANDOMX_MIN_MAJOR_VERSION,
+            monero_max_major_version: None,
             network: Default::default(),
         }
     }
 }
 
+impl MergeMiningProxyConfig {
+    /// The Monero block versions that are accepted from monerod
+    pub fn monero_version_policy(&self) -> MoneroVersionPolicy {
+        MoneroVersionPolicy {
+            min_major_version: self.monero_min_major_version,
+            max_major_version: self.monero_max_major_version,
+        }
+    }
+}
+
 impl SubConfigPath for MergeMiningProxyConfig {
     fn main_key_prefix() -> &'static str {
         "merge_mining_proxy"
@@ -105,6 +143,7 @@ mod test {
 
     use tari_common::DefaultConfigLoader;
     use tari_comms::multiaddr::Multiaddr;
+    use tari_core::proof_of_work::monero_rx::MoneroVersionPolicy;
 
     use crate::config::MergeMiningProxyConfig;
 
@@ -174,5 +213,7 @@ mod test {
         assert_eq!(config.base_node_grpc_address, None);
         assert!(!config.monerod_use_auth);
         assert!(config.submit_to_origin);
+        assert_eq!(config.stratum_listener_address, None);
+        assert_eq!(config.monero_version_policy(), MoneroVersionPolicy::default());
     }
 }
@@ -30,8 +30,11 @@ pub use cli::Cli;
 mod common;
 mod config;
 mod error;
+mod monerod_pool;
 mod proxy;
 mod run_merge_miner;
+mod stats;
+mod stratum;
 use run_merge_miner::start_merge_miner;
 
 pub async fn merge_miner(cli: Cli) -> Result<(), anyhow::Error> {
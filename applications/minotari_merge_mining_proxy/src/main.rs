@@ -28,8 +28,11 @@ mod cli;
 mod common;
 mod config;
 mod error;
+mod monerod_pool;
 mod proxy;
 mod run_merge_miner;
+mod stats;
+mod stratum;
 
 #[cfg(test)]
 mod test;
@@ -0,0 +1,241 @@
+//  Copyright 2023, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! A pool of upstream monerod servers. Servers are probed with `/get_height` and scored by the height they report and
+//! their response latency; the proxy uses the fastest server that is not behind the best known height, and fails over
+//! to the next best server on errors or when the current server falls behind.
+
+use std::{
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+use futures::future;
+use log::*;
+use serde_json as json;
+
+use crate::{config::MergeMiningProxyConfig, error::MmProxyError};
+
+const LOG_TARGET: &str = "minotari_mm_proxy::monerod_pool";
+/// Probes that take longer than this count as failures
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The last observed health of a monerod server
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MonerodHealth {
+    pub height: Option<u64>,
+    pub latency: Option<Duration>,
+    pub consecutive_failures: u32,
+}
+
+impl MonerodHealth {
+    fn is_reachable(&self) -> bool {
+        self.consecutive_failures == 0 && self.height.is_some()
+    }
+}
+
+#[derive(Debug)]
+struct PoolState {
+    servers: Vec<(String, MonerodHealth)>,
+    current: Option<usize>,
+    last_checked: Option<Instant>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MonerodPool {
+    state: Arc<RwLock<PoolState>>,
+    http_client: reqwest::Client,
+    credentials: Option<(String, String)>,
+    max_height_lag: u64,
+    health_check_interval: Duration,
+}
+
+impl MonerodPool {
+    pub fn new(config: &MergeMiningProxyConfig, http_client: reqwest::Client) -> Self {
+        let servers = config
+            .monerod_url
+            .as_slice()
+            .iter()
+            .map(|url| (url.trim_end_matches('/').to_string(), MonerodHealth::default()))
+            .collect();
+        Self {
+            state: Arc::new(RwLock::new(PoolState {
+                servers,
+                current: None,
+                last_checked: None,
+            })),
+            http_client,
+            credentials: if config.monerod_use_auth {
+                Some((config.monerod_username.clone(), config.monerod_password.clone()))
+            } else {
+                None
+            },
+            max_height_lag: config.monerod_max_height_lag,
+            health_check_interval: Duration::from_secs(config.monerod_health_check_interval),
+        }
+    }
+
+    /// The URL of the server to use. All servers are probed again if there is no current server or the health check
+    /// interval has elapsed.
+    pub async fn current_server(&self) -> Result<String, MmProxyError> {
+        {
+            let state = self.state.read().expect("Read lock should not fail");
+            let is_fresh = state
+                .last_checked
+                .map(|t| t.elapsed() < self.health_check_interval)
+                .unwrap_or(false);
+            if let (Some(current), true) = (state.current, is_fresh) {
+                return Ok(state.servers[current].0.clone());
+            }
+        }
+        self.refresh().await
+    }
+
+    /// Record that a request to the current server failed so that the next request fails over to another server
+    pub fn report_failure(&self) {
+        let mut state = self.state.write().expect("Write lock should not fail");
+        if let Some(current) = state.current.take() {
+            state.servers[current].1.consecutive_failures += 1;
+            warn!(
+                target: LOG_TARGET,
+                "Monerod server {} failed, failing over", state.servers[current].0
+            );
+        }
+    }
+
+    /// A snapshot of the health of every server
+    pub fn health(&self) -> Vec<(String, MonerodHealth)> {
+        self.state.read().expect("Read lock should not fail").servers.clone()
+    }
+
+    async fn refresh(&self) -> Result<String, MmProxyError> {
+        let urls = self
+            .state
+            .read()
+            .expect("Read lock should not fail")
+            .servers
+            .iter()
+            .map(|(url, _)| url.clone())
+            .collect::<Vec<_>>();
+        let probes = future::join_all(urls.iter().map(|url| self.probe(url))).await;
+
+        let mut state = self.state.write().expect("Write lock should not fail");
+        for ((url, health), probe) in state.servers.iter_mut().zip(probes) {
+            match probe {
+                Ok((height, latency)) => {
+                    debug!(
+                        target: LOG_TARGET,
+                        "Monerod server {} is at height {} ({}ms)",
+                        url,
+                        height,
+                        latency.as_millis()
+                    );
+                    health.height = Some(height);
+                    health.latency = Some(latency);
+                    health.consecutive_failures = 0;
+                },
+                Err(err) => {
+                    warn!(target: LOG_TARGET, "Monerod server unavailable: {} ({})", url, err);
+                    health.consecutive_failures += 1;
+                },
+            }
+        }
+        state.last_checked = Some(Instant::now());
+        let health = state.servers.iter().map(|(_, h)| h.clone()).collect::<Vec<_>>();
+        state.current = select_best(&health, self.max_height_lag);
+        match state.current {
+            Some(current) => {
+                let url = state.servers[current].0.clone();
+                info!(target: LOG_TARGET, "Monerod server selected: {}", url);
+                Ok(url)
+            },
+            None => Err(MmProxyError::ServersUnavailable),
+        }
+    }
+
+    async fn probe(&self, url: &str) -> Result<(u64, Duration), MmProxyError> {
+        let start = Instant::now();
+        let mut builder = self
+            .http_client
+            .get(format!("{}/get_height", url))
+            .timeout(PROBE_TIMEOUT);
+        if let Some((username, password)) = &self.credentials {
+            builder = builder.basic_auth(username, Some(password));
+        }
+        let resp = builder.send().await.map_err(MmProxyError::MonerodRequestFailed)?;
+        let latency = start.elapsed();
+        let json = resp
+            .json::<json::Value>()
+            .await
+            .map_err(MmProxyError::MonerodRequestFailed)?;
+        let height = json["height"].as_u64().ok_or_else(|| {
+            MmProxyError::InvalidMonerodResponse("`height` field was missing from /get_height response".to_string())
+        })?;
+        Ok((height, latency))
+    }
+}
+
+/// Pick the reachable server with the lowest latency among those that are at most `max_height_lag` blocks behind the
+/// highest reported height
+fn select_best(servers: &[MonerodHealth], max_height_lag: u64) -> Option<usize> {
+    let best_height = servers
+        .iter()
+        .filter(|h| h.is_reachable())
+        .filter_map(|h| h.height)
+        .max()?;
+    servers
+        .iter()
+        .enumerate()
+        .filter(|(_, h)| h.is_reachable())
+        .filter(|(_, h)| h.height.unwrap_or(0).saturating_add(max_height_lag) >= best_height)
+        .min_by_key(|(_, h)| h.latency.unwrap_or(Duration::MAX))
+        .map(|(i, _)| i)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn health(height: u64, latency_ms: u64) -> MonerodHealth {
+        MonerodHealth {
+            height: Some(height),
+            latency: Some(Duration::from_millis(latency_ms)),
+            consecutive_failures: 0,
+        }
+    }
+
+    #[test]
+    fn it_prefers_the_fastest_server_at_the_tip() {
+        let servers = [health(100, 50), health(100, 10), health(101, 30)];
+        assert_eq!(select_best(&servers, 1), Some(1));
+        assert_eq!(select_best(&servers, 0), Some(2));
+    }
+
+    #[test]
+    fn it_skips_stale_and_unreachable_servers() {
+        let mut unreachable = health(200, 1);
+        unreachable.consecutive_failures = 1;
+        let servers = [health(150, 1), unreachable, health(200, 100)];
+        assert_eq!(select_best(&servers, 5), Some(2));
+        assert_eq!(select_best(&[MonerodHealth::default()], 5), None);
+    }
+}
@@ -24,11 +24,11 @@ use std::{
     cmp,
     convert::TryInto,
     future::Future,
+    net::SocketAddr,
     pin::Pin,
     sync::{
         atomic::{AtomicBool, Ordering},
         Arc,
-        RwLock,
     },
     task::{Context, Poll},
     time::Instant,
@@ -36,7 +36,7 @@ use std::{
 
 use borsh::BorshSerialize;
 use bytes::Bytes;
-use hyper::{header::HeaderValue, service::Service, Body, Method, Request, Response, StatusCode, Uri};
+use hyper::{header, header::HeaderValue, service::Service, Body, Method, Request, Response, StatusCode, Uri};
 use json::json;
 use jsonrpc::error::StandardError;
 use minotari_node_grpc_client::{grpc, BaseNodeGrpcClient};
@@ -44,8 +44,8 @@ use minotari_wallet_grpc_client::WalletGrpcClient;
 use reqwest::{ResponseBuilderExt, Url};
 use serde_json as json;
 use tari_core::proof_of_work::{
+    aux_pow::{AuxChainAdapter, MoneroAdapter},
     monero_rx,
-    monero_rx::FixedByteArray,
     randomx_difficulty,
     randomx_factory::RandomXFactory,
 };
@@ -54,10 +54,12 @@ use tracing::{debug, error, info, instrument, trace, warn};
 
 use crate::{
     block_template_data::BlockTemplateRepository,
-    block_template_protocol::{BlockTemplateProtocol, MoneroMiningData},
+    block_template_protocol::{BlockTemplateProtocol, FinalBlockTemplateData, MoneroMiningData},
     common::{json_rpc, monero_rpc::CoreRpcErrorCode, proxy, proxy::convert_json_to_hyper_json_response},
     config::MergeMiningProxyConfig,
     error::MmProxyError,
+    monerod_pool::MonerodPool,
+    stats::ProxyStats,
 };
 
 const LOG_TARGET: &str = "minotari_mm_proxy::proxy";
@@ -81,6 +83,7 @@ impl MergeMiningProxyService {
         randomx_factory: RandomXFactory,
     ) -> Self {
         debug!(target: LOG_TARGET, "Config: {:?}", config);
+        let monerod_pool = MonerodPool::new(&config, http_client.clone());
         Self {
             inner: InnerService {
                 config: Arc::new(config),
@@ -89,12 +92,93 @@ impl MergeMiningProxyService {
                 base_node_client,
                 wallet_client,
                 initial_sync_achieved: Arc::new(AtomicBool::new(false)),
-                current_monerod_server: Arc::new(RwLock::new(None)),
-                last_assigned_monerod_server: Arc::new(RwLock::new(None)),
+                monerod_pool,
                 randomx_factory,
+                stats: ProxyStats::new(),
+                rig_id: "unknown".to_string(),
             },
         }
     }
+
+    /// A handle to the service for a new connection. Shares submitted on the connection are accounted to the remote
+    /// IP address.
+    pub fn for_connection(&self, remote_addr: SocketAddr) -> Self {
+        let mut inner = self.inner.clone();
+        inner.rig_id = remote_addr.ip().to_string();
+        Self { inner }
+    }
+
+    /// Requests a block template from monerod and adds the Minotari merge mining tag to it, returning it along with its
+    /// Monero height. The template is stored so that solutions for it can be submitted with
+    /// [submit_merge_mined_block](Self::submit_merge_mined_block).
+    pub(crate) async fn fetch_merge_mining_template(
+        &self,
+        wallet_address: &str,
+    ) -> Result<(u64, FinalBlockTemplateData), MmProxyError> {
+        let result = self
+            .inner
+            .monerod_json_rpc(
+                "get_block_template",
+                json!({ "wallet_address": wallet_address, "reserve_size": 0 }),
+            )
+            .await?;
+        let height = result["height"].as_u64().ok_or_else(|| {
+            MmProxyError::InvalidMonerodResponse("Expected `get_block_template` to include `result.height`".to_string())
+        })?;
+        let monero_mining_data = MoneroMiningData::from_block_template_result(&result)?;
+        let final_block_template_data = self.inner.new_block_template(monero_mining_data).await?;
+        self.inner
+            .block_templates
+            .save(
+                final_block_template_data.merge_mining_hash.clone(),
+                final_block_template_data.template.clone(),
+            )
+            .await;
+        self.inner.stats.record_new_template();
+        Ok((height, final_block_template_data))
+    }
+
+    /// Submits the Minotari block that the merge mined Monero block was mined for to the base node
+    pub(crate) async fn submit_merge_mined_block(
+        &self,
+        monero_block: monero_rx::MoneroBlock,
+    ) -> Result<MergeMinedSubmission, MmProxyError> {
+        let submission = self.inner.submit_merge_mined_block(monero_block).await;
+        self.inner.block_templates.remove_outdated().await;
+        submission
+    }
+
+    /// Submits the Monero block to monerod
+    pub(crate) async fn submit_block_to_monerod(
+        &self,
+        monero_block: &monero_rx::MoneroBlock,
+    ) -> Result<(), MmProxyError> {
+        let blob = MoneroAdapter.serialize_block(monero_block)?;
+        self.inner.monerod_json_rpc("submit_block", json!([blob])).await?;
+        Ok(())
+    }
+
+    /// The current height of the Monero chain
+    pub(crate) async fn monerod_height(&self) -> Result<u64, MmProxyError> {
+        let result = self.inner.monerod_json_rpc("get_block_count", json!({})).await?;
+        result["count"]
+            .as_u64()
+            .ok_or_else(|| MmProxyError::InvalidMonerodResponse("`get_block_count` did not return a count".to_string()))
+    }
+
+    /// The current height of the Minotari chain
+    pub(crate) async fn tari_tip_height(&self) -> Result<u64, MmProxyError> {
+        let mut base_node_client = self.inner.base_node_client.clone();
+        let tip_info = base_node_client.get_tip_info(grpc::Empty {}).await?.into_inner();
+        tip_info
+            .metadata
+            .map(|meta| meta.height_of_longest_chain)
+            .ok_or(MmProxyError::GrpcResponseMissingField("base node metadata"))
+    }
+
+    pub(crate) fn config(&self) -> &MergeMiningProxyConfig {
+        &self.inner.config
+    }
 }
 
 #[allow(clippy::type_complexity)]
@@ -152,6 +236,19 @@ impl Service<Request<Body>> for MergeMiningProxyService {
     }
 }
 
+/// The outcome of submitting a merge mined block to the Minotari base node
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum MergeMinedSubmission {
+    /// No block template matches the merge mining hash in the Monero block, e.g. because it was already submitted
+    UnknownTemplate,
+    /// The Monero block did not achieve the Minotari target difficulty
+    BelowTarget,
+    /// The Minotari block was accepted by the base node
+    Accepted { block_hash: Vec<u8> },
+    /// The Minotari block was rejected by the base node
+    Rejected,
+}
+
 #[derive(Debug, Clone)]
 struct InnerService {
     config: Arc<MergeMiningProxyConfig>,
@@ -160,9 +257,10 @@ struct InnerService {
     base_node_client: BaseNodeGrpcClient<tonic::transport::Channel>,
     wallet_client: WalletGrpcClient<tonic::transport::Channel>,
     initial_sync_achieved: Arc<AtomicBool>,
-    current_monerod_server: Arc<RwLock<Option<String>>>,
-    last_assigned_monerod_server: Arc<RwLock<Option<String>>>,
+    monerod_pool: MonerodPool,
     randomx_factory: RandomXFactory,
+    stats: ProxyStats,
+    rig_id: String,
 }
 
 impl InnerService {
@@ -241,108 +339,45 @@ impl InnerService {
         };
 
         for param in params.iter().filter_map(|p| p.as_str()) {
-            let monero_block = monero_rx::deserialize_monero_block_from_hex(param)?;
+            let monero_block = MoneroAdapter.deserialize_block(param)?;
             debug!(target: LOG_TARGET, "Monero block: {}", monero_block);
-            let hash = monero_rx::extract_tari_hash(&monero_block)?.ok_or_else(|| {
-                MmProxyError::MissingDataError("Could not find Minotari header in coinbase".to_string())
-            })?;
-
-            debug!(
-                target: LOG_TARGET,
-                "Minotari Hash found in Monero block: {}",
-                hex::encode(hash)
-            );
-
-            let mut block_data = match self.block_templates.get(&hash).await {
-                Some(d) => d,
-                None => {
-                    info!(
-                        target: LOG_TARGET,
-                        "Block `{}` submitted but no matching block template was found, possible duplicate submission",
-                        hex::encode(hash)
-                    );
-                    continue;
-                },
-            };
-
-            let monero_data = monero_rx::construct_monero_data(monero_block, block_data.monero_seed.clone())?;
-
-            debug!(target: LOG_TARGET, "Monero PoW Data: {:?}", monero_data);
-
-            let header_mut = block_data.tari_block.header.as_mut().unwrap();
-            let height = header_mut.height;
-            BorshSerialize::serialize(&monero_data, &mut header_mut.pow.as_mut().unwrap().pow_data)
-                .map_err(|err| MmProxyError::ConversionError(err.to_string()))?;
-            let tari_header = header_mut.clone().try_into().map_err(MmProxyError::ConversionError)?;
-            let mut base_node_client = self.base_node_client.clone();
-            let start = Instant::now();
-            let achieved_target = if self.config.check_tari_difficulty_before_submit {
-                trace!(target: LOG_TARGET, "Starting calculate achieved Tari difficultly");
-                let diff = randomx_difficulty(&tari_header, &self.randomx_factory)?;
-                trace!(
-                    target: LOG_TARGET,
-                    "Finished calculate achieved Tari difficultly - achieved {} vs. target {}",
-                    diff.as_u64(),
-                    block_data.tari_difficulty
-                );
-                diff.as_u64()
-            } else {
-                block_data.tari_difficulty
-            };
-
-            if achieved_target >= block_data.tari_difficulty {
-                match base_node_client.submit_block(block_data.tari_block).await {
-                    Ok(resp) => {
-                        if self.config.submit_to_origin {
-                            json_resp = json_rpc::success_response(
-                                request["id"].as_i64(),
-                                json!({ "status": "OK", "untrusted": !self.initial_sync_achieved.load(Ordering::SeqCst) }),
-                            );
-                            let resp = resp.into_inner();
-                            json_resp = append_aux_chain_data(
-                                json_resp,
-                                json!({"id": TARI_CHAIN_ID, "block_hash": resp.block_hash.to_hex()}),
-                            );
-                            debug!(
-                                target: LOG_TARGET,
-                                "Submitted block #{} to Minotari node in {:.0?} (SubmitBlock)",
-                                height,
-                                start.elapsed()
-                            );
-                        } else {
-                            // self-select related, do not change.
-                            json_resp = json_rpc::default_block_accept_response(request["id"].as_i64());
-                            trace!(
-                                target: LOG_TARGET,
-                                "pool merged mining proxy_submit_to_origin({}) json_resp: {}",
-                                self.config.submit_to_origin,
-                                json_resp
-                            );
-                        }
-                        self.block_templates.remove(&hash).await;
-                    },
-                    Err(err) => {
-                        debug!(
+            match self.submit_merge_mined_block(monero_block).await? {
+                MergeMinedSubmission::UnknownTemplate => continue,
+                MergeMinedSubmission::Accepted { block_hash } => {
+                    if self.config.submit_to_origin {
+                        json_resp = json_rpc::success_response(
+                            request["id"].as_i64(),
+                            json!({ "status": "OK", "untrusted": !self.initial_sync_achieved.load(Ordering::SeqCst) }),
+                        );
+                        json_resp = append_aux_chain_data(
+                            json_resp,
+                            json!({"id": TARI_CHAIN_ID, "block_hash": block_hash.to_hex()}),
+                        );
+                    } else {
+                        // self-select related, do not change.
+                        json_resp = json_rpc::default_block_accept_response(request["id"].as_i64());
+                        trace!(
                             target: LOG_TARGET,
-                            "Problem submitting block #{} to Tari node, responded in  {:.0?} (SubmitBlock): {}",
-                            height,
-                            start.elapsed(),
-                            err
+                            "pool merged mining proxy_submit_to_origin({}) json_resp: {}",
+                            self.config.submit_to_origin,
+                            json_resp
                         );
-
-                        if !self.config.submit_to_origin {
-                            // When "submit to origin" is turned off the block is never submitted to monerod, and so we
-                            // need to construct an error message here.
-                            json_resp = json_rpc::error_response(
-                                request["id"].as_i64(),
-                                CoreRpcErrorCode::BlockNotAccepted.into(),
-                                "Block not accepted",
-                                None,
-                            );
-                        }
-                    },
-                }
-            };
+                    }
+                },
+                MergeMinedSubmission::Rejected => {
+                    if !self.config.submit_to_origin {
+                        // When "submit to origin" is turned off the block is never submitted to monerod, and so we
+                        // need to construct an error message here.
+                        json_resp = json_rpc::error_response(
+                            request["id"].as_i64(),
+                            CoreRpcErrorCode::BlockNotAccepted.into(),
+                            "Block not accepted",
+                            None,
+                        );
+                    }
+                },
+                MergeMinedSubmission::BelowTarget => {},
+            }
             self.block_templates.remove_outdated().await;
         }
 
@@ -393,6 +428,54 @@ impl InnerService {
             ));
         }
 
+        let monero_mining_data = MoneroMiningData::from_block_template_result(&monerod_resp["result"])?;
+        let final_block_template_data = self.new_block_template(monero_mining_data).await?;
+
+        monerod_resp["result"]["blocktemplate_blob"] = final_block_template_data.blocktemplate_blob.into();
+        monerod_resp["result"]["blockhashing_blob"] = final_block_template_data.blockhashing_blob.into();
+        monerod_resp["result"]["difficulty"] = final_block_template_data.target_difficulty.as_u64().into();
+
+        let tari_height = final_block_template_data
+            .template
+            .tari_block
+            .header
+            .as_ref()
+            .map(|h| h.height)
+            .unwrap_or(0);
+        let block_reward = final_block_template_data.template.tari_miner_data.reward;
+        let total_fees = final_block_template_data.template.tari_miner_data.total_fees;
+        let mining_hash = final_block_template_data.merge_mining_hash;
+        let monerod_resp = add_aux_data(
+            monerod_resp,
+            json!({ "base_difficulty": final_block_template_data.template.monero_difficulty }),
+        );
+        let monerod_resp = append_aux_chain_data(
+            monerod_resp,
+            json!({
+                "id": TARI_CHAIN_ID,
+                "difficulty": final_block_template_data.template.tari_difficulty,
+                "height": tari_height,
+                // The merge mining hash, before the final block hash can be calculated
+                "mining_hash": mining_hash.to_hex(),
+                "miner_reward": block_reward + total_fees,
+            }),
+        );
+
+        self.block_templates
+            .save(mining_hash, final_block_template_data.template)
+            .await;
+        self.stats.record_new_template();
+
+        debug!(target: LOG_TARGET, "Returning template result: {}", monerod_resp);
+        Ok(proxy::into_response(parts, &monerod_resp))
+    }
+
+    /// Builds a merge mining block template from the mining data received from monerod. If configured to, this waits
+    /// for the base node to achieve initial sync first.
+    async fn new_block_template(
+        &self,
+        monero_mining_data: MoneroMiningData,
+    ) -> Result<FinalBlockTemplateData, MmProxyError> {
         let mut grpc_client = self.base_node_client.clone();
         let mut grpc_wallet_client = self.wallet_client.clone();
 
@@ -430,57 +513,128 @@ impl InnerService {
 
         let new_block_protocol =
             BlockTemplateProtocol::new(&mut grpc_client, &mut grpc_wallet_client, self.config.clone());
+        let template_start = Instant::now();
+        let final_block_template_data = new_block_protocol.get_next_block_template(monero_mining_data).await?;
+        self.stats.record_base_node_latency(template_start.elapsed());
+        Ok(final_block_template_data)
+    }
+
+    /// Submits the Minotari block for the merge mining tag found in the Monero block to the base node, if the Monero
+    /// block achieves the Minotari target difficulty
+    async fn submit_merge_mined_block(
+        &self,
+        monero_block: monero_rx::MoneroBlock,
+    ) -> Result<MergeMinedSubmission, MmProxyError> {
+        let hash = MoneroAdapter
+            .extract_merge_mining_hash(&monero_block)?
+            .ok_or_else(|| MmProxyError::MissingDataError("Could not find Minotari header in coinbase".to_string()))?;
+
+        debug!(
+            target: LOG_TARGET,
+            "Minotari Hash found in Monero block: {}",
+            hex::encode(hash)
+        );
 
-        let seed_hash = FixedByteArray::from_hex(&monerod_resp["result"]["seed_hash"].to_string().replace('\"', ""))
-            .map_err(|err| MmProxyError::InvalidMonerodResponse(format!("seed hash hex is invalid: {}", err)))?;
-        let blocktemplate_blob = monerod_resp["result"]["blocktemplate_blob"]
-            .to_string()
-            .replace('\"', "");
-        let difficulty = monerod_resp["result"]["difficulty"].as_u64().unwrap_or_default();
-        let monero_mining_data = MoneroMiningData {
-            seed_hash,
-            blocktemplate_blob,
-            difficulty,
+        let mut block_data = match self.block_templates.get(&hash).await {
+            Some(d) => d,
+            None => {
+                info!(
+                    target: LOG_TARGET,
+                    "Block `{}` submitted but no matching block template was found, possible duplicate submission",
+                    hex::encode(hash)
+                );
+                return Ok(MergeMinedSubmission::UnknownTemplate);
+            },
         };
+        self.stats.record_share_submitted(&self.rig_id);
 
-        let final_block_template_data = new_block_protocol.get_next_block_template(monero_mining_data).await?;
+        let monero_data = monero_rx::construct_monero_data(monero_block, block_data.monero_seed.clone())?;
 
-        monerod_resp["result"]["blocktemplate_blob"] = final_block_template_data.blocktemplate_blob.into();
-        monerod_resp["result"]["blockhashing_blob"] = final_block_template_data.blockhashing_blob.into();
-        monerod_resp["result"]["difficulty"] = final_block_template_data.target_difficulty.as_u64().into();
+        debug!(target: LOG_TARGET, "Monero PoW Data: {:?}", monero_data);
 
-        let tari_height = final_block_template_data
-            .template
-            .tari_block
-            .header
-            .as_ref()
-            .map(|h| h.height)
-            .unwrap_or(0);
-        let block_reward = final_block_template_data.template.tari_miner_data.reward;
-        let total_fees = final_block_template_data.template.tari_miner_data.total_fees;
-        let mining_hash = final_block_template_data.merge_mining_hash;
-        let monerod_resp = add_aux_data(
-            monerod_resp,
-            json!({ "base_difficulty": final_block_template_data.template.monero_difficulty }),
-        );
-        let monerod_resp = append_aux_chain_data(
-            monerod_resp,
-            json!({
-                "id": TARI_CHAIN_ID,
-                "difficulty": final_block_template_data.template.tari_difficulty,
-                "height": tari_height,
-                // The merge mining hash, before the final block hash can be calculated
-                "mining_hash": mining_hash.to_hex(),
-                "miner_reward": block_reward + total_fees,
-            }),
-        );
+        let header_mut = block_data.tari_block.header.as_mut().unwrap();
+        let height = header_mut.height;
+        BorshSerialize::serialize(&monero_data, &mut header_mut.pow.as_mut().unwrap().pow_data)
+            .map_err(|err| MmProxyError::ConversionError(err.to_string()))?;
+        let tari_header = header_mut.clone().try_into().map_err(MmProxyError::ConversionError)?;
+        let mut base_node_client = self.base_node_client.clone();
+        let start = Instant::now();
+        let achieved_target = if self.config.check_tari_difficulty_before_submit {
+            trace!(target: LOG_TARGET, "Starting calculate achieved Tari difficultly");
+            let diff = randomx_difficulty(&tari_header, &self.randomx_factory)?;
+            trace!(
+                target: LOG_TARGET,
+                "Finished calculate achieved Tari difficultly - achieved {} vs. target {}",
+                diff.as_u64(),
+                block_data.tari_difficulty
+            );
+            diff.as_u64()
+        } else {
+            block_data.tari_difficulty
+        };
 
-        self.block_templates
-            .save(mining_hash, final_block_template_data.template)
-            .await;
+        if achieved_target < block_data.tari_difficulty {
+            self.stats.record_share_rejected(&self.rig_id);
+            return Ok(MergeMinedSubmission::BelowTarget);
+        }
 
-        debug!(target: LOG_TARGET, "Returning template result: {}", monerod_resp);
-        Ok(proxy::into_response(parts, &monerod_resp))
+        let submit_start = Instant::now();
+        let submit_result = base_node_client.submit_block(block_data.tari_block).await;
+        self.stats.record_base_node_latency(submit_start.elapsed());
+        match submit_result {
+            Ok(resp) => {
+                self.stats.record_share_accepted(&self.rig_id);
+                debug!(
+                    target: LOG_TARGET,
+                    "Submitted block #{} to Minotari node in {:.0?} (SubmitBlock)",
+                    height,
+                    start.elapsed()
+                );
+                self.block_templates.remove(&hash).await;
+                Ok(MergeMinedSubmission::Accepted {
+                    block_hash: resp.into_inner().block_hash,
+                })
+            },
+            Err(err) => {
+                self.stats.record_share_rejected(&self.rig_id);
+                debug!(
+                    target: LOG_TARGET,
+                    "Problem submitting block #{} to Tari node, responded in  {:.0?} (SubmitBlock): {}",
+                    height,
+                    start.elapsed(),
+                    err
+                );
+                Ok(MergeMinedSubmission::Rejected)
+            },
+        }
+    }
+
+    /// Calls a monerod JSON-RPC method directly, returning the `result` of the response
+    async fn monerod_json_rpc(&self, method: &str, params: json::Value) -> Result<json::Value, MmProxyError> {
+        let server = self.monerod_pool.current_server().await?;
+        let monerod_uri = format!("{}/json_rpc", server).parse::<Url>()?;
+        let mut builder = self
+            .http_client
+            .post(monerod_uri)
+            .json(&json!({ "jsonrpc": "2.0", "id": "0", "method": method, "params": params }));
+        if self.config.monerod_use_auth {
+            builder = builder.basic_auth(&self.config.monerod_username, Some(&self.config.monerod_password));
+        }
+
+        let monerod_start = Instant::now();
+        let resp = builder.send().await.map_err(MmProxyError::MonerodRequestFailed)?;
+        self.stats.record_monerod_latency(monerod_start.elapsed());
+        let mut json = resp
+            .json::<json::Value>()
+            .await
+            .map_err(MmProxyError::MonerodRequestFailed)?;
+        if !json["error"].is_null() {
+            return Err(MmProxyError::InvalidMonerodResponse(format!(
+                "`{}` failed: {}",
+                method, json["error"]
+            )));
+        }
+        Ok(json["result"].take())
     }
 
     async fn handle_get_block_header_by_hash(
@@ -592,62 +746,9 @@ impl InnerService {
     }
 
     async fn get_fully_qualified_monerod_url(&self, uri: &Uri) -> Result<Url, MmProxyError> {
-        {
-            let lock = self
-                .current_monerod_server
-                .read()
-                .expect("Read lock should not fail")
-                .clone();
-            if let Some(server) = lock {
-                let uri = format!("{}{}", server, uri.path()).parse::<Url>()?;
-                return Ok(uri);
-            }
-        }
-
-        let last_used_url = {
-            let lock = self
-                .last_assigned_monerod_server
-                .read()
-                .expect("Read lock should not fail")
-                .clone();
-            match lock {
-                Some(url) => url,
-                None => "".to_string(),
-            }
-        };
-
-        // Query the list twice before giving up, starting after the last used entry
-        let pos = if let Some(index) = self.config.monerod_url.iter().position(|x| x == &last_used_url) {
-            index
-        } else {
-            0
-        };
-        let (left, right) = self.config.monerod_url.split_at(pos);
-        let left = left.to_vec();
-        let right = right.to_vec();
-        let iter = right.iter().chain(left.iter()).chain(right.iter()).chain(left.iter());
-
-        for next_url in iter {
-            let uri = format!("{}{}", next_url, uri.path()).parse::<Url>()?;
-            match reqwest::get(uri.clone()).await {
-                Ok(_) => {
-                    let mut lock = self.current_monerod_server.write().expect("Write lock should not fail");
-                    *lock = Some(next_url.to_string());
-                    let mut lock = self
-                        .last_assigned_monerod_server
-                        .write()
-                        .expect("Write lock should not fail");
-                    *lock = Some(next_url.to_string());
-                    info!(target: LOG_TARGET, "Monerod server available: {:?}", uri.clone());
-                    return Ok(uri);
-                },
-                Err(_) => {
-                    warn!(target: LOG_TARGET, "Monerod server unavailable: {:?}", uri);
-                },
-            }
-        }
-
-        Err(MmProxyError::ServersUnavailable)
+        let server = self.monerod_pool.current_server().await?;
+        let uri = format!("{}{}", server, uri.path()).parse::<Url>()?;
+        Ok(uri)
     }
 
     /// Proxy a request received by this server to Monerod
@@ -735,12 +836,14 @@ impl InnerService {
 
             convert_json_to_hyper_json_response(accept_response, StatusCode::OK, monerod_uri.clone()).await?
         } else {
+            let monerod_start = Instant::now();
             let resp = builder
                 // This is a cheap clone of the request body
                 .body(body)
                 .send()
                 .await
                 .map_err(MmProxyError::MonerodRequestFailed)?;
+            self.stats.record_monerod_latency(monerod_start.elapsed());
             convert_reqwest_response_to_hyper_json_response(resp).await?
         };
 
@@ -800,6 +903,21 @@ impl InnerService {
     async fn handle(self, method_name: &str, request: Request<Bytes>) -> Result<Response<Body>, MmProxyError> {
         let start = Instant::now();
 
+        // The stats endpoints are served by the proxy itself and never forwarded to monerod
+        if request.method() == Method::GET {
+            match request.uri().path() {
+                "/stats" => return proxy::json_response(StatusCode::OK, &self.stats.to_json()),
+                "/metrics" => {
+                    return Response::builder()
+                        .header(header::CONTENT_TYPE, "text/plain; version=0.0.4")
+                        .status(StatusCode::OK)
+                        .body(self.stats.to_prometheus().into())
+                        .map_err(Into::into);
+                },
+                _ => {},
+            }
+        }
+
         debug!(
             target: LOG_TARGET,
             "request: {} ({})",
@@ -843,9 +961,8 @@ impl InnerService {
                 Ok(response)
             },
             Err(e) => {
-                // Monero Server encountered a problem processing the request, reset the current monerod server
-                let mut lock = self.current_monerod_server.write().expect("Write lock should not fail");
-                *lock = None;
+                // Monero Server encountered a problem processing the request, fail over to the next best server
+                self.monerod_pool.report_failure();
                 Err(e)
             },
         }
@@ -23,13 +23,16 @@
 use std::convert::Infallible;
 
 use futures::future;
-use hyper::{service::make_service_fn, Server};
+use hyper::{server::conn::AddrStream, service::make_service_fn, Server};
 use log::*;
 use minotari_node_grpc_client::BaseNodeGrpcClient;
 use minotari_wallet_grpc_client::WalletGrpcClient;
 use tari_common::{
-    configuration::bootstrap::{grpc_default_port, ApplicationType},
-    load_configuration,
+    configuration::{
+        bootstrap::{grpc_default_port, ApplicationType},
+        explain::EffectiveConfig,
+    },
+    load_configuration_with_origins,
     DefaultConfigLoader,
 };
 use tari_comms::utils::multiaddr::multiaddr_to_socketaddr;
@@ -41,16 +44,24 @@ use crate::{
     config::MergeMiningProxyConfig,
     error::MmProxyError,
     proxy::MergeMiningProxyService,
+    stratum::StratumServer,
     Cli,
 };
 const LOG_TARGET: &str = "minotari_mm_proxy::proxy";
 
 pub async fn start_merge_miner(cli: Cli) -> Result<(), anyhow::Error> {
     let config_path = cli.common.config_path();
-    let cfg = load_configuration(&config_path, true, &cli)?;
+    let (cfg, config_origins) = load_configuration_with_origins(&config_path, true, &cli)?;
     let mut config = MergeMiningProxyConfig::load_from(&cfg)?;
     setup_grpc_config(&mut config);
 
+    if cli.common.print_config {
+        let mut effective = EffectiveConfig::new(&cfg, &config_origins);
+        effective.add_section(&config)?;
+        print!("{}", effective.render(cli.common.explain));
+        return Ok(());
+    }
+
     info!(target: LOG_TARGET, "Configuration: {:?}", config);
     let client = reqwest::Client::builder()
         .connect_timeout(Duration::from_secs(5))
@@ -79,6 +90,11 @@ pub async fn start_merge_miner(cli: Cli) -> Result<(), anyhow::Error> {
     let wallet_client =
         WalletGrpcClient::connect_with_auth(&wallet_addr, &config.console_wallet_grpc_authentication).await?;
     let listen_addr = multiaddr_to_socketaddr(&config.listener_address)?;
+    let stratum_listen_addr = config
+        .stratum_listener_address
+        .as_ref()
+        .map(multiaddr_to_socketaddr)
+        .transpose()?;
     let randomx_factory = RandomXFactory::new(config.max_randomx_vms);
     let randomx_service = MergeMiningProxyService::new(
         config,
@@ -88,7 +104,19 @@ pub async fn start_merge_miner(cli: Cli) -> Result<(), anyhow::Error> {
         BlockTemplateRepository::new(),
         randomx_factory,
     );
-    let service = make_service_fn(|_conn| future::ready(Result::<_, Infallible>::Ok(randomx_service.clone())));
+    if let Some(stratum_listen_addr) = stratum_listen_addr {
+        let stratum_server = StratumServer::new(randomx_service.clone())?;
+        tokio::spawn(async move {
+            if let Err(err) = stratum_server.run(stratum_listen_addr).await {
+                error!(target: LOG_TARGET, "Stratum server stopped: {}", err);
+            }
+        });
+    }
+    let service = make_service_fn(|conn: &AddrStream| {
+        future::ready(Result::<_, Infallible>::Ok(
+            randomx_service.for_connection(conn.remote_addr()),
+        ))
+    });
 
     match Server::try_bind(&listen_addr) {
         Ok(builder) => {
@@ -0,0 +1,272 @@
+//  Copyright 2023, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Share accounting and latency statistics for the merge mining proxy, served as JSON on `/stats` and in the
+//! Prometheus text exposition format on `/metrics`.
+
+use std::{
+    collections::HashMap,
+    convert::TryFrom,
+    fmt::Write,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use serde_json as json;
+use serde_json::json;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RigStats {
+    pub submitted: u64,
+    pub accepted: u64,
+    pub rejected: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LatencyStats {
+    pub count: u64,
+    pub total: Duration,
+    pub last: Option<Duration>,
+}
+
+impl LatencyStats {
+    fn record(&mut self, latency: Duration) {
+        self.count += 1;
+        self.total += latency;
+        self.last = Some(latency);
+    }
+
+    fn average(&self) -> Option<Duration> {
+        if self.count == 0 {
+            return None;
+        }
+        Some(self.total / u32::try_from(self.count).unwrap_or(u32::MAX))
+    }
+
+    fn to_json(&self) -> json::Value {
+        json!({
+            "count": self.count,
+            "last_ms": self.last.map(|d| u64::try_from(d.as_millis()).unwrap_or(u64::MAX)),
+            "average_ms": self.average().map(|d| u64::try_from(d.as_millis()).unwrap_or(u64::MAX)),
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+struct StatsInner {
+    rigs: HashMap<String, RigStats>,
+    last_template: Option<Instant>,
+    base_node_latency: LatencyStats,
+    monerod_latency: LatencyStats,
+}
+
+/// Shared statistics collected by all connections to the proxy
+#[derive(Debug, Clone, Default)]
+pub struct ProxyStats {
+    inner: Arc<Mutex<StatsInner>>,
+}
+
+impl ProxyStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record_share_submitted(&self, rig: &str) {
+        self.with_rig(rig, |stats| stats.submitted += 1);
+    }
+
+    pub fn record_share_accepted(&self, rig: &str) {
+        self.with_rig(rig, |stats| stats.accepted += 1);
+    }
+
+    pub fn record_share_rejected(&self, rig: &str) {
+        self.with_rig(rig, |stats| stats.rejected += 1);
+    }
+
+    pub fn record_new_template(&self) {
+        self.lock().last_template = Some(Instant::now());
+    }
+
+    pub fn record_base_node_latency(&self, latency: Duration) {
+        self.lock().base_node_latency.record(latency);
+    }
+
+    pub fn record_monerod_latency(&self, latency: Duration) {
+        self.lock().monerod_latency.record(latency);
+    }
+
+    pub fn rig(&self, rig: &str) -> Option<RigStats> {
+        self.lock().rigs.get(rig).cloned()
+    }
+
+    pub fn to_json(&self) -> json::Value {
+        let inner = self.lock();
+        let rigs = inner
+            .rigs
+            .iter()
+            .map(|(rig, stats)| {
+                (
+                    rig.clone(),
+                    json!({
+                        "submitted": stats.submitted,
+                        "accepted": stats.accepted,
+                        "rejected": stats.rejected,
+                    }),
+                )
+            })
+            .collect::<json::Map<_, _>>();
+        json!({
+            "rigs": rigs,
+            "template_age_secs": inner.last_template.map(|t| t.elapsed().as_secs()),
+            "base_node_latency": inner.base_node_latency.to_json(),
+            "monerod_latency": inner.monerod_latency.to_json(),
+        })
+    }
+
+    /// Render the statistics in the Prometheus text exposition format
+    pub fn to_prometheus(&self) -> String {
+        let inner = self.lock();
+        let mut rigs = inner.rigs.iter().collect::<Vec<_>>();
+        rigs.sort_by(|a, b| a.0.cmp(b.0));
+
+        let mut out = String::new();
+        write_rig_counter(
+            &mut out,
+            "mmproxy_shares_submitted_total",
+            "Shares submitted to the proxy",
+            &rigs,
+            |s| s.submitted,
+        );
+        write_rig_counter(
+            &mut out,
+            "mmproxy_shares_accepted_total",
+            "Shares accepted by the base node",
+            &rigs,
+            |s| s.accepted,
+        );
+        write_rig_counter(
+            &mut out,
+            "mmproxy_shares_rejected_total",
+            "Shares rejected by the base node",
+            &rigs,
+            |s| s.rejected,
+        );
+
+        if let Some(last_template) = inner.last_template {
+            let _ = writeln!(
+                out,
+                "# HELP mmproxy_template_age_seconds Seconds since the last block template was issued"
+            );
+            let _ = writeln!(out, "# TYPE mmproxy_template_age_seconds gauge");
+            let _ = writeln!(
+                out,
+                "mmproxy_template_age_seconds {}",
+                last_template.elapsed().as_secs_f64()
+            );
+        }
+
+        for (name, help, latency) in [
+            (
+                "mmproxy_base_node_latency_seconds",
+                "Base node round-trip latency",
+                &inner.base_node_latency,
+            ),
+            (
+                "mmproxy_monerod_latency_seconds",
+                "Monerod round-trip latency",
+                &inner.monerod_latency,
+            ),
+        ] {
+            let _ = writeln!(out, "# HELP {} {}", name, help);
+            let _ = writeln!(out, "# TYPE {} summary", name);
+            let _ = writeln!(out, "{}_sum {}", name, latency.total.as_secs_f64());
+            let _ = writeln!(out, "{}_count {}", name, latency.count);
+        }
+        out
+    }
+
+    fn with_rig<F: FnOnce(&mut RigStats)>(&self, rig: &str, f: F) {
+        let mut inner = self.lock();
+        f(inner.rigs.entry(rig.to_string()).or_default());
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, StatsInner> {
+        self.inner.lock().expect("Stats lock should not be poisoned")
+    }
+}
+
+fn write_rig_counter<F: Fn(&RigStats) -> u64>(
+    out: &mut String,
+    name: &str,
+    help: &str,
+    rigs: &[(&String, &RigStats)],
+    value: F,
+) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} counter", name);
+    for (rig, stats) in rigs {
+        let _ = writeln!(out, "{}{{rig=\"{}\"}} {}", name, escape_label(rig), value(stats));
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_accounts_shares_per_rig() {
+        let stats = ProxyStats::new();
+        stats.record_share_submitted("10.0.0.1");
+        stats.record_share_submitted("10.0.0.1");
+        stats.record_share_accepted("10.0.0.1");
+        stats.record_share_rejected("10.0.0.1");
+        stats.record_share_submitted("10.0.0.2");
+        assert_eq!(
+            stats.rig("10.0.0.1"),
+            Some(RigStats {
+                submitted: 2,
+                accepted: 1,
+                rejected: 1
+            })
+        );
+        assert_eq!(stats.rig("10.0.0.2").unwrap().submitted, 1);
+        assert!(stats.rig("10.0.0.3").is_none());
+        assert_eq!(stats.to_json()["rigs"]["10.0.0.1"]["accepted"], 1);
+    }
+
+    #[test]
+    fn it_renders_prometheus_metrics() {
+        let stats = ProxyStats::new();
+        stats.record_share_submitted("rig\"1");
+        stats.record_monerod_latency(Duration::from_millis(500));
+        stats.record_monerod_latency(Duration::from_millis(1500));
+        let metrics = stats.to_prometheus();
+        assert!(metrics.contains("mmproxy_shares_submitted_total{rig=\"rig\\\"1\"} 1\n"));
+        assert!(metrics.contains("mmproxy_monerod_latency_seconds_sum 2\n"));
+        assert!(metrics.contains("mmproxy_monerod_latency_seconds_count 2\n"));
+        assert!(!metrics.contains("mmproxy_template_age_seconds"));
+    }
+}
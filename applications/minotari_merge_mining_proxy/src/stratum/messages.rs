@@ -0,0 +1,165 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::convert::TryFrom;
+
+use serde::{Deserialize, Serialize};
+use serde_json as json;
+use serde_json::json;
+
+/// The RandomX algorithm name used by XMRig
+pub const RANDOMX_ALGO: &str = "rx/0";
+
+pub const ERROR_INVALID_REQUEST: i32 = -32600;
+pub const ERROR_METHOD_NOT_FOUND: i32 = -32601;
+pub const ERROR_INVALID_PARAMS: i32 = -32602;
+pub const ERROR_UNAUTHENTICATED: i32 = -1;
+pub const ERROR_NO_JOB: i32 = -2;
+pub const ERROR_INVALID_SHARE: i32 = -3;
+pub const ERROR_INTERNAL: i32 = -4;
+
+/// A JSON-RPC request received from a stratum client
+#[derive(Debug, Deserialize)]
+pub struct StratumRequest {
+    #[serde(default)]
+    pub id: json::Value,
+    pub method: String,
+    #[serde(default)]
+    pub params: json::Value,
+}
+
+/// The `login` request parameters. The password is not used.
+#[derive(Debug, Deserialize)]
+pub struct LoginParams {
+    pub login: String,
+    #[serde(default)]
+    pub agent: String,
+}
+
+/// The `submit` request parameters
+#[derive(Debug, Deserialize)]
+pub struct SubmitParams {
+    pub job_id: String,
+    /// The 4 byte nonce, hex encoded in little endian byte order
+    pub nonce: String,
+    /// The hex encoded RandomX hash achieved by the nonce
+    pub result: String,
+}
+
+/// A job sent to a stratum client, either in the `login` response or in a `job` notification
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct JobParams {
+    /// The session id of the client
+    pub id: String,
+    pub job_id: String,
+    /// The hex encoded Monero blockhashing blob
+    pub blob: String,
+    /// The hex encoded target, see [difficulty_to_target]
+    pub target: String,
+    /// The Monero height of the job
+    pub height: u64,
+    /// The hex encoded RandomX seed hash
+    pub seed_hash: String,
+    pub algo: &'static str,
+}
+
+pub fn success_response(id: json::Value, result: json::Value) -> json::Value {
+    json!({
+        "id": id,
+        "jsonrpc": "2.0",
+        "error": json::Value::Null,
+        "result": result,
+    })
+}
+
+pub fn error_response(id: json::Value, code: i32, message: &str) -> json::Value {
+    json!({
+        "id": id,
+        "jsonrpc": "2.0",
+        "error": { "code": code, "message": message },
+        "result": json::Value::Null,
+    })
+}
+
+/// A notification that pushes a new job to the client
+pub fn job_notification(job: &JobParams) -> json::Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "job",
+        "params": job,
+    })
+}
+
+/// Converts a difficulty into the 64-bit stratum target, hex encoded in little endian byte order
+pub fn difficulty_to_target(difficulty: u64) -> String {
+    let target = u64::MAX / difficulty.max(1);
+    hex::encode(target.to_le_bytes())
+}
+
+/// Parses a hex encoded, little endian 4 byte nonce
+pub fn parse_nonce(nonce: &str) -> Option<u32> {
+    let bytes = hex::decode(nonce).ok()?;
+    let bytes = <[u8; 4]>::try_from(bytes.as_slice()).ok()?;
+    Some(u32::from_le_bytes(bytes))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_parses_xmrig_requests() {
+        let login = r#"{"id":1,"jsonrpc":"2.0","method":"login","params":{"login":"rig1","pass":"x","agent":"XMRig/6.20.0","algo":["rx/0"]}}"#;
+        let request = json::from_str::<StratumRequest>(login).unwrap();
+        assert_eq!(request.method, "login");
+        assert_eq!(request.id, json!(1));
+        let params = json::from_value::<LoginParams>(request.params).unwrap();
+        assert_eq!(params.login, "rig1");
+        assert_eq!(params.agent, "XMRig/6.20.0");
+
+        let submit = r#"{"id":2,"jsonrpc":"2.0","method":"submit","params":{"id":"0000000000000001","job_id":"1","nonce":"0a000000","result":"00ff","algo":"rx/0"}}"#;
+        let request = json::from_str::<StratumRequest>(submit).unwrap();
+        let params = json::from_value::<SubmitParams>(request.params).unwrap();
+        assert_eq!(params.job_id, "1");
+        assert_eq!(parse_nonce(&params.nonce), Some(10));
+
+        let keepalive = r#"{"id":3,"jsonrpc":"2.0","method":"keepalived","params":{"id":"0000000000000001"}}"#;
+        let request = json::from_str::<StratumRequest>(keepalive).unwrap();
+        assert_eq!(request.method, "keepalived");
+    }
+
+    #[test]
+    fn it_converts_difficulty_to_target() {
+        assert_eq!(difficulty_to_target(0), "ffffffffffffffff");
+        assert_eq!(difficulty_to_target(1), "ffffffffffffffff");
+        assert_eq!(difficulty_to_target(2), "ffffffffffffff7f");
+        assert_eq!(difficulty_to_target(u64::MAX), "0100000000000000");
+    }
+
+    #[test]
+    fn it_rejects_malformed_nonces() {
+        assert_eq!(parse_nonce("01000000"), Some(1));
+        assert_eq!(parse_nonce("010000"), None);
+        assert_eq!(parse_nonce("0100000000"), None);
+        assert_eq!(parse_nonce("zz000000"), None);
+    }
+}
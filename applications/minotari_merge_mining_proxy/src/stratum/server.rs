@@ -0,0 +1,466 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    collections::{HashSet, VecDeque},
+    net::SocketAddr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use serde::de::DeserializeOwned;
+use serde_json as json;
+use serde_json::json;
+use tari_core::proof_of_work::{
+    aux_pow::{AuxChainAdapter, MoneroAdapter},
+    monero_rx,
+    monero_rx::MoneroBlock,
+    Difficulty,
+};
+use tari_utilities::hex::Hex;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::{tcp::OwnedWriteHalf, TcpListener, TcpStream},
+    sync::{watch, Notify},
+    time,
+};
+use tracing::{debug, info, warn};
+
+use super::messages::{
+    self,
+    JobParams,
+    LoginParams,
+    StratumRequest,
+    SubmitParams,
+    ERROR_INTERNAL,
+    ERROR_INVALID_PARAMS,
+    ERROR_INVALID_REQUEST,
+    ERROR_INVALID_SHARE,
+    ERROR_METHOD_NOT_FOUND,
+    ERROR_NO_JOB,
+    ERROR_UNAUTHENTICATED,
+    RANDOMX_ALGO,
+};
+use crate::{
+    error::MmProxyError,
+    proxy::{MergeMinedSubmission, MergeMiningProxyService},
+};
+
+const LOG_TARGET: &str = "minotari_mm_proxy::stratum";
+/// The number of recent jobs of a session that shares are still accepted for
+const MAX_SESSION_JOBS: usize = 4;
+
+/// A stratum server that pushes merge mining jobs to connected miners
+pub struct StratumServer {
+    service: MergeMiningProxyService,
+    wallet_address: String,
+    tip_poll_interval: Duration,
+    max_job_age: Duration,
+}
+
+impl StratumServer {
+    pub fn new(service: MergeMiningProxyService) -> Result<Self, MmProxyError> {
+        let config = service.config();
+        if config.stratum_monero_wallet_address.is_empty() {
+            return Err(tari_common::ConfigurationError::new(
+                "merge_mining_proxy.stratum_monero_wallet_address",
+                None,
+                "A Monero wallet address is required to request block templates in stratum mode",
+            )
+            .into());
+        }
+        Ok(Self {
+            wallet_address: config.stratum_monero_wallet_address.clone(),
+            tip_poll_interval: Duration::from_secs(config.stratum_tip_poll_interval.max(1)),
+            max_job_age: Duration::from_secs(config.stratum_max_job_age),
+            service,
+        })
+    }
+
+    /// Accepts stratum connections on the address until an IO error occurs
+    pub async fn run(self, listen_addr: SocketAddr) -> Result<(), MmProxyError> {
+        let listener = TcpListener::bind(listen_addr).await?;
+        info!(target: LOG_TARGET, "Stratum server listening on {}", listen_addr);
+        println!("Stratum server listening on {}...", listen_addr);
+
+        let (template_sender, template_receiver) = watch::channel(None);
+        let refresh = Arc::new(Notify::new());
+        let producer = TemplateProducer {
+            service: self.service.clone(),
+            wallet_address: self.wallet_address,
+            tip_poll_interval: self.tip_poll_interval,
+            max_job_age: self.max_job_age,
+            template_sender,
+            refresh: refresh.clone(),
+        };
+        tokio::spawn(producer.run());
+
+        let mut session_number = 0u64;
+        loop {
+            let (stream, remote_addr) = listener.accept().await?;
+            session_number += 1;
+            debug!(
+                target: LOG_TARGET,
+                "Stratum connection from {} (session {})", remote_addr, session_number
+            );
+            let session = StratumSession::new(
+                session_number,
+                self.service.for_connection(remote_addr),
+                template_receiver.clone(),
+                refresh.clone(),
+            );
+            tokio::spawn(async move {
+                if let Err(err) = session.run(stream).await {
+                    debug!(
+                        target: LOG_TARGET,
+                        "Stratum session {} with {} ended: {}", session_number, remote_addr, err
+                    );
+                }
+            });
+        }
+    }
+}
+
+/// A merge mining block template shared by all sessions
+struct StratumTemplate {
+    block: MoneroBlock,
+    seed_hash: String,
+    height: u64,
+    target_difficulty: u64,
+    monero_difficulty: u64,
+}
+
+/// Fetches a new template whenever the Monero or Minotari tip changes, or the current template is too old
+struct TemplateProducer {
+    service: MergeMiningProxyService,
+    wallet_address: String,
+    tip_poll_interval: Duration,
+    max_job_age: Duration,
+    template_sender: watch::Sender<Option<Arc<StratumTemplate>>>,
+    refresh: Arc<Notify>,
+}
+
+impl TemplateProducer {
+    async fn run(self) {
+        let mut last_tips = None;
+        let mut last_template_at: Option<Instant> = None;
+        loop {
+            let tips = match self.fetch_tips().await {
+                Ok(tips) => Some(tips),
+                Err(err) => {
+                    warn!(target: LOG_TARGET, "Failed to fetch the chain tips: {}", err);
+                    None
+                },
+            };
+            let is_template_old = last_template_at.map_or(true, |at| at.elapsed() >= self.max_job_age);
+            if tips.is_some() && (tips != last_tips || is_template_old) {
+                match self.new_template().await {
+                    Ok(template) => {
+                        debug!(
+                            target: LOG_TARGET,
+                            "New stratum template at Monero height {} with target difficulty {}",
+                            template.height,
+                            template.target_difficulty
+                        );
+                        last_tips = tips;
+                        last_template_at = Some(Instant::now());
+                        if self.template_sender.send(Some(Arc::new(template))).is_err() {
+                            // The stratum server is no longer running
+                            return;
+                        }
+                    },
+                    Err(err) => warn!(target: LOG_TARGET, "Failed to create a stratum template: {}", err),
+                }
+            }
+
+            tokio::select! {
+                _ = time::sleep(self.tip_poll_interval) => {},
+                _ = self.refresh.notified() => last_tips = None,
+            }
+        }
+    }
+
+    async fn fetch_tips(&self) -> Result<(u64, u64), MmProxyError> {
+        let monero_height = self.service.monerod_height().await?;
+        let tari_height = self.service.tari_tip_height().await?;
+        Ok((monero_height, tari_height))
+    }
+
+    async fn new_template(&self) -> Result<StratumTemplate, MmProxyError> {
+        let (height, template) = self.service.fetch_merge_mining_template(&self.wallet_address).await?;
+        Ok(StratumTemplate {
+            block: MoneroAdapter.deserialize_block(&template.blocktemplate_blob)?,
+            seed_hash: template.template.monero_seed.to_hex(),
+            height,
+            target_difficulty: template.target_difficulty.as_u64(),
+            monero_difficulty: template.template.monero_difficulty,
+        })
+    }
+}
+
+/// A job given to a session. Each session mines on its own variant of the template.
+struct SessionJob {
+    job_id: String,
+    template: Arc<StratumTemplate>,
+    block: MoneroBlock,
+    submitted_nonces: HashSet<u32>,
+}
+
+/// A connection with a single stratum client
+struct StratumSession {
+    id: String,
+    session_number: u64,
+    service: MergeMiningProxyService,
+    templates: watch::Receiver<Option<Arc<StratumTemplate>>>,
+    refresh: Arc<Notify>,
+    jobs: VecDeque<SessionJob>,
+    next_job_id: u64,
+    is_logged_in: bool,
+}
+
+impl StratumSession {
+    fn new(
+        session_number: u64,
+        service: MergeMiningProxyService,
+        templates: watch::Receiver<Option<Arc<StratumTemplate>>>,
+        refresh: Arc<Notify>,
+    ) -> Self {
+        Self {
+            id: format!("{:016x}", session_number),
+            session_number,
+            service,
+            templates,
+            refresh,
+            jobs: VecDeque::with_capacity(MAX_SESSION_JOBS + 1),
+            next_job_id: 0,
+            is_logged_in: false,
+        }
+    }
+
+    async fn run(mut self, stream: TcpStream) -> Result<(), MmProxyError> {
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+        loop {
+            tokio::select! {
+                line = lines.next_line() => {
+                    let line = match line? {
+                        Some(line) => line,
+                        None => return Ok(()),
+                    };
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    let response = self.handle_request(&line).await;
+                    write_message(&mut writer, &response).await?;
+                },
+                changed = self.templates.changed() => {
+                    if changed.is_err() {
+                        return Ok(());
+                    }
+                    if self.is_logged_in {
+                        if let Some(job) = self.new_job()? {
+                            write_message(&mut writer, &messages::job_notification(&job)).await?;
+                        }
+                    }
+                },
+            }
+        }
+    }
+
+    async fn handle_request(&mut self, line: &str) -> json::Value {
+        let request = match json::from_str::<StratumRequest>(line) {
+            Ok(request) => request,
+            Err(err) => {
+                return messages::error_response(
+                    json::Value::Null,
+                    ERROR_INVALID_REQUEST,
+                    &format!("Invalid request: {}", err),
+                )
+            },
+        };
+        match request.method.as_str() {
+            "login" => self.handle_login(request),
+            "getjob" => self.handle_get_job(request),
+            "submit" => self.handle_submit(request).await,
+            "keepalived" => messages::success_response(request.id, json!({ "status": "KEEPALIVED" })),
+            _ => messages::error_response(request.id, ERROR_METHOD_NOT_FOUND, "Method not found"),
+        }
+    }
+
+    fn handle_login(&mut self, request: StratumRequest) -> json::Value {
+        let params = match parse_params::<LoginParams>(&request.id, request.params) {
+            Ok(params) => params,
+            Err(response) => return response,
+        };
+        info!(
+            target: LOG_TARGET,
+            "Stratum miner `{}` ({}) logged in as session {}", params.login, params.agent, self.id
+        );
+        self.is_logged_in = true;
+        match self.new_job() {
+            Ok(Some(job)) => messages::success_response(
+                request.id,
+                json!({ "id": self.id, "job": job, "extensions": ["keepalive"], "status": "OK" }),
+            ),
+            Ok(None) => messages::error_response(
+                request.id,
+                ERROR_NO_JOB,
+                "No job available yet, waiting for a block template",
+            ),
+            Err(err) => messages::error_response(request.id, ERROR_INTERNAL, &err.to_string()),
+        }
+    }
+
+    fn handle_get_job(&mut self, request: StratumRequest) -> json::Value {
+        if !self.is_logged_in {
+            return messages::error_response(request.id, ERROR_UNAUTHENTICATED, "Unauthenticated");
+        }
+        match self.new_job() {
+            Ok(Some(job)) => messages::success_response(request.id, json!(job)),
+            Ok(None) => messages::error_response(
+                request.id,
+                ERROR_NO_JOB,
+                "No job available yet, waiting for a block template",
+            ),
+            Err(err) => messages::error_response(request.id, ERROR_INTERNAL, &err.to_string()),
+        }
+    }
+
+    async fn handle_submit(&mut self, request: StratumRequest) -> json::Value {
+        if !self.is_logged_in {
+            return messages::error_response(request.id, ERROR_UNAUTHENTICATED, "Unauthenticated");
+        }
+        let params = match parse_params::<SubmitParams>(&request.id, request.params) {
+            Ok(params) => params,
+            Err(response) => return response,
+        };
+        let nonce = match messages::parse_nonce(&params.nonce) {
+            Some(nonce) => nonce,
+            None => return messages::error_response(request.id, ERROR_INVALID_PARAMS, "Invalid nonce"),
+        };
+        let achieved_difficulty = match hex::decode(&params.result)
+            .ok()
+            .filter(|hash| hash.len() == 32)
+            .and_then(|hash| Difficulty::little_endian_difficulty(&hash).ok())
+        {
+            Some(difficulty) => difficulty.as_u64(),
+            None => return messages::error_response(request.id, ERROR_INVALID_PARAMS, "Invalid result"),
+        };
+
+        let (block, target_difficulty, monero_difficulty) =
+            match self.jobs.iter_mut().find(|job| job.job_id == params.job_id) {
+                Some(job) => {
+                    if !job.submitted_nonces.insert(nonce) {
+                        return messages::error_response(request.id, ERROR_INVALID_SHARE, "Duplicate share");
+                    }
+                    let mut block = job.block.clone();
+                    block.header.nonce = nonce;
+                    (block, job.template.target_difficulty, job.template.monero_difficulty)
+                },
+                None => return messages::error_response(request.id, ERROR_INVALID_SHARE, "Block expired"),
+            };
+        if achieved_difficulty < target_difficulty {
+            return messages::error_response(request.id, ERROR_INVALID_SHARE, "Low difficulty share");
+        }
+
+        // The share is only submitted to monerod if it could be a Monero block, as monerod verifies the hash itself
+        if self.service.config().submit_to_origin && achieved_difficulty >= monero_difficulty {
+            match self.service.submit_block_to_monerod(&block).await {
+                Ok(()) => {
+                    info!(
+                        target: LOG_TARGET,
+                        "Monero block found by stratum session {}", self.id
+                    );
+                    self.refresh.notify_one();
+                },
+                Err(err) => warn!(target: LOG_TARGET, "Failed to submit block to monerod: {}", err),
+            }
+        }
+
+        match self.service.submit_merge_mined_block(block).await {
+            Ok(MergeMinedSubmission::Accepted { block_hash }) => {
+                info!(
+                    target: LOG_TARGET,
+                    "Minotari block {} found by stratum session {}",
+                    block_hash.to_hex(),
+                    self.id
+                );
+                self.refresh.notify_one();
+            },
+            Ok(submission) => debug!(
+                target: LOG_TARGET,
+                "Share from stratum session {} submitted to Minotari: {:?}", self.id, submission
+            ),
+            Err(err) => warn!(target: LOG_TARGET, "Failed to submit block to Minotari: {}", err),
+        }
+
+        messages::success_response(request.id, json!({ "status": "OK" }))
+    }
+
+    /// Creates a new job from the latest template, or returns None if no template is available yet
+    fn new_job(&mut self) -> Result<Option<JobParams>, MmProxyError> {
+        let template = self.templates.borrow_and_update().clone();
+        let template = match template {
+            Some(template) => template,
+            None => return Ok(None),
+        };
+        // Every session gets a different extra nonce so that sessions do not search the same nonce space
+        let mut block = template.block.clone();
+        monero_rx::append_extra_nonce(&mut block, &self.session_number.to_le_bytes())?;
+        let blob = MoneroAdapter.hashing_blob(&block)?;
+
+        self.next_job_id += 1;
+        let job_id = self.next_job_id.to_string();
+        let job = JobParams {
+            id: self.id.clone(),
+            job_id: job_id.clone(),
+            blob,
+            target: messages::difficulty_to_target(template.target_difficulty),
+            height: template.height,
+            seed_hash: template.seed_hash.clone(),
+            algo: RANDOMX_ALGO,
+        };
+        self.jobs.push_back(SessionJob {
+            job_id,
+            template,
+            block,
+            submitted_nonces: HashSet::new(),
+        });
+        if self.jobs.len() > MAX_SESSION_JOBS {
+            self.jobs.pop_front();
+        }
+        Ok(Some(job))
+    }
+}
+
+/// Parses the request parameters, returning the error response to send if they are invalid
+fn parse_params<T: DeserializeOwned>(id: &json::Value, params: json::Value) -> Result<T, json::Value> {
+    json::from_value(params)
+        .map_err(|err| messages::error_response(id.clone(), ERROR_INVALID_PARAMS, &format!("Invalid params: {}", err)))
+}
+
+async fn write_message(writer: &mut OwnedWriteHalf, message: &json::Value) -> Result<(), MmProxyError> {
+    let mut bytes = json::to_vec(message)?;
+    bytes.push(b'\n');
+    writer.write_all(&bytes).await?;
+    Ok(())
+}
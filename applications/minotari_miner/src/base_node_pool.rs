@@ -0,0 +1,124 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! A list of base node gRPC endpoints the miner can fail over between, or rotate through to source block templates
+//! from different nodes.
+
+use log::*;
+use minotari_app_grpc::tari_rpc::{base_node_client::BaseNodeClient, Empty};
+use tari_comms::{multiaddr::Multiaddr, utils::multiaddr::multiaddr_to_socketaddr};
+use tonic::transport::Channel;
+
+use crate::errors::MinerError;
+
+const LOG_TARGET: &str = "minotari::miner::base_node_pool";
+
+pub struct BaseNodePool {
+    addresses: Vec<Multiaddr>,
+    current: usize,
+    require_synced: bool,
+}
+
+impl BaseNodePool {
+    /// Create a pool from a non-empty list of addresses. If `require_synced` is set, a node is only considered healthy
+    /// once it reports that it has completed its initial sync.
+    pub fn new(addresses: Vec<Multiaddr>, require_synced: bool) -> Self {
+        assert!(!addresses.is_empty(), "BaseNodePool requires at least one address");
+        Self {
+            addresses,
+            current: 0,
+            require_synced,
+        }
+    }
+
+    pub fn num_nodes(&self) -> usize {
+        self.addresses.len()
+    }
+
+    pub fn current_address(&self) -> &Multiaddr {
+        &self.addresses[self.current]
+    }
+
+    /// Connect to the first healthy node, starting with the current one
+    pub async fn connect(&mut self) -> Result<BaseNodeClient<Channel>, MinerError> {
+        let mut last_error = None;
+        for _ in 0..self.addresses.len() {
+            match self.connect_current().await {
+                Ok(client) => return Ok(client),
+                Err(err) => {
+                    warn!(
+                        target: LOG_TARGET,
+                        "Base node at {} is not available: {}",
+                        self.current_address(),
+                        err
+                    );
+                    last_error = Some(err);
+                    self.advance();
+                },
+            }
+        }
+        Err(last_error.unwrap_or(MinerError::NodeNotReady))
+    }
+
+    /// Move past the current node and connect to the next healthy one
+    pub async fn failover(&mut self) -> Result<BaseNodeClient<Channel>, MinerError> {
+        self.advance();
+        self.connect().await
+    }
+
+    fn advance(&mut self) {
+        self.current = (self.current + 1) % self.addresses.len();
+    }
+
+    async fn connect_current(&self) -> Result<BaseNodeClient<Channel>, MinerError> {
+        let address = format!("http://{}", multiaddr_to_socketaddr(self.current_address())?);
+        info!(target: LOG_TARGET, "🔗 Connecting to base node at {}", address);
+        let mut client = BaseNodeClient::connect(address).await?;
+        let tip = client.get_tip_info(Empty {}).await?.into_inner();
+        if self.require_synced && !tip.initial_sync_achieved {
+            return Err(MinerError::NodeNotReady);
+        }
+        Ok(client)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn it_cycles_through_addresses() {
+        let addresses = ["/ip4/127.0.0.1/tcp/18142", "/ip4/127.0.0.1/tcp/18143"]
+            .iter()
+            .map(|a| Multiaddr::from_str(a).unwrap())
+            .collect::<Vec<_>>();
+        let mut pool = BaseNodePool::new(addresses.clone(), true);
+        assert_eq!(pool.num_nodes(), 2);
+        assert_eq!(pool.current_address(), &addresses[0]);
+        pool.advance();
+        assert_eq!(pool.current_address(), &addresses[1]);
+        pool.advance();
+        assert_eq!(pool.current_address(), &addresses[0]);
+    }
+}
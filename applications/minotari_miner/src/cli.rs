@@ -37,6 +37,9 @@ pub struct Cli {
     pub miner_min_diff: Option<u64>,
     #[clap(long, alias = "max-difficulty")]
     pub miner_max_diff: Option<u64>,
+    /// List the GPU devices that can be used for mining and exit
+    #[clap(long)]
+    pub list_gpu_devices: bool,
 }
 
 impl ConfigOverrideProvider for Cli {
@@ -27,12 +27,15 @@
 //! specific options:
 //! - base_node_grpc_address - is IPv4/IPv6 address including port
 //! number, by which Minotari Base Node can be found
+//! - base_node_grpc_fallback_addresses - additional base nodes to fail over to, or to rotate through
+//! when base_node_round_robin is set
 //! - wallet_grpc_address - is IPv4/IPv6 address including port number,
 //! where Minotari Wallet Node can be found
 //! - num_mining_threads - number of mining threads, defaults to number of cpu cores
 //! - mine_on_tip_only - will start mining only when node is reporting bootstrapped state
 //! - validate_tip_timeout_sec - will check tip with node every N seconds to validate that still
 //! mining on a tip
+//! - gpu_enabled - will also mine on OpenCL GPU devices, requires the `opencl` feature
 //! All miner options configured under `[miner]` section of
 //! Minotari's `config.toml`.
 
@@ -49,6 +52,10 @@ use tari_comms::multiaddr::Multiaddr;
 pub struct MinerConfig {
     /// GRPC address of base node
     pub base_node_grpc_address: Option<Multiaddr>,
+    /// GRPC addresses of additional base nodes to fail over to when the current base node is unavailable
+    pub base_node_grpc_fallback_addresses: Vec<Multiaddr>,
+    /// Fetch each new block template from the next available base node, instead of only switching on failure
+    pub base_node_round_robin: bool,
     /// GRPC address of console wallet
     pub wallet_grpc_address: Option<Multiaddr>,
     /// GRPC authentication for console wallet
@@ -77,6 +84,14 @@ pub struct MinerConfig {
     pub network: Network,
     /// Base node reconnect timeout after any GRPC or miner error
     pub wait_timeout_on_error: u64,
+    /// Mine on GPU devices alongside the CPU threads. Requires the miner to be built with the `opencl` feature
+    pub gpu_enabled: bool,
+    /// Indices of the GPU devices to mine on, as listed by `--list-gpu-devices`. All GPU devices are used if empty
+    pub gpu_devices: Vec<usize>,
+    /// Each kernel launch on a GPU device hashes 2^gpu_intensity nonces
+    pub gpu_intensity: u32,
+    /// Per-device intensity overrides, in the same order as `gpu_devices`
+    pub gpu_device_intensities: Vec<u32>,
 }
 
 /// The proof of work data structure that is included in the block header. For the Minotari miner only `Sha3x` is
@@ -97,6 +112,8 @@ impl Default for MinerConfig {
     fn default() -> Self {
         Self {
             base_node_grpc_address: None,
+            base_node_grpc_fallback_addresses: vec![],
+            base_node_round_robin: false,
             wallet_grpc_address: None,
             wallet_grpc_authentication: GrpcAuthentication::default(),
             num_mining_threads: num_cpus::get(),
@@ -109,6 +126,10 @@ impl Default for MinerConfig {
             coinbase_extra: "minotari_miner".to_string(),
             network: Default::default(),
             wait_timeout_on_error: 10,
+            gpu_enabled: false,
+            gpu_devices: vec![],
+            gpu_intensity: 22,
+            gpu_device_intensities: vec![],
         }
     }
 }
@@ -123,6 +144,15 @@ impl MinerConfig {
         NewBlockTemplateRequest { algo, max_weight: 0 }
     }
 
+    /// The primary base node address followed by the fallback addresses
+    pub fn base_node_grpc_addresses(&self) -> Vec<Multiaddr> {
+        self.base_node_grpc_address
+            .iter()
+            .chain(self.base_node_grpc_fallback_addresses.iter())
+            .cloned()
+            .collect()
+    }
+
     pub fn wait_timeout(&self) -> Duration {
         Duration::from_secs(self.wait_timeout_on_error)
     }
@@ -25,7 +25,7 @@ use std::convert::TryInto;
 use minotari_app_grpc::tari_rpc::BlockHeader as grpc_header;
 use tari_core::{
     blocks::BlockHeader,
-    proof_of_work::{sha3x_difficulty, DifficultyError},
+    proof_of_work::{DifficultyError, Sha3xHasher},
 };
 use tari_utilities::epoch_time::EpochTime;
 
@@ -33,10 +33,13 @@ use crate::errors::MinerError;
 
 pub type Difficulty = u64;
 
+/// The header is only changed through the setters below, which rebuild the cached hasher whenever a field other than
+/// the nonce changes
 #[derive(Clone)]
 pub struct BlockHeaderSha3 {
-    pub header: BlockHeader,
+    header: BlockHeader,
     pub hashes: u64,
+    hasher: Sha3xHasher,
 }
 
 impl BlockHeaderSha3 {
@@ -44,7 +47,12 @@ impl BlockHeaderSha3 {
     #[allow(clippy::cast_sign_loss)]
     pub fn new(header: grpc_header) -> Result<Self, MinerError> {
         let header: BlockHeader = header.try_into().map_err(MinerError::BlockHeader)?;
-        Ok(Self { header, hashes: 0 })
+        let hasher = Sha3xHasher::new(&header);
+        Ok(Self {
+            header,
+            hashes: 0,
+            hasher,
+        })
     }
 
     /// This function will update the timestamp of the header, but only if the new timestamp is greater than the current
@@ -54,9 +62,21 @@ impl BlockHeaderSha3 {
         // should only change the timestamp if we move it forward.
         if timestamp > self.header.timestamp.as_u64() {
             self.header.timestamp = EpochTime::from(timestamp);
+            self.hasher = Sha3xHasher::new(&self.header);
         }
     }
 
+    #[inline]
+    pub fn nonce(&self) -> u64 {
+        self.header.nonce
+    }
+
+    /// Sets the nonce of the header. The nonce is not part of the cached pre-image, so the hasher is not rebuilt.
+    #[inline]
+    pub fn set_nonce(&mut self, nonce: u64) {
+        self.header.nonce = nonce;
+    }
+
     pub fn random_nonce(&mut self) {
         use rand::{rngs::OsRng, RngCore};
         self.header.nonce = OsRng.next_u64();
@@ -70,7 +90,12 @@ impl BlockHeaderSha3 {
     #[inline]
     pub fn difficulty(&mut self) -> Result<Difficulty, DifficultyError> {
         self.hashes = self.hashes.saturating_add(1);
-        Ok(sha3x_difficulty(&self.header)?.as_u64())
+        Ok(self.hasher.difficulty(self.header.nonce)?.as_u64())
+    }
+
+    /// The bytes that follow the nonce in the Sha3x pre-image
+    pub fn pre_image_tail(&self) -> Vec<u8> {
+        self.hasher.pre_image_tail().to_vec()
     }
 
     #[allow(clippy::cast_possible_wrap)]
@@ -118,7 +143,7 @@ pub mod test {
                 hasher.difficulty().unwrap(),
                 core_sha3x_difficulty(&core_header).unwrap().as_u64(),
                 "with nonces = {}:{}",
-                hasher.header.nonce,
+                hasher.nonce(),
                 core_header.nonce
             );
             core_header.nonce += 1;
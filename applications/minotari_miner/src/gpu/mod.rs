@@ -0,0 +1,297 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! GPU mining support. The hashing backend itself is only available with the `opencl` feature; without it GPU mining
+//! cannot be enabled.
+
+#[cfg(feature = "opencl")]
+mod opencl;
+
+#[cfg(feature = "opencl")]
+use std::{panic::panic_any, task::Waker, time::Instant};
+
+#[cfg(feature = "opencl")]
+use chrono::Utc;
+#[cfg(feature = "opencl")]
+use crossbeam::channel::{Sender, TrySendError};
+#[cfg(feature = "opencl")]
+use log::*;
+#[cfg(feature = "opencl")]
+use minotari_app_grpc::tari_rpc::BlockHeader;
+#[cfg(feature = "opencl")]
+pub use opencl::enumerate_devices;
+
+use crate::{config::MinerConfig, errors::MinerError};
+#[cfg(feature = "opencl")]
+use crate::{difficulty::BlockHeaderSha3, miner::MiningReport};
+
+#[cfg(feature = "opencl")]
+const LOG_TARGET: &str = "minotari::miner::gpu";
+
+/// The Keccak-f[1600] rate of SHA3-256, in bytes
+#[cfg(feature = "opencl")]
+const SHA3_256_RATE: usize = 136;
+/// The number of 64-bit lanes following the nonce in the first Keccak block
+#[cfg(feature = "opencl")]
+pub const TEMPLATE_LANES: usize = 16;
+
+/// A GPU device as enumerated by the hashing backend
+#[derive(Debug, Clone)]
+pub struct GpuDevice {
+    pub index: usize,
+    pub name: String,
+}
+
+/// A GPU device selected for mining, with the number of nonces it hashes per kernel launch given as 2^intensity
+#[derive(Debug, Clone)]
+pub struct GpuWorker {
+    pub device: GpuDevice,
+    pub intensity: u32,
+}
+
+#[cfg(feature = "opencl")]
+impl GpuWorker {
+    pub fn batch_size(&self) -> usize {
+        1usize << self.intensity
+    }
+}
+
+/// Select the GPU devices to mine on from the miner configuration
+pub fn gpu_workers(config: &MinerConfig) -> Result<Vec<GpuWorker>, MinerError> {
+    if !config.gpu_enabled {
+        return Ok(vec![]);
+    }
+    let devices = available_devices()?;
+    let selected = if config.gpu_devices.is_empty() {
+        devices
+    } else {
+        config
+            .gpu_devices
+            .iter()
+            .map(|index| {
+                devices
+                    .iter()
+                    .find(|d| d.index == *index)
+                    .cloned()
+                    .ok_or_else(|| MinerError::Gpu(format!("GPU device {} not found", index)))
+            })
+            .collect::<Result<Vec<_>, _>>()?
+    };
+    selected
+        .into_iter()
+        .enumerate()
+        .map(|(i, device)| {
+            let intensity = config
+                .gpu_device_intensities
+                .get(i)
+                .copied()
+                .unwrap_or(config.gpu_intensity);
+            if intensity == 0 || intensity > 31 {
+                return Err(MinerError::Gpu(format!(
+                    "GPU intensity for device {} must be between 1 and 31, got {}",
+                    device.index, intensity
+                )));
+            }
+            Ok(GpuWorker { device, intensity })
+        })
+        .collect()
+}
+
+/// List the GPU devices that can be used for mining
+pub fn available_devices() -> Result<Vec<GpuDevice>, MinerError> {
+    #[cfg(feature = "opencl")]
+    {
+        enumerate_devices()
+    }
+    #[cfg(not(feature = "opencl"))]
+    {
+        Err(MinerError::Gpu(
+            "This miner was compiled without GPU support, rebuild it with the `opencl` feature".to_string(),
+        ))
+    }
+}
+
+/// Pack the header bytes that follow the nonce in the Sha3x pre-image, together with the SHA3 padding, into the lanes
+/// that follow the nonce lane in the first Keccak block
+#[cfg(feature = "opencl")]
+pub fn pack_template(tail: &[u8]) -> Result<[u64; TEMPLATE_LANES], MinerError> {
+    let block_len = SHA3_256_RATE - 8;
+    if tail.len() >= block_len {
+        return Err(MinerError::Gpu(format!(
+            "Header pre-image of {} bytes does not fit in a single SHA3 block",
+            tail.len() + 8
+        )));
+    }
+    let mut block = [0u8; SHA3_256_RATE - 8];
+    block[..tail.len()].copy_from_slice(tail);
+    block[tail.len()] ^= 0x06;
+    block[block_len - 1] ^= 0x80;
+
+    let mut lanes = [0u64; TEMPLATE_LANES];
+    for (lane, bytes) in lanes.iter_mut().zip(block.chunks_exact(8)) {
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(bytes);
+        *lane = u64::from_le_bytes(buf);
+    }
+    Ok(lanes)
+}
+
+/// GPU counterpart of [crate::miner::mining_task]. Each kernel launch hashes a batch of nonces on the device, and any
+/// nonce the device reports is re-checked on the CPU before it is sent as a mined header.
+#[cfg(feature = "opencl")]
+#[allow(clippy::too_many_lines)]
+pub fn gpu_mining_task(
+    header: BlockHeader,
+    target_difficulty: u64,
+    sender: Sender<MiningReport>,
+    waker: Waker,
+    miner: usize,
+    share_mode: bool,
+    worker: GpuWorker,
+) {
+    let start = Instant::now();
+    let mut hasher = match BlockHeaderSha3::new(header) {
+        Ok(hasher) => hasher,
+        Err(err) => {
+            let err = format!("GPU miner {} failed to create hasher: {:?}", miner, err);
+            error!(target: LOG_TARGET, "{}", err);
+            panic_any(err);
+        },
+    };
+    let mut kernel = match opencl::Sha3xKernel::new(worker.device.index) {
+        Ok(kernel) => kernel,
+        Err(err) => {
+            let err = format!(
+                "GPU miner {} failed to initialize device {} ({}): {}",
+                miner, worker.device.index, worker.device.name, err
+            );
+            error!(target: LOG_TARGET, "{}", err);
+            panic_any(err);
+        },
+    };
+    hasher.random_nonce();
+    let batch_size = worker.batch_size();
+    let target = u64::MAX / target_difficulty.max(1);
+    trace!(
+        target: LOG_TARGET,
+        "GPU mining thread {} started on device {} ({})", miner, worker.device.index, worker.device.name
+    );
+    loop {
+        let template = match pack_template(&hasher.pre_image_tail()) {
+            Ok(template) => template,
+            Err(err) => {
+                let err = format!("GPU miner {} failed to pack the header: {}", miner, err);
+                error!(target: LOG_TARGET, "{}", err);
+                panic_any(err);
+            },
+        };
+        let nonce_start = hasher.nonce();
+        let found = match kernel.run(nonce_start, &template, target, batch_size) {
+            Ok(found) => found,
+            Err(err) => {
+                error!(target: LOG_TARGET, "GPU miner {} kernel failed: {}", miner, err);
+                return;
+            },
+        };
+        hasher.hashes = hasher.hashes.saturating_add(batch_size as u64);
+
+        if let Some(nonce) = found {
+            hasher.set_nonce(nonce);
+            let difficulty = match hasher.difficulty() {
+                Ok(difficulty) => difficulty,
+                Err(err) => {
+                    let err = format!("GPU miner {} failed to calculate difficulty: {:?}", miner, err);
+                    error!(target: LOG_TARGET, "{}", err);
+                    panic_any(err);
+                },
+            };
+            if difficulty >= target_difficulty {
+                debug!(
+                    target: LOG_TARGET,
+                    "GPU miner {} found nonce {} with matching difficulty {}", miner, nonce, difficulty
+                );
+                if let Err(err) = sender.try_send(MiningReport {
+                    miner,
+                    difficulty,
+                    hashes: hasher.hashes,
+                    elapsed: start.elapsed(),
+                    height: hasher.height(),
+                    last_nonce: nonce,
+                    header: Some(hasher.create_header()),
+                    target_difficulty,
+                }) {
+                    error!(target: LOG_TARGET, "GPU miner {} failed to send report: {}", miner, err);
+                }
+                if share_mode {
+                    waker.clone().wake();
+                } else {
+                    waker.wake();
+                    trace!(target: LOG_TARGET, "GPU mining thread {} stopped", miner);
+                    return;
+                }
+            }
+        }
+
+        hasher.set_nonce(nonce_start.wrapping_add(batch_size as u64));
+        let res = sender.try_send(MiningReport {
+            miner,
+            difficulty: 0,
+            hashes: hasher.hashes,
+            elapsed: start.elapsed(),
+            header: None,
+            last_nonce: hasher.nonce(),
+            height: hasher.height(),
+            target_difficulty,
+        });
+        waker.clone().wake();
+        if let Err(TrySendError::Disconnected(_)) = res {
+            info!(target: LOG_TARGET, "GPU mining thread {} disconnected", miner);
+            return;
+        }
+        if !share_mode {
+            hasher.set_forward_timestamp(Utc::now().timestamp() as u64);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "opencl"))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_packs_the_template_with_sha3_padding() {
+        let tail = [0xaau8; 33];
+        let lanes = pack_template(&tail).unwrap();
+        let bytes = lanes.iter().flat_map(|l| l.to_le_bytes()).collect::<Vec<_>>();
+        assert_eq!(bytes.len(), SHA3_256_RATE - 8);
+        assert_eq!(&bytes[..33], &tail[..]);
+        assert_eq!(bytes[33], 0x06);
+        assert!(bytes[34..bytes.len() - 1].iter().all(|b| *b == 0));
+        assert_eq!(bytes[bytes.len() - 1], 0x80);
+    }
+
+    #[test]
+    fn it_rejects_a_template_that_does_not_fit_one_block() {
+        assert!(pack_template(&[0u8; SHA3_256_RATE - 9]).is_ok());
+        assert!(pack_template(&[0u8; SHA3_256_RATE - 8]).is_err());
+    }
+}
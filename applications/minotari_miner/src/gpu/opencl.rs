@@ -0,0 +1,143 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! OpenCL Sha3x hashing backend
+
+use std::{fmt::Display, ptr};
+
+use opencl3::{
+    command_queue::CommandQueue,
+    context::Context,
+    device::{get_all_devices, Device, CL_DEVICE_TYPE_GPU},
+    kernel::{ExecuteKernel, Kernel},
+    memory::{Buffer, CL_MEM_READ_ONLY, CL_MEM_READ_WRITE},
+    program::Program,
+    types::{cl_uint, cl_ulong, CL_BLOCKING},
+};
+
+use super::{GpuDevice, TEMPLATE_LANES};
+use crate::errors::MinerError;
+
+const KERNEL_SOURCE: &str = include_str!("sha3x.cl");
+const KERNEL_NAME: &str = "sha3x";
+
+fn gpu_error<E: Display>(err: E) -> MinerError {
+    MinerError::Gpu(err.to_string())
+}
+
+/// Enumerate the OpenCL GPU devices on all platforms
+pub fn enumerate_devices() -> Result<Vec<GpuDevice>, MinerError> {
+    get_all_devices(CL_DEVICE_TYPE_GPU)
+        .map_err(gpu_error)?
+        .into_iter()
+        .enumerate()
+        .map(|(index, id)| {
+            let name = Device::new(id).name().map_err(gpu_error)?;
+            Ok(GpuDevice { index, name })
+        })
+        .collect()
+}
+
+/// The Sha3x kernel compiled for a single device, with its buffers
+pub struct Sha3xKernel {
+    kernel: Kernel,
+    queue: CommandQueue,
+    template: Buffer<cl_ulong>,
+    found: Buffer<cl_uint>,
+    found_nonce: Buffer<cl_ulong>,
+    _context: Context,
+}
+
+impl Sha3xKernel {
+    /// Compile the kernel for the GPU device at `device_index`. OpenCL handles are not `Send`, so this must be called
+    /// on the thread that will run the kernel.
+    pub fn new(device_index: usize) -> Result<Self, MinerError> {
+        let id = *get_all_devices(CL_DEVICE_TYPE_GPU)
+            .map_err(gpu_error)?
+            .get(device_index)
+            .ok_or_else(|| MinerError::Gpu(format!("GPU device {} not found", device_index)))?;
+        let device = Device::new(id);
+        let context = Context::from_device(&device).map_err(gpu_error)?;
+        let queue = CommandQueue::create_default_with_properties(&context, 0, 0).map_err(gpu_error)?;
+        let program = Program::create_and_build_from_source(&context, KERNEL_SOURCE, "").map_err(MinerError::Gpu)?;
+        let kernel = Kernel::create(&program, KERNEL_NAME).map_err(gpu_error)?;
+        // Safety: the buffers are created without a host pointer
+        let (template, found, found_nonce) = unsafe {
+            (
+                Buffer::<cl_ulong>::create(&context, CL_MEM_READ_ONLY, TEMPLATE_LANES, ptr::null_mut())
+                    .map_err(gpu_error)?,
+                Buffer::<cl_uint>::create(&context, CL_MEM_READ_WRITE, 1, ptr::null_mut()).map_err(gpu_error)?,
+                Buffer::<cl_ulong>::create(&context, CL_MEM_READ_WRITE, 1, ptr::null_mut()).map_err(gpu_error)?,
+            )
+        };
+        Ok(Self {
+            kernel,
+            queue,
+            template,
+            found,
+            found_nonce,
+            _context: context,
+        })
+    }
+
+    /// Hash `batch_size` nonces starting at `nonce_start` and return a nonce whose hash is at or below `target`, if
+    /// the device found one
+    pub fn run(
+        &mut self,
+        nonce_start: u64,
+        template: &[u64; TEMPLATE_LANES],
+        target: u64,
+        batch_size: usize,
+    ) -> Result<Option<u64>, MinerError> {
+        let mut found: [cl_uint; 1] = [0];
+        let mut found_nonce: [cl_ulong; 1] = [0];
+        // Safety: the buffers were created with the lengths written and read here, and all calls block until complete
+        unsafe {
+            self.queue
+                .enqueue_write_buffer(&mut self.template, CL_BLOCKING, 0, template, &[])
+                .map_err(gpu_error)?;
+            self.queue
+                .enqueue_write_buffer(&mut self.found, CL_BLOCKING, 0, &found, &[])
+                .map_err(gpu_error)?;
+            let event = ExecuteKernel::new(&self.kernel)
+                .set_arg(&nonce_start)
+                .set_arg(&self.template)
+                .set_arg(&target)
+                .set_arg(&self.found)
+                .set_arg(&self.found_nonce)
+                .set_global_work_size(batch_size)
+                .enqueue_nd_range(&self.queue)
+                .map_err(gpu_error)?;
+            event.wait().map_err(gpu_error)?;
+            self.queue
+                .enqueue_read_buffer(&self.found, CL_BLOCKING, 0, &mut found, &[])
+                .map_err(gpu_error)?;
+            if found[0] == 0 {
+                return Ok(None);
+            }
+            self.queue
+                .enqueue_read_buffer(&self.found_nonce, CL_BLOCKING, 0, &mut found_nonce, &[])
+                .map_err(gpu_error)?;
+        }
+        Ok(Some(found_nonce[0]))
+    }
+}
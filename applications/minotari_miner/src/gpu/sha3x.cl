@@ -0,0 +1,106 @@
+// Copyright 2023. The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+// Sha3x proof of work: SHA3-256(SHA3-256(SHA3-256(nonce || mining_hash || pow))).
+//
+// The host packs everything after the nonce, including the SHA3 padding, into lanes 1..=16 of the first (and only)
+// Keccak block, so each work item only has to insert its nonce into lane 0.
+
+__constant ulong keccakf_rndc[24] = {
+    0x0000000000000001UL, 0x0000000000008082UL, 0x800000000000808aUL, 0x8000000080008000UL,
+    0x000000000000808bUL, 0x0000000080000001UL, 0x8000000080008081UL, 0x8000000000008009UL,
+    0x000000000000008aUL, 0x0000000000000088UL, 0x0000000080008009UL, 0x000000008000000aUL,
+    0x000000008000808bUL, 0x800000000000008bUL, 0x8000000000008089UL, 0x8000000000008003UL,
+    0x8000000000008002UL, 0x8000000000000080UL, 0x000000000000800aUL, 0x800000008000000aUL,
+    0x8000000080008081UL, 0x8000000000008080UL, 0x0000000080000001UL, 0x8000000080008008UL
+};
+
+__constant uint keccakf_rotc[24] = {
+    1, 3, 6, 10, 15, 21, 28, 36, 45, 55, 2, 14, 27, 41, 56, 8, 25, 43, 62, 18, 39, 61, 20, 44
+};
+
+__constant uint keccakf_piln[24] = {
+    10, 7, 11, 17, 18, 3, 5, 16, 8, 21, 24, 4, 15, 23, 19, 13, 12, 2, 20, 14, 22, 9, 6, 1
+};
+
+void keccakf(ulong st[25]) {
+    ulong bc[5];
+    ulong t;
+    for (uint r = 0; r < 24; r++) {
+        // Theta
+        for (uint i = 0; i < 5; i++) {
+            bc[i] = st[i] ^ st[i + 5] ^ st[i + 10] ^ st[i + 15] ^ st[i + 20];
+        }
+        for (uint i = 0; i < 5; i++) {
+            t = bc[(i + 4) % 5] ^ rotate(bc[(i + 1) % 5], (ulong)1);
+            for (uint j = 0; j < 25; j += 5) {
+                st[j + i] ^= t;
+            }
+        }
+        // Rho and pi
+        t = st[1];
+        for (uint i = 0; i < 24; i++) {
+            uint j = keccakf_piln[i];
+            bc[0] = st[j];
+            st[j] = rotate(t, (ulong)keccakf_rotc[i]);
+            t = bc[0];
+        }
+        // Chi
+        for (uint j = 0; j < 25; j += 5) {
+            for (uint i = 0; i < 5; i++) {
+                bc[i] = st[j + i];
+            }
+            for (uint i = 0; i < 5; i++) {
+                st[j + i] ^= (~bc[(i + 1) % 5]) & bc[(i + 2) % 5];
+            }
+        }
+        // Iota
+        st[0] ^= keccakf_rndc[r];
+    }
+}
+
+// Hash the 32 byte digest held in lanes 0..4 of `st` in place
+void sha3_256_digest(ulong st[25]) {
+    for (uint i = 4; i < 25; i++) {
+        st[i] = 0;
+    }
+    st[4] = 0x06UL;
+    st[16] = 0x8000000000000000UL;
+    keccakf(st);
+}
+
+ulong swap_bytes(ulong x) {
+    x = ((x & 0x00000000FFFFFFFFUL) << 32) | ((x & 0xFFFFFFFF00000000UL) >> 32);
+    x = ((x & 0x0000FFFF0000FFFFUL) << 16) | ((x & 0xFFFF0000FFFF0000UL) >> 16);
+    x = ((x & 0x00FF00FF00FF00FFUL) << 8) | ((x & 0xFF00FF00FF00FF00UL) >> 8);
+    return x;
+}
+
+__kernel void sha3x(
+    ulong nonce_start,
+    __constant ulong* template,
+    ulong target,
+    __global uint* found,
+    __global ulong* found_nonce
+) {
+    ulong nonce = nonce_start + get_global_id(0);
+    ulong st[25];
+    st[0] = nonce;
+    for (uint i = 0; i < 16; i++) {
+        st[i + 1] = template[i];
+    }
+    for (uint i = 17; i < 25; i++) {
+        st[i] = 0;
+    }
+    keccakf(st);
+    sha3_256_digest(st);
+    sha3_256_digest(st);
+
+    // The first 8 bytes of the hash, read as a big endian integer, are compared against the target. The host
+    // re-checks the full difficulty of any nonce reported here.
+    if (swap_bytes(st[0]) <= target) {
+        if (atomic_cmpxchg(found, 0u, 1u) == 0u) {
+            *found_nonce = nonce;
+        }
+    }
+}
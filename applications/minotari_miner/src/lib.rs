@@ -28,9 +28,11 @@ pub use cli::Cli;
 use tari_common::exit_codes::ExitError;
 mod run_miner;
 use run_miner::start_miner;
+mod base_node_pool;
 mod config;
 mod difficulty;
 mod errors;
+mod gpu;
 mod miner;
 mod stratum;
 mod utils;
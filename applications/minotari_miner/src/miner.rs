@@ -36,6 +36,7 @@ use minotari_app_grpc::tari_rpc::BlockHeader;
 use thread::JoinHandle;
 
 use super::difficulty::BlockHeaderSha3;
+use crate::gpu::GpuWorker;
 
 pub const LOG_TARGET: &str = "minotari::miner::standalone";
 
@@ -66,6 +67,7 @@ pub struct Miner {
     threads: Vec<JoinHandle<()>>,
     channels: Vec<crossbeam::channel::Receiver<MiningReport>>,
     num_threads: usize,
+    gpu_workers: Vec<GpuWorker>,
     header: BlockHeader,
     target_difficulty: u64,
     share_mode: bool,
@@ -78,11 +80,23 @@ impl Miner {
             channels: vec![],
             header,
             num_threads,
+            gpu_workers: vec![],
             target_difficulty,
             share_mode,
         }
     }
 
+    /// Also mine on the given GPU devices. GPU workers receive the same job as the CPU threads and report through the
+    /// same channels, numbered after the CPU threads.
+    pub fn with_gpu_workers(mut self, gpu_workers: Vec<GpuWorker>) -> Self {
+        self.gpu_workers = gpu_workers;
+        self
+    }
+
+    fn num_workers(&self) -> usize {
+        self.num_threads + self.gpu_workers.len()
+    }
+
     // this will kill all mining threads currently active and attached to this miner
     pub fn kill_threads(&mut self) {
         self.channels.clear();
@@ -111,9 +125,26 @@ impl Miner {
                 (handle, rx)
             });
 
-        let (threads, channels) = miners.unzip();
+        let (threads, channels): (Vec<_>, Vec<_>) = miners.unzip();
         self.threads = threads;
         self.channels = channels;
+
+        #[cfg(feature = "opencl")]
+        for (i, worker) in self.gpu_workers.iter().enumerate() {
+            let miner = self.num_threads + i;
+            let (tx, rx) = bounded(1);
+            let header = self.header.clone();
+            let waker = ctx.waker().clone();
+            let difficulty = self.target_difficulty;
+            let share_mode = self.share_mode;
+            let worker = worker.clone();
+            let handle = thread::Builder::new()
+                .name(format!("gpu-miner-{}", worker.device.index))
+                .spawn(move || crate::gpu::gpu_mining_task(header, difficulty, tx, waker, miner, share_mode, worker))
+                .expect("Failed to create GPU mining thread");
+            self.threads.push(handle);
+            self.channels.push(rx);
+        }
     }
 }
 
@@ -123,14 +154,17 @@ impl Stream for Miner {
     fn poll_next(mut self: Pin<&mut Self>, ctx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         trace!(target: LOG_TARGET, "Polling Miner");
         // First poll would start all the threads passing async context waker
-        if self.threads.is_empty() && self.num_threads > 0 {
+        if self.threads.is_empty() && self.num_workers() > 0 {
             debug!(
                 target: LOG_TARGET,
-                "Starting {} mining threads for target difficulty {}", self.num_threads, self.target_difficulty
+                "Starting {} mining threads and {} GPU workers for target difficulty {}",
+                self.num_threads,
+                self.gpu_workers.len(),
+                self.target_difficulty
             );
             self.start_threads(ctx);
             return Poll::Pending;
-        } else if self.num_threads == 0 {
+        } else if self.num_workers() == 0 {
             error!(target: LOG_TARGET, "Cannot mine: no mining threads");
             return Poll::Ready(None);
         } else if self.channels.is_empty() {
@@ -205,7 +239,7 @@ pub fn mining_task(
         if difficulty >= target_difficulty {
             debug!(
                 target: LOG_TARGET,
-                "Miner {} found nonce {} with matching difficulty {}", miner, hasher.header.nonce, difficulty
+                "Miner {} found nonce {} with matching difficulty {}", miner, hasher.nonce(), difficulty
             );
             if let Err(err) = sender.try_send(MiningReport {
                 miner,
@@ -213,7 +247,7 @@ pub fn mining_task(
                 hashes: hasher.hashes,
                 elapsed: start.elapsed(),
                 height: hasher.height(),
-                last_nonce: hasher.header.nonce,
+                last_nonce: hasher.nonce(),
                 header: Some(hasher.create_header()),
                 target_difficulty,
             }) {
@@ -229,14 +263,14 @@ pub fn mining_task(
                 return;
             }
         }
-        if hasher.header.nonce % REPORTING_FREQUENCY == 0 {
+        if hasher.nonce() % REPORTING_FREQUENCY == 0 {
             let res = sender.try_send(MiningReport {
                 miner,
                 difficulty,
                 hashes: hasher.hashes,
                 elapsed: start.elapsed(),
                 header: None,
-                last_nonce: hasher.header.nonce,
+                last_nonce: hasher.nonce(),
                 height: hasher.height(),
                 target_difficulty,
             });
@@ -29,9 +29,12 @@ use minotari_app_grpc::{
     tari_rpc::{base_node_client::BaseNodeClient, wallet_client::WalletClient},
 };
 use tari_common::{
-    configuration::bootstrap::{grpc_default_port, ApplicationType},
+    configuration::{
+        bootstrap::{grpc_default_port, ApplicationType},
+        explain::EffectiveConfig,
+    },
     exit_codes::{ExitCode, ExitError},
-    load_configuration,
+    load_configuration_with_origins,
     DefaultConfigLoader,
 };
 use tari_comms::utils::multiaddr::multiaddr_to_socketaddr;
@@ -45,9 +48,11 @@ use tonic::{
 };
 
 use crate::{
+    base_node_pool::BaseNodePool,
     cli::Cli,
     config::MinerConfig,
     errors::{err_empty, MinerError},
+    gpu::{available_devices, gpu_workers, GpuWorker},
     miner::{Miner, MiningReport},
     stratum::stratum_controller::controller::Controller,
     utils::{coinbase_request, extract_outputs_and_kernels},
@@ -61,11 +66,36 @@ type WalletGrpcClient = WalletClient<InterceptedService<Channel, ClientAuthentic
 #[allow(clippy::too_many_lines)]
 pub async fn start_miner(cli: Cli) -> Result<(), ExitError> {
     let config_path = cli.common.config_path();
-    let cfg = load_configuration(config_path.as_path(), true, &cli)?;
+    let (cfg, config_origins) = load_configuration_with_origins(config_path.as_path(), true, &cli)?;
     let mut config = MinerConfig::load_from(&cfg).expect("Failed to load config");
     debug!(target: LOG_TARGET_FILE, "{:?}", config);
     setup_grpc_config(&mut config);
 
+    if cli.common.print_config {
+        let mut effective = EffectiveConfig::new(&cfg, &config_origins);
+        effective.add_section(&config)?;
+        print!("{}", effective.render(cli.common.explain));
+        return Ok(());
+    }
+
+    if cli.list_gpu_devices {
+        let devices = available_devices().map_err(|e| ExitError::new(ExitCode::ConfigError, e.to_string()))?;
+        if devices.is_empty() {
+            println!("No GPU devices found");
+        }
+        for device in devices {
+            println!("{}: {}", device.index, device.name);
+        }
+        return Ok(());
+    }
+    let gpu_workers = gpu_workers(&config).map_err(|e| ExitError::new(ExitCode::ConfigError, e.to_string()))?;
+    for worker in &gpu_workers {
+        info!(
+            target: LOG_TARGET,
+            "Mining on GPU device {} ({}) with intensity {}", worker.device.index, worker.device.name, worker.intensity
+        );
+    }
+
     if !config.mining_wallet_address.is_empty() && !config.mining_pool_address.is_empty() {
         let url = config.mining_pool_address.clone();
         let mut miner_address = config.mining_wallet_address.clone();
@@ -104,7 +134,8 @@ pub async fn start_miner(cli: Cli) -> Result<(), ExitError> {
 
         Ok(())
     } else {
-        let (mut node_conn, mut wallet_conn) = connect(&config).await.map_err(|e| {
+        let mut node_pool = BaseNodePool::new(config.base_node_grpc_addresses(), config.mine_on_tip_only);
+        let (mut node_conn, mut wallet_conn) = connect(&config, &mut node_pool).await.map_err(|e| {
             ExitError::new(
                 ExitCode::GrpcError,
                 format!("Could not connect to wallet or base node: {}", e),
@@ -112,16 +143,38 @@ pub async fn start_miner(cli: Cli) -> Result<(), ExitError> {
         })?;
 
         let mut blocks_found: u64 = 0;
+        let mut first_cycle = true;
         loop {
+            if config.base_node_round_robin && node_pool.num_nodes() > 1 && !first_cycle {
+                match node_pool.failover().await {
+                    Ok(nc) => node_conn = nc,
+                    Err(err) => warn!(target: LOG_TARGET, "Could not rotate to the next base node: {}", err),
+                }
+            }
+            first_cycle = false;
             debug!(target: LOG_TARGET, "Starting new mining cycle");
-            match mining_cycle(&mut node_conn, &mut wallet_conn, &config, &cli).await {
+            match mining_cycle(&mut node_conn, &mut wallet_conn, &config, &cli, &gpu_workers).await {
                 err @ Err(MinerError::GrpcConnection(_)) | err @ Err(MinerError::GrpcStatus(_)) => {
-                    // Any GRPC error we will try to reconnect with a standard delay
+                    // On any GRPC error we first try the other base nodes, then reconnect with a standard delay
                     error!(target: LOG_TARGET, "Connection error: {:?}", err);
+                    if node_pool.num_nodes() > 1 {
+                        if let Ok(nc) = node_pool.failover().await {
+                            info!(
+                                target: LOG_TARGET,
+                                "Failed over to base node at {}",
+                                node_pool.current_address()
+                            );
+                            node_conn = nc;
+                            if let Ok(wc) = connect_wallet(&config).await {
+                                wallet_conn = wc;
+                                continue;
+                            }
+                        }
+                    }
                     loop {
                         info!(target: LOG_TARGET, "Holding for {:?}", config.wait_timeout());
                         sleep(config.wait_timeout()).await;
-                        match connect(&config).await {
+                        match connect(&config, &mut node_pool).await {
                             Ok((nc, wc)) => {
                                 node_conn = nc;
                                 wallet_conn = wc;
@@ -147,6 +200,20 @@ pub async fn start_miner(cli: Cli) -> Result<(), ExitError> {
                         "Height {} already mined by other node. Restarting ...", h
                     );
                 },
+                Err(MinerError::NodeNotReady) if node_pool.num_nodes() > 1 => {
+                    warn!(
+                        target: LOG_TARGET,
+                        "Base node at {} is not ready, failing over",
+                        node_pool.current_address()
+                    );
+                    match node_pool.failover().await {
+                        Ok(nc) => node_conn = nc,
+                        Err(err) => {
+                            error!(target: LOG_TARGET, "No base node is ready: {:?}", err);
+                            sleep(config.wait_timeout()).await;
+                        },
+                    }
+                },
                 Err(err) => {
                     error!(target: LOG_TARGET, "Error: {:?}", err);
                     sleep(config.wait_timeout()).await;
@@ -167,18 +234,11 @@ pub async fn start_miner(cli: Cli) -> Result<(), ExitError> {
     }
 }
 
-async fn connect(config: &MinerConfig) -> Result<(BaseNodeClient<Channel>, WalletGrpcClient), MinerError> {
-    let base_node_addr = format!(
-        "http://{}",
-        multiaddr_to_socketaddr(
-            &config
-                .base_node_grpc_address
-                .clone()
-                .expect("no base node grpc address found"),
-        )?
-    );
-    info!(target: LOG_TARGET, "🔗 Connecting to base node at {}", base_node_addr);
-    let node_conn = BaseNodeClient::connect(base_node_addr).await?;
+async fn connect(
+    config: &MinerConfig,
+    node_pool: &mut BaseNodePool,
+) -> Result<(BaseNodeClient<Channel>, WalletGrpcClient), MinerError> {
+    let node_conn = node_pool.connect().await?;
 
     let wallet_conn = match connect_wallet(config).await {
         Ok(client) => client,
@@ -220,6 +280,7 @@ async fn mining_cycle(
     wallet_conn: &mut WalletGrpcClient,
     config: &MinerConfig,
     cli: &Cli,
+    gpu_workers: &[GpuWorker],
 ) -> Result<bool, MinerError> {
     debug!(target: LOG_TARGET, "Getting new block template");
     let template = node_conn
@@ -265,7 +326,8 @@ async fn mining_cycle(
     let header = block.clone().header.ok_or_else(|| err_empty("block.header"))?;
 
     debug!(target: LOG_TARGET, "Initializing miner");
-    let mut reports = Miner::init_mining(header.clone(), target_difficulty, config.num_mining_threads, false);
+    let mut reports = Miner::init_mining(header.clone(), target_difficulty, config.num_mining_threads, false)
+        .with_gpu_workers(gpu_workers.to_vec());
     let mut reporting_timeout = Instant::now();
     let mut block_submitted = false;
     while let Some(report) = reports.next().await {
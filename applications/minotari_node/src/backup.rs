@@ -0,0 +1,242 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Scheduled backups of the blockchain database, and restoring the database from a backup at startup, so that a node
+//! on an unreliable disk can recover without a full resync.
+
+use std::{
+    fs,
+    io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use chrono::Utc;
+use log::*;
+use serde::{Deserialize, Serialize};
+use tari_common::{
+    configuration::serializers,
+    exit_codes::{ExitCode, ExitError},
+};
+use tari_core::chain_storage::{BlockchainDatabase, LMDBDatabase};
+use tari_shutdown::ShutdownSignal;
+use tokio::{task, time, time::MissedTickBehavior};
+
+const LOG_TARGET: &str = "minotari::base_node::backup";
+
+const BACKUP_PREFIX: &str = "db_backup_";
+const PARTIAL_SUFFIX: &str = ".partial";
+const LMDB_DATA_FILE: &str = "data.mdb";
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct DatabaseBackupConfig {
+    /// Take scheduled backups of the blockchain database
+    pub enabled: bool,
+    /// The directory to write backups to. Relative paths are relative to the base node data directory.
+    pub backup_dir: PathBuf,
+    /// The time between backups
+    #[serde(with = "serializers::seconds")]
+    pub interval: Duration,
+    /// The number of backups to keep. Older backups are deleted once a new backup has been written.
+    pub max_backups: usize,
+}
+
+impl Default for DatabaseBackupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            backup_dir: PathBuf::from("backups"),
+            interval: Duration::from_secs(24 * 60 * 60),
+            max_backups: 3,
+        }
+    }
+}
+
+/// Takes a backup of the blockchain database every `config.interval` until shutdown
+pub async fn run_database_backups(
+    db: BlockchainDatabase<LMDBDatabase>,
+    config: DatabaseBackupConfig,
+    mut shutdown_signal: ShutdownSignal,
+) {
+    info!(
+        target: LOG_TARGET,
+        "Backing up the blockchain database to {} every {:.0?}",
+        config.backup_dir.display(),
+        config.interval
+    );
+    let mut interval = time::interval_at(time::Instant::now() + config.interval, config.interval);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                let db = db.clone();
+                let config = config.clone();
+                match task::spawn_blocking(move || backup_database(&db, &config)).await {
+                    Ok(Ok(path)) => info!(target: LOG_TARGET, "Blockchain database backed up to {}", path.display()),
+                    Ok(Err(e)) => error!(target: LOG_TARGET, "Blockchain database backup failed: {}", e),
+                    Err(e) => error!(target: LOG_TARGET, "Blockchain database backup task failed: {}", e),
+                }
+            },
+            _ = shutdown_signal.wait() => {
+                info!(target: LOG_TARGET, "Database backups stopped");
+                break;
+            }
+        }
+    }
+}
+
+/// Writes a new backup and then deletes the oldest backups beyond `config.max_backups`. The backup is written to a
+/// partial directory that is only renamed once it is complete, so an interrupted backup is never rotated in or
+/// restored from.
+fn backup_database(db: &BlockchainDatabase<LMDBDatabase>, config: &DatabaseBackupConfig) -> anyhow::Result<PathBuf> {
+    let name = format!("{}{}", BACKUP_PREFIX, Utc::now().format("%Y%m%d_%H%M%S"));
+    let path = config.backup_dir.join(&name);
+    let partial_path = config.backup_dir.join(format!("{}{}", name, PARTIAL_SUFFIX));
+    if partial_path.exists() {
+        fs::remove_dir_all(&partial_path)?;
+    }
+    db.backup_to(&partial_path)?;
+    fs::rename(&partial_path, &path)?;
+    rotate_backups(&config.backup_dir, config.max_backups)?;
+    Ok(path)
+}
+
+fn rotate_backups(backup_dir: &Path, max_backups: usize) -> io::Result<()> {
+    let mut backups = Vec::new();
+    for entry in fs::read_dir(backup_dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with(BACKUP_PREFIX) && entry.file_type()?.is_dir() {
+            backups.push((name, entry.path()));
+        }
+    }
+    // Backup names sort by the time they were taken, and abandoned partial backups sort with them
+    backups.sort();
+    let complete = backups
+        .iter()
+        .filter(|(name, _)| !name.ends_with(PARTIAL_SUFFIX))
+        .count();
+    let mut to_delete = complete.saturating_sub(max_backups.max(1));
+    for (name, path) in backups {
+        if name.ends_with(PARTIAL_SUFFIX) {
+            fs::remove_dir_all(path)?;
+        } else if to_delete > 0 {
+            debug!(target: LOG_TARGET, "Deleting old database backup {}", path.display());
+            fs::remove_dir_all(path)?;
+            to_delete -= 1;
+        } else {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Replaces the blockchain database at `lmdb_path` with the backup at `backup_path`. The existing database is moved
+/// aside rather than deleted, so that it can be recovered if the backup turns out to be unusable.
+pub fn restore_database(backup_path: &Path, lmdb_path: &Path) -> Result<(), ExitError> {
+    let backup_file = backup_path.join(LMDB_DATA_FILE);
+    if !backup_file.is_file() {
+        return Err(ExitError::new(
+            ExitCode::DatabaseError,
+            format!("{} is not a database backup", backup_path.display()),
+        ));
+    }
+    let into_exit_error = |e: io::Error| ExitError::new(ExitCode::DatabaseError, e);
+    if lmdb_path.exists() {
+        let mut previous = lmdb_path.as_os_str().to_os_string();
+        previous.push(format!("_before_restore_{}", Utc::now().format("%Y%m%d_%H%M%S")));
+        let previous = PathBuf::from(previous);
+        fs::rename(lmdb_path, &previous).map_err(into_exit_error)?;
+        info!(
+            target: LOG_TARGET,
+            "Moved the existing blockchain database to {}",
+            previous.display()
+        );
+    }
+    fs::create_dir_all(lmdb_path).map_err(into_exit_error)?;
+    fs::copy(&backup_file, lmdb_path.join(LMDB_DATA_FILE)).map_err(into_exit_error)?;
+    info!(
+        target: LOG_TARGET,
+        "Restored the blockchain database from {}",
+        backup_path.display()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[test]
+    fn it_keeps_the_newest_backups() {
+        let temp_dir = tempdir().unwrap();
+        for name in [
+            "db_backup_20230101_000000",
+            "db_backup_20230102_000000",
+            "db_backup_20230103_000000",
+            "db_backup_20230104_000000.partial",
+            "other",
+        ] {
+            fs::create_dir(temp_dir.path().join(name)).unwrap();
+        }
+
+        rotate_backups(temp_dir.path(), 2).unwrap();
+        let mut remaining = fs::read_dir(temp_dir.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .collect::<Vec<_>>();
+        remaining.sort();
+        assert_eq!(remaining, vec![
+            "db_backup_20230102_000000",
+            "db_backup_20230103_000000",
+            "other"
+        ]);
+    }
+
+    #[test]
+    fn it_restores_a_backup_and_keeps_the_previous_database() {
+        let temp_dir = tempdir().unwrap();
+        let backup_path = temp_dir.path().join("backup");
+        let lmdb_path = temp_dir.path().join("db");
+        fs::create_dir_all(&backup_path).unwrap();
+        fs::create_dir_all(&lmdb_path).unwrap();
+        fs::write(backup_path.join(LMDB_DATA_FILE), b"backup").unwrap();
+        fs::write(lmdb_path.join(LMDB_DATA_FILE), b"corrupt").unwrap();
+
+        restore_database(&backup_path, &lmdb_path).unwrap();
+        assert_eq!(fs::read(lmdb_path.join(LMDB_DATA_FILE)).unwrap(), b"backup");
+        let previous = fs::read_dir(temp_dir.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .find(|name| name.starts_with("db_before_restore_"))
+            .unwrap();
+        assert_eq!(
+            fs::read(temp_dir.path().join(previous).join(LMDB_DATA_FILE)).unwrap(),
+            b"corrupt"
+        );
+
+        assert!(restore_database(&temp_dir.path().join("missing"), &lmdb_path).is_err());
+    }
+}
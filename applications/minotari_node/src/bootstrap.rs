@@ -34,8 +34,11 @@ use tari_core::{
     base_node,
     base_node::{
         chain_metadata_service::ChainMetadataServiceInitializer,
+        chain_split_monitor::ChainSplitMonitorInitializer,
+        pruning_service::PruningServiceInitializer,
         service::BaseNodeServiceInitializer,
         state_machine_service::initializer::BaseNodeStateMachineInitializer,
+        BlockchainSyncConfig,
         LocalNodeCommsInterface,
         StateMachineHandle,
     },
@@ -103,6 +106,15 @@ where B: BlockchainBackend + 'static
 
         let mempool_sync = MempoolSyncInitializer::new(mempool_config, self.mempool.clone());
         let mempool_protocol = mempool_sync.get_protocol_extension();
+        let base_node_service = BaseNodeServiceInitializer::new(
+            peer_message_subscriptions.clone(),
+            self.db.clone().into(),
+            self.mempool.clone(),
+            self.rules.clone(),
+            base_node_config.messaging_request_timeout,
+            self.randomx_factory.clone(),
+        );
+        let base_node_protocol = base_node_service.get_protocol_extension();
 
         let tor_identity = load_from_json(&base_node_config.tor_identity_file)
             .map_err(|e| ExitError::new(ExitCode::ConfigError, e))?;
@@ -124,14 +136,7 @@ where B: BlockchainBackend + 'static
                     .expect("Unable to parse application version. Not valid semver"),
                 self.app_config.auto_update.clone(),
             ))
-            .add_initializer(BaseNodeServiceInitializer::new(
-                peer_message_subscriptions.clone(),
-                self.db.clone().into(),
-                self.mempool.clone(),
-                self.rules.clone(),
-                base_node_config.messaging_request_timeout,
-                self.randomx_factory.clone(),
-            ))
+            .add_initializer(base_node_service)
             .add_initializer(MempoolServiceInitializer::new(
                 self.mempool.clone(),
                 peer_message_subscriptions.clone(),
@@ -146,6 +151,14 @@ where B: BlockchainBackend + 'static
                 peer_message_subscriptions,
             ))
             .add_initializer(ChainMetadataServiceInitializer)
+            .add_initializer(PruningServiceInitializer::new(
+                self.db.clone().into(),
+                base_node_config.storage,
+            ))
+            .add_initializer(ChainSplitMonitorInitializer::new(
+                self.db.clone().into(),
+                base_node_config.chain_split_monitor.clone(),
+            ))
             .add_initializer(BaseNodeStateMachineInitializer::new(
                 self.db.clone().into(),
                 base_node_config.state_machine.clone(),
@@ -161,8 +174,16 @@ where B: BlockchainBackend + 'static
             .take_handle::<UnspawnedCommsNode>()
             .expect("P2pInitializer was not added to the stack or did not add UnspawnedCommsNode");
 
-        let comms = comms.add_protocol_extension(mempool_protocol);
-        let comms = Self::setup_rpc_services(comms, &handles, self.db.into(), &p2p_config);
+        let comms = comms
+            .add_protocol_extension(mempool_protocol)
+            .add_protocol_extension(base_node_protocol);
+        let comms = Self::setup_rpc_services(
+            comms,
+            &handles,
+            self.db.into(),
+            &p2p_config,
+            base_node_config.state_machine.blockchain_sync_config.clone(),
+        );
         let comms = initialization::spawn_comms_using_transport(comms, p2p_config.transport.clone())
             .await
             .map_err(|e| e.to_exit_error())?;
@@ -191,12 +212,14 @@ where B: BlockchainBackend + 'static
         handles: &ServiceHandles,
         db: AsyncBlockchainDb<B>,
         config: &P2pConfig,
+        sync_config: BlockchainSyncConfig,
     ) -> UnspawnedCommsNode {
         let dht = handles.expect_handle::<Dht>();
         let base_node_service = handles.expect_handle::<LocalNodeCommsInterface>();
         let rpc_server = RpcServer::builder()
             .with_maximum_simultaneous_sessions(config.rpc_max_simultaneous_sessions)
             .with_maximum_sessions_per_client(config.rpc_max_sessions_per_peer)
+            .with_compression(config.rpc_compression)
             .finish();
 
         // Add your RPC services here ‍🏴‍☠️️☮️🌊
@@ -205,6 +228,8 @@ where B: BlockchainBackend + 'static
             .add_service(base_node::create_base_node_sync_rpc_service(
                 db.clone(),
                 base_node_service,
+                sync_config,
+                comms.connectivity(),
             ))
             .add_service(mempool::create_mempool_rpc_service(
                 handles.expect_handle::<MempoolHandle>(),
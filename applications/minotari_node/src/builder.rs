@@ -30,7 +30,12 @@ use tari_common::{
 use tari_comms::{peer_manager::NodeIdentity, protocol::rpc::RpcServerHandle, CommsNode};
 use tari_comms_dht::Dht;
 use tari_core::{
-    base_node::{state_machine_service::states::StatusInfo, LocalNodeCommsInterface, StateMachineHandle},
+    base_node::{
+        chain_split_monitor::ChainSplitMonitorHandle,
+        state_machine_service::states::StatusInfo,
+        LocalNodeCommsInterface,
+        StateMachineHandle,
+    },
     chain_storage::{create_lmdb_database, BlockchainDatabase, ChainStorageError, LMDBDatabase, Validators},
     consensus::ConsensusManager,
     mempool::{service::LocalMempoolService, Mempool},
@@ -48,7 +53,7 @@ use tari_service_framework::ServiceHandles;
 use tari_shutdown::ShutdownSignal;
 use tokio::sync::watch;
 
-use crate::{bootstrap::BaseNodeBootstrapper, ApplicationConfig, DatabaseType};
+use crate::{bootstrap::BaseNodeBootstrapper, shutdown, ApplicationConfig, DatabaseType};
 
 const LOG_TARGET: &str = "c::bn::initialization";
 
@@ -59,13 +64,15 @@ pub struct BaseNodeContext {
     config: Arc<ApplicationConfig>,
     consensus_rules: ConsensusManager,
     blockchain_db: BlockchainDatabase<LMDBDatabase>,
+    mempool: Mempool,
     base_node_comms: CommsNode,
     base_node_dht: Dht,
     base_node_handles: ServiceHandles,
 }
 
 impl BaseNodeContext {
-    /// Waits for shutdown of the base node state machine and comms.
+    /// Waits for shutdown of the base node state machine and comms, and then runs the shutdown sequence that flushes
+    /// the blockchain database and saves the mempool.
     /// This call consumes the NodeContainer instance.
     pub async fn wait_for_shutdown(self) {
         self.state_machine().shutdown_signal().wait().await;
@@ -73,6 +80,8 @@ impl BaseNodeContext {
 
         self.base_node_comms.wait_until_shutdown().await;
         info!(target: LOG_TARGET, "Communications stack has shutdown");
+
+        shutdown::run_shutdown_sequence(self.blockchain_db, self.mempool, &self.config.base_node.shutdown).await;
     }
 
     /// Return the node config
@@ -105,6 +114,11 @@ impl BaseNodeContext {
         self.base_node_handles.expect_handle()
     }
 
+    /// Returns the chain split monitor handle
+    pub fn chain_split_monitor(&self) -> ChainSplitMonitorHandle {
+        self.base_node_handles.expect_handle()
+    }
+
     /// Returns this node's identity.
     pub fn base_node_identity(&self) -> Arc<NodeIdentity> {
         self.base_node_comms.node_identity()
@@ -249,6 +263,19 @@ async fn build_node_context(
         rules.clone(),
         Box::new(mempool_validator),
     );
+    if app_config.base_node.shutdown.persist_mempool {
+        let snapshot_file = &app_config.base_node.shutdown.mempool_snapshot_file;
+        match shutdown::restore_mempool_snapshot(&mempool, snapshot_file).await {
+            Ok(0) => {},
+            Ok(count) => info!(
+                target: LOG_TARGET,
+                "Resubmitted {} saved transaction(s) from {} to the mempool",
+                count,
+                snapshot_file.display()
+            ),
+            Err(e) => warn!(target: LOG_TARGET, "Failed to restore the mempool snapshot: {}", e),
+        }
+    }
 
     //---------------------------------- Base Node  --------------------------------------------//
     debug!(target: LOG_TARGET, "Creating base node state machine.");
@@ -257,7 +284,7 @@ async fn build_node_context(
         app_config: &app_config,
         node_identity: base_node_identity,
         db: blockchain_db.clone(),
-        mempool,
+        mempool: mempool.clone(),
         rules: rules.clone(),
         factories: factories.clone(),
         randomx_factory,
@@ -273,6 +300,7 @@ async fn build_node_context(
         config: app_config,
         consensus_rules: rules,
         blockchain_db,
+        mempool,
         base_node_comms,
         base_node_dht,
         base_node_handles,
@@ -20,6 +20,8 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use std::path::PathBuf;
+
 use clap::Parser;
 use minotari_app_utilities::common_cli_args::CommonCliArgs;
 use tari_common::configuration::{ConfigOverrideProvider, Network};
@@ -37,6 +39,10 @@ pub struct Cli {
     /// This will rebuild the db, adding block for block in
     #[clap(long, alias = "rebuild_db")]
     pub rebuild_db: bool,
+    /// Replace the blockchain database with the database backup in this directory before starting. The existing
+    /// database is kept alongside it.
+    #[clap(long, alias = "restore_from")]
+    pub restore_from: Option<PathBuf>,
     /// Run in non-interactive mode, with no UI.
     #[clap(short, long, alias = "non-interactive", env = "TARI_NON_INTERACTIVE")]
     pub non_interactive_mode: bool,
@@ -8,7 +8,8 @@ use crossterm::{
     event::{Event, EventStream, KeyCode, KeyEvent, KeyModifiers},
     terminal,
 };
-use futures::{FutureExt, StreamExt};
+use futures::{future::BoxFuture, FutureExt, StreamExt};
+use minotari_app_utilities::service_manager;
 use rustyline::{config::OutputStreamType, error::ReadlineError, CompletionType, Config, EditMode, Editor};
 use tari_shutdown::ShutdownSignal;
 use tokio::{signal, time};
@@ -32,6 +33,7 @@ pub struct CliLoop {
     first_signal: bool,
     done: bool,
     shutdown_signal: ShutdownSignal,
+    stop_request: BoxFuture<'static, io::Result<()>>,
 }
 
 impl CliLoop {
@@ -69,6 +71,7 @@ impl CliLoop {
             first_signal: false,
             done: false,
             shutdown_signal,
+            stop_request: service_manager::wait_for_stop_request().fuse().boxed(),
         }
     }
 
@@ -87,7 +90,9 @@ impl CliLoop {
         } else {
             while !self.done {
                 self.watch_loop().await;
-                self.execute_command().await;
+                if !self.done {
+                    self.execute_command().await;
+                }
             }
         }
     }
@@ -139,6 +144,10 @@ impl CliLoop {
                         _ = &mut interrupt => {
                             break;
                         }
+                        Ok(_) = &mut self.stop_request => {
+                            self.done = true;
+                            break;
+                        }
                         event = events.next() => {
                             if self.is_interrupted(event) {
                                 break;
@@ -187,6 +196,9 @@ impl CliLoop {
                         _ = &mut interrupt => {
                             break;
                         },
+                        Ok(_) = &mut self.stop_request => {
+                            self.done = true;
+                        },
                         _ = self.shutdown_signal.wait() => {
                             self.done = true;
                         }
@@ -241,6 +253,9 @@ impl CliLoop {
                     self.done = true;
                 }
             },
+            Ok(_) = &mut self.stop_request => {
+                self.done = true;
+            },
             _ = self.shutdown_signal.wait() => {
                 self.done = true;
             }
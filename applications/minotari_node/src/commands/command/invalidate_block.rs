@@ -0,0 +1,67 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use anyhow::Error;
+use async_trait::async_trait;
+use clap::Parser;
+use tari_common_types::types::BlockHash;
+use tari_core::base_node::comms_interface::BlockEvent;
+use tari_utilities::hex::Hex;
+
+use super::{CommandContext, HandleCommand};
+use crate::commands::parser::FromHex;
+
+/// Marks a block as invalid until the node is restarted, rewinding the chain to its parent if it is on the main chain
+#[derive(Debug, Parser)]
+pub struct Args {
+    /// The hash of the block to invalidate
+    hash: FromHex<BlockHash>,
+}
+
+#[async_trait]
+impl HandleCommand<Args> for CommandContext {
+    async fn handle_command(&mut self, args: Args) -> Result<(), Error> {
+        self.invalidate_block(args.hash.0).await
+    }
+}
+
+impl CommandContext {
+    pub async fn invalidate_block(&self, hash: BlockHash) -> Result<(), Error> {
+        let blocks = self.blockchain_db.invalidate_block(hash).await?;
+        if blocks.is_empty() {
+            println!("Block {} has been invalidated", hash.to_hex());
+        } else {
+            let tip = self.blockchain_db.fetch_tip_header().await?;
+            println!(
+                "Block {} has been invalidated. {} block(s) were disconnected, new tip is #{} ({})",
+                hash.to_hex(),
+                blocks.len(),
+                tip.height(),
+                tip.hash()
+            );
+            self.node_service
+                .publish_block_event(BlockEvent::BlockSyncRewind(blocks));
+        }
+        println!("The block will be reconsidered when the node is restarted");
+        Ok(())
+    }
+}
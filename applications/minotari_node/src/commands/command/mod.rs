@@ -21,6 +21,7 @@
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 mod add_peer;
+mod allow_deep_reorg;
 mod ban_peer;
 mod block_timing;
 mod check_db;
@@ -36,6 +37,7 @@ mod get_network_stats;
 mod get_peer;
 mod get_state_info;
 mod header_stats;
+mod invalidate_block;
 mod list_banned_peers;
 mod list_connections;
 mod list_headers;
@@ -47,8 +49,10 @@ mod ping_peer;
 mod quit;
 mod reset_offline_peers;
 mod rewind_blockchain;
+mod rotate_onion_address;
 mod search_kernel;
 mod search_utxo;
+mod stage_update;
 mod status;
 mod unban_all_peers;
 mod version;
@@ -101,6 +105,7 @@ pub struct Args {
 pub enum Command {
     Version(version::Args),
     CheckForUpdates(check_for_updates::Args),
+    StageUpdate(stage_update::Args),
     Status(status::Args),
     GetChainMetadata(get_chain_metadata::Args),
     GetDbStats(get_db_stats::Args),
@@ -109,7 +114,11 @@ pub enum Command {
     DialPeer(dial_peer::Args),
     PingPeer(ping_peer::Args),
     ResetOfflinePeers(reset_offline_peers::Args),
+    #[clap(alias = "rewind-chain")]
     RewindBlockchain(rewind_blockchain::Args),
+    InvalidateBlock(invalidate_block::Args),
+    AllowDeepReorg(allow_deep_reorg::Args),
+    RotateOnionAddress(rotate_onion_address::Args),
     AddPeer(add_peer::ArgsAddPeer),
     BanPeer(ban_peer::ArgsBan),
     UnbanPeer(ban_peer::ArgsUnban),
@@ -205,6 +214,7 @@ impl CommandContext {
                 Command::UnbanPeer(_) |
                 Command::GetPeer(_) |
                 Command::ResetOfflinePeers(_) |
+                Command::RotateOnionAddress(_) |
                 Command::DialPeer(_) |
                 Command::PingPeer(_) |
                 Command::DiscoverPeer(_) |
@@ -231,7 +241,13 @@ impl CommandContext {
                 Command::Quit(_) |
                 Command::Exit(_) => 30,
                 // These commands involve intense blockchain db operations and needs a lot of time to complete
-                Command::CheckDb(_) | Command::PeriodStats(_) | Command::RewindBlockchain(_) => 600,
+                Command::CheckDb(_) |
+                Command::PeriodStats(_) |
+                Command::RewindBlockchain(_) |
+                Command::InvalidateBlock(_) |
+                Command::AllowDeepReorg(_) => 600,
+                // Downloading an update binary can take a while on slow connections
+                Command::StageUpdate(_) => 600,
             };
             let fut = self.handle_command(args.command);
             if let Err(e) = time::timeout(Duration::from_secs(time_out), fut).await? {
@@ -259,6 +275,7 @@ impl HandleCommand<Command> for CommandContext {
         match command {
             Command::Version(args) => self.handle_command(args).await,
             Command::CheckForUpdates(args) => self.handle_command(args).await,
+            Command::StageUpdate(args) => self.handle_command(args).await,
             Command::Status(args) => self.handle_command(args).await,
             Command::GetChainMetadata(args) => self.handle_command(args).await,
             Command::GetDbStats(args) => self.handle_command(args).await,
@@ -273,6 +290,9 @@ impl HandleCommand<Command> for CommandContext {
             Command::UnbanPeer(args) => self.handle_command(args).await,
             Command::ResetOfflinePeers(args) => self.handle_command(args).await,
             Command::RewindBlockchain(args) => self.handle_command(args).await,
+            Command::InvalidateBlock(args) => self.handle_command(args).await,
+            Command::AllowDeepReorg(args) => self.handle_command(args).await,
+            Command::RotateOnionAddress(args) => self.handle_command(args).await,
             Command::UnbanAllPeers(args) => self.handle_command(args).await,
             Command::ListHeaders(args) => self.handle_command(args).await,
             Command::CheckDb(args) => self.handle_command(args).await,
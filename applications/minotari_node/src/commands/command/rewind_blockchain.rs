@@ -20,14 +20,14 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use anyhow::Error;
+use anyhow::{anyhow, Error};
 use async_trait::async_trait;
 use clap::Parser;
 use tari_core::base_node::comms_interface::BlockEvent;
 
 use super::{CommandContext, HandleCommand};
 
-/// Rewinds the blockchain to the given height
+/// Rewinds the blockchain to the given height, resubmitting the transactions of the disconnected blocks to the mempool
 #[derive(Debug, Parser)]
 pub struct Args {
     /// new_height must be less than the current height
@@ -43,6 +43,12 @@ impl HandleCommand<Args> for CommandContext {
 
 impl CommandContext {
     pub async fn rewind_blockchain(&self, new_height: u64) -> Result<(), Error> {
+        let header = self
+            .blockchain_db
+            .fetch_header(new_height)
+            .await?
+            .ok_or_else(|| anyhow!("No block exists at height {}", new_height))?;
+        self.blockchain_db.check_rewind_to_hash(header.hash()).await?;
         let blocks = self.blockchain_db.rewind_to_height(new_height).await?;
         if !blocks.is_empty() {
             self.node_service
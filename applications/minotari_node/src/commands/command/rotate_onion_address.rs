@@ -0,0 +1,73 @@
+//  Copyright 2022, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use anyhow::{anyhow, Error};
+use async_trait::async_trait;
+use clap::Parser;
+use minotari_app_utilities::identity_management;
+use tari_comms::multiaddr::Protocol;
+
+use super::{CommandContext, HandleCommand};
+
+/// Replace this node's onion address with a newly generated one. The new tor identity is persisted so that it is used
+/// on subsequent restarts.
+#[derive(Debug, Parser)]
+pub struct Args {}
+
+#[async_trait]
+impl HandleCommand<Args> for CommandContext {
+    async fn handle_command(&mut self, _: Args) -> Result<(), Error> {
+        self.rotate_onion_address().await
+    }
+}
+
+impl CommandContext {
+    pub async fn rotate_onion_address(&mut self) -> Result<(), Error> {
+        let hidden_service = self
+            .comms
+            .hidden_service()
+            .ok_or_else(|| anyhow!("This node is not running a tor hidden service"))?;
+        let tor_identity = hidden_service.rotate_identity().await?;
+        let onion_address = tor_identity.try_get_onion_address()?;
+
+        // Replace any previous onion addresses with the new one
+        let mut public_addresses = self
+            .base_node_identity
+            .public_addresses()
+            .into_iter()
+            .filter(|addr| {
+                !addr
+                    .iter()
+                    .any(|p| matches!(p, Protocol::Onion(..) | Protocol::Onion3(_)))
+            })
+            .collect::<Vec<_>>();
+        public_addresses.push(onion_address.clone());
+        self.base_node_identity.set_public_addresses(public_addresses);
+
+        let base_node_config = &self.config.base_node;
+        identity_management::save_as_json(&base_node_config.tor_identity_file, &tor_identity)?;
+        identity_management::save_as_json(&base_node_config.identity_file, &*self.base_node_identity)?;
+
+        println!("Onion address rotated. New address is {}", onion_address);
+        Ok(())
+    }
+}
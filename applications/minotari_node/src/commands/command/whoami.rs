@@ -24,7 +24,7 @@ use anyhow::Error;
 use async_trait::async_trait;
 use clap::Parser;
 use qrcode::{render::unicode, QrCode};
-use tari_utilities::hex::Hex;
+use tari_common_types::tari_uri::{TariUri, TariUriAction};
 
 use super::{CommandContext, HandleCommand};
 
@@ -44,23 +44,17 @@ impl CommandContext {
     /// Function to process the whoami command
     pub fn whoami(&self) -> Result<(), Error> {
         println!("{}", self.base_node_identity);
-        let peer = format!(
-            "{}::{}",
-            self.base_node_identity.public_key().to_hex(),
-            self.base_node_identity
+        let qr_link = TariUri::new(self.config.network(), TariUriAction::AddBaseNode {
+            name: Some(self.base_node_identity.node_id().to_string()),
+            public_key: self.base_node_identity.public_key().clone(),
+            addresses: self
+                .base_node_identity
                 .public_addresses()
                 .iter()
                 .map(|addr| addr.to_string())
-                .collect::<Vec<_>>()
-                .join("::")
-        );
-        let network = self.config.network();
-        let qr_link = format!(
-            "tari://{}/base_nodes/add?name={}&peer={}",
-            network,
-            self.base_node_identity.node_id(),
-            peer
-        );
+                .collect(),
+        })
+        .to_string();
         let code = QrCode::new(qr_link).unwrap();
         let image = code
             .render::<unicode::Dense1x2>()
@@ -29,14 +29,21 @@ use config::Config;
 use minotari_app_utilities::consts;
 use serde::{Deserialize, Serialize};
 use tari_common::{
-    configuration::{serializers, CommonConfig, Network, StringList},
+    configuration::{
+        explain::{ConfigOrigins, EffectiveConfig},
+        serializers,
+        CommonConfig,
+        Network,
+        StringList,
+    },
     ConfigurationError,
     DefaultConfigLoader,
     SubConfigPath,
 };
+use tari_common_types::grpc_authentication::GrpcAuthentication;
 use tari_comms::multiaddr::Multiaddr;
 use tari_core::{
-    base_node::BaseNodeStateMachineConfig,
+    base_node::{chain_split_monitor::ChainSplitMonitorConfig, BaseNodeStateMachineConfig},
     chain_storage::BlockchainDatabaseConfig,
     mempool::MempoolConfig,
 };
@@ -45,6 +52,7 @@ use tari_storage::lmdb_store::LMDBConfig;
 
 #[cfg(feature = "metrics")]
 use crate::metrics::MetricsConfig;
+use crate::{backup::DatabaseBackupConfig, shutdown::ShutdownConfig};
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ApplicationConfig {
@@ -68,12 +76,29 @@ impl ApplicationConfig {
         };
 
         config.base_node.set_base_path(config.common.base_path());
+        config.auto_update.set_base_path(config.common.base_path());
         Ok(config)
     }
 
     pub fn network(&self) -> Network {
         self.base_node.network
     }
+
+    /// The effective configuration of every section, as printed by `--print-config`
+    pub fn effective_config<'a>(
+        &self,
+        cfg: &'a Config,
+        origins: &'a ConfigOrigins,
+    ) -> Result<EffectiveConfig<'a>, ConfigurationError> {
+        let mut effective = EffectiveConfig::new(cfg, origins);
+        effective.add_section(&self.common)?;
+        effective.add_section(&self.auto_update)?;
+        effective.add_section(&self.base_node)?;
+        effective.add_section(&self.peer_seeds)?;
+        #[cfg(feature = "metrics")]
+        effective.add_section(&self.metrics)?;
+        Ok(effective)
+    }
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -87,6 +112,15 @@ pub struct BaseNodeConfig {
     pub grpc_enabled: bool,
     /// GRPC address of base node
     pub grpc_address: Option<Multiaddr>,
+    /// GRPC authentication mode, also used for the HTTP gateway event stream
+    pub grpc_authentication: GrpcAuthentication,
+    /// Enable the read-only HTTP/JSON gateway. This only works if the base node was built with the "http" feature.
+    pub http_enabled: bool,
+    /// HTTP gateway address of the base node
+    pub http_address: Multiaddr,
+    /// Enable the GraphQL endpoint on the HTTP gateway. This only works if the base node was built with the
+    /// "graphql" feature.
+    pub graphql_enabled: bool,
     /// A path to the file that stores the base node identity and secret key
     pub identity_file: PathBuf,
     /// Spin up and use a built-in Tor instance. This only works on macos/linux - requires that the wallet was built
@@ -102,6 +136,8 @@ pub struct BaseNodeConfig {
     pub data_dir: PathBuf,
     /// The relative path to store the lmbd data
     pub lmdb_path: PathBuf,
+    /// The scheduled database backup settings
+    pub backup: DatabaseBackupConfig,
     /// The maximum amount of VMs that RandomX will be use
     pub max_randomx_vms: usize,
     /// Bypass range proof verification to speed up validation
@@ -129,6 +165,10 @@ pub struct BaseNodeConfig {
     pub state_machine: BaseNodeStateMachineConfig,
     /// Obscure GRPC error responses
     pub report_grpc_error: bool,
+    /// The shutdown sequence settings
+    pub shutdown: ShutdownConfig,
+    /// The chain split monitor settings
+    pub chain_split_monitor: ChainSplitMonitorConfig,
 }
 
 impl Default for BaseNodeConfig {
@@ -143,6 +183,10 @@ impl Default for BaseNodeConfig {
             network: Network::default(),
             grpc_enabled: true,
             grpc_address: None,
+            grpc_authentication: GrpcAuthentication::default(),
+            http_enabled: false,
+            http_address: "/ip4/127.0.0.1/tcp/18145".parse().unwrap(),
+            graphql_enabled: false,
             identity_file: PathBuf::from("config/base_node_id.json"),
             use_libtor: false,
             tor_identity_file: PathBuf::from("config/base_node_tor_id.json"),
@@ -151,6 +195,7 @@ impl Default for BaseNodeConfig {
             lmdb: Default::default(),
             data_dir: PathBuf::from("data/base_node"),
             lmdb_path: PathBuf::from("db"),
+            backup: Default::default(),
             max_randomx_vms: 5,
             bypass_range_proof_verification: false,
             force_sync_peers: StringList::default(),
@@ -162,6 +207,8 @@ impl Default for BaseNodeConfig {
             metadata_auto_ping_interval: Duration::from_secs(30),
             state_machine: Default::default(),
             report_grpc_error: false,
+            shutdown: Default::default(),
+            chain_split_monitor: Default::default(),
         }
     }
 }
@@ -186,6 +233,12 @@ impl BaseNodeConfig {
         if !self.lmdb_path.is_absolute() {
             self.lmdb_path = self.data_dir.join(self.lmdb_path.as_path());
         }
+        if !self.backup.backup_dir.is_absolute() {
+            self.backup.backup_dir = self.data_dir.join(self.backup.backup_dir.as_path());
+        }
+        if !self.shutdown.mempool_snapshot_file.is_absolute() {
+            self.shutdown.mempool_snapshot_file = self.data_dir.join(self.shutdown.mempool_snapshot_file.as_path());
+        }
         self.p2p.set_base_path(base_path);
     }
 }
@@ -22,6 +22,7 @@
 
 use std::{
     cmp,
+    collections::HashSet,
     convert::{TryFrom, TryInto},
 };
 
@@ -34,26 +35,39 @@ use minotari_app_grpc::{
     tari_rpc::{CalcType, Sorting},
 };
 use minotari_app_utilities::consts;
-use tari_common_types::types::{Commitment, FixedHash, PublicKey, Signature};
+use prost::Message;
+use tari_common_types::{
+    epoch::VnEpoch,
+    types::{FixedHash, PublicKey, Signature},
+};
 use tari_comms::{Bytes, CommsNode};
 use tari_core::{
     base_node::{
-        comms_interface::CommsInterfaceError,
-        state_machine_service::states::StateInfo,
+        chain_metadata_service::PeerChainMetadata,
+        chain_split_monitor::{ChainSplitEvent, ChainSplitMonitorHandle},
+        comms_interface::{BlockEvent, CommsInterfaceError},
+        state_machine_service::{evaluate_sync_candidates, states::PeerMetadata},
         LocalNodeCommsInterface,
         StateMachineHandle,
     },
     blocks::{Block, BlockHeader, NewBlockTemplate},
-    chain_storage::ChainStorageError,
+    chain_storage::{shuffle_validator_committees, validator_committee_seed, ChainStorageError},
     consensus::{emission::Emission, ConsensusManager, NetworkConsensus},
+    covenants::Covenant,
     iterators::NonOverlappingIntegerPairIter,
     mempool::{service::LocalMempoolService, TxStorageResponse},
-    proof_of_work::PowAlgorithm,
-    transactions::transaction_components::Transaction,
+    proof_of_work::{calculate_target_difficulty, PowAlgorithm},
+    transactions::{
+        payment_proof::PaymentProof,
+        transaction_components::{OutputFeatures, RangeProofType, Transaction},
+        weight::WeightCalculator,
+    },
+    validation::ValidationRejection,
 };
 use tari_p2p::{auto_update::SoftwareUpdaterHandle, services::liveness::LivenessHandle};
+use tari_script::{Opcode, TariScript};
 use tari_utilities::{hex::Hex, message_format::MessageFormat, ByteArray};
-use tokio::task;
+use tokio::{sync::broadcast, task};
 use tonic::{Request, Response, Status};
 
 use crate::{
@@ -62,6 +76,7 @@ use crate::{
         blocks::{block_fees, block_heights, block_size, GET_BLOCKS_MAX_HEIGHTS, GET_BLOCKS_PAGE_SIZE},
         hash_rate::HashRateMovingAverage,
         helpers::{mean, median},
+        search::{KernelSearch, UtxoSearch},
     },
 };
 
@@ -73,6 +88,10 @@ const GET_TOKENS_IN_CIRCULATION_PAGE_SIZE: usize = 1_000;
 // number here to keep the node busy
 const GET_DIFFICULTY_MAX_HEIGHTS: u64 = 10_000;
 const GET_DIFFICULTY_PAGE_SIZE: usize = 1_000;
+// The maximum number of headers scanned back from the requested height in one GetTargetDifficulty request while
+// collecting difficulty samples for the requested PoW algorithm
+const GET_TARGET_DIFFICULTY_MAX_SCAN_HEIGHTS: u64 = 10_000;
+const GET_TARGET_DIFFICULTY_PAGE_SIZE: u64 = 100;
 // The maximum number of headers a client can request at a time. If the client requests more than
 // this, this is the maximum that will be returned.
 const LIST_HEADERS_MAX_NUM_HEADERS: u64 = 10_000;
@@ -83,6 +102,14 @@ const LIST_HEADERS_PAGE_SIZE: usize = 10;
 const LIST_HEADERS_DEFAULT_NUM_HEADERS: u64 = 10;
 
 const BLOCK_TIMING_MAX_BLOCKS: u64 = 10_000;
+// The maximum number of blocks that can be scanned in one GetMaturingOutputs request
+const GET_MATURING_OUTPUTS_MAX_HEIGHTS: u64 = 10_000;
+// The maximum number of headers that can be requested in one GetHeadersByHashes request
+const GET_HEADERS_BY_HASHES_MAX_HASHES: usize = 100;
+// The number of chain split events buffered for a slow StreamChainSplitEvents client
+const CHAIN_SPLIT_EVENTS_BUFFER_SIZE: usize = 10;
+// The number of block templates buffered for a slow StreamBlockTemplates client
+const BLOCK_TEMPLATES_BUFFER_SIZE: usize = 4;
 
 pub struct BaseNodeGrpcServer {
     node_service: LocalNodeCommsInterface,
@@ -93,6 +120,7 @@ pub struct BaseNodeGrpcServer {
     software_updater: SoftwareUpdaterHandle,
     comms: CommsNode,
     liveness: LivenessHandle,
+    chain_split_monitor: ChainSplitMonitorHandle,
     report_grpc_error: bool,
 }
 
@@ -107,6 +135,7 @@ impl BaseNodeGrpcServer {
             software_updater: ctx.software_updater(),
             comms: ctx.base_node_comms().clone(),
             liveness: ctx.liveness(),
+            chain_split_monitor: ctx.chain_split_monitor(),
             report_grpc_error: ctx.get_report_grpc_error(),
         }
     }
@@ -125,6 +154,106 @@ pub fn obscure_error_if_true(report: bool, status: Status) -> Status {
     }
 }
 
+/// Converts a block submission error into a status. Validation failures are reported as `InvalidArgument` with the
+/// encoded `ValidationRejection` attached as the status details.
+fn submit_block_error_status(report: bool, err: CommsInterfaceError) -> Status {
+    let status = match err {
+        CommsInterfaceError::ChainStorageError(ChainStorageError::ValidationError { source }) => {
+            let rejection = tari_rpc::ValidationRejection::from(ValidationRejection::from(&source));
+            Status::with_details(
+                tonic::Code::InvalidArgument,
+                source.to_string(),
+                Bytes::from(rejection.encode_to_vec()),
+            )
+        },
+        e => Status::internal(e.to_string()),
+    };
+    obscure_error_if_true(report, status)
+}
+
+/// Scans the blocks in the inclusive height range and streams those that match to the client, stopping once `limit`
+/// blocks have been sent
+async fn stream_matching_blocks<F>(
+    mut handler: LocalNodeCommsInterface,
+    (start_height, end_height): (u64, u64),
+    limit: Option<u64>,
+    is_match: F,
+    mut tx: mpsc::Sender<Result<tari_rpc::HistoricalBlock, Status>>,
+    report_error_flag: bool,
+    request_name: &str,
+) where
+    F: Fn(&Block) -> bool,
+{
+    let page_iter =
+        match NonOverlappingIntegerPairIter::new(start_height, end_height.saturating_add(1), GET_BLOCKS_PAGE_SIZE) {
+            Ok(iter) => iter,
+            Err(err) => {
+                let _ = tx.send(Err(Status::invalid_argument(err))).await;
+                return;
+            },
+        };
+    let mut num_sent = 0u64;
+    for (start, end) in page_iter {
+        let blocks = match handler.get_blocks(start..=end, false).await {
+            Ok(blocks) => blocks,
+            Err(err) => {
+                warn!(target: LOG_TARGET, "Error communicating with local base node: {:?}", err,);
+                let _ = tx
+                    .send(Err(obscure_error_if_true(
+                        report_error_flag,
+                        Status::internal("Internal error when fetching blocks"),
+                    )))
+                    .await;
+                return;
+            },
+        };
+        for block in blocks.into_iter().filter(|b| is_match(b.block())) {
+            let result = block.try_into().map_err(|err| {
+                obscure_error_if_true(
+                    report_error_flag,
+                    Status::internal(format!("Could not provide block:{}", err)),
+                )
+            });
+            if tx.send(result).await.is_err() {
+                warn!(
+                    target: LOG_TARGET,
+                    "[{}] Request was cancelled while sending a response", request_name
+                );
+                return;
+            }
+            num_sent += 1;
+            if limit.map_or(false, |limit| num_sent >= limit) {
+                return;
+            }
+        }
+    }
+}
+
+fn new_block_template_response(
+    template: NewBlockTemplate,
+    algo: PowAlgorithm,
+    initial_sync_achieved: bool,
+) -> Result<tari_rpc::NewBlockTemplateResponse, String> {
+    Ok(tari_rpc::NewBlockTemplateResponse {
+        miner_data: Some(tari_rpc::MinerData {
+            reward: template.reward.into(),
+            target_difficulty: template.target_difficulty.as_u64(),
+            total_fees: template.total_fees.into(),
+            algo: Some(tari_rpc::PowAlgo { pow_algo: algo as i32 }),
+        }),
+        new_block_template: Some(template.try_into()?),
+        initial_sync_achieved,
+    })
+}
+
+fn is_new_tip_event(event: &BlockEvent) -> bool {
+    match event {
+        BlockEvent::ValidBlockAdded(_, result) => result.was_chain_modified(),
+        BlockEvent::BlockSyncComplete(_, _) | BlockEvent::BlockSyncRewind(_) => true,
+        BlockEvent::AddBlockValidationFailed { .. } | BlockEvent::AddBlockErrored { .. } => false,
+    }
+}
+
 pub async fn get_heights(
     request: &tari_rpc::HeightRequest,
     handler: LocalNodeCommsInterface,
@@ -138,6 +267,7 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
     type FetchMatchingUtxosStream = mpsc::Receiver<Result<tari_rpc::FetchMatchingUtxosResponse, Status>>;
     type GetActiveValidatorNodesStream = mpsc::Receiver<Result<tari_rpc::GetActiveValidatorNodesResponse, Status>>;
     type GetBlocksStream = mpsc::Receiver<Result<tari_rpc::HistoricalBlock, Status>>;
+    type GetCirculatingSupplyStream = mpsc::Receiver<Result<tari_rpc::CirculatingSupplyResponse, Status>>;
     type GetMempoolTransactionsStream = mpsc::Receiver<Result<tari_rpc::GetMempoolTransactionsResponse, Status>>;
     type GetNetworkDifficultyStream = mpsc::Receiver<Result<tari_rpc::NetworkDifficultyResponse, Status>>;
     type GetPeersStream = mpsc::Receiver<Result<tari_rpc::GetPeersResponse, Status>>;
@@ -147,6 +277,8 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
     type ListHeadersStream = mpsc::Receiver<Result<tari_rpc::BlockHeaderResponse, Status>>;
     type SearchKernelsStream = mpsc::Receiver<Result<tari_rpc::HistoricalBlock, Status>>;
     type SearchUtxosStream = mpsc::Receiver<Result<tari_rpc::HistoricalBlock, Status>>;
+    type StreamBlockTemplatesStream = mpsc::Receiver<Result<tari_rpc::NewBlockTemplateResponse, Status>>;
+    type StreamChainSplitEventsStream = mpsc::Receiver<Result<tari_rpc::ChainSplitEvent, Status>>;
 
     async fn get_network_difficulty(
         &self,
@@ -252,6 +384,78 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
         Ok(Response::new(rx))
     }
 
+    async fn get_target_difficulty(
+        &self,
+        request: Request<tari_rpc::GetTargetDifficultyRequest>,
+    ) -> Result<Response<tari_rpc::GetTargetDifficultyResponse>, Status> {
+        let report_error_flag = self.report_error_flag();
+        let request = request.into_inner();
+        debug!(
+            target: LOG_TARGET,
+            "Incoming GRPC request for GetTargetDifficulty: algo: {:?} height: {}", request.algo, request.height
+        );
+        let algo = request
+            .algo
+            .map(|algo| u64::try_from(algo.pow_algo))
+            .ok_or_else(|| Status::invalid_argument("PoW algo not provided"))?
+            .map_err(|_| Status::invalid_argument("Invalid PoW algo"))?;
+        let algo = PowAlgorithm::try_from(algo).map_err(|_| Status::invalid_argument("Invalid PoW algo"))?;
+
+        let mut handler = self.node_service.clone();
+        let tip_height = handler
+            .get_metadata()
+            .await
+            .map_err(|e| obscure_error_if_true(report_error_flag, Status::internal(e.to_string())))?
+            .height_of_longest_chain();
+        let height = if request.height == 0 {
+            tip_height.saturating_add(1)
+        } else {
+            request.height
+        };
+        if height > tip_height.saturating_add(1) {
+            return Err(Status::invalid_argument(format!(
+                "Height {} is more than one block above the tip at height {}",
+                height, tip_height
+            )));
+        }
+
+        let constants = self.consensus_rules.consensus_constants(height);
+        let max_samples = constants.difficulty_block_window().saturating_add(1);
+        let scan_floor = height.saturating_sub(GET_TARGET_DIFFICULTY_MAX_SCAN_HEIGHTS);
+        // Samples are collected from the newest block backwards and reversed before calculating
+        let mut samples = Vec::new();
+        let mut end = height;
+        while end > scan_floor && (samples.len() as u64) < max_samples {
+            let start = cmp::max(scan_floor, end.saturating_sub(GET_TARGET_DIFFICULTY_PAGE_SIZE));
+            let headers = handler.get_headers(start..=end - 1).await.map_err(|e| {
+                obscure_error_if_true(
+                    report_error_flag,
+                    Status::internal(format!("Could not provide headers: {}", e)),
+                )
+            })?;
+            samples.extend(
+                headers
+                    .iter()
+                    .rev()
+                    .filter(|h| h.header().pow.pow_algo == algo)
+                    .map(|h| (h.header().timestamp, h.accumulated_data().target_difficulty)),
+            );
+            end = start;
+        }
+        samples.truncate(usize::try_from(max_samples).unwrap_or(usize::MAX));
+        let (timestamps, target_difficulties): (Vec<_>, Vec<_>) = samples.into_iter().rev().unzip();
+
+        let target_difficulty = calculate_target_difficulty(algo, &timestamps, &target_difficulties, constants)
+            .map_err(|e| obscure_error_if_true(report_error_flag, Status::internal(e.to_string())))?;
+
+        debug!(target: LOG_TARGET, "Sending GetTargetDifficulty response to client");
+        Ok(Response::new(tari_rpc::GetTargetDifficultyResponse {
+            target_difficulty: target_difficulty.as_u64(),
+            height,
+            num_samples: timestamps.len() as u64,
+        }))
+    }
+
     async fn get_mempool_transactions(
         &self,
         request: Request<tari_rpc::GetMempoolTransactionsRequest>,
@@ -507,27 +711,93 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
             })?;
 
         let status_watch = self.state_machine_handle.get_status_info_watch();
-        let pow = algo as i32;
-        let response = tari_rpc::NewBlockTemplateResponse {
-            miner_data: Some(tari_rpc::MinerData {
-                reward: new_template.reward.into(),
-                target_difficulty: new_template.target_difficulty.as_u64(),
-                total_fees: new_template.total_fees.into(),
-                algo: Some(tari_rpc::PowAlgo { pow_algo: pow }),
-            }),
-            new_block_template: Some(
-                new_template
-                    .try_into()
-                    .map_err(|e| obscure_error_if_true(report_error_flag, Status::internal(e)))?,
-            ),
-
-            initial_sync_achieved: status_watch.borrow().bootstrapped,
-        };
+        let initial_sync_achieved = status_watch.borrow().bootstrapped;
+        let response = new_block_template_response(new_template, algo, initial_sync_achieved)
+            .map_err(|e| obscure_error_if_true(report_error_flag, Status::internal(e)))?;
 
         debug!(target: LOG_TARGET, "Sending GetNewBlockTemplate response to client");
         Ok(Response::new(response))
     }
 
+    async fn stream_block_templates(
+        &self,
+        request: Request<tari_rpc::NewBlockTemplateRequest>,
+    ) -> Result<Response<Self::StreamBlockTemplatesStream>, Status> {
+        let report_error_flag = self.report_error_flag();
+        let request = request.into_inner();
+        debug!(target: LOG_TARGET, "Incoming GRPC request for StreamBlockTemplates");
+        let algo = request
+            .algo
+            .map(|algo| u64::try_from(algo.pow_algo))
+            .ok_or_else(|| Status::invalid_argument("PoW algo not provided"))?
+            .map_err(|_| Status::invalid_argument("Invalid PoW algo"))?;
+        let algo = PowAlgorithm::try_from(algo).map_err(|_| Status::invalid_argument("Invalid PoW algo"))?;
+        let max_weight = request.max_weight;
+
+        // Subscribe before building the first templates so that no new tip is missed in between
+        let mut block_events = self.node_service.get_block_event_stream();
+        let mut handler = self.node_service.clone();
+        let status_watch = self.state_machine_handle.get_status_info_watch();
+        let (mut tx, rx) = mpsc::channel(BLOCK_TEMPLATES_BUFFER_SIZE);
+        task::spawn(async move {
+            loop {
+                // Header-first mining: the empty template can be mined on the new tip straight away, the full
+                // template follows once its transactions have been selected from the mempool
+                for empty in [true, false] {
+                    let template = if empty {
+                        handler.get_new_empty_block_template(algo).await
+                    } else {
+                        handler.get_new_block_template(algo, max_weight).await
+                    };
+                    let initial_sync_achieved = status_watch.borrow().bootstrapped;
+                    let response = template
+                        .map_err(|e| e.to_string())
+                        .and_then(|template| new_block_template_response(template, algo, initial_sync_achieved));
+                    let response = match response {
+                        Ok(response) => response,
+                        Err(err) => {
+                            warn!(
+                                target: LOG_TARGET,
+                                "[stream_block_templates] Could not get new block template: {}", err
+                            );
+                            let _ = tx
+                                .send(Err(obscure_error_if_true(report_error_flag, Status::internal(err))))
+                                .await;
+                            return;
+                        },
+                    };
+                    if tx.send(Ok(response)).await.is_err() {
+                        debug!(
+                            target: LOG_TARGET,
+                            "[stream_block_templates] Client closed the block template stream"
+                        );
+                        return;
+                    }
+                }
+
+                loop {
+                    match block_events.recv().await {
+                        Ok(event) if is_new_tip_event(&event) => break,
+                        Ok(_) => continue,
+                        Err(broadcast::error::RecvError::Lagged(n)) => {
+                            // One of the missed events may have been a new tip
+                            warn!(
+                                target: LOG_TARGET,
+                                "[stream_block_templates] Subscriber lagged by {} event(s)", n
+                            );
+                            break;
+                        },
+                        Err(broadcast::error::RecvError::Closed) => return,
+                    }
+                }
+                // Templates are always built on the current tip, so any other queued events are covered as well
+                while block_events.try_recv().is_ok() {}
+            }
+        });
+
+        Ok(Response::new(rx))
+    }
+
     async fn get_new_block(
         &self,
         request: Request<tari_rpc::NewBlockTemplate>,
@@ -655,7 +925,7 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
         let block_hash = handler
             .submit_block(block)
             .await
-            .map_err(|e| obscure_error_if_true(report_error_flag, Status::internal(e.to_string())))?
+            .map_err(|e| submit_block_error_status(report_error_flag, e))?
             .to_vec();
 
         debug!(
@@ -669,6 +939,7 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
         &self,
         request: Request<tari_rpc::BlockBlobRequest>,
     ) -> Result<Response<tari_rpc::SubmitBlockResponse>, Status> {
+        let report_error_flag = self.report_error_flag();
         debug!(target: LOG_TARGET, "Received block blob from miner: {:?}", request);
         let request = request.into_inner();
         debug!(target: LOG_TARGET, "request: {:?}", request);
@@ -692,7 +963,7 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
         let block_hash = handler
             .submit_block(block)
             .await
-            .map_err(|e| Status::internal(e.to_string()))?
+            .map_err(|e| submit_block_error_status(report_error_flag, e))?
             .to_vec();
 
         debug!(
@@ -720,6 +991,7 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
             txn.body.outputs().len(),
             txn.body.inputs().len()
         );
+        let excess_sig = txn.first_kernel_excess_sig().cloned();
 
         let mut handler = self.mempool_service.clone();
         let res = handler.submit_transaction(txn).await.map_err(|e| {
@@ -727,20 +999,35 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
             obscure_error_if_true(report_error_flag, Status::internal(e.to_string()))
         })?;
         let response = match res {
-            TxStorageResponse::UnconfirmedPool => tari_rpc::SubmitTransactionResponse {
+            TxStorageResponse::UnconfirmedPool | TxStorageResponse::StemPool => tari_rpc::SubmitTransactionResponse {
                 result: tari_rpc::SubmitTransactionResult::Accepted.into(),
+                rejection: None,
             },
             TxStorageResponse::ReorgPool |
             TxStorageResponse::NotStoredAlreadySpent |
             TxStorageResponse::NotStoredAlreadyMined => tari_rpc::SubmitTransactionResponse {
                 result: tari_rpc::SubmitTransactionResult::AlreadyMined.into(),
+                rejection: None,
+            },
+            TxStorageResponse::NotStored | TxStorageResponse::NotStoredConsensus => {
+                let rejection = match excess_sig {
+                    Some(excess_sig) => handler
+                        .get_transaction_rejection_by_excess_sig(excess_sig)
+                        .await
+                        .map_err(|e| obscure_error_if_true(report_error_flag, Status::internal(e.to_string())))?,
+                    None => None,
+                };
+                tari_rpc::SubmitTransactionResponse {
+                    result: tari_rpc::SubmitTransactionResult::Rejected.into(),
+                    rejection: rejection.map(Into::into),
+                }
             },
-            TxStorageResponse::NotStored |
-            TxStorageResponse::NotStoredOrphan |
-            TxStorageResponse::NotStoredConsensus |
             TxStorageResponse::NotStoredFeeTooLow |
+            TxStorageResponse::NotStoredMempoolFull |
+            TxStorageResponse::NotStoredPeerLimit |
             TxStorageResponse::NotStoredTimeLocked => tari_rpc::SubmitTransactionResponse {
                 result: tari_rpc::SubmitTransactionResult::Rejected.into(),
+                rejection: None,
             },
         };
 
@@ -807,10 +1094,13 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
                                                                             * node does not think it is. */
                 }
             },
+            TxStorageResponse::StemPool |
             TxStorageResponse::NotStored |
             TxStorageResponse::NotStoredConsensus |
             TxStorageResponse::NotStoredOrphan |
             TxStorageResponse::NotStoredFeeTooLow |
+            TxStorageResponse::NotStoredMempoolFull |
+            TxStorageResponse::NotStoredPeerLimit |
             TxStorageResponse::NotStoredTimeLocked |
             TxStorageResponse::NotStoredAlreadyMined => tari_rpc::TransactionStateResponse {
                 result: tari_rpc::TransactionLocation::NotStored.into(),
@@ -954,29 +1244,66 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
     ) -> Result<Response<Self::SearchKernelsStream>, Status> {
         let report_error_flag = self.report_error_flag();
         debug!(target: LOG_TARGET, "Incoming GRPC request for SearchKernels");
-        let request = request.into_inner();
-
-        let kernels = request
-            .signatures
-            .into_iter()
-            .map(|s| s.try_into())
-            .collect::<Result<Vec<_>, _>>()
-            .map_err(|e| Status::invalid_argument(format!("Invalid signatures provided: {}", e)))?;
+        let search = KernelSearch::try_from(request.into_inner()).map_err(Status::invalid_argument)?;
 
         let mut handler = self.node_service.clone();
 
         let (mut tx, rx) = mpsc::channel(GET_BLOCKS_PAGE_SIZE);
+        if !search.is_exact_lookup() {
+            let tip_height = handler
+                .get_metadata()
+                .await
+                .map_err(|e| obscure_error_if_true(report_error_flag, Status::internal(e.to_string())))?
+                .height_of_longest_chain();
+            let height_range = search.scan.height_range(tip_height).map_err(Status::invalid_argument)?;
+            task::spawn(async move {
+                stream_matching_blocks(
+                    handler,
+                    height_range,
+                    search.scan.limit(),
+                    |block| search.is_match(block),
+                    tx,
+                    report_error_flag,
+                    "search_kernels",
+                )
+                .await;
+            });
+            debug!(target: LOG_TARGET, "Sending SearchKernels response stream to client");
+            return Ok(Response::new(rx));
+        }
+
+        let KernelSearch {
+            signatures, excesses, ..
+        } = search;
         task::spawn(async move {
-            let blocks = match handler.get_blocks_with_kernels(kernels).await {
-                Err(err) => {
-                    warn!(
-                        target: LOG_TARGET,
-                        "Error communicating with local base node: {:?}", err,
-                    );
-                    return;
-                },
-                Ok(data) => data,
-            };
+            let mut blocks = Vec::new();
+            if !signatures.is_empty() {
+                match handler.get_blocks_with_kernels(signatures).await {
+                    Err(err) => {
+                        warn!(
+                            target: LOG_TARGET,
+                            "Error communicating with local base node: {:?}", err,
+                        );
+                        return;
+                    },
+                    Ok(data) => blocks.extend(data),
+                }
+            }
+            if !excesses.is_empty() {
+                match handler.get_blocks_with_kernel_excesses(excesses).await {
+                    Err(err) => {
+                        warn!(
+                            target: LOG_TARGET,
+                            "Error communicating with local base node: {:?}", err,
+                        );
+                        return;
+                    },
+                    Ok(data) => blocks.extend(data),
+                }
+            }
+            // A block is only returned once even if it contains several of the requested kernels
+            let mut seen = HashSet::with_capacity(blocks.len());
+            blocks.retain(|block| seen.insert(*block.hash()));
             for block in blocks {
                 let result = block.try_into().map_err(|err| {
                     obscure_error_if_true(
@@ -1004,18 +1331,35 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
     ) -> Result<Response<Self::SearchUtxosStream>, Status> {
         let report_error_flag = self.report_error_flag();
         debug!(target: LOG_TARGET, "Incoming GRPC request for SearchUtxos");
-        let request = request.into_inner();
-
-        let outputs = request
-            .commitments
-            .into_iter()
-            .map(|s| Commitment::from_bytes(&s))
-            .collect::<Result<Vec<_>, _>>()
-            .map_err(|_| Status::invalid_argument("Invalid commitments provided"))?;
+        let search = UtxoSearch::try_from(request.into_inner()).map_err(Status::invalid_argument)?;
 
         let mut handler = self.node_service.clone();
 
         let (mut tx, rx) = mpsc::channel(GET_BLOCKS_PAGE_SIZE);
+        if !search.is_exact_lookup() {
+            let tip_height = handler
+                .get_metadata()
+                .await
+                .map_err(|e| obscure_error_if_true(report_error_flag, Status::internal(e.to_string())))?
+                .height_of_longest_chain();
+            let height_range = search.scan.height_range(tip_height).map_err(Status::invalid_argument)?;
+            task::spawn(async move {
+                stream_matching_blocks(
+                    handler,
+                    height_range,
+                    search.scan.limit(),
+                    |block| search.is_match(block),
+                    tx,
+                    report_error_flag,
+                    "search_utxos",
+                )
+                .await;
+            });
+            debug!(target: LOG_TARGET, "Sending SearchUtxos response stream to client");
+            return Ok(Response::new(rx));
+        }
+
+        let outputs = search.commitments;
         task::spawn(async move {
             let blocks = match handler.fetch_blocks_with_utxos(outputs).await {
                 Err(err) => {
@@ -1279,43 +1623,31 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
         &self,
         _request: Request<tari_rpc::Empty>,
     ) -> Result<Response<tari_rpc::SyncProgressResponse>, Status> {
-        let state = self
-            .state_machine_handle
-            .get_status_info_watch()
-            .borrow()
-            .state_info
-            .clone();
-        let response = match state {
-            StateInfo::HeaderSync(None) => tari_rpc::SyncProgressResponse {
-                tip_height: 0,
-                local_height: 0,
-                state: tari_rpc::SyncState::HeaderStarting.into(),
-            },
-            StateInfo::HeaderSync(Some(info)) => tari_rpc::SyncProgressResponse {
-                tip_height: info.tip_height,
-                local_height: info.local_height,
-                state: tari_rpc::SyncState::Header.into(),
-            },
-            StateInfo::Connecting(_) => tari_rpc::SyncProgressResponse {
-                tip_height: 0,
-                local_height: 0,
-                state: tari_rpc::SyncState::BlockStarting.into(),
-            },
-            StateInfo::BlockSync(info) => tari_rpc::SyncProgressResponse {
-                tip_height: info.tip_height,
-                local_height: info.local_height,
-                state: tari_rpc::SyncState::Block.into(),
-            },
-            _ => tari_rpc::SyncProgressResponse {
-                tip_height: 0,
-                local_height: 0,
-                state: if state.is_synced() {
-                    tari_rpc::SyncState::Done.into()
-                } else {
-                    tari_rpc::SyncState::Startup.into()
-                },
-            },
-        };
+        let report_error_flag = self.report_error_flag();
+        let mut response = tari_rpc::SyncProgressResponse::from(
+            &self.state_machine_handle.get_status_info_watch().borrow().state_info,
+        );
+
+        let mut connectivity = self.comms.connectivity();
+        let mut liveness = self.liveness.clone();
+        let connected_peers = connectivity
+            .get_active_connections()
+            .await
+            .map_err(|err| obscure_error_if_true(report_error_flag, Status::internal(err.to_string())))?;
+        response.peer_latencies = Vec::with_capacity(connected_peers.len());
+        for conn in connected_peers {
+            let latency = liveness
+                .get_avg_latency(conn.peer_node_id().clone())
+                .await
+                .map_err(|err| obscure_error_if_true(report_error_flag, Status::internal(err.to_string())))?;
+            response.peer_latencies.push(tari_rpc::PeerLatency {
+                node_id: conn.peer_node_id().to_vec(),
+                latency_ms: latency
+                    .map(|l| u64::try_from(l.as_millis()).unwrap_or(u64::MAX))
+                    .unwrap_or(0),
+            });
+        }
+
         Ok(Response::new(response))
     }
 
@@ -1380,6 +1712,189 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
         Ok(Response::new(resp))
     }
 
+    async fn get_headers_by_hashes(
+        &self,
+        request: Request<tari_rpc::GetHeadersByHashesRequest>,
+    ) -> Result<Response<tari_rpc::GetHeadersByHashesResponse>, Status> {
+        let report_error_flag = self.report_error_flag();
+        let tari_rpc::GetHeadersByHashesRequest { hashes } = request.into_inner();
+        debug!(
+            target: LOG_TARGET,
+            "Incoming GRPC request for GetHeadersByHashes: {} hash(es)",
+            hashes.len()
+        );
+        if hashes.len() > GET_HEADERS_BY_HASHES_MAX_HASHES {
+            return Err(Status::invalid_argument(format!(
+                "Exceeded the maximum of {} hashes",
+                GET_HEADERS_BY_HASHES_MAX_HASHES
+            )));
+        }
+        let hashes = hashes
+            .into_iter()
+            .map(|hash| {
+                hash.try_into()
+                    .map_err(|_| Status::invalid_argument("Malformed block hash".to_string()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut node_service = self.node_service.clone();
+        let tip_height = node_service
+            .get_metadata()
+            .await
+            .map_err(|err| obscure_error_if_true(report_error_flag, Status::internal(err.to_string())))?
+            .height_of_longest_chain();
+        let mut headers = Vec::with_capacity(hashes.len());
+        for hash in hashes {
+            let chain_header = match node_service
+                .get_header_by_hash(hash)
+                .await
+                .map_err(|err| obscure_error_if_true(report_error_flag, Status::internal(err.to_string())))?
+            {
+                Some(chain_header) => chain_header,
+                None => continue,
+            };
+            let (header, acc_data) = chain_header.into_parts();
+            headers.push(tari_rpc::HeaderWithPowMetadata {
+                confirmations: tip_height.saturating_sub(header.height).saturating_add(1),
+                achieved_difficulty: acc_data.achieved_difficulty.into(),
+                target_difficulty: acc_data.target_difficulty.into(),
+                accumulated_randomx_difficulty: acc_data.accumulated_randomx_difficulty.into(),
+                accumulated_sha3x_difficulty: acc_data.accumulated_sha3x_difficulty.into(),
+                total_accumulated_difficulty: acc_data.total_accumulated_difficulty.to_le_bytes().to_vec(),
+                header: Some(header.into()),
+            });
+        }
+
+        Ok(Response::new(tari_rpc::GetHeadersByHashesResponse { headers }))
+    }
+
+    async fn get_sync_candidates(
+        &self,
+        _: Request<tari_rpc::Empty>,
+    ) -> Result<Response<tari_rpc::GetSyncCandidatesResponse>, Status> {
+        debug!(target: LOG_TARGET, "Incoming GRPC request for GetSyncCandidates");
+        let report_error_flag = self.report_error_flag();
+        let mut connectivity = self.comms.connectivity();
+        let peer_manager = self.comms.peer_manager();
+        let mut liveness = self.liveness.clone();
+        let connected_peers = connectivity
+            .get_active_connections()
+            .await
+            .map_err(|err| obscure_error_if_true(report_error_flag, Status::internal(err.to_string())))?;
+
+        let mut peers = Vec::with_capacity(connected_peers.len());
+        for conn in connected_peers {
+            let peer = peer_manager
+                .find_by_node_id(conn.peer_node_id())
+                .await
+                .map_err(|err| obscure_error_if_true(report_error_flag, Status::internal(err.to_string())))?;
+            // Only peers that have advertised their chain metadata can be evaluated
+            let metadata = peer
+                .as_ref()
+                .and_then(|p| p.get_metadata(1))
+                .and_then(|v| bincode::deserialize::<PeerMetadata>(v).ok());
+            if let Some(metadata) = metadata {
+                let latency = liveness
+                    .get_avg_latency(conn.peer_node_id().clone())
+                    .await
+                    .map_err(|err| obscure_error_if_true(report_error_flag, Status::internal(err.to_string())))?;
+                peers.push(PeerChainMetadata::new(
+                    conn.peer_node_id().clone(),
+                    metadata.metadata,
+                    latency,
+                ));
+            }
+        }
+
+        let local_metadata = self
+            .node_service
+            .clone()
+            .get_metadata()
+            .await
+            .map_err(|err| obscure_error_if_true(report_error_flag, Status::internal(err.to_string())))?;
+        let candidates = evaluate_sync_candidates(&local_metadata, &peers);
+
+        Ok(Response::new(tari_rpc::GetSyncCandidatesResponse {
+            local_metadata: Some(local_metadata.into()),
+            candidates: candidates.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    async fn get_deployment_status(
+        &self,
+        request: Request<tari_rpc::GetDeploymentStatusRequest>,
+    ) -> Result<Response<tari_rpc::GetDeploymentStatusResponse>, Status> {
+        let request = request.into_inner();
+        debug!(target: LOG_TARGET, "Incoming GRPC request for GetDeploymentStatus");
+        let report_error_flag = self.report_error_flag();
+        let mut handler = self.node_service.clone();
+        let height = if request.height == 0 {
+            handler
+                .get_metadata()
+                .await
+                .map_err(|err| obscure_error_if_true(report_error_flag, Status::internal(err.to_string())))?
+                .height_of_longest_chain()
+        } else {
+            request.height
+        };
+
+        let deployments = handler
+            .get_deployment_statuses(height)
+            .await
+            .map_err(|err| obscure_error_if_true(report_error_flag, Status::internal(err.to_string())))?
+            .iter()
+            .map(|status| {
+                tari_rpc::DeploymentStatus::new(&status.deployment, status.state, status.signals_in_current_period)
+            })
+            .collect();
+
+        Ok(Response::new(tari_rpc::GetDeploymentStatusResponse {
+            height,
+            deployments,
+        }))
+    }
+
+    async fn stream_chain_split_events(
+        &self,
+        _: Request<tari_rpc::Empty>,
+    ) -> Result<Response<Self::StreamChainSplitEventsStream>, Status> {
+        debug!(target: LOG_TARGET, "Incoming GRPC request for StreamChainSplitEvents");
+        // Subscribe before reading the current split so that no event is missed in between
+        let mut events = self.chain_split_monitor.get_event_stream();
+        let current_split = self.chain_split_monitor.current_split();
+        let (mut tx, rx) = mpsc::channel(CHAIN_SPLIT_EVENTS_BUFFER_SIZE);
+        task::spawn(async move {
+            if let Some(split) = current_split {
+                let event = tari_rpc::ChainSplitEvent::from(ChainSplitEvent::SplitDetected(split));
+                if tx.send(Ok(event)).await.is_err() {
+                    return;
+                }
+            }
+            loop {
+                let event = match events.recv().await {
+                    Ok(event) => tari_rpc::ChainSplitEvent::from((*event).clone()),
+                    Err(broadcast::error::RecvError::Lagged(n)) => {
+                        warn!(
+                            target: LOG_TARGET,
+                            "[stream_chain_split_events] Subscriber lagged by {} event(s)", n
+                        );
+                        continue;
+                    },
+                    Err(broadcast::error::RecvError::Closed) => return,
+                };
+                if tx.send(Ok(event)).await.is_err() {
+                    debug!(
+                        target: LOG_TARGET,
+                        "[stream_chain_split_events] Client closed the chain split event stream"
+                    );
+                    return;
+                }
+            }
+        });
+
+        Ok(Response::new(rx))
+    }
+
     async fn identify(&self, _: Request<tari_rpc::Empty>) -> Result<Response<tari_rpc::NodeIdentity>, Status> {
         let identity = self.comms.node_identity_ref();
         Ok(Response::new(tari_rpc::NodeIdentity {
@@ -1471,6 +1986,46 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
             unconfirmed_txs: mempool_stats.unconfirmed_txs,
             reorg_txs: mempool_stats.reorg_txs,
             unconfirmed_weight: mempool_stats.unconfirmed_weight,
+            rejected_txs: mempool_stats.rejected_txs,
+            evicted_txs: mempool_stats.evicted_txs,
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn get_mempool_graph(
+        &self,
+        _: Request<tari_rpc::Empty>,
+    ) -> Result<Response<tari_rpc::GetMempoolGraphResponse>, Status> {
+        let report_error_flag = self.report_error_flag();
+        let mut mempool_handle = self.mempool_service.clone();
+
+        let graph = mempool_handle.get_mempool_graph().await.map_err(|e| {
+            error!(target: LOG_TARGET, "Error submitting query:{}", e);
+            obscure_error_if_true(report_error_flag, Status::internal(e.to_string()))
+        })?;
+
+        let response = tari_rpc::GetMempoolGraphResponse {
+            transactions: graph
+                .transactions
+                .into_iter()
+                .map(|tx| tari_rpc::MempoolGraphTransaction {
+                    excess_sig: Some(tx.excess_sig.into()),
+                    weight: tx.weight,
+                    total_fee: tx.total_fee.as_u64(),
+                    fee_per_gram: tx.fee_per_gram,
+                    num_missing_parent_outputs: tx.num_missing_parent_outputs as u64,
+                })
+                .collect(),
+            edges: graph
+                .edges
+                .into_iter()
+                .map(|edge| tari_rpc::MempoolGraphEdge {
+                    parent: edge.parent as u64,
+                    child: edge.child as u64,
+                    output_hash: edge.output_hash.to_vec(),
+                })
+                .collect(),
         };
 
         Ok(Response::new(response))
@@ -1503,6 +2058,65 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
         }
     }
 
+    async fn get_validator_committees(
+        &self,
+        request: Request<tari_rpc::GetValidatorCommitteesRequest>,
+    ) -> Result<Response<tari_rpc::GetValidatorCommitteesResponse>, Status> {
+        let request = request.into_inner();
+        let report_error_flag = self.report_error_flag();
+        debug!(target: LOG_TARGET, "Incoming GRPC request for GetValidatorCommittees");
+        if request.num_committees == 0 {
+            return Err(Status::invalid_argument("num_committees must be greater than zero"));
+        }
+
+        let mut handler = self.node_service.clone();
+        let constants = self.consensus_rules.consensus_constants(request.height);
+        let epoch = constants.block_height_to_epoch(request.height);
+        let previous_epoch_block_hashes = match epoch.as_u64().checked_sub(1) {
+            Some(previous_epoch) => {
+                let start = constants.epoch_to_block_height(VnEpoch(previous_epoch));
+                let end = constants.epoch_to_block_height(epoch).saturating_sub(1);
+                let headers = handler
+                    .get_headers(start..=end)
+                    .await
+                    .map_err(|e| obscure_error_if_true(report_error_flag, Status::internal(e.to_string())))?;
+                if headers.len() as u64 != constants.epoch_length() {
+                    return Err(Status::not_found(format!(
+                        "The blocks of epoch {} were not found",
+                        previous_epoch
+                    )));
+                }
+                headers.iter().map(|header| *header.hash()).collect()
+            },
+            None => Vec::new(),
+        };
+        let committee_seed = validator_committee_seed(epoch, &previous_epoch_block_hashes);
+        let active_validator_nodes = handler
+            .get_active_validator_nodes(request.height)
+            .await
+            .map_err(|e| obscure_error_if_true(report_error_flag, Status::internal(e.to_string())))?;
+
+        let committees =
+            shuffle_validator_committees(active_validator_nodes, request.num_committees as usize, &committee_seed)
+                .into_iter()
+                .map(|members| tari_rpc::ValidatorCommittee {
+                    members: members
+                        .into_iter()
+                        .map(|(public_key, shard_key)| tari_rpc::GetActiveValidatorNodesResponse {
+                            public_key: public_key.to_vec(),
+                            shard_key: shard_key.to_vec(),
+                        })
+                        .collect(),
+                })
+                .collect();
+
+        Ok(Response::new(tari_rpc::GetValidatorCommitteesResponse {
+            epoch: epoch.as_u64(),
+            committee_seed: committee_seed.to_vec(),
+            committees,
+        }))
+    }
+
     async fn get_active_validator_nodes(
         &self,
         request: Request<tari_rpc::GetActiveValidatorNodesRequest>,
@@ -1749,6 +2363,357 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
         );
         Ok(Response::new(rx))
     }
+
+    async fn calculate_transaction_weight(
+        &self,
+        request: Request<tari_rpc::CalculateTransactionWeightRequest>,
+    ) -> Result<Response<tari_rpc::CalculateTransactionWeightResponse>, Status> {
+        let request = request.into_inner();
+        let report_error_flag = self.report_error_flag();
+        debug!(
+            target: LOG_TARGET,
+            "Incoming GRPC request for CalculateTransactionWeight: {} kernel(s), {} input(s), {} output(s)",
+            request.num_kernels,
+            request.num_inputs,
+            request.outputs.len()
+        );
+
+        let num_kernels = usize::try_from(request.num_kernels)
+            .map_err(|_| obscure_error_if_true(report_error_flag, Status::invalid_argument("num_kernels overflow")))?;
+        let num_inputs = usize::try_from(request.num_inputs)
+            .map_err(|_| obscure_error_if_true(report_error_flag, Status::invalid_argument("num_inputs overflow")))?;
+        let weighting = *self
+            .consensus_rules
+            .consensus_constants(request.block_height)
+            .transaction_weight_params();
+        let mut calculator = WeightCalculator::new(weighting)
+            .with_kernels(num_kernels)
+            .with_inputs(num_inputs);
+
+        for output in request.outputs {
+            let features = output
+                .features
+                .map(OutputFeatures::try_from)
+                .transpose()
+                .map_err(|e| obscure_error_if_true(report_error_flag, Status::invalid_argument(e)))?
+                .unwrap_or_default();
+            let script = if output.script.is_empty() {
+                TariScript::default()
+            } else {
+                TariScript::from_bytes(&output.script).map_err(|e| {
+                    obscure_error_if_true(
+                        report_error_flag,
+                        Status::invalid_argument(format!("Invalid script: {}", e)),
+                    )
+                })?
+            };
+            let covenant = if output.covenant.is_empty() {
+                Covenant::default()
+            } else {
+                BorshDeserialize::deserialize(&mut output.covenant.as_slice()).map_err(|e| {
+                    obscure_error_if_true(
+                        report_error_flag,
+                        Status::invalid_argument(format!("Invalid covenant: {}", e)),
+                    )
+                })?
+            };
+            calculator
+                .add_output(&features, &script, &covenant)
+                .map_err(|e| obscure_error_if_true(report_error_flag, Status::internal(e.to_string())))?;
+        }
+
+        let weight = calculator.checked_weight().ok_or_else(|| {
+            obscure_error_if_true(
+                report_error_flag,
+                Status::invalid_argument("Transaction weight overflow"),
+            )
+        })?;
+        let fee = calculator.checked_fee(request.fee_per_gram.into()).ok_or_else(|| {
+            obscure_error_if_true(report_error_flag, Status::invalid_argument("Transaction fee overflow"))
+        })?;
+        Ok(Response::new(tari_rpc::CalculateTransactionWeightResponse {
+            weight,
+            fee: fee.as_u64(),
+        }))
+    }
+
+    async fn verify_payment_proof(
+        &self,
+        request: Request<tari_rpc::PaymentProof>,
+    ) -> Result<Response<tari_rpc::VerifyPaymentProofResponse>, Status> {
+        let report_error_flag = self.report_error_flag();
+        debug!(target: LOG_TARGET, "Incoming GRPC request for VerifyPaymentProof");
+        let proof = PaymentProof::try_from(request.into_inner())
+            .map_err(|e| obscure_error_if_true(report_error_flag, Status::invalid_argument(e)))?;
+
+        if !proof.verify_signature() {
+            return Ok(Response::new(tari_rpc::VerifyPaymentProofResponse {
+                is_signature_valid: false,
+                is_kernel_mined: false,
+            }));
+        }
+
+        let mut handler = self.node_service.clone();
+        let kernels = handler
+            .get_kernel_by_excess_sig(proof.kernel_excess_sig.clone())
+            .await
+            .map_err(|e| {
+                error!(target: LOG_TARGET, "Error {}", e);
+                obscure_error_if_true(report_error_flag, Status::internal(e.to_string()))
+            })?;
+
+        Ok(Response::new(tari_rpc::VerifyPaymentProofResponse {
+            is_signature_valid: true,
+            is_kernel_mined: kernels.iter().any(|k| proof.is_for_kernel(k)),
+        }))
+    }
+
+    async fn get_total_burnt(
+        &self,
+        request: Request<tari_rpc::GetTotalBurntRequest>,
+    ) -> Result<Response<tari_rpc::GetTotalBurntResponse>, Status> {
+        let report_error_flag = self.report_error_flag();
+        let request = request.into_inner();
+        debug!(
+            target: LOG_TARGET,
+            "Incoming GRPC request for GetTotalBurnt: {}-{}", request.start_height, request.end_height
+        );
+        let mut handler = self.node_service.clone();
+        let tip_height = handler
+            .get_metadata()
+            .await
+            .map_err(|e| obscure_error_if_true(report_error_flag, Status::internal(e.to_string())))?
+            .height_of_longest_chain();
+        let end_height = if request.end_height == 0 {
+            tip_height
+        } else {
+            cmp::min(request.end_height, tip_height)
+        };
+        if request.start_height > end_height {
+            return Err(Status::invalid_argument("start_height is greater than end_height"));
+        }
+
+        // The totals are kept as running totals per height, so the range is the difference between the totals at the end
+        // of the range and the totals at the block before it
+        let mut heights = vec![end_height];
+        if let Some(height) = request.start_height.checked_sub(1) {
+            heights.push(height);
+        }
+        let totals = handler.get_burnt_totals(heights).await.map_err(|e| {
+            warn!(target: LOG_TARGET, "Base node service error: {:?}", e);
+            obscure_error_if_true(
+                report_error_flag,
+                Status::internal("Internal error when fetching burnt totals"),
+            )
+        })?;
+        let not_known = |height: u64| {
+            Status::failed_precondition(format!(
+                "The burnt totals at height {} are not known, the node may be pruned",
+                height
+            ))
+        };
+        let mut totals = totals.into_iter();
+        let end_totals = totals.next().flatten().ok_or_else(|| not_known(end_height))?;
+        let range_totals = match request.start_height.checked_sub(1) {
+            Some(height) => end_totals.since(&totals.next().flatten().ok_or_else(|| not_known(height))?),
+            None => end_totals,
+        };
+
+        Ok(Response::new(tari_rpc::GetTotalBurntResponse {
+            revealed_burnt_value: range_totals.revealed_burnt_value.as_u64(),
+            num_burnt_outputs: range_totals.num_burnt_outputs,
+            num_confidential_burnt_outputs: range_totals.num_confidential_burnt_outputs,
+            end_height,
+        }))
+    }
+
+    async fn get_circulating_supply(
+        &self,
+        request: Request<tari_rpc::GetCirculatingSupplyRequest>,
+    ) -> Result<Response<Self::GetCirculatingSupplyStream>, Status> {
+        let report_error_flag = self.report_error_flag();
+        let request = request.into_inner();
+        debug!(target: LOG_TARGET, "Incoming GRPC request for GetCirculatingSupply");
+        let mut handler = self.node_service.clone();
+        let tip_height = handler
+            .get_metadata()
+            .await
+            .map_err(|e| obscure_error_if_true(report_error_flag, Status::internal(e.to_string())))?
+            .height_of_longest_chain();
+
+        let mut heights = if request.heights.is_empty() {
+            let end_height = if request.end_height == 0 {
+                tip_height
+            } else {
+                cmp::min(request.end_height, tip_height)
+            };
+            if request.start_height > end_height {
+                return Err(Status::invalid_argument("start_height is greater than end_height"));
+            }
+            let step = cmp::max(request.step, 1);
+            if (end_height - request.start_height) / step >= GET_TOKENS_IN_CIRCULATION_MAX_HEIGHTS as u64 {
+                return Err(Status::invalid_argument(format!(
+                    "Height range exceeds the maximum of {} heights",
+                    GET_TOKENS_IN_CIRCULATION_MAX_HEIGHTS
+                )));
+            }
+            (request.start_height..=end_height)
+                .step_by(usize::try_from(step).unwrap_or(usize::MAX))
+                .collect::<Vec<_>>()
+        } else {
+            if request.heights.len() > GET_TOKENS_IN_CIRCULATION_MAX_HEIGHTS {
+                return Err(Status::invalid_argument(format!(
+                    "Number of heights exceeds the maximum of {}",
+                    GET_TOKENS_IN_CIRCULATION_MAX_HEIGHTS
+                )));
+            }
+            if let Some(height) = request.heights.iter().find(|h| **h > tip_height) {
+                return Err(Status::invalid_argument(format!(
+                    "Height {} is greater than the tip height {}",
+                    height, tip_height
+                )));
+            }
+            request.heights
+        };
+        heights.sort_unstable();
+        heights.dedup();
+        let consensus_rules = self.consensus_rules.clone();
+
+        let (mut tx, rx) = mpsc::channel(GET_TOKENS_IN_CIRCULATION_PAGE_SIZE);
+        task::spawn(async move {
+            for page in heights.chunks(GET_TOKENS_IN_CIRCULATION_PAGE_SIZE) {
+                let totals = match handler.get_burnt_totals(page.to_vec()).await {
+                    Ok(totals) => totals,
+                    Err(err) => {
+                        warn!(target: LOG_TARGET, "Base node service error: {:?}", err);
+                        let _ = tx
+                            .send(Err(obscure_error_if_true(
+                                report_error_flag,
+                                Status::internal("Internal error when fetching burnt totals"),
+                            )))
+                            .await;
+                        return;
+                    },
+                };
+                for (height, totals) in page.iter().copied().zip(totals) {
+                    let totals = match totals {
+                        Some(totals) => totals,
+                        None => {
+                            let _ = tx
+                                .send(Err(Status::failed_precondition(format!(
+                                    "The burnt totals at height {} are not known, the node may be pruned",
+                                    height
+                                ))))
+                                .await;
+                            return;
+                        },
+                    };
+                    let emitted_supply = consensus_rules.emission_schedule().supply_at_block(height);
+                    let response = tari_rpc::CirculatingSupplyResponse {
+                        height,
+                        emitted_supply: emitted_supply.as_u64(),
+                        revealed_burnt_value: totals.revealed_burnt_value.as_u64(),
+                        circulating_supply: emitted_supply.saturating_sub(totals.revealed_burnt_value).as_u64(),
+                        num_confidential_burnt_outputs: totals.num_confidential_burnt_outputs,
+                    };
+                    if tx.send(Ok(response)).await.is_err() {
+                        warn!(
+                            target: LOG_TARGET,
+                            "[get_circulating_supply] Request was cancelled while sending a response"
+                        );
+                        return;
+                    }
+                }
+            }
+        });
+
+        debug!(target: LOG_TARGET, "Sending GetCirculatingSupply response to client");
+        Ok(Response::new(rx))
+    }
+
+    async fn get_maturing_outputs(
+        &self,
+        request: Request<tari_rpc::GetMaturingOutputsRequest>,
+    ) -> Result<Response<tari_rpc::GetMaturingOutputsResponse>, Status> {
+        let report_error_flag = self.report_error_flag();
+        let request = request.into_inner();
+        debug!(
+            target: LOG_TARGET,
+            "Incoming GRPC request for GetMaturingOutputs from height {}", request.from_height
+        );
+        let script_key = if request.script_key.is_empty() {
+            None
+        } else {
+            Some(
+                PublicKey::from_bytes(&request.script_key)
+                    .map_err(|e| Status::invalid_argument(format!("Invalid script_key: {}", e)))?,
+            )
+        };
+        let mut handler = self.node_service.clone();
+        let tip_height = handler
+            .get_metadata()
+            .await
+            .map_err(|e| obscure_error_if_true(report_error_flag, Status::internal(e.to_string())))?
+            .height_of_longest_chain();
+        if request.from_height > tip_height {
+            return Ok(Response::new(tari_rpc::GetMaturingOutputsResponse {
+                outputs: vec![],
+                tip_height,
+            }));
+        }
+        if tip_height - request.from_height >= GET_MATURING_OUTPUTS_MAX_HEIGHTS {
+            return Err(Status::invalid_argument(format!(
+                "Height range exceeds the maximum of {} blocks",
+                GET_MATURING_OUTPUTS_MAX_HEIGHTS
+            )));
+        }
+
+        let page_iter =
+            NonOverlappingIntegerPairIter::new(request.from_height, tip_height.saturating_add(1), GET_BLOCKS_PAGE_SIZE)
+                .map_err(Status::invalid_argument)?;
+        let mut outputs = Vec::new();
+        for (start, end) in page_iter {
+            let headers = handler
+                .get_headers(start..=end)
+                .await
+                .map_err(|e| obscure_error_if_true(report_error_flag, Status::internal(e.to_string())))?;
+            for header in headers {
+                let unspent = handler
+                    .fetch_unspent_utxos_in_block(*header.hash())
+                    .await
+                    .map_err(|e| obscure_error_if_true(report_error_flag, Status::internal(e.to_string())))?;
+                let maturing = unspent
+                    .into_iter()
+                    .filter(|o| o.features.maturity > tip_height)
+                    .filter(|o| {
+                        script_key.as_ref().map_or(true, |key| {
+                            o.script
+                                .as_slice()
+                                .iter()
+                                .any(|op| matches!(op, Opcode::PushPubKey(k) if k.as_ref() == key))
+                        })
+                    })
+                    .map(|o| tari_rpc::MaturingOutput {
+                        commitment: o.commitment.to_vec(),
+                        output_hash: o.hash().to_vec(),
+                        mined_height: header.height(),
+                        maturity: o.features.maturity,
+                        is_coinbase: o.is_coinbase(),
+                        revealed_value: if o.features.range_proof_type == RangeProofType::RevealedValue {
+                            o.minimum_value_promise.as_u64()
+                        } else {
+                            0
+                        },
+                    });
+                outputs.extend(maturing);
+            }
+        }
+
+        Ok(Response::new(tari_rpc::GetMaturingOutputsResponse {
+            outputs,
+            tip_height,
+        }))
+    }
 }
 
 enum BlockGroupType {
@@ -0,0 +1,341 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{cmp, convert::TryFrom};
+
+use blake2::{digest::consts::U32, Blake2b};
+use minotari_app_grpc::tari_rpc;
+use tari_common_types::types::{Commitment, Signature};
+use tari_core::{
+    blocks::Block,
+    transactions::transaction_components::{TransactionKernel, TransactionOutput},
+};
+use tari_utilities::ByteArray;
+
+// The maximum number of blocks that are scanned in one SearchKernels or SearchUtxos request. Requests for a larger
+// height range are truncated to this many blocks, so that clients can page through the chain.
+pub const SEARCH_MAX_HEIGHTS: u64 = 10_000;
+// Public nonces and script hashes are both 32 bytes, so longer prefixes can never match
+const MAX_PREFIX_LEN: usize = 32;
+
+/// The blocks that a SearchKernels or SearchUtxos request scans
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BlockScan {
+    start_height: u64,
+    end_height: u64,
+    from_timestamp: u64,
+    to_timestamp: u64,
+    limit: u64,
+}
+
+impl BlockScan {
+    fn new(
+        start_height: u64,
+        end_height: u64,
+        from_timestamp: u64,
+        to_timestamp: u64,
+        limit: u64,
+    ) -> Result<Self, String> {
+        if end_height != 0 && start_height > end_height {
+            return Err("start_height is greater than end_height".to_string());
+        }
+        if to_timestamp != 0 && from_timestamp > to_timestamp {
+            return Err("from_timestamp is greater than to_timestamp".to_string());
+        }
+        Ok(Self {
+            start_height,
+            end_height,
+            from_timestamp,
+            to_timestamp,
+            limit,
+        })
+    }
+
+    fn is_unbounded(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Returns the inclusive height range to scan given the current tip height, truncated to [SEARCH_MAX_HEIGHTS]
+    /// blocks
+    pub fn height_range(&self, tip_height: u64) -> Result<(u64, u64), String> {
+        let end_height = if self.end_height == 0 {
+            tip_height
+        } else {
+            cmp::min(self.end_height, tip_height)
+        };
+        if self.start_height > end_height {
+            return Err(format!(
+                "start_height {} is greater than the chain tip {}",
+                self.start_height, tip_height
+            ));
+        }
+        let end_height = cmp::min(end_height, self.start_height.saturating_add(SEARCH_MAX_HEIGHTS - 1));
+        Ok((self.start_height, end_height))
+    }
+
+    /// The maximum number of blocks to return, or `None` if all matching blocks should be returned
+    pub fn limit(&self) -> Option<u64> {
+        if self.limit == 0 {
+            None
+        } else {
+            Some(self.limit)
+        }
+    }
+
+    fn includes_timestamp(&self, timestamp: u64) -> bool {
+        timestamp >= self.from_timestamp && (self.to_timestamp == 0 || timestamp <= self.to_timestamp)
+    }
+}
+
+fn validate_prefixes(prefixes: &[Vec<u8>], name: &str) -> Result<(), String> {
+    match prefixes
+        .iter()
+        .find(|prefix| prefix.is_empty() || prefix.len() > MAX_PREFIX_LEN)
+    {
+        Some(prefix) => Err(format!(
+            "Each {} must be between 1 and {} bytes, got {} bytes",
+            name,
+            MAX_PREFIX_LEN,
+            prefix.len()
+        )),
+        None => Ok(()),
+    }
+}
+
+/// A parsed SearchKernels request
+#[derive(Debug, Clone)]
+pub struct KernelSearch {
+    pub signatures: Vec<Signature>,
+    pub excesses: Vec<Commitment>,
+    public_nonce_prefixes: Vec<Vec<u8>>,
+    pub scan: BlockScan,
+}
+
+impl KernelSearch {
+    /// Returns true if the request only contains complete excess signatures or excesses, which can be looked up in the
+    /// kernel indexes without scanning blocks
+    pub fn is_exact_lookup(&self) -> bool {
+        self.public_nonce_prefixes.is_empty() && self.scan.is_unbounded()
+    }
+
+    /// Returns true if the block is in the requested time range and contains a matching kernel
+    pub fn is_match(&self, block: &Block) -> bool {
+        self.scan.includes_timestamp(block.header.timestamp.as_u64()) &&
+            block
+                .body
+                .kernels()
+                .iter()
+                .any(|kernel| self.is_matching_kernel(kernel))
+    }
+
+    fn is_matching_kernel(&self, kernel: &TransactionKernel) -> bool {
+        let nonce = kernel.excess_sig.get_public_nonce().as_bytes();
+        self.signatures.iter().any(|sig| kernel.excess_sig == *sig) ||
+            self.excesses.iter().any(|excess| kernel.excess == *excess) ||
+            self.public_nonce_prefixes
+                .iter()
+                .any(|prefix| nonce.starts_with(prefix))
+    }
+}
+
+impl TryFrom<tari_rpc::SearchKernelsRequest> for KernelSearch {
+    type Error = String;
+
+    fn try_from(request: tari_rpc::SearchKernelsRequest) -> Result<Self, Self::Error> {
+        let signatures = request
+            .signatures
+            .into_iter()
+            .map(Signature::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Invalid signatures provided: {}", e))?;
+        let excesses = request
+            .excesses
+            .iter()
+            .map(|e| Commitment::from_bytes(e))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| "Invalid excesses provided".to_string())?;
+        validate_prefixes(&request.public_nonce_prefixes, "public_nonce_prefix")?;
+        Ok(Self {
+            signatures,
+            excesses,
+            public_nonce_prefixes: request.public_nonce_prefixes,
+            scan: BlockScan::new(
+                request.start_height,
+                request.end_height,
+                request.from_timestamp,
+                request.to_timestamp,
+                request.limit,
+            )?,
+        })
+    }
+}
+
+/// A parsed SearchUtxos request
+#[derive(Debug, Clone)]
+pub struct UtxoSearch {
+    pub commitments: Vec<Commitment>,
+    script_hashes: Vec<Vec<u8>>,
+    pub scan: BlockScan,
+}
+
+impl UtxoSearch {
+    /// Returns true if the request only contains complete commitments, which can be looked up in the output index
+    /// without scanning blocks
+    pub fn is_exact_lookup(&self) -> bool {
+        self.script_hashes.is_empty() && self.scan.is_unbounded()
+    }
+
+    /// Returns true if the block is in the requested time range and contains a matching output
+    pub fn is_match(&self, block: &Block) -> bool {
+        self.scan.includes_timestamp(block.header.timestamp.as_u64()) &&
+            block
+                .body
+                .outputs()
+                .iter()
+                .any(|output| self.is_matching_output(output))
+    }
+
+    fn is_matching_output(&self, output: &TransactionOutput) -> bool {
+        if self.commitments.iter().any(|c| output.commitment == *c) {
+            return true;
+        }
+        if self.script_hashes.is_empty() {
+            return false;
+        }
+        match output.script.as_hash::<Blake2b<U32>>() {
+            Ok(hash) => self.script_hashes.iter().any(|h| hash.as_slice() == h.as_slice()),
+            Err(_) => false,
+        }
+    }
+}
+
+impl TryFrom<tari_rpc::SearchUtxosRequest> for UtxoSearch {
+    type Error = String;
+
+    fn try_from(request: tari_rpc::SearchUtxosRequest) -> Result<Self, Self::Error> {
+        let commitments = request
+            .commitments
+            .iter()
+            .map(|c| Commitment::from_bytes(c))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| "Invalid commitments provided".to_string())?;
+        if let Some(hash) = request.script_hashes.iter().find(|h| h.len() != MAX_PREFIX_LEN) {
+            return Err(format!(
+                "Each script_hash must be {} bytes, got {} bytes",
+                MAX_PREFIX_LEN,
+                hash.len()
+            ));
+        }
+        Ok(Self {
+            commitments,
+            script_hashes: request.script_hashes,
+            scan: BlockScan::new(
+                request.start_height,
+                request.end_height,
+                request.from_timestamp,
+                request.to_timestamp,
+                request.limit,
+            )?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn height_range_is_truncated_to_the_tip_and_max_heights() {
+        let scan = BlockScan::new(10, 0, 0, 0, 0).unwrap();
+        assert_eq!(scan.height_range(100), Ok((10, 100)));
+        assert_eq!(scan.height_range(100_000), Ok((10, 10 + SEARCH_MAX_HEIGHTS - 1)));
+        assert!(scan.height_range(5).is_err());
+
+        let scan = BlockScan::new(10, 20, 0, 0, 0).unwrap();
+        assert_eq!(scan.height_range(100), Ok((10, 20)));
+        assert!(BlockScan::new(20, 10, 0, 0, 0).is_err());
+    }
+
+    #[test]
+    fn timestamps_outside_the_range_are_excluded() {
+        let scan = BlockScan::new(0, 0, 100, 200, 0).unwrap();
+        assert!(!scan.includes_timestamp(99));
+        assert!(scan.includes_timestamp(100));
+        assert!(scan.includes_timestamp(200));
+        assert!(!scan.includes_timestamp(201));
+
+        let scan = BlockScan::new(0, 0, 100, 0, 0).unwrap();
+        assert!(scan.includes_timestamp(u64::MAX));
+        assert!(BlockScan::new(0, 0, 200, 100, 0).is_err());
+    }
+
+    #[test]
+    fn signature_only_requests_are_exact_lookups() {
+        let search = KernelSearch::try_from(tari_rpc::SearchKernelsRequest::default()).unwrap();
+        assert!(search.is_exact_lookup());
+
+        let search = KernelSearch::try_from(tari_rpc::SearchKernelsRequest {
+            public_nonce_prefixes: vec![vec![1, 2, 3]],
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(!search.is_exact_lookup());
+
+        let search = KernelSearch::try_from(tari_rpc::SearchKernelsRequest {
+            excesses: vec![Commitment::default().to_vec()],
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(search.is_exact_lookup());
+        assert!(KernelSearch::try_from(tari_rpc::SearchKernelsRequest {
+            excesses: vec![vec![1; 31]],
+            ..Default::default()
+        })
+        .is_err());
+
+        let search = UtxoSearch::try_from(tari_rpc::SearchUtxosRequest {
+            end_height: 100,
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(!search.is_exact_lookup());
+    }
+
+    #[test]
+    fn invalid_prefixes_and_script_hashes_are_rejected() {
+        assert!(KernelSearch::try_from(tari_rpc::SearchKernelsRequest {
+            public_nonce_prefixes: vec![vec![]],
+            ..Default::default()
+        })
+        .is_err());
+        assert!(KernelSearch::try_from(tari_rpc::SearchKernelsRequest {
+            public_nonce_prefixes: vec![vec![0; 33]],
+            ..Default::default()
+        })
+        .is_err());
+        assert!(UtxoSearch::try_from(tari_rpc::SearchUtxosRequest {
+            script_hashes: vec![vec![0; 31]],
+            ..Default::default()
+        })
+        .is_err());
+    }
+}
@@ -0,0 +1,100 @@
+//  Copyright 2023, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use log::*;
+use serde::Serialize;
+use tari_core::{base_node::comms_interface::CommsInterfaceError, mempool::service::MempoolServiceError};
+use thiserror::Error;
+
+const LOG_TARGET: &str = "minotari::base_node::http";
+
+#[derive(Debug, Error)]
+pub enum HttpGatewayError {
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+    #[error("Not found: {0}")]
+    NotFound(String),
+    #[error("Auth failed")]
+    Unauthenticated,
+    #[error("Base node service error: {0}")]
+    CommsInterfaceError(#[from] CommsInterfaceError),
+    #[error("Mempool service error: {0}")]
+    MempoolServiceError(#[from] MempoolServiceError),
+}
+
+impl HttpGatewayError {
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            HttpGatewayError::InvalidArgument(_) => StatusCode::BAD_REQUEST,
+            HttpGatewayError::NotFound(_) => StatusCode::NOT_FOUND,
+            HttpGatewayError::Unauthenticated => StatusCode::UNAUTHORIZED,
+            HttpGatewayError::CommsInterfaceError(_) | HttpGatewayError::MempoolServiceError(_) => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            },
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ErrorBody {
+    error: String,
+}
+
+impl IntoResponse for HttpGatewayError {
+    fn into_response(self) -> Response {
+        let status = self.status_code();
+        // Internal errors are logged rather than returned, the gateway is intended for public consumption
+        let error = if status.is_server_error() {
+            error!(target: LOG_TARGET, "HTTP gateway request failed: {}", self);
+            "Internal error".to_string()
+        } else {
+            self.to_string()
+        };
+        (status, Json(ErrorBody { error })).into_response()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_maps_errors_to_status_codes() {
+        assert_eq!(
+            HttpGatewayError::InvalidArgument("bad".to_string()).status_code(),
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            HttpGatewayError::NotFound("block".to_string()).status_code(),
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(
+            HttpGatewayError::from(CommsInterfaceError::UnexpectedApiResponse).status_code(),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+}
@@ -0,0 +1,216 @@
+//  Copyright 2023, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::time::Duration;
+
+use axum::extract::ws::{Message, WebSocket};
+use log::*;
+use serde::Serialize;
+use tari_common_types::{chain_metadata::ChainMetadata, types::FixedHash};
+use tari_core::{
+    base_node::comms_interface::BlockEvent,
+    blocks::ChainBlock,
+    chain_storage::BlockAddResult,
+    mempool::StatsResponse,
+    proof_of_work::PowAlgorithm,
+};
+use tokio::{sync::broadcast::error::RecvError, time};
+
+use crate::http::HttpGatewayState;
+
+const LOG_TARGET: &str = "minotari::base_node::http::events";
+
+/// The mempool does not publish events, so the bridge polls its stats and only sends changes
+const MEMPOOL_STATS_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// An event sent to WebSocket subscribers, JSON encoded and tagged by `type`
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GatewayEvent {
+    /// The chain tip changed
+    Tip { metadata: ChainMetadata },
+    /// A block was added to the main chain
+    NewBlock { block: BlockSummary },
+    /// The main chain was reorged. `added` is ordered from lowest to highest height, `removed` from highest to lowest.
+    Reorg {
+        added: Vec<BlockSummary>,
+        removed: Vec<BlockSummary>,
+    },
+    /// The mempool stats changed
+    Mempool { stats: StatsResponse },
+    /// The subscriber fell behind and `skipped` block events were dropped
+    Lagged { skipped: u64 },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BlockSummary {
+    pub height: u64,
+    pub hash: FixedHash,
+    pub timestamp: u64,
+    pub pow_algo: PowAlgorithm,
+    pub num_inputs: usize,
+    pub num_outputs: usize,
+    pub num_kernels: usize,
+}
+
+impl From<&ChainBlock> for BlockSummary {
+    fn from(chain_block: &ChainBlock) -> Self {
+        let block = chain_block.block();
+        Self {
+            height: chain_block.height(),
+            hash: *chain_block.hash(),
+            timestamp: block.header.timestamp.as_u64(),
+            pow_algo: block.header.pow.pow_algo,
+            num_inputs: block.body.inputs().len(),
+            num_outputs: block.body.outputs().len(),
+            num_kernels: block.body.kernels().len(),
+        }
+    }
+}
+
+/// Returns the gateway events to send for the given block event, and whether it changed the chain tip
+fn block_event_to_gateway_events(event: &BlockEvent) -> (Vec<GatewayEvent>, bool) {
+    match event {
+        BlockEvent::ValidBlockAdded(_, BlockAddResult::Ok(chain_block)) => (
+            vec![GatewayEvent::NewBlock {
+                block: chain_block.as_ref().into(),
+            }],
+            true,
+        ),
+        BlockEvent::ValidBlockAdded(_, BlockAddResult::ChainReorg { added, removed }) => (
+            vec![GatewayEvent::Reorg {
+                added: added.iter().map(|b| b.as_ref().into()).collect(),
+                removed: removed.iter().map(|b| b.as_ref().into()).collect(),
+            }],
+            true,
+        ),
+        BlockEvent::BlockSyncComplete(..) | BlockEvent::BlockSyncRewind(_) => (vec![], true),
+        _ => (vec![], false),
+    }
+}
+
+async fn send_event(socket: &mut WebSocket, event: &GatewayEvent) -> Result<(), axum::Error> {
+    // Serializing these types cannot fail
+    let json = serde_json::to_string(event).map_err(axum::Error::new)?;
+    socket.send(Message::Text(json)).await
+}
+
+async fn tip_event(state: &mut HttpGatewayState) -> Option<GatewayEvent> {
+    match state.node_service.get_metadata().await {
+        Ok(metadata) => Some(GatewayEvent::Tip { metadata }),
+        Err(err) => {
+            warn!(target: LOG_TARGET, "Failed to fetch chain metadata: {}", err);
+            None
+        },
+    }
+}
+
+async fn mempool_event(state: &mut HttpGatewayState, last_stats: &mut Option<StatsResponse>) -> Option<GatewayEvent> {
+    match state.mempool_service.get_mempool_stats().await {
+        Ok(stats) if last_stats.as_ref() != Some(&stats) => {
+            *last_stats = Some(stats.clone());
+            Some(GatewayEvent::Mempool { stats })
+        },
+        Ok(_) => None,
+        Err(err) => {
+            warn!(target: LOG_TARGET, "Failed to fetch mempool stats: {}", err);
+            None
+        },
+    }
+}
+
+/// Streams gateway events to the subscriber until it disconnects or the node shuts down
+pub async fn stream_events(mut socket: WebSocket, mut state: HttpGatewayState) {
+    debug!(target: LOG_TARGET, "New event subscriber connected");
+    let mut block_events = state.node_service.get_block_event_stream();
+    let mut mempool_poll = time::interval(MEMPOOL_STATS_POLL_INTERVAL);
+    let mut last_stats = None;
+    let mut shutdown = state.shutdown.clone();
+
+    // Subscribers always start with the current tip
+    let mut pending = tip_event(&mut state).await.into_iter().collect::<Vec<_>>();
+    loop {
+        for event in pending.drain(..) {
+            if let Err(err) = send_event(&mut socket, &event).await {
+                debug!(target: LOG_TARGET, "Event subscriber disconnected: {}", err);
+                return;
+            }
+        }
+
+        tokio::select! {
+            event = block_events.recv() => match event {
+                Ok(event) => {
+                    let (events, tip_changed) = block_event_to_gateway_events(&event);
+                    pending = events;
+                    if tip_changed {
+                        pending.extend(tip_event(&mut state).await);
+                    }
+                },
+                Err(RecvError::Lagged(skipped)) => {
+                    pending.push(GatewayEvent::Lagged { skipped });
+                    pending.extend(tip_event(&mut state).await);
+                },
+                Err(RecvError::Closed) => break,
+            },
+            _ = mempool_poll.tick() => {
+                pending.extend(mempool_event(&mut state, &mut last_stats).await);
+            },
+            msg = socket.recv() => match msg {
+                // Subscribers are not expected to send anything, pings are answered by the websocket implementation
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {},
+            },
+            _ = shutdown.wait() => break,
+        }
+    }
+
+    debug!(target: LOG_TARGET, "Event subscriber stream ended");
+    let _result = socket.close().await;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_serializes_tagged_events() {
+        let event = GatewayEvent::Lagged { skipped: 3 };
+        assert_eq!(
+            serde_json::to_string(&event).unwrap(),
+            r#"{"type":"lagged","skipped":3}"#
+        );
+
+        let event = GatewayEvent::Mempool {
+            stats: StatsResponse {
+                unconfirmed_txs: 1,
+                reorg_txs: 0,
+                unconfirmed_weight: 10,
+                rejected_txs: 2,
+                evicted_txs: 1,
+            },
+        };
+        assert_eq!(
+            serde_json::to_string(&event).unwrap(),
+            r#"{"type":"mempool","stats":{"unconfirmed_txs":1,"reorg_txs":0,"unconfirmed_weight":10,"rejected_txs":2,"evicted_txs":1}}"#
+        );
+    }
+}
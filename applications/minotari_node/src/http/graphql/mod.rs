@@ -0,0 +1,81 @@
+//  Copyright 2023, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! A GraphQL query surface over the chain database, served at `/graphql`. Nested fields (e.g. the kernels and
+//! outputs of a block) are only loaded when selected and lists are paginated using connections with opaque cursors.
+
+mod pagination;
+
+mod types;
+
+use async_graphql::{EmptyMutation, EmptySubscription, Schema};
+use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
+use axum::{extract::State, routing::post, Router};
+use tari_core::chain_storage::{AsyncBlockchainDb, LMDBDatabase};
+
+use crate::http::graphql::types::QueryRoot;
+
+pub type ChainDb = AsyncBlockchainDb<LMDBDatabase>;
+
+pub type ChainSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Bounds the cost of a single query, since nested selections can fan out to many database reads
+const MAX_QUERY_DEPTH: usize = 8;
+const MAX_QUERY_COMPLEXITY: usize = 2_000;
+
+pub fn create_schema(db: ChainDb) -> ChainSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(db)
+        .limit_depth(MAX_QUERY_DEPTH)
+        .limit_complexity(MAX_QUERY_COMPLEXITY)
+        .finish()
+}
+
+async fn graphql_handler(State(schema): State<ChainSchema>, request: GraphQLRequest) -> GraphQLResponse {
+    schema.execute(request.into_inner()).await.into()
+}
+
+pub fn create_router<S>(schema: ChainSchema) -> Router<S>
+where S: Clone + Send + Sync + 'static {
+    Router::new()
+        .route("/graphql", post(graphql_handler))
+        .with_state(schema)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_exports_the_schema() {
+        let sdl = create_schema_sdl();
+        assert!(sdl.contains("type QueryRoot"));
+        assert!(sdl.contains("KernelConnection!"));
+        assert!(sdl.contains("OutputConnection!"));
+    }
+
+    fn create_schema_sdl() -> String {
+        Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+            .finish()
+            .sdl()
+    }
+}
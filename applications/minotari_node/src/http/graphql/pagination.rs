@@ -0,0 +1,137 @@
+//  Copyright 2023, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{convert::TryFrom, fmt::Display, num::ParseIntError, ops::Range};
+
+use async_graphql::connection::CursorType;
+
+/// The default number of nodes returned if neither `first` nor `last` are given
+pub const DEFAULT_PAGE_SIZE: u64 = 20;
+/// The maximum number of nodes that can be requested in a single page
+pub const MAX_PAGE_SIZE: u64 = 100;
+
+/// An opaque cursor holding a position (a block height, or an index within a block)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PositionCursor(pub u64);
+
+impl CursorType for PositionCursor {
+    type Error = ParseIntError;
+
+    fn decode_cursor(s: &str) -> Result<Self, Self::Error> {
+        s.parse().map(PositionCursor)
+    }
+
+    fn encode_cursor(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Page {
+    pub range: Range<u64>,
+    pub has_previous_page: bool,
+    pub has_next_page: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageSizeExceeded(u64);
+
+impl Display for PageSizeExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Requested page size {} exceeds the maximum of {}",
+            self.0, MAX_PAGE_SIZE
+        )
+    }
+}
+
+/// Resolves the connection arguments to a page of the positions `0..total`. `after` and `before` are exclusive.
+/// If `first` is given the page starts after `after`, otherwise if `last` is given it ends before `before`.
+pub fn resolve_page(
+    after: Option<PositionCursor>,
+    before: Option<PositionCursor>,
+    first: Option<usize>,
+    last: Option<usize>,
+    total: u64,
+) -> Result<Page, PageSizeExceeded> {
+    let first = first.map(|n| u64::try_from(n).unwrap_or(u64::MAX));
+    let last = last.map(|n| u64::try_from(n).unwrap_or(u64::MAX));
+    if let Some(n) = first.or(last).filter(|n| *n > MAX_PAGE_SIZE) {
+        return Err(PageSizeExceeded(n));
+    }
+
+    let mut start = after.map(|c| c.0.saturating_add(1)).unwrap_or(0).min(total);
+    let mut end = before.map(|c| c.0).unwrap_or(total).clamp(start, total);
+    match (first, last) {
+        (Some(n), _) => end = end.min(start.saturating_add(n)),
+        (None, Some(n)) => start = start.max(end.saturating_sub(n)),
+        (None, None) => end = end.min(start.saturating_add(DEFAULT_PAGE_SIZE)),
+    }
+
+    Ok(Page {
+        range: start..end,
+        has_previous_page: start > 0,
+        has_next_page: end < total,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_pages_forwards() {
+        let page = resolve_page(None, None, Some(10), None, 25).unwrap();
+        assert_eq!(page.range, 0..10);
+        assert!(!page.has_previous_page);
+        assert!(page.has_next_page);
+
+        let page = resolve_page(Some(PositionCursor(19)), None, Some(10), None, 25).unwrap();
+        assert_eq!(page.range, 20..25);
+        assert!(page.has_previous_page);
+        assert!(!page.has_next_page);
+    }
+
+    #[test]
+    fn it_pages_backwards() {
+        let page = resolve_page(None, None, None, Some(10), 25).unwrap();
+        assert_eq!(page.range, 15..25);
+
+        let page = resolve_page(None, Some(PositionCursor(5)), None, Some(10), 25).unwrap();
+        assert_eq!(page.range, 0..5);
+        assert!(!page.has_previous_page);
+        assert!(page.has_next_page);
+    }
+
+    #[test]
+    fn it_applies_defaults_and_limits() {
+        let page = resolve_page(None, None, None, None, 1000).unwrap();
+        assert_eq!(page.range, 0..DEFAULT_PAGE_SIZE);
+        assert!(resolve_page(None, None, Some(101), None, 1000).is_err());
+
+        let page = resolve_page(Some(PositionCursor(1000)), None, Some(10), None, 25).unwrap();
+        assert!(page.range.is_empty());
+        assert_eq!(PositionCursor::decode_cursor("42").unwrap(), PositionCursor(42));
+        assert!(PositionCursor::decode_cursor("abc").is_err());
+    }
+}
@@ -0,0 +1,482 @@
+//  Copyright 2023, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::convert::TryFrom;
+
+use async_graphql::{
+    connection::{query, Connection, Edge},
+    Context,
+    Error,
+    Object,
+    OutputType,
+    Result,
+    SimpleObject,
+};
+use tari_common_types::{
+    chain_metadata::ChainMetadata,
+    types::{Commitment, FixedHash},
+};
+use tari_core::{
+    blocks::BlockHeader,
+    chain_storage::PrunedOutput,
+    transactions::transaction_components::TransactionKernel,
+};
+use tari_utilities::hex::Hex;
+
+use crate::http::{
+    graphql::{
+        pagination::{resolve_page, Page, PositionCursor},
+        ChainDb,
+    },
+    handlers::parse_excess_sig,
+};
+
+fn chain_db(ctx: &Context<'_>) -> Result<ChainDb> {
+    ctx.data::<ChainDb>().cloned()
+}
+
+fn build_connection<N, I>(page: &Page, nodes: I) -> Connection<PositionCursor, N>
+where
+    N: OutputType,
+    I: IntoIterator<Item = (u64, N)>,
+{
+    let mut connection = Connection::new(page.has_previous_page, page.has_next_page);
+    connection.edges.extend(
+        nodes
+            .into_iter()
+            .map(|(pos, node)| Edge::new(PositionCursor(pos), node)),
+    );
+    connection
+}
+
+/// Pages over items that are already loaded in full, e.g. the kernels of a single block
+fn paginate<T, N, F>(
+    items: Vec<T>,
+    after: Option<PositionCursor>,
+    before: Option<PositionCursor>,
+    first: Option<usize>,
+    last: Option<usize>,
+    to_node: F,
+) -> Result<Connection<PositionCursor, N>>
+where
+    N: OutputType,
+    F: Fn(T) -> N,
+{
+    let total = u64::try_from(items.len()).unwrap_or(u64::MAX);
+    let page = resolve_page(after, before, first, last, total)?;
+    let nodes = items
+        .into_iter()
+        .zip(0u64..)
+        .filter(|(_, pos)| page.range.contains(pos))
+        .map(|(item, pos)| (pos, to_node(item)));
+    Ok(build_connection(&page, nodes))
+}
+
+async fn fetch_header(db: &ChainDb, height: Option<u64>, hash: Option<String>) -> Result<Option<BlockHeader>> {
+    match (height, hash) {
+        (Some(height), None) => Ok(db.fetch_header(height).await?),
+        (None, Some(hash)) => {
+            let hash = FixedHash::from_hex(&hash).map_err(|_| Error::new("hash is not a valid block hash"))?;
+            Ok(db.fetch_header_by_block_hash(hash).await?)
+        },
+        _ => Err(Error::new("Exactly one of height or hash must be provided")),
+    }
+}
+
+async fn chain_length(db: &ChainDb) -> Result<u64> {
+    let metadata = db.get_chain_metadata().await?;
+    Ok(metadata.height_of_longest_chain().saturating_add(1))
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// The current chain tip
+    async fn tip(&self, ctx: &Context<'_>) -> Result<ChainTip> {
+        let metadata = chain_db(ctx)?.get_chain_metadata().await?;
+        Ok(metadata.into())
+    }
+
+    /// A main chain block, by height or hash
+    async fn block(&self, ctx: &Context<'_>, height: Option<u64>, hash: Option<String>) -> Result<Option<Block>> {
+        let header = fetch_header(&chain_db(ctx)?, height, hash).await?;
+        Ok(header.map(Block::new))
+    }
+
+    /// A main chain block header, by height or hash
+    async fn header(&self, ctx: &Context<'_>, height: Option<u64>, hash: Option<String>) -> Result<Option<Header>> {
+        let header = fetch_header(&chain_db(ctx)?, height, hash).await?;
+        Ok(header.map(Header::new))
+    }
+
+    /// Main chain blocks ordered by height. Cursors are block heights.
+    async fn blocks(
+        &self,
+        ctx: &Context<'_>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> Result<Connection<PositionCursor, Block>> {
+        let db = chain_db(ctx)?;
+        query(after, before, first, last, |after, before, first, last| async move {
+            let page = resolve_page(after, before, first, last, chain_length(&db).await?)?;
+            let headers = fetch_headers(&db, &page).await?;
+            Ok::<_, Error>(build_connection(
+                &page,
+                headers.into_iter().map(|h| (h.height, Block::new(h))),
+            ))
+        })
+        .await
+    }
+
+    /// Main chain block headers ordered by height. Cursors are block heights.
+    async fn headers(
+        &self,
+        ctx: &Context<'_>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> Result<Connection<PositionCursor, Header>> {
+        let db = chain_db(ctx)?;
+        query(after, before, first, last, |after, before, first, last| async move {
+            let page = resolve_page(after, before, first, last, chain_length(&db).await?)?;
+            let headers = fetch_headers(&db, &page).await?;
+            Ok::<_, Error>(build_connection(
+                &page,
+                headers.into_iter().map(|h| (h.height, Header::new(h))),
+            ))
+        })
+        .await
+    }
+
+    /// A kernel, by its hex encoded excess signature
+    async fn kernel(&self, ctx: &Context<'_>, public_nonce: String, signature: String) -> Result<Option<Kernel>> {
+        let excess_sig = parse_excess_sig(&public_nonce, &signature)?;
+        let kernel = chain_db(ctx)?.fetch_kernel_by_excess_sig(excess_sig).await?;
+        Ok(kernel.map(|(kernel, block_hash)| Kernel { kernel, block_hash }))
+    }
+
+    /// An output, spent or unspent, by its hex encoded commitment
+    async fn output(&self, ctx: &Context<'_>, commitment: String) -> Result<Option<Output>> {
+        let commitment =
+            Commitment::from_hex(&commitment).map_err(|_| Error::new("commitment is not a valid commitment"))?;
+        let block = chain_db(ctx)?.fetch_block_with_utxo(commitment.clone()).await?;
+        Ok(block.and_then(|block| {
+            let block_hash = *block.hash();
+            block
+                .block()
+                .body
+                .outputs()
+                .iter()
+                .find(|o| o.commitment == commitment)
+                .map(|o| Output {
+                    output: PrunedOutput::NotPruned { output: o.clone() },
+                    block_hash,
+                })
+        }))
+    }
+}
+
+async fn fetch_headers(db: &ChainDb, page: &Page) -> Result<Vec<BlockHeader>> {
+    if page.range.is_empty() {
+        return Ok(vec![]);
+    }
+    Ok(db.fetch_headers(page.range.clone()).await?)
+}
+
+#[derive(SimpleObject)]
+pub struct ChainTip {
+    height: u64,
+    best_block: String,
+    pruning_horizon: u64,
+    pruned_height: u64,
+    /// Decimal encoded, the accumulated difficulty does not fit in a GraphQL integer
+    accumulated_difficulty: String,
+    timestamp: u64,
+}
+
+impl From<ChainMetadata> for ChainTip {
+    fn from(metadata: ChainMetadata) -> Self {
+        Self {
+            height: metadata.height_of_longest_chain(),
+            best_block: metadata.best_block().to_hex(),
+            pruning_horizon: metadata.pruning_horizon(),
+            pruned_height: metadata.pruned_height(),
+            accumulated_difficulty: metadata.accumulated_difficulty().to_string(),
+            timestamp: metadata.timestamp(),
+        }
+    }
+}
+
+/// A main chain block. The block body is only loaded for the nested fields that are selected.
+pub struct Block {
+    header: BlockHeader,
+    hash: FixedHash,
+}
+
+impl Block {
+    fn new(header: BlockHeader) -> Self {
+        let hash = header.hash();
+        Self { header, hash }
+    }
+}
+
+#[Object]
+impl Block {
+    async fn height(&self) -> u64 {
+        self.header.height
+    }
+
+    async fn hash(&self) -> String {
+        self.hash.to_hex()
+    }
+
+    async fn header(&self) -> Header {
+        Header {
+            header: self.header.clone(),
+            hash: self.hash,
+        }
+    }
+
+    /// The number of blocks mined on top of this block, including this one
+    async fn confirmations(&self, ctx: &Context<'_>) -> Result<u64> {
+        let chain_length = chain_length(&chain_db(ctx)?).await?;
+        Ok(chain_length.saturating_sub(self.header.height))
+    }
+
+    /// The kernels in this block. Cursors are indexes within the block.
+    async fn kernels(
+        &self,
+        ctx: &Context<'_>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> Result<Connection<PositionCursor, Kernel>> {
+        let db = chain_db(ctx)?;
+        let block_hash = self.hash;
+        query(after, before, first, last, |after, before, first, last| async move {
+            let kernels = db.fetch_kernels_in_block(block_hash).await?;
+            paginate(kernels, after, before, first, last, |kernel| Kernel {
+                kernel,
+                block_hash,
+            })
+        })
+        .await
+    }
+
+    /// The outputs created in this block, which may have been pruned. Cursors are indexes within the block.
+    async fn outputs(
+        &self,
+        ctx: &Context<'_>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> Result<Connection<PositionCursor, Output>> {
+        let db = chain_db(ctx)?;
+        let block_hash = self.hash;
+        query(after, before, first, last, |after, before, first, last| async move {
+            let outputs = db.fetch_outputs_in_block(block_hash).await?;
+            paginate(outputs, after, before, first, last, |output| Output {
+                output,
+                block_hash,
+            })
+        })
+        .await
+    }
+}
+
+pub struct Header {
+    header: BlockHeader,
+    hash: FixedHash,
+}
+
+impl Header {
+    fn new(header: BlockHeader) -> Self {
+        let hash = header.hash();
+        Self { header, hash }
+    }
+}
+
+#[Object]
+impl Header {
+    async fn height(&self) -> u64 {
+        self.header.height
+    }
+
+    async fn hash(&self) -> String {
+        self.hash.to_hex()
+    }
+
+    async fn version(&self) -> u16 {
+        self.header.version
+    }
+
+    async fn prev_hash(&self) -> String {
+        self.header.prev_hash.to_hex()
+    }
+
+    /// Seconds since the unix epoch
+    async fn timestamp(&self) -> u64 {
+        self.header.timestamp.as_u64()
+    }
+
+    async fn pow_algo(&self) -> String {
+        self.header.pow.pow_algo.to_string()
+    }
+
+    async fn nonce(&self) -> u64 {
+        self.header.nonce
+    }
+
+    async fn input_mr(&self) -> String {
+        self.header.input_mr.to_hex()
+    }
+
+    async fn output_mr(&self) -> String {
+        self.header.output_mr.to_hex()
+    }
+
+    async fn output_mmr_size(&self) -> u64 {
+        self.header.output_mmr_size
+    }
+
+    async fn kernel_mr(&self) -> String {
+        self.header.kernel_mr.to_hex()
+    }
+
+    async fn kernel_mmr_size(&self) -> u64 {
+        self.header.kernel_mmr_size
+    }
+
+    async fn validator_node_mr(&self) -> String {
+        self.header.validator_node_mr.to_hex()
+    }
+
+    async fn total_kernel_offset(&self) -> String {
+        self.header.total_kernel_offset.to_hex()
+    }
+
+    async fn total_script_offset(&self) -> String {
+        self.header.total_script_offset.to_hex()
+    }
+
+    /// The block this header belongs to
+    async fn block(&self) -> Block {
+        Block {
+            header: self.header.clone(),
+            hash: self.hash,
+        }
+    }
+}
+
+pub struct Kernel {
+    kernel: TransactionKernel,
+    block_hash: FixedHash,
+}
+
+#[Object]
+impl Kernel {
+    async fn excess(&self) -> String {
+        self.kernel.excess.to_hex()
+    }
+
+    async fn excess_sig_public_nonce(&self) -> String {
+        self.kernel.excess_sig.get_public_nonce().to_hex()
+    }
+
+    async fn excess_sig_signature(&self) -> String {
+        self.kernel.excess_sig.get_signature().to_hex()
+    }
+
+    /// The fee in µT
+    async fn fee(&self) -> u64 {
+        self.kernel.fee.as_u64()
+    }
+
+    async fn lock_height(&self) -> u64 {
+        self.kernel.lock_height
+    }
+
+    /// The kernel feature flags
+    async fn features(&self) -> u8 {
+        self.kernel.features.bits()
+    }
+
+    async fn burn_commitment(&self) -> Option<String> {
+        self.kernel.burn_commitment.as_ref().map(|c| c.to_hex())
+    }
+
+    /// The block containing this kernel
+    async fn block(&self, ctx: &Context<'_>) -> Result<Option<Block>> {
+        let header = chain_db(ctx)?.fetch_header_by_block_hash(self.block_hash).await?;
+        Ok(header.map(Block::new))
+    }
+}
+
+pub struct Output {
+    output: PrunedOutput,
+    block_hash: FixedHash,
+}
+
+#[Object]
+impl Output {
+    async fn hash(&self) -> String {
+        self.output.hash().to_hex()
+    }
+
+    /// Whether the output was pruned, in which case only the hash and block are available
+    async fn pruned(&self) -> bool {
+        self.output.is_pruned()
+    }
+
+    async fn commitment(&self) -> Option<String> {
+        self.output.as_transaction_output().map(|o| o.commitment.to_hex())
+    }
+
+    async fn output_type(&self) -> Option<String> {
+        self.output
+            .as_transaction_output()
+            .map(|o| o.features.output_type.to_string())
+    }
+
+    async fn maturity(&self) -> Option<u64> {
+        self.output.as_transaction_output().map(|o| o.features.maturity)
+    }
+
+    /// The minimum value in µT proven by the range proof
+    async fn minimum_value_promise(&self) -> Option<u64> {
+        self.output
+            .as_transaction_output()
+            .map(|o| o.minimum_value_promise.as_u64())
+    }
+
+    /// The block that created this output
+    async fn block(&self, ctx: &Context<'_>) -> Result<Option<Block>> {
+        let header = chain_db(ctx)?.fetch_header_by_block_hash(self.block_hash).await?;
+        Ok(header.map(Block::new))
+    }
+}
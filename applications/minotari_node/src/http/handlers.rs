@@ -0,0 +1,313 @@
+//  Copyright 2023, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use axum::{
+    extract::{Path, Query, State, WebSocketUpgrade},
+    http::{header, HeaderMap, Request},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use log::*;
+use minotari_app_grpc::authentication::BasicAuthCredentials;
+use serde::{Deserialize, Serialize};
+use tari_common_types::{
+    chain_metadata::ChainMetadata,
+    grpc_authentication::GrpcAuthentication,
+    types::{Commitment, FixedHash, PrivateKey, PublicKey, Signature},
+};
+use tari_core::{
+    blocks::HistoricalBlock,
+    mempool::{StatsResponse, TxStorageResponse},
+};
+use tari_utilities::hex::Hex;
+
+use crate::http::{error::HttpGatewayError, events, HttpGatewayState};
+
+const LOG_TARGET: &str = "minotari::base_node::http";
+
+/// The OpenAPI document describing the routes served by the gateway
+const OPENAPI_SPEC: &str = include_str!("openapi.json");
+
+#[derive(Debug, Serialize)]
+pub struct TipInfo {
+    pub metadata: ChainMetadata,
+    pub initial_sync_achieved: bool,
+    pub base_node_state: String,
+}
+
+/// A kernel excess signature, given as hex encoded public nonce and signature
+#[derive(Debug, Deserialize)]
+pub struct ExcessSigQuery {
+    pub public_nonce: String,
+    pub signature: String,
+}
+
+impl ExcessSigQuery {
+    pub fn to_signature(&self) -> Result<Signature, HttpGatewayError> {
+        parse_excess_sig(&self.public_nonce, &self.signature)
+    }
+}
+
+/// Parses a kernel excess signature from its hex encoded public nonce and signature
+pub fn parse_excess_sig(public_nonce: &str, signature: &str) -> Result<Signature, HttpGatewayError> {
+    let public_nonce = PublicKey::from_hex(public_nonce)
+        .map_err(|_| HttpGatewayError::InvalidArgument("public_nonce is not a valid public key".to_string()))?;
+    let signature = PrivateKey::from_hex(signature)
+        .map_err(|_| HttpGatewayError::InvalidArgument("signature is not a valid scalar".to_string()))?;
+    Ok(Signature::new(public_nonce, signature))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionLocation {
+    Mined,
+    Mempool,
+    Unknown,
+    NotStored,
+}
+
+impl From<TxStorageResponse> for TransactionLocation {
+    fn from(response: TxStorageResponse) -> Self {
+        match response {
+            TxStorageResponse::UnconfirmedPool => TransactionLocation::Mempool,
+            // The mempool should not think it is mined, but the node does not think it is either
+            TxStorageResponse::ReorgPool | TxStorageResponse::NotStoredAlreadySpent => TransactionLocation::Unknown,
+            TxStorageResponse::StemPool |
+            TxStorageResponse::NotStored |
+            TxStorageResponse::NotStoredConsensus |
+            TxStorageResponse::NotStoredOrphan |
+            TxStorageResponse::NotStoredFeeTooLow |
+            TxStorageResponse::NotStoredMempoolFull |
+            TxStorageResponse::NotStoredPeerLimit |
+            TxStorageResponse::NotStoredTimeLocked |
+            TxStorageResponse::NotStoredAlreadyMined => TransactionLocation::NotStored,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct TransactionStateResponse {
+    pub location: TransactionLocation,
+}
+
+pub async fn get_openapi_spec() -> impl IntoResponse {
+    ([(header::CONTENT_TYPE, "application/json")], OPENAPI_SPEC)
+}
+
+pub async fn get_tip_info(State(mut state): State<HttpGatewayState>) -> Result<Json<TipInfo>, HttpGatewayError> {
+    debug!(target: LOG_TARGET, "Incoming HTTP request for tip info");
+    let metadata = state.node_service.get_metadata().await?;
+    let status_watch = state.state_machine_handle.get_status_info_watch();
+    let status = status_watch.borrow();
+    Ok(Json(TipInfo {
+        metadata,
+        initial_sync_achieved: status.bootstrapped,
+        base_node_state: status.state_info.short_desc(),
+    }))
+}
+
+pub async fn get_block_by_height(
+    State(mut state): State<HttpGatewayState>,
+    Path(height): Path<u64>,
+) -> Result<Json<HistoricalBlock>, HttpGatewayError> {
+    debug!(target: LOG_TARGET, "Incoming HTTP request for block #{}", height);
+    state
+        .node_service
+        .get_blocks(height..=height, false)
+        .await?
+        .pop()
+        .map(Json)
+        .ok_or_else(|| HttpGatewayError::NotFound(format!("Block at height {}", height)))
+}
+
+pub async fn get_block_by_hash(
+    State(mut state): State<HttpGatewayState>,
+    Path(hash): Path<String>,
+) -> Result<Json<HistoricalBlock>, HttpGatewayError> {
+    debug!(target: LOG_TARGET, "Incoming HTTP request for block {}", hash);
+    let block_hash = FixedHash::from_hex(&hash)
+        .map_err(|_| HttpGatewayError::InvalidArgument("hash is not a valid block hash".to_string()))?;
+    state
+        .node_service
+        .get_block_by_hash(block_hash)
+        .await?
+        .map(Json)
+        .ok_or_else(|| HttpGatewayError::NotFound(format!("Block with hash {}", hash)))
+}
+
+pub async fn get_mempool_stats(
+    State(mut state): State<HttpGatewayState>,
+) -> Result<Json<StatsResponse>, HttpGatewayError> {
+    debug!(target: LOG_TARGET, "Incoming HTTP request for mempool stats");
+    let stats = state.mempool_service.get_mempool_stats().await?;
+    Ok(Json(stats))
+}
+
+pub async fn get_transaction_state(
+    State(mut state): State<HttpGatewayState>,
+    Query(query): Query<ExcessSigQuery>,
+) -> Result<Json<TransactionStateResponse>, HttpGatewayError> {
+    let excess_sig = query.to_signature()?;
+    debug!(
+        target: LOG_TARGET,
+        "Incoming HTTP request for transaction state ({})",
+        excess_sig.get_signature().to_hex()
+    );
+    let kernels = state.node_service.get_kernel_by_excess_sig(excess_sig.clone()).await?;
+    if !kernels.is_empty() {
+        return Ok(Json(TransactionStateResponse {
+            location: TransactionLocation::Mined,
+        }));
+    }
+
+    // Base node does not yet know of kernel excess sig, lets ask the mempool
+    let response = state
+        .mempool_service
+        .get_transaction_state_by_excess_sig(excess_sig)
+        .await?;
+    Ok(Json(TransactionStateResponse {
+        location: response.into(),
+    }))
+}
+
+pub async fn search_kernel(
+    State(mut state): State<HttpGatewayState>,
+    Query(query): Query<ExcessSigQuery>,
+) -> Result<Json<Vec<HistoricalBlock>>, HttpGatewayError> {
+    let excess_sig = query.to_signature()?;
+    debug!(target: LOG_TARGET, "Incoming HTTP request to search for a kernel");
+    let blocks = state.node_service.get_blocks_with_kernels(vec![excess_sig]).await?;
+    Ok(Json(blocks))
+}
+
+pub async fn search_commitment(
+    State(mut state): State<HttpGatewayState>,
+    Path(commitment): Path<String>,
+) -> Result<Json<Vec<HistoricalBlock>>, HttpGatewayError> {
+    debug!(
+        target: LOG_TARGET,
+        "Incoming HTTP request to search for commitment {}", commitment
+    );
+    let commitment = Commitment::from_hex(&commitment)
+        .map_err(|_| HttpGatewayError::InvalidArgument("commitment is not a valid commitment".to_string()))?;
+    let blocks = state.node_service.fetch_blocks_with_utxos(vec![commitment]).await?;
+    Ok(Json(blocks))
+}
+
+/// Checks the request's authorization header against the gRPC authentication config
+fn authenticate(auth: &GrpcAuthentication, headers: &HeaderMap) -> Result<(), HttpGatewayError> {
+    let (username, password) = match auth.username_password() {
+        Some(credentials) => credentials,
+        None => return Ok(()),
+    };
+    let result = headers
+        .get(header::AUTHORIZATION)
+        .ok_or_else(|| "Missing authorization header".to_string())
+        .and_then(|value| value.to_str().map_err(|e| e.to_string()))
+        .and_then(|value| BasicAuthCredentials::from_header(value).map_err(|e| e.to_string()))
+        .and_then(|credentials| {
+            credentials
+                .validate(username, password.reveal())
+                .map_err(|e| e.to_string())
+        });
+    result.map_err(|err| {
+        warn!(target: LOG_TARGET, "HTTP gateway authentication failed: {}", err);
+        HttpGatewayError::Unauthenticated
+    })
+}
+
+/// Router middleware that rejects requests which do not authenticate against the gRPC authentication config
+pub async fn require_authentication<B>(
+    State(auth): State<GrpcAuthentication>,
+    request: Request<B>,
+    next: Next<B>,
+) -> Result<Response, HttpGatewayError> {
+    authenticate(&auth, request.headers())?;
+    Ok(next.run(request).await)
+}
+
+pub async fn subscribe_events(State(state): State<HttpGatewayState>, ws: WebSocketUpgrade) -> Response {
+    ws.on_upgrade(move |socket| events::stream_events(socket, state))
+}
+
+#[cfg(test)]
+mod test {
+    use tari_crypto::keys::PublicKey as PublicKeyTrait;
+
+    use super::*;
+
+    #[test]
+    fn it_parses_excess_sig_queries() {
+        let k = PrivateKey::from(123u64);
+        let nonce = PublicKey::from_secret_key(&k);
+        let query = ExcessSigQuery {
+            public_nonce: nonce.to_hex(),
+            signature: k.to_hex(),
+        };
+        let sig = query.to_signature().unwrap();
+        assert_eq!(sig.get_public_nonce(), &nonce);
+        assert_eq!(sig.get_signature(), &k);
+
+        let query = ExcessSigQuery {
+            public_nonce: "not hex".to_string(),
+            signature: k.to_hex(),
+        };
+        assert!(matches!(
+            query.to_signature(),
+            Err(HttpGatewayError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn it_rejects_missing_or_invalid_credentials() {
+        let headers = HeaderMap::new();
+        assert!(authenticate(&GrpcAuthentication::None, &headers).is_ok());
+
+        let auth = GrpcAuthentication::Basic {
+            username: "admin".to_string(),
+            password: "secret".to_string().into(),
+        };
+        assert!(matches!(
+            authenticate(&auth, &headers),
+            Err(HttpGatewayError::Unauthenticated)
+        ));
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::AUTHORIZATION, "Bearer abc".parse().unwrap());
+        assert!(matches!(
+            authenticate(&auth, &headers),
+            Err(HttpGatewayError::Unauthenticated)
+        ));
+    }
+
+    #[test]
+    fn it_serializes_transaction_locations() {
+        let response = TransactionStateResponse {
+            location: TxStorageResponse::NotStoredFeeTooLow.into(),
+        };
+        assert_eq!(
+            serde_json::to_string(&response).unwrap(),
+            r#"{"location":"not_stored"}"#
+        );
+    }
+}
@@ -0,0 +1,141 @@
+//  Copyright 2023, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! An optional, read-only HTTP/JSON gateway exposing a subset of the base node gRPC API for clients (e.g. web
+//! block explorers) that cannot easily speak gRPC. The routes are documented by the OpenAPI spec served at
+//! `/openapi.json`. Tip, block and mempool events can be subscribed to over a WebSocket at `/v1/events`. If built with
+//! the "graphql" feature, a GraphQL query surface over the chain database can also be enabled at `/graphql`. Every
+//! route requires the same authentication as the gRPC server.
+
+mod error;
+
+mod events;
+
+#[cfg(feature = "graphql")]
+mod graphql;
+
+mod handlers;
+
+use axum::{middleware, routing::get, Router};
+use futures::FutureExt;
+use log::*;
+use tari_common_types::grpc_authentication::GrpcAuthentication;
+use tari_comms::{multiaddr::Multiaddr, utils::multiaddr::multiaddr_to_socketaddr};
+use tari_core::{
+    base_node::{LocalNodeCommsInterface, StateMachineHandle},
+    mempool::service::LocalMempoolService,
+};
+use tari_shutdown::ShutdownSignal;
+
+use crate::{builder::BaseNodeContext, config::BaseNodeConfig};
+
+const LOG_TARGET: &str = "minotari::base_node::http";
+
+/// Handles to the base node services used by the HTTP gateway handlers
+#[derive(Clone)]
+pub struct HttpGatewayState {
+    node_service: LocalNodeCommsInterface,
+    mempool_service: LocalMempoolService,
+    state_machine_handle: StateMachineHandle,
+    shutdown: ShutdownSignal,
+}
+
+impl HttpGatewayState {
+    pub fn from_base_node_context(ctx: &BaseNodeContext, shutdown: ShutdownSignal) -> Self {
+        Self {
+            node_service: ctx.local_node(),
+            mempool_service: ctx.local_mempool(),
+            state_machine_handle: ctx.state_machine(),
+            shutdown,
+        }
+    }
+}
+
+/// Creates the gateway routes for the base node, including the GraphQL endpoint if it is enabled. The gRPC
+/// authentication config is checked for every route.
+pub fn create_router(ctx: &BaseNodeContext, config: &BaseNodeConfig, shutdown: ShutdownSignal) -> Router {
+    let state = HttpGatewayState::from_base_node_context(ctx, shutdown);
+    let router = create_gateway_router(state);
+    let router = if config.graphql_enabled {
+        merge_graphql_router(router, ctx)
+    } else {
+        router
+    };
+    with_authentication(router, config.grpc_authentication.clone())
+}
+
+#[cfg(feature = "graphql")]
+fn merge_graphql_router(router: Router, ctx: &BaseNodeContext) -> Router {
+    router.merge(graphql::create_router(graphql::create_schema(
+        ctx.blockchain_db().into(),
+    )))
+}
+
+#[cfg(not(feature = "graphql"))]
+fn merge_graphql_router(router: Router, _ctx: &BaseNodeContext) -> Router {
+    warn!(
+        target: LOG_TARGET,
+        "GraphQL is enabled but this base node was not built with the \"graphql\" feature"
+    );
+    router
+}
+
+/// Requires every route of the router to authenticate
+fn with_authentication(router: Router, auth: GrpcAuthentication) -> Router {
+    router.layer(middleware::from_fn_with_state(auth, handlers::require_authentication))
+}
+
+fn create_gateway_router(state: HttpGatewayState) -> Router {
+    Router::new()
+        .route("/openapi.json", get(handlers::get_openapi_spec))
+        .route("/v1/tip", get(handlers::get_tip_info))
+        .route("/v1/blocks/:height", get(handlers::get_block_by_height))
+        .route("/v1/blocks/hash/:hash", get(handlers::get_block_by_hash))
+        .route("/v1/mempool/stats", get(handlers::get_mempool_stats))
+        .route("/v1/transactions/state", get(handlers::get_transaction_state))
+        .route("/v1/search/kernel", get(handlers::search_kernel))
+        .route("/v1/search/commitment/:commitment", get(handlers::search_commitment))
+        .route("/v1/events", get(handlers::subscribe_events))
+        .with_state(state)
+}
+
+/// Runs the HTTP gateway until the shutdown signal is triggered
+pub async fn run_http_gateway(
+    router: Router,
+    http_address: Multiaddr,
+    interrupt_signal: ShutdownSignal,
+) -> Result<(), anyhow::Error> {
+    info!(target: LOG_TARGET, "Starting HTTP gateway on {}", http_address);
+
+    let http_address = multiaddr_to_socketaddr(&http_address)?;
+    axum::Server::try_bind(&http_address)?
+        .serve(router.into_make_service())
+        .with_graceful_shutdown(interrupt_signal.map(|_| ()))
+        .await
+        .map_err(|err| {
+            error!(target: LOG_TARGET, "HTTP gateway encountered an error: {:?}", err);
+            err
+        })?;
+
+    info!(target: LOG_TARGET, "Stopping HTTP gateway");
+    Ok(())
+}
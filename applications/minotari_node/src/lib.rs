@@ -26,39 +26,61 @@ minotari_app_utilities::deny_non_64_bit_archs!();
 #[macro_use]
 mod table;
 
+mod backup;
 mod bootstrap;
 mod builder;
 pub mod cli;
 mod commands;
 pub mod config;
 mod grpc;
+#[cfg(feature = "http")]
+mod http;
 #[cfg(feature = "metrics")]
 mod metrics;
 mod recovery;
+mod shutdown;
 mod utils;
 
 use std::{process, sync::Arc};
 
 use commands::{cli_loop::CliLoop, command::CommandContext};
-use futures::FutureExt;
+use futures::{future::BoxFuture, FutureExt};
 use log::*;
-use minotari_app_utilities::{common_cli_args::CommonCliArgs, network_check::is_network_choice_valid};
+use minotari_app_grpc::{
+    authentication::ServerAuthenticationInterceptor,
+    grpc_health_v1::health_server::HealthServer,
+    grpc_reflection_v1alpha::server_reflection_server::ServerReflectionServer,
+    health::HealthService,
+    reflection::ReflectionService,
+    tari_rpc::base_node_server::BaseNodeServer,
+};
+use minotari_app_utilities::{
+    common_cli_args::CommonCliArgs,
+    network_check::is_network_choice_valid,
+    service_manager::{self, HealthStatus, ServiceNotifier},
+};
 use tari_common::{
     configuration::bootstrap::{grpc_default_port, ApplicationType},
     exit_codes::{ExitCode, ExitError},
 };
+use tari_common_types::grpc_authentication::GrpcAuthentication;
 use tari_comms::{multiaddr::Multiaddr, utils::multiaddr::multiaddr_to_socketaddr, NodeIdentity};
 use tari_shutdown::{Shutdown, ShutdownSignal};
 use tokio::task;
-use tonic::transport::Server;
+use tonic::transport::{NamedService, Server};
 
-use crate::cli::Cli;
 pub use crate::{
+    backup::DatabaseBackupConfig,
     config::{ApplicationConfig, BaseNodeConfig, DatabaseType},
     metrics::MetricsConfig,
+    shutdown::ShutdownConfig,
 };
+use crate::{builder::BaseNodeContext, cli::Cli};
 
 const LOG_TARGET: &str = "minotari::base_node::app";
+/// The fully qualified name of the base node gRPC service
+const BASE_NODE_GRPC_SERVICE: &str =
+    <BaseNodeServer<grpc::base_node_grpc_server::BaseNodeGrpcServer> as NamedService>::NAME;
 
 pub async fn run_base_node(
     shutdown: Shutdown,
@@ -79,9 +101,12 @@ pub async fn run_base_node(
             log_level: None,
             network: None,
             config_property_overrides: vec![],
+            print_config: false,
+            explain: false,
         },
         init: true,
         rebuild_db: false,
+        restore_from: None,
         non_interactive_mode: true,
         watch: None,
         profile_with_tokio_console: false,
@@ -124,9 +149,23 @@ pub async fn run_base_node_with_cli(
         return Ok(());
     };
 
+    if let Some(backup_path) = cli.restore_from.as_ref() {
+        match &config.base_node.db_type {
+            DatabaseType::Lmdb => backup::restore_database(backup_path, &config.base_node.lmdb_path)?,
+        }
+    }
+
     // Build, node, build!
     let ctx = builder::configure_and_initialize_node(config.clone(), node_identity, shutdown.to_signal()).await?;
 
+    if config.base_node.backup.enabled {
+        task::spawn(backup::run_database_backups(
+            ctx.blockchain_db(),
+            config.base_node.backup.clone(),
+            shutdown.to_signal(),
+        ));
+    }
+
     if config.base_node.grpc_enabled {
         let grpc_address = config.base_node.grpc_address.clone().unwrap_or_else(|| {
             let port = grpc_default_port(ApplicationType::BaseNode, config.base_node.network);
@@ -134,9 +173,40 @@ pub async fn run_base_node_with_cli(
         });
         // Go, GRPC, go go
         let grpc = grpc::base_node_grpc_server::BaseNodeGrpcServer::from_base_node_context(&ctx);
-        task::spawn(run_grpc(grpc, grpc_address, shutdown.to_signal()));
+        let auth = config.base_node.grpc_authentication.clone();
+        let mut health_check = health_check(&ctx);
+        let health = HealthService::new(&[BASE_NODE_GRPC_SERVICE], move || {
+            health_check()
+                .map(|status| matches!(status, HealthStatus::Healthy(_)))
+                .boxed()
+        });
+        task::spawn(run_grpc(grpc, grpc_address, auth, health, shutdown.to_signal()));
     }
 
+    if config.base_node.http_enabled {
+        #[cfg(feature = "http")]
+        {
+            let router = http::create_router(&ctx, &config.base_node, shutdown.to_signal());
+            task::spawn(http::run_http_gateway(
+                router,
+                config.base_node.http_address.clone(),
+                shutdown.to_signal(),
+            ));
+        }
+        #[cfg(not(feature = "http"))]
+        warn!(
+            target: LOG_TARGET,
+            "The HTTP gateway is enabled but this base node was not built with the \"http\" feature"
+        );
+    }
+
+    let notifier = ServiceNotifier::from_env();
+    task::spawn(service_manager::run_watchdog(
+        notifier.clone(),
+        health_check(&ctx),
+        shutdown.to_signal(),
+    ));
+
     // Run, node, run!
     let context = CommandContext::new(&ctx, shutdown);
     let main_loop = CliLoop::new(context, cli.watch, cli.non_interactive_mode);
@@ -156,25 +226,63 @@ pub async fn run_base_node_with_cli(
     }
 
     info!(target: LOG_TARGET, "Minotari base node has STARTED");
+    notifier.ready();
     main_loop.cli_loop().await;
 
+    notifier.stopping();
     ctx.wait_for_shutdown().await;
 
     println!("Goodbye!");
     Ok(())
 }
 
-/// Runs the gRPC server
+/// The base node is healthy while the state machine is running and the base node service can read the tip of the
+/// chain
+fn health_check(ctx: &BaseNodeContext) -> impl FnMut() -> BoxFuture<'static, HealthStatus> {
+    let state_machine = ctx.state_machine();
+    let local_node = ctx.local_node();
+    move || {
+        let state_machine = state_machine.clone();
+        let mut local_node = local_node.clone();
+        async move {
+            if state_machine.shutdown_signal().is_triggered() {
+                return HealthStatus::Unhealthy("The state machine has stopped".to_string());
+            }
+            match local_node.get_metadata().await {
+                Ok(metadata) => HealthStatus::Healthy(format!(
+                    "{}, tip #{}",
+                    state_machine.get_status_info_watch().borrow().state_info.short_desc(),
+                    metadata.height_of_longest_chain()
+                )),
+                Err(err) => HealthStatus::Unhealthy(format!("Could not fetch the chain metadata: {}", err)),
+            }
+        }
+        .boxed()
+    }
+}
+
+/// Runs the gRPC server. The health and reflection services are served alongside the base node service without
+/// authentication, so that load balancers and tools like grpcurl can use them.
 async fn run_grpc(
     grpc: grpc::base_node_grpc_server::BaseNodeGrpcServer,
     grpc_address: Multiaddr,
+    auth_config: GrpcAuthentication,
+    health: HealthService,
     interrupt_signal: ShutdownSignal,
 ) -> Result<(), anyhow::Error> {
     info!(target: LOG_TARGET, "Starting GRPC on {}", grpc_address);
 
     let grpc_address = multiaddr_to_socketaddr(&grpc_address)?;
+    let auth = ServerAuthenticationInterceptor::new(auth_config);
+    let reflection = ReflectionService::new(&[
+        BASE_NODE_GRPC_SERVICE,
+        <HealthServer<HealthService> as NamedService>::NAME,
+    ])?;
+    let service = BaseNodeServer::with_interceptor(grpc, auth);
     Server::builder()
-        .add_service(minotari_app_grpc::tari_rpc::base_node_server::BaseNodeServer::new(grpc))
+        .add_service(service)
+        .add_service(HealthServer::new(health))
+        .add_service(ServerReflectionServer::new(reflection))
         .serve_with_shutdown(grpc_address, interrupt_signal.map(|_| ()))
         .await
         .map_err(|err| {
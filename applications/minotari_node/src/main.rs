@@ -75,7 +75,7 @@ use clap::Parser;
 use log::*;
 use minotari_app_utilities::{consts, identity_management::setup_node_identity, utilities::setup_runtime};
 use minotari_node::{cli::Cli, run_base_node_with_cli, ApplicationConfig};
-use tari_common::{exit_codes::ExitError, initialize_logging, load_configuration};
+use tari_common::{exit_codes::ExitError, initialize_logging, load_configuration_with_origins};
 use tari_comms::peer_manager::PeerFeatures;
 #[cfg(all(unix, feature = "libtor"))]
 use tari_libtor::tor::Tor;
@@ -113,7 +113,7 @@ fn main_inner() -> Result<(), ExitError> {
     let cli = Cli::parse();
 
     let config_path = cli.common.config_path();
-    let cfg = load_configuration(config_path, true, &cli)?;
+    let (cfg, config_origins) = load_configuration_with_origins(config_path, true, &cli)?;
 
     if cli.profile_with_tokio_console {
         console_subscriber::init();
@@ -136,6 +136,12 @@ fn main_inner() -> Result<(), ExitError> {
     let config = ApplicationConfig::load_from(&cfg)?;
     debug!(target: LOG_TARGET, "Using base node configuration: {:?}", config);
 
+    if cli.common.print_config {
+        let effective = config.effective_config(&cfg, &config_origins)?;
+        print!("{}", effective.render(cli.common.explain));
+        return Ok(());
+    }
+
     // Load or create the Node identity
     let node_identity = setup_node_identity(
         &config.base_node.identity_file,
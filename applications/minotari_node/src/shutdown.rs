@@ -0,0 +1,164 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! The coordinated shutdown sequence of the base node. It runs once the comms stack has stopped, so no new RPC or
+//! block propagation work can start. Block adds that are still in flight are allowed to commit before the blockchain
+//! database stops accepting writes and is flushed to disk, and the unconfirmed mempool transactions are saved so that
+//! they can be resubmitted when the node starts again.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use log::*;
+use serde::{Deserialize, Serialize};
+use tari_common::configuration::serializers;
+use tari_core::{
+    chain_storage::{BlockchainDatabase, LMDBDatabase},
+    mempool::Mempool,
+    transactions::transaction_components::Transaction,
+};
+use tokio::{task, time};
+
+const LOG_TARGET: &str = "minotari::base_node::shutdown";
+
+const PARTIAL_EXTENSION: &str = "partial";
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ShutdownConfig {
+    /// Save the unconfirmed mempool transactions on shutdown and resubmit them to the mempool at the next startup
+    pub persist_mempool: bool,
+    /// The file the mempool transactions are saved to. Relative paths are relative to the base node data directory.
+    pub mempool_snapshot_file: PathBuf,
+    /// The maximum time the shutdown sequence may take. This should be less than the stop timeout of the service
+    /// manager running the node (e.g. systemd's `TimeoutStopSec`, 90s by default), so that the node is never killed
+    /// while flushing to disk.
+    #[serde(with = "serializers::seconds")]
+    pub timeout: Duration,
+}
+
+impl Default for ShutdownConfig {
+    fn default() -> Self {
+        Self {
+            persist_mempool: true,
+            mempool_snapshot_file: PathBuf::from("mempool_snapshot.bin"),
+            timeout: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Finishes in-flight block adds, closes the blockchain database to writes and flushes it to disk, then saves the
+/// mempool snapshot. Gives up once `config.timeout` has elapsed.
+pub async fn run_shutdown_sequence(db: BlockchainDatabase<LMDBDatabase>, mempool: Mempool, config: &ShutdownConfig) {
+    let sequence = async {
+        info!(target: LOG_TARGET, "Waiting for in-flight blockchain database writes to complete");
+        match task::spawn_blocking(move || db.shutdown()).await {
+            Ok(Ok(())) => info!(target: LOG_TARGET, "Blockchain database has been flushed to disk"),
+            Ok(Err(e)) => error!(target: LOG_TARGET, "Failed to flush the blockchain database: {}", e),
+            Err(e) => error!(target: LOG_TARGET, "Blockchain database flush task failed: {}", e),
+        }
+
+        if config.persist_mempool {
+            match save_mempool_snapshot(&mempool, &config.mempool_snapshot_file).await {
+                Ok(count) => info!(
+                    target: LOG_TARGET,
+                    "Saved {} mempool transaction(s) to {}",
+                    count,
+                    config.mempool_snapshot_file.display()
+                ),
+                Err(e) => warn!(target: LOG_TARGET, "Failed to save the mempool snapshot: {}", e),
+            }
+        }
+    };
+
+    if time::timeout(config.timeout, sequence).await.is_err() {
+        error!(
+            target: LOG_TARGET,
+            "The shutdown sequence did not complete within {:.0?}", config.timeout
+        );
+    }
+}
+
+/// Resubmits the transactions saved by the previous shutdown to the mempool. The snapshot is removed once read, so it
+/// is only ever restored once.
+pub async fn restore_mempool_snapshot(mempool: &Mempool, path: &Path) -> anyhow::Result<usize> {
+    let Some(transactions) = take_snapshot(path)? else {
+        return Ok(0);
+    };
+    let count = transactions.len();
+    mempool
+        .insert_all(transactions.into_iter().map(Arc::new).collect())
+        .await?;
+    Ok(count)
+}
+
+async fn save_mempool_snapshot(mempool: &Mempool, path: &Path) -> anyhow::Result<usize> {
+    let transactions = mempool.snapshot().await?;
+    write_snapshot(path, transactions.iter().map(|tx| &**tx).collect())?;
+    Ok(transactions.len())
+}
+
+/// Writes the snapshot to a partial file that is only renamed once it is complete, so an interrupted write is never
+/// restored from.
+fn write_snapshot(path: &Path, transactions: Vec<&Transaction>) -> anyhow::Result<()> {
+    let partial_path = path.with_extension(PARTIAL_EXTENSION);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&partial_path, bincode::serialize(&transactions)?)?;
+    fs::rename(&partial_path, path)?;
+    Ok(())
+}
+
+fn take_snapshot(path: &Path) -> anyhow::Result<Option<Vec<Transaction>>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let bytes = fs::read(path)?;
+    fs::remove_file(path)?;
+    Ok(Some(bincode::deserialize(&bytes)?))
+}
+
+#[cfg(test)]
+mod test {
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[test]
+    fn it_takes_the_snapshot_that_was_written() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("mempool").join("mempool_snapshot.bin");
+        assert!(take_snapshot(&path).unwrap().is_none());
+
+        write_snapshot(&path, vec![]).unwrap();
+        assert!(!path.with_extension(PARTIAL_EXTENSION).exists());
+
+        assert_eq!(take_snapshot(&path).unwrap().unwrap().len(), 0);
+        assert!(!path.exists());
+        assert!(take_snapshot(&path).unwrap().is_none());
+    }
+}
@@ -213,6 +213,185 @@ void add_chat_contact(struct ChatClientFFI *client, struct TariAddress *address,
  */
 int check_online_status(struct ChatClientFFI *client, struct TariAddress *receiver, int *error_out);
 
+/**
+ * Set who the client shares its online status and last seen time with
+ *
+ * ## Arguments
+ * `client` - The Client pointer
+ * `mode` - An int representing the privacy mode
+ *            Always = 1,
+ *            ContactsOnly = 2,
+ *            Never = 3,
+ * `error_out` - Pointer to an int which will be modified
+ *
+ * ## Returns
+ * `()` - Does not return a value, equivalent to void in C
+ *
+ * # Safety
+ * None
+ */
+void set_chat_liveness_privacy_mode(struct ChatClientFFI *client, int mode, int *error_out);
+
+/**
+ * Override who the client shares its online status and last seen time with for a single contact
+ *
+ * ## Arguments
+ * `client` - The Client pointer
+ * `address` - A TariAddress ptr
+ * `mode` - An int representing the privacy mode
+ *            UseGlobalMode = 0,
+ *            Always = 1,
+ *            ContactsOnly = 2,
+ *            Never = 3,
+ * `error_out` - Pointer to an int which will be modified
+ *
+ * ## Returns
+ * `()` - Does not return a value, equivalent to void in C
+ *
+ * # Safety
+ * The ```address``` should be destroyed after use
+ */
+void set_chat_contact_liveness_privacy_mode(struct ChatClientFFI *client,
+                                            struct TariAddress *address,
+                                            int mode,
+                                            int *error_out);
+
+/**
+ * Ask the chat identity at the given address to link this client as one of its devices. Once approved, contacts
+ * of the identity send messages to this client as well.
+ *
+ * ## Arguments
+ * `client` - The Client pointer
+ * `identity` - A TariAddress ptr of the identity
+ * `error_out` - Pointer to an int which will be modified
+ *
+ * ## Returns
+ * `()` - Does not return a value, equivalent to void in C
+ *
+ * # Safety
+ * The ```identity``` should be destroyed after use
+ */
+void request_chat_device_link(struct ChatClientFFI *client, struct TariAddress *identity, int *error_out);
+
+/**
+ * Approve a link request received from the device at the given address
+ *
+ * ## Arguments
+ * `client` - The Client pointer
+ * `device` - A TariAddress ptr of the device
+ * `error_out` - Pointer to an int which will be modified
+ *
+ * ## Returns
+ * `()` - Does not return a value, equivalent to void in C
+ *
+ * # Safety
+ * The ```device``` should be destroyed after use
+ */
+void approve_chat_device_link(struct ChatClientFFI *client, struct TariAddress *device, int *error_out);
+
+/**
+ * Unlink the device at the given address from this client's identity, or unlink this client from its identity if
+ * the address is its own
+ *
+ * ## Arguments
+ * `client` - The Client pointer
+ * `device` - A TariAddress ptr of the device
+ * `error_out` - Pointer to an int which will be modified
+ *
+ * ## Returns
+ * `()` - Does not return a value, equivalent to void in C
+ *
+ * # Safety
+ * The ```device``` should be destroyed after use
+ */
+void unlink_chat_device(struct ChatClientFFI *client, struct TariAddress *device, int *error_out);
+
+/**
+ * Enable or disable message requests. When enabled, messages from senders that are not contacts, not allowed and
+ * that this client has no conversation with are held back as message requests until accepted or rejected.
+ *
+ * ## Arguments
+ * `client` - The Client pointer
+ * `enabled` - Whether first-contact messages should be quarantined
+ * `error_out` - Pointer to an int which will be modified
+ *
+ * ## Returns
+ * `()` - Does not return a value, equivalent to void in C
+ *
+ * # Safety
+ * None
+ */
+void set_chat_message_requests_enabled(struct ChatClientFFI *client, bool enabled, int *error_out);
+
+/**
+ * Accept the message request from the given address, moving its messages into the conversation and allowing
+ * further messages from the sender
+ *
+ * ## Arguments
+ * `client` - The Client pointer
+ * `address` - A TariAddress ptr of the sender
+ * `error_out` - Pointer to an int which will be modified
+ *
+ * ## Returns
+ * `()` - Does not return a value, equivalent to void in C
+ *
+ * # Safety
+ * The ```address``` should be destroyed after use
+ */
+void accept_chat_message_request(struct ChatClientFFI *client, struct TariAddress *address, int *error_out);
+
+/**
+ * Reject the message request from the given address, discarding its messages
+ *
+ * ## Arguments
+ * `client` - The Client pointer
+ * `address` - A TariAddress ptr of the sender
+ * `block` - Whether the sender should also be blocked
+ * `error_out` - Pointer to an int which will be modified
+ *
+ * ## Returns
+ * `()` - Does not return a value, equivalent to void in C
+ *
+ * # Safety
+ * The ```address``` should be destroyed after use
+ */
+void reject_chat_message_request(struct ChatClientFFI *client,
+                                 struct TariAddress *address,
+                                 bool block,
+                                 int *error_out);
+
+/**
+ * Block the given address. Messages from a blocked sender are dropped and pending message requests discarded.
+ *
+ * ## Arguments
+ * `client` - The Client pointer
+ * `address` - A TariAddress ptr of the sender
+ * `error_out` - Pointer to an int which will be modified
+ *
+ * ## Returns
+ * `()` - Does not return a value, equivalent to void in C
+ *
+ * # Safety
+ * The ```address``` should be destroyed after use
+ */
+void block_chat_address(struct ChatClientFFI *client, struct TariAddress *address, int *error_out);
+
+/**
+ * Remove the given address from the block list
+ *
+ * ## Arguments
+ * `client` - The Client pointer
+ * `address` - A TariAddress ptr of the sender
+ * `error_out` - Pointer to an int which will be modified
+ *
+ * ## Returns
+ * `()` - Does not return a value, equivalent to void in C
+ *
+ * # Safety
+ * The ```address``` should be destroyed after use
+ */
+void unblock_chat_address(struct ChatClientFFI *client, struct TariAddress *address, int *error_out);
+
 /**
  * Creates a message and returns a ptr to it
  *
@@ -451,6 +630,21 @@ void destroy_chat_tor_transport_config(struct TransportConfig *transport);
  */
 struct TariAddress *create_tari_address(const char *receiver_c_char, int *error_out);
 
+/**
+ * Creates a TariAddress from a `tari://` URI that names one, i.e. a contact or payment request link, and returns a ptr
+ *
+ * ## Arguments
+ * `uri_c_char` - A string containing a Tari URI
+ * `error_out` - Pointer to an int which will be modified
+ *
+ * ## Returns
+ * `*mut TariAddress` - A ptr to a TariAddress
+ *
+ * # Safety
+ * The ```destroy_tari_address``` function should be called when finished with the TariAddress
+ */
+struct TariAddress *create_tari_address_from_uri(const char *uri_c_char, int *error_out);
+
 /**
  * Frees memory for a TariAddress
  *
@@ -89,7 +89,16 @@ impl CallbackHandler {
                                 MessageDispatch::ReadConfirmation(c) => {
                                     trace!(target: LOG_TARGET, "FFI Callback monitor received a new Read Confirmation");
                                     self.trigger_read_confirmation_received(c.clone());
-                                }
+                                },
+                                MessageDispatch::DeviceLink(d) => {
+                                    trace!(target: LOG_TARGET, "FFI Callback monitor received a device link for device {}", d.device);
+                                },
+                                MessageDispatch::DeviceUnlink(r) => {
+                                    trace!(target: LOG_TARGET, "FFI Callback monitor received a device unlink for device {}", r.device);
+                                },
+                                MessageDispatch::SessionReset => {
+                                    trace!(target: LOG_TARGET, "FFI Callback monitor received a chat session reset");
+                                },
                             };
                         },
                         Err(_) => { debug!(target: LOG_TARGET, "FFI Callback monitor had an error receiving new messages")}
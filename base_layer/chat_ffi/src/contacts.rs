@@ -20,11 +20,12 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::ptr;
+use std::{convert::TryFrom, ptr};
 
 use libc::c_int;
 use tari_chat_client::ChatClient;
 use tari_common_types::tari_address::TariAddress;
+use tari_contacts::contacts_service::service::LivenessPrivacyMode;
 
 use crate::{
     error::{InterfaceError, LibChatError},
@@ -105,3 +106,414 @@ pub unsafe extern "C" fn check_online_status(
 
     status.as_u8().into()
 }
+
+/// Set who the client shares its online status and last seen time with
+///
+/// ## Arguments
+/// `client` - The Client pointer
+/// `mode` - An int representing the privacy mode
+///            Always = 1,
+///            ContactsOnly = 2,
+///            Never = 3,
+/// `error_out` - Pointer to an int which will be modified
+///
+/// ## Returns
+/// `()` - Does not return a value, equivalent to void in C
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn set_chat_liveness_privacy_mode(
+    client: *mut ChatClientFFI,
+    mode: c_int,
+    error_out: *mut c_int,
+) {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+
+    if client.is_null() {
+        error = LibChatError::from(InterfaceError::NullError("client".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return;
+    }
+
+    let mode = match u8::try_from(mode).ok().and_then(LivenessPrivacyMode::from_byte) {
+        Some(mode) => mode,
+        None => {
+            error = LibChatError::from(InterfaceError::InvalidArgument(
+                "Couldn't convert int to liveness privacy mode".to_string(),
+            ))
+            .code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            return;
+        },
+    };
+
+    (*client)
+        .runtime
+        .block_on((*client).client.set_liveness_privacy_mode(mode));
+}
+
+/// Override who the client shares its online status and last seen time with for a single contact
+///
+/// ## Arguments
+/// `client` - The Client pointer
+/// `address` - A TariAddress ptr
+/// `mode` - An int representing the privacy mode
+///            UseGlobalMode = 0,
+///            Always = 1,
+///            ContactsOnly = 2,
+///            Never = 3,
+/// `error_out` - Pointer to an int which will be modified
+///
+/// ## Returns
+/// `()` - Does not return a value, equivalent to void in C
+///
+/// # Safety
+/// The ```address``` should be destroyed after use
+#[no_mangle]
+pub unsafe extern "C" fn set_chat_contact_liveness_privacy_mode(
+    client: *mut ChatClientFFI,
+    address: *mut TariAddress,
+    mode: c_int,
+    error_out: *mut c_int,
+) {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+
+    if client.is_null() {
+        error = LibChatError::from(InterfaceError::NullError("client".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return;
+    }
+
+    if address.is_null() {
+        error = LibChatError::from(InterfaceError::NullError("address".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return;
+    }
+
+    let mode = match u8::try_from(mode) {
+        Ok(0) => None,
+        Ok(byte) => match LivenessPrivacyMode::from_byte(byte) {
+            Some(mode) => Some(mode),
+            None => {
+                error = LibChatError::from(InterfaceError::InvalidArgument(
+                    "Couldn't convert int to liveness privacy mode".to_string(),
+                ))
+                .code;
+                ptr::swap(error_out, &mut error as *mut c_int);
+                return;
+            },
+        },
+        Err(e) => {
+            error = LibChatError::from(InterfaceError::InvalidArgument(e.to_string())).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            return;
+        },
+    };
+
+    (*client)
+        .runtime
+        .block_on((*client).client.set_contact_liveness_privacy_mode(&(*address), mode));
+}
+
+/// Ask the chat identity at the given address to link this client as one of its devices. Once approved, contacts
+/// of the identity send messages to this client as well.
+///
+/// ## Arguments
+/// `client` - The Client pointer
+/// `identity` - A TariAddress ptr of the identity
+/// `error_out` - Pointer to an int which will be modified
+///
+/// ## Returns
+/// `()` - Does not return a value, equivalent to void in C
+///
+/// # Safety
+/// The ```identity``` should be destroyed after use
+#[no_mangle]
+pub unsafe extern "C" fn request_chat_device_link(
+    client: *mut ChatClientFFI,
+    identity: *mut TariAddress,
+    error_out: *mut c_int,
+) {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+
+    if client.is_null() {
+        error = LibChatError::from(InterfaceError::NullError("client".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return;
+    }
+
+    if identity.is_null() {
+        error = LibChatError::from(InterfaceError::NullError("identity".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return;
+    }
+
+    (*client)
+        .runtime
+        .block_on((*client).client.request_device_link(&(*identity)));
+}
+
+/// Approve a link request received from the device at the given address
+///
+/// ## Arguments
+/// `client` - The Client pointer
+/// `device` - A TariAddress ptr of the device
+/// `error_out` - Pointer to an int which will be modified
+///
+/// ## Returns
+/// `()` - Does not return a value, equivalent to void in C
+///
+/// # Safety
+/// The ```device``` should be destroyed after use
+#[no_mangle]
+pub unsafe extern "C" fn approve_chat_device_link(
+    client: *mut ChatClientFFI,
+    device: *mut TariAddress,
+    error_out: *mut c_int,
+) {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+
+    if client.is_null() {
+        error = LibChatError::from(InterfaceError::NullError("client".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return;
+    }
+
+    if device.is_null() {
+        error = LibChatError::from(InterfaceError::NullError("device".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return;
+    }
+
+    (*client)
+        .runtime
+        .block_on((*client).client.approve_device_link(&(*device)));
+}
+
+/// Unlink the device at the given address from this client's identity, or unlink this client from its identity if
+/// the address is its own
+///
+/// ## Arguments
+/// `client` - The Client pointer
+/// `device` - A TariAddress ptr of the device
+/// `error_out` - Pointer to an int which will be modified
+///
+/// ## Returns
+/// `()` - Does not return a value, equivalent to void in C
+///
+/// # Safety
+/// The ```device``` should be destroyed after use
+#[no_mangle]
+pub unsafe extern "C" fn unlink_chat_device(
+    client: *mut ChatClientFFI,
+    device: *mut TariAddress,
+    error_out: *mut c_int,
+) {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+
+    if client.is_null() {
+        error = LibChatError::from(InterfaceError::NullError("client".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return;
+    }
+
+    if device.is_null() {
+        error = LibChatError::from(InterfaceError::NullError("device".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return;
+    }
+
+    (*client).runtime.block_on((*client).client.unlink_device(&(*device)));
+}
+
+/// Enable or disable message requests. When enabled, messages from senders that are not contacts, not allowed and
+/// that this client has no conversation with are held back as message requests until accepted or rejected.
+///
+/// ## Arguments
+/// `client` - The Client pointer
+/// `enabled` - Whether first-contact messages should be quarantined
+/// `error_out` - Pointer to an int which will be modified
+///
+/// ## Returns
+/// `()` - Does not return a value, equivalent to void in C
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn set_chat_message_requests_enabled(
+    client: *mut ChatClientFFI,
+    enabled: bool,
+    error_out: *mut c_int,
+) {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+
+    if client.is_null() {
+        error = LibChatError::from(InterfaceError::NullError("client".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return;
+    }
+
+    (*client)
+        .runtime
+        .block_on((*client).client.set_message_requests_enabled(enabled));
+}
+
+/// Accept the message request from the given address, moving its messages into the conversation and allowing
+/// further messages from the sender
+///
+/// ## Arguments
+/// `client` - The Client pointer
+/// `address` - A TariAddress ptr of the sender
+/// `error_out` - Pointer to an int which will be modified
+///
+/// ## Returns
+/// `()` - Does not return a value, equivalent to void in C
+///
+/// # Safety
+/// The ```address``` should be destroyed after use
+#[no_mangle]
+pub unsafe extern "C" fn accept_chat_message_request(
+    client: *mut ChatClientFFI,
+    address: *mut TariAddress,
+    error_out: *mut c_int,
+) {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+
+    if client.is_null() {
+        error = LibChatError::from(InterfaceError::NullError("client".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return;
+    }
+
+    if address.is_null() {
+        error = LibChatError::from(InterfaceError::NullError("address".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return;
+    }
+
+    (*client)
+        .runtime
+        .block_on((*client).client.accept_message_request(&(*address)));
+}
+
+/// Reject the message request from the given address, discarding its messages
+///
+/// ## Arguments
+/// `client` - The Client pointer
+/// `address` - A TariAddress ptr of the sender
+/// `block` - Whether the sender should also be blocked
+/// `error_out` - Pointer to an int which will be modified
+///
+/// ## Returns
+/// `()` - Does not return a value, equivalent to void in C
+///
+/// # Safety
+/// The ```address``` should be destroyed after use
+#[no_mangle]
+pub unsafe extern "C" fn reject_chat_message_request(
+    client: *mut ChatClientFFI,
+    address: *mut TariAddress,
+    block: bool,
+    error_out: *mut c_int,
+) {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+
+    if client.is_null() {
+        error = LibChatError::from(InterfaceError::NullError("client".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return;
+    }
+
+    if address.is_null() {
+        error = LibChatError::from(InterfaceError::NullError("address".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return;
+    }
+
+    (*client)
+        .runtime
+        .block_on((*client).client.reject_message_request(&(*address), block));
+}
+
+/// Block the given address. Messages from a blocked sender are dropped and pending message requests discarded.
+///
+/// ## Arguments
+/// `client` - The Client pointer
+/// `address` - A TariAddress ptr of the sender
+/// `error_out` - Pointer to an int which will be modified
+///
+/// ## Returns
+/// `()` - Does not return a value, equivalent to void in C
+///
+/// # Safety
+/// The ```address``` should be destroyed after use
+#[no_mangle]
+pub unsafe extern "C" fn block_chat_address(
+    client: *mut ChatClientFFI,
+    address: *mut TariAddress,
+    error_out: *mut c_int,
+) {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+
+    if client.is_null() {
+        error = LibChatError::from(InterfaceError::NullError("client".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return;
+    }
+
+    if address.is_null() {
+        error = LibChatError::from(InterfaceError::NullError("address".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return;
+    }
+
+    (*client).runtime.block_on((*client).client.block_address(&(*address)));
+}
+
+/// Remove the given address from the block list
+///
+/// ## Arguments
+/// `client` - The Client pointer
+/// `address` - A TariAddress ptr of the sender
+/// `error_out` - Pointer to an int which will be modified
+///
+/// ## Returns
+/// `()` - Does not return a value, equivalent to void in C
+///
+/// # Safety
+/// The ```address``` should be destroyed after use
+#[no_mangle]
+pub unsafe extern "C" fn unblock_chat_address(
+    client: *mut ChatClientFFI,
+    address: *mut TariAddress,
+    error_out: *mut c_int,
+) {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+
+    if client.is_null() {
+        error = LibChatError::from(InterfaceError::NullError("client".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return;
+    }
+
+    if address.is_null() {
+        error = LibChatError::from(InterfaceError::NullError("address".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return;
+    }
+
+    (*client)
+        .runtime
+        .block_on((*client).client.unblock_address(&(*address)));
+}
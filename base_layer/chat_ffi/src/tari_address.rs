@@ -23,7 +23,10 @@
 use std::{ffi::CStr, ptr, str::FromStr};
 
 use libc::{c_char, c_int};
-use tari_common_types::tari_address::TariAddress;
+use tari_common_types::{
+    tari_address::TariAddress,
+    tari_uri::{TariUri, TariUriAction},
+};
 
 use crate::error::{InterfaceError, LibChatError};
 
@@ -65,6 +68,56 @@ pub unsafe extern "C" fn create_tari_address(
     Box::into_raw(Box::new(receiver))
 }
 
+/// Creates a TariAddress from a `tari://` URI that names one, i.e. a contact or payment request link, and returns a ptr
+///
+/// ## Arguments
+/// `uri_c_char` - A string containing a Tari URI
+/// `error_out` - Pointer to an int which will be modified
+///
+/// ## Returns
+/// `*mut TariAddress` - A ptr to a TariAddress
+///
+/// # Safety
+/// The ```destroy_tari_address``` function should be called when finished with the TariAddress
+#[no_mangle]
+pub unsafe extern "C" fn create_tari_address_from_uri(
+    uri_c_char: *const c_char,
+    error_out: *mut c_int,
+) -> *mut TariAddress {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+
+    let uri = match CStr::from_ptr(uri_c_char).to_str() {
+        Ok(str) => match TariUri::from_str(str) {
+            Ok(uri) => uri,
+            Err(e) => {
+                error = LibChatError::from(InterfaceError::InvalidArgument(e.to_string())).code;
+                ptr::swap(error_out, &mut error as *mut c_int);
+                return ptr::null_mut();
+            },
+        },
+        Err(e) => {
+            error = LibChatError::from(InterfaceError::NullError(e.to_string())).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            return ptr::null_mut();
+        },
+    };
+
+    match uri.action {
+        TariUriAction::AddContact { address, .. } | TariUriAction::Send { address, .. } => {
+            Box::into_raw(Box::new(address))
+        },
+        _ => {
+            error = LibChatError::from(InterfaceError::InvalidArgument(
+                "The URI does not contain a Tari address".to_string(),
+            ))
+            .code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            ptr::null_mut()
+        },
+    }
+}
+
 /// Frees memory for a TariAddress
 ///
 /// ## Arguments
@@ -0,0 +1,226 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Utilities for checking emoji IDs typed in by hand. A single mistyped emoji always breaks the address checksum, and
+//! for any one position there is exactly one emoji that repairs it. This lets us either correct an emoji that is not
+//! part of the emoji set, or list the handful of single-emoji substitutions that produce a valid address.
+
+use tari_common::configuration::Network;
+
+use crate::{
+    emoji::{EMOJI, REVERSE_EMOJI},
+    tari_address::{TariAddress, TariAddressError, INTERNAL_SIZE},
+};
+
+/// A single-emoji substitution that turns an invalid emoji ID into a valid Tari address
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmojiCorrection {
+    /// The zero-based position of the emoji to replace
+    pub position: usize,
+    /// The emoji found at this position
+    pub found: char,
+    /// The emoji that makes the checksum valid
+    pub replacement: char,
+    /// The address obtained after the replacement
+    pub address: TariAddress,
+}
+
+/// The outcome of checking an emoji ID
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EmojiIdCheck {
+    /// The emoji ID is a valid address for the network
+    Valid(TariAddress),
+    /// The emoji ID is invalid, but each of these single-emoji substitutions produce a valid address. They are ordered
+    /// by position.
+    Corrections(Vec<EmojiCorrection>),
+}
+
+impl EmojiIdCheck {
+    /// The only possible correction, if the erroneous emoji could be pinpointed
+    pub fn unique_correction(&self) -> Option<&EmojiCorrection> {
+        match self {
+            EmojiIdCheck::Corrections(corrections) if corrections.len() == 1 => corrections.first(),
+            _ => None,
+        }
+    }
+}
+
+/// Checks an emoji ID for the given network, looking for single-emoji transcription errors if it is invalid.
+///
+/// Separators (`|`) and whitespace are ignored. An emoji that is not part of the emoji set pinpoints the error, in
+/// which case at most one correction is returned. Otherwise every position is tried, and more than one correction may
+/// be returned; these are candidates for the user to confirm and must never be applied silently.
+pub fn check_emoji_id(emoji: &str, network: Network) -> Result<EmojiIdCheck, TariAddressError> {
+    let chars = emoji
+        .chars()
+        .filter(|c| *c != '|' && !c.is_whitespace())
+        .collect::<Vec<_>>();
+    if chars.len() != INTERNAL_SIZE {
+        return Err(TariAddressError::InvalidSize);
+    }
+
+    let mut unknown = None;
+    let mut bytes = Vec::with_capacity(INTERNAL_SIZE);
+    for (position, c) in chars.iter().enumerate() {
+        match REVERSE_EMOJI.get(c) {
+            Some(b) => bytes.push(*b),
+            None => {
+                // More than one unknown emoji cannot be repaired with a single substitution
+                if unknown.is_some() {
+                    return Err(TariAddressError::InvalidEmoji);
+                }
+                unknown = Some(position);
+                bytes.push(0);
+            },
+        }
+    }
+
+    let positions = match unknown {
+        Some(position) => position..position + 1,
+        None => match TariAddress::from_bytes_with_network(&bytes, network) {
+            Ok(address) => return Ok(EmojiIdCheck::Valid(address)),
+            // The checksum is valid, so a single substitution would break it
+            Err(TariAddressError::CannotRecoverPublicKey) => return Err(TariAddressError::CannotRecoverPublicKey),
+            Err(_) => 0..INTERNAL_SIZE,
+        },
+    };
+
+    let mut corrections = Vec::new();
+    for position in positions {
+        let original = bytes[position];
+        for candidate in 0..=u8::MAX {
+            if unknown.is_none() && candidate == original {
+                continue;
+            }
+            bytes[position] = candidate;
+            if let Ok(address) = TariAddress::from_bytes_with_network(&bytes, network) {
+                corrections.push(EmojiCorrection {
+                    position,
+                    found: chars[position],
+                    replacement: EMOJI[candidate as usize],
+                    address,
+                });
+                // The checksum detects every single substitution, so no other candidate can be valid here
+                break;
+            }
+        }
+        bytes[position] = original;
+    }
+
+    if corrections.is_empty() {
+        return Err(TariAddressError::InvalidNetworkOrChecksum);
+    }
+    Ok(EmojiIdCheck::Corrections(corrections))
+}
+
+#[cfg(test)]
+mod test {
+    use tari_crypto::keys::{PublicKey as PublicKeyTrait, SecretKey};
+
+    use super::*;
+    use crate::types::{PrivateKey, PublicKey};
+
+    fn random_address() -> TariAddress {
+        let mut rng = rand::thread_rng();
+        let public_key = PublicKey::from_secret_key(&PrivateKey::random(&mut rng));
+        TariAddress::new(public_key, Network::Esmeralda)
+    }
+
+    fn replace_at(emoji: &str, position: usize, replacement: char) -> String {
+        emoji
+            .chars()
+            .enumerate()
+            .map(|(i, c)| if i == position { replacement } else { c })
+            .collect()
+    }
+
+    #[test]
+    fn it_accepts_a_valid_emoji_id() {
+        let address = random_address();
+        let emoji = address.to_emoji_string();
+        assert_eq!(
+            check_emoji_id(&emoji, Network::Esmeralda),
+            Ok(EmojiIdCheck::Valid(address.clone()))
+        );
+
+        // Separators and whitespace are ignored
+        let spaced = emoji.chars().map(|c| format!("{} |", c)).collect::<String>();
+        assert_eq!(
+            check_emoji_id(&spaced, Network::Esmeralda),
+            Ok(EmojiIdCheck::Valid(address))
+        );
+    }
+
+    #[test]
+    fn it_corrects_an_unknown_emoji() {
+        let address = random_address();
+        let emoji = address.to_emoji_string();
+        let original = emoji.chars().nth(5).unwrap();
+        let typo = replace_at(&emoji, 5, '🎅');
+
+        let check = check_emoji_id(&typo, Network::Esmeralda).unwrap();
+        let correction = check.unique_correction().unwrap();
+        assert_eq!(correction.position, 5);
+        assert_eq!(correction.found, '🎅');
+        assert_eq!(correction.replacement, original);
+        assert_eq!(correction.address, address);
+    }
+
+    #[test]
+    fn it_suggests_the_original_address_for_a_substitution() {
+        let address = random_address();
+        let emoji = address.to_emoji_string();
+        for position in [0, 16, 31] {
+            let original = emoji.chars().nth(position).unwrap();
+            let replacement = EMOJI.iter().find(|c| **c != original).unwrap();
+            let typo = replace_at(&emoji, position, *replacement);
+
+            match check_emoji_id(&typo, Network::Esmeralda).unwrap() {
+                EmojiIdCheck::Corrections(corrections) => {
+                    assert!(corrections
+                        .iter()
+                        .any(|c| c.position == position && c.replacement == original && c.address == address));
+                    assert!(corrections.windows(2).all(|w| w[0].position < w[1].position));
+                },
+                EmojiIdCheck::Valid(_) => panic!("A substitution must break the checksum"),
+            }
+        }
+    }
+
+    #[test]
+    fn it_rejects_uncorrectable_emoji_ids() {
+        let address = random_address();
+        let emoji = address.to_emoji_string();
+
+        let short = emoji.chars().skip(1).collect::<String>();
+        assert_eq!(
+            check_emoji_id(&short, Network::Esmeralda),
+            Err(TariAddressError::InvalidSize)
+        );
+
+        let two_unknown = replace_at(&replace_at(&emoji, 1, '🎅'), 2, '🎅');
+        assert_eq!(
+            check_emoji_id(&two_unknown, Network::Esmeralda),
+            Err(TariAddressError::InvalidEmoji)
+        );
+    }
+}
@@ -20,7 +20,10 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::mem::size_of;
+use std::{
+    mem::size_of,
+    sync::{Arc, PoisonError, RwLock},
+};
 
 use chacha20poly1305::{
     aead::{Aead, Payload},
@@ -42,6 +45,9 @@ pub trait Encryptable<C> {
     const KNOWN_ONESIDED_PAYMENT_SCRIPT: &'static [u8] = b"KNOWN_ONESIDED_PAYMENT_SCRIPT";
     const CLIENT_KEY_VALUE: &'static [u8] = b"CLIENT_KEY_VALUE";
     const BURNT_PROOF: &'static [u8] = b"BURNT_PROOF";
+    const SCHEDULED_PAYMENT: &'static [u8] = b"SCHEDULED_PAYMENT";
+    const RATCHET_SESSION: &'static [u8] = b"RATCHET_SESSION";
+    const CONTACT: &'static [u8] = b"CONTACT";
 
     fn domain(&self, field_name: &'static str) -> Vec<u8>;
     fn encrypt(self, cipher: &C) -> Result<Self, String>
@@ -50,6 +56,42 @@ pub trait Encryptable<C> {
     where Self: Sized;
 }
 
+/// A database cipher that is shared by every storage backend using it, so that it can be removed from memory for all of
+/// them at once while the database is locked
+#[derive(Clone)]
+pub struct SharedCipher(Arc<RwLock<Option<XChaCha20Poly1305>>>);
+
+impl SharedCipher {
+    pub fn new(cipher: XChaCha20Poly1305) -> Self {
+        Self(Arc::new(RwLock::new(Some(cipher))))
+    }
+
+    /// Returns the cipher, or None if it has been cleared
+    pub fn get(&self) -> Option<XChaCha20Poly1305> {
+        self.0.read().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+
+    /// Restores the cipher for every backend sharing it
+    pub fn set(&self, cipher: XChaCha20Poly1305) {
+        *self.0.write().unwrap_or_else(PoisonError::into_inner) = Some(cipher);
+    }
+
+    /// Removes the cipher from memory for every backend sharing it
+    pub fn clear(&self) {
+        *self.0.write().unwrap_or_else(PoisonError::into_inner) = None;
+    }
+
+    pub fn is_cleared(&self) -> bool {
+        self.0.read().unwrap_or_else(PoisonError::into_inner).is_none()
+    }
+}
+
+impl From<XChaCha20Poly1305> for SharedCipher {
+    fn from(cipher: XChaCha20Poly1305) -> Self {
+        Self::new(cipher)
+    }
+}
+
 // Decrypt data (with domain binding and authentication) using XChaCha20-Poly1305
 pub fn decrypt_bytes_integral_nonce(
     cipher: &XChaCha20Poly1305,
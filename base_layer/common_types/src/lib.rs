@@ -20,6 +20,7 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+pub mod address_utils;
 pub mod burnt_proof;
 pub mod chain_metadata;
 pub mod dammsum;
@@ -29,6 +30,7 @@ pub mod epoch;
 pub mod grpc_authentication;
 pub mod serializers;
 pub mod tari_address;
+pub mod tari_uri;
 pub mod transaction;
 mod tx_id;
 pub mod types;
@@ -38,7 +38,7 @@ use crate::{
     types::PublicKey,
 };
 
-const INTERNAL_SIZE: usize = 33; // number of bytes used for the internal representation
+pub(crate) const INTERNAL_SIZE: usize = 33; // number of bytes used for the internal representation
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub struct TariAddress {
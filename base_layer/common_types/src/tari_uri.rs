@@ -0,0 +1,448 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Tari applications hand actions to each other, e.g. in a QR code or a link, as deep links of the form
+//! `tari://<network>/<action>?<parameters>`. The supported actions are:
+//!
+//! - `transactions/send?tariAddress=<address>[&amount=<amount in µT>][&message=<message>]`
+//! - `contacts/add?tariAddress=<address>[&alias=<alias>]`
+//! - `base_nodes/add?[name=<name>&]peer=<public key>::<address>[::<address>...]`
+//! - `templates/call?template=<template address>&function=<function>[&arg=<argument>...]`
+//!
+//! Parameter values are percent-encoded. Unrecognised parameters are ignored so that newer applications can extend
+//! an action without breaking older ones.
+
+use std::{
+    fmt,
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use tari_common::configuration::Network;
+use tari_utilities::hex::Hex;
+use thiserror::Error;
+
+use crate::{
+    tari_address::TariAddress,
+    types::{FixedHash, PublicKey},
+};
+
+pub const TARI_URI_SCHEME: &str = "tari://";
+
+const SEND_PATH: &str = "transactions/send";
+const ADD_CONTACT_PATH: &str = "contacts/add";
+const ADD_BASE_NODE_PATH: &str = "base_nodes/add";
+const CALL_TEMPLATE_PATH: &str = "templates/call";
+
+const ADDRESS_PARAM: &str = "tariAddress";
+const AMOUNT_PARAM: &str = "amount";
+const MESSAGE_PARAM: &str = "message";
+const ALIAS_PARAM: &str = "alias";
+const NAME_PARAM: &str = "name";
+const PEER_PARAM: &str = "peer";
+const TEMPLATE_PARAM: &str = "template";
+const FUNCTION_PARAM: &str = "function";
+const ARG_PARAM: &str = "arg";
+
+const PEER_SEPARATOR: &str = "::";
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum TariUriError {
+    #[error("Not a Tari URI")]
+    InvalidScheme,
+    #[error("Unsupported Tari URI action `{0}`")]
+    UnsupportedAction(String),
+    #[error("Unknown network `{0}`")]
+    InvalidNetwork(String),
+    #[error("The Tari URI does not contain the `{0}` parameter")]
+    MissingParameter(&'static str),
+    #[error("Invalid value `{value}` for the `{name}` parameter")]
+    InvalidParameter { name: &'static str, value: String },
+    #[error("The Tari address is for {address} but the URI is for {uri}")]
+    NetworkMismatch { address: Network, uri: Network },
+    #[error("Invalid percent-encoding in `{0}`")]
+    InvalidEncoding(String),
+}
+
+/// The action a Tari URI asks the receiving application to perform
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TariUriAction {
+    /// Send funds to an address, optionally for a specific amount (in µT) and with a message for the recipient
+    Send {
+        address: TariAddress,
+        amount: Option<u64>,
+        message: Option<String>,
+    },
+    /// Add an address to the contacts book
+    AddContact {
+        address: TariAddress,
+        alias: Option<String>,
+    },
+    /// Add a base node peer. Addresses are multiaddrs in their string form.
+    AddBaseNode {
+        name: Option<String>,
+        public_key: PublicKey,
+        addresses: Vec<String>,
+    },
+    /// Call a function on a template with the given arguments
+    CallTemplate {
+        template_address: FixedHash,
+        function: String,
+        args: Vec<String>,
+    },
+}
+
+impl TariUriAction {
+    fn path(&self) -> &'static str {
+        match self {
+            TariUriAction::Send { .. } => SEND_PATH,
+            TariUriAction::AddContact { .. } => ADD_CONTACT_PATH,
+            TariUriAction::AddBaseNode { .. } => ADD_BASE_NODE_PATH,
+            TariUriAction::CallTemplate { .. } => CALL_TEMPLATE_PATH,
+        }
+    }
+
+    fn address(&self) -> Option<&TariAddress> {
+        match self {
+            TariUriAction::Send { address, .. } | TariUriAction::AddContact { address, .. } => Some(address),
+            TariUriAction::AddBaseNode { .. } | TariUriAction::CallTemplate { .. } => None,
+        }
+    }
+}
+
+/// A `tari://` deep link
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TariUri {
+    pub network: Network,
+    pub action: TariUriAction,
+}
+
+impl TariUri {
+    pub fn new(network: Network, action: TariUriAction) -> Self {
+        Self { network, action }
+    }
+
+    fn query(&self) -> Vec<(&'static str, String)> {
+        let mut params = Vec::new();
+        match &self.action {
+            TariUriAction::Send {
+                address,
+                amount,
+                message,
+            } => {
+                params.push((ADDRESS_PARAM, address.to_hex()));
+                if let Some(amount) = amount {
+                    params.push((AMOUNT_PARAM, amount.to_string()));
+                }
+                if let Some(message) = message.as_ref().filter(|m| !m.is_empty()) {
+                    params.push((MESSAGE_PARAM, message.clone()));
+                }
+            },
+            TariUriAction::AddContact { address, alias } => {
+                params.push((ADDRESS_PARAM, address.to_hex()));
+                if let Some(alias) = alias.as_ref().filter(|a| !a.is_empty()) {
+                    params.push((ALIAS_PARAM, alias.clone()));
+                }
+            },
+            TariUriAction::AddBaseNode {
+                name,
+                public_key,
+                addresses,
+            } => {
+                if let Some(name) = name.as_ref().filter(|n| !n.is_empty()) {
+                    params.push((NAME_PARAM, name.clone()));
+                }
+                let peer = Some(public_key.to_hex())
+                    .into_iter()
+                    .chain(addresses.iter().cloned())
+                    .collect::<Vec<_>>()
+                    .join(PEER_SEPARATOR);
+                params.push((PEER_PARAM, peer));
+            },
+            TariUriAction::CallTemplate {
+                template_address,
+                function,
+                args,
+            } => {
+                params.push((TEMPLATE_PARAM, template_address.to_hex()));
+                params.push((FUNCTION_PARAM, function.clone()));
+                params.extend(args.iter().map(|arg| (ARG_PARAM, arg.clone())));
+            },
+        }
+        params
+    }
+}
+
+impl Display for TariUri {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}/{}", TARI_URI_SCHEME, self.network, self.action.path())?;
+        for (i, (key, value)) in self.query().iter().enumerate() {
+            let separator = if i == 0 { '?' } else { '&' };
+            write!(f, "{}{}={}", separator, key, percent_encode(value))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for TariUri {
+    type Err = TariUriError;
+
+    fn from_str(uri: &str) -> Result<Self, Self::Err> {
+        let uri = uri.trim();
+        let rest = uri
+            .get(..TARI_URI_SCHEME.len())
+            .filter(|scheme| scheme.eq_ignore_ascii_case(TARI_URI_SCHEME))
+            .map(|_| &uri[TARI_URI_SCHEME.len()..])
+            .ok_or(TariUriError::InvalidScheme)?;
+        let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let (network, path) = path.split_once('/').unwrap_or((path, ""));
+        let network = Network::from_str(network).map_err(|_| TariUriError::InvalidNetwork(network.to_string()))?;
+        let params = QueryParams::parse(query)?;
+
+        let action = match path.trim_end_matches('/') {
+            SEND_PATH => TariUriAction::Send {
+                address: params.address()?,
+                amount: params
+                    .get(AMOUNT_PARAM)
+                    .map(|amount| {
+                        amount.parse::<u64>().map_err(|_| TariUriError::InvalidParameter {
+                            name: AMOUNT_PARAM,
+                            value: amount.to_string(),
+                        })
+                    })
+                    .transpose()?,
+                message: params.get(MESSAGE_PARAM).map(ToString::to_string),
+            },
+            ADD_CONTACT_PATH => TariUriAction::AddContact {
+                address: params.address()?,
+                alias: params.get(ALIAS_PARAM).map(ToString::to_string),
+            },
+            ADD_BASE_NODE_PATH => {
+                let peer = params.require(PEER_PARAM)?;
+                let mut parts = peer.split(PEER_SEPARATOR);
+                let public_key = parts
+                    .next()
+                    .and_then(|public_key| PublicKey::from_hex(public_key).ok())
+                    .ok_or_else(|| TariUriError::InvalidParameter {
+                        name: PEER_PARAM,
+                        value: peer.to_string(),
+                    })?;
+                TariUriAction::AddBaseNode {
+                    name: params.get(NAME_PARAM).map(ToString::to_string),
+                    public_key,
+                    addresses: parts.filter(|a| !a.is_empty()).map(ToString::to_string).collect(),
+                }
+            },
+            CALL_TEMPLATE_PATH => {
+                let template_address = params.require(TEMPLATE_PARAM)?;
+                TariUriAction::CallTemplate {
+                    template_address: FixedHash::from_hex(template_address).map_err(|_| {
+                        TariUriError::InvalidParameter {
+                            name: TEMPLATE_PARAM,
+                            value: template_address.to_string(),
+                        }
+                    })?,
+                    function: params.require(FUNCTION_PARAM)?.to_string(),
+                    args: params.get_all(ARG_PARAM).map(ToString::to_string).collect(),
+                }
+            },
+            path => return Err(TariUriError::UnsupportedAction(path.to_string())),
+        };
+
+        if let Some(address) = action.address() {
+            if address.network() != network {
+                return Err(TariUriError::NetworkMismatch {
+                    address: address.network(),
+                    uri: network,
+                });
+            }
+        }
+        Ok(Self { network, action })
+    }
+}
+
+/// The percent-decoded query parameters of a URI, in order
+struct QueryParams(Vec<(String, String)>);
+
+impl QueryParams {
+    fn parse(query: &str) -> Result<Self, TariUriError> {
+        query
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .map(|(key, value)| Ok::<_, TariUriError>((percent_decode(key)?, percent_decode(value)?)))
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+
+    fn get_all<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.0
+            .iter()
+            .filter(move |(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn get(&self, name: &str) -> Option<&str> {
+        self.get_all(name).next()
+    }
+
+    fn require(&self, name: &'static str) -> Result<&str, TariUriError> {
+        self.get(name).ok_or(TariUriError::MissingParameter(name))
+    }
+
+    fn address(&self) -> Result<TariAddress, TariUriError> {
+        let address = self.require(ADDRESS_PARAM)?;
+        TariAddress::from_str(address).map_err(|_| TariUriError::InvalidParameter {
+            name: ADDRESS_PARAM,
+            value: address.to_string(),
+        })
+    }
+}
+
+/// Percent-encodes everything except the characters that RFC 3986 allows unescaped in a query value, so that peer
+/// multiaddrs stay readable
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' | b':' | b'@' => {
+                char::from(b).to_string()
+            },
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+fn percent_decode(value: &str) -> Result<String, TariUriError> {
+    let invalid = || TariUriError::InvalidEncoding(value.to_string());
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut iter = value.bytes();
+    while let Some(b) = iter.next() {
+        match b {
+            b'%' => {
+                let hex = [iter.next().ok_or_else(invalid)?, iter.next().ok_or_else(invalid)?];
+                let hex = std::str::from_utf8(&hex).map_err(|_| invalid())?;
+                bytes.push(u8::from_str_radix(hex, 16).map_err(|_| invalid())?);
+            },
+            b'+' => bytes.push(b' '),
+            _ => bytes.push(b),
+        }
+    }
+    String::from_utf8(bytes).map_err(|_| invalid())
+}
+
+#[cfg(test)]
+mod test {
+    use tari_crypto::keys::PublicKey as PublicKeyTrait;
+
+    use super::*;
+
+    fn public_key() -> PublicKey {
+        let (_, public_key) = PublicKey::random_keypair(&mut rand::rngs::OsRng);
+        public_key
+    }
+
+    fn round_trip(uri: &TariUri) {
+        let encoded = uri.to_string();
+        assert!(!encoded.contains(' '));
+        assert_eq!(&encoded.parse::<TariUri>().unwrap(), uri);
+    }
+
+    #[test]
+    fn it_round_trips_every_action() {
+        let address = TariAddress::new(public_key(), Network::Esmeralda);
+        round_trip(&TariUri::new(Network::Esmeralda, TariUriAction::Send {
+            address: address.clone(),
+            amount: Some(1_234_567),
+            message: Some("Coffee & cake, 100% 🍰".to_string()),
+        }));
+        round_trip(&TariUri::new(Network::Esmeralda, TariUriAction::AddContact {
+            address,
+            alias: Some("Alice=Bob?".to_string()),
+        }));
+        round_trip(&TariUri::new(Network::LocalNet, TariUriAction::AddBaseNode {
+            name: Some("node".to_string()),
+            public_key: public_key(),
+            addresses: vec!["/ip4/127.0.0.1/tcp/18189".to_string(), "/onion3/abc:18141".to_string()],
+        }));
+        round_trip(&TariUri::new(Network::LocalNet, TariUriAction::CallTemplate {
+            template_address: FixedHash::zero(),
+            function: "mint".to_string(),
+            args: vec!["1".to_string(), "a&b".to_string()],
+        }));
+    }
+
+    #[test]
+    fn it_matches_the_base_node_link_format() {
+        let public_key = public_key();
+        let uri = format!(
+            "tari://localnet/base_nodes/add?name=abc&peer={}::/ip4/127.0.0.1/tcp/18189",
+            public_key.to_hex()
+        );
+        let parsed = uri.parse::<TariUri>().unwrap();
+        assert_eq!(parsed.action, TariUriAction::AddBaseNode {
+            name: Some("abc".to_string()),
+            public_key,
+            addresses: vec!["/ip4/127.0.0.1/tcp/18189".to_string()],
+        });
+        assert_eq!(parsed.to_string(), uri);
+    }
+
+    #[test]
+    fn it_rejects_invalid_uris() {
+        let address = TariAddress::new(public_key(), Network::LocalNet);
+        assert_eq!("https://tari.com".parse::<TariUri>(), Err(TariUriError::InvalidScheme));
+        assert_eq!(
+            "tari://nonet/contacts/add".parse::<TariUri>(),
+            Err(TariUriError::InvalidNetwork("nonet".to_string()))
+        );
+        assert_eq!(
+            "tari://localnet/wallets/delete".parse::<TariUri>(),
+            Err(TariUriError::UnsupportedAction("wallets/delete".to_string()))
+        );
+        assert_eq!(
+            "tari://localnet/transactions/send?amount=5".parse::<TariUri>(),
+            Err(TariUriError::MissingParameter(ADDRESS_PARAM))
+        );
+        assert_eq!(
+            format!(
+                "tari://localnet/transactions/send?tariAddress={}&amount=-1",
+                address.to_hex()
+            )
+            .parse::<TariUri>(),
+            Err(TariUriError::InvalidParameter {
+                name: AMOUNT_PARAM,
+                value: "-1".to_string()
+            })
+        );
+        assert_eq!(
+            format!("tari://esmeralda/contacts/add?tariAddress={}", address.to_hex()).parse::<TariUri>(),
+            Err(TariUriError::NetworkMismatch {
+                address: Network::LocalNet,
+                uri: Network::Esmeralda
+            })
+        );
+        assert_eq!(
+            "tari://localnet/transactions/send?message=%G1".parse::<TariUri>(),
+            Err(TariUriError::InvalidEncoding("%G1".to_string()))
+        );
+    }
+}
@@ -32,8 +32,8 @@ use tari_common_types::tari_address::TariAddress;
 use tari_comms::{CommsNode, NodeIdentity};
 use tari_contacts::contacts_service::{
     handle::ContactsServiceHandle,
-    service::ContactOnlineStatus,
-    types::{Message, MessageBuilder, MessageMetadata, MessageMetadataType},
+    service::{ContactOnlineStatus, LivenessPrivacyMode},
+    types::{Message, MessageBuilder, MessageMetadata, MessageMetadataType, SenderListType},
 };
 use tari_shutdown::Shutdown;
 
@@ -50,6 +50,16 @@ pub trait ChatClient {
     async fn get_messages(&self, sender: &TariAddress, limit: u64, page: u64) -> Vec<Message>;
     async fn send_message(&self, message: Message);
     async fn send_read_receipt(&self, message: Message);
+    async fn set_liveness_privacy_mode(&self, mode: LivenessPrivacyMode);
+    async fn set_contact_liveness_privacy_mode(&self, address: &TariAddress, mode: Option<LivenessPrivacyMode>);
+    async fn request_device_link(&self, identity: &TariAddress);
+    async fn approve_device_link(&self, device: &TariAddress);
+    async fn unlink_device(&self, device: &TariAddress);
+    async fn set_message_requests_enabled(&self, enabled: bool);
+    async fn accept_message_request(&self, address: &TariAddress);
+    async fn reject_message_request(&self, address: &TariAddress, block: bool);
+    async fn block_address(&self, address: &TariAddress);
+    async fn unblock_address(&self, address: &TariAddress);
     fn identity(&self) -> &NodeIdentity;
     fn shutdown(&mut self);
 }
@@ -181,6 +191,96 @@ impl ChatClient for Client {
         }
     }
 
+    async fn set_liveness_privacy_mode(&self, mode: LivenessPrivacyMode) {
+        if let Some(mut contacts_service) = self.contacts.clone() {
+            contacts_service
+                .set_liveness_privacy_mode(mode)
+                .await
+                .expect("Liveness privacy mode not set");
+        }
+    }
+
+    async fn set_contact_liveness_privacy_mode(&self, address: &TariAddress, mode: Option<LivenessPrivacyMode>) {
+        if let Some(mut contacts_service) = self.contacts.clone() {
+            contacts_service
+                .set_contact_liveness_privacy_mode(address.clone(), mode)
+                .await
+                .expect("Contact liveness privacy mode not set");
+        }
+    }
+
+    async fn request_device_link(&self, identity: &TariAddress) {
+        if let Some(mut contacts_service) = self.contacts.clone() {
+            contacts_service
+                .request_device_link(identity.clone())
+                .await
+                .expect("Device link not requested");
+        }
+    }
+
+    async fn approve_device_link(&self, device: &TariAddress) {
+        if let Some(mut contacts_service) = self.contacts.clone() {
+            contacts_service
+                .approve_device_link(device.clone())
+                .await
+                .expect("Device link not approved");
+        }
+    }
+
+    async fn unlink_device(&self, device: &TariAddress) {
+        if let Some(mut contacts_service) = self.contacts.clone() {
+            contacts_service
+                .unlink_device(device.clone())
+                .await
+                .expect("Device not unlinked");
+        }
+    }
+
+    async fn set_message_requests_enabled(&self, enabled: bool) {
+        if let Some(mut contacts_service) = self.contacts.clone() {
+            contacts_service
+                .set_message_requests_enabled(enabled)
+                .await
+                .expect("Message requests not enabled");
+        }
+    }
+
+    async fn accept_message_request(&self, address: &TariAddress) {
+        if let Some(mut contacts_service) = self.contacts.clone() {
+            contacts_service
+                .accept_message_request(address.clone())
+                .await
+                .expect("Message request not accepted");
+        }
+    }
+
+    async fn reject_message_request(&self, address: &TariAddress, block: bool) {
+        if let Some(mut contacts_service) = self.contacts.clone() {
+            contacts_service
+                .reject_message_request(address.clone(), block)
+                .await
+                .expect("Message request not rejected");
+        }
+    }
+
+    async fn block_address(&self, address: &TariAddress) {
+        if let Some(mut contacts_service) = self.contacts.clone() {
+            contacts_service
+                .set_sender_list_type(address.clone(), Some(SenderListType::Blocked))
+                .await
+                .expect("Address not blocked");
+        }
+    }
+
+    async fn unblock_address(&self, address: &TariAddress) {
+        if let Some(mut contacts_service) = self.contacts.clone() {
+            contacts_service
+                .set_sender_list_type(address.clone(), None)
+                .await
+                .expect("Address not unblocked");
+        }
+    }
+
     fn create_message(&self, receiver: &TariAddress, message: String) -> Message {
         MessageBuilder::new().address(receiver.clone()).message(message).build()
     }
@@ -27,13 +27,20 @@ use tari_common_sqlite::{
     connection::{DbConnection, DbConnectionUrl},
     error::StorageError,
 };
-use tari_contacts::contacts_service::storage::sqlite_db::ContactsServiceSqliteDatabase;
+use tari_comms::NodeIdentity;
+use tari_contacts::contacts_service::storage::sqlite_db::{cipher_from_node_identity, ContactsServiceSqliteDatabase};
 use tari_storage::lmdb_store::{LMDBBuilder, LMDBConfig};
 
-pub fn connect_to_db(db_path: PathBuf) -> Result<ContactsServiceSqliteDatabase<DbConnection>, StorageError> {
+pub fn connect_to_db(
+    db_path: PathBuf,
+    node_identity: &NodeIdentity,
+) -> Result<ContactsServiceSqliteDatabase<DbConnection>, StorageError> {
     let url: DbConnectionUrl = db_path.into_os_string().into_string().unwrap().try_into().unwrap();
     let connection = DbConnection::connect_url(&url)?;
-    Ok(ContactsServiceSqliteDatabase::init(connection))
+    Ok(ContactsServiceSqliteDatabase::init(
+        connection,
+        cipher_from_node_identity(node_identity.secret_key()),
+    ))
 }
 
 pub fn create_chat_storage(db_file_path: &PathBuf) {
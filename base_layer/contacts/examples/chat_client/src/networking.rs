@@ -49,7 +49,7 @@ pub async fn start(
     shutdown_signal: ShutdownSignal,
 ) -> anyhow::Result<(ContactsServiceHandle, CommsNode)> {
     create_chat_storage(&config.chat_client.db_file);
-    let backend = connect_to_db(config.chat_client.db_file)?;
+    let backend = connect_to_db(config.chat_client.db_file, &node_identity)?;
 
     let (publisher, subscription_factory) = pubsub_connector(100);
     let in_msg = Arc::new(subscription_factory);
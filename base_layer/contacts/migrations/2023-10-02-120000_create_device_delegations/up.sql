@@ -0,0 +1,11 @@
+CREATE TABLE device_delegations (
+    device_public_key     BLOB PRIMARY KEY NOT NULL,
+    identity_public_key   BLOB             NOT NULL,
+    timestamp             BIGINT           NOT NULL,
+    device_public_nonce   BLOB             NOT NULL,
+    device_signature      BLOB             NOT NULL,
+    identity_public_nonce BLOB             NOT NULL,
+    identity_signature    BLOB             NOT NULL
+);
+
+CREATE INDEX idx_device_delegations_identity_public_key ON device_delegations (identity_public_key);
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS message_requests;
+DROP TABLE IF EXISTS sender_lists;
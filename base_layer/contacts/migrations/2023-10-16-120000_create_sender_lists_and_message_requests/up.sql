@@ -0,0 +1,15 @@
+CREATE TABLE sender_lists (
+    public_key BLOB PRIMARY KEY NOT NULL,
+    address    BLOB             NOT NULL,
+    list_type  INTEGER          NOT NULL
+);
+
+CREATE TABLE message_requests (
+    address    BLOB             NOT NULL,
+    message_id BLOB PRIMARY KEY NOT NULL,
+    body       BLOB             NOT NULL,
+    metadata   BLOB             NOT NULL,
+    stored_at  TIMESTAMP        NOT NULL
+);
+
+CREATE INDEX idx_message_requests_address ON message_requests (address);
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS ratchet_sessions;
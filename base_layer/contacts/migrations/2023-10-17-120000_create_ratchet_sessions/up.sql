@@ -0,0 +1,5 @@
+CREATE TABLE ratchet_sessions (
+    public_key BLOB PRIMARY KEY NOT NULL,
+    state      BLOB             NOT NULL,
+    updated_at TIMESTAMP        NOT NULL
+);
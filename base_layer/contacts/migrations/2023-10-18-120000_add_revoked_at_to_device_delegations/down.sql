@@ -0,0 +1 @@
+ALTER TABLE device_delegations drop revoked_at;
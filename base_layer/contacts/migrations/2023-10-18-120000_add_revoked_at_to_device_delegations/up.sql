@@ -0,0 +1 @@
+ALTER TABLE device_delegations ADD revoked_at BIGINT NULL;
@@ -0,0 +1,4 @@
+CREATE TABLE contact_privacy_modes (
+    node_id      BLOB PRIMARY KEY NOT NULL,
+    privacy_mode INTEGER          NOT NULL
+);
@@ -53,6 +53,51 @@ pub enum ContactsServiceError {
     MalformedMessageError(#[from] prost::DecodeError),
     #[error("Message source does not match authenticated origin")]
     MessageSourceDoesNotMatchOrigin,
+    #[error("Device delegation signature is invalid")]
+    InvalidDeviceDelegation,
+    #[error("No link request was received from the device")]
+    DeviceLinkRequestNotFound,
+    #[error("The device is not linked to this node")]
+    DeviceLinkNotFound,
+    #[error("No message request was received from the sender")]
+    MessageRequestNotFound,
+    #[error("Chat session error: `{0}`")]
+    SessionError(#[from] SessionError),
+}
+
+#[derive(Debug, Error)]
+pub enum SessionError {
+    #[error("Session version {0} is not supported")]
+    UnsupportedVersion(u32),
+    #[error("Invalid ratchet header: `{0}`")]
+    InvalidHeader(String),
+    #[error("Invalid session state: `{0}`")]
+    InvalidState(String),
+    #[error("No session exists with the sender")]
+    NoSession,
+    #[error("Message is too far ahead of the receiving chain")]
+    TooManySkippedMessages,
+    #[error("Failed to encrypt the payload")]
+    EncryptionFailed,
+    #[error("Failed to decrypt the payload")]
+    DecryptionFailed,
+    #[error("Invalid payload: `{0}`")]
+    InvalidPayload(String),
+}
+
+impl SessionError {
+    /// Returns true if the error means that the sender is using a session that this node cannot open, so both sides
+    /// have to start a new session
+    pub fn requires_new_session(&self) -> bool {
+        matches!(
+            self,
+            SessionError::InvalidHeader(_) |
+                SessionError::InvalidState(_) |
+                SessionError::NoSession |
+                SessionError::TooManySkippedMessages |
+                SessionError::DecryptionFailed
+        )
+    }
 }
 
 #[derive(Debug, Error)]
@@ -77,6 +122,10 @@ pub enum ContactsServiceStorageError {
     DatabaseMigrationError(String),
     #[error("Blocking task spawn error: `{0}`")]
     BlockingTaskSpawnError(String),
+    #[error("The contacts database is locked")]
+    DatabaseLocked,
+    #[error("Aead error: `{0}`")]
+    AeadError(String),
     #[error("We got an error")]
     UnknownError,
 }
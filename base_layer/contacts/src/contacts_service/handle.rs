@@ -36,8 +36,8 @@ use tower::Service;
 
 use crate::contacts_service::{
     error::ContactsServiceError,
-    service::{ContactMessageType, ContactOnlineStatus},
-    types::{Confirmation, Contact, Message, MessageDispatch},
+    service::{ContactMessageType, ContactOnlineStatus, LivenessPrivacyMode},
+    types::{Confirmation, Contact, DeviceDelegation, Message, MessageDispatch, SenderListType},
 };
 
 pub static DEFAULT_MESSAGE_LIMIT: u64 = 35;
@@ -139,6 +139,19 @@ pub enum ContactsServiceRequest {
     SendMessage(TariAddress, Message),
     GetMessages(TariAddress, i64, i64),
     SendReadConfirmation(TariAddress, Confirmation),
+    SetLivenessPrivacyMode(LivenessPrivacyMode),
+    SetContactLivenessPrivacyMode(TariAddress, Option<LivenessPrivacyMode>),
+    RequestDeviceLink(TariAddress),
+    ApproveDeviceLink(TariAddress),
+    GetDeviceLinkRequests,
+    GetLinkedDevices(TariAddress),
+    UnlinkDevice(TariAddress),
+    SetMessageRequestsEnabled(bool),
+    GetMessageRequests,
+    AcceptMessageRequest(TariAddress),
+    RejectMessageRequest(TariAddress, bool),
+    SetSenderListType(TariAddress, Option<SenderListType>),
+    GetSenderList(SenderListType),
 }
 
 #[derive(Debug)]
@@ -151,6 +164,18 @@ pub enum ContactsServiceResponse {
     Messages(Vec<Message>),
     MessageSent,
     ReadConfirmationSent,
+    LivenessPrivacyModeSet,
+    DeviceLinkRequested,
+    DeviceLinked(DeviceDelegation),
+    DeviceLinkRequests(Vec<DeviceDelegation>),
+    LinkedDevices(Vec<DeviceDelegation>),
+    DeviceUnlinked,
+    MessageRequestsEnabledSet,
+    MessageRequests(Vec<Message>),
+    MessageRequestAccepted(Vec<Message>),
+    MessageRequestRejected,
+    SenderListUpdated,
+    SenderList(Vec<TariAddress>),
 }
 
 #[derive(Clone)]
@@ -221,6 +246,184 @@ impl ContactsServiceHandle {
         }
     }
 
+    /// Sets who this node shares its online status and last seen time with
+    pub async fn set_liveness_privacy_mode(&mut self, mode: LivenessPrivacyMode) -> Result<(), ContactsServiceError> {
+        match self
+            .request_response_service
+            .call(ContactsServiceRequest::SetLivenessPrivacyMode(mode))
+            .await??
+        {
+            ContactsServiceResponse::LivenessPrivacyModeSet => Ok(()),
+            _ => Err(ContactsServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Overrides the liveness privacy mode for a single peer, `None` reverts the peer to the global mode
+    pub async fn set_contact_liveness_privacy_mode(
+        &mut self,
+        address: TariAddress,
+        mode: Option<LivenessPrivacyMode>,
+    ) -> Result<(), ContactsServiceError> {
+        match self
+            .request_response_service
+            .call(ContactsServiceRequest::SetContactLivenessPrivacyMode(address, mode))
+            .await??
+        {
+            ContactsServiceResponse::LivenessPrivacyModeSet => Ok(()),
+            _ => Err(ContactsServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Ask the chat identity at `identity` to link this node as one of its devices
+    pub async fn request_device_link(&mut self, identity: TariAddress) -> Result<(), ContactsServiceError> {
+        match self
+            .request_response_service
+            .call(ContactsServiceRequest::RequestDeviceLink(identity))
+            .await??
+        {
+            ContactsServiceResponse::DeviceLinkRequested => Ok(()),
+            _ => Err(ContactsServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Approve a pending link request from the device at `device`, after which contacts will send messages for this
+    /// identity to the device as well
+    pub async fn approve_device_link(&mut self, device: TariAddress) -> Result<DeviceDelegation, ContactsServiceError> {
+        match self
+            .request_response_service
+            .call(ContactsServiceRequest::ApproveDeviceLink(device))
+            .await??
+        {
+            ContactsServiceResponse::DeviceLinked(delegation) => Ok(delegation),
+            _ => Err(ContactsServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Returns the link requests received from devices that have not been approved yet
+    pub async fn get_device_link_requests(&mut self) -> Result<Vec<DeviceDelegation>, ContactsServiceError> {
+        match self
+            .request_response_service
+            .call(ContactsServiceRequest::GetDeviceLinkRequests)
+            .await??
+        {
+            ContactsServiceResponse::DeviceLinkRequests(requests) => Ok(requests),
+            _ => Err(ContactsServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Returns the devices linked to the given identity
+    pub async fn get_linked_devices(
+        &mut self,
+        identity: TariAddress,
+    ) -> Result<Vec<DeviceDelegation>, ContactsServiceError> {
+        match self
+            .request_response_service
+            .call(ContactsServiceRequest::GetLinkedDevices(identity))
+            .await??
+        {
+            ContactsServiceResponse::LinkedDevices(devices) => Ok(devices),
+            _ => Err(ContactsServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Unlink the device at `device` from this identity, or unlink this node from its identity if `device` is our own
+    /// address. Contacts stop sending messages for the identity to the device.
+    pub async fn unlink_device(&mut self, device: TariAddress) -> Result<(), ContactsServiceError> {
+        match self
+            .request_response_service
+            .call(ContactsServiceRequest::UnlinkDevice(device))
+            .await??
+        {
+            ContactsServiceResponse::DeviceUnlinked => Ok(()),
+            _ => Err(ContactsServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// When enabled, messages from senders that are not contacts, not on the allow list and that we have no
+    /// conversation with are quarantined as message requests instead of being added to the conversation
+    pub async fn set_message_requests_enabled(&mut self, enabled: bool) -> Result<(), ContactsServiceError> {
+        match self
+            .request_response_service
+            .call(ContactsServiceRequest::SetMessageRequestsEnabled(enabled))
+            .await??
+        {
+            ContactsServiceResponse::MessageRequestsEnabledSet => Ok(()),
+            _ => Err(ContactsServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Returns the quarantined first-contact messages, oldest first
+    pub async fn get_message_requests(&mut self) -> Result<Vec<Message>, ContactsServiceError> {
+        match self
+            .request_response_service
+            .call(ContactsServiceRequest::GetMessageRequests)
+            .await??
+        {
+            ContactsServiceResponse::MessageRequests(messages) => Ok(messages),
+            _ => Err(ContactsServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Moves the quarantined messages from the sender into the conversation and adds the sender to the allow list.
+    /// Returns the accepted messages.
+    pub async fn accept_message_request(&mut self, address: TariAddress) -> Result<Vec<Message>, ContactsServiceError> {
+        match self
+            .request_response_service
+            .call(ContactsServiceRequest::AcceptMessageRequest(address))
+            .await??
+        {
+            ContactsServiceResponse::MessageRequestAccepted(messages) => Ok(messages),
+            _ => Err(ContactsServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Discards the quarantined messages from the sender, optionally adding the sender to the block list
+    pub async fn reject_message_request(
+        &mut self,
+        address: TariAddress,
+        block: bool,
+    ) -> Result<(), ContactsServiceError> {
+        match self
+            .request_response_service
+            .call(ContactsServiceRequest::RejectMessageRequest(address, block))
+            .await??
+        {
+            ContactsServiceResponse::MessageRequestRejected => Ok(()),
+            _ => Err(ContactsServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Places the sender on the allow or block list, `None` removes the sender from both lists
+    pub async fn set_sender_list_type(
+        &mut self,
+        address: TariAddress,
+        list_type: Option<SenderListType>,
+    ) -> Result<(), ContactsServiceError> {
+        match self
+            .request_response_service
+            .call(ContactsServiceRequest::SetSenderListType(address, list_type))
+            .await??
+        {
+            ContactsServiceResponse::SenderListUpdated => Ok(()),
+            _ => Err(ContactsServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Returns the addresses of the senders on the allow or block list
+    pub async fn get_sender_list(
+        &mut self,
+        list_type: SenderListType,
+    ) -> Result<Vec<TariAddress>, ContactsServiceError> {
+        match self
+            .request_response_service
+            .call(ContactsServiceRequest::GetSenderList(list_type))
+            .await??
+        {
+            ContactsServiceResponse::SenderList(addresses) => Ok(addresses),
+            _ => Err(ContactsServiceError::UnexpectedApiResponse),
+        }
+    }
+
     pub fn get_contacts_liveness_event_stream(&self) -> broadcast::Receiver<Arc<ContactsLivenessEvent>> {
         self.liveness_events.subscribe()
     }
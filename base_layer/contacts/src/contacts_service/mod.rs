@@ -24,6 +24,7 @@ pub mod error;
 pub mod handle;
 pub mod proto;
 pub mod service;
+pub mod session;
 pub mod storage;
 pub mod types;
 
@@ -31,7 +32,7 @@ use std::{sync::Arc, time::Duration};
 
 use futures::future;
 use log::*;
-use tari_comms::connectivity::ConnectivityRequester;
+use tari_comms::{connectivity::ConnectivityRequester, CommsNode};
 use tari_comms_dht::Dht;
 use tari_p2p::{comms_connector::SubscriptionFactory, services::liveness::LivenessHandle};
 use tari_service_framework::{
@@ -106,6 +107,7 @@ where T: ContactsBackend + 'static
             let liveness = handles.expect_handle::<LivenessHandle>();
             let connectivity = handles.expect_handle::<ConnectivityRequester>();
             let dht = handles.expect_handle::<Dht>();
+            let node_identity = handles.expect_handle::<CommsNode>().node_identity();
 
             let service = ContactsService::new(
                 ContactsDatabase::new(backend),
@@ -114,6 +116,7 @@ where T: ContactsBackend + 'static
                 liveness,
                 connectivity,
                 dht,
+                node_identity,
                 subscription_factory,
                 publisher,
                 message_publisher,
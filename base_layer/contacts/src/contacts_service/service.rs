@@ -21,6 +21,7 @@
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use std::{
+    collections::{HashMap, HashSet},
     convert::TryFrom,
     fmt::{Display, Error, Formatter},
     ops::Sub,
@@ -34,6 +35,7 @@ use log::*;
 use tari_common_types::tari_address::TariAddress;
 use tari_comms::{
     connectivity::{ConnectivityEvent, ConnectivityRequester},
+    peer_manager::{NodeId, NodeIdentity},
     types::CommsPublicKey,
 };
 use tari_comms_dht::{domain_message::OutboundDomainMessage, outbound::OutboundEncryption, Dht};
@@ -41,7 +43,7 @@ use tari_p2p::{
     comms_connector::SubscriptionFactory,
     domain_message::DomainMessage,
     services::{
-        liveness::{LivenessEvent, LivenessHandle, MetadataKey, PingPongEvent},
+        liveness::{LivenessEvent, LivenessHandle, MetadataKey, MetadataRecipients, PingPongEvent},
         utils::map_decode,
     },
     tari_message::TariMessageType,
@@ -52,15 +54,20 @@ use tari_utilities::{epoch_time::EpochTime, ByteArray};
 use tokio::sync::broadcast;
 
 use crate::contacts_service::{
-    error::ContactsServiceError,
+    error::{ContactsServiceError, ContactsServiceStorageError},
     handle::{ContactsLivenessData, ContactsLivenessEvent, ContactsServiceRequest, ContactsServiceResponse},
     proto,
+    session,
     storage::database::{ContactsBackend, ContactsDatabase},
-    types::{Confirmation, Contact, Message, MessageDispatch},
+    types::{Confirmation, Contact, DeviceDelegation, DeviceRevocation, Message, MessageDispatch, SenderListType},
 };
 
 const LOG_TARGET: &str = "contacts::contacts_service";
 const NUM_ROUNDS_NETWORK_SILENCE: u16 = 3;
+/// The most device link requests kept while waiting for approval, the oldest request is dropped to make room
+const MAX_DEVICE_LINK_REQUESTS: usize = 16;
+/// Device link requests older than this are dropped
+const DEVICE_LINK_REQUEST_EXPIRY: Duration = Duration::from_secs(24 * 60 * 60);
 pub const SUBSCRIPTION_LABEL: &str = "Chat";
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -120,6 +127,52 @@ impl Display for ContactOnlineStatus {
     }
 }
 
+/// Controls who this node shares its contacts liveness (online status and last seen time) with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LivenessPrivacyMode {
+    Always,
+    ContactsOnly,
+    Never,
+}
+
+impl LivenessPrivacyMode {
+    pub fn as_u8(self) -> u8 {
+        match self {
+            Self::Always => 1,
+            Self::ContactsOnly => 2,
+            Self::Never => 3,
+        }
+    }
+
+    pub fn from_byte(value: u8) -> Option<Self> {
+        match value {
+            1 => Some(Self::Always),
+            2 => Some(Self::ContactsOnly),
+            3 => Some(Self::Never),
+            _ => None,
+        }
+    }
+
+    /// Whether liveness is shared with a peer under this mode
+    pub fn shares_with(self, is_contact: bool) -> bool {
+        match self {
+            Self::Always => true,
+            Self::ContactsOnly => is_contact,
+            Self::Never => false,
+        }
+    }
+}
+
+impl Display for LivenessPrivacyMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self {
+            LivenessPrivacyMode::Always => write!(f, "Always"),
+            LivenessPrivacyMode::ContactsOnly => write!(f, "ContactsOnly"),
+            LivenessPrivacyMode::Never => write!(f, "Never"),
+        }
+    }
+}
+
 pub struct ContactsService<T>
 where T: ContactsBackend + 'static
 {
@@ -131,12 +184,20 @@ where T: ContactsBackend + 'static
     liveness_data: Vec<ContactsLivenessData>,
     connectivity: ConnectivityRequester,
     dht: Dht,
+    node_identity: Arc<NodeIdentity>,
     subscription_factory: Arc<SubscriptionFactory>,
     event_publisher: broadcast::Sender<Arc<ContactsLivenessEvent>>,
     message_publisher: broadcast::Sender<Arc<MessageDispatch>>,
     number_of_rounds_no_pings: u16,
     contacts_auto_ping_interval: Duration,
     contacts_online_ping_window: usize,
+    privacy_mode: LivenessPrivacyMode,
+    contact_privacy_modes: HashMap<NodeId, LivenessPrivacyMode>,
+    device_link_requests: HashMap<CommsPublicKey, DeviceDelegation>,
+    message_requests_enabled: bool,
+    peer_session_versions: HashMap<CommsPublicKey, u32>,
+    /// Nodes that were asked to start a new session and have not yet sent a payload that could be opened
+    pending_session_resets: HashSet<CommsPublicKey>,
 }
 
 impl<T> ContactsService<T>
@@ -152,6 +213,7 @@ where T: ContactsBackend + 'static
         liveness: LivenessHandle,
         connectivity: ConnectivityRequester,
         dht: Dht,
+        node_identity: Arc<NodeIdentity>,
         subscription_factory: Arc<SubscriptionFactory>,
         event_publisher: broadcast::Sender<Arc<ContactsLivenessEvent>>,
         message_publisher: broadcast::Sender<Arc<MessageDispatch>>,
@@ -166,12 +228,19 @@ where T: ContactsBackend + 'static
             liveness_data: Vec::new(),
             connectivity,
             dht,
+            node_identity,
             subscription_factory,
             event_publisher,
             message_publisher,
             number_of_rounds_no_pings: 0,
             contacts_auto_ping_interval,
             contacts_online_ping_window,
+            privacy_mode: LivenessPrivacyMode::Always,
+            contact_privacy_modes: HashMap::new(),
+            device_link_requests: HashMap::new(),
+            message_requests_enabled: false,
+            peer_session_versions: HashMap::new(),
+            pending_session_resets: HashSet::new(),
         }
     }
 
@@ -208,6 +277,8 @@ where T: ContactsBackend + 'static
             self.add_contacts_to_liveness_service(contacts).await?;
         }
         self.set_liveness_metadata(b"Watching you!".to_vec()).await?;
+        self.contact_privacy_modes = self.db.get_contact_privacy_modes()?;
+        self.update_liveness_metadata_recipients().await?;
         debug!(target: LOG_TARGET, "Contacts Service started");
         loop {
             tokio::select! {
@@ -266,6 +337,8 @@ where T: ContactsBackend + 'static
             ContactsServiceRequest::UpsertContact(c) => {
                 self.db.upsert_contact(c.clone())?;
                 self.liveness.check_add_monitored_peer(c.node_id.clone()).await?;
+                self.update_liveness_metadata_recipients().await?;
+                self.send_device_delegations(c.address.clone()).await?;
                 info!(
                     target: LOG_TARGET,
                     "Contact Saved: \nAlias: {}\nAddress: {}\nNodeId: {}", c.alias, c.address, c.node_id
@@ -277,6 +350,7 @@ where T: ContactsBackend + 'static
                 self.liveness
                     .check_remove_monitored_peer(result.node_id.clone())
                     .await?;
+                self.update_liveness_metadata_recipients().await?;
                 info!(
                     target: LOG_TARGET,
                     "Contact Removed: \nAlias: {}\nAddress: {} ", result.alias, result.address
@@ -318,6 +392,172 @@ where T: ContactsBackend + 'static
 
                 Ok(ContactsServiceResponse::ReadConfirmationSent)
             },
+            ContactsServiceRequest::SetLivenessPrivacyMode(mode) => {
+                self.privacy_mode = mode;
+                self.update_liveness_metadata_recipients().await?;
+                info!(target: LOG_TARGET, "Liveness privacy mode set to {}", mode);
+                Ok(ContactsServiceResponse::LivenessPrivacyModeSet)
+            },
+            ContactsServiceRequest::SetContactLivenessPrivacyMode(address, mode) => {
+                let node_id = NodeId::from_key(address.public_key());
+                self.db.set_contact_privacy_mode(node_id.clone(), mode)?;
+                match mode {
+                    Some(mode) => self.contact_privacy_modes.insert(node_id, mode),
+                    None => self.contact_privacy_modes.remove(&node_id),
+                };
+                self.update_liveness_metadata_recipients().await?;
+                Ok(ContactsServiceResponse::LivenessPrivacyModeSet)
+            },
+            ContactsServiceRequest::RequestDeviceLink(identity) => {
+                let request = DeviceDelegation::request(self.node_identity.secret_key(), identity.public_key().clone());
+                let msg = OutboundDomainMessage::from(MessageDispatch::DeviceLink(request));
+                self.deliver_message_to_node(identity, msg).await?;
+                Ok(ContactsServiceResponse::DeviceLinkRequested)
+            },
+            ContactsServiceRequest::ApproveDeviceLink(device) => {
+                self.remove_expired_device_link_requests();
+                let delegation = self
+                    .device_link_requests
+                    .remove(device.public_key())
+                    .ok_or(ContactsServiceError::DeviceLinkRequestNotFound)?
+                    .approve(self.node_identity.secret_key());
+                self.db.upsert_device_delegation(delegation.clone())?;
+                info!(
+                    target: LOG_TARGET,
+                    "Device {} linked to this identity", delegation.device
+                );
+
+                // Let the device and all contacts know that messages for this identity should also go to the device
+                let msg = OutboundDomainMessage::from(MessageDispatch::DeviceLink(delegation.clone()));
+                self.deliver_message_to_node(device, msg.clone()).await?;
+                for contact in self.db.get_contacts()? {
+                    self.deliver_message_to_node(contact.address, msg.clone()).await?;
+                }
+                Ok(ContactsServiceResponse::DeviceLinked(delegation))
+            },
+            ContactsServiceRequest::GetDeviceLinkRequests => {
+                self.remove_expired_device_link_requests();
+                Ok(ContactsServiceResponse::DeviceLinkRequests(
+                    self.device_link_requests.values().cloned().collect(),
+                ))
+            },
+            ContactsServiceRequest::GetLinkedDevices(identity) => {
+                let result = self.db.get_device_delegations(identity.public_key().clone());
+                Ok(result.map(ContactsServiceResponse::LinkedDevices)?)
+            },
+            ContactsServiceRequest::UnlinkDevice(device) => {
+                let our_public_key = self.node_identity.public_key().clone();
+                let delegation = self
+                    .get_device_delegation(device.public_key())?
+                    .filter(|d| d.identity == our_public_key || d.device == our_public_key)
+                    .ok_or(ContactsServiceError::DeviceLinkNotFound)?;
+                let revocation = DeviceRevocation::new(
+                    self.node_identity.secret_key(),
+                    delegation.identity.clone(),
+                    delegation.device.clone(),
+                );
+                self.db.unlink_device(revocation.device.clone(), revocation.timestamp)?;
+                info!(
+                    target: LOG_TARGET,
+                    "Device {} unlinked from identity {}", revocation.device, revocation.identity
+                );
+
+                // Let the other side of the link and all contacts know that the device no longer receives messages
+                // for the identity
+                let other = if delegation.identity == our_public_key {
+                    delegation.device
+                } else {
+                    delegation.identity
+                };
+                let msg = OutboundDomainMessage::from(MessageDispatch::DeviceUnlink(revocation));
+                self.deliver_message_to_node(TariAddress::new(other, device.network()), msg.clone())
+                    .await?;
+                for contact in self.db.get_contacts()? {
+                    self.deliver_message_to_node(contact.address, msg.clone()).await?;
+                }
+                Ok(ContactsServiceResponse::DeviceUnlinked)
+            },
+            ContactsServiceRequest::SetMessageRequestsEnabled(enabled) => {
+                self.message_requests_enabled = enabled;
+                info!(target: LOG_TARGET, "Message requests enabled: {}", enabled);
+                Ok(ContactsServiceResponse::MessageRequestsEnabledSet)
+            },
+            ContactsServiceRequest::GetMessageRequests => {
+                let result = self.db.get_message_requests();
+                Ok(result.map(ContactsServiceResponse::MessageRequests)?)
+            },
+            ContactsServiceRequest::AcceptMessageRequest(address) => {
+                let messages = self.db.remove_message_requests(address.clone())?;
+                if messages.is_empty() {
+                    return Err(ContactsServiceError::MessageRequestNotFound);
+                }
+                self.db.set_sender_list_type(address, Some(SenderListType::Allowed))?;
+                for message in &messages {
+                    self.db.save_message(message.clone())?;
+                    let _msg = self
+                        .message_publisher
+                        .send(Arc::new(MessageDispatch::Message(message.clone())));
+                    self.create_and_send_delivery_confirmation_for_msg(message).await?;
+                }
+                Ok(ContactsServiceResponse::MessageRequestAccepted(messages))
+            },
+            ContactsServiceRequest::RejectMessageRequest(address, block) => {
+                let messages = self.db.remove_message_requests(address.clone())?;
+                if messages.is_empty() && !block {
+                    return Err(ContactsServiceError::MessageRequestNotFound);
+                }
+                if block {
+                    self.db.set_sender_list_type(address, Some(SenderListType::Blocked))?;
+                }
+                Ok(ContactsServiceResponse::MessageRequestRejected)
+            },
+            ContactsServiceRequest::SetSenderListType(address, list_type) => {
+                if list_type == Some(SenderListType::Blocked) {
+                    self.db.remove_message_requests(address.clone())?;
+                }
+                self.db.set_sender_list_type(address.clone(), list_type)?;
+                debug!(
+                    target: LOG_TARGET,
+                    "Sender {} list set to {}",
+                    address,
+                    list_type.map_or_else(|| "none".to_string(), |t| t.to_string())
+                );
+                Ok(ContactsServiceResponse::SenderListUpdated)
+            },
+            ContactsServiceRequest::GetSenderList(list_type) => {
+                let result = self.db.get_sender_list(list_type);
+                Ok(result.map(ContactsServiceResponse::SenderList)?)
+            },
+        }
+    }
+
+    /// Send the delegations of the devices linked to our identity, or the delegation of our own node if it is a linked
+    /// device, to a new contact
+    async fn send_device_delegations(&mut self, address: TariAddress) -> Result<(), ContactsServiceError> {
+        let public_key = self.node_identity.public_key().clone();
+        let mut delegations = self.db.get_device_delegations(public_key.clone())?;
+        if let Some(delegation) = self.get_device_delegation(&public_key)? {
+            delegations.push(delegation);
+        }
+        for delegation in delegations {
+            let msg = OutboundDomainMessage::from(MessageDispatch::DeviceLink(delegation));
+            self.deliver_message_to_node(address.clone(), msg).await?;
+        }
+        Ok(())
+    }
+
+    /// Drop link requests that were not approved in time
+    fn remove_expired_device_link_requests(&mut self) {
+        let cutoff = device_link_request_cutoff();
+        self.device_link_requests
+            .retain(|_, request| request.timestamp >= cutoff);
+    }
+
+    fn get_device_delegation(&self, device: &CommsPublicKey) -> Result<Option<DeviceDelegation>, ContactsServiceError> {
+        match self.db.get_device_delegation(device.clone()) {
+            Ok(delegation) => Ok(Some(delegation)),
+            Err(ContactsServiceStorageError::ValueNotFound(_)) => Ok(None),
+            Err(e) => Err(e.into()),
         }
     }
 
@@ -336,6 +576,45 @@ where T: ContactsBackend + 'static
         Ok(())
     }
 
+    /// Only attach the contacts liveness metadata to the ping/pongs of peers that the privacy modes allow, so that
+    /// other peers never learn our online status or last seen time
+    async fn update_liveness_metadata_recipients(&mut self) -> Result<(), ContactsServiceError> {
+        let contacts = self
+            .db
+            .get_contacts()?
+            .into_iter()
+            .map(|c| c.node_id)
+            .collect::<HashSet<_>>();
+        let shares_with = |node_id: &NodeId| {
+            self.contact_privacy_modes
+                .get(node_id)
+                .copied()
+                .unwrap_or(self.privacy_mode)
+                .shares_with(contacts.contains(node_id))
+        };
+        let recipients = match self.privacy_mode {
+            LivenessPrivacyMode::Always => MetadataRecipients::AllExcept(
+                self.contact_privacy_modes
+                    .keys()
+                    .filter(|node_id| !shares_with(node_id))
+                    .cloned()
+                    .collect(),
+            ),
+            LivenessPrivacyMode::ContactsOnly | LivenessPrivacyMode::Never => MetadataRecipients::Only(
+                contacts
+                    .iter()
+                    .chain(self.contact_privacy_modes.keys())
+                    .filter(|node_id| shares_with(node_id))
+                    .cloned()
+                    .collect(),
+            ),
+        };
+        self.liveness
+            .set_metadata_recipients(MetadataKey::ContactsLiveness, recipients)
+            .await?;
+        Ok(())
+    }
+
     async fn handle_liveness_event(&mut self, event: &LivenessEvent) -> Result<(), ContactsServiceError> {
         match event {
             // Received a ping, check if it contains ContactsLiveness
@@ -407,6 +686,14 @@ where T: ContactsBackend + 'static
             },
         };
         if let Some(source_public_key) = msg.authenticated_origin {
+            if self.db.get_sender_list_type(source_public_key.clone())? == Some(SenderListType::Blocked) {
+                trace!(
+                    target: LOG_TARGET,
+                    "Dropping message from blocked sender {}", source_public_key
+                );
+                return Ok(());
+            }
+            let msg_inner = self.decrypt_dispatch(&source_public_key, msg_inner).await?;
             let dispatch = MessageDispatch::try_from(msg_inner).map_err(ContactsServiceError::MessageParsingError)?;
 
             match dispatch {
@@ -414,12 +701,117 @@ where T: ContactsBackend + 'static
                 MessageDispatch::DeliveryConfirmation(_) | MessageDispatch::ReadConfirmation(_) => {
                     self.handle_confirmation(dispatch.clone()).await
                 },
+                MessageDispatch::DeviceLink(d) => self.handle_device_link(d, source_public_key),
+                MessageDispatch::DeviceUnlink(r) => self.handle_device_unlink(r, source_public_key),
+                MessageDispatch::SessionReset => self.handle_session_reset(&source_public_key),
             }
         } else {
             Err(ContactsServiceError::MessageSourceDoesNotMatchOrigin)
         }
     }
 
+    /// Decrypt a dispatch that was sent within a session and remember the session version the sender supports. If the
+    /// payload cannot be opened the session is dropped and the sender is asked to start a new one.
+    async fn decrypt_dispatch(
+        &mut self,
+        sender: &CommsPublicKey,
+        dispatch: proto::MessageDispatch,
+    ) -> Result<proto::MessageDispatch, ContactsServiceError> {
+        if dispatch.session_version > 0 {
+            self.peer_session_versions
+                .insert(sender.clone(), dispatch.session_version);
+        }
+        match dispatch.contents {
+            Some(proto::message_dispatch::Contents::Encrypted(encrypted)) => {
+                let ratchet_session = self.load_ratchet_session(sender)?;
+                match session::open_dispatch(ratchet_session, self.node_identity.secret_key(), sender, encrypted) {
+                    Ok((ratchet_session, dispatch)) => {
+                        self.db.upsert_ratchet_session(sender.clone(), ratchet_session)?;
+                        self.pending_session_resets.remove(sender);
+                        Ok(dispatch)
+                    },
+                    Err(e) if e.requires_new_session() => {
+                        self.reset_session(sender).await?;
+                        Err(e.into())
+                    },
+                    Err(e) => Err(e.into()),
+                }
+            },
+            contents => Ok(proto::MessageDispatch { contents, ..dispatch }),
+        }
+    }
+
+    /// Returns the session with the node. A session whose stored state cannot be decrypted or decoded is discarded, so
+    /// that a new one is started.
+    fn load_ratchet_session(
+        &self,
+        public_key: &CommsPublicKey,
+    ) -> Result<Option<session::RatchetSession>, ContactsServiceError> {
+        match self.db.get_ratchet_session(public_key.clone()) {
+            Ok(ratchet_session) => Ok(ratchet_session),
+            Err(e @ ContactsServiceStorageError::AeadError(_)) |
+            Err(e @ ContactsServiceStorageError::ConversionError) => {
+                warn!(
+                    target: LOG_TARGET,
+                    "Discarding unreadable chat session with {}: {}", public_key, e
+                );
+                self.db.remove_ratchet_session(public_key.clone())?;
+                Ok(None)
+            },
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Drops the session with `sender` after one of its payloads could not be opened and asks it to start a new
+    /// session. Only one request is sent until a payload from the sender can be opened again.
+    async fn reset_session(&mut self, sender: &CommsPublicKey) -> Result<(), ContactsServiceError> {
+        self.db.remove_ratchet_session(sender.clone())?;
+        if !self.pending_session_resets.insert(sender.clone()) {
+            return Ok(());
+        }
+        warn!(
+            target: LOG_TARGET,
+            "Could not open a chat payload from {}, asking it to start a new session", sender
+        );
+        // The reset is not sent within the session, as the sessions of both sides no longer match
+        let message = OutboundDomainMessage::from(MessageDispatch::SessionReset);
+        let encryption = OutboundEncryption::EncryptFor(Box::new(sender.clone()));
+        self.dht
+            .outbound_requester()
+            .closest_broadcast(sender.clone(), encryption, vec![], message)
+            .await?;
+        Ok(())
+    }
+
+    /// The sender could not open one of our payloads, so the next payload starts a new session
+    fn handle_session_reset(&mut self, sender: &CommsPublicKey) -> Result<(), ContactsServiceError> {
+        debug!(target: LOG_TARGET, "Chat session reset requested by {}", sender);
+        self.db.remove_ratchet_session(sender.clone())?;
+        self.pending_session_resets.remove(sender);
+        Ok(())
+    }
+
+    /// Encrypt a dispatch within the session with the node. Nodes that never advertised a session version receive the
+    /// dispatch as it is, so that clients without sessions can still read it.
+    fn encrypt_dispatch(
+        &mut self,
+        recipient: &CommsPublicKey,
+        message: OutboundDomainMessage<proto::MessageDispatch>,
+    ) -> Result<OutboundDomainMessage<proto::MessageDispatch>, ContactsServiceError> {
+        let ratchet_session = self.load_ratchet_session(recipient)?;
+        if ratchet_session.is_none() && !self.peer_session_versions.contains_key(recipient) {
+            return Ok(message);
+        }
+        let (ratchet_session, dispatch) = session::seal_dispatch(
+            ratchet_session,
+            self.node_identity.secret_key(),
+            recipient,
+            &message.into_inner(),
+        )?;
+        self.db.upsert_ratchet_session(recipient.clone(), ratchet_session)?;
+        Ok(OutboundDomainMessage::new(&TariMessageType::Chat, dispatch))
+    }
+
     async fn get_online_status(&self, contact: &Contact) -> Result<ContactOnlineStatus, ContactsServiceError> {
         let mut online_status = ContactOnlineStatus::NeverSeen;
         if let Some(peer_data) = self.connectivity.get_peer_info(contact.node_id.clone()).await? {
@@ -546,12 +938,35 @@ where T: ContactsBackend + 'static
         message: Message,
         source_public_key: CommsPublicKey,
     ) -> Result<(), ContactsServiceError> {
+        // Messages sent by a linked device belong to the conversation with its identity
+        let sender = self
+            .get_device_delegation(&source_public_key)?
+            .map_or(source_public_key, |delegation| delegation.identity);
         let our_message = Message {
-            address: TariAddress::from_public_key(&source_public_key, message.address.network()),
+            address: TariAddress::from_public_key(&sender, message.address.network()),
             stored_at: EpochTime::now().as_u64(),
             ..message
         };
 
+        // A linked device may be sending on behalf of a blocked identity
+        match self.db.get_sender_list_type(sender.clone())? {
+            Some(SenderListType::Blocked) => {
+                trace!(target: LOG_TARGET, "Dropping message from blocked sender {}", sender);
+                return Ok(());
+            },
+            Some(SenderListType::Allowed) => {},
+            None => {
+                if self.message_requests_enabled && !self.has_conversation_with(&our_message.address)? {
+                    debug!(
+                        target: LOG_TARGET,
+                        "Quarantining first-contact message from {} as a message request", our_message.address
+                    );
+                    self.db.save_message_request(our_message)?;
+                    return Ok(());
+                }
+            },
+        }
+
         match self.db.save_message(our_message.clone()) {
             Ok(..) => {
                 let _msg = self
@@ -567,6 +982,16 @@ where T: ContactsBackend + 'static
         }
     }
 
+    /// A sender we have a conversation with is either a contact or someone we have exchanged messages with
+    fn has_conversation_with(&self, address: &TariAddress) -> Result<bool, ContactsServiceError> {
+        match self.db.get_contact(address.clone()) {
+            Ok(_) => return Ok(true),
+            Err(ContactsServiceStorageError::ValueNotFound(_)) => {},
+            Err(e) => return Err(e.into()),
+        }
+        Ok(!self.db.get_messages(address.clone(), 1, 0)?.is_empty())
+    }
+
     async fn create_and_send_delivery_confirmation_for_msg(
         &mut self,
         message: &Message,
@@ -587,6 +1012,106 @@ where T: ContactsBackend + 'static
         Ok(())
     }
 
+    fn handle_device_link(
+        &mut self,
+        delegation: DeviceDelegation,
+        source_public_key: CommsPublicKey,
+    ) -> Result<(), ContactsServiceError> {
+        if delegation.identity_signature.is_none() {
+            // A device is asking us to link it to our identity, the request is kept until it is approved
+            if source_public_key != delegation.device || delegation.identity != *self.node_identity.public_key() {
+                return Err(ContactsServiceError::MessageSourceDoesNotMatchOrigin);
+            }
+            if !delegation.is_valid_request() {
+                return Err(ContactsServiceError::InvalidDeviceDelegation);
+            }
+            self.remove_expired_device_link_requests();
+            if delegation.timestamp < device_link_request_cutoff() {
+                debug!(
+                    target: LOG_TARGET,
+                    "Ignoring expired device link request from {}", delegation.device
+                );
+                return Ok(());
+            }
+            if !self.device_link_requests.contains_key(&delegation.device) &&
+                self.device_link_requests.len() >= MAX_DEVICE_LINK_REQUESTS
+            {
+                let oldest = self
+                    .device_link_requests
+                    .values()
+                    .min_by_key(|request| request.timestamp)
+                    .map(|request| request.device.clone());
+                if let Some(oldest) = oldest {
+                    self.device_link_requests.remove(&oldest);
+                }
+            }
+            debug!(
+                target: LOG_TARGET,
+                "Received device link request from {}", delegation.device
+            );
+            self.device_link_requests
+                .insert(delegation.device.clone(), delegation.clone());
+        } else {
+            if source_public_key != delegation.identity && source_public_key != delegation.device {
+                return Err(ContactsServiceError::MessageSourceDoesNotMatchOrigin);
+            }
+            if !delegation.is_valid() {
+                return Err(ContactsServiceError::InvalidDeviceDelegation);
+            }
+            let unlinked_at = self.db.get_device_unlinked_at(delegation.device.clone())?;
+            if unlinked_at.map_or(false, |unlinked_at| unlinked_at >= delegation.timestamp) {
+                debug!(
+                    target: LOG_TARGET,
+                    "Ignoring device link for {} that was made before the device was unlinked", delegation.device
+                );
+                return Ok(());
+            }
+            debug!(
+                target: LOG_TARGET,
+                "Device {} is linked to identity {}", delegation.device, delegation.identity
+            );
+            self.db.upsert_device_delegation(delegation.clone())?;
+        }
+
+        let _msg = self
+            .message_publisher
+            .send(Arc::new(MessageDispatch::DeviceLink(delegation)));
+        Ok(())
+    }
+
+    fn handle_device_unlink(
+        &mut self,
+        revocation: DeviceRevocation,
+        source_public_key: CommsPublicKey,
+    ) -> Result<(), ContactsServiceError> {
+        if source_public_key != revocation.identity && source_public_key != revocation.device {
+            return Err(ContactsServiceError::MessageSourceDoesNotMatchOrigin);
+        }
+        if !revocation.is_valid() {
+            return Err(ContactsServiceError::InvalidDeviceDelegation);
+        }
+        let is_linked = self.get_device_delegation(&revocation.device)?.map_or(false, |d| {
+            d.identity == revocation.identity && d.timestamp <= revocation.timestamp
+        });
+        if !is_linked {
+            debug!(
+                target: LOG_TARGET,
+                "Ignoring unlink of device {} that is not linked to identity {}", revocation.device, revocation.identity
+            );
+            return Ok(());
+        }
+        debug!(
+            target: LOG_TARGET,
+            "Device {} is unlinked from identity {}", revocation.device, revocation.identity
+        );
+        self.db.unlink_device(revocation.device.clone(), revocation.timestamp)?;
+
+        let _msg = self
+            .message_publisher
+            .send(Arc::new(MessageDispatch::DeviceUnlink(revocation)));
+        Ok(())
+    }
+
     async fn handle_confirmation(&mut self, dispatch: MessageDispatch) -> Result<(), ContactsServiceError> {
         let (message_id, delivery, read) = match dispatch.clone() {
             MessageDispatch::DeliveryConfirmation(c) => (c.message_id, Some(c.timestamp), None),
@@ -605,15 +1130,43 @@ where T: ContactsBackend + 'static
         Ok(())
     }
 
+    /// Deliver a message to the identity at `address` and to every device linked to that identity. Each node receives
+    /// its own copy, either directly or via store and forward, so that messages reach all of the devices of a user.
     async fn deliver_message(
         &mut self,
         address: TariAddress,
         message: OutboundDomainMessage<proto::MessageDispatch>,
+    ) -> Result<(), ContactsServiceError> {
+        let identity = self
+            .get_device_delegation(address.public_key())?
+            .map_or_else(|| address.public_key().clone(), |delegation| delegation.identity);
+        let devices = self
+            .db
+            .get_device_delegations(identity.clone())?
+            .into_iter()
+            .map(|delegation| delegation.device);
+        let nodes = std::iter::once(identity)
+            .chain(devices)
+            .filter(|public_key| public_key != self.node_identity.public_key())
+            .collect::<Vec<_>>();
+        for public_key in nodes {
+            let node_address = TariAddress::new(public_key, address.network());
+            self.deliver_message_to_node(node_address, message.clone()).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn deliver_message_to_node(
+        &mut self,
+        address: TariAddress,
+        message: OutboundDomainMessage<proto::MessageDispatch>,
     ) -> Result<(), ContactsServiceError> {
         let contact = match self.db.get_contact(address.clone()) {
             Ok(contact) => contact,
             Err(_) => Contact::from(&address),
         };
+        let message = self.encrypt_dispatch(address.public_key(), message)?;
         let encryption = OutboundEncryption::EncryptFor(Box::new(address.public_key().clone()));
 
         match self.get_online_status(&contact).await {
@@ -642,3 +1195,10 @@ where T: ContactsBackend + 'static
         Ok(())
     }
 }
+
+/// Link requests made before this time have expired
+fn device_link_request_cutoff() -> u64 {
+    EpochTime::now()
+        .as_u64()
+        .saturating_sub(DEVICE_LINK_REQUEST_EXPIRY.as_secs())
+}
@@ -0,0 +1,81 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::convert::TryFrom;
+
+use tari_comms::types::CommsPublicKey;
+use tari_utilities::ByteArray;
+
+use crate::contacts_service::{error::SessionError, proto};
+
+/// The unencrypted header sent with every session payload, which tells the receiver how to derive the message key
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RatchetHeader {
+    pub ratchet_public_key: CommsPublicKey,
+    pub previous_chain_length: u32,
+    pub message_number: u32,
+    /// Set by the initiator until it has received a reply, so that the receiver can derive the session
+    pub initiator_ephemeral_public_key: Option<CommsPublicKey>,
+}
+
+impl RatchetHeader {
+    /// The canonical encoding of the header, which is authenticated along with the payload
+    pub fn to_bytes(&self) -> Vec<u8> {
+        prost::Message::encode_to_vec(&proto::RatchetHeader::from(self.clone()))
+    }
+}
+
+impl TryFrom<proto::RatchetHeader> for RatchetHeader {
+    type Error = SessionError;
+
+    fn try_from(header: proto::RatchetHeader) -> Result<Self, Self::Error> {
+        let initiator_ephemeral_public_key = if header.initiator_ephemeral_public_key.is_empty() {
+            None
+        } else {
+            Some(
+                CommsPublicKey::from_bytes(&header.initiator_ephemeral_public_key)
+                    .map_err(|e| SessionError::InvalidHeader(e.to_string()))?,
+            )
+        };
+        Ok(Self {
+            ratchet_public_key: CommsPublicKey::from_bytes(&header.ratchet_public_key)
+                .map_err(|e| SessionError::InvalidHeader(e.to_string()))?,
+            previous_chain_length: header.previous_chain_length,
+            message_number: header.message_number,
+            initiator_ephemeral_public_key,
+        })
+    }
+}
+
+impl From<RatchetHeader> for proto::RatchetHeader {
+    fn from(header: RatchetHeader) -> Self {
+        Self {
+            ratchet_public_key: header.ratchet_public_key.to_vec(),
+            previous_chain_length: header.previous_chain_length,
+            message_number: header.message_number,
+            initiator_ephemeral_public_key: header
+                .initiator_ephemeral_public_key
+                .map(|key| key.to_vec())
+                .unwrap_or_default(),
+        }
+    }
+}
@@ -0,0 +1,283 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Double ratchet sessions for chat payloads.
+//!
+//! The DHT encrypts chat payloads for the static node identity of the receiver, so anyone who obtains that identity
+//! later can decrypt every payload that was stored and forwarded for it. A session adds a ratchet on top: it is set
+//! up from the node identities of both sides and an ephemeral key of the initiator, every payload is encrypted with
+//! its own message key and both sides move to new ratchet keys on every round trip.
+//!
+//! Sessions are versioned. Every dispatch advertises the highest session version its sender supports and payloads
+//! are only encrypted for nodes that advertised a version, so clients without sessions keep receiving plain
+//! dispatches.
+
+use std::convert::TryFrom;
+
+use prost::Message as _;
+use tari_comms::types::{CommsPublicKey, CommsSecretKey};
+use tari_crypto::keys::PublicKey as PublicKeyTrait;
+use tari_utilities::ByteArray;
+
+use crate::contacts_service::{error::SessionError, proto, proto::message_dispatch::Contents};
+
+mod header;
+pub use header::RatchetHeader;
+
+mod ratchet;
+pub use ratchet::RatchetSession;
+
+/// The highest session version supported by this client
+pub const SESSION_VERSION: u32 = 1;
+
+/// Encrypt a dispatch for `their_identity`, starting a new session if there is none yet. Returns the session to
+/// persist and the encrypted dispatch.
+pub fn seal_dispatch(
+    session: Option<RatchetSession>,
+    our_secret_key: &CommsSecretKey,
+    their_identity: &CommsPublicKey,
+    dispatch: &proto::MessageDispatch,
+) -> Result<(RatchetSession, proto::MessageDispatch), SessionError> {
+    let our_identity = CommsPublicKey::from_secret_key(our_secret_key);
+    let mut session = session.unwrap_or_else(|| RatchetSession::initiate(our_secret_key, their_identity));
+    let (header, ciphertext) = session.encrypt(
+        &dispatch.encode_to_vec(),
+        &associated_data(&our_identity, their_identity),
+    )?;
+
+    let encrypted = proto::EncryptedDispatch {
+        version: SESSION_VERSION,
+        header: Some(header.into()),
+        ciphertext,
+    };
+    Ok((session, proto::MessageDispatch {
+        contents: Some(Contents::Encrypted(encrypted)),
+        session_version: SESSION_VERSION,
+    }))
+}
+
+/// Decrypt a dispatch from `their_identity`. Returns the session to persist and the decrypted dispatch.
+pub fn open_dispatch(
+    session: Option<RatchetSession>,
+    our_secret_key: &CommsSecretKey,
+    their_identity: &CommsPublicKey,
+    encrypted: proto::EncryptedDispatch,
+) -> Result<(RatchetSession, proto::MessageDispatch), SessionError> {
+    if encrypted.version == 0 || encrypted.version > SESSION_VERSION {
+        return Err(SessionError::UnsupportedVersion(encrypted.version));
+    }
+    let header = RatchetHeader::try_from(
+        encrypted
+            .header
+            .ok_or_else(|| SessionError::InvalidHeader("Missing header".to_string()))?,
+    )?;
+    let our_identity = CommsPublicKey::from_secret_key(our_secret_key);
+    let associated_data = associated_data(their_identity, &our_identity);
+
+    let (session, plaintext) = match (header.initiator_ephemeral_public_key.as_ref(), session) {
+        (Some(initiator_key), Some(mut session)) if session.remote_initiator_public_key() == Some(initiator_key) => {
+            let plaintext = session.decrypt(&header, &encrypted.ciphertext, &associated_data)?;
+            (session, plaintext)
+        },
+        (Some(_), existing) => {
+            let mut responder = RatchetSession::respond(our_secret_key, their_identity, &header)?;
+            let plaintext = responder.decrypt(&header, &encrypted.ciphertext, &associated_data)?;
+            match existing {
+                // Both sides started a session at the same time. The session started by the lower node identity is
+                // kept, the other side switches over once it receives a payload from that session.
+                Some(session) if !session.is_acknowledged() && our_identity.as_bytes() < their_identity.as_bytes() => {
+                    (session, plaintext)
+                },
+                _ => (responder, plaintext),
+            }
+        },
+        (None, Some(mut session)) => {
+            let plaintext = session.decrypt(&header, &encrypted.ciphertext, &associated_data)?;
+            (session, plaintext)
+        },
+        (None, None) => return Err(SessionError::NoSession),
+    };
+
+    let dispatch = proto::MessageDispatch::decode(plaintext.as_slice())
+        .map_err(|e| SessionError::InvalidPayload(e.to_string()))?;
+    if let Some(Contents::Encrypted(_)) = dispatch.contents {
+        return Err(SessionError::InvalidPayload("Nested encrypted dispatch".to_string()));
+    }
+    Ok((session, dispatch))
+}
+
+fn associated_data(sender: &CommsPublicKey, receiver: &CommsPublicKey) -> Vec<u8> {
+    [sender.as_bytes(), receiver.as_bytes()].concat()
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::OsRng;
+
+    use super::*;
+
+    fn confirmation(id: u8) -> proto::MessageDispatch {
+        proto::MessageDispatch {
+            contents: Some(Contents::DeliveryConfirmation(proto::Confirmation {
+                message_id: vec![id],
+                timestamp: 0,
+            })),
+            session_version: SESSION_VERSION,
+        }
+    }
+
+    fn encrypted(dispatch: &proto::MessageDispatch) -> proto::EncryptedDispatch {
+        match &dispatch.contents {
+            Some(Contents::Encrypted(encrypted)) => encrypted.clone(),
+            _ => panic!("Dispatch is not encrypted"),
+        }
+    }
+
+    fn header(dispatch: &proto::MessageDispatch) -> RatchetHeader {
+        RatchetHeader::try_from(encrypted(dispatch).header.unwrap()).unwrap()
+    }
+
+    #[test]
+    fn it_ratchets_on_every_round_trip() {
+        let (alice_secret_key, alice) = CommsPublicKey::random_keypair(&mut OsRng);
+        let (bob_secret_key, bob) = CommsPublicKey::random_keypair(&mut OsRng);
+
+        let (alice_session, first) = seal_dispatch(None, &alice_secret_key, &bob, &confirmation(1)).unwrap();
+        let (alice_session, second) =
+            seal_dispatch(Some(alice_session), &alice_secret_key, &bob, &confirmation(2)).unwrap();
+        assert!(header(&second).initiator_ephemeral_public_key.is_some());
+
+        let (bob_session, opened) = open_dispatch(None, &bob_secret_key, &alice, encrypted(&first)).unwrap();
+        assert_eq!(opened, confirmation(1));
+        let (bob_session, opened) =
+            open_dispatch(Some(bob_session), &bob_secret_key, &alice, encrypted(&second)).unwrap();
+        assert_eq!(opened, confirmation(2));
+
+        let (_bob_session, reply) =
+            seal_dispatch(Some(bob_session), &bob_secret_key, &alice, &confirmation(3)).unwrap();
+        let (alice_session, opened) =
+            open_dispatch(Some(alice_session), &alice_secret_key, &bob, encrypted(&reply)).unwrap();
+        assert_eq!(opened, confirmation(3));
+        assert!(alice_session.is_acknowledged());
+
+        // Alice moved to a new ratchet key after the reply and no longer announces the session
+        let (_alice_session, third) =
+            seal_dispatch(Some(alice_session), &alice_secret_key, &bob, &confirmation(4)).unwrap();
+        assert_ne!(header(&third).ratchet_public_key, header(&second).ratchet_public_key);
+        assert!(header(&third).initiator_ephemeral_public_key.is_none());
+    }
+
+    #[test]
+    fn it_decrypts_out_of_order_payloads_once() {
+        let (alice_secret_key, alice) = CommsPublicKey::random_keypair(&mut OsRng);
+        let (bob_secret_key, bob) = CommsPublicKey::random_keypair(&mut OsRng);
+
+        let mut session = None;
+        let mut dispatches = Vec::new();
+        for id in 0..3 {
+            let (alice_session, dispatch) = seal_dispatch(session, &alice_secret_key, &bob, &confirmation(id)).unwrap();
+            session = Some(alice_session);
+            dispatches.push(dispatch);
+        }
+
+        let (bob_session, opened) = open_dispatch(None, &bob_secret_key, &alice, encrypted(&dispatches[2])).unwrap();
+        assert_eq!(opened, confirmation(2));
+        let (bob_session, opened) =
+            open_dispatch(Some(bob_session), &bob_secret_key, &alice, encrypted(&dispatches[0])).unwrap();
+        assert_eq!(opened, confirmation(0));
+
+        // A persisted session keeps the skipped message keys
+        let bob_session = RatchetSession::try_from(proto::RatchetSessionState::from(&bob_session)).unwrap();
+        let (bob_session, opened) =
+            open_dispatch(Some(bob_session), &bob_secret_key, &alice, encrypted(&dispatches[1])).unwrap();
+        assert_eq!(opened, confirmation(1));
+
+        // Message keys are deleted after use, so a replayed payload cannot be decrypted
+        assert!(matches!(
+            open_dispatch(Some(bob_session), &bob_secret_key, &alice, encrypted(&dispatches[1])),
+            Err(SessionError::DecryptionFailed)
+        ));
+    }
+
+    #[test]
+    fn it_rejects_tampered_and_unsupported_payloads() {
+        let (alice_secret_key, alice) = CommsPublicKey::random_keypair(&mut OsRng);
+        let (bob_secret_key, bob) = CommsPublicKey::random_keypair(&mut OsRng);
+        let (eve_secret_key, _eve) = CommsPublicKey::random_keypair(&mut OsRng);
+
+        let (_, dispatch) = seal_dispatch(None, &alice_secret_key, &bob, &confirmation(1)).unwrap();
+
+        let mut tampered = encrypted(&dispatch);
+        tampered.ciphertext[0] ^= 1;
+        assert!(matches!(
+            open_dispatch(None, &bob_secret_key, &alice, tampered),
+            Err(SessionError::DecryptionFailed)
+        ));
+
+        let mut future = encrypted(&dispatch);
+        future.version = SESSION_VERSION + 1;
+        assert!(matches!(
+            open_dispatch(None, &bob_secret_key, &alice, future),
+            Err(SessionError::UnsupportedVersion(_))
+        ));
+
+        // Only the intended receiver can derive the session
+        assert!(matches!(
+            open_dispatch(None, &eve_secret_key, &alice, encrypted(&dispatch)),
+            Err(SessionError::DecryptionFailed)
+        ));
+    }
+
+    #[test]
+    fn it_settles_on_one_session_when_both_sides_initiate() {
+        let (alice_secret_key, alice) = CommsPublicKey::random_keypair(&mut OsRng);
+        let (bob_secret_key, bob) = CommsPublicKey::random_keypair(&mut OsRng);
+
+        let (alice_session, from_alice) = seal_dispatch(None, &alice_secret_key, &bob, &confirmation(1)).unwrap();
+        let (bob_session, from_bob) = seal_dispatch(None, &bob_secret_key, &alice, &confirmation(2)).unwrap();
+
+        let (mut alice_session, _) =
+            open_dispatch(Some(alice_session), &alice_secret_key, &bob, encrypted(&from_bob)).unwrap();
+        let (mut bob_session, _) =
+            open_dispatch(Some(bob_session), &bob_secret_key, &alice, encrypted(&from_alice)).unwrap();
+
+        for id in 3..6 {
+            let (session, dispatch) =
+                seal_dispatch(Some(alice_session), &alice_secret_key, &bob, &confirmation(id)).unwrap();
+            alice_session = session;
+            let (session, opened) =
+                open_dispatch(Some(bob_session), &bob_secret_key, &alice, encrypted(&dispatch)).unwrap();
+            bob_session = session;
+            assert_eq!(opened, confirmation(id));
+
+            let (session, dispatch) =
+                seal_dispatch(Some(bob_session), &bob_secret_key, &alice, &confirmation(id)).unwrap();
+            bob_session = session;
+            let (session, opened) =
+                open_dispatch(Some(alice_session), &alice_secret_key, &bob, encrypted(&dispatch)).unwrap();
+            alice_session = session;
+            assert_eq!(opened, confirmation(id));
+        }
+        assert!(alice_session.is_acknowledged());
+        assert!(bob_session.is_acknowledged());
+    }
+}
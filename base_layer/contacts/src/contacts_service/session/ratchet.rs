@@ -0,0 +1,445 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{collections::VecDeque, convert::TryFrom};
+
+use chacha20poly1305::{
+    aead::{Aead, Payload},
+    ChaCha20Poly1305,
+    Key,
+    KeyInit,
+    Nonce,
+};
+use rand::rngs::OsRng;
+use tari_comms::types::{CommsChallenge, CommsDHKE, CommsPublicKey, CommsSecretKey};
+use tari_crypto::{hash_domain, hashing::DomainSeparatedHasher, keys::PublicKey as PublicKeyTrait};
+use tari_utilities::ByteArray;
+use zeroize::Zeroizing;
+
+use crate::contacts_service::{error::SessionError, proto, session::RatchetHeader};
+
+hash_domain!(ContactsRatchetDomain, "com.tari.base_layer.contacts.ratchet", 1);
+
+/// The maximum number of message keys derived ahead of the receiving chain for a single payload
+const MAX_SKIP: u32 = 1000;
+/// The maximum number of message keys kept for payloads that have not arrived yet
+const MAX_SKIPPED_MESSAGE_KEYS: usize = 2000;
+
+type RatchetKey = Zeroizing<[u8; 32]>;
+
+#[derive(Clone)]
+struct SkippedMessageKey {
+    ratchet_public_key: CommsPublicKey,
+    message_number: u32,
+    message_key: RatchetKey,
+}
+
+/// A double ratchet session with a single remote node.
+///
+/// The session is set up from the node identities of both sides and an ephemeral key of the initiator. Every payload
+/// is encrypted with a fresh message key and both sides replace their ratchet keys on every round trip, so a
+/// compromised node identity does not reveal payloads that were exchanged before.
+#[derive(Clone)]
+pub struct RatchetSession {
+    root_key: RatchetKey,
+    sending_ratchet_secret_key: CommsSecretKey,
+    remote_ratchet_public_key: Option<CommsPublicKey>,
+    sending_chain_key: Option<RatchetKey>,
+    receiving_chain_key: Option<RatchetKey>,
+    sending_message_number: u32,
+    receiving_message_number: u32,
+    previous_chain_length: u32,
+    initiator_ephemeral_public_key: Option<CommsPublicKey>,
+    remote_initiator_public_key: Option<CommsPublicKey>,
+    skipped_message_keys: VecDeque<SkippedMessageKey>,
+}
+
+impl RatchetSession {
+    /// Start a session with the node identity `their_identity`
+    pub fn initiate(our_secret_key: &CommsSecretKey, their_identity: &CommsPublicKey) -> Self {
+        let our_identity = CommsPublicKey::from_secret_key(our_secret_key);
+        let (ephemeral_secret_key, ephemeral_public_key) = CommsPublicKey::random_keypair(&mut OsRng);
+        let root_key = initial_root_key(
+            &CommsDHKE::new(our_secret_key, their_identity),
+            &CommsDHKE::new(&ephemeral_secret_key, their_identity),
+            &our_identity,
+            their_identity,
+        );
+
+        // The identity of the responder serves as its first ratchet key
+        let (sending_ratchet_secret_key, _) = CommsPublicKey::random_keypair(&mut OsRng);
+        let (root_key, sending_chain_key) =
+            kdf_root(&root_key, &CommsDHKE::new(&sending_ratchet_secret_key, their_identity));
+
+        Self {
+            root_key,
+            sending_ratchet_secret_key,
+            remote_ratchet_public_key: Some(their_identity.clone()),
+            sending_chain_key: Some(sending_chain_key),
+            receiving_chain_key: None,
+            sending_message_number: 0,
+            receiving_message_number: 0,
+            previous_chain_length: 0,
+            initiator_ephemeral_public_key: Some(ephemeral_public_key),
+            remote_initiator_public_key: None,
+            skipped_message_keys: VecDeque::new(),
+        }
+    }
+
+    /// Derive the session started by `their_identity` from the header of one of its first payloads
+    pub fn respond(
+        our_secret_key: &CommsSecretKey,
+        their_identity: &CommsPublicKey,
+        header: &RatchetHeader,
+    ) -> Result<Self, SessionError> {
+        let initiator_ephemeral_public_key = header
+            .initiator_ephemeral_public_key
+            .clone()
+            .ok_or_else(|| SessionError::InvalidHeader("Missing initiator ephemeral key".to_string()))?;
+        let our_identity = CommsPublicKey::from_secret_key(our_secret_key);
+        let root_key = initial_root_key(
+            &CommsDHKE::new(our_secret_key, their_identity),
+            &CommsDHKE::new(our_secret_key, &initiator_ephemeral_public_key),
+            their_identity,
+            &our_identity,
+        );
+
+        let mut session = Self {
+            root_key,
+            sending_ratchet_secret_key: our_secret_key.clone(),
+            remote_ratchet_public_key: None,
+            sending_chain_key: None,
+            receiving_chain_key: None,
+            sending_message_number: 0,
+            receiving_message_number: 0,
+            previous_chain_length: 0,
+            initiator_ephemeral_public_key: None,
+            remote_initiator_public_key: Some(initiator_ephemeral_public_key),
+            skipped_message_keys: VecDeque::new(),
+        };
+        // Replaces our identity as the sending ratchet key, so it is never persisted with the session
+        session.ratchet_step(header.ratchet_public_key.clone());
+        Ok(session)
+    }
+
+    /// Returns true once the remote node has shown that it derived this session
+    pub fn is_acknowledged(&self) -> bool {
+        self.initiator_ephemeral_public_key.is_none()
+    }
+
+    /// The ephemeral key of the remote node if it initiated this session
+    pub fn remote_initiator_public_key(&self) -> Option<&CommsPublicKey> {
+        self.remote_initiator_public_key.as_ref()
+    }
+
+    /// Encrypt a payload with the next sending message key
+    pub fn encrypt(
+        &mut self,
+        plaintext: &[u8],
+        associated_data: &[u8],
+    ) -> Result<(RatchetHeader, Vec<u8>), SessionError> {
+        let chain_key = self
+            .sending_chain_key
+            .as_ref()
+            .ok_or_else(|| SessionError::InvalidState("No sending chain".to_string()))?;
+        let (next_chain_key, message_key) = kdf_chain(chain_key);
+        let header = RatchetHeader {
+            ratchet_public_key: CommsPublicKey::from_secret_key(&self.sending_ratchet_secret_key),
+            previous_chain_length: self.previous_chain_length,
+            message_number: self.sending_message_number,
+            initiator_ephemeral_public_key: self.initiator_ephemeral_public_key.clone(),
+        };
+        let ciphertext = encrypt_payload(&message_key, plaintext, &[associated_data, &header.to_bytes()].concat())?;
+
+        self.sending_chain_key = Some(next_chain_key);
+        self.sending_message_number += 1;
+        Ok((header, ciphertext))
+    }
+
+    /// Decrypt a payload, which may arrive out of order. The session is left untouched if decryption fails.
+    pub fn decrypt(
+        &mut self,
+        header: &RatchetHeader,
+        ciphertext: &[u8],
+        associated_data: &[u8],
+    ) -> Result<Vec<u8>, SessionError> {
+        let mut session = self.clone();
+        let message_key = session.receiving_message_key(header)?;
+        let plaintext = decrypt_payload(
+            &message_key,
+            ciphertext,
+            &[associated_data, &header.to_bytes()].concat(),
+        )?;
+
+        // Only the remote node can produce a payload for this session, so it must have derived it
+        session.initiator_ephemeral_public_key = None;
+        *self = session;
+        Ok(plaintext)
+    }
+
+    fn receiving_message_key(&mut self, header: &RatchetHeader) -> Result<RatchetKey, SessionError> {
+        if let Some(index) = self.skipped_message_keys.iter().position(|skipped| {
+            skipped.ratchet_public_key == header.ratchet_public_key && skipped.message_number == header.message_number
+        }) {
+            if let Some(skipped) = self.skipped_message_keys.remove(index) {
+                return Ok(skipped.message_key);
+            }
+        }
+
+        if self.remote_ratchet_public_key.as_ref() != Some(&header.ratchet_public_key) {
+            self.skip_message_keys(header.previous_chain_length)?;
+            self.ratchet_step(header.ratchet_public_key.clone());
+        }
+        self.skip_message_keys(header.message_number)?;
+
+        let chain_key = self
+            .receiving_chain_key
+            .as_ref()
+            .ok_or_else(|| SessionError::InvalidState("No receiving chain".to_string()))?;
+        let (next_chain_key, message_key) = kdf_chain(chain_key);
+        self.receiving_chain_key = Some(next_chain_key);
+        self.receiving_message_number += 1;
+        Ok(message_key)
+    }
+
+    /// Store the message keys of the receiving chain up to `until` for payloads that arrive later
+    fn skip_message_keys(&mut self, until: u32) -> Result<(), SessionError> {
+        let (mut chain_key, ratchet_public_key) =
+            match (self.receiving_chain_key.clone(), self.remote_ratchet_public_key.clone()) {
+                (Some(chain_key), Some(ratchet_public_key)) => (chain_key, ratchet_public_key),
+                _ => return Ok(()),
+            };
+        if until > self.receiving_message_number.saturating_add(MAX_SKIP) {
+            return Err(SessionError::TooManySkippedMessages);
+        }
+
+        while self.receiving_message_number < until {
+            let (next_chain_key, message_key) = kdf_chain(&chain_key);
+            self.skipped_message_keys.push_back(SkippedMessageKey {
+                ratchet_public_key: ratchet_public_key.clone(),
+                message_number: self.receiving_message_number,
+                message_key,
+            });
+            chain_key = next_chain_key;
+            self.receiving_message_number += 1;
+        }
+        self.receiving_chain_key = Some(chain_key);
+
+        while self.skipped_message_keys.len() > MAX_SKIPPED_MESSAGE_KEYS {
+            self.skipped_message_keys.pop_front();
+        }
+        Ok(())
+    }
+
+    /// Advance the root chain with the new ratchet key of the remote node and start new sending and receiving chains
+    fn ratchet_step(&mut self, remote_ratchet_public_key: CommsPublicKey) {
+        let (root_key, receiving_chain_key) = kdf_root(
+            &self.root_key,
+            &CommsDHKE::new(&self.sending_ratchet_secret_key, &remote_ratchet_public_key),
+        );
+        let (sending_ratchet_secret_key, _) = CommsPublicKey::random_keypair(&mut OsRng);
+        let (root_key, sending_chain_key) = kdf_root(
+            &root_key,
+            &CommsDHKE::new(&sending_ratchet_secret_key, &remote_ratchet_public_key),
+        );
+
+        self.root_key = root_key;
+        self.sending_ratchet_secret_key = sending_ratchet_secret_key;
+        self.remote_ratchet_public_key = Some(remote_ratchet_public_key);
+        self.sending_chain_key = Some(sending_chain_key);
+        self.receiving_chain_key = Some(receiving_chain_key);
+        self.previous_chain_length = self.sending_message_number;
+        self.sending_message_number = 0;
+        self.receiving_message_number = 0;
+    }
+}
+
+fn hash(label: &'static str, parts: &[&[u8]]) -> RatchetKey {
+    let hasher = parts.iter().fold(
+        DomainSeparatedHasher::<CommsChallenge, ContactsRatchetDomain>::new_with_label(label),
+        |hasher, part| hasher.chain(part),
+    );
+    let mut key = Zeroizing::new([0u8; 32]);
+    key.copy_from_slice(hasher.finalize().as_ref());
+    key
+}
+
+/// The shared secret of the initiator and the responder, bound to both identities
+fn initial_root_key(
+    identity_dh: &CommsDHKE,
+    ephemeral_dh: &CommsDHKE,
+    initiator: &CommsPublicKey,
+    responder: &CommsPublicKey,
+) -> RatchetKey {
+    hash("initial_root_key", &[
+        identity_dh.as_bytes(),
+        ephemeral_dh.as_bytes(),
+        initiator.as_bytes(),
+        responder.as_bytes(),
+    ])
+}
+
+fn kdf_root(root_key: &RatchetKey, dh: &CommsDHKE) -> (RatchetKey, RatchetKey) {
+    (
+        hash("root_key", &[&root_key[..], dh.as_bytes()]),
+        hash("chain_key", &[&root_key[..], dh.as_bytes()]),
+    )
+}
+
+fn kdf_chain(chain_key: &RatchetKey) -> (RatchetKey, RatchetKey) {
+    (
+        hash("next_chain_key", &[&chain_key[..]]),
+        hash("message_key", &[&chain_key[..]]),
+    )
+}
+
+// Every message key encrypts a single payload, so a fixed nonce is safe here
+fn encrypt_payload(
+    message_key: &RatchetKey,
+    plaintext: &[u8],
+    associated_data: &[u8],
+) -> Result<Vec<u8>, SessionError> {
+    ChaCha20Poly1305::new(Key::from_slice(&message_key[..]))
+        .encrypt(&Nonce::default(), Payload {
+            msg: plaintext,
+            aad: associated_data,
+        })
+        .map_err(|_| SessionError::EncryptionFailed)
+}
+
+fn decrypt_payload(
+    message_key: &RatchetKey,
+    ciphertext: &[u8],
+    associated_data: &[u8],
+) -> Result<Vec<u8>, SessionError> {
+    ChaCha20Poly1305::new(Key::from_slice(&message_key[..]))
+        .decrypt(&Nonce::default(), Payload {
+            msg: ciphertext,
+            aad: associated_data,
+        })
+        .map_err(|_| SessionError::DecryptionFailed)
+}
+
+fn key_from_bytes(bytes: &[u8]) -> Result<RatchetKey, SessionError> {
+    if bytes.len() != 32 {
+        return Err(SessionError::InvalidState("Invalid key length".to_string()));
+    }
+    let mut key = Zeroizing::new([0u8; 32]);
+    key.copy_from_slice(bytes);
+    Ok(key)
+}
+
+fn optional_public_key(bytes: &[u8]) -> Result<Option<CommsPublicKey>, SessionError> {
+    if bytes.is_empty() {
+        return Ok(None);
+    }
+    CommsPublicKey::from_bytes(bytes)
+        .map(Some)
+        .map_err(|e| SessionError::InvalidState(e.to_string()))
+}
+
+fn optional_key(bytes: &[u8]) -> Result<Option<RatchetKey>, SessionError> {
+    if bytes.is_empty() {
+        return Ok(None);
+    }
+    key_from_bytes(bytes).map(Some)
+}
+
+impl TryFrom<proto::RatchetSessionState> for RatchetSession {
+    type Error = SessionError;
+
+    fn try_from(state: proto::RatchetSessionState) -> Result<Self, Self::Error> {
+        let skipped_message_keys = state
+            .skipped_message_keys
+            .iter()
+            .map(|skipped| {
+                Ok(SkippedMessageKey {
+                    ratchet_public_key: CommsPublicKey::from_bytes(&skipped.ratchet_public_key)
+                        .map_err(|e| SessionError::InvalidState(e.to_string()))?,
+                    message_number: skipped.message_number,
+                    message_key: key_from_bytes(&skipped.message_key)?,
+                })
+            })
+            .collect::<Result<_, SessionError>>()?;
+
+        Ok(Self {
+            root_key: key_from_bytes(&state.root_key)?,
+            sending_ratchet_secret_key: CommsSecretKey::from_bytes(&state.sending_ratchet_secret_key)
+                .map_err(|e| SessionError::InvalidState(e.to_string()))?,
+            remote_ratchet_public_key: optional_public_key(&state.remote_ratchet_public_key)?,
+            sending_chain_key: optional_key(&state.sending_chain_key)?,
+            receiving_chain_key: optional_key(&state.receiving_chain_key)?,
+            sending_message_number: state.sending_message_number,
+            receiving_message_number: state.receiving_message_number,
+            previous_chain_length: state.previous_chain_length,
+            initiator_ephemeral_public_key: optional_public_key(&state.initiator_ephemeral_public_key)?,
+            remote_initiator_public_key: optional_public_key(&state.remote_initiator_public_key)?,
+            skipped_message_keys,
+        })
+    }
+}
+
+impl From<&RatchetSession> for proto::RatchetSessionState {
+    fn from(session: &RatchetSession) -> Self {
+        Self {
+            root_key: session.root_key.to_vec(),
+            sending_ratchet_secret_key: session.sending_ratchet_secret_key.to_vec(),
+            remote_ratchet_public_key: session
+                .remote_ratchet_public_key
+                .as_ref()
+                .map(|key| key.to_vec())
+                .unwrap_or_default(),
+            sending_chain_key: session
+                .sending_chain_key
+                .as_ref()
+                .map(|key| key.to_vec())
+                .unwrap_or_default(),
+            receiving_chain_key: session
+                .receiving_chain_key
+                .as_ref()
+                .map(|key| key.to_vec())
+                .unwrap_or_default(),
+            sending_message_number: session.sending_message_number,
+            receiving_message_number: session.receiving_message_number,
+            previous_chain_length: session.previous_chain_length,
+            initiator_ephemeral_public_key: session
+                .initiator_ephemeral_public_key
+                .as_ref()
+                .map(|key| key.to_vec())
+                .unwrap_or_default(),
+            remote_initiator_public_key: session
+                .remote_initiator_public_key
+                .as_ref()
+                .map(|key| key.to_vec())
+                .unwrap_or_default(),
+            skipped_message_keys: session
+                .skipped_message_keys
+                .iter()
+                .map(|skipped| proto::SkippedMessageKey {
+                    ratchet_public_key: skipped.ratchet_public_key.to_vec(),
+                    message_number: skipped.message_number,
+                    message_key: skipped.message_key.to_vec(),
+                })
+                .collect(),
+        }
+    }
+}
@@ -21,6 +21,7 @@
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use std::{
+    collections::HashMap,
     convert::TryFrom,
     fmt::{Display, Error, Formatter},
     sync::Arc,
@@ -29,11 +30,13 @@ use std::{
 use chrono::NaiveDateTime;
 use log::*;
 use tari_common_types::tari_address::TariAddress;
-use tari_comms::peer_manager::NodeId;
+use tari_comms::{peer_manager::NodeId, types::CommsPublicKey};
 
 use crate::contacts_service::{
     error::ContactsServiceStorageError,
-    types::{Contact, Message},
+    service::LivenessPrivacyMode,
+    session::RatchetSession,
+    types::{Contact, DeviceDelegation, Message, SenderListType},
 };
 
 const LOG_TARGET: &str = "contacts::contacts_service::database";
@@ -53,6 +56,16 @@ pub enum DbKey {
     Contacts,
     Message(Vec<u8>),
     Messages(TariAddress, i64, i64),
+    DeviceDelegation(CommsPublicKey),
+    DeviceDelegations(CommsPublicKey),
+    DeviceUnlinkedAt(CommsPublicKey),
+    SenderListEntry(CommsPublicKey),
+    SenderList(SenderListType),
+    MessageRequests,
+    MessageRequestsFrom(TariAddress),
+    RatchetSession(CommsPublicKey),
+    ContactPrivacyMode(NodeId),
+    ContactPrivacyModes,
 }
 
 pub enum DbValue {
@@ -61,6 +74,15 @@ pub enum DbValue {
     TariAddress(Box<TariAddress>),
     Message(Box<Message>),
     Messages(Vec<Message>),
+    DeviceDelegation(Box<DeviceDelegation>),
+    DeviceDelegations(Vec<DeviceDelegation>),
+    DeviceUnlinkedAt(u64),
+    SenderListEntry(SenderListType),
+    SenderList(Vec<TariAddress>),
+    MessageRequest(Box<Message>),
+    MessageRequests(Vec<Message>),
+    RatchetSession(Box<RatchetSession>),
+    ContactPrivacyModes(HashMap<NodeId, LivenessPrivacyMode>),
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -68,6 +90,11 @@ pub enum DbKeyValuePair {
     Contact(TariAddress, Contact),
     MessageConfirmations(Vec<u8>, Option<NaiveDateTime>, Option<NaiveDateTime>),
     LastSeen(NodeId, NaiveDateTime, Option<i32>),
+    DeviceDelegation(CommsPublicKey, DeviceDelegation),
+    DeviceUnlinked(CommsPublicKey, u64),
+    SenderListEntry(TariAddress, SenderListType),
+    RatchetSession(CommsPublicKey, Box<RatchetSession>),
+    ContactPrivacyMode(NodeId, LivenessPrivacyMode),
 }
 
 pub enum WriteOperation {
@@ -166,6 +193,210 @@ where T: ContactsBackend + 'static
         }
     }
 
+    /// Returns the delegation for the given device node
+    pub fn get_device_delegation(
+        &self,
+        device: CommsPublicKey,
+    ) -> Result<DeviceDelegation, ContactsServiceStorageError> {
+        let db_clone = self.db.clone();
+        fetch!(db_clone, device, DeviceDelegation)
+    }
+
+    /// Returns all the devices linked to the given identity
+    pub fn get_device_delegations(
+        &self,
+        identity: CommsPublicKey,
+    ) -> Result<Vec<DeviceDelegation>, ContactsServiceStorageError> {
+        let key = DbKey::DeviceDelegations(identity);
+        let db_clone = self.db.clone();
+        match db_clone.fetch(&key) {
+            Ok(None) => Ok(Vec::new()),
+            Ok(Some(DbValue::DeviceDelegations(delegations))) => Ok(delegations),
+            Ok(Some(other)) => unexpected_result(key, other),
+            Err(e) => log_error(key, e),
+        }
+    }
+
+    pub fn upsert_device_delegation(&self, delegation: DeviceDelegation) -> Result<(), ContactsServiceStorageError> {
+        self.db
+            .write(WriteOperation::Upsert(Box::new(DbKeyValuePair::DeviceDelegation(
+                delegation.device.clone(),
+                delegation,
+            ))))?;
+        Ok(())
+    }
+
+    /// Returns when the given device was last unlinked from its identity, if it has been
+    pub fn get_device_unlinked_at(&self, device: CommsPublicKey) -> Result<Option<u64>, ContactsServiceStorageError> {
+        let key = DbKey::DeviceUnlinkedAt(device);
+        let db_clone = self.db.clone();
+        match db_clone.fetch(&key) {
+            Ok(None) => Ok(None),
+            Ok(Some(DbValue::DeviceUnlinkedAt(timestamp))) => Ok(Some(timestamp)),
+            Ok(Some(other)) => unexpected_result(key, other),
+            Err(e) => log_error(key, e),
+        }
+    }
+
+    /// Unlinks the given device from its identity, unless it was linked again after `timestamp`
+    pub fn unlink_device(&self, device: CommsPublicKey, timestamp: u64) -> Result<(), ContactsServiceStorageError> {
+        self.db
+            .write(WriteOperation::Upsert(Box::new(DbKeyValuePair::DeviceUnlinked(
+                device, timestamp,
+            ))))?;
+        Ok(())
+    }
+
+    /// Returns the list the sender with the given public key is on, if any
+    pub fn get_sender_list_type(
+        &self,
+        public_key: CommsPublicKey,
+    ) -> Result<Option<SenderListType>, ContactsServiceStorageError> {
+        let key = DbKey::SenderListEntry(public_key);
+        let db_clone = self.db.clone();
+        match db_clone.fetch(&key) {
+            Ok(None) => Ok(None),
+            Ok(Some(DbValue::SenderListEntry(list_type))) => Ok(Some(list_type)),
+            Ok(Some(other)) => unexpected_result(key, other),
+            Err(e) => log_error(key, e),
+        }
+    }
+
+    /// Returns the addresses of all senders on the given list
+    pub fn get_sender_list(&self, list_type: SenderListType) -> Result<Vec<TariAddress>, ContactsServiceStorageError> {
+        let key = DbKey::SenderList(list_type);
+        let db_clone = self.db.clone();
+        match db_clone.fetch(&key) {
+            Ok(None) => Ok(Vec::new()),
+            Ok(Some(DbValue::SenderList(addresses))) => Ok(addresses),
+            Ok(Some(other)) => unexpected_result(key, other),
+            Err(e) => log_error(key, e),
+        }
+    }
+
+    /// Places the sender on the given list, or removes it from the allow and block lists if `list_type` is `None`
+    pub fn set_sender_list_type(
+        &self,
+        address: TariAddress,
+        list_type: Option<SenderListType>,
+    ) -> Result<(), ContactsServiceStorageError> {
+        match list_type {
+            Some(list_type) => {
+                self.db
+                    .write(WriteOperation::Upsert(Box::new(DbKeyValuePair::SenderListEntry(
+                        address, list_type,
+                    ))))?;
+            },
+            None => {
+                self.db.write(WriteOperation::Remove(DbKey::SenderListEntry(
+                    address.public_key().clone(),
+                )))?;
+            },
+        }
+        Ok(())
+    }
+
+    /// Returns the liveness privacy mode overrides of all peers that have one
+    pub fn get_contact_privacy_modes(
+        &self,
+    ) -> Result<HashMap<NodeId, LivenessPrivacyMode>, ContactsServiceStorageError> {
+        let key = DbKey::ContactPrivacyModes;
+        let db_clone = self.db.clone();
+        match db_clone.fetch(&key) {
+            Ok(None) => Ok(HashMap::new()),
+            Ok(Some(DbValue::ContactPrivacyModes(modes))) => Ok(modes),
+            Ok(Some(other)) => unexpected_result(key, other),
+            Err(e) => log_error(key, e),
+        }
+    }
+
+    /// Overrides the liveness privacy mode for the peer, or removes the override if `mode` is `None`
+    pub fn set_contact_privacy_mode(
+        &self,
+        node_id: NodeId,
+        mode: Option<LivenessPrivacyMode>,
+    ) -> Result<(), ContactsServiceStorageError> {
+        match mode {
+            Some(mode) => {
+                self.db
+                    .write(WriteOperation::Upsert(Box::new(DbKeyValuePair::ContactPrivacyMode(
+                        node_id, mode,
+                    ))))?;
+            },
+            None => {
+                self.db
+                    .write(WriteOperation::Remove(DbKey::ContactPrivacyMode(node_id)))?;
+            },
+        }
+        Ok(())
+    }
+
+    /// Quarantines a first-contact message until the user accepts or rejects the message request
+    pub fn save_message_request(&self, message: Message) -> Result<(), ContactsServiceStorageError> {
+        self.db
+            .write(WriteOperation::Insert(Box::new(DbValue::MessageRequest(Box::new(
+                message,
+            )))))?;
+        Ok(())
+    }
+
+    /// Returns all quarantined messages, oldest first
+    pub fn get_message_requests(&self) -> Result<Vec<Message>, ContactsServiceStorageError> {
+        let db_clone = self.db.clone();
+        match db_clone.fetch(&DbKey::MessageRequests) {
+            Ok(None) => Ok(Vec::new()),
+            Ok(Some(DbValue::MessageRequests(messages))) => Ok(messages),
+            Ok(Some(other)) => unexpected_result(DbKey::MessageRequests, other),
+            Err(e) => log_error(DbKey::MessageRequests, e),
+        }
+    }
+
+    /// Removes and returns the quarantined messages from the given sender, oldest first
+    pub fn remove_message_requests(&self, address: TariAddress) -> Result<Vec<Message>, ContactsServiceStorageError> {
+        let key = DbKey::MessageRequestsFrom(address);
+        match self.db.write(WriteOperation::Remove(key.clone()))? {
+            None => Ok(Vec::new()),
+            Some(DbValue::MessageRequests(messages)) => Ok(messages),
+            Some(other) => unexpected_result(key, other),
+        }
+    }
+
+    /// Returns the ratchet session with the given node, if one was established
+    pub fn get_ratchet_session(
+        &self,
+        public_key: CommsPublicKey,
+    ) -> Result<Option<RatchetSession>, ContactsServiceStorageError> {
+        let key = DbKey::RatchetSession(public_key);
+        let db_clone = self.db.clone();
+        match db_clone.fetch(&key) {
+            Ok(None) => Ok(None),
+            Ok(Some(DbValue::RatchetSession(session))) => Ok(Some(*session)),
+            Ok(Some(other)) => unexpected_result(key, other),
+            Err(e) => log_error(key, e),
+        }
+    }
+
+    /// Removes the ratchet session with the given node, so that the next payload starts a new session
+    pub fn remove_ratchet_session(&self, public_key: CommsPublicKey) -> Result<(), ContactsServiceStorageError> {
+        self.db
+            .write(WriteOperation::Remove(DbKey::RatchetSession(public_key)))?;
+        Ok(())
+    }
+
+    /// Persists the state of the ratchet session with the given node, which changes with every payload
+    pub fn upsert_ratchet_session(
+        &self,
+        public_key: CommsPublicKey,
+        session: RatchetSession,
+    ) -> Result<(), ContactsServiceStorageError> {
+        self.db
+            .write(WriteOperation::Upsert(Box::new(DbKeyValuePair::RatchetSession(
+                public_key,
+                Box::new(session),
+            ))))?;
+        Ok(())
+    }
+
     pub fn get_messages(
         &self,
         address: TariAddress,
@@ -239,6 +470,16 @@ impl Display for DbKey {
             DbKey::Contacts => f.write_str("Contacts"),
             DbKey::Messages(c, _l, _p) => f.write_str(&format!("Messages for id: {:?}", c)),
             DbKey::Message(m) => f.write_str(&format!("Message for id: {:?}", m)),
+            DbKey::DeviceDelegation(d) => f.write_str(&format!("Device delegation for device: {:?}", d)),
+            DbKey::DeviceDelegations(i) => f.write_str(&format!("Device delegations for identity: {:?}", i)),
+            DbKey::DeviceUnlinkedAt(d) => f.write_str(&format!("Device unlinked at for device: {:?}", d)),
+            DbKey::SenderListEntry(pk) => f.write_str(&format!("Sender list entry for: {:?}", pk)),
+            DbKey::SenderList(l) => f.write_str(&format!("Sender list: {}", l)),
+            DbKey::MessageRequests => f.write_str("Message requests"),
+            DbKey::MessageRequestsFrom(a) => f.write_str(&format!("Message requests from: {:?}", a)),
+            DbKey::RatchetSession(pk) => f.write_str(&format!("Ratchet session with: {:?}", pk)),
+            DbKey::ContactPrivacyMode(id) => f.write_str(&format!("Contact privacy mode for: {:?}", id)),
+            DbKey::ContactPrivacyModes => f.write_str("Contact privacy modes"),
         }
     }
 }
@@ -251,6 +492,15 @@ impl Display for DbValue {
             DbValue::TariAddress(_) => f.write_str("Address"),
             DbValue::Messages(_) => f.write_str("Messages"),
             DbValue::Message(_) => f.write_str("Message"),
+            DbValue::DeviceDelegation(_) => f.write_str("DeviceDelegation"),
+            DbValue::DeviceDelegations(_) => f.write_str("DeviceDelegations"),
+            DbValue::DeviceUnlinkedAt(_) => f.write_str("DeviceUnlinkedAt"),
+            DbValue::SenderListEntry(_) => f.write_str("SenderListEntry"),
+            DbValue::SenderList(_) => f.write_str("SenderList"),
+            DbValue::MessageRequest(_) => f.write_str("MessageRequest"),
+            DbValue::MessageRequests(_) => f.write_str("MessageRequests"),
+            DbValue::RatchetSession(_) => f.write_str("RatchetSession"),
+            DbValue::ContactPrivacyModes(_) => f.write_str("ContactPrivacyModes"),
         }
     }
 }
@@ -20,49 +20,104 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{convert::TryFrom, sync::Arc};
+use std::{collections::HashMap, convert::TryFrom, sync::Arc};
 
+use chacha20poly1305::{Key, KeyInit, XChaCha20Poly1305};
 use diesel::result::Error as DieselError;
 use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
 use tari_common_sqlite::{error::SqliteStorageError, sqlite_connection_pool::PooledDbConnection};
-use tari_common_types::tari_address::TariAddress;
+use tari_common_types::{
+    encryption::{Encryptable, SharedCipher},
+    tari_address::TariAddress,
+};
+use tari_comms::types::{CommsChallenge, CommsSecretKey};
+use tari_crypto::{hash_domain, hashing::DomainSeparatedHasher};
 use tari_utilities::ByteArray;
+use zeroize::Zeroizing;
 
 use crate::contacts_service::{
     error::ContactsServiceStorageError,
     storage::{
         database::{ContactsBackend, DbKey, DbKeyValuePair, DbValue, WriteOperation},
         types::{
+            contact_privacy_modes::ContactPrivacyModeSql,
             contacts::{ContactSql, UpdateContact},
+            device_delegations::DeviceDelegationSql,
+            message_requests::MessageRequestSql,
             messages::{MessageUpdate, MessagesSql, MessagesSqlInsert},
+            ratchet_sessions::RatchetSessionSql,
+            sender_lists::SenderListSql,
         },
     },
-    types::{Contact, Message},
+    types::{DeviceDelegation, Message},
 };
 
 const MIGRATIONS: EmbeddedMigrations = embed_migrations!("./migrations");
 
+hash_domain!(ContactsDatabaseDomain, "com.tari.base_layer.contacts.database", 1);
+
+/// Derives the database cipher from the node identity, for chat clients that run without a wallet and so have no
+/// passphrase to derive it from
+pub fn cipher_from_node_identity(secret_key: &CommsSecretKey) -> XChaCha20Poly1305 {
+    let hasher = DomainSeparatedHasher::<CommsChallenge, ContactsDatabaseDomain>::new_with_label("cipher")
+        .chain(secret_key.as_bytes());
+    let mut key = Zeroizing::new([0u8; 32]);
+    key.copy_from_slice(hasher.finalize().as_ref());
+    XChaCha20Poly1305::new(Key::from_slice(key.as_ref()))
+}
+
 /// A Sqlite backend for the Output Manager Service. The Backend is accessed via a connection pool to the Sqlite file.
+/// The cipher encrypts the contact aliases and the ratchet session state, which holds the keys of every chat session.
 #[derive(Clone)]
 pub struct ContactsServiceSqliteDatabase<TContactServiceDbConnection> {
     database_connection: Arc<TContactServiceDbConnection>,
+    cipher: SharedCipher,
 }
 
 impl<TContactServiceDbConnection: PooledDbConnection<Error = SqliteStorageError>>
     ContactsServiceSqliteDatabase<TContactServiceDbConnection>
 {
-    pub fn new(database_connection: TContactServiceDbConnection) -> Self {
+    pub fn new<C: Into<SharedCipher>>(database_connection: TContactServiceDbConnection, cipher: C) -> Self {
         Self {
             database_connection: Arc::new(database_connection),
+            cipher: cipher.into(),
         }
     }
 
-    pub fn init(database_connection: TContactServiceDbConnection) -> Self {
-        let db = Self::new(database_connection);
+    pub fn init<C: Into<SharedCipher>>(database_connection: TContactServiceDbConnection, cipher: C) -> Self {
+        let db = Self::new(database_connection, cipher);
         db.run_migrations().expect("Migrations to run");
+        db.encrypt_plaintext_aliases().expect("Contact aliases to be encrypted");
         db
     }
 
+    /// Encrypts the aliases of contacts saved before they were stored encrypted. A stored alias that can't be decrypted
+    /// is a plaintext one, as the database cipher doesn't change.
+    fn encrypt_plaintext_aliases(&self) -> Result<(), ContactsServiceStorageError> {
+        let cipher = self.unlocked_cipher()?;
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        for contact in ContactSql::index(&mut conn)? {
+            if contact.clone().decrypt(&cipher).is_ok() {
+                continue;
+            }
+            let contact = contact
+                .encrypt(&cipher)
+                .map_err(ContactsServiceStorageError::AeadError)?;
+            ContactSql::find_by_address_and_update(&mut conn, &contact.address, UpdateContact {
+                alias: Some(contact.alias.clone()),
+                last_seen: None,
+                latency: None,
+                favourite: None,
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Returns the database cipher, or an error if the database is locked
+    fn unlocked_cipher(&self) -> Result<XChaCha20Poly1305, ContactsServiceStorageError> {
+        self.cipher.get().ok_or(ContactsServiceStorageError::DatabaseLocked)
+    }
+
     fn run_migrations(&self) -> Result<Vec<String>, SqliteStorageError> {
         let mut conn = self.database_connection.get_pooled_connection()?;
         conn.run_pending_migrations(MIGRATIONS)
@@ -89,21 +144,24 @@ where TContactServiceDbConnection: PooledDbConnection<Error = SqliteStorageError
 
         let result = match key {
             DbKey::Contact(address) => match ContactSql::find_by_address(&address.to_bytes(), &mut conn) {
-                Ok(c) => Some(DbValue::Contact(Box::new(Contact::try_from(c)?))),
+                Ok(c) => Some(DbValue::Contact(Box::new(c.into_contact(&self.unlocked_cipher()?)?))),
                 Err(ContactsServiceStorageError::DieselError(DieselError::NotFound)) => None,
                 Err(e) => return Err(e),
             },
             DbKey::ContactId(id) => match ContactSql::find_by_node_id(&id.to_vec(), &mut conn) {
-                Ok(c) => Some(DbValue::Contact(Box::new(Contact::try_from(c)?))),
+                Ok(c) => Some(DbValue::Contact(Box::new(c.into_contact(&self.unlocked_cipher()?)?))),
                 Err(ContactsServiceStorageError::DieselError(DieselError::NotFound)) => None,
                 Err(e) => return Err(e),
             },
-            DbKey::Contacts => Some(DbValue::Contacts(
-                ContactSql::index(&mut conn)?
-                    .iter()
-                    .map(|c| Contact::try_from(c.clone()))
-                    .collect::<Result<Vec<_>, _>>()?,
-            )),
+            DbKey::Contacts => {
+                let cipher = self.unlocked_cipher()?;
+                Some(DbValue::Contacts(
+                    ContactSql::index(&mut conn)?
+                        .into_iter()
+                        .map(|c| c.into_contact(&cipher))
+                        .collect::<Result<Vec<_>, _>>()?,
+                ))
+            },
             DbKey::Messages(address, limit, page) => {
                 match MessagesSql::find_by_address(&address.to_bytes(), *limit, *page, &mut conn) {
                     Ok(messages) => Some(DbValue::Messages(
@@ -121,6 +179,67 @@ where TContactServiceDbConnection: PooledDbConnection<Error = SqliteStorageError
                 Err(ContactsServiceStorageError::DieselError(DieselError::NotFound)) => None,
                 Err(e) => return Err(e),
             },
+            DbKey::DeviceDelegation(device) => {
+                match DeviceDelegationSql::find_by_device(device.as_bytes(), &mut conn) {
+                    Ok(d) => Some(DbValue::DeviceDelegation(Box::new(DeviceDelegation::try_from(d)?))),
+                    Err(ContactsServiceStorageError::DieselError(DieselError::NotFound)) => None,
+                    Err(e) => return Err(e),
+                }
+            },
+            DbKey::DeviceDelegations(identity) => Some(DbValue::DeviceDelegations(
+                DeviceDelegationSql::find_by_identity(identity.as_bytes(), &mut conn)?
+                    .into_iter()
+                    .map(DeviceDelegation::try_from)
+                    .collect::<Result<Vec<_>, _>>()?,
+            )),
+            DbKey::DeviceUnlinkedAt(device) => DeviceDelegationSql::find_revoked_at(device.as_bytes(), &mut conn)?
+                .map(|revoked_at| {
+                    u64::try_from(revoked_at)
+                        .map(DbValue::DeviceUnlinkedAt)
+                        .map_err(|_| ContactsServiceStorageError::ConversionError)
+                })
+                .transpose()?,
+            DbKey::SenderListEntry(public_key) => {
+                match SenderListSql::find_by_public_key(public_key.as_bytes(), &mut conn) {
+                    Ok(entry) => Some(DbValue::SenderListEntry(entry.list_type()?)),
+                    Err(ContactsServiceStorageError::DieselError(DieselError::NotFound)) => None,
+                    Err(e) => return Err(e),
+                }
+            },
+            DbKey::SenderList(list_type) => Some(DbValue::SenderList(
+                SenderListSql::find_by_list_type(*list_type, &mut conn)?
+                    .iter()
+                    .map(SenderListSql::address)
+                    .collect::<Result<Vec<_>, _>>()?,
+            )),
+            DbKey::MessageRequests => Some(DbValue::MessageRequests(
+                MessageRequestSql::index(&mut conn)?
+                    .into_iter()
+                    .map(Message::try_from)
+                    .collect::<Result<Vec<_>, _>>()?,
+            )),
+            DbKey::MessageRequestsFrom(address) => Some(DbValue::MessageRequests(
+                MessageRequestSql::find_by_address(&address.to_bytes(), &mut conn)?
+                    .into_iter()
+                    .map(Message::try_from)
+                    .collect::<Result<Vec<_>, _>>()?,
+            )),
+            DbKey::RatchetSession(public_key) => {
+                match RatchetSessionSql::find_by_public_key(public_key.as_bytes(), &mut conn) {
+                    Ok(s) => Some(DbValue::RatchetSession(Box::new(
+                        s.into_session(&self.unlocked_cipher()?)?,
+                    ))),
+                    Err(ContactsServiceStorageError::DieselError(DieselError::NotFound)) => None,
+                    Err(e) => return Err(e),
+                }
+            },
+            DbKey::ContactPrivacyModes => Some(DbValue::ContactPrivacyModes(
+                ContactPrivacyModeSql::index(&mut conn)?
+                    .iter()
+                    .map(|m| Ok((m.node_id()?, m.privacy_mode()?)))
+                    .collect::<Result<HashMap<_, _>, ContactsServiceStorageError>>()?,
+            )),
+            DbKey::ContactPrivacyMode(_) => return Err(ContactsServiceStorageError::OperationNotSupported),
         };
 
         Ok(result)
@@ -142,17 +261,37 @@ where TContactServiceDbConnection: PooledDbConnection<Error = SqliteStorageError
                     }
                 },
                 DbKeyValuePair::Contact(k, c) => {
+                    let contact = ContactSql::from(c)
+                        .encrypt(&self.unlocked_cipher()?)
+                        .map_err(ContactsServiceStorageError::AeadError)?;
                     if ContactSql::find_by_address_and_update(&mut conn, &k.to_bytes(), UpdateContact {
-                        alias: Some(c.clone().alias),
+                        alias: Some(contact.alias.clone()),
                         last_seen: None,
                         latency: None,
-                        favourite: Some(i32::from(c.favourite)),
+                        favourite: Some(contact.favourite),
                     })
                     .is_err()
                     {
-                        ContactSql::from(c).commit(&mut conn)?;
+                        contact.commit(&mut conn)?;
                     }
                 },
+                DbKeyValuePair::DeviceDelegation(_, d) => {
+                    DeviceDelegationSql::try_from(d)?.commit(&mut conn)?;
+                },
+                DbKeyValuePair::DeviceUnlinked(device, timestamp) => {
+                    let revoked_at =
+                        i64::try_from(timestamp).map_err(|_| ContactsServiceStorageError::ConversionError)?;
+                    DeviceDelegationSql::revoke(device.as_bytes(), revoked_at, &mut conn)?;
+                },
+                DbKeyValuePair::SenderListEntry(address, list_type) => {
+                    SenderListSql::new(&address, list_type).commit(&mut conn)?;
+                },
+                DbKeyValuePair::RatchetSession(public_key, session) => {
+                    RatchetSessionSql::new(&public_key, &session, &self.unlocked_cipher()?)?.commit(&mut conn)?;
+                },
+                DbKeyValuePair::ContactPrivacyMode(node_id, mode) => {
+                    ContactPrivacyModeSql::new(&node_id, mode).commit(&mut conn)?;
+                },
                 DbKeyValuePair::LastSeen(..) => return Err(ContactsServiceStorageError::OperationNotSupported),
             },
             WriteOperation::UpdateLastSeen(kvp) => match *kvp {
@@ -170,33 +309,71 @@ where TContactServiceDbConnection: PooledDbConnection<Error = SqliteStorageError
                     ))));
                 },
                 DbKeyValuePair::Contact(..) => return Err(ContactsServiceStorageError::OperationNotSupported),
-                DbKeyValuePair::MessageConfirmations(..) => {
+                DbKeyValuePair::MessageConfirmations(..) |
+                DbKeyValuePair::DeviceDelegation(..) |
+                DbKeyValuePair::DeviceUnlinked(..) |
+                DbKeyValuePair::SenderListEntry(..) |
+                DbKeyValuePair::RatchetSession(..) |
+                DbKeyValuePair::ContactPrivacyMode(..) => {
                     return Err(ContactsServiceStorageError::OperationNotSupported)
                 },
             },
             WriteOperation::Remove(k) => match k {
-                DbKey::Contact(k) => match ContactSql::find_by_address_and_delete(&mut conn, &k.to_bytes()) {
-                    Ok(c) => {
-                        return Ok(Some(DbValue::Contact(Box::new(Contact::try_from(c)?))));
-                    },
-                    Err(ContactsServiceStorageError::DieselError(DieselError::NotFound)) => (),
-                    Err(e) => return Err(e),
+                DbKey::Contact(k) => {
+                    let cipher = self.unlocked_cipher()?;
+                    match ContactSql::find_by_address_and_delete(&mut conn, &k.to_bytes()) {
+                        Ok(c) => {
+                            return Ok(Some(DbValue::Contact(Box::new(c.into_contact(&cipher)?))));
+                        },
+                        Err(ContactsServiceStorageError::DieselError(DieselError::NotFound)) => (),
+                        Err(e) => return Err(e),
+                    }
                 },
-                DbKey::ContactId(id) => match ContactSql::find_by_node_id_and_delete(&mut conn, &id.to_vec()) {
-                    Ok(c) => {
-                        return Ok(Some(DbValue::Contact(Box::new(Contact::try_from(c)?))));
-                    },
-                    Err(ContactsServiceStorageError::DieselError(DieselError::NotFound)) => (),
-                    Err(e) => return Err(e),
+                DbKey::ContactId(id) => {
+                    let cipher = self.unlocked_cipher()?;
+                    match ContactSql::find_by_node_id_and_delete(&mut conn, &id.to_vec()) {
+                        Ok(c) => {
+                            return Ok(Some(DbValue::Contact(Box::new(c.into_contact(&cipher)?))));
+                        },
+                        Err(ContactsServiceStorageError::DieselError(DieselError::NotFound)) => (),
+                        Err(e) => return Err(e),
+                    }
                 },
                 DbKey::Contacts => return Err(ContactsServiceStorageError::OperationNotSupported),
                 DbKey::Messages(_pk, _l, _p) => return Err(ContactsServiceStorageError::OperationNotSupported),
                 DbKey::Message(_id) => return Err(ContactsServiceStorageError::OperationNotSupported),
+                DbKey::SenderListEntry(public_key) => {
+                    SenderListSql::delete_by_public_key(public_key.as_bytes(), &mut conn)?;
+                },
+                DbKey::RatchetSession(public_key) => {
+                    RatchetSessionSql::delete_by_public_key(public_key.as_bytes(), &mut conn)?;
+                },
+                DbKey::ContactPrivacyMode(node_id) => {
+                    ContactPrivacyModeSql::delete_by_node_id(node_id.as_bytes(), &mut conn)?;
+                },
+                DbKey::MessageRequestsFrom(address) => {
+                    return Ok(Some(DbValue::MessageRequests(
+                        MessageRequestSql::find_by_address_and_delete(&address.to_bytes(), &mut conn)?
+                            .into_iter()
+                            .map(Message::try_from)
+                            .collect::<Result<Vec<_>, _>>()?,
+                    )));
+                },
+                DbKey::DeviceDelegation(_) |
+                DbKey::DeviceDelegations(_) |
+                DbKey::DeviceUnlinkedAt(_) |
+                DbKey::SenderList(_) |
+                DbKey::MessageRequests |
+                DbKey::ContactPrivacyModes => return Err(ContactsServiceStorageError::OperationNotSupported),
             },
-            WriteOperation::Insert(i) => {
-                if let DbValue::Message(m) = *i {
+            WriteOperation::Insert(i) => match *i {
+                DbValue::Message(m) => {
                     MessagesSqlInsert::try_from(*m)?.commit(&mut conn)?;
-                }
+                },
+                DbValue::MessageRequest(m) => {
+                    MessageRequestSql::try_from(*m)?.commit(&mut conn)?;
+                },
+                _ => {},
             },
         }
 
@@ -208,6 +385,7 @@ where TContactServiceDbConnection: PooledDbConnection<Error = SqliteStorageError
 mod test {
     use std::convert::{TryFrom, TryInto};
 
+    use prost::Message as _;
     use rand::rngs::OsRng;
     use tari_common::configuration::Network;
     use tari_common_sqlite::connection::{DbConnection, DbConnectionUrl};
@@ -215,11 +393,15 @@ mod test {
         tari_address::TariAddress,
         types::{PrivateKey, PublicKey},
     };
+    use tari_comms::peer_manager::NodeId;
     use tari_crypto::keys::{PublicKey as PublicKeyTrait, SecretKey as SecretKeyTrait};
     use tari_test_utils::{paths::with_temp_dir, random::string};
 
     use super::*;
     use crate::contacts_service::{
+        proto,
+        service::LivenessPrivacyMode,
+        session::RatchetSession,
         storage::types::contacts::{ContactSql, UpdateContact},
         types::Contact,
     };
@@ -232,7 +414,10 @@ mod test {
             let url: DbConnectionUrl = db_path.try_into().unwrap();
 
             let db = DbConnection::connect_url(&url).unwrap();
-            let _service = ContactsServiceSqliteDatabase::init(db.clone());
+            let _service = ContactsServiceSqliteDatabase::init(
+                db.clone(),
+                cipher_from_node_identity(&PrivateKey::random(&mut OsRng)),
+            );
             let mut conn = db.get_pooled_connection().unwrap();
 
             let names = ["Alice".to_string(), "Bob".to_string(), "Carol".to_string()];
@@ -280,4 +465,146 @@ mod test {
             assert_eq!(c_updated.favourite, i32::from(true));
         });
     }
+
+    #[test]
+    fn test_ratchet_sessions_are_encrypted() {
+        with_temp_dir(|dir_path| {
+            let db_name = format!("{}.sqlite3", string(8).as_str());
+            let db_path = format!("{}/{}", dir_path.to_str().unwrap(), db_name);
+            let url: DbConnectionUrl = db_path.try_into().unwrap();
+
+            let db = DbConnection::connect_url(&url).unwrap();
+            let cipher = SharedCipher::new(cipher_from_node_identity(&PrivateKey::random(&mut OsRng)));
+            let backend = ContactsServiceSqliteDatabase::init(db.clone(), cipher.clone());
+
+            let (_, remote) = PublicKey::random_keypair(&mut OsRng);
+            let session = RatchetSession::initiate(&PrivateKey::random(&mut OsRng), &remote);
+            let state = proto::RatchetSessionState::from(&session);
+            backend
+                .write(WriteOperation::Upsert(Box::new(DbKeyValuePair::RatchetSession(
+                    remote.clone(),
+                    Box::new(session),
+                ))))
+                .unwrap();
+
+            let mut conn = db.get_pooled_connection().unwrap();
+            let stored = RatchetSessionSql::find_by_public_key(remote.as_bytes(), &mut conn).unwrap();
+            assert_ne!(stored.state, state.encode_to_vec());
+
+            let key = DbKey::RatchetSession(remote.clone());
+            match backend.fetch(&key).unwrap() {
+                Some(DbValue::RatchetSession(fetched)) => {
+                    assert_eq!(proto::RatchetSessionState::from(fetched.as_ref()), state)
+                },
+                _ => panic!("Expected a ratchet session"),
+            }
+
+            cipher.clear();
+            assert!(matches!(
+                backend.fetch(&key),
+                Err(ContactsServiceStorageError::DatabaseLocked)
+            ));
+
+            backend.write(WriteOperation::Remove(key.clone())).unwrap();
+            assert!(backend.fetch(&key).unwrap().is_none());
+        });
+    }
+
+    #[test]
+    fn test_contact_aliases_are_encrypted() {
+        with_temp_dir(|dir_path| {
+            let db_name = format!("{}.sqlite3", string(8).as_str());
+            let db_path = format!("{}/{}", dir_path.to_str().unwrap(), db_name);
+            let url: DbConnectionUrl = db_path.try_into().unwrap();
+
+            let db = DbConnection::connect_url(&url).unwrap();
+            let cipher = SharedCipher::new(cipher_from_node_identity(&PrivateKey::random(&mut OsRng)));
+            let _backend = ContactsServiceSqliteDatabase::init(db.clone(), cipher.clone());
+            let mut conn = db.get_pooled_connection().unwrap();
+
+            // A contact saved before the aliases were encrypted is encrypted when the backend is initialized
+            let legacy_address = TariAddress::new(PublicKey::random_keypair(&mut OsRng).1, Network::default());
+            let legacy = Contact::new("Alice".to_string(), legacy_address.clone(), None, None, false);
+            ContactSql::from(legacy.clone()).commit(&mut conn).unwrap();
+            let backend = ContactsServiceSqliteDatabase::init(db.clone(), cipher.clone());
+            let stored = ContactSql::find_by_address(&legacy_address.to_bytes(), &mut conn).unwrap();
+            assert_ne!(stored.alias, legacy.alias);
+
+            let address = TariAddress::new(PublicKey::random_keypair(&mut OsRng).1, Network::default());
+            let contact = Contact::new("Bob".to_string(), address.clone(), None, None, true);
+            backend
+                .write(WriteOperation::Upsert(Box::new(DbKeyValuePair::Contact(
+                    address.clone(),
+                    contact.clone(),
+                ))))
+                .unwrap();
+            let stored = ContactSql::find_by_address(&address.to_bytes(), &mut conn).unwrap();
+            assert_ne!(stored.alias, contact.alias);
+
+            match backend.fetch(&DbKey::Contacts).unwrap() {
+                Some(DbValue::Contacts(contacts)) => {
+                    assert_eq!(contacts.len(), 2);
+                    assert!(contacts.contains(&legacy));
+                    assert!(contacts.contains(&contact));
+                },
+                _ => panic!("Expected contacts"),
+            }
+
+            cipher.clear();
+            assert!(matches!(
+                backend.fetch(&DbKey::Contact(address.clone())),
+                Err(ContactsServiceStorageError::DatabaseLocked)
+            ));
+            assert!(matches!(
+                backend.write(WriteOperation::Remove(DbKey::Contact(address.clone()))),
+                Err(ContactsServiceStorageError::DatabaseLocked)
+            ));
+        });
+    }
+
+    #[test]
+    fn test_contact_privacy_modes_survive_a_restart() {
+        with_temp_dir(|dir_path| {
+            let db_name = format!("{}.sqlite3", string(8).as_str());
+            let db_path = format!("{}/{}", dir_path.to_str().unwrap(), db_name);
+            let url: DbConnectionUrl = db_path.try_into().unwrap();
+            let cipher = cipher_from_node_identity(&PrivateKey::random(&mut OsRng));
+
+            let db = DbConnection::connect_url(&url).unwrap();
+            let backend = ContactsServiceSqliteDatabase::init(db, cipher.clone());
+            let alice = NodeId::from_key(&PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng)));
+            let bob = NodeId::from_key(&PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng)));
+            for (node_id, mode) in [
+                (alice.clone(), LivenessPrivacyMode::Never),
+                (bob.clone(), LivenessPrivacyMode::ContactsOnly),
+                (bob.clone(), LivenessPrivacyMode::Always),
+            ] {
+                backend
+                    .write(WriteOperation::Upsert(Box::new(DbKeyValuePair::ContactPrivacyMode(
+                        node_id, mode,
+                    ))))
+                    .unwrap();
+            }
+            drop(backend);
+
+            let db = DbConnection::connect_url(&url).unwrap();
+            let backend = ContactsServiceSqliteDatabase::init(db, cipher);
+            match backend.fetch(&DbKey::ContactPrivacyModes).unwrap() {
+                Some(DbValue::ContactPrivacyModes(modes)) => {
+                    assert_eq!(modes.len(), 2);
+                    assert_eq!(modes[&alice], LivenessPrivacyMode::Never);
+                    assert_eq!(modes[&bob], LivenessPrivacyMode::Always);
+                },
+                _ => panic!("Expected contact privacy modes"),
+            }
+
+            backend
+                .write(WriteOperation::Remove(DbKey::ContactPrivacyMode(alice.clone())))
+                .unwrap();
+            match backend.fetch(&DbKey::ContactPrivacyModes).unwrap() {
+                Some(DbValue::ContactPrivacyModes(modes)) => assert!(!modes.contains_key(&alice)),
+                _ => panic!("Expected contact privacy modes"),
+            }
+        });
+    }
 }
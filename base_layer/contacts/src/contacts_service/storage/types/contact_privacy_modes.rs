@@ -0,0 +1,85 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::convert::TryFrom;
+
+use diesel::{prelude::*, SqliteConnection};
+use tari_comms::peer_manager::NodeId;
+use tari_utilities::ByteArray;
+
+use crate::{
+    contacts_service::{error::ContactsServiceStorageError, service::LivenessPrivacyMode},
+    schema::contact_privacy_modes,
+};
+
+/// A Sql version of a per-peer liveness privacy mode override
+#[derive(Clone, Debug, Queryable, Insertable, PartialEq, Eq)]
+#[diesel(table_name = contact_privacy_modes)]
+#[diesel(primary_key(node_id))]
+pub struct ContactPrivacyModeSql {
+    pub node_id: Vec<u8>,
+    pub privacy_mode: i32,
+}
+
+impl ContactPrivacyModeSql {
+    pub fn new(node_id: &NodeId, privacy_mode: LivenessPrivacyMode) -> Self {
+        Self {
+            node_id: node_id.to_vec(),
+            privacy_mode: i32::from(privacy_mode.as_u8()),
+        }
+    }
+
+    /// Write this struct to the database, replacing any existing override for the peer
+    pub fn commit(&self, conn: &mut SqliteConnection) -> Result<(), ContactsServiceStorageError> {
+        diesel::replace_into(contact_privacy_modes::table)
+            .values(self.clone())
+            .execute(conn)?;
+        Ok(())
+    }
+
+    /// Return all privacy mode overrides
+    pub fn index(conn: &mut SqliteConnection) -> Result<Vec<ContactPrivacyModeSql>, ContactsServiceStorageError> {
+        Ok(contact_privacy_modes::table.load::<ContactPrivacyModeSql>(conn)?)
+    }
+
+    /// Remove the override for a particular peer, returning the number of entries removed
+    pub fn delete_by_node_id(
+        node_id: &[u8],
+        conn: &mut SqliteConnection,
+    ) -> Result<usize, ContactsServiceStorageError> {
+        Ok(
+            diesel::delete(contact_privacy_modes::table.filter(contact_privacy_modes::node_id.eq(node_id)))
+                .execute(conn)?,
+        )
+    }
+
+    pub fn node_id(&self) -> Result<NodeId, ContactsServiceStorageError> {
+        NodeId::from_bytes(&self.node_id).map_err(|_| ContactsServiceStorageError::ConversionError)
+    }
+
+    pub fn privacy_mode(&self) -> Result<LivenessPrivacyMode, ContactsServiceStorageError> {
+        u8::try_from(self.privacy_mode)
+            .ok()
+            .and_then(LivenessPrivacyMode::from_byte)
+            .ok_or(ContactsServiceStorageError::ConversionError)
+    }
+}
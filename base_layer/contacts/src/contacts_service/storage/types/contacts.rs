@@ -20,21 +20,30 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::convert::TryFrom;
+use std::{convert::TryFrom, str::from_utf8};
 
+use chacha20poly1305::XChaCha20Poly1305;
 use chrono::NaiveDateTime;
 use diesel::{prelude::*, SqliteConnection};
 use tari_common_sqlite::util::diesel_ext::ExpectedRowsExtension;
-use tari_common_types::tari_address::TariAddress;
+use tari_common_types::{
+    encryption::{decrypt_bytes_integral_nonce, encrypt_bytes_integral_nonce, Encryptable},
+    tari_address::TariAddress,
+};
 use tari_comms::peer_manager::NodeId;
-use tari_utilities::ByteArray;
+use tari_utilities::{
+    hex::{from_hex, Hex},
+    ByteArray,
+    Hidden,
+};
+use zeroize::Zeroize;
 
 use crate::{
     contacts_service::{error::ContactsServiceStorageError, types::Contact},
     schema::contacts,
 };
 
-/// A Sql version of the Contact struct
+/// A Sql version of the Contact struct. The alias is stored encrypted.
 #[derive(Clone, Debug, Queryable, Insertable, PartialEq, Eq)]
 #[diesel(table_name = contacts)]
 pub struct ContactSql {
@@ -133,6 +142,41 @@ impl ContactSql {
             .num_rows_affected_or_not_found(1)?;
         Ok(contact)
     }
+
+    /// Decrypt the stored alias and convert the record to the datatype form
+    pub fn into_contact(self, cipher: &XChaCha20Poly1305) -> Result<Contact, ContactsServiceStorageError> {
+        let decrypted = self.decrypt(cipher).map_err(ContactsServiceStorageError::AeadError)?;
+        Contact::try_from(decrypted)
+    }
+}
+
+impl Encryptable<XChaCha20Poly1305> for ContactSql {
+    fn domain(&self, field_name: &'static str) -> Vec<u8> {
+        [Self::CONTACT, self.address.as_slice(), field_name.as_bytes()].concat()
+    }
+
+    fn encrypt(mut self, cipher: &XChaCha20Poly1305) -> Result<Self, String> {
+        self.alias = encrypt_bytes_integral_nonce(
+            cipher,
+            self.domain("alias"),
+            Hidden::hide(self.alias.as_bytes().to_vec()),
+        )?
+        .to_hex();
+        Ok(self)
+    }
+
+    fn decrypt(mut self, cipher: &XChaCha20Poly1305) -> Result<Self, String> {
+        let mut decrypted_alias = decrypt_bytes_integral_nonce(
+            cipher,
+            self.domain("alias"),
+            &from_hex(self.alias.as_str()).map_err(|e| e.to_string())?,
+        )?;
+        self.alias = from_utf8(decrypted_alias.as_slice())
+            .map_err(|e| e.to_string())?
+            .to_string();
+        decrypted_alias.zeroize();
+        Ok(self)
+    }
 }
 
 /// Conversion from an Contact to the Sql datatype form
@@ -0,0 +1,153 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::convert::TryFrom;
+
+use diesel::{prelude::*, SqliteConnection};
+use tari_comms::types::{CommsPublicKey, CommsSecretKey, Signature};
+use tari_utilities::ByteArray;
+
+use crate::{
+    contacts_service::{error::ContactsServiceStorageError, types::DeviceDelegation},
+    schema::device_delegations,
+};
+
+/// A Sql version of the DeviceDelegation struct
+#[derive(Clone, Debug, Queryable, Insertable, PartialEq, Eq)]
+#[diesel(table_name = device_delegations)]
+#[diesel(primary_key(device_public_key))]
+pub struct DeviceDelegationSql {
+    pub device_public_key: Vec<u8>,
+    pub identity_public_key: Vec<u8>,
+    pub timestamp: i64,
+    pub device_public_nonce: Vec<u8>,
+    pub device_signature: Vec<u8>,
+    pub identity_public_nonce: Vec<u8>,
+    pub identity_signature: Vec<u8>,
+    pub revoked_at: Option<i64>,
+}
+
+impl DeviceDelegationSql {
+    /// Write this struct to the database, replacing any existing delegation for the device
+    pub fn commit(&self, conn: &mut SqliteConnection) -> Result<(), ContactsServiceStorageError> {
+        diesel::replace_into(device_delegations::table)
+            .values(self.clone())
+            .execute(conn)?;
+        Ok(())
+    }
+
+    /// Find the delegation for a particular device, if it exists and has not been revoked
+    pub fn find_by_device(
+        device_public_key: &[u8],
+        conn: &mut SqliteConnection,
+    ) -> Result<DeviceDelegationSql, ContactsServiceStorageError> {
+        Ok(device_delegations::table
+            .filter(device_delegations::device_public_key.eq(device_public_key))
+            .filter(device_delegations::revoked_at.is_null())
+            .first::<DeviceDelegationSql>(conn)?)
+    }
+
+    /// Find when a particular device was last unlinked, if it has been
+    pub fn find_revoked_at(
+        device_public_key: &[u8],
+        conn: &mut SqliteConnection,
+    ) -> Result<Option<i64>, ContactsServiceStorageError> {
+        Ok(device_delegations::table
+            .select(device_delegations::revoked_at)
+            .filter(device_delegations::device_public_key.eq(device_public_key))
+            .first::<Option<i64>>(conn)
+            .optional()?
+            .flatten())
+    }
+
+    /// Revoke the delegation for a particular device, unless it was made after `revoked_at`
+    pub fn revoke(
+        device_public_key: &[u8],
+        revoked_at: i64,
+        conn: &mut SqliteConnection,
+    ) -> Result<(), ContactsServiceStorageError> {
+        diesel::update(
+            device_delegations::table
+                .filter(device_delegations::device_public_key.eq(device_public_key))
+                .filter(device_delegations::timestamp.le(revoked_at)),
+        )
+        .set(device_delegations::revoked_at.eq(Some(revoked_at)))
+        .execute(conn)?;
+        Ok(())
+    }
+
+    /// Return all delegations made by a particular identity that have not been revoked
+    pub fn find_by_identity(
+        identity_public_key: &[u8],
+        conn: &mut SqliteConnection,
+    ) -> Result<Vec<DeviceDelegationSql>, ContactsServiceStorageError> {
+        Ok(device_delegations::table
+            .filter(device_delegations::identity_public_key.eq(identity_public_key))
+            .filter(device_delegations::revoked_at.is_null())
+            .load::<DeviceDelegationSql>(conn)?)
+    }
+}
+
+fn signature_from_bytes(public_nonce: &[u8], signature: &[u8]) -> Result<Signature, ContactsServiceStorageError> {
+    Ok(Signature::new(
+        CommsPublicKey::from_bytes(public_nonce).map_err(|_| ContactsServiceStorageError::ConversionError)?,
+        CommsSecretKey::from_bytes(signature).map_err(|_| ContactsServiceStorageError::ConversionError)?,
+    ))
+}
+
+/// Conversion from the Sql datatype form to a DeviceDelegation
+impl TryFrom<DeviceDelegationSql> for DeviceDelegation {
+    type Error = ContactsServiceStorageError;
+
+    fn try_from(o: DeviceDelegationSql) -> Result<Self, Self::Error> {
+        Ok(Self {
+            identity: CommsPublicKey::from_bytes(&o.identity_public_key)
+                .map_err(|_| ContactsServiceStorageError::ConversionError)?,
+            device: CommsPublicKey::from_bytes(&o.device_public_key)
+                .map_err(|_| ContactsServiceStorageError::ConversionError)?,
+            timestamp: u64::try_from(o.timestamp).map_err(|_| ContactsServiceStorageError::ConversionError)?,
+            device_signature: signature_from_bytes(&o.device_public_nonce, &o.device_signature)?,
+            identity_signature: Some(signature_from_bytes(&o.identity_public_nonce, &o.identity_signature)?),
+        })
+    }
+}
+
+/// Conversion from a DeviceDelegation to the Sql datatype form. Only approved delegations are stored.
+impl TryFrom<DeviceDelegation> for DeviceDelegationSql {
+    type Error = ContactsServiceStorageError;
+
+    fn try_from(o: DeviceDelegation) -> Result<Self, Self::Error> {
+        let identity_signature = o
+            .identity_signature
+            .ok_or(ContactsServiceStorageError::ConversionError)?;
+        Ok(Self {
+            device_public_key: o.device.to_vec(),
+            identity_public_key: o.identity.to_vec(),
+            timestamp: i64::try_from(o.timestamp).map_err(|_| ContactsServiceStorageError::ConversionError)?,
+            device_public_nonce: o.device_signature.get_public_nonce().to_vec(),
+            device_signature: o.device_signature.get_signature().to_vec(),
+            identity_public_nonce: identity_signature.get_public_nonce().to_vec(),
+            identity_signature: identity_signature.get_signature().to_vec(),
+            revoked_at: None,
+        })
+    }
+}
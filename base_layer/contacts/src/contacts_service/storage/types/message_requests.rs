@@ -0,0 +1,128 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::convert::TryFrom;
+
+use chrono::NaiveDateTime;
+use diesel::{prelude::*, SqliteConnection};
+use tari_common_types::tari_address::TariAddress;
+
+use crate::{
+    contacts_service::{
+        error::ContactsServiceStorageError,
+        types::{Direction, Message, MessageMetadata},
+    },
+    schema::message_requests,
+};
+
+/// A Sql version of a quarantined first-contact message
+#[derive(Clone, Debug, Queryable, Insertable, PartialEq, Eq)]
+#[diesel(table_name = message_requests)]
+#[diesel(primary_key(message_id))]
+pub struct MessageRequestSql {
+    pub address: Vec<u8>,
+    pub message_id: Vec<u8>,
+    pub body: Vec<u8>,
+    pub metadata: Vec<u8>,
+    pub stored_at: NaiveDateTime,
+}
+
+impl MessageRequestSql {
+    /// Write this struct to the database, ignoring messages that are already quarantined
+    pub fn commit(&self, conn: &mut SqliteConnection) -> Result<(), ContactsServiceStorageError> {
+        diesel::insert_or_ignore_into(message_requests::table)
+            .values(self.clone())
+            .execute(conn)?;
+        Ok(())
+    }
+
+    /// Return all quarantined messages, oldest first
+    pub fn index(conn: &mut SqliteConnection) -> Result<Vec<MessageRequestSql>, ContactsServiceStorageError> {
+        Ok(message_requests::table
+            .order(message_requests::stored_at.asc())
+            .load::<MessageRequestSql>(conn)?)
+    }
+
+    /// Find the quarantined messages from a particular sender, oldest first
+    pub fn find_by_address(
+        address: &[u8],
+        conn: &mut SqliteConnection,
+    ) -> Result<Vec<MessageRequestSql>, ContactsServiceStorageError> {
+        Ok(message_requests::table
+            .filter(message_requests::address.eq(address))
+            .order(message_requests::stored_at.asc())
+            .load::<MessageRequestSql>(conn)?)
+    }
+
+    /// Remove and return the quarantined messages from a particular sender
+    pub fn find_by_address_and_delete(
+        address: &[u8],
+        conn: &mut SqliteConnection,
+    ) -> Result<Vec<MessageRequestSql>, ContactsServiceStorageError> {
+        conn.transaction::<_, ContactsServiceStorageError, _>(|conn| {
+            let requests = MessageRequestSql::find_by_address(address, conn)?;
+            diesel::delete(message_requests::table.filter(message_requests::address.eq(address))).execute(conn)?;
+            Ok(requests)
+        })
+    }
+}
+
+/// Conversion from the Sql datatype form to a Message
+impl TryFrom<MessageRequestSql> for Message {
+    type Error = ContactsServiceStorageError;
+
+    #[allow(clippy::cast_sign_loss)]
+    fn try_from(o: MessageRequestSql) -> Result<Self, Self::Error> {
+        let address = TariAddress::from_bytes(&o.address).map_err(|_| ContactsServiceStorageError::ConversionError)?;
+        let metadata: Vec<MessageMetadata> =
+            serde_json::from_slice(&o.metadata).map_err(|_| ContactsServiceStorageError::ConversionError)?;
+
+        Ok(Self {
+            address,
+            direction: Direction::Inbound,
+            stored_at: o.stored_at.timestamp() as u64,
+            body: o.body,
+            metadata,
+            message_id: o.message_id,
+            ..Message::default()
+        })
+    }
+}
+
+/// Conversion from a Message to the Sql datatype form
+#[allow(clippy::cast_possible_wrap)]
+impl TryFrom<Message> for MessageRequestSql {
+    type Error = ContactsServiceStorageError;
+
+    fn try_from(o: Message) -> Result<Self, Self::Error> {
+        let metadata = serde_json::to_vec(&o.metadata).map_err(|_| ContactsServiceStorageError::ConversionError)?;
+
+        Ok(Self {
+            address: o.address.to_bytes().to_vec(),
+            message_id: o.message_id,
+            body: o.body,
+            metadata,
+            stored_at: NaiveDateTime::from_timestamp_opt(o.stored_at as i64, 0)
+                .ok_or(ContactsServiceStorageError::ConversionError)?,
+        })
+    }
+}
@@ -20,5 +20,10 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+pub mod contact_privacy_modes;
 pub mod contacts;
+pub mod device_delegations;
+pub mod message_requests;
 pub mod messages;
+pub mod ratchet_sessions;
+pub mod sender_lists;
@@ -0,0 +1,127 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::convert::TryFrom;
+
+use chacha20poly1305::XChaCha20Poly1305;
+use chrono::{NaiveDateTime, Utc};
+use diesel::{prelude::*, SqliteConnection};
+use prost::Message as _;
+use tari_common_types::encryption::{decrypt_bytes_integral_nonce, encrypt_bytes_integral_nonce, Encryptable};
+use tari_comms::types::CommsPublicKey;
+use tari_utilities::{ByteArray, Hidden};
+use zeroize::Zeroize;
+
+use crate::{
+    contacts_service::{error::ContactsServiceStorageError, proto, session::RatchetSession},
+    schema::ratchet_sessions,
+};
+
+/// A Sql version of the ratchet session with a remote node. The session state holds the keys of the session, so it is
+/// only stored encrypted.
+#[derive(Clone, Queryable, Insertable)]
+#[diesel(table_name = ratchet_sessions)]
+#[diesel(primary_key(public_key))]
+pub struct RatchetSessionSql {
+    pub public_key: Vec<u8>,
+    pub state: Vec<u8>,
+    pub updated_at: NaiveDateTime,
+}
+
+impl RatchetSessionSql {
+    pub fn new(
+        public_key: &CommsPublicKey,
+        session: &RatchetSession,
+        cipher: &XChaCha20Poly1305,
+    ) -> Result<Self, ContactsServiceStorageError> {
+        Self {
+            public_key: public_key.to_vec(),
+            state: proto::RatchetSessionState::from(session).encode_to_vec(),
+            updated_at: Utc::now().naive_utc(),
+        }
+        .encrypt(cipher)
+        .map_err(ContactsServiceStorageError::AeadError)
+    }
+
+    /// Write this struct to the database, replacing the previous state of the session
+    pub fn commit(&self, conn: &mut SqliteConnection) -> Result<(), ContactsServiceStorageError> {
+        diesel::replace_into(ratchet_sessions::table)
+            .values(self.clone())
+            .execute(conn)?;
+        Ok(())
+    }
+
+    /// Find the session with a particular node, if it exists
+    pub fn find_by_public_key(
+        public_key: &[u8],
+        conn: &mut SqliteConnection,
+    ) -> Result<RatchetSessionSql, ContactsServiceStorageError> {
+        Ok(ratchet_sessions::table
+            .filter(ratchet_sessions::public_key.eq(public_key))
+            .first::<RatchetSessionSql>(conn)?)
+    }
+
+    /// Delete the session with a particular node, if it exists
+    pub fn delete_by_public_key(
+        public_key: &[u8],
+        conn: &mut SqliteConnection,
+    ) -> Result<(), ContactsServiceStorageError> {
+        diesel::delete(ratchet_sessions::table.filter(ratchet_sessions::public_key.eq(public_key))).execute(conn)?;
+        Ok(())
+    }
+
+    /// Decrypt the stored session state
+    pub fn into_session(self, cipher: &XChaCha20Poly1305) -> Result<RatchetSession, ContactsServiceStorageError> {
+        let decrypted = self.decrypt(cipher).map_err(ContactsServiceStorageError::AeadError)?;
+        RatchetSession::try_from(decrypted)
+    }
+}
+
+impl Encryptable<XChaCha20Poly1305> for RatchetSessionSql {
+    fn domain(&self, field_name: &'static str) -> Vec<u8> {
+        [Self::RATCHET_SESSION, self.public_key.as_slice(), field_name.as_bytes()].concat()
+    }
+
+    fn encrypt(mut self, cipher: &XChaCha20Poly1305) -> Result<Self, String> {
+        let state = std::mem::take(&mut self.state);
+        self.state = encrypt_bytes_integral_nonce(cipher, self.domain("state"), Hidden::hide(state))?;
+        Ok(self)
+    }
+
+    fn decrypt(mut self, cipher: &XChaCha20Poly1305) -> Result<Self, String> {
+        self.state = decrypt_bytes_integral_nonce(cipher, self.domain("state"), &self.state)?;
+        Ok(self)
+    }
+}
+
+impl TryFrom<RatchetSessionSql> for RatchetSession {
+    type Error = ContactsServiceStorageError;
+
+    fn try_from(o: RatchetSessionSql) -> Result<Self, Self::Error> {
+        let mut state = o.state;
+        let decoded = proto::RatchetSessionState::decode(state.as_slice());
+        // The decrypted state holds the keys of the session
+        state.zeroize();
+        let state = decoded.map_err(|_| ContactsServiceStorageError::ConversionError)?;
+        RatchetSession::try_from(state).map_err(|_| ContactsServiceStorageError::ConversionError)
+    }
+}
@@ -0,0 +1,99 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::convert::TryFrom;
+
+use diesel::{prelude::*, SqliteConnection};
+use tari_common_types::tari_address::TariAddress;
+use tari_utilities::ByteArray;
+
+use crate::{
+    contacts_service::{error::ContactsServiceStorageError, types::SenderListType},
+    schema::sender_lists,
+};
+
+/// A Sql version of a sender list entry
+#[derive(Clone, Debug, Queryable, Insertable, PartialEq, Eq)]
+#[diesel(table_name = sender_lists)]
+#[diesel(primary_key(public_key))]
+pub struct SenderListSql {
+    pub public_key: Vec<u8>,
+    pub address: Vec<u8>,
+    pub list_type: i32,
+}
+
+impl SenderListSql {
+    pub fn new(address: &TariAddress, list_type: SenderListType) -> Self {
+        Self {
+            public_key: address.public_key().to_vec(),
+            address: address.to_bytes().to_vec(),
+            list_type: i32::from(list_type.as_u8()),
+        }
+    }
+
+    /// Write this struct to the database, replacing any existing entry for the sender
+    pub fn commit(&self, conn: &mut SqliteConnection) -> Result<(), ContactsServiceStorageError> {
+        diesel::replace_into(sender_lists::table)
+            .values(self.clone())
+            .execute(conn)?;
+        Ok(())
+    }
+
+    /// Find the entry for a particular sender, if it exists
+    pub fn find_by_public_key(
+        public_key: &[u8],
+        conn: &mut SqliteConnection,
+    ) -> Result<SenderListSql, ContactsServiceStorageError> {
+        Ok(sender_lists::table
+            .filter(sender_lists::public_key.eq(public_key))
+            .first::<SenderListSql>(conn)?)
+    }
+
+    /// Return all senders on the given list
+    pub fn find_by_list_type(
+        list_type: SenderListType,
+        conn: &mut SqliteConnection,
+    ) -> Result<Vec<SenderListSql>, ContactsServiceStorageError> {
+        Ok(sender_lists::table
+            .filter(sender_lists::list_type.eq(i32::from(list_type.as_u8())))
+            .load::<SenderListSql>(conn)?)
+    }
+
+    /// Remove the entry for a particular sender, returning the number of entries removed
+    pub fn delete_by_public_key(
+        public_key: &[u8],
+        conn: &mut SqliteConnection,
+    ) -> Result<usize, ContactsServiceStorageError> {
+        Ok(diesel::delete(sender_lists::table.filter(sender_lists::public_key.eq(public_key))).execute(conn)?)
+    }
+
+    pub fn list_type(&self) -> Result<SenderListType, ContactsServiceStorageError> {
+        u8::try_from(self.list_type)
+            .ok()
+            .and_then(SenderListType::from_byte)
+            .ok_or(ContactsServiceStorageError::ConversionError)
+    }
+
+    pub fn address(&self) -> Result<TariAddress, ContactsServiceStorageError> {
+        TariAddress::from_bytes(&self.address).map_err(|_| ContactsServiceStorageError::ConversionError)
+    }
+}
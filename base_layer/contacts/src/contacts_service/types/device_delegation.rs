@@ -0,0 +1,311 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::convert::TryFrom;
+
+use rand::rngs::OsRng;
+use tari_comms::types::{CommsChallenge, CommsPublicKey, CommsSecretKey, Signature};
+use tari_crypto::{hash_domain, hashing::DomainSeparatedHasher, keys::PublicKey as PublicKeyTrait};
+use tari_utilities::{epoch_time::EpochTime, ByteArray};
+
+use crate::contacts_service::proto;
+
+hash_domain!(
+    ContactsDeviceDelegationDomain,
+    "com.tari.base_layer.contacts.device_delegation",
+    1
+);
+
+const DEVICE_SIGNATURE: &str = "device_signature";
+const IDENTITY_SIGNATURE: &str = "identity_signature";
+const REVOCATION_SIGNATURE: &str = "revocation_signature";
+
+/// Links a device node to a chat identity so that it can send and receive chat messages on behalf of that identity.
+///
+/// The device first signs a link request for the identity. The delegation only takes effect once the identity has
+/// approved the request by adding its own signature, so neither side can be linked without its consent.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeviceDelegation {
+    pub identity: CommsPublicKey,
+    pub device: CommsPublicKey,
+    pub timestamp: u64,
+    pub device_signature: Signature,
+    pub identity_signature: Option<Signature>,
+}
+
+impl DeviceDelegation {
+    /// Request that the device belonging to `device_secret_key` is linked to the given identity
+    pub fn request(device_secret_key: &CommsSecretKey, identity: CommsPublicKey) -> Self {
+        let device = CommsPublicKey::from_secret_key(device_secret_key);
+        let timestamp = EpochTime::now().as_u64();
+        let device_signature = Self::sign(DEVICE_SIGNATURE, device_secret_key, &identity, &device, timestamp);
+        Self {
+            identity,
+            device,
+            timestamp,
+            device_signature,
+            identity_signature: None,
+        }
+    }
+
+    /// Approve the link request with the secret key of the identity
+    pub fn approve(self, identity_secret_key: &CommsSecretKey) -> Self {
+        let identity_signature = Self::sign(
+            IDENTITY_SIGNATURE,
+            identity_secret_key,
+            &self.identity,
+            &self.device,
+            self.timestamp,
+        );
+        Self {
+            identity_signature: Some(identity_signature),
+            ..self
+        }
+    }
+
+    /// Returns true if the device signed a request to be linked to a different identity
+    pub fn is_valid_request(&self) -> bool {
+        self.identity != self.device &&
+            Self::verify(
+                DEVICE_SIGNATURE,
+                &self.device_signature,
+                &self.device,
+                &self.identity,
+                &self.device,
+                self.timestamp,
+            )
+    }
+
+    /// Returns true if the link was requested by the device and approved by the identity
+    pub fn is_valid(&self) -> bool {
+        self.is_valid_request() &&
+            self.identity_signature.as_ref().map_or(false, |signature| {
+                Self::verify(
+                    IDENTITY_SIGNATURE,
+                    signature,
+                    &self.identity,
+                    &self.identity,
+                    &self.device,
+                    self.timestamp,
+                )
+            })
+    }
+
+    fn sign(
+        label: &'static str,
+        secret_key: &CommsSecretKey,
+        identity: &CommsPublicKey,
+        device: &CommsPublicKey,
+        timestamp: u64,
+    ) -> Signature {
+        let (secret_nonce, public_nonce) = CommsPublicKey::random_keypair(&mut OsRng);
+        let challenge = Self::construct_challenge(label, identity, device, &public_nonce, timestamp).finalize();
+        Signature::sign_raw(secret_key, secret_nonce, challenge.as_ref())
+            .expect("unreachable panic: challenge hash digest is the correct length")
+    }
+
+    fn verify(
+        label: &'static str,
+        signature: &Signature,
+        signer: &CommsPublicKey,
+        identity: &CommsPublicKey,
+        device: &CommsPublicKey,
+        timestamp: u64,
+    ) -> bool {
+        let challenge =
+            Self::construct_challenge(label, identity, device, signature.get_public_nonce(), timestamp).finalize();
+        signature.verify_challenge(signer, challenge.as_ref())
+    }
+
+    fn construct_challenge(
+        label: &'static str,
+        identity: &CommsPublicKey,
+        device: &CommsPublicKey,
+        public_nonce: &CommsPublicKey,
+        timestamp: u64,
+    ) -> DomainSeparatedHasher<CommsChallenge, ContactsDeviceDelegationDomain> {
+        DomainSeparatedHasher::<CommsChallenge, ContactsDeviceDelegationDomain>::new_with_label(label)
+            .chain(identity.as_bytes())
+            .chain(device.as_bytes())
+            .chain(public_nonce.as_bytes())
+            .chain(timestamp.to_le_bytes())
+    }
+}
+
+fn signature_from_bytes(public_nonce: &[u8], signature: &[u8]) -> Result<Signature, String> {
+    let public_nonce = CommsPublicKey::from_bytes(public_nonce).map_err(|e| e.to_string())?;
+    let signature = CommsSecretKey::from_bytes(signature).map_err(|e| e.to_string())?;
+    Ok(Signature::new(public_nonce, signature))
+}
+
+impl TryFrom<proto::DeviceDelegation> for DeviceDelegation {
+    type Error = String;
+
+    fn try_from(delegation: proto::DeviceDelegation) -> Result<Self, Self::Error> {
+        let identity_signature = if delegation.identity_signature.is_empty() {
+            None
+        } else {
+            Some(signature_from_bytes(
+                &delegation.identity_public_nonce,
+                &delegation.identity_signature,
+            )?)
+        };
+        Ok(Self {
+            identity: CommsPublicKey::from_bytes(&delegation.identity_public_key).map_err(|e| e.to_string())?,
+            device: CommsPublicKey::from_bytes(&delegation.device_public_key).map_err(|e| e.to_string())?,
+            timestamp: delegation.timestamp,
+            device_signature: signature_from_bytes(&delegation.device_public_nonce, &delegation.device_signature)?,
+            identity_signature,
+        })
+    }
+}
+
+impl From<DeviceDelegation> for proto::DeviceDelegation {
+    fn from(delegation: DeviceDelegation) -> Self {
+        let (identity_public_nonce, identity_signature) = delegation
+            .identity_signature
+            .map(|s| (s.get_public_nonce().to_vec(), s.get_signature().to_vec()))
+            .unwrap_or_default();
+        Self {
+            identity_public_key: delegation.identity.to_vec(),
+            device_public_key: delegation.device.to_vec(),
+            timestamp: delegation.timestamp,
+            device_public_nonce: delegation.device_signature.get_public_nonce().to_vec(),
+            device_signature: delegation.device_signature.get_signature().to_vec(),
+            identity_public_nonce,
+            identity_signature,
+        }
+    }
+}
+
+/// Unlinks a device node from a chat identity. Either side of the link can revoke it, after which the device no longer
+/// receives messages for the identity. Delegations made before the revocation cannot be used to link the device again.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeviceRevocation {
+    pub identity: CommsPublicKey,
+    pub device: CommsPublicKey,
+    pub timestamp: u64,
+    pub signature: Signature,
+}
+
+impl DeviceRevocation {
+    /// Revoke the link between the identity and the device with the secret key of either of them
+    pub fn new(secret_key: &CommsSecretKey, identity: CommsPublicKey, device: CommsPublicKey) -> Self {
+        let timestamp = EpochTime::now().as_u64();
+        let signature = DeviceDelegation::sign(REVOCATION_SIGNATURE, secret_key, &identity, &device, timestamp);
+        Self {
+            identity,
+            device,
+            timestamp,
+            signature,
+        }
+    }
+
+    /// Returns true if the revocation was signed by the identity or the device
+    pub fn is_valid(&self) -> bool {
+        [&self.identity, &self.device].iter().any(|signer| {
+            DeviceDelegation::verify(
+                REVOCATION_SIGNATURE,
+                &self.signature,
+                signer,
+                &self.identity,
+                &self.device,
+                self.timestamp,
+            )
+        })
+    }
+}
+
+impl TryFrom<proto::DeviceRevocation> for DeviceRevocation {
+    type Error = String;
+
+    fn try_from(revocation: proto::DeviceRevocation) -> Result<Self, Self::Error> {
+        Ok(Self {
+            identity: CommsPublicKey::from_bytes(&revocation.identity_public_key).map_err(|e| e.to_string())?,
+            device: CommsPublicKey::from_bytes(&revocation.device_public_key).map_err(|e| e.to_string())?,
+            timestamp: revocation.timestamp,
+            signature: signature_from_bytes(&revocation.public_nonce, &revocation.signature)?,
+        })
+    }
+}
+
+impl From<DeviceRevocation> for proto::DeviceRevocation {
+    fn from(revocation: DeviceRevocation) -> Self {
+        Self {
+            identity_public_key: revocation.identity.to_vec(),
+            device_public_key: revocation.device.to_vec(),
+            timestamp: revocation.timestamp,
+            public_nonce: revocation.signature.get_public_nonce().to_vec(),
+            signature: revocation.signature.get_signature().to_vec(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_is_only_valid_once_both_sides_signed() {
+        let (identity_secret_key, identity) = CommsPublicKey::random_keypair(&mut OsRng);
+        let (device_secret_key, _) = CommsPublicKey::random_keypair(&mut OsRng);
+
+        let request = DeviceDelegation::request(&device_secret_key, identity);
+        assert!(request.is_valid_request());
+        assert!(!request.is_valid());
+
+        let delegation = request.clone().approve(&identity_secret_key);
+        assert!(delegation.is_valid());
+        assert_eq!(
+            DeviceDelegation::try_from(proto::DeviceDelegation::from(delegation.clone())).unwrap(),
+            delegation
+        );
+
+        // Approving with any key other than the identity's is rejected
+        let forged = request.approve(&device_secret_key);
+        assert!(!forged.is_valid());
+
+        // A device cannot claim another identity's approval
+        let (_, other_device) = CommsPublicKey::random_keypair(&mut OsRng);
+        let moved = DeviceDelegation {
+            device: other_device,
+            ..delegation
+        };
+        assert!(!moved.is_valid_request());
+    }
+
+    #[test]
+    fn it_accepts_revocations_from_either_side_of_the_link() {
+        let (identity_secret_key, identity) = CommsPublicKey::random_keypair(&mut OsRng);
+        let (device_secret_key, device) = CommsPublicKey::random_keypair(&mut OsRng);
+        let (other_secret_key, _) = CommsPublicKey::random_keypair(&mut OsRng);
+
+        let by_identity = DeviceRevocation::new(&identity_secret_key, identity.clone(), device.clone());
+        assert!(by_identity.is_valid());
+        assert_eq!(
+            DeviceRevocation::try_from(proto::DeviceRevocation::from(by_identity.clone())).unwrap(),
+            by_identity
+        );
+        assert!(DeviceRevocation::new(&device_secret_key, identity.clone(), device.clone()).is_valid());
+        assert!(!DeviceRevocation::new(&other_secret_key, identity, device).is_valid());
+    }
+}
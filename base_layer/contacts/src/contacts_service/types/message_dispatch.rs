@@ -27,7 +27,8 @@ use tari_p2p::tari_message::TariMessageType;
 
 use crate::contacts_service::{
     proto,
-    types::{Confirmation, Message},
+    session::SESSION_VERSION,
+    types::{Confirmation, DeviceDelegation, DeviceRevocation, Message},
 };
 
 #[derive(Clone)]
@@ -35,6 +36,9 @@ pub enum MessageDispatch {
     Message(Message),
     DeliveryConfirmation(Confirmation),
     ReadConfirmation(Confirmation),
+    DeviceLink(DeviceDelegation),
+    DeviceUnlink(DeviceRevocation),
+    SessionReset,
 }
 
 impl TryFrom<proto::MessageDispatch> for MessageDispatch {
@@ -49,6 +53,16 @@ impl TryFrom<proto::MessageDispatch> for MessageDispatch {
             Some(proto::message_dispatch::Contents::ReadConfirmation(c)) => {
                 MessageDispatch::ReadConfirmation(Confirmation::from(c))
             },
+            Some(proto::message_dispatch::Contents::DeviceLink(d)) => {
+                MessageDispatch::DeviceLink(DeviceDelegation::try_from(d)?)
+            },
+            Some(proto::message_dispatch::Contents::DeviceUnlink(r)) => {
+                MessageDispatch::DeviceUnlink(DeviceRevocation::try_from(r)?)
+            },
+            Some(proto::message_dispatch::Contents::SessionReset(_)) => MessageDispatch::SessionReset,
+            Some(proto::message_dispatch::Contents::Encrypted(_)) => {
+                return Err("Encrypted chat messages have to be decrypted first".to_string())
+            },
             None => return Err("We didn't get any known type of chat message".to_string()),
         })
     }
@@ -62,10 +76,14 @@ impl From<MessageDispatch> for proto::MessageDispatch {
                 proto::message_dispatch::Contents::DeliveryConfirmation(c.into())
             },
             MessageDispatch::ReadConfirmation(c) => proto::message_dispatch::Contents::ReadConfirmation(c.into()),
+            MessageDispatch::DeviceLink(d) => proto::message_dispatch::Contents::DeviceLink(d.into()),
+            MessageDispatch::DeviceUnlink(r) => proto::message_dispatch::Contents::DeviceUnlink(r.into()),
+            MessageDispatch::SessionReset => proto::message_dispatch::Contents::SessionReset(proto::SessionReset {}),
         };
 
         Self {
             contents: Some(content),
+            session_version: SESSION_VERSION,
         }
     }
 }
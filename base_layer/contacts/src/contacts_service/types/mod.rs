@@ -34,3 +34,9 @@ pub use message_dispatch::MessageDispatch;
 
 mod confirmation;
 pub use confirmation::Confirmation;
+
+mod device_delegation;
+pub use device_delegation::{DeviceDelegation, DeviceRevocation};
+
+mod sender_list;
+pub use sender_list::SenderListType;
@@ -1,5 +1,12 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    contact_privacy_modes (node_id) {
+        node_id -> Binary,
+        privacy_mode -> Integer,
+    }
+}
+
 diesel::table! {
     contacts (address) {
         address -> Binary,
@@ -11,6 +18,29 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    device_delegations (device_public_key) {
+        device_public_key -> Binary,
+        identity_public_key -> Binary,
+        timestamp -> BigInt,
+        device_public_nonce -> Binary,
+        device_signature -> Binary,
+        identity_public_nonce -> Binary,
+        identity_signature -> Binary,
+        revoked_at -> Nullable<BigInt>,
+    }
+}
+
+diesel::table! {
+    message_requests (message_id) {
+        address -> Binary,
+        message_id -> Binary,
+        body -> Binary,
+        metadata -> Binary,
+        stored_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     messages (message_id) {
         address -> Binary,
@@ -23,3 +53,19 @@ diesel::table! {
         direction -> Integer,
     }
 }
+
+diesel::table! {
+    ratchet_sessions (public_key) {
+        public_key -> Binary,
+        state -> Binary,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    sender_lists (public_key) {
+        public_key -> Binary,
+        address -> Binary,
+        list_type -> Integer,
+    }
+}
@@ -22,23 +22,24 @@
 
 use std::{convert::TryInto, sync::Arc, time::Duration};
 
+use chacha20poly1305::XChaCha20Poly1305;
 use rand::rngs::OsRng;
 use tari_common::configuration::{MultiaddrList, Network, StringList};
 use tari_common_sqlite::connection::{DbConnection, DbConnectionUrl};
 use tari_common_types::{tari_address::TariAddress, types::PublicKey};
-use tari_comms::{peer_manager::PeerFeatures, NodeIdentity};
+use tari_comms::{peer_manager::PeerFeatures, types::CommsSecretKey, NodeIdentity};
 use tari_comms_dht::{store_forward::SafConfig, DhtConfig};
 use tari_contacts::contacts_service::{
     error::{ContactsServiceError, ContactsServiceStorageError},
     handle::{ContactsServiceHandle, DEFAULT_MESSAGE_LIMIT, MAX_MESSAGE_LIMIT},
     storage::{
         database::{ContactsBackend, ContactsDatabase, DbKey},
-        sqlite_db::ContactsServiceSqliteDatabase,
+        sqlite_db::{cipher_from_node_identity, ContactsServiceSqliteDatabase},
     },
-    types::{Contact, MessageBuilder},
+    types::{Contact, DeviceDelegation, MessageBuilder, SenderListType},
     ContactsServiceInitializer,
 };
-use tari_crypto::keys::PublicKey as PublicKeyTrait;
+use tari_crypto::keys::{PublicKey as PublicKeyTrait, SecretKey as SecretKeyTrait};
 use tari_p2p::{
     comms_connector::pubsub_connector,
     initialization::P2pInitializer,
@@ -55,6 +56,10 @@ use tari_test_utils::{comms_and_services::get_next_memory_address, paths::with_t
 use tempfile::tempdir;
 use tokio::{runtime::Runtime, sync::broadcast::error::TryRecvError};
 
+fn random_cipher() -> XChaCha20Poly1305 {
+    cipher_from_node_identity(&CommsSecretKey::random(&mut OsRng))
+}
+
 pub fn setup_contacts_service<T: ContactsBackend + 'static>(
     runtime: &mut Runtime,
     backend: T,
@@ -96,6 +101,9 @@ pub fn setup_contacts_service<T: ContactsBackend + 'static>(
         user_agent: "tari/test-contacts-service".to_string(),
         rpc_max_simultaneous_sessions: 0,
         rpc_max_sessions_per_peer: 0,
+        rpc_compression: Default::default(),
+        max_substreams_per_protocol: 0,
+        max_connections: 0,
         listener_liveness_check_interval: None,
     };
     let peer_message_subscription_factory = Arc::new(subscription_factory);
@@ -142,7 +150,7 @@ pub fn test_contacts_service() {
         let url: DbConnectionUrl = db_path.try_into().unwrap();
 
         let db = DbConnection::connect_url(&url).unwrap();
-        let backend = ContactsServiceSqliteDatabase::init(db);
+        let backend = ContactsServiceSqliteDatabase::init(db, random_cipher());
 
         let (mut contacts_service, _node_identity, _shutdown) = setup_contacts_service(&mut runtime, backend);
         let mut liveness_event_stream = contacts_service.get_contacts_liveness_event_stream();
@@ -227,7 +235,7 @@ pub fn test_message_pagination() {
         let url: DbConnectionUrl = db_path.try_into().unwrap();
 
         let db = DbConnection::connect_url(&url).unwrap();
-        let backend = ContactsServiceSqliteDatabase::init(db);
+        let backend = ContactsServiceSqliteDatabase::init(db, random_cipher());
         let contacts_db = ContactsDatabase::new(backend.clone());
 
         let (mut contacts_service, _node_identity, _shutdown) = setup_contacts_service(&mut runtime, backend);
@@ -305,3 +313,119 @@ pub fn test_message_pagination() {
         assert_eq!(0, messages.len());
     });
 }
+
+#[test]
+pub fn test_device_delegations() {
+    with_temp_dir(|dir_path| {
+        let mut runtime = Runtime::new().unwrap();
+
+        let db_name = format!("{}.sqlite3", string(8).as_str());
+        let db_path = format!("{}/{}", dir_path.to_str().unwrap(), db_name);
+        let url: DbConnectionUrl = db_path.try_into().unwrap();
+
+        let db = DbConnection::connect_url(&url).unwrap();
+        let backend = ContactsServiceSqliteDatabase::init(db, random_cipher());
+        let contacts_db = ContactsDatabase::new(backend.clone());
+
+        let (mut contacts_service, node_identity, _shutdown) = setup_contacts_service(&mut runtime, backend);
+        let identity = TariAddress::new(node_identity.public_key().clone(), Network::default());
+
+        let (device_secret_key, device_public_key) = PublicKey::random_keypair(&mut OsRng);
+        let device = TariAddress::new(device_public_key.clone(), Network::default());
+
+        // A link cannot be approved before the device requested it
+        match runtime.block_on(contacts_service.approve_device_link(device.clone())) {
+            Err(ContactsServiceError::DeviceLinkRequestNotFound) => (),
+            _ => panic!("Should have failed"),
+        }
+
+        let delegation = DeviceDelegation::request(&device_secret_key, node_identity.public_key().clone())
+            .approve(node_identity.secret_key());
+        contacts_db.upsert_device_delegation(delegation.clone()).unwrap();
+        assert_eq!(
+            contacts_db.get_device_delegation(device_public_key).unwrap(),
+            delegation
+        );
+
+        let devices = runtime.block_on(contacts_service.get_linked_devices(identity)).unwrap();
+        assert_eq!(devices, vec![delegation]);
+
+        let devices = runtime.block_on(contacts_service.get_linked_devices(device)).unwrap();
+        assert!(devices.is_empty());
+    });
+}
+
+#[test]
+pub fn test_message_requests_and_sender_lists() {
+    with_temp_dir(|dir_path| {
+        let mut runtime = Runtime::new().unwrap();
+
+        let db_name = format!("{}.sqlite3", string(8).as_str());
+        let db_path = format!("{}/{}", dir_path.to_str().unwrap(), db_name);
+        let url: DbConnectionUrl = db_path.try_into().unwrap();
+
+        let db = DbConnection::connect_url(&url).unwrap();
+        let backend = ContactsServiceSqliteDatabase::init(db, random_cipher());
+        let contacts_db = ContactsDatabase::new(backend.clone());
+
+        let (mut contacts_service, _node_identity, _shutdown) = setup_contacts_service(&mut runtime, backend);
+        runtime
+            .block_on(contacts_service.set_message_requests_enabled(true))
+            .unwrap();
+
+        let (_secret_key, public_key) = PublicKey::random_keypair(&mut OsRng);
+        let sender = TariAddress::new(public_key, Network::default());
+
+        // Nothing to accept before the sender sent anything
+        match runtime.block_on(contacts_service.accept_message_request(sender.clone())) {
+            Err(ContactsServiceError::MessageRequestNotFound) => (),
+            _ => panic!("Should have failed"),
+        }
+
+        for num in 0..3 {
+            let message = MessageBuilder::new()
+                .message(format!("Test {:?}", num))
+                .address(sender.clone())
+                .build();
+            contacts_db.save_message_request(message).unwrap();
+        }
+
+        let requests = runtime.block_on(contacts_service.get_message_requests()).unwrap();
+        assert_eq!(requests.len(), 3);
+        // Quarantined messages are not part of the conversation
+        let messages = runtime
+            .block_on(contacts_service.get_messages(sender.clone(), 10, 0))
+            .unwrap();
+        assert!(messages.is_empty());
+
+        runtime
+            .block_on(contacts_service.reject_message_request(sender.clone(), true))
+            .unwrap();
+        let requests = runtime.block_on(contacts_service.get_message_requests()).unwrap();
+        assert!(requests.is_empty());
+        let blocked = runtime
+            .block_on(contacts_service.get_sender_list(SenderListType::Blocked))
+            .unwrap();
+        assert_eq!(blocked, vec![sender.clone()]);
+
+        runtime
+            .block_on(contacts_service.set_sender_list_type(sender.clone(), Some(SenderListType::Allowed)))
+            .unwrap();
+        assert_eq!(
+            contacts_db.get_sender_list_type(sender.public_key().clone()).unwrap(),
+            Some(SenderListType::Allowed)
+        );
+        let blocked = runtime
+            .block_on(contacts_service.get_sender_list(SenderListType::Blocked))
+            .unwrap();
+        assert!(blocked.is_empty());
+
+        runtime
+            .block_on(contacts_service.set_sender_list_type(sender.clone(), None))
+            .unwrap();
+        assert_eq!(
+            contacts_db.get_sender_list_type(sender.public_key().clone()).unwrap(),
+            None
+        );
+    });
+}
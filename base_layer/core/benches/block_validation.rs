@@ -0,0 +1,116 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+#[cfg(not(feature = "benches"))]
+mod benches {
+    pub fn main() {
+        println!("Enable the `benches` feature to run benches");
+    }
+}
+
+#[cfg(feature = "benches")]
+mod benches {
+    use criterion::{black_box, criterion_group, BatchSize, Criterion};
+    use tari_core::{
+        blocks::Block,
+        test_helpers::{chain_builder::ChainBuilder, create_block, BlockSpec},
+        transactions::{
+            transaction_components::{transaction_output::batch_verify_range_proofs, TransactionOutput},
+            CryptoFactories,
+        },
+        validation::block_body::BlockBodyInternalConsistencyValidator,
+    };
+    use tokio::runtime::Runtime;
+
+    const NUM_BLOCKS: u64 = 20;
+    const NUM_TXNS: usize = 15;
+
+    pub fn block_validation_perf_test(c: &mut Criterion) {
+        let runtime = Runtime::new().unwrap();
+        let chain = runtime.block_on(ChainBuilder::new().with_blocks(NUM_BLOCKS).build());
+        let transactions = runtime
+            .block_on(chain.create_mempool_transactions(NUM_TXNS))
+            .into_iter()
+            .map(|tx| (*tx).clone())
+            .collect();
+        let (block, _) = runtime.block_on(create_block(
+            chain.rules(),
+            chain.tip().block(),
+            BlockSpec::new().with_transactions(transactions).finish(),
+            chain.key_manager(),
+        ));
+        eprintln!(
+            "Benchmarking a block with {} inputs, {} outputs and {} kernels",
+            block.body.inputs().len(),
+            block.body.outputs().len(),
+            block.body.kernels().len()
+        );
+        let factories = CryptoFactories::default();
+
+        let mut group = c.benchmark_group("block_validation");
+        let validator = BlockBodyInternalConsistencyValidator::new(chain.rules().clone(), false, factories.clone());
+        group.bench_function("internal_consistency", |b| {
+            b.iter(|| validator.validate(black_box(&block)).unwrap());
+        });
+        let validator = BlockBodyInternalConsistencyValidator::new(chain.rules().clone(), true, factories.clone());
+        group.bench_function("internal_consistency_without_range_proofs", |b| {
+            b.iter(|| validator.validate(black_box(&block)).unwrap());
+        });
+        group.bench_function("calculate_mmr_roots", |b| {
+            b.iter_batched(
+                || block.clone(),
+                |block: Block| chain.db().calculate_mmr_roots(block).unwrap(),
+                BatchSize::SmallInput,
+            );
+        });
+        group.finish();
+
+        let outputs = block.body.outputs().iter().collect::<Vec<&TransactionOutput>>();
+        let mut group = c.benchmark_group("range_proofs");
+        group.bench_function("batch_verify", |b| {
+            b.iter(|| batch_verify_range_proofs(&factories.range_proof, black_box(&outputs)).unwrap());
+        });
+        group.bench_function("individual_verify", |b| {
+            b.iter(|| {
+                for output in &outputs {
+                    output.verify_range_proof(&factories.range_proof).unwrap();
+                }
+            });
+        });
+        group.finish();
+    }
+
+    criterion_group!(
+        name = block_validation_perf;
+        config = Criterion::default().sample_size(20);
+        targets = block_validation_perf_test
+    );
+
+    pub fn main() {
+        block_validation_perf();
+        criterion::Criterion::default().configure_from_args().final_summary();
+    }
+}
+
+fn main() {
+    benches::main();
+}
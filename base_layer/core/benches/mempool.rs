@@ -31,12 +31,12 @@ mod benches {
 mod benches {
     use std::sync::Arc;
 
-    use criterion::{criterion_group, Criterion};
+    use criterion::{criterion_group, BatchSize, Criterion};
     use tari_common::configuration::Network;
     use tari_core::{
         consensus::ConsensusManager,
         mempool::{Mempool, MempoolConfig},
-        test_helpers::blockchain::create_new_blockchain,
+        test_helpers::{blockchain::create_new_blockchain, chain_builder::ChainBuilder, create_block, BlockSpec},
         transactions::{
             tari_amount::{uT, T},
             test_helpers::create_test_core_key_manager_with_memory_db,
@@ -97,10 +97,41 @@ mod benches {
         });
     }
 
+    pub fn mempool_evict_perf_test(c: &mut Criterion) {
+        const NUM_TXNS: usize = 15;
+        let runtime = Runtime::new().unwrap();
+        let chain = runtime.block_on(ChainBuilder::new().with_blocks(20).build());
+        let transactions = runtime.block_on(chain.create_mempool_transactions(NUM_TXNS));
+        let (block, _) = runtime.block_on(create_block(
+            chain.rules(),
+            chain.tip().block(),
+            BlockSpec::new()
+                .with_transactions(transactions.iter().map(|tx| (**tx).clone()).collect())
+                .finish(),
+            chain.key_manager(),
+        ));
+        let block = Arc::new(block);
+        c.bench_function("Mempool Evict Published", move |b| {
+            b.iter_batched(
+                || {
+                    let mempool = chain.create_mempool();
+                    runtime.block_on(mempool.insert_all(transactions.clone())).unwrap();
+                    mempool
+                },
+                |mempool| {
+                    runtime
+                        .block_on(mempool.process_published_block(block.clone()))
+                        .unwrap();
+                },
+                BatchSize::SmallInput,
+            );
+        });
+    }
+
     criterion_group!(
         name = mempool_perf;
         config = Criterion::default().sample_size(10);
-        targets = mempool_perf_test
+        targets = mempool_perf_test, mempool_evict_perf_test
     );
 
     pub fn main() {
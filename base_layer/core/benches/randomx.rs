@@ -0,0 +1,99 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+#[cfg(not(feature = "benches"))]
+mod benches {
+    pub fn main() {
+        println!("Enable the `benches` feature to run benches");
+    }
+}
+
+#[cfg(feature = "benches")]
+mod benches {
+    use borsh::BorshSerialize;
+    use criterion::{black_box, criterion_group, Criterion};
+    use tari_core::{
+        blocks::BlockHeader,
+        proof_of_work::{
+            monero_rx::{
+                append_merge_mining_tag,
+                construct_monero_data,
+                deserialize_monero_block_from_hex,
+                randomx_difficulty,
+                verify_header,
+                FixedByteArray,
+            },
+            randomx_factory::RandomXFactory,
+            PowAlgorithm,
+            ProofOfWork,
+        },
+    };
+    use tari_utilities::{hex::from_hex, ByteArray};
+
+    // A Monero block template and its RandomX seed hash
+    const BLOCKTEMPLATE_BLOB: &str = "0c0c8cd6a0fa057fe21d764e7abf004e975396a2160773b93712bf6118c3b4959ddd8ee0f76aad0000000002e1ea2701ffa5ea2701d5a299e2abb002028eb3066ced1b2cc82ea046f3716a48e9ae37144057d5fb48a97f941225a1957b2b0106225b7ec0a6544d8da39abe68d8bd82619b4a7c5bdae89c3783b256a8fa47820208f63aa86d2e857f070000";
+    const SEED_HASH: &str = "9f02e032f9b15d2aded991e0f68cc3c3427270b568b782e55fbd269ead0bad97";
+
+    fn get_merge_mined_header() -> BlockHeader {
+        let mut header = BlockHeader::new(0);
+        let mut block = deserialize_monero_block_from_hex(BLOCKTEMPLATE_BLOB).unwrap();
+        append_merge_mining_tag(&mut block, header.merge_mining_hash()).unwrap();
+        let seed = FixedByteArray::from_bytes(&from_hex(SEED_HASH).unwrap()).unwrap();
+        let monero_data = construct_monero_data(block, seed).unwrap();
+        header.pow = ProofOfWork {
+            pow_algo: PowAlgorithm::RandomX,
+            pow_data: monero_data.try_to_vec().unwrap(),
+        };
+        header
+    }
+
+    pub fn randomx_perf_test(c: &mut Criterion) {
+        let header = get_merge_mined_header();
+        let factory = RandomXFactory::default();
+        // Create the VM for the seed up front, as a base node does for all blocks after the first with a given seed
+        randomx_difficulty(&header, &factory).unwrap();
+
+        let mut group = c.benchmark_group("randomx");
+        group.bench_function("verify_header", |b| {
+            b.iter(|| verify_header(black_box(&header)).unwrap());
+        });
+        group.bench_function("randomx_difficulty", |b| {
+            b.iter(|| randomx_difficulty(black_box(&header), &factory).unwrap());
+        });
+        group.finish();
+    }
+
+    criterion_group!(
+        name = randomx_perf;
+        config = Criterion::default().sample_size(20);
+        targets = randomx_perf_test
+    );
+
+    pub fn main() {
+        randomx_perf();
+        criterion::Criterion::default().configure_from_args().final_summary();
+    }
+}
+
+fn main() {
+    benches::main();
+}
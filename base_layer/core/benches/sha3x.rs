@@ -0,0 +1,78 @@
+//  Copyright 2023. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+#[cfg(not(feature = "benches"))]
+mod benches {
+    pub fn main() {
+        println!("Enable the `benches` feature to run benches");
+    }
+}
+
+#[cfg(feature = "benches")]
+mod benches {
+    use criterion::{black_box, criterion_group, Criterion};
+    use tari_core::{
+        blocks::BlockHeader,
+        proof_of_work::{sha3x_difficulty, PowAlgorithm, Sha3xHasher},
+    };
+
+    fn get_header() -> BlockHeader {
+        let mut header = BlockHeader::new(0);
+        header.pow.pow_algo = PowAlgorithm::Sha3x;
+        header
+    }
+
+    pub fn sha3x_perf_test(c: &mut Criterion) {
+        let mut header = get_header();
+        c.bench_function("sha3x_difficulty", move |b| {
+            b.iter(|| {
+                header.nonce = header.nonce.wrapping_add(1);
+                black_box(sha3x_difficulty(&header).unwrap())
+            });
+        });
+
+        let header = get_header();
+        let mut hasher = Sha3xHasher::new(&header);
+        let mut nonce = 0u64;
+        c.bench_function("sha3x_hasher_difficulty", move |b| {
+            b.iter(|| {
+                nonce = nonce.wrapping_add(1);
+                black_box(hasher.difficulty(nonce).unwrap())
+            });
+        });
+    }
+
+    criterion_group!(
+        name = sha3x_perf;
+        config = Criterion::default();
+        targets = sha3x_perf_test
+    );
+
+    pub fn main() {
+        sha3x_perf();
+        criterion::Criterion::default().configure_from_args().final_summary();
+    }
+}
+
+fn main() {
+    benches::main();
+}
@@ -0,0 +1,53 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tari_common::configuration::serializers;
+
+/// Configuration for the chain split monitor
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ChainSplitMonitorConfig {
+    /// Enable chain split detection
+    pub enabled: bool,
+    /// The number of blocks that both our chain and a competing fork must grow by, after a peer was first seen on the
+    /// competing fork, before the split is reported
+    pub min_fork_depth: u64,
+    /// The minimum number of peers that must be on competing forks before a split is reported
+    pub min_competing_peers: usize,
+    /// Peers that have not advertised their chain metadata for this long are no longer considered
+    #[serde(with = "serializers::seconds")]
+    pub peer_timeout: Duration,
+}
+
+impl Default for ChainSplitMonitorConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            min_fork_depth: 10,
+            min_competing_peers: 2,
+            peer_timeout: Duration::from_secs(10 * 60),
+        }
+    }
+}
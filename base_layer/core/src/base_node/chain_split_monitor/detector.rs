@@ -0,0 +1,282 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+    time::Instant,
+};
+
+use tari_common_types::chain_metadata::ChainMetadata;
+use tari_comms::peer_manager::NodeId;
+
+use super::ChainSplitMonitorConfig;
+use crate::base_node::chain_metadata_service::PeerChainMetadata;
+
+/// A persistent split between our chain and the chain advertised by some of our peers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainSplit {
+    /// Our chain tip at the time the split was evaluated
+    pub local_chain: ChainMetadata,
+    /// The peers that are on a competing fork, strongest first
+    pub competing_peers: Vec<ForkedPeer>,
+    /// The number of blocks our chain has grown by since the first of the competing peers was seen on its fork
+    pub fork_depth: u64,
+}
+
+impl ChainSplit {
+    /// Returns true if any of the competing peers claims more accumulated difficulty than our chain
+    pub fn is_competing_fork_stronger(&self) -> bool {
+        self.competing_peers
+            .iter()
+            .any(|p| p.chain_metadata.accumulated_difficulty() > self.local_chain.accumulated_difficulty())
+    }
+}
+
+impl Display for ChainSplit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "fork_depth={} competing_peers={} local_height={} local_tip={} competing_fork_stronger={}",
+            self.fork_depth,
+            self.competing_peers.len(),
+            self.local_chain.height_of_longest_chain(),
+            self.local_chain.best_block(),
+            self.is_competing_fork_stronger()
+        )
+    }
+}
+
+/// A peer on a competing fork
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForkedPeer {
+    pub node_id: NodeId,
+    /// The chain metadata most recently advertised by the peer
+    pub chain_metadata: ChainMetadata,
+    /// The number of blocks the peer's chain has grown by since it was first seen on the competing fork
+    pub blocks_since_divergence: u64,
+}
+
+struct DivergentPeer {
+    local_height_at_divergence: u64,
+    peer_height_at_divergence: u64,
+    chain_metadata: ChainMetadata,
+    last_seen: Instant,
+}
+
+/// Tracks peers whose advertised chain tip is not part of our main chain and decides when they constitute a
+/// persistent chain split
+pub struct ChainSplitDetector {
+    config: ChainSplitMonitorConfig,
+    divergent_peers: HashMap<NodeId, DivergentPeer>,
+}
+
+impl ChainSplitDetector {
+    pub fn new(config: ChainSplitMonitorConfig) -> Self {
+        Self {
+            config,
+            divergent_peers: HashMap::new(),
+        }
+    }
+
+    /// Records the chain metadata advertised by a peer. `is_on_local_chain` must be true if the peer's best block is
+    /// part of our main chain, in which case the peer is not on a competing fork.
+    pub fn record_peer(&mut self, local: &ChainMetadata, peer: &PeerChainMetadata, is_on_local_chain: bool) {
+        if is_on_local_chain {
+            self.divergent_peers.remove(peer.node_id());
+            return;
+        }
+
+        let claimed = peer.claimed_chain_metadata();
+        let entry = self
+            .divergent_peers
+            .entry(peer.node_id().clone())
+            .or_insert_with(|| DivergentPeer {
+                local_height_at_divergence: local.height_of_longest_chain(),
+                peer_height_at_divergence: claimed.height_of_longest_chain(),
+                chain_metadata: claimed.clone(),
+                last_seen: Instant::now(),
+            });
+        entry.chain_metadata = claimed.clone();
+        entry.last_seen = Instant::now();
+    }
+
+    /// Forgets a peer, for instance because it disconnected
+    pub fn remove_peer(&mut self, node_id: &NodeId) {
+        self.divergent_peers.remove(node_id);
+    }
+
+    /// The number of peers currently seen on a competing fork, whether or not the split is persistent yet
+    pub fn num_divergent_peers(&self) -> usize {
+        self.divergent_peers.len()
+    }
+
+    /// Returns the current chain split, if the peers on competing forks and the depth of those forks meet the
+    /// configured thresholds
+    pub fn evaluate(&mut self, local: &ChainMetadata) -> Option<ChainSplit> {
+        let peer_timeout = self.config.peer_timeout;
+        self.divergent_peers
+            .retain(|_, peer| peer.last_seen.elapsed() <= peer_timeout);
+
+        let local_height = local.height_of_longest_chain();
+        let min_fork_depth = self.config.min_fork_depth;
+        let mut fork_depth = 0;
+        let mut competing_peers = self
+            .divergent_peers
+            .iter()
+            .filter_map(|(node_id, peer)| {
+                let local_growth = local_height.saturating_sub(peer.local_height_at_divergence);
+                let peer_growth = peer
+                    .chain_metadata
+                    .height_of_longest_chain()
+                    .saturating_sub(peer.peer_height_at_divergence);
+                // Both forks must have kept growing, otherwise the peer is most likely stuck or we are lagging
+                if local_growth < min_fork_depth || peer_growth < min_fork_depth {
+                    return None;
+                }
+                fork_depth = fork_depth.max(local_growth);
+                Some(ForkedPeer {
+                    node_id: node_id.clone(),
+                    chain_metadata: peer.chain_metadata.clone(),
+                    blocks_since_divergence: peer_growth,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        if competing_peers.is_empty() || competing_peers.len() < self.config.min_competing_peers {
+            return None;
+        }
+
+        competing_peers.sort_by(|a, b| {
+            b.chain_metadata
+                .accumulated_difficulty()
+                .cmp(&a.chain_metadata.accumulated_difficulty())
+        });
+        Some(ChainSplit {
+            local_chain: local.clone(),
+            competing_peers,
+            fork_depth,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::OsRng;
+    use tari_common_types::types::FixedHash;
+    use tari_comms::types::CommsPublicKey;
+    use tari_crypto::keys::PublicKey;
+
+    use super::*;
+
+    fn random_node_id() -> NodeId {
+        let (_secret_key, public_key) = CommsPublicKey::random_keypair(&mut OsRng);
+        NodeId::from_key(&public_key)
+    }
+
+    fn chain(height: u64, hash: u8) -> ChainMetadata {
+        ChainMetadata::new(height, FixedHash::from([hash; 32]), 0, 0, u128::from(height) * 10, 0)
+    }
+
+    fn detector() -> ChainSplitDetector {
+        ChainSplitDetector::new(ChainSplitMonitorConfig {
+            min_fork_depth: 5,
+            min_competing_peers: 2,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn it_detects_a_persistent_split() {
+        let mut detector = detector();
+        let (a, b) = (random_node_id(), random_node_id());
+        detector.record_peer(
+            &chain(100, 1),
+            &PeerChainMetadata::new(a.clone(), chain(100, 2), None),
+            false,
+        );
+        detector.record_peer(
+            &chain(100, 1),
+            &PeerChainMetadata::new(b.clone(), chain(99, 3), None),
+            false,
+        );
+        assert!(detector.evaluate(&chain(100, 1)).is_none());
+        assert_eq!(detector.num_divergent_peers(), 2);
+
+        detector.record_peer(&chain(105, 4), &PeerChainMetadata::new(a, chain(106, 5), None), false);
+        detector.record_peer(
+            &chain(105, 4),
+            &PeerChainMetadata::new(b.clone(), chain(104, 5), None),
+            false,
+        );
+        let split = detector.evaluate(&chain(105, 4)).unwrap();
+        assert_eq!(split.fork_depth, 5);
+        assert_eq!(split.competing_peers.len(), 2);
+        assert_eq!(split.competing_peers[0].blocks_since_divergence, 6);
+        assert!(split.is_competing_fork_stronger());
+
+        // One peer rejoining our chain resolves the split
+        detector.record_peer(&chain(106, 6), &PeerChainMetadata::new(b, chain(106, 6), None), true);
+        assert!(detector.evaluate(&chain(106, 6)).is_none());
+    }
+
+    #[test]
+    fn it_ignores_stuck_peers() {
+        let mut detector = detector();
+        let (a, b) = (random_node_id(), random_node_id());
+        for node_id in [a.clone(), b.clone()] {
+            detector.record_peer(
+                &chain(100, 1),
+                &PeerChainMetadata::new(node_id, chain(90, 2), None),
+                false,
+            );
+        }
+        for node_id in [a, b] {
+            detector.record_peer(
+                &chain(120, 3),
+                &PeerChainMetadata::new(node_id, chain(90, 2), None),
+                false,
+            );
+        }
+        assert!(detector.evaluate(&chain(120, 3)).is_none());
+    }
+
+    #[test]
+    fn it_requires_the_minimum_number_of_competing_peers() {
+        let mut detector = detector();
+        let a = random_node_id();
+        detector.record_peer(
+            &chain(100, 1),
+            &PeerChainMetadata::new(a.clone(), chain(100, 2), None),
+            false,
+        );
+        detector.record_peer(
+            &chain(110, 3),
+            &PeerChainMetadata::new(a.clone(), chain(110, 4), None),
+            false,
+        );
+        assert!(detector.evaluate(&chain(110, 3)).is_none());
+
+        detector.remove_peer(&a);
+        assert_eq!(detector.num_divergent_peers(), 0);
+    }
+}
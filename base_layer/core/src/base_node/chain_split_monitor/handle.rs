@@ -0,0 +1,62 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::sync::Arc;
+
+use tokio::sync::{broadcast, watch};
+
+use super::ChainSplit;
+
+#[derive(Debug, Clone)]
+pub enum ChainSplitEvent {
+    /// A persistent chain split was detected
+    SplitDetected(ChainSplit),
+    /// The previously detected chain split is no longer present
+    SplitResolved,
+}
+
+#[derive(Clone)]
+pub struct ChainSplitMonitorHandle {
+    event_stream: broadcast::Sender<Arc<ChainSplitEvent>>,
+    current_split: watch::Receiver<Option<ChainSplit>>,
+}
+
+impl ChainSplitMonitorHandle {
+    pub fn new(
+        event_stream: broadcast::Sender<Arc<ChainSplitEvent>>,
+        current_split: watch::Receiver<Option<ChainSplit>>,
+    ) -> Self {
+        Self {
+            event_stream,
+            current_split,
+        }
+    }
+
+    pub fn get_event_stream(&self) -> broadcast::Receiver<Arc<ChainSplitEvent>> {
+        self.event_stream.subscribe()
+    }
+
+    /// Returns the chain split that is currently detected, if any
+    pub fn current_split(&self) -> Option<ChainSplit> {
+        self.current_split.borrow().clone()
+    }
+}
@@ -0,0 +1,81 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use log::*;
+use tari_service_framework::{async_trait, ServiceInitializationError, ServiceInitializer, ServiceInitializerContext};
+use tokio::sync::{broadcast, watch};
+
+use super::{service::ChainSplitMonitorService, ChainSplitMonitorConfig, ChainSplitMonitorHandle, LOG_TARGET};
+use crate::{
+    base_node::{chain_metadata_service::ChainMetadataHandle, StateMachineHandle},
+    chain_storage::{async_db::AsyncBlockchainDb, BlockchainBackend},
+};
+
+pub struct ChainSplitMonitorInitializer<B> {
+    db: AsyncBlockchainDb<B>,
+    config: ChainSplitMonitorConfig,
+}
+
+impl<B> ChainSplitMonitorInitializer<B>
+where B: BlockchainBackend + 'static
+{
+    pub fn new(db: AsyncBlockchainDb<B>, config: ChainSplitMonitorConfig) -> Self {
+        Self { db, config }
+    }
+}
+
+#[async_trait]
+impl<B> ServiceInitializer for ChainSplitMonitorInitializer<B>
+where B: BlockchainBackend + 'static
+{
+    async fn initialize(&mut self, context: ServiceInitializerContext) -> Result<(), ServiceInitializationError> {
+        let (event_publisher, _) = broadcast::channel(10);
+        let (split_publisher, current_split) = watch::channel(None);
+        // The handle is always registered so that subscribers simply never receive events when the monitor is disabled
+        context.register_handle(ChainSplitMonitorHandle::new(event_publisher.clone(), current_split));
+
+        if !self.config.enabled {
+            debug!(target: LOG_TARGET, "Chain split monitor is disabled");
+            return Ok(());
+        }
+        debug!(target: LOG_TARGET, "Initializing Chain Split Monitor");
+
+        let db = self.db.clone();
+        let config = self.config.clone();
+        context.spawn_until_shutdown(move |handles| {
+            let chain_metadata = handles.expect_handle::<ChainMetadataHandle>();
+            let state_machine = handles.expect_handle::<StateMachineHandle>();
+            ChainSplitMonitorService::new(
+                db,
+                config,
+                chain_metadata.get_event_stream(),
+                state_machine.get_status_info_watch(),
+                event_publisher,
+                split_publisher,
+            )
+            .run()
+        });
+
+        debug!(target: LOG_TARGET, "Chain Split Monitor initialized");
+        Ok(())
+    }
+}
@@ -0,0 +1,42 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Network-wide chain split detection.
+//!
+//! The monitor compares the chain tips advertised by connected peers with the local chain. A peer whose advertised
+//! best block is not part of our main chain is on a competing fork. When enough peers stay on a competing fork while
+//! both that fork and our own chain grow by at least `min_fork_depth` blocks, the split is considered persistent and
+//! an alert is raised in the logs, the `base_node::chain_split` metrics and the [ChainSplitMonitorHandle] event
+//! stream.
+
+const LOG_TARGET: &str = "c::bn::chain_split_monitor";
+
+mod config;
+mod detector;
+mod handle;
+mod initializer;
+mod service;
+
+pub use config::ChainSplitMonitorConfig;
+pub use detector::{ChainSplit, ChainSplitDetector, ForkedPeer};
+pub use handle::{ChainSplitEvent, ChainSplitMonitorHandle};
+pub use initializer::ChainSplitMonitorInitializer;
@@ -0,0 +1,146 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{convert::TryFrom, sync::Arc};
+
+use log::*;
+use tokio::sync::{broadcast, watch};
+
+use super::{ChainSplit, ChainSplitDetector, ChainSplitEvent, ChainSplitMonitorConfig, LOG_TARGET};
+use crate::{
+    base_node::{
+        chain_metadata_service::{ChainMetadataEvent, PeerChainMetadata},
+        metrics,
+        state_machine_service::states::StatusInfo,
+    },
+    chain_storage::{async_db::AsyncBlockchainDb, BlockchainBackend, ChainStorageError},
+};
+
+pub(super) struct ChainSplitMonitorService<B> {
+    db: AsyncBlockchainDb<B>,
+    detector: ChainSplitDetector,
+    chain_metadata_events: broadcast::Receiver<Arc<ChainMetadataEvent>>,
+    status_watch: watch::Receiver<StatusInfo>,
+    event_publisher: broadcast::Sender<Arc<ChainSplitEvent>>,
+    split_publisher: watch::Sender<Option<ChainSplit>>,
+}
+
+impl<B> ChainSplitMonitorService<B>
+where B: BlockchainBackend + 'static
+{
+    pub fn new(
+        db: AsyncBlockchainDb<B>,
+        config: ChainSplitMonitorConfig,
+        chain_metadata_events: broadcast::Receiver<Arc<ChainMetadataEvent>>,
+        status_watch: watch::Receiver<StatusInfo>,
+        event_publisher: broadcast::Sender<Arc<ChainSplitEvent>>,
+        split_publisher: watch::Sender<Option<ChainSplit>>,
+    ) -> Self {
+        Self {
+            db,
+            detector: ChainSplitDetector::new(config),
+            chain_metadata_events,
+            status_watch,
+            event_publisher,
+            split_publisher,
+        }
+    }
+
+    /// Run the service
+    pub async fn run(mut self) {
+        loop {
+            match self.chain_metadata_events.recv().await {
+                Ok(event) => {
+                    if let ChainMetadataEvent::PeerChainMetadataReceived(peer) = &*event {
+                        if let Err(err) = self.handle_peer_metadata(peer).await {
+                            warn!(target: LOG_TARGET, "Failed to evaluate peer chain metadata: {}", err);
+                        }
+                    }
+                },
+                Err(broadcast::error::RecvError::Lagged(n)) => {
+                    debug!(target: LOG_TARGET, "Chain metadata subscriber lagged by {} item(s)", n);
+                },
+                Err(broadcast::error::RecvError::Closed) => {
+                    debug!(target: LOG_TARGET, "Chain metadata event stream closed");
+                    break;
+                },
+            }
+        }
+    }
+
+    async fn handle_peer_metadata(&mut self, peer: &PeerChainMetadata) -> Result<(), ChainStorageError> {
+        // While syncing, every peer ahead of us looks like it is on a competing fork
+        if !self.status_watch.borrow().state_info.is_synced() {
+            return Ok(());
+        }
+
+        let local = self.db.get_chain_metadata().await?;
+        let is_on_local_chain = self
+            .db
+            .fetch_header_by_block_hash(*peer.claimed_chain_metadata().best_block())
+            .await?
+            .is_some();
+        self.detector.record_peer(&local, peer, is_on_local_chain);
+        let split = self.detector.evaluate(&local);
+        metrics::chain_split_divergent_peers()
+            .set(i64::try_from(self.detector.num_divergent_peers()).unwrap_or(i64::MAX));
+        self.publish(split);
+        Ok(())
+    }
+
+    fn publish(&mut self, split: Option<ChainSplit>) {
+        let was_split = self.split_publisher.borrow().is_some();
+        match &split {
+            Some(split) => {
+                metrics::chain_split_detected().set(1);
+                metrics::chain_split_fork_depth().set(i64::try_from(split.fork_depth).unwrap_or(i64::MAX));
+                if !was_split {
+                    warn!(target: LOG_TARGET, "Chain split detected: {}", split);
+                    for peer in &split.competing_peers {
+                        warn!(
+                            target: LOG_TARGET,
+                            "Chain split: peer={} height={} tip={} accumulated_difficulty={} blocks_since_divergence={}",
+                            peer.node_id,
+                            peer.chain_metadata.height_of_longest_chain(),
+                            peer.chain_metadata.best_block(),
+                            peer.chain_metadata.accumulated_difficulty(),
+                            peer.blocks_since_divergence
+                        );
+                    }
+                    metrics::chain_splits_detected().inc();
+                    let _result = self
+                        .event_publisher
+                        .send(Arc::new(ChainSplitEvent::SplitDetected(split.clone())));
+                }
+            },
+            None => {
+                metrics::chain_split_detected().set(0);
+                metrics::chain_split_fork_depth().set(0);
+                if was_split {
+                    info!(target: LOG_TARGET, "Chain split resolved");
+                    let _result = self.event_publisher.send(Arc::new(ChainSplitEvent::SplitResolved));
+                }
+            },
+        }
+        let _result = self.split_publisher.send(split);
+    }
+}
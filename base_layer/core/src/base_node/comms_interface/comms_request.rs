@@ -29,7 +29,11 @@ use serde::{Deserialize, Serialize};
 use tari_common_types::types::{BlockHash, Commitment, HashOutput, PrivateKey, PublicKey, Signature};
 use tari_utilities::hex::Hex;
 
-use crate::{blocks::NewBlockTemplate, chain_storage::MmrTree, proof_of_work::PowAlgorithm};
+use crate::{
+    blocks::{NewBlockTemplate, ShortKernelId},
+    chain_storage::MmrTree,
+    proof_of_work::PowAlgorithm,
+};
 
 /// A container for the parameters required for a FetchMmrState request.
 #[derive(Debug, Serialize, Deserialize)]
@@ -46,8 +50,12 @@ pub enum NodeCommsRequest {
     FetchHeaders(RangeInclusive<u64>),
     FetchHeadersByHashes(Vec<HashOutput>),
     FetchMatchingUtxos(Vec<HashOutput>),
-    FetchMatchingBlocks { range: RangeInclusive<u64>, compact: bool },
+    FetchMatchingBlocks {
+        range: RangeInclusive<u64>,
+        compact: bool,
+    },
     FetchBlocksByKernelExcessSigs(Vec<Signature>),
+    FetchBlocksByKernelExcesses(Vec<Commitment>),
     FetchBlocksByUtxos(Vec<Commitment>),
     GetHeaderByHash(HashOutput),
     GetBlockByHash(HashOutput),
@@ -55,17 +63,42 @@ pub enum NodeCommsRequest {
     GetNewBlock(NewBlockTemplate),
     GetBlockFromAllChains(HashOutput),
     FetchKernelByExcessSig(Signature),
-    FetchMempoolTransactionsByExcessSigs { excess_sigs: Vec<PrivateKey> },
-    FetchValidatorNodesKeys { height: u64 },
-    GetShardKey { height: u64, public_key: PublicKey },
-    FetchTemplateRegistrations { start_height: u64, end_height: u64 },
-    FetchUnspentUtxosInBlock { block_hash: BlockHash },
+    FetchMempoolTransactionsByExcessSigs {
+        excess_sigs: Vec<PrivateKey>,
+    },
+    FetchMempoolTransactionsByShortKernelIds {
+        block_hash: BlockHash,
+        short_id_nonce: u64,
+        short_kernel_ids: Vec<ShortKernelId>,
+    },
+    FetchValidatorNodesKeys {
+        height: u64,
+    },
+    GetShardKey {
+        height: u64,
+        public_key: PublicKey,
+    },
+    FetchTemplateRegistrations {
+        start_height: u64,
+        end_height: u64,
+    },
+    FetchUnspentUtxosInBlock {
+        block_hash: BlockHash,
+    },
+    FetchDeploymentStatuses {
+        height: u64,
+    },
+    FetchBurntTotals {
+        heights: Vec<u64>,
+    },
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GetNewBlockTemplateRequest {
     pub algo: PowAlgorithm,
     pub max_weight: u64,
+    /// Build the template without selecting any transactions from the mempool
+    pub empty: bool,
 }
 
 impl Display for NodeCommsRequest {
@@ -83,10 +116,17 @@ impl Display for NodeCommsRequest {
                 write!(f, "FetchMatchingBlocks ({:?}, {})", range, compact)
             },
             FetchBlocksByKernelExcessSigs(v) => write!(f, "FetchBlocksByKernelExcessSigs (n={})", v.len()),
+            FetchBlocksByKernelExcesses(v) => write!(f, "FetchBlocksByKernelExcesses (n={})", v.len()),
             FetchBlocksByUtxos(v) => write!(f, "FetchBlocksByUtxos (n={})", v.len()),
             GetHeaderByHash(v) => write!(f, "GetHeaderByHash({})", v.to_hex()),
             GetBlockByHash(v) => write!(f, "GetBlockByHash({})", v.to_hex()),
-            GetNewBlockTemplate(v) => write!(f, "GetNewBlockTemplate ({}) with weight {}", v.algo, v.max_weight),
+            GetNewBlockTemplate(v) => write!(
+                f,
+                "GetNewBlockTemplate ({}) with weight {}{}",
+                v.algo,
+                v.max_weight,
+                if v.empty { " (empty)" } else { "" }
+            ),
             GetNewBlock(b) => write!(f, "GetNewBlock (Block Height={})", b.header.height),
             GetBlockFromAllChains(v) => write!(f, "GetBlockFromAllChains({})", v.to_hex()),
             FetchKernelByExcessSig(s) => write!(
@@ -98,6 +138,18 @@ impl Display for NodeCommsRequest {
             FetchMempoolTransactionsByExcessSigs { .. } => {
                 write!(f, "FetchMempoolTransactionsByExcessSigs")
             },
+            FetchMempoolTransactionsByShortKernelIds {
+                block_hash,
+                short_kernel_ids,
+                ..
+            } => {
+                write!(
+                    f,
+                    "FetchMempoolTransactionsByShortKernelIds ({}, n={})",
+                    block_hash.to_hex(),
+                    short_kernel_ids.len()
+                )
+            },
             FetchValidatorNodesKeys { height } => {
                 write!(f, "FetchValidatorNodesKeys ({})", height)
             },
@@ -113,6 +165,12 @@ impl Display for NodeCommsRequest {
             FetchUnspentUtxosInBlock { block_hash } => {
                 write!(f, "FetchUnspentUtxosInBlock ({})", block_hash)
             },
+            FetchDeploymentStatuses { height } => {
+                write!(f, "FetchDeploymentStatuses ({})", height)
+            },
+            FetchBurntTotals { heights } => {
+                write!(f, "FetchBurntTotals (n={})", heights.len())
+            },
         }
     }
 }
@@ -32,7 +32,8 @@ use tari_common_types::{
 
 use crate::{
     blocks::{Block, ChainHeader, HistoricalBlock, NewBlockTemplate},
-    chain_storage::TemplateRegistrationEntry,
+    chain_storage::{BurntTotals, TemplateRegistrationEntry},
+    consensus::deployments::DeploymentStatus,
     proof_of_work::Difficulty,
     transactions::transaction_components::{Transaction, TransactionKernel, TransactionOutput},
 };
@@ -61,6 +62,8 @@ pub enum NodeCommsResponse {
     FetchValidatorNodesKeysResponse(Vec<(PublicKey, [u8; 32])>),
     GetShardKeyResponse(Option<[u8; 32]>),
     FetchTemplateRegistrationsResponse(Vec<TemplateRegistrationEntry>),
+    DeploymentStatuses(Vec<DeploymentStatus>),
+    FetchBurntTotalsResponse(Vec<Option<BurntTotals>>),
 }
 
 impl Display for NodeCommsResponse {
@@ -98,6 +101,8 @@ impl Display for NodeCommsResponse {
             FetchValidatorNodesKeysResponse(_) => write!(f, "FetchValidatorNodesKeysResponse"),
             GetShardKeyResponse(_) => write!(f, "GetShardKeyResponse"),
             FetchTemplateRegistrationsResponse(_) => write!(f, "FetchTemplateRegistrationsResponse"),
+            DeploymentStatuses(_) => write!(f, "DeploymentStatuses"),
+            FetchBurntTotalsResponse(_) => write!(f, "FetchBurntTotalsResponse"),
         }
     }
 }
@@ -21,6 +21,7 @@
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use tari_common_types::types::FixedHash;
+use tari_comms::{connectivity::ConnectivityError, peer_manager::PeerManagerError};
 use tari_comms_dht::outbound::DhtOutboundError;
 use tari_service_framework::reply_channel::TransportChannelError;
 use thiserror::Error;
@@ -75,4 +76,8 @@ pub enum CommsInterfaceError {
     MergeMineError(#[from] MergeMineError),
     #[error("Invalid difficulty: {0}")]
     DifficultyError(#[from] DifficultyError),
+    #[error("Connectivity error: {0}")]
+    ConnectivityError(#[from] ConnectivityError),
+    #[error("Peer manager error: {0}")]
+    PeerManagerError(#[from] PeerManagerError),
 }
@@ -21,7 +21,7 @@
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     convert::{TryFrom, TryInto},
     sync::Arc,
     time::{Duration, Instant},
@@ -29,16 +29,20 @@ use std::{
 
 use log::*;
 use strum_macros::Display;
-use tari_common_types::types::{BlockHash, FixedHash, HashOutput};
+use tari_common_types::types::{BlockHash, FixedHash, HashOutput, Signature};
 use tari_comms::{connectivity::ConnectivityRequester, peer_manager::NodeId};
 use tari_utilities::hex::Hex;
-use tokio::sync::RwLock;
+use tokio::{
+    sync::{Mutex, RwLock},
+    task,
+};
 
 use crate::{
     base_node::{
         comms_interface::{
             error::CommsInterfaceError,
             local_interface::BlockEventSender,
+            orphan_parent_requests::OrphanParentRequests,
             FetchMempoolTransactionsResponse,
             NodeCommsRequest,
             NodeCommsResponse,
@@ -46,9 +50,26 @@ use crate::{
         },
         metrics,
     },
-    blocks::{Block, BlockBuilder, BlockHeader, BlockHeaderValidationError, ChainBlock, NewBlock, NewBlockTemplate},
-    chain_storage::{async_db::AsyncBlockchainDb, BlockAddResult, BlockchainBackend, ChainStorageError, PrunedOutput},
-    consensus::{ConsensusConstants, ConsensusManager},
+    blocks::{
+        Block,
+        BlockBuilder,
+        BlockHeader,
+        BlockHeaderValidationError,
+        ChainBlock,
+        NewBlock,
+        NewBlockTemplate,
+        ShortKernelId,
+        ShortKernelIdGenerator,
+    },
+    chain_storage::{
+        async_db::AsyncBlockchainDb,
+        BlockAddResult,
+        BlockchainBackend,
+        ChainStorageError,
+        Optional,
+        PrunedOutput,
+    },
+    consensus::{deployments, ConsensusConstants, ConsensusManager},
     mempool::Mempool,
     proof_of_work::{
         randomx_difficulty,
@@ -58,14 +79,20 @@ use crate::{
         PowAlgorithm,
         PowError,
     },
-    transactions::aggregated_body::AggregateBody,
+    transactions::{
+        aggregated_body::AggregateBody,
+        transaction_components::{Transaction, TransactionOutput},
+    },
     validation::{helpers, ValidationError},
 };
 
 const LOG_TARGET: &str = "c::bn::comms_interface::inbound_handler";
 const MAX_REQUEST_BY_BLOCK_HASHES: usize = 100;
 const MAX_REQUEST_BY_KERNEL_EXCESS_SIGS: usize = 100;
+const MAX_REQUEST_BY_KERNEL_EXCESSES: usize = 100;
 const MAX_REQUEST_BY_UTXO_HASHES: usize = 100;
+/// The maximum number of ancestors of an orphan block that are requested from the peer that sent it
+const MAX_ORPHAN_PARENT_DEPTH: usize = 10;
 
 /// Events that can be published on the Validated Block Event Stream
 /// Broadcast is to notify subscribers if this is a valid propagated block event
@@ -90,6 +117,7 @@ pub struct InboundNodeCommsHandlers<B> {
     mempool: Mempool,
     consensus_manager: ConsensusManager,
     list_of_reconciling_blocks: Arc<RwLock<HashSet<HashOutput>>>,
+    orphan_parent_requests: Arc<Mutex<OrphanParentRequests>>,
     outbound_nci: OutboundNodeCommsInterface,
     connectivity: ConnectivityRequester,
     randomx_factory: RandomXFactory,
@@ -114,6 +142,7 @@ where B: BlockchainBackend + 'static
             mempool,
             consensus_manager,
             list_of_reconciling_blocks: Arc::new(RwLock::new(HashSet::new())),
+            orphan_parent_requests: Arc::new(Mutex::new(OrphanParentRequests::new())),
             outbound_nci,
             connectivity,
             randomx_factory,
@@ -215,6 +244,42 @@ where B: BlockchainBackend + 'static
                 }
                 Ok(NodeCommsResponse::HistoricalBlocks(blocks))
             },
+            NodeCommsRequest::FetchBlocksByKernelExcesses(excesses) => {
+                if excesses.len() > MAX_REQUEST_BY_KERNEL_EXCESSES {
+                    return Err(CommsInterfaceError::InvalidRequest {
+                        request: "FetchBlocksByKernelExcesses",
+                        details: format!(
+                            "Exceeded maximum number of kernel excesses in request (max: {}, got:{})",
+                            MAX_REQUEST_BY_KERNEL_EXCESSES,
+                            excesses.len()
+                        ),
+                    });
+                }
+                let mut blocks = Vec::with_capacity(excesses.len());
+                for excess in excesses {
+                    let excess_hex = excess.to_hex();
+                    debug!(
+                        target: LOG_TARGET,
+                        "A peer has requested a block with kernel with excess {}", excess_hex
+                    );
+                    match self.blockchain_db.fetch_block_with_kernel_excess(excess).await {
+                        Ok(Some(block)) => blocks.push(block),
+                        Ok(None) => warn!(
+                            target: LOG_TARGET,
+                            "Could not provide requested block containing kernel with excess {} to peer because not \
+                             stored",
+                            excess_hex
+                        ),
+                        Err(e) => warn!(
+                            target: LOG_TARGET,
+                            "Could not provide requested block containing kernel with excess {} to peer because: {}",
+                            excess_hex,
+                            e.to_string()
+                        ),
+                    }
+                }
+                Ok(NodeCommsResponse::HistoricalBlocks(blocks))
+            },
             NodeCommsRequest::FetchBlocksByUtxos(commitments) => {
                 if commitments.len() > MAX_REQUEST_BY_UTXO_HASHES {
                     return Err(CommsInterfaceError::InvalidRequest {
@@ -263,6 +328,9 @@ where B: BlockchainBackend + 'static
                 let mut header = BlockHeader::from_previous(best_block_header.header());
                 let constants = self.consensus_manager.consensus_constants(header.height);
                 header.version = constants.blockchain_version();
+                if constants.is_deployment_signalling_active(header.height) {
+                    header.version |= deployments::signal_bits(constants.deployments(), header.height);
+                }
                 header.pow.pow_algo = request.algo;
 
                 let constants_weight = constants
@@ -274,17 +342,20 @@ where B: BlockchainBackend + 'static
                     request.max_weight
                 };
 
-                debug!(
-                    target: LOG_TARGET,
-                    "Fetching transactions with a maximum weight of {} for the template", asking_weight
-                );
-                let transactions = self
-                    .mempool
-                    .retrieve(asking_weight)
-                    .await?
-                    .into_iter()
-                    .map(|tx| Arc::try_unwrap(tx).unwrap_or_else(|tx| (*tx).clone()))
-                    .collect::<Vec<_>>();
+                let transactions = if request.empty {
+                    Vec::new()
+                } else {
+                    debug!(
+                        target: LOG_TARGET,
+                        "Fetching transactions with a maximum weight of {} for the template", asking_weight
+                    );
+                    self.mempool
+                        .retrieve(asking_weight)
+                        .await?
+                        .into_iter()
+                        .map(|tx| Arc::try_unwrap(tx).unwrap_or_else(|tx| (*tx).clone()))
+                        .collect::<Vec<_>>()
+                };
 
                 debug!(
                     target: LOG_TARGET,
@@ -393,6 +464,39 @@ where B: BlockchainBackend + 'static
                     },
                 ))
             },
+            NodeCommsRequest::FetchMempoolTransactionsByShortKernelIds {
+                block_hash,
+                short_id_nonce,
+                short_kernel_ids,
+            } => {
+                // Short ids are only meaningful relative to the kernels of the block they were announced for, so we
+                // resolve them to excess signatures using our copy of that block.
+                let maybe_block = match self.blockchain_db.fetch_block_by_hash(block_hash, false).await? {
+                    Some(block) => Some(block.into_block()),
+                    None => self.blockchain_db.fetch_orphan(block_hash).await.ok(),
+                };
+                let generator = ShortKernelIdGenerator::new(block_hash, short_id_nonce);
+                let wanted = short_kernel_ids.into_iter().collect::<HashSet<_>>();
+                let excess_sigs = maybe_block
+                    .map(|block| {
+                        block
+                            .body
+                            .kernels()
+                            .iter()
+                            .filter(|k| !k.is_coinbase())
+                            .map(|k| k.excess_sig.clone())
+                            .filter(|sig| wanted.contains(&generator.generate(sig.get_signature())))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let (transactions, not_found) = self.mempool.retrieve_by_excess_sigs(excess_sigs).await?;
+                Ok(NodeCommsResponse::FetchMempoolTransactionsByExcessSigsResponse(
+                    FetchMempoolTransactionsResponse {
+                        transactions,
+                        not_found,
+                    },
+                ))
+            },
             NodeCommsRequest::FetchValidatorNodesKeys { height } => {
                 let active_validator_nodes = self.blockchain_db.fetch_active_validator_nodes(height).await?;
                 Ok(NodeCommsResponse::FetchValidatorNodesKeysResponse(
@@ -415,6 +519,16 @@ where B: BlockchainBackend + 'static
                     template_registrations,
                 ))
             },
+            NodeCommsRequest::FetchDeploymentStatuses { height } => Ok(NodeCommsResponse::DeploymentStatuses(
+                self.blockchain_db.fetch_deployment_statuses(height).await?,
+            )),
+            NodeCommsRequest::FetchBurntTotals { heights } => {
+                let mut totals = Vec::with_capacity(heights.len());
+                for height in heights {
+                    totals.push(self.blockchain_db.fetch_burnt_totals(height).await?);
+                }
+                Ok(NodeCommsResponse::FetchBurntTotalsResponse(totals))
+            },
             NodeCommsRequest::FetchUnspentUtxosInBlock { block_hash } => {
                 let utxos = self.blockchain_db.fetch_outputs_in_block(block_hash).await?;
                 Ok(NodeCommsResponse::TransactionOutputs(
@@ -530,6 +644,18 @@ where B: BlockchainBackend + 'static
             );
             return Ok(true);
         }
+        if self.blockchain_db.is_block_invalidated(block).await? {
+            debug!(
+                target: LOG_TARGET,
+                "Block with hash `{}` was invalidated by this node",
+                block.to_hex()
+            );
+            return Err(CommsInterfaceError::ChainStorageError(
+                ChainStorageError::ValidationError {
+                    source: ValidationError::BlockLocallyInvalidated { hash: block.to_hex() },
+                },
+            ));
+        }
         if self.blockchain_db.bad_block_exists(block).await? {
             debug!(
                 target: LOG_TARGET,
@@ -561,15 +687,19 @@ where B: BlockchainBackend + 'static
         source_peer: NodeId,
         new_block: NewBlock,
     ) -> Result<Block, CommsInterfaceError> {
+        let is_coinbase_only = new_block.is_coinbase_only();
+        let generator = new_block.short_kernel_id_generator();
         let NewBlock {
             header,
             coinbase_kernel,
             coinbase_output,
             kernel_excess_sigs: excess_sigs,
+            short_kernel_ids,
+            ..
         } = new_block;
         // If the block is empty, we dont have to ask for the block, as we already have the full block available
         // to us.
-        if excess_sigs.is_empty() {
+        if is_coinbase_only {
             let block = BlockBuilder::new(header.version)
                 .with_coinbase_utxo(coinbase_output, coinbase_kernel)
                 .with_header(header)
@@ -592,79 +722,31 @@ where B: BlockchainBackend + 'static
                 current_meta.best_block().to_hex(),
                 source_peer,
             );
-            if excess_sigs.is_empty() {
-                let block = BlockBuilder::new(header.version)
-                    .with_coinbase_utxo(coinbase_output, coinbase_kernel)
-                    .with_header(header.clone())
-                    .build();
-                return Ok(block);
-            }
-            metrics::compact_block_tx_misses(header.height).set(excess_sigs.len() as i64);
+            metrics::compact_block_tx_misses(header.height).set(excess_sigs.len().max(short_kernel_ids.len()) as i64);
             let block = self.request_full_block_from_peer(source_peer, block_hash).await?;
             return Ok(block);
         }
 
         // We know that the block is neither and orphan or a coinbase, so lets ask our mempool for the transactions
-        let (known_transactions, missing_excess_sigs) = self.mempool.retrieve_by_excess_sigs(excess_sigs).await?;
-        let known_transactions = known_transactions.into_iter().map(|tx| (*tx).clone()).collect();
-
-        metrics::compact_block_tx_misses(header.height).set(missing_excess_sigs.len() as i64);
-
-        let mut builder = BlockBuilder::new(header.version)
-            .with_coinbase_utxo(coinbase_output, coinbase_kernel)
-            .with_transactions(known_transactions);
-
-        if missing_excess_sigs.is_empty() {
-            debug!(
-                target: LOG_TARGET,
-                "All transactions for block #{} ({}) found in mempool",
-                header.height,
-                block_hash.to_hex()
-            );
+        let transactions = if short_kernel_ids.is_empty() {
+            self.fetch_transactions_by_excess_sigs(&source_peer, &header, excess_sigs)
+                .await?
         } else {
-            debug!(
-                target: LOG_TARGET,
-                "Requesting {} unknown transaction(s) from peer '{}'.",
-                missing_excess_sigs.len(),
-                source_peer
-            );
-
-            let FetchMempoolTransactionsResponse {
-                transactions,
-                not_found,
-            } = self
-                .outbound_nci
-                .request_transactions_by_excess_sig(source_peer.clone(), missing_excess_sigs)
-                .await?;
-
-            // Add returned transactions to unconfirmed pool
-            if !transactions.is_empty() {
-                self.mempool.insert_all(transactions.clone()).await?;
-            }
-
-            if !not_found.is_empty() {
-                warn!(
-                    target: LOG_TARGET,
-                    "Peer {} was not able to return all transactions for block #{} ({}). {} transaction(s) not found. \
-                     Requesting full block.",
-                    source_peer,
-                    header.height,
-                    block_hash.to_hex(),
-                    not_found.len()
-                );
-
+            self.fetch_transactions_by_short_kernel_ids(&source_peer, &header, generator, short_kernel_ids)
+                .await?
+        };
+        let transactions = match transactions {
+            Some(transactions) => transactions,
+            None => {
                 metrics::compact_block_full_misses(header.height).inc();
                 let block = self.request_full_block_from_peer(source_peer, block_hash).await?;
                 return Ok(block);
-            }
+            },
+        };
 
-            builder = builder.with_transactions(
-                transactions
-                    .into_iter()
-                    .map(|tx| Arc::try_unwrap(tx).unwrap_or_else(|tx| (*tx).clone()))
-                    .collect(),
-            );
-        }
+        let mut builder = BlockBuilder::new(header.version)
+            .with_coinbase_utxo(coinbase_output, coinbase_kernel)
+            .with_transactions(transactions);
 
         // NB: Add the header last because `with_transactions` etc updates the current header, but we have the final one
         // already
@@ -698,6 +780,157 @@ where B: BlockchainBackend + 'static
         Ok(block)
     }
 
+    /// Collects the transactions for a compact block announced with full excess signatures, requesting any that are
+    /// missing from our mempool from the source peer. Returns `None` if the peer could not provide all of them.
+    async fn fetch_transactions_by_excess_sigs(
+        &mut self,
+        source_peer: &NodeId,
+        header: &BlockHeader,
+        excess_sigs: Vec<Signature>,
+    ) -> Result<Option<Vec<Transaction>>, CommsInterfaceError> {
+        let (known_transactions, missing_excess_sigs) = self.mempool.retrieve_by_excess_sigs(excess_sigs).await?;
+        let mut transactions = known_transactions
+            .into_iter()
+            .map(|tx| (*tx).clone())
+            .collect::<Vec<_>>();
+
+        metrics::compact_block_tx_misses(header.height).set(missing_excess_sigs.len() as i64);
+
+        if missing_excess_sigs.is_empty() {
+            debug!(
+                target: LOG_TARGET,
+                "All transactions for block #{} ({}) found in mempool",
+                header.height,
+                header.hash().to_hex()
+            );
+            return Ok(Some(transactions));
+        }
+
+        debug!(
+            target: LOG_TARGET,
+            "Requesting {} unknown transaction(s) from peer '{}'.",
+            missing_excess_sigs.len(),
+            source_peer
+        );
+
+        let FetchMempoolTransactionsResponse {
+            transactions: fetched,
+            not_found,
+        } = self
+            .outbound_nci
+            .request_transactions_by_excess_sig(source_peer.clone(), missing_excess_sigs)
+            .await?;
+
+        // Add returned transactions to unconfirmed pool
+        if !fetched.is_empty() {
+            self.mempool.insert_all(fetched.clone()).await?;
+        }
+
+        if !not_found.is_empty() {
+            warn!(
+                target: LOG_TARGET,
+                "Peer {} was not able to return all transactions for block #{} ({}). {} transaction(s) not found. \
+                 Requesting full block.",
+                source_peer,
+                header.height,
+                header.hash().to_hex(),
+                not_found.len()
+            );
+            return Ok(None);
+        }
+
+        transactions.extend(
+            fetched
+                .into_iter()
+                .map(|tx| Arc::try_unwrap(tx).unwrap_or_else(|tx| (*tx).clone())),
+        );
+        Ok(Some(transactions))
+    }
+
+    /// Collects the transactions for a compact block announced with short kernel ids, requesting any that are missing
+    /// from our mempool from the source peer. Returns `None` if the peer could not provide a transaction for every
+    /// missing id.
+    async fn fetch_transactions_by_short_kernel_ids(
+        &mut self,
+        source_peer: &NodeId,
+        header: &BlockHeader,
+        generator: ShortKernelIdGenerator,
+        short_kernel_ids: Vec<ShortKernelId>,
+    ) -> Result<Option<Vec<Transaction>>, CommsInterfaceError> {
+        let (known_transactions, missing_ids) = self
+            .mempool
+            .retrieve_by_short_kernel_ids(generator, short_kernel_ids)
+            .await?;
+        let mut transactions = known_transactions
+            .into_iter()
+            .map(|tx| (*tx).clone())
+            .collect::<Vec<_>>();
+
+        metrics::compact_block_tx_misses(header.height).set(missing_ids.len() as i64);
+
+        if missing_ids.is_empty() {
+            debug!(
+                target: LOG_TARGET,
+                "All transactions for block #{} ({}) found in mempool",
+                header.height,
+                header.hash().to_hex()
+            );
+            return Ok(Some(transactions));
+        }
+
+        debug!(
+            target: LOG_TARGET,
+            "Requesting {} unknown transaction(s) by short id from peer '{}'.",
+            missing_ids.len(),
+            source_peer
+        );
+
+        let FetchMempoolTransactionsResponse {
+            transactions: fetched, ..
+        } = self
+            .outbound_nci
+            .request_transactions_by_short_kernel_ids(
+                source_peer.clone(),
+                header.hash(),
+                generator.nonce(),
+                missing_ids.clone(),
+            )
+            .await?;
+
+        // Add returned transactions to unconfirmed pool
+        if !fetched.is_empty() {
+            self.mempool.insert_all(fetched.clone()).await?;
+        }
+
+        // The peer cannot tell us which ids it failed to resolve, so check that every missing id is covered by a kernel
+        // of the returned transactions.
+        let returned_ids = fetched
+            .iter()
+            .flat_map(|tx| tx.body.kernels().iter())
+            .map(|k| generator.generate(k.excess_sig.get_signature()))
+            .collect::<HashSet<_>>();
+        let num_not_found = missing_ids.iter().filter(|id| !returned_ids.contains(id)).count();
+        if num_not_found > 0 {
+            warn!(
+                target: LOG_TARGET,
+                "Peer {} was not able to return all transactions for block #{} ({}). {} transaction(s) not found. \
+                 Requesting full block.",
+                source_peer,
+                header.height,
+                header.hash().to_hex(),
+                num_not_found
+            );
+            return Ok(None);
+        }
+
+        transactions.extend(
+            fetched
+                .into_iter()
+                .map(|tx| Arc::try_unwrap(tx).unwrap_or_else(|tx| (*tx).clone())),
+        );
+        Ok(Some(transactions))
+    }
+
     async fn request_full_block_from_peer(
         &mut self,
         source_peer: NodeId,
@@ -775,7 +1008,8 @@ where B: BlockchainBackend + 'static
         );
         debug!(target: LOG_TARGET, "Incoming block: {}", block);
         let timer = Instant::now();
-        let block = self.hydrate_block(block).await?;
+        let fork_outputs = self.fetch_fork_outputs(block.header.prev_hash).await?;
+        let block = self.hydrate_block(block, &fork_outputs).await?;
 
         let add_block_result = self.blockchain_db.add_block(block.clone()).await;
         // Create block event on block event stream
@@ -797,9 +1031,18 @@ where B: BlockchainBackend + 'static
                     BlockAddResult::ChainReorg { .. } => true,
                 };
 
+                let is_orphan = matches!(block_add_result, BlockAddResult::OrphanBlock);
                 self.update_block_result_metrics(&block_add_result).await?;
                 self.publish_block_event(BlockEvent::ValidBlockAdded(block.clone(), block_add_result));
 
+                if is_orphan {
+                    if let Some(peer) = source_peer.as_ref() {
+                        self.spawn_orphan_parent_request(block.header.prev_hash, peer.clone());
+                    }
+                } else {
+                    self.orphan_parent_requests.lock().await.complete(&block_hash);
+                }
+
                 if should_propagate {
                     debug!(
                         target: LOG_TARGET,
@@ -850,7 +1093,121 @@ where B: BlockchainBackend + 'static
         }
     }
 
-    async fn hydrate_block(&mut self, block: Block) -> Result<Arc<Block>, CommsInterfaceError> {
+    /// Requests the missing parent of an orphan block, and its missing ancestors up to `MAX_ORPHAN_PARENT_DEPTH`, from
+    /// the peer that sent the orphan
+    fn spawn_orphan_parent_request(&self, parent: BlockHash, peer: NodeId) {
+        let mut handler = self.clone();
+        task::spawn(async move {
+            if let Err(e) = handler.request_orphan_parents(parent, peer.clone()).await {
+                debug!(
+                    target: LOG_TARGET,
+                    "Failed to fetch orphan parent `{}` from peer `{}`: {}",
+                    parent.to_hex(),
+                    peer,
+                    e
+                );
+            }
+        });
+    }
+
+    async fn request_orphan_parents(&mut self, mut parent: BlockHash, peer: NodeId) -> Result<(), CommsInterfaceError> {
+        // The parents of a fork block usually spend outputs created further down the fork, so all of them are fetched
+        // before any is hydrated and they are then added oldest first.
+        let mut parents = Vec::new();
+        for _ in 0..MAX_ORPHAN_PARENT_DEPTH {
+            if self.blockchain_db.block_exists(parent).await? {
+                self.orphan_parent_requests.lock().await.complete(&parent);
+                break;
+            }
+            {
+                let mut requests = self.orphan_parent_requests.lock().await;
+                let is_scheduled = requests.try_schedule(parent, &peer, Instant::now());
+                metrics::pending_orphan_parent_requests().set(i64::try_from(requests.len()).unwrap_or(i64::MAX));
+                if !is_scheduled {
+                    debug!(
+                        target: LOG_TARGET,
+                        "Orphan parent `{}` was recently requested from {} peer(s), backing off",
+                        parent.to_hex(),
+                        requests.requested_from(&parent).map(HashSet::len).unwrap_or_default()
+                    );
+                    break;
+                }
+            }
+
+            metrics::orphan_parent_requests().inc();
+            let block = match self
+                .outbound_nci
+                .request_blocks_by_hashes_from_peer(parent, Some(peer.clone()))
+                .await?
+            {
+                Some(block) if block.hash() == parent => block,
+                Some(_) => {
+                    return Err(CommsInterfaceError::InvalidPeerResponse(format!(
+                        "Peer `{}` returned a different block to the orphan parent `{}` that was requested",
+                        peer,
+                        parent.to_hex()
+                    )));
+                },
+                None => {
+                    debug!(
+                        target: LOG_TARGET,
+                        "Peer `{}` does not have orphan parent `{}`",
+                        peer,
+                        parent.to_hex()
+                    );
+                    break;
+                },
+            };
+            self.orphan_parent_requests.lock().await.complete(&parent);
+
+            parent = block.header.prev_hash;
+            parents.push(block);
+        }
+
+        let mut fork_outputs = self.fetch_fork_outputs(parent).await?;
+        for block in parents.into_iter().rev() {
+            let block = self.hydrate_block(block, &fork_outputs).await?;
+            fork_outputs.extend(
+                block
+                    .body
+                    .outputs()
+                    .iter()
+                    .map(|output| (output.hash(), output.clone())),
+            );
+            let block_add_result = self.blockchain_db.add_block(block.clone()).await?;
+            self.update_block_result_metrics(&block_add_result).await?;
+            self.publish_block_event(BlockEvent::ValidBlockAdded(block, block_add_result));
+        }
+        Ok(())
+    }
+
+    /// Collects the outputs of the stored orphans between `hash` and the main chain, so that blocks on that fork
+    /// which spend them can be hydrated.
+    async fn fetch_fork_outputs(
+        &self,
+        mut hash: BlockHash,
+    ) -> Result<HashMap<HashOutput, TransactionOutput>, CommsInterfaceError> {
+        let mut outputs = HashMap::new();
+        for _ in 0..MAX_ORPHAN_PARENT_DEPTH {
+            if self.blockchain_db.fetch_header_by_block_hash(hash).await?.is_some() {
+                break;
+            }
+            let orphan = match self.blockchain_db.fetch_orphan(hash).await.optional()? {
+                Some(orphan) => orphan,
+                None => break,
+            };
+            hash = orphan.header.prev_hash;
+            let (_, _, orphan_outputs, _) = orphan.dissolve();
+            outputs.extend(orphan_outputs.into_iter().map(|output| (output.hash(), output)));
+        }
+        Ok(outputs)
+    }
+
+    async fn hydrate_block(
+        &mut self,
+        block: Block,
+        fork_outputs: &HashMap<HashOutput, TransactionOutput>,
+    ) -> Result<Arc<Block>, CommsInterfaceError> {
         let block_hash = block.hash();
         let block_height = block.header.height;
         if block.body.inputs().is_empty() {
@@ -872,39 +1229,40 @@ where B: BlockchainBackend + 'static
                 continue;
             }
 
-            let output_mined_info =
-                db.fetch_output(&input.output_hash())?
-                    .ok_or_else(|| CommsInterfaceError::InvalidFullBlock {
+            let output = match db.fetch_output(&input.output_hash())? {
+                Some(output_mined_info) => match output_mined_info.output {
+                    PrunedOutput::Pruned { .. } => {
+                        return Err(CommsInterfaceError::InvalidFullBlock {
+                            hash: block_hash,
+                            details: format!("Output {} to be spent is pruned", input.output_hash()),
+                        });
+                    },
+                    PrunedOutput::NotPruned { output } => output,
+                },
+                // The output may have been created by a block on the same fork that is not in the main chain
+                None => fork_outputs.get(&input.output_hash()).cloned().ok_or_else(|| {
+                    CommsInterfaceError::InvalidFullBlock {
                         hash: block_hash,
                         details: format!("Output {} to be spent does not exist in db", input.output_hash()),
-                    })?;
-
-            match output_mined_info.output {
-                PrunedOutput::Pruned { .. } => {
-                    return Err(CommsInterfaceError::InvalidFullBlock {
-                        hash: block_hash,
-                        details: format!("Output {} to be spent is pruned", input.output_hash()),
-                    });
-                },
-                PrunedOutput::NotPruned { output } => {
-                    let rp_hash = match output.proof {
-                        Some(proof) => proof.hash(),
-                        None => FixedHash::zero(),
-                    };
-                    input.add_output_data(
-                        output.version,
-                        output.features,
-                        output.commitment,
-                        output.script,
-                        output.sender_offset_public_key,
-                        output.covenant,
-                        output.encrypted_data,
-                        output.metadata_signature,
-                        rp_hash,
-                        output.minimum_value_promise,
-                    );
-                },
-            }
+                    }
+                })?,
+            };
+            let rp_hash = match output.proof {
+                Some(proof) => proof.hash(),
+                None => FixedHash::zero(),
+            };
+            input.add_output_data(
+                output.version,
+                output.features,
+                output.commitment,
+                output.script,
+                output.sender_offset_public_key,
+                output.covenant,
+                output.encrypted_data,
+                output.metadata_signature,
+                rp_hash,
+                output.minimum_value_promise,
+            );
         }
         debug!(
             target: LOG_TARGET,
@@ -960,6 +1318,8 @@ where B: BlockchainBackend + 'static
             },
             BlockAddResult::OrphanBlock => {
                 metrics::orphaned_blocks().inc();
+                let orphan_count = self.blockchain_db.orphan_count().await?;
+                metrics::orphan_pool_size().set(i64::try_from(orphan_count).unwrap_or(i64::MAX));
             },
             _ => {},
         }
@@ -994,6 +1354,7 @@ impl<B> Clone for InboundNodeCommsHandlers<B> {
             mempool: self.mempool.clone(),
             consensus_manager: self.consensus_manager.clone(),
             list_of_reconciling_blocks: self.list_of_reconciling_blocks.clone(),
+            orphan_parent_requests: self.orphan_parent_requests.clone(),
             outbound_nci: self.outbound_nci.clone(),
             connectivity: self.connectivity.clone(),
             randomx_factory: self.randomx_factory.clone(),
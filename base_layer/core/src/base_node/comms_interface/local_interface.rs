@@ -38,7 +38,8 @@ use crate::{
         NodeCommsResponse,
     },
     blocks::{Block, ChainHeader, HistoricalBlock, NewBlockTemplate},
-    chain_storage::TemplateRegistrationEntry,
+    chain_storage::{BurntTotals, TemplateRegistrationEntry},
+    consensus::deployments::DeploymentStatus,
     proof_of_work::PowAlgorithm,
     transactions::transaction_components::{TransactionKernel, TransactionOutput},
 };
@@ -150,7 +151,29 @@ impl LocalNodeCommsInterface {
         let request = GetNewBlockTemplateRequest {
             algo: pow_algorithm,
             max_weight,
+            empty: false,
         };
+        self.request_new_block_template(request).await
+    }
+
+    /// Request the construction of a new mineable block template that contains no transactions. This skips the
+    /// mempool selection, so it is the quickest template that can be mined on a new tip.
+    pub async fn get_new_empty_block_template(
+        &mut self,
+        pow_algorithm: PowAlgorithm,
+    ) -> Result<NewBlockTemplate, CommsInterfaceError> {
+        let request = GetNewBlockTemplateRequest {
+            algo: pow_algorithm,
+            max_weight: 0,
+            empty: true,
+        };
+        self.request_new_block_template(request).await
+    }
+
+    async fn request_new_block_template(
+        &mut self,
+        request: GetNewBlockTemplateRequest,
+    ) -> Result<NewBlockTemplate, CommsInterfaceError> {
         match self
             .request_sender
             .call(NodeCommsRequest::GetNewBlockTemplate(request))
@@ -239,6 +262,21 @@ impl LocalNodeCommsInterface {
         }
     }
 
+    /// Fetches the blocks with the specified kernel excesses
+    pub async fn get_blocks_with_kernel_excesses(
+        &mut self,
+        excesses: Vec<Commitment>,
+    ) -> Result<Vec<HistoricalBlock>, CommsInterfaceError> {
+        match self
+            .request_sender
+            .call(NodeCommsRequest::FetchBlocksByKernelExcesses(excesses))
+            .await??
+        {
+            NodeCommsResponse::HistoricalBlocks(blocks) => Ok(blocks),
+            _ => Err(CommsInterfaceError::UnexpectedApiResponse),
+        }
+    }
+
     /// Return header matching the given hash. If the header cannot be found `Ok(None)` is returned.
     pub async fn get_header_by_hash(&mut self, hash: HashOutput) -> Result<Option<ChainHeader>, CommsInterfaceError> {
         match self
@@ -328,6 +366,33 @@ impl LocalNodeCommsInterface {
         }
     }
 
+    /// Fetches the status of the deployments defined for `height`
+    pub async fn get_deployment_statuses(&mut self, height: u64) -> Result<Vec<DeploymentStatus>, CommsInterfaceError> {
+        match self
+            .request_sender
+            .call(NodeCommsRequest::FetchDeploymentStatuses { height })
+            .await??
+        {
+            NodeCommsResponse::DeploymentStatuses(statuses) => Ok(statuses),
+            _ => Err(CommsInterfaceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Fetches the running burnt output totals at each of `heights`, None for a height where they are not known
+    pub async fn get_burnt_totals(
+        &mut self,
+        heights: Vec<u64>,
+    ) -> Result<Vec<Option<BurntTotals>>, CommsInterfaceError> {
+        match self
+            .request_sender
+            .call(NodeCommsRequest::FetchBurntTotals { heights })
+            .await??
+        {
+            NodeCommsResponse::FetchBurntTotalsResponse(totals) => Ok(totals),
+            _ => Err(CommsInterfaceError::UnexpectedApiResponse),
+        }
+    }
+
     /// Fetches UTXOs that are not spent for the given block hash up to the current chain tip.
     pub async fn fetch_unspent_utxos_in_block(
         &mut self,
@@ -0,0 +1,139 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    cmp,
+    collections::{HashMap, HashSet},
+    iter,
+    time::{Duration, Instant},
+};
+
+use tari_common_types::types::BlockHash;
+use tari_comms::peer_manager::NodeId;
+
+const INITIAL_BACKOFF: Duration = Duration::from_secs(2);
+const MAX_BACKOFF: Duration = Duration::from_secs(5 * 60);
+/// The maximum number of missing parents that are tracked at once
+const MAX_PENDING_REQUESTS: usize = 128;
+
+#[derive(Debug)]
+struct ParentRequest {
+    requested_from: HashSet<NodeId>,
+    attempts: u32,
+    next_attempt: Instant,
+}
+
+/// Tracks the parents of orphan blocks that have been requested from peers. Repeated requests for the same parent are
+/// delayed with exponential backoff so that a long fork does not result in a flood of requests.
+#[derive(Debug, Default)]
+pub(crate) struct OrphanParentRequests {
+    pending: HashMap<BlockHash, ParentRequest>,
+}
+
+impl OrphanParentRequests {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns true and records the request if `parent` may be requested from `peer` now, otherwise returns false
+    pub fn try_schedule(&mut self, parent: BlockHash, peer: &NodeId, now: Instant) -> bool {
+        if let Some(request) = self.pending.get_mut(&parent) {
+            if now < request.next_attempt {
+                return false;
+            }
+            request.attempts += 1;
+            request.next_attempt = now + backoff(request.attempts);
+            request.requested_from.insert(peer.clone());
+            return true;
+        }
+
+        if self.pending.len() >= MAX_PENDING_REQUESTS {
+            self.prune_expired(now);
+            if self.pending.len() >= MAX_PENDING_REQUESTS {
+                return false;
+            }
+        }
+        self.pending.insert(parent, ParentRequest {
+            requested_from: iter::once(peer.clone()).collect(),
+            attempts: 1,
+            next_attempt: now + backoff(1),
+        });
+        true
+    }
+
+    /// Stops tracking `parent`, e.g. once it has been received
+    pub fn complete(&mut self, parent: &BlockHash) {
+        self.pending.remove(parent);
+    }
+
+    /// The peers that `parent` has been requested from
+    pub fn requested_from(&self, parent: &BlockHash) -> Option<&HashSet<NodeId>> {
+        self.pending.get(parent).map(|request| &request.requested_from)
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Forgets requests that have not been retried for longer than the maximum backoff
+    fn prune_expired(&mut self, now: Instant) {
+        self.pending
+            .retain(|_, request| now.saturating_duration_since(request.next_attempt) < MAX_BACKOFF);
+    }
+}
+
+fn backoff(attempts: u32) -> Duration {
+    let factor = 2u32.saturating_pow(attempts.saturating_sub(1));
+    cmp::min(INITIAL_BACKOFF.saturating_mul(factor), MAX_BACKOFF)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_backs_off_exponentially() {
+        let mut requests = OrphanParentRequests::new();
+        let parent = BlockHash::from([1u8; 32]);
+        let peer = NodeId::default();
+        let now = Instant::now();
+
+        assert!(requests.try_schedule(parent, &peer, now));
+        assert!(!requests.try_schedule(parent, &peer, now + Duration::from_secs(1)));
+        assert!(requests.try_schedule(parent, &peer, now + Duration::from_secs(2)));
+        // The second attempt doubles the backoff
+        assert!(!requests.try_schedule(parent, &peer, now + Duration::from_secs(5)));
+        assert!(requests.try_schedule(parent, &peer, now + Duration::from_secs(6)));
+        assert_eq!(requests.requested_from(&parent).unwrap().len(), 1);
+
+        requests.complete(&parent);
+        assert_eq!(requests.len(), 0);
+        assert!(requests.try_schedule(parent, &peer, now + Duration::from_secs(6)));
+    }
+
+    #[test]
+    fn it_caps_the_backoff() {
+        assert_eq!(backoff(1), INITIAL_BACKOFF);
+        assert_eq!(backoff(3), INITIAL_BACKOFF * 4);
+        assert_eq!(backoff(100), MAX_BACKOFF);
+    }
+}
@@ -32,7 +32,7 @@ use crate::{
         NodeCommsRequest,
         NodeCommsResponse,
     },
-    blocks::{Block, NewBlock},
+    blocks::{Block, NewBlock, ShortKernelId},
 };
 
 /// The OutboundNodeCommsInterface provides an interface to request information from remove nodes.
@@ -94,6 +94,33 @@ impl OutboundNodeCommsInterface {
         }
     }
 
+    /// Fetch the transactions corresponding to the short kernel ids of a block announcement from the given peer
+    /// `NodeId`.
+    pub async fn request_transactions_by_short_kernel_ids(
+        &mut self,
+        node_id: NodeId,
+        block_hash: BlockHash,
+        short_id_nonce: u64,
+        short_kernel_ids: Vec<ShortKernelId>,
+    ) -> Result<FetchMempoolTransactionsResponse, CommsInterfaceError> {
+        if let NodeCommsResponse::FetchMempoolTransactionsByExcessSigsResponse(resp) = self
+            .request_sender
+            .call((
+                NodeCommsRequest::FetchMempoolTransactionsByShortKernelIds {
+                    block_hash,
+                    short_id_nonce,
+                    short_kernel_ids,
+                },
+                Some(node_id),
+            ))
+            .await??
+        {
+            Ok(resp)
+        } else {
+            Err(CommsInterfaceError::UnexpectedApiResponse)
+        }
+    }
+
     /// Transmit a block to remote base nodes, excluding the provided peers.
     pub async fn propagate_block(
         &self,
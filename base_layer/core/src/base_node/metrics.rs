@@ -174,3 +174,123 @@ pub fn utxo_set_size() -> &'static IntGauge {
 
     &METER
 }
+
+pub fn orphan_pool_size() -> &'static IntGauge {
+    static METER: Lazy<IntGauge> = Lazy::new(|| {
+        tari_metrics::register_int_gauge(
+            "base_node::blockchain::orphan_pool_size",
+            "The number of blocks in the orphan pool",
+        )
+        .unwrap()
+    });
+
+    &METER
+}
+
+pub fn orphan_parent_requests() -> IntCounter {
+    static METER: Lazy<IntCounter> = Lazy::new(|| {
+        tari_metrics::register_int_counter(
+            "base_node::blockchain::orphan_parent_requests",
+            "Number of orphan block parents requested from peers",
+        )
+        .unwrap()
+    });
+
+    METER.clone()
+}
+
+pub fn pending_orphan_parent_requests() -> &'static IntGauge {
+    static METER: Lazy<IntGauge> = Lazy::new(|| {
+        tari_metrics::register_int_gauge(
+            "base_node::blockchain::pending_orphan_parent_requests",
+            "The number of orphan block parents that are being requested from peers",
+        )
+        .unwrap()
+    });
+
+    &METER
+}
+
+pub fn pruned_height() -> &'static IntGauge {
+    static METER: Lazy<IntGauge> = Lazy::new(|| {
+        tari_metrics::register_int_gauge(
+            "base_node::blockchain::pruned_height",
+            "The height up to which the blockchain database is pruned",
+        )
+        .unwrap()
+    });
+
+    &METER
+}
+
+pub fn pruning_backlog() -> &'static IntGauge {
+    static METER: Lazy<IntGauge> = Lazy::new(|| {
+        tari_metrics::register_int_gauge(
+            "base_node::blockchain::pruning_backlog",
+            "The number of blocks that still need to be pruned to reach the pruning horizon",
+        )
+        .unwrap()
+    });
+
+    &METER
+}
+
+pub fn pruned_blocks() -> IntCounter {
+    static METER: Lazy<IntCounter> = Lazy::new(|| {
+        tari_metrics::register_int_counter(
+            "base_node::blockchain::pruned_blocks",
+            "Number of blocks pruned by the background pruning service",
+        )
+        .unwrap()
+    });
+
+    METER.clone()
+}
+
+pub fn chain_split_detected() -> &'static IntGauge {
+    static METER: Lazy<IntGauge> = Lazy::new(|| {
+        tari_metrics::register_int_gauge(
+            "base_node::chain_split::detected",
+            "Set to 1 while a persistent chain split between this node and some of its peers is detected",
+        )
+        .unwrap()
+    });
+
+    &METER
+}
+
+pub fn chain_split_fork_depth() -> &'static IntGauge {
+    static METER: Lazy<IntGauge> = Lazy::new(|| {
+        tari_metrics::register_int_gauge(
+            "base_node::chain_split::fork_depth",
+            "The number of blocks the local chain has grown by since the detected chain split started",
+        )
+        .unwrap()
+    });
+
+    &METER
+}
+
+pub fn chain_split_divergent_peers() -> &'static IntGauge {
+    static METER: Lazy<IntGauge> = Lazy::new(|| {
+        tari_metrics::register_int_gauge(
+            "base_node::chain_split::divergent_peers",
+            "The number of peers advertising a chain tip that is not part of the local chain",
+        )
+        .unwrap()
+    });
+
+    &METER
+}
+
+pub fn chain_splits_detected() -> IntCounter {
+    static METER: Lazy<IntCounter> = Lazy::new(|| {
+        tari_metrics::register_int_counter(
+            "base_node::chain_split::splits_detected",
+            "Number of persistent chain splits detected",
+        )
+        .unwrap()
+    });
+
+    METER.clone()
+}
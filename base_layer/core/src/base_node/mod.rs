@@ -35,6 +35,9 @@
 #[cfg(feature = "base_node")]
 pub mod chain_metadata_service;
 
+#[cfg(feature = "base_node")]
+pub mod chain_split_monitor;
+
 #[cfg(feature = "base_node")]
 pub mod comms_interface;
 #[cfg(feature = "base_node")]
@@ -42,6 +45,9 @@ pub use comms_interface::LocalNodeCommsInterface;
 #[cfg(feature = "base_node")]
 mod metrics;
 
+#[cfg(feature = "base_node")]
+pub mod pruning_service;
+
 #[cfg(feature = "base_node")]
 pub mod service;
 
@@ -57,6 +63,7 @@ pub mod sync;
 pub use sync::{
     rpc::{create_base_node_sync_rpc_service, BaseNodeSyncService},
     BlockchainSyncConfig,
+    SyncRpcBudgetConfig,
     SyncValidators,
 };
 
@@ -27,6 +27,7 @@ use tari_utilities::ByteArray;
 
 use crate::{
     base_node::comms_interface::NodeCommsRequest,
+    blocks::ShortKernelId,
     proto::{base_node as proto, base_node::base_node_service_request::Request as ProtoNodeCommsRequest},
 };
 
@@ -35,7 +36,11 @@ impl TryInto<NodeCommsRequest> for ProtoNodeCommsRequest {
     type Error = String;
 
     fn try_into(self) -> Result<NodeCommsRequest, Self::Error> {
-        use ProtoNodeCommsRequest::{FetchMempoolTransactionsByExcessSigs, GetBlockFromAllChains};
+        use ProtoNodeCommsRequest::{
+            FetchMempoolTransactionsByExcessSigs,
+            FetchMempoolTransactionsByShortKernelIds,
+            GetBlockFromAllChains,
+        };
         let request = match self {
             GetBlockFromAllChains(req) => {
                 NodeCommsRequest::GetBlockFromAllChains(req.hash.try_into().map_err(|_| "Malformed hash".to_string())?)
@@ -49,6 +54,13 @@ impl TryInto<NodeCommsRequest> for ProtoNodeCommsRequest {
 
                 NodeCommsRequest::FetchMempoolTransactionsByExcessSigs { excess_sigs }
             },
+            FetchMempoolTransactionsByShortKernelIds(req) => {
+                NodeCommsRequest::FetchMempoolTransactionsByShortKernelIds {
+                    block_hash: req.block_hash.try_into().map_err(|_| "Malformed hash".to_string())?,
+                    short_id_nonce: req.short_id_nonce,
+                    short_kernel_ids: ShortKernelId::split_bytes(&req.short_kernel_ids)?,
+                }
+            },
         };
         Ok(request)
     }
@@ -58,7 +70,11 @@ impl TryFrom<NodeCommsRequest> for ProtoNodeCommsRequest {
     type Error = String;
 
     fn try_from(request: NodeCommsRequest) -> Result<Self, Self::Error> {
-        use NodeCommsRequest::{FetchMempoolTransactionsByExcessSigs, GetBlockFromAllChains};
+        use NodeCommsRequest::{
+            FetchMempoolTransactionsByExcessSigs,
+            FetchMempoolTransactionsByShortKernelIds,
+            GetBlockFromAllChains,
+        };
         match request {
             GetBlockFromAllChains(hash) => Ok(ProtoNodeCommsRequest::GetBlockFromAllChains(
                 proto::GetBlockFromAllChainsRequest { hash: hash.to_vec() },
@@ -68,6 +84,17 @@ impl TryFrom<NodeCommsRequest> for ProtoNodeCommsRequest {
                     excess_sigs: excess_sigs.into_iter().map(|sig| sig.to_vec()).collect(),
                 }),
             ),
+            FetchMempoolTransactionsByShortKernelIds {
+                block_hash,
+                short_id_nonce,
+                short_kernel_ids,
+            } => Ok(ProtoNodeCommsRequest::FetchMempoolTransactionsByShortKernelIds(
+                proto::ShortKernelIds {
+                    block_hash: block_hash.to_vec(),
+                    short_id_nonce,
+                    short_kernel_ids: ShortKernelId::join_bytes(&short_kernel_ids),
+                },
+            )),
             e => Err(format!("{} request is not supported", e)),
         }
     }
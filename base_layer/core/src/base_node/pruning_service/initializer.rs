@@ -0,0 +1,67 @@
+// Copyright 2023, The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use log::*;
+use tari_service_framework::{async_trait, ServiceInitializationError, ServiceInitializer, ServiceInitializerContext};
+
+use super::{service::PruningService, LOG_TARGET};
+use crate::{
+    base_node::LocalNodeCommsInterface,
+    chain_storage::{async_db::AsyncBlockchainDb, BlockchainBackend, BlockchainDatabaseConfig},
+};
+
+pub struct PruningServiceInitializer<B> {
+    db: AsyncBlockchainDb<B>,
+    config: BlockchainDatabaseConfig,
+}
+
+impl<B> PruningServiceInitializer<B>
+where B: BlockchainBackend + 'static
+{
+    pub fn new(db: AsyncBlockchainDb<B>, config: BlockchainDatabaseConfig) -> Self {
+        Self { db, config }
+    }
+}
+
+#[async_trait]
+impl<B> ServiceInitializer for PruningServiceInitializer<B>
+where B: BlockchainBackend + 'static
+{
+    async fn initialize(&mut self, context: ServiceInitializerContext) -> Result<(), ServiceInitializationError> {
+        if !self.config.background_pruning || self.config.pruning_horizon == 0 {
+            debug!(target: LOG_TARGET, "Background pruning is disabled");
+            return Ok(());
+        }
+        debug!(target: LOG_TARGET, "Initializing Pruning Service");
+
+        let db = self.db.clone();
+        let batch_size = self.config.pruning_batch_size;
+        let batch_delay = self.config.pruning_batch_delay;
+        context.spawn_until_shutdown(move |handles| {
+            let base_node = handles.expect_handle::<LocalNodeCommsInterface>();
+            PruningService::new(db, base_node.get_block_event_stream(), batch_size, batch_delay).run()
+        });
+
+        debug!(target: LOG_TARGET, "Pruning Service initialized");
+        Ok(())
+    }
+}
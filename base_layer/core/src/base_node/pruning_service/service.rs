@@ -0,0 +1,123 @@
+// Copyright 2023, The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{convert::TryFrom, time::Duration};
+
+use log::*;
+use tokio::{sync::broadcast, time};
+
+use super::LOG_TARGET;
+use crate::{
+    base_node::{
+        comms_interface::{BlockEvent, BlockEventReceiver},
+        metrics,
+    },
+    chain_storage::{async_db::AsyncBlockchainDb, BlockAddResult, BlockchainBackend, ChainStorageError},
+};
+
+pub(super) struct PruningService<B> {
+    db: AsyncBlockchainDb<B>,
+    block_event_stream: BlockEventReceiver,
+    batch_size: u64,
+    batch_delay: Duration,
+}
+
+impl<B> PruningService<B>
+where B: BlockchainBackend + 'static
+{
+    pub fn new(
+        db: AsyncBlockchainDb<B>,
+        block_event_stream: BlockEventReceiver,
+        batch_size: u64,
+        batch_delay: Duration,
+    ) -> Self {
+        Self {
+            db,
+            block_event_stream,
+            // A batch size of zero would never make progress
+            batch_size: batch_size.max(1),
+            batch_delay,
+        }
+    }
+
+    /// Run the service
+    pub async fn run(mut self) {
+        // Catch up on any pruning that was outstanding when the node was stopped
+        self.prune_to_horizon().await;
+
+        loop {
+            match self.block_event_stream.recv().await {
+                Ok(event) => match &*event {
+                    BlockEvent::ValidBlockAdded(_, BlockAddResult::Ok(_)) |
+                    BlockEvent::ValidBlockAdded(_, BlockAddResult::ChainReorg { .. }) |
+                    BlockEvent::BlockSyncComplete(_, _) => {
+                        self.prune_to_horizon().await;
+                    },
+                    _ => {},
+                },
+                Err(broadcast::error::RecvError::Lagged(n)) => {
+                    debug!(target: LOG_TARGET, "Block event subscriber lagged by {} item(s)", n);
+                    self.prune_to_horizon().await;
+                },
+                Err(broadcast::error::RecvError::Closed) => {
+                    debug!(target: LOG_TARGET, "Block event stream closed");
+                    break;
+                },
+            }
+        }
+    }
+
+    /// Prunes in batches until the pruning horizon is reached, waiting `batch_delay` between batches
+    async fn prune_to_horizon(&mut self) {
+        loop {
+            match self.prune_batch().await {
+                Ok(true) => return,
+                Ok(false) => time::sleep(self.batch_delay).await,
+                Err(err) => {
+                    warn!(target: LOG_TARGET, "Failed to prune blockchain database: {}", err);
+                    return;
+                },
+            }
+        }
+    }
+
+    /// Prunes a single batch and returns true if the pruning horizon has been reached
+    async fn prune_batch(&self) -> Result<bool, ChainStorageError> {
+        let before = self.db.get_chain_metadata().await?.pruned_height();
+        let progress = self.db.prune_step(self.batch_size).await?;
+        let pruned_blocks = progress.pruned_height.saturating_sub(before);
+        if pruned_blocks > 0 {
+            debug!(
+                target: LOG_TARGET,
+                "Pruned {} block(s) to height {}, {} block(s) remaining until the pruning horizon at height {}",
+                pruned_blocks,
+                progress.pruned_height,
+                progress.remaining(),
+                progress.horizon_height
+            );
+            metrics::pruned_blocks().inc_by(pruned_blocks);
+        }
+        metrics::pruned_height().set(i64::try_from(progress.pruned_height).unwrap_or(i64::MAX));
+        metrics::pruning_backlog().set(i64::try_from(progress.remaining()).unwrap_or(i64::MAX));
+        Ok(progress.is_complete())
+    }
+}
@@ -47,6 +47,8 @@ use crate::{
             GetMempoolFeePerGramStatsResponse,
             QueryDeletedRequest,
             QueryDeletedResponse,
+            QueryUtxoStatusesRequest,
+            QueryUtxoStatusesResponse,
             Signatures,
             SyncUtxosByBlockRequest,
             SyncUtxosByBlockResponse,
@@ -119,6 +121,12 @@ pub trait BaseNodeWalletService: Send + Sync + 'static {
         &self,
         request: Request<GetMempoolFeePerGramStatsRequest>,
     ) -> Result<Response<GetMempoolFeePerGramStatsResponse>, RpcStatus>;
+
+    #[rpc(method = 13)]
+    async fn query_utxo_statuses(
+        &self,
+        request: Request<QueryUtxoStatusesRequest>,
+    ) -> Result<Response<QueryUtxoStatusesResponse>, RpcStatus>;
 }
 
 #[cfg(feature = "base_node")]
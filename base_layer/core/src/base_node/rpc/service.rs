@@ -24,9 +24,9 @@
 use std::convert::{TryFrom, TryInto};
 
 use log::*;
-use tari_common_types::types::{FixedHash, Signature};
+use tari_common_types::types::{Commitment, FixedHash, Signature};
 use tari_comms::protocol::rpc::{Request, Response, RpcStatus, RpcStatusResultExt, Streaming};
-use tari_utilities::hex::Hex;
+use tari_utilities::{hex::Hex, ByteArray};
 use tokio::sync::mpsc;
 
 use crate::{
@@ -46,6 +46,8 @@ use crate::{
             GetMempoolFeePerGramStatsResponse,
             QueryDeletedRequest,
             QueryDeletedResponse,
+            QueryUtxoStatusesRequest,
+            QueryUtxoStatusesResponse,
             Signatures as SignaturesProto,
             SyncUtxosByBlockRequest,
             SyncUtxosByBlockResponse,
@@ -59,6 +61,8 @@ use crate::{
             UtxoQueryRequest,
             UtxoQueryResponse,
             UtxoQueryResponses,
+            UtxoStatus,
+            UtxoStatusResponse,
         },
         types::{Signature as SignatureProto, Transaction as TransactionProto},
     },
@@ -66,6 +70,7 @@ use crate::{
 };
 
 const LOG_TARGET: &str = "c::base_node::rpc";
+const MAX_ALLOWED_QUERY_SIZE: usize = 512;
 
 pub struct BaseNodeWalletRpcService<B> {
     db: AsyncBlockchainDb<B>,
@@ -153,12 +158,15 @@ impl<B: BlockchainBackend + 'static> BaseNodeWalletRpcService<B> {
                 mined_timestamp: None,
             },
             TxStorageResponse::ReorgPool |
+            TxStorageResponse::StemPool |
             TxStorageResponse::NotStoredOrphan |
             TxStorageResponse::NotStoredTimeLocked |
             TxStorageResponse::NotStoredAlreadySpent |
             TxStorageResponse::NotStoredConsensus |
             TxStorageResponse::NotStored |
             TxStorageResponse::NotStoredFeeTooLow |
+            TxStorageResponse::NotStoredMempoolFull |
+            TxStorageResponse::NotStoredPeerLimit |
             TxStorageResponse::NotStoredAlreadyMined => TxQueryResponse {
                 location: TxLocation::NotStored as i32,
                 block_hash: vec![],
@@ -196,7 +204,7 @@ impl<B: BlockchainBackend + 'static> BaseNodeWalletService for BaseNodeWalletRpc
             .await
             .rpc_status_internal_error(LOG_TARGET)?
         {
-            TxStorageResponse::UnconfirmedPool => TxSubmissionResponse {
+            TxStorageResponse::UnconfirmedPool | TxStorageResponse::StemPool => TxSubmissionResponse {
                 accepted: true,
                 rejection_reason: TxSubmissionRejectionReason::None.into(),
                 is_synced,
@@ -207,7 +215,7 @@ impl<B: BlockchainBackend + 'static> BaseNodeWalletService for BaseNodeWalletRpc
                 rejection_reason: TxSubmissionRejectionReason::Orphan.into(),
                 is_synced,
             },
-            TxStorageResponse::NotStoredFeeTooLow => TxSubmissionResponse {
+            TxStorageResponse::NotStoredFeeTooLow | TxStorageResponse::NotStoredMempoolFull => TxSubmissionResponse {
                 accepted: false,
                 rejection_reason: TxSubmissionRejectionReason::FeeTooLow.into(),
                 is_synced,
@@ -217,7 +225,9 @@ impl<B: BlockchainBackend + 'static> BaseNodeWalletService for BaseNodeWalletRpc
                 rejection_reason: TxSubmissionRejectionReason::TimeLocked.into(),
                 is_synced,
             },
-            TxStorageResponse::NotStoredConsensus | TxStorageResponse::NotStored => TxSubmissionResponse {
+            TxStorageResponse::NotStoredConsensus |
+            TxStorageResponse::NotStored |
+            TxStorageResponse::NotStoredPeerLimit => TxSubmissionResponse {
                 accepted: false,
                 rejection_reason: TxSubmissionRejectionReason::ValidationFailed.into(),
                 is_synced,
@@ -375,7 +385,6 @@ impl<B: BlockchainBackend + 'static> BaseNodeWalletService for BaseNodeWalletRpc
         if message.output_hashes.is_empty() {
             return Err(RpcStatus::bad_request("Empty output hashes"));
         }
-        const MAX_ALLOWED_QUERY_SIZE: usize = 512;
         if message.output_hashes.len() > MAX_ALLOWED_QUERY_SIZE {
             return Err(RpcStatus::bad_request(&format!(
                 "Exceeded maximum allowed query hashes. Max: {}",
@@ -679,4 +688,75 @@ impl<B: BlockchainBackend + 'static> BaseNodeWalletService for BaseNodeWalletRpc
 
         Ok(Response::new(stats.into()))
     }
+
+    async fn query_utxo_statuses(
+        &self,
+        request: Request<QueryUtxoStatusesRequest>,
+    ) -> Result<Response<QueryUtxoStatusesResponse>, RpcStatus> {
+        let message = request.into_message();
+        if message.commitments.is_empty() {
+            return Err(RpcStatus::bad_request("Empty commitments"));
+        }
+        if message.commitments.len() > MAX_ALLOWED_QUERY_SIZE {
+            return Err(RpcStatus::bad_request(&format!(
+                "Exceeded maximum allowed query commitments. Max: {}",
+                MAX_ALLOWED_QUERY_SIZE
+            )));
+        }
+
+        let commitments = message
+            .commitments
+            .iter()
+            .map(|c| Commitment::from_bytes(c))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| RpcStatus::bad_request("Malformed commitment received"))?;
+        debug!(
+            target: LOG_TARGET,
+            "Querying {} UTXO(s) by commitment for status",
+            commitments.len(),
+        );
+
+        let mined_info_resp = self
+            .db
+            .fetch_utxos_by_commitment(commitments)
+            .await
+            .rpc_status_internal_error(LOG_TARGET)?;
+        let metadata = self
+            .db
+            .get_chain_metadata()
+            .await
+            .rpc_status_internal_error(LOG_TARGET)?;
+
+        let statuses = message
+            .commitments
+            .into_iter()
+            .zip(mined_info_resp)
+            .map(|(commitment, utxo)| match utxo {
+                Some((utxo, is_spent)) => UtxoStatusResponse {
+                    commitment,
+                    status: if is_spent {
+                        UtxoStatus::Spent.into()
+                    } else {
+                        UtxoStatus::Unspent.into()
+                    },
+                    output_hash: utxo.output.hash().to_vec(),
+                    mmr_position: utxo.mmr_position.into(),
+                    mined_height: utxo.mined_height,
+                    mined_in_block: utxo.header_hash.to_vec(),
+                    mined_timestamp: utxo.mined_timestamp,
+                },
+                None => UtxoStatusResponse {
+                    commitment,
+                    status: UtxoStatus::NotFound.into(),
+                    ..Default::default()
+                },
+            })
+            .collect();
+
+        Ok(Response::new(QueryUtxoStatusesResponse {
+            statuses,
+            best_block: metadata.best_block().to_vec(),
+            height_of_longest_chain: metadata.height_of_longest_chain(),
+        }))
+    }
 }
@@ -82,7 +82,9 @@ impl BaseNodeServiceError {
                 CommsInterfaceError::BlockError(_) |
                 CommsInterfaceError::InvalidFullBlock { .. } |
                 CommsInterfaceError::MergeMineError(_) |
-                CommsInterfaceError::DifficultyError(_) => None,
+                CommsInterfaceError::DifficultyError(_) |
+                CommsInterfaceError::ConnectivityError(_) |
+                CommsInterfaceError::PeerManagerError(_) => None,
             },
             BaseNodeServiceError::DhtOutboundError(_) => None,
             BaseNodeServiceError::InvalidRequest(e) => Some(BanReason {
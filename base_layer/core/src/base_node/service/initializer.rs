@@ -24,7 +24,19 @@ use std::{convert::TryFrom, sync::Arc, time::Duration};
 
 use futures::{future, Stream, StreamExt};
 use log::*;
-use tari_comms::connectivity::ConnectivityRequester;
+use tari_comms::{
+    connectivity::ConnectivityRequester,
+    peer_manager::PeerManager,
+    protocol::{
+        ProtocolEvent,
+        ProtocolExtension,
+        ProtocolExtensionContext,
+        ProtocolExtensionError,
+        ProtocolId,
+        ProtocolNotification,
+    },
+    Substream,
+};
 use tari_comms_dht::Dht;
 use tari_p2p::{
     comms_connector::{PeerMessage, SubscriptionFactory},
@@ -60,6 +72,10 @@ use crate::{
 const LOG_TARGET: &str = "c::bn::service::initializer";
 const SUBSCRIPTION_LABEL: &str = "Base Node";
 
+/// Advertised by nodes that reconcile block announcements from short kernel IDs. Peers never open substreams on this
+/// protocol, it only tells them that block announcements to this node do not need the kernel excess signatures.
+pub static SHORT_KERNEL_IDS_PROTOCOL: ProtocolId = ProtocolId::from_static(b"t/short-kernel-ids/1");
+
 /// Initializer for the Base Node service handle and service future.
 pub struct BaseNodeServiceInitializer<T> {
     inbound_message_subscription_factory: Arc<SubscriptionFactory>,
@@ -68,6 +84,8 @@ pub struct BaseNodeServiceInitializer<T> {
     consensus_manager: ConsensusManager,
     service_request_timeout: Duration,
     randomx_factory: RandomXFactory,
+    notif_rx: Option<mpsc::Receiver<ProtocolNotification<Substream>>>,
+    notif_tx: mpsc::Sender<ProtocolNotification<Substream>>,
 }
 
 impl<T> BaseNodeServiceInitializer<T>
@@ -82,6 +100,7 @@ where T: BlockchainBackend
         service_request_timeout: Duration,
         randomx_factory: RandomXFactory,
     ) -> Self {
+        let (notif_tx, notif_rx) = mpsc::channel(3);
         Self {
             inbound_message_subscription_factory,
             blockchain_db,
@@ -89,6 +108,17 @@ where T: BlockchainBackend
             consensus_manager,
             service_request_timeout,
             randomx_factory,
+            notif_rx: Some(notif_rx),
+            notif_tx,
+        }
+    }
+
+    /// Returns the protocol extension that advertises [SHORT_KERNEL_IDS_PROTOCOL] to peers
+    pub fn get_protocol_extension(&self) -> impl ProtocolExtension {
+        let notif_tx = self.notif_tx.clone();
+        move |context: &mut ProtocolExtensionContext| -> Result<(), ProtocolExtensionError> {
+            context.add_protocol(&[SHORT_KERNEL_IDS_PROTOCOL.clone()], &notif_tx);
+            Ok(())
         }
     }
 
@@ -180,10 +210,26 @@ where T: BlockchainBackend + 'static
         let mempool = self.mempool.clone();
         let consensus_manager = self.consensus_manager.clone();
         let randomx_factory = self.randomx_factory.clone();
+        let mut notif_rx = self
+            .notif_rx
+            .take()
+            .expect("BaseNodeServiceInitializer initialized more than once");
+
+        // The short kernel IDs protocol is only advertised, so any substream that a peer opens on it is closed
+        context.clone().spawn_until_shutdown(move |_| async move {
+            while let Some(notification) = notif_rx.recv().await {
+                let ProtocolEvent::NewInboundSubstream(node_id, _substream) = notification.event;
+                debug!(
+                    target: LOG_TARGET,
+                    "Closing substream that peer {} opened on the short kernel IDs protocol", node_id
+                );
+            }
+        });
 
         context.spawn_when_ready(move |handles| async move {
             let dht = handles.expect_handle::<Dht>();
             let connectivity = handles.expect_handle::<ConnectivityRequester>();
+            let peer_manager = handles.expect_handle::<Arc<PeerManager>>();
             let outbound_message_service = dht.outbound_requester();
 
             let state_machine = handles.expect_handle::<StateMachineHandle>();
@@ -213,6 +259,7 @@ where T: BlockchainBackend + 'static
                 service_request_timeout,
                 state_machine,
                 connectivity,
+                peer_manager,
             )
             .start(streams);
             futures::pin_mut!(service);
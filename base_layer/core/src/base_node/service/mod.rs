@@ -23,7 +23,7 @@
 mod error;
 
 mod initializer;
-pub use initializer::BaseNodeServiceInitializer;
+pub use initializer::{BaseNodeServiceInitializer, SHORT_KERNEL_IDS_PROTOCOL};
 
 #[allow(clippy::module_inception)]
 mod service;
@@ -22,6 +22,7 @@
 
 use std::{
     convert::{TryFrom, TryInto},
+    sync::Arc,
     time::Duration,
 };
 
@@ -32,7 +33,10 @@ use tari_common_types::{
     types::BlockHash,
     waiting_requests::{generate_request_key, RequestKey, WaitingRequests},
 };
-use tari_comms::{connectivity::ConnectivityRequester, peer_manager::NodeId};
+use tari_comms::{
+    connectivity::{ConnectivityRequester, ConnectivitySelection},
+    peer_manager::{NodeId, PeerManager},
+};
 use tari_comms_dht::{
     domain_message::OutboundDomainMessage,
     envelope::NodeDestination,
@@ -53,7 +57,10 @@ use tokio::{
 use crate::{
     base_node::{
         comms_interface::{CommsInterfaceError, InboundNodeCommsHandlers, NodeCommsRequest, NodeCommsResponse},
-        service::{error::BaseNodeServiceError, initializer::ExtractBlockError},
+        service::{
+            error::BaseNodeServiceError,
+            initializer::{ExtractBlockError, SHORT_KERNEL_IDS_PROTOCOL},
+        },
         state_machine_service::states::StateInfo,
         StateMachineHandle,
     },
@@ -97,6 +104,7 @@ pub(super) struct BaseNodeService<B> {
     service_request_timeout: Duration,
     state_machine_handle: StateMachineHandle,
     connectivity: ConnectivityRequester,
+    peer_manager: Arc<PeerManager>,
 }
 
 impl<B> BaseNodeService<B>
@@ -108,6 +116,7 @@ where B: BlockchainBackend + 'static
         service_request_timeout: Duration,
         state_machine_handle: StateMachineHandle,
         connectivity: ConnectivityRequester,
+        peer_manager: Arc<PeerManager>,
     ) -> Self {
         let (timeout_sender, timeout_receiver) = mpsc::channel(100);
         Self {
@@ -119,6 +128,7 @@ where B: BlockchainBackend + 'static
             service_request_timeout,
             state_machine_handle,
             connectivity,
+            peer_manager,
         }
     }
 
@@ -239,8 +249,17 @@ where B: BlockchainBackend + 'static
 
     fn spawn_handle_outbound_block(&self, new_block: NewBlock, excluded_peers: Vec<NodeId>) {
         let outbound_message_service = self.outbound_message_service.clone();
+        let connectivity = self.connectivity.clone();
+        let peer_manager = self.peer_manager.clone();
         task::spawn(async move {
-            let result = handle_outbound_block(outbound_message_service, new_block, excluded_peers).await;
+            let result = handle_outbound_block(
+                outbound_message_service,
+                connectivity,
+                peer_manager,
+                new_block,
+                excluded_peers,
+            )
+            .await;
 
             if let Err(e) = result {
                 error!(target: LOG_TARGET, "Failed to handle outbound block message {:?}", e);
@@ -620,21 +639,67 @@ async fn handle_outbound_request(
 
 async fn handle_outbound_block(
     mut outbound_message_service: OutboundMessageRequester,
+    mut connectivity: ConnectivityRequester,
+    peer_manager: Arc<PeerManager>,
     new_block: NewBlock,
     exclude_peers: Vec<NodeId>,
 ) -> Result<(), CommsInterfaceError> {
+    let connections = connectivity
+        .select_connections(ConnectivitySelection::all_nodes(exclude_peers))
+        .await?;
+    let mut short_id_peers = Vec::with_capacity(connections.len());
+    let mut excess_sig_peers = Vec::new();
+    for conn in connections {
+        let node_id = conn.peer_node_id().clone();
+        let supports_short_ids = peer_manager.find_by_node_id(&node_id).await?.map_or(false, |peer| {
+            peer.supported_protocols().contains(&SHORT_KERNEL_IDS_PROTOCOL)
+        });
+        if supports_short_ids {
+            short_id_peers.push(node_id);
+        } else {
+            excess_sig_peers.push(node_id);
+        }
+    }
+
+    send_new_block(
+        &mut outbound_message_service,
+        short_id_peers,
+        new_block.without_kernel_excess_sigs(),
+    )
+    .await?;
+    send_new_block(
+        &mut outbound_message_service,
+        excess_sig_peers,
+        new_block.without_short_kernel_ids(),
+    )
+    .await
+}
+
+async fn send_new_block(
+    outbound_message_service: &mut OutboundMessageRequester,
+    peers: Vec<NodeId>,
+    new_block: NewBlock,
+) -> Result<(), CommsInterfaceError> {
+    if peers.is_empty() {
+        return Ok(());
+    }
     let result = outbound_message_service
-        .flood(
-            NodeDestination::Unknown,
-            OutboundEncryption::ClearText,
-            exclude_peers,
+        .send_message(
+            SendMessageParams::new()
+                .with_debug_info("Outbound new block from base node".to_string())
+                .selected_peers(peers)
+                .with_destination(NodeDestination::Unknown)
+                .with_encryption(OutboundEncryption::ClearText)
+                .finish(),
             OutboundDomainMessage::new(
                 &TariMessageType::NewBlock,
                 shared_protos::core::NewBlock::try_from(new_block).map_err(CommsInterfaceError::InternalError)?,
             ),
-            "Outbound new block from base node".to_string(),
         )
-        .await;
+        .await?
+        .resolve()
+        .await
+        .map_err(DhtOutboundError::from);
     if let Err(e) = result {
         return match e {
             DhtOutboundError::NoMessagesQueued => Ok(()),
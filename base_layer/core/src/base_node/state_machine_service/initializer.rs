@@ -24,6 +24,7 @@ use std::sync::Arc;
 
 use log::*;
 use tari_comms::{connectivity::ConnectivityRequester, PeerManager};
+use tari_p2p::services::liveness::LivenessHandle;
 use tari_service_framework::{async_trait, ServiceInitializationError, ServiceInitializer, ServiceInitializerContext};
 use tokio::sync::{broadcast, watch};
 
@@ -108,6 +109,7 @@ where B: BlockchainBackend + 'static
             let node_local_interface = handles.expect_handle::<LocalNodeCommsInterface>();
             let connectivity = handles.expect_handle::<ConnectivityRequester>();
             let peer_manager = handles.expect_handle::<Arc<PeerManager>>();
+            let liveness = handles.expect_handle::<LivenessHandle>();
 
             let sync_validators =
                 SyncValidators::full_consensus(rules.clone(), factories, bypass_range_proof_verification);
@@ -117,6 +119,7 @@ where B: BlockchainBackend + 'static
                 node_local_interface,
                 connectivity,
                 peer_manager,
+                liveness,
                 chain_metadata_service.get_event_stream(),
                 config,
                 sync_validators,
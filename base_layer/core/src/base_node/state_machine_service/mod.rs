@@ -29,3 +29,6 @@ mod state_machine;
 pub use state_machine::{BaseNodeStateMachine, BaseNodeStateMachineConfig};
 
 pub mod states;
+
+mod sync_candidates;
+pub use sync_candidates::{evaluate_sync_candidates, SyncCandidate, SyncCandidateReason};
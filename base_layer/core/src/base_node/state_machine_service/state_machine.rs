@@ -27,6 +27,7 @@ use randomx_rs::RandomXFlag;
 use serde::{Deserialize, Serialize};
 use tari_common::configuration::serializers;
 use tari_comms::{connectivity::ConnectivityRequester, PeerManager};
+use tari_p2p::services::liveness::LivenessHandle;
 use tari_shutdown::ShutdownSignal;
 use tokio::sync::{broadcast, watch};
 
@@ -87,6 +88,7 @@ pub struct BaseNodeStateMachine<B: BlockchainBackend> {
     pub(super) local_node_interface: LocalNodeCommsInterface,
     pub(super) connectivity: ConnectivityRequester,
     pub(super) peer_manager: Arc<PeerManager>,
+    pub(super) liveness: LivenessHandle,
     pub(super) metadata_event_stream: broadcast::Receiver<Arc<ChainMetadataEvent>>,
     pub(super) config: BaseNodeStateMachineConfig,
     pub(super) info: StateInfo,
@@ -101,12 +103,13 @@ pub struct BaseNodeStateMachine<B: BlockchainBackend> {
 
 impl<B: BlockchainBackend + 'static> BaseNodeStateMachine<B> {
     /// Instantiate a new Base Node.
-
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         db: AsyncBlockchainDb<B>,
         local_node_interface: LocalNodeCommsInterface,
         connectivity: ConnectivityRequester,
         peer_manager: Arc<PeerManager>,
+        liveness: LivenessHandle,
         metadata_event_stream: broadcast::Receiver<Arc<ChainMetadataEvent>>,
         config: BaseNodeStateMachineConfig,
         sync_validators: SyncValidators<B>,
@@ -121,6 +124,7 @@ impl<B: BlockchainBackend + 'static> BaseNodeStateMachine<B> {
             local_node_interface,
             connectivity,
             peer_manager,
+            liveness,
             metadata_event_stream,
             config,
             info: StateInfo::StartUp,
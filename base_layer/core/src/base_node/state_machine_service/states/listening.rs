@@ -273,7 +273,7 @@ impl From<DecideNextSync> for Listening {
 }
 
 /// Given a local and the network chain state respectively, figure out what synchronisation state we should be in.
-fn determine_sync_mode(
+pub(crate) fn determine_sync_mode(
     blocks_behind_before_considered_lagging: u64,
     local: &ChainMetadata,
     network: &PeerChainMetadata,
@@ -72,6 +72,7 @@ mod horizon_state_sync;
 pub use horizon_state_sync::HorizonStateSync;
 
 mod listening;
+pub(crate) use listening::determine_sync_mode;
 pub use listening::{Listening, ListeningInfo, PeerMetadata};
 
 mod shutdown_state;
@@ -23,6 +23,7 @@
 use std::cmp::Ordering;
 
 use log::*;
+use tari_p2p::services::liveness::NetworkLatency;
 
 use crate::{
     base_node::{
@@ -58,6 +59,16 @@ impl DecideNextSync {
             self.sync_peers.len()
         );
 
+        match shared.liveness.clone().get_network_latency().await {
+            Ok(network_latency) => sort_by_latency_bucket(&mut self.sync_peers, &network_latency),
+            Err(err) => {
+                warn!(
+                    target: LOG_TARGET,
+                    "Unable to get network latency from the liveness service: {}", err
+                );
+            },
+        }
+
         if local_metadata.pruning_horizon() > 0 {
             let last_header = match shared.db.fetch_last_header().await {
                 Ok(h) => h,
@@ -119,6 +130,16 @@ impl DecideNextSync {
     }
 }
 
+/// Orders sync peers by the latency bucket of their liveness RTT histogram so that peers with low round-trip times are
+/// preferred. The existing order is kept within a bucket and peers without any recorded round-trip times are placed
+/// last.
+fn sort_by_latency_bucket(sync_peers: &mut [SyncPeer], network_latency: &NetworkLatency) {
+    sync_peers.sort_by_key(|sync_peer| {
+        let bucket = network_latency.bucket_of(sync_peer.node_id());
+        (bucket.is_none(), bucket)
+    });
+}
+
 impl From<HeaderSyncState> for DecideNextSync {
     fn from(sync: HeaderSyncState) -> Self {
         sync.into_sync_peers().into()
@@ -171,5 +192,44 @@ mod test {
             let decide = DecideNextSync::from(shuffled);
             assert_eq!(decide.sync_peers, peers);
         }
+
+        #[test]
+        fn it_prefers_low_latency_buckets() {
+            use std::collections::HashMap;
+
+            use tari_comms::{peer_manager::NodeId, types::CommsPublicKey};
+            use tari_crypto::keys::PublicKey;
+            use tari_p2p::services::liveness::{PeerLatency, RttHistogram};
+
+            let new_sync_peer = |latency_ms| -> SyncPeer {
+                let (_, public_key) = CommsPublicKey::random_keypair(&mut OsRng);
+                PeerChainMetadata::new(
+                    NodeId::from_key(&public_key),
+                    ChainMetadata::empty(),
+                    Some(Duration::from_millis(latency_ms)),
+                )
+                .into()
+            };
+            let peer_latency = |latency_ms| {
+                let mut histogram = RttHistogram::new();
+                histogram.add_sample(Duration::from_millis(latency_ms));
+                PeerLatency {
+                    avg_latency: Duration::from_millis(latency_ms),
+                    histogram,
+                }
+            };
+
+            let distant = new_sync_peer(1);
+            let local = new_sync_peer(2);
+            let unknown = new_sync_peer(3);
+            let network_latency = NetworkLatency::new(HashMap::from([
+                (distant.node_id().clone(), peer_latency(900)),
+                (local.node_id().clone(), peer_latency(20)),
+            ]));
+
+            let mut sync_peers = vec![unknown.clone(), distant.clone(), local.clone()];
+            sort_by_latency_bucket(&mut sync_peers, &network_latency);
+            assert_eq!(sync_peers, vec![local, distant, unknown]);
+        }
     }
 }
@@ -0,0 +1,214 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    cmp::Ordering,
+    fmt::{Display, Formatter},
+};
+
+use tari_common_types::chain_metadata::ChainMetadata;
+
+use crate::base_node::{
+    chain_metadata_service::PeerChainMetadata,
+    state_machine_service::states::{determine_sync_mode, SyncStatus},
+};
+
+/// The reason a peer was, or was not, considered a sync candidate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncCandidateReason {
+    /// The peer claims a chain with more accumulated proof-of-work than ours
+    HigherAccumulatedDifficulty,
+    /// The peer claims a different chain tip at the same height as ours
+    SameHeightFork,
+    /// The peer claims a chain with no more accumulated proof-of-work than ours
+    Behind,
+    /// The peer claims the same chain tip as ours
+    InSync,
+    /// The peer claims a stronger chain, but has pruned blocks that we would need to sync from it
+    CannotSupplyBlocks,
+}
+
+impl Display for SyncCandidateReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::HigherAccumulatedDifficulty => write!(f, "Higher accumulated difficulty"),
+            Self::SameHeightFork => write!(f, "Same height fork"),
+            Self::Behind => write!(f, "Behind"),
+            Self::InSync => write!(f, "In sync"),
+            Self::CannotSupplyBlocks => write!(f, "Cannot supply blocks"),
+        }
+    }
+}
+
+/// The evaluation of a single peer's advertised chain metadata against our local chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncCandidate {
+    pub peer: PeerChainMetadata,
+    pub reason: SyncCandidateReason,
+    /// True if we would sync from this peer
+    pub is_candidate: bool,
+}
+
+/// Evaluates the given peer-advertised chain metadata against our local chain metadata and returns every peer ranked
+/// from best to worst sync candidate. Peers we would sync from come first, ordered by the accumulated difficulty they
+/// claim and then by latency. The remaining peers follow, ordered by accumulated difficulty.
+pub fn evaluate_sync_candidates(local: &ChainMetadata, peers: &[PeerChainMetadata]) -> Vec<SyncCandidate> {
+    let mut candidates = peers
+        .iter()
+        .map(|peer| {
+            // The lagging threshold only decides when the listening state starts syncing, so it is not used here
+            let sync_status = determine_sync_mode(0, local, peer);
+            let network = peer.claimed_chain_metadata();
+            let is_same_height = network.height_of_longest_chain() == local.height_of_longest_chain();
+            let (reason, is_candidate) = match sync_status {
+                SyncStatus::SyncNotPossible { .. } => (SyncCandidateReason::CannotSupplyBlocks, false),
+                SyncStatus::Lagging { .. } | SyncStatus::BehindButNotYetLagging { .. } => {
+                    if is_same_height {
+                        (SyncCandidateReason::SameHeightFork, true)
+                    } else {
+                        (SyncCandidateReason::HigherAccumulatedDifficulty, true)
+                    }
+                },
+                SyncStatus::UpToDate => {
+                    if network.best_block() == local.best_block() {
+                        (SyncCandidateReason::InSync, false)
+                    } else if is_same_height {
+                        (SyncCandidateReason::SameHeightFork, false)
+                    } else {
+                        (SyncCandidateReason::Behind, false)
+                    }
+                },
+            };
+            SyncCandidate {
+                peer: peer.clone(),
+                reason,
+                is_candidate,
+            }
+        })
+        .collect::<Vec<_>>();
+    candidates.sort_by(rank_candidates);
+    candidates
+}
+
+fn rank_candidates(a: &SyncCandidate, b: &SyncCandidate) -> Ordering {
+    b.is_candidate
+        .cmp(&a.is_candidate)
+        .then_with(|| {
+            b.peer
+                .claimed_chain_metadata()
+                .accumulated_difficulty()
+                .cmp(&a.peer.claimed_chain_metadata().accumulated_difficulty())
+        })
+        .then_with(|| match (a.peer.latency(), b.peer.latency()) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use rand::rngs::OsRng;
+    use tari_common_types::types::FixedHash;
+    use tari_comms::{peer_manager::NodeId, types::CommsPublicKey};
+    use tari_crypto::keys::PublicKey;
+
+    use super::*;
+
+    fn random_node_id() -> NodeId {
+        let (_secret_key, public_key) = CommsPublicKey::random_keypair(&mut OsRng);
+        NodeId::from_key(&public_key)
+    }
+
+    fn peer(height: u64, hash: u8, accumulated_difficulty: u128, latency: Option<Duration>) -> PeerChainMetadata {
+        PeerChainMetadata::new(
+            random_node_id(),
+            ChainMetadata::new(height, FixedHash::from([hash; 32]), 0, 0, accumulated_difficulty, 0),
+            latency,
+        )
+    }
+
+    #[test]
+    fn it_ranks_peers_with_reasons() {
+        let local = ChainMetadata::new(100, FixedHash::from([1; 32]), 0, 0, 1000, 0);
+        let in_sync = peer(100, 1, 1000, None);
+        let behind = peer(90, 2, 900, None);
+        let weaker_fork = peer(100, 3, 999, None);
+        let stronger_fork = peer(100, 4, 1001, Some(Duration::from_millis(10)));
+        let ahead_slow = peer(110, 5, 1100, Some(Duration::from_millis(500)));
+        let ahead_fast = peer(110, 5, 1100, Some(Duration::from_millis(50)));
+        let peers = vec![
+            in_sync.clone(),
+            behind.clone(),
+            weaker_fork.clone(),
+            stronger_fork.clone(),
+            ahead_slow.clone(),
+            ahead_fast.clone(),
+        ];
+
+        let candidates = evaluate_sync_candidates(&local, &peers);
+        let ranked = candidates
+            .iter()
+            .map(|c| (c.peer.node_id().clone(), c.reason, c.is_candidate))
+            .collect::<Vec<_>>();
+        assert_eq!(ranked, vec![
+            (
+                ahead_fast.node_id().clone(),
+                SyncCandidateReason::HigherAccumulatedDifficulty,
+                true
+            ),
+            (
+                ahead_slow.node_id().clone(),
+                SyncCandidateReason::HigherAccumulatedDifficulty,
+                true
+            ),
+            (
+                stronger_fork.node_id().clone(),
+                SyncCandidateReason::SameHeightFork,
+                true
+            ),
+            (in_sync.node_id().clone(), SyncCandidateReason::InSync, false),
+            (
+                weaker_fork.node_id().clone(),
+                SyncCandidateReason::SameHeightFork,
+                false
+            ),
+            (behind.node_id().clone(), SyncCandidateReason::Behind, false),
+        ]);
+    }
+
+    #[test]
+    fn it_rejects_pruned_peers_that_cannot_supply_blocks() {
+        let local = ChainMetadata::new(100, FixedHash::from([1; 32]), 0, 0, 1000, 0);
+        let pruned = PeerChainMetadata::new(
+            random_node_id(),
+            ChainMetadata::new(200, FixedHash::from([2; 32]), 50, 150, 2000, 0),
+            None,
+        );
+        let candidates = evaluate_sync_candidates(&local, &[pruned]);
+        assert_eq!(candidates[0].reason, SyncCandidateReason::CannotSupplyBlocks);
+        assert!(!candidates[0].is_candidate);
+    }
+}
@@ -323,7 +323,9 @@ impl<'a, B: BlockchainBackend + 'static> BlockSynchronizer<'a, B> {
 
             let block = match res {
                 Ok(block) => block,
-                Err(err @ ValidationError::BadBlockFound { .. }) | Err(err @ ValidationError::FatalStorageError(_)) => {
+                Err(err)
+                    if err.is_internal_error() || matches!(err.root_cause(), ValidationError::BadBlockFound { .. }) =>
+                {
                     return Err(err.into());
                 },
                 Err(err) => {
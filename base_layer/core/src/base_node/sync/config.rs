@@ -51,6 +51,8 @@ pub struct BlockchainSyncConfig {
     /// The RPC deadline to set on sync clients. If this deadline is reached, a new sync peer will be selected for
     /// sync.
     pub rpc_deadline: Duration,
+    /// Per-peer resource budgets enforced by the sync RPC service
+    pub rpc_budget: SyncRpcBudgetConfig,
 }
 
 impl Default for BlockchainSyncConfig {
@@ -63,6 +65,33 @@ impl Default for BlockchainSyncConfig {
             forced_sync_peers: Default::default(),
             validation_concurrency: 6,
             rpc_deadline: Duration::from_secs(30),
+            rpc_budget: Default::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SyncRpcBudgetConfig {
+    /// The maximum number of sync RPC requests a peer may make in any one minute window
+    pub max_requests_per_minute: usize,
+    /// The maximum number of bytes that will be streamed to a peer in a single sync session. The session is
+    /// terminated once this is exceeded.
+    pub max_bytes_per_session: u64,
+    /// The maximum number of block, header, kernel or UTXO sync streams a peer may have open at once
+    pub max_concurrent_streams: usize,
+    /// The number of budget violations a peer may commit before being banned. The first ban lasts for
+    /// `short_ban_period`, subsequent bans last for `ban_period`.
+    pub violations_before_ban: usize,
+}
+
+impl Default for SyncRpcBudgetConfig {
+    fn default() -> Self {
+        Self {
+            max_requests_per_minute: 60,
+            max_bytes_per_session: 16 * 1024 * 1024 * 1024,
+            max_concurrent_streams: 1,
+            violations_before_ban: 3,
         }
     }
 }
@@ -486,6 +486,7 @@ impl<'a, B: BlockchainBackend + 'static> HeaderSynchronizer<'a, B> {
             split_hash.to_hex()
         );
 
+        self.db.check_rewind_to_hash(split_hash).await?;
         let blocks = self.db.rewind_to_hash(split_hash).await?;
         debug!(
             target: LOG_TARGET,
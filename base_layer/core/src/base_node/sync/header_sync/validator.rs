@@ -136,12 +136,9 @@ impl<B: BlockchainBackend + 'static> BlockHeaderSyncValidator<B> {
                 return Err(e.into())
             },
             // We dont want to mark a block as bad for internal failures
-            Err(
-                e @ ValidationError::FatalStorageError(_) |
-                e @ ValidationError::IncorrectNumberOfTimestampsProvided { .. },
-            ) => return Err(e.into()),
+            Err(e) if e.is_internal_error() => return Err(e.into()),
             // We dont have to mark the block twice
-            Err(e @ ValidationError::BadBlockFound { .. }) => return Err(e.into()),
+            Err(e) if matches!(e.root_cause(), ValidationError::BadBlockFound { .. }) => return Err(e.into()),
 
             Err(e) => {
                 let mut txn = self.db.write_transaction();
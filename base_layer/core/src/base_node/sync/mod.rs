@@ -26,7 +26,7 @@ pub mod ban;
 #[cfg(feature = "base_node")]
 mod config;
 #[cfg(feature = "base_node")]
-pub use self::config::BlockchainSyncConfig;
+pub use self::config::{BlockchainSyncConfig, SyncRpcBudgetConfig};
 
 #[cfg(feature = "base_node")]
 mod block_sync;
@@ -0,0 +1,280 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use log::*;
+use tari_comms::{connectivity::ConnectivityRequester, peer_manager::NodeId, protocol::rpc::RpcStatus};
+use tokio::{
+    sync::{mpsc, Mutex},
+    task,
+};
+
+use crate::{
+    base_node::{sync::ban::PeerBanManager, BlockchainSyncConfig, SyncRpcBudgetConfig},
+    common::BanReason,
+};
+
+const LOG_TARGET: &str = "c::base_node::sync_rpc::budget";
+
+/// The rolling window over which request rates are measured
+const REQUEST_WINDOW: Duration = Duration::from_secs(60);
+
+/// Tracks the resources each peer consumes from the sync RPC service and bans peers that repeatedly exceed their
+/// budget. The first ban uses the short ban period, any further bans use the (longer) ban period.
+pub struct SyncRpcBudget {
+    config: SyncRpcBudgetConfig,
+    short_ban_period: Duration,
+    ban_period: Duration,
+    peers: Mutex<HashMap<NodeId, PeerBudget>>,
+    ban_manager: Mutex<PeerBanManager>,
+}
+
+#[derive(Debug, Default)]
+struct PeerBudget {
+    requests: VecDeque<Instant>,
+    violations: usize,
+    num_bans: usize,
+    last_violation: Option<Instant>,
+}
+
+impl PeerBudget {
+    fn expire_requests(&mut self, now: Instant) {
+        while self
+            .requests
+            .front()
+            .filter(|t| now.duration_since(**t) >= REQUEST_WINDOW)
+            .is_some()
+        {
+            self.requests.pop_front();
+        }
+    }
+
+    fn is_idle(&self, now: Instant, retention: Duration) -> bool {
+        self.requests.is_empty() && self.last_violation.map_or(true, |t| now.duration_since(t) >= retention)
+    }
+}
+
+impl SyncRpcBudget {
+    pub fn new(config: BlockchainSyncConfig, connectivity: ConnectivityRequester) -> Self {
+        Self {
+            config: config.rpc_budget.clone(),
+            short_ban_period: config.short_ban_period,
+            ban_period: config.ban_period,
+            peers: Mutex::new(HashMap::new()),
+            ban_manager: Mutex::new(PeerBanManager::new(config, connectivity)),
+        }
+    }
+
+    pub fn max_concurrent_streams(&self) -> usize {
+        self.config.max_concurrent_streams
+    }
+
+    /// Records a request from the peer. An error is returned if the peer has exceeded the number of requests it may
+    /// make within the request window.
+    pub async fn check_request(&self, peer: &NodeId) -> Result<(), RpcStatus> {
+        let now = Instant::now();
+        let mut peers = self.peers.lock().await;
+        let retention = self.ban_period;
+        peers.retain(|_, budget| {
+            budget.expire_requests(now);
+            !budget.is_idle(now, retention)
+        });
+
+        let budget = peers.entry(peer.clone()).or_default();
+        if budget.requests.len() < self.config.max_requests_per_minute {
+            budget.requests.push_back(now);
+            return Ok(());
+        }
+
+        let reason = format!(
+            "exceeded {} sync requests per minute",
+            self.config.max_requests_per_minute
+        );
+        let ban = self.register_violation(budget, now, &reason);
+        drop(peers);
+        self.ban_if_required(peer, ban).await;
+        Err(RpcStatus::forbidden(&format!("Request budget exhausted: {}", reason)))
+    }
+
+    /// Records a budget violation for the peer, banning it if it has exceeded the permitted number of violations.
+    pub async fn record_violation(&self, peer: &NodeId, reason: &str) {
+        let ban = {
+            let mut peers = self.peers.lock().await;
+            let budget = peers.entry(peer.clone()).or_default();
+            self.register_violation(budget, Instant::now(), reason)
+        };
+        self.ban_if_required(peer, ban).await;
+    }
+
+    /// Forwards the items of a response stream, terminating the stream if the number of bytes sent exceeds the
+    /// per-session budget.
+    pub fn meter_stream<T>(
+        self: &Arc<Self>,
+        peer: NodeId,
+        mut rx: mpsc::Receiver<Result<T, RpcStatus>>,
+    ) -> mpsc::Receiver<Result<T, RpcStatus>>
+    where
+        T: prost::Message + Send + 'static,
+    {
+        let (tx, metered_rx) = mpsc::channel(1);
+        let budget = self.clone();
+        task::spawn(async move {
+            let max_bytes = budget.config.max_bytes_per_session;
+            let mut bytes_sent = 0u64;
+            loop {
+                let item = tokio::select! {
+                    item = rx.recv() => item,
+                    // Ensure the upstream task stops promptly if the peer prematurely stops their RPC session
+                    _ = tx.closed() => break,
+                };
+                let item = match item {
+                    Some(item) => item,
+                    None => break,
+                };
+
+                if let Ok(msg) = &item {
+                    bytes_sent = bytes_sent.saturating_add(msg.encoded_len() as u64);
+                    if bytes_sent > max_bytes {
+                        let reason = format!("exceeded {} bytes in a single sync session", max_bytes);
+                        warn!(target: LOG_TARGET, "Sync peer `{}` {}", peer, reason);
+                        budget.record_violation(&peer, &reason).await;
+                        let _result = tx
+                            .send(Err(RpcStatus::forbidden(&format!(
+                                "Session budget exhausted: {}",
+                                reason
+                            ))))
+                            .await;
+                        break;
+                    }
+                }
+
+                if tx.send(item).await.is_err() {
+                    break;
+                }
+            }
+        });
+        metered_rx
+    }
+
+    fn register_violation(&self, budget: &mut PeerBudget, now: Instant, reason: &str) -> Option<BanReason> {
+        budget.violations += 1;
+        budget.last_violation = Some(now);
+        debug!(
+            target: LOG_TARGET,
+            "Sync budget violation #{} ({})", budget.violations, reason
+        );
+        if budget.violations < self.config.violations_before_ban {
+            return None;
+        }
+
+        budget.violations = 0;
+        let ban_duration = if budget.num_bans == 0 {
+            self.short_ban_period
+        } else {
+            self.ban_period
+        };
+        budget.num_bans += 1;
+        Some(BanReason {
+            reason: format!("Sync RPC budget repeatedly exceeded: {}", reason),
+            ban_duration,
+        })
+    }
+
+    async fn ban_if_required(&self, peer: &NodeId, ban: Option<BanReason>) {
+        if ban.is_some() {
+            self.ban_manager.lock().await.ban_peer_if_required(peer, &ban).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tari_comms::{protocol::rpc::RpcStatusCode, test_utils::mocks::create_connectivity_mock};
+
+    use super::*;
+
+    fn create_budget(rpc_budget: SyncRpcBudgetConfig) -> Arc<SyncRpcBudget> {
+        let (connectivity, mock) = create_connectivity_mock();
+        mock.spawn();
+        Arc::new(SyncRpcBudget::new(
+            BlockchainSyncConfig {
+                rpc_budget,
+                ..Default::default()
+            },
+            connectivity,
+        ))
+    }
+
+    #[tokio::test]
+    async fn it_limits_the_request_rate_per_peer() {
+        let budget = create_budget(SyncRpcBudgetConfig {
+            max_requests_per_minute: 2,
+            ..Default::default()
+        });
+        let peer = NodeId::default();
+        budget.check_request(&peer).await.unwrap();
+        budget.check_request(&peer).await.unwrap();
+        let err = budget.check_request(&peer).await.unwrap_err();
+        assert_eq!(err.as_status_code(), RpcStatusCode::Forbidden);
+    }
+
+    #[tokio::test]
+    async fn it_escalates_bans_for_repeat_offenders() {
+        let budget = create_budget(SyncRpcBudgetConfig {
+            violations_before_ban: 2,
+            ..Default::default()
+        });
+        let peer = NodeId::default();
+        let mut peer_budget = PeerBudget::default();
+        let now = Instant::now();
+        assert!(budget.register_violation(&mut peer_budget, now, "test").is_none());
+        let ban = budget.register_violation(&mut peer_budget, now, "test").unwrap();
+        assert_eq!(ban.ban_duration, budget.short_ban_period);
+        assert!(budget.register_violation(&mut peer_budget, now, "test").is_none());
+        let ban = budget.register_violation(&mut peer_budget, now, "test").unwrap();
+        assert_eq!(ban.ban_duration, budget.ban_period);
+
+        budget.record_violation(&peer, "test").await;
+        assert_eq!(budget.peers.lock().await.get(&peer).unwrap().violations, 1);
+    }
+
+    #[tokio::test]
+    async fn it_terminates_streams_that_exceed_the_session_budget() {
+        let budget = create_budget(SyncRpcBudgetConfig {
+            max_bytes_per_session: 10,
+            ..Default::default()
+        });
+        let (tx, rx) = mpsc::channel(10);
+        let mut rx = budget.meter_stream(NodeId::default(), rx);
+        tx.send(Ok(vec![1u8; 4])).await.unwrap();
+        tx.send(Ok(vec![1u8; 4])).await.unwrap();
+        assert_eq!(rx.recv().await.unwrap().unwrap(), vec![1u8; 4]);
+        let err = rx.recv().await.unwrap().unwrap_err();
+        assert_eq!(err.as_status_code(), RpcStatusCode::Forbidden);
+        assert!(rx.recv().await.is_none());
+    }
+}
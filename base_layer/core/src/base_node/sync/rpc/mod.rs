@@ -20,6 +20,8 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+#[cfg(feature = "base_node")]
+mod budget;
 #[cfg(feature = "base_node")]
 mod service;
 #[cfg(feature = "base_node")]
@@ -31,12 +33,14 @@ pub use service::BaseNodeSyncRpcService;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "base_node")]
+use tari_comms::connectivity::ConnectivityRequester;
 use tari_comms::protocol::rpc::{Request, Response, RpcStatus, Streaming};
 use tari_comms_rpc_macros::tari_rpc;
 
 #[cfg(feature = "base_node")]
 use crate::{
-    base_node::LocalNodeCommsInterface,
+    base_node::{BlockchainSyncConfig, LocalNodeCommsInterface},
     chain_storage::{async_db::AsyncBlockchainDb, BlockchainBackend},
 };
 use crate::{
@@ -98,6 +102,8 @@ pub trait BaseNodeSyncService: Send + Sync + 'static {
 pub fn create_base_node_sync_rpc_service<B: BlockchainBackend + 'static>(
     db: AsyncBlockchainDb<B>,
     base_node_service: LocalNodeCommsInterface,
+    config: BlockchainSyncConfig,
+    connectivity: ConnectivityRequester,
 ) -> BaseNodeSyncRpcServer<BaseNodeSyncRpcService<B>> {
-    BaseNodeSyncRpcServer::new(BaseNodeSyncRpcService::new(db, base_node_service))
+    BaseNodeSyncRpcServer::new(BaseNodeSyncRpcService::new(db, base_node_service, config, connectivity))
 }
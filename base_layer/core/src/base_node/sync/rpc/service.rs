@@ -29,6 +29,7 @@ use std::{
 use log::*;
 use tari_common_types::types::FixedHash;
 use tari_comms::{
+    connectivity::ConnectivityRequester,
     peer_manager::NodeId,
     protocol::rpc::{Request, Response, RpcStatus, RpcStatusResultExt, Streaming},
     utils,
@@ -44,10 +45,11 @@ use crate::{
     base_node::{
         comms_interface::BlockEvent,
         metrics,
-        sync::rpc::{sync_utxos_task::SyncUtxosTask, BaseNodeSyncService},
+        sync::rpc::{budget::SyncRpcBudget, sync_utxos_task::SyncUtxosTask, BaseNodeSyncService},
+        BlockchainSyncConfig,
         LocalNodeCommsInterface,
     },
-    chain_storage::{async_db::AsyncBlockchainDb, BlockAddResult, BlockchainBackend},
+    chain_storage::{async_db::AsyncBlockchainDb, BlockAddResult, BlockchainBackend, Optional},
     iterators::NonOverlappingIntegerPairIter,
     proto,
     proto::base_node::{
@@ -67,14 +69,21 @@ pub struct BaseNodeSyncRpcService<B> {
     db: AsyncBlockchainDb<B>,
     active_sessions: Mutex<Vec<Weak<NodeId>>>,
     base_node_service: LocalNodeCommsInterface,
+    budget: Arc<SyncRpcBudget>,
 }
 
 impl<B: BlockchainBackend + 'static> BaseNodeSyncRpcService<B> {
-    pub fn new(db: AsyncBlockchainDb<B>, base_node_service: LocalNodeCommsInterface) -> Self {
+    pub fn new(
+        db: AsyncBlockchainDb<B>,
+        base_node_service: LocalNodeCommsInterface,
+        config: BlockchainSyncConfig,
+        connectivity: ConnectivityRequester,
+    ) -> Self {
         Self {
             db,
             active_sessions: Mutex::new(Vec::new()),
             base_node_service,
+            budget: Arc::new(SyncRpcBudget::new(config, connectivity)),
         }
     }
 
@@ -83,15 +92,24 @@ impl<B: BlockchainBackend + 'static> BaseNodeSyncRpcService<B> {
         self.db.clone()
     }
 
-    pub async fn try_add_exclusive_session(&self, peer: NodeId) -> Result<Arc<NodeId>, RpcStatus> {
+    pub async fn try_add_sync_session(&self, peer: NodeId) -> Result<Arc<NodeId>, RpcStatus> {
         let mut lock = self.active_sessions.lock().await;
         *lock = lock.drain(..).filter(|l| l.strong_count() > 0).collect();
         debug!(target: LOG_TARGET, "Number of active sync sessions: {}", lock.len());
 
-        if lock.iter().any(|p| p.upgrade().filter(|p| **p == peer).is_some()) {
-            return Err(RpcStatus::forbidden(
-                "Existing sync session found for this client. Only a single session is permitted",
-            ));
+        let max_streams = self.budget.max_concurrent_streams();
+        let num_peer_sessions = lock
+            .iter()
+            .filter(|p| p.upgrade().filter(|p| **p == peer).is_some())
+            .count();
+        if num_peer_sessions >= max_streams {
+            drop(lock);
+            let reason = format!("exceeded {} concurrent sync session(s)", max_streams);
+            self.budget.record_violation(&peer, &reason).await;
+            return Err(RpcStatus::forbidden(&format!(
+                "Existing sync session(s) found for this client. Only {} session(s) permitted",
+                max_streams
+            )));
         }
 
         let token = Arc::new(peer);
@@ -109,6 +127,7 @@ impl<B: BlockchainBackend + 'static> BaseNodeSyncService for BaseNodeSyncRpcServ
         request: Request<SyncBlocksRequest>,
     ) -> Result<Streaming<proto::base_node::BlockBodyResponse>, RpcStatus> {
         let peer_node_id = request.context().peer_node_id().clone();
+        self.budget.check_request(&peer_node_id).await?;
         let message = request.into_message();
         let mut block_event_stream = self.base_node_service.get_block_event_stream();
 
@@ -160,7 +179,7 @@ impl<B: BlockchainBackend + 'static> BaseNodeSyncService for BaseNodeSyncRpcServ
             "Initiating block sync with peer `{}` from height {} to {}", peer_node_id, start_height, end_height,
         );
 
-        let session_token = self.try_add_exclusive_session(peer_node_id).await?;
+        let session_token = self.try_add_sync_session(peer_node_id.clone()).await?;
         // Number of blocks to load and push to the stream before loading the next batch
         const BATCH_SIZE: usize = 2;
         let (tx, rx) = mpsc::channel(BATCH_SIZE);
@@ -260,7 +279,7 @@ impl<B: BlockchainBackend + 'static> BaseNodeSyncService for BaseNodeSyncRpcServ
             .instrument(span),
         );
 
-        Ok(Streaming::new(rx))
+        Ok(Streaming::new(self.budget.meter_stream(peer_node_id, rx)))
     }
 
     #[instrument(level = "trace", name = "sync_rpc::sync_headers", skip(self), err)]
@@ -270,6 +289,7 @@ impl<B: BlockchainBackend + 'static> BaseNodeSyncService for BaseNodeSyncRpcServ
     ) -> Result<Streaming<proto::core::BlockHeader>, RpcStatus> {
         let db = self.db();
         let peer_node_id = request.context().peer_node_id().clone();
+        self.budget.check_request(&peer_node_id).await?;
         let message = request.into_message();
         let hash = message
             .start_hash
@@ -301,7 +321,7 @@ impl<B: BlockchainBackend + 'static> BaseNodeSyncService for BaseNodeSyncRpcServ
             chunk_size
         );
 
-        let session_token = self.try_add_exclusive_session(peer_node_id.clone()).await?;
+        let session_token = self.try_add_sync_session(peer_node_id.clone()).await?;
         let (tx, rx) = mpsc::channel(chunk_size);
         let span = span!(Level::TRACE, "sync_rpc::sync_headers::inner_worker");
         let iter = NonOverlappingIntegerPairIter::new(
@@ -319,8 +339,18 @@ impl<B: BlockchainBackend + 'static> BaseNodeSyncService for BaseNodeSyncRpcServ
                         break;
                     }
                     debug!(target: LOG_TARGET, "Sending headers #{} - #{}", start, end);
+                    // Read from a snapshot so that a block being added does not hold up the stream
                     let headers = db
-                        .fetch_headers(start..=end)
+                        .with_snapshot(move |snapshot| {
+                            let mut headers = Vec::new();
+                            for height in start..=end {
+                                match snapshot.fetch_chain_header_by_height(height).optional()? {
+                                    Some(header) => headers.push(header.into_header()),
+                                    None => break,
+                                }
+                            }
+                            Ok(headers)
+                        })
                         .await
                         .map_err(RpcStatus::log_internal_error(LOG_TARGET));
 
@@ -358,7 +388,7 @@ impl<B: BlockchainBackend + 'static> BaseNodeSyncService for BaseNodeSyncRpcServ
             .instrument(span),
         );
 
-        Ok(Streaming::new(rx))
+        Ok(Streaming::new(self.budget.meter_stream(peer_node_id, rx)))
     }
 
     #[instrument(skip(self), err)]
@@ -366,6 +396,7 @@ impl<B: BlockchainBackend + 'static> BaseNodeSyncService for BaseNodeSyncRpcServ
         &self,
         request: Request<u64>,
     ) -> Result<Response<proto::core::BlockHeader>, RpcStatus> {
+        self.budget.check_request(request.context().peer_node_id()).await?;
         let height = request.into_message();
         let header = self
             .db()
@@ -386,6 +417,7 @@ impl<B: BlockchainBackend + 'static> BaseNodeSyncService for BaseNodeSyncRpcServ
         const MAX_ALLOWED_HEADER_COUNT: u64 = 1000;
 
         let peer = request.context().peer_node_id().clone();
+        self.budget.check_request(&peer).await?;
         let message = request.into_message();
         if message.block_hashes.is_empty() {
             return Err(RpcStatus::bad_request(
@@ -444,7 +476,11 @@ impl<B: BlockchainBackend + 'static> BaseNodeSyncService for BaseNodeSyncRpcServ
     }
 
     #[instrument(skip(self), err)]
-    async fn get_chain_metadata(&self, _: Request<()>) -> Result<Response<proto::base_node::ChainMetadata>, RpcStatus> {
+    async fn get_chain_metadata(
+        &self,
+        request: Request<()>,
+    ) -> Result<Response<proto::base_node::ChainMetadata>, RpcStatus> {
+        self.budget.check_request(request.context().peer_node_id()).await?;
         let chain_metadata = self
             .db()
             .get_chain_metadata()
@@ -459,6 +495,7 @@ impl<B: BlockchainBackend + 'static> BaseNodeSyncService for BaseNodeSyncRpcServ
         request: Request<SyncKernelsRequest>,
     ) -> Result<Streaming<proto::types::TransactionKernel>, RpcStatus> {
         let peer_node_id = request.context().peer_node_id().clone();
+        self.budget.check_request(&peer_node_id).await?;
         let req = request.into_message();
         let (tx, rx) = mpsc::channel(100);
         let db = self.db();
@@ -487,7 +524,7 @@ impl<B: BlockchainBackend + 'static> BaseNodeSyncService for BaseNodeSyncRpcServ
             return Err(RpcStatus::bad_request("start header height is after end header"));
         }
 
-        let session_token = self.try_add_exclusive_session(peer_node_id).await?;
+        let session_token = self.try_add_sync_session(peer_node_id.clone()).await?;
         task::spawn(async move {
             // Move session token into task
             let peer_node_id = session_token;
@@ -495,8 +532,19 @@ impl<B: BlockchainBackend + 'static> BaseNodeSyncService for BaseNodeSyncRpcServ
                 if tx.is_closed() {
                     break;
                 }
+                // The kernels and the header of the next block are read from the same snapshot, so that a block being
+                // added does not hold up the stream and a reorg cannot be observed half way
+                let next_height = current_height + 1;
                 let res = db
-                    .fetch_kernels_in_block(current_header_hash)
+                    .with_snapshot(move |snapshot| {
+                        let kernels = snapshot.fetch_kernels_in_block(&current_header_hash)?;
+                        let next_header = if next_height <= end_height {
+                            snapshot.fetch_chain_header_by_height(next_height).optional()?
+                        } else {
+                            None
+                        };
+                        Ok((kernels, next_header))
+                    })
                     .await
                     .map_err(RpcStatus::log_internal_error(LOG_TARGET));
 
@@ -508,8 +556,8 @@ impl<B: BlockchainBackend + 'static> BaseNodeSyncService for BaseNodeSyncRpcServ
                     break;
                 }
 
-                match res {
-                    Ok(kernels) if kernels.is_empty() => {
+                let next_header = match res {
+                    Ok((kernels, _)) if kernels.is_empty() => {
                         let _result = tx
                             .send(Err(RpcStatus::general(&format!(
                                 "No kernels in block {}",
@@ -518,7 +566,7 @@ impl<B: BlockchainBackend + 'static> BaseNodeSyncService for BaseNodeSyncRpcServ
                             .await;
                         break;
                     },
-                    Ok(kernels) => {
+                    Ok((kernels, next_header)) => {
                         debug!(
                             target: LOG_TARGET,
                             "Streaming kernels {} to {}",
@@ -531,25 +579,22 @@ impl<B: BlockchainBackend + 'static> BaseNodeSyncService for BaseNodeSyncRpcServ
                         if utils::mpsc::send_all(&tx, kernels).await.is_err() {
                             break;
                         }
+                        next_header
                     },
                     Err(err) => {
                         let _result = tx.send(Err(err)).await;
                         break;
                     },
-                }
+                };
 
-                current_height += 1;
+                current_height = next_height;
 
                 if current_height <= end_height {
-                    let res = db
-                        .fetch_header(current_height)
-                        .await
-                        .map_err(RpcStatus::log_internal_error(LOG_TARGET));
-                    match res {
-                        Ok(Some(header)) => {
-                            current_header_hash = header.hash();
+                    match next_header {
+                        Some(header) => {
+                            current_header_hash = *header.hash();
                         },
-                        Ok(None) => {
+                        None => {
                             let _result = tx
                                 .send(Err(RpcStatus::not_found(&format!(
                                     "Could not find header #{} while streaming UTXOs after position {}",
@@ -558,13 +603,6 @@ impl<B: BlockchainBackend + 'static> BaseNodeSyncService for BaseNodeSyncRpcServ
                                 .await;
                             break;
                         },
-                        Err(err) => {
-                            error!(target: LOG_TARGET, "DB error while streaming kernels: {}", err);
-                            let _result = tx
-                                .send(Err(RpcStatus::general("DB error while streaming kernels")))
-                                .await;
-                            break;
-                        },
                     }
                 }
             }
@@ -575,13 +613,14 @@ impl<B: BlockchainBackend + 'static> BaseNodeSyncService for BaseNodeSyncRpcServ
                 "Kernel sync round complete for peer `{}`.", peer_node_id,
             );
         });
-        Ok(Streaming::new(rx))
+        Ok(Streaming::new(self.budget.meter_stream(peer_node_id, rx)))
     }
 
     #[instrument(skip(self), err)]
     async fn sync_utxos(&self, request: Request<SyncUtxosRequest>) -> Result<Streaming<SyncUtxosResponse>, RpcStatus> {
         let req = request.message();
-        let peer_node_id = request.context().peer_node_id();
+        let peer_node_id = request.context().peer_node_id().clone();
+        self.budget.check_request(&peer_node_id).await?;
         debug!(
             target: LOG_TARGET,
             "Received sync_utxos request from header {} to {} (start = {}, include_pruned_utxos = {}, \
@@ -593,11 +632,11 @@ impl<B: BlockchainBackend + 'static> BaseNodeSyncService for BaseNodeSyncRpcServ
             req.include_deleted_bitmaps
         );
 
-        let session_token = self.try_add_exclusive_session(peer_node_id.clone()).await?;
+        let session_token = self.try_add_sync_session(peer_node_id.clone()).await?;
         let (tx, rx) = mpsc::channel(200);
         let task = SyncUtxosTask::new(self.db(), session_token);
         task.run(request, tx).await?;
 
-        Ok(Streaming::new(rx))
+        Ok(Streaming::new(self.budget.meter_stream(peer_node_id, rx)))
     }
 }
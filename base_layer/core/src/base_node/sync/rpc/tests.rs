@@ -21,7 +21,10 @@
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use futures::StreamExt;
-use tari_comms::protocol::rpc::{mock::RpcRequestMock, RpcStatusCode};
+use tari_comms::{
+    protocol::rpc::{mock::RpcRequestMock, RpcStatusCode},
+    test_utils::mocks::create_connectivity_mock,
+};
 use tari_service_framework::reply_channel;
 use tari_test_utils::{streams::convert_mpsc_to_stream, unpack_enum};
 use tempfile::{tempdir, TempDir};
@@ -52,9 +55,12 @@ fn setup() -> (
     let (req_tx, _) = reply_channel::unbounded();
     let (block_tx, _) = reply_channel::unbounded();
     let (block_event_tx, _) = broadcast::channel(1);
+    let (connectivity, _) = create_connectivity_mock();
     let service = BaseNodeSyncRpcService::new(
         db.clone().into(),
         LocalNodeCommsInterface::new(req_tx, block_tx, block_event_tx),
+        Default::default(),
+        connectivity,
     );
     (service, db, request_mock, tmp)
 }
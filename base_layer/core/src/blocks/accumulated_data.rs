@@ -312,6 +312,8 @@ impl BlockHeaderAccumulatedDataBuilder<'_> {
             hash,
             total_kernel_offset,
             achieved_difficulty: achieved_target.achieved(),
+            // The product of two u64 accumulated difficulties always fits in a u128, and each accumulated difficulty
+            // is itself checked for overflow above
             total_accumulated_difficulty: u128::from(randomx_diff.as_u64()) * u128::from(sha3x_diff.as_u64()),
             accumulated_randomx_difficulty: randomx_diff,
             accumulated_sha3x_difficulty: sha3x_diff,
@@ -30,13 +30,14 @@ use std::{
 
 use borsh::{BorshDeserialize, BorshSerialize};
 use log::*;
+use rand::{rngs::OsRng, RngCore};
 use serde::{Deserialize, Serialize};
 use tari_common_types::types::{FixedHash, PrivateKey};
 use tari_utilities::hex::Hex;
 use thiserror::Error;
 
 use crate::{
-    blocks::BlockHeader,
+    blocks::{BlockHeader, ShortKernelId, ShortKernelIdGenerator},
     consensus::ConsensusConstants,
     proof_of_work::ProofOfWork,
     transactions::{
@@ -256,8 +257,49 @@ pub struct NewBlock {
     pub coinbase_kernel: TransactionKernel,
     /// Coinbase output of the block
     pub coinbase_output: TransactionOutput,
-    /// The scalar `s` component of the kernel excess signatures of the transactions contained in the block.
+    /// The scalar `s` component of the kernel excess signatures of the transactions contained in the block. These are
+    /// only sent to peers that do not advertise support for short kernel IDs.
     pub kernel_excess_sigs: Vec<PrivateKey>,
+    /// The nonce used to derive the short kernel IDs of this announcement
+    pub short_id_nonce: u64,
+    /// The short IDs of the non-coinbase kernels contained in the block
+    pub short_kernel_ids: Vec<ShortKernelId>,
+}
+
+impl NewBlock {
+    /// Returns true if the block contains no transactions other than the coinbase
+    pub fn is_coinbase_only(&self) -> bool {
+        self.kernel_excess_sigs.is_empty() && self.short_kernel_ids.is_empty()
+    }
+
+    pub fn short_kernel_id_generator(&self) -> ShortKernelIdGenerator {
+        ShortKernelIdGenerator::new(self.header.hash(), self.short_id_nonce)
+    }
+
+    /// Returns this announcement for peers that reconcile blocks from short kernel IDs, without the kernel excess
+    /// signatures
+    pub fn without_kernel_excess_sigs(&self) -> Self {
+        Self {
+            header: self.header.clone(),
+            coinbase_kernel: self.coinbase_kernel.clone(),
+            coinbase_output: self.coinbase_output.clone(),
+            kernel_excess_sigs: Vec::new(),
+            short_id_nonce: self.short_id_nonce,
+            short_kernel_ids: self.short_kernel_ids.clone(),
+        }
+    }
+
+    /// Returns this announcement for peers that do not support short kernel IDs, without the short kernel IDs
+    pub fn without_short_kernel_ids(&self) -> Self {
+        Self {
+            header: self.header.clone(),
+            coinbase_kernel: self.coinbase_kernel.clone(),
+            coinbase_output: self.coinbase_output.clone(),
+            kernel_excess_sigs: self.kernel_excess_sigs.clone(),
+            short_id_nonce: 0,
+            short_kernel_ids: Vec::new(),
+        }
+    }
 }
 
 impl From<&Block> for NewBlock {
@@ -277,17 +319,24 @@ impl From<&Block> for NewBlock {
             .cloned()
             .expect("Invalid block given to NewBlock::from, no coinbase output");
 
+        let generator = ShortKernelIdGenerator::new(block.hash(), OsRng.next_u64());
+        let (kernel_excess_sigs, short_kernel_ids) = block
+            .body
+            .kernels()
+            .iter()
+            .filter(|k| !k.features.contains(KernelFeatures::COINBASE_KERNEL))
+            .map(|kernel| {
+                let excess_sig = kernel.excess_sig.get_signature();
+                (excess_sig.clone(), generator.generate(excess_sig))
+            })
+            .unzip();
         Self {
             header: block.header.clone(),
             coinbase_kernel,
             coinbase_output,
-            kernel_excess_sigs: block
-                .body
-                .kernels()
-                .iter()
-                .filter(|k| !k.features.contains(KernelFeatures::COINBASE_KERNEL))
-                .map(|kernel| kernel.excess_sig.get_signature().clone())
-                .collect(),
+            kernel_excess_sigs,
+            short_id_nonce: generator.nonce(),
+            short_kernel_ids,
         }
     }
 }
@@ -334,6 +334,16 @@ mod test {
         assert_eq!(h2.prev_hash, hash1, "Previous hash");
     }
 
+    // Golden vector: the mining hash is part of the Sha3x pre-image computed by external miners
+    #[test]
+    fn mining_hash_golden_vector() {
+        let header = crate::proof_of_work::sha3x_test::get_golden_header();
+        assert_eq!(
+            header.mining_hash().to_hex(),
+            "e45d2aa8726dcc19fa35ef7ea8dd82dde6b239b610994437610bdf15465807ab"
+        );
+    }
+
     #[test]
     fn test_timing_stats() {
         let headers = vec![500, 350, 300, 210, 100u64]
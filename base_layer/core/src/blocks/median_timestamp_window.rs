@@ -0,0 +1,155 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::collections::VecDeque;
+
+use tari_utilities::epoch_time::EpochTime;
+
+/// The timestamps of the most recent blocks of a chain, in chain order. A new block's timestamp may not be less than
+/// the median of this window, nor greater than the future time limit (FTL).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MedianTimestampWindow {
+    capacity: usize,
+    timestamps: VecDeque<EpochTime>,
+}
+
+impl MedianTimestampWindow {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            timestamps: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Creates a window from timestamps given in chain order, keeping only the most recent `capacity` of them
+    pub fn from_timestamps<I: IntoIterator<Item = EpochTime>>(capacity: usize, timestamps: I) -> Self {
+        let mut window = Self::new(capacity);
+        for timestamp in timestamps {
+            window.push(timestamp);
+        }
+        window
+    }
+
+    /// Adds the timestamp of a new tip, returning the oldest timestamp if it was evicted from a full window
+    pub fn push(&mut self, timestamp: EpochTime) -> Option<EpochTime> {
+        if self.capacity == 0 {
+            return Some(timestamp);
+        }
+        let evicted = if self.is_full() {
+            self.timestamps.pop_front()
+        } else {
+            None
+        };
+        self.timestamps.push_back(timestamp);
+        evicted
+    }
+
+    /// Removes the timestamp of the current tip, e.g. when the tip is rewound
+    pub fn pop(&mut self) -> Option<EpochTime> {
+        self.timestamps.pop_back()
+    }
+
+    pub fn len(&self) -> usize {
+        self.timestamps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.timestamps.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.timestamps.len() >= self.capacity
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The timestamps in the window in ascending order, as required by header validation
+    pub fn sorted(&self) -> Vec<EpochTime> {
+        let mut timestamps = self.timestamps.iter().copied().collect::<Vec<_>>();
+        timestamps.sort_unstable();
+        timestamps
+    }
+
+    /// The median timestamp of the window, or `None` if the window is empty. For an even number of timestamps this is
+    /// the average of the two middle timestamps, matching header validation.
+    pub fn median(&self) -> Option<EpochTime> {
+        let timestamps = self.sorted();
+        if timestamps.is_empty() {
+            return None;
+        }
+        let mid_index = timestamps.len() / 2;
+        if timestamps.len() % 2 == 0 {
+            Some((timestamps[mid_index - 1] + timestamps[mid_index]) / 2)
+        } else {
+            Some(timestamps[mid_index])
+        }
+    }
+
+    /// Adjusts `proposed` so that it is accepted as the timestamp of the next block: not less than the median and not
+    /// greater than `ftl`. Returns `None` if no such timestamp exists because the median is beyond the FTL.
+    pub fn next_valid_timestamp(&self, proposed: EpochTime, ftl: EpochTime) -> Option<EpochTime> {
+        let timestamp = match self.median() {
+            Some(median) if median > ftl => return None,
+            Some(median) if median > proposed => median.increase(1),
+            _ => proposed,
+        };
+        Some(if timestamp > ftl { ftl } else { timestamp })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn window(timestamps: &[u64]) -> MedianTimestampWindow {
+        MedianTimestampWindow::from_timestamps(3, timestamps.iter().copied().map(EpochTime::from))
+    }
+
+    #[test]
+    fn it_keeps_the_most_recent_timestamps() {
+        let mut window = window(&[10, 30, 20, 40]);
+        assert_eq!(window.len(), 3);
+        assert_eq!(window.sorted(), vec![20.into(), 30.into(), 40.into()]);
+        assert_eq!(window.median(), Some(30.into()));
+        assert_eq!(window.push(5.into()), Some(30.into()));
+        assert_eq!(window.median(), Some(20.into()));
+        assert_eq!(window.pop(), Some(5.into()));
+        assert_eq!(window.median(), Some(30.into()));
+        assert_eq!(MedianTimestampWindow::new(3).median(), None);
+    }
+
+    #[test]
+    fn it_averages_an_even_window() {
+        assert_eq!(window(&[10, 20]).median(), Some(15.into()));
+    }
+
+    #[test]
+    fn it_adjusts_timestamps_into_the_valid_range() {
+        let window = window(&[100, 110, 120]);
+        assert_eq!(window.next_valid_timestamp(130.into(), 200.into()), Some(130.into()));
+        assert_eq!(window.next_valid_timestamp(90.into(), 200.into()), Some(111.into()));
+        assert_eq!(window.next_valid_timestamp(300.into(), 200.into()), Some(200.into()));
+        assert_eq!(window.next_valid_timestamp(130.into(), 105.into()), None);
+    }
+}
@@ -41,6 +41,9 @@ pub use error::BlockError;
 mod block;
 pub use block::{Block, BlockBuilder, BlockValidationError, NewBlock};
 
+mod short_kernel_id;
+pub use short_kernel_id::{ShortKernelId, ShortKernelIdGenerator};
+
 #[cfg(any(feature = "base_node", feature = "base_node_proto"))]
 mod block_header;
 #[cfg(any(feature = "base_node", feature = "base_node_proto"))]
@@ -64,4 +67,9 @@ mod new_blockheader_template;
 #[cfg(feature = "base_node")]
 pub use new_blockheader_template::NewBlockHeaderTemplate;
 
+#[cfg(feature = "base_node")]
+mod median_timestamp_window;
+#[cfg(feature = "base_node")]
+pub use median_timestamp_window::MedianTimestampWindow;
+
 hash_domain!(BlocksHashDomain, "com.tari.base_layer.core.blocks", 0);
@@ -0,0 +1,152 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    convert::TryFrom,
+    fmt::{Display, Formatter},
+};
+
+use blake2::Blake2b;
+use digest::consts::U32;
+use serde::{Deserialize, Serialize};
+use tari_common_types::types::{FixedHash, PrivateKey};
+use tari_crypto::hashing::DomainSeparatedHasher;
+use tari_utilities::{hex::Hex, ByteArray};
+
+use crate::blocks::BlocksHashDomain;
+
+/// A short identifier for a transaction kernel in a block announcement. Peers use short IDs to reconstruct a block from
+/// the transactions in their mempool without the announcement having to carry full kernel excess signatures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ShortKernelId([u8; ShortKernelId::BYTE_SIZE]);
+
+impl ShortKernelId {
+    /// The number of bytes in a short ID. 48 bits keeps collisions within a mempool unlikely while being an order of
+    /// magnitude smaller than an excess signature.
+    pub const BYTE_SIZE: usize = 6;
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Splits a byte string of concatenated short IDs into its constituent IDs
+    pub fn split_bytes(bytes: &[u8]) -> Result<Vec<Self>, String> {
+        if bytes.len() % Self::BYTE_SIZE != 0 {
+            return Err(format!(
+                "Short kernel ids must be a multiple of {} bytes, got {} bytes",
+                Self::BYTE_SIZE,
+                bytes.len()
+            ));
+        }
+        bytes.chunks_exact(Self::BYTE_SIZE).map(Self::try_from).collect()
+    }
+
+    /// Concatenates the given short IDs into a single byte string
+    pub fn join_bytes(ids: &[Self]) -> Vec<u8> {
+        ids.iter().flat_map(|id| id.0).collect()
+    }
+}
+
+impl TryFrom<&[u8]> for ShortKernelId {
+    type Error = String;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        let id = <[u8; Self::BYTE_SIZE]>::try_from(bytes)
+            .map_err(|_| format!("Short kernel id must be {} bytes, got {}", Self::BYTE_SIZE, bytes.len()))?;
+        Ok(Self(id))
+    }
+}
+
+impl Display for ShortKernelId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.to_hex())
+    }
+}
+
+/// Derives short kernel IDs for a particular block announcement. The IDs are keyed on the block hash and a nonce
+/// chosen by the announcing peer, so that an attacker cannot precompute transactions with colliding short IDs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShortKernelIdGenerator {
+    block_hash: FixedHash,
+    nonce: u64,
+}
+
+impl ShortKernelIdGenerator {
+    pub fn new(block_hash: FixedHash, nonce: u64) -> Self {
+        Self { block_hash, nonce }
+    }
+
+    pub fn nonce(&self) -> u64 {
+        self.nonce
+    }
+
+    /// Returns the short ID of the kernel with the given excess signature scalar
+    pub fn generate(&self, excess_sig: &PrivateKey) -> ShortKernelId {
+        let hasher = DomainSeparatedHasher::<Blake2b<U32>, BlocksHashDomain>::new_with_label("short_kernel_id")
+            .chain(self.block_hash.as_slice())
+            .chain(self.nonce.to_le_bytes())
+            .chain(excess_sig.as_bytes());
+        let hash = digest::Digest::finalize(hasher);
+        let mut id = [0u8; ShortKernelId::BYTE_SIZE];
+        id.copy_from_slice(&hash[..ShortKernelId::BYTE_SIZE]);
+        ShortKernelId(id)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::OsRng;
+    use tari_crypto::keys::SecretKey;
+
+    use super::*;
+
+    #[test]
+    fn it_generates_ids_keyed_on_the_block_and_nonce() {
+        let sig = PrivateKey::random(&mut OsRng);
+        let generator = ShortKernelIdGenerator::new(FixedHash::zero(), 1);
+        assert_eq!(generator.generate(&sig), generator.generate(&sig));
+        assert_ne!(
+            generator.generate(&sig),
+            ShortKernelIdGenerator::new(FixedHash::zero(), 2).generate(&sig)
+        );
+        assert_ne!(
+            generator.generate(&sig),
+            ShortKernelIdGenerator::new(FixedHash::from([1u8; 32]), 1).generate(&sig)
+        );
+        assert_ne!(
+            generator.generate(&sig),
+            generator.generate(&PrivateKey::random(&mut OsRng))
+        );
+    }
+
+    #[test]
+    fn it_joins_and_splits_ids() {
+        let generator = ShortKernelIdGenerator::new(FixedHash::zero(), 0);
+        let ids = (0..3)
+            .map(|_| generator.generate(&PrivateKey::random(&mut OsRng)))
+            .collect::<Vec<_>>();
+        let bytes = ShortKernelId::join_bytes(&ids);
+        assert_eq!(bytes.len(), 3 * ShortKernelId::BYTE_SIZE);
+        assert_eq!(ShortKernelId::split_bytes(&bytes).unwrap(), ids);
+        ShortKernelId::split_bytes(&bytes[1..]).unwrap_err();
+    }
+}
@@ -51,6 +51,8 @@ use crate::{
         BlockAddResult,
         BlockchainBackend,
         BlockchainDatabase,
+        BlockchainSnapshot,
+        BurntTotals,
         ChainStorageError,
         DbBasicStats,
         DbTotalSizeStats,
@@ -58,12 +60,18 @@ use crate::{
         HorizonData,
         MmrTree,
         PrunedOutput,
+        PruningProgress,
         TargetDifficulties,
     },
     common::rolling_vec::RollingVec,
+    consensus::deployments::DeploymentStatus,
     proof_of_work::{PowAlgorithm, TargetDifficultyWindow},
     transactions::transaction_components::{TransactionKernel, TransactionOutput},
 };
+
+mod writer;
+pub(crate) use writer::DbWriter;
+
 const LOG_TARGET: &str = "c::bn::async_db";
 
 fn trace_log<F, R>(name: &str, f: F) -> R
@@ -122,8 +130,21 @@ macro_rules! make_async_fn {
     };
 }
 
+macro_rules! make_async_write_fn {
+    (
+     $(#[$outer:meta])*
+     $fn:ident($($param:ident:$ptype:ty),*) -> $rtype:ty, $name:expr) => {
+        $(#[$outer])*
+        pub async fn $fn(&self, $($param: $ptype),*) -> Result<$rtype, ChainStorageError> {
+            let db = self.db.clone();
+            self.db.writer().submit($name, move || db.$fn($($param),*)).await
+        }
+    };
+}
+
 /// Asynchronous version of the BlockchainDatabase.
-/// This component proxies all functions within BlockchainDatabase, executing each on tokio's blocking thread pool.
+/// This component proxies all functions within BlockchainDatabase. Reads are executed on tokio's blocking thread
+/// pool, while writes are queued, in order, for the dedicated writer thread of the underlying BlockchainDatabase.
 pub struct AsyncBlockchainDb<B> {
     db: BlockchainDatabase<B>,
 }
@@ -144,10 +165,30 @@ impl<B: BlockchainBackend + 'static> AsyncBlockchainDb<B> {
     pub fn inner(&self) -> &BlockchainDatabase<B> {
         &self.db
     }
+
+    /// Opens a read-only snapshot of the blockchain and calls `f` with it on tokio's blocking thread pool. The
+    /// snapshot is closed once `f` returns. See [BlockchainDatabase::snapshot].
+    pub async fn with_snapshot<F, R>(&self, f: F) -> Result<R, ChainStorageError>
+    where
+        F: FnOnce(&dyn BlockchainSnapshot) -> Result<R, ChainStorageError> + Send + 'static,
+        R: Send + 'static,
+    {
+        let db = self.db.clone();
+        let mut mdc = vec![];
+        log_mdc::iter(|k, v| mdc.push((k.to_owned(), v.to_owned())));
+        tokio::task::spawn_blocking(move || {
+            log_mdc::extend(mdc);
+            trace_log("with_snapshot", move || {
+                let snapshot = db.snapshot()?;
+                f(&*snapshot)
+            })
+        })
+        .await?
+    }
 }
 
 impl<B: BlockchainBackend + 'static> AsyncBlockchainDb<B> {
-    make_async_fn!(write(transaction: DbTransaction) -> (), "write");
+    make_async_write_fn!(write(transaction: DbTransaction) -> (), "write");
 
     //---------------------------------- Metadata --------------------------------------------//
     make_async_fn!(get_chain_metadata() -> ChainMetadata, "get_chain_metadata");
@@ -161,12 +202,16 @@ impl<B: BlockchainBackend + 'static> AsyncBlockchainDb<B> {
 
     make_async_fn!(fetch_utxos_and_mined_info(hashes: Vec<HashOutput>) -> Vec<Option<UtxoMinedInfo>>, "fetch_utxos_and_mined_info");
 
+    make_async_fn!(fetch_utxos_by_commitment(commitments: Vec<Commitment>) -> Vec<Option<(UtxoMinedInfo, bool)>>, "fetch_utxos_by_commitment");
+
     make_async_fn!(fetch_utxos_in_block(hash: HashOutput, deleted: Option<Arc<Bitmap>>) -> (Vec<PrunedOutput>, Bitmap), "fetch_utxos_in_block");
 
     make_async_fn!(fetch_outputs_in_block(hash: HashOutput) -> Vec<PrunedOutput>, "fetch_outputs_in_block");
 
     make_async_fn!(utxo_count() -> usize, "utxo_count");
 
+    make_async_fn!(orphan_count() -> usize, "orphan_count");
+
     //---------------------------------- Kernel --------------------------------------------//
     make_async_fn!(fetch_kernel_by_excess_sig(excess_sig: Signature) -> Option<(TransactionKernel, HashOutput)>, "fetch_kernel_by_excess_sig");
 
@@ -206,26 +251,28 @@ impl<B: BlockchainBackend + 'static> AsyncBlockchainDb<B> {
 
     make_async_fn!(fetch_last_header() -> BlockHeader, "fetch_last_header");
 
-    make_async_fn!(clear_all_pending_headers() -> usize, "clear_all_pending_headers");
+    make_async_write_fn!(clear_all_pending_headers() -> usize, "clear_all_pending_headers");
 
     make_async_fn!(fetch_last_chain_header() -> ChainHeader, "fetch_last_chain_header");
 
     make_async_fn!(fetch_tip_header() -> ChainHeader, "fetch_tip_header");
 
-    make_async_fn!(insert_valid_headers(headers: Vec<ChainHeader>) -> (), "insert_valid_headers");
+    make_async_write_fn!(insert_valid_headers(headers: Vec<ChainHeader>) -> (), "insert_valid_headers");
 
     //---------------------------------- Block --------------------------------------------//
-    make_async_fn!(add_block(block: Arc<Block>) -> BlockAddResult, "add_block");
+    make_async_write_fn!(add_block(block: Arc<Block>) -> BlockAddResult, "add_block");
 
-    make_async_fn!(cleanup_orphans() -> (), "cleanup_orphans");
+    make_async_write_fn!(cleanup_orphans() -> (), "cleanup_orphans");
 
-    make_async_fn!(cleanup_all_orphans() -> (), "cleanup_all_orphans");
+    make_async_write_fn!(cleanup_all_orphans() -> (), "cleanup_all_orphans");
 
     make_async_fn!(block_exists(block_hash: BlockHash) -> bool, "block_exists");
 
     make_async_fn!(bad_block_exists(block_hash: BlockHash) -> bool, "bad_block_exists");
 
-    make_async_fn!(add_bad_block(hash: BlockHash, height: u64) -> (), "add_bad_block");
+    make_async_fn!(is_block_invalidated(block_hash: BlockHash) -> bool, "is_block_invalidated");
+
+    make_async_write_fn!(add_bad_block(hash: BlockHash, height: u64) -> (), "add_bad_block");
 
     make_async_fn!(fetch_block(height: u64, compact: bool) -> HistoricalBlock, "fetch_block");
 
@@ -237,6 +284,8 @@ impl<B: BlockchainBackend + 'static> AsyncBlockchainDb<B> {
 
     make_async_fn!(fetch_block_with_kernel(excess_sig: Signature) -> Option<HistoricalBlock>, "fetch_block_with_kernel");
 
+    make_async_fn!(fetch_block_with_kernel_excess(excess: Commitment) -> Option<HistoricalBlock>, "fetch_block_with_kernel_excess");
+
     make_async_fn!(fetch_block_with_utxo(commitment: Commitment) -> Option<HistoricalBlock>, "fetch_block_with_utxo");
 
     make_async_fn!(fetch_block_accumulated_data(hash: HashOutput) -> BlockAccumulatedData, "fetch_block_accumulated_data");
@@ -245,11 +294,17 @@ impl<B: BlockchainBackend + 'static> AsyncBlockchainDb<B> {
 
     //---------------------------------- Misc. --------------------------------------------//
 
-    make_async_fn!(prune_to_height(height: u64) -> (), "prune_to_height");
+    make_async_write_fn!(prune_to_height(height: u64) -> (), "prune_to_height");
+
+    make_async_write_fn!(prune_step(max_blocks: u64) -> PruningProgress, "prune_step");
 
-    make_async_fn!(rewind_to_height(height: u64) -> Vec<Arc<ChainBlock>>, "rewind_to_height");
+    make_async_write_fn!(rewind_to_height(height: u64) -> Vec<Arc<ChainBlock>>, "rewind_to_height");
 
-    make_async_fn!(rewind_to_hash(hash: BlockHash) -> Vec<Arc<ChainBlock>>, "rewind_to_hash");
+    make_async_write_fn!(rewind_to_hash(hash: BlockHash) -> Vec<Arc<ChainBlock>>, "rewind_to_hash");
+
+    make_async_fn!(check_rewind_to_hash(hash: BlockHash) -> (), "check_rewind_to_hash");
+
+    make_async_write_fn!(invalidate_block(hash: BlockHash) -> Vec<Arc<ChainBlock>>, "invalidate_block");
 
     make_async_fn!(fetch_block_timestamps(start_hash: HashOutput) -> RollingVec<EpochTime>, "fetch_block_timestamps");
 
@@ -275,7 +330,11 @@ impl<B: BlockchainBackend + 'static> AsyncBlockchainDb<B> {
 
     make_async_fn!(fetch_template_registrations<T: RangeBounds<u64>>(range: T) -> Vec<TemplateRegistrationEntry>, "fetch_template_registrations");
 
-    make_async_fn!(swap_to_highest_pow_chain() -> (), "swap to highest proof-of-work chain");
+    make_async_fn!(fetch_deployment_statuses(height: u64) -> Vec<DeploymentStatus>, "fetch_deployment_statuses");
+
+    make_async_fn!(fetch_burnt_totals(height: u64) -> Option<BurntTotals>, "fetch_burnt_totals");
+
+    make_async_write_fn!(swap_to_highest_pow_chain() -> (), "swap to highest proof-of-work chain");
 }
 
 impl<B: BlockchainBackend + 'static> From<BlockchainDatabase<B>> for AsyncBlockchainDb<B> {
@@ -420,9 +479,7 @@ mod tests {
 
     impl AsyncBlockchainDb<TempDatabase> {
         pub fn sample() -> Self {
-            Self {
-                db: create_new_blockchain(),
-            }
+            Self::new(create_new_blockchain())
         }
     }
 
@@ -442,4 +499,26 @@ mod tests {
         obj.fetch_total_size_stats().await.unwrap();
         let _trans = obj.write_transaction();
     }
+
+    #[tokio::test]
+    async fn writer_survives_a_panicking_write() {
+        let obj = AsyncBlockchainDb::sample();
+        let err = obj
+            .db
+            .writer()
+            .submit("panics", || -> Result<(), ChainStorageError> { panic!("write failed") })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ChainStorageError::BlockingTaskSpawnError(_)));
+        obj.write(DbTransaction::new()).await.unwrap();
+        obj.clone().cleanup_orphans().await.unwrap();
+    }
+
+    #[test]
+    fn writer_is_shared_by_the_database() {
+        let db = create_new_blockchain();
+        let a = AsyncBlockchainDb::new(db.clone());
+        let b = AsyncBlockchainDb::from(db);
+        assert!(std::ptr::eq(a.db.writer(), b.db.writer()));
+    }
 }
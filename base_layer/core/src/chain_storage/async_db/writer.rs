@@ -0,0 +1,97 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    panic::{self, AssertUnwindSafe},
+    thread,
+};
+
+use log::*;
+use tokio::sync::{mpsc, oneshot};
+
+use super::trace_log;
+use crate::chain_storage::ChainStorageError;
+
+const LOG_TARGET: &str = "c::bn::async_db::writer";
+
+/// The maximum number of write requests that may be queued before callers have to wait for the writer to catch up
+const WRITE_QUEUE_SIZE: usize = 32;
+
+type WriteJob = Box<dyn FnOnce() + Send>;
+
+/// Executes blockchain database writes, in the order they were submitted, on a dedicated thread.
+///
+/// Writes take the exclusive backend lock, so running them on tokio's blocking thread pool lets a burst of writes
+/// tie up many blocking threads that are all waiting on the same lock, while reads queue up behind them in no
+/// particular order. A single writer thread fed by a bounded queue applies back-pressure to writers instead and
+/// leaves the blocking pool free for reads. Each [BlockchainDatabase](crate::chain_storage::BlockchainDatabase) owns
+/// one writer, which is shared by all of its clones, and the thread exits once the database is dropped.
+pub(crate) struct DbWriter {
+    sender: mpsc::Sender<WriteJob>,
+}
+
+impl DbWriter {
+    pub fn spawn() -> Self {
+        let (sender, mut receiver) = mpsc::channel::<WriteJob>(WRITE_QUEUE_SIZE);
+        thread::Builder::new()
+            .name("chain-storage-writer".to_string())
+            .spawn(move || {
+                while let Some(job) = receiver.blocking_recv() {
+                    job();
+                }
+                debug!(target: LOG_TARGET, "Blockchain database dropped. Writer thread exiting.");
+            })
+            .expect("Failed to spawn the blockchain database writer thread");
+        Self { sender }
+    }
+
+    /// Queues `f` to be run on the writer thread and waits for its result
+    pub async fn submit<F, R>(&self, name: &'static str, f: F) -> Result<R, ChainStorageError>
+    where
+        F: FnOnce() -> Result<R, ChainStorageError> + Send + 'static,
+        R: Send + 'static,
+    {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        let mut mdc = vec![];
+        log_mdc::iter(|k, v| mdc.push((k.to_owned(), v.to_owned())));
+        let job: WriteJob = Box::new(move || {
+            log_mdc::extend(mdc);
+            // A panicking write must not take the writer thread, and with it every subsequent write, down with it
+            let result = panic::catch_unwind(AssertUnwindSafe(move || trace_log(name, f))).unwrap_or_else(|_| {
+                Err(ChainStorageError::BlockingTaskSpawnError(format!(
+                    "[{}] panicked on the database writer thread",
+                    name
+                )))
+            });
+            if reply_tx.send(result).is_err() {
+                debug!(target: LOG_TARGET, "[{}] Caller went away before the write completed", name);
+            }
+        });
+        self.sender
+            .send(job)
+            .await
+            .map_err(|_| ChainStorageError::AccessError("Blockchain database writer has shut down".to_string()))?;
+        reply_rx.await.map_err(|_| {
+            ChainStorageError::AccessError("Blockchain database writer stopped before completing the write".to_string())
+        })?
+    }
+}
@@ -1,6 +1,8 @@
 // Copyright 2022 The Tari Project
 // SPDX-License-Identifier: BSD-3-Clause
 
+use std::sync::Arc;
+
 use croaring::Bitmap;
 use tari_common_types::{
     chain_metadata::ChainMetadata,
@@ -20,6 +22,8 @@ use crate::{
     },
     chain_storage::{
         pruned_output::PrunedOutput,
+        BlockchainSnapshotSource,
+        BurntTotals,
         ChainStorageError,
         DbBasicStats,
         DbKey,
@@ -55,6 +59,10 @@ pub trait BlockchainBackend: Send + Sync {
     /// access or integrity issue with the backend.
     fn contains(&self, key: &DbKey) -> Result<bool, ChainStorageError>;
 
+    /// Returns a source of read-only snapshots of this backend. Snapshots opened from the source must not require
+    /// access to the backend itself, so that they can be used while another thread is writing to it.
+    fn snapshot_source(&self) -> Arc<dyn BlockchainSnapshotSource>;
+
     /// Fetches data that is calculated and accumulated for blocks that have been
     /// added to a chain of headers
     fn fetch_chain_header_by_height(&self, height: u64) -> Result<ChainHeader, ChainStorageError>;
@@ -114,6 +122,21 @@ pub trait BlockchainBackend: Send + Sync {
         commitment: &Commitment,
     ) -> Result<Option<HashOutput>, ChainStorageError>;
 
+    /// Returns the hash of the most recent unpruned output with the given commitment on the main chain, whether it has
+    /// been spent or not.
+    fn fetch_output_hash_by_commitment(&self, commitment: &Commitment)
+        -> Result<Option<HashOutput>, ChainStorageError>;
+
+    /// Returns the hash of the main chain block containing the unpruned output with the given commitment, whether it
+    /// has been spent or not. Outputs that have been pruned are not indexed.
+    fn fetch_block_hash_by_output_commitment(
+        &self,
+        commitment: &Commitment,
+    ) -> Result<Option<HashOutput>, ChainStorageError>;
+
+    /// Returns the hash of the main chain block containing the kernel with the given excess
+    fn fetch_block_hash_by_kernel_excess(&self, excess: &Commitment) -> Result<Option<HashOutput>, ChainStorageError>;
+
     /// Fetch all outputs in a block
     fn fetch_outputs_in_block(&self, header_hash: &HashOutput) -> Result<Vec<PrunedOutput>, ChainStorageError>;
 
@@ -158,11 +181,12 @@ pub trait BlockchainBackend: Send + Sync {
     /// Returns the full deleted bitmap at the current blockchain tip
     fn fetch_deleted_bitmap(&self) -> Result<DeletedBitmap, ChainStorageError>;
 
-    /// Delete orphans according to age. Used to keep the orphan pool at a certain capacity
+    /// Delete orphans according to age. Used to keep the orphan pool within a certain capacity and total block weight
     fn delete_oldest_orphans(
         &mut self,
         horizon_height: u64,
         orphan_storage_capacity: usize,
+        orphan_storage_max_weight: u64,
     ) -> Result<(), ChainStorageError>;
 
     /// This gets the monero seed_height. This will return 0, if the seed is unkown
@@ -170,12 +194,18 @@ pub trait BlockchainBackend: Send + Sync {
 
     fn fetch_horizon_data(&self) -> Result<Option<HorizonData>, ChainStorageError>;
 
+    /// Returns the running burnt output totals up to and including the main chain block at `height`, or None if they
+    /// are not known, e.g. for blocks a pruned node synced past
+    fn fetch_burnt_totals(&self, height: u64) -> Result<Option<BurntTotals>, ChainStorageError>;
+
     /// Returns basic database stats for each internal database, such as number of entries and page sizes. This call may
     /// not apply to every database implementation.
     fn get_stats(&self) -> Result<DbBasicStats, ChainStorageError>;
     /// Returns total size information about each internal database. This call may be very slow and will obtain a read
     /// lock for the duration.
     fn fetch_total_size_stats(&self) -> Result<DbTotalSizeStats, ChainStorageError>;
+    /// Flushes all committed writes to durable storage.
+    fn sync(&self) -> Result<(), ChainStorageError>;
 
     /// Returns a (block height/hash) tuple for each mmr position of the height it was spent, or None if it is not spent
     fn fetch_header_hash_by_deleted_mmr_positions(
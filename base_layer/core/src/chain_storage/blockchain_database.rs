@@ -23,17 +23,20 @@
 use std::{
     cmp,
     cmp::Ordering,
-    collections::VecDeque,
+    collections::{HashSet, VecDeque},
     convert::TryFrom,
     mem,
     ops::{Bound, RangeBounds},
+    path::Path,
     sync::{atomic, atomic::AtomicBool, Arc, RwLock, RwLockReadGuard, RwLockWriteGuard},
-    time::Instant,
+    time::{Duration, Instant},
 };
 
 use croaring::Bitmap;
 use log::*;
+use once_cell::sync::OnceCell;
 use serde::{Deserialize, Serialize};
+use tari_common::configuration::serializers;
 use tari_common_types::{
     chain_metadata::ChainMetadata,
     types::{BlockHash, Commitment, FixedHash, HashOutput, PublicKey, Signature},
@@ -54,13 +57,18 @@ use crate::{
         CompleteDeletedBitmap,
         DeletedBitmap,
         HistoricalBlock,
+        MedianTimestampWindow,
         NewBlockTemplate,
         UpdateBlockAccumulatedData,
     },
     chain_storage::{
+        async_db::DbWriter,
         consts::{
             BLOCKCHAIN_DATABASE_ORPHAN_STORAGE_CAPACITY,
+            BLOCKCHAIN_DATABASE_ORPHAN_STORAGE_MAX_WEIGHT,
             BLOCKCHAIN_DATABASE_PRUNED_MODE_PRUNING_INTERVAL,
+            BLOCKCHAIN_DATABASE_PRUNING_BATCH_DELAY,
+            BLOCKCHAIN_DATABASE_PRUNING_BATCH_SIZE,
             BLOCKCHAIN_DATABASE_PRUNING_HORIZON,
         },
         db_transaction::{DbKey, DbTransaction, DbValue},
@@ -69,8 +77,14 @@ use crate::{
         utxo_mined_info::UtxoMinedInfo,
         BlockAddResult,
         BlockchainBackend,
+        BlockchainSnapshot,
+        BlockchainSnapshotSource,
+        BurntTotals,
+        ChainRewind,
         DbBasicStats,
         DbTotalSizeStats,
+        FinalityGadget,
+        FinalityGuard,
         HorizonData,
         MmrTree,
         Optional,
@@ -81,6 +95,7 @@ use crate::{
     common::rolling_vec::RollingVec,
     consensus::{
         chain_strength_comparer::ChainStrengthComparer,
+        deployments::DeploymentStatus,
         ConsensusConstants,
         ConsensusManager,
         DomainSeparatedConsensusHasher,
@@ -91,7 +106,6 @@ use crate::{
         TransactionHashDomain,
     },
     validation::{
-        helpers::calc_median_timestamp,
         CandidateBlockValidator,
         DifficultyCalculator,
         HeaderChainLinkedValidator,
@@ -111,24 +125,61 @@ const LOG_TARGET: &str = "c::cs::database";
 #[serde(deny_unknown_fields)]
 pub struct BlockchainDatabaseConfig {
     pub orphan_storage_capacity: usize,
+    pub orphan_storage_max_weight: u64,
     pub pruning_horizon: u64,
     pub pruning_interval: u64,
+    /// If true, pruning is performed incrementally by a background service instead of while adding blocks
+    pub background_pruning: bool,
+    /// The maximum number of blocks pruned in one batch when pruning in the background
+    pub pruning_batch_size: u64,
+    /// The time to wait between pruning batches when pruning in the background
+    #[serde(with = "serializers::seconds")]
+    pub pruning_batch_delay: Duration,
     pub track_reorgs: bool,
     pub cleanup_orphans_at_startup: bool,
+    /// The maximum number of blocks the node will rewind to reorg onto a stronger chain. Deeper reorgs are refused
+    /// until overridden manually. If not set, reorgs of any depth are permitted.
+    pub max_reorg_depth: Option<u64>,
 }
 
 impl Default for BlockchainDatabaseConfig {
     fn default() -> Self {
         Self {
             orphan_storage_capacity: BLOCKCHAIN_DATABASE_ORPHAN_STORAGE_CAPACITY,
+            orphan_storage_max_weight: BLOCKCHAIN_DATABASE_ORPHAN_STORAGE_MAX_WEIGHT,
             pruning_horizon: BLOCKCHAIN_DATABASE_PRUNING_HORIZON,
             pruning_interval: BLOCKCHAIN_DATABASE_PRUNED_MODE_PRUNING_INTERVAL,
+            background_pruning: false,
+            pruning_batch_size: BLOCKCHAIN_DATABASE_PRUNING_BATCH_SIZE,
+            pruning_batch_delay: Duration::from_secs(BLOCKCHAIN_DATABASE_PRUNING_BATCH_DELAY),
             track_reorgs: false,
             cleanup_orphans_at_startup: false,
+            max_reorg_depth: None,
         }
     }
 }
 
+/// The pruning state of the blockchain database, as returned by [BlockchainDatabase::prune_step]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PruningProgress {
+    /// The height up to which the database is pruned
+    pub pruned_height: u64,
+    /// The height up to which the database should be pruned given the current tip and pruning horizon
+    pub horizon_height: u64,
+}
+
+impl PruningProgress {
+    /// Returns true if the database is pruned up to the pruning horizon
+    pub fn is_complete(&self) -> bool {
+        self.pruned_height >= self.horizon_height
+    }
+
+    /// The number of blocks that still need to be pruned to reach the pruning horizon
+    pub fn remaining(&self) -> u64 {
+        self.horizon_height.saturating_sub(self.pruned_height)
+    }
+}
+
 /// A placeholder struct that contains the two validators that the database uses to decide whether or not a block is
 /// eligible to be added to the database. The `block` validator should perform a full consensus check. The `orphan`
 /// validator needs to check that the block is internally consistent, but can't know whether the PoW is sufficient,
@@ -207,12 +258,24 @@ pub struct BlockchainDatabase<B> {
     consensus_manager: ConsensusManager,
     difficulty_calculator: Arc<DifficultyCalculator>,
     disable_add_block_flag: Arc<AtomicBool>,
+    shutting_down: Arc<AtomicBool>,
+    snapshot_source: Arc<dyn BlockchainSnapshotSource>,
+    finality: FinalityGuard,
+    invalidated_blocks: Arc<RwLock<HashSet<BlockHash>>>,
+    /// The timestamp window ending at the tip with the given hash that new block templates were last prepared on
+    median_timestamps: Arc<RwLock<Option<(BlockHash, MedianTimestampWindow)>>>,
+    writer: Arc<OnceCell<DbWriter>>,
 }
 
 #[allow(clippy::ptr_arg)]
 impl<B> BlockchainDatabase<B>
 where B: BlockchainBackend
 {
+    /// Returns the writer that runs the asynchronous writes to this database, starting it on first use
+    pub(crate) fn writer(&self) -> &DbWriter {
+        self.writer.get_or_init(DbWriter::spawn)
+    }
+
     /// Creates a new `BlockchainDatabase` using the provided backend.
     pub fn new(
         db: B,
@@ -223,6 +286,7 @@ where B: BlockchainBackend
     ) -> Result<Self, ChainStorageError> {
         debug!(target: LOG_TARGET, "BlockchainDatabase config: {:?}", config);
         let is_empty = db.is_empty()?;
+        let snapshot_source = db.snapshot_source();
         let blockchain_db = BlockchainDatabase {
             db: Arc::new(RwLock::new(db)),
             validators,
@@ -230,6 +294,12 @@ where B: BlockchainBackend
             consensus_manager,
             difficulty_calculator: Arc::new(difficulty_calculator),
             disable_add_block_flag: Arc::new(AtomicBool::new(false)),
+            shutting_down: Arc::new(AtomicBool::new(false)),
+            snapshot_source,
+            finality: FinalityGuard::new(config.max_reorg_depth),
+            invalidated_blocks: Arc::new(RwLock::new(HashSet::new())),
+            median_timestamps: Arc::new(RwLock::new(None)),
+            writer: Arc::new(OnceCell::new()),
         };
         let genesis_block = Arc::new(blockchain_db.consensus_manager.get_genesis_block());
         if is_empty {
@@ -317,6 +387,25 @@ where B: BlockchainBackend
         })
     }
 
+    /// Opens a consistent, read-only view of the blockchain as it is at this moment. Unlike the other fetch methods,
+    /// this does not take out the backend lock, so reads from a snapshot are not blocked by a block being added
+    /// concurrently. The snapshot will not see any changes made after it was opened.
+    ///
+    /// Snapshots should be dropped as soon as they are no longer needed. The backend may need to wait for all open
+    /// snapshots to close before it can grow, so a snapshot must never be held by a thread that is writing to this
+    /// database.
+    pub fn snapshot(&self) -> Result<Box<dyn BlockchainSnapshot>, ChainStorageError> {
+        self.snapshot_source.open_snapshot()
+    }
+
+    /// Writes a consistent copy of the blockchain as it is at this moment to the empty or non-existent directory at
+    /// `path`, from which the node can be restored. Like [Self::snapshot], this does not take out the backend lock, so
+    /// blocks can be added while the copy is written. This blocks until the copy is complete and should be called
+    /// from a blocking task.
+    pub fn backup_to(&self, path: &Path) -> Result<(), ChainStorageError> {
+        self.snapshot_source.backup_to(path)
+    }
+
     #[cfg(test)]
     pub fn test_db_write_access(&self) -> Result<RwLockWriteGuard<B>, ChainStorageError> {
         self.db.write().map_err(|e| {
@@ -329,13 +418,33 @@ where B: BlockchainBackend
     }
 
     fn db_write_access(&self) -> Result<RwLockWriteGuard<B>, ChainStorageError> {
-        self.db.write().map_err(|e| {
+        let db = self.db.write().map_err(|e| {
             error!(
                 target: LOG_TARGET,
                 "An attempt to get a write lock on the blockchain backend failed. {:?}", e
             );
             ChainStorageError::AccessError("Write lock on blockchain backend failed".into())
-        })
+        })?;
+        // Checked while holding the lock so that no write can start after `shutdown` has drained in-flight writes
+        if self.is_shutting_down() {
+            return Err(ChainStorageError::DatabaseShuttingDown);
+        }
+        Ok(db)
+    }
+
+    pub fn is_shutting_down(&self) -> bool {
+        self.shutting_down.load(atomic::Ordering::SeqCst)
+    }
+
+    /// Stops the database from accepting any further writes, waits for in-flight writes (e.g. a block add) to complete
+    /// and flushes the backend to disk. Every write is committed atomically, so once this returns the database is
+    /// consistent on disk even if the process is killed. This call blocks until in-flight writes complete and should
+    /// be called from a blocking task.
+    pub fn shutdown(&self) -> Result<(), ChainStorageError> {
+        self.shutting_down.store(true, atomic::Ordering::SeqCst);
+        let db = self.db_read_access()?;
+        // A read lock can only be obtained once the current writer, if any, has released the write lock
+        db.sync()
     }
 
     pub(crate) fn is_add_block_disabled(&self) -> bool {
@@ -419,6 +528,30 @@ where B: BlockchainBackend
         Ok(result)
     }
 
+    /// Returns the mined info of the outputs matching the given commitments, and a boolean indicating if the output was
+    /// spent as of the current tip. Spent outputs are included, None is returned for commitments that have never been
+    /// mined or whose outputs have been pruned.
+    pub fn fetch_utxos_by_commitment(
+        &self,
+        commitments: Vec<Commitment>,
+    ) -> Result<Vec<Option<(UtxoMinedInfo, bool)>>, ChainStorageError> {
+        let db = self.db_read_access()?;
+        let deleted = db.fetch_deleted_bitmap()?;
+
+        let mut result = Vec::with_capacity(commitments.len());
+        for commitment in commitments {
+            let output = match db.fetch_output_hash_by_commitment(&commitment)? {
+                Some(hash) => db.fetch_output(&hash)?,
+                None => None,
+            };
+            result.push(output.map(|mined_info| {
+                let is_spent = deleted.bitmap().contains(mined_info.mmr_position);
+                (mined_info, is_spent)
+            }));
+        }
+        Ok(result)
+    }
+
     pub fn fetch_kernel_by_excess_sig(
         &self,
         excess_sig: Signature,
@@ -764,12 +897,6 @@ where B: BlockchainBackend
 
         body.sort();
         let mut header = BlockHeader::from(header);
-        let prev_block_height = header.height - 1;
-        let min_height = header.height.saturating_sub(
-            self.consensus_manager
-                .consensus_constants(header.height)
-                .median_timestamp_count() as u64,
-        );
 
         let db = self.db_read_access()?;
         let tip_header = db.fetch_tip_header()?;
@@ -796,30 +923,32 @@ where B: BlockchainBackend
             });
         }
 
-        let timestamps = fetch_headers(&*db, min_height, prev_block_height)?
-            .iter()
-            .map(|h| h.timestamp)
-            .collect::<Vec<_>>();
+        let constants = self.consensus_manager.consensus_constants(header.height);
+        let timestamps = self.median_timestamp_window(&*db, &tip_header, constants.median_timestamp_count())?;
         if timestamps.is_empty() {
             return Err(ChainStorageError::DataInconsistencyDetected {
                 function: "prepare_new_block",
                 details: format!(
-                    "No timestamps were returned within heights {} - {} by the database despite the tip header height \
-                     being {}",
-                    min_height,
-                    prev_block_height,
+                    "The median timestamp window of {} header(s) ending at the tip at height {} is empty",
+                    timestamps.capacity(),
                     tip_header.height()
                 ),
             });
         }
 
-        let median_timestamp = calc_median_timestamp(&timestamps);
         // If someone advanced the median timestamp such that the local time is less than the median timestamp, we need
         // to increase the timestamp to be greater than the median timestamp otherwise the block wont be accepted by
-        // nodes
-        if median_timestamp > header.timestamp {
-            header.timestamp = median_timestamp.increase(1);
-        }
+        // nodes. The timestamp must also stay within the future time limit.
+        header.timestamp = timestamps
+            .next_valid_timestamp(header.timestamp, constants.ftl())
+            .ok_or_else(|| {
+                ChainStorageError::InvalidOperation(format!(
+                    "Cannot create a block template at height {}: the median timestamp {} is beyond the future time \
+                     limit",
+                    header.height,
+                    timestamps.median().map(|m| m.to_string()).unwrap_or_default(),
+                ))
+            })?;
         let mut block = Block { header, body };
         let roots = calculate_mmr_roots(&*db, self.rules(), &block)?;
         block.header.kernel_mr = roots.kernel_mr;
@@ -920,6 +1049,13 @@ where B: BlockchainBackend
         if db.contains(&DbKey::BlockHash(block_hash))? {
             return Ok(BlockAddResult::BlockExists);
         }
+        if self.is_block_invalidated(block_hash)? {
+            return Err(ChainStorageError::ValidationError {
+                source: ValidationError::BlockLocallyInvalidated {
+                    hash: block_hash.to_hex(),
+                },
+            });
+        }
         if db.bad_block_exists(block_hash)? {
             return Err(ChainStorageError::ValidationError {
                 source: ValidationError::BadBlockFound {
@@ -936,6 +1072,7 @@ where B: BlockchainBackend
         let block_add_result = add_block(
             &mut *db,
             &self.config,
+            &self.finality,
             &self.consensus_manager,
             &*self.validators.block,
             &*self.validators.header,
@@ -950,12 +1087,19 @@ where B: BlockchainBackend
                 "Best chain is now at height: {}",
                 db.fetch_chain_metadata()?.height_of_longest_chain()
             );
-            // If blocks were added and the node is in pruned mode, perform pruning
-            prune_database_if_needed(&mut *db, self.config.pruning_horizon, self.config.pruning_interval)?;
+            // If blocks were added and the node is in pruned mode, perform pruning, unless the background pruning
+            // service is responsible for it
+            if !self.config.background_pruning {
+                prune_database_if_needed(&mut *db, self.config.pruning_horizon, self.config.pruning_interval)?;
+            }
         }
 
         // Clean up orphan pool
-        if let Err(e) = cleanup_orphans(&mut *db, self.config.orphan_storage_capacity) {
+        if let Err(e) = cleanup_orphans(
+            &mut *db,
+            self.config.orphan_storage_capacity,
+            self.config.orphan_storage_max_weight,
+        ) {
             warn!(target: LOG_TARGET, "Failed to clean up orphans: {}", e);
         }
 
@@ -970,7 +1114,11 @@ where B: BlockchainBackend
     /// Clean out the entire orphan pool
     pub fn cleanup_orphans(&self) -> Result<(), ChainStorageError> {
         let mut db = self.db_write_access()?;
-        cleanup_orphans(&mut *db, self.config.orphan_storage_capacity)?;
+        cleanup_orphans(
+            &mut *db,
+            self.config.orphan_storage_capacity,
+            self.config.orphan_storage_max_weight,
+        )?;
         Ok(())
     }
 
@@ -982,7 +1130,7 @@ where B: BlockchainBackend
     /// Clean out the entire orphan pool
     pub fn cleanup_all_orphans(&self) -> Result<(), ChainStorageError> {
         let mut db = self.db_write_access()?;
-        cleanup_orphans(&mut *db, 0)?;
+        cleanup_orphans(&mut *db, 0, 0)?;
         Ok(())
     }
 
@@ -1004,6 +1152,35 @@ where B: BlockchainBackend
         prune_to_height(&mut *db, height)
     }
 
+    /// Prunes at most `max_blocks` blocks towards the configured pruning horizon and returns the resulting pruning
+    /// progress. The write lock is only held while pruning this batch, so block processing can continue between
+    /// calls.
+    pub fn prune_step(&self, max_blocks: u64) -> Result<PruningProgress, ChainStorageError> {
+        let mut db = self.db_write_access()?;
+        let metadata = db.fetch_chain_metadata()?;
+        if !metadata.is_pruned_node() {
+            return Ok(PruningProgress {
+                pruned_height: metadata.pruned_height(),
+                horizon_height: metadata.pruned_height(),
+            });
+        }
+        let horizon_height = metadata
+            .height_of_longest_chain()
+            .saturating_sub(self.config.pruning_horizon);
+        if metadata.pruned_height() >= horizon_height {
+            return Ok(PruningProgress {
+                pruned_height: metadata.pruned_height(),
+                horizon_height,
+            });
+        }
+        let target_height = cmp::min(horizon_height, metadata.pruned_height().saturating_add(max_blocks));
+        prune_to_height(&mut *db, target_height)?;
+        Ok(PruningProgress {
+            pruned_height: target_height,
+            horizon_height,
+        })
+    }
+
     /// Fetch a block from the blockchain database.
     ///
     /// # Returns
@@ -1075,11 +1252,27 @@ where B: BlockchainBackend
         fetch_block_by_kernel_signature(&*db, excess_sig)
     }
 
-    /// Attempt to fetch the block corresponding to the provided utxo hash from the main chain, if the block is past
-    /// pruning horizon, it will return Ok<None>
+    /// Attempt to fetch the block corresponding to the provided kernel excess from the main chain, if the block is
+    /// past pruning horizon, it will return Ok<None>
+    pub fn fetch_block_with_kernel_excess(
+        &self,
+        excess: Commitment,
+    ) -> Result<Option<HistoricalBlock>, ChainStorageError> {
+        let db = self.db_read_access()?;
+        match db.fetch_block_hash_by_kernel_excess(&excess)? {
+            Some(hash) => fetch_unpruned_block_by_hash(&*db, hash),
+            None => Ok(None),
+        }
+    }
+
+    /// Attempt to fetch the block containing the output with the provided commitment from the main chain, whether
+    /// or not the output has been spent. If the block is past pruning horizon, it will return Ok<None>
     pub fn fetch_block_with_utxo(&self, commitment: Commitment) -> Result<Option<HistoricalBlock>, ChainStorageError> {
         let db = self.db_read_access()?;
-        fetch_block_by_utxo_commitment(&*db, &commitment)
+        match db.fetch_block_hash_by_output_commitment(&commitment)? {
+            Some(hash) => fetch_unpruned_block_by_hash(&*db, hash),
+            None => Ok(None),
+        }
     }
 
     /// Returns true if this block exists in the chain, or is orphaned.
@@ -1138,12 +1331,153 @@ where B: BlockchainBackend
         swap_to_highest_pow_chain(
             &mut *db,
             &self.config,
+            &self.finality,
             &*self.validators.block,
             self.consensus_manager.chain_strength_comparer(),
         )?;
         Ok(())
     }
 
+    /// Checks that rewinding the main chain to the given block hash is permitted by the maximum reorg depth and any
+    /// registered finality gadgets.
+    pub fn check_rewind_to_hash(&self, hash: BlockHash) -> Result<(), ChainStorageError> {
+        let db = self.db_read_access()?;
+        let fork_header = fetch_header_by_block_hash(&*db, hash).or_not_found("BlockHeader", "hash", hash.to_hex())?;
+        let tip_header = db.fetch_tip_header()?;
+        self.finality.check_rewind(&ChainRewind {
+            tip_height: tip_header.height(),
+            tip_hash: *tip_header.hash(),
+            fork_height: fork_header.height,
+            fork_hash: hash,
+        })
+    }
+
+    /// Manually marks the block with the given hash as invalid. If the block is part of the main chain, the chain is
+    /// rewound to the block's parent. The block and all of its known descendants are removed from the orphan pool and
+    /// the block will be rejected as locally invalidated until the node is restarted, after which it is reconsidered.
+    /// Peers that send the block are not banned for it, since the block was only rejected by this node's operator.
+    /// Returns the blocks that were removed from the main chain.
+    ///
+    /// The operation will fail if
+    /// * The block is the genesis block
+    /// * Rewinding to the block's parent is not permitted by the maximum reorg depth or a finality gadget
+    pub fn invalidate_block(&self, hash: BlockHash) -> Result<Vec<Arc<ChainBlock>>, ChainStorageError> {
+        let mut db = self.db_write_access()?;
+        let mut removed_blocks = Vec::new();
+        if let Some(header) = fetch_header_by_block_hash(&*db, hash)? {
+            if header.height == 0 {
+                return Err(ChainStorageError::InvalidOperation(
+                    "The genesis block cannot be invalidated".to_string(),
+                ));
+            }
+            let tip_header = db.fetch_tip_header()?;
+            self.finality.check_rewind(&ChainRewind {
+                tip_height: tip_header.height(),
+                tip_hash: *tip_header.hash(),
+                fork_height: header.height - 1,
+                fork_hash: header.prev_hash,
+            })?;
+            info!(
+                target: LOG_TARGET,
+                "Invalidating main chain block #{} ({}), rewinding to height {}",
+                header.height,
+                hash.to_hex(),
+                header.height - 1
+            );
+            removed_blocks = rewind_to_height(&mut *db, header.height - 1)?;
+        }
+
+        // Remove the block and its descendants from the orphan pool, deleting the leaves first so that no orphan
+        // chain tip is left pointing into the invalidated branch
+        let mut branch = vec![hash];
+        let mut i = 0;
+        while i < branch.len() {
+            let children = db.fetch_orphan_children_of(branch[i])?;
+            branch.extend(children.iter().map(|child| child.hash()));
+            i += 1;
+        }
+        let mut txn = DbTransaction::new();
+        for orphan_hash in branch.into_iter().rev() {
+            txn.delete_orphan(orphan_hash);
+        }
+        db.write(txn)?;
+
+        self.invalidated_blocks
+            .write()
+            .map_err(|e| {
+                error!(
+                    target: LOG_TARGET,
+                    "An attempt to get a write lock on the invalidated blocks failed. {:?}", e
+                );
+                ChainStorageError::AccessError("Write lock on invalidated blocks failed".into())
+            })?
+            .insert(hash);
+        Ok(removed_blocks)
+    }
+
+    /// Returns the timestamp window of the `capacity` blocks ending at the tip. The window is kept between calls and
+    /// advanced when the tip is the child of the block it last ended at, so that it is only rebuilt from the database
+    /// after the tip was reorged or rewound.
+    fn median_timestamp_window(
+        &self,
+        db: &B,
+        tip_header: &ChainHeader,
+        capacity: usize,
+    ) -> Result<MedianTimestampWindow, ChainStorageError> {
+        let mut median_timestamps = self.median_timestamps.write().map_err(|e| {
+            error!(
+                target: LOG_TARGET,
+                "An attempt to get a write lock on the median timestamps failed. {:?}", e
+            );
+            ChainStorageError::AccessError("Write lock on median timestamps failed".into())
+        })?;
+        let window = match median_timestamps.take() {
+            Some((hash, window)) if hash == *tip_header.hash() && window.capacity() == capacity => window,
+            Some((hash, mut window)) if hash == tip_header.header().prev_hash && window.capacity() == capacity => {
+                window.push(tip_header.header().timestamp);
+                window
+            },
+            _ => {
+                let min_height = (tip_header.height() + 1).saturating_sub(capacity as u64);
+                MedianTimestampWindow::from_timestamps(
+                    capacity,
+                    fetch_headers(db, min_height, tip_header.height())?
+                        .iter()
+                        .map(|h| h.timestamp),
+                )
+            },
+        };
+        *median_timestamps = Some((*tip_header.hash(), window.clone()));
+        Ok(window)
+    }
+
+    /// Returns true if the block was manually invalidated with [BlockchainDatabase::invalidate_block].
+    pub fn is_block_invalidated(&self, hash: BlockHash) -> Result<bool, ChainStorageError> {
+        let invalidated_blocks = self.invalidated_blocks.read().map_err(|e| {
+            error!(
+                target: LOG_TARGET,
+                "An attempt to get a read lock on the invalidated blocks failed. {:?}", e
+            );
+            ChainStorageError::AccessError("Read lock on invalidated blocks failed".into())
+        })?;
+        Ok(invalidated_blocks.contains(&hash))
+    }
+
+    /// Registers a hook that is consulted before any chain reorg, allowing it to veto rewinds.
+    pub fn register_finality_gadget(&self, gadget: Arc<dyn FinalityGadget>) -> Result<(), ChainStorageError> {
+        self.finality.register_gadget(gadget)
+    }
+
+    /// Permits the next reorg that exceeds the configured maximum reorg depth.
+    pub fn allow_next_deep_reorg(&self) {
+        self.finality.allow_next_deep_reorg();
+    }
+
+    /// Returns true if a manual override for the next deep reorg has been given and not yet used.
+    pub fn is_deep_reorg_allowed(&self) -> bool {
+        self.finality.is_deep_reorg_allowed()
+    }
+
     pub fn fetch_horizon_data(&self) -> Result<HorizonData, ChainStorageError> {
         let db = self.db_read_access()?;
         Ok(db.fetch_horizon_data()?.unwrap_or_default())
@@ -1234,6 +1568,18 @@ where B: BlockchainBackend
         let (start, end) = (start.unwrap_or(0), end.unwrap());
         db.fetch_template_registrations(start, end)
     }
+
+    /// Returns the running burnt output totals up to and including the main chain block at `height`
+    pub fn fetch_burnt_totals(&self, height: u64) -> Result<Option<BurntTotals>, ChainStorageError> {
+        let db = self.db_read_access()?;
+        db.fetch_burnt_totals(height)
+    }
+
+    /// Returns the status of the deployments defined for `height` on the main chain
+    pub fn fetch_deployment_statuses(&self, height: u64) -> Result<Vec<DeploymentStatus>, ChainStorageError> {
+        let db = self.db_read_access()?;
+        self.consensus_manager.deployment_statuses(&*db, height)
+    }
 }
 
 fn unexpected_result<T>(request: DbKey, response: DbValue) -> Result<T, ChainStorageError> {
@@ -1483,9 +1829,11 @@ fn fetch_orphan<T: BlockchainBackend>(db: &T, hash: BlockHash) -> Result<Block,
     fetch!(db, hash, OrphanBlock)
 }
 
+#[allow(clippy::too_many_arguments)]
 fn add_block<T: BlockchainBackend>(
     db: &mut T,
     config: &BlockchainDatabaseConfig,
+    finality: &FinalityGuard,
     consensus_manager: &ConsensusManager,
     block_validator: &dyn CandidateBlockValidator<T>,
     header_validator: &dyn HeaderChainLinkedValidator<T>,
@@ -1495,6 +1843,7 @@ fn add_block<T: BlockchainBackend>(
     handle_possible_reorg(
         db,
         config,
+        finality,
         consensus_manager,
         block_validator,
         header_validator,
@@ -1690,7 +2039,7 @@ fn fetch_block_by_kernel_signature<T: BlockchainBackend>(
 ) -> Result<Option<HistoricalBlock>, ChainStorageError> {
     match db.fetch_kernel_by_excess_sig(&excess_sig) {
         Ok(kernel) => match kernel {
-            Some((_kernel, hash)) => fetch_block_by_hash(db, hash, false),
+            Some((_kernel, hash)) => fetch_unpruned_block_by_hash(db, hash),
             None => Ok(None),
         },
         Err(_) => Err(ChainStorageError::ValueNotFound {
@@ -1701,18 +2050,21 @@ fn fetch_block_by_kernel_signature<T: BlockchainBackend>(
     }
 }
 
-fn fetch_block_by_utxo_commitment<T: BlockchainBackend>(
+/// Fetches the main chain block with the given hash, or None if the block is below the pruned height and can no
+/// longer be provided in full
+fn fetch_unpruned_block_by_hash<T: BlockchainBackend>(
     db: &T,
-    commitment: &Commitment,
+    hash: BlockHash,
 ) -> Result<Option<HistoricalBlock>, ChainStorageError> {
-    let output = db.fetch_unspent_output_hash_by_commitment(commitment)?;
-    match output {
-        Some(hash) => match db.fetch_output(&hash)? {
-            Some(mined_info) => fetch_block_by_hash(db, mined_info.header_hash, false),
-            None => Ok(None),
-        },
-        None => Ok(None),
+    let header = match fetch_header_by_block_hash(db, hash)? {
+        Some(header) => header,
+        None => return Ok(None),
+    };
+    let (_, is_pruned) = check_for_valid_height(db, header.height)?;
+    if is_pruned {
+        return Ok(None);
     }
+    Ok(Some(fetch_block(db, header.height, false)?))
 }
 
 fn fetch_block_by_hash<T: BlockchainBackend>(
@@ -1887,9 +2239,11 @@ fn rewind_to_hash<T: BlockchainBackend>(
 
 // Checks whether we should add the block as an orphan. If it is the case, the orphan block is added and the chain
 // is reorganised if necessary.
+#[allow(clippy::too_many_arguments)]
 fn handle_possible_reorg<T: BlockchainBackend>(
     db: &mut T,
     config: &BlockchainDatabaseConfig,
+    finality: &FinalityGuard,
     consensus_manager: &ConsensusManager,
     block_validator: &dyn CandidateBlockValidator<T>,
     header_validator: &dyn HeaderChainLinkedValidator<T>,
@@ -1901,7 +2255,7 @@ fn handle_possible_reorg<T: BlockchainBackend>(
     let hash = candidate_block.header.hash();
     insert_orphan_and_find_new_tips(db, candidate_block, header_validator, consensus_manager)?;
     let after_orphans = timer.elapsed();
-    let res = swap_to_highest_pow_chain(db, config, block_validator, chain_strength_comparer);
+    let res = swap_to_highest_pow_chain(db, config, finality, block_validator, chain_strength_comparer);
     trace!(
         target: LOG_TARGET,
         "[handle_possible_reorg] block #{}, insert_orphans in {:.2?}, swap_to_highest in {:.2?} '{}'",
@@ -1947,7 +2301,10 @@ fn reorganize_chain<T: BlockchainBackend>(
                 block_hash,
                 e
             );
-            txn.insert_bad_block(block.header().hash(), block.header().height);
+            // We dont want to mark a block as bad for internal failures
+            if !e.is_internal_error() {
+                txn.insert_bad_block(block.header().hash(), block.header().height);
+            }
             remove_orphan(backend, block_hash)?;
 
             info!(target: LOG_TARGET, "Restoring previous chain after failed reorg.");
@@ -1976,6 +2333,7 @@ fn reorganize_chain<T: BlockchainBackend>(
 fn swap_to_highest_pow_chain<T: BlockchainBackend>(
     db: &mut T,
     config: &BlockchainDatabaseConfig,
+    finality: &FinalityGuard,
     block_validator: &dyn CandidateBlockValidator<T>,
     chain_strength_comparer: &dyn ChainStrengthComparer,
 ) -> Result<BlockAddResult, ChainStorageError> {
@@ -2027,11 +2385,24 @@ fn swap_to_highest_pow_chain<T: BlockchainBackend>(
     }
 
     let reorg_chain = get_orphan_link_main_chain(db, best_fork_header.hash())?;
-    let fork_hash = reorg_chain
+    let fork_header = reorg_chain
         .front()
         .expect("The new orphan block should be in the queue")
-        .header()
-        .prev_hash;
+        .header();
+    let fork_hash = fork_header.prev_hash;
+
+    let rewind = ChainRewind {
+        tip_height: tip_header.height(),
+        tip_hash: *tip_header.hash(),
+        fork_height: fork_header.height.saturating_sub(1),
+        fork_hash,
+    };
+    match finality.check_rewind(&rewind) {
+        Ok(()) => {},
+        // The stronger chain remains in the orphan pool, so the reorg can still be applied once it is permitted
+        Err(ChainStorageError::RewindRefused { .. }) => return Ok(BlockAddResult::OrphanBlock),
+        Err(err) => return Err(err),
+    }
 
     let num_added_blocks = reorg_chain.len();
     let removed_blocks = reorganize_chain(db, block_validator, fork_hash, &reorg_chain)?;
@@ -2183,11 +2554,9 @@ fn insert_orphan_and_find_new_tips<T: BlockchainBackend>(
             return Err(e.into())
         },
         // We dont want to mark a block as bad for internal failures
-        Err(
-            e @ ValidationError::FatalStorageError(_) | e @ ValidationError::IncorrectNumberOfTimestampsProvided { .. },
-        ) => return Err(e.into()),
+        Err(e) if e.is_internal_error() => return Err(e.into()),
         // We dont have to mark the block twice
-        Err(e @ ValidationError::BadBlockFound { .. }) => return Err(e.into()),
+        Err(e) if matches!(e.root_cause(), ValidationError::BadBlockFound { .. }) => return Err(e.into()),
 
         Err(e) => {
             let mut txn = DbTransaction::new();
@@ -2394,13 +2763,17 @@ fn find_strongest_orphan_tip(
 }
 
 // Perform a comprehensive search to remove all the minimum height orphans to maintain the configured orphan pool
-// storage limit. If the node is configured to run in pruned mode then orphan blocks with heights lower than the horizon
-// block height will also be discarded.
-fn cleanup_orphans<T: BlockchainBackend>(db: &mut T, orphan_storage_capacity: usize) -> Result<(), ChainStorageError> {
+// storage and weight limits. If the node is configured to run in pruned mode then orphan blocks with heights lower than
+// the horizon block height will also be discarded.
+fn cleanup_orphans<T: BlockchainBackend>(
+    db: &mut T,
+    orphan_storage_capacity: usize,
+    orphan_storage_max_weight: u64,
+) -> Result<(), ChainStorageError> {
     let metadata = db.fetch_chain_metadata()?;
     let horizon_height = metadata.horizon_block(metadata.height_of_longest_chain());
 
-    db.delete_oldest_orphans(horizon_height, orphan_storage_capacity)
+    db.delete_oldest_orphans(horizon_height, orphan_storage_capacity, orphan_storage_max_weight)
 }
 
 fn prune_database_if_needed<T: BlockchainBackend>(
@@ -2519,6 +2892,12 @@ impl<T> Clone for BlockchainDatabase<T> {
             consensus_manager: self.consensus_manager.clone(),
             difficulty_calculator: self.difficulty_calculator.clone(),
             disable_add_block_flag: self.disable_add_block_flag.clone(),
+            shutting_down: self.shutting_down.clone(),
+            snapshot_source: self.snapshot_source.clone(),
+            finality: self.finality.clone(),
+            invalidated_blocks: self.invalidated_blocks.clone(),
+            median_timestamps: self.median_timestamps.clone(),
+            writer: self.writer.clone(),
         }
     }
 }
@@ -2605,6 +2984,20 @@ mod test {
         }
     }
 
+    #[test]
+    fn it_rejects_writes_after_shutdown() {
+        let db = create_test_blockchain_db();
+        db.shutdown().unwrap();
+        assert!(db.is_shutting_down());
+
+        let mut txn = DbTransaction::new();
+        txn.insert_monero_seed_height(b"test1".to_vec(), 5);
+        let err = db.write(txn).unwrap_err();
+        assert!(matches!(err, ChainStorageError::DatabaseShuttingDown));
+        // Reads are still served while the node winds down
+        assert_eq!(db.get_height().unwrap(), 0);
+    }
+
     mod get_orphan_link_main_chain {
         use super::*;
 
@@ -2670,6 +3063,48 @@ mod test {
         }
     }
 
+    mod invalidate_block {
+        use super::*;
+
+        #[tokio::test]
+        async fn it_rewinds_and_rejects_an_invalidated_main_chain_block() {
+            let db = create_new_blockchain();
+            let (_, main_chain) = create_main_chain(&db, block_specs!(["A->GB"], ["B->A"], ["C->B"])).await;
+            let block_b = main_chain.get("B").unwrap();
+            let block_c = main_chain.get("C").unwrap();
+
+            let removed = db.invalidate_block(*block_b.hash()).unwrap();
+            assert_eq!(removed.len(), 2);
+            assert_eq!(removed[0].hash(), block_c.hash());
+            assert_eq!(removed[1].hash(), block_b.hash());
+            assert_eq!(db.get_height().unwrap(), 1);
+            assert!(!db.block_exists(*block_b.hash()).unwrap());
+            assert!(!db.block_exists(*block_c.hash()).unwrap());
+            let access = db.db_read_access().unwrap();
+            assert!(access.fetch_orphan_chain_tip_by_hash(block_c.hash()).unwrap().is_none());
+            drop(access);
+
+            assert!(db.is_block_invalidated(*block_b.hash()).unwrap());
+            assert!(!db.bad_block_exists(*block_b.hash()).unwrap());
+            let err = db.add_block(block_b.to_arc_block()).unwrap_err();
+            match err {
+                ChainStorageError::ValidationError { source } => {
+                    assert!(matches!(source, ValidationError::BlockLocallyInvalidated { .. }));
+                    assert!(source.get_ban_reason(None).is_none());
+                },
+                err => panic!("unexpected error: {}", err),
+            }
+        }
+
+        #[tokio::test]
+        async fn it_refuses_to_invalidate_the_genesis_block() {
+            let db = create_new_blockchain();
+            let genesis_hash = *db.fetch_chain_header(0).unwrap().hash();
+            let err = db.invalidate_block(genesis_hash).unwrap_err();
+            assert!(matches!(err, ChainStorageError::InvalidOperation(_)));
+        }
+    }
+
     mod insert_orphan_and_find_new_tips {
         use super::*;
 
@@ -2839,6 +3274,40 @@ mod test {
 
         use super::*;
 
+        #[tokio::test]
+        async fn it_refuses_reorgs_deeper_than_the_max_reorg_depth() {
+            let mut test = TestHarness::setup();
+            test.finality = FinalityGuard::new(Some(2));
+
+            let (_, main_chain) =
+                create_main_chain(&test.db, block_specs!(["1a->GB"], ["2a->1a"], ["3a->2a"], ["4a->3a"])).await;
+            let fork_root = main_chain.get("1a").unwrap().clone();
+            let (_, orphan_chain_b) =
+                create_chained_blocks(block_specs!(["2b->GB"], ["3b->2b"], ["4b->3b"], ["5b->4b"]), fork_root).await;
+
+            // 5b makes the fork chain stronger, but switching to it requires rewinding 3 blocks
+            for name in ["2b", "3b", "4b", "5b"] {
+                let block = orphan_chain_b.get(name).unwrap();
+                let result = test.handle_possible_reorg(block.to_arc_block()).unwrap();
+                assert!(result.is_orphaned());
+            }
+            let tip = test.db_write_access().fetch_tip_header().unwrap();
+            assert_eq!(tip.hash(), main_chain.get("4a").unwrap().hash());
+
+            test.finality.allow_next_deep_reorg();
+            let result = swap_to_highest_pow_chain(
+                &mut *test.db_write_access(),
+                &test.config,
+                &test.finality,
+                &*test.post_orphan_body_validator,
+                &*test.chain_strength_comparer,
+            )
+            .unwrap();
+            result.assert_reorg(4, 3);
+            let tip = test.db_write_access().fetch_tip_header().unwrap();
+            assert_eq!(tip.hash(), orphan_chain_b.get("5b").unwrap().hash());
+        }
+
         #[tokio::test]
         async fn it_links_many_orphan_branches_to_main_chain() {
             let test = TestHarness::setup();
@@ -3173,6 +3642,7 @@ mod test {
         let result = handle_possible_reorg(
             &mut *access,
             &Default::default(),
+            &Default::default(),
             &db.consensus_manager,
             &mock_validator,
             &mock_validator,
@@ -3186,6 +3656,7 @@ mod test {
         let result = handle_possible_reorg(
             &mut *access,
             &Default::default(),
+            &Default::default(),
             &db.consensus_manager,
             &mock_validator,
             &mock_validator,
@@ -3198,6 +3669,7 @@ mod test {
         let result = handle_possible_reorg(
             &mut *access,
             &Default::default(),
+            &Default::default(),
             &db.consensus_manager,
             &mock_validator,
             &mock_validator,
@@ -3213,6 +3685,7 @@ mod test {
         let result = handle_possible_reorg(
             &mut *access,
             &Default::default(),
+            &Default::default(),
             &db.consensus_manager,
             &mock_validator,
             &mock_validator,
@@ -3249,6 +3722,7 @@ mod test {
         let result = handle_possible_reorg(
             &mut *access,
             &Default::default(),
+            &Default::default(),
             &db.consensus_manager,
             &mock_validator,
             &mock_validator,
@@ -3261,6 +3735,7 @@ mod test {
         let _error = handle_possible_reorg(
             &mut *access,
             &Default::default(),
+            &Default::default(),
             &db.consensus_manager,
             &MockValidator::new(false),
             &mock_validator,
@@ -3467,6 +3942,7 @@ mod test {
     struct TestHarness {
         db: BlockchainDatabase<TempDatabase>,
         config: BlockchainDatabaseConfig,
+        finality: FinalityGuard,
         consensus: ConsensusManager,
         chain_strength_comparer: Box<dyn ChainStrengthComparer>,
         post_orphan_body_validator: Box<dyn CandidateBlockValidator<TempDatabase>>,
@@ -3484,6 +3960,7 @@ mod test {
             Self {
                 db,
                 config: Default::default(),
+                finality: Default::default(),
                 consensus,
                 chain_strength_comparer,
                 header_validator,
@@ -3500,6 +3977,7 @@ mod test {
             handle_possible_reorg(
                 &mut *access,
                 &self.config,
+                &self.finality,
                 &self.consensus,
                 &*self.post_orphan_body_validator,
                 &*self.header_validator,
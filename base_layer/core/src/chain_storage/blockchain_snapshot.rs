@@ -0,0 +1,85 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::path::Path;
+
+use tari_common_types::{
+    chain_metadata::ChainMetadata,
+    types::{HashOutput, Signature},
+};
+
+use crate::{
+    blocks::ChainHeader,
+    chain_storage::{ChainStorageError, PrunedOutput, UtxoMinedInfo},
+    transactions::transaction_components::{TransactionInput, TransactionKernel},
+};
+
+/// A consistent, read-only view of the blockchain backend at the point in time that it was opened.
+///
+/// Blocks that are added or removed after the snapshot is opened are not visible to it, so a sequence of reads from
+/// one snapshot never observes a half-applied block or reorg. Snapshots are read without holding the
+/// `BlockchainDatabase` lock and so do not wait for, or hold up, block writes. They should be short-lived: a long-lived
+/// snapshot keeps old database pages from being reused and delays database resizes, which wait for all snapshots to
+/// close. A thread must not add blocks while it holds a snapshot.
+#[allow(clippy::ptr_arg)]
+pub trait BlockchainSnapshot {
+    /// Fetches the chain metadata
+    fn fetch_chain_metadata(&self) -> Result<ChainMetadata, ChainStorageError>;
+
+    /// Fetches the chain header at the tip of the main chain
+    fn fetch_tip_header(&self) -> Result<ChainHeader, ChainStorageError>;
+
+    /// Fetches the main chain header at the given height
+    fn fetch_chain_header_by_height(&self, height: u64) -> Result<ChainHeader, ChainStorageError>;
+
+    /// Fetches the main chain header with the given hash, if it exists
+    fn fetch_chain_header_by_block_hash(&self, hash: &HashOutput) -> Result<Option<ChainHeader>, ChainStorageError>;
+
+    /// Fetches all kernels in the block with the given header hash
+    fn fetch_kernels_in_block(&self, header_hash: &HashOutput) -> Result<Vec<TransactionKernel>, ChainStorageError>;
+
+    /// Fetches a kernel and the hash of the block containing it, by its excess signature
+    fn fetch_kernel_by_excess_sig(
+        &self,
+        excess_sig: &Signature,
+    ) -> Result<Option<(TransactionKernel, HashOutput)>, ChainStorageError>;
+
+    /// Fetches all outputs in the block with the given header hash
+    fn fetch_outputs_in_block(&self, header_hash: &HashOutput) -> Result<Vec<PrunedOutput>, ChainStorageError>;
+
+    /// Fetches all inputs in the block with the given header hash
+    fn fetch_inputs_in_block(&self, header_hash: &HashOutput) -> Result<Vec<TransactionInput>, ChainStorageError>;
+
+    /// Fetches an output and the details of the block it was mined in
+    fn fetch_output(&self, output_hash: &HashOutput) -> Result<Option<UtxoMinedInfo>, ChainStorageError>;
+}
+
+/// Opens [BlockchainSnapshot]s of a backend. The source is obtained from the backend once and can then open snapshots
+/// without access to the backend.
+pub trait BlockchainSnapshotSource: Send + Sync {
+    /// Opens a new snapshot of the current state of the backend
+    fn open_snapshot(&self) -> Result<Box<dyn BlockchainSnapshot>, ChainStorageError>;
+
+    /// Writes a consistent copy of the current state of the backend to the empty or non-existent directory at `path`.
+    /// Like a snapshot, the copy is taken without holding the `BlockchainDatabase` lock.
+    fn backup_to(&self, path: &Path) -> Result<(), ChainStorageError>;
+}
@@ -0,0 +1,60 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+use serde::{Deserialize, Serialize};
+
+use crate::transactions::{tari_amount::MicroMinotari, transaction_components::TransactionOutput};
+
+/// The running totals of the burnt outputs on the main chain, up to and including a block
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct BurntTotals {
+    /// The total value of the burnt outputs that reveal their value
+    pub revealed_burnt_value: MicroMinotari,
+    /// The number of burnt outputs
+    pub num_burnt_outputs: u64,
+    /// The number of burnt outputs with a confidential value, which is not included in `revealed_burnt_value`
+    pub num_confidential_burnt_outputs: u64,
+}
+
+impl BurntTotals {
+    /// Adds the burnt outputs in `outputs` to the totals
+    pub fn add_outputs<'a, I: IntoIterator<Item = &'a TransactionOutput>>(&mut self, outputs: I) {
+        for output in outputs.into_iter().filter(|o| o.is_burned()) {
+            self.num_burnt_outputs += 1;
+            match output.revealed_burnt_value() {
+                Some(value) => self.revealed_burnt_value += value,
+                None => self.num_confidential_burnt_outputs += 1,
+            }
+        }
+    }
+
+    /// Returns the totals of the burnt outputs that were added after `earlier`, i.e. the burnt outputs in the blocks
+    /// after the block at which `earlier` was taken, up to and including the block of these totals
+    pub fn since(&self, earlier: &BurntTotals) -> BurntTotals {
+        BurntTotals {
+            revealed_burnt_value: self.revealed_burnt_value.saturating_sub(earlier.revealed_burnt_value),
+            num_burnt_outputs: self.num_burnt_outputs.saturating_sub(earlier.num_burnt_outputs),
+            num_confidential_burnt_outputs: self
+                .num_confidential_burnt_outputs
+                .saturating_sub(earlier.num_confidential_burnt_outputs),
+        }
+    }
+}
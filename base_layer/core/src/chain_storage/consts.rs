@@ -22,7 +22,13 @@
 
 /// The maximum number of orphans that can be stored in the Orphan block pool.
 pub const BLOCKCHAIN_DATABASE_ORPHAN_STORAGE_CAPACITY: usize = 720;
+/// The maximum total weight of the blocks that can be stored in the Orphan block pool.
+pub const BLOCKCHAIN_DATABASE_ORPHAN_STORAGE_MAX_WEIGHT: u64 = 64_000_000;
 /// The pruning horizon that is set for a default configuration of the blockchain db.
 pub const BLOCKCHAIN_DATABASE_PRUNING_HORIZON: u64 = 0;
 /// The chain height interval used to determine when a pruned node should perform pruning.
 pub const BLOCKCHAIN_DATABASE_PRUNED_MODE_PRUNING_INTERVAL: u64 = 50;
+/// The maximum number of blocks pruned in one batch by the background pruning service.
+pub const BLOCKCHAIN_DATABASE_PRUNING_BATCH_SIZE: u64 = 100;
+/// The number of seconds the background pruning service waits between pruning batches.
+pub const BLOCKCHAIN_DATABASE_PRUNING_BATCH_DELAY: u64 = 1;
@@ -55,6 +55,12 @@ pub enum ChainStorageError {
     UnexpectedResult(String),
     #[error("You tried to execute an invalid Database operation: {0}")]
     InvalidOperation(String),
+    #[error("Rewind of {depth} block(s) from tip height {tip_height} refused: {reason}")]
+    RewindRefused {
+        tip_height: u64,
+        depth: u64,
+        reason: String,
+    },
     #[error("DATABASE INCONSISTENCY DETECTED at {function}: {details}")]
     DataInconsistencyDetected { function: &'static str, details: String },
     #[error("There appears to be a critical error on the back end: {0}. Check the logs for more information.")]
@@ -126,6 +132,8 @@ pub enum ChainStorageError {
     BlockError(#[from] BlockError),
     #[error("Add block is currently locked. No blocks may be added using add_block until the flag is cleared.")]
     AddBlockOperationLocked,
+    #[error("The blockchain database is shutting down and no longer accepts writes")]
+    DatabaseShuttingDown,
     #[error("Transaction Error: {0}")]
     TransactionError(#[from] TransactionError),
     #[error("Could not convert data:{0}")]
@@ -0,0 +1,215 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+    RwLock,
+};
+
+use log::*;
+use tari_common_types::types::HashOutput;
+
+use crate::chain_storage::ChainStorageError;
+
+const LOG_TARGET: &str = "c::cs::finality";
+
+/// A proposed rewind of the main chain to a fork block, typically as part of a chain reorg.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainRewind {
+    pub tip_height: u64,
+    pub tip_hash: HashOutput,
+    pub fork_height: u64,
+    pub fork_hash: HashOutput,
+}
+
+impl ChainRewind {
+    /// The number of main chain blocks that would be removed by this rewind
+    pub fn depth(&self) -> u64 {
+        self.tip_height.saturating_sub(self.fork_height)
+    }
+}
+
+/// A hook that is consulted before the main chain is rewound. Finality mechanisms implement this trait to veto
+/// rewinds past blocks that they consider final.
+pub trait FinalityGadget: Send + Sync {
+    /// Returns `Err` with the reason for the veto if the rewind must not be performed.
+    fn check_rewind(&self, rewind: &ChainRewind) -> Result<(), String>;
+}
+
+/// Decides whether a chain rewind may proceed. A rewind is refused if any registered [FinalityGadget] vetoes it, or if
+/// it is deeper than the configured maximum reorg depth and no manual override has been given.
+#[derive(Clone, Default)]
+pub struct FinalityGuard {
+    max_reorg_depth: Option<u64>,
+    gadgets: Arc<RwLock<Vec<Arc<dyn FinalityGadget>>>>,
+    allow_deep_reorg: Arc<AtomicBool>,
+}
+
+impl FinalityGuard {
+    pub fn new(max_reorg_depth: Option<u64>) -> Self {
+        Self {
+            max_reorg_depth,
+            ..Default::default()
+        }
+    }
+
+    pub fn max_reorg_depth(&self) -> Option<u64> {
+        self.max_reorg_depth
+    }
+
+    pub fn register_gadget(&self, gadget: Arc<dyn FinalityGadget>) -> Result<(), ChainStorageError> {
+        self.gadgets
+            .write()
+            .map_err(|_| ChainStorageError::AccessError("Finality gadget lock poisoned".to_string()))?
+            .push(gadget);
+        Ok(())
+    }
+
+    /// Permits the next rewind that exceeds the maximum reorg depth. The override is consumed by that rewind.
+    pub fn allow_next_deep_reorg(&self) {
+        self.allow_deep_reorg.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_deep_reorg_allowed(&self) -> bool {
+        self.allow_deep_reorg.load(Ordering::SeqCst)
+    }
+
+    pub fn check_rewind(&self, rewind: &ChainRewind) -> Result<(), ChainStorageError> {
+        if rewind.depth() == 0 {
+            return Ok(());
+        }
+
+        let gadgets = self
+            .gadgets
+            .read()
+            .map_err(|_| ChainStorageError::AccessError("Finality gadget lock poisoned".to_string()))?;
+        for gadget in gadgets.iter() {
+            if let Err(reason) = gadget.check_rewind(rewind) {
+                return Err(refuse_rewind(rewind, format!("vetoed by finality gadget: {}", reason)));
+            }
+        }
+
+        match self.max_reorg_depth {
+            Some(max_depth) if rewind.depth() > max_depth => {
+                if self.allow_deep_reorg.swap(false, Ordering::SeqCst) {
+                    warn!(
+                        target: LOG_TARGET,
+                        "Manual override given. Allowing rewind of {} block(s) from #{} to fork #{} ({}) which exceeds \
+                         the maximum reorg depth of {}",
+                        rewind.depth(),
+                        rewind.tip_height,
+                        rewind.fork_height,
+                        rewind.fork_hash,
+                        max_depth
+                    );
+                    return Ok(());
+                }
+                Err(refuse_rewind(
+                    rewind,
+                    format!(
+                        "exceeds the maximum reorg depth of {}. If this chain is correct, use the `allow-deep-reorg` \
+                         command to override",
+                        max_depth
+                    ),
+                ))
+            },
+            _ => Ok(()),
+        }
+    }
+}
+
+fn refuse_rewind(rewind: &ChainRewind, reason: String) -> ChainStorageError {
+    error!(
+        target: LOG_TARGET,
+        "************************************************************************************************"
+    );
+    error!(
+        target: LOG_TARGET,
+        "DEEP REORG REFUSED: rewinding {} block(s) from tip #{} ({}) to fork #{} ({}) {}",
+        rewind.depth(),
+        rewind.tip_height,
+        rewind.tip_hash,
+        rewind.fork_height,
+        rewind.fork_hash,
+        reason
+    );
+    error!(
+        target: LOG_TARGET,
+        "************************************************************************************************"
+    );
+    ChainStorageError::RewindRefused {
+        tip_height: rewind.tip_height,
+        depth: rewind.depth(),
+        reason,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct VetoAll;
+
+    impl FinalityGadget for VetoAll {
+        fn check_rewind(&self, _: &ChainRewind) -> Result<(), String> {
+            Err("final".to_string())
+        }
+    }
+
+    fn rewind(depth: u64) -> ChainRewind {
+        ChainRewind {
+            tip_height: 100,
+            tip_hash: HashOutput::zero(),
+            fork_height: 100 - depth,
+            fork_hash: HashOutput::zero(),
+        }
+    }
+
+    #[test]
+    fn it_refuses_rewinds_deeper_than_the_max_reorg_depth() {
+        let guard = FinalityGuard::new(Some(10));
+        guard.check_rewind(&rewind(10)).unwrap();
+        let err = guard.check_rewind(&rewind(11)).unwrap_err();
+        assert!(matches!(err, ChainStorageError::RewindRefused { depth: 11, .. }));
+    }
+
+    #[test]
+    fn it_allows_a_single_deep_reorg_with_override() {
+        let guard = FinalityGuard::new(Some(10));
+        guard.allow_next_deep_reorg();
+        guard.check_rewind(&rewind(20)).unwrap();
+        assert!(!guard.is_deep_reorg_allowed());
+        guard.check_rewind(&rewind(20)).unwrap_err();
+    }
+
+    #[test]
+    fn it_applies_finality_gadget_vetoes() {
+        let guard = FinalityGuard::new(None);
+        guard.check_rewind(&rewind(1)).unwrap();
+        guard.register_gadget(Arc::new(VetoAll)).unwrap();
+        guard.allow_next_deep_reorg();
+        let err = guard.check_rewind(&rewind(1)).unwrap_err();
+        assert!(matches!(err, ChainStorageError::RewindRefused { depth: 1, .. }));
+        guard.check_rewind(&rewind(0)).unwrap();
+    }
+}
@@ -34,11 +34,16 @@ use tari_common_types::{
 };
 use tari_storage::lmdb_store::{db, LMDBBuilder, LMDBConfig, LMDBStore};
 use tari_utilities::{
+    epoch_time::EpochTime,
     hex::{to_hex, Hex},
     ByteArray,
 };
 
-use super::{cursors::KeyPrefixCursor, lmdb::lmdb_get_prefix_cursor};
+use super::{
+    cursors::KeyPrefixCursor,
+    lmdb::lmdb_get_prefix_cursor,
+    snapshot::{LMDBSnapshotSource, SnapshotGate},
+};
 use crate::{
     blocks::{
         Block,
@@ -75,6 +80,7 @@ use crate::{
                 lmdb_replace,
             },
             validator_node_store::ValidatorNodeStore,
+            OrphanIndexRowData,
             TransactionInputRowData,
             TransactionInputRowDataRef,
             TransactionKernelRowData,
@@ -83,6 +89,8 @@ use crate::{
         stats::DbTotalSizeStats,
         utxo_mined_info::UtxoMinedInfo,
         BlockchainBackend,
+        BlockchainSnapshotSource,
+        BurntTotals,
         ChainTipData,
         DbBasicStats,
         DbSize,
@@ -97,6 +105,7 @@ use crate::{
     transactions::{
         aggregated_body::AggregateBody,
         transaction_components::{TransactionInput, TransactionKernel, TransactionOutput, ValidatorNodeRegistration},
+        weight::TransactionWeight,
     },
     MutablePrunedOutputMmr,
     PrunedKernelMmr,
@@ -121,6 +130,7 @@ const LMDB_DB_KERNEL_MMR_SIZE_INDEX: &str = "kernel_mmr_size_index";
 const LMDB_DB_UTXO_MMR_SIZE_INDEX: &str = "utxo_mmr_size_index";
 const LMDB_DB_DELETED_TXO_MMR_POSITION_TO_HEIGHT_INDEX: &str = "deleted_txo_mmr_position_to_height_index";
 const LMDB_DB_UTXO_COMMITMENT_INDEX: &str = "utxo_commitment_index";
+const LMDB_DB_TXO_COMMITMENT_INDEX: &str = "txo_commitment_index";
 const LMDB_DB_UNIQUE_ID_INDEX: &str = "unique_id_index";
 const LMDB_DB_CONTRACT_ID_INDEX: &str = "contract_index";
 const LMDB_DB_ORPHANS: &str = "orphans";
@@ -128,11 +138,13 @@ const LMDB_DB_MONERO_SEED_HEIGHT: &str = "monero_seed_height";
 const LMDB_DB_ORPHAN_HEADER_ACCUMULATED_DATA: &str = "orphan_accumulated_data";
 const LMDB_DB_ORPHAN_CHAIN_TIPS: &str = "orphan_chain_tips";
 const LMDB_DB_ORPHAN_PARENT_MAP_INDEX: &str = "orphan_parent_map_index";
+const LMDB_DB_ORPHAN_ACCESSED_INDEX: &str = "orphan_accessed_index";
 const LMDB_DB_BAD_BLOCK_LIST: &str = "bad_blocks";
 const LMDB_DB_REORGS: &str = "reorgs";
 const LMDB_DB_VALIDATOR_NODES: &str = "validator_nodes";
 const LMDB_DB_VALIDATOR_NODES_MAPPING: &str = "validator_nodes_mapping";
 const LMDB_DB_TEMPLATE_REGISTRATIONS: &str = "template_registrations";
+const LMDB_DB_BURNT_TOTALS: &str = "burnt_totals";
 
 /// HeaderHash(32), mmr_pos(4), hash(32)
 type InputKey = CompositeKey<68>;
@@ -143,58 +155,71 @@ type OutputKey = CompositeKey<68>;
 /// Height(8), Hash(32)
 type ValidatorNodeRegistrationKey = CompositeKey<40>;
 
+/// The names and flags of the LMDB databases that make up the blockchain database
+pub(super) fn lmdb_database_flags() -> [(&'static str, db::Flags); 30] {
+    let flags = db::CREATE;
+    [
+        (LMDB_DB_METADATA, flags | db::INTEGERKEY),
+        (LMDB_DB_HEADERS, flags | db::INTEGERKEY),
+        (LMDB_DB_HEADER_ACCUMULATED_DATA, flags | db::INTEGERKEY),
+        (LMDB_DB_BLOCK_ACCUMULATED_DATA, flags | db::INTEGERKEY),
+        (LMDB_DB_BLOCK_HASHES, flags),
+        (LMDB_DB_UTXOS, flags),
+        (LMDB_DB_INPUTS, flags),
+        (LMDB_DB_TXOS_HASH_TO_INDEX, flags),
+        (LMDB_DB_KERNELS, flags),
+        (LMDB_DB_KERNEL_EXCESS_INDEX, flags),
+        (LMDB_DB_KERNEL_EXCESS_SIG_INDEX, flags),
+        (LMDB_DB_KERNEL_MMR_SIZE_INDEX, flags),
+        (LMDB_DB_UTXO_MMR_SIZE_INDEX, flags),
+        (LMDB_DB_UTXO_COMMITMENT_INDEX, flags),
+        (LMDB_DB_TXO_COMMITMENT_INDEX, flags),
+        (LMDB_DB_UNIQUE_ID_INDEX, flags),
+        (LMDB_DB_CONTRACT_ID_INDEX, flags),
+        (LMDB_DB_DELETED_TXO_MMR_POSITION_TO_HEIGHT_INDEX, flags | db::INTEGERKEY),
+        (LMDB_DB_ORPHANS, flags),
+        (LMDB_DB_ORPHAN_HEADER_ACCUMULATED_DATA, flags),
+        (LMDB_DB_MONERO_SEED_HEIGHT, flags),
+        (LMDB_DB_ORPHAN_CHAIN_TIPS, flags),
+        (LMDB_DB_ORPHAN_PARENT_MAP_INDEX, flags | db::DUPSORT),
+        (LMDB_DB_ORPHAN_ACCESSED_INDEX, flags),
+        (LMDB_DB_BAD_BLOCK_LIST, flags),
+        (LMDB_DB_REORGS, flags | db::INTEGERKEY),
+        (LMDB_DB_VALIDATOR_NODES, flags),
+        (LMDB_DB_VALIDATOR_NODES_MAPPING, flags),
+        (LMDB_DB_TEMPLATE_REGISTRATIONS, flags | db::DUPSORT),
+        (LMDB_DB_BURNT_TOTALS, flags | db::INTEGERKEY),
+    ]
+}
+
 pub fn create_lmdb_database<P: AsRef<Path>>(
     path: P,
     config: LMDBConfig,
     consensus_manager: ConsensusManager,
 ) -> Result<LMDBDatabase, ChainStorageError> {
-    let flags = db::CREATE;
     debug!(target: LOG_TARGET, "Creating LMDB database at {:?}", path.as_ref());
     fs::create_dir_all(&path)?;
 
     let file_lock = acquire_exclusive_file_lock(path.as_ref())?;
 
-    let lmdb_store = LMDBBuilder::new()
+    let builder = LMDBBuilder::new()
         .set_path(path)
         // NOLOCK - No lock required because we manage the DB locking using a RwLock
         .set_env_flags(open::NOLOCK)
         .set_env_config(config)
-        .set_max_number_of_databases(40)
-        .add_database(LMDB_DB_METADATA, flags | db::INTEGERKEY)
-        .add_database(LMDB_DB_HEADERS, flags | db::INTEGERKEY)
-        .add_database(LMDB_DB_HEADER_ACCUMULATED_DATA, flags | db::INTEGERKEY)
-        .add_database(LMDB_DB_BLOCK_ACCUMULATED_DATA, flags | db::INTEGERKEY)
-        .add_database(LMDB_DB_BLOCK_HASHES, flags)
-        .add_database(LMDB_DB_UTXOS, flags)
-        .add_database(LMDB_DB_INPUTS, flags)
-        .add_database(LMDB_DB_TXOS_HASH_TO_INDEX, flags)
-        .add_database(LMDB_DB_KERNELS, flags)
-        .add_database(LMDB_DB_KERNEL_EXCESS_INDEX, flags)
-        .add_database(LMDB_DB_KERNEL_EXCESS_SIG_INDEX, flags)
-        .add_database(LMDB_DB_KERNEL_MMR_SIZE_INDEX, flags)
-        .add_database(LMDB_DB_UTXO_MMR_SIZE_INDEX, flags)
-        .add_database(LMDB_DB_UTXO_COMMITMENT_INDEX, flags)
-        .add_database(LMDB_DB_UNIQUE_ID_INDEX, flags)
-        .add_database(LMDB_DB_CONTRACT_ID_INDEX, flags)
-        .add_database(LMDB_DB_DELETED_TXO_MMR_POSITION_TO_HEIGHT_INDEX, flags | db::INTEGERKEY)
-        .add_database(LMDB_DB_ORPHANS, flags)
-        .add_database(LMDB_DB_ORPHAN_HEADER_ACCUMULATED_DATA, flags)
-        .add_database(LMDB_DB_MONERO_SEED_HEIGHT, flags)
-        .add_database(LMDB_DB_ORPHAN_CHAIN_TIPS, flags)
-        .add_database(LMDB_DB_ORPHAN_PARENT_MAP_INDEX, flags | db::DUPSORT)
-        .add_database(LMDB_DB_BAD_BLOCK_LIST, flags)
-        .add_database(LMDB_DB_REORGS, flags | db::INTEGERKEY)
-        .add_database(LMDB_DB_VALIDATOR_NODES, flags)
-        .add_database(LMDB_DB_VALIDATOR_NODES_MAPPING, flags)
-        .add_database(LMDB_DB_TEMPLATE_REGISTRATIONS, flags | db::DUPSORT)
+        .set_max_number_of_databases(40);
+    let lmdb_store = lmdb_database_flags()
+        .iter()
+        .fold(builder, |builder, (name, flags)| builder.add_database(name, *flags))
         .build()
         .map_err(|err| ChainStorageError::CriticalError(format!("Could not create LMDB store:{}", err)))?;
     debug!(target: LOG_TARGET, "LMDB database creation successful");
     LMDBDatabase::new(&lmdb_store, file_lock, consensus_manager)
 }
 
-/// This is a lmdb-based blockchain database for persistent storage of the chain state.
-pub struct LMDBDatabase {
+/// The LMDB environment and the handles of its databases. These are shared by the [LMDBDatabase] and the handles that
+/// are used to read from snapshots.
+pub struct LMDBDatabases {
     env: Arc<Environment>,
     env_config: LMDBConfig,
     metadata_db: DatabaseRef,
@@ -224,6 +249,8 @@ pub struct LMDBDatabase {
     output_mmr_size_index: DatabaseRef,
     /// Maps commitment -> output_hash
     utxo_commitment_index: DatabaseRef,
+    /// Maps commitment -> <block_hash, output_hash> for all unpruned outputs, spent or unspent
+    txo_commitment_index: DatabaseRef,
     /// Maps unique_id -> output_hash
     unique_id_index: DatabaseRef,
     /// Maps <contract_id, output_type> -> (block_hash, output_hash)
@@ -241,6 +268,8 @@ pub struct LMDBDatabase {
     orphan_chain_tips_db: DatabaseRef,
     /// Maps parent_block_hash -> block_hash
     orphan_parent_map_index: DatabaseRef,
+    /// Maps block_hash -> OrphanIndexRowData
+    orphan_accessed_index: DatabaseRef,
     /// Stores bad blocks by block_hash and height
     bad_blocks: DatabaseRef,
     /// Stores reorgs by epochtime and Reorg
@@ -251,8 +280,24 @@ pub struct LMDBDatabase {
     validator_nodes_mapping: DatabaseRef,
     /// Maps CodeTemplateRegistration <block_height, hash> -> TemplateRegistration
     template_registrations: DatabaseRef,
+    /// Maps height -> BurntTotals
+    burnt_totals_db: DatabaseRef,
+}
+
+/// This is a lmdb-based blockchain database for persistent storage of the chain state.
+pub struct LMDBDatabase {
+    dbs: Arc<LMDBDatabases>,
     _file_lock: Arc<File>,
     consensus_manager: ConsensusManager,
+    snapshot_gate: Arc<SnapshotGate>,
+}
+
+impl Deref for LMDBDatabase {
+    type Target = LMDBDatabases;
+
+    fn deref(&self) -> &Self::Target {
+        &self.dbs
+    }
 }
 
 impl LMDBDatabase {
@@ -263,7 +308,7 @@ impl LMDBDatabase {
     ) -> Result<Self, ChainStorageError> {
         let env = store.env();
 
-        let db = Self {
+        let dbs = LMDBDatabases {
             metadata_db: get_database(store, LMDB_DB_METADATA)?,
             headers_db: get_database(store, LMDB_DB_HEADERS)?,
             header_accumulated_data_db: get_database(store, LMDB_DB_HEADER_ACCUMULATED_DATA)?,
@@ -278,6 +323,7 @@ impl LMDBDatabase {
             kernel_mmr_size_index: get_database(store, LMDB_DB_KERNEL_MMR_SIZE_INDEX)?,
             output_mmr_size_index: get_database(store, LMDB_DB_UTXO_MMR_SIZE_INDEX)?,
             utxo_commitment_index: get_database(store, LMDB_DB_UTXO_COMMITMENT_INDEX)?,
+            txo_commitment_index: get_database(store, LMDB_DB_TXO_COMMITMENT_INDEX)?,
             unique_id_index: get_database(store, LMDB_DB_UNIQUE_ID_INDEX)?,
             contract_index: get_database(store, LMDB_DB_CONTRACT_ID_INDEX)?,
             deleted_txo_mmr_position_to_height_index: get_database(
@@ -289,15 +335,21 @@ impl LMDBDatabase {
             monero_seed_height_db: get_database(store, LMDB_DB_MONERO_SEED_HEIGHT)?,
             orphan_chain_tips_db: get_database(store, LMDB_DB_ORPHAN_CHAIN_TIPS)?,
             orphan_parent_map_index: get_database(store, LMDB_DB_ORPHAN_PARENT_MAP_INDEX)?,
+            orphan_accessed_index: get_database(store, LMDB_DB_ORPHAN_ACCESSED_INDEX)?,
             bad_blocks: get_database(store, LMDB_DB_BAD_BLOCK_LIST)?,
             reorgs: get_database(store, LMDB_DB_REORGS)?,
             validator_nodes: get_database(store, LMDB_DB_VALIDATOR_NODES)?,
             validator_nodes_mapping: get_database(store, LMDB_DB_VALIDATOR_NODES_MAPPING)?,
             template_registrations: get_database(store, LMDB_DB_TEMPLATE_REGISTRATIONS)?,
+            burnt_totals_db: get_database(store, LMDB_DB_BURNT_TOTALS)?,
             env,
             env_config: store.env_config(),
+        };
+        let db = Self {
+            dbs: Arc::new(dbs),
             _file_lock: Arc::new(file_lock),
             consensus_manager,
+            snapshot_gate: Arc::new(SnapshotGate::default()),
         };
 
         run_migrations(&db)?;
@@ -305,6 +357,29 @@ impl LMDBDatabase {
         Ok(db)
     }
 
+    /// Returns a handle to the same LMDB environment and databases. The handle is only used to read from snapshots, the
+    /// exclusive write access guaranteed by `BlockchainDatabase` does not extend to it.
+    pub(super) fn clone_for_snapshots(&self) -> Self {
+        Self {
+            dbs: self.dbs.clone(),
+            _file_lock: self._file_lock.clone(),
+            consensus_manager: self.consensus_manager.clone(),
+            snapshot_gate: self.snapshot_gate.clone(),
+        }
+    }
+
+    pub(super) fn env(&self) -> &Arc<Environment> {
+        &self.env
+    }
+
+    pub(super) fn env_config(&self) -> &LMDBConfig {
+        &self.env_config
+    }
+
+    pub(super) fn snapshot_gate(&self) -> &Arc<SnapshotGate> {
+        &self.snapshot_gate
+    }
+
     /// Try to establish a read lock on the LMDB database. If an exclusive write lock has been previously acquired, this
     /// method will block until that lock is released.
     fn read_transaction(&self) -> Result<ReadTransaction<'_>, ChainStorageError> {
@@ -506,7 +581,49 @@ impl LMDBDatabase {
         Ok(())
     }
 
-    fn all_dbs(&self) -> [(&'static str, &DatabaseRef); 27] {
+    /// Returns the handles of the LMDB databases, keyed by the names they were created with
+    pub(super) fn lmdb_dbs(&self) -> [(&'static str, &DatabaseRef); 30] {
+        [
+            (LMDB_DB_METADATA, &self.metadata_db),
+            (LMDB_DB_HEADERS, &self.headers_db),
+            (LMDB_DB_HEADER_ACCUMULATED_DATA, &self.header_accumulated_data_db),
+            (LMDB_DB_BLOCK_ACCUMULATED_DATA, &self.block_accumulated_data_db),
+            (LMDB_DB_BLOCK_HASHES, &self.block_hashes_db),
+            (LMDB_DB_UTXOS, &self.utxos_db),
+            (LMDB_DB_INPUTS, &self.inputs_db),
+            (LMDB_DB_TXOS_HASH_TO_INDEX, &self.txos_hash_to_index_db),
+            (LMDB_DB_KERNELS, &self.kernels_db),
+            (LMDB_DB_KERNEL_EXCESS_INDEX, &self.kernel_excess_index),
+            (LMDB_DB_KERNEL_EXCESS_SIG_INDEX, &self.kernel_excess_sig_index),
+            (LMDB_DB_KERNEL_MMR_SIZE_INDEX, &self.kernel_mmr_size_index),
+            (LMDB_DB_UTXO_MMR_SIZE_INDEX, &self.output_mmr_size_index),
+            (LMDB_DB_UTXO_COMMITMENT_INDEX, &self.utxo_commitment_index),
+            (LMDB_DB_TXO_COMMITMENT_INDEX, &self.txo_commitment_index),
+            (LMDB_DB_UNIQUE_ID_INDEX, &self.unique_id_index),
+            (LMDB_DB_CONTRACT_ID_INDEX, &self.contract_index),
+            (
+                LMDB_DB_DELETED_TXO_MMR_POSITION_TO_HEIGHT_INDEX,
+                &self.deleted_txo_mmr_position_to_height_index,
+            ),
+            (LMDB_DB_ORPHANS, &self.orphans_db),
+            (
+                LMDB_DB_ORPHAN_HEADER_ACCUMULATED_DATA,
+                &self.orphan_header_accumulated_data_db,
+            ),
+            (LMDB_DB_MONERO_SEED_HEIGHT, &self.monero_seed_height_db),
+            (LMDB_DB_ORPHAN_CHAIN_TIPS, &self.orphan_chain_tips_db),
+            (LMDB_DB_ORPHAN_PARENT_MAP_INDEX, &self.orphan_parent_map_index),
+            (LMDB_DB_ORPHAN_ACCESSED_INDEX, &self.orphan_accessed_index),
+            (LMDB_DB_BAD_BLOCK_LIST, &self.bad_blocks),
+            (LMDB_DB_REORGS, &self.reorgs),
+            (LMDB_DB_VALIDATOR_NODES, &self.validator_nodes),
+            (LMDB_DB_VALIDATOR_NODES_MAPPING, &self.validator_nodes_mapping),
+            (LMDB_DB_TEMPLATE_REGISTRATIONS, &self.template_registrations),
+            (LMDB_DB_BURNT_TOTALS, &self.burnt_totals_db),
+        ]
+    }
+
+    fn all_dbs(&self) -> [(&'static str, &DatabaseRef); 30] {
         [
             ("metadata_db", &self.metadata_db),
             ("headers_db", &self.headers_db),
@@ -522,6 +639,7 @@ impl LMDBDatabase {
             ("kernel_mmr_size_index", &self.kernel_mmr_size_index),
             ("output_mmr_size_index", &self.output_mmr_size_index),
             ("utxo_commitment_index", &self.utxo_commitment_index),
+            ("txo_commitment_index", &self.txo_commitment_index),
             ("contract_index", &self.contract_index),
             ("unique_id_index", &self.unique_id_index),
             (
@@ -536,11 +654,13 @@ impl LMDBDatabase {
             ("monero_seed_height_db", &self.monero_seed_height_db),
             ("orphan_chain_tips_db", &self.orphan_chain_tips_db),
             ("orphan_parent_map_index", &self.orphan_parent_map_index),
+            ("orphan_accessed_index", &self.orphan_accessed_index),
             ("bad_blocks", &self.bad_blocks),
             ("reorgs", &self.reorgs),
             ("validator_nodes", &self.validator_nodes),
             ("validator_nodes_mapping", &self.validator_nodes_mapping),
             ("template_registrations", &self.template_registrations),
+            ("burnt_totals_db", &self.burnt_totals_db),
         ]
     }
 
@@ -560,6 +680,7 @@ impl LMDBDatabase {
             })?;
         // output.output is None
         lmdb_replace(txn, &self.utxos_db, key, &output)?;
+        self.delete_txo_commitment_index_entry(txn, &pruned_output.commitment, &output.hash)?;
         Ok(pruned_output)
     }
 
@@ -583,6 +704,14 @@ impl LMDBDatabase {
             &output_hash,
             "utxo_commitment_index",
         )?;
+        // A commitment can be reused once its previous output has been spent, in which case the most recent output is
+        // indexed
+        lmdb_replace(
+            txn,
+            &self.txo_commitment_index,
+            output.commitment.as_bytes(),
+            &(*header_hash, output_hash),
+        )?;
 
         lmdb_insert(
             txn,
@@ -757,7 +886,27 @@ impl LMDBDatabase {
         let k = block.hash();
         lmdb_insert_dup(txn, &self.orphan_parent_map_index, block.header.prev_hash.deref(), &k)?;
         lmdb_insert(txn, &self.orphans_db, k.as_slice(), &block, "orphans_db")?;
+        let weight = block
+            .body
+            .calculate_weight(&TransactionWeight::latest())
+            .unwrap_or(u64::MAX);
+        lmdb_replace(txn, &self.orphan_accessed_index, k.as_slice(), &OrphanIndexRowData {
+            hash: k,
+            height: block.header.height,
+            weight,
+            accessed_at: EpochTime::now().as_u64(),
+        })?;
+
+        Ok(())
+    }
 
+    /// Marks the orphan as recently used so that it is evicted after orphans that have not been touched since
+    fn touch_orphan(&self, txn: &WriteTransaction<'_>, hash: &HashOutput) -> Result<(), ChainStorageError> {
+        let row: Option<OrphanIndexRowData> = lmdb_get(txn, &self.orphan_accessed_index, hash.as_slice())?;
+        if let Some(mut row) = row {
+            row.accessed_at = EpochTime::now().as_u64();
+            lmdb_replace(txn, &self.orphan_accessed_index, hash.as_slice(), &row)?;
+        }
         Ok(())
     }
 
@@ -780,6 +929,7 @@ impl LMDBDatabase {
             &accumulated_data,
             "orphan_header_accumulated_data_db",
         )?;
+        self.touch_orphan(txn, &accumulated_data.hash)?;
 
         Ok(())
     }
@@ -959,6 +1109,9 @@ impl LMDBDatabase {
             &height,
             "block_accumulated_data_db",
         )?;
+        if lmdb_exists(write_txn, &self.burnt_totals_db, &height)? {
+            lmdb_delete(write_txn, &self.burnt_totals_db, &height, "burnt_totals_db")?;
+        }
 
         self.delete_block_inputs_outputs(write_txn, block_hash.as_slice())?;
         self.delete_block_kernels(write_txn, block_hash.as_slice())?;
@@ -966,6 +1119,27 @@ impl LMDBDatabase {
         Ok(())
     }
 
+    /// Records the burnt totals up to and including the block at `height`. Nothing is recorded if the totals for the
+    /// previous block are not known, as is the case after a horizon sync.
+    fn insert_burnt_totals(
+        &self,
+        txn: &WriteTransaction<'_>,
+        height: u64,
+        outputs: &[TransactionOutput],
+    ) -> Result<(), ChainStorageError> {
+        let mut totals = if height == 0 {
+            BurntTotals::default()
+        } else {
+            match lmdb_get(txn, &self.burnt_totals_db, &(height - 1))? {
+                Some(totals) => totals,
+                None => return Ok(()),
+            }
+        };
+        totals.add_outputs(outputs);
+        lmdb_replace(txn, &self.burnt_totals_db, &height, &totals)?;
+        Ok(())
+    }
+
     fn delete_block_inputs_outputs(
         &self,
         txn: &WriteTransaction<'_>,
@@ -986,6 +1160,7 @@ impl LMDBDatabase {
             )?;
             if let Some(ref output) = utxo.output {
                 let output_hash = output.hash();
+                self.delete_txo_commitment_index_entry(txn, &output.commitment, &output_hash)?;
                 // if an output was already spent in the block, it was never created as unspent, so dont delete it as it
                 // does not exist here
                 if inputs.iter().any(|r| r.input.output_hash() == output_hash) {
@@ -1164,6 +1339,14 @@ impl LMDBDatabase {
                 "orphan_header_accumulated_data_db",
             )?;
         }
+        if lmdb_exists(txn, &self.orphan_accessed_index, hash.as_slice())? {
+            lmdb_delete(
+                txn,
+                &self.orphan_accessed_index,
+                hash.as_slice(),
+                "orphan_accessed_index",
+            )?;
+        }
         lmdb_delete(txn, &self.orphans_db, hash.as_slice(), "orphans_db")?;
         Ok(())
     }
@@ -1241,6 +1424,8 @@ impl LMDBDatabase {
 
         let leaf_count = output_mmr.get_leaf_count();
 
+        self.insert_burnt_totals(txn, header.height, &outputs)?;
+
         // Output hashes added before inputs so that inputs can spend outputs in this transaction (0-conf and combined)
         let mut burned_outputs = Vec::new();
         let outputs = outputs
@@ -1537,6 +1722,25 @@ impl LMDBDatabase {
         Ok(())
     }
 
+    /// Removes the commitment from the txo commitment index if it refers to the given output
+    fn delete_txo_commitment_index_entry(
+        &self,
+        txn: &WriteTransaction<'_>,
+        commitment: &Commitment,
+        output_hash: &HashOutput,
+    ) -> Result<(), ChainStorageError> {
+        let entry = lmdb_get::<_, (HashOutput, HashOutput)>(txn, &self.txo_commitment_index, commitment.as_bytes())?;
+        if entry.map(|(_, hash)| hash == *output_hash).unwrap_or(false) {
+            lmdb_delete(
+                txn,
+                &self.txo_commitment_index,
+                commitment.as_bytes(),
+                "txo_commitment_index",
+            )?;
+        }
+        Ok(())
+    }
+
     fn prune_outputs_at_positions(
         &self,
         write_txn: &WriteTransaction<'_>,
@@ -1598,7 +1802,7 @@ impl LMDBDatabase {
 
     fn fetch_header_accumulated_data_by_height(
         &self,
-        txn: &ReadTransaction,
+        txn: &ConstTransaction<'_>,
         height: u64,
     ) -> Result<Option<BlockHeaderAccumulatedData>, ChainStorageError> {
         lmdb_get(txn, &self.header_accumulated_data_db, &height)
@@ -1608,6 +1812,143 @@ impl LMDBDatabase {
         lmdb_last(txn, &self.headers_db)
     }
 
+    pub(super) fn fetch_chain_metadata_in_txn(
+        &self,
+        txn: &ConstTransaction<'_>,
+    ) -> Result<ChainMetadata, ChainStorageError> {
+        fetch_metadata(txn, &self.metadata_db)
+    }
+
+    pub(super) fn fetch_chain_header_by_height_in_txn(
+        &self,
+        txn: &ConstTransaction<'_>,
+        height: u64,
+    ) -> Result<ChainHeader, ChainStorageError> {
+        let header: BlockHeader =
+            lmdb_get(txn, &self.headers_db, &height)?.ok_or_else(|| ChainStorageError::ValueNotFound {
+                entity: "BlockHeader",
+                field: "height",
+                value: height.to_string(),
+            })?;
+
+        let accum_data = self
+            .fetch_header_accumulated_data_by_height(txn, height)?
+            .ok_or_else(|| ChainStorageError::ValueNotFound {
+                entity: "BlockHeaderAccumulatedData",
+                field: "height",
+                value: height.to_string(),
+            })?;
+
+        let height = header.height;
+        let chain_header = ChainHeader::try_construct(header, accum_data).ok_or_else(|| {
+            ChainStorageError::DataInconsistencyDetected {
+                function: "fetch_chain_header_by_height",
+                details: format!("Mismatch in accumulated data at height #{}", height),
+            }
+        })?;
+
+        Ok(chain_header)
+    }
+
+    /// Fetches the header with the given hash if it is in the main chain
+    #[allow(clippy::ptr_arg)]
+    pub(super) fn fetch_chain_header_by_hash_in_txn(
+        &self,
+        txn: &ConstTransaction<'_>,
+        hash: &HashOutput,
+    ) -> Result<Option<ChainHeader>, ChainStorageError> {
+        match self.fetch_height_from_hash(txn, hash)? {
+            Some(height) => self.fetch_chain_header_by_height_in_txn(txn, height).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    pub(super) fn fetch_tip_header_in_txn(&self, txn: &ConstTransaction<'_>) -> Result<ChainHeader, ChainStorageError> {
+        let metadata = fetch_metadata(txn, &self.metadata_db)?;
+        let height = metadata.height_of_longest_chain();
+        let header = lmdb_get(txn, &self.headers_db, &height)?.ok_or_else(|| ChainStorageError::ValueNotFound {
+            entity: "Header",
+            field: "height",
+            value: height.to_string(),
+        })?;
+        let accumulated_data = self
+            .fetch_header_accumulated_data_by_height(txn, height)?
+            .ok_or_else(|| ChainStorageError::ValueNotFound {
+                entity: "BlockHeaderAccumulatedData",
+                field: "height",
+                value: height.to_string(),
+            })?;
+        let chain_header = ChainHeader::try_construct(header, accumulated_data).ok_or_else(|| {
+            ChainStorageError::DataInconsistencyDetected {
+                function: "fetch_tip_header",
+                details: format!("Accumulated data mismatch at height #{}", height),
+            }
+        })?;
+        Ok(chain_header)
+    }
+
+    #[allow(clippy::ptr_arg)]
+    pub(super) fn fetch_kernels_in_block_in_txn(
+        &self,
+        txn: &ConstTransaction<'_>,
+        header_hash: &HashOutput,
+    ) -> Result<Vec<TransactionKernel>, ChainStorageError> {
+        Ok(lmdb_fetch_matching_after(txn, &self.kernels_db, header_hash.deref())?
+            .into_iter()
+            .map(|f: TransactionKernelRowData| f.kernel)
+            .collect())
+    }
+
+    pub(super) fn fetch_kernel_by_excess_sig_in_txn(
+        &self,
+        txn: &ConstTransaction<'_>,
+        excess_sig: &Signature,
+    ) -> Result<Option<(TransactionKernel, HashOutput)>, ChainStorageError> {
+        let mut key = Vec::<u8>::new();
+        key.extend(excess_sig.get_public_nonce().as_bytes());
+        key.extend(excess_sig.get_signature().as_bytes());
+        if let Some((header_hash, mmr_position, hash)) =
+            lmdb_get::<_, (HashOutput, u32, HashOutput)>(txn, &self.kernel_excess_sig_index, key.as_slice())?
+        {
+            let key = KernelKey::try_from_parts(&[
+                header_hash.as_slice(),
+                mmr_position.to_be_bytes().as_slice(),
+                hash.as_slice(),
+            ])?;
+            Ok(lmdb_get(txn, &self.kernels_db, &key)?
+                .map(|kernel: TransactionKernelRowData| (kernel.kernel, header_hash)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    #[allow(clippy::ptr_arg)]
+    pub(super) fn fetch_outputs_in_block_in_txn(
+        &self,
+        txn: &ConstTransaction<'_>,
+        header_hash: &HashOutput,
+    ) -> Result<Vec<PrunedOutput>, ChainStorageError> {
+        Ok(lmdb_fetch_matching_after(txn, &self.utxos_db, header_hash.as_slice())?
+            .into_iter()
+            .map(|f: TransactionOutputRowData| match f.output {
+                Some(o) => PrunedOutput::NotPruned { output: o },
+                None => PrunedOutput::Pruned { output_hash: f.hash },
+            })
+            .collect())
+    }
+
+    #[allow(clippy::ptr_arg)]
+    pub(super) fn fetch_inputs_in_block_in_txn(
+        &self,
+        txn: &ConstTransaction<'_>,
+        header_hash: &HashOutput,
+    ) -> Result<Vec<TransactionInput>, ChainStorageError> {
+        Ok(lmdb_fetch_matching_after(txn, &self.inputs_db, header_hash.as_slice())?
+            .into_iter()
+            .map(|f: TransactionInputRowData| f.input)
+            .collect())
+    }
+
     fn insert_bad_block_and_cleanup(
         &self,
         txn: &WriteTransaction<'_>,
@@ -1654,7 +1995,7 @@ impl LMDBDatabase {
         )
     }
 
-    fn fetch_output_in_txn(
+    pub(super) fn fetch_output_in_txn(
         &self,
         txn: &ConstTransaction<'_>,
         output_hash: &[u8],
@@ -1774,10 +2115,10 @@ impl BlockchainBackend for LMDBDatabase {
                     // SAFETY: This depends on the thread safety of the caller. Technically, `write` is unsafe too
                     // however we happen to know that `LmdbDatabase` is wrapped in an exclusive write lock in
                     // BlockchainDatabase, so we know there are no other threads taking out LMDB transactions when this
-                    // is called.
-                    unsafe {
-                        LMDBStore::resize(&self.env, &self.env_config)?;
-                    }
+                    // is called. Snapshots take out read transactions without that lock, so we wait until all open
+                    // snapshots are closed and prevent new ones from being opened while resizing.
+                    self.snapshot_gate
+                        .while_closed(|| unsafe { LMDBStore::resize(&self.env, &self.env_config) })??;
                 },
                 Err(e) => {
                     error!(target: LOG_TARGET, "Failed to apply DB transaction: {:?}", e);
@@ -1835,33 +2176,13 @@ impl BlockchainBackend for LMDBDatabase {
         })
     }
 
+    fn snapshot_source(&self) -> Arc<dyn BlockchainSnapshotSource> {
+        Arc::new(LMDBSnapshotSource::new(self.clone_for_snapshots()))
+    }
+
     fn fetch_chain_header_by_height(&self, height: u64) -> Result<ChainHeader, ChainStorageError> {
         let txn = self.read_transaction()?;
-
-        let header: BlockHeader =
-            lmdb_get(&txn, &self.headers_db, &height)?.ok_or_else(|| ChainStorageError::ValueNotFound {
-                entity: "BlockHeader",
-                field: "height",
-                value: height.to_string(),
-            })?;
-
-        let accum_data = self
-            .fetch_header_accumulated_data_by_height(&txn, height)?
-            .ok_or_else(|| ChainStorageError::ValueNotFound {
-                entity: "BlockHeaderAccumulatedData",
-                field: "height",
-                value: height.to_string(),
-            })?;
-
-        let height = header.height;
-        let chain_header = ChainHeader::try_construct(header, accum_data).ok_or_else(|| {
-            ChainStorageError::DataInconsistencyDetected {
-                function: "fetch_chain_header_by_height",
-                details: format!("Mismatch in accumulated data at height #{}", height),
-            }
-        })?;
-
-        Ok(chain_header)
+        self.fetch_chain_header_by_height_in_txn(&txn, height)
     }
 
     fn fetch_header_accumulated_data(
@@ -2016,10 +2337,7 @@ impl BlockchainBackend for LMDBDatabase {
 
     fn fetch_kernels_in_block(&self, header_hash: &HashOutput) -> Result<Vec<TransactionKernel>, ChainStorageError> {
         let txn = self.read_transaction()?;
-        Ok(lmdb_fetch_matching_after(&txn, &self.kernels_db, header_hash.deref())?
-            .into_iter()
-            .map(|f: TransactionKernelRowData| f.kernel)
-            .collect())
+        self.fetch_kernels_in_block_in_txn(&txn, header_hash)
     }
 
     fn fetch_kernel_by_excess_sig(
@@ -2027,22 +2345,7 @@ impl BlockchainBackend for LMDBDatabase {
         excess_sig: &Signature,
     ) -> Result<Option<(TransactionKernel, HashOutput)>, ChainStorageError> {
         let txn = self.read_transaction()?;
-        let mut key = Vec::<u8>::new();
-        key.extend(excess_sig.get_public_nonce().as_bytes());
-        key.extend(excess_sig.get_signature().as_bytes());
-        if let Some((header_hash, mmr_position, hash)) =
-            lmdb_get::<_, (HashOutput, u32, HashOutput)>(&txn, &self.kernel_excess_sig_index, key.as_slice())?
-        {
-            let key = KernelKey::try_from_parts(&[
-                header_hash.as_slice(),
-                mmr_position.to_be_bytes().as_slice(),
-                hash.as_slice(),
-            ])?;
-            Ok(lmdb_get(&txn, &self.kernels_db, &key)?
-                .map(|kernel: TransactionKernelRowData| (kernel.kernel, header_hash)))
-        } else {
-            Ok(None)
-        }
+        self.fetch_kernel_by_excess_sig_in_txn(&txn, excess_sig)
     }
 
     fn fetch_utxos_in_block(
@@ -2103,25 +2406,38 @@ impl BlockchainBackend for LMDBDatabase {
         lmdb_get::<_, HashOutput>(&txn, &self.utxo_commitment_index, commitment.as_bytes())
     }
 
+    fn fetch_output_hash_by_commitment(
+        &self,
+        commitment: &Commitment,
+    ) -> Result<Option<HashOutput>, ChainStorageError> {
+        let txn = self.read_transaction()?;
+        let entry = lmdb_get::<_, (HashOutput, HashOutput)>(&txn, &self.txo_commitment_index, commitment.as_bytes())?;
+        Ok(entry.map(|(_, output_hash)| output_hash))
+    }
+
+    fn fetch_block_hash_by_output_commitment(
+        &self,
+        commitment: &Commitment,
+    ) -> Result<Option<HashOutput>, ChainStorageError> {
+        let txn = self.read_transaction()?;
+        let entry = lmdb_get::<_, (HashOutput, HashOutput)>(&txn, &self.txo_commitment_index, commitment.as_bytes())?;
+        Ok(entry.map(|(block_hash, _)| block_hash))
+    }
+
+    fn fetch_block_hash_by_kernel_excess(&self, excess: &Commitment) -> Result<Option<HashOutput>, ChainStorageError> {
+        let txn = self.read_transaction()?;
+        let entry = lmdb_get::<_, (HashOutput, u32, HashOutput)>(&txn, &self.kernel_excess_index, excess.as_bytes())?;
+        Ok(entry.map(|(block_hash, _, _)| block_hash))
+    }
+
     fn fetch_outputs_in_block(&self, header_hash: &HashOutput) -> Result<Vec<PrunedOutput>, ChainStorageError> {
         let txn = self.read_transaction()?;
-        Ok(lmdb_fetch_matching_after(&txn, &self.utxos_db, header_hash.as_slice())?
-            .into_iter()
-            .map(|f: TransactionOutputRowData| match f.output {
-                Some(o) => PrunedOutput::NotPruned { output: o },
-                None => PrunedOutput::Pruned { output_hash: f.hash },
-            })
-            .collect())
+        self.fetch_outputs_in_block_in_txn(&txn, header_hash)
     }
 
     fn fetch_inputs_in_block(&self, header_hash: &HashOutput) -> Result<Vec<TransactionInput>, ChainStorageError> {
         let txn = self.read_transaction()?;
-        Ok(
-            lmdb_fetch_matching_after(&txn, &self.inputs_db, header_hash.as_slice())?
-                .into_iter()
-                .map(|f: TransactionInputRowData| f.input)
-                .collect(),
-        )
+        self.fetch_inputs_in_block_in_txn(&txn, header_hash)
     }
 
     fn fetch_mmr_size(&self, tree: MmrTree) -> Result<u64, ChainStorageError> {
@@ -2180,35 +2496,13 @@ impl BlockchainBackend for LMDBDatabase {
 
     fn fetch_tip_header(&self) -> Result<ChainHeader, ChainStorageError> {
         let txn = self.read_transaction()?;
-
-        let metadata = self.fetch_chain_metadata()?;
-        let height = metadata.height_of_longest_chain();
-        let header = lmdb_get(&txn, &self.headers_db, &height)?.ok_or_else(|| ChainStorageError::ValueNotFound {
-            entity: "Header",
-            field: "height",
-            value: height.to_string(),
-        })?;
-        let accumulated_data = self
-            .fetch_header_accumulated_data_by_height(&txn, metadata.height_of_longest_chain())?
-            .ok_or_else(|| ChainStorageError::ValueNotFound {
-                entity: "BlockHeaderAccumulatedData",
-                field: "height",
-                value: height.to_string(),
-            })?;
-        let chain_header = ChainHeader::try_construct(header, accumulated_data).ok_or_else(|| {
-            ChainStorageError::DataInconsistencyDetected {
-                function: "fetch_tip_header",
-                details: format!("Accumulated data mismatch at height #{}", height),
-            }
-        })?;
-        Ok(chain_header)
+        self.fetch_tip_header_in_txn(&txn)
     }
 
     /// Returns the metadata of the chain.
     fn fetch_chain_metadata(&self) -> Result<ChainMetadata, ChainStorageError> {
         let txn = self.read_transaction()?;
-        let metadata = fetch_metadata(&txn, &self.metadata_db)?;
-        Ok(metadata)
+        self.fetch_chain_metadata_in_txn(&txn)
     }
 
     fn utxo_count(&self) -> Result<usize, ChainStorageError> {
@@ -2372,42 +2666,48 @@ impl BlockchainBackend for LMDBDatabase {
         &mut self,
         horizon_height: u64,
         orphan_storage_capacity: usize,
+        orphan_storage_max_weight: u64,
     ) -> Result<(), ChainStorageError> {
-        let orphan_count = self.orphan_count()?;
-        let num_over_limit = orphan_count.saturating_sub(orphan_storage_capacity);
-        if num_over_limit == 0 {
+        let mut orphans = {
+            let read_txn = self.read_transaction()?;
+            lmdb_filter_map_values(&read_txn, &self.orphan_accessed_index, |row: OrphanIndexRowData| {
+                Some(row)
+            })?
+        };
+
+        let num_over_limit = orphans.len().saturating_sub(orphan_storage_capacity);
+        let total_weight = orphans.iter().fold(0u64, |total, row| total.saturating_add(row.weight));
+        if num_over_limit == 0 && total_weight <= orphan_storage_max_weight {
             return Ok(());
         }
         debug!(
             target: LOG_TARGET,
-            "Orphan block storage limit of {} reached, performing cleanup of {} entries.",
+            "Orphan block storage limit of {} blocks (weight {}) reached with {} blocks (weight {}), performing cleanup.",
             orphan_storage_capacity,
-            num_over_limit,
+            orphan_storage_max_weight,
+            orphans.len(),
+            total_weight,
         );
 
-        let mut orphans;
-
-        {
-            let read_txn = self.read_transaction()?;
-
-            orphans = lmdb_filter_map_values(&read_txn, &self.orphans_db, |block: Block| {
-                Some((block.header.height, block.hash()))
-            })?;
-        }
-
-        orphans.sort_by(|a, b| a.0.cmp(&b.0));
+        // Orphans at or below the pruning horizon are always discarded, then the least recently accessed go first
+        orphans.sort_by_key(|row| (row.height > horizon_height, row.accessed_at, row.height));
+        let mut remaining_weight = total_weight;
         let mut txn = DbTransaction::new();
-        for (removed_count, (height, block_hash)) in orphans.into_iter().enumerate() {
-            if height > horizon_height && removed_count >= num_over_limit {
+        for (removed_count, row) in orphans.into_iter().enumerate() {
+            if row.height > horizon_height &&
+                removed_count >= num_over_limit &&
+                remaining_weight <= orphan_storage_max_weight
+            {
                 break;
             }
+            remaining_weight = remaining_weight.saturating_sub(row.weight);
             debug!(
                 target: LOG_TARGET,
                 "Discarding orphan block #{} ({}).",
-                height,
-                block_hash.to_hex()
+                row.height,
+                row.hash.to_hex()
             );
-            txn.delete_orphan(block_hash);
+            txn.delete_orphan(row.hash);
         }
         self.write(txn)?;
 
@@ -2424,6 +2724,11 @@ impl BlockchainBackend for LMDBDatabase {
         Ok(Some(fetch_horizon_data(&txn, &self.metadata_db)?))
     }
 
+    fn fetch_burnt_totals(&self, height: u64) -> Result<Option<BurntTotals>, ChainStorageError> {
+        let txn = self.read_transaction()?;
+        lmdb_get(&txn, &self.burnt_totals_db, &height)
+    }
+
     fn get_stats(&self) -> Result<DbBasicStats, ChainStorageError> {
         let global = self.env.stat()?;
         let env_info = self.env.info()?;
@@ -2437,6 +2742,11 @@ impl BlockchainBackend for LMDBDatabase {
         Ok(DbBasicStats::new(global, env_info, db_stats))
     }
 
+    fn sync(&self) -> Result<(), ChainStorageError> {
+        self.env.sync(true)?;
+        Ok(())
+    }
+
     fn fetch_total_size_stats(&self) -> Result<DbTotalSizeStats, ChainStorageError> {
         let txn = self.read_transaction()?;
         self.all_dbs()
@@ -2795,8 +3105,11 @@ impl<'a, 'b> DeletedBitmapModel<'a, WriteTransaction<'b>> {
     }
 }
 
+/// The number of entries a migration writes in each write transaction
+const MIGRATION_BATCH_SIZE: usize = 1000;
+
 fn run_migrations(db: &LMDBDatabase) -> Result<(), ChainStorageError> {
-    const MIGRATION_VERSION: u64 = 1;
+    const MIGRATION_VERSION: u64 = 4;
     let txn = db.read_transaction()?;
 
     let k = MetadataKey::MigrationVersion;
@@ -2813,6 +3126,15 @@ fn run_migrations(db: &LMDBDatabase) -> Result<(), ChainStorageError> {
 
     if n < MIGRATION_VERSION {
         // Add migrations here
+        if n < 2 {
+            build_txo_commitment_index(db)?;
+        }
+        if n < 3 {
+            build_orphan_accessed_index(db)?;
+        }
+        if n < 4 {
+            build_burnt_totals(db)?;
+        }
         info!(target: LOG_TARGET, "Migrated database to version {}", MIGRATION_VERSION);
         let txn = db.write_transaction()?;
         lmdb_replace(
@@ -2826,3 +3148,116 @@ fn run_migrations(db: &LMDBDatabase) -> Result<(), ChainStorageError> {
 
     Ok(())
 }
+
+/// Populates the txo commitment index from all unpruned outputs in the database
+fn build_txo_commitment_index(db: &LMDBDatabase) -> Result<(), ChainStorageError> {
+    // Resize this many times per batch before assuming something is not right
+    const MAX_RESIZES: usize = 5;
+    info!(target: LOG_TARGET, "Building the txo commitment index. This may take a while.");
+    let mut outputs = {
+        let txn = db.read_transaction()?;
+        lmdb_filter_map_values(&txn, &db.utxos_db, |row: TransactionOutputRowData| {
+            row.output
+                .map(|output| (row.mined_height, output.commitment, row.header_hash, row.hash))
+        })?
+    };
+    // Insert in mined order so that the most recent output wins if a commitment was reused after being spent
+    outputs.sort_by_key(|(height, _, _, _)| *height);
+    let num_outputs = outputs.len();
+    for batch in outputs.chunks(MIGRATION_BATCH_SIZE) {
+        let mut num_resizes = 0;
+        loop {
+            let result = db.write_transaction().and_then(|txn| {
+                for (_, commitment, header_hash, output_hash) in batch {
+                    lmdb_replace(
+                        &txn,
+                        &db.txo_commitment_index,
+                        commitment.as_bytes(),
+                        &(header_hash, output_hash),
+                    )?;
+                }
+                txn.commit()?;
+                Ok(())
+            });
+            match result {
+                Ok(()) => break,
+                Err(ChainStorageError::DbResizeRequired) if num_resizes < MAX_RESIZES => {
+                    num_resizes += 1;
+                    info!(
+                        target: LOG_TARGET,
+                        "Database resize required while building the txo commitment index (resized {} time(s) in \
+                         this batch)",
+                        num_resizes
+                    );
+                    // SAFETY: Migrations run while the database is opened, before it is shared with any other thread.
+                    // Snapshots are still gated like in `write` so that no read transaction is open while resizing.
+                    db.snapshot_gate
+                        .while_closed(|| unsafe { LMDBStore::resize(&db.env, &db.env_config) })??;
+                },
+                Err(ChainStorageError::DbResizeRequired) => {
+                    return Err(ChainStorageError::DbTransactionTooLarge(batch.len()));
+                },
+                Err(e) => return Err(e),
+            }
+        }
+    }
+    info!(target: LOG_TARGET, "Indexed {} output commitments", num_outputs);
+    Ok(())
+}
+
+/// Populates the orphan accessed index for orphans that were stored before the index existed
+fn build_orphan_accessed_index(db: &LMDBDatabase) -> Result<(), ChainStorageError> {
+    info!(target: LOG_TARGET, "Building the orphan accessed index");
+    let txn = db.write_transaction()?;
+    let weighting = TransactionWeight::latest();
+    let now = EpochTime::now().as_u64();
+    let orphans = lmdb_filter_map_values(&txn, &db.orphans_db, |block: Block| {
+        let weight = block.body.calculate_weight(&weighting).unwrap_or(u64::MAX);
+        Some(OrphanIndexRowData {
+            hash: block.hash(),
+            height: block.header.height,
+            weight,
+            accessed_at: now,
+        })
+    })?;
+    let num_orphans = orphans.len();
+    for row in orphans {
+        lmdb_replace(&txn, &db.orphan_accessed_index, row.hash.as_slice(), &row)?;
+    }
+    txn.commit()?;
+    info!(target: LOG_TARGET, "Indexed {} orphan blocks", num_orphans);
+    Ok(())
+}
+
+/// Populates the burnt totals of the blocks stored before they were recorded. Pruned nodes no longer have the burnt
+/// outputs of the blocks below their pruned height, so their totals are left unknown.
+fn build_burnt_totals(db: &LMDBDatabase) -> Result<(), ChainStorageError> {
+    {
+        let txn = db.read_transaction()?;
+        if fetch_pruned_height(&txn, &db.metadata_db)? > 0 {
+            info!(target: LOG_TARGET, "Database is pruned, not building the burnt totals");
+            return Ok(());
+        }
+    }
+    info!(target: LOG_TARGET, "Building the burnt totals. This may take a while.");
+    let mut totals = BurntTotals::default();
+    let mut height = 0u64;
+    loop {
+        let txn = db.write_transaction()?;
+        for _ in 0..MIGRATION_BATCH_SIZE {
+            if !lmdb_exists(&txn, &db.block_accumulated_data_db, &height)? {
+                txn.commit()?;
+                info!(target: LOG_TARGET, "Recorded the burnt totals of {} blocks", height);
+                return Ok(());
+            }
+            let header: BlockHeader =
+                lmdb_get(&txn, &db.headers_db, &height).or_not_found("BlockHeader", "height", height.to_string())?;
+            let rows =
+                lmdb_fetch_matching_after::<TransactionOutputRowData>(&txn, &db.utxos_db, header.hash().as_slice())?;
+            totals.add_outputs(rows.iter().filter_map(|row| row.output.as_ref()));
+            lmdb_replace(&txn, &db.burnt_totals_db, &height, &totals)?;
+            height += 1;
+        }
+        txn.commit()?;
+    }
+}
@@ -33,6 +33,7 @@ pub(crate) mod helpers;
 mod lmdb;
 #[allow(clippy::module_inception)]
 mod lmdb_db;
+mod snapshot;
 mod validator_node_store;
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -73,4 +74,14 @@ pub(crate) struct TransactionKernelRowData {
     pub hash: HashOutput,
 }
 
+/// Small per-orphan record used to choose which orphans to evict without deserialising the orphan blocks
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct OrphanIndexRowData {
+    pub hash: HashOutput,
+    pub height: u64,
+    pub weight: u64,
+    /// Unix timestamp of when the orphan was last inserted or linked to its parent
+    pub accessed_at: u64,
+}
+
 hash_domain!(CoreChainStorageHashDomain, "com.tari.base_layer.core.lmdb_db", 1);
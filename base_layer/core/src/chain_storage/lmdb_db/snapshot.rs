@@ -0,0 +1,332 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    fs,
+    path::Path,
+    sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError},
+};
+
+use lmdb_zero::{open, put, LmdbResultExt, ReadTransaction, WriteTransaction};
+use log::*;
+use tari_common_types::{
+    chain_metadata::ChainMetadata,
+    types::{HashOutput, Signature},
+};
+use tari_storage::lmdb_store::{db, LMDBBuilder, LMDBConfig, LMDBStore};
+
+use crate::{
+    blocks::ChainHeader,
+    chain_storage::{
+        lmdb_db::{lmdb_database_flags, LMDBDatabase},
+        BlockchainSnapshot,
+        BlockchainSnapshotSource,
+        ChainStorageError,
+        PrunedOutput,
+        UtxoMinedInfo,
+    },
+    transactions::transaction_components::{TransactionInput, TransactionKernel},
+};
+
+const LOG_TARGET: &str = "c::cs::lmdb_db::snapshot";
+
+/// The number of entries that a backup copies before checking whether a resize is waiting for it
+const BACKUP_BATCH_SIZE: usize = 10_000;
+
+#[derive(Debug, Default)]
+struct GateState {
+    open_snapshots: usize,
+    is_closed: bool,
+}
+
+/// Tracks the snapshots that are open on an LMDB environment, so that operations that require that no read
+/// transactions are active (i.e. resizing the environment) can wait for them to close.
+#[derive(Debug, Default)]
+pub(super) struct SnapshotGate {
+    state: Mutex<GateState>,
+    changed: Condvar,
+}
+
+impl SnapshotGate {
+    fn lock(&self) -> Result<MutexGuard<'_, GateState>, ChainStorageError> {
+        self.state
+            .lock()
+            .map_err(|_| ChainStorageError::AccessError("Snapshot gate lock poisoned".into()))
+    }
+
+    fn enter(self: &Arc<Self>) -> Result<SnapshotPermit, ChainStorageError> {
+        let mut state = self.lock()?;
+        while state.is_closed {
+            state = self
+                .changed
+                .wait(state)
+                .map_err(|_| ChainStorageError::AccessError("Snapshot gate lock poisoned".into()))?;
+        }
+        state.open_snapshots += 1;
+        Ok(SnapshotPermit { gate: self.clone() })
+    }
+
+    /// Returns true if a resize is waiting for the open snapshots to close
+    fn is_closing(&self) -> Result<bool, ChainStorageError> {
+        Ok(self.lock()?.is_closed)
+    }
+
+    /// Waits for all open snapshots to close and runs `f` while no new snapshots can be opened
+    pub(super) fn while_closed<F, R>(&self, f: F) -> Result<R, ChainStorageError>
+    where F: FnOnce() -> R {
+        let mut state = self.lock()?;
+        while state.is_closed {
+            state = self
+                .changed
+                .wait(state)
+                .map_err(|_| ChainStorageError::AccessError("Snapshot gate lock poisoned".into()))?;
+        }
+        state.is_closed = true;
+        // Reopens the gate once `f` has completed, or if waiting for the snapshots fails or `f` panics
+        let _reopen = ReopenOnDrop { gate: self };
+        while state.open_snapshots > 0 {
+            state = self
+                .changed
+                .wait(state)
+                .map_err(|_| ChainStorageError::AccessError("Snapshot gate lock poisoned".into()))?;
+        }
+        drop(state);
+        Ok(f())
+    }
+}
+
+struct ReopenOnDrop<'a> {
+    gate: &'a SnapshotGate,
+}
+
+impl Drop for ReopenOnDrop<'_> {
+    fn drop(&mut self) {
+        self.gate.state.lock().unwrap_or_else(PoisonError::into_inner).is_closed = false;
+        self.gate.changed.notify_all();
+    }
+}
+
+struct SnapshotPermit {
+    gate: Arc<SnapshotGate>,
+}
+
+impl Drop for SnapshotPermit {
+    fn drop(&mut self) {
+        if let Ok(mut state) = self.gate.state.lock() {
+            state.open_snapshots -= 1;
+        }
+        self.gate.changed.notify_all();
+    }
+}
+
+/// Opens [LMDBSnapshot]s of an [LMDBDatabase]
+pub(super) struct LMDBSnapshotSource {
+    db: Arc<LMDBDatabase>,
+}
+
+impl LMDBSnapshotSource {
+    pub fn new(db: LMDBDatabase) -> Self {
+        Self { db: Arc::new(db) }
+    }
+}
+
+impl BlockchainSnapshotSource for LMDBSnapshotSource {
+    fn open_snapshot(&self) -> Result<Box<dyn BlockchainSnapshot>, ChainStorageError> {
+        let permit = self.db.snapshot_gate().enter()?;
+        let txn = ReadTransaction::new(self.db.env().clone())?;
+        Ok(Box::new(LMDBSnapshot {
+            db: self.db.clone(),
+            txn,
+            _permit: permit,
+        }))
+    }
+
+    fn backup_to(&self, path: &Path) -> Result<(), ChainStorageError> {
+        fs::create_dir_all(path)?;
+        let env_config = self.db.env_config();
+        // The backup is written in key order with appends, so it does not need more space than the source database
+        let config = LMDBConfig::new(
+            self.db.env().info()?.mapsize,
+            env_config.grow_size_bytes(),
+            env_config.resize_threshold_bytes(),
+        );
+        let builder = LMDBBuilder::new()
+            .set_path(path)
+            .set_env_flags(open::NOLOCK)
+            .set_env_config(config.clone())
+            .set_max_number_of_databases(40);
+        let backup = lmdb_database_flags()
+            .iter()
+            .fold(builder, |builder, (name, flags)| builder.add_database(name, *flags))
+            .build()
+            .map_err(|err| ChainStorageError::CriticalError(format!("Could not create backup LMDB store: {}", err)))?;
+
+        // The copy is read from a snapshot, which must be closed before the source environment can be resized. Rather
+        // than holding up a resize, and the block write waiting on it, until the whole database has been copied, the
+        // backup gives way and starts again from a new snapshot.
+        while !self.copy_snapshot_to(&backup)? {
+            debug!(target: LOG_TARGET, "Database resize requested during backup. Restarting the backup.");
+            // SAFETY: No transactions are open on the backup environment, which is only used by this function
+            unsafe { LMDBStore::resize(&backup.env(), &config)? };
+        }
+        backup.flush()?;
+        Ok(())
+    }
+}
+
+impl LMDBSnapshotSource {
+    /// Copies a snapshot of every database to `backup`, replacing its contents. Returns false, without completing the
+    /// copy, if a resize starts waiting for the snapshot to close.
+    fn copy_snapshot_to(&self, backup: &LMDBStore) -> Result<bool, ChainStorageError> {
+        let gate = self.db.snapshot_gate();
+        let _permit = gate.enter()?;
+        let txn = ReadTransaction::new(self.db.env().clone())?;
+        let access = txn.access();
+        let source_dbs = self.db.lmdb_dbs();
+        for (name, flags) in &lmdb_database_flags() {
+            let source = source_dbs
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, db)| (*db).clone())
+                .ok_or_else(|| ChainStorageError::CriticalError(format!("Could not get `{}` database", name)))?;
+            let target = backup
+                .get_handle(name)
+                .ok_or_else(|| ChainStorageError::CriticalError(format!("Could not get `{}` backup database", name)))?
+                .db();
+            let put_flags = if flags.contains(db::DUPSORT) {
+                put::APPENDDUP
+            } else {
+                put::APPEND
+            };
+
+            let mut cursor = txn.cursor(&*source)?;
+            let mut row = cursor.first::<[u8], [u8]>(&access).to_opt()?;
+            let mut is_first_batch = true;
+            while is_first_batch || row.is_some() {
+                if gate.is_closing()? {
+                    return Ok(false);
+                }
+                let write_txn = WriteTransaction::new(backup.env())?;
+                {
+                    let mut backup_access = write_txn.access();
+                    if is_first_batch {
+                        backup_access.clear_db(&target)?;
+                    }
+                    for _ in 0..BACKUP_BATCH_SIZE {
+                        match row {
+                            Some((key, value)) => backup_access.put(&target, key, value, put_flags)?,
+                            None => break,
+                        }
+                        row = cursor.next::<[u8], [u8]>(&access).to_opt()?;
+                    }
+                }
+                write_txn.commit()?;
+                is_first_batch = false;
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// A [BlockchainSnapshot] backed by a single LMDB read transaction
+struct LMDBSnapshot {
+    db: Arc<LMDBDatabase>,
+    // Declared before the permit so that the read transaction is closed before the permit is released
+    txn: ReadTransaction<'static>,
+    _permit: SnapshotPermit,
+}
+
+impl BlockchainSnapshot for LMDBSnapshot {
+    fn fetch_chain_metadata(&self) -> Result<ChainMetadata, ChainStorageError> {
+        self.db.fetch_chain_metadata_in_txn(&self.txn)
+    }
+
+    fn fetch_tip_header(&self) -> Result<ChainHeader, ChainStorageError> {
+        self.db.fetch_tip_header_in_txn(&self.txn)
+    }
+
+    fn fetch_chain_header_by_height(&self, height: u64) -> Result<ChainHeader, ChainStorageError> {
+        self.db.fetch_chain_header_by_height_in_txn(&self.txn, height)
+    }
+
+    fn fetch_chain_header_by_block_hash(&self, hash: &HashOutput) -> Result<Option<ChainHeader>, ChainStorageError> {
+        self.db.fetch_chain_header_by_hash_in_txn(&self.txn, hash)
+    }
+
+    fn fetch_kernels_in_block(&self, header_hash: &HashOutput) -> Result<Vec<TransactionKernel>, ChainStorageError> {
+        self.db.fetch_kernels_in_block_in_txn(&self.txn, header_hash)
+    }
+
+    fn fetch_kernel_by_excess_sig(
+        &self,
+        excess_sig: &Signature,
+    ) -> Result<Option<(TransactionKernel, HashOutput)>, ChainStorageError> {
+        self.db.fetch_kernel_by_excess_sig_in_txn(&self.txn, excess_sig)
+    }
+
+    fn fetch_outputs_in_block(&self, header_hash: &HashOutput) -> Result<Vec<PrunedOutput>, ChainStorageError> {
+        self.db.fetch_outputs_in_block_in_txn(&self.txn, header_hash)
+    }
+
+    fn fetch_inputs_in_block(&self, header_hash: &HashOutput) -> Result<Vec<TransactionInput>, ChainStorageError> {
+        self.db.fetch_inputs_in_block_in_txn(&self.txn, header_hash)
+    }
+
+    fn fetch_output(&self, output_hash: &HashOutput) -> Result<Option<UtxoMinedInfo>, ChainStorageError> {
+        self.db.fetch_output_in_txn(&self.txn, output_hash.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::panic;
+
+    use super::*;
+
+    #[test]
+    fn it_reopens_the_gate_if_the_closed_operation_panics() {
+        let gate = Arc::new(SnapshotGate::default());
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            let _ = gate.while_closed(|| panic!("resize failed"));
+        }));
+        assert!(result.is_err());
+        assert!(!gate.is_closing().unwrap());
+        let _permit = gate.enter().unwrap();
+        assert_eq!(gate.lock().unwrap().open_snapshots, 1);
+    }
+
+    #[test]
+    fn it_waits_for_open_snapshots_before_closing() {
+        let gate = Arc::new(SnapshotGate::default());
+        let permit = gate.enter().unwrap();
+        let closer = {
+            let gate = gate.clone();
+            std::thread::spawn(move || gate.while_closed(|| gate.lock().unwrap().open_snapshots).unwrap())
+        };
+        while !gate.is_closing().unwrap() {
+            std::thread::yield_now();
+        }
+        drop(permit);
+        assert_eq!(closer.join().unwrap(), 0);
+        assert!(!gate.is_closing().unwrap());
+    }
+}
@@ -46,12 +46,19 @@ pub use blockchain_database::{
     BlockchainDatabase,
     BlockchainDatabaseConfig,
     MmrRoots,
+    PruningProgress,
     Validators,
 };
 
 mod blockchain_backend;
 pub use blockchain_backend::BlockchainBackend;
 
+mod blockchain_snapshot;
+pub use blockchain_snapshot::{BlockchainSnapshot, BlockchainSnapshotSource};
+
+mod burnt_totals;
+pub use burnt_totals::BurntTotals;
+
 mod consts;
 
 mod db_transaction;
@@ -63,6 +70,9 @@ pub use mmr_tree::MmrTree;
 mod error;
 pub use error::{ChainStorageError, Optional, OrNotFound};
 
+mod finality;
+pub use finality::{ChainRewind, FinalityGadget, FinalityGuard};
+
 mod horizon_data;
 pub use horizon_data::HorizonData;
 
@@ -90,6 +100,9 @@ use tari_common_types::types::HashOutput;
 mod template_registation;
 pub use template_registation::TemplateRegistrationEntry;
 
+mod validator_committee;
+pub use validator_committee::{shuffle_validator_committees, validator_committee_seed, ActiveValidatorNode};
+
 #[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq)]
 pub struct ChainTipData {
     pub hash: HashOutput,
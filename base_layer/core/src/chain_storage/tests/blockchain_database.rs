@@ -22,6 +22,8 @@
 // DAMAGE.
 use std::sync::Arc;
 
+use tari_utilities::epoch_time::EpochTime;
+
 use crate::{
     blocks::{Block, BlockHeader, BlockHeaderAccumulatedData, ChainHeader, NewBlockTemplate},
     chain_storage::{BlockchainDatabase, ChainStorageError},
@@ -416,6 +418,70 @@ mod fetch_total_size_stats {
     }
 }
 
+mod prune_step {
+    use tari_common::configuration::Network;
+
+    use super::*;
+    use crate::{
+        chain_storage::{BlockchainDatabaseConfig, Validators},
+        consensus::ConsensusManager,
+        test_helpers::blockchain::create_store_with_consensus_and_validators_and_config,
+        validation::mocks::MockValidator,
+    };
+
+    fn setup_background_pruning() -> BlockchainDatabase<TempDatabase> {
+        let rules = ConsensusManager::builder(Network::LocalNet).build().unwrap();
+        let validators = Validators::new(
+            MockValidator::new(true),
+            MockValidator::new(true),
+            MockValidator::new(true),
+        );
+        let config = BlockchainDatabaseConfig {
+            pruning_horizon: 2,
+            pruning_interval: 1,
+            background_pruning: true,
+            ..Default::default()
+        };
+        create_store_with_consensus_and_validators_and_config(rules, validators, config)
+    }
+
+    #[tokio::test]
+    async fn it_prunes_in_batches_up_to_the_horizon() {
+        let db = setup_background_pruning();
+        let key_manager = create_test_core_key_manager_with_memory_db();
+        let _block_and_outputs = add_many_chained_blocks(6, &db, &key_manager).await;
+
+        // Adding blocks does not prune when background pruning is enabled
+        let metadata = db.get_chain_metadata().unwrap();
+        assert_eq!(metadata.height_of_longest_chain(), 6);
+        assert_eq!(metadata.pruned_height(), 0);
+
+        let progress = db.prune_step(3).unwrap();
+        assert_eq!(progress.pruned_height, 3);
+        assert_eq!(progress.horizon_height, 4);
+        assert_eq!(progress.remaining(), 1);
+        assert!(!progress.is_complete());
+        assert_eq!(db.get_chain_metadata().unwrap().pruned_height(), 3);
+
+        let progress = db.prune_step(3).unwrap();
+        assert_eq!(progress.pruned_height, 4);
+        assert!(progress.is_complete());
+
+        // Once the horizon is reached, further steps do nothing
+        let progress = db.prune_step(3).unwrap();
+        assert_eq!(progress.pruned_height, 4);
+        assert_eq!(db.get_chain_metadata().unwrap().pruned_height(), 4);
+    }
+
+    #[test]
+    fn it_does_nothing_for_archival_nodes() {
+        let db = setup();
+        let progress = db.prune_step(3).unwrap();
+        assert!(progress.is_complete());
+        assert_eq!(progress.pruned_height, 0);
+    }
+}
+
 mod prepare_new_block {
     use super::*;
 
@@ -452,6 +518,30 @@ mod prepare_new_block {
         let block = db.prepare_new_block(template).unwrap();
         assert_eq!(block.header.height, 1);
     }
+
+    #[test]
+    fn it_raises_the_timestamp_above_the_median() {
+        let db = setup();
+        let genesis = db.fetch_block(0, true).unwrap();
+        let mut next_block = BlockHeader::from_previous(genesis.header());
+        next_block.timestamp = EpochTime::from(0);
+        let template = NewBlockTemplate::from_block(next_block.into_builder().build(), Difficulty::min(), 5000 * T);
+        let block = db.prepare_new_block(template).unwrap();
+        // The median of a window containing only the genesis block is the genesis timestamp
+        assert_eq!(block.header.timestamp, genesis.header().timestamp.increase(1));
+    }
+
+    #[test]
+    fn it_limits_the_timestamp_to_the_ftl() {
+        let db = setup();
+        let genesis = db.fetch_block(0, true).unwrap();
+        let mut next_block = BlockHeader::from_previous(genesis.header());
+        next_block.timestamp = EpochTime::from(u64::MAX);
+        let template = NewBlockTemplate::from_block(next_block.into_builder().build(), Difficulty::min(), 5000 * T);
+        let block = db.prepare_new_block(template).unwrap();
+        assert!(block.header.timestamp <= db.rules().consensus_constants(1).ftl());
+        assert!(block.header.timestamp > genesis.header().timestamp);
+    }
 }
 
 mod fetch_header_containing_utxo_mmr {
@@ -552,6 +642,29 @@ mod fetch_header_containing_kernel_mmr {
     }
 }
 
+mod fetch_block_with_kernel_excess_and_utxo {
+    use super::*;
+
+    #[tokio::test]
+    async fn it_finds_the_block_until_it_is_rewound() {
+        let db = setup();
+        let key_manager = create_test_core_key_manager_with_memory_db();
+        let (blocks, _) = add_many_chained_blocks(3, &db, &key_manager).await;
+        let block = &blocks[1];
+        let excess = block.body.kernels()[0].excess.clone();
+        let commitment = block.body.outputs()[0].commitment.clone();
+
+        let found = db.fetch_block_with_kernel_excess(excess.clone()).unwrap().unwrap();
+        assert_eq!(*found.hash(), block.hash());
+        let found = db.fetch_block_with_utxo(commitment.clone()).unwrap().unwrap();
+        assert_eq!(*found.hash(), block.hash());
+
+        db.rewind_to_height(1).unwrap();
+        assert!(db.fetch_block_with_kernel_excess(excess).unwrap().is_none());
+        assert!(db.fetch_block_with_utxo(commitment).unwrap().is_none());
+    }
+}
+
 mod clear_all_pending_headers {
     use super::*;
 
@@ -669,3 +782,67 @@ mod validator_node_merkle_root {
         assert_eq!(tip.header().validator_node_mr, merkle_root);
     }
 }
+
+mod snapshot {
+    use std::{sync::mpsc, thread};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn it_does_not_see_blocks_added_after_it_was_opened() {
+        let db = setup();
+        let key_manager = create_test_core_key_manager_with_memory_db();
+        add_many_chained_blocks(2, &db, &key_manager).await;
+
+        let (opened_tx, opened_rx) = mpsc::channel();
+        let (added_tx, added_rx) = mpsc::channel();
+        let reader_db = db.clone();
+        let reader = thread::spawn(move || {
+            let snapshot = reader_db.snapshot().unwrap();
+            opened_tx.send(()).unwrap();
+            added_rx.recv().unwrap();
+            let metadata = snapshot.fetch_chain_metadata().unwrap();
+            let tip = snapshot.fetch_tip_header().unwrap();
+            (metadata.height_of_longest_chain(), tip.height())
+        });
+
+        opened_rx.recv().unwrap();
+        let (blocks, _) = add_many_chained_blocks(1, &db, &key_manager).await;
+        added_tx.send(()).unwrap();
+        assert_eq!(reader.join().unwrap(), (2, 2));
+
+        let snapshot = db.snapshot().unwrap();
+        assert_eq!(*snapshot.fetch_tip_header().unwrap().hash(), blocks[0].hash());
+        let header = snapshot
+            .fetch_chain_header_by_block_hash(&blocks[0].hash())
+            .unwrap()
+            .unwrap();
+        assert_eq!(header.height(), 3);
+    }
+}
+
+mod backup_to {
+    use tari_storage::lmdb_store::LMDBConfig;
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::chain_storage::{create_lmdb_database, BlockchainBackend};
+
+    #[tokio::test]
+    async fn it_writes_a_copy_that_can_be_opened() {
+        let db = setup();
+        let key_manager = create_test_core_key_manager_with_memory_db();
+        let (blocks, _) = add_many_chained_blocks(3, &db, &key_manager).await;
+
+        let temp_dir = tempdir().unwrap();
+        let backup_path = temp_dir.path().join("backup");
+        db.backup_to(&backup_path).unwrap();
+        // More blocks added after the backup do not end up in it
+        add_many_chained_blocks(1, &db, &key_manager).await;
+
+        let backup = create_lmdb_database(&backup_path, LMDBConfig::default(), db.rules().clone()).unwrap();
+        let metadata = backup.fetch_chain_metadata().unwrap();
+        assert_eq!(metadata.height_of_longest_chain(), 3);
+        assert_eq!(*metadata.best_block(), blocks[2].hash());
+    }
+}
@@ -0,0 +1,147 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+use tari_common_types::{
+    epoch::VnEpoch,
+    types::{FixedHash, PublicKey},
+};
+
+use crate::{consensus::DomainSeparatedConsensusHasher, transactions::TransactionHashDomain};
+
+/// An active validator node, identified by its public key and the shard key it was assigned on registration
+pub type ActiveValidatorNode = (PublicKey, [u8; 32]);
+
+/// Derives the seed that the committees of `epoch` are shuffled with from the hashes of every block in the previous
+/// epoch.
+///
+/// The seed accumulates the contributions of every miner in the previous epoch rather than trusting a single block, so
+/// the only way to influence it is to withhold or re-mine the final block of that epoch at the full proof of work cost.
+/// The validator node set of `epoch` is fixed by registrations made before the epoch starts, so registrants can not
+/// grind their keys against a seed that does not exist yet.
+pub fn validator_committee_seed<'a, I: IntoIterator<Item = &'a FixedHash>>(
+    epoch: VnEpoch,
+    previous_epoch_block_hashes: I,
+) -> FixedHash {
+    previous_epoch_block_hashes
+        .into_iter()
+        .fold(
+            DomainSeparatedConsensusHasher::<TransactionHashDomain>::new("validator_node_committee_seed")
+                .chain(&epoch.as_u64()),
+            |hasher, hash| hasher.chain(hash),
+        )
+        .finalize()
+        .into()
+}
+
+/// Deterministically shuffles the validator nodes active in an epoch into at most `num_committees` committees.
+///
+/// Every validator node is ranked by a hash of the committee seed and the shard key the chain assigned it on
+/// registration, and the ranked list is dealt round robin into the committees. Committee sizes differ by at most one.
+/// The number of committees is clamped to the number of validator nodes so that no committee is empty, and no
+/// committees are returned if `num_committees` is zero.
+pub fn shuffle_validator_committees(
+    validator_nodes: Vec<ActiveValidatorNode>,
+    num_committees: usize,
+    seed: &FixedHash,
+) -> Vec<Vec<ActiveValidatorNode>> {
+    let num_committees = num_committees.min(validator_nodes.len());
+    if num_committees == 0 {
+        return Vec::new();
+    }
+    let mut ranked = validator_nodes
+        .into_iter()
+        .map(|vn| (committee_rank(seed, &vn.1), vn))
+        .collect::<Vec<_>>();
+    ranked.sort_by(|(a, a_vn), (b, b_vn)| a.cmp(b).then_with(|| a_vn.0.cmp(&b_vn.0)));
+
+    let mut committees = vec![Vec::new(); num_committees];
+    for (i, (_, vn)) in ranked.into_iter().enumerate() {
+        committees[i % num_committees].push(vn);
+    }
+    committees
+}
+
+fn committee_rank(seed: &FixedHash, shard_key: &[u8; 32]) -> [u8; 32] {
+    DomainSeparatedConsensusHasher::<TransactionHashDomain>::new("validator_node_committee")
+        .chain(seed)
+        .chain(shard_key)
+        .finalize()
+}
+
+#[cfg(test)]
+mod test {
+    use rand::{rngs::OsRng, RngCore};
+
+    use super::*;
+    use crate::test_helpers::new_public_key;
+
+    fn validator_nodes(n: usize) -> Vec<ActiveValidatorNode> {
+        (0..n)
+            .map(|_| {
+                let mut shard_key = [0u8; 32];
+                OsRng.fill_bytes(&mut shard_key);
+                (new_public_key(), shard_key)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn it_assigns_every_validator_node_to_one_balanced_committee() {
+        let vns = validator_nodes(10);
+        let committees = shuffle_validator_committees(vns.clone(), 3, &FixedHash::zero());
+        assert_eq!(committees.iter().map(Vec::len).collect::<Vec<_>>(), vec![4, 3, 3]);
+        let mut assigned = committees.into_iter().flatten().collect::<Vec<_>>();
+        let mut expected = vns;
+        assigned.sort();
+        expected.sort();
+        assert_eq!(assigned, expected);
+        assert!(shuffle_validator_committees(validator_nodes(2), 0, &FixedHash::zero()).is_empty());
+    }
+
+    #[test]
+    fn it_clamps_the_number_of_committees_to_the_number_of_validator_nodes() {
+        let committees = shuffle_validator_committees(validator_nodes(3), usize::MAX, &FixedHash::zero());
+        assert_eq!(committees.iter().map(Vec::len).collect::<Vec<_>>(), vec![1, 1, 1]);
+        assert!(shuffle_validator_committees(Vec::new(), 4, &FixedHash::zero()).is_empty());
+    }
+
+    #[test]
+    fn it_reshuffles_when_the_seed_changes() {
+        let vns = validator_nodes(20);
+        let a = shuffle_validator_committees(vns.clone(), 4, &FixedHash::zero());
+        assert_eq!(a, shuffle_validator_committees(vns.clone(), 4, &FixedHash::zero()));
+        let b = shuffle_validator_committees(vns, 4, &FixedHash::from([1u8; 32]));
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn it_derives_the_seed_from_every_block_of_the_previous_epoch() {
+        let hashes = vec![FixedHash::zero(), FixedHash::from([1u8; 32])];
+        let seed = validator_committee_seed(VnEpoch(1), &hashes);
+        assert_eq!(seed, validator_committee_seed(VnEpoch(1), &hashes));
+        assert_ne!(seed, validator_committee_seed(VnEpoch(2), &hashes));
+        assert_ne!(seed, validator_committee_seed(VnEpoch(1), &hashes[..1]));
+        assert_ne!(
+            seed,
+            validator_committee_seed(VnEpoch(1), &[FixedHash::zero(), FixedHash::zero()])
+        );
+    }
+}
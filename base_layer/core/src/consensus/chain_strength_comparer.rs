@@ -5,6 +5,10 @@ use std::{cmp::Ordering, fmt::Debug};
 
 use crate::blocks::ChainHeader;
 
+/// Compares the strength of two chains by their tip headers. Comparers return a total [Ordering], so fork choice is
+/// always decidable; the default comparer compares the product of the accumulated RandomX and SHA3x difficulties
+/// (which orders chains the same way as their geometric mean) before falling back to height and the per-algorithm
+/// accumulated difficulties.
 pub trait ChainStrengthComparer: Debug {
     fn compare(&self, a: &ChainHeader, b: &ChainHeader) -> Ordering;
 }
@@ -33,7 +33,7 @@ use tari_utilities::epoch_time::EpochTime;
 
 use crate::{
     borsh::SerializedSize,
-    consensus::network::NetworkConsensus,
+    consensus::{deployments::Deployment, network::NetworkConsensus},
     proof_of_work::{Difficulty, PowAlgorithm},
     transactions::{
         tari_amount::{uT, MicroMinotari, T},
@@ -115,6 +115,16 @@ pub struct ConsensusConstants {
     vn_registration_lock_height: u64,
     /// The period after which the VNs will be reshuffled.
     vn_registration_shuffle_interval: VnEpoch,
+    /// Consensus changes activated by miners signalling in the header version
+    deployments: &'static [Deployment],
+    /// The height from which the high byte of the header version holds deployment signals. Before this height the
+    /// whole header version must be a valid blockchain version.
+    deployment_signalling_height: u64,
+    /// The maximum size in bytes of the Monero pow data in a merge mined header, from `monero_pow_data_rules_height`
+    max_monero_pow_data_size: usize,
+    /// The height from which merge mined headers must keep their pow data within `max_monero_pow_data_size`, use a
+    /// 32-byte RandomX key and include the coinbase in their Monero transaction count
+    monero_pow_data_rules_height: u64,
 }
 
 #[derive(Debug, Clone)]
@@ -150,6 +160,8 @@ pub struct PowAlgorithmConstants {
 }
 
 const ESMERALDA_FAUCET_VALUE: u64 = 3_798_996_893_688_987;
+/// Bounds the work done parsing the Monero pow data of untrusted merge mined headers
+const MAX_MONERO_POW_DATA_SIZE: usize = 64 * 1024;
 
 // The target time used by the difficulty adjustment algorithms, their target time is the target block interval * PoW
 // algorithm count
@@ -330,6 +342,36 @@ impl ConsensusConstants {
         self.vn_registration_lock_height
     }
 
+    /// The version bits deployments defined for the network
+    pub fn deployments(&self) -> &'static [Deployment] {
+        self.deployments
+    }
+
+    /// The height from which the high byte of the header version holds deployment signals
+    pub fn deployment_signalling_height(&self) -> u64 {
+        self.deployment_signalling_height
+    }
+
+    /// Returns true if headers at `height` may set deployment signal bits in their version
+    pub fn is_deployment_signalling_active(&self, height: u64) -> bool {
+        height >= self.deployment_signalling_height
+    }
+
+    /// The maximum size in bytes of the Monero pow data in a merge mined header
+    pub fn max_monero_pow_data_size(&self) -> usize {
+        self.max_monero_pow_data_size
+    }
+
+    /// The height from which the limits on merge mined pow data are enforced
+    pub fn monero_pow_data_rules_height(&self) -> u64 {
+        self.monero_pow_data_rules_height
+    }
+
+    /// Returns true if merge mined headers at `height` must keep to the limits on their pow data
+    pub fn is_monero_pow_data_rules_active(&self, height: u64) -> bool {
+        height >= self.monero_pow_data_rules_height
+    }
+
     /// Returns the current epoch from the given height
     pub fn block_height_to_epoch(&self, height: u64) -> VnEpoch {
         VnEpoch(height / self.vn_epoch_length)
@@ -386,6 +428,10 @@ impl ConsensusConstants {
             vn_registration_min_deposit_amount: MicroMinotari(0),
             vn_registration_lock_height: 0,
             vn_registration_shuffle_interval: VnEpoch(100),
+            deployments: &[],
+            deployment_signalling_height: 0,
+            max_monero_pow_data_size: MAX_MONERO_POW_DATA_SIZE,
+            monero_pow_data_rules_height: 0,
             coinbase_output_features_extra_max_length: 64,
         }];
         #[cfg(any(test, debug_assertions))]
@@ -449,6 +495,10 @@ impl ConsensusConstants {
             vn_registration_min_deposit_amount: MicroMinotari(0),
             vn_registration_lock_height: 0,
             vn_registration_shuffle_interval: VnEpoch(100),
+            deployments: &[],
+            deployment_signalling_height: u64::MAX,
+            max_monero_pow_data_size: MAX_MONERO_POW_DATA_SIZE,
+            monero_pow_data_rules_height: u64::MAX,
             coinbase_output_features_extra_max_length: 64,
         }];
         #[cfg(any(test, debug_assertions))]
@@ -509,6 +559,10 @@ impl ConsensusConstants {
             vn_registration_min_deposit_amount: MicroMinotari(0),
             vn_registration_lock_height: 0,
             vn_registration_shuffle_interval: VnEpoch(100),
+            deployments: &[],
+            deployment_signalling_height: u64::MAX,
+            max_monero_pow_data_size: MAX_MONERO_POW_DATA_SIZE,
+            monero_pow_data_rules_height: u64::MAX,
             coinbase_output_features_extra_max_length: 64,
         }];
         #[cfg(any(test, debug_assertions))]
@@ -563,6 +617,10 @@ impl ConsensusConstants {
             vn_registration_min_deposit_amount: MicroMinotari(0),
             vn_registration_lock_height: 0,
             vn_registration_shuffle_interval: VnEpoch(100),
+            deployments: &[],
+            deployment_signalling_height: u64::MAX,
+            max_monero_pow_data_size: MAX_MONERO_POW_DATA_SIZE,
+            monero_pow_data_rules_height: u64::MAX,
             coinbase_output_features_extra_max_length: 64,
         }];
         #[cfg(any(test, debug_assertions))]
@@ -611,6 +669,10 @@ impl ConsensusConstants {
             vn_registration_min_deposit_amount: MicroMinotari(0),
             vn_registration_lock_height: 0,
             vn_registration_shuffle_interval: VnEpoch(100),
+            deployments: &[],
+            deployment_signalling_height: u64::MAX,
+            max_monero_pow_data_size: MAX_MONERO_POW_DATA_SIZE,
+            monero_pow_data_rules_height: u64::MAX,
             coinbase_output_features_extra_max_length: 64,
         }];
         #[cfg(any(test, debug_assertions))]
@@ -661,6 +723,10 @@ impl ConsensusConstants {
             vn_registration_min_deposit_amount: MicroMinotari(0),
             vn_registration_lock_height: 0,
             vn_registration_shuffle_interval: VnEpoch(100),
+            deployments: &[],
+            deployment_signalling_height: u64::MAX,
+            max_monero_pow_data_size: MAX_MONERO_POW_DATA_SIZE,
+            monero_pow_data_rules_height: u64::MAX,
             coinbase_output_features_extra_max_length: 64,
         }];
         #[cfg(any(test, debug_assertions))]
@@ -848,6 +914,21 @@ impl ConsensusConstantsBuilder {
         self
     }
 
+    pub fn with_deployments(mut self, deployments: &'static [Deployment]) -> Self {
+        self.consensus.deployments = deployments;
+        self
+    }
+
+    pub fn with_deployment_signalling_height(mut self, height: u64) -> Self {
+        self.consensus.deployment_signalling_height = height;
+        self
+    }
+
+    pub fn with_monero_pow_data_rules_height(mut self, height: u64) -> Self {
+        self.consensus.monero_pow_data_rules_height = height;
+        self
+    }
+
     pub fn build(self) -> ConsensusConstants {
         self.consensus
     }
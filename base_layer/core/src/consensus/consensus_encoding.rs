@@ -22,6 +22,8 @@
 
 mod bytes;
 mod hashing;
+#[cfg(test)]
+pub mod strategies;
 mod string;
 
 pub use hashing::{ConsensusHasher, DomainSeparatedConsensusHasher};
@@ -20,30 +20,34 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{cmp, convert::TryFrom, ops::Deref};
+use std::{cmp, convert::TryFrom, io, ops::Deref};
 
 use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
 use tari_utilities::hex::{from_hex, HexError};
 
-#[derive(
-    Debug,
-    Clone,
-    Hash,
-    PartialEq,
-    Eq,
-    PartialOrd,
-    Ord,
-    Default,
-    Deserialize,
-    Serialize,
-    BorshSerialize,
-    BorshDeserialize,
-)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Default, Deserialize, Serialize, BorshSerialize)]
 pub struct MaxSizeBytes<const MAX: usize> {
     inner: Vec<u8>,
 }
 
+impl<const MAX: usize> BorshDeserialize for MaxSizeBytes<MAX> {
+    fn deserialize_reader<R>(reader: &mut R) -> Result<Self, io::Error>
+    where R: io::Read {
+        let len = usize::try_from(u32::deserialize_reader(reader)?)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string()))?;
+        if len > MAX {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("length {} exceeded maximum of {} bytes for MaxSizeBytes", len, MAX),
+            ));
+        }
+        let mut inner = vec![0u8; len];
+        reader.read_exact(&mut inner)?;
+        Ok(Self { inner })
+    }
+}
+
 impl<const MAX: usize> MaxSizeBytes<MAX> {
     pub fn into_vec(self) -> Vec<u8> {
         self.inner
@@ -0,0 +1,366 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! [proptest] strategies for the consensus-encoded types, and a round-trip check that any test can reuse.
+//!
+//! The strategies favour structurally valid values (for example, real curve points and covenants built from tokens)
+//! over raw random bytes, so that the round-trip properties exercise the encoders rather than only the decoders'
+//! error paths.
+
+use std::{convert::TryFrom, fmt::Debug};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use proptest::{collection::vec, option, prelude::*, test_runner::TestCaseError};
+use tari_common_types::types::{
+    BulletRangeProof,
+    ComAndPubSignature,
+    Commitment,
+    FixedHash,
+    PrivateKey,
+    PublicKey,
+    Signature,
+};
+use tari_crypto::keys::PublicKey as PublicKeyTrait;
+use tari_script::{script, TariScript};
+use tari_utilities::epoch_time::EpochTime;
+
+use super::{MaxSizeBytes, MaxSizeString};
+use crate::{
+    blocks::BlockHeader,
+    borsh::SerializedSize,
+    covenant,
+    covenants::Covenant,
+    proof_of_work::{PowAlgorithm, ProofOfWork},
+    transactions::{
+        tari_amount::MicroMinotari,
+        transaction_components::{
+            EncryptedData,
+            KernelFeatures,
+            OutputFeatures,
+            OutputFeaturesVersion,
+            OutputType,
+            RangeProofType,
+            TransactionKernel,
+            TransactionOutput,
+        },
+    },
+};
+
+/// Encode `value`, decode it again and check that the result is identical, that decoding consumed every byte, that
+/// re-encoding is canonical (produces the same bytes) and that [SerializedSize] agrees with the encoded length.
+pub fn check_round_trip<T>(value: &T) -> Result<(), TestCaseError>
+where T: BorshSerialize + BorshDeserialize + PartialEq + Debug {
+    let bytes = value.try_to_vec().map_err(|e| TestCaseError::fail(e.to_string()))?;
+    let size = value
+        .get_serialized_size()
+        .map_err(|e| TestCaseError::fail(e.to_string()))?;
+    prop_assert_eq!(size, bytes.len());
+
+    let mut buf = bytes.as_slice();
+    let decoded = T::deserialize(&mut buf).map_err(|e| TestCaseError::fail(e.to_string()))?;
+    prop_assert!(buf.is_empty(), "{} trailing bytes were not consumed", buf.len());
+    prop_assert_eq!(&decoded, value);
+    let reencoded = decoded.try_to_vec().map_err(|e| TestCaseError::fail(e.to_string()))?;
+    prop_assert_eq!(reencoded, bytes);
+    Ok(())
+}
+
+pub fn fixed_hash() -> impl Strategy<Value = FixedHash> {
+    any::<[u8; 32]>().prop_map(FixedHash::from)
+}
+
+pub fn private_key() -> impl Strategy<Value = PrivateKey> {
+    any::<u64>().prop_map(PrivateKey::from)
+}
+
+pub fn public_key() -> impl Strategy<Value = PublicKey> {
+    private_key().prop_map(|k| PublicKey::from_secret_key(&k))
+}
+
+pub fn commitment() -> impl Strategy<Value = Commitment> {
+    public_key().prop_map(|p| Commitment::from_public_key(&p))
+}
+
+pub fn signature() -> impl Strategy<Value = Signature> {
+    (public_key(), private_key()).prop_map(|(r, s)| Signature::new(r, s))
+}
+
+pub fn com_and_pub_signature() -> impl Strategy<Value = ComAndPubSignature> {
+    (commitment(), public_key(), private_key(), private_key(), private_key())
+        .prop_map(|(r_a, r_x, u_a, u_x, u_y)| ComAndPubSignature::new(r_a, r_x, u_a, u_x, u_y))
+}
+
+pub fn max_size_bytes<const MAX: usize>() -> impl Strategy<Value = MaxSizeBytes<MAX>> {
+    vec(any::<u8>(), 0..=MAX).prop_map(|b| MaxSizeBytes::try_from(b).expect("length is bounded by MAX"))
+}
+
+/// Strings of up to `MAX` bytes. Characters are drawn from the whole of unicode, so the strategy also covers
+/// multi-byte characters.
+pub fn max_size_string<const MAX: usize>() -> impl Strategy<Value = MaxSizeString<MAX>> {
+    vec(any::<char>(), 0..=MAX).prop_map(|chars| {
+        let mut s = String::with_capacity(MAX);
+        for c in chars.into_iter().take_while(|c| s.len() + c.len_utf8() <= MAX) {
+            s.push(c);
+        }
+        MaxSizeString::try_from(s).expect("length is bounded by MAX")
+    })
+}
+
+pub fn tari_script() -> impl Strategy<Value = TariScript> {
+    prop_oneof![
+        Just(script!(Nop)),
+        any::<u64>().prop_map(|height| script!(CheckHeight(height))),
+        public_key().prop_map(|key| script!(Dup PushPubKey(Box::new(key)) Drop)),
+    ]
+}
+
+pub fn covenant() -> impl Strategy<Value = Covenant> {
+    prop_oneof![
+        Just(Covenant::new()),
+        Just(covenant!(identity())),
+        any::<u64>().prop_map(|height| covenant!(absolute_height(@uint(height)))),
+        tari_script().prop_map(|script| covenant!(field_eq(@field::script, @script(script)))),
+        (any::<u64>(), commitment()).prop_map(|(maturity, commitment)| covenant!(or(
+            field_eq(@field::features_maturity, @uint(maturity)),
+            field_eq(@field::commitment, @commitment(commitment))
+        ))),
+    ]
+}
+
+pub fn output_type() -> impl Strategy<Value = OutputType> {
+    prop_oneof![
+        Just(OutputType::Standard),
+        Just(OutputType::Coinbase),
+        Just(OutputType::Burn),
+        Just(OutputType::ValidatorNodeRegistration),
+        Just(OutputType::CodeTemplateRegistration),
+    ]
+}
+
+pub fn range_proof_type() -> impl Strategy<Value = RangeProofType> {
+    prop_oneof![
+        Just(RangeProofType::BulletProofPlus),
+        Just(RangeProofType::RevealedValue)
+    ]
+}
+
+pub fn output_features() -> impl Strategy<Value = OutputFeatures> {
+    (output_type(), any::<u64>(), vec(any::<u8>(), 0..64), range_proof_type()).prop_map(
+        |(output_type, maturity, coinbase_extra, range_proof_type)| {
+            OutputFeatures::new(
+                OutputFeaturesVersion::get_current_version(),
+                output_type,
+                maturity,
+                coinbase_extra,
+                None,
+                range_proof_type,
+            )
+        },
+    )
+}
+
+pub fn encrypted_data() -> impl Strategy<Value = EncryptedData> {
+    let len = EncryptedData::default().as_bytes().len();
+    vec(any::<u8>(), len).prop_map(|b| EncryptedData::from_bytes(&b).expect("length is exactly the encrypted size"))
+}
+
+pub fn transaction_output() -> impl Strategy<Value = TransactionOutput> {
+    (
+        output_features(),
+        commitment(),
+        option::of(vec(any::<u8>(), 0..1024).prop_map(BulletRangeProof)),
+        tari_script(),
+        public_key(),
+        com_and_pub_signature(),
+        covenant(),
+        encrypted_data(),
+        any::<u64>().prop_map(MicroMinotari::from),
+    )
+        .prop_map(
+            |(features, commitment, proof, script, sender_offset_public_key, signature, covenant, data, min_value)| {
+                TransactionOutput::new_current_version(
+                    features,
+                    commitment,
+                    proof,
+                    script,
+                    sender_offset_public_key,
+                    signature,
+                    covenant,
+                    data,
+                    min_value,
+                )
+            },
+        )
+}
+
+pub fn kernel_features() -> impl Strategy<Value = KernelFeatures> {
+    (0u8..=KernelFeatures::all().bits()).prop_map(KernelFeatures::from_bits_truncate)
+}
+
+pub fn transaction_kernel() -> impl Strategy<Value = TransactionKernel> {
+    (
+        kernel_features(),
+        any::<u64>(),
+        any::<u64>(),
+        commitment(),
+        signature(),
+        option::of(commitment()),
+    )
+        .prop_map(|(features, fee, lock_height, excess, excess_sig, burn_commitment)| {
+            TransactionKernel::new_current_version(
+                features,
+                fee.into(),
+                lock_height,
+                excess,
+                excess_sig,
+                burn_commitment,
+            )
+        })
+}
+
+pub fn proof_of_work() -> impl Strategy<Value = ProofOfWork> {
+    (
+        prop_oneof![Just(PowAlgorithm::RandomX), Just(PowAlgorithm::Sha3x)],
+        vec(any::<u8>(), 0..256),
+    )
+        .prop_map(|(pow_algo, pow_data)| ProofOfWork { pow_algo, pow_data })
+}
+
+pub fn block_header() -> impl Strategy<Value = BlockHeader> {
+    (
+        (any::<u16>(), any::<u64>(), fixed_hash(), any::<u64>()),
+        (fixed_hash(), fixed_hash(), any::<u64>(), fixed_hash(), any::<u64>()),
+        (
+            private_key(),
+            private_key(),
+            fixed_hash(),
+            proof_of_work(),
+            any::<u64>(),
+        ),
+    )
+        .prop_map(
+            |(
+                (version, height, prev_hash, timestamp),
+                (input_mr, output_mr, output_mmr_size, kernel_mr, kernel_mmr_size),
+                (total_kernel_offset, total_script_offset, validator_node_mr, pow, nonce),
+            )| BlockHeader {
+                version,
+                height,
+                prev_hash,
+                timestamp: EpochTime::from(timestamp),
+                input_mr,
+                output_mr,
+                output_mmr_size,
+                kernel_mr,
+                kernel_mmr_size,
+                total_kernel_offset,
+                total_script_offset,
+                validator_node_mr,
+                pow,
+                nonce,
+            },
+        )
+}
+
+#[cfg(test)]
+mod test {
+    use integer_encoding::VarIntWriter;
+
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn max_size_bytes_round_trips(value in max_size_bytes::<32>()) {
+            check_round_trip(&value)?;
+        }
+
+        #[test]
+        fn max_size_string_round_trips(value in max_size_string::<255>()) {
+            check_round_trip(&value)?;
+        }
+
+        #[test]
+        fn covenants_round_trip(value in covenant()) {
+            check_round_trip(&value)?;
+        }
+
+        #[test]
+        fn output_features_round_trip(value in output_features()) {
+            check_round_trip(&value)?;
+        }
+
+        #[test]
+        fn transaction_outputs_round_trip(value in transaction_output()) {
+            check_round_trip(&value)?;
+        }
+
+        #[test]
+        fn transaction_kernels_round_trip(value in transaction_kernel()) {
+            check_round_trip(&value)?;
+        }
+
+        #[test]
+        fn proof_of_work_round_trips(value in proof_of_work()) {
+            check_round_trip(&value)?;
+        }
+
+        #[test]
+        fn block_headers_round_trip(value in block_header()) {
+            check_round_trip(&value)?;
+            // Header equality is defined by hash, so also compare the fields that feed into it
+            let decoded = BlockHeader::deserialize(&mut value.try_to_vec().unwrap().as_slice()).unwrap();
+            prop_assert_eq!(decoded.hash(), value.hash());
+            prop_assert_eq!(decoded.nonce, value.nonce);
+            prop_assert_eq!(decoded.pow, value.pow);
+        }
+
+        #[test]
+        fn max_size_bytes_rejects_oversized_encodings(bytes in vec(any::<u8>(), 33..128)) {
+            let encoded = bytes.try_to_vec().unwrap();
+            prop_assert!(MaxSizeBytes::<32>::deserialize(&mut encoded.as_slice()).is_err());
+        }
+
+        #[test]
+        fn max_size_string_rejects_oversized_encodings(s in "[a-z]{33,128}") {
+            let encoded = s.try_to_vec().unwrap();
+            prop_assert!(MaxSizeString::<32>::deserialize(&mut encoded.as_slice()).is_err());
+        }
+
+        #[test]
+        fn covenant_rejects_oversized_encodings(len in (1usize << 13)..(1usize << 20)) {
+            let mut encoded = Vec::new();
+            encoded.write_varint(len).unwrap();
+            prop_assert!(Covenant::deserialize(&mut encoded.as_slice()).is_err());
+        }
+    }
+
+    #[cfg(feature = "base_node")]
+    proptest! {
+        #[test]
+        fn fixed_byte_array_rejects_oversized_encodings(len in 64u8..) {
+            use crate::proof_of_work::monero_rx::FixedByteArray;
+            let mut encoded = vec![len];
+            encoded.resize(usize::from(len) + 1, 0);
+            prop_assert!(FixedByteArray::deserialize(&mut encoded.as_slice()).is_err());
+        }
+    }
+}
@@ -21,14 +21,22 @@
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use std::sync::Arc;
+#[cfg(feature = "base_node")]
+use std::{collections::HashMap, ops::RangeInclusive, sync::RwLock};
 
 use tari_common::configuration::Network;
+#[cfg(feature = "base_node")]
+use tari_common_types::types::HashOutput;
 use thiserror::Error;
 
 #[cfg(feature = "base_node")]
 use crate::{
     blocks::ChainBlock,
-    consensus::chain_strength_comparer::{strongest_chain, ChainStrengthComparer},
+    chain_storage::{BlockchainBackend, ChainStorageError},
+    consensus::{
+        chain_strength_comparer::{strongest_chain, ChainStrengthComparer},
+        deployments::{Deployment, DeploymentStatus},
+    },
     proof_of_work::PowAlgorithm,
     proof_of_work::TargetDifficultyWindow,
 };
@@ -163,6 +171,78 @@ impl ConsensusManager {
     pub fn network(&self) -> NetworkConsensus {
         self.inner.network
     }
+
+    /// Returns the status of each deployment defined for `height`, reading signals from the main chain headers in
+    /// `db`. The signal counts of completed periods are cached by the hash of the last header of the period, so
+    /// repeated calls only read the headers of the current period and a reorg is picked up as a cache miss.
+    #[cfg(feature = "base_node")]
+    pub fn deployment_statuses<B: BlockchainBackend>(
+        &self,
+        db: &B,
+        height: u64,
+    ) -> Result<Vec<DeploymentStatus>, ChainStorageError> {
+        self.consensus_constants(height)
+            .deployments()
+            .iter()
+            .map(|deployment| {
+                let state = deployment.state_at(height, |range| self.period_signal_count(db, deployment, range))?;
+                let signals_in_current_period = if deployment.period == 0 {
+                    0
+                } else {
+                    count_deployment_signals(db, deployment, *deployment.period_range(height).start()..=height)?
+                };
+                Ok(DeploymentStatus {
+                    deployment: *deployment,
+                    state,
+                    signals_in_current_period,
+                })
+            })
+            .collect()
+    }
+
+    /// The number of headers signalling for `deployment` in the completed period `range`
+    #[cfg(feature = "base_node")]
+    fn period_signal_count<B: BlockchainBackend>(
+        &self,
+        db: &B,
+        deployment: &Deployment,
+        range: RangeInclusive<u64>,
+    ) -> Result<u64, ChainStorageError> {
+        let key = (deployment.name, *db.fetch_chain_header_by_height(*range.end())?.hash());
+        let cached = self
+            .inner
+            .deployment_signal_counts
+            .read()
+            .map_err(|e| ChainStorageError::AccessError(e.to_string()))?
+            .get(&key)
+            .copied();
+        if let Some(count) = cached {
+            return Ok(count);
+        }
+        let count = count_deployment_signals(db, deployment, range)?;
+        self.inner
+            .deployment_signal_counts
+            .write()
+            .map_err(|e| ChainStorageError::AccessError(e.to_string()))?
+            .insert(key, count);
+        Ok(count)
+    }
+}
+
+/// Counts the main chain headers in `range` that signal for `deployment`
+#[cfg(feature = "base_node")]
+fn count_deployment_signals<B: BlockchainBackend>(
+    db: &B,
+    deployment: &Deployment,
+    range: RangeInclusive<u64>,
+) -> Result<u64, ChainStorageError> {
+    let mut count = 0;
+    for height in range {
+        if deployment.is_signalled_by(db.fetch_chain_header_by_height(height)?.header().version) {
+            count += 1;
+        }
+    }
+    Ok(count)
 }
 
 /// This is the used to control all consensus values.
@@ -180,6 +260,10 @@ struct ConsensusManagerInner {
     #[cfg(feature = "base_node")]
     /// The comparer used to determine which chain is stronger for reorgs.
     pub chain_strength_comparer: Box<dyn ChainStrengthComparer + Send + Sync>,
+    /// Deployment signal counts of completed periods, keyed by deployment name and the hash of the last header of the
+    /// period
+    #[cfg(feature = "base_node")]
+    pub deployment_signal_counts: RwLock<HashMap<(&'static str, HashOutput), u64>>,
 }
 
 /// Constructor for the consensus manager struct
@@ -261,6 +345,8 @@ impl ConsensusManagerBuilder {
                     .by_sha3x_difficulty()
                     .build()
             }),
+            #[cfg(feature = "base_node")]
+            deployment_signal_counts: RwLock::new(HashMap::new()),
         };
         Ok(ConsensusManager { inner: Arc::new(inner) })
     }
@@ -0,0 +1,239 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+//! Version bits deployments, modelled on BIP9.
+//!
+//! The low byte of a block header version is the blockchain version, which is validated against the consensus
+//! constants. From the deployment signalling height set in the consensus constants, the high byte is a bitfield miners
+//! use to signal readiness for consensus changes; before it, the whole version is the blockchain version. A
+//! [Deployment] assigns one of these bits to a change and defines a window of heights in which signalling counts.
+//! Heights are grouped into periods of [Deployment::period] blocks and the [DeploymentState] only changes at period
+//! boundaries:
+//!
+//! - `Defined` until the first period starting at or after `start_height`, which is `Started`
+//! - `Started` becomes `LockedIn` once at least `threshold` headers of a period signal the bit, or `Failed` if the
+//!   period starts at or after `timeout_height` without that happening
+//! - `LockedIn` becomes `Active` one period later, after which validation rules gated on the deployment apply
+//!
+//! `Active` and `Failed` are final.
+
+use std::{fmt, ops::RangeInclusive};
+
+/// The bits of a block header version that hold the blockchain version
+pub const BLOCKCHAIN_VERSION_MASK: u16 = 0x00ff;
+/// The number of header version bits available for deployment signalling
+pub const NUM_SIGNAL_BITS: u8 = 8;
+const SIGNAL_BITS_SHIFT: u8 = 8;
+
+/// A consensus change that is activated by miner signalling
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Deployment {
+    /// A human readable, unique name for the deployment
+    pub name: &'static str,
+    /// The signal bit, in `0..NUM_SIGNAL_BITS`
+    pub bit: u8,
+    /// The height from which signalling is counted
+    pub start_height: u64,
+    /// The deployment fails if it has not locked in by the first period starting at or after this height
+    pub timeout_height: u64,
+    /// The number of blocks in a signalling period. Must be greater than zero.
+    pub period: u64,
+    /// The number of headers in a period that must signal for the deployment to lock in
+    pub threshold: u64,
+}
+
+/// The activation state of a [Deployment] at a given height
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeploymentState {
+    Defined,
+    Started,
+    LockedIn,
+    Active,
+    Failed,
+}
+
+/// The state of a [Deployment] at a given height, as tracked by the consensus manager
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeploymentStatus {
+    pub deployment: Deployment,
+    pub state: DeploymentState,
+    /// The number of headers in the period containing the height that signal for the deployment, up to and including
+    /// the height
+    pub signals_in_current_period: u64,
+}
+
+impl fmt::Display for DeploymentState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeploymentState::Defined => write!(f, "Defined"),
+            DeploymentState::Started => write!(f, "Started"),
+            DeploymentState::LockedIn => write!(f, "LockedIn"),
+            DeploymentState::Active => write!(f, "Active"),
+            DeploymentState::Failed => write!(f, "Failed"),
+        }
+    }
+}
+
+impl Deployment {
+    /// The header version bit this deployment signals on
+    pub fn version_mask(&self) -> u16 {
+        1u16 << (SIGNAL_BITS_SHIFT + self.bit)
+    }
+
+    /// Returns true if a header with `version` signals for this deployment
+    pub fn is_signalled_by(&self, version: u16) -> bool {
+        version & self.version_mask() != 0
+    }
+
+    /// Returns true if headers at `height` should signal for this deployment
+    pub fn is_signalling_height(&self, height: u64) -> bool {
+        height >= self.start_height && height < self.timeout_height
+    }
+
+    /// The heights of the signalling period containing `height`
+    pub fn period_range(&self, height: u64) -> RangeInclusive<u64> {
+        let start = height - height % self.period;
+        start..=start + self.period - 1
+    }
+
+    /// The completed periods whose signal counts [Deployment::state_at] may request for the block at `height`
+    pub fn counted_periods(&self, height: u64) -> Vec<RangeInclusive<u64>> {
+        if self.period == 0 {
+            return Vec::new();
+        }
+        (self.first_period()..height / self.period)
+            .map(|period| period * self.period..=(period + 1) * self.period - 1)
+            .take_while(|range| *range.start() < self.timeout_height)
+            .collect()
+    }
+
+    /// Computes the state of this deployment for the block at `height`. `count_signals` is called with the heights of
+    /// each completed period since `start_height` and must return how many headers in that range signal this
+    /// deployment.
+    pub fn state_at<F, E>(&self, height: u64, mut count_signals: F) -> Result<DeploymentState, E>
+    where F: FnMut(RangeInclusive<u64>) -> Result<u64, E> {
+        if self.period == 0 {
+            return Ok(DeploymentState::Defined);
+        }
+        let current_period = height / self.period;
+        let first_period = self.first_period();
+        if current_period < first_period {
+            return Ok(DeploymentState::Defined);
+        }
+
+        let mut state = if first_period * self.period >= self.timeout_height {
+            DeploymentState::Failed
+        } else {
+            DeploymentState::Started
+        };
+        for period in first_period + 1..=current_period {
+            let period_start = period * self.period;
+            state = match state {
+                DeploymentState::Started => {
+                    if period_start >= self.timeout_height {
+                        DeploymentState::Failed
+                    } else if count_signals(period_start - self.period..=period_start - 1)? >= self.threshold {
+                        DeploymentState::LockedIn
+                    } else {
+                        DeploymentState::Started
+                    }
+                },
+                DeploymentState::LockedIn => DeploymentState::Active,
+                state => return Ok(state),
+            };
+        }
+        Ok(state)
+    }
+
+    /// The index of the first period starting at or after `start_height`
+    fn first_period(&self) -> u64 {
+        self.start_height / self.period + u64::from(self.start_height % self.period != 0)
+    }
+}
+
+/// Returns the blockchain version part of a header version
+pub fn blockchain_version(header_version: u16) -> u16 {
+    header_version & BLOCKCHAIN_VERSION_MASK
+}
+
+/// The header version signal bits for all `deployments` in their signalling window at `height`
+pub fn signal_bits(deployments: &[Deployment], height: u64) -> u16 {
+    deployments
+        .iter()
+        .filter(|d| d.is_signalling_height(height))
+        .fold(0, |bits, d| bits | d.version_mask())
+}
+
+#[cfg(test)]
+mod test {
+    use std::convert::Infallible;
+
+    use super::*;
+
+    const DEPLOYMENT: Deployment = Deployment {
+        name: "test",
+        bit: 2,
+        start_height: 15,
+        timeout_height: 100,
+        period: 10,
+        threshold: 8,
+    };
+
+    fn state_with(height: u64, signals: impl Fn(u64) -> u64) -> DeploymentState {
+        DEPLOYMENT
+            .state_at(height, |range| Ok::<_, Infallible>(signals(*range.start())))
+            .unwrap()
+    }
+
+    #[test]
+    fn it_splits_header_versions() {
+        assert_eq!(DEPLOYMENT.version_mask(), 0x0400);
+        assert!(DEPLOYMENT.is_signalled_by(0x0401));
+        assert!(!DEPLOYMENT.is_signalled_by(0x0001));
+        assert_eq!(blockchain_version(0x0401), 1);
+        assert_eq!(signal_bits(&[DEPLOYMENT], 14), 0);
+        assert_eq!(signal_bits(&[DEPLOYMENT], 15), 0x0400);
+        assert_eq!(signal_bits(&[DEPLOYMENT], 100), 0);
+        assert_eq!(DEPLOYMENT.period_range(27), 20..=29);
+        assert_eq!(DEPLOYMENT.counted_periods(35), vec![20..=29]);
+        assert_eq!(DEPLOYMENT.counted_periods(1000).len(), 8);
+    }
+
+    #[test]
+    fn it_locks_in_and_activates_after_enough_signals() {
+        // Signalling starts in the first period starting at or after height 15
+        assert_eq!(state_with(19, |_| 10), DeploymentState::Defined);
+        assert_eq!(state_with(20, |_| 10), DeploymentState::Started);
+        // Period 20..=29 signals enough, so period 30 is locked in and period 40 is active
+        let signals = |start| if start == 20 { 8 } else { 0 };
+        assert_eq!(state_with(29, signals), DeploymentState::Started);
+        assert_eq!(state_with(30, signals), DeploymentState::LockedIn);
+        assert_eq!(state_with(40, signals), DeploymentState::Active);
+        assert_eq!(state_with(1000, signals), DeploymentState::Active);
+    }
+
+    #[test]
+    fn it_fails_at_the_timeout() {
+        assert_eq!(state_with(99, |_| 7), DeploymentState::Started);
+        assert_eq!(state_with(100, |_| 7), DeploymentState::Failed);
+        assert_eq!(state_with(1000, |_| 7), DeploymentState::Failed);
+    }
+}
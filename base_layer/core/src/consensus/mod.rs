@@ -34,4 +34,6 @@ pub use consensus_encoding::{DomainSeparatedConsensusHasher, MaxSizeBytes, MaxSi
 mod network;
 pub use network::NetworkConsensus;
 
+pub mod deployments;
+
 pub mod emission;
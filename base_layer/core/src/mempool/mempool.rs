@@ -23,22 +23,24 @@
 use std::sync::{Arc, RwLock};
 
 use tari_common_types::types::{PrivateKey, Signature};
+use tari_comms::peer_manager::NodeId;
 use tokio::task;
 
 use crate::{
-    blocks::Block,
+    blocks::{Block, ShortKernelId, ShortKernelIdGenerator},
     consensus::ConsensusManager,
     mempool::{
         error::MempoolError,
         mempool_storage::MempoolStorage,
         FeePerGramStat,
         MempoolConfig,
+        MempoolGraph,
         StateResponse,
         StatsResponse,
         TxStorageResponse,
     },
     transactions::transaction_components::Transaction,
-    validation::TransactionValidator,
+    validation::{TransactionValidator, ValidationRejection},
 };
 
 /// The Mempool consists of an Unconfirmed Transaction Pool, Pending Pool, Orphan Pool and Reorg Pool and is responsible
@@ -61,18 +63,69 @@ impl Mempool {
     pub async fn insert(&self, tx: Arc<Transaction>) -> Result<TxStorageResponse, MempoolError> {
         self.with_write_access(|storage| {
             storage
-                .insert(tx)
+                .insert(tx, None)
                 .map_err(|e| MempoolError::InternalError(e.to_string()))
         })
         .await
     }
 
+    /// Insert an unconfirmed transaction relayed by `source_peer` into the Mempool. The number of transactions a single
+    /// peer can have in the Mempool is limited.
+    pub async fn insert_from_peer(
+        &self,
+        tx: Arc<Transaction>,
+        source_peer: NodeId,
+    ) -> Result<TxStorageResponse, MempoolError> {
+        self.with_write_access(move |storage| {
+            storage
+                .insert(tx, Some(&source_peer))
+                .map_err(|e| MempoolError::InternalError(e.to_string()))
+        })
+        .await
+    }
+
+    /// Validate a Dandelion++ stem-phase transaction relayed by `source_peer` (`None` for local transactions) and hold
+    /// it in the stem pool until it is fluffed. Stem transactions are not shared by mempool sync or selected into block
+    /// templates.
+    pub async fn insert_stem(
+        &self,
+        tx: Arc<Transaction>,
+        source_peer: Option<NodeId>,
+    ) -> Result<TxStorageResponse, MempoolError> {
+        self.with_write_access(move |storage| {
+            storage
+                .insert_stem(tx, source_peer.as_ref())
+                .map_err(|e| MempoolError::InternalError(e.to_string()))
+        })
+        .await
+    }
+
+    /// Move a stem transaction into the unconfirmed pool. Returns the transaction and where it was stored, or `None` if
+    /// it is no longer in the stem pool.
+    pub async fn fluff_stem_transaction(
+        &self,
+        excess_sig: PrivateKey,
+    ) -> Result<Option<(Arc<Transaction>, TxStorageResponse)>, MempoolError> {
+        self.with_write_access(move |storage| {
+            storage
+                .fluff_stem_transaction(&excess_sig)
+                .map_err(|e| MempoolError::InternalError(e.to_string()))
+        })
+        .await
+    }
+
+    /// Check if the specified transaction is held in the stem pool.
+    pub async fn has_stem_transaction(&self, tx: Arc<Transaction>) -> Result<bool, MempoolError> {
+        self.with_read_access(move |storage| Ok(storage.has_stem_transaction(&tx)))
+            .await
+    }
+
     /// Inserts all transactions into the mempool.
     pub async fn insert_all(&self, transactions: Vec<Arc<Transaction>>) -> Result<(), MempoolError> {
         self.with_write_access(|storage| {
             for tx in transactions {
                 storage
-                    .insert(tx)
+                    .insert(tx, None)
                     .map_err(|e| MempoolError::InternalError(e.to_string()))?;
             }
 
@@ -129,12 +182,28 @@ impl Mempool {
             .await
     }
 
+    pub async fn retrieve_by_short_kernel_ids(
+        &self,
+        generator: ShortKernelIdGenerator,
+        short_ids: Vec<ShortKernelId>,
+    ) -> Result<(Vec<Arc<Transaction>>, Vec<ShortKernelId>), MempoolError> {
+        self.with_read_access(move |storage| storage.retrieve_by_short_kernel_ids(&generator, &short_ids))
+            .await
+    }
+
     /// Check if the specified excess signature is found in the Mempool.
     pub async fn has_tx_with_excess_sig(&self, excess_sig: Signature) -> Result<TxStorageResponse, MempoolError> {
         self.with_read_access(move |storage| Ok(storage.has_tx_with_excess_sig(&excess_sig)))
             .await
     }
 
+    /// Returns the reason the transaction with the specified excess signature was rejected, if it recently failed
+    /// validation.
+    pub async fn get_rejection(&self, excess_sig: Signature) -> Result<Option<ValidationRejection>, MempoolError> {
+        self.with_read_access(move |storage| Ok(storage.get_rejection(&excess_sig)))
+            .await
+    }
+
     /// Check if the specified transaction is stored in the Mempool.
     pub async fn has_transaction(&self, tx: Arc<Transaction>) -> Result<TxStorageResponse, MempoolError> {
         self.with_read_access(move |storage| storage.has_transaction(&tx)).await
@@ -151,6 +220,12 @@ impl Mempool {
         self.with_read_access(|storage| Ok(storage.state())).await
     }
 
+    /// Returns the dependency graph of the unconfirmed pool, for visualising why transactions are not selected into
+    /// block templates.
+    pub async fn graph(&self) -> Result<MempoolGraph, MempoolError> {
+        self.with_read_access(|storage| storage.graph()).await
+    }
+
     pub async fn get_fee_per_gram_stats(
         &self,
         count: usize,
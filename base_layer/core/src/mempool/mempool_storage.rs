@@ -20,40 +20,56 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{sync::Arc, time::Instant};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+    time::Instant,
+};
 
 use log::*;
 use tari_common_types::types::{PrivateKey, Signature};
+use tari_comms::peer_manager::NodeId;
 use tari_utilities::hex::Hex;
 
 use crate::{
-    blocks::Block,
+    blocks::{Block, ShortKernelId, ShortKernelIdGenerator},
     consensus::ConsensusManager,
     mempool::{
         error::MempoolError,
         reorg_pool::ReorgPool,
-        unconfirmed_pool::UnconfirmedPool,
+        stem_pool::StemPool,
+        unconfirmed_pool::{UnconfirmedPool, UnconfirmedPoolInsertResult},
         FeePerGramStat,
         MempoolConfig,
+        MempoolGraph,
         StateResponse,
         StatsResponse,
         TxStorageResponse,
     },
     transactions::{transaction_components::Transaction, weight::TransactionWeight},
-    validation::{TransactionValidator, ValidationError},
+    validation::{TransactionValidator, ValidationError, ValidationRejection},
 };
 
 pub const LOG_TARGET: &str = "c::mp::mempool_storage";
 
+/// The number of recently rejected transactions whose rejection reason is kept
+const RECENT_REJECTIONS_CAPACITY: usize = 1_000;
+/// The maximum number of transactions held in the stem pool
+const STEM_POOL_CAPACITY: usize = 1_000;
+
 /// The Mempool consists of an Unconfirmed Transaction Pool and Reorg Pool and is responsible
 /// for managing and maintaining all unconfirmed transactions have not yet been included in a block, and transactions
 /// that have recently been included in a block.
 pub struct MempoolStorage {
     unconfirmed_pool: UnconfirmedPool,
     reorg_pool: ReorgPool,
+    stem_pool: StemPool,
     validator: Box<dyn TransactionValidator>,
     rules: ConsensusManager,
     last_seen_height: u64,
+    rejected_txs: u64,
+    evicted_txs: u64,
+    recent_rejections: VecDeque<(Signature, ValidationRejection)>,
 }
 
 impl MempoolStorage {
@@ -62,20 +78,78 @@ impl MempoolStorage {
         Self {
             unconfirmed_pool: UnconfirmedPool::new(config.unconfirmed_pool),
             reorg_pool: ReorgPool::new(config.reorg_pool),
+            stem_pool: StemPool::new(STEM_POOL_CAPACITY),
             validator,
             rules,
             last_seen_height: 0,
+            rejected_txs: 0,
+            evicted_txs: 0,
+            recent_rejections: VecDeque::new(),
+        }
+    }
+
+    /// Insert an unconfirmed transaction into the Mempool. `source_peer` is the peer that relayed the transaction, if
+    /// any, and is used to enforce the per-peer transaction limit.
+    pub fn insert(&mut self, tx: Arc<Transaction>, source_peer: Option<&NodeId>) -> std::io::Result<TxStorageResponse> {
+        let response = self.insert_transaction(tx, source_peer, false)?;
+        if !response.is_stored() {
+            self.rejected_txs += 1;
         }
+        Ok(response)
     }
 
-    /// Insert an unconfirmed transaction into the Mempool.
-    pub fn insert(&mut self, tx: Arc<Transaction>) -> std::io::Result<TxStorageResponse> {
+    /// Validate a Dandelion++ stem-phase transaction and hold it in the stem pool until it is fluffed. A transaction
+    /// that spends outputs of transactions in the unconfirmed pool cannot be validated on its own, so it is inserted
+    /// into the unconfirmed pool instead.
+    pub fn insert_stem(
+        &mut self,
+        tx: Arc<Transaction>,
+        source_peer: Option<&NodeId>,
+    ) -> std::io::Result<TxStorageResponse> {
+        let response = self.insert_transaction(tx, source_peer, true)?;
+        if !response.is_stored() {
+            self.rejected_txs += 1;
+        }
+        Ok(response)
+    }
+
+    /// Move the stem transaction with the given first kernel excess signature into the unconfirmed pool, revalidating
+    /// it. Returns `None` if the stem pool does not contain the transaction.
+    pub fn fluff_stem_transaction(
+        &mut self,
+        excess_sig: &PrivateKey,
+    ) -> std::io::Result<Option<(Arc<Transaction>, TxStorageResponse)>> {
+        let tx = match self.stem_pool.remove(excess_sig) {
+            Some(tx) => tx,
+            None => return Ok(None),
+        };
+        let response = self.insert(tx.clone(), None)?;
+        Ok(Some((tx, response)))
+    }
+
+    /// Returns true if the transaction is held in the stem pool
+    pub fn has_stem_transaction(&self, tx: &Transaction) -> bool {
+        self.stem_pool.contains(tx)
+    }
+
+    fn insert_transaction(
+        &mut self,
+        tx: Arc<Transaction>,
+        source_peer: Option<&NodeId>,
+        stem: bool,
+    ) -> std::io::Result<TxStorageResponse> {
         let tx_id = tx
             .body
             .kernels()
             .first()
             .map(|k| k.excess_sig.get_signature().to_hex())
             .unwrap_or_else(|| "None?!".into());
+        if !stem {
+            // A stem transaction that has been fluffed by another node no longer needs to be held back
+            if let Some(excess_sig) = tx.first_kernel_excess_sig() {
+                self.stem_pool.remove(excess_sig.get_signature());
+            }
+        }
         let timer = Instant::now();
         // This check is almost free, so lets check this before we do any expensive validation.
         if tx.body.get_total_fee().as_u64() < self.unconfirmed_pool.config.min_fee {
@@ -83,7 +157,17 @@ impl MempoolStorage {
             return Ok(TxStorageResponse::NotStoredFeeTooLow);
         }
         debug!(target: LOG_TARGET, "Inserting tx into mempool: {}", tx_id);
-        match self.validator.validate(&tx) {
+        let err = match self.validator.validate(&tx) {
+            Ok(()) if stem => {
+                debug!(
+                    target: LOG_TARGET,
+                    "Transaction {} is VALID ({:.2?}), inserting in stem pool",
+                    tx_id,
+                    timer.elapsed()
+                );
+                self.stem_pool.insert(tx);
+                return Ok(TxStorageResponse::StemPool);
+            },
             Ok(()) => {
                 debug!(
                     target: LOG_TARGET,
@@ -93,52 +177,96 @@ impl MempoolStorage {
                 );
                 let timer = Instant::now();
                 let weight = self.get_transaction_weighting();
-                self.unconfirmed_pool.insert(tx, None, &weight)?;
+                let result = self.unconfirmed_pool.insert(tx, None, &weight, source_peer)?;
                 debug!(
                     target: LOG_TARGET,
-                    "Transaction {} inserted in {:.2?}",
+                    "Transaction {} processed in {:.2?}: {:?}",
                     tx_id,
-                    timer.elapsed()
+                    timer.elapsed(),
+                    result
                 );
-                Ok(TxStorageResponse::UnconfirmedPool)
+                return Ok(self.storage_response_from_insert_result(result));
             },
-            Err(ValidationError::UnknownInputs(dependent_outputs)) => {
-                if self.unconfirmed_pool.contains_all_outputs(&dependent_outputs) {
+            Err(err) => err,
+        };
+        // Output and kernel errors carry the location of the failure, but the response depends on the underlying error
+        match err.root_cause() {
+            ValidationError::UnknownInputs(dependent_outputs) => {
+                if self.unconfirmed_pool.contains_all_outputs(dependent_outputs) {
+                    let dependent_outputs = dependent_outputs.clone();
                     let weight = self.get_transaction_weighting();
-                    self.unconfirmed_pool.insert(tx, Some(dependent_outputs), &weight)?;
-                    Ok(TxStorageResponse::UnconfirmedPool)
+                    let result = self
+                        .unconfirmed_pool
+                        .insert(tx, Some(dependent_outputs), &weight, source_peer)?;
+                    Ok(self.storage_response_from_insert_result(result))
                 } else {
                     warn!(target: LOG_TARGET, "Validation failed due to unknown inputs");
                     Ok(TxStorageResponse::NotStoredOrphan)
                 }
             },
-            Err(ValidationError::ContainsSTxO) => {
+            ValidationError::ContainsSTxO => {
                 warn!(target: LOG_TARGET, "Validation failed due to already spent input");
                 Ok(TxStorageResponse::NotStoredAlreadySpent)
             },
-            Err(ValidationError::MaturityError) => {
+            ValidationError::MaturityError => {
                 warn!(target: LOG_TARGET, "Validation failed due to maturity error");
                 Ok(TxStorageResponse::NotStoredTimeLocked)
             },
-            Err(ValidationError::ConsensusError(msg)) => {
-                warn!(target: LOG_TARGET, "Validation failed due to consensus rule: {}", msg);
+            ValidationError::ConsensusError(_) => {
+                warn!(target: LOG_TARGET, "Validation failed due to consensus rule: {}", err);
+                self.record_rejection(&tx, &err);
                 Ok(TxStorageResponse::NotStoredConsensus)
             },
-            Err(ValidationError::DuplicateKernelError(msg)) => {
+            ValidationError::DuplicateKernelError(msg) => {
                 debug!(
                     target: LOG_TARGET,
                     "Validation failed due to already mined kernel: {}", msg
                 );
                 Ok(TxStorageResponse::NotStoredAlreadyMined)
             },
-            Err(e) => {
-                eprintln!("Validation failed due to error: {}", e);
-                warn!(target: LOG_TARGET, "Validation failed due to error: {}", e);
+            _ => {
+                eprintln!("Validation failed due to error: {}", err);
+                warn!(target: LOG_TARGET, "Validation failed due to error: {}", err);
+                self.record_rejection(&tx, &err);
                 Ok(TxStorageResponse::NotStored)
             },
         }
     }
 
+    /// Keeps the reason a transaction failed validation, so that the submitter can look it up
+    fn record_rejection(&mut self, tx: &Transaction, err: &ValidationError) {
+        let excess_sig = match tx.first_kernel_excess_sig() {
+            Some(excess_sig) => excess_sig.clone(),
+            None => return,
+        };
+        if self.recent_rejections.len() >= RECENT_REJECTIONS_CAPACITY {
+            self.recent_rejections.pop_front();
+        }
+        self.recent_rejections.push_back((excess_sig, err.into()));
+    }
+
+    /// Returns the reason the transaction with the given first kernel excess signature was rejected, if it recently
+    /// failed validation
+    pub fn get_rejection(&self, excess_sig: &Signature) -> Option<ValidationRejection> {
+        self.recent_rejections
+            .iter()
+            .rev()
+            .find(|(sig, _)| sig == excess_sig)
+            .map(|(_, rejection)| rejection.clone())
+    }
+
+    fn storage_response_from_insert_result(&mut self, result: UnconfirmedPoolInsertResult) -> TxStorageResponse {
+        match result {
+            UnconfirmedPoolInsertResult::Inserted { num_evicted } => {
+                self.evicted_txs += num_evicted as u64;
+                TxStorageResponse::UnconfirmedPool
+            },
+            UnconfirmedPoolInsertResult::AlreadyStored => TxStorageResponse::UnconfirmedPool,
+            UnconfirmedPoolInsertResult::RejectedPoolFull => TxStorageResponse::NotStoredMempoolFull,
+            UnconfirmedPoolInsertResult::RejectedPeerLimit => TxStorageResponse::NotStoredPeerLimit,
+        }
+    }
+
     fn get_transaction_weighting(&self) -> TransactionWeight {
         *self
             .rules
@@ -149,7 +277,7 @@ impl MempoolStorage {
     // Insert a set of new transactions into the UTxPool.
     fn insert_txs(&mut self, txs: Vec<Arc<Transaction>>) -> std::io::Result<()> {
         for tx in txs {
-            self.insert(tx)?;
+            self.insert_transaction(tx, None, false)?;
         }
         Ok(())
     }
@@ -177,6 +305,7 @@ impl MempoolStorage {
             published_block.header.hash().to_hex(),
             published_block.body.to_counts_string()
         );
+        self.stem_pool.remove_published(published_block);
         let timer = Instant::now();
         self.reorg_pool
             .insert_all(published_block.header.height, removed_transactions);
@@ -242,11 +371,13 @@ impl MempoolStorage {
             .remove_reorged_txs_and_discard_double_spends(removed_blocks, new_blocks);
         self.insert_txs(removed_txs)
             .map_err(|e| MempoolError::InternalError(e.to_string()))?;
-        if let Some(height) = new_blocks
-            .last()
-            .or_else(|| removed_blocks.first())
-            .map(|block| block.header.height)
-        {
+        // If no blocks were added, the chain was rewound and the new tip is the parent of the lowest removed block
+        if let Some(height) = new_blocks.last().map(|block| block.header.height).or_else(|| {
+            removed_blocks
+                .iter()
+                .map(|block| block.header.height.saturating_sub(1))
+                .min()
+        }) {
             self.last_seen_height = height;
         }
         Ok(())
@@ -297,6 +428,33 @@ impl MempoolStorage {
         }
     }
 
+    /// Retrieves the transactions matching the given short kernel IDs, returning the transactions that were found and
+    /// the short IDs that did not match any transaction.
+    pub fn retrieve_by_short_kernel_ids(
+        &self,
+        generator: &ShortKernelIdGenerator,
+        short_ids: &[ShortKernelId],
+    ) -> Result<(Vec<Arc<Transaction>>, Vec<ShortKernelId>), MempoolError> {
+        let sigs_by_short_id = self
+            .unconfirmed_pool
+            .excess_sigs()
+            .chain(self.reorg_pool.excess_sigs())
+            .map(|sig| (generator.generate(sig), sig))
+            .collect::<HashMap<_, _>>();
+
+        let mut excess_sigs = Vec::with_capacity(short_ids.len());
+        let mut remaining = Vec::new();
+        for id in short_ids {
+            match sigs_by_short_id.get(id) {
+                Some(sig) => excess_sigs.push((*sig).clone()),
+                None => remaining.push(*id),
+            }
+        }
+
+        let (found, _) = self.retrieve_by_excess_sigs(&excess_sigs)?;
+        Ok((found, remaining))
+    }
+
     /// Check if the specified excess signature is found in the Mempool.
     pub fn has_tx_with_excess_sig(&self, excess_sig: &Signature) -> TxStorageResponse {
         if self.unconfirmed_pool.has_tx_with_excess_sig(excess_sig) {
@@ -347,6 +505,8 @@ impl MempoolStorage {
             unconfirmed_txs: self.unconfirmed_pool.len() as u64,
             reorg_txs: self.reorg_pool.len() as u64,
             unconfirmed_weight: self.unconfirmed_pool.calculate_weight(&weighting)?,
+            rejected_txs: self.rejected_txs,
+            evicted_txs: self.evicted_txs,
         })
     }
 
@@ -365,6 +525,11 @@ impl MempoolStorage {
         }
     }
 
+    /// Returns the dependency graph of the unconfirmed pool
+    pub fn graph(&self) -> Result<MempoolGraph, MempoolError> {
+        Ok(self.unconfirmed_pool.graph()?)
+    }
+
     pub fn get_fee_per_gram_stats(&self, count: usize, tip_height: u64) -> Result<Vec<FeePerGramStat>, MempoolError> {
         let target_weight = self
             .rules
@@ -47,6 +47,8 @@ mod metrics;
 #[cfg(feature = "base_node")]
 mod shrink_hashmap;
 #[cfg(feature = "base_node")]
+mod stem_pool;
+#[cfg(feature = "base_node")]
 mod unconfirmed_pool;
 
 // Public re-exports
@@ -74,7 +76,7 @@ use std::sync::Arc;
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "base_node")]
 pub use sync_protocol::MempoolSyncInitializer;
-use tari_common_types::types::Signature;
+use tari_common_types::types::{HashOutput, Signature};
 
 use crate::{
     proto::base_node as base_node_proto,
@@ -86,14 +88,19 @@ pub struct StatsResponse {
     pub unconfirmed_txs: u64,
     pub reorg_txs: u64,
     pub unconfirmed_weight: u64,
+    /// The number of transactions rejected by the mempool since startup
+    pub rejected_txs: u64,
+    /// The number of transactions evicted from the unconfirmed pool to make space for higher priority transactions
+    /// since startup
+    pub evicted_txs: u64,
 }
 
 impl Display for StatsResponse {
     fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), Error> {
         write!(
             fmt,
-            "Mempool stats: Unconfirmed: {}, In Reorg Pool: {}, Total Weight: {}g",
-            self.unconfirmed_txs, self.reorg_txs, self.unconfirmed_weight
+            "Mempool stats: Unconfirmed: {}, In Reorg Pool: {}, Total Weight: {}g, Rejected: {}, Evicted: {}",
+            self.unconfirmed_txs, self.reorg_txs, self.unconfirmed_weight, self.rejected_txs, self.evicted_txs
         )
     }
 }
@@ -108,6 +115,9 @@ pub struct StateResponse {
 pub enum TxStorageResponse {
     UnconfirmedPool,
     ReorgPool,
+    /// The transaction is valid and is being relayed in the Dandelion++ stem phase. It is not yet in the unconfirmed
+    /// pool.
+    StemPool,
     NotStoredOrphan,
     NotStoredTimeLocked,
     NotStoredAlreadySpent,
@@ -115,11 +125,13 @@ pub enum TxStorageResponse {
     NotStored,
     NotStoredAlreadyMined,
     NotStoredFeeTooLow,
+    NotStoredMempoolFull,
+    NotStoredPeerLimit,
 }
 
 impl TxStorageResponse {
     pub fn is_stored(&self) -> bool {
-        matches!(self, Self::UnconfirmedPool | Self::ReorgPool)
+        matches!(self, Self::UnconfirmedPool | Self::ReorgPool | Self::StemPool)
     }
 }
 
@@ -128,6 +140,7 @@ impl Display for TxStorageResponse {
         let storage = match self {
             TxStorageResponse::UnconfirmedPool => "Unconfirmed pool",
             TxStorageResponse::ReorgPool => "Reorg pool",
+            TxStorageResponse::StemPool => "Stem pool",
             TxStorageResponse::NotStoredOrphan => "Not stored orphan transaction",
             TxStorageResponse::NotStoredTimeLocked => "Not stored time locked transaction",
             TxStorageResponse::NotStoredAlreadySpent => "Not stored output already spent",
@@ -135,11 +148,43 @@ impl Display for TxStorageResponse {
             TxStorageResponse::NotStored => "Not stored",
             TxStorageResponse::NotStoredAlreadyMined => "Not stored tx already mined",
             TxStorageResponse::NotStoredFeeTooLow => "Not stored tx fee is below the minimum accepted by this mempool",
+            TxStorageResponse::NotStoredMempoolFull => {
+                "Not stored mempool is full and tx fee is too low to replace others"
+            },
+            TxStorageResponse::NotStoredPeerLimit => "Not stored peer has reached its mempool transaction limit",
         };
         fmt.write_str(storage)
     }
 }
 
+/// A transaction in the unconfirmed pool, as a node of the [MempoolGraph]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MempoolGraphTransaction {
+    pub excess_sig: Signature,
+    pub weight: u64,
+    pub total_fee: MicroMinotari,
+    pub fee_per_gram: u64,
+    /// The number of outputs this transaction spends that were in the pool when it was inserted, but are no longer
+    /// there. Such a transaction cannot be selected into a block template until it has been revalidated.
+    pub num_missing_parent_outputs: usize,
+}
+
+/// A dependency between two transactions in the unconfirmed pool: `child` spends an output created by `parent`. Both
+/// are indexes into [MempoolGraph::transactions].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MempoolGraphEdge {
+    pub parent: usize,
+    pub child: usize,
+    pub output_hash: HashOutput,
+}
+
+/// The dependency graph of the unconfirmed pool, with transactions ordered from highest to lowest priority
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MempoolGraph {
+    pub transactions: Vec<MempoolGraphTransaction>,
+    pub edges: Vec<MempoolGraphEdge>,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct FeePerGramStat {
     pub order: u64,
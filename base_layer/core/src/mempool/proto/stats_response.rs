@@ -32,6 +32,8 @@ impl TryFrom<ProtoStatsResponse> for StatsResponse {
             unconfirmed_txs: stats.unconfirmed_txs,
             reorg_txs: stats.reorg_txs,
             unconfirmed_weight: stats.unconfirmed_weight,
+            rejected_txs: stats.rejected_txs,
+            evicted_txs: stats.evicted_txs,
         })
     }
 }
@@ -42,6 +44,8 @@ impl From<StatsResponse> for ProtoStatsResponse {
             unconfirmed_txs: stats.unconfirmed_txs,
             reorg_txs: stats.reorg_txs,
             unconfirmed_weight: stats.unconfirmed_weight,
+            rejected_txs: stats.rejected_txs,
+            evicted_txs: stats.evicted_txs,
         }
     }
 }
@@ -45,6 +45,8 @@ impl From<TxStorageResponse> for proto::TxStorageResponse {
         match response {
             UnconfirmedPool => proto::TxStorageResponse::UnconfirmedPool,
             ReorgPool => proto::TxStorageResponse::ReorgPool,
+            // The transaction was accepted, it is only held back from the unconfirmed pool while it is stemmed
+            StemPool => proto::TxStorageResponse::UnconfirmedPool,
             NotStored => proto::TxStorageResponse::NotStored,
             NotStoredOrphan => proto::TxStorageResponse::NotStored,
             NotStoredTimeLocked => proto::TxStorageResponse::NotStored,
@@ -52,6 +54,8 @@ impl From<TxStorageResponse> for proto::TxStorageResponse {
             NotStoredConsensus => proto::TxStorageResponse::NotStored,
             NotStoredAlreadyMined => proto::TxStorageResponse::NotStored,
             NotStoredFeeTooLow => proto::TxStorageResponse::NotStored,
+            NotStoredMempoolFull => proto::TxStorageResponse::NotStored,
+            NotStoredPeerLimit => proto::TxStorageResponse::NotStored,
         }
     }
 }
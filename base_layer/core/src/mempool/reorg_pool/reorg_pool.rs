@@ -167,6 +167,11 @@ impl ReorgPool {
         Ok((found, remaining))
     }
 
+    /// Returns an iterator over the kernel excess signature scalars of all transactions in the pool
+    pub fn excess_sigs(&self) -> impl Iterator<Item = &PrivateKey> {
+        self.txs_by_signature.keys()
+    }
+
     /// Check if a transaction is stored in the ReorgPool
     pub fn has_tx_with_excess_sig(&self, excess_sig: &Signature) -> bool {
         self.txs_by_signature.contains_key(excess_sig.get_signature())
@@ -52,6 +52,8 @@ mod get_stats {
 
             reorg_txs: 5,
             unconfirmed_weight: 6,
+            rejected_txs: 7,
+            evicted_txs: 8,
         };
         mempool.set_get_stats_response(expected_stats.clone()).await;
 
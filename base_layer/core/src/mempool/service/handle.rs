@@ -27,12 +27,14 @@ use crate::{
     mempool::{
         service::{MempoolRequest, MempoolResponse},
         FeePerGramStat,
+        MempoolGraph,
         MempoolServiceError,
         StateResponse,
         StatsResponse,
         TxStorageResponse,
     },
     transactions::transaction_components::Transaction,
+    validation::ValidationRejection,
 };
 
 #[derive(Clone)]
@@ -97,4 +99,25 @@ impl MempoolHandle {
             _ => panic!("Incorrect response"),
         }
     }
+
+    pub async fn get_transaction_rejection_by_excess_sig(
+        &mut self,
+        excess_sig: Signature,
+    ) -> Result<Option<ValidationRejection>, MempoolServiceError> {
+        match self
+            .inner
+            .call(MempoolRequest::GetTxRejectionByExcessSig(excess_sig))
+            .await??
+        {
+            MempoolResponse::TxRejection(rejection) => Ok(rejection),
+            _ => panic!("Incorrect response"),
+        }
+    }
+
+    pub async fn get_mempool_graph(&mut self) -> Result<MempoolGraph, MempoolServiceError> {
+        match self.inner.call(MempoolRequest::GetGraph).await?? {
+            MempoolResponse::Graph(graph) => Ok(graph),
+            _ => panic!("Incorrect response"),
+        }
+    }
 }
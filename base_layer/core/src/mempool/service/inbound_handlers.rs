@@ -23,7 +23,9 @@
 use std::sync::Arc;
 
 use log::*;
+use tari_common_types::types::PrivateKey;
 use tari_comms::peer_manager::NodeId;
+use tari_comms_dht::dandelion::DandelionPhase;
 use tari_utilities::hex::Hex;
 
 use crate::{
@@ -60,7 +62,15 @@ impl MempoolInboundHandlers {
     /// Handle inbound Mempool service requests from remote nodes and local services.
     pub async fn handle_request(&mut self, request: MempoolRequest) -> Result<MempoolResponse, MempoolServiceError> {
         debug!(target: LOG_TARGET, "Handling remote request: {}", request);
-        use MempoolRequest::{GetFeePerGramStats, GetState, GetStats, GetTxStateByExcessSig, SubmitTransaction};
+        use MempoolRequest::{
+            GetFeePerGramStats,
+            GetGraph,
+            GetState,
+            GetStats,
+            GetTxRejectionByExcessSig,
+            GetTxStateByExcessSig,
+            SubmitTransaction,
+        };
         match request {
             GetStats => Ok(MempoolResponse::Stats(self.mempool.stats().await?)),
             GetState => Ok(MempoolResponse::State(self.mempool.state().await?)),
@@ -78,20 +88,28 @@ impl MempoolInboundHandlers {
                     "Transaction ({}) submitted using request.",
                     first_tx_kernel_excess_sig,
                 );
-                Ok(MempoolResponse::TxStorage(self.submit_transaction(tx, None).await?))
+                Ok(MempoolResponse::TxStorage(
+                    self.submit_transaction(tx, None, DandelionPhase::Stem).await?,
+                ))
             },
             GetFeePerGramStats { count, tip_height } => {
                 let stats = self.mempool.get_fee_per_gram_stats(count, tip_height).await?;
                 Ok(MempoolResponse::FeePerGramStats { response: stats })
             },
+            GetGraph => Ok(MempoolResponse::Graph(self.mempool.graph().await?)),
+            GetTxRejectionByExcessSig(excess_sig) => Ok(MempoolResponse::TxRejection(
+                self.mempool.get_rejection(excess_sig).await?,
+            )),
         }
     }
 
-    /// Handle inbound transactions from remote wallets and local services.
+    /// Handle inbound transactions from remote wallets and local services. `phase` is the Dandelion++ phase the
+    /// transaction was received in.
     pub async fn handle_transaction(
         &mut self,
         tx: Transaction,
         source_peer: Option<NodeId>,
+        phase: DandelionPhase,
     ) -> Result<(), MempoolServiceError> {
         debug!(
             target: LOG_TARGET,
@@ -102,15 +120,17 @@ impl MempoolInboundHandlers {
                 .map(|p| format!("remote peer: {}", p))
                 .unwrap_or_else(|| "local services".to_string())
         );
-        self.submit_transaction(tx, source_peer).await?;
+        self.submit_transaction(tx, source_peer, phase).await?;
         Ok(())
     }
 
-    /// Submits a transaction to the mempool and propagate valid transactions.
+    /// Submits a transaction to the mempool and propagate valid transactions. Valid stem-phase transactions are held in
+    /// the stem pool and relayed in the stem phase, all others are inserted into the mempool and fluffed.
     async fn submit_transaction(
         &mut self,
         tx: Transaction,
         source_peer: Option<NodeId>,
+        phase: DandelionPhase,
     ) -> Result<TxStorageResponse, MempoolServiceError> {
         trace!(target: LOG_TARGET, "submit_transaction: {}.", tx);
 
@@ -128,7 +148,19 @@ impl MempoolInboundHandlers {
             );
             return Ok(tx_storage);
         }
-        match self.mempool.insert(tx.clone()).await {
+        if phase == DandelionPhase::Stem && self.mempool.has_stem_transaction(tx.clone()).await? {
+            debug!(
+                target: LOG_TARGET,
+                "Stem pool already has transaction: {}.", kernel_excess_sig
+            );
+            return Ok(TxStorageResponse::StemPool);
+        }
+        let result = match (phase, source_peer.clone()) {
+            (DandelionPhase::Stem, source_peer) => self.mempool.insert_stem(tx.clone(), source_peer).await,
+            (DandelionPhase::Fluff, Some(peer)) => self.mempool.insert_from_peer(tx.clone(), peer).await,
+            (DandelionPhase::Fluff, None) => self.mempool.insert(tx.clone()).await,
+        };
+        match result {
             Ok(tx_storage) => {
                 if tx_storage.is_stored() {
                     metrics::inbound_transactions(source_peer.as_ref()).inc();
@@ -141,14 +173,19 @@ impl MempoolInboundHandlers {
                     target: LOG_TARGET,
                     "Transaction inserted into mempool: {}, pool: {}.", kernel_excess_sig, tx_storage
                 );
-                // propagate the tx if it was accepted to the unconfirmed pool
-                if matches!(tx_storage, TxStorageResponse::UnconfirmedPool) {
+                // propagate the tx if it was accepted to the unconfirmed pool or the stem pool
+                let phase = match tx_storage {
+                    TxStorageResponse::UnconfirmedPool => Some(DandelionPhase::Fluff),
+                    TxStorageResponse::StemPool => Some(DandelionPhase::Stem),
+                    _ => None,
+                };
+                if let Some(phase) = phase {
                     debug!(
                         target: LOG_TARGET,
-                        "Propagate transaction ({}) to network.", kernel_excess_sig,
+                        "Propagate transaction ({}) to network ({:?} phase).", kernel_excess_sig, phase,
                     );
                     self.outbound_service
-                        .propagate_tx(tx, source_peer.into_iter().collect())
+                        .propagate_tx(tx, source_peer.into_iter().collect(), phase)
                         .await?;
                 }
                 Ok(tx_storage)
@@ -157,6 +194,29 @@ impl MempoolInboundHandlers {
         }
     }
 
+    /// Moves a stem transaction into the unconfirmed pool. Returns the transaction if it was accepted and should now be
+    /// fluffed to the network.
+    pub async fn fluff_stem_transaction(
+        &mut self,
+        excess_sig: PrivateKey,
+    ) -> Result<Option<Arc<Transaction>>, MempoolServiceError> {
+        let excess_sig_hex = excess_sig.to_hex();
+        match self.mempool.fluff_stem_transaction(excess_sig).await? {
+            Some((tx, TxStorageResponse::UnconfirmedPool)) => {
+                self.update_pool_size_metrics().await;
+                Ok(Some(tx))
+            },
+            Some((_, tx_storage)) => {
+                debug!(
+                    target: LOG_TARGET,
+                    "Stem transaction ({}) was not fluffed: {}.", excess_sig_hex, tx_storage
+                );
+                Ok(None)
+            },
+            None => Ok(None),
+        }
+    }
+
     #[allow(clippy::cast_possible_wrap)]
     async fn update_pool_size_metrics(&self) {
         if let Ok(stats) = self.mempool.stats().await {
@@ -181,7 +241,12 @@ impl MempoolInboundHandlers {
                     .await?;
             },
             ValidBlockAdded(_, _) => {},
-            BlockSyncRewind(_) => {},
+            BlockSyncRewind(removed) => {
+                // Resubmit the transactions of the disconnected blocks
+                self.mempool
+                    .process_reorg(removed.iter().map(|b| b.to_arc_block()).collect(), vec![])
+                    .await?;
+            },
             BlockSyncComplete(_, _) => {
                 self.mempool.process_sync().await?;
             },
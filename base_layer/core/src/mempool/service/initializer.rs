@@ -73,10 +73,13 @@ impl MempoolServiceInitializer {
         }
     }
 
-    /// Create a stream of 'New Transaction` messages
-    fn inbound_transaction_stream(&self) -> impl Stream<Item = DomainMessage<Transaction>> {
+    /// Create a stream of transaction messages of the given type (`NewTransaction` or `StemTransaction`)
+    fn inbound_transaction_stream(
+        &self,
+        message_type: TariMessageType,
+    ) -> impl Stream<Item = DomainMessage<Transaction>> {
         self.inbound_message_subscription_factory
-            .get_subscription(TariMessageType::NewTransaction, SUBSCRIPTION_LABEL)
+            .get_subscription(message_type, SUBSCRIPTION_LABEL)
             .filter_map(extract_transaction)
     }
 }
@@ -116,7 +119,8 @@ async fn extract_transaction(msg: Arc<PeerMessage>) -> Option<DomainMessage<Tran
 impl ServiceInitializer for MempoolServiceInitializer {
     async fn initialize(&mut self, context: ServiceInitializerContext) -> Result<(), ServiceInitializationError> {
         // Create streams for receiving Mempool service requests and response messages from comms
-        let inbound_transaction_stream = self.inbound_transaction_stream();
+        let inbound_transaction_stream = self.inbound_transaction_stream(TariMessageType::NewTransaction);
+        let inbound_stem_transaction_stream = self.inbound_transaction_stream(TariMessageType::StemTransaction);
 
         // Connect MempoolOutboundServiceHandle to MempoolService
         let (request_sender, request_receiver) = reply_channel::unbounded();
@@ -134,18 +138,25 @@ impl ServiceInitializer for MempoolServiceInitializer {
         context.register_handle(local_mp_interface);
 
         context.spawn_until_shutdown(move |handles| {
-            let outbound_message_service = handles.expect_handle::<Dht>().outbound_requester();
+            let dht = handles.expect_handle::<Dht>();
             let base_node = handles.expect_handle::<LocalNodeCommsInterface>();
 
             let streams = MempoolStreams {
                 outbound_tx_stream,
                 inbound_transaction_stream,
+                inbound_stem_transaction_stream,
                 local_request_stream,
                 block_event_stream: base_node.get_block_event_stream(),
                 request_receiver,
             };
             debug!(target: LOG_TARGET, "Mempool service started");
-            MempoolService::new(outbound_message_service, inbound_handlers).start(streams)
+            MempoolService::new(
+                dht.outbound_requester(),
+                dht.dht_requester(),
+                inbound_handlers,
+                dht.dandelion_router(),
+            )
+            .start(streams)
         });
 
         Ok(())
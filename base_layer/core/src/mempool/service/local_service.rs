@@ -26,11 +26,13 @@ use tari_service_framework::{reply_channel::SenderService, Service};
 use crate::{
     mempool::{
         service::{MempoolRequest, MempoolResponse, MempoolServiceError},
+        MempoolGraph,
         StateResponse,
         StatsResponse,
         TxStorageResponse,
     },
     transactions::transaction_components::Transaction,
+    validation::ValidationRejection,
 };
 
 pub type LocalMempoolRequester = SenderService<MempoolRequest, Result<MempoolResponse, MempoolServiceError>>;
@@ -73,6 +75,30 @@ impl LocalMempoolService {
         }
     }
 
+    /// Returns the reason the transaction with the given excess signature was rejected, if it recently failed
+    /// validation
+    pub async fn get_transaction_rejection_by_excess_sig(
+        &mut self,
+        excess_sig: Signature,
+    ) -> Result<Option<ValidationRejection>, MempoolServiceError> {
+        match self
+            .request_sender
+            .call(MempoolRequest::GetTxRejectionByExcessSig(excess_sig))
+            .await??
+        {
+            MempoolResponse::TxRejection(rejection) => Ok(rejection),
+            _ => Err(MempoolServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Returns the dependency graph of the unconfirmed pool
+    pub async fn get_mempool_graph(&mut self) -> Result<MempoolGraph, MempoolServiceError> {
+        match self.request_sender.call(MempoolRequest::GetGraph).await?? {
+            MempoolResponse::Graph(graph) => Ok(graph),
+            _ => Err(MempoolServiceError::UnexpectedApiResponse),
+        }
+    }
+
     pub async fn submit_transaction(
         &mut self,
         transaction: Transaction,
@@ -121,6 +147,8 @@ mod test {
             unconfirmed_txs: 3,
             reorg_txs: 4,
             unconfirmed_weight: 1000,
+            rejected_txs: 5,
+            evicted_txs: 6,
         }
     }
 
@@ -24,6 +24,7 @@ use std::sync::Arc;
 
 use log::*;
 use tari_comms::peer_manager::NodeId;
+use tari_comms_dht::dandelion::DandelionPhase;
 use tokio::sync::mpsc::UnboundedSender;
 
 use crate::{mempool::service::MempoolServiceError, transactions::transaction_components::Transaction};
@@ -34,22 +35,24 @@ pub const LOG_TARGET: &str = "c::mp::service::outbound_interface";
 /// nodes.
 #[derive(Clone)]
 pub struct OutboundMempoolServiceInterface {
-    tx_sender: UnboundedSender<(Arc<Transaction>, Vec<NodeId>)>,
+    tx_sender: UnboundedSender<(Arc<Transaction>, Vec<NodeId>, DandelionPhase)>,
 }
 
 impl OutboundMempoolServiceInterface {
     /// Construct a new OutboundMempoolServiceInterface with the specified SenderService.
-    pub fn new(tx_sender: UnboundedSender<(Arc<Transaction>, Vec<NodeId>)>) -> Self {
+    pub fn new(tx_sender: UnboundedSender<(Arc<Transaction>, Vec<NodeId>, DandelionPhase)>) -> Self {
         Self { tx_sender }
     }
 
-    /// Transmit a transaction to remote base nodes, excluding the provided peers.
+    /// Transmit a transaction to remote base nodes, excluding the provided peers. In the stem phase the transaction is
+    /// relayed to a single peer other than the excluded (source) peer.
     pub async fn propagate_tx(
         &mut self,
         transaction: Arc<Transaction>,
         exclude_peers: Vec<NodeId>,
+        phase: DandelionPhase,
     ) -> Result<(), MempoolServiceError> {
-        self.tx_sender.send((transaction, exclude_peers)).map_err(|e| {
+        self.tx_sender.send((transaction, exclude_peers, phase)).map_err(|e| {
             error!(target: LOG_TARGET, "Could not broadcast transaction. {:?}", e);
             MempoolServiceError::BroadcastFailed
         })
@@ -35,8 +35,10 @@ pub enum MempoolRequest {
     GetStats,
     GetState,
     GetTxStateByExcessSig(Signature),
+    GetTxRejectionByExcessSig(Signature),
     SubmitTransaction(Transaction),
     GetFeePerGramStats { count: usize, tip_height: u64 },
+    GetGraph,
 }
 
 impl Display for MempoolRequest {
@@ -47,6 +49,9 @@ impl Display for MempoolRequest {
             MempoolRequest::GetTxStateByExcessSig(sig) => {
                 write!(f, "GetTxStateByExcessSig ({})", sig.get_signature().to_hex())
             },
+            MempoolRequest::GetTxRejectionByExcessSig(sig) => {
+                write!(f, "GetTxRejectionByExcessSig ({})", sig.get_signature().to_hex())
+            },
             MempoolRequest::SubmitTransaction(tx) => write!(
                 f,
                 "SubmitTransaction ({})",
@@ -55,6 +60,7 @@ impl Display for MempoolRequest {
             MempoolRequest::GetFeePerGramStats { count, tip_height } => {
                 write!(f, "GetFeePerGramStats(count: {}, tip_height: {})", *count, *tip_height)
             },
+            MempoolRequest::GetGraph => write!(f, "GetGraph"),
         }
     }
 }
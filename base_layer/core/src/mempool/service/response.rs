@@ -24,7 +24,10 @@ use std::{fmt, fmt::Formatter};
 
 use tari_common_types::waiting_requests::RequestKey;
 
-use crate::mempool::{FeePerGramStat, StateResponse, StatsResponse, TxStorageResponse};
+use crate::{
+    mempool::{FeePerGramStat, MempoolGraph, StateResponse, StatsResponse, TxStorageResponse},
+    validation::ValidationRejection,
+};
 
 /// API Response enum for Mempool responses.
 #[derive(Clone, Debug)]
@@ -32,17 +35,26 @@ pub enum MempoolResponse {
     Stats(StatsResponse),
     State(StateResponse),
     TxStorage(TxStorageResponse),
+    TxRejection(Option<ValidationRejection>),
     FeePerGramStats { response: Vec<FeePerGramStat> },
+    Graph(MempoolGraph),
 }
 
 impl fmt::Display for MempoolResponse {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        use MempoolResponse::{FeePerGramStats, State, Stats, TxStorage};
+        use MempoolResponse::{FeePerGramStats, Graph, State, Stats, TxRejection, TxStorage};
         match &self {
             Stats(_) => write!(f, "Stats"),
             State(_) => write!(f, "State"),
             TxStorage(_) => write!(f, "TxStorage"),
+            TxRejection(_) => write!(f, "TxRejection"),
             FeePerGramStats { response } => write!(f, "FeePerGramStats({} item(s))", response.len()),
+            Graph(graph) => write!(
+                f,
+                "Graph({} transaction(s), {} edge(s))",
+                graph.transactions.len(),
+                graph.edges.len()
+            ),
         }
     }
 }
@@ -20,20 +20,29 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{convert::TryFrom, sync::Arc};
+use std::{
+    collections::HashMap,
+    convert::TryFrom,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use futures::{pin_mut, stream::StreamExt, Stream};
 use log::*;
+use tari_common_types::types::PrivateKey;
 use tari_comms::peer_manager::NodeId;
 use tari_comms_dht::{
+    broadcast_strategy::BroadcastStrategy,
+    dandelion::{DandelionPhase, DandelionRoute, DandelionRouter},
     domain_message::OutboundDomainMessage,
     envelope::NodeDestination,
     outbound::{DhtOutboundError, OutboundEncryption, OutboundMessageRequester},
+    DhtRequester,
 };
 use tari_p2p::{domain_message::DomainMessage, tari_message::TariMessageType};
 use tari_service_framework::{reply_channel, reply_channel::RequestContext};
 use tari_utilities::hex::Hex;
-use tokio::{sync::mpsc, task};
+use tokio::{sync::mpsc, task, time};
 
 use crate::{
     base_node::comms_interface::{BlockEvent, BlockEventReceiver},
@@ -48,11 +57,14 @@ use crate::{
 };
 
 const LOG_TARGET: &str = "c::mempool::service::service";
+/// How often stemmed transactions are checked for expired embargoes
+const EMBARGO_CHECK_INTERVAL: Duration = Duration::from_secs(1);
 
 /// A convenience struct to hold all the Mempool service streams
 pub struct MempoolStreams<STxIn, SLocalReq> {
-    pub outbound_tx_stream: mpsc::UnboundedReceiver<(Arc<Transaction>, Vec<NodeId>)>,
+    pub outbound_tx_stream: mpsc::UnboundedReceiver<(Arc<Transaction>, Vec<NodeId>, DandelionPhase)>,
     pub inbound_transaction_stream: STxIn,
+    pub inbound_stem_transaction_stream: STxIn,
     pub local_request_stream: SLocalReq,
     pub block_event_stream: BlockEventReceiver,
     pub request_receiver: reply_channel::TryReceiver<MempoolRequest, MempoolResponse, MempoolServiceError>,
@@ -62,14 +74,27 @@ pub struct MempoolStreams<STxIn, SLocalReq> {
 /// Mempools of remote Base nodes.
 pub struct MempoolService {
     outbound_message_service: OutboundMessageRequester,
+    dht_requester: DhtRequester,
     inbound_handlers: MempoolInboundHandlers,
+    dandelion: DandelionRouter,
+    /// The embargo expiry of transactions we have stemmed, keyed by their first kernel's excess. The transactions are
+    /// held in the stem pool and are fluffed by us if they are not seen on the network before the embargo expires.
+    embargoes: HashMap<PrivateKey, Instant>,
 }
 
 impl MempoolService {
-    pub fn new(outbound_message_service: OutboundMessageRequester, inbound_handlers: MempoolInboundHandlers) -> Self {
+    pub fn new(
+        outbound_message_service: OutboundMessageRequester,
+        dht_requester: DhtRequester,
+        inbound_handlers: MempoolInboundHandlers,
+        dandelion: DandelionRouter,
+    ) -> Self {
         Self {
             outbound_message_service,
+            dht_requester,
             inbound_handlers,
+            dandelion,
+            embargoes: HashMap::new(),
         }
     }
 
@@ -84,10 +109,14 @@ impl MempoolService {
         let mut outbound_tx_stream = streams.outbound_tx_stream;
         let inbound_transaction_stream = streams.inbound_transaction_stream.fuse();
         pin_mut!(inbound_transaction_stream);
+        let inbound_stem_transaction_stream = streams.inbound_stem_transaction_stream.fuse();
+        pin_mut!(inbound_stem_transaction_stream);
         let local_request_stream = streams.local_request_stream.fuse();
         pin_mut!(local_request_stream);
         let mut block_event_stream = streams.block_event_stream;
         let mut request_receiver = streams.request_receiver;
+        let mut embargo_interval = time::interval(EMBARGO_CHECK_INTERVAL);
+        embargo_interval.set_missed_tick_behavior(time::MissedTickBehavior::Delay);
 
         loop {
             tokio::select! {
@@ -98,14 +127,24 @@ impl MempoolService {
                 },
 
                 // Outbound tx messages from the OutboundMempoolServiceInterface
-                Some((txn, excluded_peers)) = outbound_tx_stream.recv() => {
-                    let _res = self.handle_outbound_tx(txn, excluded_peers).await.map_err(|e|
+                Some((txn, excluded_peers, phase)) = outbound_tx_stream.recv() => {
+                    let _res = self.handle_outbound_tx(txn, excluded_peers, phase).await.map_err(|e|
                         error!(target: LOG_TARGET, "Error sending outbound tx message: {}", e)
                     );
                 },
 
                 // Incoming transaction messages from the Comms layer
-                Some(transaction_msg) = inbound_transaction_stream.next() => self.handle_incoming_tx(transaction_msg),
+                Some(transaction_msg) = inbound_transaction_stream.next() => {
+                    self.handle_incoming_tx(transaction_msg, DandelionPhase::Fluff);
+                },
+
+                // Incoming stem-phase transaction messages from the Comms layer
+                Some(transaction_msg) = inbound_stem_transaction_stream.next() => {
+                    self.handle_incoming_tx(transaction_msg, DandelionPhase::Stem);
+                },
+
+                // Fluff stemmed transactions that were not seen on the network in time
+                _ = embargo_interval.tick() => self.fluff_expired_embargoes().await,
 
                 // Incoming local request messages from the LocalMempoolServiceInterface and other local services
                 Some(local_request_context) = local_request_stream.next() => {
@@ -163,11 +202,19 @@ impl MempoolService {
         });
     }
 
-    fn handle_incoming_tx(&self, domain_transaction_msg: DomainMessage<Transaction>) {
+    fn handle_incoming_tx(&mut self, domain_transaction_msg: DomainMessage<Transaction>, phase: DandelionPhase) {
         let DomainMessage::<_> { source_peer, inner, .. } = domain_transaction_msg;
 
+        // A stemmed transaction seen in the fluff phase has reached the network, so its embargo can be lifted
+        if phase == DandelionPhase::Fluff {
+            if let Some(excess_sig) = inner.first_kernel_excess_sig() {
+                self.embargoes.remove(excess_sig.get_signature());
+            }
+        }
+
         debug!(
-            "New transaction received: {}, from: {}",
+            "New {:?} transaction received: {}, from: {}",
+            phase,
             inner
                 .first_kernel_excess_sig()
                 .map(|s| s.get_signature().to_hex())
@@ -183,7 +230,7 @@ impl MempoolService {
         let mut inbound_handlers = self.inbound_handlers.clone();
         task::spawn(async move {
             let result = inbound_handlers
-                .handle_transaction(inner, Some(source_peer.node_id))
+                .handle_transaction(inner, Some(source_peer.node_id), phase)
                 .await;
             if let Err(e) = result {
                 error!(
@@ -198,6 +245,115 @@ impl MempoolService {
         &mut self,
         tx: Arc<Transaction>,
         exclude_peers: Vec<NodeId>,
+        phase: DandelionPhase,
+    ) -> Result<(), MempoolServiceError> {
+        if phase == DandelionPhase::Stem {
+            return self.stem_outbound_tx(tx, exclude_peers).await;
+        }
+        self.fluff_outbound_tx(tx, exclude_peers).await
+    }
+
+    /// Relays a stem-phase transaction to the next hop chosen by the Dandelion++ router, falling back to fluffing it if
+    /// no relay is available or the relay could not be sent the transaction.
+    async fn stem_outbound_tx(
+        &mut self,
+        tx: Arc<Transaction>,
+        exclude_peers: Vec<NodeId>,
+    ) -> Result<(), MempoolServiceError> {
+        let excess_sig = tx
+            .first_kernel_excess_sig()
+            .ok_or(MempoolServiceError::TransactionNoKernels)?
+            .get_signature()
+            .clone();
+        let candidates = self
+            .dht_requester
+            .select_peers(BroadcastStrategy::Flood(vec![]))
+            .await
+            .unwrap_or_else(|e| {
+                warn!(target: LOG_TARGET, "Failed to select stem relay candidates: {}", e);
+                vec![]
+            });
+        let relay = match self.dandelion.route(exclude_peers.first(), &candidates) {
+            DandelionRoute::Stem(relay) => relay,
+            DandelionRoute::Fluff => return self.fluff_stem_tx(excess_sig, exclude_peers).await,
+        };
+
+        let result = self
+            .outbound_message_service
+            .send_direct_node_id(
+                relay.clone(),
+                OutboundDomainMessage::new(
+                    &TariMessageType::StemTransaction,
+                    proto::types::Transaction::try_from(tx).map_err(MempoolServiceError::ConversionError)?,
+                ),
+                format!("Outbound mempool stem tx: {}", excess_sig.to_hex()),
+            )
+            .await;
+
+        match result {
+            Ok(_) => {
+                debug!(
+                    target: LOG_TARGET,
+                    "Stemmed transaction {} to peer {}",
+                    excess_sig.to_hex(),
+                    relay
+                );
+                let expiry = self.dandelion.embargo_expiry();
+                self.embargoes.insert(excess_sig, expiry);
+                Ok(())
+            },
+            Err(e) => {
+                warn!(
+                    target: LOG_TARGET,
+                    "Failed to stem transaction {} to peer {}: {}. Fluffing instead.",
+                    excess_sig.to_hex(),
+                    relay,
+                    e
+                );
+                self.dandelion.record_failure(&relay);
+                self.fluff_stem_tx(excess_sig, exclude_peers).await
+            },
+        }
+    }
+
+    async fn fluff_expired_embargoes(&mut self) {
+        let now = Instant::now();
+        let expired = self
+            .embargoes
+            .iter()
+            .filter(|(_, expiry)| **expiry <= now)
+            .map(|(excess_sig, _)| excess_sig.clone())
+            .collect::<Vec<_>>();
+        for excess_sig in expired {
+            self.embargoes.remove(&excess_sig);
+            info!(
+                target: LOG_TARGET,
+                "Embargo expired for stemmed transaction {}, fluffing it",
+                excess_sig.to_hex()
+            );
+            if let Err(e) = self.fluff_stem_tx(excess_sig, vec![]).await {
+                error!(target: LOG_TARGET, "Failed to fluff embargoed transaction: {}", e);
+            }
+        }
+    }
+
+    /// Moves a stem transaction from the stem pool into the mempool and floods it to the network. Nothing is sent if
+    /// the transaction has already been fluffed or is no longer valid.
+    async fn fluff_stem_tx(
+        &mut self,
+        excess_sig: PrivateKey,
+        exclude_peers: Vec<NodeId>,
+    ) -> Result<(), MempoolServiceError> {
+        match self.inbound_handlers.fluff_stem_transaction(excess_sig).await? {
+            Some(tx) => self.fluff_outbound_tx(tx, exclude_peers).await,
+            None => Ok(()),
+        }
+    }
+
+    async fn fluff_outbound_tx(
+        &mut self,
+        tx: Arc<Transaction>,
+        exclude_peers: Vec<NodeId>,
     ) -> Result<(), MempoolServiceError> {
         let result = self
             .outbound_message_service
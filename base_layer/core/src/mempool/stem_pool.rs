@@ -0,0 +1,142 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+};
+
+use tari_common_types::types::PrivateKey;
+
+use crate::{blocks::Block, transactions::transaction_components::Transaction};
+
+/// Transactions that are being relayed in the Dandelion++ stem phase. They have been validated, but are kept apart from
+/// the unconfirmed pool so that they are not shared by mempool sync or selected into block templates before they are
+/// fluffed, either of which would reveal that this node knows about them.
+pub struct StemPool {
+    capacity: usize,
+    txs: HashMap<PrivateKey, Arc<Transaction>>,
+    insert_order: VecDeque<PrivateKey>,
+}
+
+impl StemPool {
+    /// Create a stem pool that holds at most `capacity` transactions, evicting the oldest when it is full.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            txs: HashMap::new(),
+            insert_order: VecDeque::new(),
+        }
+    }
+
+    /// Insert a stem transaction, keyed by its first kernel excess signature. Returns false if the transaction has no
+    /// kernels.
+    pub fn insert(&mut self, tx: Arc<Transaction>) -> bool {
+        let excess_sig = match tx.first_kernel_excess_sig() {
+            Some(excess_sig) => excess_sig.get_signature().clone(),
+            None => return false,
+        };
+        if self.txs.contains_key(&excess_sig) {
+            return true;
+        }
+        while self.txs.len() >= self.capacity {
+            match self.insert_order.pop_front() {
+                Some(oldest) => {
+                    self.txs.remove(&oldest);
+                },
+                None => break,
+            }
+        }
+        self.insert_order.push_back(excess_sig.clone());
+        self.txs.insert(excess_sig, tx);
+        true
+    }
+
+    /// Returns true if the stem pool contains a transaction with the same first kernel as `tx`.
+    pub fn contains(&self, tx: &Transaction) -> bool {
+        tx.first_kernel_excess_sig()
+            .map_or(false, |excess_sig| self.txs.contains_key(excess_sig.get_signature()))
+    }
+
+    /// Remove and return the stem transaction with the given first kernel excess signature.
+    pub fn remove(&mut self, excess_sig: &PrivateKey) -> Option<Arc<Transaction>> {
+        let tx = self.txs.remove(excess_sig)?;
+        self.insert_order.retain(|sig| sig != excess_sig);
+        Some(tx)
+    }
+
+    /// Discard stem transactions that share a kernel with the published block. They no longer need to be fluffed.
+    pub fn remove_published(&mut self, published_block: &Block) {
+        let is_published = |tx: &Transaction| {
+            tx.body.kernels().iter().any(|kernel| {
+                published_block
+                    .body
+                    .kernels()
+                    .iter()
+                    .any(|k| k.excess_sig == kernel.excess_sig)
+            })
+        };
+        let published = self
+            .txs
+            .iter()
+            .filter(|(_, tx)| is_published(tx))
+            .map(|(excess_sig, _)| excess_sig.clone())
+            .collect::<Vec<_>>();
+        for excess_sig in published {
+            self.remove(&excess_sig);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        transactions::{tari_amount::MicroMinotari, test_helpers::create_test_core_key_manager_with_memory_db},
+        tx,
+    };
+
+    #[tokio::test]
+    async fn it_evicts_the_oldest_transaction_when_full() {
+        let key_manager = create_test_core_key_manager_with_memory_db();
+        let mut txs = Vec::new();
+        for _ in 0..3 {
+            let tx = tx!(MicroMinotari(100_000), fee: MicroMinotari(100), inputs: 1, outputs: 1, &key_manager)
+                .expect("Failed to get tx")
+                .0;
+            txs.push(Arc::new(tx));
+        }
+
+        let mut stem_pool = StemPool::new(2);
+        for tx in &txs {
+            assert!(stem_pool.insert(tx.clone()));
+        }
+        assert!(!stem_pool.contains(&txs[0]));
+        assert!(stem_pool.contains(&txs[1]));
+        assert!(stem_pool.contains(&txs[2]));
+
+        let excess_sig = txs[1].first_kernel_excess_sig().unwrap().get_signature().clone();
+        assert!(stem_pool.remove(&excess_sig).is_some());
+        assert!(stem_pool.remove(&excess_sig).is_none());
+        assert!(stem_pool.contains(&txs[2]));
+    }
+}
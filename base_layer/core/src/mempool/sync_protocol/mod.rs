@@ -578,7 +578,7 @@ where TSubstream: AsyncRead + AsyncWrite + Unpin
             return Ok(());
         }
 
-        let stored_result = self.mempool.insert(txn).await?;
+        let stored_result = self.mempool.insert_from_peer(txn, self.peer_node_id.clone()).await?;
         if stored_result.is_stored() {
             metrics::inbound_transactions(Some(&self.peer_node_id)).inc();
             debug!(
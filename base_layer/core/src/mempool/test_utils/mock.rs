@@ -61,6 +61,8 @@ impl Default for MempoolMockState {
                 unconfirmed_txs: 0,
                 reorg_txs: 0,
                 unconfirmed_weight: 0,
+                rejected_txs: 0,
+                evicted_txs: 0,
             })),
             get_state: Arc::new(Mutex::new(StateResponse {
                 unconfirmed_pool: vec![],
@@ -124,7 +126,15 @@ impl MempoolServiceMock {
     }
 
     async fn handle_request(&self, req: MempoolRequest) -> Result<MempoolResponse, MempoolServiceError> {
-        use MempoolRequest::{GetFeePerGramStats, GetState, GetStats, GetTxStateByExcessSig, SubmitTransaction};
+        use MempoolRequest::{
+            GetFeePerGramStats,
+            GetGraph,
+            GetState,
+            GetStats,
+            GetTxRejectionByExcessSig,
+            GetTxStateByExcessSig,
+            SubmitTransaction,
+        };
 
         self.state.inc_call_count();
         match req {
@@ -136,7 +146,7 @@ impl MempoolServiceMock {
             SubmitTransaction(_) => Ok(MempoolResponse::TxStorage(
                 self.state.submit_transaction.lock().await.clone(),
             )),
-            GetFeePerGramStats { .. } => {
+            GetFeePerGramStats { .. } | GetGraph | GetTxRejectionByExcessSig(_) => {
                 unimplemented!()
             },
         }
@@ -27,7 +27,7 @@ mod unconfirmed_pool;
 // Public re-exports
 pub use error::UnconfirmedPoolError;
 use tari_crypto::hash_domain;
-pub use unconfirmed_pool::{UnconfirmedPool, UnconfirmedPoolConfig};
+pub use unconfirmed_pool::{UnconfirmedPool, UnconfirmedPoolConfig, UnconfirmedPoolInsertResult};
 
 hash_domain!(
     UnconfirmedPoolOutputTokenIdHashDomain,
@@ -28,6 +28,7 @@ use std::{
 use log::*;
 use serde::{Deserialize, Serialize};
 use tari_common_types::types::{FixedHash, HashOutput, PrivateKey, Signature};
+use tari_comms::peer_manager::NodeId;
 use tokio::time::Instant;
 
 use crate::{
@@ -38,6 +39,9 @@ use crate::{
         unconfirmed_pool::UnconfirmedPoolError,
         FeePerGramStat,
         MempoolError,
+        MempoolGraph,
+        MempoolGraphEdge,
+        MempoolGraphTransaction,
     },
     transactions::{tari_amount::MicroMinotari, transaction_components::Transaction, weight::TransactionWeight},
 };
@@ -57,6 +61,12 @@ pub struct UnconfirmedPoolConfig {
     pub weight_tx_skip_count: usize,
     /// The minimum fee accepted by this mempool
     pub min_fee: u64,
+    /// The maximum total weight (in grams) of the transactions stored in the Unconfirmed Transaction pool. The lowest
+    /// fee-per-gram transactions are evicted to make space for higher fee-per-gram transactions once this is reached.
+    pub storage_capacity_weight: u64,
+    /// The maximum number of transactions relayed by a single peer that can be stored in the Unconfirmed Transaction
+    /// pool at once
+    pub max_transactions_per_peer: usize,
 }
 
 impl Default for UnconfirmedPoolConfig {
@@ -65,10 +75,26 @@ impl Default for UnconfirmedPoolConfig {
             storage_capacity: 40_000,
             weight_tx_skip_count: 20,
             min_fee: 0,
+            // Roughly 100 full blocks
+            storage_capacity_weight: 12_800_000,
+            max_transactions_per_peer: 4_000,
         }
     }
 }
 
+/// The outcome of inserting a transaction into the UnconfirmedPool
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnconfirmedPoolInsertResult {
+    /// The transaction was inserted, evicting `num_evicted` lower priority transactions to make space for it
+    Inserted { num_evicted: usize },
+    /// All of the transaction's kernels are already in the pool
+    AlreadyStored,
+    /// The pool is full and the transaction's priority is too low to evict any stored transactions
+    RejectedPoolFull,
+    /// The peer that relayed the transaction already has the maximum number of transactions in the pool
+    RejectedPeerLimit,
+}
+
 /// The Unconfirmed Transaction Pool consists of all unconfirmed transactions that are ready to be included in a block
 /// and they are prioritised according to the priority metric.
 /// The txs_by_signature HashMap is used to find a transaction using its excess_sig, this functionality is used to match
@@ -85,6 +111,9 @@ pub struct UnconfirmedPool {
     tx_by_priority: BTreeMap<FeePriority, TransactionKey>,
     txs_by_output: HashMap<HashOutput, Vec<TransactionKey>>,
     txs_by_unique_id: HashMap<[u8; 32], Vec<TransactionKey>>,
+    peer_by_key: HashMap<TransactionKey, NodeId>,
+    num_txs_by_peer: HashMap<NodeId, usize>,
+    total_weight: u64,
 }
 
 // helper class to reduce type complexity
@@ -106,34 +135,58 @@ impl UnconfirmedPool {
             tx_by_priority: BTreeMap::new(),
             txs_by_output: HashMap::new(),
             txs_by_unique_id: HashMap::new(),
+            peer_by_key: HashMap::new(),
+            num_txs_by_peer: HashMap::new(),
+            total_weight: 0,
         }
     }
 
     /// Insert a new transaction into the UnconfirmedPool. Low priority transactions will be removed to make space for
-    /// higher priority transactions. The lowest priority transactions will be removed when the maximum capacity is
-    /// reached and the new transaction has a higher priority than the currently stored lowest priority transaction.
+    /// higher priority transactions. The lowest priority transactions will be removed when the maximum capacity (by
+    /// count or by weight) is reached and the new transaction has a higher priority than the evicted transactions.
+    /// Transactions relayed by `source_peer` are rejected once that peer has reached its limit.
     pub fn insert(
         &mut self,
         tx: Arc<Transaction>,
         dependent_outputs: Option<Vec<HashOutput>>,
         transaction_weighting: &TransactionWeight,
-    ) -> std::io::Result<()> {
+        source_peer: Option<&NodeId>,
+    ) -> std::io::Result<UnconfirmedPoolInsertResult> {
         if tx
             .body
             .kernels()
             .iter()
             .all(|k| self.txs_by_signature.contains_key(k.excess_sig.get_signature()))
         {
-            return Ok(());
+            return Ok(UnconfirmedPoolInsertResult::AlreadyStored);
+        }
+
+        if let Some(peer) = source_peer {
+            if self.num_txs_by_peer.get(peer).copied().unwrap_or(0) >= self.config.max_transactions_per_peer {
+                debug!(
+                    target: LOG_TARGET,
+                    "Peer {} has reached its limit of {} transaction(s) in the unconfirmed pool",
+                    peer,
+                    self.config.max_transactions_per_peer
+                );
+                return Ok(UnconfirmedPoolInsertResult::RejectedPeerLimit);
+            }
         }
 
         let new_key = self.get_next_key();
         let prioritized_tx = PrioritizedTransaction::new(new_key, transaction_weighting, tx, dependent_outputs)?;
-        if self.tx_by_key.len() >= self.config.storage_capacity {
-            if prioritized_tx.priority < *self.lowest_priority() {
-                return Ok(());
-            }
-            self.remove_lowest_priority_tx();
+        let evictions = match self.find_evictions(&prioritized_tx) {
+            Some(evictions) => evictions,
+            None => {
+                debug!(
+                    target: LOG_TARGET,
+                    "Unconfirmed pool is full, rejecting transaction {}", prioritized_tx
+                );
+                return Ok(UnconfirmedPoolInsertResult::RejectedPoolFull);
+            },
+        };
+        for tx_key in &evictions {
+            self.remove_transaction(*tx_key);
         }
 
         self.tx_by_priority.insert(prioritized_tx.priority.clone(), new_key);
@@ -149,9 +202,40 @@ impl UnconfirmedPool {
             target: LOG_TARGET,
             "Inserted transaction {} into unconfirmed pool:", prioritized_tx
         );
+        self.total_weight += prioritized_tx.weight;
+        if let Some(peer) = source_peer {
+            *self.num_txs_by_peer.entry(peer.clone()).or_default() += 1;
+            self.peer_by_key.insert(new_key, peer.clone());
+        }
         self.tx_by_key.insert(new_key, prioritized_tx);
 
-        Ok(())
+        Ok(UnconfirmedPoolInsertResult::Inserted {
+            num_evicted: evictions.len(),
+        })
+    }
+
+    /// Returns the lowest priority transactions that have to be evicted for the given transaction to fit into the
+    /// pool, or None if it cannot fit without evicting a transaction with a priority at least as high as its own.
+    fn find_evictions(&self, prioritized_tx: &PrioritizedTransaction) -> Option<Vec<TransactionKey>> {
+        if prioritized_tx.weight > self.config.storage_capacity_weight {
+            return None;
+        }
+        let mut evictions = Vec::new();
+        let mut len = self.tx_by_key.len();
+        let mut weight = self.total_weight;
+        let mut lowest_first = self.tx_by_priority.iter();
+        while len >= self.config.storage_capacity ||
+            weight + prioritized_tx.weight > self.config.storage_capacity_weight
+        {
+            let (priority, tx_key) = lowest_first.next()?;
+            if *priority >= prioritized_tx.priority {
+                return None;
+            }
+            len -= 1;
+            weight -= self.tx_by_key.get(tx_key).map(|ptx| ptx.weight).unwrap_or(0);
+            evictions.push(*tx_key);
+        }
+        Some(evictions)
     }
 
     /// This will search the unconfirmed pool for the set of outputs and return true if all of them are found
@@ -167,7 +251,7 @@ impl UnconfirmedPool {
         transaction_weighting: &TransactionWeight,
     ) -> std::io::Result<()> {
         for tx in txs {
-            self.insert(tx, None, transaction_weighting)?;
+            self.insert(tx, None, transaction_weighting, None)?;
         }
         Ok(())
     }
@@ -430,6 +514,11 @@ impl UnconfirmedPool {
         Ok((found, remaining))
     }
 
+    /// Returns an iterator over the kernel excess signature scalars of all transactions in the pool
+    pub fn excess_sigs(&self) -> impl Iterator<Item = &PrivateKey> {
+        self.txs_by_signature.keys()
+    }
+
     fn get_all_dependent_transactions(
         &self,
         transaction: &PrioritizedTransaction,
@@ -522,24 +611,14 @@ impl UnconfirmedPool {
         false
     }
 
-    fn lowest_priority(&self) -> &FeePriority {
-        self.tx_by_priority
-            .keys()
-            .next()
-            .expect("lowest_priority called on empty mempool")
-    }
-
-    fn remove_lowest_priority_tx(&mut self) {
-        if let Some(tx_key) = self.tx_by_priority.values().next().copied() {
-            self.remove_transaction(tx_key);
-        }
-    }
-
     /// Remove all current mempool transactions from the UnconfirmedPoolStorage, returning that which have been removed
     pub fn drain_all_mempool_transactions(&mut self) -> Vec<Arc<Transaction>> {
         self.txs_by_signature.clear();
         self.tx_by_priority.clear();
         self.txs_by_output.clear();
+        self.peer_by_key.clear();
+        self.num_txs_by_peer.clear();
+        self.total_weight = 0;
         self.tx_by_key.drain().map(|(_, val)| val.transaction).collect()
     }
 
@@ -652,6 +731,15 @@ impl UnconfirmedPool {
         let prioritized_transaction = self.tx_by_key.remove(&tx_key)?;
 
         self.tx_by_priority.remove(&prioritized_transaction.priority);
+        self.total_weight = self.total_weight.saturating_sub(prioritized_transaction.weight);
+        if let Some(peer) = self.peer_by_key.remove(&tx_key) {
+            if let Some(num_txs) = self.num_txs_by_peer.get_mut(&peer) {
+                *num_txs = num_txs.saturating_sub(1);
+                if *num_txs == 0 {
+                    self.num_txs_by_peer.remove(&peer);
+                }
+            }
+        }
 
         for kernel in prioritized_transaction.transaction.body.kernels() {
             let sig = kernel.excess_sig.get_signature();
@@ -689,6 +777,11 @@ impl UnconfirmedPool {
         self.txs_by_signature.len()
     }
 
+    /// Returns the total weight of all transactions stored in the pool, as calculated when they were inserted.
+    pub fn total_weight(&self) -> u64 {
+        self.total_weight
+    }
+
     /// Returns all transaction stored in the UnconfirmedPool.
     pub fn snapshot(&self) -> Vec<Arc<Transaction>> {
         self.tx_by_key.values().map(|ptx| ptx.transaction.clone()).collect()
@@ -755,6 +848,49 @@ impl UnconfirmedPool {
         Ok(stats)
     }
 
+    /// Returns the dependency graph of the pool. Transactions are ordered from highest to lowest priority, and there is
+    /// an edge for every output a transaction spends that is created by another transaction in the pool.
+    pub fn graph(&self) -> Result<MempoolGraph, UnconfirmedPoolError> {
+        let keys = self.tx_by_priority.values().rev().collect::<Vec<_>>();
+        let index_by_key = keys
+            .iter()
+            .enumerate()
+            .map(|(index, key)| (**key, index))
+            .collect::<HashMap<_, _>>();
+
+        let mut graph = MempoolGraph::default();
+        for (child, key) in keys.iter().enumerate() {
+            let ptx = self.tx_by_key.get(key).ok_or(UnconfirmedPoolError::StorageOutofSync)?;
+            let mut num_missing_parent_outputs = 0;
+            for output_hash in &ptx.dependent_output_hashes {
+                match self.txs_by_output.get(output_hash) {
+                    Some(parent_keys) => {
+                        for parent_key in parent_keys {
+                            let parent = *index_by_key
+                                .get(parent_key)
+                                .ok_or(UnconfirmedPoolError::StorageOutofSync)?;
+                            graph.edges.push(MempoolGraphEdge {
+                                parent,
+                                child,
+                                output_hash: *output_hash,
+                            });
+                        }
+                    },
+                    None => num_missing_parent_outputs += 1,
+                }
+            }
+            graph.transactions.push(MempoolGraphTransaction {
+                excess_sig: ptx.transaction.first_kernel_excess_sig().cloned().unwrap_or_default(),
+                weight: ptx.weight,
+                total_fee: ptx.transaction.body.get_total_fee(),
+                fee_per_gram: ptx.fee_per_byte,
+                num_missing_parent_outputs,
+            });
+        }
+
+        Ok(graph)
+    }
+
     /// Returns false if there are any inconsistencies in the internal mempool state, otherwise true
     #[cfg(test)]
     fn check_data_consistency(&self) -> bool {
@@ -770,7 +906,12 @@ impl UnconfirmedPool {
                 .all(|tx_keys| tx_keys.iter().all(|tx_key| self.tx_by_key.contains_key(tx_key))) &&
             self.txs_by_unique_id
                 .values()
-                .all(|tx_keys| tx_keys.iter().all(|tx_key| self.tx_by_key.contains_key(tx_key)))
+                .all(|tx_keys| tx_keys.iter().all(|tx_key| self.tx_by_key.contains_key(tx_key))) &&
+            self.peer_by_key
+                .keys()
+                .all(|tx_key| self.tx_by_key.contains_key(tx_key)) &&
+            self.num_txs_by_peer.values().sum::<usize>() == self.peer_by_key.len() &&
+            self.tx_by_key.values().map(|ptx| ptx.weight).sum::<u64>() == self.total_weight
     }
 
     fn get_next_key(&mut self) -> usize {
@@ -786,6 +927,8 @@ impl UnconfirmedPool {
         shrink_hashmap(&mut self.txs_by_signature);
         shrink_hashmap(&mut self.txs_by_output);
         shrink_hashmap(&mut self.txs_by_unique_id);
+        shrink_hashmap(&mut self.peer_by_key);
+        shrink_hashmap(&mut self.num_txs_by_peer);
 
         if old > new {
             debug!(
@@ -882,6 +1025,7 @@ mod test {
             storage_capacity: 4,
             weight_tx_skip_count: 3,
             min_fee: 0,
+            ..Default::default()
         });
 
         let tx_weight = TransactionWeight::latest();
@@ -980,6 +1124,7 @@ mod test {
             storage_capacity: 4,
             weight_tx_skip_count: 3,
             min_fee: 0,
+            ..Default::default()
         });
 
         let tx_weight = TransactionWeight::latest();
@@ -1040,6 +1185,7 @@ mod test {
             storage_capacity: 10,
             weight_tx_skip_count: 3,
             min_fee: 0,
+            ..Default::default()
         });
         unconfirmed_pool
             .insert_many(
@@ -1116,6 +1262,7 @@ mod test {
             storage_capacity: 10,
             weight_tx_skip_count: 3,
             min_fee: 0,
+            ..Default::default()
         });
         unconfirmed_pool
             .insert_many(
@@ -1171,6 +1318,7 @@ mod test {
             storage_capacity: 10,
             weight_tx_skip_count: 3,
             min_fee: 0,
+            ..Default::default()
         });
         let txns = vec![
             Arc::new(tx1.clone()),
@@ -1238,6 +1386,149 @@ mod test {
         }
     }
 
+    #[tokio::test]
+    async fn test_evicts_by_weight() {
+        let key_manager = create_test_core_key_manager_with_memory_db();
+        let tx1 = Arc::new(
+            tx!(MicroMinotari(5_000), fee: MicroMinotari(5), inputs: 2, outputs: 1, &key_manager)
+                .expect("Failed to get tx")
+                .0,
+        );
+        let tx2 = Arc::new(
+            tx!(MicroMinotari(5_000), fee: MicroMinotari(50), inputs: 2, outputs: 1, &key_manager)
+                .expect("Failed to get tx")
+                .0,
+        );
+        let tx3 = Arc::new(
+            tx!(MicroMinotari(5_000), fee: MicroMinotari(1), inputs: 2, outputs: 1, &key_manager)
+                .expect("Failed to get tx")
+                .0,
+        );
+        let tx_weight = TransactionWeight::latest();
+        let weight = tx1.calculate_weight(&tx_weight).unwrap();
+
+        // Space for a single transaction
+        let mut unconfirmed_pool = UnconfirmedPool::new(UnconfirmedPoolConfig {
+            storage_capacity_weight: weight,
+            ..Default::default()
+        });
+        assert_eq!(
+            unconfirmed_pool.insert(tx1.clone(), None, &tx_weight, None).unwrap(),
+            UnconfirmedPoolInsertResult::Inserted { num_evicted: 0 }
+        );
+        // A higher fee-per-gram transaction evicts the stored transaction
+        assert_eq!(
+            unconfirmed_pool.insert(tx2.clone(), None, &tx_weight, None).unwrap(),
+            UnconfirmedPoolInsertResult::Inserted { num_evicted: 1 }
+        );
+        // A lower fee-per-gram transaction is rejected
+        assert_eq!(
+            unconfirmed_pool.insert(tx3.clone(), None, &tx_weight, None).unwrap(),
+            UnconfirmedPoolInsertResult::RejectedPoolFull
+        );
+        assert!(!unconfirmed_pool.has_tx_with_excess_sig(&tx1.body.kernels()[0].excess_sig));
+        assert!(unconfirmed_pool.has_tx_with_excess_sig(&tx2.body.kernels()[0].excess_sig));
+        assert!(!unconfirmed_pool.has_tx_with_excess_sig(&tx3.body.kernels()[0].excess_sig));
+        assert_eq!(unconfirmed_pool.total_weight(), weight);
+        assert!(unconfirmed_pool.check_data_consistency());
+    }
+
+    #[tokio::test]
+    async fn test_limits_transactions_per_peer() {
+        let key_manager = create_test_core_key_manager_with_memory_db();
+        let mut txs = Vec::new();
+        for _ in 0..3 {
+            txs.push(Arc::new(
+                tx!(MicroMinotari(5_000), fee: MicroMinotari(5), inputs: 1, outputs: 1, &key_manager)
+                    .expect("Failed to get tx")
+                    .0,
+            ));
+        }
+        let tx_weight = TransactionWeight::latest();
+        let peer = NodeId::default();
+
+        let mut unconfirmed_pool = UnconfirmedPool::new(UnconfirmedPoolConfig {
+            max_transactions_per_peer: 1,
+            ..Default::default()
+        });
+        assert_eq!(
+            unconfirmed_pool
+                .insert(txs[0].clone(), None, &tx_weight, Some(&peer))
+                .unwrap(),
+            UnconfirmedPoolInsertResult::Inserted { num_evicted: 0 }
+        );
+        assert_eq!(
+            unconfirmed_pool
+                .insert(txs[1].clone(), None, &tx_weight, Some(&peer))
+                .unwrap(),
+            UnconfirmedPoolInsertResult::RejectedPeerLimit
+        );
+        // Transactions without a source peer are not limited
+        assert_eq!(
+            unconfirmed_pool.insert(txs[2].clone(), None, &tx_weight, None).unwrap(),
+            UnconfirmedPoolInsertResult::Inserted { num_evicted: 0 }
+        );
+        assert!(unconfirmed_pool.check_data_consistency());
+
+        // Removing the peer's transaction frees up its allowance
+        let _drained = unconfirmed_pool.drain_all_mempool_transactions();
+        assert_eq!(
+            unconfirmed_pool
+                .insert(txs[1].clone(), None, &tx_weight, Some(&peer))
+                .unwrap(),
+            UnconfirmedPoolInsertResult::Inserted { num_evicted: 0 }
+        );
+        assert!(unconfirmed_pool.check_data_consistency());
+    }
+
+    #[tokio::test]
+    async fn test_graph() {
+        let key_manager = create_test_core_key_manager_with_memory_db();
+        let (tx1, _, _) = tx!(MicroMinotari(150_000), fee: MicroMinotari(5), inputs:2, outputs:1, &key_manager)
+            .expect("Failed to get tx");
+        let (tx2, _, _) = tx!(MicroMinotari(250_000), fee: MicroMinotari(20), inputs:2, outputs:1, &key_manager)
+            .expect("Failed to get tx");
+        let (tx3, _, _) = tx!(MicroMinotari(350_000), fee: MicroMinotari(10), inputs:2, outputs:1, &key_manager)
+            .expect("Failed to get tx");
+        let tx_weight = TransactionWeight::latest();
+        let mut unconfirmed_pool = UnconfirmedPool::new(UnconfirmedPoolConfig::default());
+
+        let tx1 = Arc::new(tx1);
+        let parent_output = tx1.body.outputs()[0].hash();
+        unconfirmed_pool.insert(tx1.clone(), None, &tx_weight, None).unwrap();
+        // tx2 spends the output of tx1, tx3 spends an output that is no longer in the pool
+        unconfirmed_pool
+            .insert(Arc::new(tx2), Some(vec![parent_output]), &tx_weight, None)
+            .unwrap();
+        unconfirmed_pool
+            .insert(Arc::new(tx3), Some(vec![FixedHash::zero()]), &tx_weight, None)
+            .unwrap();
+
+        let graph = unconfirmed_pool.graph().unwrap();
+        assert_eq!(graph.transactions.len(), 3);
+        assert!(graph
+            .transactions
+            .windows(2)
+            .all(|w| w[0].fee_per_gram >= w[1].fee_per_gram));
+        let parent = graph
+            .transactions
+            .iter()
+            .position(|t| Some(&t.excess_sig) == tx1.first_kernel_excess_sig())
+            .unwrap();
+        assert_eq!(graph.edges.len(), 1);
+        assert_eq!(graph.edges[0].parent, parent);
+        assert_eq!(graph.edges[0].output_hash, parent_output);
+        assert_eq!(graph.transactions[graph.edges[0].child].num_missing_parent_outputs, 0);
+        assert_eq!(
+            graph
+                .transactions
+                .iter()
+                .map(|t| t.num_missing_parent_outputs)
+                .sum::<usize>(),
+            1
+        );
+    }
+
     mod get_fee_per_gram_stats {
 
         use super::*;
@@ -0,0 +1,73 @@
+//  Copyright 2023, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Merge mining support for auxiliary chains.
+//!
+//! Tari can be merge mined with any chain that can carry the Tari merge mining hash in its coinbase and whose proof of
+//! work Tari can verify. Each such chain is supported by implementing [AuxChainAdapter], which tells the node and the
+//! merge mining proxy where the merge mining commitment is placed and how the achieved difficulty is extracted from the
+//! auxiliary proof of work data embedded in a Tari header.
+
+mod monero_adapter;
+pub use monero_adapter::MoneroAdapter;
+
+use crate::{
+    blocks::BlockHeader,
+    proof_of_work::{monero_rx::MergeMineError, randomx_factory::RandomXFactory, Difficulty},
+};
+
+/// The size in bytes of the Tari merge mining hash committed to in an auxiliary chain coinbase
+pub const MERGE_MINING_HASH_SIZE: usize = 32;
+
+/// An adapter for a chain that Tari can be merge mined with
+pub trait AuxChainAdapter {
+    /// The block (or block template) type of the auxiliary chain
+    type Block;
+
+    /// A short identifier for the auxiliary chain, e.g. `"xmr"`
+    fn chain_id(&self) -> &'static str;
+
+    /// Deserialize a hex encoded block or block template as returned by the auxiliary chain daemon
+    fn deserialize_block(&self, hex: &str) -> Result<Self::Block, MergeMineError>;
+
+    /// Serialize a block into the hex encoding expected by the auxiliary chain daemon
+    fn serialize_block(&self, block: &Self::Block) -> Result<String, MergeMineError>;
+
+    /// Place the commitment to the Tari merge mining hash in the coinbase of `block`
+    fn append_merge_mining_tag(&self, block: &mut Self::Block, merge_mining_hash: &[u8]) -> Result<(), MergeMineError>;
+
+    /// Extract the Tari merge mining hash from the coinbase of `block`, if present
+    fn extract_merge_mining_hash(
+        &self,
+        block: &Self::Block,
+    ) -> Result<Option<[u8; MERGE_MINING_HASH_SIZE]>, MergeMineError>;
+
+    /// The blob the miner hashes for `block`
+    fn hashing_blob(&self, block: &Self::Block) -> Result<String, MergeMineError>;
+
+    /// Verify the auxiliary proof of work data in `header` and return the difficulty it achieves
+    fn achieved_difficulty(
+        &self,
+        header: &BlockHeader,
+        randomx_factory: &RandomXFactory,
+    ) -> Result<Difficulty, MergeMineError>;
+}
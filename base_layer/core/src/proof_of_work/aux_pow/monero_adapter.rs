@@ -0,0 +1,106 @@
+//  Copyright 2023, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use super::{AuxChainAdapter, MERGE_MINING_HASH_SIZE};
+use crate::{
+    blocks::BlockHeader,
+    proof_of_work::{
+        monero_rx::{
+            append_merge_mining_tag,
+            create_blockhashing_blob_from_block,
+            deserialize_monero_block_from_hex,
+            extract_tari_hash,
+            randomx_difficulty,
+            serialize_monero_block_to_hex,
+            MergeMineError,
+        },
+        randomx_factory::RandomXFactory,
+        Difficulty,
+    },
+};
+
+/// Merge mining with Monero. The merge mining hash is placed in a merge mining tag in the extra field of the coinbase
+/// transaction.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MoneroAdapter;
+
+impl AuxChainAdapter for MoneroAdapter {
+    type Block = monero::Block;
+
+    fn chain_id(&self) -> &'static str {
+        "xmr"
+    }
+
+    fn deserialize_block(&self, hex: &str) -> Result<Self::Block, MergeMineError> {
+        deserialize_monero_block_from_hex(hex)
+    }
+
+    fn serialize_block(&self, block: &Self::Block) -> Result<String, MergeMineError> {
+        serialize_monero_block_to_hex(block)
+    }
+
+    fn append_merge_mining_tag(&self, block: &mut Self::Block, merge_mining_hash: &[u8]) -> Result<(), MergeMineError> {
+        append_merge_mining_tag(block, merge_mining_hash)
+    }
+
+    fn extract_merge_mining_hash(
+        &self,
+        block: &Self::Block,
+    ) -> Result<Option<[u8; MERGE_MINING_HASH_SIZE]>, MergeMineError> {
+        Ok(extract_tari_hash(block)?.map(|hash| hash.to_fixed_bytes()))
+    }
+
+    fn hashing_blob(&self, block: &Self::Block) -> Result<String, MergeMineError> {
+        create_blockhashing_blob_from_block(block)
+    }
+
+    fn achieved_difficulty(
+        &self,
+        header: &BlockHeader,
+        randomx_factory: &RandomXFactory,
+    ) -> Result<Difficulty, MergeMineError> {
+        randomx_difficulty(header, randomx_factory)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const BLOCK_TEMPLATE: &str = "0c0c8cd6a0fa057fe21d764e7abf004e975396a2160773b93712bf6118c3b4959ddd8ee0f76aad0000000002e1ea2701ffa5ea2701d5a299e2abb002028eb3066ced1b2cc82ea046f3716a48e9ae37144057d5fb48a97f941225a1957b2b0106225b7ec0a6544d8da39abe68d8bd82619b4a7c5bdae89c3783b256a8fa47820208f63aa86d2e857f070000";
+
+    #[test]
+    fn it_places_and_extracts_the_merge_mining_hash() {
+        let adapter = MoneroAdapter;
+        let mut block = adapter.deserialize_block(BLOCK_TEMPLATE).unwrap();
+        assert_eq!(adapter.extract_merge_mining_hash(&block).unwrap(), None);
+
+        let hash = [7u8; MERGE_MINING_HASH_SIZE];
+        adapter.append_merge_mining_tag(&mut block, &hash).unwrap();
+        assert_eq!(adapter.extract_merge_mining_hash(&block).unwrap(), Some(hash));
+
+        let hex = adapter.serialize_block(&block).unwrap();
+        let block = adapter.deserialize_block(&hex).unwrap();
+        assert_eq!(adapter.extract_merge_mining_hash(&block).unwrap(), Some(hash));
+        assert!(adapter.append_merge_mining_tag(&mut block.clone(), &[0u8; 31]).is_err());
+    }
+}
@@ -62,4 +62,9 @@ pub enum DifficultyError {
     MaxBlockTimeOverflow,
     #[error("Divide by zero")]
     DivideByZero,
+    #[error("Mismatched difficulty samples: {timestamps} timestamps, {target_difficulties} target difficulties")]
+    SampleLengthMismatch {
+        timestamps: usize,
+        target_difficulties: usize,
+    },
 }
@@ -75,67 +75,11 @@ impl LinearWeightedMovingAverage {
     }
 
     fn calculate(&self) -> Option<Difficulty> {
-        // This function uses u128 internally for most of the math as its possible to have an overflow with large
-        // difficulties and large block windows
-        if self.target_difficulties.len() <= 1 {
-            return None;
-        }
-
-        // Use the array length rather than block_window to include early cases where the no. of pts < block_window
-        let n = (self.target_difficulties.len() - 1) as u128;
-
-        let mut weighted_times: u128 = 0;
-        let difficulty_sum = self
-            .target_difficulties
-            .iter()
-            .skip(1)
-            .fold(0u128, |difficulty, (_, d)| difficulty + u128::from(d.as_u64()));
-
-        let ave_difficulty = difficulty_sum / n;
-
-        let (mut previous_timestamp, _) = self.target_difficulties[0];
-        let mut this_timestamp;
-        // Loop through N most recent blocks.
-        for (i, (timestamp, _)) in self.target_difficulties.iter().skip(1).enumerate() {
-            // We cannot have if solve_time < 1 then solve_time = 1, this will greatly increase the next timestamp
-            // difficulty which will lower the difficulty
-            if *timestamp > previous_timestamp {
-                this_timestamp = *timestamp;
-            } else {
-                this_timestamp = previous_timestamp.increase(1);
-            }
-            let solve_time = min((this_timestamp - previous_timestamp).as_u64(), self.max_block_time);
-            previous_timestamp = this_timestamp;
-
-            // Give linearly higher weight to more recent solve times.
-            // Note: This will not overflow for practical values of block_window and solve time.
-            weighted_times += u128::from(solve_time * (i + 1) as u64);
-        }
-        // k is the sum of weights (1+2+..+n) * target_time
-        let k = n * (n + 1) * self.target_time / 2;
-        let target = u64::try_from(ave_difficulty * k / weighted_times).unwrap_or(u64::MAX);
-        trace!(
-            target: LOG_TARGET,
-            "DiffCalc; t={}; bw={}; n={}; ts[0]={}; ts[n]={}; weighted_ts={}; k={}; diff[0]={}; diff[n]={}; \
-             ave_difficulty={}; target={}",
+        lwma(
+            self.target_difficulties.iter().copied(),
             self.target_time,
-            self.block_window,
-            n,
-            self.target_difficulties[0].0,
-            self.target_difficulties[n as usize].0,
-            weighted_times,
-            k,
-            self.target_difficulties[0].1,
-            self.target_difficulties[n as usize].1,
-            ave_difficulty,
-            target
-        );
-        trace!(target: LOG_TARGET, "New target difficulty: {}", target);
-        if target < Difficulty::min().as_u64() {
-            None
-        } else {
-            Some(Difficulty::from_u64(target).expect("Difficulty is valid"))
-        }
+            self.max_block_time,
+        )
     }
 
     /// Indicates if the `LinearWeightedMovingAverage` is full
@@ -172,6 +116,96 @@ impl LinearWeightedMovingAverage {
     }
 }
 
+/// Calculates the LWMA target difficulty for the next block from the `timestamps` and `target_difficulties` of the
+/// preceding blocks, ordered from oldest to newest. All of the given samples are used, so callers should limit them to
+/// the last `block_window + 1` blocks. Returns `None` if fewer than two samples are given or if the calculated target
+/// is below the minimum difficulty.
+pub fn lwma_target_difficulty(
+    timestamps: &[EpochTime],
+    target_difficulties: &[Difficulty],
+    target_time: u64,
+) -> Result<Option<Difficulty>, DifficultyError> {
+    if timestamps.len() != target_difficulties.len() {
+        return Err(DifficultyError::SampleLengthMismatch {
+            timestamps: timestamps.len(),
+            target_difficulties: target_difficulties.len(),
+        });
+    }
+    if target_time == 0 {
+        return Err(DifficultyError::DivideByZero);
+    }
+    let max_block_time = LinearWeightedMovingAverage::max_block_time(target_time)?;
+    Ok(lwma(
+        timestamps.iter().copied().zip(target_difficulties.iter().copied()),
+        u128::from(target_time),
+        max_block_time,
+    ))
+}
+
+fn lwma<I>(samples: I, target_time: u128, max_block_time: u64) -> Option<Difficulty>
+where I: ExactSizeIterator<Item = (EpochTime, Difficulty)> {
+    // This function uses u128 internally for most of the math as its possible to have an overflow with large
+    // difficulties and large block windows
+    let mut samples = samples;
+    if samples.len() <= 1 {
+        return None;
+    }
+
+    // Use the array length rather than block_window to include early cases where the no. of pts < block_window
+    let n = (samples.len() - 1) as u128;
+
+    let (first_timestamp, first_difficulty) = samples.next()?;
+    let mut previous_timestamp = first_timestamp;
+    let mut last_sample = (first_timestamp, first_difficulty);
+    let mut weighted_times: u128 = 0;
+    let mut difficulty_sum: u128 = 0;
+    let mut this_timestamp;
+    // Loop through N most recent blocks.
+    for (i, (timestamp, difficulty)) in samples.enumerate() {
+        difficulty_sum += u128::from(difficulty.as_u64());
+        // We cannot have if solve_time < 1 then solve_time = 1, this will greatly increase the next timestamp
+        // difficulty which will lower the difficulty
+        if timestamp > previous_timestamp {
+            this_timestamp = timestamp;
+        } else {
+            this_timestamp = previous_timestamp.increase(1);
+        }
+        let solve_time = min((this_timestamp - previous_timestamp).as_u64(), max_block_time);
+        previous_timestamp = this_timestamp;
+        last_sample = (timestamp, difficulty);
+
+        // Give linearly higher weight to more recent solve times.
+        // Note: This will not overflow for practical values of block_window and solve time.
+        weighted_times += u128::from(solve_time * (i + 1) as u64);
+    }
+
+    let ave_difficulty = difficulty_sum / n;
+    // k is the sum of weights (1+2+..+n) * target_time
+    let k = n * (n + 1) * target_time / 2;
+    let target = u64::try_from(ave_difficulty * k / weighted_times).unwrap_or(u64::MAX);
+    trace!(
+        target: LOG_TARGET,
+        "DiffCalc; t={}; n={}; ts[0]={}; ts[n]={}; weighted_ts={}; k={}; diff[0]={}; diff[n]={}; ave_difficulty={}; \
+         target={}",
+        target_time,
+        n,
+        first_timestamp,
+        last_sample.0,
+        weighted_times,
+        k,
+        first_difficulty,
+        last_sample.1,
+        ave_difficulty,
+        target
+    );
+    trace!(target: LOG_TARGET, "New target difficulty: {}", target);
+    if target < Difficulty::min().as_u64() {
+        None
+    } else {
+        Some(Difficulty::from_u64(target).expect("Difficulty is valid"))
+    }
+}
+
 impl DifficultyAdjustment for LinearWeightedMovingAverage {
     fn add(&mut self, timestamp: EpochTime, target_difficulty: Difficulty) -> Result<(), DifficultyAdjustmentError> {
         self.add_back(timestamp, target_difficulty);
@@ -185,7 +219,14 @@ impl DifficultyAdjustment for LinearWeightedMovingAverage {
 
 #[cfg(test)]
 mod test {
-    use crate::proof_of_work::{lwma_diff::LinearWeightedMovingAverage, Difficulty, DifficultyAdjustment};
+    use tari_utilities::epoch_time::EpochTime;
+
+    use crate::proof_of_work::{
+        lwma_diff::{lwma_target_difficulty, LinearWeightedMovingAverage},
+        Difficulty,
+        DifficultyAdjustment,
+        DifficultyError,
+    };
 
     #[test]
     fn lwma_zero_len() {
@@ -290,6 +331,37 @@ mod test {
         assert_eq!(dif.get_difficulty().unwrap(), Difficulty::from_u64(173).unwrap());
     }
 
+    #[test]
+    fn lwma_target_difficulty_matches_moving_average() {
+        let timestamps = [60u64, 120, 180, 240, 300, 350, 380, 445, 515, 615, 975]
+            .iter()
+            .map(|t| EpochTime::from(*t))
+            .collect::<Vec<_>>();
+        let difficulties = [100u64, 100, 100, 100, 100, 105, 128, 123, 116, 94, 39]
+            .iter()
+            .map(|d| Difficulty::from_u64(*d).unwrap())
+            .collect::<Vec<_>>();
+
+        let mut dif = LinearWeightedMovingAverage::new(5, 60).unwrap();
+        for (t, d) in timestamps.iter().zip(&difficulties) {
+            let _ = dif.add(*t, *d);
+        }
+        let window = timestamps.len() - 6;
+        let target = lwma_target_difficulty(&timestamps[window..], &difficulties[window..], 60).unwrap();
+        assert_eq!(target, dif.get_difficulty());
+        assert_eq!(target.unwrap(), Difficulty::from_u64(35).unwrap());
+
+        assert_eq!(
+            lwma_target_difficulty(&timestamps[..1], &difficulties[..1], 60).unwrap(),
+            None
+        );
+        assert!(matches!(
+            lwma_target_difficulty(&timestamps, &difficulties[1..], 60),
+            Err(DifficultyError::SampleLengthMismatch { .. })
+        ));
+        assert!(lwma_target_difficulty(&timestamps, &difficulties, 0).is_err());
+    }
+
     #[test]
     fn ensure_calculate_does_not_overflow_with_large_block_window() {
         let mut dif = LinearWeightedMovingAverage::new(6000, 60).unwrap();
@@ -38,6 +38,10 @@ pub mod monero_rx;
 #[cfg(feature = "base_node")]
 pub use monero_rx::randomx_difficulty;
 
+/// Crates for auxiliary chain merge mining
+#[cfg(feature = "base_node")]
+pub mod aux_pow;
+
 /// Crate for proof of work itself
 #[cfg(any(feature = "base_node", feature = "transactions"))]
 #[allow(clippy::module_inception)]
@@ -58,6 +62,8 @@ mod sha3x_pow;
 pub use sha3x_pow::sha3x_difficulty;
 #[cfg(all(test, feature = "base_node"))]
 pub use sha3x_pow::test as sha3x_test;
+#[cfg(feature = "base_node")]
+pub use sha3x_pow::Sha3xHasher;
 
 /// Crates for proof of work target_difficulty
 mod target_difficulty;
@@ -67,7 +73,7 @@ pub use target_difficulty::AchievedTargetDifficulty;
 #[cfg(feature = "base_node")]
 mod target_difficulty_window;
 #[cfg(feature = "base_node")]
-pub use target_difficulty_window::TargetDifficultyWindow;
+pub use target_difficulty_window::{calculate_target_difficulty, TargetDifficultyWindow};
 
 /// Crates for proof of work lwma_diff
 pub mod lwma_diff;
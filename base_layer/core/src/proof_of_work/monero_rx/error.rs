@@ -41,6 +41,8 @@ pub enum MergeMineError {
     ValidationError(String),
     #[error("Hex conversion error: {0}")]
     HexError(String),
+    #[error("Unsupported Monero block version: {0}")]
+    UnsupportedMoneroVersion(String),
     #[error("Monero PoW data did not contain a valid merkle root")]
     InvalidMerkleRoot,
     #[error("Invalid difficulty: {0}")]
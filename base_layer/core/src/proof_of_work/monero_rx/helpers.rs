@@ -34,7 +34,7 @@ use super::{
     error::MergeMineError,
     fixed_array::FixedByteArray,
     merkle_tree::{create_merkle_proof, tree_hash},
-    pow_data::MoneroPowData,
+    pow_data::{MoneroPowData, RANDOMX_KEY_SIZE},
 };
 use crate::{
     blocks::BlockHeader,
@@ -45,6 +45,8 @@ use crate::{
 };
 
 pub const LOG_TARGET: &str = "c::pow::monero_rx";
+/// The maximum size of a nonce in the coinbase extra field
+const MAX_EXTRA_NONCE_SIZE: usize = 255;
 ///  Calculates the achieved Monero difficulty for the `BlockHeader`. An error is returned if the BlockHeader does not
 /// contain valid Monero PoW data.
 pub fn randomx_difficulty(
@@ -109,6 +111,39 @@ pub fn verify_header(header: &BlockHeader) -> Result<MoneroPowData, MergeMineErr
     Ok(monero_data)
 }
 
+/// Checks the limits on merge mined pow data that consensus enforces from the `monero_pow_data_rules_height`:
+/// 1. The pow data is at most `max_pow_data_size` bytes, which is checked before it is parsed
+/// 1. The RandomX key is a 32-byte seed hash
+/// 1. The Monero transaction count includes the coinbase transaction
+///
+/// If these assertions pass, the parsed `MoneroPowData` is returned
+pub fn verify_pow_data_limits(header: &BlockHeader, max_pow_data_size: usize) -> Result<MoneroPowData, MergeMineError> {
+    if header.pow.pow_data.len() > max_pow_data_size {
+        return Err(MergeMineError::DeserializeError(format!(
+            "pow data is {} bytes which exceeds the maximum of {} bytes",
+            header.pow.pow_data.len(),
+            max_pow_data_size
+        )));
+    }
+    let monero_data = MoneroPowData::from_header(header)?;
+
+    // The seed hash is always a 32-byte block hash, so any other length is a malleable encoding of the key
+    if monero_data.randomx_key().len() != RANDOMX_KEY_SIZE {
+        return Err(MergeMineError::ValidationError(format!(
+            "RandomX key must be {} bytes but was {} bytes",
+            RANDOMX_KEY_SIZE,
+            monero_data.randomx_key().len()
+        )));
+    }
+    if monero_data.transaction_count == 0 {
+        return Err(MergeMineError::ValidationError(
+            "Monero transaction count must include the coinbase transaction".to_string(),
+        ));
+    }
+
+    Ok(monero_data)
+}
+
 /// Extracts the Monero block hash from the coinbase transaction's extra field
 pub fn extract_tari_hash(monero: &monero::Block) -> Result<Option<monero::Hash>, MergeMineError> {
     let extra_field = ExtraField::try_parse(&monero.miner_tx.prefix.extra)
@@ -192,6 +227,23 @@ pub fn append_merge_mining_tag<T: AsRef<[u8]>>(block: &mut monero::Block, hash:
     Ok(())
 }
 
+/// Appends an extra nonce to the coinbase extra field of a Monero block. Miners that are given the same block template
+/// with different extra nonces do not search the same nonce space.
+pub fn append_extra_nonce(block: &mut monero::Block, extra_nonce: &[u8]) -> Result<(), MergeMineError> {
+    if extra_nonce.len() > MAX_EXTRA_NONCE_SIZE {
+        return Err(MergeMineError::SerializeError(format!(
+            "Extra nonce must be at most {} bytes, but it was {} bytes",
+            MAX_EXTRA_NONCE_SIZE,
+            extra_nonce.len()
+        )));
+    }
+    let mut extra_field = ExtraField::try_parse(&block.miner_tx.prefix.extra)
+        .map_err(|_| MergeMineError::DeserializeError("Invalid extra field".to_string()))?;
+    extra_field.0.push(SubField::Nonce(extra_nonce.to_vec()));
+    block.miner_tx.prefix.extra = extra_field.into();
+    Ok(())
+}
+
 /// Creates a hex encoded Monero blockhashing_blob
 pub fn create_block_hashing_blob(
     header: &monero::BlockHeader,
@@ -231,7 +283,10 @@ mod test {
     };
 
     use super::*;
-    use crate::proof_of_work::{monero_rx::fixed_array::FixedByteArray, PowAlgorithm, ProofOfWork};
+    use crate::{
+        consensus::ConsensusConstants,
+        proof_of_work::{monero_rx::fixed_array::FixedByteArray, PowAlgorithm, ProofOfWork},
+    };
 
     // This tests checks the hash of monero-rs
     #[test]
@@ -587,6 +642,28 @@ mod test {
         assert!(details.contains("More than one merge mining tag found in coinbase"));
     }
 
+    #[test]
+    fn test_append_extra_nonce() {
+        let blocktemplate_blob = "0c0c8cd6a0fa057fe21d764e7abf004e975396a2160773b93712bf6118c3b4959ddd8ee0f76aad0000000002e1ea2701ffa5ea2701d5a299e2abb002028eb3066ced1b2cc82ea046f3716a48e9ae37144057d5fb48a97f941225a1957b2b0106225b7ec0a6544d8da39abe68d8bd82619b4a7c5bdae89c3783b256a8fa47820208f63aa86d2e857f070000".to_string();
+        let mut block = deserialize_monero_block_from_hex(blocktemplate_blob).unwrap();
+        let hash = [7u8; 32];
+        append_merge_mining_tag(&mut block, hash).unwrap();
+        let mut other_block = block.clone();
+        append_extra_nonce(&mut block, &[1, 0, 0, 0]).unwrap();
+        append_extra_nonce(&mut other_block, &[2, 0, 0, 0]).unwrap();
+
+        // The merge mining tag is still found, but the blocks no longer share a blockhashing blob
+        assert_eq!(extract_tari_hash(&block).unwrap(), Some(Hash::from_slice(&hash)));
+        assert_ne!(
+            create_blockhashing_blob_from_block(&block).unwrap(),
+            create_blockhashing_blob_from_block(&other_block).unwrap()
+        );
+        let extra_field = ExtraField::try_parse(&block.miner_tx.prefix.extra).unwrap();
+        assert!(extra_field.0.contains(&SubField::Nonce(vec![1, 0, 0, 0])));
+
+        assert!(append_extra_nonce(&mut block, &[0u8; 256]).is_err());
+    }
+
     #[test]
     fn test_verify_header_no_coinbase() {
         let blocktemplate_blob = "0c0c8cd6a0fa057fe21d764e7abf004e975396a2160773b93712bf6118c3b4959ddd8ee0f76aad0000000002e1ea2701ffa5ea2701d5a299e2abb002028eb3066ced1b2cc82ea046f3716a48e9ae37144057d5fb48a97f941225a1957b2b0106225b7ec0a6544d8da39abe68d8bd82619b4a7c5bdae89c3783b256a8fa47820208f63aa86d2e857f070000".to_string();
@@ -734,6 +811,109 @@ mod test {
         unpack_enum!(MergeMineError::InvalidMerkleRoot = err);
     }
 
+    fn merge_mined_header_with_key(randomx_key: &[u8]) -> BlockHeader {
+        let blocktemplate_blob = "0c0c8cd6a0fa057fe21d764e7abf004e975396a2160773b93712bf6118c3b4959ddd8ee0f76aad0000000002e1ea2701ffa5ea2701d5a299e2abb002028eb3066ced1b2cc82ea046f3716a48e9ae37144057d5fb48a97f941225a1957b2b0106225b7ec0a6544d8da39abe68d8bd82619b4a7c5bdae89c3783b256a8fa47820208f63aa86d2e857f070000".to_string();
+        let bytes = hex::decode(blocktemplate_blob).unwrap();
+        let mut block = deserialize::<monero::Block>(&bytes[..]).unwrap();
+        let mut block_header = BlockHeader {
+            version: 0,
+            height: 0,
+            prev_hash: FixedHash::zero(),
+            timestamp: EpochTime::now(),
+            output_mr: FixedHash::zero(),
+            output_mmr_size: 0,
+            kernel_mr: FixedHash::zero(),
+            kernel_mmr_size: 0,
+            input_mr: FixedHash::zero(),
+            total_kernel_offset: Default::default(),
+            total_script_offset: Default::default(),
+            nonce: 0,
+            pow: ProofOfWork::default(),
+            validator_node_mr: FixedHash::zero(),
+        };
+        let hash = block_header.merge_mining_hash();
+        append_merge_mining_tag(&mut block, hash).unwrap();
+        let hashes = [block.miner_tx.hash()];
+        let monero_data = MoneroPowData {
+            header: block.header,
+            randomx_key: FixedByteArray::from_bytes(randomx_key).unwrap(),
+            transaction_count: 1,
+            merkle_root: tree_hash(&hashes).unwrap(),
+            coinbase_merkle_proof: create_merkle_proof(&hashes).unwrap(),
+            coinbase_tx: block.miner_tx,
+        };
+        let mut serialized = Vec::new();
+        monero_data.serialize(&mut serialized).unwrap();
+        block_header.pow = ProofOfWork {
+            pow_algo: PowAlgorithm::RandomX,
+            pow_data: serialized,
+        };
+        block_header
+    }
+
+    fn max_pow_data_size() -> usize {
+        ConsensusConstants::localnet().remove(0).max_monero_pow_data_size()
+    }
+
+    #[test]
+    fn test_verify_pow_data_limits_invalid_randomx_key_length() {
+        let seed_hash = from_hex("9f02e032f9b15d2aded991e0f68cc3c3427270b568b782e55fbd269ead0bad97").unwrap();
+        let block_header = merge_mined_header_with_key(&seed_hash);
+        verify_pow_data_limits(&block_header, max_pow_data_size()).unwrap();
+
+        let mut oversized_seed = seed_hash.clone();
+        oversized_seed.extend_from_slice(&[0u8; 28]);
+        let block_header = merge_mined_header_with_key(&oversized_seed);
+        // The key length is only a consensus rule from the activation height
+        verify_header(&block_header).unwrap();
+        let err = verify_pow_data_limits(&block_header, max_pow_data_size()).unwrap_err();
+        unpack_enum!(MergeMineError::ValidationError(details) = err);
+        assert!(details.contains("RandomX key must be 32 bytes"));
+
+        let block_header = merge_mined_header_with_key(&seed_hash[..16]);
+        let err = verify_pow_data_limits(&block_header, max_pow_data_size()).unwrap_err();
+        unpack_enum!(MergeMineError::ValidationError(details) = err);
+        assert!(details.contains("RandomX key must be 32 bytes"));
+    }
+
+    #[test]
+    fn test_verify_pow_data_limits_oversized_pow_data() {
+        let mut block_header = merge_mined_header_with_key(&[1u8; 32]);
+        block_header.pow.pow_data.resize(max_pow_data_size() + 1, 0);
+        let err = verify_pow_data_limits(&block_header, max_pow_data_size()).unwrap_err();
+        unpack_enum!(MergeMineError::DeserializeError(details) = err);
+        assert!(details.contains("exceeds the maximum"));
+    }
+
+    #[test]
+    fn test_verify_header_rejects_malformed_pow_data() {
+        let block_header = merge_mined_header_with_key(&[1u8; 32]);
+        let pow_data = block_header.pow.pow_data.clone();
+
+        // Every strict prefix and every single-byte extension of valid pow data must be rejected without panicking
+        for len in 0..pow_data.len() {
+            let mut header = block_header.clone();
+            header.pow.pow_data.truncate(len);
+            assert!(verify_header(&header).is_err());
+        }
+        let mut header = block_header.clone();
+        header.pow.pow_data.push(0);
+        assert!(verify_header(&header).is_err());
+
+        // Flipping any bit must either be rejected or still produce data that re-serializes to the same bytes
+        for i in 0..pow_data.len() {
+            for bit in 0..8 {
+                let mut header = block_header.clone();
+                header.pow.pow_data[i] ^= 1 << bit;
+                if let Ok(data) = MoneroPowData::from_header(&header) {
+                    let mut serialized = Vec::new();
+                    data.serialize(&mut serialized).unwrap();
+                    assert_eq!(serialized, header.pow.pow_data);
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_difficulty() {
         // Taken from block: https://stagenet.xmrchain.net/search?value=672576
@@ -24,6 +24,7 @@ pub use error::MergeMineError;
 
 mod helpers;
 pub use helpers::{
+    append_extra_nonce,
     append_merge_mining_tag,
     construct_monero_data,
     create_blockhashing_blob_from_block,
@@ -33,19 +34,29 @@ pub use helpers::{
     randomx_difficulty,
     serialize_monero_block_to_hex,
     verify_header,
+    verify_pow_data_limits,
 };
 
 mod fixed_array;
 pub use fixed_array::FixedByteArray;
 
 mod pow_data;
-pub use pow_data::MoneroPowData;
+pub use pow_data::{MoneroPowData, RANDOMX_KEY_SIZE};
 
 mod merkle_tree;
 pub use merkle_tree::{create_merkle_proof, tree_hash};
+
+mod version;
 // Re-exports
 pub use monero::{
     consensus::{deserialize, serialize},
     Block as MoneroBlock,
     BlockHeader as MoneroBlockHeader,
 };
+pub use version::{
+    randomx_seed_height,
+    MoneroHardFork,
+    MoneroVersionPolicy,
+    LATEST_KNOWN_MAJOR_VERSION,
+    RANDOMX_MIN_MAJOR_VERSION,
+};
@@ -37,6 +37,9 @@ use tari_utilities::hex::{to_hex, Hex};
 use super::{error::MergeMineError, fixed_array::FixedByteArray, merkle_tree::MerkleProof};
 use crate::{blocks::BlockHeader, proof_of_work::monero_rx::helpers::create_block_hashing_blob};
 
+/// The size in bytes of a RandomX vm key (the Monero seed hash) that is accepted at consensus level.
+pub const RANDOMX_KEY_SIZE: usize = 32;
+
 /// This is a struct to deserialize the data from he pow field into data required for the randomX Monero merged mine
 /// pow.
 #[derive(Clone, Debug)]
@@ -0,0 +1,269 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::fmt;
+
+use log::*;
+use monero::blockdata::transaction::TxOutTarget;
+
+use super::{error::MergeMineError, helpers::LOG_TARGET};
+
+/// The first Monero block major version that uses RandomX proof of work
+pub const RANDOMX_MIN_MAJOR_VERSION: u64 = 12;
+/// The highest Monero block major version whose rules are known
+pub const LATEST_KNOWN_MAJOR_VERSION: u64 = 16;
+/// The number of blocks in a RandomX seed hash epoch
+pub const RANDOMX_SEEDHASH_EPOCH_BLOCKS: u64 = 2048;
+/// The number of blocks a new RandomX seed hash lags behind the start of its epoch
+pub const RANDOMX_SEEDHASH_EPOCH_LAG: u64 = 64;
+
+/// The Monero hard forks that change the block rules relevant to merge mining
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MoneroHardFork {
+    /// v12: RandomX proof of work
+    RandomX,
+    /// v13 and v14: CLSAG ring signatures
+    Clsag,
+    /// v15 and v16: view tags on outputs and Bulletproofs+
+    ViewTags,
+}
+
+impl MoneroHardFork {
+    /// Returns the hard fork for the given block major version, or None if the version is older than RandomX or not
+    /// yet known
+    pub fn from_major_version(major_version: u64) -> Option<Self> {
+        match major_version {
+            12 => Some(MoneroHardFork::RandomX),
+            13 | 14 => Some(MoneroHardFork::Clsag),
+            15 | 16 => Some(MoneroHardFork::ViewTags),
+            _ => None,
+        }
+    }
+
+    /// The latest hard fork whose rules are known
+    pub fn latest() -> Self {
+        MoneroHardFork::ViewTags
+    }
+
+    /// Returns true if coinbase outputs must carry a view tag
+    pub fn requires_view_tags(self) -> bool {
+        self >= MoneroHardFork::ViewTags
+    }
+}
+
+impl fmt::Display for MoneroHardFork {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoneroHardFork::RandomX => write!(f, "RandomX"),
+            MoneroHardFork::Clsag => write!(f, "CLSAG"),
+            MoneroHardFork::ViewTags => write!(f, "ViewTags"),
+        }
+    }
+}
+
+/// The range of Monero block major versions that are accepted for merge mining. Block versions newer than the latest
+/// known version are validated using the rules of the latest known hard fork, unless `max_major_version` excludes
+/// them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MoneroVersionPolicy {
+    pub min_major_version: u64,
+    pub max_major_version: Option<u64>,
+}
+
+impl MoneroVersionPolicy {
+    /// Checks that the Monero block's version is accepted and that its coinbase follows the parsing rules of that
+    /// version, returning the hard fork the block belongs to
+    pub fn check_block(&self, block: &monero::Block) -> Result<MoneroHardFork, MergeMineError> {
+        let major_version = block.header.major_version.0;
+        let minor_version = block.header.minor_version.0;
+        let min_major_version = self.min_major_version.max(RANDOMX_MIN_MAJOR_VERSION);
+        if major_version < min_major_version {
+            return Err(MergeMineError::UnsupportedMoneroVersion(format!(
+                "Block major version {} is lower than the minimum supported version {}",
+                major_version, min_major_version
+            )));
+        }
+        if let Some(max_major_version) = self.max_major_version {
+            if major_version > max_major_version {
+                return Err(MergeMineError::UnsupportedMoneroVersion(format!(
+                    "Block major version {} is higher than the configured maximum version {}",
+                    major_version, max_major_version
+                )));
+            }
+        }
+        // The minor version is the hard fork vote and can never be lower than the version in force
+        if minor_version < major_version {
+            return Err(MergeMineError::UnsupportedMoneroVersion(format!(
+                "Block minor version {} is lower than its major version {}",
+                minor_version, major_version
+            )));
+        }
+
+        let hard_fork = MoneroHardFork::from_major_version(major_version).unwrap_or_else(|| {
+            warn!(
+                target: LOG_TARGET,
+                "Monero block major version {} is newer than the latest known version {}, applying {} rules",
+                major_version,
+                LATEST_KNOWN_MAJOR_VERSION,
+                MoneroHardFork::latest()
+            );
+            MoneroHardFork::latest()
+        });
+
+        for (i, output) in block.miner_tx.prefix.outputs.iter().enumerate() {
+            let is_valid = match output.target {
+                TxOutTarget::ToKey { .. } => !hard_fork.requires_view_tags(),
+                TxOutTarget::ToTaggedKey { .. } => hard_fork.requires_view_tags(),
+                _ => false,
+            };
+            if !is_valid {
+                return Err(MergeMineError::UnsupportedMoneroVersion(format!(
+                    "Coinbase output {} has an output type that is invalid for the {} hard fork (major version {})",
+                    i, hard_fork, major_version
+                )));
+            }
+        }
+
+        Ok(hard_fork)
+    }
+}
+
+impl Default for MoneroVersionPolicy {
+    fn default() -> Self {
+        Self {
+            min_major_version: RANDOMX_MIN_MAJOR_VERSION,
+            max_major_version: None,
+        }
+    }
+}
+
+/// Returns the height of the Monero block whose hash is the RandomX seed for a block at the given height. The seed
+/// behaviour is the same for every RandomX hard fork.
+pub fn randomx_seed_height(height: u64) -> u64 {
+    if height <= RANDOMX_SEEDHASH_EPOCH_BLOCKS + RANDOMX_SEEDHASH_EPOCH_LAG {
+        0
+    } else {
+        (height - RANDOMX_SEEDHASH_EPOCH_LAG - 1) & !(RANDOMX_SEEDHASH_EPOCH_BLOCKS - 1)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use monero::{PublicKey, VarInt};
+
+    use super::*;
+    use crate::proof_of_work::monero_rx::deserialize_monero_block_from_hex;
+
+    // A v12 mainnet block template with only a coinbase transaction
+    const BLOCK_TEMPLATE: &str = "0c0c8cd6a0fa057fe21d764e7abf004e975396a2160773b93712bf6118c3b4959ddd8ee0f76aad0000000002e1ea2701ffa5ea2701d5a299e2abb002028eb3066ced1b2cc82ea046f3716a48e9ae37144057d5fb48a97f941225a1957b2b0106225b7ec0a6544d8da39abe68d8bd82619b4a7c5bdae89c3783b256a8fa47820208f63aa86d2e857f070000";
+
+    fn block_with_version(major_version: u64, minor_version: u64) -> monero::Block {
+        let mut block = deserialize_monero_block_from_hex(BLOCK_TEMPLATE).unwrap();
+        block.header.major_version = VarInt(major_version);
+        block.header.minor_version = VarInt(minor_version);
+        block
+    }
+
+    fn tag_coinbase_outputs(block: &mut monero::Block) {
+        for output in &mut block.miner_tx.prefix.outputs {
+            let key: PublicKey = match output.target {
+                TxOutTarget::ToKey { key } => key,
+                _ => panic!("Expected a ToKey output"),
+            };
+            output.target = TxOutTarget::ToTaggedKey { key, view_tag: 0x5a };
+        }
+    }
+
+    #[test]
+    fn it_maps_major_versions_to_hard_forks() {
+        assert_eq!(MoneroHardFork::from_major_version(11), None);
+        assert_eq!(MoneroHardFork::from_major_version(12), Some(MoneroHardFork::RandomX));
+        assert_eq!(MoneroHardFork::from_major_version(14), Some(MoneroHardFork::Clsag));
+        assert_eq!(MoneroHardFork::from_major_version(16), Some(MoneroHardFork::ViewTags));
+        assert_eq!(MoneroHardFork::from_major_version(LATEST_KNOWN_MAJOR_VERSION + 1), None);
+    }
+
+    #[test]
+    fn it_accepts_known_versions() {
+        let policy = MoneroVersionPolicy::default();
+        assert_eq!(
+            policy.check_block(&block_with_version(12, 12)).unwrap(),
+            MoneroHardFork::RandomX
+        );
+        assert_eq!(
+            policy.check_block(&block_with_version(14, 14)).unwrap(),
+            MoneroHardFork::Clsag
+        );
+
+        let mut block = block_with_version(16, 16);
+        tag_coinbase_outputs(&mut block);
+        assert_eq!(policy.check_block(&block).unwrap(), MoneroHardFork::ViewTags);
+    }
+
+    #[test]
+    fn it_applies_the_output_rules_of_each_version() {
+        let policy = MoneroVersionPolicy::default();
+        let err = policy.check_block(&block_with_version(15, 15)).unwrap_err();
+        assert!(matches!(err, MergeMineError::UnsupportedMoneroVersion(_)));
+
+        let mut block = block_with_version(13, 13);
+        tag_coinbase_outputs(&mut block);
+        let err = policy.check_block(&block).unwrap_err();
+        assert!(matches!(err, MergeMineError::UnsupportedMoneroVersion(_)));
+    }
+
+    #[test]
+    fn it_rejects_versions_outside_the_policy() {
+        let policy = MoneroVersionPolicy::default();
+        assert!(policy.check_block(&block_with_version(11, 11)).is_err());
+        assert!(policy.check_block(&block_with_version(14, 13)).is_err());
+
+        let policy = MoneroVersionPolicy {
+            min_major_version: 14,
+            max_major_version: Some(15),
+        };
+        assert!(policy.check_block(&block_with_version(13, 13)).is_err());
+        let mut block = block_with_version(16, 16);
+        tag_coinbase_outputs(&mut block);
+        assert!(policy.check_block(&block).is_err());
+    }
+
+    #[test]
+    fn it_applies_the_latest_rules_to_unknown_versions() {
+        let policy = MoneroVersionPolicy::default();
+        let mut block = block_with_version(LATEST_KNOWN_MAJOR_VERSION + 1, LATEST_KNOWN_MAJOR_VERSION + 1);
+        tag_coinbase_outputs(&mut block);
+        assert_eq!(policy.check_block(&block).unwrap(), MoneroHardFork::latest());
+    }
+
+    #[test]
+    fn it_calculates_the_randomx_seed_height() {
+        assert_eq!(randomx_seed_height(0), 0);
+        assert_eq!(randomx_seed_height(2112), 0);
+        assert_eq!(randomx_seed_height(2113), 2048);
+        assert_eq!(randomx_seed_height(4160), 2048);
+        assert_eq!(randomx_seed_height(4161), 4096);
+        // Seed heights of mainnet blocks either side of the v15 hard fork at height 2688888
+        assert_eq!(randomx_seed_height(2688887), 2686976);
+        assert_eq!(randomx_seed_height(2688888), 2686976);
+    }
+}
@@ -62,7 +62,8 @@ impl ProofOfWork {
         }
     }
 
-    /// Serialises the ProofOfWork instance into a byte string. Useful for feeding the PoW into a hash function.
+    /// Serialises the ProofOfWork instance into the RFC-0131 Sha3x pre-image encoding: the algorithm byte followed by
+    /// the raw PoW data, with no length prefix. Header hashes use the Borsh consensus encoding of this struct instead.
     #[allow(deprecated)]
     pub fn to_bytes(&self) -> Vec<u8> {
         let mut buf = Vec::with_capacity(256);
@@ -82,6 +83,8 @@ impl Display for ProofOfWork {
 
 #[cfg(test)]
 mod test {
+    use borsh::BorshSerialize;
+
     use crate::proof_of_work::proof_of_work::{PowAlgorithm, ProofOfWork};
 
     #[test]
@@ -98,4 +101,26 @@ mod test {
         };
         assert_eq!(pow.to_bytes(), vec![1]);
     }
+
+    // Golden vectors: these encodings are consensus critical and must never change for an existing version
+    #[test]
+    fn to_bytes_golden_vector() {
+        let pow = ProofOfWork {
+            pow_algo: PowAlgorithm::RandomX,
+            pow_data: vec![0xde, 0xad, 0xbe, 0xef],
+        };
+        assert_eq!(pow.to_bytes(), vec![0x00, 0xde, 0xad, 0xbe, 0xef]);
+    }
+
+    #[test]
+    fn consensus_encoding_golden_vector() {
+        let pow = ProofOfWork {
+            pow_algo: PowAlgorithm::Sha3x,
+            pow_data: vec![0xde, 0xad],
+        };
+        assert_eq!(pow.try_to_vec().unwrap(), vec![
+            0x01, 0x02, 0x00, 0x00, 0x00, 0xde, 0xad
+        ]);
+        assert_eq!(PowAlgorithm::RandomX.try_to_vec().unwrap(), vec![0x00]);
+    }
 }
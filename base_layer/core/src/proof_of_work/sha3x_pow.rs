@@ -39,31 +39,84 @@ pub fn sha3x_difficulty(header: &BlockHeader) -> Result<Difficulty, DifficultyEr
 
 /// Calculate the Tari Sha3 mining hash
 pub fn sha3_hash(header: &BlockHeader) -> Vec<u8> {
-    Sha3_256::new()
-        .chain_update(header.nonce.to_le_bytes())
-        .chain_update(header.mining_hash())
-        .chain_update(header.pow.to_bytes())
-        .finalize()
-        .to_vec()
+    Sha3_256::digest(sha3x_pre_image(header)).to_vec()
+}
+
+/// Encodes the Sha3X pre-image `nonce || mining_hash || pow bytes`
+fn sha3x_pre_image(header: &BlockHeader) -> Vec<u8> {
+    let pow_bytes = header.pow.to_bytes();
+    let mut pre_image = Vec::with_capacity(Sha3xHasher::NONCE_SIZE + 32 + pow_bytes.len());
+    pre_image.extend_from_slice(&header.nonce.to_le_bytes());
+    pre_image.extend_from_slice(header.mining_hash().as_slice());
+    pre_image.extend_from_slice(&pow_bytes);
+    pre_image
 }
 
 /// Calculate the Tari Sha3X mining hash and achieved difficulty
 fn sha3x_difficulty_with_hash(header: &BlockHeader) -> Result<(Difficulty, Vec<u8>), DifficultyError> {
-    let hash = sha3_hash(header);
-    let hash = Sha3_256::digest(hash);
+    let (difficulty, hash) = sha3x_difficulty_with_pre_image(&sha3x_pre_image(header))?;
+    Ok((difficulty, hash.to_vec()))
+}
+
+/// Calculate the Tari Sha3X mining hash and achieved difficulty of an encoded pre-image
+fn sha3x_difficulty_with_pre_image(pre_image: &[u8]) -> Result<(Difficulty, [u8; 32]), DifficultyError> {
+    let hash = Sha3_256::digest(pre_image);
     let hash = Sha3_256::digest(hash);
+    let hash: [u8; 32] = Sha3_256::digest(hash).into();
     let difficulty = Difficulty::big_endian_difficulty(&hash)?;
-    Ok((difficulty, hash.to_vec()))
+    Ok((difficulty, hash))
+}
+
+/// A miner-facing Sha3x hasher that caches the parts of the pre-image that do not change between nonces.
+///
+/// The Sha3x pre-image is `nonce || mining_hash || pow bytes`. Computing the mining hash requires hashing the entire
+/// header, so doing it for every nonce dominates the cost of [sha3x_difficulty]. Because the nonce is the first field,
+/// there is no constant prefix to absorb ahead of time. Instead, the mining hash and PoW bytes are encoded once and
+/// only the leading nonce bytes are overwritten between attempts. The whole pre-image fits in a single Keccak block,
+/// so each attempt costs exactly three Sha3-256 permutations.
+///
+/// The hasher must be recreated whenever any header field other than the nonce changes.
+#[derive(Debug, Clone)]
+pub struct Sha3xHasher {
+    pre_image: Vec<u8>,
+}
+
+impl Sha3xHasher {
+    const NONCE_SIZE: usize = 8;
+
+    /// Creates a new hasher for the given header. The header nonce is ignored.
+    pub fn new(header: &BlockHeader) -> Self {
+        Self {
+            pre_image: sha3x_pre_image(header),
+        }
+    }
+
+    /// The bytes that follow the nonce in the Sha3x pre-image
+    pub fn pre_image_tail(&self) -> &[u8] {
+        &self.pre_image[Self::NONCE_SIZE..]
+    }
+
+    /// Calculate the achieved difficulty for the given nonce
+    pub fn difficulty(&mut self, nonce: u64) -> Result<Difficulty, DifficultyError> {
+        self.pre_image[..Self::NONCE_SIZE].copy_from_slice(&nonce.to_le_bytes());
+        Ok(sha3x_difficulty_with_pre_image(&self.pre_image)?.0)
+    }
 }
 
 #[cfg(test)]
 pub mod test {
     use chrono::{DateTime, NaiveDate, Utc};
-    use tari_utilities::epoch_time::EpochTime;
+    use tari_common_types::types::PrivateKey;
+    use tari_utilities::{epoch_time::EpochTime, hex::Hex, ByteArray};
 
     use crate::{
         blocks::BlockHeader,
-        proof_of_work::{sha3x_pow::sha3x_difficulty, Difficulty, PowAlgorithm},
+        proof_of_work::{
+            sha3x_pow::{sha3_hash, sha3x_difficulty, sha3x_difficulty_with_hash, sha3x_pre_image, Sha3xHasher},
+            Difficulty,
+            PowAlgorithm,
+            ProofOfWork,
+        },
     };
 
     /// A simple example miner. It starts at nonce = 0 and iterates until it finds a header hash that meets the desired
@@ -95,6 +148,26 @@ pub mod test {
         header
     }
 
+    /// A header with every field of the Sha3x pre-image set, used for golden vectors
+    pub fn get_golden_header() -> BlockHeader {
+        BlockHeader {
+            version: 1,
+            height: 12345,
+            prev_hash: [1u8; 32].into(),
+            timestamp: EpochTime::from(1_700_000_000),
+            input_mr: [2u8; 32].into(),
+            output_mr: [3u8; 32].into(),
+            output_mmr_size: 100,
+            kernel_mr: [4u8; 32].into(),
+            kernel_mmr_size: 200,
+            total_kernel_offset: PrivateKey::from_bytes(&[5u8; 32]).unwrap(),
+            total_script_offset: PrivateKey::from_bytes(&[6u8; 32]).unwrap(),
+            validator_node_mr: [7u8; 32].into(),
+            pow: ProofOfWork::new(PowAlgorithm::Sha3x),
+            nonce: 42,
+        }
+    }
+
     #[test]
     fn validate_max_target() {
         let mut header = get_header();
@@ -102,4 +175,34 @@ pub mod test {
         println!("{:?}", header);
         assert_eq!(sha3x_difficulty(&header).unwrap(), Difficulty::from_u64(6564).unwrap());
     }
+
+    #[test]
+    fn hasher_matches_sha3x_difficulty() {
+        let mut header = get_header();
+        let mut hasher = Sha3xHasher::new(&header);
+        for nonce in (0..500).chain(u64::MAX - 10..=u64::MAX) {
+            header.nonce = nonce;
+            assert_eq!(hasher.difficulty(nonce).unwrap(), sha3x_difficulty(&header).unwrap());
+        }
+        assert_eq!(hasher.difficulty(154).unwrap(), Difficulty::from_u64(6564).unwrap());
+    }
+
+    // Golden vectors: the Sha3x pre-image is computed by external miners and must never change
+    #[test]
+    fn pre_image_golden_vector() {
+        let header = get_golden_header();
+        assert_eq!(
+            sha3x_pre_image(&header).to_hex(),
+            "2a00000000000000e45d2aa8726dcc19fa35ef7ea8dd82dde6b239b610994437610bdf15465807ab01"
+        );
+        assert_eq!(
+            sha3_hash(&header).to_hex(),
+            "146b0bb6d525f395a2d13051ddda327994461da4527178c2bceb0b741734c0e8"
+        );
+        let (_, hash) = sha3x_difficulty_with_hash(&header).unwrap();
+        assert_eq!(
+            hash.to_hex(),
+            "8fc89be440f5a30d03adb1e73044a7107b9b1fc9fe160e3567f59d279c40db45"
+        );
+    }
 }
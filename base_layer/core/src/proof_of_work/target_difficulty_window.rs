@@ -20,11 +20,20 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::cmp;
+use std::{cmp, convert::TryFrom};
 
 use tari_utilities::epoch_time::EpochTime;
 
-use crate::proof_of_work::{difficulty::DifficultyAdjustment, lwma_diff::LinearWeightedMovingAverage, Difficulty};
+use crate::{
+    consensus::ConsensusConstants,
+    proof_of_work::{
+        difficulty::DifficultyAdjustment,
+        lwma_diff::{lwma_target_difficulty, LinearWeightedMovingAverage},
+        Difficulty,
+        DifficultyError,
+        PowAlgorithm,
+    },
+};
 
 /// A window of target difficulties
 #[derive(Debug, Clone)]
@@ -77,6 +86,34 @@ impl TargetDifficultyWindow {
     }
 }
 
+/// Calculates the target difficulty of the next `pow_algo` block from the timestamps and target difficulties of the
+/// preceding `pow_algo` blocks, ordered from oldest to newest. Only the last `difficulty_block_window + 1` samples are
+/// used and the result is clamped to the consensus minimum and maximum difficulty, matching the value a base node
+/// expects in the next block header.
+pub fn calculate_target_difficulty(
+    pow_algo: PowAlgorithm,
+    timestamps: &[EpochTime],
+    target_difficulties: &[Difficulty],
+    constants: &ConsensusConstants,
+) -> Result<Difficulty, DifficultyError> {
+    if timestamps.len() != target_difficulties.len() {
+        return Err(DifficultyError::SampleLengthMismatch {
+            timestamps: timestamps.len(),
+            target_difficulties: target_difficulties.len(),
+        });
+    }
+    let block_window = usize::try_from(constants.difficulty_block_window()).unwrap_or(usize::MAX);
+    let skip = timestamps.len().saturating_sub(block_window.saturating_add(1));
+    let min = constants.min_pow_difficulty(pow_algo);
+    let max = constants.max_pow_difficulty(pow_algo);
+    let target = lwma_target_difficulty(
+        &timestamps[skip..],
+        &target_difficulties[skip..],
+        constants.pow_target_block_interval(pow_algo),
+    )?;
+    Ok(cmp::max(min, cmp::min(max, target.unwrap_or(min))))
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -98,4 +135,32 @@ mod test {
             Difficulty::from_u64(100).unwrap()
         );
     }
+
+    #[test]
+    fn it_calculates_the_target_difficulty_from_samples() {
+        let constants = ConsensusConstants::localnet().remove(0);
+        let algo = PowAlgorithm::Sha3x;
+        let block_window = usize::try_from(constants.difficulty_block_window()).unwrap();
+        let target_time = constants.pow_target_block_interval(algo);
+        let mut window = TargetDifficultyWindow::new(block_window, target_time).unwrap();
+
+        let mut timestamps = Vec::new();
+        let mut difficulties = Vec::new();
+        for i in 0..(block_window as u64 * 2) {
+            let time = EpochTime::from(i * target_time + (i % 7) * 3);
+            let difficulty = Difficulty::from_u64(1_000 + (i % 5) * 100).unwrap();
+            window.add_back(time, difficulty);
+            timestamps.push(time);
+            difficulties.push(difficulty);
+        }
+
+        let min = constants.min_pow_difficulty(algo);
+        let max = constants.max_pow_difficulty(algo);
+        assert_eq!(
+            calculate_target_difficulty(algo, &timestamps, &difficulties, &constants).unwrap(),
+            window.calculate(min, max)
+        );
+        assert_eq!(calculate_target_difficulty(algo, &[], &[], &constants).unwrap(), min);
+        assert!(calculate_target_difficulty(algo, &timestamps, &difficulties[1..], &constants).is_err());
+    }
 }
@@ -27,7 +27,7 @@ use tari_utilities::ByteArray;
 
 use super::core as proto;
 use crate::{
-    blocks::{Block, BlockHeaderAccumulatedData, HistoricalBlock, NewBlock},
+    blocks::{Block, BlockHeaderAccumulatedData, HistoricalBlock, NewBlock, ShortKernelId},
     proof_of_work::Difficulty,
 };
 
@@ -128,8 +128,11 @@ impl TryFrom<proto::BlockHeaderAccumulatedData> for BlockHeaderAccumulatedData {
     type Error = String;
 
     fn try_from(source: proto::BlockHeaderAccumulatedData) -> Result<Self, Self::Error> {
-        let mut acc_diff = [0; 16];
-        acc_diff.copy_from_slice(&source.total_accumulated_difficulty[0..16]);
+        let acc_diff: [u8; 16] = source
+            .total_accumulated_difficulty
+            .as_slice()
+            .try_into()
+            .map_err(|_| "Malformed total accumulated difficulty".to_string())?;
         let accumulated_difficulty = u128::from_le_bytes(acc_diff);
         let hash = source.hash.try_into().map_err(|_| "Malformed hash".to_string())?;
         Ok(Self {
@@ -169,6 +172,8 @@ impl TryFrom<proto::NewBlock> for NewBlock {
                 .map(|bytes| PrivateKey::from_bytes(bytes))
                 .collect::<Result<Vec<_>, _>>()
                 .map_err(|_| "Invalid excess signature scalar")?,
+            short_id_nonce: new_block.short_id_nonce,
+            short_kernel_ids: ShortKernelId::split_bytes(&new_block.short_kernel_ids)?,
         })
     }
 }
@@ -182,6 +187,39 @@ impl TryFrom<NewBlock> for proto::NewBlock {
             coinbase_kernel: Some(new_block.coinbase_kernel.into()),
             coinbase_output: Some(new_block.coinbase_output.try_into()?),
             kernel_excess_sigs: new_block.kernel_excess_sigs.into_iter().map(|s| s.to_vec()).collect(),
+            short_id_nonce: new_block.short_id_nonce,
+            short_kernel_ids: ShortKernelId::join_bytes(&new_block.short_kernel_ids),
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn accumulated_data() -> BlockHeaderAccumulatedData {
+        BlockHeaderAccumulatedData {
+            total_accumulated_difficulty: u128::from(u64::MAX) * 3,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn it_converts_the_total_accumulated_difficulty() {
+        let data = accumulated_data();
+        let converted =
+            BlockHeaderAccumulatedData::try_from(proto::BlockHeaderAccumulatedData::from(data.clone())).unwrap();
+        assert_eq!(converted, data);
+    }
+
+    #[test]
+    fn it_errors_on_a_malformed_total_accumulated_difficulty() {
+        let proto_data = proto::BlockHeaderAccumulatedData::from(accumulated_data());
+        for bytes in [vec![], vec![1u8; 8], vec![1u8; 15], vec![1u8; 17]] {
+            let mut proto_data = proto_data.clone();
+            proto_data.total_accumulated_difficulty = bytes;
+            let err = BlockHeaderAccumulatedData::try_from(proto_data).unwrap_err();
+            assert_eq!(err, "Malformed total accumulated difficulty");
+        }
+    }
+}
@@ -54,6 +54,8 @@ use crate::{
         BlockchainBackend,
         BlockchainDatabase,
         BlockchainDatabaseConfig,
+        BlockchainSnapshotSource,
+        BurntTotals,
         ChainStorageError,
         DbBasicStats,
         DbKey,
@@ -224,6 +226,10 @@ impl BlockchainBackend for TempDatabase {
         self.db.as_ref().unwrap().contains(key)
     }
 
+    fn snapshot_source(&self) -> Arc<dyn BlockchainSnapshotSource> {
+        self.db.as_ref().unwrap().snapshot_source()
+    }
+
     fn fetch_chain_header_by_height(&self, height: u64) -> Result<ChainHeader, ChainStorageError> {
         self.db.as_ref().unwrap().fetch_chain_header_by_height(height)
     }
@@ -301,6 +307,27 @@ impl BlockchainBackend for TempDatabase {
             .fetch_unspent_output_hash_by_commitment(commitment)
     }
 
+    fn fetch_output_hash_by_commitment(
+        &self,
+        commitment: &Commitment,
+    ) -> Result<Option<HashOutput>, ChainStorageError> {
+        self.db.as_ref().unwrap().fetch_output_hash_by_commitment(commitment)
+    }
+
+    fn fetch_block_hash_by_output_commitment(
+        &self,
+        commitment: &Commitment,
+    ) -> Result<Option<HashOutput>, ChainStorageError> {
+        self.db
+            .as_ref()
+            .unwrap()
+            .fetch_block_hash_by_output_commitment(commitment)
+    }
+
+    fn fetch_block_hash_by_kernel_excess(&self, excess: &Commitment) -> Result<Option<HashOutput>, ChainStorageError> {
+        self.db.as_ref().unwrap().fetch_block_hash_by_kernel_excess(excess)
+    }
+
     fn fetch_outputs_in_block(&self, header_hash: &HashOutput) -> Result<Vec<PrunedOutput>, ChainStorageError> {
         self.db.as_ref().unwrap().fetch_outputs_in_block(header_hash)
     }
@@ -373,11 +400,13 @@ impl BlockchainBackend for TempDatabase {
         &mut self,
         horizon_height: u64,
         orphan_storage_capacity: usize,
+        orphan_storage_max_weight: u64,
     ) -> Result<(), ChainStorageError> {
-        self.db
-            .as_mut()
-            .unwrap()
-            .delete_oldest_orphans(horizon_height, orphan_storage_capacity)
+        self.db.as_mut().unwrap().delete_oldest_orphans(
+            horizon_height,
+            orphan_storage_capacity,
+            orphan_storage_max_weight,
+        )
     }
 
     fn fetch_monero_seed_first_seen_height(&self, seed: &[u8]) -> Result<u64, ChainStorageError> {
@@ -388,6 +417,10 @@ impl BlockchainBackend for TempDatabase {
         self.db.as_ref().unwrap().fetch_horizon_data()
     }
 
+    fn fetch_burnt_totals(&self, height: u64) -> Result<Option<BurntTotals>, ChainStorageError> {
+        self.db.as_ref().unwrap().fetch_burnt_totals(height)
+    }
+
     fn get_stats(&self) -> Result<DbBasicStats, ChainStorageError> {
         self.db.as_ref().unwrap().get_stats()
     }
@@ -396,6 +429,10 @@ impl BlockchainBackend for TempDatabase {
         self.db.as_ref().unwrap().fetch_total_size_stats()
     }
 
+    fn sync(&self) -> Result<(), ChainStorageError> {
+        self.db.as_ref().unwrap().sync()
+    }
+
     fn fetch_header_hash_by_deleted_mmr_positions(
         &self,
         mmr_positions: Vec<u32>,
@@ -458,7 +495,11 @@ pub async fn create_chained_blocks<T: Into<BlockSpecs>>(
     (block_names, block_hashes)
 }
 
-fn mine_block(block: Block, prev_block_accum: &BlockHeaderAccumulatedData, difficulty: Difficulty) -> Arc<ChainBlock> {
+pub(super) fn mine_block(
+    block: Block,
+    prev_block_accum: &BlockHeaderAccumulatedData,
+    difficulty: Difficulty,
+) -> Arc<ChainBlock> {
     let block = mine_to_difficulty(block, difficulty).unwrap();
     let accum = BlockHeaderAccumulatedData::builder(prev_block_accum)
         .with_hash(block.hash())
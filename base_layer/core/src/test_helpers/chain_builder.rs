@@ -0,0 +1,264 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! A deterministic builder for realistic test chains.
+//!
+//! ```ignore
+//! let chain = ChainBuilder::new().with_blocks(10).with_reorg_at(7).build().await;
+//! assert_eq!(chain.tip().height(), 10);
+//! let txs = chain.create_mempool_transactions(3).await;
+//! ```
+//!
+//! Every block's coinbase is derived from a key manager seeded with
+//! [DETERMINISTIC_TEST_SEED_PHRASE](crate::transactions::test_helpers::DETERMINISTIC_TEST_SEED_PHRASE), so the keys
+//! (and therefore the spendable outputs) of a chain are the same from one run to the next.
+
+use std::sync::Arc;
+
+use tari_common::configuration::Network;
+
+use super::{
+    blockchain::{create_custom_blockchain, mine_block, TempDatabase},
+    create_block,
+    BlockSpec,
+};
+use crate::{
+    blocks::ChainBlock,
+    chain_storage::BlockchainDatabase,
+    consensus::ConsensusManager,
+    mempool::{Mempool, MempoolConfig},
+    proof_of_work::Difficulty,
+    transactions::{
+        test_helpers::{create_deterministic_key_manager, spend_utxos, TestKeyManager},
+        transaction_components::{Transaction, WalletOutput},
+    },
+    txn_schema,
+    validation::transaction::TransactionChainLinkedValidator,
+};
+
+/// Builds a [TestChain] of a given length, optionally with a reorg.
+pub struct ChainBuilder {
+    num_blocks: u64,
+    reorg_at: Option<u64>,
+    rules: Option<ConsensusManager>,
+}
+
+impl ChainBuilder {
+    pub fn new() -> Self {
+        Self {
+            num_blocks: 0,
+            reorg_at: None,
+            rules: None,
+        }
+    }
+
+    /// The number of blocks to mine on top of the genesis block
+    pub fn with_blocks(mut self, num_blocks: u64) -> Self {
+        self.num_blocks = num_blocks;
+        self
+    }
+
+    /// Replace every block from `height` up to the tip with a stronger fork of the same length. The replaced blocks
+    /// are available from [TestChain::reorged_blocks].
+    pub fn with_reorg_at(mut self, height: u64) -> Self {
+        self.reorg_at = Some(height);
+        self
+    }
+
+    /// Use these consensus rules instead of the LocalNet defaults
+    pub fn with_consensus_manager(mut self, rules: ConsensusManager) -> Self {
+        self.rules = Some(rules);
+        self
+    }
+
+    /// Mine the chain into a fresh temporary database.
+    ///
+    /// # Panics
+    /// This is a test helper and panics if the requested reorg height is outside of `1..=num_blocks`, or if the
+    /// database rejects any of the blocks.
+    pub async fn build(self) -> TestChain {
+        let rules = self
+            .rules
+            .unwrap_or_else(|| ConsensusManager::builder(Network::LocalNet).build().unwrap());
+        let db = create_custom_blockchain(rules.clone());
+        let genesis = db
+            .fetch_block(0, true)
+            .unwrap()
+            .try_into_chain_block()
+            .map(Arc::new)
+            .unwrap();
+        let mut chain = TestChain {
+            db,
+            rules,
+            km: create_deterministic_key_manager(),
+            genesis,
+            main_chain: Vec::new(),
+            reorged: Vec::new(),
+        };
+
+        for _ in 0..self.num_blocks {
+            let parent = chain.tip().clone();
+            let block = chain.mine_on(&parent, Difficulty::min()).await;
+            let result = chain.db.add_block(block.0.to_arc_block()).unwrap();
+            assert!(
+                result.is_added(),
+                "Block {} was not added to the chain",
+                block.0.height()
+            );
+            chain.main_chain.push(block);
+        }
+
+        if let Some(height) = self.reorg_at {
+            assert!(
+                (1..=self.num_blocks).contains(&height),
+                "Cannot reorg at height {} on a chain of {} blocks",
+                height,
+                self.num_blocks
+            );
+            chain.reorg_from(height).await;
+        }
+
+        chain
+    }
+}
+
+impl Default for ChainBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A chain created by [ChainBuilder], together with the key manager that owns its coinbase outputs.
+pub struct TestChain {
+    db: BlockchainDatabase<TempDatabase>,
+    rules: ConsensusManager,
+    km: TestKeyManager,
+    genesis: Arc<ChainBlock>,
+    main_chain: Vec<(Arc<ChainBlock>, WalletOutput)>,
+    reorged: Vec<(Arc<ChainBlock>, WalletOutput)>,
+}
+
+impl TestChain {
+    pub fn db(&self) -> &BlockchainDatabase<TempDatabase> {
+        &self.db
+    }
+
+    pub fn rules(&self) -> &ConsensusManager {
+        &self.rules
+    }
+
+    pub fn key_manager(&self) -> &TestKeyManager {
+        &self.km
+    }
+
+    pub fn genesis(&self) -> &Arc<ChainBlock> {
+        &self.genesis
+    }
+
+    /// The current tip of the main chain
+    pub fn tip(&self) -> &Arc<ChainBlock> {
+        self.main_chain.last().map(|(block, _)| block).unwrap_or(&self.genesis)
+    }
+
+    /// The main chain block at `height`, if any
+    pub fn block_at_height(&self, height: u64) -> Option<&Arc<ChainBlock>> {
+        if height == 0 {
+            return Some(&self.genesis);
+        }
+        self.main_chain
+            .get(usize::try_from(height - 1).ok()?)
+            .map(|(block, _)| block)
+    }
+
+    /// The main chain blocks above genesis and their coinbase outputs, ordered by height
+    pub fn main_chain(&self) -> &[(Arc<ChainBlock>, WalletOutput)] {
+        &self.main_chain
+    }
+
+    /// The blocks (and coinbase outputs) that were replaced by the reorg, ordered by height
+    pub fn reorged_blocks(&self) -> &[(Arc<ChainBlock>, WalletOutput)] {
+        &self.reorged
+    }
+
+    /// The main chain coinbase outputs that have matured at the current tip
+    pub fn spendable_coinbases(&self) -> Vec<WalletOutput> {
+        let tip_height = self.tip().height();
+        self.main_chain
+            .iter()
+            .map(|(_, coinbase)| coinbase)
+            .filter(|coinbase| coinbase.features.maturity <= tip_height)
+            .cloned()
+            .collect()
+    }
+
+    /// Create an empty mempool that validates transactions against this chain
+    pub fn create_mempool(&self) -> Mempool {
+        Mempool::new(
+            MempoolConfig::default(),
+            self.rules.clone(),
+            Box::new(TransactionChainLinkedValidator::new(
+                self.db.clone(),
+                self.rules.clone(),
+            )),
+        )
+    }
+
+    /// Create up to `count` valid, independent transactions that each spend one matured coinbase of the main chain.
+    /// Fewer transactions are returned if the chain does not have enough matured coinbases.
+    pub async fn create_mempool_transactions(&self, count: usize) -> Vec<Arc<Transaction>> {
+        let mut transactions = Vec::with_capacity(count);
+        for coinbase in self.spendable_coinbases().into_iter().take(count) {
+            let inputs = vec![coinbase];
+            let (tx, _) = spend_utxos(txn_schema!(from: inputs), &self.km).await;
+            transactions.push(Arc::new(tx));
+        }
+        transactions
+    }
+
+    async fn mine_on(&self, parent: &Arc<ChainBlock>, difficulty: Difficulty) -> (Arc<ChainBlock>, WalletOutput) {
+        let spec = BlockSpec::new().with_difficulty(difficulty).finish();
+        let (block, coinbase) = create_block(&self.rules, parent.block(), spec, &self.km).await;
+        (mine_block(block, parent.accumulated_data(), difficulty), coinbase)
+    }
+
+    async fn reorg_from(&mut self, height: u64) {
+        let fork_len = self.main_chain.len() - usize::try_from(height - 1).unwrap();
+        let mut parent = self.block_at_height(height - 1).unwrap().clone();
+        // Each fork block is mined at twice the difficulty of the blocks it replaces, so the fork has the greater
+        // accumulated difficulty once it is as long as the chain it replaces.
+        let difficulty = Difficulty::from_u64(Difficulty::min().as_u64() * 2).unwrap();
+        let mut fork = Vec::with_capacity(fork_len);
+        for _ in 0..fork_len {
+            let block = self.mine_on(&parent, difficulty).await;
+            self.db.add_block(block.0.to_arc_block()).unwrap();
+            parent = block.0.clone();
+            fork.push(block);
+        }
+        assert_eq!(
+            self.db.fetch_tip_header().unwrap().hash(),
+            parent.hash(),
+            "The fork did not become the main chain"
+        );
+        self.reorged = self.main_chain.split_off(self.main_chain.len() - fork_len);
+        self.main_chain.extend(fork);
+    }
+}
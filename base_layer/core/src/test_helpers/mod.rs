@@ -51,6 +51,7 @@ use crate::{
 #[macro_use]
 mod block_spec;
 pub mod blockchain;
+pub mod chain_builder;
 
 pub fn create_consensus_rules() -> ConsensusManager {
     ConsensusManager::builder(Network::LocalNet).build().unwrap()
@@ -298,6 +298,9 @@ where TKeyManagerInterface: TransactionKeyManagerInterface
             // Coinbase has no script offset https://rfc.tari.com/RFC-0201_TariScript.html#script-offset
             .add_script_offset(PrivateKey::default())
             .with_reward(total_reward)
+            .with_fee(0 * uT)
+            .with_lock_height(0)
+            .with_kernel_features(kernel_features)
             .with_kernel(kernel);
         let tx = builder
             .build()
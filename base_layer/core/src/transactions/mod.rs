@@ -12,6 +12,7 @@ mod coinbase_builder;
 pub use coinbase_builder::{CoinbaseBuildError, CoinbaseBuilder};
 
 pub mod fee;
+pub mod payment_proof;
 pub mod tari_amount;
 pub mod transaction_components;
 
@@ -0,0 +1,438 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Payment proofs allow the sender of a transaction to prove to a third party that they paid a given address. The
+//! proof binds the kernel of the transaction to the recipient address, amount and message, and is signed by the
+//! sender's wallet key. It also carries the recipient's receipt: a signature made by the recipient's wallet key during
+//! the transaction protocol over the kernel excess, kernel nonce, sender address and amount. The receipt is what shows
+//! that the recipient took part in the transaction; the sender signature on its own only covers public kernel data. A
+//! verifier checks both signatures and then confirms that the kernel has been mined.
+
+use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
+use tari_common_types::{
+    tari_address::TariAddress,
+    types::{Commitment, PrivateKey, PublicKey, Signature},
+};
+use tari_crypto::keys::{PublicKey as PublicKeyTrait, SecretKey};
+use thiserror::Error;
+
+use crate::{
+    consensus::DomainSeparatedConsensusHasher,
+    transactions::{tari_amount::MicroMinotari, transaction_components::TransactionKernel, TransactionHashDomain},
+};
+
+/// The maximum length of the message in a payment proof
+pub const MAX_PAYMENT_PROOF_MESSAGE_LENGTH: usize = 512;
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum PaymentProofError {
+    #[error("The signing key does not match the sender address")]
+    SenderKeyMismatch,
+    #[error("Payment proof message exceeds {MAX_PAYMENT_PROOF_MESSAGE_LENGTH} bytes")]
+    MessageTooLong,
+    #[error("Failed to sign payment proof: {0}")]
+    SigningError(String),
+    #[error("The recipient receipt is not valid for this transaction")]
+    InvalidRecipientReceipt,
+}
+
+/// A statement that the transaction with the given kernel paid `amount` to `recipient_address`, signed by the sender
+/// and acknowledged by the recipient
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaymentProof {
+    pub kernel_excess: Commitment,
+    pub kernel_excess_sig: Signature,
+    pub sender_address: TariAddress,
+    pub recipient_address: TariAddress,
+    pub amount: MicroMinotari,
+    pub message: String,
+    recipient_signature: Signature,
+    signature: Signature,
+}
+
+impl PaymentProof {
+    pub fn new(
+        kernel_excess: Commitment,
+        kernel_excess_sig: Signature,
+        sender_address: TariAddress,
+        recipient_address: TariAddress,
+        amount: MicroMinotari,
+        message: String,
+        recipient_signature: Signature,
+        signature: Signature,
+    ) -> Self {
+        Self {
+            kernel_excess,
+            kernel_excess_sig,
+            sender_address,
+            recipient_address,
+            amount,
+            message,
+            recipient_signature,
+            signature,
+        }
+    }
+
+    /// Creates the recipient's receipt for a transaction. The recipient calls this during the transaction protocol,
+    /// once it knows the aggregate kernel excess and nonce, and returns the receipt to the sender with its reply.
+    pub fn sign_receipt(
+        recipient_secret_key: &PrivateKey,
+        recipient_address: &TariAddress,
+        sender_address: &TariAddress,
+        kernel_excess: &Commitment,
+        kernel_public_nonce: &PublicKey,
+        amount: MicroMinotari,
+    ) -> Result<Signature, PaymentProofError> {
+        if &PublicKey::from_secret_key(recipient_secret_key) != recipient_address.public_key() {
+            return Err(PaymentProofError::SigningError(
+                "The signing key does not match the recipient address".to_string(),
+            ));
+        }
+        let secret_nonce = PrivateKey::random(&mut OsRng);
+        let public_nonce = PublicKey::from_secret_key(&secret_nonce);
+        let challenge = Self::build_receipt_challenge(
+            recipient_address,
+            &public_nonce,
+            sender_address,
+            kernel_excess,
+            kernel_public_nonce,
+            amount,
+        );
+        Signature::sign_raw(recipient_secret_key, secret_nonce, &challenge)
+            .map_err(|e| PaymentProofError::SigningError(e.to_string()))
+    }
+
+    /// Returns true if `receipt` was signed by `recipient_address` for the given transaction
+    pub fn verify_receipt(
+        receipt: &Signature,
+        recipient_address: &TariAddress,
+        sender_address: &TariAddress,
+        kernel_excess: &Commitment,
+        kernel_public_nonce: &PublicKey,
+        amount: MicroMinotari,
+    ) -> bool {
+        let challenge = Self::build_receipt_challenge(
+            recipient_address,
+            receipt.get_public_nonce(),
+            sender_address,
+            kernel_excess,
+            kernel_public_nonce,
+            amount,
+        );
+        receipt.verify_challenge(recipient_address.public_key(), &challenge)
+    }
+
+    /// Creates a payment proof for `kernel`, signed with the secret key of `sender_address`. `recipient_receipt` is the
+    /// receipt returned by the recipient during the transaction protocol and must be valid for the kernel.
+    pub fn sign(
+        sender_secret_key: &PrivateKey,
+        kernel: &TransactionKernel,
+        sender_address: TariAddress,
+        recipient_address: TariAddress,
+        amount: MicroMinotari,
+        message: String,
+        recipient_receipt: Signature,
+    ) -> Result<Self, PaymentProofError> {
+        if &PublicKey::from_secret_key(sender_secret_key) != sender_address.public_key() {
+            return Err(PaymentProofError::SenderKeyMismatch);
+        }
+        if message.len() > MAX_PAYMENT_PROOF_MESSAGE_LENGTH {
+            return Err(PaymentProofError::MessageTooLong);
+        }
+        if !Self::verify_receipt(
+            &recipient_receipt,
+            &recipient_address,
+            &sender_address,
+            &kernel.excess,
+            kernel.excess_sig.get_public_nonce(),
+            amount,
+        ) {
+            return Err(PaymentProofError::InvalidRecipientReceipt);
+        }
+        let secret_nonce = PrivateKey::random(&mut OsRng);
+        let public_nonce = PublicKey::from_secret_key(&secret_nonce);
+        let challenge = Self::build_challenge(
+            sender_address.public_key(),
+            &public_nonce,
+            &kernel.excess,
+            &kernel.excess_sig,
+            &recipient_address,
+            amount,
+            &message,
+        );
+        let signature = Signature::sign_raw(sender_secret_key, secret_nonce, &challenge)
+            .map_err(|e| PaymentProofError::SigningError(e.to_string()))?;
+        Ok(Self {
+            kernel_excess: kernel.excess.clone(),
+            kernel_excess_sig: kernel.excess_sig.clone(),
+            sender_address,
+            recipient_address,
+            amount,
+            message,
+            recipient_signature: recipient_receipt,
+            signature,
+        })
+    }
+
+    pub fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    pub fn recipient_signature(&self) -> &Signature {
+        &self.recipient_signature
+    }
+
+    /// Returns true if the proof was signed by the sender address and carries a valid receipt from the recipient
+    /// address. This does not check that the kernel exists on chain, which must be checked separately against a base
+    /// node.
+    pub fn verify_signature(&self) -> bool {
+        if self.message.len() > MAX_PAYMENT_PROOF_MESSAGE_LENGTH {
+            return false;
+        }
+        if !Self::verify_receipt(
+            &self.recipient_signature,
+            &self.recipient_address,
+            &self.sender_address,
+            &self.kernel_excess,
+            self.kernel_excess_sig.get_public_nonce(),
+            self.amount,
+        ) {
+            return false;
+        }
+        let challenge = Self::build_challenge(
+            self.sender_address.public_key(),
+            self.signature.get_public_nonce(),
+            &self.kernel_excess,
+            &self.kernel_excess_sig,
+            &self.recipient_address,
+            self.amount,
+            &self.message,
+        );
+        self.signature
+            .verify_challenge(self.sender_address.public_key(), &challenge)
+    }
+
+    /// Returns true if `kernel` is the kernel that this proof refers to
+    pub fn is_for_kernel(&self, kernel: &TransactionKernel) -> bool {
+        kernel.excess == self.kernel_excess && kernel.excess_sig == self.kernel_excess_sig
+    }
+
+    fn build_challenge(
+        sender_public_key: &PublicKey,
+        public_nonce: &PublicKey,
+        kernel_excess: &Commitment,
+        kernel_excess_sig: &Signature,
+        recipient_address: &TariAddress,
+        amount: MicroMinotari,
+        message: &str,
+    ) -> [u8; 32] {
+        DomainSeparatedConsensusHasher::<TransactionHashDomain>::new("payment_proof")
+            .chain(sender_public_key)
+            .chain(public_nonce)
+            .chain(kernel_excess)
+            .chain(kernel_excess_sig)
+            .chain(&recipient_address.to_bytes().to_vec())
+            .chain(&amount)
+            .chain(&message.to_string())
+            .finalize()
+    }
+
+    fn build_receipt_challenge(
+        recipient_address: &TariAddress,
+        public_nonce: &PublicKey,
+        sender_address: &TariAddress,
+        kernel_excess: &Commitment,
+        kernel_public_nonce: &PublicKey,
+        amount: MicroMinotari,
+    ) -> [u8; 32] {
+        DomainSeparatedConsensusHasher::<TransactionHashDomain>::new("payment_proof_receipt")
+            .chain(recipient_address.public_key())
+            .chain(public_nonce)
+            .chain(&sender_address.to_bytes().to_vec())
+            .chain(kernel_excess)
+            .chain(kernel_public_nonce)
+            .chain(&amount)
+            .finalize()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tari_common::configuration::Network;
+
+    use super::*;
+    use crate::transactions::transaction_components::KernelFeatures;
+
+    fn test_kernel() -> TransactionKernel {
+        let (_, excess_nonce) = PublicKey::random_keypair(&mut OsRng);
+        let (_, excess) = PublicKey::random_keypair(&mut OsRng);
+        TransactionKernel::new_current_version(
+            KernelFeatures::empty(),
+            MicroMinotari::from(100),
+            0,
+            Commitment::from_public_key(&excess),
+            Signature::new(excess_nonce, PrivateKey::random(&mut OsRng)),
+            None,
+        )
+    }
+
+    fn test_receipt(
+        recipient_secret_key: &PrivateKey,
+        kernel: &TransactionKernel,
+        sender_address: &TariAddress,
+        recipient_address: &TariAddress,
+        amount: MicroMinotari,
+    ) -> Signature {
+        PaymentProof::sign_receipt(
+            recipient_secret_key,
+            recipient_address,
+            sender_address,
+            &kernel.excess,
+            kernel.excess_sig.get_public_nonce(),
+            amount,
+        )
+        .unwrap()
+    }
+
+    fn test_proof() -> PaymentProof {
+        let (secret_key, public_key) = PublicKey::random_keypair(&mut OsRng);
+        let (recipient_secret_key, recipient_key) = PublicKey::random_keypair(&mut OsRng);
+        let kernel = test_kernel();
+        let sender_address = TariAddress::new(public_key, Network::LocalNet);
+        let recipient_address = TariAddress::new(recipient_key, Network::LocalNet);
+        let amount = MicroMinotari::from(1000);
+        let receipt = test_receipt(
+            &recipient_secret_key,
+            &kernel,
+            &sender_address,
+            &recipient_address,
+            amount,
+        );
+        PaymentProof::sign(
+            &secret_key,
+            &kernel,
+            sender_address,
+            recipient_address,
+            amount,
+            "Invoice 123".to_string(),
+            receipt,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn it_verifies_a_valid_proof() {
+        let proof = test_proof();
+        assert!(proof.verify_signature());
+    }
+
+    #[test]
+    fn it_rejects_a_tampered_proof() {
+        let mut proof = test_proof();
+        proof.amount = MicroMinotari::from(1001);
+        assert!(!proof.verify_signature());
+
+        let mut proof = test_proof();
+        let (_, other_key) = PublicKey::random_keypair(&mut OsRng);
+        proof.recipient_address = TariAddress::new(other_key, Network::LocalNet);
+        assert!(!proof.verify_signature());
+
+        let mut proof = test_proof();
+        proof.message = "Invoice 124".to_string();
+        assert!(!proof.verify_signature());
+
+        let mut proof = test_proof();
+        proof.recipient_signature = test_proof().recipient_signature;
+        assert!(!proof.verify_signature());
+    }
+
+    #[test]
+    fn it_requires_a_receipt_from_the_recipient() {
+        let (secret_key, public_key) = PublicKey::random_keypair(&mut OsRng);
+        let (_, recipient_key) = PublicKey::random_keypair(&mut OsRng);
+        let kernel = test_kernel();
+        let sender_address = TariAddress::new(public_key, Network::LocalNet);
+        let recipient_address = TariAddress::new(recipient_key, Network::LocalNet);
+        let amount = MicroMinotari::from(1000);
+        // The sender cannot stand in for the recipient by signing the receipt themselves
+        let (secret_nonce, public_nonce) = PublicKey::random_keypair(&mut OsRng);
+        let challenge = PaymentProof::build_receipt_challenge(
+            &recipient_address,
+            &public_nonce,
+            &sender_address,
+            &kernel.excess,
+            kernel.excess_sig.get_public_nonce(),
+            amount,
+        );
+        let forged_receipt = Signature::sign_raw(&secret_key, secret_nonce, &challenge).unwrap();
+        let err = PaymentProof::sign(
+            &secret_key,
+            &kernel,
+            sender_address,
+            recipient_address,
+            amount,
+            String::new(),
+            forged_receipt,
+        )
+        .unwrap_err();
+        assert_eq!(err, PaymentProofError::InvalidRecipientReceipt);
+    }
+
+    #[test]
+    fn it_errors_if_the_key_does_not_match_the_sender() {
+        let secret_key = PrivateKey::random(&mut OsRng);
+        let (_, sender_key) = PublicKey::random_keypair(&mut OsRng);
+        let err = PaymentProof::sign(
+            &secret_key,
+            &test_kernel(),
+            TariAddress::new(sender_key.clone(), Network::LocalNet),
+            TariAddress::new(sender_key, Network::LocalNet),
+            MicroMinotari::from(1000),
+            String::new(),
+            Signature::default(),
+        )
+        .unwrap_err();
+        assert_eq!(err, PaymentProofError::SenderKeyMismatch);
+    }
+
+    #[test]
+    fn it_matches_its_kernel() {
+        let (secret_key, public_key) = PublicKey::random_keypair(&mut OsRng);
+        let kernel = test_kernel();
+        let address = TariAddress::new(public_key, Network::LocalNet);
+        let amount = MicroMinotari::from(1000);
+        let receipt = test_receipt(&secret_key, &kernel, &address, &address, amount);
+        let proof = PaymentProof::sign(
+            &secret_key,
+            &kernel,
+            address.clone(),
+            address,
+            amount,
+            String::new(),
+            receipt,
+        )
+        .unwrap();
+        assert!(proof.is_for_kernel(&kernel));
+        assert!(!proof.is_for_kernel(&test_kernel()));
+    }
+}
@@ -20,7 +20,7 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{iter, mem::size_of, sync::Arc};
+use std::{iter, mem::size_of, str::FromStr, sync::Arc};
 
 use chacha20poly1305::{Key, KeyInit, XChaCha20Poly1305};
 use rand::{distributions::Alphanumeric, rngs::OsRng, Rng, RngCore};
@@ -34,6 +34,8 @@ use tari_key_manager::{
         storage::{database::KeyManagerDatabase, sqlite_db::KeyManagerSqliteDatabase},
         KeyManagerInterface,
     },
+    mnemonic::Mnemonic,
+    SeedWords,
 };
 use tari_script::{inputs, script, ExecutionStack, TariScript};
 
@@ -889,15 +891,35 @@ fn random_string(len: usize) -> String {
         .collect()
 }
 
+/// The seed phrase used by [create_deterministic_key_manager]. Do not use it for anything other than tests.
+pub const DETERMINISTIC_TEST_SEED_PHRASE: &str = "scan announce neither belt grace arch sting butter run frost debris \
+                                                  slide glory nature asthma fame during silly panda picnic run small \
+                                                  engage pride";
+
 pub fn create_test_core_key_manager_with_memory_db_with_range_proof_size(size: usize) -> TestKeyManager {
+    create_test_core_key_manager_with_seed(CipherSeed::new(), size)
+}
+
+pub fn create_test_core_key_manager_with_memory_db() -> TestKeyManager {
+    create_test_core_key_manager_with_memory_db_with_range_proof_size(64)
+}
+
+/// Create a key manager that is seeded from [DETERMINISTIC_TEST_SEED_PHRASE]. Every key it hands out, on every
+/// branch, is the same from one run to the next.
+pub fn create_deterministic_key_manager() -> TestKeyManager {
+    let seed_words = SeedWords::from_str(DETERMINISTIC_TEST_SEED_PHRASE).unwrap();
+    let cipher = CipherSeed::from_mnemonic(&seed_words, None).unwrap();
+    create_test_core_key_manager_with_seed(cipher, 64)
+}
+
+pub fn create_test_core_key_manager_with_seed(cipher: CipherSeed, range_proof_size: usize) -> TestKeyManager {
     let connection = DbConnection::connect_url(&DbConnectionUrl::MemoryShared(random_string(8))).unwrap();
-    let cipher = CipherSeed::new();
 
     let mut key = [0u8; size_of::<Key>()];
     OsRng.fill_bytes(&mut key);
     let key_ga = Key::from_slice(&key);
     let db_cipher = XChaCha20Poly1305::new(key_ga);
-    let factory = CryptoFactories::new(size);
+    let factory = CryptoFactories::new(range_proof_size);
 
     TransactionKeyManagerWrapper::<KeyManagerSqliteDatabase<DbConnection>>::new(
         cipher,
@@ -906,7 +928,3 @@ pub fn create_test_core_key_manager_with_memory_db_with_range_proof_size(size: u
     )
     .unwrap()
 }
-
-pub fn create_test_core_key_manager_with_memory_db() -> TestKeyManager {
-    create_test_core_key_manager_with_memory_db_with_range_proof_size(64)
-}
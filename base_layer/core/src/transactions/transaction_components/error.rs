@@ -24,21 +24,28 @@
 // Version 2.0, available at http://www.apache.org/licenses/LICENSE-2.0.
 
 use serde::{Deserialize, Serialize};
+use tari_common_types::types::{Commitment, FixedHash};
 use tari_crypto::{
     errors::RangeProofError,
     signatures::{CommitmentAndPublicKeySignatureError, SchnorrSignatureError},
 };
 use tari_key_manager::key_manager_service::KeyManagerServiceError;
 use tari_script::ScriptError;
+use tari_utilities::hex::Hex;
 use thiserror::Error;
 
-use crate::transactions::transaction_components::EncryptedDataError;
+use crate::transactions::{
+    tari_amount::MicroMinotari,
+    transaction_components::{EncryptedDataError, KernelFeatures},
+};
 
 //----------------------------------------     TransactionError   ----------------------------------------------------//
 #[derive(Clone, Debug, PartialEq, Error, Deserialize, Serialize, Eq)]
 pub enum TransactionError {
     #[error("Error building the transaction: {0}")]
     BuilderError(String),
+    #[error("Transaction builder validation failed: {0}")]
+    TransactionBuilderError(#[from] TransactionBuilderError),
     #[error("Signature is invalid: {0}")]
     InvalidSignatureError(String),
     #[error("A range proof construction or verification has produced an error: {0}")]
@@ -73,6 +80,46 @@ pub enum TransactionError {
     EncryptedDataError(String),
 }
 
+//----------------------------------------  TransactionBuilderError  -------------------------------------------------//
+/// The reasons a [TransactionBuilder](super::TransactionBuilder) can refuse to finalize a transaction.
+#[derive(Clone, Debug, PartialEq, Error, Deserialize, Serialize, Eq)]
+pub enum TransactionBuilderError {
+    #[error("The transaction offset has not been set")]
+    MissingOffset,
+    #[error("The script offset has not been set")]
+    MissingScriptOffset,
+    #[error("The transaction kernel has not been set")]
+    MissingKernel,
+    #[error("The transaction has no inputs and no outputs")]
+    EmptyTransaction,
+    #[error("Input `{0}` was added more than once")]
+    DuplicateInput(FixedHash),
+    #[error("Output with commitment `{}` was added more than once", .0.to_hex())]
+    DuplicateOutput(Commitment),
+    #[error("A coinbase transaction cannot spend inputs")]
+    CoinbaseWithInputs,
+    #[error("A reward was provided but the kernel is not a coinbase kernel")]
+    RewardWithoutCoinbaseKernel,
+    #[error("The kernel fee is {actual} but {expected} was expected")]
+    FeeMismatch {
+        expected: MicroMinotari,
+        actual: MicroMinotari,
+    },
+    #[error("The kernel lock height is {actual} but {expected} was expected")]
+    LockHeightMismatch { expected: u64, actual: u64 },
+    #[error("The kernel features are {actual:?} but {expected:?} was expected")]
+    KernelFeaturesMismatch {
+        expected: KernelFeatures,
+        actual: KernelFeatures,
+    },
+    #[error("A burn kernel must contain a burn commitment")]
+    MissingBurnCommitment,
+    #[error("Only a burn kernel may contain a burn commitment")]
+    UnexpectedBurnCommitment,
+    #[error("The burn commitment `{}` does not match any output", .0.to_hex())]
+    BurnCommitmentNotInOutputs(Commitment),
+}
+
 impl From<KeyManagerServiceError> for TransactionError {
     fn from(err: KeyManagerServiceError) -> Self {
         TransactionError::KeyManagerError(err.to_string())
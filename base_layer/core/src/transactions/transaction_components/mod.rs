@@ -25,7 +25,7 @@
 
 use chacha20poly1305::Key;
 pub use encrypted_data::{EncryptedData, EncryptedDataError};
-pub use error::TransactionError;
+pub use error::{TransactionBuilderError, TransactionError};
 pub use kernel_builder::KernelBuilder;
 pub use kernel_features::KernelFeatures;
 pub use kernel_sum::KernelSum;
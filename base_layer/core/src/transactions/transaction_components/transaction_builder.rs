@@ -23,20 +23,39 @@
 // Portions of this file were originally copyrighted (c) 2018 The Grin Developers, issued under the Apache License,
 // Version 2.0, available at http://www.apache.org/licenses/LICENSE-2.0.
 
+use std::collections::HashSet;
+
 use tari_common_types::types::PrivateKey;
 
 use crate::transactions::{
     aggregated_body::AggregateBody,
     tari_amount::MicroMinotari,
-    transaction_components::{Transaction, TransactionError, TransactionInput, TransactionKernel, TransactionOutput},
+    transaction_components::{
+        KernelFeatures,
+        Transaction,
+        TransactionBuilderError,
+        TransactionError,
+        TransactionInput,
+        TransactionKernel,
+        TransactionOutput,
+    },
 };
 
 //----------------------------------------  Transaction Builder   ----------------------------------------------------//
+/// Assembles a [Transaction] from its inputs, outputs, kernel and offsets.
+///
+/// Besides the mandatory pieces, callers may declare the fee, lock height and kernel features they expect the
+/// transaction to carry. [TransactionBuilder::build] checks the assembled pieces against these expectations (and
+/// against a set of structural rules) and reports the first problem as a [TransactionBuilderError], so that an
+/// inconsistently assembled transaction is caught before it is handed to the mempool.
 pub struct TransactionBuilder {
     body: AggregateBody,
     offset: Option<PrivateKey>,
     script_offset: Option<PrivateKey>,
     reward: Option<MicroMinotari>,
+    fee: Option<MicroMinotari>,
+    lock_height: Option<u64>,
+    kernel_features: Option<KernelFeatures>,
 }
 
 impl TransactionBuilder {
@@ -92,8 +111,106 @@ impl TransactionBuilder {
         self
     }
 
+    /// Declare the fee the kernel is expected to pay
+    pub fn with_fee(&mut self, fee: MicroMinotari) -> &mut Self {
+        self.fee = Some(fee);
+        self
+    }
+
+    /// Declare the lock height the kernel is expected to carry
+    pub fn with_lock_height(&mut self, lock_height: u64) -> &mut Self {
+        self.lock_height = Some(lock_height);
+        self
+    }
+
+    /// Declare the features the kernel is expected to carry
+    pub fn with_kernel_features(&mut self, features: KernelFeatures) -> &mut Self {
+        self.kernel_features = Some(features);
+        self
+    }
+
+    /// Check the assembled pieces without consuming the builder. This is the same validation that
+    /// [TransactionBuilder::build] performs.
+    pub fn validate(&self) -> Result<(), TransactionBuilderError> {
+        if self.offset.is_none() {
+            return Err(TransactionBuilderError::MissingOffset);
+        }
+        if self.script_offset.is_none() {
+            return Err(TransactionBuilderError::MissingScriptOffset);
+        }
+        let kernel = self
+            .body
+            .kernels()
+            .first()
+            .ok_or(TransactionBuilderError::MissingKernel)?;
+        if self.body.inputs().is_empty() && self.body.outputs().is_empty() {
+            return Err(TransactionBuilderError::EmptyTransaction);
+        }
+        self.validate_unique_io()?;
+        self.validate_kernel(kernel)
+    }
+
+    fn validate_unique_io(&self) -> Result<(), TransactionBuilderError> {
+        let mut spent = HashSet::with_capacity(self.body.inputs().len());
+        for input in self.body.inputs() {
+            let hash = input.output_hash();
+            if !spent.insert(hash) {
+                return Err(TransactionBuilderError::DuplicateInput(hash));
+            }
+        }
+        let mut created = HashSet::with_capacity(self.body.outputs().len());
+        for output in self.body.outputs() {
+            if !created.insert(output.commitment()) {
+                return Err(TransactionBuilderError::DuplicateOutput(output.commitment().clone()));
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_kernel(&self, kernel: &TransactionKernel) -> Result<(), TransactionBuilderError> {
+        if let Some(expected) = self.fee {
+            if kernel.fee != expected {
+                return Err(TransactionBuilderError::FeeMismatch {
+                    expected,
+                    actual: kernel.fee,
+                });
+            }
+        }
+        if let Some(expected) = self.lock_height {
+            if kernel.lock_height != expected {
+                return Err(TransactionBuilderError::LockHeightMismatch {
+                    expected,
+                    actual: kernel.lock_height,
+                });
+            }
+        }
+        if let Some(expected) = self.kernel_features {
+            if kernel.features != expected {
+                return Err(TransactionBuilderError::KernelFeaturesMismatch {
+                    expected,
+                    actual: kernel.features,
+                });
+            }
+        }
+        if kernel.is_coinbase() && !self.body.inputs().is_empty() {
+            return Err(TransactionBuilderError::CoinbaseWithInputs);
+        }
+        if self.reward.is_some() && !kernel.is_coinbase() {
+            return Err(TransactionBuilderError::RewardWithoutCoinbaseKernel);
+        }
+        match (&kernel.burn_commitment, kernel.is_burned()) {
+            (None, true) => Err(TransactionBuilderError::MissingBurnCommitment),
+            (Some(_), false) => Err(TransactionBuilderError::UnexpectedBurnCommitment),
+            (Some(commitment), true) if !self.body.outputs().iter().any(|o| o.commitment() == commitment) => {
+                Err(TransactionBuilderError::BurnCommitmentNotInOutputs(commitment.clone()))
+            },
+            _ => Ok(()),
+        }
+    }
+
     /// Build the transaction.
     pub fn build(self) -> Result<Transaction, TransactionError> {
+        self.validate()?;
         if let (Some(script_offset), Some(offset)) = (self.script_offset, self.offset) {
             let (i, o, k) = self.body.dissolve();
             let mut tx = Transaction::new(i, o, k, offset, script_offset);
@@ -112,6 +229,167 @@ impl Default for TransactionBuilder {
             body: AggregateBody::empty(),
             reward: None,
             script_offset: None,
+            fee: None,
+            lock_height: None,
+            kernel_features: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tari_common_types::types::{ComAndPubSignature, Commitment, CommitmentFactory, FixedHash, Signature};
+    use tari_crypto::commitment::HomomorphicCommitmentFactory;
+    use tari_script::ExecutionStack;
+
+    use super::*;
+
+    fn kernel(features: KernelFeatures, fee: u64, burn_commitment: Option<Commitment>) -> TransactionKernel {
+        TransactionKernel::new_current_version(
+            features,
+            fee.into(),
+            0,
+            Commitment::default(),
+            Signature::default(),
+            burn_commitment,
+        )
+    }
+
+    fn input(seed: u8) -> TransactionInput {
+        TransactionInput::new_with_output_hash(
+            FixedHash::from([seed; 32]),
+            ExecutionStack::default(),
+            ComAndPubSignature::default(),
+        )
+    }
+
+    fn output(value: u64) -> TransactionOutput {
+        TransactionOutput {
+            commitment: CommitmentFactory::default().commit_value(&PrivateKey::from(value), value),
+            ..Default::default()
         }
     }
+
+    fn valid_builder() -> TransactionBuilder {
+        let mut builder = TransactionBuilder::new();
+        builder
+            .add_input(input(1))
+            .add_output(output(1))
+            .add_offset(PrivateKey::default())
+            .add_script_offset(PrivateKey::default())
+            .with_kernel(kernel(KernelFeatures::default(), 10, None));
+        builder
+    }
+
+    #[test]
+    fn it_builds_a_valid_transaction() {
+        let mut builder = valid_builder();
+        builder
+            .with_fee(10.into())
+            .with_lock_height(0)
+            .with_kernel_features(KernelFeatures::default());
+        let tx = builder.build().unwrap();
+        assert_eq!(tx.body.inputs().len(), 1);
+        assert_eq!(tx.body.outputs().len(), 1);
+        assert_eq!(tx.body.kernels().len(), 1);
+    }
+
+    #[test]
+    fn it_reports_missing_pieces() {
+        let mut builder = TransactionBuilder::new();
+        assert_eq!(builder.validate(), Err(TransactionBuilderError::MissingOffset));
+        builder.add_offset(PrivateKey::default());
+        assert_eq!(builder.validate(), Err(TransactionBuilderError::MissingScriptOffset));
+        builder.add_script_offset(PrivateKey::default());
+        assert_eq!(builder.validate(), Err(TransactionBuilderError::MissingKernel));
+        builder.with_kernel(kernel(KernelFeatures::default(), 0, None));
+        assert_eq!(builder.validate(), Err(TransactionBuilderError::EmptyTransaction));
+        assert_eq!(
+            builder.build().unwrap_err(),
+            TransactionError::TransactionBuilderError(TransactionBuilderError::EmptyTransaction)
+        );
+    }
+
+    #[test]
+    fn it_rejects_duplicate_inputs_and_outputs() {
+        let mut dup_input = valid_builder();
+        dup_input.add_input(input(1));
+        assert_eq!(
+            dup_input.validate(),
+            Err(TransactionBuilderError::DuplicateInput(FixedHash::from([1; 32])))
+        );
+
+        let mut dup_output = valid_builder();
+        dup_output.add_output(output(1));
+        assert_eq!(
+            dup_output.validate(),
+            Err(TransactionBuilderError::DuplicateOutput(output(1).commitment))
+        );
+    }
+
+    #[test]
+    fn it_checks_declared_kernel_values() {
+        let mut builder = valid_builder();
+        builder.with_fee(11.into());
+        assert_eq!(
+            builder.validate(),
+            Err(TransactionBuilderError::FeeMismatch {
+                expected: 11.into(),
+                actual: 10.into()
+            })
+        );
+        builder.with_fee(10.into()).with_lock_height(5);
+        assert_eq!(
+            builder.validate(),
+            Err(TransactionBuilderError::LockHeightMismatch { expected: 5, actual: 0 })
+        );
+        builder
+            .with_lock_height(0)
+            .with_kernel_features(KernelFeatures::create_burn());
+        assert_eq!(
+            builder.validate(),
+            Err(TransactionBuilderError::KernelFeaturesMismatch {
+                expected: KernelFeatures::create_burn(),
+                actual: KernelFeatures::default()
+            })
+        );
+    }
+
+    #[test]
+    fn it_checks_coinbase_rules() {
+        let mut builder = valid_builder();
+        builder.with_kernel(kernel(KernelFeatures::create_coinbase(), 0, None));
+        assert_eq!(builder.validate(), Err(TransactionBuilderError::CoinbaseWithInputs));
+
+        let mut builder = valid_builder();
+        builder.with_reward(100.into());
+        assert_eq!(
+            builder.validate(),
+            Err(TransactionBuilderError::RewardWithoutCoinbaseKernel)
+        );
+    }
+
+    #[test]
+    fn it_checks_burn_commitments() {
+        let mut builder = valid_builder();
+        builder.with_kernel(kernel(KernelFeatures::create_burn(), 10, None));
+        assert_eq!(builder.validate(), Err(TransactionBuilderError::MissingBurnCommitment));
+
+        builder.with_kernel(kernel(KernelFeatures::default(), 10, Some(output(1).commitment)));
+        assert_eq!(
+            builder.validate(),
+            Err(TransactionBuilderError::UnexpectedBurnCommitment)
+        );
+
+        builder.with_kernel(kernel(KernelFeatures::create_burn(), 10, Some(output(2).commitment)));
+        assert_eq!(
+            builder.validate(),
+            Err(TransactionBuilderError::BurnCommitmentNotInOutputs(
+                output(2).commitment
+            ))
+        );
+
+        builder.with_kernel(kernel(KernelFeatures::create_burn(), 10, Some(output(1).commitment)));
+        assert_eq!(builder.validate(), Ok(()));
+    }
 }
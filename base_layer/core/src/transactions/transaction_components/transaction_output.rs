@@ -367,6 +367,16 @@ impl TransactionOutput {
         matches!(self.features.output_type, OutputType::Burn)
     }
 
+    /// Returns the burnt value of a burn output that reveals its value, otherwise None. The value of a burn output
+    /// with a `RevealedValue` range proof is the minimum value promise, which consensus checks against the commitment.
+    pub fn revealed_burnt_value(&self) -> Option<MicroMinotari> {
+        if self.is_burned() && self.features.range_proof_type == RangeProofType::RevealedValue {
+            Some(self.minimum_value_promise)
+        } else {
+            None
+        }
+    }
+
     /// Convenience function that calculates the challenge for the metadata commitment signature
     pub fn build_metadata_signature_challenge(
         version: &TransactionOutputVersion,
@@ -578,7 +588,7 @@ mod test {
         key_manager::TransactionKeyManagerInterface,
         tari_amount::MicroMinotari,
         test_helpers::{create_test_core_key_manager_with_memory_db, TestKeyManager, TestParams, UtxoTestParams},
-        transaction_components::{OutputFeatures, RangeProofType},
+        transaction_components::{OutputFeatures, OutputType, RangeProofType},
         CryptoFactories,
     };
 
@@ -770,6 +780,36 @@ mod test {
         }
     }
 
+    #[tokio::test]
+    async fn it_returns_the_revealed_burnt_value() {
+        let key_manager = create_test_core_key_manager_with_memory_db();
+        let test_params = TestParams::new(&key_manager).await;
+        let mut revealed = create_output(
+            &test_params,
+            MicroMinotari(20),
+            MicroMinotari(20),
+            RangeProofType::RevealedValue,
+            &key_manager,
+        )
+        .await
+        .unwrap();
+        assert_eq!(revealed.revealed_burnt_value(), None);
+        revealed.features.output_type = OutputType::Burn;
+        assert_eq!(revealed.revealed_burnt_value(), Some(MicroMinotari(20)));
+
+        let mut confidential = create_output(
+            &test_params,
+            MicroMinotari(20),
+            MicroMinotari::zero(),
+            RangeProofType::BulletProofPlus,
+            &key_manager,
+        )
+        .await
+        .unwrap();
+        confidential.features.output_type = OutputType::Burn;
+        assert_eq!(confidential.revealed_burnt_value(), None);
+    }
+
     #[tokio::test]
     async fn it_does_not_batch_verify_incorrect_minimum_values() {
         let factories = CryptoFactories::default();
@@ -50,6 +50,7 @@ impl TryFrom<proto::RecipientSignedMessage> for RecipientSignedMessage {
             .ok_or_else(|| "Transaction metadata not provided".to_string())??;
 
         let offset = PrivateKey::from_bytes(&message.offset).map_err(|err| format!("offset: {}", err))?;
+        let payment_receipt = message.payment_receipt.map(TryInto::try_into).transpose()?;
 
         Ok(Self {
             tx_id: message.tx_id.into(),
@@ -58,6 +59,7 @@ impl TryFrom<proto::RecipientSignedMessage> for RecipientSignedMessage {
             partial_signature,
             tx_metadata: metadata,
             offset,
+            payment_receipt,
         })
     }
 }
@@ -73,6 +75,7 @@ impl TryFrom<RecipientSignedMessage> for proto::RecipientSignedMessage {
             partial_signature: Some(message.partial_signature.into()),
             metadata: Some(message.tx_metadata.into()),
             offset: message.offset.to_vec(),
+            payment_receipt: message.payment_receipt.map(Into::into),
         })
     }
 }
@@ -91,6 +91,9 @@ pub struct RecipientSignedMessage {
     pub partial_signature: Signature,
     pub tx_metadata: TransactionMetadata,
     pub offset: PrivateKey,
+    /// The recipient's payment proof receipt, signed with its wallet key over the aggregate kernel excess and nonce
+    #[serde(default)]
+    pub payment_receipt: Option<Signature>,
 }
 
 /// The generalised transaction recipient protocol. A different state transition network is followed depending on
@@ -151,6 +154,17 @@ impl ReceiverTransactionProtocol {
         }
     }
 
+    /// Attach the recipient's payment proof receipt to the signed data that is returned to the sender
+    pub fn add_payment_receipt(&mut self, receipt: Signature) -> Result<(), TransactionProtocolError> {
+        match &mut self.state {
+            RecipientState::Finalized(data) => {
+                data.payment_receipt = Some(receipt);
+                Ok(())
+            },
+            _ => Err(TransactionProtocolError::InvalidStateError),
+        }
+    }
+
     /// Run the single-round recipient protocol, which can immediately construct an output and sign the data
     async fn single_round<KM: TransactionKeyManagerInterface>(
         output: WalletOutput,
@@ -680,8 +680,12 @@ impl SenderTransactionProtocol {
         }
         let script_offset = key_manager.get_script_offset(&script_keys, &sender_offset_keys).await?;
 
-        tx_builder.add_offset(offset);
-        tx_builder.add_script_offset(script_offset);
+        tx_builder
+            .add_offset(offset)
+            .add_script_offset(script_offset)
+            .with_fee(info.metadata.fee)
+            .with_lock_height(info.metadata.lock_height)
+            .with_kernel_features(info.metadata.kernel_features);
         let excess = PedersenCommitment::from_public_key(&total_public_excess);
 
         let kernel = KernelBuilder::new()
@@ -95,6 +95,7 @@ impl SingleReceiverTransactionProtocol {
             partial_signature: signature,
             tx_metadata: tx_meta,
             offset,
+            payment_receipt: None,
         };
         Ok(data)
     }
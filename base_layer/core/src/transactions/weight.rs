@@ -22,7 +22,17 @@
 
 use std::{convert::TryFrom, num::NonZeroU64};
 
-use crate::transactions::aggregated_body::AggregateBody;
+use tari_script::TariScript;
+
+use crate::{
+    borsh::SerializedSize,
+    covenants::Covenant,
+    transactions::{
+        aggregated_body::AggregateBody,
+        tari_amount::MicroMinotari,
+        transaction_components::OutputFeatures,
+    },
+};
 
 #[derive(Debug, Clone, Copy)]
 pub struct WeightParams {
@@ -84,6 +94,26 @@ impl TransactionWeight {
             rounded_up_features_and_scripts_byte_size as u64 / params.features_and_scripts_bytes_per_gram.get()
     }
 
+    /// Same as [TransactionWeight::calculate], returning `None` if the weight does not fit in a u64. This should be
+    /// used for counts that have not been bounded by consensus, e.g. when they are provided by a client.
+    pub fn checked_calculate(
+        &self,
+        num_kernels: usize,
+        num_inputs: usize,
+        num_outputs: usize,
+        rounded_up_features_and_scripts_byte_size: usize,
+    ) -> Option<u64> {
+        let params = self.params();
+        let count_weight = |weight: u64, count: usize| weight.checked_mul(u64::try_from(count).ok()?);
+        count_weight(params.kernel_weight, num_kernels)?
+            .checked_add(count_weight(params.input_weight, num_inputs)?)?
+            .checked_add(count_weight(params.output_weight, num_outputs)?)?
+            .checked_add(
+                u64::try_from(rounded_up_features_and_scripts_byte_size).ok()? /
+                    params.features_and_scripts_bytes_per_gram.get(),
+            )
+    }
+
     pub fn calculate_body(&self, body: &AggregateBody) -> std::io::Result<u64> {
         let rounded_up_features_and_scripts_bytes_size =
             self.calculate_normalised_total_features_and_scripts_size(body)?;
@@ -141,9 +171,120 @@ impl From<WeightParams> for TransactionWeight {
     }
 }
 
+/// Accumulates the kernels, inputs and outputs of a transaction that has not been built yet and calculates its
+/// weight exactly as consensus would for the completed transaction. This allows wallets to estimate fees from the
+/// output features, scripts and covenants they intend to use.
+#[derive(Debug, Clone)]
+pub struct WeightCalculator {
+    weighting: TransactionWeight,
+    num_kernels: usize,
+    num_inputs: usize,
+    num_outputs: usize,
+    rounded_up_features_and_scripts_byte_size: usize,
+}
+
+impl WeightCalculator {
+    pub fn new(weighting: TransactionWeight) -> Self {
+        Self {
+            weighting,
+            num_kernels: 0,
+            num_inputs: 0,
+            num_outputs: 0,
+            rounded_up_features_and_scripts_byte_size: 0,
+        }
+    }
+
+    pub fn with_kernels(mut self, num_kernels: usize) -> Self {
+        self.num_kernels += num_kernels;
+        self
+    }
+
+    pub fn with_inputs(mut self, num_inputs: usize) -> Self {
+        self.num_inputs += num_inputs;
+        self
+    }
+
+    /// Adds an output with the given features, script and covenant. The range proof and other fixed size output
+    /// fields are accounted for by the per-output weight.
+    pub fn add_output(
+        &mut self,
+        features: &OutputFeatures,
+        script: &TariScript,
+        covenant: &Covenant,
+    ) -> std::io::Result<&mut Self> {
+        let size = features.get_serialized_size()? + script.get_serialized_size()? + covenant.get_serialized_size()?;
+        self.add_output_with_features_and_scripts_size(size);
+        Ok(self)
+    }
+
+    /// Adds an output given the unrounded byte size of its serialized features, script and covenant
+    pub fn add_output_with_features_and_scripts_size(&mut self, features_and_scripts_size: usize) -> &mut Self {
+        self.num_outputs += 1;
+        self.rounded_up_features_and_scripts_byte_size = self.rounded_up_features_and_scripts_byte_size.saturating_add(
+            self.weighting
+                .round_up_features_and_scripts_size(features_and_scripts_size),
+        );
+        self
+    }
+
+    /// Adds `num_outputs` outputs with default features, a `Nop` script and an empty covenant
+    pub fn add_default_outputs(&mut self, num_outputs: usize) -> std::io::Result<&mut Self> {
+        let size = OutputFeatures::default().get_serialized_size()? +
+            TariScript::default().get_serialized_size()? +
+            Covenant::default().get_serialized_size()?;
+        for _ in 0..num_outputs {
+            self.add_output_with_features_and_scripts_size(size);
+        }
+        Ok(self)
+    }
+
+    pub fn num_kernels(&self) -> usize {
+        self.num_kernels
+    }
+
+    pub fn num_inputs(&self) -> usize {
+        self.num_inputs
+    }
+
+    pub fn num_outputs(&self) -> usize {
+        self.num_outputs
+    }
+
+    /// The weight in grams of the transaction
+    pub fn weight(&self) -> u64 {
+        self.weighting.calculate(
+            self.num_kernels,
+            self.num_inputs,
+            self.num_outputs,
+            self.rounded_up_features_and_scripts_byte_size,
+        )
+    }
+
+    /// The fee of the transaction at the given fee per gram
+    pub fn fee(&self, fee_per_gram: MicroMinotari) -> MicroMinotari {
+        MicroMinotari::from(self.weight()) * fee_per_gram
+    }
+
+    /// The weight in grams of the transaction, or `None` if it does not fit in a u64
+    pub fn checked_weight(&self) -> Option<u64> {
+        self.weighting.checked_calculate(
+            self.num_kernels,
+            self.num_inputs,
+            self.num_outputs,
+            self.rounded_up_features_and_scripts_byte_size,
+        )
+    }
+
+    /// The fee of the transaction at the given fee per gram, or `None` if the weight or fee does not fit in a u64
+    pub fn checked_fee(&self, fee_per_gram: MicroMinotari) -> Option<MicroMinotari> {
+        MicroMinotari::from(self.checked_weight()?).checked_mul(fee_per_gram)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::transactions::transaction_components::{OutputType, RangeProofType};
 
     #[test]
     fn round_up_features_and_scripts_size() {
@@ -163,4 +304,58 @@ mod test {
             );
         }
     }
+
+    #[test]
+    fn weight_calculator_matches_calculate() {
+        let weighting = TransactionWeight::latest();
+        let mut calc = WeightCalculator::new(weighting).with_kernels(1).with_inputs(2);
+        calc.add_default_outputs(2).unwrap();
+        let default_size = OutputFeatures::default().get_serialized_size().unwrap() +
+            TariScript::default().get_serialized_size().unwrap() +
+            Covenant::default().get_serialized_size().unwrap();
+        let expected = weighting.calculate(1, 2, 2, weighting.round_up_features_and_scripts_size(default_size) * 2);
+        assert_eq!(calc.weight(), expected);
+        assert_eq!(calc.fee(5.into()), MicroMinotari::from(expected * 5));
+    }
+
+    #[test]
+    fn weight_calculator_rounds_up_each_output() {
+        let weighting = TransactionWeight::latest();
+        let features = OutputFeatures::new_current_version(
+            OutputType::Standard,
+            10,
+            vec![1u8; 17],
+            None,
+            RangeProofType::BulletProofPlus,
+        );
+        let mut calc = WeightCalculator::new(weighting);
+        calc.add_output(&features, &TariScript::default(), &Covenant::default())
+            .unwrap();
+        calc.add_output(&features, &TariScript::default(), &Covenant::default())
+            .unwrap();
+        let size = features.get_serialized_size().unwrap() +
+            TariScript::default().get_serialized_size().unwrap() +
+            Covenant::default().get_serialized_size().unwrap();
+        assert_eq!(calc.num_outputs(), 2);
+        assert_eq!(
+            calc.weight(),
+            weighting.calculate(0, 0, 2, weighting.round_up_features_and_scripts_size(size) * 2)
+        );
+    }
+    #[test]
+    fn weight_calculator_detects_overflow() {
+        let weighting = TransactionWeight::latest();
+        let calc = WeightCalculator::new(weighting).with_kernels(1).with_inputs(2);
+        assert_eq!(calc.checked_weight(), Some(calc.weight()));
+        assert_eq!(calc.checked_fee(5.into()), Some(calc.fee(5.into())));
+        assert_eq!(calc.checked_fee(u64::MAX.into()), None);
+
+        let calc = WeightCalculator::new(weighting).with_kernels(usize::MAX);
+        assert_eq!(calc.checked_weight(), None);
+        assert_eq!(calc.checked_fee(1.into()), None);
+        let calc = WeightCalculator::new(weighting)
+            .with_kernels(usize::MAX / 10)
+            .with_inputs(usize::MAX / 8);
+        assert_eq!(calc.checked_weight(), None);
+    }
 }
@@ -243,10 +243,11 @@ pub fn check_outputs<B: BlockchainBackend>(
     body: &AggregateBody,
 ) -> Result<(), ValidationError> {
     let max_script_size = constants.max_script_byte_size();
-    for output in body.outputs() {
-        check_tari_script_byte_size(&output.script, max_script_size)?;
-        check_not_duplicate_txo(db, output)?;
-        check_validator_node_registration_utxo(constants, output)?;
+    for (index, output) in body.outputs().iter().enumerate() {
+        check_tari_script_byte_size(&output.script, max_script_size)
+            .and_then(|_| check_not_duplicate_txo(db, output))
+            .and_then(|_| check_validator_node_registration_utxo(constants, output))
+            .map_err(|e| e.at_output(index))?;
     }
     Ok(())
 }
@@ -110,12 +110,13 @@ impl AggregateBodyInternalConsistencyValidator {
 
         validate_versions(body, constants)?;
 
-        for output in body.outputs() {
-            check_permitted_output_types(constants, output)?;
-            check_script_size(output, constants.max_script_byte_size())?;
-            check_covenant_length(&output.covenant, constants.max_covenant_length())?;
-            check_permitted_range_proof_types(constants, output)?;
-            check_validator_node_registration_utxo(constants, output)?;
+        for (index, output) in body.outputs().iter().enumerate() {
+            check_permitted_output_types(constants, output)
+                .and_then(|_| check_script_size(output, constants.max_script_byte_size()))
+                .and_then(|_| check_covenant_length(&output.covenant, constants.max_covenant_length()))
+                .and_then(|_| check_permitted_range_proof_types(constants, output))
+                .and_then(|_| check_validator_node_registration_utxo(constants, output))
+                .map_err(|e| e.at_output(index))?;
         }
 
         check_weight(body, height, constants)?;
@@ -147,10 +148,10 @@ impl AggregateBodyInternalConsistencyValidator {
 /// will be added to the public key used in the signature verification.
 fn verify_kernel_signatures(body: &AggregateBody) -> Result<(), ValidationError> {
     trace!(target: LOG_TARGET, "Checking kernel signatures",);
-    for kernel in body.kernels() {
+    for (index, kernel) in body.kernels().iter().enumerate() {
         kernel.verify_signature().map_err(|e| {
             warn!(target: LOG_TARGET, "Kernel ({}) signature failed {:?}.", kernel, e);
-            e
+            ValidationError::from(e).at_kernel(index)
         })?;
     }
     Ok(())
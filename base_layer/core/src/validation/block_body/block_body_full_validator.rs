@@ -57,6 +57,10 @@ impl BlockBodyFullValidator {
         }
     }
 
+    /// Validates the block body against the chain and checks its merkle mountain range roots. The chain linked
+    /// validation runs first, as it resolves the inputs that both of the later stages need. Only the internal
+    /// consistency checks and the root calculation run concurrently. The block is still written to the db by
+    /// `add_block` in a single write transaction after validation.
     pub fn validate<B: BlockchainBackend>(
         &self,
         backend: &B,
@@ -75,12 +79,17 @@ impl BlockBodyFullValidator {
         let body = self.aggregate_body_chain_validator.validate(body, height, backend)?;
         let block = Block::new(block.header.clone(), body);
 
-        // validate the internal consistency of the block body
-        self.block_internal_validator.validate(&block)?;
+        // Validating the internal consistency of the block body (range proofs, signatures) and calculating the merkle
+        // mountain range roots do not depend on each other, so they run concurrently on the rayon thread pool. This
+        // shortens the time that the db write lock is held for when a block is added.
+        let (internal_result, mmr_roots_result) = rayon::join(
+            || self.block_internal_validator.validate(&block),
+            || chain_storage::calculate_mmr_roots(backend, &self.consensus_manager, &block),
+        );
+        internal_result?;
 
         // validate the merkle mountain range roots
-        let mmr_roots = chain_storage::calculate_mmr_roots(backend, &self.consensus_manager, &block)?;
-        check_mmr_roots(&block.header, &mmr_roots)?;
+        check_mmr_roots(&block.header, &mmr_roots_result?)?;
 
         Ok(block)
     }
@@ -336,7 +336,8 @@ async fn it_limits_the_script_byte_size() {
 
     let txn = blockchain.db().db_read_access().unwrap();
     let err = validator.validate_body(&*txn, block.block()).unwrap_err();
-    assert!(matches!(err, ValidationError::TariScriptExceedsMaxSize { .. }));
+    unpack_enum!(ValidationError::InvalidOutput { source, .. } = err);
+    assert!(matches!(*source, ValidationError::TariScriptExceedsMaxSize { .. }));
 }
 
 #[tokio::test]
@@ -497,7 +498,8 @@ mod orphan_validator {
             .create_unmined_block(block_spec!("2", parent: "1", transactions: transactions))
             .await;
         let err = validator.validate(&unmined).unwrap_err();
-        unpack_enum!(ValidationError::OutputTypeNotPermitted { output_type } = err);
+        unpack_enum!(ValidationError::InvalidOutput { source, .. } = err);
+        unpack_enum!(ValidationError::OutputTypeNotPermitted { output_type } = *source);
         assert_eq!(output_type, OutputType::Standard);
     }
 
@@ -525,7 +527,8 @@ mod orphan_validator {
             .create_unmined_block(block_spec!("2", parent: "1", transactions: transactions))
             .await;
         let err = validator.validate(&unmined).unwrap_err();
-        unpack_enum!(ValidationError::RangeProofTypeNotPermitted { range_proof_type } = err);
+        unpack_enum!(ValidationError::InvalidOutput { source, .. } = err);
+        unpack_enum!(ValidationError::RangeProofTypeNotPermitted { range_proof_type } = *source);
         assert_eq!(range_proof_type, RangeProofType::BulletProofPlus);
     }
 }
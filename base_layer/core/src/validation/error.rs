@@ -84,14 +84,16 @@ pub enum ValidationError {
     UnsortedOrDuplicateKernel,
     #[error("Error in merge mine data:{0}")]
     MergeMineError(#[from] MergeMineError),
-    #[error("Maximum transaction weight exceeded")]
-    MaxTransactionWeightExceeded,
+    #[error("The transaction weight ({weight}) is above the maximum ({max_weight})")]
+    MaxTransactionWeightExceeded { weight: u64, max_weight: u64 },
     #[error("Expected block height to be {expected}, but was {block_height}")]
     IncorrectHeight { expected: u64, block_height: u64 },
     #[error("Expected block previous hash to be {expected}, but was {block_hash}")]
     IncorrectPreviousHash { expected: String, block_hash: String },
     #[error("Bad block with hash {hash} found")]
     BadBlockFound { hash: String },
+    #[error("Block with hash {hash} was invalidated by this node")]
+    BlockLocallyInvalidated { hash: String },
     #[error("Script exceeded maximum script size, expected less than {max_script_size} but was {actual_script_size}")]
     TariScriptExceedsMaxSize {
         max_script_size: usize,
@@ -126,6 +128,10 @@ pub enum ValidationError {
     DifficultyError(#[from] DifficultyError),
     #[error("Covenant too large. Max size: {max_size}, Actual size: {actual_size}")]
     CovenantTooLarge { max_size: usize, actual_size: usize },
+    #[error("Output {index} is invalid: {source}")]
+    InvalidOutput { index: usize, source: Box<ValidationError> },
+    #[error("Kernel {index} is invalid: {source}")]
+    InvalidKernel { index: usize, source: Box<ValidationError> },
 }
 
 // ChainStorageError has a ValidationError variant, so to prevent a cyclic dependency we use a string representation in
@@ -137,6 +143,41 @@ impl From<ChainStorageError> for ValidationError {
 }
 
 impl ValidationError {
+    /// Attaches the index of the offending output to this error
+    pub fn at_output(self, index: usize) -> Self {
+        Self::InvalidOutput {
+            index,
+            source: Box::new(self),
+        }
+    }
+
+    /// Attaches the index of the offending kernel to this error
+    pub fn at_kernel(self, index: usize) -> Self {
+        Self::InvalidKernel {
+            index,
+            source: Box::new(self),
+        }
+    }
+
+    /// Returns the underlying error, looking through the output or kernel index attached to it
+    pub fn root_cause(&self) -> &ValidationError {
+        match self {
+            ValidationError::InvalidOutput { source, .. } | ValidationError::InvalidKernel { source, .. } => {
+                source.root_cause()
+            },
+            err => err,
+        }
+    }
+
+    /// Returns true if validation failed because of a local failure rather than the data being validated, in which
+    /// case the data must not be marked as bad
+    pub fn is_internal_error(&self) -> bool {
+        matches!(
+            self.root_cause(),
+            ValidationError::FatalStorageError(_) | ValidationError::IncorrectNumberOfTimestampsProvided { .. }
+        )
+    }
+
     pub fn get_ban_reason(&self, long_ban_duration: Option<Duration>) -> Option<BanReason> {
         match self {
             err @ ValidationError::SerializationError(_) |
@@ -158,7 +199,7 @@ impl ValidationError {
             err @ ValidationError::UnsortedOrDuplicateOutput |
             err @ ValidationError::UnsortedOrDuplicateKernel |
             err @ ValidationError::MergeMineError(_) |
-            err @ ValidationError::MaxTransactionWeightExceeded |
+            err @ ValidationError::MaxTransactionWeightExceeded { .. } |
             err @ ValidationError::IncorrectHeight { .. } |
             err @ ValidationError::IncorrectPreviousHash { .. } |
             err @ ValidationError::BadBlockFound { .. } |
@@ -179,7 +220,15 @@ impl ValidationError {
                 reason: format!("{}", err),
                 ban_duration: long_ban_duration.unwrap_or_else(|| Duration::from_secs(2 * 60 * 60)),
             }),
-            ValidationError::FatalStorageError(_) | ValidationError::IncorrectNumberOfTimestampsProvided { .. } => None,
+            ValidationError::InvalidOutput { source, .. } | ValidationError::InvalidKernel { source, .. } => {
+                source.get_ban_reason(long_ban_duration).map(|ban| BanReason {
+                    reason: self.to_string(),
+                    ..ban
+                })
+            },
+            ValidationError::FatalStorageError(_) |
+            ValidationError::IncorrectNumberOfTimestampsProvided { .. } |
+            ValidationError::BlockLocallyInvalidated { .. } => None,
         }
     }
 }
@@ -29,8 +29,14 @@ use tari_utilities::{epoch_time::EpochTime, hex::Hex};
 use crate::{
     blocks::{BlockHeader, BlockHeaderValidationError},
     chain_storage::BlockchainBackend,
-    consensus::{ConsensusConstants, ConsensusManager},
-    proof_of_work::{monero_rx::MoneroPowData, AchievedTargetDifficulty, Difficulty, PowAlgorithm, PowError},
+    consensus::{deployments, ConsensusConstants, ConsensusManager},
+    proof_of_work::{
+        monero_rx::{verify_pow_data_limits, MoneroPowData},
+        AchievedTargetDifficulty,
+        Difficulty,
+        PowAlgorithm,
+        PowError,
+    },
     validation::{
         helpers::{check_header_timestamp_greater_than_median, check_target_difficulty},
         DifficultyCalculator,
@@ -68,7 +74,7 @@ impl<B: BlockchainBackend> HeaderChainLinkedValidator<B> for HeaderFullValidator
         let constants = self.rules.consensus_constants(header.height);
 
         check_not_bad_block(db, header.hash())?;
-        check_blockchain_version(constants, header.version)?;
+        check_blockchain_version(constants, header.height, header.version)?;
         check_height(header, prev_header)?;
 
         sanity_check_timestamp_count(header, prev_timestamps, constants)?;
@@ -140,8 +146,16 @@ fn check_prev_hash(header: &BlockHeader, prev_header: &BlockHeader) -> Result<()
     Ok(())
 }
 
-fn check_blockchain_version(constants: &ConsensusConstants, version: u16) -> Result<(), ValidationError> {
-    if constants.valid_blockchain_version_range().contains(&version) {
+/// Checks the blockchain version of the header. Once deployment signalling is active only the blockchain version part
+/// of the header version is checked; the remaining bits are deployment signals, which may be set for any deployment,
+/// including ones this node does not know of.
+fn check_blockchain_version(constants: &ConsensusConstants, height: u64, version: u16) -> Result<(), ValidationError> {
+    let blockchain_version = if constants.is_deployment_signalling_active(height) {
+        deployments::blockchain_version(version)
+    } else {
+        version
+    };
+    if constants.valid_blockchain_version_range().contains(&blockchain_version) {
         Ok(())
     } else {
         Err(ValidationError::InvalidBlockchainVersion { version })
@@ -187,12 +201,17 @@ fn check_pow_data<B: BlockchainBackend>(
                     BlockHeaderValidationError::InvalidNonce,
                 ));
             }
-            let monero_data = MoneroPowData::from_header(block_header)?;
+            let constants = rules.consensus_constants(block_header.height);
+            let monero_data = if constants.is_monero_pow_data_rules_active(block_header.height) {
+                verify_pow_data_limits(block_header, constants.max_monero_pow_data_size())?
+            } else {
+                MoneroPowData::from_header(block_header)?
+            };
             let seed_height = db.fetch_monero_seed_first_seen_height(&monero_data.randomx_key)?;
             if seed_height != 0 {
                 // Saturating sub: subtraction can underflow in reorgs / rewind-blockchain command
                 let seed_used_height = block_header.height.saturating_sub(seed_height);
-                if seed_used_height > rules.consensus_constants(block_header.height).max_randomx_seed_height() {
+                if seed_used_height > constants.max_randomx_seed_height() {
                     return Err(ValidationError::BlockHeaderError(
                         BlockHeaderValidationError::OldSeedHash,
                     ));
@@ -31,7 +31,7 @@ use crate::{
     consensus::ConsensusConstants,
     covenants::Covenant,
     proof_of_work::{
-        randomx_difficulty,
+        aux_pow::{AuxChainAdapter, MoneroAdapter},
         randomx_factory::RandomXFactory,
         sha3x_difficulty,
         AchievedTargetDifficulty,
@@ -112,7 +112,7 @@ pub fn check_target_difficulty(
     randomx_factory: &RandomXFactory,
 ) -> Result<AchievedTargetDifficulty, ValidationError> {
     let achieved = match block_header.pow_algo() {
-        PowAlgorithm::RandomX => randomx_difficulty(block_header, randomx_factory)?,
+        PowAlgorithm::RandomX => MoneroAdapter.achieved_difficulty(block_header, randomx_factory)?,
         PowAlgorithm::Sha3x => sha3x_difficulty(block_header)?,
     };
 
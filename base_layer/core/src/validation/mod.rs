@@ -30,6 +30,9 @@
 mod error;
 pub use error::ValidationError;
 
+mod rejection;
+pub use rejection::{RejectionLocation, ValidationRejection};
+
 pub(crate) mod helpers;
 
 mod traits;
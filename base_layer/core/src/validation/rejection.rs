@@ -0,0 +1,221 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::fmt::{Display, Formatter};
+
+use crate::validation::ValidationError;
+
+/// The part of a block or transaction body that violated a validation rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RejectionLocation {
+    Output(usize),
+    Kernel(usize),
+}
+
+impl Display for RejectionLocation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RejectionLocation::Output(index) => write!(f, "output {}", index),
+            RejectionLocation::Kernel(index) => write!(f, "kernel {}", index),
+        }
+    }
+}
+
+/// A machine-readable description of why a block or transaction failed validation, so that clients can act on a
+/// rejection without parsing the error message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationRejection {
+    /// A stable identifier of the validation rule that was violated, e.g. `output_type_not_permitted`
+    pub rule: &'static str,
+    /// The offending output or kernel, if the rule applies to a single one
+    pub location: Option<RejectionLocation>,
+    /// The name of the consensus constant that the body was checked against, if any
+    pub consensus_constant: Option<&'static str>,
+    /// The limit set by the consensus constant, if it is a number
+    pub limit: Option<u64>,
+    /// The value that violated the limit
+    pub actual: Option<u64>,
+    /// The human readable error
+    pub message: String,
+}
+
+impl ValidationRejection {
+    fn new(rule: &'static str, err: &ValidationError) -> Self {
+        Self {
+            rule,
+            location: None,
+            consensus_constant: None,
+            limit: None,
+            actual: None,
+            message: err.to_string(),
+        }
+    }
+
+    fn with_limit(mut self, consensus_constant: &'static str, limit: u64, actual: u64) -> Self {
+        self.consensus_constant = Some(consensus_constant);
+        self.limit = Some(limit);
+        self.actual = Some(actual);
+        self
+    }
+
+    fn with_constant(mut self, consensus_constant: &'static str) -> Self {
+        self.consensus_constant = Some(consensus_constant);
+        self
+    }
+}
+
+impl From<&ValidationError> for ValidationRejection {
+    #[allow(clippy::too_many_lines)]
+    fn from(err: &ValidationError) -> Self {
+        use ValidationError::*;
+        match err {
+            InvalidOutput { index, source } | InvalidKernel { index, source } => {
+                // Keep the rule of the underlying error, but report the message with the location
+                let mut rejection = Self::from(source.as_ref());
+                rejection.location = Some(if matches!(err, InvalidOutput { .. }) {
+                    RejectionLocation::Output(*index)
+                } else {
+                    RejectionLocation::Kernel(*index)
+                });
+                rejection.message = err.to_string();
+                rejection
+            },
+            BlockTooLarge {
+                actual_weight,
+                max_weight,
+            } => Self::new("block_too_large", err).with_limit(
+                "max_block_transaction_weight",
+                *max_weight,
+                *actual_weight,
+            ),
+            MaxTransactionWeightExceeded { weight, max_weight } => Self::new("max_transaction_weight_exceeded", err)
+                .with_limit("max_block_transaction_weight", *max_weight, *weight),
+            TariScriptExceedsMaxSize {
+                max_script_size,
+                actual_script_size,
+            } => Self::new("script_exceeds_max_size", err).with_limit(
+                "max_script_byte_size",
+                *max_script_size as u64,
+                *actual_script_size as u64,
+            ),
+            CovenantTooLarge { max_size, actual_size } => Self::new("covenant_too_large", err).with_limit(
+                "max_covenant_length",
+                *max_size as u64,
+                *actual_size as u64,
+            ),
+            OutputTypeNotPermitted { .. } => {
+                Self::new("output_type_not_permitted", err).with_constant("permitted_output_types")
+            },
+            RangeProofTypeNotPermitted { .. } => {
+                Self::new("range_proof_type_not_permitted", err).with_constant("permitted_range_proof_types")
+            },
+            ValidatorNodeRegistrationMinDepositAmount { min, actual } => {
+                Self::new("validator_node_registration_min_deposit_amount", err).with_limit(
+                    "validator_node_registration_min_deposit_amount",
+                    min.as_u64(),
+                    actual.as_u64(),
+                )
+            },
+            ValidatorNodeRegistrationMinLockHeight { min, actual } => Self::new(
+                "validator_node_registration_min_lock_height",
+                err,
+            )
+            .with_limit("validator_node_registration_min_lock_height", *min, *actual),
+            InvalidBlockchainVersion { .. } => {
+                Self::new("invalid_blockchain_version", err).with_constant("blockchain_version")
+            },
+            ConsensusError(_) => Self::new("consensus_error", err),
+            SerializationError(_) => Self::new("serialization_error", err),
+            BlockHeaderError(_) => Self::new("invalid_block_header", err),
+            BlockError(_) => Self::new("invalid_block", err),
+            MaturityError => Self::new("immature_input_or_kernel", err),
+            UnknownInputs(_) | UnknownInput => Self::new("unknown_input", err),
+            TransactionError(_) => Self::new("invalid_transaction", err),
+            FatalStorageError(_) => Self::new("storage_error", err),
+            InvalidAccountingBalance => Self::new("invalid_accounting_balance", err),
+            ContainsSTxO => Self::new("input_already_spent", err),
+            ContainsTxO => Self::new("output_already_exists", err),
+            ContainsDuplicateUtxoCommitment => Self::new("duplicate_output_commitment", err),
+            ChainBalanceValidationFailed(_) => Self::new("chain_balance_validation_failed", err),
+            CoinbaseExceedsMaxLimit => Self::new("coinbase_exceeds_max_limit", err),
+            ProofOfWorkError(_) => Self::new("invalid_proof_of_work", err),
+            ValidatingGenesis => Self::new("validating_genesis", err),
+            UnsortedOrDuplicateInput => Self::new("unsorted_or_duplicate_input", err),
+            UnsortedOrDuplicateOutput => Self::new("unsorted_or_duplicate_output", err),
+            UnsortedOrDuplicateKernel => Self::new("unsorted_or_duplicate_kernel", err),
+            MergeMineError(_) => Self::new("invalid_merge_mining_data", err),
+            IncorrectHeight { .. } => Self::new("incorrect_height", err),
+            IncorrectPreviousHash { .. } => Self::new("incorrect_previous_hash", err),
+            BadBlockFound { .. } => Self::new("bad_block", err),
+            BlockLocallyInvalidated { .. } => Self::new("block_locally_invalidated", err),
+            DuplicateKernelError(_) => Self::new("duplicate_kernel", err),
+            CovenantError(_) => Self::new("covenant_failed", err),
+            InvalidBurnError(_) => Self::new("invalid_burn", err),
+            InvalidValidatorNodeSignature => Self::new("invalid_validator_node_signature", err),
+            IncorrectNumberOfTimestampsProvided { .. } => Self::new("incorrect_number_of_timestamps", err),
+            DifficultyError(_) => Self::new("invalid_difficulty", err),
+        }
+    }
+}
+
+impl From<ValidationError> for ValidationRejection {
+    fn from(err: ValidationError) -> Self {
+        Self::from(&err)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::transactions::transaction_components::OutputType;
+
+    #[test]
+    fn it_reports_the_consensus_limit() {
+        let rejection = ValidationRejection::from(ValidationError::CovenantTooLarge {
+            max_size: 128,
+            actual_size: 200,
+        });
+        assert_eq!(rejection.rule, "covenant_too_large");
+        assert_eq!(rejection.location, None);
+        assert_eq!(rejection.consensus_constant, Some("max_covenant_length"));
+        assert_eq!(rejection.limit, Some(128));
+        assert_eq!(rejection.actual, Some(200));
+    }
+
+    #[test]
+    fn it_reports_the_location_of_the_underlying_rule() {
+        let err = ValidationError::OutputTypeNotPermitted {
+            output_type: OutputType::Burn,
+        }
+        .at_output(3);
+        let rejection = ValidationRejection::from(&err);
+        assert_eq!(rejection.rule, "output_type_not_permitted");
+        assert_eq!(rejection.location, Some(RejectionLocation::Output(3)));
+        assert_eq!(rejection.consensus_constant, Some("permitted_output_types"));
+        assert_eq!(rejection.message, err.to_string());
+
+        let rejection = ValidationRejection::from(ValidationError::MaturityError.at_kernel(1));
+        assert_eq!(rejection.rule, "immature_input_or_kernel");
+        assert_eq!(rejection.location, Some(RejectionLocation::Kernel(1)));
+        assert_eq!(rejection.consensus_constant, None);
+    }
+}
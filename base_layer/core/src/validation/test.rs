@@ -128,6 +128,37 @@ mod header_validators {
         }));
     }
 
+    #[test]
+    fn it_only_ignores_signal_bits_once_deployment_signalling_is_active() {
+        let validate_signalling_header = |signalling_height: u64| {
+            let constants = ConsensusConstantsBuilder::new(Network::LocalNet)
+                .with_deployment_signalling_height(signalling_height)
+                .build();
+            let consensus_manager = ConsensusManagerBuilder::new(Network::LocalNet)
+                .add_consensus_constants(constants)
+                .build()
+                .unwrap();
+            let db = create_store_with_consensus(consensus_manager.clone());
+            let genesis = db.fetch_chain_header(0).unwrap();
+            let mut header = BlockHeader::from_previous(genesis.header());
+            header.version |= 0x0100;
+            let difficulty_calculator = DifficultyCalculator::new(consensus_manager.clone(), Default::default());
+            let validator = HeaderFullValidator::new(consensus_manager, difficulty_calculator);
+            validator
+                .validate(&*db.db_read_access().unwrap(), &header, genesis.header(), &[], None)
+                .unwrap_err()
+        };
+
+        assert!(matches!(
+            validate_signalling_header(u64::MAX),
+            ValidationError::InvalidBlockchainVersion { .. }
+        ));
+        assert!(!matches!(
+            validate_signalling_header(0),
+            ValidationError::InvalidBlockchainVersion { .. }
+        ));
+    }
+
     #[tokio::test]
     async fn it_does_a_sanity_check_on_the_number_of_timestamps_provided() {
         let consensus_manager = ConsensusManagerBuilder::new(Network::LocalNet).build().unwrap();
@@ -513,6 +544,23 @@ async fn chain_balance_validation_burned() {
         .unwrap();
 }
 
+mod located_errors {
+    use super::*;
+
+    #[test]
+    fn it_bans_only_for_the_underlying_error() {
+        let err = ValidationError::FatalStorageError("disk full".to_string()).at_output(2);
+        assert!(err.is_internal_error());
+        assert!(err.get_ban_reason(None).is_none());
+
+        let err = ValidationError::ContainsSTxO.at_output(0).at_kernel(1);
+        assert!(matches!(err.root_cause(), ValidationError::ContainsSTxO));
+        assert!(!err.is_internal_error());
+        let ban = err.get_ban_reason(None).unwrap();
+        assert_eq!(ban.reason, err.to_string());
+    }
+}
+
 mod transaction_validator {
     use super::*;
     use crate::{
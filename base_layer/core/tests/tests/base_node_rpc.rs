@@ -25,6 +25,7 @@ use std::{convert::TryFrom, sync::Arc, time::Duration};
 use futures::StreamExt;
 use randomx_rs::RandomXFlag;
 use tari_common::configuration::Network;
+use tari_common_types::types::Commitment;
 use tari_comms::protocol::rpc::mock::RpcRequestMock;
 use tari_core::{
     base_node::{
@@ -35,6 +36,7 @@ use tari_core::{
             TxQueryResponse,
             TxSubmissionRejectionReason,
             TxSubmissionResponse,
+            UtxoStatus,
         },
         rpc::{BaseNodeWalletRpcService, BaseNodeWalletService},
         state_machine_service::states::{ListeningInfo, StateInfo, StatusInfo},
@@ -43,7 +45,12 @@ use tari_core::{
     blocks::ChainBlock,
     consensus::{ConsensusConstantsBuilder, ConsensusManager, ConsensusManagerBuilder, NetworkConsensus},
     proto::{
-        base_node::{FetchMatchingUtxos, Signatures as SignaturesProto, SyncUtxosByBlockRequest},
+        base_node::{
+            FetchMatchingUtxos,
+            QueryUtxoStatusesRequest,
+            Signatures as SignaturesProto,
+            SyncUtxosByBlockRequest,
+        },
         types::{Signature as SignatureProto, Transaction as TransactionProto},
     },
     test_helpers::blockchain::TempDatabase,
@@ -56,7 +63,7 @@ use tari_core::{
 };
 use tari_service_framework::reply_channel;
 use tari_test_utils::streams::convert_mpsc_to_stream;
-use tari_utilities::epoch_time::EpochTime;
+use tari_utilities::{epoch_time::EpochTime, ByteArray};
 use tempfile::{tempdir, TempDir};
 use tokio::sync::broadcast;
 
@@ -112,7 +119,12 @@ async fn setup() -> (
     let (block_tx, _) = reply_channel::unbounded();
     let (block_event_tx, _) = broadcast::channel(1);
     let local_nci = LocalNodeCommsInterface::new(req_tx, block_tx, block_event_tx);
-    let base_node_service = BaseNodeSyncRpcService::new(base_node.blockchain_db.clone().into(), local_nci);
+    let base_node_service = BaseNodeSyncRpcService::new(
+        base_node.blockchain_db.clone().into(),
+        local_nci,
+        Default::default(),
+        base_node.comms.connectivity(),
+    );
     (
         wallet_service,
         base_node_service,
@@ -349,6 +361,70 @@ async fn test_get_height_at_time() {
     let resp = service.get_height_at_time(req).await.unwrap().into_message();
     assert_eq!(resp, 10);
 }
+
+#[tokio::test]
+#[allow(clippy::identity_op)]
+async fn test_query_utxo_statuses() {
+    let (service, _, mut base_node, request_mock, consensus_manager, block0, utxo0, _temp_dir, key_manager) =
+        setup().await;
+
+    let req = request_mock.request_with_context(Default::default(), QueryUtxoStatusesRequest::default());
+    assert!(service.query_utxo_statuses(req).await.is_err());
+
+    let genesis_output = block0.block().body.outputs()[0].clone();
+    let unknown_commitment = Commitment::default();
+    let msg = QueryUtxoStatusesRequest {
+        commitments: vec![unknown_commitment.to_vec(), genesis_output.commitment.to_vec()],
+    };
+    let req = request_mock.request_with_context(Default::default(), msg);
+    let resp = service.query_utxo_statuses(req).await.unwrap().into_message();
+
+    assert_eq!(resp.height_of_longest_chain, 0);
+    assert_eq!(resp.best_block, block0.hash().to_vec());
+    assert_eq!(resp.statuses.len(), 2);
+    assert_eq!(resp.statuses[0].commitment, unknown_commitment.to_vec());
+    assert_eq!(resp.statuses[0].status, i32::from(UtxoStatus::NotFound));
+    assert_eq!(resp.statuses[1].commitment, genesis_output.commitment.to_vec());
+    assert_eq!(resp.statuses[1].status, i32::from(UtxoStatus::Unspent));
+    assert_eq!(resp.statuses[1].output_hash, genesis_output.hash().to_vec());
+    assert_eq!(resp.statuses[1].mined_height, 0);
+    assert_eq!(resp.statuses[1].mined_in_block, block0.hash().to_vec());
+
+    // Spent outputs are still found by their commitment
+    let (txs1, utxos1) =
+        schema_to_transaction(&[txn_schema!(from: vec![utxo0.clone()], to: vec![1 * T])], &key_manager).await;
+    let block1 = base_node
+        .blockchain_db
+        .prepare_new_block(
+            chain_block(
+                block0.block(),
+                vec![(*txs1[0]).clone()],
+                &consensus_manager,
+                &key_manager,
+            )
+            .await,
+        )
+        .unwrap();
+    base_node.local_nci.submit_block(block1.clone()).await.unwrap();
+
+    let spent_output = utxo0.to_transaction_output(&key_manager).await.unwrap();
+    let new_output = utxos1[0].to_transaction_output(&key_manager).await.unwrap();
+    let msg = QueryUtxoStatusesRequest {
+        commitments: vec![spent_output.commitment.to_vec(), new_output.commitment.to_vec()],
+    };
+    let req = request_mock.request_with_context(Default::default(), msg);
+    let resp = service.query_utxo_statuses(req).await.unwrap().into_message();
+
+    assert_eq!(resp.height_of_longest_chain, 1);
+    assert_eq!(resp.statuses[0].status, i32::from(UtxoStatus::Spent));
+    assert_eq!(resp.statuses[0].output_hash, spent_output.hash().to_vec());
+    assert_eq!(resp.statuses[0].mined_in_block, block0.hash().to_vec());
+    assert_eq!(resp.statuses[1].status, i32::from(UtxoStatus::Unspent));
+    assert_eq!(resp.statuses[1].output_hash, new_output.hash().to_vec());
+    assert_eq!(resp.statuses[1].mined_height, 1);
+    assert_eq!(resp.statuses[1].mined_in_block, block1.hash().to_vec());
+}
+
 #[tokio::test]
 async fn test_sync_utxos_by_block() {
     let (service, _, mut base_node, request_mock, consensus_manager, block0, utxo0, _temp_dir, key_manager) =
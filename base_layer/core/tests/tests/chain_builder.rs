@@ -0,0 +1,84 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use tari_core::{mempool::TxStorageResponse, test_helpers::chain_builder::ChainBuilder};
+use tari_key_manager::key_manager_service::KeyManagerInterface;
+
+#[tokio::test]
+async fn it_builds_a_chain_of_the_requested_length() {
+    let chain = ChainBuilder::new().with_blocks(5).build().await;
+    assert_eq!(chain.tip().height(), 5);
+    assert_eq!(chain.main_chain().len(), 5);
+    assert!(chain.reorged_blocks().is_empty());
+    assert_eq!(chain.db().fetch_tip_header().unwrap().hash(), chain.tip().hash());
+    assert_eq!(chain.block_at_height(0).unwrap().hash(), chain.genesis().hash());
+}
+
+#[tokio::test]
+async fn it_reorgs_at_the_requested_height() {
+    let chain = ChainBuilder::new().with_blocks(6).with_reorg_at(4).build().await;
+    assert_eq!(chain.tip().height(), 6);
+    assert_eq!(chain.db().fetch_tip_header().unwrap().hash(), chain.tip().hash());
+
+    let reorged = chain.reorged_blocks();
+    assert_eq!(reorged.len(), 3);
+    assert_eq!(reorged[0].0.height(), 4);
+    // Blocks below the reorg height are shared with the original chain
+    assert_eq!(
+        reorged[0].0.header().prev_hash,
+        *chain.block_at_height(3).unwrap().hash()
+    );
+    for (block, _) in reorged {
+        assert_ne!(chain.block_at_height(block.height()).unwrap().hash(), block.hash());
+    }
+}
+
+#[tokio::test]
+async fn it_derives_the_same_coinbase_keys_every_time() {
+    let first = ChainBuilder::new().with_blocks(3).build().await;
+    let second = ChainBuilder::new().with_blocks(3).build().await;
+    for ((_, a), (_, b)) in first.main_chain().iter().zip(second.main_chain()) {
+        let key_a = first
+            .key_manager()
+            .get_public_key_at_key_id(&a.spending_key_id)
+            .await
+            .unwrap();
+        let key_b = second
+            .key_manager()
+            .get_public_key_at_key_id(&b.spending_key_id)
+            .await
+            .unwrap();
+        assert_eq!(key_a, key_b);
+        assert_eq!(a.value, b.value);
+    }
+}
+
+#[tokio::test]
+async fn it_creates_mempool_fixtures_that_the_mempool_accepts() {
+    let chain = ChainBuilder::new().with_blocks(8).build().await;
+    let mempool = chain.create_mempool();
+    let transactions = chain.create_mempool_transactions(3).await;
+    assert_eq!(transactions.len(), 3);
+    for tx in transactions {
+        assert_eq!(mempool.insert(tx).await.unwrap(), TxStorageResponse::UnconfirmedPool);
+    }
+}
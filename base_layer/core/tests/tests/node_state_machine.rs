@@ -93,6 +93,7 @@ async fn test_listening_lagging() {
         alice_node.local_nci.clone(),
         alice_node.comms.connectivity(),
         alice_node.comms.peer_manager(),
+        alice_node.liveness_handle.clone(),
         alice_node.chain_metadata_handle.get_event_stream(),
         BaseNodeStateMachineConfig::default(),
         SyncValidators::new(MockValidator::new(true), MockValidator::new(true)),
@@ -154,6 +155,7 @@ async fn test_event_channel() {
         node.local_nci.clone(),
         node.comms.connectivity(),
         node.comms.peer_manager(),
+        node.liveness_handle.clone(),
         mock.subscription(),
         BaseNodeStateMachineConfig::default(),
         SyncValidators::new(MockValidator::new(true), MockValidator::new(true)),
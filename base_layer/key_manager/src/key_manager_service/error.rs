@@ -92,6 +92,8 @@ pub enum KeyManagerStorageError {
     ByteArrayError(String),
     #[error("Aead error: `{0}`")]
     AeadError(String),
+    #[error("The wallet database is locked")]
+    DatabaseLocked,
     #[error("Binary not stored as valid hex:{0}")]
     HexError(String),
     #[error("Tari Key Manager error: `{0}`")]
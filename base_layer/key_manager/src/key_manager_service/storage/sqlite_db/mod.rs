@@ -20,19 +20,15 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{
-    convert::TryFrom,
-    sync::{Arc, RwLock},
-};
+use std::{convert::TryFrom, sync::Arc};
 
 use chacha20poly1305::XChaCha20Poly1305;
 use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
 pub use key_manager_state::{KeyManagerStateSql, NewKeyManagerStateSql};
 use log::*;
 use tari_common_sqlite::{error::SqliteStorageError, sqlite_connection_pool::PooledDbConnection};
-use tari_common_types::encryption::Encryptable;
+use tari_common_types::encryption::{Encryptable, SharedCipher};
 use tari_crypto::keys::PublicKey;
-use tari_utilities::acquire_read_lock;
 use tokio::time::Instant;
 
 use crate::key_manager_service::{
@@ -53,7 +49,7 @@ const LOG_TARGET: &str = "wallet::key_manager_service::database::wallet";
 #[derive(Clone)]
 pub struct KeyManagerSqliteDatabase<TKeyManagerDbConnection> {
     database_connection: Arc<TKeyManagerDbConnection>,
-    cipher: Arc<RwLock<XChaCha20Poly1305>>,
+    cipher: SharedCipher,
 }
 
 impl<TKeyManagerDbConnection: PooledDbConnection<Error = SqliteStorageError> + Clone>
@@ -61,20 +57,26 @@ impl<TKeyManagerDbConnection: PooledDbConnection<Error = SqliteStorageError> + C
 {
     /// Creates a new sql backend from provided wallet db connection
     /// * `cipher` is used to encrypt the sensitive fields in the database, a cipher is derived
-    /// from a provided password, which we enforce for class instantiation
-    fn new(database_connection: TKeyManagerDbConnection, cipher: XChaCha20Poly1305) -> Self {
+    /// from a provided password, which we enforce for class instantiation. It is shared with the wallet database so
+    /// that the key manager is unavailable while the wallet is locked
+    fn new(database_connection: TKeyManagerDbConnection, cipher: SharedCipher) -> Self {
         Self {
             database_connection: Arc::new(database_connection),
-            cipher: Arc::new(RwLock::new(cipher)),
+            cipher,
         }
     }
 
-    pub fn init(database_connection: TKeyManagerDbConnection, cipher: XChaCha20Poly1305) -> Self {
-        let db = Self::new(database_connection, cipher);
+    pub fn init<C: Into<SharedCipher>>(database_connection: TKeyManagerDbConnection, cipher: C) -> Self {
+        let db = Self::new(database_connection, cipher.into());
         db.run_migrations().expect("Migrations to run");
         db
     }
 
+    /// Returns the database cipher, or an error if the wallet database is locked
+    fn unlocked_cipher(&self) -> Result<XChaCha20Poly1305, KeyManagerStorageError> {
+        self.cipher.get().ok_or(KeyManagerStorageError::DatabaseLocked)
+    }
+
     fn run_migrations(&self) -> Result<Vec<String>, SqliteStorageError> {
         let mut conn = self.database_connection.get_pooled_connection()?;
         conn.run_pending_migrations(MIGRATIONS)
@@ -106,7 +108,7 @@ where
         let result = match KeyManagerStateSql::get_state(branch, &mut conn).ok() {
             None => None,
             Some(km) => {
-                let cipher = acquire_read_lock!(self.cipher);
+                let cipher = self.unlocked_cipher()?;
                 let km = km
                     .decrypt(&cipher)
                     .map_err(|e| KeyManagerStorageError::AeadError(format!("Decryption Error: {}", e)))?;
@@ -130,7 +132,7 @@ where
         let start = Instant::now();
         let mut conn = self.database_connection.get_pooled_connection()?;
         let acquire_lock = start.elapsed();
-        let cipher = acquire_read_lock!(self.cipher);
+        let cipher = self.unlocked_cipher()?;
 
         let km_sql = NewKeyManagerStateSql::from(key_manager);
         let km_sql = km_sql
@@ -154,7 +156,7 @@ where
         let start = Instant::now();
         let mut conn = self.database_connection.get_pooled_connection()?;
         let acquire_lock = start.elapsed();
-        let cipher = acquire_read_lock!(self.cipher);
+        let cipher = self.unlocked_cipher()?;
         let km = KeyManagerStateSql::get_state(branch, &mut conn)?;
         let mut km = km
             .decrypt(&cipher)
@@ -184,7 +186,7 @@ where
         let start = Instant::now();
         let mut conn = self.database_connection.get_pooled_connection()?;
         let acquire_lock = start.elapsed();
-        let cipher = acquire_read_lock!(self.cipher);
+        let cipher = self.unlocked_cipher()?;
         let km = KeyManagerStateSql::get_state(branch, &mut conn)?;
         let mut km = km
             .decrypt(&cipher)
@@ -216,7 +218,7 @@ where
             return Ok(());
         }
         let acquire_lock = start.elapsed();
-        let cipher = acquire_read_lock!(self.cipher);
+        let cipher = self.unlocked_cipher()?;
         let key = ImportedKey {
             public_key,
             private_key,
@@ -240,7 +242,7 @@ where
         let start = Instant::now();
         let mut conn = self.database_connection.get_pooled_connection()?;
         let acquire_lock = start.elapsed();
-        let cipher = acquire_read_lock!(self.cipher);
+        let cipher = self.unlocked_cipher()?;
         let key = ImportedKeySql::get_key(public_key, &mut conn)?;
         let unencrypted_key = key.to_imported_key::<PK>(&cipher)?;
         if start.elapsed().as_millis() > 0 {
@@ -261,6 +261,7 @@ mod test {
                         "https://raw.githubusercontent.com/tari-project/tari/development/meta/hashes.txt.sig"
                             .to_string(),
                     check_interval: Some(Duration::from_secs(30)),
+                    ..Default::default()
                 }
             }
         }
@@ -30,4 +30,16 @@ pub enum AutoUpdateError {
     DownloadError(#[from] reqwest::Error),
     #[error("Failed to verify signature: {0}")]
     SignatureError(#[from] pgp::errors::Error),
+    #[error("Invalid release manifest: {0}")]
+    InvalidManifest(String),
+    #[error("Invalid maintainer public key: {0}")]
+    InvalidMaintainerKey(String),
+    #[error("Release manifest has {valid} valid maintainer signature(s) but {required} are required")]
+    InsufficientSignatures { valid: usize, required: usize },
+    #[error("Downloaded binary from {url} does not match the expected hash {expected}")]
+    HashMismatch { expected: String, url: String },
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("Software updater service is not running")]
+    ServiceUnavailable,
 }
@@ -0,0 +1,288 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{collections::HashSet, convert::TryInto, str::FromStr};
+
+use ed25519_dalek::{Signature, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use tari_common::configuration::bootstrap::ApplicationType;
+use tari_utilities::hex::from_hex;
+
+use super::{dns::UpdateSpec, error::AutoUpdateError, SoftwareUpdate, Version};
+
+const LOG_TARGET: &str = "p2p::auto_update::manifest";
+
+/// A release manifest together with the maintainer signatures over it. The manifest is kept as the exact JSON string
+/// that was signed so that verification does not depend on how it is re-serialized.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedReleaseManifest {
+    pub manifest: String,
+    pub signatures: Vec<ManifestSignature>,
+}
+
+/// A hex-encoded Ed25519 signature and the hex-encoded public key of the maintainer that produced it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestSignature {
+    pub public_key: String,
+    pub signature: String,
+}
+
+/// The list of released binaries for a single version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseManifest {
+    pub version: String,
+    pub binaries: Vec<ReleaseBinary>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseBinary {
+    pub application: String,
+    pub arch: String,
+    pub url: String,
+    /// Hex-encoded SHA-256 hash of the binary
+    pub sha256: String,
+}
+
+impl SignedReleaseManifest {
+    pub fn from_json(s: &str) -> Result<Self, AutoUpdateError> {
+        serde_json::from_str(s).map_err(|e| AutoUpdateError::InvalidManifest(e.to_string()))
+    }
+}
+
+/// Verifies that a release manifest has been signed by at least `threshold` distinct maintainers
+pub struct ManifestVerifier {
+    maintainers: Vec<VerifyingKey>,
+    threshold: usize,
+}
+
+impl ManifestVerifier {
+    pub fn new(maintainers: Vec<VerifyingKey>, threshold: usize) -> Self {
+        Self {
+            maintainers,
+            // A manifest must always be signed by at least one maintainer
+            threshold: threshold.max(1),
+        }
+    }
+
+    /// Creates a verifier from hex-encoded maintainer public keys
+    pub fn from_hex_keys<I, S>(keys: I, threshold: usize) -> Result<Self, AutoUpdateError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let maintainers = keys
+            .into_iter()
+            .map(|key| {
+                parse_public_key(key.as_ref())
+                    .ok_or_else(|| AutoUpdateError::InvalidMaintainerKey(key.as_ref().to_string()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::new(maintainers, threshold))
+    }
+
+    /// Checks the signatures on the manifest and returns the parsed manifest if the signature threshold is met
+    pub fn verify(&self, signed: &SignedReleaseManifest) -> Result<ReleaseManifest, AutoUpdateError> {
+        let num_valid = self.count_valid_signatures(signed);
+        if num_valid < self.threshold {
+            return Err(AutoUpdateError::InsufficientSignatures {
+                valid: num_valid,
+                required: self.threshold,
+            });
+        }
+
+        serde_json::from_str(&signed.manifest).map_err(|e| AutoUpdateError::InvalidManifest(e.to_string()))
+    }
+
+    fn count_valid_signatures(&self, signed: &SignedReleaseManifest) -> usize {
+        let mut signers = HashSet::new();
+        for sig in &signed.signatures {
+            let public_key = match parse_public_key(&sig.public_key) {
+                Some(pk) => pk,
+                None => {
+                    log::debug!(target: LOG_TARGET, "Ignoring malformed public key '{}'", sig.public_key);
+                    continue;
+                },
+            };
+            if !self.maintainers.contains(&public_key) {
+                log::debug!(target: LOG_TARGET, "Ignoring signature from non-maintainer '{}'", sig.public_key);
+                continue;
+            }
+            let signature = match from_hex(&sig.signature)
+                .ok()
+                .and_then(|bytes| Signature::from_slice(&bytes).ok())
+            {
+                Some(s) => s,
+                None => {
+                    log::debug!(target: LOG_TARGET, "Ignoring malformed signature from '{}'", sig.public_key);
+                    continue;
+                },
+            };
+            if public_key.verify_strict(signed.manifest.as_bytes(), &signature).is_ok() {
+                signers.insert(public_key.to_bytes());
+            } else {
+                log::warn!(
+                    target: LOG_TARGET,
+                    "Invalid manifest signature from maintainer '{}'",
+                    sig.public_key
+                );
+            }
+        }
+        signers.len()
+    }
+}
+
+impl ReleaseManifest {
+    /// Returns the update for the given application and arch if this manifest contains a version newer than
+    /// `current_version`
+    pub fn find_update(
+        &self,
+        app: ApplicationType,
+        arch: &str,
+        current_version: &Version,
+    ) -> Result<Option<SoftwareUpdate>, AutoUpdateError> {
+        let version = Version::from_str(&self.version).map_err(|e| AutoUpdateError::InvalidManifest(e.to_string()))?;
+        if version <= *current_version {
+            return Ok(None);
+        }
+
+        let binary = self.binaries.iter().find(|b| {
+            b.arch == arch &&
+                ApplicationType::from_str(&b.application)
+                    .map(|a| a == app)
+                    .unwrap_or(false)
+        });
+
+        match binary {
+            Some(binary) => {
+                let hash = from_hex(&binary.sha256).map_err(|e| AutoUpdateError::InvalidManifest(e.to_string()))?;
+                Ok(Some(SoftwareUpdate {
+                    spec: UpdateSpec {
+                        application: app,
+                        arch: arch.to_string(),
+                        version,
+                        hash,
+                    },
+                    download_url: binary.url.clone(),
+                }))
+            },
+            None => Ok(None),
+        }
+    }
+}
+
+fn parse_public_key(hex: &str) -> Option<VerifyingKey> {
+    let bytes = from_hex(hex).ok()?;
+    let bytes: [u8; 32] = bytes.try_into().ok()?;
+    VerifyingKey::from_bytes(&bytes).ok()
+}
+
+#[cfg(test)]
+mod test {
+    use ed25519_dalek::{Signer, SigningKey};
+    use tari_utilities::hex::Hex;
+
+    use super::*;
+
+    const MANIFEST: &str = r#"{"version":"1.2.0","binaries":[{"application":"base-node","arch":"linux-x86_64","url":"https://example.com/minotari_node","sha256":"bada55"}]}"#;
+
+    fn signing_key(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32])
+    }
+
+    fn sign(key: &SigningKey, manifest: &str) -> ManifestSignature {
+        ManifestSignature {
+            public_key: key.verifying_key().to_bytes().to_vec().to_hex(),
+            signature: key.sign(manifest.as_bytes()).to_bytes().to_vec().to_hex(),
+        }
+    }
+
+    fn verifier(threshold: usize) -> ManifestVerifier {
+        ManifestVerifier::new((1..=3).map(|i| signing_key(i).verifying_key()).collect(), threshold)
+    }
+
+    #[test]
+    fn it_verifies_a_manifest_that_meets_the_threshold() {
+        let signed = SignedReleaseManifest {
+            manifest: MANIFEST.to_string(),
+            signatures: vec![sign(&signing_key(1), MANIFEST), sign(&signing_key(3), MANIFEST)],
+        };
+        let manifest = verifier(2).verify(&signed).unwrap();
+        assert_eq!(manifest.version, "1.2.0");
+        assert_eq!(manifest.binaries.len(), 1);
+    }
+
+    #[test]
+    fn it_rejects_duplicate_and_unknown_signers() {
+        let signed = SignedReleaseManifest {
+            manifest: MANIFEST.to_string(),
+            signatures: vec![
+                sign(&signing_key(1), MANIFEST),
+                sign(&signing_key(1), MANIFEST),
+                sign(&signing_key(9), MANIFEST),
+            ],
+        };
+        let err = verifier(2).verify(&signed).unwrap_err();
+        assert!(matches!(err, AutoUpdateError::InsufficientSignatures {
+            valid: 1,
+            required: 2
+        }));
+    }
+
+    #[test]
+    fn it_rejects_a_tampered_manifest() {
+        let tampered = MANIFEST.replace("bada55", "deadbeef");
+        let signed = SignedReleaseManifest {
+            manifest: tampered,
+            signatures: vec![sign(&signing_key(1), MANIFEST), sign(&signing_key(2), MANIFEST)],
+        };
+        assert!(verifier(1).verify(&signed).is_err());
+    }
+
+    #[test]
+    fn it_finds_a_newer_update() {
+        let manifest = serde_json::from_str::<ReleaseManifest>(MANIFEST).unwrap();
+        let update = manifest
+            .find_update(ApplicationType::BaseNode, "linux-x86_64", &Version::new(1, 0, 0))
+            .unwrap()
+            .unwrap();
+        assert_eq!(update.version(), &Version::new(1, 2, 0));
+        assert_eq!(update.hash(), &[0xba, 0xda, 0x55]);
+        assert_eq!(update.download_url(), "https://example.com/minotari_node");
+
+        assert!(manifest
+            .find_update(ApplicationType::BaseNode, "linux-x86_64", &Version::new(1, 2, 0))
+            .unwrap()
+            .is_none());
+        assert!(manifest
+            .find_update(ApplicationType::ConsoleWallet, "linux-x86_64", &Version::new(1, 0, 0))
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn it_rejects_invalid_maintainer_keys() {
+        assert!(matches!(
+            ManifestVerifier::from_hex_keys(["not-a-key"], 1),
+            Err(AutoUpdateError::InvalidMaintainerKey(_))
+        ));
+    }
+}
@@ -23,6 +23,12 @@
 mod dns;
 mod signature;
 
+mod manifest;
+pub use manifest::{ManifestSignature, ManifestVerifier, ReleaseBinary, ReleaseManifest, SignedReleaseManifest};
+
+mod staging;
+pub use staging::{stage_update, StagedUpdate};
+
 mod service;
 pub use service::{SoftwareUpdaterHandle, SoftwareUpdaterService};
 
@@ -31,6 +37,7 @@ use std::{
     fmt,
     fmt::{Display, Formatter},
     io,
+    path::{Path, PathBuf},
     str::FromStr,
     time::Duration,
 };
@@ -74,6 +81,18 @@ pub struct AutoUpdateConfig {
     pub hashes_sig_url: String,
     #[serde(with = "optional_seconds")]
     pub check_interval: Option<Duration>,
+    /// URL of a maintainer-signed release manifest. When set, updates are discovered from the manifest instead of DNS
+    /// TXT records.
+    pub manifest_url: Option<String>,
+    /// Hex-encoded Ed25519 public keys of the maintainers that may sign the release manifest
+    pub manifest_maintainer_keys: Vec<String>,
+    /// The number of distinct maintainer signatures required for a release manifest to be accepted
+    pub manifest_signature_threshold: usize,
+    /// The directory to which verified update binaries are downloaded
+    pub staging_path: PathBuf,
+    /// Automatically download and stage verified updates as soon as they are found. The operator still has to install
+    /// a staged update.
+    pub auto_stage: bool,
 }
 
 impl Default for AutoUpdateConfig {
@@ -87,6 +106,11 @@ impl Default for AutoUpdateConfig {
             hashes_url: String::new(),
             hashes_sig_url: String::new(),
             check_interval: None,
+            manifest_url: None,
+            manifest_maintainer_keys: vec![],
+            manifest_signature_threshold: 2,
+            staging_path: PathBuf::from("updates"),
+            auto_stage: false,
         }
     }
 }
@@ -99,7 +123,13 @@ impl SubConfigPath for AutoUpdateConfig {
 
 impl AutoUpdateConfig {
     pub fn is_update_enabled(&self) -> bool {
-        !self.update_uris.is_empty()
+        self.manifest_url.is_some() || !self.update_uris.is_empty()
+    }
+
+    pub fn set_base_path<P: AsRef<Path>>(&mut self, base_path: P) {
+        if !self.staging_path.is_absolute() {
+            self.staging_path = base_path.as_ref().join(self.staging_path.as_path());
+        }
     }
 }
 
@@ -109,6 +139,10 @@ pub async fn check_for_updates(
     version: &Version,
     config: AutoUpdateConfig,
 ) -> Result<Option<SoftwareUpdate>, AutoUpdateError> {
+    if let Some(manifest_url) = config.manifest_url.as_ref() {
+        return check_manifest_for_updates(app, arch, version, manifest_url, &config).await;
+    }
+
     let download_base_url = config.download_base_url.clone();
     let hashes_url = config.hashes_url.clone();
     let hashes_sig_url = config.hashes_sig_url.clone();
@@ -148,6 +182,30 @@ pub async fn check_for_updates(
     }
 }
 
+async fn check_manifest_for_updates(
+    app: ApplicationType,
+    arch: &str,
+    version: &Version,
+    manifest_url: &str,
+    config: &AutoUpdateConfig,
+) -> Result<Option<SoftwareUpdate>, AutoUpdateError> {
+    let verifier =
+        ManifestVerifier::from_hex_keys(&config.manifest_maintainer_keys, config.manifest_signature_threshold)?;
+    log::debug!(target: LOG_TARGET, "Downloading release manifest from {}", manifest_url);
+    let signed = SignedReleaseManifest::from_json(&http_download(manifest_url).await?.text().await?)?;
+    let manifest = verifier.verify(&signed)?;
+    match manifest.find_update(app, arch, version)? {
+        Some(update) => {
+            log::info!(target: LOG_TARGET, "Valid update found in release manifest: {}", update);
+            Ok(Some(update))
+        },
+        None => {
+            log::info!(target: LOG_TARGET, "No new updates for {} ({} {})", app, arch, version);
+            Ok(None)
+        },
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct SoftwareUpdate {
     spec: UpdateSpec,
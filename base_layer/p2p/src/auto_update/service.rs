@@ -35,7 +35,7 @@ use tokio_stream::wrappers;
 
 use crate::{
     auto_update,
-    auto_update::{AutoUpdateConfig, SoftwareUpdate, Version},
+    auto_update::{AutoUpdateConfig, AutoUpdateError, SoftwareUpdate, StagedUpdate, Version},
 };
 
 const LOG_TARGET: &str = "p2p::auto_update";
@@ -43,10 +43,15 @@ const LOG_TARGET: &str = "p2p::auto_update";
 /// A watch notifier that contains the latest software update, if any
 pub type SoftwareUpdateNotifier = watch::Receiver<Option<SoftwareUpdate>>;
 
+enum SoftwareUpdaterRequest {
+    CheckForUpdates(oneshot::Sender<Option<SoftwareUpdate>>),
+    StageUpdate(SoftwareUpdate, oneshot::Sender<Result<StagedUpdate, AutoUpdateError>>),
+}
+
 #[derive(Clone)]
 pub struct SoftwareUpdaterHandle {
     update_notifier: SoftwareUpdateNotifier,
-    request_tx: mpsc::Sender<oneshot::Sender<Option<SoftwareUpdate>>>,
+    request_tx: mpsc::Sender<SoftwareUpdaterRequest>,
 }
 
 impl SoftwareUpdaterHandle {
@@ -66,9 +71,23 @@ impl SoftwareUpdaterHandle {
     pub async fn check_for_updates(&mut self) -> Option<SoftwareUpdate> {
         let (tx, rx) = oneshot::channel();
         // If this is cancelled (e.g due to shutdown being triggered), return None (no update)
-        self.request_tx.send(tx).await.ok()?;
+        self.request_tx
+            .send(SoftwareUpdaterRequest::CheckForUpdates(tx))
+            .await
+            .ok()?;
         rx.await.ok().flatten()
     }
+
+    /// Downloads the given update, verifies its hash and stages it in the configured staging directory. The staged
+    /// binary is not installed; this is left to the operator.
+    pub async fn stage_update(&mut self, update: SoftwareUpdate) -> Result<StagedUpdate, AutoUpdateError> {
+        let (tx, rx) = oneshot::channel();
+        self.request_tx
+            .send(SoftwareUpdaterRequest::StageUpdate(update, tx))
+            .await
+            .map_err(|_| AutoUpdateError::ServiceUnavailable)?;
+        rx.await.map_err(|_| AutoUpdateError::ServiceUnavailable)?
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -89,7 +108,7 @@ impl SoftwareUpdaterService {
 
     async fn run(
         self,
-        mut request_rx: mpsc::Receiver<oneshot::Sender<Option<SoftwareUpdate>>>,
+        mut request_rx: mpsc::Receiver<SoftwareUpdaterRequest>,
         notifier: watch::Sender<Option<SoftwareUpdate>>,
         new_update_notification: watch::Receiver<Option<SoftwareUpdate>>,
     ) {
@@ -110,10 +129,18 @@ impl SoftwareUpdaterService {
             let last_version = new_update_notification.borrow().clone();
 
             let maybe_update = tokio::select! {
-                Some(reply) = request_rx.recv() => {
-                    let maybe_update = self.check_for_updates().await;
-                    let _result = reply.send(maybe_update.clone());
-                    maybe_update
+                Some(request) = request_rx.recv() => {
+                    match request {
+                        SoftwareUpdaterRequest::CheckForUpdates(reply) => {
+                            let maybe_update = self.check_for_updates().await;
+                            let _result = reply.send(maybe_update.clone());
+                            maybe_update
+                        },
+                        SoftwareUpdaterRequest::StageUpdate(update, reply) => {
+                            let _result = reply.send(self.stage_update(&update).await);
+                            None
+                        },
+                    }
                },
 
                Some(_) = interval_or_never.next() => {
@@ -131,16 +158,30 @@ impl SoftwareUpdaterService {
                     .unwrap_or(true)
                 {
                     let _result = notifier.send(Some(update.clone()));
+                    if self.config.auto_stage {
+                        if let Err(err) = self.stage_update(&update).await {
+                            warn!(target: LOG_TARGET, "Failed to stage update {}: {}", update.version(), err);
+                        }
+                    }
                 }
             }
         }
     }
 
+    async fn stage_update(&self, update: &SoftwareUpdate) -> Result<StagedUpdate, AutoUpdateError> {
+        let staged = auto_update::stage_update(update, &self.config.staging_path).await?;
+        info!(target: LOG_TARGET, "Update {}. Restart with the staged binary to upgrade.", staged);
+        Ok(staged)
+    }
+
     async fn check_for_updates(&self) -> Option<SoftwareUpdate> {
         log::info!(
             target: LOG_TARGET,
             "Checking for updates ({})...",
-            self.config.update_uris.join(", ")
+            self.config
+                .manifest_url
+                .clone()
+                .unwrap_or_else(|| self.config.update_uris.join(", "))
         );
         if !self.config.is_update_enabled() {
             warn!(
@@ -0,0 +1,144 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    fmt,
+    fmt::{Display, Formatter},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use sha2::{Digest, Sha256};
+
+use super::{error::AutoUpdateError, http_download, SoftwareUpdate};
+
+const LOG_TARGET: &str = "p2p::auto_update::staging";
+
+/// A downloaded and verified update binary that is ready to be installed by the operator
+#[derive(Debug, Clone)]
+pub struct StagedUpdate {
+    pub update: SoftwareUpdate,
+    pub path: PathBuf,
+}
+
+impl Display for StagedUpdate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} staged at {}", self.update.version(), self.path.display())
+    }
+}
+
+/// Downloads the binary for the given update, checks it against the expected SHA-256 hash and writes it to
+/// `<staging_dir>/<version>/<filename>`. Nothing is written to the final path if the hash does not match.
+pub async fn stage_update(update: &SoftwareUpdate, staging_dir: &Path) -> Result<StagedUpdate, AutoUpdateError> {
+    let dest_dir = staging_dir.join(update.version().to_string());
+    let dest = dest_dir.join(file_name_from_url(update.download_url()));
+    if dest.exists() && hash_matches(&fs::read(&dest)?, update.hash()) {
+        log::info!(target: LOG_TARGET, "Update {} is already staged", update.version());
+        return Ok(StagedUpdate {
+            update: update.clone(),
+            path: dest,
+        });
+    }
+
+    log::info!(target: LOG_TARGET, "Downloading update from {}", update.download_url());
+    let bytes = http_download(update.download_url()).await?.bytes().await?;
+    if !hash_matches(&bytes, update.hash()) {
+        return Err(AutoUpdateError::HashMismatch {
+            expected: update.to_hash_hex(),
+            url: update.download_url().to_string(),
+        });
+    }
+
+    fs::create_dir_all(&dest_dir)?;
+    let partial = dest.with_extension("partial");
+    fs::write(&partial, &bytes)?;
+    fs::rename(&partial, &dest)?;
+    log::info!(
+        target: LOG_TARGET,
+        "Update {} verified and staged at {}",
+        update.version(),
+        dest.display()
+    );
+
+    Ok(StagedUpdate {
+        update: update.clone(),
+        path: dest,
+    })
+}
+
+fn hash_matches(bytes: &[u8], expected: &[u8]) -> bool {
+    Sha256::digest(bytes).as_slice() == expected
+}
+
+/// Returns the last path segment of `url` if it is a single plain file name, otherwise `"update"`. Segments containing
+/// anything other than ASCII alphanumerics, `.`, `-` and `_` (e.g. `..` or percent-encoded separators) are rejected so
+/// that the download cannot be staged outside of the staging directory.
+fn file_name_from_url(url: &str) -> &str {
+    const DEFAULT_FILE_NAME: &str = "update";
+    let path = url.split(|c| c == '?' || c == '#').next().unwrap_or(url);
+    let name = match path.rsplit('/').next() {
+        Some(name) => name,
+        None => return DEFAULT_FILE_NAME,
+    };
+    let is_allowed = |c: char| c.is_ascii_alphanumeric() || c == '.' || c == '-' || c == '_';
+    if name.is_empty() || !name.chars().all(is_allowed) {
+        return DEFAULT_FILE_NAME;
+    }
+    match Path::new(name).file_name() {
+        Some(file_name) if file_name == name => name,
+        _ => DEFAULT_FILE_NAME,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_extracts_the_file_name_from_a_url() {
+        assert_eq!(
+            file_name_from_url("https://example.com/latest/minotari_node-1.2.0.zip"),
+            "minotari_node-1.2.0.zip"
+        );
+        assert_eq!(file_name_from_url("https://example.com/bin.zip?sig=abc"), "bin.zip");
+        assert_eq!(file_name_from_url("https://example.com/"), "update");
+    }
+
+    #[test]
+    fn it_rejects_file_names_that_are_not_a_single_component() {
+        assert_eq!(file_name_from_url("https://example.com/latest/.."), "update");
+        assert_eq!(file_name_from_url("https://example.com/latest/."), "update");
+        assert_eq!(
+            file_name_from_url("https://example.com/..%2F..%2Fetc%2Fpasswd"),
+            "update"
+        );
+        assert_eq!(file_name_from_url("https://example.com/..%5Cbin.exe"), "update");
+        assert_eq!(file_name_from_url("https://example.com/a\\..\\bin.exe"), "update");
+    }
+
+    #[test]
+    fn it_checks_the_sha256_hash() {
+        let expected = Sha256::digest(b"binary");
+        assert!(hash_matches(b"binary", expected.as_slice()));
+        assert!(!hash_matches(b"tampered", expected.as_slice()));
+    }
+}
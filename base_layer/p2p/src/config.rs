@@ -36,7 +36,7 @@ use tari_common::{
     DnsNameServer,
     SubConfigPath,
 };
-use tari_comms::multiaddr::Multiaddr;
+use tari_comms::{multiaddr::Multiaddr, protocol::rpc::RpcCompression};
 use tari_comms_dht::{DbConnectionUrl, DhtConfig};
 
 use crate::{transport::TransportConfig, DEFAULT_DNS_NAME_SERVER};
@@ -59,6 +59,10 @@ pub struct PeerSeedsConfig {
     pub dns_seeds_name_server: DnsNameServer,
     /// All DNS seed records must pass DNSSEC validation
     pub dns_seeds_use_dnssec: bool,
+    /// Signed list of seed peers that is used only if no seed peers could be obtained from DNS seeds
+    pub fallback_peer_seeds: StringList,
+    /// Hex signature (public nonce followed by signature) over `fallback_peer_seeds`
+    pub fallback_peer_seeds_signature: Option<String>,
 }
 
 impl Default for PeerSeedsConfig {
@@ -69,6 +73,8 @@ impl Default for PeerSeedsConfig {
             dns_seeds: StringList::default(),
             dns_seeds_name_server: DEFAULT_DNS_NAME_SERVER.parse().unwrap(),
             dns_seeds_use_dnssec: false,
+            fallback_peer_seeds: StringList::default(),
+            fallback_peer_seeds_signature: None,
         }
     }
 }
@@ -127,6 +133,16 @@ pub struct P2pConfig {
     /// The maximum allowed RPC sessions per peer.
     /// Default: 10
     pub rpc_max_sessions_per_peer: usize,
+    /// The compression to apply to large RPC responses for clients that support it. Set to `none` to disable.
+    /// Default: lz4
+    pub rpc_compression: RpcCompression,
+    /// The maximum number of inbound substreams a peer may have open for a single protocol. Set to 0 for no limit.
+    /// Default: 50
+    pub max_substreams_per_protocol: usize,
+    /// The maximum number of peer connections. The least recently used idle connections are disconnected to make room
+    /// for new connections once this limit is reached. Set to 0 for no limit.
+    /// Default: 200
+    pub max_connections: usize,
 }
 
 impl Default for P2pConfig {
@@ -152,6 +168,9 @@ impl Default for P2pConfig {
             auxiliary_tcp_listener_address: None,
             rpc_max_simultaneous_sessions: 100,
             rpc_max_sessions_per_peer: 10,
+            rpc_compression: RpcCompression::Lz4,
+            max_substreams_per_protocol: 50,
+            max_connections: 200,
         }
     }
 }
@@ -30,6 +30,7 @@ use std::{
     time::{Duration, Instant},
 };
 
+use anyhow::anyhow;
 use fs2::FileExt;
 use futures::future;
 use lmdb_zero::open;
@@ -53,6 +54,7 @@ use tari_comms::{
     tor,
     tor::HiddenServiceControllerError,
     transports::{predicate::FalsePredicate, MemoryTransport, SocksConfig, SocksTransport, TcpWithTorTransport},
+    types::CommsPublicKey,
     utils::cidr::parse_cidrs,
     CommsBuilder,
     CommsBuilderError,
@@ -67,6 +69,7 @@ use tari_storage::{
     lmdb_store::{LMDBBuilder, LMDBConfig},
     LMDBWrapper,
 };
+use tari_utilities::hex::Hex;
 use thiserror::Error;
 use tokio::sync::{broadcast, mpsc};
 use tower::ServiceBuilder;
@@ -74,7 +77,7 @@ use tower::ServiceBuilder;
 use crate::{
     comms_connector::{InboundDomainConnector, PubsubDomainConnector},
     config::{P2pConfig, PeerSeedsConfig},
-    peer_seeds::{DnsSeedResolver, SeedPeer},
+    peer_seeds::{DnsSeedResolver, SeedPeer, SeedPeerSource, SignedSeedPeerList, FALLBACK_PEER_SEEDS_PUBLIC_KEY},
     transport::{TorTransportConfig, TransportType},
     TransportConfig,
     MAJOR_NETWORK_VERSION,
@@ -170,7 +173,13 @@ pub async fn initialize_local_test_comms<P: AsRef<Path>>(
         .with_shutdown_signal(shutdown_signal)
         .build()?;
 
-    add_seed_peers(&comms.peer_manager(), &comms.node_identity(), seed_peers).await?;
+    add_seed_peers(
+        &comms.peer_manager(),
+        &comms.node_identity(),
+        SeedPeerSource::Config,
+        seed_peers,
+    )
+    .await?;
 
     // Create outbound channel
     let (outbound_tx, outbound_rx) = mpsc::channel(10);
@@ -245,6 +254,7 @@ pub async fn spawn_comms_using_transport(
                     proxy_address: addr,
                     authentication: config.tor_socks_auth.into(),
                     proxy_bypass_predicate: Arc::new(FalsePredicate::new()),
+                    isolate_streams: false,
                 });
             }
             comms
@@ -276,10 +286,24 @@ pub async fn spawn_comms_using_transport(
                 .await?
         },
         TransportType::Socks5 => {
-            debug!(target: LOG_TARGET, "Building SOCKS5 comms stack");
-            let transport = SocksTransport::new(transport_config.socks.into());
+            let socks_config = transport_config.socks;
+            debug!(
+                target: LOG_TARGET,
+                "Building SOCKS5 comms stack (outbound_only = {}, isolate_streams = {})",
+                socks_config.outbound_only,
+                socks_config.isolate_streams
+            );
+            let listener_address = if socks_config.outbound_only {
+                // Comms requires a listener, however in outbound-only mode it is only reachable locally and is never
+                // advertised to peers
+                comms.node_identity().set_public_addresses(vec![]);
+                multiaddr![Ip4([127, 0, 0, 1]), Tcp(0u16)]
+            } else {
+                transport_config.tcp.listener_address
+            };
+            let transport = SocksTransport::new(socks_config.into());
             comms
-                .with_listener_address(transport_config.tcp.listener_address)
+                .with_listener_address(listener_address)
                 .spawn_with_transport(transport)
                 .await?
         },
@@ -335,6 +359,8 @@ async fn configure_comms_and_dht(
     let builder = builder
         .with_listener_liveness_max_sessions(config.listener_liveness_max_sessions)
         .with_listener_liveness_allowlist_cidrs(listener_liveness_allowlist_cidrs)
+        .with_max_substreams_per_protocol(config.max_substreams_per_protocol)
+        .with_max_connections(config.max_connections)
         .with_dial_backoff(ConstantBackoff::new(Duration::from_millis(500)))
         .with_peer_storage(peer_database, Some(file_lock));
 
@@ -416,9 +442,25 @@ fn acquire_exclusive_file_lock(db_path: &Path) -> Result<File, CommsInitializati
     Ok(file)
 }
 
+/// Logs how many peers a seed source provided, so that unreliable seed sources are visible in the node logs
+fn log_seed_source(source: SeedPeerSource, num_peers: usize, elapsed: Duration) {
+    if num_peers == 0 {
+        warn!(
+            target: LOG_TARGET,
+            "Seed source {} provided no peers (took {:.0?})", source, elapsed
+        );
+    } else {
+        info!(
+            target: LOG_TARGET,
+            "Seed source {} provided {} peer(s) in {:.0?}", source, num_peers, elapsed
+        );
+    }
+}
+
 /// Adds a new peer to the base node
 /// ## Parameters
 /// `comms_node` - A reference to the comms node. This is the communications stack
+/// `source` - The seed source the peers were obtained from, recorded in the peer metadata
 /// `peers` - A list of peers to be added to the comms node, the current node identity of the comms stack is excluded if
 /// found in the list.
 ///
@@ -427,6 +469,7 @@ fn acquire_exclusive_file_lock(db_path: &Path) -> Result<File, CommsInitializati
 async fn add_seed_peers(
     peer_manager: &PeerManager,
     node_identity: &NodeIdentity,
+    source: SeedPeerSource,
     peers: Vec<Peer>,
 ) -> Result<(), CommsInitializationError> {
     for mut peer in peers {
@@ -438,6 +481,7 @@ async fn add_seed_peers(
             continue;
         }
         peer.add_flags(PeerFlags::SEED);
+        source.set_on_peer(&mut peer);
 
         debug!(target: LOG_TARGET, "Adding seed peer [{}]", peer);
         peer_manager
@@ -483,6 +527,22 @@ impl P2pInitializer {
             .map_err(Into::into)
     }
 
+    fn try_parse_fallback_seed_peers(config: &PeerSeedsConfig) -> Result<Vec<Peer>, ServiceInitializationError> {
+        if config.fallback_peer_seeds.is_empty() {
+            debug!(target: LOG_TARGET, "No fallback seed peers configured");
+            return Ok(Vec::new());
+        }
+        let public_key = CommsPublicKey::from_hex(FALLBACK_PEER_SEEDS_PUBLIC_KEY)
+            .map_err(|_| anyhow!("Invalid fallback seed peer public key"))?;
+        let signature = config
+            .fallback_peer_seeds_signature
+            .as_deref()
+            .ok_or_else(|| anyhow!("fallback_peer_seeds_signature is required when fallback_peer_seeds is set"))?;
+        let peers = SignedSeedPeerList::from_hex_signature(config.fallback_peer_seeds.to_vec(), signature)?
+            .into_verified_peers(&public_key)?;
+        Ok(peers.into_iter().map(Peer::from).collect())
+    }
+
     async fn try_resolve_dns_seeds(config: &PeerSeedsConfig) -> Result<Vec<Peer>, ServiceInitializationError> {
         if config.dns_seeds.is_empty() {
             debug!(target: LOG_TARGET, "No DNS Seeds configured");
@@ -578,6 +638,7 @@ impl ServiceInitializer for P2pInitializer {
         let peer_manager = comms.peer_manager();
         let node_identity = comms.node_identity();
 
+        let start = Instant::now();
         let peers = match Self::try_resolve_dns_seeds(&self.seed_config).await {
             Ok(peers) => peers,
             Err(err) => {
@@ -585,11 +646,27 @@ impl ServiceInitializer for P2pInitializer {
                 Vec::new()
             },
         };
-        add_seed_peers(&peer_manager, &node_identity, peers).await?;
+        let num_dns_peers = peers.len();
+        log_seed_source(SeedPeerSource::Dns, num_dns_peers, start.elapsed());
+        add_seed_peers(&peer_manager, &node_identity, SeedPeerSource::Dns, peers).await?;
+
+        // The signed fallback list is only used when DNS seeds did not yield any peers
+        if num_dns_peers == 0 && !self.seed_config.fallback_peer_seeds.is_empty() {
+            let start = Instant::now();
+            let peers = match Self::try_parse_fallback_seed_peers(&self.seed_config) {
+                Ok(peers) => peers,
+                Err(err) => {
+                    warn!(target: LOG_TARGET, "Failed to load the fallback seed peers: {}", err);
+                    Vec::new()
+                },
+            };
+            log_seed_source(SeedPeerSource::SignedFallback, peers.len(), start.elapsed());
+            add_seed_peers(&peer_manager, &node_identity, SeedPeerSource::SignedFallback, peers).await?;
+        }
 
         let peers = Self::try_parse_seed_peers(&self.seed_config.peer_seeds)?;
 
-        add_seed_peers(&peer_manager, &node_identity, peers).await?;
+        add_seed_peers(&peer_manager, &node_identity, SeedPeerSource::Config, peers).await?;
 
         context.register_handle(comms.connectivity());
         context.register_handle(peer_manager);
@@ -27,19 +27,31 @@ use std::{
 };
 
 use anyhow::anyhow;
+use rand::rngs::OsRng;
 use serde::{Deserialize, Serialize};
 use tari_common::DnsNameServer;
 use tari_comms::{
     multiaddr::Multiaddr,
     net_address::{MultiaddressesWithStats, PeerAddressSource},
     peer_manager::{NodeId, Peer, PeerFeatures},
-    types::CommsPublicKey,
+    types::{CommsChallenge, CommsPublicKey, CommsSecretKey, Signature},
 };
-use tari_utilities::hex::Hex;
+use tari_crypto::{hash_domain, hashing::DomainSeparatedHasher, keys::PublicKey};
+use tari_utilities::{hex::Hex, ByteArray};
 
 use super::dns::DnsClientError;
 use crate::dns::{default_trust_anchor, DnsClient};
 
+hash_domain!(SeedPeerListSignatureDomain, "com.tari.base_layer.p2p.seed_peer_list", 0);
+
+const SEED_PEER_LIST_SIGNATURE: &str = "seed_peer_list_signature";
+
+/// Hex public key of the release key that signs the fallback seed peer list
+pub const FALLBACK_PEER_SEEDS_PUBLIC_KEY: &str = "b27bef4f7b3c7a9d2242fd5294094b5efc000220914646e13c175401e4a76c3e";
+
+/// The peer metadata key under which the [`SeedPeerSource`] of a seed peer is recorded
+pub const SEED_PEER_SOURCE_METADATA_KEY: u8 = 0x53;
+
 #[derive(Clone)]
 pub struct DnsSeedResolver {
     client: DnsClient,
@@ -158,6 +170,147 @@ impl From<SeedPeer> for Peer {
     }
 }
 
+/// The bootstrap source that a seed peer was obtained from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeedPeerSource {
+    /// Resolved from DNS seed TXT records
+    Dns,
+    /// Specified in the `peer_seeds` config
+    Config,
+    /// Taken from the signed fallback seed list
+    SignedFallback,
+}
+
+impl SeedPeerSource {
+    pub fn as_byte(self) -> u8 {
+        match self {
+            SeedPeerSource::Dns => 0,
+            SeedPeerSource::Config => 1,
+            SeedPeerSource::SignedFallback => 2,
+        }
+    }
+
+    pub fn from_byte(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(SeedPeerSource::Dns),
+            1 => Some(SeedPeerSource::Config),
+            2 => Some(SeedPeerSource::SignedFallback),
+            _ => None,
+        }
+    }
+
+    /// Returns the seed source recorded on the peer, if any
+    pub fn from_peer(peer: &Peer) -> Option<Self> {
+        peer.get_metadata(SEED_PEER_SOURCE_METADATA_KEY)
+            .and_then(|v| v.first().copied())
+            .and_then(Self::from_byte)
+    }
+
+    /// Records this seed source on the peer
+    pub fn set_on_peer(self, peer: &mut Peer) {
+        peer.set_metadata(SEED_PEER_SOURCE_METADATA_KEY, vec![self.as_byte()]);
+    }
+}
+
+impl Display for SeedPeerSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SeedPeerSource::Dns => write!(f, "DNS"),
+            SeedPeerSource::Config => write!(f, "Config"),
+            SeedPeerSource::SignedFallback => write!(f, "SignedFallback"),
+        }
+    }
+}
+
+/// A list of seed peers signed by a known key. This list is used as a fallback when no seed peers could be obtained
+/// from DNS seeds, so that a node bootstrapping for the first time does not depend solely on an unauthenticated source.
+/// The signature is over the seed peer strings exactly as they are configured, so that the list is verified before any
+/// entry is parsed.
+#[derive(Debug, Clone)]
+pub struct SignedSeedPeerList {
+    peers: Vec<String>,
+    signature: Signature,
+}
+
+impl SignedSeedPeerList {
+    pub fn new(peers: Vec<String>, signature: Signature) -> Self {
+        Self { peers, signature }
+    }
+
+    /// Signs the given seed peer strings with `secret_key`
+    pub fn sign(secret_key: &CommsSecretKey, peers: Vec<String>) -> Self {
+        let public_key = CommsPublicKey::from_secret_key(secret_key);
+        let (secret_nonce, public_nonce) = CommsPublicKey::random_keypair(&mut OsRng);
+        let challenge = Self::construct_challenge(&public_key, &public_nonce, &peers).finalize();
+        let signature = Signature::sign_raw(secret_key, secret_nonce, challenge.as_ref())
+            .expect("unreachable panic: challenge hash digest is the correct length");
+        Self { peers, signature }
+    }
+
+    /// Parses a signature in the form `<public nonce hex><signature hex>` as produced by [`Self::signature_hex`]
+    pub fn from_hex_signature(peers: Vec<String>, signature_hex: &str) -> Result<Self, anyhow::Error> {
+        let signature_hex = signature_hex.trim();
+        if signature_hex.len() != 128 || !signature_hex.is_char_boundary(64) {
+            return Err(anyhow!("Invalid seed peer list signature length"));
+        }
+        let public_nonce = CommsPublicKey::from_hex(&signature_hex[..64])
+            .map_err(|_| anyhow!("Invalid public nonce in seed peer list signature"))?;
+        let signature =
+            CommsSecretKey::from_hex(&signature_hex[64..]).map_err(|_| anyhow!("Invalid seed peer list signature"))?;
+        Ok(Self::new(peers, Signature::new(public_nonce, signature)))
+    }
+
+    pub fn signature_hex(&self) -> String {
+        format!(
+            "{}{}",
+            self.signature.get_public_nonce().to_hex(),
+            self.signature.get_signature().to_hex()
+        )
+    }
+
+    pub fn peers(&self) -> &[String] {
+        &self.peers
+    }
+
+    /// Returns true if the list was signed by `public_key`
+    pub fn verify(&self, public_key: &CommsPublicKey) -> bool {
+        let challenge =
+            Self::construct_challenge(public_key, self.signature.get_public_nonce(), &self.peers).finalize();
+        self.signature.verify_challenge(public_key, challenge.as_ref())
+    }
+
+    /// Consumes the list, returning the parsed seed peers if the list was signed by `public_key`. The whole list is
+    /// rejected if any entry is not a valid seed peer.
+    pub fn into_verified_peers(self, public_key: &CommsPublicKey) -> Result<Vec<SeedPeer>, anyhow::Error> {
+        if !self.verify(public_key) {
+            return Err(anyhow!("Seed peer list signature is invalid"));
+        }
+        self.peers
+            .iter()
+            .map(|s| SeedPeer::from_str(s).map_err(|err| anyhow!("Invalid seed peer '{}' in signed list: {}", s, err)))
+            .collect()
+    }
+
+    fn construct_challenge(
+        public_key: &CommsPublicKey,
+        public_nonce: &CommsPublicKey,
+        peers: &[String],
+    ) -> DomainSeparatedHasher<CommsChallenge, SeedPeerListSignatureDomain> {
+        // e = H(P||R||m)
+        let challenge = DomainSeparatedHasher::<CommsChallenge, SeedPeerListSignatureDomain>::new_with_label(
+            SEED_PEER_LIST_SIGNATURE,
+        )
+        .chain(public_key.as_bytes())
+        .chain(public_nonce.as_bytes())
+        .chain((peers.len() as u64).to_le_bytes());
+        peers.iter().fold(challenge, |challenge, peer| {
+            challenge
+                .chain((peer.len() as u64).to_le_bytes())
+                .chain(peer.as_bytes())
+        })
+    }
+}
+
 #[cfg(test)]
 mod test {
     use tari_utilities::hex::Hex;
@@ -226,6 +379,74 @@ mod test {
         }
     }
 
+    mod signed_seed_peer_list {
+        use tari_crypto::keys::SecretKey;
+
+        use super::*;
+
+        fn sample_peers() -> Vec<String> {
+            vec![
+                "06e98e9c5eb52bd504836edec1878eccf12eb9f26a5fe5ec0e279423156e657a::/ip4/127.0.0.1/tcp/8000".to_string(),
+                "fab24c542183073996ddf3a6c73ff8b8562fed351d252ec5cb8f269d1ad92f0c::/onion3/\
+                 bsmuof2cn4y2ysz253gzsvg3s72fcgh4f3qcm3hdlxdtcwe6al2dicyd:1234"
+                    .to_string(),
+            ]
+        }
+
+        #[test]
+        fn it_verifies_a_valid_signature() {
+            let secret_key = CommsSecretKey::random(&mut OsRng);
+            let public_key = CommsPublicKey::from_secret_key(&secret_key);
+            let list = SignedSeedPeerList::sign(&secret_key, sample_peers());
+            assert!(list.verify(&public_key));
+
+            let list = SignedSeedPeerList::from_hex_signature(sample_peers(), &list.signature_hex()).unwrap();
+            assert_eq!(list.into_verified_peers(&public_key).unwrap().len(), 2);
+        }
+
+        #[test]
+        fn it_rejects_a_different_signer() {
+            let secret_key = CommsSecretKey::random(&mut OsRng);
+            let (_, other_public_key) = CommsPublicKey::random_keypair(&mut OsRng);
+            let list = SignedSeedPeerList::sign(&secret_key, sample_peers());
+            assert!(!list.verify(&other_public_key));
+            list.into_verified_peers(&other_public_key).unwrap_err();
+        }
+
+        #[test]
+        fn it_rejects_a_modified_list() {
+            let secret_key = CommsSecretKey::random(&mut OsRng);
+            let public_key = CommsPublicKey::from_secret_key(&secret_key);
+            let list = SignedSeedPeerList::sign(&secret_key, sample_peers());
+            let mut peers = sample_peers();
+            peers.pop();
+            let list = SignedSeedPeerList::from_hex_signature(peers, &list.signature_hex()).unwrap();
+            assert!(!list.verify(&public_key));
+        }
+
+        #[test]
+        fn it_rejects_the_list_if_any_entry_is_malformed() {
+            let secret_key = CommsSecretKey::random(&mut OsRng);
+            let public_key = CommsPublicKey::from_secret_key(&secret_key);
+            let mut peers = sample_peers();
+            peers.push("not a seed peer".to_string());
+            let list = SignedSeedPeerList::sign(&secret_key, peers);
+            assert!(list.verify(&public_key));
+            list.into_verified_peers(&public_key).unwrap_err();
+        }
+
+        #[test]
+        fn it_has_a_valid_release_public_key() {
+            CommsPublicKey::from_hex(FALLBACK_PEER_SEEDS_PUBLIC_KEY).unwrap();
+        }
+
+        #[test]
+        fn it_errors_on_malformed_signature_hex() {
+            SignedSeedPeerList::from_hex_signature(sample_peers(), "").unwrap_err();
+            SignedSeedPeerList::from_hex_signature(sample_peers(), &"zz".repeat(64)).unwrap_err();
+        }
+    }
+
     mod peer_seed_resolver {
         use trust_dns_client::{
             proto::{
@@ -27,7 +27,11 @@ use tari_service_framework::reply_channel::SenderService;
 use tokio::sync::broadcast;
 use tower::Service;
 
-use super::{error::LivenessError, state::Metadata};
+use super::{
+    error::LivenessError,
+    latency::NetworkLatency,
+    state::{Metadata, MetadataRecipients},
+};
 use crate::proto::liveness::MetadataKey;
 
 /// Request types made through the `LivenessHandle` and are handled by the `LivenessService`
@@ -43,8 +47,12 @@ pub enum LivenessRequest {
     GetAvgLatency(NodeId),
     /// Get average latency for all connected nodes
     GetNetworkAvgLatency,
+    /// Get the latency statistics and RTT histograms of all peers, grouped into latency buckets
+    GetNetworkLatency,
     /// Set the metadata attached to each ping/pong message
     SetMetadataEntry(MetadataKey, Vec<u8>),
+    /// Set the peers that the metadata entry for the given key is attached to
+    SetMetadataRecipients(MetadataKey, MetadataRecipients),
     /// Add a monitored peer to the basic config
     AddMonitoredPeer(NodeId),
     /// Remove a monitored peer from the basic config
@@ -62,6 +70,8 @@ pub enum LivenessResponse {
     AvgLatency(Option<Duration>),
     /// The number of active neighbouring peers
     NumActiveNeighbours(usize),
+    /// Response for GetNetworkLatency
+    NetworkLatency(NetworkLatency),
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -157,6 +167,22 @@ impl LivenessHandle {
         }
     }
 
+    /// Restrict the peers that receive the metadata entry for the given key in ping/pong messages
+    pub async fn set_metadata_recipients(
+        &mut self,
+        key: MetadataKey,
+        recipients: MetadataRecipients,
+    ) -> Result<(), LivenessError> {
+        match self
+            .handle
+            .call(LivenessRequest::SetMetadataRecipients(key, recipients))
+            .await??
+        {
+            LivenessResponse::Ok => Ok(()),
+            _ => Err(LivenessError::UnexpectedApiResponse),
+        }
+    }
+
     /// Add a monitored peer to the basic config if not present
     pub async fn check_add_monitored_peer(&mut self, node_id: NodeId) -> Result<(), LivenessError> {
         match self.handle.call(LivenessRequest::AddMonitoredPeer(node_id)).await?? {
@@ -192,4 +218,12 @@ impl LivenessHandle {
             _ => Err(LivenessError::UnexpectedApiResponse),
         }
     }
+
+    /// Retrieve the latency statistics and RTT histograms of all peers for which round-trip times are known
+    pub async fn get_network_latency(&mut self) -> Result<NetworkLatency, LivenessError> {
+        match self.handle.call(LivenessRequest::GetNetworkLatency).await?? {
+            LivenessResponse::NetworkLatency(v) => Ok(v),
+            _ => Err(LivenessError::UnexpectedApiResponse),
+        }
+    }
 }
@@ -0,0 +1,282 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    collections::HashMap,
+    convert::TryFrom,
+    fmt::{Display, Formatter},
+    time::Duration,
+};
+
+use tari_comms::peer_manager::NodeId;
+
+/// Coarse round-trip time buckets. The bucket boundaries roughly correspond to the latency expected between peers
+/// in the same locality, region, continent etc.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LatencyBucket {
+    /// Less than 50ms
+    Local,
+    /// 50ms to 150ms
+    Regional,
+    /// 150ms to 300ms
+    Continental,
+    /// 300ms to 600ms
+    Intercontinental,
+    /// 600ms or more
+    Distant,
+}
+
+impl LatencyBucket {
+    pub const ALL: [LatencyBucket; 5] = [
+        LatencyBucket::Local,
+        LatencyBucket::Regional,
+        LatencyBucket::Continental,
+        LatencyBucket::Intercontinental,
+        LatencyBucket::Distant,
+    ];
+
+    /// Returns the bucket that the given round-trip time falls into
+    pub fn from_latency(latency: Duration) -> Self {
+        match latency.as_millis() {
+            0..=49 => LatencyBucket::Local,
+            50..=149 => LatencyBucket::Regional,
+            150..=299 => LatencyBucket::Continental,
+            300..=599 => LatencyBucket::Intercontinental,
+            _ => LatencyBucket::Distant,
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+impl Display for LatencyBucket {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LatencyBucket::Local => write!(f, "Local (<50ms)"),
+            LatencyBucket::Regional => write!(f, "Regional (<150ms)"),
+            LatencyBucket::Continental => write!(f, "Continental (<300ms)"),
+            LatencyBucket::Intercontinental => write!(f, "Intercontinental (<600ms)"),
+            LatencyBucket::Distant => write!(f, "Distant (>=600ms)"),
+        }
+    }
+}
+
+/// Histogram of round-trip time samples for a peer, counted per [LatencyBucket].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RttHistogram {
+    counts: [u64; LatencyBucket::ALL.len()],
+}
+
+impl RttHistogram {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Add a round-trip time sample
+    pub fn add_sample(&mut self, latency: Duration) {
+        let count = &mut self.counts[LatencyBucket::from_latency(latency).index()];
+        *count = count.saturating_add(1);
+    }
+
+    /// The number of samples in the given bucket
+    pub fn count(&self, bucket: LatencyBucket) -> u64 {
+        self.counts[bucket.index()]
+    }
+
+    /// The total number of samples
+    pub fn total(&self) -> u64 {
+        self.counts.iter().fold(0, |acc, c| acc.saturating_add(*c))
+    }
+
+    /// Returns the bucket that contains the given percentile (0-100) of samples, or None if there are no samples
+    pub fn percentile(&self, percentile: u8) -> Option<LatencyBucket> {
+        let total = self.total();
+        if total == 0 {
+            return None;
+        }
+        let threshold = ((total * u64::from(percentile.min(100)) + 99) / 100).max(1);
+        let mut cumulative = 0u64;
+        LatencyBucket::ALL.iter().copied().find(|bucket| {
+            cumulative += self.count(*bucket);
+            cumulative >= threshold
+        })
+    }
+
+    /// The bucket containing the median sample, or None if there are no samples
+    pub fn median(&self) -> Option<LatencyBucket> {
+        self.percentile(50)
+    }
+}
+
+/// Latency statistics for a single peer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeerLatency {
+    /// The mean average of recent round-trip time samples
+    pub avg_latency: Duration,
+    /// The round-trip time histogram of all samples for this peer
+    pub histogram: RttHistogram,
+}
+
+impl PeerLatency {
+    /// The bucket that this peer is classified in, determined by the median of the peer's RTT histogram.
+    pub fn bucket(&self) -> LatencyBucket {
+        self.histogram
+            .median()
+            .unwrap_or_else(|| LatencyBucket::from_latency(self.avg_latency))
+    }
+}
+
+/// A snapshot of the latency to all peers for which round-trip times have been recorded.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NetworkLatency {
+    peers: HashMap<NodeId, PeerLatency>,
+}
+
+impl NetworkLatency {
+    pub fn new(peers: HashMap<NodeId, PeerLatency>) -> Self {
+        Self { peers }
+    }
+
+    /// Returns the latency statistics for the given peer, if any
+    pub fn get(&self, node_id: &NodeId) -> Option<&PeerLatency> {
+        self.peers.get(node_id)
+    }
+
+    /// Returns the latency bucket of the given peer, or None if no round-trip times are known for the peer
+    pub fn bucket_of(&self, node_id: &NodeId) -> Option<LatencyBucket> {
+        self.get(node_id).map(PeerLatency::bucket)
+    }
+
+    /// Returns the peers classified in the given bucket
+    pub fn peers_in_bucket(&self, bucket: LatencyBucket) -> impl Iterator<Item = &NodeId> + '_ {
+        self.peers
+            .iter()
+            .filter(move |(_, latency)| latency.bucket() == bucket)
+            .map(|(node_id, _)| node_id)
+    }
+
+    /// Returns the number of peers in each bucket, ordered from lowest to highest latency
+    pub fn bucket_counts(&self) -> Vec<(LatencyBucket, usize)> {
+        LatencyBucket::ALL
+            .iter()
+            .map(|bucket| (*bucket, self.peers_in_bucket(*bucket).count()))
+            .collect()
+    }
+
+    /// The mean average latency over all peers, or None if there are no peers
+    pub fn avg_latency(&self) -> Option<Duration> {
+        let total = self
+            .peers
+            .values()
+            .fold(Duration::ZERO, |acc, latency| acc.saturating_add(latency.avg_latency));
+        u32::try_from(self.peers.len())
+            .ok()
+            .filter(|n| *n > 0)
+            .map(|n| total / n)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&NodeId, &PeerLatency)> {
+        self.peers.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.peers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.peers.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn from_latency() {
+        assert_eq!(
+            LatencyBucket::from_latency(Duration::from_millis(0)),
+            LatencyBucket::Local
+        );
+        assert_eq!(
+            LatencyBucket::from_latency(Duration::from_millis(50)),
+            LatencyBucket::Regional
+        );
+        assert_eq!(
+            LatencyBucket::from_latency(Duration::from_millis(299)),
+            LatencyBucket::Continental
+        );
+        assert_eq!(
+            LatencyBucket::from_latency(Duration::from_millis(300)),
+            LatencyBucket::Intercontinental
+        );
+        assert_eq!(
+            LatencyBucket::from_latency(Duration::from_secs(5)),
+            LatencyBucket::Distant
+        );
+    }
+
+    #[test]
+    fn histogram_percentile() {
+        let mut histogram = RttHistogram::new();
+        assert_eq!(histogram.median(), None);
+
+        for ms in [10, 20, 30, 100, 1000] {
+            histogram.add_sample(Duration::from_millis(ms));
+        }
+        assert_eq!(histogram.total(), 5);
+        assert_eq!(histogram.count(LatencyBucket::Local), 3);
+        assert_eq!(histogram.median(), Some(LatencyBucket::Local));
+        assert_eq!(histogram.percentile(80), Some(LatencyBucket::Regional));
+        assert_eq!(histogram.percentile(100), Some(LatencyBucket::Distant));
+        assert_eq!(histogram.percentile(0), Some(LatencyBucket::Local));
+    }
+
+    #[test]
+    fn network_latency_buckets() {
+        let peer_latency = |ms| {
+            let mut histogram = RttHistogram::new();
+            histogram.add_sample(Duration::from_millis(ms));
+            PeerLatency {
+                avg_latency: Duration::from_millis(ms),
+                histogram,
+            }
+        };
+        let local_peer = NodeId::default();
+        let distant_peer = NodeId::from_public_key(&Default::default());
+        let network = NetworkLatency::new(HashMap::from([
+            (local_peer.clone(), peer_latency(10)),
+            (distant_peer.clone(), peer_latency(1000)),
+        ]));
+
+        assert_eq!(network.bucket_of(&local_peer), Some(LatencyBucket::Local));
+        assert_eq!(network.bucket_of(&distant_peer), Some(LatencyBucket::Distant));
+        assert_eq!(network.avg_latency(), Some(Duration::from_millis(505)));
+        let counts = network.bucket_counts();
+        assert_eq!(counts[0], (LatencyBucket::Local, 1));
+        assert_eq!(counts[4], (LatencyBucket::Distant, 1));
+        assert_eq!(counts.iter().map(|(_, n)| n).sum::<usize>(), 2);
+        assert!(NetworkLatency::default().avg_latency().is_none());
+    }
+}
@@ -139,9 +139,17 @@ impl LivenessMock {
             GetNetworkAvgLatency => {
                 reply.send(Ok(LivenessResponse::AvgLatency(None))).unwrap();
             },
+            GetNetworkLatency => {
+                reply
+                    .send(Ok(LivenessResponse::NetworkLatency(Default::default())))
+                    .unwrap();
+            },
             SetMetadataEntry(_, _) => {
                 reply.send(Ok(LivenessResponse::Ok)).unwrap();
             },
+            SetMetadataRecipients(_, _) => {
+                reply.send(Ok(LivenessResponse::Ok)).unwrap();
+            },
             AddMonitoredPeer(_) => {
                 reply.send(Ok(LivenessResponse::Ok)).unwrap();
             },
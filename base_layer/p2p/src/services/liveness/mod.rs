@@ -50,11 +50,14 @@ pub use handle::{
     PingPongEvent,
 };
 
+mod latency;
+pub use latency::{LatencyBucket, NetworkLatency, PeerLatency, RttHistogram};
+
 mod message;
 mod service;
 
 mod state;
-pub use state::Metadata;
+pub use state::{Metadata, MetadataRecipients};
 
 #[cfg(feature = "test-mocks")]
 pub mod mock;
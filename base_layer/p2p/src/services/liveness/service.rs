@@ -176,7 +176,7 @@ where
         match ping_pong_msg.kind().ok_or(LivenessError::InvalidPingPongType)? {
             PingPong::Ping => {
                 self.state.inc_pings_received();
-                self.send_pong(ping_pong_msg.nonce, public_key).await?;
+                self.send_pong(ping_pong_msg.nonce, public_key, &node_id).await?;
                 self.state.inc_pongs_sent();
 
                 debug!(
@@ -222,7 +222,7 @@ where
     }
 
     async fn send_ping(&mut self, node_id: NodeId) -> Result<(), LivenessError> {
-        let msg = PingPongMessage::ping_with_metadata(self.state.metadata().clone());
+        let msg = PingPongMessage::ping_with_metadata(self.state.metadata_for(&node_id));
         self.state.add_inflight_ping(msg.nonce, node_id.clone());
         debug!(target: LOG_TARGET, "Sending ping to peer '{}'", node_id.short_str(),);
 
@@ -238,8 +238,8 @@ where
         Ok(())
     }
 
-    async fn send_pong(&mut self, nonce: u64, dest: CommsPublicKey) -> Result<(), LivenessError> {
-        let msg = PingPongMessage::pong_with_metadata(nonce, self.state.metadata().clone());
+    async fn send_pong(&mut self, nonce: u64, dest: CommsPublicKey, node_id: &NodeId) -> Result<(), LivenessError> {
+        let msg = PingPongMessage::pong_with_metadata(nonce, self.state.metadata_for(node_id));
         self.outbound_messaging
             .send_direct_unencrypted(
                 dest,
@@ -276,10 +276,15 @@ where
                 let latency = self.state.get_network_avg_latency();
                 Ok(LivenessResponse::AvgLatency(latency))
             },
+            GetNetworkLatency => Ok(LivenessResponse::NetworkLatency(self.state.get_network_latency())),
             SetMetadataEntry(key, value) => {
                 self.state.set_metadata_entry(key, value);
                 Ok(LivenessResponse::Ok)
             },
+            SetMetadataRecipients(key, recipients) => {
+                self.state.set_metadata_recipients(key, recipients);
+                Ok(LivenessResponse::Ok)
+            },
             AddMonitoredPeer(node_id) => {
                 let node_id_exists = { self.monitored_peers.read().await.iter().any(|val| val == &node_id) };
                 if !node_id_exists {
@@ -321,7 +326,7 @@ where
         let len_peers = selected_peers.len();
 
         for peer in selected_peers {
-            let msg = PingPongMessage::ping_with_metadata(self.state.metadata().clone());
+            let msg = PingPongMessage::ping_with_metadata(self.state.metadata_for(&peer));
             self.state.add_inflight_ping(msg.nonce, peer.clone());
             self.outbound_messaging
                 .send_direct_node_id(
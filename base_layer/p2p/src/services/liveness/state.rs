@@ -21,7 +21,7 @@
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     convert::TryFrom,
     time::{Duration, Instant},
 };
@@ -29,7 +29,10 @@ use std::{
 use log::*;
 use tari_comms::peer_manager::NodeId;
 
-use super::LOG_TARGET;
+use super::{
+    latency::{NetworkLatency, PeerLatency, RttHistogram},
+    LOG_TARGET,
+};
 use crate::proto::liveness::MetadataKey;
 
 const LATENCY_SAMPLE_WINDOW_SIZE: usize = 25;
@@ -59,6 +62,27 @@ impl Metadata {
     }
 }
 
+/// The peers that a metadata entry is sent to
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MetadataRecipients {
+    /// The entry is sent to all peers
+    All,
+    /// The entry is sent to all peers except the given peers
+    AllExcept(HashSet<NodeId>),
+    /// The entry is only sent to the given peers
+    Only(HashSet<NodeId>),
+}
+
+impl MetadataRecipients {
+    pub fn includes(&self, node_id: &NodeId) -> bool {
+        match self {
+            Self::All => true,
+            Self::AllExcept(excluded) => !excluded.contains(node_id),
+            Self::Only(included) => included.contains(node_id),
+        }
+    }
+}
+
 impl From<HashMap<i32, Vec<u8>>> for Metadata {
     fn from(inner: HashMap<i32, Vec<u8>>) -> Self {
         Self { inner }
@@ -76,6 +100,7 @@ impl From<Metadata> for HashMap<i32, Vec<u8>> {
 pub struct LivenessState {
     inflight_pings: HashMap<u64, (NodeId, Instant)>,
     peer_latency: HashMap<NodeId, AverageLatency>,
+    peer_rtt_histograms: HashMap<NodeId, RttHistogram>,
     failed_pings: HashMap<NodeId, usize>,
 
     pings_received: usize,
@@ -84,6 +109,7 @@ pub struct LivenessState {
     pongs_sent: usize,
 
     local_metadata: Metadata,
+    metadata_recipients: HashMap<i32, MetadataRecipients>,
 }
 
 impl LivenessState {
@@ -130,11 +156,32 @@ impl LivenessState {
         &self.local_metadata
     }
 
+    /// Returns the local metadata that may be sent to the given peer
+    pub fn metadata_for(&self, node_id: &NodeId) -> Metadata {
+        let inner = self
+            .local_metadata
+            .inner
+            .iter()
+            .filter(|(key, _)| {
+                self.metadata_recipients
+                    .get(key)
+                    .map_or(true, |recipients| recipients.includes(node_id))
+            })
+            .map(|(key, value)| (*key, value.clone()))
+            .collect();
+        Metadata { inner }
+    }
+
     /// Set a metadata entry for the local node. Duplicate entries are replaced.
     pub fn set_metadata_entry(&mut self, key: MetadataKey, value: Vec<u8>) {
         self.local_metadata.insert(key, value);
     }
 
+    /// Set the peers that the metadata entry for the given key is sent to. By default, entries are sent to all peers.
+    pub fn set_metadata_recipients(&mut self, key: MetadataKey, recipients: MetadataRecipients) {
+        self.metadata_recipients.insert(key as i32, recipients);
+    }
+
     /// Adds a ping to the inflight ping list, while noting the current time that a ping was sent.
     pub fn add_inflight_ping(&mut self, nonce: u64, node_id: NodeId) {
         self.inflight_pings.insert(nonce, (node_id, Instant::now()));
@@ -191,6 +238,10 @@ impl LivenessState {
     }
 
     fn add_latency_sample(&mut self, node_id: NodeId, duration: Duration) -> &mut AverageLatency {
+        self.peer_rtt_histograms
+            .entry(node_id.clone())
+            .or_default()
+            .add_sample(duration);
         let latency = self
             .peer_latency
             .entry(node_id)
@@ -217,6 +268,22 @@ impl LivenessState {
             .map(|latency| Duration::from_millis(u64::try_from(latency.as_millis()).unwrap() / num_peers as u64))
     }
 
+    /// Returns the latency statistics of all peers for which round-trip times have been recorded
+    pub fn get_network_latency(&self) -> NetworkLatency {
+        let peers = self
+            .peer_latency
+            .iter()
+            .map(|(node_id, latency)| {
+                let peer_latency = PeerLatency {
+                    avg_latency: latency.calc_average(),
+                    histogram: self.peer_rtt_histograms.get(node_id).cloned().unwrap_or_default(),
+                };
+                (node_id.clone(), peer_latency)
+            })
+            .collect();
+        NetworkLatency::new(peers)
+    }
+
     pub fn failed_pings_iter(&self) -> impl Iterator<Item = (&NodeId, &usize)> {
         self.failed_pings.iter()
     }
@@ -265,6 +332,7 @@ impl AverageLatency {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::services::liveness::LatencyBucket;
 
     #[test]
     fn new() {
@@ -328,6 +396,24 @@ mod test {
         assert!(latency < Duration::from_millis(50));
     }
 
+    #[test]
+    fn get_network_latency() {
+        let mut state = LivenessState::new();
+        assert!(state.get_network_latency().is_empty());
+
+        let node_id = NodeId::default();
+        state.add_latency_sample(node_id.clone(), Duration::from_millis(20));
+        state.add_latency_sample(node_id.clone(), Duration::from_millis(40));
+        state.add_latency_sample(node_id.clone(), Duration::from_millis(200));
+
+        let network_latency = state.get_network_latency();
+        assert_eq!(network_latency.len(), 1);
+        let peer_latency = network_latency.get(&node_id).unwrap();
+        assert_eq!(peer_latency.avg_latency, Duration::from_millis(86));
+        assert_eq!(peer_latency.histogram.total(), 3);
+        assert_eq!(peer_latency.bucket(), LatencyBucket::Local);
+    }
+
     #[test]
     fn set_metadata_entry() {
         let mut state = LivenessState::new();
@@ -335,6 +421,31 @@ mod test {
         assert_eq!(state.metadata().get(MetadataKey::ChainMetadata).unwrap(), b"dummy-data");
     }
 
+    #[test]
+    fn metadata_for() {
+        let mut state = LivenessState::new();
+        let peer1 = NodeId::default();
+        let peer2 = NodeId::from_public_key(&Default::default());
+        state.set_metadata_entry(MetadataKey::ChainMetadata, b"chain".to_vec());
+        state.set_metadata_entry(MetadataKey::ContactsLiveness, b"contacts".to_vec());
+        assert_eq!(state.metadata_for(&peer1), *state.metadata());
+
+        state.set_metadata_recipients(
+            MetadataKey::ContactsLiveness,
+            MetadataRecipients::Only(HashSet::from([peer1.clone()])),
+        );
+        assert!(state.metadata_for(&peer1).has(MetadataKey::ContactsLiveness));
+        assert!(!state.metadata_for(&peer2).has(MetadataKey::ContactsLiveness));
+        assert!(state.metadata_for(&peer2).has(MetadataKey::ChainMetadata));
+
+        state.set_metadata_recipients(
+            MetadataKey::ContactsLiveness,
+            MetadataRecipients::AllExcept(HashSet::from([peer1.clone()])),
+        );
+        assert!(!state.metadata_for(&peer1).has(MetadataKey::ContactsLiveness));
+        assert!(state.metadata_for(&peer2).has(MetadataKey::ContactsLiveness));
+    }
+
     #[test]
     fn clear_stale_inflight_pings() {
         let mut state = LivenessState::new();
@@ -209,6 +209,12 @@ impl Default for TorTransportConfig {
 pub struct Socks5TransportConfig {
     pub proxy_address: Multiaddr,
     pub auth: SocksAuthentication,
+    /// When set to true, connections to each peer use distinct SOCKS credentials so that a tor proxy builds a separate
+    /// circuit per destination. The `auth` setting is ignored when this is enabled.
+    pub isolate_streams: bool,
+    /// When set to true, the node only makes outbound connections through the proxy. The listener is bound to an
+    /// ephemeral localhost port and no public addresses are advertised.
+    pub outbound_only: bool,
 }
 
 impl From<Socks5TransportConfig> for SocksConfig {
@@ -217,6 +223,7 @@ impl From<Socks5TransportConfig> for SocksConfig {
             proxy_address: config.proxy_address,
             authentication: config.auth.into(),
             proxy_bypass_predicate: Arc::new(FalsePredicate::new()),
+            isolate_streams: config.isolate_streams,
         }
     }
 }
@@ -226,6 +233,8 @@ impl Default for Socks5TransportConfig {
         Self {
             proxy_address: "/ip4/127.0.0.1/tcp/8080".parse().unwrap(),
             auth: SocksAuthentication::None,
+            isolate_streams: false,
+            outbound_only: false,
         }
     }
 }
@@ -0,0 +1,12 @@
+CREATE TABLE scheduled_payments
+(
+    id              BIGINT PRIMARY KEY NOT NULL,
+    payload         TEXT               NOT NULL,
+    next_payment_at DATETIME           NOT NULL,
+    payments_made   BIGINT             NOT NULL DEFAULT 0,
+    last_tx_id      BIGINT             NULL,
+    status          INTEGER            NOT NULL,
+    created_at      DATETIME           NOT NULL
+);
+
+CREATE INDEX idx_scheduled_payments_status_next_payment_at ON scheduled_payments (status, next_payment_at);
@@ -0,0 +1,14 @@
+CREATE TABLE balance_snapshots
+(
+    resolution               INTEGER  NOT NULL,
+    bucket                   BIGINT   NOT NULL,
+    block_height             BIGINT   NOT NULL,
+    recorded_at              DATETIME NOT NULL,
+    available_balance        BIGINT   NOT NULL,
+    time_locked_balance      BIGINT   NULL,
+    pending_incoming_balance BIGINT   NOT NULL,
+    pending_outgoing_balance BIGINT   NOT NULL,
+    PRIMARY KEY (resolution, bucket)
+);
+
+CREATE INDEX idx_balance_snapshots_resolution_recorded_at ON balance_snapshots (resolution, recorded_at);
@@ -0,0 +1,10 @@
+CREATE TABLE transaction_events
+(
+    seq         INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    tx_id       BIGINT   NOT NULL,
+    event       TEXT     NOT NULL,
+    status      INTEGER  NULL,
+    recorded_at DATETIME NOT NULL
+);
+
+CREATE INDEX idx_transaction_events_tx_id ON transaction_events (tx_id);
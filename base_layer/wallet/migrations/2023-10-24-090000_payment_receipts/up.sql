@@ -0,0 +1,6 @@
+CREATE TABLE payment_receipts
+(
+    tx_id        BIGINT PRIMARY KEY NOT NULL,
+    public_nonce BLOB   NOT NULL,
+    signature    BLOB   NOT NULL
+);
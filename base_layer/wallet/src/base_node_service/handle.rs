@@ -48,6 +48,8 @@ pub enum BaseNodeServiceResponse {
 pub enum BaseNodeEvent {
     BaseNodeStateChanged(BaseNodeState),
     NewBlockDetected(BlockHash, u64),
+    /// The last seen tip at the given height was reorged out of the base node's chain
+    ReorgDetected(u64),
 }
 
 impl fmt::Display for BaseNodeEvent {
@@ -59,6 +61,9 @@ impl fmt::Display for BaseNodeEvent {
             BaseNodeEvent::NewBlockDetected(hash, height) => {
                 write!(f, "NewBlockDetected: {} ({})", height, hash.to_hex())
             },
+            BaseNodeEvent::ReorgDetected(height) => {
+                write!(f, "ReorgDetected: {}", height)
+            },
         }
     }
 }
@@ -36,6 +36,7 @@ use tari_comms::{
     backoff::{Backoff, ExponentialBackoff},
     protocol::rpc::RpcError,
 };
+use tari_core::{base_node::rpc::BaseNodeWalletRpcClient, blocks::BlockHeader};
 use tokio::{sync::RwLock, time};
 
 use crate::{
@@ -172,6 +173,16 @@ where
                 timer.elapsed().as_millis()
             );
 
+            if let Some(reorged_height) = self.detect_reorg(&mut client, &chain_metadata).await? {
+                warn!(
+                    target: LOG_TARGET,
+                    "Base node {} no longer has our last seen tip at height {} in its chain, a reorg has occurred",
+                    base_node_id,
+                    reorged_height
+                );
+                self.publish_event(BaseNodeEvent::ReorgDetected(reorged_height));
+            }
+
             self.db.set_chain_metadata(chain_metadata.clone())?;
 
             let is_synced = tip_info.is_synced;
@@ -216,6 +227,31 @@ where
         Ok(())
     }
 
+    /// Returns the height of the last seen tip if it is no longer part of the base node's chain
+    async fn detect_reorg(
+        &self,
+        client: &mut BaseNodeWalletRpcClient,
+        new_metadata: &ChainMetadata,
+    ) -> Result<Option<u64>, BaseNodeMonitorError> {
+        let old_metadata = match self.state.read().await.chain_metadata.clone() {
+            Some(metadata) => metadata,
+            None => return Ok(None),
+        };
+        if old_metadata.best_block() == new_metadata.best_block() {
+            return Ok(None);
+        }
+        let old_height = old_metadata.height_of_longest_chain();
+        // If the chain grew, the old tip is still ours only if the base node has it at the same height
+        if new_metadata.height_of_longest_chain() > old_height {
+            let header = client.get_header_by_height(old_height).await?;
+            let header = BlockHeader::try_from(header).map_err(BaseNodeMonitorError::InvalidBaseNodeResponse)?;
+            if header.hash() == *old_metadata.best_block() {
+                return Ok(None);
+            }
+        }
+        Ok(Some(old_height))
+    }
+
     // returns true if a new block, otherwise false
     async fn update_state(&self, new_state: BaseNodeState) -> bool {
         let mut lock = self.state.write().await;
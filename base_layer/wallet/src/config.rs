@@ -40,6 +40,7 @@ use tari_utilities::SafePassword;
 use crate::{
     base_node_service::config::BaseNodeServiceConfig,
     output_manager_service::config::OutputManagerServiceConfig,
+    scheduled_payments_service::config::ScheduledPaymentsServiceConfig,
     transaction_service::config::TransactionServiceConfig,
 };
 
@@ -70,6 +71,9 @@ pub struct WalletConfig {
     /// The base_node_service_config config settings
     #[serde(rename = "base_node")]
     pub base_node_service_config: BaseNodeServiceConfig,
+    /// The scheduled_payments_service_config config settings
+    #[serde(rename = "scheduled_payments")]
+    pub scheduled_payments_service_config: ScheduledPaymentsServiceConfig,
     /// The relative path to store persistent data
     pub data_dir: PathBuf,
     /// The main wallet db file
@@ -131,6 +135,7 @@ impl Default for WalletConfig {
             buffer_size: 50_000,
             network: Default::default(),
             base_node_service_config: Default::default(),
+            scheduled_payments_service_config: Default::default(),
             data_dir: PathBuf::from_str("data/wallet").unwrap(),
             db_file: PathBuf::from_str("db/console_wallet.db").unwrap(),
             db_connection_pool_size: 16, // Note: Do not reduce this default number
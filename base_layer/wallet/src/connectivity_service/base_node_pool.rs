@@ -0,0 +1,333 @@
+//  Copyright 2023, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    cmp,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use log::*;
+use tari_comms::{peer_manager::Peer, types::CommsPublicKey};
+use tokio::sync::broadcast;
+
+use crate::util::watch::Watch;
+
+const LOG_TARGET: &str = "wallet::connectivity::base_node_pool";
+
+/// The number of blocks a base node may be behind the highest known base node before it is considered lagging
+const MAX_HEIGHT_LAG: u64 = 3;
+/// The time a base node is avoided for after each consecutive failure
+const FAILURE_COOLDOWN: Duration = Duration::from_secs(60);
+/// The maximum time a base node is avoided for after failing
+const MAX_FAILURE_COOLDOWN: Duration = Duration::from_secs(10 * 60);
+
+/// The reason that the active base node changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BaseNodeChangeReason {
+    /// The base node was set explicitly
+    Selected,
+    /// The base node was pinned as the preferred base node
+    Pinned,
+    /// The previous base node failed and the best scoring candidate was selected
+    Failover,
+}
+
+/// Emitted when the active base node changes
+#[derive(Debug, Clone)]
+pub struct BaseNodeChangedEvent {
+    pub previous: Option<Peer>,
+    pub current: Peer,
+    pub reason: BaseNodeChangeReason,
+}
+
+/// The score information of a candidate base node
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BaseNodeCandidateInfo {
+    pub public_key: CommsPublicKey,
+    pub latency: Option<Duration>,
+    pub height: Option<u64>,
+    pub consecutive_failures: u32,
+    pub is_healthy: bool,
+    pub is_pinned: bool,
+    pub is_active: bool,
+}
+
+#[derive(Debug, Clone)]
+struct BaseNodeCandidate {
+    peer: Peer,
+    latency: Option<Duration>,
+    height: Option<u64>,
+    consecutive_failures: u32,
+    last_failure: Option<Instant>,
+}
+
+impl BaseNodeCandidate {
+    fn new(peer: Peer) -> Self {
+        Self {
+            peer,
+            latency: None,
+            height: None,
+            consecutive_failures: 0,
+            last_failure: None,
+        }
+    }
+
+    fn is_healthy(&self, now: Instant) -> bool {
+        match self.last_failure {
+            Some(last_failure) => {
+                let cooldown = cmp::min(FAILURE_COOLDOWN * self.consecutive_failures, MAX_FAILURE_COOLDOWN);
+                now.saturating_duration_since(last_failure) >= cooldown
+            },
+            None => true,
+        }
+    }
+}
+
+/// A pool of candidate base nodes, scored by latency and chain height, used to choose a base node to fail over to.
+#[derive(Debug, Default)]
+pub struct BaseNodePool {
+    candidates: Vec<BaseNodeCandidate>,
+    pinned: Option<CommsPublicKey>,
+}
+
+impl BaseNodePool {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Adds a candidate base node to the pool. If the peer is already known, its peer information is updated.
+    pub fn add_peer(&mut self, peer: Peer) {
+        match self.find_mut(&peer.public_key) {
+            Some(candidate) => candidate.peer = peer,
+            None => self.candidates.push(BaseNodeCandidate::new(peer)),
+        }
+    }
+
+    /// Returns the peer with the given public key, if it is in the pool
+    pub fn get_peer(&self, public_key: &CommsPublicKey) -> Option<&Peer> {
+        self.find(public_key).map(|c| &c.peer)
+    }
+
+    /// Pins the given base node as the preferred base node. Returns false if the base node is not in the pool.
+    pub fn pin(&mut self, public_key: &CommsPublicKey) -> bool {
+        if self.find(public_key).is_none() {
+            return false;
+        }
+        self.pinned = Some(public_key.clone());
+        true
+    }
+
+    pub fn unpin(&mut self) {
+        self.pinned = None;
+    }
+
+    pub fn pinned(&self) -> Option<&Peer> {
+        self.pinned.as_ref().and_then(|pk| self.get_peer(pk))
+    }
+
+    /// Records a successful interaction with the given base node
+    pub fn record_success(&mut self, public_key: &CommsPublicKey, latency: Duration, height: u64) {
+        if let Some(candidate) = self.find_mut(public_key) {
+            candidate.latency = Some(latency);
+            candidate.height = Some(height);
+            candidate.consecutive_failures = 0;
+            candidate.last_failure = None;
+        }
+    }
+
+    /// Records a failed interaction with the given base node
+    pub fn record_failure(&mut self, public_key: &CommsPublicKey, now: Instant) {
+        if let Some(candidate) = self.find_mut(public_key) {
+            candidate.consecutive_failures = candidate.consecutive_failures.saturating_add(1);
+            candidate.last_failure = Some(now);
+            debug!(
+                target: LOG_TARGET,
+                "Base node {} failed ({} consecutive failure(s))", public_key, candidate.consecutive_failures
+            );
+        }
+    }
+
+    /// Returns the best base node to use, excluding the given base node. The pinned base node is returned if it is
+    /// healthy. Otherwise, healthy base nodes that are not lagging behind the highest known chain are preferred,
+    /// followed by the lowest latency. Base nodes without measurements rank after measured ones.
+    pub fn select_best(&self, exclude: Option<&CommsPublicKey>, now: Instant) -> Option<&Peer> {
+        let healthy = self
+            .candidates
+            .iter()
+            .filter(|c| Some(&c.peer.public_key) != exclude && c.is_healthy(now));
+        if let Some(pinned) = self.pinned.as_ref() {
+            if let Some(candidate) = healthy.clone().find(|c| &c.peer.public_key == pinned) {
+                return Some(&candidate.peer);
+            }
+        }
+        let max_height = self.candidates.iter().filter_map(|c| c.height).max().unwrap_or(0);
+        healthy
+            .min_by_key(|c| {
+                let is_lagging = c
+                    .height
+                    .map_or(false, |h| h.saturating_add(MAX_HEIGHT_LAG) < max_height);
+                (
+                    is_lagging,
+                    c.latency.is_none(),
+                    c.latency.unwrap_or_default(),
+                    c.consecutive_failures,
+                )
+            })
+            .map(|c| &c.peer)
+    }
+
+    /// Returns the score information of all base nodes in the pool
+    pub fn candidates(&self, active: Option<&CommsPublicKey>, now: Instant) -> Vec<BaseNodeCandidateInfo> {
+        self.candidates
+            .iter()
+            .map(|c| BaseNodeCandidateInfo {
+                public_key: c.peer.public_key.clone(),
+                latency: c.latency,
+                height: c.height,
+                consecutive_failures: c.consecutive_failures,
+                is_healthy: c.is_healthy(now),
+                is_pinned: self.pinned.as_ref() == Some(&c.peer.public_key),
+                is_active: active == Some(&c.peer.public_key),
+            })
+            .collect()
+    }
+
+    fn find(&self, public_key: &CommsPublicKey) -> Option<&BaseNodeCandidate> {
+        self.candidates.iter().find(|c| &c.peer.public_key == public_key)
+    }
+
+    fn find_mut(&mut self, public_key: &CommsPublicKey) -> Option<&mut BaseNodeCandidate> {
+        self.candidates.iter_mut().find(|c| &c.peer.public_key == public_key)
+    }
+}
+
+/// Makes the given peer the active base node and emits a [BaseNodeChangedEvent]
+pub(super) fn switch_base_node(
+    base_node_watch: &Watch<Option<Peer>>,
+    event_publisher: &broadcast::Sender<Arc<BaseNodeChangedEvent>>,
+    peer: Peer,
+    reason: BaseNodeChangeReason,
+) {
+    let previous = base_node_watch.borrow().clone();
+    if previous.as_ref().map(|p| &p.public_key) == Some(&peer.public_key) {
+        return;
+    }
+    info!(
+        target: LOG_TARGET,
+        "Active base node changed to {} ({:?})", peer.public_key, reason
+    );
+    base_node_watch.send(Some(peer.clone()));
+    // Sending fails only if there are no subscribers, which is fine
+    let _result = event_publisher.send(Arc::new(BaseNodeChangedEvent {
+        previous,
+        current: peer,
+        reason,
+    }));
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::OsRng;
+    use tari_comms::{
+        net_address::MultiaddressesWithStats,
+        peer_manager::{NodeId, PeerFeatures, PeerFlags},
+    };
+    use tari_crypto::keys::PublicKey;
+
+    use super::*;
+
+    fn random_peer() -> Peer {
+        let (_secret_key, public_key) = CommsPublicKey::random_keypair(&mut OsRng);
+        let node_id = NodeId::from_key(&public_key);
+        Peer::new(
+            public_key,
+            node_id,
+            MultiaddressesWithStats::default(),
+            PeerFlags::empty(),
+            PeerFeatures::COMMUNICATION_NODE,
+            Default::default(),
+            String::new(),
+        )
+    }
+
+    #[test]
+    fn it_selects_the_lowest_latency_node_that_is_not_lagging() {
+        let mut pool = BaseNodePool::new();
+        let (fast_lagging, slow, fast, unmeasured) = (random_peer(), random_peer(), random_peer(), random_peer());
+        for peer in [&fast_lagging, &slow, &fast, &unmeasured] {
+            pool.add_peer(peer.clone());
+        }
+        pool.record_success(&fast_lagging.public_key, Duration::from_millis(10), 90);
+        pool.record_success(&slow.public_key, Duration::from_millis(500), 100);
+        pool.record_success(&fast.public_key, Duration::from_millis(50), 99);
+
+        let now = Instant::now();
+        assert_eq!(pool.select_best(None, now).unwrap().public_key, fast.public_key);
+        assert_eq!(
+            pool.select_best(Some(&fast.public_key), now).unwrap().public_key,
+            slow.public_key
+        );
+    }
+
+    #[test]
+    fn it_avoids_failed_nodes_until_the_cooldown_passes() {
+        let mut pool = BaseNodePool::new();
+        let (a, b) = (random_peer(), random_peer());
+        pool.add_peer(a.clone());
+        pool.add_peer(b.clone());
+        pool.record_success(&a.public_key, Duration::from_millis(10), 100);
+        pool.record_success(&b.public_key, Duration::from_millis(100), 100);
+
+        let now = Instant::now();
+        pool.record_failure(&a.public_key, now);
+        assert_eq!(pool.select_best(None, now).unwrap().public_key, b.public_key);
+        assert_eq!(
+            pool.select_best(None, now + FAILURE_COOLDOWN).unwrap().public_key,
+            a.public_key
+        );
+
+        pool.record_failure(&b.public_key, now);
+        assert!(pool.select_best(None, now).is_none());
+    }
+
+    #[test]
+    fn it_prefers_the_pinned_node_while_it_is_healthy() {
+        let mut pool = BaseNodePool::new();
+        let (a, b) = (random_peer(), random_peer());
+        pool.add_peer(a.clone());
+        pool.add_peer(b.clone());
+        pool.record_success(&a.public_key, Duration::from_millis(10), 100);
+        pool.record_success(&b.public_key, Duration::from_millis(100), 100);
+        assert!(!pool.pin(&random_peer().public_key));
+        assert!(pool.pin(&b.public_key));
+
+        let now = Instant::now();
+        assert_eq!(pool.select_best(None, now).unwrap().public_key, b.public_key);
+        pool.record_failure(&b.public_key, now);
+        assert_eq!(pool.select_best(None, now).unwrap().public_key, a.public_key);
+
+        let candidates = pool.candidates(Some(&a.public_key), now);
+        let pinned = candidates.iter().find(|c| c.public_key == b.public_key).unwrap();
+        assert!(pinned.is_pinned && !pinned.is_healthy && !pinned.is_active);
+    }
+}
@@ -21,12 +21,14 @@
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use futures::channel::{mpsc, oneshot};
-use tari_comms::connectivity::ConnectivityError;
+use tari_comms::{connectivity::ConnectivityError, types::CommsPublicKey};
 
 #[derive(Debug, thiserror::Error)]
 pub enum WalletConnectivityError {
     #[error("Base node has not been set")]
     BaseNodeNotSet,
+    #[error("Base node {0} is not a known base node candidate")]
+    UnknownBaseNode(CommsPublicKey),
     #[error("Connectivity error: {0}")]
     ConnectivityError(#[from] ConnectivityError),
     #[error("Service is terminated and can no longer response to requests")]
@@ -20,16 +20,33 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use std::{
+    sync::{Arc, RwLock},
+    time::Instant,
+};
+
 use tari_comms::{
     peer_manager::{NodeId, Peer},
     protocol::rpc::RpcClientLease,
     types::CommsPublicKey,
 };
 use tari_core::base_node::{rpc::BaseNodeWalletRpcClient, sync::rpc::BaseNodeSyncRpcClient};
-use tokio::sync::{mpsc, oneshot, watch};
-
-use super::service::OnlineStatus;
-use crate::{connectivity_service::WalletConnectivityInterface, util::watch::Watch};
+use tokio::sync::{broadcast, mpsc, oneshot, watch};
+
+use super::{
+    base_node_pool::{
+        switch_base_node,
+        BaseNodeCandidateInfo,
+        BaseNodeChangeReason,
+        BaseNodeChangedEvent,
+        BaseNodePool,
+    },
+    service::OnlineStatus,
+};
+use crate::{
+    connectivity_service::{WalletConnectivityError, WalletConnectivityInterface},
+    util::watch::Watch,
+};
 
 pub enum WalletConnectivityRequest {
     ObtainBaseNodeWalletRpcClient(oneshot::Sender<RpcClientLease<BaseNodeWalletRpcClient>>),
@@ -41,6 +58,8 @@ pub struct WalletConnectivityHandle {
     sender: mpsc::Sender<WalletConnectivityRequest>,
     base_node_watch: Watch<Option<Peer>>,
     online_status_rx: watch::Receiver<OnlineStatus>,
+    base_node_pool: Arc<RwLock<BaseNodePool>>,
+    event_publisher: broadcast::Sender<Arc<BaseNodeChangedEvent>>,
 }
 
 impl WalletConnectivityHandle {
@@ -48,24 +67,75 @@ impl WalletConnectivityHandle {
         sender: mpsc::Sender<WalletConnectivityRequest>,
         base_node_watch: Watch<Option<Peer>>,
         online_status_rx: watch::Receiver<OnlineStatus>,
+        base_node_pool: Arc<RwLock<BaseNodePool>>,
+        event_publisher: broadcast::Sender<Arc<BaseNodeChangedEvent>>,
     ) -> Self {
         Self {
             sender,
             base_node_watch,
             online_status_rx,
+            base_node_pool,
+            event_publisher,
+        }
+    }
+
+    /// Adds base nodes that the wallet may fail over to if the active base node misbehaves. The peers must be known
+    /// to the peer manager so that they can be dialed.
+    pub fn add_base_node_candidates<I: IntoIterator<Item = Peer>>(&mut self, peers: I) {
+        let mut pool = acquire_write_lock!(self.base_node_pool);
+        for peer in peers {
+            pool.add_peer(peer);
+        }
+    }
+
+    /// Pins the given base node candidate as the preferred base node and makes it the active base node. The wallet
+    /// only fails over to another base node while the pinned base node is failing.
+    pub fn pin_base_node(&mut self, public_key: &CommsPublicKey) -> Result<(), WalletConnectivityError> {
+        let peer = {
+            let mut pool = acquire_write_lock!(self.base_node_pool);
+            if !pool.pin(public_key) {
+                return Err(WalletConnectivityError::UnknownBaseNode(public_key.clone()));
+            }
+            pool.get_peer(public_key).cloned()
+        };
+        if let Some(peer) = peer {
+            switch_base_node(
+                &self.base_node_watch,
+                &self.event_publisher,
+                peer,
+                BaseNodeChangeReason::Pinned,
+            );
         }
+        Ok(())
+    }
+
+    /// Removes the preferred base node, if any
+    pub fn unpin_base_node(&mut self) {
+        acquire_write_lock!(self.base_node_pool).unpin();
+    }
+
+    /// Returns the score information of all base node candidates
+    pub fn get_base_node_candidates(&self) -> Vec<BaseNodeCandidateInfo> {
+        let active = self.get_current_base_node_peer_public_key();
+        acquire_read_lock!(self.base_node_pool).candidates(active.as_ref(), Instant::now())
+    }
+
+    /// Returns a stream of events that are emitted whenever the active base node changes
+    pub fn get_base_node_changed_event_stream(&self) -> broadcast::Receiver<Arc<BaseNodeChangedEvent>> {
+        self.event_publisher.subscribe()
     }
 }
 
 #[async_trait::async_trait]
 impl WalletConnectivityInterface for WalletConnectivityHandle {
     fn set_base_node(&mut self, base_node_peer: Peer) {
-        if let Some(peer) = self.base_node_watch.borrow().as_ref() {
-            if peer.public_key == base_node_peer.public_key {
-                return;
-            }
-        }
-        self.base_node_watch.send(Some(base_node_peer));
+        acquire_write_lock!(self.base_node_pool).add_peer(base_node_peer.clone());
+        switch_base_node(
+            &self.base_node_watch,
+            &self.event_publisher,
+            base_node_peer,
+            BaseNodeChangeReason::Selected,
+        );
     }
 
     fn get_current_base_node_watcher(&self) -> watch::Receiver<Option<Peer>> {
@@ -28,13 +28,15 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use std::sync::{Arc, RwLock};
+
 use tari_service_framework::{async_trait, ServiceInitializationError, ServiceInitializer, ServiceInitializerContext};
-use tokio::sync::mpsc;
+use tokio::sync::{broadcast, mpsc};
 
 use super::{handle::WalletConnectivityHandle, service::WalletConnectivityService};
 use crate::{
     base_node_service::config::BaseNodeServiceConfig,
-    connectivity_service::service::OnlineStatus,
+    connectivity_service::{service::OnlineStatus, BaseNodePool},
     util::watch::Watch,
 };
 
@@ -54,10 +56,14 @@ impl ServiceInitializer for WalletConnectivityInitializer {
         let (sender, receiver) = mpsc::channel(5);
         let base_node_watch = Watch::new(None);
         let online_status_watch = Watch::new(OnlineStatus::Offline);
+        let base_node_pool = Arc::new(RwLock::new(BaseNodePool::new()));
+        let (event_publisher, _) = broadcast::channel(10);
         context.register_handle(WalletConnectivityHandle::new(
             sender,
             base_node_watch.clone(),
             online_status_watch.get_receiver(),
+            base_node_pool.clone(),
+            event_publisher.clone(),
         ));
 
         let config = self.config.clone();
@@ -67,9 +73,11 @@ impl ServiceInitializer for WalletConnectivityInitializer {
             let service = WalletConnectivityService::new(
                 config,
                 receiver,
-                base_node_watch.get_receiver(),
+                base_node_watch,
                 online_status_watch,
                 connectivity,
+                base_node_pool,
+                event_publisher,
             );
             service.start()
         });
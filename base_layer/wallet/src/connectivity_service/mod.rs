@@ -20,6 +20,9 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+mod base_node_pool;
+pub use base_node_pool::{BaseNodeCandidateInfo, BaseNodeChangeReason, BaseNodeChangedEvent, BaseNodePool};
+
 mod error;
 pub use error::WalletConnectivityError;
 
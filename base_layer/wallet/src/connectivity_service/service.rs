@@ -20,9 +20,15 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{mem, time::Duration};
+use std::{
+    convert::TryFrom,
+    mem,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
 
 use log::*;
+use tari_common_types::chain_metadata::ChainMetadata;
 use tari_comms::{
     connectivity::{ConnectivityError, ConnectivityRequester},
     peer_manager::{NodeId, Peer},
@@ -31,14 +37,21 @@ use tari_comms::{
 };
 use tari_core::base_node::{rpc::BaseNodeWalletRpcClient, sync::rpc::BaseNodeSyncRpcClient};
 use tokio::{
-    sync::{mpsc, oneshot, watch},
+    sync::{broadcast, mpsc, oneshot, watch},
     time,
     time::MissedTickBehavior,
 };
 
 use crate::{
     base_node_service::config::BaseNodeServiceConfig,
-    connectivity_service::{error::WalletConnectivityError, handle::WalletConnectivityRequest},
+    connectivity_service::{
+        base_node_pool::switch_base_node,
+        error::WalletConnectivityError,
+        handle::WalletConnectivityRequest,
+        BaseNodeChangeReason,
+        BaseNodeChangedEvent,
+        BaseNodePool,
+    },
     util::watch::Watch,
 };
 
@@ -56,10 +69,13 @@ pub struct WalletConnectivityService {
     config: BaseNodeServiceConfig,
     request_receiver: mpsc::Receiver<WalletConnectivityRequest>,
     connectivity: ConnectivityRequester,
+    base_node_watch_sender: Watch<Option<Peer>>,
     base_node_watch: watch::Receiver<Option<Peer>>,
     pools: Option<ClientPoolContainer>,
     online_status_watch: Watch<OnlineStatus>,
     pending_requests: Vec<ReplyOneshot>,
+    base_node_pool: Arc<RwLock<BaseNodePool>>,
+    event_publisher: broadcast::Sender<Arc<BaseNodeChangedEvent>>,
 }
 
 struct ClientPoolContainer {
@@ -71,18 +87,23 @@ impl WalletConnectivityService {
     pub(super) fn new(
         config: BaseNodeServiceConfig,
         request_receiver: mpsc::Receiver<WalletConnectivityRequest>,
-        base_node_watch: watch::Receiver<Option<Peer>>,
+        base_node_watch: Watch<Option<Peer>>,
         online_status_watch: Watch<OnlineStatus>,
         connectivity: ConnectivityRequester,
+        base_node_pool: Arc<RwLock<BaseNodePool>>,
+        event_publisher: broadcast::Sender<Arc<BaseNodeChangedEvent>>,
     ) -> Self {
         Self {
             config,
             request_receiver,
             connectivity,
-            base_node_watch,
+            base_node_watch: base_node_watch.get_receiver(),
+            base_node_watch_sender: base_node_watch,
             pools: None,
             pending_requests: Vec::new(),
             online_status_watch,
+            base_node_pool,
+            event_publisher,
         }
     }
 
@@ -98,7 +119,9 @@ impl WalletConnectivityService {
                 biased;
 
                 Ok(_) = self.base_node_watch.changed() => {
-                    if self.base_node_watch.borrow().is_some() {
+                    let current = self.base_node_watch.borrow().clone();
+                    if let Some(peer) = current {
+                        acquire_write_lock!(self.base_node_pool).add_peer(peer);
                         // This will block the rest until the connection is established. This is what we want.
                         self.setup_base_node_connection().await;
                     }
@@ -118,9 +141,12 @@ impl WalletConnectivityService {
     async fn check_connection(&mut self) {
         match self.pools.as_ref() {
             Some(pool) => {
-                if !pool.base_node_wallet_rpc_client.is_connected().await {
+                if pool.base_node_wallet_rpc_client.is_connected().await {
+                    self.update_base_node_score().await;
+                } else {
                     debug!(target: LOG_TARGET, "Peer connection lost. Attempting to reconnect...");
                     self.set_online_status(OnlineStatus::Offline);
+                    self.fail_over();
                     self.setup_base_node_connection().await;
                 }
             },
@@ -218,6 +244,82 @@ impl WalletConnectivityService {
         }
     }
 
+    /// Measures the latency and chain height of the active base node. If a pinned base node is preferred over the
+    /// active base node and it has recovered, the wallet switches back to it.
+    async fn update_base_node_score(&mut self) {
+        let public_key = match self.base_node_watch.borrow().as_ref() {
+            Some(peer) => peer.public_key.clone(),
+            None => return,
+        };
+        let mut client = match self.pools.as_ref() {
+            Some(pools) => match pools.base_node_wallet_rpc_client.get().await {
+                Ok(client) => client,
+                Err(e) => {
+                    debug!(target: LOG_TARGET, "Failed to obtain base node RPC client: {}", e);
+                    return;
+                },
+            },
+            None => return,
+        };
+        let timer = Instant::now();
+        let height = client
+            .get_tip_info()
+            .await
+            .ok()
+            .and_then(|tip_info| tip_info.metadata)
+            .and_then(|metadata| ChainMetadata::try_from(metadata).ok())
+            .map(|metadata| metadata.height_of_longest_chain());
+        let latency = client.get_last_request_latency().unwrap_or_else(|| timer.elapsed());
+
+        let pinned = {
+            let mut pool = acquire_write_lock!(self.base_node_pool);
+            match height {
+                Some(height) => pool.record_success(&public_key, latency, height),
+                None => pool.record_failure(&public_key, Instant::now()),
+            }
+            pool.select_best(None, Instant::now())
+                .filter(|best| pool.pinned().map(|p| &p.public_key) == Some(&best.public_key))
+                .cloned()
+        };
+        if let Some(pinned) = pinned {
+            if pinned.public_key != public_key {
+                self.switch_base_node(pinned, BaseNodeChangeReason::Pinned);
+            }
+        }
+    }
+
+    /// Records a failure for the active base node and switches to the best scoring healthy base node, if there is
+    /// one. Returns true if the base node was switched.
+    fn fail_over(&mut self) -> bool {
+        let public_key = match self.base_node_watch.borrow().as_ref() {
+            Some(peer) => peer.public_key.clone(),
+            None => return false,
+        };
+        let next = {
+            let now = Instant::now();
+            let mut pool = acquire_write_lock!(self.base_node_pool);
+            pool.record_failure(&public_key, now);
+            pool.select_best(Some(&public_key), now).cloned()
+        };
+        match next {
+            Some(peer) => {
+                warn!(
+                    target: LOG_TARGET,
+                    "Base node {} failed. Failing over to base node {}", public_key, peer.public_key
+                );
+                self.switch_base_node(peer, BaseNodeChangeReason::Failover);
+                true
+            },
+            None => false,
+        }
+    }
+
+    fn switch_base_node(&mut self, peer: Peer, reason: BaseNodeChangeReason) {
+        switch_base_node(&self.base_node_watch_sender, &self.event_publisher, peer, reason);
+        // The connection to the new base node is set up by the caller, so the change does not need to be handled again
+        self.base_node_watch.borrow_and_update();
+    }
+
     fn current_base_node(&self) -> Option<NodeId> {
         self.base_node_watch.borrow().as_ref().map(|p| p.node_id.clone())
     }
@@ -278,7 +380,9 @@ impl WalletConnectivityService {
                     if self.current_base_node().as_ref() == Some(&node_id) {
                         self.disconnect_base_node(node_id).await;
                         self.set_online_status(OnlineStatus::Offline);
-                        time::sleep(self.config.base_node_monitor_max_refresh_interval).await;
+                        if !self.fail_over() {
+                            time::sleep(self.config.base_node_monitor_max_refresh_interval).await;
+                        }
                     }
                     continue;
                 },
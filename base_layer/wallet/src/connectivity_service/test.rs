@@ -21,7 +21,10 @@
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use core::convert;
-use std::{iter, sync::Arc};
+use std::{
+    iter,
+    sync::{Arc, RwLock},
+};
 
 use futures::future;
 use tari_comms::{
@@ -38,13 +41,19 @@ use tari_comms::{
 use tari_shutdown::Shutdown;
 use tari_test_utils::runtime::spawn_until_shutdown;
 use tokio::{
-    sync::{mpsc, Barrier},
+    sync::{broadcast, mpsc, Barrier},
     task,
 };
 
 use super::service::WalletConnectivityService;
 use crate::{
-    connectivity_service::{OnlineStatus, WalletConnectivityHandle, WalletConnectivityInterface},
+    connectivity_service::{
+        BaseNodeChangeReason,
+        BaseNodePool,
+        OnlineStatus,
+        WalletConnectivityHandle,
+        WalletConnectivityInterface,
+    },
     util::watch::Watch,
 };
 
@@ -57,16 +66,26 @@ async fn setup() -> (
     let (tx, rx) = mpsc::channel(1);
     let base_node_watch = Watch::new(None);
     let online_status_watch = Watch::new(OnlineStatus::Offline);
-    let handle = WalletConnectivityHandle::new(tx, base_node_watch.clone(), online_status_watch.get_receiver());
+    let base_node_pool = Arc::new(RwLock::new(BaseNodePool::new()));
+    let (event_publisher, _) = broadcast::channel(10);
+    let handle = WalletConnectivityHandle::new(
+        tx,
+        base_node_watch.clone(),
+        online_status_watch.get_receiver(),
+        base_node_pool.clone(),
+        event_publisher.clone(),
+    );
     let (connectivity, mock) = create_connectivity_mock();
     let mock_state = mock.spawn();
     // let peer_manager = create_peer_manager(tempdir().unwrap());
     let service = WalletConnectivityService::new(
         Default::default(),
         rx,
-        base_node_watch.get_receiver(),
+        base_node_watch,
         online_status_watch,
         connectivity,
+        base_node_pool,
+        event_publisher,
     );
     let shutdown = spawn_until_shutdown(service.start());
 
@@ -77,6 +96,37 @@ async fn setup() -> (
     (handle, mock_server, mock_state, shutdown)
 }
 
+#[tokio::test]
+async fn it_emits_an_event_when_the_base_node_changes() {
+    let (mut handle, _mock_server, _mock_state, _shutdown) = setup().await;
+    let mut events = handle.get_base_node_changed_event_stream();
+    let first = build_node_identity(PeerFeatures::COMMUNICATION_NODE).to_peer();
+    let second = build_node_identity(PeerFeatures::COMMUNICATION_NODE).to_peer();
+
+    handle.set_base_node(first.clone());
+    handle.set_base_node(first.clone());
+    let event = events.try_recv().unwrap();
+    assert!(event.previous.is_none());
+    assert_eq!(event.current.public_key, first.public_key);
+    assert_eq!(event.reason, BaseNodeChangeReason::Selected);
+    assert!(events.try_recv().is_err());
+
+    handle.set_base_node(second.clone());
+    events.try_recv().unwrap();
+    let unknown = build_node_identity(PeerFeatures::COMMUNICATION_NODE).to_peer();
+    assert!(handle.pin_base_node(&unknown.public_key).is_err());
+    handle.pin_base_node(&first.public_key).unwrap();
+    let event = events.try_recv().unwrap();
+    assert_eq!(event.previous.unwrap().public_key, second.public_key);
+    assert_eq!(event.reason, BaseNodeChangeReason::Pinned);
+
+    let candidates = handle.get_base_node_candidates();
+    assert_eq!(candidates.len(), 2);
+    assert!(candidates
+        .iter()
+        .any(|c| c.public_key == first.public_key && c.is_pinned && c.is_active));
+}
+
 #[tokio::test]
 async fn it_dials_peer_when_base_node_is_set() {
     let (mut handle, mock_server, mock_state, _shutdown) = setup().await;
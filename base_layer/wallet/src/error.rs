@@ -32,7 +32,7 @@ use tari_comms::{
 };
 use tari_comms_dht::store_forward::StoreAndForwardError;
 use tari_contacts::contacts_service::error::ContactsServiceError;
-use tari_core::transactions::transaction_components::TransactionError;
+use tari_core::transactions::{payment_proof::PaymentProofError, transaction_components::TransactionError};
 use tari_key_manager::{error::KeyManagerError, key_manager_service::KeyManagerServiceError};
 use tari_p2p::{initialization::CommsInitializationError, services::liveness::error::LivenessError};
 use tari_service_framework::{reply_channel::TransportChannelError, ServiceInitializationError};
@@ -41,7 +41,9 @@ use thiserror::Error;
 
 use crate::{
     base_node_service::error::BaseNodeServiceError,
+    connectivity_service::WalletConnectivityError,
     output_manager_service::error::OutputManagerError,
+    scheduled_payments_service::error::ScheduledPaymentsServiceError,
     storage::database::DbKey,
     transaction_service::error::TransactionServiceError,
     utxo_scanner_service::error::UtxoScannerError,
@@ -77,10 +79,14 @@ pub enum WalletError {
     StoreAndForwardError(#[from] StoreAndForwardError),
     #[error("Connectivity error: `{0}`")]
     ConnectivityError(#[from] ConnectivityError),
+    #[error("Wallet connectivity error: `{0}`")]
+    WalletConnectivityError(#[from] WalletConnectivityError),
     #[error("Failed to initialize services: {0}")]
     ServiceInitializationError(#[from] ServiceInitializationError),
     #[error("Base Node Service error: {0}")]
     BaseNodeServiceError(#[from] BaseNodeServiceError),
+    #[error("Scheduled payments service error: {0}")]
+    ScheduledPaymentsServiceError(#[from] ScheduledPaymentsServiceError),
     #[error("Node ID error: `{0}`")]
     NodeIdError(#[from] NodeIdError),
     #[error("Error performing wallet recovery: '{0}'")]
@@ -103,6 +109,8 @@ pub enum WalletError {
     UnexpectedApiResponse { method: String, api: String },
     #[error("Public address not set for this wallet")]
     PublicAddressNotSet,
+    #[error("Payment proof error: {0}")]
+    PaymentProofError(#[from] PaymentProofError),
 }
 
 pub const LOG_TARGET: &str = "minotari::application";
@@ -183,6 +191,8 @@ pub enum WalletStorageError {
     RecoverySeedError(String),
     #[error("Bad encryption version: `{0}`")]
     BadEncryptionVersion(String),
+    #[error("The wallet database is locked")]
+    DatabaseLocked,
 }
 
 impl From<HexError> for WalletStorageError {
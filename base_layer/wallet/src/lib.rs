@@ -14,6 +14,7 @@ pub mod connectivity_service;
 pub mod error;
 mod operation_id;
 pub mod output_manager_service;
+pub mod scheduled_payments_service;
 pub mod storage;
 pub mod test_utils;
 pub mod transaction_service;
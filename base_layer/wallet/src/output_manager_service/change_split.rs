@@ -0,0 +1,177 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::cmp::max;
+
+use tari_core::transactions::tari_amount::MicroMinotari;
+
+use crate::output_manager_service::{config::OutputManagerServiceConfig, UtxoSelectionCriteria, UtxoSelectionOrdering};
+
+/// Decides how the change of an outbound transaction is spread over several outputs. While a transaction is
+/// unconfirmed its change is locked up, so a wallet holding a single large UTXO can not make another payment until
+/// the first one is mined. Splitting the change leaves the wallet with multiple outputs to fund concurrent sends.
+#[derive(Debug, Clone, Copy)]
+pub struct ChangeSplitter {
+    max_outputs: usize,
+    min_output_value: MicroMinotari,
+}
+
+impl ChangeSplitter {
+    pub fn new(config: &OutputManagerServiceConfig) -> Self {
+        Self {
+            max_outputs: config.change_output_split_count,
+            min_output_value: config.change_output_split_min_value,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.max_outputs > 1
+    }
+
+    /// Returns the values of the change outputs to create in addition to the regular change output, which receives
+    /// the remainder. The change is split into as many outputs as configured, but never into outputs that are worth
+    /// less than the configured minimum or than the fee required to spend them later (`spend_fee`). Every additional
+    /// output adds `output_fee` to the transaction, which is paid from the change.
+    ///
+    /// No split is made when specific outputs were requested, or when the smallest outputs are being selected first,
+    /// as those spends deliberately reduce the number of outputs the wallet holds.
+    pub fn split(
+        &self,
+        change: MicroMinotari,
+        selection_criteria: &UtxoSelectionCriteria,
+        output_fee: MicroMinotari,
+        spend_fee: MicroMinotari,
+    ) -> Vec<MicroMinotari> {
+        if !self.is_enabled() ||
+            !selection_criteria.filter.is_standard() ||
+            selection_criteria.ordering == UtxoSelectionOrdering::SmallestFirst
+        {
+            return Vec::new();
+        }
+        let dust_limit = max(self.min_output_value, spend_fee);
+        for num_outputs in (2..=self.max_outputs).rev() {
+            let extra_outputs = num_outputs as u64 - 1;
+            let value = match change.checked_sub(output_fee * extra_outputs) {
+                Some(remaining) => remaining / num_outputs as u64,
+                None => continue,
+            };
+            if value >= dust_limit {
+                return vec![value; num_outputs - 1];
+            }
+        }
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tari_common_types::types::Commitment;
+
+    use super::*;
+
+    fn splitter(max_outputs: usize, min_output_value: u64) -> ChangeSplitter {
+        ChangeSplitter::new(&OutputManagerServiceConfig {
+            change_output_split_count: max_outputs,
+            change_output_split_min_value: MicroMinotari::from(min_output_value),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn it_does_not_split_when_disabled() {
+        let splitter = splitter(1, 100);
+        assert!(!splitter.is_enabled());
+        assert!(splitter
+            .split(
+                MicroMinotari::from(100_000),
+                &UtxoSelectionCriteria::default(),
+                MicroMinotari::from(10),
+                MicroMinotari::from(10)
+            )
+            .is_empty());
+    }
+
+    #[test]
+    fn it_splits_change_evenly() {
+        let values = splitter(4, 100).split(
+            MicroMinotari::from(10_030),
+            &UtxoSelectionCriteria::default(),
+            MicroMinotari::from(10),
+            MicroMinotari::from(10),
+        );
+        assert_eq!(values, vec![MicroMinotari::from(2_500); 3]);
+    }
+
+    #[test]
+    fn it_reduces_the_number_of_outputs_to_avoid_dust() {
+        let values = splitter(10, 1_000).split(
+            MicroMinotari::from(3_500),
+            &UtxoSelectionCriteria::default(),
+            MicroMinotari::from(100),
+            MicroMinotari::from(10),
+        );
+        assert_eq!(values, vec![MicroMinotari::from(1_100); 2]);
+
+        let values = splitter(10, 1_000).split(
+            MicroMinotari::from(1_500),
+            &UtxoSelectionCriteria::default(),
+            MicroMinotari::from(100),
+            MicroMinotari::from(10),
+        );
+        assert!(values.is_empty());
+    }
+
+    #[test]
+    fn it_does_not_create_outputs_that_cost_more_to_spend_than_they_are_worth() {
+        let values = splitter(4, 1).split(
+            MicroMinotari::from(4_000),
+            &UtxoSelectionCriteria::default(),
+            MicroMinotari::from(0),
+            MicroMinotari::from(1_500),
+        );
+        assert_eq!(values, vec![MicroMinotari::from(2_000)]);
+    }
+
+    #[test]
+    fn it_respects_the_selection_strategy() {
+        let splitter = splitter(4, 100);
+        let change = MicroMinotari::from(100_000);
+        let fee = MicroMinotari::from(10);
+        assert!(splitter
+            .split(change, &UtxoSelectionCriteria::smallest_first(), fee, fee)
+            .is_empty());
+        assert!(splitter
+            .split(
+                change,
+                &UtxoSelectionCriteria::specific(vec![Commitment::default()]),
+                fee,
+                fee
+            )
+            .is_empty());
+        assert_eq!(
+            splitter
+                .split(change, &UtxoSelectionCriteria::largest_first(), fee, fee)
+                .len(),
+            3
+        );
+    }
+}
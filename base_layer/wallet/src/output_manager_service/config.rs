@@ -21,6 +21,7 @@
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use serde::{Deserialize, Serialize};
+use tari_core::transactions::tari_amount::MicroMinotari;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -35,6 +36,9 @@ pub struct OutputManagerServiceConfig {
     pub num_confirmations_required: u64,
     /// The number of batches the unconfirmed outputs will be divided into before being queried from the base node
     pub tx_validator_batch_size: usize,
+    /// The number of times a batch of outputs is re-queried from the base node if the query fails, before the
+    /// validation is aborted
+    pub tx_validator_batch_retries: usize,
     /// Wallets currently will choose the best outputs as inputs when spending, however since a lurking base node can
     /// generate a transaction graph of inputs to outputs with relative ease, a wallet may reveal its transaction
     /// history by including a (non-stealth address) one-sided payment.
@@ -43,6 +47,14 @@ pub struct OutputManagerServiceConfig {
     pub autoignore_onesided_utxos: bool,
     /// The number of seconds that have to pass for the wallet to run revalidation of invalid UTXOs on startup.
     pub num_of_seconds_to_revalidate_invalid_utxos: u64,
+    /// Record a balance snapshot for every block height at which outputs are validated, in addition to the daily
+    /// balance history
+    pub record_per_block_balance_history: bool,
+    /// The maximum number of outputs the change of an outbound transaction is split into, so that further payments
+    /// can be made while the transaction is unconfirmed. A value of 1 disables splitting.
+    pub change_output_split_count: usize,
+    /// Change is only split into outputs worth at least this value
+    pub change_output_split_min_value: MicroMinotari,
 }
 
 impl Default for OutputManagerServiceConfig {
@@ -52,8 +64,12 @@ impl Default for OutputManagerServiceConfig {
             event_channel_size: 250,
             num_confirmations_required: 3,
             tx_validator_batch_size: 100,
+            tx_validator_batch_retries: 3,
             autoignore_onesided_utxos: false,
             num_of_seconds_to_revalidate_invalid_utxos: 60 * 60 * 24 * 3,
+            record_per_block_balance_history: false,
+            change_output_split_count: 1,
+            change_output_split_min_value: MicroMinotari::from(100_000),
         }
     }
 }
@@ -207,6 +207,8 @@ pub enum OutputManagerStorageError {
     ByteArrayError(String),
     #[error("Aead error: `{0}`")]
     AeadError(String),
+    #[error("The wallet database is locked")]
+    DatabaseLocked,
     #[error("Tried to insert a script that already exists in the database")]
     DuplicateScript,
     #[error("Tari script error: {0}")]
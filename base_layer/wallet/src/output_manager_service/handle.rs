@@ -20,8 +20,9 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{fmt, fmt::Formatter, sync::Arc};
+use std::{collections::HashSet, fmt, fmt::Formatter, sync::Arc};
 
+use chrono::NaiveDateTime;
 use tari_common_types::{
     transaction::TxId,
     types::{Commitment, HashOutput, PublicKey},
@@ -44,10 +45,16 @@ use tower::Service;
 
 use crate::output_manager_service::{
     error::OutputManagerError,
-    service::{Balance, OutputStatusesByTxId},
+    service::{Balance, EncumbranceAudit, OutputStatusesByTxId, TransactionWeightEstimate},
     storage::{
         database::OutputBackendQuery,
-        models::{DbWalletOutput, KnownOneSidedPaymentScript, SpendingPriority},
+        models::{
+            BalanceHistoryResolution,
+            BalanceSnapshot,
+            DbWalletOutput,
+            KnownOneSidedPaymentScript,
+            SpendingPriority,
+        },
     },
     UtxoSelectionCriteria,
 };
@@ -56,6 +63,11 @@ use crate::output_manager_service::{
 #[allow(clippy::large_enum_variant)]
 pub enum OutputManagerRequest {
     GetBalance,
+    GetBalanceHistory {
+        resolution: BalanceHistoryResolution,
+        from: NaiveDateTime,
+        to: NaiveDateTime,
+    },
     AddOutput((Box<WalletOutput>, Option<SpendingPriority>)),
     AddOutputWithTxId((TxId, Box<WalletOutput>, Option<SpendingPriority>)),
     AddUnvalidatedOutput((TxId, Box<WalletOutput>, Option<SpendingPriority>)),
@@ -116,6 +128,12 @@ pub enum OutputManagerRequest {
         num_kernels: usize,
         num_outputs: usize,
     },
+    EstimateTransactionWeight {
+        amount: MicroMinotari,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+        output_features: Vec<OutputFeatures>,
+    },
 
     ScanForRecoverableOutputs(Vec<TransactionOutput>),
     ScanOutputs(Vec<TransactionOutput>),
@@ -130,6 +148,10 @@ pub enum OutputManagerRequest {
     CreateClaimShaAtomicSwapTransaction(HashOutput, PublicKey, MicroMinotari),
     CreateHtlcRefundTransaction(HashOutput, MicroMinotari),
     GetOutputStatusesByTxId(TxId),
+    AuditEncumbrances {
+        live_tx_ids: HashSet<TxId>,
+        repair: bool,
+    },
 }
 
 impl fmt::Display for OutputManagerRequest {
@@ -139,6 +161,9 @@ impl fmt::Display for OutputManagerRequest {
         use OutputManagerRequest::*;
         match self {
             GetBalance => write!(f, "GetBalance"),
+            GetBalanceHistory { resolution, from, to } => {
+                write!(f, "GetBalanceHistory ({:?}: {} - {})", resolution, from, to)
+            },
             AddOutput((v, _)) => write!(f, "AddOutput ({})", v.value),
             AddOutputWithTxId((t, v, _)) => write!(f, "AddOutputWithTxId ({}: {})", t, v.value),
             AddUnvalidatedOutput((t, v, _)) => {
@@ -196,6 +221,19 @@ impl fmt::Display for OutputManagerRequest {
                 "FeeEstimate(amount: {}, fee_per_gram: {}, num_kernels: {}, num_outputs: {}, selection_criteria: {:?})",
                 amount, fee_per_gram, num_kernels, num_outputs, selection_criteria
             ),
+            EstimateTransactionWeight {
+                amount,
+                selection_criteria,
+                fee_per_gram,
+                output_features,
+            } => write!(
+                f,
+                "EstimateTransactionWeight(amount: {}, fee_per_gram: {}, num_outputs: {}, selection_criteria: {:?})",
+                amount,
+                fee_per_gram,
+                output_features.len(),
+                selection_criteria
+            ),
             ScanForRecoverableOutputs(_) => write!(f, "ScanForRecoverableOutputs"),
             ScanOutputs(_) => write!(f, "ScanOutputs"),
             AddKnownOneSidedPaymentScript(_) => write!(f, "AddKnownOneSidedPaymentScript"),
@@ -220,6 +258,12 @@ impl fmt::Display for OutputManagerRequest {
             ),
 
             GetOutputStatusesByTxId(t) => write!(f, "GetOutputStatusesByTxId: {}", t),
+            AuditEncumbrances { live_tx_ids, repair } => write!(
+                f,
+                "AuditEncumbrances (live transactions: {}, repair: {})",
+                live_tx_ids.len(),
+                repair
+            ),
         }
     }
 }
@@ -228,6 +272,7 @@ impl fmt::Display for OutputManagerRequest {
 #[derive(Debug, Clone)]
 pub enum OutputManagerResponse {
     Balance(Balance),
+    BalanceHistory(Vec<BalanceSnapshot>),
     OutputAdded,
     ConvertedToTransactionOutput(Box<TransactionOutput>),
     OutputMetadataSignatureUpdated,
@@ -248,6 +293,7 @@ pub enum OutputManagerResponse {
     PublicRewindKeys(Box<PublicRewindKeys>),
     RecoveryByte(u8),
     FeeEstimate(MicroMinotari),
+    TransactionWeightEstimate(TransactionWeightEstimate),
     RewoundOutputs(Vec<RecoveredOutput>),
     ScanOutputs(Vec<RecoveredOutput>),
     AddKnownOneSidedPaymentScript,
@@ -257,6 +303,7 @@ pub enum OutputManagerResponse {
     CoinbaseAbandonedSet,
     ClaimHtlcTransaction((TxId, MicroMinotari, MicroMinotari, Transaction)),
     OutputStatusesByTxId(OutputStatusesByTxId),
+    EncumbranceAudit(EncumbranceAudit),
     CoinPreview((Vec<MicroMinotari>, MicroMinotari)),
 }
 
@@ -417,6 +464,23 @@ impl OutputManagerHandle {
         }
     }
 
+    /// Returns the balance snapshots of the given resolution that were recorded between `from` and `to`, oldest first
+    pub async fn get_balance_history(
+        &mut self,
+        resolution: BalanceHistoryResolution,
+        from: NaiveDateTime,
+        to: NaiveDateTime,
+    ) -> Result<Vec<BalanceSnapshot>, OutputManagerError> {
+        match self
+            .handle
+            .call(OutputManagerRequest::GetBalanceHistory { resolution, from, to })
+            .await??
+        {
+            OutputManagerResponse::BalanceHistory(history) => Ok(history),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
     pub async fn revalidate_all_outputs(&mut self) -> Result<u64, OutputManagerError> {
         match self.handle.call(OutputManagerRequest::RevalidateTxos).await?? {
             OutputManagerResponse::TxoValidationStarted(request_key) => Ok(request_key),
@@ -522,6 +586,30 @@ impl OutputManagerHandle {
         }
     }
 
+    /// Estimate the weight of a transaction sending an amount of MicroMinotari to outputs with the given features,
+    /// including the inputs that would be selected and a change output if one is needed.
+    pub async fn estimate_transaction_weight(
+        &mut self,
+        amount: MicroMinotari,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+        output_features: Vec<OutputFeatures>,
+    ) -> Result<TransactionWeightEstimate, OutputManagerError> {
+        match self
+            .handle
+            .call(OutputManagerRequest::EstimateTransactionWeight {
+                amount,
+                selection_criteria,
+                fee_per_gram,
+                output_features,
+            })
+            .await??
+        {
+            OutputManagerResponse::TransactionWeightEstimate(estimate) => Ok(estimate),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
     pub async fn confirm_pending_transaction(&mut self, tx_id: TxId) -> Result<(), OutputManagerError> {
         match self
             .handle
@@ -824,4 +912,22 @@ impl OutputManagerHandle {
             _ => Err(OutputManagerError::UnexpectedApiResponse),
         }
     }
+
+    /// Check that every encumbered output belongs to one of `live_tx_ids`, the wallet's pending and completed
+    /// transactions that have not been cancelled. When `repair` is set, the orphaned encumbrances are released and a
+    /// TXO validation is started so that the released outputs are checked against the base node's UTXO set.
+    pub async fn audit_encumbrances(
+        &mut self,
+        live_tx_ids: HashSet<TxId>,
+        repair: bool,
+    ) -> Result<EncumbranceAudit, OutputManagerError> {
+        match self
+            .handle
+            .call(OutputManagerRequest::AuditEncumbrances { live_tx_ids, repair })
+            .await??
+        {
+            OutputManagerResponse::EncumbranceAudit(audit) => Ok(audit),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
 }
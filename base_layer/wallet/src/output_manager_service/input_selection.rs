@@ -60,6 +60,22 @@ impl UtxoSelectionCriteria {
         }
     }
 
+    pub fn branch_and_bound() -> Self {
+        Self {
+            filter: UtxoSelectionFilter::Standard,
+            ordering: UtxoSelectionOrdering::BranchAndBound,
+            ..Default::default()
+        }
+    }
+
+    pub fn privacy_random() -> Self {
+        Self {
+            filter: UtxoSelectionFilter::Standard,
+            ordering: UtxoSelectionOrdering::PrivacyRandom,
+            ..Default::default()
+        }
+    }
+
     pub fn specific(commitments: Vec<Commitment>) -> Self {
         Self {
             filter: UtxoSelectionFilter::SpecificOutputs { commitments },
@@ -75,7 +91,8 @@ impl Display for UtxoSelectionCriteria {
     }
 }
 
-/// UTXO selection ordering
+/// UTXO selection ordering, which determines the [UtxoSelectionStrategy](super::UtxoSelectionStrategy) used to
+/// choose the inputs of a transaction
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum UtxoSelectionOrdering {
     /// The Default ordering is heuristic and depends on the requested value and the value of the available UTXOs.
@@ -88,6 +105,11 @@ pub enum UtxoSelectionOrdering {
     SmallestFirst,
     /// A strategy that selects the largest UTXOs first. Preferred when the amount is large
     LargestFirst,
+    /// Search for a set of UTXOs that covers the amount and fee without requiring a change output, falling back to
+    /// LargestFirst if there is none. Saves the fee of the change output and avoids creating dust.
+    BranchAndBound,
+    /// Select UTXOs in a random order, so that the choice of inputs reveals less about the wallet
+    PrivacyRandom,
 }
 
 impl Display for UtxoSelectionOrdering {
@@ -96,6 +118,8 @@ impl Display for UtxoSelectionOrdering {
             UtxoSelectionOrdering::SmallestFirst => write!(f, "Smallest"),
             UtxoSelectionOrdering::LargestFirst => write!(f, "Largest"),
             UtxoSelectionOrdering::Default => write!(f, "Default"),
+            UtxoSelectionOrdering::BranchAndBound => write!(f, "BranchAndBound"),
+            UtxoSelectionOrdering::PrivacyRandom => write!(f, "PrivacyRandom"),
         }
     }
 }
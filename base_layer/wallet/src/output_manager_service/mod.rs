@@ -20,6 +20,7 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+mod change_split;
 pub mod config;
 pub mod error;
 pub mod handle;
@@ -29,6 +30,16 @@ pub use input_selection::{UtxoSelectionCriteria, UtxoSelectionFilter, UtxoSelect
 
 mod recovery;
 pub mod resources;
+mod selection_strategy;
+pub use selection_strategy::{
+    BranchAndBound,
+    DefaultSelection,
+    LargestFirst,
+    PrivacyRandom,
+    SelectionTarget,
+    SmallestFirst,
+    UtxoSelectionStrategy,
+};
 pub mod service;
 pub mod storage;
 mod tasks;
@@ -0,0 +1,360 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::cmp::{Ordering, Reverse};
+
+use rand::{rngs::OsRng, seq::SliceRandom};
+use tari_core::transactions::{fee::Fee, tari_amount::MicroMinotari};
+
+use crate::output_manager_service::{storage::models::DbWalletOutput, UtxoSelectionOrdering};
+
+/// The value and fee that a selection of inputs has to cover
+#[derive(Debug, Clone, Copy)]
+pub struct SelectionTarget {
+    amount: MicroMinotari,
+    fee_calc: Fee,
+    fee_per_gram: MicroMinotari,
+    num_outputs: usize,
+    output_features_and_scripts_size: usize,
+    change_features_and_scripts_size: usize,
+}
+
+impl SelectionTarget {
+    pub fn new(
+        amount: MicroMinotari,
+        fee_calc: Fee,
+        fee_per_gram: MicroMinotari,
+        num_outputs: usize,
+        output_features_and_scripts_size: usize,
+        change_features_and_scripts_size: usize,
+    ) -> Self {
+        Self {
+            amount,
+            fee_calc,
+            fee_per_gram,
+            num_outputs,
+            output_features_and_scripts_size,
+            change_features_and_scripts_size,
+        }
+    }
+
+    pub fn amount(&self) -> MicroMinotari {
+        self.amount
+    }
+
+    /// The fee of the transaction when funded by `num_inputs` inputs, without a change output
+    pub fn fee_without_change(&self, num_inputs: usize) -> MicroMinotari {
+        self.fee_calc.calculate(
+            self.fee_per_gram,
+            1,
+            num_inputs,
+            self.num_outputs,
+            self.output_features_and_scripts_size,
+        )
+    }
+
+    /// The fee of the transaction when funded by `num_inputs` inputs, including a change output
+    pub fn fee_with_change(&self, num_inputs: usize) -> MicroMinotari {
+        self.fee_calc.calculate(
+            self.fee_per_gram,
+            1,
+            num_inputs,
+            self.num_outputs + 1,
+            self.output_features_and_scripts_size + self.change_features_and_scripts_size,
+        )
+    }
+
+    /// The fee added to the transaction by each input
+    pub fn input_fee(&self) -> MicroMinotari {
+        self.fee_calc.calculate(self.fee_per_gram, 0, 1, 0, 0)
+    }
+
+    /// The fee added to the transaction by a change output
+    pub fn change_fee(&self) -> MicroMinotari {
+        self.fee_with_change(0).saturating_sub(self.fee_without_change(0))
+    }
+
+    /// Returns true if `total` from `num_inputs` inputs pays the amount and fee exactly, or leaves enough to pay for a
+    /// change output
+    pub fn is_covered_by(&self, total: MicroMinotari, num_inputs: usize) -> bool {
+        total == self.amount + self.fee_without_change(num_inputs) ||
+            total > self.amount + self.fee_with_change(num_inputs)
+    }
+}
+
+/// A strategy for choosing which of the spendable outputs are used as inputs to fund a transaction
+pub trait UtxoSelectionStrategy: Send + Sync {
+    /// Chooses inputs from `candidates`, which are the spendable outputs matching the selection criteria. The
+    /// selection may fall short of the target if the candidates do not hold enough funds.
+    fn select(&self, candidates: Vec<DbWalletOutput>, target: &SelectionTarget) -> Vec<DbWalletOutput>;
+
+    /// The amount by which a selection may exceed the amount and fee without a change output, with the excess paid as
+    /// fee instead of being returned as change. Strategies that only stop once change can be paid for allow none.
+    fn max_excess_fee(&self, _target: &SelectionTarget) -> MicroMinotari {
+        MicroMinotari::zero()
+    }
+}
+
+impl UtxoSelectionOrdering {
+    /// Returns the selection strategy for this ordering
+    pub fn strategy(self) -> Box<dyn UtxoSelectionStrategy> {
+        match self {
+            UtxoSelectionOrdering::Default => Box::new(DefaultSelection),
+            UtxoSelectionOrdering::SmallestFirst => Box::new(SmallestFirst),
+            UtxoSelectionOrdering::LargestFirst => Box::new(LargestFirst),
+            UtxoSelectionOrdering::BranchAndBound => Box::new(BranchAndBound::default()),
+            UtxoSelectionOrdering::PrivacyRandom => Box::new(PrivacyRandom),
+        }
+    }
+}
+
+fn priority(output: &DbWalletOutput) -> u32 {
+    u32::from(output.spending_priority.clone())
+}
+
+/// Sorts the outputs by spending priority, highest first, and then by value using `cmp`
+fn sort_by_value<F>(candidates: &mut [DbWalletOutput], cmp: F)
+where F: Fn(&MicroMinotari, &MicroMinotari) -> Ordering {
+    candidates.sort_by(|a, b| {
+        priority(b)
+            .cmp(&priority(a))
+            .then_with(|| cmp(&a.wallet_output.value, &b.wallet_output.value))
+    });
+}
+
+/// Takes outputs in the given order until the target is covered
+fn accumulate<I>(candidates: I, target: &SelectionTarget) -> Vec<DbWalletOutput>
+where I: IntoIterator<Item = DbWalletOutput> {
+    let mut selected = Vec::new();
+    let mut total = MicroMinotari::zero();
+    for output in candidates {
+        total += output.wallet_output.value;
+        selected.push(output);
+        if target.is_covered_by(total, selected.len()) {
+            break;
+        }
+    }
+    selected
+}
+
+/// Selects the smallest outputs first. This consolidates small outputs at the cost of higher fees.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SmallestFirst;
+
+impl UtxoSelectionStrategy for SmallestFirst {
+    fn select(&self, mut candidates: Vec<DbWalletOutput>, target: &SelectionTarget) -> Vec<DbWalletOutput> {
+        sort_by_value(&mut candidates, |a, b| a.cmp(b));
+        accumulate(candidates, target)
+    }
+}
+
+/// Selects the largest outputs first, which minimises the number of inputs and so the fee
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LargestFirst;
+
+impl UtxoSelectionStrategy for LargestFirst {
+    fn select(&self, mut candidates: Vec<DbWalletOutput>, target: &SelectionTarget) -> Vec<DbWalletOutput> {
+        sort_by_value(&mut candidates, |a, b| b.cmp(a));
+        accumulate(candidates, target)
+    }
+}
+
+/// Selects the largest outputs first if the amount is larger than any single output, and the smallest outputs first
+/// otherwise
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultSelection;
+
+impl UtxoSelectionStrategy for DefaultSelection {
+    fn select(&self, candidates: Vec<DbWalletOutput>, target: &SelectionTarget) -> Vec<DbWalletOutput> {
+        let largest = candidates.iter().map(|o| o.wallet_output.value).max();
+        match largest {
+            Some(largest) if target.amount() > largest => LargestFirst.select(candidates, target),
+            _ => SmallestFirst.select(candidates, target),
+        }
+    }
+}
+
+/// Searches for a set of outputs that pays the amount and fee without needing a change output, with any excess being
+/// less than the cost of the change output. Avoiding the change output saves fees and does not create a new, possibly
+/// dust, output. If no such set is found, or outputs with a spending priority are present, the largest outputs are
+/// selected first.
+#[derive(Debug, Clone, Copy)]
+pub struct BranchAndBound {
+    max_tries: usize,
+}
+
+impl BranchAndBound {
+    pub fn new(max_tries: usize) -> Self {
+        Self { max_tries }
+    }
+}
+
+impl Default for BranchAndBound {
+    fn default() -> Self {
+        Self::new(100_000)
+    }
+}
+
+impl UtxoSelectionStrategy for BranchAndBound {
+    fn select(&self, candidates: Vec<DbWalletOutput>, target: &SelectionTarget) -> Vec<DbWalletOutput> {
+        if candidates.iter().any(|o| priority(o) > 0) {
+            return LargestFirst.select(candidates, target);
+        }
+
+        // Each input pays for itself, so the search is done over the value of each output less its input fee
+        let input_fee = target.input_fee();
+        let mut effective_values = candidates
+            .iter()
+            .enumerate()
+            .filter_map(|(i, o)| {
+                o.wallet_output
+                    .value
+                    .checked_sub(input_fee)
+                    .filter(|v| *v > MicroMinotari::zero())
+                    .map(|v| (i, v.as_u64()))
+            })
+            .collect::<Vec<_>>();
+        effective_values.sort_by(|a, b| b.1.cmp(&a.1));
+        let values = effective_values.iter().map(|(_, v)| *v).collect::<Vec<_>>();
+
+        let lower = (target.amount() + target.fee_without_change(0)).as_u64();
+        let upper = lower.saturating_add(self.max_excess_fee(target).as_u64());
+        match find_exact_match(&values, lower, upper, self.max_tries) {
+            Some(selection) => {
+                let mut selected_indexes = selection.into_iter().map(|i| effective_values[i].0).collect::<Vec<_>>();
+                selected_indexes.sort_unstable();
+                candidates
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(i, _)| selected_indexes.binary_search(i).is_ok())
+                    .map(|(_, o)| o)
+                    .collect()
+            },
+            None => LargestFirst.select(candidates, target),
+        }
+    }
+
+    fn max_excess_fee(&self, target: &SelectionTarget) -> MicroMinotari {
+        target.change_fee()
+    }
+}
+
+/// Depth first search for the subset of `values` (sorted largest first) whose sum is closest to `lower` while lying
+/// in `lower..=upper`. Returns the indexes of the subset, or None if no subset was found within `max_tries` steps.
+fn find_exact_match(values: &[u64], lower: u64, upper: u64, max_tries: usize) -> Option<Vec<usize>> {
+    let mut remaining = values.iter().fold(0u64, |acc, v| acc.saturating_add(*v));
+    let mut current = 0u64;
+    let mut included = Vec::<bool>::with_capacity(values.len());
+    let mut best: Option<(u64, Vec<usize>)> = None;
+
+    for _ in 0..max_tries {
+        let backtrack = if current.saturating_add(remaining) < lower || current > upper {
+            true
+        } else if current >= lower {
+            let excess = current - lower;
+            if best.as_ref().map_or(true, |(best_excess, _)| excess < *best_excess) {
+                let selection = included
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, included)| **included)
+                    .map(|(i, _)| i)
+                    .collect();
+                best = Some((excess, selection));
+            }
+            if excess == 0 {
+                break;
+            }
+            true
+        } else {
+            false
+        };
+
+        if backtrack {
+            // Undo trailing exclusions, then exclude the last included value instead
+            while let Some(false) = included.last() {
+                included.pop();
+                remaining += values[included.len()];
+            }
+            match included.last_mut() {
+                Some(last) => {
+                    *last = false;
+                    current -= values[included.len() - 1];
+                },
+                None => break,
+            }
+        } else {
+            let next = included.len();
+            remaining -= values[next];
+            current += values[next];
+            included.push(true);
+        }
+    }
+
+    best.map(|(_, selection)| selection)
+}
+
+/// Selects outputs in a random order, so that the inputs of a transaction do not reveal which outputs the wallet holds
+/// or how it chooses between them. Outputs with a spending priority are still selected first.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PrivacyRandom;
+
+impl UtxoSelectionStrategy for PrivacyRandom {
+    fn select(&self, mut candidates: Vec<DbWalletOutput>, target: &SelectionTarget) -> Vec<DbWalletOutput> {
+        candidates.shuffle(&mut OsRng);
+        candidates.sort_by_key(|o| Reverse(priority(o)));
+        accumulate(candidates, target)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_finds_an_exact_match() {
+        let values = [50, 40, 30, 20, 10];
+        let selection = find_exact_match(&values, 60, 60, 1000).unwrap();
+        let sum = selection.iter().map(|i| values[*i]).sum::<u64>();
+        assert_eq!(sum, 60);
+    }
+
+    #[test]
+    fn it_prefers_the_smallest_excess() {
+        let values = [100, 47, 31, 9];
+        let selection = find_exact_match(&values, 75, 90, 1000).unwrap();
+        assert_eq!(selection, vec![1, 2]);
+    }
+
+    #[test]
+    fn it_returns_none_without_a_match_in_range() {
+        assert!(find_exact_match(&[100, 50], 60, 70, 1000).is_none());
+        assert!(find_exact_match(&[10, 20], 60, 70, 1000).is_none());
+        assert!(find_exact_match(&[], 1, 2, 1000).is_none());
+    }
+
+    #[test]
+    fn it_gives_up_after_max_tries() {
+        let values = [50, 40, 30, 20, 10];
+        assert!(find_exact_match(&values, 60, 60, 2).is_none());
+        assert!(find_exact_match(&values, 60, 60, 100).is_some());
+    }
+}
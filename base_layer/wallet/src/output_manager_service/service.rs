@@ -20,7 +20,7 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{convert::TryInto, fmt, sync::Arc};
+use std::{collections::HashSet, convert::TryInto, fmt, sync::Arc};
 
 use diesel::result::{DatabaseErrorKind, Error as DieselError};
 use futures::{pin_mut, StreamExt};
@@ -53,6 +53,7 @@ use tari_core::{
             WalletOutputBuilder,
         },
         transaction_protocol::{sender::TransactionSenderMessage, TransactionMetadata},
+        weight::WeightCalculator,
         CoinbaseBuilder,
         CryptoFactories,
         ReceiverTransactionProtocol,
@@ -69,6 +70,7 @@ use crate::{
     base_node_service::handle::{BaseNodeEvent, BaseNodeServiceHandle},
     connectivity_service::WalletConnectivityInterface,
     output_manager_service::{
+        change_split::ChangeSplitter,
         config::OutputManagerServiceConfig,
         error::{OutputManagerError, OutputManagerProtocolError, OutputManagerStorageError},
         handle::{
@@ -81,6 +83,7 @@ use crate::{
         input_selection::UtxoSelectionCriteria,
         recovery::StandardUtxoRecoverer,
         resources::OutputManagerResources,
+        selection_strategy::SelectionTarget,
         storage::{
             database::{OutputBackendQuery, OutputManagerBackend, OutputManagerDatabase},
             models::{DbWalletOutput, KnownOneSidedPaymentScript, SpendingPriority},
@@ -232,6 +235,9 @@ where
                 self.get_balance(current_tip_for_time_lock_calculation)
                     .map(OutputManagerResponse::Balance)
             },
+            OutputManagerRequest::GetBalanceHistory { resolution, from, to } => Ok(
+                OutputManagerResponse::BalanceHistory(self.resources.db.fetch_balance_history(resolution, from, to)?),
+            ),
             OutputManagerRequest::GetRecipientTransaction(tsm) => self
                 .get_default_recipient_transaction(tsm)
                 .await
@@ -300,6 +306,15 @@ where
                 .fee_estimate(amount, selection_criteria, fee_per_gram, num_kernels, num_outputs)
                 .await
                 .map(OutputManagerResponse::FeeEstimate),
+            OutputManagerRequest::EstimateTransactionWeight {
+                amount,
+                selection_criteria,
+                fee_per_gram,
+                output_features,
+            } => self
+                .estimate_transaction_weight(amount, selection_criteria, fee_per_gram, output_features)
+                .await
+                .map(OutputManagerResponse::TransactionWeightEstimate),
             OutputManagerRequest::ConfirmPendingTransaction(tx_id) => self
                 .confirm_encumberance(tx_id)
                 .map(|_| OutputManagerResponse::PendingTransactionConfirmed),
@@ -425,6 +440,9 @@ where
                 let output_statuses_by_tx_id = self.get_output_status_by_tx_id(tx_id)?;
                 Ok(OutputManagerResponse::OutputStatusesByTxId(output_statuses_by_tx_id))
             },
+            OutputManagerRequest::AuditEncumbrances { live_tx_ids, repair } => self
+                .audit_encumbrances(&live_tx_ids, repair)
+                .map(OutputManagerResponse::EncumbranceAudit),
         }
     }
 
@@ -450,6 +468,78 @@ where
         })
     }
 
+    /// Find the encumbered outputs that do not belong to a live transaction and, if `repair` is set, release them.
+    /// Short-term encumbrances are not audited, as they belong to transactions that are still being created.
+    fn audit_encumbrances(
+        &mut self,
+        live_tx_ids: &HashSet<TxId>,
+        repair: bool,
+    ) -> Result<EncumbranceAudit, OutputManagerError> {
+        let outputs = self.resources.db.fetch_outputs_by(OutputBackendQuery {
+            status: vec![OutputStatus::EncumberedToBeReceived, OutputStatus::EncumberedToBeSpent],
+            ..Default::default()
+        })?;
+
+        let mut audit = EncumbranceAudit::default();
+        for output in outputs {
+            let tx_id = match output.status {
+                OutputStatus::EncumberedToBeReceived => output.received_in_tx_id,
+                _ => output.spent_in_tx_id,
+            };
+            if tx_id.map_or(false, |tx_id| live_tx_ids.contains(&tx_id)) {
+                continue;
+            }
+            warn!(
+                target: LOG_TARGET,
+                "Output {} is {} for a transaction that is not live (TxId: {:?})",
+                output.commitment.to_hex(),
+                output.status,
+                tx_id
+            );
+            audit.orphaned.push(OrphanedEncumbrance {
+                commitment: output.commitment,
+                status: output.status,
+                tx_id,
+            });
+        }
+
+        if !repair {
+            return Ok(audit);
+        }
+
+        // Releasing by transaction updates all of its outputs in a single database transaction
+        let mut orphaned_tx_ids = audit.orphaned.iter().filter_map(|o| o.tx_id).collect::<Vec<_>>();
+        let mut seen = HashSet::new();
+        orphaned_tx_ids.retain(|tx_id| seen.insert(*tx_id));
+        for tx_id in orphaned_tx_ids {
+            match self.resources.db.cancel_pending_transaction_outputs(tx_id) {
+                Ok(()) => audit.repaired_tx_ids.push(tx_id),
+                Err(e) => warn!(
+                    target: LOG_TARGET,
+                    "Could not release the orphaned encumbrances of TxId {}: {}", tx_id, e
+                ),
+            }
+        }
+        if !audit.repaired_tx_ids.is_empty() {
+            // The repair has already been applied, so a validation that cannot start now is left for the next one
+            match self.validate_outputs() {
+                Ok(request_key) => audit.validation_request_key = Some(request_key),
+                Err(e) => warn!(
+                    target: LOG_TARGET,
+                    "Could not start output validation after the encumbrance audit: {}", e
+                ),
+            }
+        }
+        info!(
+            target: LOG_TARGET,
+            "Encumbrance audit found {} orphaned output(s), released the outputs of {} transaction(s)",
+            audit.orphaned.len(),
+            audit.repaired_tx_ids.len()
+        );
+
+        Ok(audit)
+    }
+
     async fn claim_sha_atomic_swap_with_hash(
         &mut self,
         output_hash: HashOutput,
@@ -482,6 +572,12 @@ where
                     e
                 });
             },
+            BaseNodeEvent::ReorgDetected(_height) => {
+                trace!(
+                    target: LOG_TARGET,
+                    "Received Base Node reorg, txos are revalidated on the new tip"
+                );
+            },
         }
     }
 
@@ -873,6 +969,69 @@ where
         Ok(fee)
     }
 
+    /// Estimate the weight of a transaction sending `amount` to outputs with the given features, a default script and
+    /// covenant. The inputs are selected as they would be when sending, if there are not enough funds available we
+    /// estimate 1 input and no change, as for `fee_estimate`.
+    async fn estimate_transaction_weight(
+        &mut self,
+        amount: MicroMinotari,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+        output_features: Vec<OutputFeatures>,
+    ) -> Result<TransactionWeightEstimate, OutputManagerError> {
+        debug!(
+            target: LOG_TARGET,
+            "Estimating transaction weight. Amount: {}. Fee per gram: {}. Num outputs: {}",
+            amount,
+            fee_per_gram,
+            output_features.len()
+        );
+        let weighting = *self.resources.consensus_constants.transaction_weight_params();
+        let mut calculator = WeightCalculator::new(weighting).with_kernels(1);
+        let mut features_and_scripts_byte_size = 0;
+        for features in &output_features {
+            let size = features
+                .get_serialized_size()
+                .map_err(|e| OutputManagerError::ConversionError(e.to_string()))? +
+                TariScript::default()
+                    .get_serialized_size()
+                    .map_err(|e| OutputManagerError::ConversionError(e.to_string()))? +
+                Covenant::new()
+                    .get_serialized_size()
+                    .map_err(|e| OutputManagerError::ConversionError(e.to_string()))?;
+            features_and_scripts_byte_size += weighting.round_up_features_and_scripts_size(size);
+            calculator.add_output_with_features_and_scripts_size(size);
+        }
+
+        let (num_inputs, has_change_output) = match self
+            .select_utxos(
+                amount,
+                selection_criteria,
+                fee_per_gram,
+                output_features.len(),
+                features_and_scripts_byte_size,
+            )
+            .await
+        {
+            Ok(selection) => (selection.num_selected(), selection.requires_change_output()),
+            Err(OutputManagerError::FundsPending | OutputManagerError::NotEnoughFunds) => (1, false),
+            Err(e) => return Err(e),
+        };
+
+        let mut calculator = calculator.with_inputs(num_inputs);
+        if has_change_output {
+            calculator
+                .add_default_outputs(1)
+                .map_err(|e| OutputManagerError::ConversionError(e.to_string()))?;
+        }
+
+        Ok(TransactionWeightEstimate {
+            weight: calculator.weight(),
+            num_inputs,
+            has_change_output,
+        })
+    }
+
     /// Prepare a Sender Transaction Protocol for the amount and fee_per_gram specified. If required a change output
     /// will be produced.
     #[allow(clippy::too_many_lines)]
@@ -915,13 +1074,22 @@ where
         let input_selection = self
             .select_utxos(
                 amount,
-                selection_criteria,
+                selection_criteria.clone(),
                 fee_per_gram,
                 1,
                 features_and_scripts_byte_size,
             )
             .await?;
 
+        let split_change_values = if input_selection.requires_change_output() {
+            let change = input_selection
+                .total_value()
+                .saturating_sub(amount + input_selection.as_final_fee());
+            self.split_change_values(change, &selection_criteria, fee_per_gram)?
+        } else {
+            Vec::new()
+        };
+
         let mut builder = SenderTransactionProtocol::builder(
             self.resources.consensus_constants.clone(),
             self.resources.key_manager.clone(),
@@ -952,6 +1120,27 @@ where
             input_selection.num_selected()
         );
 
+        // Additional change outputs are added as outputs to self, with the remaining change going to the change output
+        let mut change_output = Vec::<DbWalletOutput>::with_capacity(split_change_values.len() + 1);
+        for value in split_change_values {
+            let (output, sender_offset_key_id) = self
+                .output_to_self(OutputFeatures::default(), value, Covenant::default())
+                .await?;
+            builder
+                .with_output(output.wallet_output.clone(), sender_offset_key_id)
+                .await
+                .map_err(|e| OutputManagerError::BuildError(e.to_string()))?;
+            change_output.push(output);
+        }
+        if !change_output.is_empty() {
+            debug!(
+                target: LOG_TARGET,
+                "Splitting the change of transaction (TxId: {}) into {} outputs",
+                tx_id,
+                change_output.len() + 1
+            );
+        }
+
         let (change_spending_key_id, _, change_script_key_id, change_script_public_key) =
             self.resources.key_manager.get_next_spend_and_script_key_ids().await?;
         builder.with_change_data(
@@ -968,7 +1157,6 @@ where
             .map_err(|e| OutputManagerError::BuildError(e.message))?;
 
         // If a change output was created add it to the pending_outputs list.
-        let mut change_output = Vec::<DbWalletOutput>::new();
         if input_selection.requires_change_output() {
             let wallet_output = stp.get_change_output()?.ok_or_else(|| {
                 OutputManagerError::BuildError(
@@ -1345,8 +1533,6 @@ where
             total_output_features_and_scripts_byte_size,
             selection_criteria
         );
-        let mut utxos = Vec::new();
-
         let fee_calc = self.get_fee_calc();
 
         // Attempt to get the chain tip height
@@ -1390,43 +1576,34 @@ where
 
         trace!(target: LOG_TARGET, "We found {} UTXOs to select from", uo.len());
 
-        let mut requires_change_output = false;
-        let mut utxos_total_value = MicroMinotari::from(0);
-        let mut fee_without_change = MicroMinotari::from(0);
-        let mut fee_with_change = MicroMinotari::from(0);
-        for o in uo {
-            utxos_total_value += o.wallet_output.value;
-
-            trace!(target: LOG_TARGET, "-- utxos_total_value = {:?}", utxos_total_value);
-            utxos.push(o);
-            // The assumption here is that the only output will be the payment output and change if required
-            fee_without_change = fee_calc.calculate(
-                fee_per_gram,
-                1,
-                utxos.len(),
-                num_outputs,
-                total_output_features_and_scripts_byte_size,
-            );
-            if utxos_total_value == amount + fee_without_change {
-                break;
-            }
-            fee_with_change = fee_calc.calculate(
-                fee_per_gram,
-                1,
-                utxos.len(),
-                num_outputs + 1,
-                total_output_features_and_scripts_byte_size + default_features_and_scripts_size,
-            );
+        let target = SelectionTarget::new(
+            amount,
+            fee_calc,
+            fee_per_gram,
+            num_outputs,
+            total_output_features_and_scripts_byte_size,
+            default_features_and_scripts_size,
+        );
+        let strategy = selection_criteria.ordering.strategy();
+        let utxos = strategy.select(uo, &target);
+        trace!(
+            target: LOG_TARGET,
+            "{} selection strategy chose {} UTXOs",
+            selection_criteria.ordering,
+            utxos.len()
+        );
 
-            trace!(target: LOG_TARGET, "-- amt+fee = {:?} {}", amount, fee_with_change);
-            if utxos_total_value > amount + fee_with_change {
-                requires_change_output = true;
-                break;
-            }
-        }
+        let utxos_total_value = utxos.iter().map(|o| o.wallet_output.value).sum::<MicroMinotari>();
+        let fee_without_change = target.fee_without_change(utxos.len());
+        let fee_with_change = target.fee_with_change(utxos.len());
+        // The assumption here is that the only output will be the payment output and change if required
+        let requires_change_output = utxos_total_value > amount + fee_with_change;
 
-        let perfect_utxo_selection = utxos_total_value == amount + fee_without_change;
-        let enough_spendable = utxos_total_value > amount + fee_with_change;
+        // Some strategies avoid the change output by paying a small excess as fee
+        let perfect_utxo_selection = utxos_total_value
+            .checked_sub(amount + fee_without_change)
+            .map_or(false, |excess| excess <= strategy.max_excess_fee(&target));
+        let enough_spendable = requires_change_output;
 
         if !perfect_utxo_selection && !enough_spendable {
             let current_tip_for_time_lock_calculation = chain_metadata.map(|cm| cm.height_of_longest_chain());
@@ -1469,6 +1646,35 @@ where
         Ok(())
     }
 
+    /// Returns the values of the additional change outputs to create for a transaction with the given change, see
+    /// [ChangeSplitter::split]
+    fn split_change_values(
+        &self,
+        change: MicroMinotari,
+        selection_criteria: &UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+    ) -> Result<Vec<MicroMinotari>, OutputManagerError> {
+        let splitter = ChangeSplitter::new(&self.resources.config);
+        if !splitter.is_enabled() {
+            return Ok(Vec::new());
+        }
+        let fee_calc = self.get_fee_calc();
+        let output_features_and_scripts_size = fee_calc.weighting().round_up_features_and_scripts_size(
+            OutputFeatures::default()
+                .get_serialized_size()
+                .map_err(|e| OutputManagerError::ConversionError(e.to_string()))? +
+                Covenant::default()
+                    .get_serialized_size()
+                    .map_err(|e| OutputManagerError::ConversionError(e.to_string()))? +
+                script!(PushPubKey(Box::new(PublicKey::default())))
+                    .get_serialized_size()
+                    .map_err(|e| OutputManagerError::ConversionError(e.to_string()))?,
+        );
+        let output_fee = fee_calc.calculate(fee_per_gram, 0, 0, 1, output_features_and_scripts_size);
+        let spend_fee = fee_calc.calculate(fee_per_gram, 0, 1, 0, 0);
+        Ok(splitter.split(change, selection_criteria, output_fee, spend_fee))
+    }
+
     fn default_features_and_scripts_size(&self) -> Result<usize, OutputManagerError> {
         Ok(self
             .resources
@@ -2515,6 +2721,17 @@ where
     }
 }
 
+/// The estimated weight of a transaction that has not been built yet
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransactionWeightEstimate {
+    /// The weight in grams
+    pub weight: u64,
+    /// The number of inputs that would be selected to fund the transaction
+    pub num_inputs: usize,
+    /// Whether a change output is included in the estimate
+    pub has_change_output: bool,
+}
+
 /// This struct holds the detailed balance of the Output Manager Service.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Balance {
@@ -2591,6 +2808,25 @@ impl UtxoSelection {
     }
 }
 
+/// An encumbered output that does not belong to any live transaction
+#[derive(Debug, Clone)]
+pub struct OrphanedEncumbrance {
+    pub commitment: Commitment,
+    pub status: OutputStatus,
+    /// The transaction the output is encumbered to, if any
+    pub tx_id: Option<TxId>,
+}
+
+/// The outcome of auditing the encumbered outputs against the wallet's live transactions
+#[derive(Debug, Clone, Default)]
+pub struct EncumbranceAudit {
+    pub orphaned: Vec<OrphanedEncumbrance>,
+    /// Transactions whose orphaned encumbrances were released
+    pub repaired_tx_ids: Vec<TxId>,
+    /// The request key of the TXO validation started to re-check the released outputs with the base node
+    pub validation_request_key: Option<u64>,
+}
+
 #[derive(Debug, Clone)]
 pub struct OutputStatusesByTxId {
     pub statuses: Vec<OutputStatus>,
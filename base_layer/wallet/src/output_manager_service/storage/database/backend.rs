@@ -1,6 +1,7 @@
 // Copyright 2022 The Tari Project
 // SPDX-License-Identifier: BSD-3-Clause
 
+use chrono::NaiveDateTime;
 use tari_common_types::{
     transaction::TxId,
     types::{Commitment, FixedHash},
@@ -13,7 +14,7 @@ use crate::output_manager_service::{
     service::Balance,
     storage::{
         database::{DbKey, DbValue, OutputBackendQuery, WriteOperation},
-        models::DbWalletOutput,
+        models::{BalanceHistoryResolution, BalanceSnapshot, DbWalletOutput},
     },
 };
 
@@ -95,6 +96,19 @@ pub trait OutputManagerBackend: Send + Sync + Clone {
     fn reinstate_cancelled_inbound_output(&self, tx_id: TxId) -> Result<(), OutputManagerStorageError>;
     /// Return the available, time locked, pending incoming and pending outgoing balance
     fn get_balance(&self, tip: Option<u64>) -> Result<Balance, OutputManagerStorageError>;
+    /// Records a balance snapshot, replacing any earlier snapshot in the same bucket of the given resolution
+    fn record_balance_snapshot(
+        &self,
+        snapshot: &BalanceSnapshot,
+        resolution: BalanceHistoryResolution,
+    ) -> Result<(), OutputManagerStorageError>;
+    /// Fetches the balance snapshots of the given resolution recorded between `from` and `to`, oldest first
+    fn fetch_balance_history(
+        &self,
+        resolution: BalanceHistoryResolution,
+        from: NaiveDateTime,
+        to: NaiveDateTime,
+    ) -> Result<Vec<BalanceSnapshot>, OutputManagerStorageError>;
     /// Import unvalidated output
     fn add_unvalidated_output(&self, output: DbWalletOutput, tx_id: TxId) -> Result<(), OutputManagerStorageError>;
     fn fetch_unspent_outputs_for_spending(
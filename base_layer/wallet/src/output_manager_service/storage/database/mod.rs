@@ -27,6 +27,7 @@ use std::{
 };
 
 pub use backend::OutputManagerBackend;
+use chrono::NaiveDateTime;
 use log::*;
 use tari_common_types::{
     transaction::TxId,
@@ -43,7 +44,7 @@ use crate::output_manager_service::{
     input_selection::UtxoSelectionCriteria,
     service::Balance,
     storage::{
-        models::{DbWalletOutput, KnownOneSidedPaymentScript},
+        models::{BalanceHistoryResolution, BalanceSnapshot, DbWalletOutput, KnownOneSidedPaymentScript},
         OutputStatus,
     },
 };
@@ -199,6 +200,23 @@ where T: OutputManagerBackend + 'static
         self.db.get_balance(current_tip_for_time_lock_calculation)
     }
 
+    pub fn record_balance_snapshot(
+        &self,
+        snapshot: &BalanceSnapshot,
+        resolution: BalanceHistoryResolution,
+    ) -> Result<(), OutputManagerStorageError> {
+        self.db.record_balance_snapshot(snapshot, resolution)
+    }
+
+    pub fn fetch_balance_history(
+        &self,
+        resolution: BalanceHistoryResolution,
+        from: NaiveDateTime,
+        to: NaiveDateTime,
+    ) -> Result<Vec<BalanceSnapshot>, OutputManagerStorageError> {
+        self.db.fetch_balance_history(resolution, from, to)
+    }
+
     /// This method is called when a transaction is built to be sent. It will encumber unspent outputs against a pending
     /// transaction in the short term.
     pub fn encumber_outputs(
@@ -20,7 +20,7 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::cmp::Ordering;
+use std::{cmp::Ordering, convert::TryFrom};
 
 use chrono::NaiveDateTime;
 use derivative::Derivative;
@@ -36,6 +36,7 @@ use tari_script::{ExecutionStack, TariScript};
 
 use crate::output_manager_service::{
     error::OutputManagerStorageError,
+    service::Balance,
     storage::{OutputSource, OutputStatus},
 };
 
@@ -157,3 +158,58 @@ impl PartialEq for KnownOneSidedPaymentScript {
         self.script_hash == other.script_hash
     }
 }
+
+// ---------------------------------------------------------------------------
+
+/// The granularity of recorded balance snapshots
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BalanceHistoryResolution {
+    /// The last balance recorded on each UTC day
+    Daily,
+    /// The balance recorded at each block height. These are only recorded if enabled in the config.
+    PerBlock,
+}
+
+impl BalanceHistoryResolution {
+    /// Returns the bucket a snapshot falls in. Only the latest snapshot in each bucket is kept.
+    pub fn bucket(self, block_height: u64, recorded_at: NaiveDateTime) -> i64 {
+        match self {
+            BalanceHistoryResolution::Daily => recorded_at.timestamp().div_euclid(SECONDS_PER_DAY),
+            BalanceHistoryResolution::PerBlock => block_height as i64,
+        }
+    }
+}
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+impl From<BalanceHistoryResolution> for i32 {
+    fn from(value: BalanceHistoryResolution) -> Self {
+        match value {
+            BalanceHistoryResolution::Daily => 0,
+            BalanceHistoryResolution::PerBlock => 1,
+        }
+    }
+}
+
+impl TryFrom<i32> for BalanceHistoryResolution {
+    type Error = OutputManagerStorageError;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(BalanceHistoryResolution::Daily),
+            1 => Ok(BalanceHistoryResolution::PerBlock),
+            _ => Err(OutputManagerStorageError::ConversionError {
+                reason: format!("Invalid balance history resolution {}", value),
+            }),
+        }
+    }
+}
+
+/// The wallet balance at a point in time
+#[derive(Debug, Clone, PartialEq)]
+pub struct BalanceSnapshot {
+    /// The chain tip height when the snapshot was recorded
+    pub block_height: u64,
+    pub recorded_at: NaiveDateTime,
+    pub balance: Balance,
+}
@@ -0,0 +1,103 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::convert::TryFrom;
+
+use chrono::NaiveDateTime;
+use diesel::{prelude::*, SqliteConnection};
+use tari_core::transactions::tari_amount::MicroMinotari;
+
+use crate::{
+    output_manager_service::{
+        error::OutputManagerStorageError,
+        service::Balance,
+        storage::models::{BalanceHistoryResolution, BalanceSnapshot},
+    },
+    schema::balance_snapshots,
+};
+
+#[derive(Clone, Debug, Queryable, Insertable, PartialEq)]
+#[diesel(table_name = balance_snapshots)]
+pub struct BalanceSnapshotSql {
+    resolution: i32,
+    bucket: i64,
+    block_height: i64,
+    recorded_at: NaiveDateTime,
+    available_balance: i64,
+    time_locked_balance: Option<i64>,
+    pending_incoming_balance: i64,
+    pending_outgoing_balance: i64,
+}
+
+impl BalanceSnapshotSql {
+    pub fn new(snapshot: &BalanceSnapshot, resolution: BalanceHistoryResolution) -> Self {
+        Self {
+            resolution: i32::from(resolution),
+            bucket: resolution.bucket(snapshot.block_height, snapshot.recorded_at),
+            block_height: snapshot.block_height as i64,
+            recorded_at: snapshot.recorded_at,
+            available_balance: snapshot.balance.available_balance.as_u64() as i64,
+            time_locked_balance: snapshot.balance.time_locked_balance.map(|v| v.as_u64() as i64),
+            pending_incoming_balance: snapshot.balance.pending_incoming_balance.as_u64() as i64,
+            pending_outgoing_balance: snapshot.balance.pending_outgoing_balance.as_u64() as i64,
+        }
+    }
+
+    /// Inserts the snapshot, replacing any earlier snapshot in the same bucket
+    pub fn set(&self, conn: &mut SqliteConnection) -> Result<(), OutputManagerStorageError> {
+        diesel::replace_into(balance_snapshots::table)
+            .values(self.clone())
+            .execute(conn)?;
+        Ok(())
+    }
+
+    /// Returns the snapshots of the given resolution recorded between `from` and `to` (inclusive), oldest first
+    pub fn index_range(
+        resolution: BalanceHistoryResolution,
+        from: NaiveDateTime,
+        to: NaiveDateTime,
+        conn: &mut SqliteConnection,
+    ) -> Result<Vec<Self>, OutputManagerStorageError> {
+        Ok(balance_snapshots::table
+            .filter(balance_snapshots::resolution.eq(i32::from(resolution)))
+            .filter(balance_snapshots::recorded_at.between(from, to))
+            .order(balance_snapshots::recorded_at.asc())
+            .load::<BalanceSnapshotSql>(conn)?)
+    }
+}
+
+impl TryFrom<BalanceSnapshotSql> for BalanceSnapshot {
+    type Error = OutputManagerStorageError;
+
+    fn try_from(entry: BalanceSnapshotSql) -> Result<Self, Self::Error> {
+        Ok(Self {
+            block_height: entry.block_height as u64,
+            recorded_at: entry.recorded_at,
+            balance: Balance {
+                available_balance: MicroMinotari::from(entry.available_balance as u64),
+                time_locked_balance: entry.time_locked_balance.map(|v| MicroMinotari::from(v as u64)),
+                pending_incoming_balance: MicroMinotari::from(entry.pending_incoming_balance as u64),
+                pending_outgoing_balance: MicroMinotari::from(entry.pending_outgoing_balance as u64),
+            },
+        })
+    }
+}
@@ -20,8 +20,13 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{convert::TryFrom, str::FromStr};
+use std::{
+    convert::TryFrom,
+    str::{from_utf8, FromStr},
+};
 
+use balance_snapshot_sql::BalanceSnapshotSql;
+use chacha20poly1305::XChaCha20Poly1305;
 use chrono::{NaiveDateTime, Utc};
 use derivative::Derivative;
 use diesel::{
@@ -35,6 +40,7 @@ pub use new_output_sql::NewOutputSql;
 pub use output_sql::OutputSql;
 use tari_common_sqlite::{sqlite_connection_pool::PooledDbConnection, util::diesel_ext::ExpectedRowsExtension};
 use tari_common_types::{
+    encryption::{decrypt_bytes_integral_nonce, encrypt_bytes_integral_nonce, Encryptable, SharedCipher},
     transaction::TxId,
     types::{Commitment, FixedHash},
 };
@@ -42,9 +48,14 @@ use tari_core::transactions::{
     key_manager::TariKeyId,
     transaction_components::{OutputType, TransactionOutput},
 };
-use tari_crypto::tari_utilities::{hex::Hex, ByteArray};
+use tari_crypto::tari_utilities::{
+    hex::{from_hex, Hex},
+    ByteArray,
+    Hidden,
+};
 use tari_script::{ExecutionStack, TariScript};
 use tokio::time::Instant;
+use zeroize::Zeroize;
 
 use crate::{
     output_manager_service::{
@@ -52,7 +63,7 @@ use crate::{
         service::Balance,
         storage::{
             database::{DbKey, DbKeyValuePair, DbValue, OutputBackendQuery, OutputManagerBackend, WriteOperation},
-            models::{DbWalletOutput, KnownOneSidedPaymentScript},
+            models::{BalanceHistoryResolution, BalanceSnapshot, DbWalletOutput, KnownOneSidedPaymentScript},
             OutputStatus,
         },
         UtxoSelectionCriteria,
@@ -60,19 +71,92 @@ use crate::{
     schema::{known_one_sided_payment_scripts, outputs},
     storage::sqlite_utilities::wallet_db_connection::WalletDbConnection,
 };
+mod balance_snapshot_sql;
 mod new_output_sql;
 mod output_sql;
 const LOG_TARGET: &str = "wallet::output_manager_service::database::wallet";
 
 /// A Sqlite backend for the Output Manager Service. The Backend is accessed via a connection pool to the Sqlite file.
+/// The cipher encrypts the spending and script key ids of the outputs and known scripts.
 #[derive(Clone)]
 pub struct OutputManagerSqliteDatabase {
     database_connection: WalletDbConnection,
+    cipher: SharedCipher,
 }
 
 impl OutputManagerSqliteDatabase {
-    pub fn new(database_connection: WalletDbConnection) -> Self {
-        Self { database_connection }
+    pub fn new<C: Into<SharedCipher>>(database_connection: WalletDbConnection, cipher: C) -> Self {
+        Self {
+            database_connection,
+            cipher: cipher.into(),
+        }
+    }
+
+    pub fn init<C: Into<SharedCipher>>(database_connection: WalletDbConnection, cipher: C) -> Self {
+        let db = Self::new(database_connection, cipher);
+        db.encrypt_plaintext_key_ids().expect("Key ids to be encrypted");
+        db
+    }
+
+    /// Returns the database cipher, or an error if the database is locked
+    fn unlocked_cipher(&self) -> Result<XChaCha20Poly1305, OutputManagerStorageError> {
+        self.cipher.get().ok_or(OutputManagerStorageError::DatabaseLocked)
+    }
+
+    /// Encrypts the key ids of rows written before they were stored encrypted. A stored key id that can't be decrypted
+    /// is a plaintext one, as the database cipher is derived from the wallet passphrase.
+    fn encrypt_plaintext_key_ids(&self) -> Result<(), OutputManagerStorageError> {
+        let cipher = self.unlocked_cipher()?;
+        let mut conn = self.database_connection.get_pooled_connection()?;
+
+        conn.transaction::<_, OutputManagerStorageError, _>(|conn| {
+            for output in OutputSql::index(conn)? {
+                if output.clone().decrypt(&cipher).is_ok() {
+                    continue;
+                }
+                let output = output.encrypt(&cipher).map_err(OutputManagerStorageError::AeadError)?;
+                diesel::update(outputs::table.filter(outputs::id.eq(output.id)))
+                    .set((
+                        outputs::spending_key.eq(output.spending_key),
+                        outputs::script_private_key.eq(output.script_private_key),
+                    ))
+                    .execute(conn)
+                    .num_rows_affected_or_not_found(1)?;
+            }
+
+            for script in KnownOneSidedPaymentScriptSql::index(conn)? {
+                if script.clone().decrypt(&cipher).is_ok() {
+                    continue;
+                }
+                let script = script.encrypt(&cipher).map_err(OutputManagerStorageError::AeadError)?;
+                diesel::update(
+                    known_one_sided_payment_scripts::table
+                        .filter(known_one_sided_payment_scripts::script_hash.eq(&script.script_hash)),
+                )
+                .set(known_one_sided_payment_scripts::private_key.eq(&script.private_key))
+                .execute(conn)
+                .num_rows_affected_or_not_found(1)?;
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Finds the output in the given state with the given spending key id. The key ids are encrypted under a random
+    /// nonce, so the outputs in that state are decrypted to compare them.
+    fn find_by_spending_key(
+        spending_key: &str,
+        status: OutputStatus,
+        cipher: &XChaCha20Poly1305,
+        conn: &mut SqliteConnection,
+    ) -> Result<Option<DbWalletOutput>, OutputManagerStorageError> {
+        for output in OutputSql::index_status(vec![status], conn)? {
+            let output = output.to_db_wallet_output(cipher)?;
+            if output.wallet_output.spending_key_id.to_string() == spending_key {
+                return Ok(Some(output));
+            }
+        }
+        Ok(None)
     }
 
     fn insert(
@@ -80,19 +164,20 @@ impl OutputManagerSqliteDatabase {
         key_value_pair: DbKeyValuePair,
         conn: &mut SqliteConnection,
     ) -> Result<(), OutputManagerStorageError> {
+        let cipher = self.unlocked_cipher()?;
         match key_value_pair {
             DbKeyValuePair::UnspentOutput(c, o) => {
                 if OutputSql::find_by_commitment_and_cancelled(&c.to_vec(), false, conn).is_ok() {
                     return Err(OutputManagerStorageError::DuplicateOutput);
                 }
-                let new_output = NewOutputSql::new(*o, OutputStatus::Unspent, None, None)?;
+                let new_output = NewOutputSql::new(*o, OutputStatus::Unspent, None, None, &cipher)?;
                 new_output.commit(conn)?
             },
             DbKeyValuePair::UnspentOutputWithTxId(c, (tx_id, o)) => {
                 if OutputSql::find_by_commitment_and_cancelled(&c.to_vec(), false, conn).is_ok() {
                     return Err(OutputManagerStorageError::DuplicateOutput);
                 }
-                let new_output = NewOutputSql::new(*o, OutputStatus::Unspent, Some(tx_id), None)?;
+                let new_output = NewOutputSql::new(*o, OutputStatus::Unspent, Some(tx_id), None, &cipher)?;
                 new_output.commit(conn)?
             },
             DbKeyValuePair::OutputToBeReceived(c, (tx_id, o, coinbase_block_height)) => {
@@ -104,12 +189,13 @@ impl OutputManagerSqliteDatabase {
                     OutputStatus::EncumberedToBeReceived,
                     Some(tx_id),
                     coinbase_block_height,
+                    &cipher,
                 )?;
                 new_output.commit(conn)?
             },
 
             DbKeyValuePair::KnownOneSidedPaymentScripts(script) => {
-                let script_sql = KnownOneSidedPaymentScriptSql::from_known_one_sided_payment_script(script)?;
+                let script_sql = KnownOneSidedPaymentScriptSql::from_known_one_sided_payment_script(script, &cipher)?;
                 if KnownOneSidedPaymentScriptSql::find(&script_sql.script_hash, conn).is_ok() {
                     return Err(OutputManagerStorageError::DuplicateScript);
                 }
@@ -124,34 +210,19 @@ impl OutputManagerBackend for OutputManagerSqliteDatabase {
     #[allow(clippy::cognitive_complexity)]
     #[allow(clippy::too_many_lines)]
     fn fetch(&self, key: &DbKey) -> Result<Option<DbValue>, OutputManagerStorageError> {
+        let cipher = self.unlocked_cipher()?;
         let start = Instant::now();
         let mut conn = self.database_connection.get_pooled_connection()?;
         let acquire_lock = start.elapsed();
 
         let result = match key {
-            DbKey::SpentOutput(k) => match OutputSql::find_status(k, OutputStatus::Spent, &mut conn) {
-                Ok(o) => Some(DbValue::SpentOutput(Box::new(o.to_db_wallet_output()?))),
-                Err(e) => {
-                    match e {
-                        OutputManagerStorageError::DieselError(DieselError::NotFound) => (),
-                        e => return Err(e),
-                    };
-                    None
-                },
-            },
-            DbKey::UnspentOutput(k) => match OutputSql::find_status(k, OutputStatus::Unspent, &mut conn) {
-                Ok(o) => Some(DbValue::UnspentOutput(Box::new(o.to_db_wallet_output()?))),
-                Err(e) => {
-                    match e {
-                        OutputManagerStorageError::DieselError(DieselError::NotFound) => (),
-                        e => return Err(e),
-                    };
-                    None
-                },
-            },
+            DbKey::SpentOutput(k) => Self::find_by_spending_key(k, OutputStatus::Spent, &cipher, &mut conn)?
+                .map(|o| DbValue::SpentOutput(Box::new(o))),
+            DbKey::UnspentOutput(k) => Self::find_by_spending_key(k, OutputStatus::Unspent, &cipher, &mut conn)?
+                .map(|o| DbValue::UnspentOutput(Box::new(o))),
             DbKey::UnspentOutputHash(hash) => {
                 match OutputSql::find_by_hash(hash.as_slice(), OutputStatus::Unspent, &mut conn) {
-                    Ok(o) => Some(DbValue::UnspentOutput(Box::new(o.to_db_wallet_output()?))),
+                    Ok(o) => Some(DbValue::UnspentOutput(Box::new(o.to_db_wallet_output(&cipher)?))),
                     Err(e) => {
                         match e {
                             OutputManagerStorageError::DieselError(DieselError::NotFound) => (),
@@ -163,7 +234,7 @@ impl OutputManagerBackend for OutputManagerSqliteDatabase {
             },
             DbKey::AnyOutputByCommitment(commitment) => {
                 match OutputSql::find_by_commitment(&commitment.to_vec(), &mut conn) {
-                    Ok(o) => Some(DbValue::AnyOutput(Box::new(o.to_db_wallet_output()?))),
+                    Ok(o) => Some(DbValue::AnyOutput(Box::new(o.to_db_wallet_output(&cipher)?))),
                     Err(e) => {
                         match e {
                             OutputManagerStorageError::DieselError(DieselError::NotFound) => (),
@@ -179,7 +250,7 @@ impl OutputManagerBackend for OutputManagerSqliteDatabase {
                 Some(DbValue::AnyOutputs(
                     outputs
                         .iter()
-                        .map(|o| o.clone().to_db_wallet_output())
+                        .map(|o| o.clone().to_db_wallet_output(&cipher))
                         .collect::<Result<Vec<_>, _>>()?,
                 ))
             },
@@ -192,7 +263,7 @@ impl OutputManagerBackend for OutputManagerSqliteDatabase {
                 Some(DbValue::UnspentOutputs(
                     outputs
                         .iter()
-                        .map(|o| o.clone().to_db_wallet_output())
+                        .map(|o| o.clone().to_db_wallet_output(&cipher))
                         .collect::<Result<Vec<_>, _>>()?,
                 ))
             },
@@ -202,7 +273,7 @@ impl OutputManagerBackend for OutputManagerSqliteDatabase {
                 Some(DbValue::SpentOutputs(
                     outputs
                         .iter()
-                        .map(|o| o.clone().to_db_wallet_output())
+                        .map(|o| o.clone().to_db_wallet_output(&cipher))
                         .collect::<Result<Vec<_>, _>>()?,
                 ))
             },
@@ -212,7 +283,7 @@ impl OutputManagerBackend for OutputManagerSqliteDatabase {
                 Some(DbValue::UnspentOutputs(
                     outputs
                         .iter()
-                        .map(|o| o.clone().to_db_wallet_output())
+                        .map(|o| o.clone().to_db_wallet_output(&cipher))
                         .collect::<Result<Vec<_>, _>>()?,
                 ))
             },
@@ -222,7 +293,7 @@ impl OutputManagerBackend for OutputManagerSqliteDatabase {
                 Some(DbValue::InvalidOutputs(
                     outputs
                         .iter()
-                        .map(|o| o.clone().to_db_wallet_output())
+                        .map(|o| o.clone().to_db_wallet_output(&cipher))
                         .collect::<Result<Vec<_>, _>>()?,
                 ))
             },
@@ -232,7 +303,7 @@ impl OutputManagerBackend for OutputManagerSqliteDatabase {
                 Some(DbValue::KnownOneSidedPaymentScripts(
                     known_one_sided_payment_scripts
                         .iter()
-                        .map(|script| script.clone().to_known_one_sided_payment_script())
+                        .map(|script| script.clone().to_known_one_sided_payment_script(&cipher))
                         .collect::<Result<Vec<_>, _>>()?,
                 ))
             },
@@ -252,26 +323,29 @@ impl OutputManagerBackend for OutputManagerSqliteDatabase {
     }
 
     fn fetch_with_features(&self, output_type: OutputType) -> Result<Vec<DbWalletOutput>, OutputManagerStorageError> {
+        let cipher = self.unlocked_cipher()?;
         let mut conn = self.database_connection.get_pooled_connection()?;
         let outputs = OutputSql::index_by_output_type(output_type, &mut conn)?;
 
         outputs
             .iter()
-            .map(|o| o.clone().to_db_wallet_output())
+            .map(|o| o.clone().to_db_wallet_output(&cipher))
             .collect::<Result<Vec<_>, _>>()
     }
 
     fn fetch_sorted_unspent_outputs(&self) -> Result<Vec<DbWalletOutput>, OutputManagerStorageError> {
+        let cipher = self.unlocked_cipher()?;
         let mut conn = self.database_connection.get_pooled_connection()?;
         let outputs = OutputSql::index_unspent(&mut conn)?;
 
         outputs
             .into_iter()
-            .map(|o| o.to_db_wallet_output())
+            .map(|o| o.to_db_wallet_output(&cipher))
             .collect::<Result<Vec<_>, _>>()
     }
 
     fn fetch_mined_unspent_outputs(&self) -> Result<Vec<DbWalletOutput>, OutputManagerStorageError> {
+        let cipher = self.unlocked_cipher()?;
         let start = Instant::now();
         let mut conn = self.database_connection.get_pooled_connection()?;
         let acquire_lock = start.elapsed();
@@ -289,11 +363,12 @@ impl OutputManagerBackend for OutputManagerSqliteDatabase {
 
         outputs
             .into_iter()
-            .map(|o| o.to_db_wallet_output())
+            .map(|o| o.to_db_wallet_output(&cipher))
             .collect::<Result<Vec<_>, _>>()
     }
 
     fn fetch_invalid_outputs(&self, timestamp: i64) -> Result<Vec<DbWalletOutput>, OutputManagerStorageError> {
+        let cipher = self.unlocked_cipher()?;
         let start = Instant::now();
         let mut conn = self.database_connection.get_pooled_connection()?;
         let acquire_lock = start.elapsed();
@@ -311,11 +386,12 @@ impl OutputManagerBackend for OutputManagerSqliteDatabase {
 
         outputs
             .into_iter()
-            .map(|o| o.to_db_wallet_output())
+            .map(|o| o.to_db_wallet_output(&cipher))
             .collect::<Result<Vec<_>, _>>()
     }
 
     fn fetch_unspent_mined_unconfirmed_outputs(&self) -> Result<Vec<DbWalletOutput>, OutputManagerStorageError> {
+        let cipher = self.unlocked_cipher()?;
         let start = Instant::now();
         let mut conn = self.database_connection.get_pooled_connection()?;
         let acquire_lock = start.elapsed();
@@ -333,11 +409,12 @@ impl OutputManagerBackend for OutputManagerSqliteDatabase {
 
         outputs
             .into_iter()
-            .map(|o| o.to_db_wallet_output())
+            .map(|o| o.to_db_wallet_output(&cipher))
             .collect::<Result<Vec<_>, _>>()
     }
 
     fn write(&self, op: WriteOperation) -> Result<Option<DbValue>, OutputManagerStorageError> {
+        let cipher = self.unlocked_cipher()?;
         let start = Instant::now();
         let mut conn = self.database_connection.get_pooled_connection()?;
         let acquire_lock = start.elapsed();
@@ -357,7 +434,7 @@ impl OutputManagerBackend for OutputManagerSqliteDatabase {
                         match OutputSql::find_by_commitment(&commitment.to_vec(), conn) {
                             Ok(o) => {
                                 o.delete(conn)?;
-                                Ok(Some(DbValue::AnyOutput(Box::new(o.to_db_wallet_output()?))))
+                                Ok(Some(DbValue::AnyOutput(Box::new(o.to_db_wallet_output(&cipher)?))))
                             },
                             Err(e) => match e {
                                 OutputManagerStorageError::DieselError(DieselError::NotFound) => Ok(None),
@@ -392,6 +469,7 @@ impl OutputManagerBackend for OutputManagerSqliteDatabase {
     }
 
     fn fetch_pending_incoming_outputs(&self) -> Result<Vec<DbWalletOutput>, OutputManagerStorageError> {
+        let cipher = self.unlocked_cipher()?;
         let start = Instant::now();
         let mut conn = self.database_connection.get_pooled_connection()?;
         let acquire_lock = start.elapsed();
@@ -416,7 +494,7 @@ impl OutputManagerBackend for OutputManagerSqliteDatabase {
         }
         outputs
             .iter()
-            .map(|o| o.clone().to_db_wallet_output())
+            .map(|o| o.clone().to_db_wallet_output(&cipher))
             .collect::<Result<Vec<_>, _>>()
     }
 
@@ -628,6 +706,7 @@ impl OutputManagerBackend for OutputManagerSqliteDatabase {
         outputs_to_send: &[DbWalletOutput],
         outputs_to_receive: &[DbWalletOutput],
     ) -> Result<(), OutputManagerStorageError> {
+        let cipher = self.unlocked_cipher()?;
         let start = Instant::now();
         let mut conn = self.database_connection.get_pooled_connection()?;
         let acquire_lock = start.elapsed();
@@ -672,6 +751,7 @@ impl OutputManagerBackend for OutputManagerSqliteDatabase {
                 OutputStatus::ShortTermEncumberedToBeReceived,
                 Some(tx_id),
                 None,
+                &cipher,
             )?;
             new_output.commit(&mut conn)?;
         }
@@ -759,6 +839,7 @@ impl OutputManagerBackend for OutputManagerSqliteDatabase {
     }
 
     fn get_last_mined_output(&self) -> Result<Option<DbWalletOutput>, OutputManagerStorageError> {
+        let cipher = self.unlocked_cipher()?;
         let start = Instant::now();
         let mut conn = self.database_connection.get_pooled_connection()?;
         let acquire_lock = start.elapsed();
@@ -774,12 +855,13 @@ impl OutputManagerBackend for OutputManagerSqliteDatabase {
             );
         }
         match output {
-            Some(o) => Ok(Some(o.to_db_wallet_output()?)),
+            Some(o) => Ok(Some(o.to_db_wallet_output(&cipher)?)),
             None => Ok(None),
         }
     }
 
     fn get_last_spent_output(&self) -> Result<Option<DbWalletOutput>, OutputManagerStorageError> {
+        let cipher = self.unlocked_cipher()?;
         let start = Instant::now();
         let mut conn = self.database_connection.get_pooled_connection()?;
         let acquire_lock = start.elapsed();
@@ -795,7 +877,7 @@ impl OutputManagerBackend for OutputManagerSqliteDatabase {
             );
         }
         match output {
-            Some(o) => Ok(Some(o.to_db_wallet_output()?)),
+            Some(o) => Ok(Some(o.to_db_wallet_output(&cipher)?)),
             None => Ok(None),
         }
     }
@@ -821,6 +903,28 @@ impl OutputManagerBackend for OutputManagerSqliteDatabase {
         result
     }
 
+    fn record_balance_snapshot(
+        &self,
+        snapshot: &BalanceSnapshot,
+        resolution: BalanceHistoryResolution,
+    ) -> Result<(), OutputManagerStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        BalanceSnapshotSql::new(snapshot, resolution).set(&mut conn)
+    }
+
+    fn fetch_balance_history(
+        &self,
+        resolution: BalanceHistoryResolution,
+        from: NaiveDateTime,
+        to: NaiveDateTime,
+    ) -> Result<Vec<BalanceSnapshot>, OutputManagerStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        BalanceSnapshotSql::index_range(resolution, from, to, &mut conn)?
+            .into_iter()
+            .map(BalanceSnapshot::try_from)
+            .collect()
+    }
+
     fn cancel_pending_transaction(&self, tx_id: TxId) -> Result<(), OutputManagerStorageError> {
         let start = Instant::now();
         let mut conn = self.database_connection.get_pooled_connection()?;
@@ -1027,6 +1131,7 @@ impl OutputManagerBackend for OutputManagerSqliteDatabase {
     }
 
     fn add_unvalidated_output(&self, output: DbWalletOutput, tx_id: TxId) -> Result<(), OutputManagerStorageError> {
+        let cipher = self.unlocked_cipher()?;
         let start = Instant::now();
         let mut conn = self.database_connection.get_pooled_connection()?;
         let acquire_lock = start.elapsed();
@@ -1034,7 +1139,7 @@ impl OutputManagerBackend for OutputManagerSqliteDatabase {
         if OutputSql::find_by_commitment_and_cancelled(&output.commitment.to_vec(), false, &mut conn).is_ok() {
             return Err(OutputManagerStorageError::DuplicateOutput);
         }
-        let new_output = NewOutputSql::new(output, OutputStatus::EncumberedToBeReceived, Some(tx_id), None)?;
+        let new_output = NewOutputSql::new(output, OutputStatus::EncumberedToBeReceived, Some(tx_id), None, &cipher)?;
         new_output.commit(&mut conn)?;
 
         if start.elapsed().as_millis() > 0 {
@@ -1056,6 +1161,7 @@ impl OutputManagerBackend for OutputManagerSqliteDatabase {
         amount: u64,
         tip_height: Option<u64>,
     ) -> Result<Vec<DbWalletOutput>, OutputManagerStorageError> {
+        let cipher = self.unlocked_cipher()?;
         let start = Instant::now();
         let mut conn = self.database_connection.get_pooled_connection()?;
         let acquire_lock = start.elapsed();
@@ -1071,26 +1177,28 @@ impl OutputManagerBackend for OutputManagerSqliteDatabase {
         );
         outputs
             .iter()
-            .map(|o| o.clone().to_db_wallet_output())
+            .map(|o| o.clone().to_db_wallet_output(&cipher))
             .collect::<Result<Vec<_>, _>>()
     }
 
     fn fetch_outputs_by_tx_id(&self, tx_id: TxId) -> Result<Vec<DbWalletOutput>, OutputManagerStorageError> {
+        let cipher = self.unlocked_cipher()?;
         let mut conn = self.database_connection.get_pooled_connection()?;
         let outputs = OutputSql::find_by_tx_id(tx_id, &mut conn)?;
 
         outputs
             .iter()
-            .map(|o| o.clone().to_db_wallet_output())
+            .map(|o| o.clone().to_db_wallet_output(&cipher))
             .collect::<Result<Vec<_>, _>>()
     }
 
     fn fetch_outputs_by(&self, q: OutputBackendQuery) -> Result<Vec<DbWalletOutput>, OutputManagerStorageError> {
+        let cipher = self.unlocked_cipher()?;
         let mut conn = self.database_connection.get_pooled_connection()?;
         Ok(OutputSql::fetch_outputs_by(q, &mut conn)?
             .into_iter()
             .filter_map(|x| {
-                x.to_db_wallet_output()
+                x.to_db_wallet_output(&cipher)
                     .map_err(|e| {
                         error!(
                             target: LOG_TARGET,
@@ -1254,26 +1362,30 @@ impl KnownOneSidedPaymentScriptSql {
     }
 
     /// Conversion from an KnownOneSidedPaymentScriptSQL to the datatype form
-    pub fn to_known_one_sided_payment_script(self) -> Result<KnownOneSidedPaymentScript, OutputManagerStorageError> {
-        let script_hash = self.script_hash.clone();
+    pub fn to_known_one_sided_payment_script(
+        self,
+        cipher: &XChaCha20Poly1305,
+    ) -> Result<KnownOneSidedPaymentScript, OutputManagerStorageError> {
+        let decrypted = self.decrypt(cipher).map_err(OutputManagerStorageError::AeadError)?;
+        let script_hash = decrypted.script_hash.clone();
         let private_key =
-            TariKeyId::from_str(&self.private_key).map_err(|_| OutputManagerStorageError::ConversionError {
+            TariKeyId::from_str(&decrypted.private_key).map_err(|_| OutputManagerStorageError::ConversionError {
                 reason: "Could not convert private key to TariKeyId".to_string(),
             })?;
 
-        let script = TariScript::from_bytes(&self.script).map_err(|_| {
+        let script = TariScript::from_bytes(&decrypted.script).map_err(|_| {
             error!(target: LOG_TARGET, "Could not create tari script from stored bytes");
             OutputManagerStorageError::ConversionError {
                 reason: "Tari Script could not be converted from bytes".to_string(),
             }
         })?;
-        let input = ExecutionStack::from_bytes(&self.input).map_err(|_| {
+        let input = ExecutionStack::from_bytes(&decrypted.input).map_err(|_| {
             error!(target: LOG_TARGET, "Could not create execution stack from stored bytes");
             OutputManagerStorageError::ConversionError {
                 reason: "ExecutionStack could not be converted from bytes".to_string(),
             }
         })?;
-        let script_lock_height = self.script_lock_height as u64;
+        let script_lock_height = decrypted.script_lock_height as u64;
 
         Ok(KnownOneSidedPaymentScript {
             script_hash,
@@ -1287,6 +1399,7 @@ impl KnownOneSidedPaymentScriptSql {
     /// Conversion from an KnownOneSidedPaymentScriptSQL to the datatype form
     pub fn from_known_one_sided_payment_script(
         known_script: KnownOneSidedPaymentScript,
+        cipher: &XChaCha20Poly1305,
     ) -> Result<Self, OutputManagerStorageError> {
         let script_lock_height = known_script.script_lock_height as i64;
         let script_hash = known_script.script_hash;
@@ -1302,13 +1415,57 @@ impl KnownOneSidedPaymentScriptSql {
             script_lock_height,
         };
 
-        Ok(payment_script)
+        payment_script
+            .encrypt(cipher)
+            .map_err(OutputManagerStorageError::AeadError)
+    }
+}
+
+impl Encryptable<XChaCha20Poly1305> for KnownOneSidedPaymentScriptSql {
+    fn domain(&self, field_name: &'static str) -> Vec<u8> {
+        [
+            Self::KNOWN_ONESIDED_PAYMENT_SCRIPT,
+            self.script_hash.as_slice(),
+            field_name.as_bytes(),
+        ]
+        .concat()
+    }
+
+    fn encrypt(mut self, cipher: &XChaCha20Poly1305) -> Result<Self, String> {
+        self.private_key = encrypt_string(cipher, self.domain("private_key"), &self.private_key)?;
+        Ok(self)
+    }
+
+    fn decrypt(mut self, cipher: &XChaCha20Poly1305) -> Result<Self, String> {
+        self.private_key = decrypt_string(cipher, self.domain("private_key"), &self.private_key)?;
+        Ok(self)
     }
 }
 
+/// Encrypts a string column, storing the ciphertext hex encoded
+fn encrypt_string(cipher: &XChaCha20Poly1305, domain: Vec<u8>, value: &str) -> Result<String, String> {
+    Ok(encrypt_bytes_integral_nonce(cipher, domain, Hidden::hide(value.as_bytes().to_vec()))?.to_hex())
+}
+
+/// Decrypts a string column encrypted with `encrypt_string`
+fn decrypt_string(cipher: &XChaCha20Poly1305, domain: Vec<u8>, value: &str) -> Result<String, String> {
+    let mut decrypted_value =
+        decrypt_bytes_integral_nonce(cipher, domain, &from_hex(value).map_err(|e| e.to_string())?)?;
+    let value = from_utf8(decrypted_value.as_slice())
+        .map_err(|e| e.to_string())?
+        .to_string();
+
+    // we zeroize the decrypted value
+    decrypted_value.zeroize();
+
+    Ok(value)
+}
+
 #[cfg(test)]
 mod test {
+    use std::mem::size_of;
 
+    use chacha20poly1305::{Key, KeyInit, XChaCha20Poly1305};
     use diesel::{sql_query, Connection, RunQueryDsl, SqliteConnection};
     use diesel_migrations::{EmbeddedMigrations, MigrationHarness};
     use rand::{rngs::OsRng, RngCore};
@@ -1373,6 +1530,10 @@ mod test {
 
         sql_query("PRAGMA foreign_keys = ON").execute(&mut conn).unwrap();
 
+        let mut key = [0u8; size_of::<Key>()];
+        OsRng.fill_bytes(&mut key);
+        let cipher = XChaCha20Poly1305::new(Key::from_slice(&key));
+
         let mut outputs = Vec::new();
         let mut outputs_spent = Vec::new();
         let mut outputs_unspent = Vec::new();
@@ -1383,7 +1544,7 @@ mod test {
             let uo = DbWalletOutput::from_wallet_output(uo, &key_manager, None, OutputSource::Unknown, None, None)
                 .await
                 .unwrap();
-            let o = NewOutputSql::new(uo, OutputStatus::Unspent, None, None).unwrap();
+            let o = NewOutputSql::new(uo, OutputStatus::Unspent, None, None, &cipher).unwrap();
             outputs.push(o.clone());
             outputs_unspent.push(o.clone());
             o.commit(&mut conn).unwrap();
@@ -1394,7 +1555,7 @@ mod test {
             let uo = DbWalletOutput::from_wallet_output(uo, &key_manager, None, OutputSource::Unknown, None, None)
                 .await
                 .unwrap();
-            let o = NewOutputSql::new(uo, OutputStatus::Spent, None, None).unwrap();
+            let o = NewOutputSql::new(uo, OutputStatus::Spent, None, None, &cipher).unwrap();
             outputs.push(o.clone());
             outputs_spent.push(o.clone());
             o.commit(&mut conn).unwrap();
@@ -1431,29 +1592,31 @@ mod test {
                 .collect::<Vec<String>>()
         );
 
+        let output = OutputSql::find_by_commitment(&outputs[0].commitment, &mut conn).unwrap();
+        assert_eq!(output.spending_key, outputs[0].spending_key);
         assert_eq!(
-            OutputSql::find(&outputs[0].spending_key, &mut conn)
-                .unwrap()
-                .spending_key,
-            outputs[0].spending_key
+            OutputSql::find_by_id(output.id, &mut conn).unwrap().commitment,
+            output.commitment
         );
 
-        assert_eq!(
-            OutputSql::find_status(&outputs[0].spending_key, OutputStatus::Unspent, &mut conn)
-                .unwrap()
-                .spending_key,
-            outputs[0].spending_key
+        // The key ids are only stored encrypted
+        let db_output = output.to_db_wallet_output(&cipher).unwrap();
+        assert_ne!(
+            outputs[0].spending_key,
+            db_output.wallet_output.spending_key_id.to_string()
+        );
+        assert_ne!(
+            outputs[0].script_private_key,
+            db_output.wallet_output.script_key_id.to_string()
         );
 
-        assert!(OutputSql::find_status(&outputs[0].spending_key, OutputStatus::Spent, &mut conn).is_err());
-
-        let _result = OutputSql::find(&outputs[4].spending_key, &mut conn)
+        let _result = OutputSql::find_by_commitment(&outputs[4].commitment, &mut conn)
             .unwrap()
             .delete(&mut conn);
 
         assert_eq!(OutputSql::index(&mut conn).unwrap().len(), 4);
 
-        let _updated1 = OutputSql::find(&outputs[0].spending_key, &mut conn)
+        let _updated1 = OutputSql::find_by_commitment(&outputs[0].commitment, &mut conn)
             .unwrap()
             .update(
                 UpdateOutput {
@@ -1465,7 +1628,7 @@ mod test {
             )
             .unwrap();
 
-        let _updated2 = OutputSql::find(&outputs[1].spending_key, &mut conn)
+        let _updated2 = OutputSql::find_by_commitment(&outputs[1].commitment, &mut conn)
             .unwrap()
             .update(
                 UpdateOutput {
@@ -21,21 +21,26 @@ use borsh::BorshSerialize;
 // CAUSED AND ON ANY THEORY OF LIABILITY,  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR
 // OTHERWISE) ARISING IN ANY WAY OUT OF THE  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH
 // DAMAGE.
+use chacha20poly1305::XChaCha20Poly1305;
 use derivative::Derivative;
 use diesel::{prelude::*, SqliteConnection};
-use tari_common_types::transaction::TxId;
+use tari_common_types::{encryption::Encryptable, transaction::TxId};
 use tari_utilities::ByteArray;
 
 use crate::{
     output_manager_service::{
         error::OutputManagerStorageError,
-        storage::{models::DbWalletOutput, OutputStatus},
+        storage::{
+            models::DbWalletOutput,
+            sqlite_db::{decrypt_string, encrypt_string},
+            OutputStatus,
+        },
     },
     schema::outputs,
 };
 
 /// This struct represents an Output in the Sql database. A distinct struct is required to define the Sql friendly
-/// equivalent datatypes for the members.
+/// equivalent datatypes for the members. The spending and script key ids are stored encrypted.
 #[derive(Clone, Derivative, Insertable, PartialEq)]
 #[derivative(Debug)]
 #[diesel(table_name = outputs)]
@@ -74,6 +79,7 @@ impl NewOutputSql {
         status: OutputStatus,
         received_in_tx_id: Option<TxId>,
         coinbase_block_height: Option<u64>,
+        cipher: &XChaCha20Poly1305,
     ) -> Result<Self, OutputManagerStorageError> {
         let mut covenant = Vec::new();
         BorshSerialize::serialize(&output.wallet_output.covenant, &mut covenant)?;
@@ -114,7 +120,7 @@ impl NewOutputSql {
             source: output.source as i32,
         };
 
-        Ok(output)
+        output.encrypt(cipher).map_err(OutputManagerStorageError::AeadError)
     }
 
     /// Write this struct to the database
@@ -123,3 +129,21 @@ impl NewOutputSql {
         Ok(())
     }
 }
+
+impl Encryptable<XChaCha20Poly1305> for NewOutputSql {
+    fn domain(&self, field_name: &'static str) -> Vec<u8> {
+        [Self::OUTPUT, self.commitment.as_slice(), field_name.as_bytes()].concat()
+    }
+
+    fn encrypt(mut self, cipher: &XChaCha20Poly1305) -> Result<Self, String> {
+        self.spending_key = encrypt_string(cipher, self.domain("spending_key"), &self.spending_key)?;
+        self.script_private_key = encrypt_string(cipher, self.domain("script_private_key"), &self.script_private_key)?;
+        Ok(self)
+    }
+
+    fn decrypt(mut self, cipher: &XChaCha20Poly1305) -> Result<Self, String> {
+        self.spending_key = decrypt_string(cipher, self.domain("spending_key"), &self.spending_key)?;
+        self.script_private_key = decrypt_string(cipher, self.domain("script_private_key"), &self.script_private_key)?;
+        Ok(self)
+    }
+}
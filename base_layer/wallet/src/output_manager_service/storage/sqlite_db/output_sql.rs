@@ -26,12 +26,14 @@ use std::{
 };
 
 use borsh::BorshDeserialize;
+use chacha20poly1305::XChaCha20Poly1305;
 use chrono::NaiveDateTime;
 use derivative::Derivative;
 use diesel::{prelude::*, sql_query, SqliteConnection};
 use log::*;
 use tari_common_sqlite::util::diesel_ext::ExpectedRowsExtension;
 use tari_common_types::{
+    encryption::Encryptable,
     transaction::TxId,
     types::{ComAndPubSignature, Commitment, FixedHash, PrivateKey, PublicKey, RangeProof},
 };
@@ -51,7 +53,7 @@ use crate::{
         storage::{
             database::{OutputBackendQuery, SortDirection},
             models::DbWalletOutput,
-            sqlite_db::{UpdateOutput, UpdateOutputSql},
+            sqlite_db::{decrypt_string, encrypt_string, UpdateOutput, UpdateOutputSql},
             OutputSource,
             OutputStatus,
         },
@@ -240,7 +242,10 @@ impl OutputSql {
 
         query = match selection_criteria.ordering {
             UtxoSelectionOrdering::SmallestFirst => query.then_order_by(outputs::value.asc()),
-            UtxoSelectionOrdering::LargestFirst => query.then_order_by(outputs::value.desc()),
+            // The branch and bound and random strategies do their own ordering of the selected candidates
+            UtxoSelectionOrdering::LargestFirst |
+            UtxoSelectionOrdering::BranchAndBound |
+            UtxoSelectionOrdering::PrivacyRandom => query.then_order_by(outputs::value.desc()),
             UtxoSelectionOrdering::Default => {
                 // NOTE: keeping filtering by `script_lock_height` and `maturity` for all modes
                 // lets get the max value for all utxos
@@ -365,10 +370,8 @@ impl OutputSql {
     }
 
     /// Find a particular Output, if it exists
-    pub fn find(spending_key: &str, conn: &mut SqliteConnection) -> Result<OutputSql, OutputManagerStorageError> {
-        Ok(outputs::table
-            .filter(outputs::spending_key.eq(spending_key.to_string()))
-            .first::<OutputSql>(conn)?)
+    pub fn find_by_id(id: i32, conn: &mut SqliteConnection) -> Result<OutputSql, OutputManagerStorageError> {
+        Ok(outputs::table.filter(outputs::id.eq(id)).first::<OutputSql>(conn)?)
     }
 
     pub fn find_by_tx_id(
@@ -578,18 +581,6 @@ impl OutputSql {
             .load(conn)?)
     }
 
-    /// Find a particular Output, if it exists and is in the specified Spent state
-    pub fn find_status(
-        spending_key: &str,
-        status: OutputStatus,
-        conn: &mut SqliteConnection,
-    ) -> Result<OutputSql, OutputManagerStorageError> {
-        Ok(outputs::table
-            .filter(outputs::status.eq(status as i32))
-            .filter(outputs::spending_key.eq(spending_key.to_string()))
-            .first::<OutputSql>(conn)?)
-    }
-
     /// Find a particular Output, if it exists and is in the specified Spent state
     pub fn find_by_hash(
         hash: &[u8],
@@ -614,8 +605,7 @@ impl OutputSql {
     }
 
     pub fn delete(&self, conn: &mut SqliteConnection) -> Result<(), OutputManagerStorageError> {
-        let num_deleted =
-            diesel::delete(outputs::table.filter(outputs::spending_key.eq(&self.spending_key))).execute(conn)?;
+        let num_deleted = diesel::delete(outputs::table.filter(outputs::id.eq(&self.id))).execute(conn)?;
 
         if num_deleted == 0 {
             return Err(OutputManagerStorageError::ValuesNotFound);
@@ -634,11 +624,18 @@ impl OutputSql {
             .execute(conn)
             .num_rows_affected_or_not_found(1)?;
 
-        OutputSql::find(&self.spending_key, conn)
+        OutputSql::find_by_id(self.id, conn)
+    }
+
+    /// Decrypts the key ids of the output and converts it to the datatype form
+    pub fn to_db_wallet_output(self, cipher: &XChaCha20Poly1305) -> Result<DbWalletOutput, OutputManagerStorageError> {
+        self.decrypt(cipher)
+            .map_err(OutputManagerStorageError::AeadError)?
+            .decrypted_to_db_wallet_output()
     }
 
     #[allow(clippy::too_many_lines)]
-    pub fn to_db_wallet_output(self) -> Result<DbWalletOutput, OutputManagerStorageError> {
+    fn decrypted_to_db_wallet_output(self) -> Result<DbWalletOutput, OutputManagerStorageError> {
         let features: OutputFeatures =
             serde_json::from_str(&self.features_json).map_err(|s| OutputManagerStorageError::ConversionError {
                 reason: format!("Could not convert json into OutputFeatures:{}", s),
@@ -788,3 +785,21 @@ impl OutputSql {
         })
     }
 }
+
+impl Encryptable<XChaCha20Poly1305> for OutputSql {
+    fn domain(&self, field_name: &'static str) -> Vec<u8> {
+        [Self::OUTPUT, self.commitment.as_slice(), field_name.as_bytes()].concat()
+    }
+
+    fn encrypt(mut self, cipher: &XChaCha20Poly1305) -> Result<Self, String> {
+        self.spending_key = encrypt_string(cipher, self.domain("spending_key"), &self.spending_key)?;
+        self.script_private_key = encrypt_string(cipher, self.domain("script_private_key"), &self.script_private_key)?;
+        Ok(self)
+    }
+
+    fn decrypt(mut self, cipher: &XChaCha20Poly1305) -> Result<Self, String> {
+        self.spending_key = decrypt_string(cipher, self.domain("spending_key"), &self.spending_key)?;
+        self.script_private_key = decrypt_string(cipher, self.domain("script_private_key"), &self.script_private_key)?;
+        Ok(self)
+    }
+}
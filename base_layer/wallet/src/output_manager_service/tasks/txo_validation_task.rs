@@ -19,22 +19,18 @@
 // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
-use std::{
-    collections::HashMap,
-    convert::{TryFrom, TryInto},
-    sync::Arc,
-};
+use std::{convert::TryInto, sync::Arc};
 
 use chrono::{Duration, Utc};
 use log::*;
-use tari_common_types::types::{BlockHash, FixedHash};
+use tari_common_types::types::BlockHash;
 use tari_comms::{peer_manager::Peer, protocol::rpc::RpcError::RequestFailed};
 use tari_core::{
     base_node::rpc::BaseNodeWalletRpcClient,
     blocks::BlockHeader,
-    proto::base_node::{QueryDeletedRequest, UtxoQueryRequest},
+    proto::base_node::{QueryDeletedRequest, QueryUtxoStatusesRequest, QueryUtxoStatusesResponse, UtxoStatus},
 };
-use tari_utilities::hex::Hex;
+use tari_utilities::{hex::Hex, ByteArray};
 use tokio::sync::watch;
 
 use crate::{
@@ -45,7 +41,7 @@ use crate::{
         handle::{OutputManagerEvent, OutputManagerEventSender},
         storage::{
             database::{OutputManagerBackend, OutputManagerDatabase},
-            models::DbWalletOutput,
+            models::{BalanceHistoryResolution, BalanceSnapshot, DbWalletOutput},
         },
     },
 };
@@ -111,6 +107,8 @@ where
 
         self.update_invalid_outputs(&mut base_node_client).await?;
 
+        self.record_balance_snapshot(&mut base_node_client).await;
+
         self.publish_event(OutputManagerEvent::TxoValidationSuccess(self.operation_id));
         debug!(
             target: LOG_TARGET,
@@ -521,60 +519,91 @@ where
         ),
         OutputManagerError,
     > {
-        let batch_hashes = batch.iter().map(|o| o.hash.to_vec()).collect();
-        trace!(
-            target: LOG_TARGET,
-            "UTXO hashes queried from base node: {:?}",
-            batch.iter().map(|o| o.hash.to_hex()).collect::<Vec<String>>()
-        );
-
-        let batch_response = base_node_client
-            .utxo_query(UtxoQueryRequest {
-                output_hashes: batch_hashes,
-            })
-            .await?;
+        let batch_response = self.query_utxo_statuses(batch, base_node_client).await?;
+        if batch_response.statuses.len() != batch.len() {
+            return Err(OutputManagerError::InconsistentBaseNodeDataError(
+                "Base node returned a different number of UTXO statuses than were queried",
+            ));
+        }
 
         let mut mined = vec![];
         let mut unmined = vec![];
-
-        let mut returned_outputs = HashMap::new();
-        for output_proto in &batch_response.responses {
-            match FixedHash::try_from(output_proto.output_hash.clone()) {
-                Ok(v) => {
-                    returned_outputs.insert(v, output_proto);
-                },
+        for (output, status) in batch.iter().zip(&batch_response.statuses) {
+            if status.commitment != output.commitment.as_bytes() {
+                return Err(OutputManagerError::InconsistentBaseNodeDataError(
+                    "Base node returned UTXO statuses in a different order than were queried",
+                ));
+            }
+            // Spent outputs are still mined, whether they have been spent is checked by the deleted query
+            if status.status == i32::from(UtxoStatus::NotFound) {
+                unmined.push(output.clone());
+                continue;
+            }
+            // A commitment can be reused, so the output the base node found must be the one this wallet holds
+            if status.output_hash != output.hash.as_slice() {
+                debug!(
+                    target: LOG_TARGET,
+                    "Output with commitment {} was mined with a different hash (Operation ID: {})",
+                    output.commitment.to_hex(),
+                    self.operation_id
+                );
+                unmined.push(output.clone());
+                continue;
+            }
+            match status.mined_in_block.clone().try_into() {
+                Ok(block_hash) => mined.push((
+                    output.clone(),
+                    status.mined_height,
+                    block_hash,
+                    status.mmr_position,
+                    status.mined_timestamp,
+                )),
                 Err(_) => {
                     warn!(
                         target: LOG_TARGET,
-                        "Malformed utxo hash received from node: {:?}", output_proto
+                        "Malformed block hash received from node: {:?}", status
                     )
                 },
             };
         }
 
-        for output in batch {
-            if let Some(returned_output) = returned_outputs.get(&output.hash) {
-                match returned_output.mined_in_block.clone().try_into() {
-                    Ok(block_hash) => mined.push((
-                        output.clone(),
-                        returned_output.mined_height,
-                        block_hash,
-                        returned_output.mmr_position,
-                        returned_output.mined_timestamp,
-                    )),
-                    Err(_) => {
-                        warn!(
-                            target: LOG_TARGET,
-                            "Malformed block hash received from node: {:?}", returned_output
-                        )
-                    },
-                };
-            } else {
-                unmined.push(output.clone());
+        Ok((mined, unmined, batch_response.height_of_longest_chain))
+    }
+
+    /// Queries the base node for the status of a batch of outputs by commitment. A failed query is retried up to
+    /// `tx_validator_batch_retries` times before the error is returned.
+    async fn query_utxo_statuses(
+        &self,
+        batch: &[DbWalletOutput],
+        base_node_client: &mut BaseNodeWalletRpcClient,
+    ) -> Result<QueryUtxoStatusesResponse, OutputManagerError> {
+        trace!(
+            target: LOG_TARGET,
+            "UTXO commitments queried from base node: {:?}",
+            batch.iter().map(|o| o.commitment.to_hex()).collect::<Vec<String>>()
+        );
+        let request = QueryUtxoStatusesRequest {
+            commitments: batch.iter().map(|o| o.commitment.to_vec()).collect(),
+        };
+        let mut attempt = 0;
+        loop {
+            match base_node_client.query_utxo_statuses(request.clone()).await {
+                Ok(response) => return Ok(response),
+                Err(e) if attempt < self.config.tx_validator_batch_retries => {
+                    attempt += 1;
+                    warn!(
+                        target: LOG_TARGET,
+                        "Failed to query the status of {} outputs: {}. Retrying (attempt {} of {}, Operation ID: {})",
+                        batch.len(),
+                        e,
+                        attempt,
+                        self.config.tx_validator_batch_retries,
+                        self.operation_id
+                    );
+                },
+                Err(e) => return Err(e.into()),
             }
         }
-
-        Ok((mined, unmined, batch_response.height_of_longest_chain))
     }
 
     #[allow(clippy::ptr_arg)]
@@ -603,6 +632,47 @@ where
         Ok(())
     }
 
+    /// Records the balance once the outputs have been validated, so that the balance history includes any
+    /// transactions that were confirmed since the last validation. Failing to record a snapshot does not fail the
+    /// validation.
+    async fn record_balance_snapshot(&self, wallet_client: &mut BaseNodeWalletRpcClient) {
+        let tip_height = match wallet_client.get_tip_info().await {
+            Ok(tip_info) => tip_info.metadata.and_then(|m| m.height_of_longest_chain),
+            Err(e) => {
+                warn!(
+                    target: LOG_TARGET,
+                    "Could not fetch the chain tip for the balance history (Id: {}): {}", self.operation_id, e
+                );
+                return;
+            },
+        };
+        let block_height = match tip_height {
+            Some(height) => height,
+            None => return,
+        };
+
+        let result = self.db.get_balance(Some(block_height)).and_then(|balance| {
+            let snapshot = BalanceSnapshot {
+                block_height,
+                recorded_at: Utc::now().naive_utc(),
+                balance,
+            };
+            self.db
+                .record_balance_snapshot(&snapshot, BalanceHistoryResolution::Daily)?;
+            if self.config.record_per_block_balance_history {
+                self.db
+                    .record_balance_snapshot(&snapshot, BalanceHistoryResolution::PerBlock)?;
+            }
+            Ok(())
+        });
+        if let Err(e) = result {
+            warn!(
+                target: LOG_TARGET,
+                "Could not record the balance history (Id: {}): {}", self.operation_id, e
+            );
+        }
+    }
+
     fn publish_event(&self, event: OutputManagerEvent) {
         if let Err(e) = self.event_publisher.send(Arc::new(event)) {
             debug!(
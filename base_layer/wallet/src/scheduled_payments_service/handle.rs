@@ -0,0 +1,197 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{fmt, fmt::Formatter, sync::Arc};
+
+use chrono::NaiveDateTime;
+use tari_common_types::{tari_address::TariAddress, transaction::TxId};
+use tari_core::transactions::tari_amount::MicroMinotari;
+use tari_service_framework::reply_channel::SenderService;
+use tokio::sync::broadcast;
+use tower::Service;
+
+use super::{
+    error::ScheduledPaymentsServiceError,
+    models::{Recurrence, ScheduledPayment, ScheduledPaymentId},
+};
+
+pub type ScheduledPaymentEventSender = broadcast::Sender<Arc<ScheduledPaymentEvent>>;
+pub type ScheduledPaymentEventReceiver = broadcast::Receiver<Arc<ScheduledPaymentEvent>>;
+
+/// API Request enum
+#[derive(Debug)]
+pub enum ScheduledPaymentsRequest {
+    SchedulePayment {
+        destination: TariAddress,
+        amount: MicroMinotari,
+        fee_per_gram: MicroMinotari,
+        message: String,
+        first_payment_at: NaiveDateTime,
+        recurrence: Option<Recurrence>,
+    },
+    CancelScheduledPayment(ScheduledPaymentId),
+    GetScheduledPayment(ScheduledPaymentId),
+    GetScheduledPayments,
+}
+
+/// API Response enum
+#[derive(Debug)]
+pub enum ScheduledPaymentsResponse {
+    PaymentScheduled(ScheduledPaymentId),
+    ScheduledPaymentCancelled,
+    ScheduledPayment(Box<ScheduledPayment>),
+    ScheduledPayments(Vec<ScheduledPayment>),
+}
+
+/// Why a due payment was not sent on this check. The payment stays due and is retried on the next check.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum DeferralReason {
+    InsufficientBalance,
+    Offline,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ScheduledPaymentEvent {
+    PaymentSent {
+        payment_id: ScheduledPaymentId,
+        tx_id: TxId,
+    },
+    PaymentDeferred {
+        payment_id: ScheduledPaymentId,
+        reason: DeferralReason,
+    },
+    PaymentFailed {
+        payment_id: ScheduledPaymentId,
+        reason: String,
+    },
+    ScheduleCompleted(ScheduledPaymentId),
+}
+
+impl fmt::Display for ScheduledPaymentEvent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ScheduledPaymentEvent::PaymentSent { payment_id, tx_id } => {
+                write!(f, "PaymentSent: {} (TxId: {})", payment_id, tx_id)
+            },
+            ScheduledPaymentEvent::PaymentDeferred { payment_id, reason } => {
+                write!(f, "PaymentDeferred: {} ({:?})", payment_id, reason)
+            },
+            ScheduledPaymentEvent::PaymentFailed { payment_id, reason } => {
+                write!(f, "PaymentFailed: {} ({})", payment_id, reason)
+            },
+            ScheduledPaymentEvent::ScheduleCompleted(payment_id) => write!(f, "ScheduleCompleted: {}", payment_id),
+        }
+    }
+}
+
+/// The Scheduled Payments Service Handle is a struct that contains the interfaces used to communicate with a running
+/// Scheduled Payments Service
+#[derive(Clone)]
+pub struct ScheduledPaymentsHandle {
+    handle: SenderService<ScheduledPaymentsRequest, Result<ScheduledPaymentsResponse, ScheduledPaymentsServiceError>>,
+    event_stream_sender: ScheduledPaymentEventSender,
+}
+
+impl ScheduledPaymentsHandle {
+    pub fn new(
+        handle: SenderService<
+            ScheduledPaymentsRequest,
+            Result<ScheduledPaymentsResponse, ScheduledPaymentsServiceError>,
+        >,
+        event_stream_sender: ScheduledPaymentEventSender,
+    ) -> Self {
+        Self {
+            handle,
+            event_stream_sender,
+        }
+    }
+
+    pub fn get_event_stream(&self) -> ScheduledPaymentEventReceiver {
+        self.event_stream_sender.subscribe()
+    }
+
+    /// Schedules a payment to `destination` at `first_payment_at`, repeating according to `recurrence` if provided.
+    pub async fn schedule_payment(
+        &mut self,
+        destination: TariAddress,
+        amount: MicroMinotari,
+        fee_per_gram: MicroMinotari,
+        message: String,
+        first_payment_at: NaiveDateTime,
+        recurrence: Option<Recurrence>,
+    ) -> Result<ScheduledPaymentId, ScheduledPaymentsServiceError> {
+        match self
+            .handle
+            .call(ScheduledPaymentsRequest::SchedulePayment {
+                destination,
+                amount,
+                fee_per_gram,
+                message,
+                first_payment_at,
+                recurrence,
+            })
+            .await??
+        {
+            ScheduledPaymentsResponse::PaymentScheduled(id) => Ok(id),
+            _ => Err(ScheduledPaymentsServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    pub async fn cancel_scheduled_payment(
+        &mut self,
+        payment_id: ScheduledPaymentId,
+    ) -> Result<(), ScheduledPaymentsServiceError> {
+        match self
+            .handle
+            .call(ScheduledPaymentsRequest::CancelScheduledPayment(payment_id))
+            .await??
+        {
+            ScheduledPaymentsResponse::ScheduledPaymentCancelled => Ok(()),
+            _ => Err(ScheduledPaymentsServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    pub async fn get_scheduled_payment(
+        &mut self,
+        payment_id: ScheduledPaymentId,
+    ) -> Result<ScheduledPayment, ScheduledPaymentsServiceError> {
+        match self
+            .handle
+            .call(ScheduledPaymentsRequest::GetScheduledPayment(payment_id))
+            .await??
+        {
+            ScheduledPaymentsResponse::ScheduledPayment(payment) => Ok(*payment),
+            _ => Err(ScheduledPaymentsServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    pub async fn get_scheduled_payments(&mut self) -> Result<Vec<ScheduledPayment>, ScheduledPaymentsServiceError> {
+        match self
+            .handle
+            .call(ScheduledPaymentsRequest::GetScheduledPayments)
+            .await??
+        {
+            ScheduledPaymentsResponse::ScheduledPayments(payments) => Ok(payments),
+            _ => Err(ScheduledPaymentsServiceError::UnexpectedApiResponse),
+        }
+    }
+}
@@ -0,0 +1,113 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+pub mod config;
+pub mod error;
+pub mod handle;
+pub mod models;
+pub mod service;
+
+use log::*;
+use tari_service_framework::{
+    async_trait,
+    reply_channel,
+    ServiceInitializationError,
+    ServiceInitializer,
+    ServiceInitializerContext,
+};
+use tokio::sync::broadcast;
+
+use crate::{
+    connectivity_service::WalletConnectivityHandle,
+    output_manager_service::handle::OutputManagerHandle,
+    scheduled_payments_service::{
+        config::ScheduledPaymentsServiceConfig,
+        handle::ScheduledPaymentsHandle,
+        service::ScheduledPaymentsService,
+    },
+    storage::database::{WalletBackend, WalletDatabase},
+    transaction_service::handle::TransactionServiceHandle,
+};
+
+const LOG_TARGET: &str = "wallet::scheduled_payments_service";
+
+pub struct ScheduledPaymentsServiceInitializer<T>
+where T: WalletBackend + 'static
+{
+    config: ScheduledPaymentsServiceConfig,
+    db: WalletDatabase<T>,
+}
+
+impl<T> ScheduledPaymentsServiceInitializer<T>
+where T: WalletBackend + 'static
+{
+    pub fn new(config: ScheduledPaymentsServiceConfig, db: WalletDatabase<T>) -> Self {
+        Self { config, db }
+    }
+}
+
+#[async_trait]
+impl<T> ServiceInitializer for ScheduledPaymentsServiceInitializer<T>
+where T: WalletBackend + 'static
+{
+    async fn initialize(&mut self, context: ServiceInitializerContext) -> Result<(), ServiceInitializationError> {
+        info!(target: LOG_TARGET, "Wallet scheduled payments service initializing.");
+
+        let (sender, request_stream) = reply_channel::unbounded();
+
+        let (event_publisher, _) = broadcast::channel(self.config.event_channel_size);
+
+        let scheduled_payments_handle = ScheduledPaymentsHandle::new(sender, event_publisher.clone());
+
+        // Register handle before waiting for handles to be ready
+        context.register_handle(scheduled_payments_handle);
+
+        let config = self.config.clone();
+        let db = self.db.clone();
+
+        context.spawn_when_ready(move |handles| async move {
+            let transaction_service = handles.expect_handle::<TransactionServiceHandle>();
+            let output_manager_service = handles.expect_handle::<OutputManagerHandle>();
+            let wallet_connectivity = handles.expect_handle::<WalletConnectivityHandle>();
+
+            let result = ScheduledPaymentsService::new(
+                config,
+                request_stream,
+                db,
+                transaction_service,
+                output_manager_service,
+                wallet_connectivity,
+                event_publisher,
+                handles.get_shutdown_signal(),
+            )
+            .start()
+            .await;
+
+            info!(
+                target: LOG_TARGET,
+                "Wallet Scheduled Payments Service shutdown with result {:?}", result
+            );
+        });
+
+        Ok(())
+    }
+}
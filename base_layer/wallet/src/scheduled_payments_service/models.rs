@@ -0,0 +1,223 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{convert::TryFrom, fmt, time::Duration};
+
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use strum_macros::Display;
+use tari_common_types::{tari_address::TariAddress, transaction::TxId};
+use tari_core::transactions::tari_amount::MicroMinotari;
+
+use crate::error::WalletStorageError;
+
+pub type ScheduledPaymentId = u64;
+
+/// The lifecycle state of a scheduled payment. Only `Active` payments are picked up by the service.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Display)]
+pub enum ScheduledPaymentStatus {
+    Active,
+    Completed,
+    Cancelled,
+    Failed,
+}
+
+impl From<ScheduledPaymentStatus> for i32 {
+    fn from(status: ScheduledPaymentStatus) -> Self {
+        match status {
+            ScheduledPaymentStatus::Active => 0,
+            ScheduledPaymentStatus::Completed => 1,
+            ScheduledPaymentStatus::Cancelled => 2,
+            ScheduledPaymentStatus::Failed => 3,
+        }
+    }
+}
+
+impl TryFrom<i32> for ScheduledPaymentStatus {
+    type Error = WalletStorageError;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(ScheduledPaymentStatus::Active),
+            1 => Ok(ScheduledPaymentStatus::Completed),
+            2 => Ok(ScheduledPaymentStatus::Cancelled),
+            3 => Ok(ScheduledPaymentStatus::Failed),
+            _ => Err(WalletStorageError::ConversionError(
+                "Was expecting value between 0 and 3 for ScheduledPaymentStatus".to_string(),
+            )),
+        }
+    }
+}
+
+/// How often a scheduled payment repeats after its first payment.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Recurrence {
+    /// The time between consecutive payments
+    pub interval: Duration,
+    /// The total number of payments to make, or `None` to repeat until cancelled
+    pub max_payments: Option<u64>,
+}
+
+impl Recurrence {
+    pub fn is_exhausted(&self, payments_made: u64) -> bool {
+        self.max_payments.map_or(false, |max| payments_made >= max)
+    }
+
+    /// Returns the first payment time after `now` that falls on this recurrence's schedule starting from `previous`.
+    /// Periods that were missed, for instance while the wallet was offline, are skipped rather than paid in a burst.
+    /// Returns `None` if the next payment time cannot be represented.
+    pub fn next_after(&self, previous: NaiveDateTime, now: NaiveDateTime) -> Option<NaiveDateTime> {
+        let interval_secs = i64::try_from(self.interval.as_secs()).ok()?.max(1);
+        let elapsed_secs = now.signed_duration_since(previous).num_seconds().max(0);
+        let periods = elapsed_secs / interval_secs + 1;
+        previous.checked_add_signed(chrono::Duration::seconds(periods.checked_mul(interval_secs)?))
+    }
+}
+
+/// A future-dated, optionally recurring, payment that the wallet will send once it falls due.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScheduledPayment {
+    pub id: ScheduledPaymentId,
+    pub destination: TariAddress,
+    pub amount: MicroMinotari,
+    pub fee_per_gram: MicroMinotari,
+    pub message: String,
+    pub recurrence: Option<Recurrence>,
+    pub next_payment_at: NaiveDateTime,
+    pub payments_made: u64,
+    pub last_tx_id: Option<TxId>,
+    pub status: ScheduledPaymentStatus,
+    pub created_at: NaiveDateTime,
+}
+
+impl ScheduledPayment {
+    pub fn is_due(&self, now: NaiveDateTime) -> bool {
+        self.status == ScheduledPaymentStatus::Active && self.next_payment_at <= now
+    }
+
+    /// Records a payment that was sent for the current occurrence and moves the schedule on to the next one, completing
+    /// it if there are no further payments to make.
+    pub fn record_payment(&mut self, tx_id: TxId, now: NaiveDateTime) {
+        self.payments_made = self.payments_made.saturating_add(1);
+        self.last_tx_id = Some(tx_id);
+        let next = self
+            .recurrence
+            .filter(|r| !r.is_exhausted(self.payments_made))
+            .and_then(|r| r.next_after(self.next_payment_at, now));
+        match next {
+            Some(next) => self.next_payment_at = next,
+            None => self.status = ScheduledPaymentStatus::Completed,
+        }
+    }
+}
+
+impl fmt::Display for ScheduledPayment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Scheduled payment {} of {} to {} (next at {}, made: {}, status: {})",
+            self.id, self.amount, self.destination, self.next_payment_at, self.payments_made, self.status
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    fn at(hour: u32, min: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2023, 7, 10)
+            .unwrap()
+            .and_hms_opt(hour, min, 0)
+            .unwrap()
+    }
+
+    fn payment(recurrence: Option<Recurrence>) -> ScheduledPayment {
+        ScheduledPayment {
+            id: 1,
+            destination: TariAddress::default(),
+            amount: MicroMinotari::from(1000),
+            fee_per_gram: MicroMinotari::from(5),
+            message: "payroll".to_string(),
+            recurrence,
+            next_payment_at: at(9, 0),
+            payments_made: 0,
+            last_tx_id: None,
+            status: ScheduledPaymentStatus::Active,
+            created_at: at(8, 0),
+        }
+    }
+
+    #[test]
+    fn it_completes_a_one_off_payment() {
+        let mut p = payment(None);
+        assert!(!p.is_due(at(8, 59)));
+        assert!(p.is_due(at(9, 0)));
+        p.record_payment(TxId::from(7u64), at(9, 1));
+        assert_eq!(p.status, ScheduledPaymentStatus::Completed);
+        assert_eq!(p.last_tx_id, Some(TxId::from(7u64)));
+        assert!(!p.is_due(at(23, 0)));
+    }
+
+    #[test]
+    fn it_advances_a_recurring_payment_and_skips_missed_periods() {
+        let mut p = payment(Some(Recurrence {
+            interval: Duration::from_secs(60 * 60),
+            max_payments: None,
+        }));
+        p.record_payment(TxId::from(1u64), at(9, 5));
+        assert_eq!(p.next_payment_at, at(10, 0));
+        assert_eq!(p.status, ScheduledPaymentStatus::Active);
+
+        // The wallet was offline from 10:00 until 13:30, so only the 14:00 payment remains scheduled
+        p.record_payment(TxId::from(2u64), at(13, 30));
+        assert_eq!(p.next_payment_at, at(14, 0));
+        assert_eq!(p.payments_made, 2);
+    }
+
+    #[test]
+    fn it_completes_a_recurring_payment_after_max_payments() {
+        let mut p = payment(Some(Recurrence {
+            interval: Duration::from_secs(60),
+            max_payments: Some(2),
+        }));
+        p.record_payment(TxId::from(1u64), at(9, 0));
+        assert_eq!(p.status, ScheduledPaymentStatus::Active);
+        p.record_payment(TxId::from(2u64), at(9, 1));
+        assert_eq!(p.status, ScheduledPaymentStatus::Completed);
+    }
+
+    #[test]
+    fn status_round_trips_through_i32() {
+        for status in [
+            ScheduledPaymentStatus::Active,
+            ScheduledPaymentStatus::Completed,
+            ScheduledPaymentStatus::Cancelled,
+            ScheduledPaymentStatus::Failed,
+        ] {
+            assert_eq!(ScheduledPaymentStatus::try_from(i32::from(status)).unwrap(), status);
+        }
+        assert!(ScheduledPaymentStatus::try_from(4).is_err());
+    }
+}
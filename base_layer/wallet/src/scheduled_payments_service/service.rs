@@ -0,0 +1,327 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{collections::HashMap, sync::Arc};
+
+use chrono::{NaiveDateTime, Utc};
+use futures::StreamExt;
+use log::*;
+use tari_common_types::{tari_address::TariAddress, transaction::TxId};
+use tari_core::transactions::{tari_amount::MicroMinotari, transaction_components::OutputFeatures};
+use tari_service_framework::reply_channel::Receiver;
+use tari_shutdown::ShutdownSignal;
+use tokio::time::{self, MissedTickBehavior};
+
+use super::{
+    config::ScheduledPaymentsServiceConfig,
+    error::ScheduledPaymentsServiceError,
+    handle::{
+        DeferralReason,
+        ScheduledPaymentEvent,
+        ScheduledPaymentEventSender,
+        ScheduledPaymentsRequest,
+        ScheduledPaymentsResponse,
+    },
+    models::{Recurrence, ScheduledPayment, ScheduledPaymentId, ScheduledPaymentStatus},
+};
+use crate::{
+    connectivity_service::{OnlineStatus, WalletConnectivityInterface},
+    output_manager_service::{handle::OutputManagerHandle, UtxoSelectionCriteria},
+    storage::database::{WalletBackend, WalletDatabase},
+    transaction_service::handle::TransactionServiceHandle,
+};
+
+const LOG_TARGET: &str = "wallet::scheduled_payments_service::service";
+
+/// The scheduled payments service persists future-dated and recurring payments and sends them through the
+/// transaction service once they fall due, provided the wallet is online and has the funds available.
+pub struct ScheduledPaymentsService<T, TWalletConnectivity>
+where T: WalletBackend + 'static
+{
+    config: ScheduledPaymentsServiceConfig,
+    request_stream:
+        Option<Receiver<ScheduledPaymentsRequest, Result<ScheduledPaymentsResponse, ScheduledPaymentsServiceError>>>,
+    db: WalletDatabase<T>,
+    transaction_service: TransactionServiceHandle,
+    output_manager_service: OutputManagerHandle,
+    wallet_connectivity: TWalletConnectivity,
+    event_publisher: ScheduledPaymentEventSender,
+    shutdown_signal: ShutdownSignal,
+    /// The last deferral reason published per payment, so that a payment that stays deferred across checks is only
+    /// reported once per reason
+    deferred: HashMap<ScheduledPaymentId, DeferralReason>,
+}
+
+impl<T, TWalletConnectivity> ScheduledPaymentsService<T, TWalletConnectivity>
+where
+    T: WalletBackend + 'static,
+    TWalletConnectivity: WalletConnectivityInterface,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        config: ScheduledPaymentsServiceConfig,
+        request_stream: Receiver<
+            ScheduledPaymentsRequest,
+            Result<ScheduledPaymentsResponse, ScheduledPaymentsServiceError>,
+        >,
+        db: WalletDatabase<T>,
+        transaction_service: TransactionServiceHandle,
+        output_manager_service: OutputManagerHandle,
+        wallet_connectivity: TWalletConnectivity,
+        event_publisher: ScheduledPaymentEventSender,
+        shutdown_signal: ShutdownSignal,
+    ) -> Self {
+        Self {
+            config,
+            request_stream: Some(request_stream),
+            db,
+            transaction_service,
+            output_manager_service,
+            wallet_connectivity,
+            event_publisher,
+            shutdown_signal,
+            deferred: HashMap::new(),
+        }
+    }
+
+    /// Starts the service.
+    pub async fn start(mut self) -> Result<(), ScheduledPaymentsServiceError> {
+        let mut request_stream = self
+            .request_stream
+            .take()
+            .expect("Scheduled Payments Service initialized without request_stream");
+        let mut shutdown_signal = self.shutdown_signal.clone();
+
+        let mut check_interval = time::interval(self.config.check_interval);
+        check_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        debug!(target: LOG_TARGET, "Scheduled Payments Service started");
+        loop {
+            tokio::select! {
+                Some(request_context) = request_stream.next() => {
+                    let (request, reply_tx) = request_context.split();
+                    let response = self.handle_request(request).map_err(|e| {
+                        error!(target: LOG_TARGET, "Error handling request: {:?}", e);
+                        e
+                    });
+                    let _result = reply_tx.send(response).map_err(|e| {
+                        warn!(target: LOG_TARGET, "Failed to send reply");
+                        e
+                    });
+                },
+                _ = check_interval.tick() => {
+                    if let Err(e) = self.process_due_payments().await {
+                        warn!(target: LOG_TARGET, "Failed to process due scheduled payments: {}", e);
+                    }
+                },
+                _ = shutdown_signal.wait() => break,
+            }
+        }
+
+        info!(
+            target: LOG_TARGET,
+            "Scheduled Payments Service shutting down because the shutdown signal was received"
+        );
+        Ok(())
+    }
+
+    fn handle_request(
+        &mut self,
+        request: ScheduledPaymentsRequest,
+    ) -> Result<ScheduledPaymentsResponse, ScheduledPaymentsServiceError> {
+        trace!(target: LOG_TARGET, "Handling Scheduled Payments Service Request: {:?}", request);
+        match request {
+            ScheduledPaymentsRequest::SchedulePayment {
+                destination,
+                amount,
+                fee_per_gram,
+                message,
+                first_payment_at,
+                recurrence,
+            } => self
+                .schedule_payment(destination, amount, fee_per_gram, message, first_payment_at, recurrence)
+                .map(ScheduledPaymentsResponse::PaymentScheduled),
+            ScheduledPaymentsRequest::CancelScheduledPayment(payment_id) => self
+                .cancel_scheduled_payment(payment_id)
+                .map(|_| ScheduledPaymentsResponse::ScheduledPaymentCancelled),
+            ScheduledPaymentsRequest::GetScheduledPayment(payment_id) => self
+                .db
+                .fetch_scheduled_payment(payment_id)?
+                .map(|payment| ScheduledPaymentsResponse::ScheduledPayment(Box::new(payment)))
+                .ok_or(ScheduledPaymentsServiceError::ScheduledPaymentNotFound(payment_id)),
+            ScheduledPaymentsRequest::GetScheduledPayments => Ok(ScheduledPaymentsResponse::ScheduledPayments(
+                self.db.fetch_scheduled_payments()?,
+            )),
+        }
+    }
+
+    fn schedule_payment(
+        &mut self,
+        destination: TariAddress,
+        amount: MicroMinotari,
+        fee_per_gram: MicroMinotari,
+        message: String,
+        first_payment_at: NaiveDateTime,
+        recurrence: Option<Recurrence>,
+    ) -> Result<ScheduledPaymentId, ScheduledPaymentsServiceError> {
+        if amount == MicroMinotari::zero() {
+            return Err(ScheduledPaymentsServiceError::InvalidSchedule(
+                "Payment amount must be greater than zero".to_string(),
+            ));
+        }
+        if let Some(recurrence) = recurrence {
+            if recurrence.interval < self.config.min_recurrence_interval {
+                return Err(ScheduledPaymentsServiceError::InvalidSchedule(format!(
+                    "Recurrence interval must be at least {}s",
+                    self.config.min_recurrence_interval.as_secs()
+                )));
+            }
+            if recurrence.max_payments == Some(0) {
+                return Err(ScheduledPaymentsServiceError::InvalidSchedule(
+                    "A recurring payment must allow at least one payment".to_string(),
+                ));
+            }
+        }
+
+        let payment = ScheduledPayment {
+            id: TxId::new_random().as_u64(),
+            destination,
+            amount,
+            fee_per_gram,
+            message,
+            recurrence,
+            next_payment_at: first_payment_at,
+            payments_made: 0,
+            last_tx_id: None,
+            status: ScheduledPaymentStatus::Active,
+            created_at: Utc::now().naive_utc(),
+        };
+        self.db.save_scheduled_payment(&payment)?;
+        info!(target: LOG_TARGET, "Added {}", payment);
+        Ok(payment.id)
+    }
+
+    fn cancel_scheduled_payment(
+        &mut self,
+        payment_id: ScheduledPaymentId,
+    ) -> Result<(), ScheduledPaymentsServiceError> {
+        let mut payment = self
+            .db
+            .fetch_scheduled_payment(payment_id)?
+            .ok_or(ScheduledPaymentsServiceError::ScheduledPaymentNotFound(payment_id))?;
+        if payment.status != ScheduledPaymentStatus::Active {
+            return Err(ScheduledPaymentsServiceError::ScheduledPaymentNotActive(payment_id));
+        }
+        payment.status = ScheduledPaymentStatus::Cancelled;
+        self.db.save_scheduled_payment(&payment)?;
+        self.deferred.remove(&payment_id);
+        info!(target: LOG_TARGET, "Cancelled scheduled payment {}", payment_id);
+        Ok(())
+    }
+
+    /// Sends every payment that has fallen due. Payments are deferred to the next check while the wallet is not online
+    /// or when the available balance does not cover them.
+    async fn process_due_payments(&mut self) -> Result<(), ScheduledPaymentsServiceError> {
+        let now = Utc::now().naive_utc();
+        let due = self.db.fetch_due_scheduled_payments(now)?;
+        if due.is_empty() {
+            return Ok(());
+        }
+
+        if self.wallet_connectivity.get_connectivity_status() != OnlineStatus::Online {
+            debug!(
+                target: LOG_TARGET,
+                "Deferring {} due scheduled payment(s) because the wallet is not online",
+                due.len()
+            );
+            for payment in &due {
+                self.defer(payment.id, DeferralReason::Offline);
+            }
+            return Ok(());
+        }
+
+        let mut available_balance = self.output_manager_service.get_balance().await?.available_balance;
+        for mut payment in due {
+            if payment.amount > available_balance {
+                self.defer(payment.id, DeferralReason::InsufficientBalance);
+                continue;
+            }
+
+            match self
+                .transaction_service
+                .send_transaction(
+                    payment.destination.clone(),
+                    payment.amount,
+                    UtxoSelectionCriteria::default(),
+                    OutputFeatures::default(),
+                    payment.fee_per_gram,
+                    payment.message.clone(),
+                )
+                .await
+            {
+                Ok(tx_id) => {
+                    available_balance = available_balance.saturating_sub(payment.amount);
+                    payment.record_payment(tx_id, now);
+                    self.db.save_scheduled_payment(&payment)?;
+                    self.deferred.remove(&payment.id);
+                    info!(target: LOG_TARGET, "Sent TxId {} for {}", tx_id, payment);
+                    self.publish_event(ScheduledPaymentEvent::PaymentSent {
+                        payment_id: payment.id,
+                        tx_id,
+                    });
+                    if payment.status == ScheduledPaymentStatus::Completed {
+                        self.publish_event(ScheduledPaymentEvent::ScheduleCompleted(payment.id));
+                    }
+                },
+                Err(e) => {
+                    warn!(target: LOG_TARGET, "Failed to send {}: {}", payment, e);
+                    payment.status = ScheduledPaymentStatus::Failed;
+                    self.db.save_scheduled_payment(&payment)?;
+                    self.deferred.remove(&payment.id);
+                    self.publish_event(ScheduledPaymentEvent::PaymentFailed {
+                        payment_id: payment.id,
+                        reason: e.to_string(),
+                    });
+                },
+            }
+        }
+
+        Ok(())
+    }
+
+    fn defer(&mut self, payment_id: ScheduledPaymentId, reason: DeferralReason) {
+        if self.deferred.insert(payment_id, reason) != Some(reason) {
+            self.publish_event(ScheduledPaymentEvent::PaymentDeferred { payment_id, reason });
+        }
+    }
+
+    fn publish_event(&self, event: ScheduledPaymentEvent) {
+        let _size = self.event_publisher.send(Arc::new(event)).map_err(|e| {
+            trace!(
+                target: LOG_TARGET,
+                "Error sending event because there are no subscribers: {:?}",
+                e
+            );
+            e
+        });
+    }
+}
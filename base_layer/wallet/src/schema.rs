@@ -1,5 +1,18 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    balance_snapshots (resolution, bucket) {
+        resolution -> Integer,
+        bucket -> BigInt,
+        block_height -> BigInt,
+        recorded_at -> Timestamp,
+        available_balance -> BigInt,
+        time_locked_balance -> Nullable<BigInt>,
+        pending_incoming_balance -> BigInt,
+        pending_outgoing_balance -> BigInt,
+    }
+}
+
 diesel::table! {
     burnt_proofs (id) {
         id -> Integer,
@@ -123,6 +136,14 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    payment_receipts (tx_id) {
+        tx_id -> BigInt,
+        public_nonce -> Binary,
+        signature -> Binary,
+    }
+}
+
 diesel::table! {
     scanned_blocks (header_hash) {
         header_hash -> Binary,
@@ -133,6 +154,28 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    scheduled_payments (id) {
+        id -> BigInt,
+        payload -> Text,
+        next_payment_at -> Timestamp,
+        payments_made -> BigInt,
+        last_tx_id -> Nullable<BigInt>,
+        status -> Integer,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    transaction_events (seq) {
+        seq -> BigInt,
+        tx_id -> BigInt,
+        event -> Text,
+        status -> Nullable<Integer>,
+        recorded_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     wallet_settings (key) {
         key -> Text,
@@ -141,6 +184,7 @@ diesel::table! {
 }
 
 diesel::allow_tables_to_appear_in_same_query!(
+    balance_snapshots,
     burnt_proofs,
     client_key_values,
     completed_transactions,
@@ -148,6 +192,9 @@ diesel::allow_tables_to_appear_in_same_query!(
     known_one_sided_payment_scripts,
     outbound_transactions,
     outputs,
+    payment_receipts,
     scanned_blocks,
+    scheduled_payments,
+    transaction_events,
     wallet_settings,
 );
@@ -36,7 +36,11 @@ use tari_comms::{
 use tari_key_manager::cipher_seed::CipherSeed;
 use tari_utilities::SafePassword;
 
-use crate::{error::WalletStorageError, utxo_scanner_service::service::ScannedBlock};
+use crate::{
+    error::WalletStorageError,
+    scheduled_payments_service::models::{ScheduledPayment, ScheduledPaymentId},
+    utxo_scanner_service::service::ScannedBlock,
+};
 
 const LOG_TARGET: &str = "wallet::database";
 
@@ -62,6 +66,12 @@ pub trait WalletBackend: Send + Sync + Clone {
 
     /// Change the passphrase used to encrypt the database
     fn change_passphrase(&self, existing: &SafePassword, new: &SafePassword) -> Result<(), WalletStorageError>;
+    /// Remove the main encryption key from memory; encrypted values are unavailable until the database is unlocked
+    fn lock(&self);
+    /// Restore the main encryption key from the passphrase
+    fn unlock(&self, passphrase: &SafePassword) -> Result<(), WalletStorageError>;
+    /// Returns true if the main encryption key is not currently held in memory
+    fn is_locked(&self) -> bool;
 
     fn create_burnt_proof(
         &self,
@@ -72,6 +82,13 @@ pub trait WalletBackend: Send + Sync + Clone {
     fn fetch_burnt_proof(&self, id: u32) -> Result<(u32, String, String, NaiveDateTime), WalletStorageError>;
     fn fetch_burnt_proofs(&self) -> Result<Vec<(u32, String, String, NaiveDateTime)>, WalletStorageError>;
     fn delete_burnt_proof(&self, id: u32) -> Result<(), WalletStorageError>;
+
+    /// Inserts the scheduled payment, or replaces the stored copy if one with the same id exists
+    fn save_scheduled_payment(&self, payment: &ScheduledPayment) -> Result<(), WalletStorageError>;
+    fn fetch_scheduled_payment(&self, id: ScheduledPaymentId) -> Result<Option<ScheduledPayment>, WalletStorageError>;
+    fn fetch_scheduled_payments(&self) -> Result<Vec<ScheduledPayment>, WalletStorageError>;
+    /// Fetches the active scheduled payments whose next payment is due at or before `now`
+    fn fetch_due_scheduled_payments(&self, now: NaiveDateTime) -> Result<Vec<ScheduledPayment>, WalletStorageError>;
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -165,6 +182,18 @@ where T: WalletBackend + 'static
         Ok(())
     }
 
+    pub fn lock(&self) {
+        self.db.lock();
+    }
+
+    pub fn unlock(&self, passphrase: &SafePassword) -> Result<(), WalletStorageError> {
+        self.db.unlock(passphrase)
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.db.is_locked()
+    }
+
     pub fn get_master_seed(&self) -> Result<Option<CipherSeed>, WalletStorageError> {
         let c = match self.db.fetch(&DbKey::MasterSeed) {
             Ok(None) => Ok(None),
@@ -384,6 +413,28 @@ where T: WalletBackend + 'static
     pub fn delete_burnt_proof(&self, id: u32) -> Result<(), WalletStorageError> {
         self.db.delete_burnt_proof(id)
     }
+
+    pub fn save_scheduled_payment(&self, payment: &ScheduledPayment) -> Result<(), WalletStorageError> {
+        self.db.save_scheduled_payment(payment)
+    }
+
+    pub fn fetch_scheduled_payment(
+        &self,
+        id: ScheduledPaymentId,
+    ) -> Result<Option<ScheduledPayment>, WalletStorageError> {
+        self.db.fetch_scheduled_payment(id)
+    }
+
+    pub fn fetch_scheduled_payments(&self) -> Result<Vec<ScheduledPayment>, WalletStorageError> {
+        self.db.fetch_scheduled_payments()
+    }
+
+    pub fn fetch_due_scheduled_payments(
+        &self,
+        now: NaiveDateTime,
+    ) -> Result<Vec<ScheduledPayment>, WalletStorageError> {
+        self.db.fetch_due_scheduled_payments(now)
+    }
 }
 
 impl Display for DbValue {
@@ -25,4 +25,7 @@
 pub mod scanned_blocks;
 // converting between unsigned and signed is okay here as we do it both ways
 #[allow(clippy::cast_possible_wrap)]
+pub mod scheduled_payments;
+// converting between unsigned and signed is okay here as we do it both ways
+#[allow(clippy::cast_possible_wrap)]
 pub mod wallet;
@@ -0,0 +1,187 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{convert::TryFrom, str::from_utf8};
+
+use chacha20poly1305::XChaCha20Poly1305;
+use chrono::NaiveDateTime;
+use diesel::{prelude::*, result::Error, SqliteConnection};
+use serde::{Deserialize, Serialize};
+use tari_common_types::{
+    encryption::{decrypt_bytes_integral_nonce, encrypt_bytes_integral_nonce, Encryptable},
+    tari_address::TariAddress,
+    transaction::TxId,
+};
+use tari_core::transactions::tari_amount::MicroMinotari;
+use tari_utilities::{
+    hex::{from_hex, Hex},
+    ByteArray,
+    Hidden,
+};
+use zeroize::Zeroize;
+
+use crate::{
+    error::WalletStorageError,
+    scheduled_payments_service::models::{Recurrence, ScheduledPayment, ScheduledPaymentStatus},
+    schema::scheduled_payments,
+};
+
+/// The sensitive details of a scheduled payment, stored encrypted in the `payload` column. The scheduling columns are
+/// kept in the clear so that due payments can be queried without decrypting every row.
+#[derive(Serialize, Deserialize)]
+struct ScheduledPaymentPayload {
+    destination: TariAddress,
+    amount: MicroMinotari,
+    fee_per_gram: MicroMinotari,
+    message: String,
+    recurrence: Option<Recurrence>,
+}
+
+#[derive(Clone, Debug, Queryable, Insertable, PartialEq)]
+#[diesel(table_name = scheduled_payments)]
+pub struct ScheduledPaymentSql {
+    id: i64,
+    payload: String,
+    next_payment_at: NaiveDateTime,
+    payments_made: i64,
+    last_tx_id: Option<i64>,
+    status: i32,
+    created_at: NaiveDateTime,
+}
+
+impl ScheduledPaymentSql {
+    pub fn new(payment: &ScheduledPayment, cipher: &XChaCha20Poly1305) -> Result<Self, WalletStorageError> {
+        let payload = ScheduledPaymentPayload {
+            destination: payment.destination.clone(),
+            amount: payment.amount,
+            fee_per_gram: payment.fee_per_gram,
+            message: payment.message.clone(),
+            recurrence: payment.recurrence,
+        };
+        let entry = Self {
+            id: payment.id as i64,
+            payload: serde_json::to_string(&payload)?,
+            next_payment_at: payment.next_payment_at,
+            payments_made: payment.payments_made as i64,
+            last_tx_id: payment.last_tx_id.map(TxId::as_i64_wrapped),
+            status: i32::from(payment.status),
+            created_at: payment.created_at,
+        };
+        entry.encrypt(cipher).map_err(WalletStorageError::AeadError)
+    }
+
+    pub fn index(conn: &mut SqliteConnection) -> Result<Vec<Self>, WalletStorageError> {
+        Ok(scheduled_payments::table
+            .order(scheduled_payments::next_payment_at.asc())
+            .load::<ScheduledPaymentSql>(conn)?)
+    }
+
+    /// Returns the active payments that are due at `now`, oldest first
+    pub fn index_due(now: NaiveDateTime, conn: &mut SqliteConnection) -> Result<Vec<Self>, WalletStorageError> {
+        Ok(scheduled_payments::table
+            .filter(scheduled_payments::status.eq(i32::from(ScheduledPaymentStatus::Active)))
+            .filter(scheduled_payments::next_payment_at.le(now))
+            .order(scheduled_payments::next_payment_at.asc())
+            .load::<ScheduledPaymentSql>(conn)?)
+    }
+
+    pub fn get(id: u64, conn: &mut SqliteConnection) -> Result<Option<Self>, WalletStorageError> {
+        scheduled_payments::table
+            .filter(scheduled_payments::id.eq(id as i64))
+            .first::<ScheduledPaymentSql>(conn)
+            .map(Some)
+            .or_else(|err| match err {
+                Error::NotFound => Ok(None),
+                err => Err(err.into()),
+            })
+    }
+
+    pub fn set(&self, conn: &mut SqliteConnection) -> Result<(), WalletStorageError> {
+        diesel::replace_into(scheduled_payments::table)
+            .values(self.clone())
+            .execute(conn)?;
+        Ok(())
+    }
+}
+
+impl TryFrom<ScheduledPaymentSql> for ScheduledPayment {
+    type Error = WalletStorageError;
+
+    fn try_from(entry: ScheduledPaymentSql) -> Result<Self, Self::Error> {
+        let payload: ScheduledPaymentPayload = serde_json::from_str(&entry.payload)?;
+        Ok(Self {
+            id: entry.id as u64,
+            destination: payload.destination,
+            amount: payload.amount,
+            fee_per_gram: payload.fee_per_gram,
+            message: payload.message,
+            recurrence: payload.recurrence,
+            next_payment_at: entry.next_payment_at,
+            payments_made: entry.payments_made as u64,
+            last_tx_id: entry.last_tx_id.map(|id| TxId::from(id as u64)),
+            status: ScheduledPaymentStatus::try_from(entry.status)?,
+            created_at: entry.created_at,
+        })
+    }
+}
+
+impl Encryptable<XChaCha20Poly1305> for ScheduledPaymentSql {
+    fn domain(&self, field_name: &'static str) -> Vec<u8> {
+        [
+            Self::SCHEDULED_PAYMENT,
+            self.id.to_be_bytes().as_bytes(),
+            field_name.as_bytes(),
+        ]
+        .concat()
+        .to_vec()
+    }
+
+    #[allow(unused_assignments)]
+    fn encrypt(mut self, cipher: &XChaCha20Poly1305) -> Result<Self, String> {
+        self.payload = encrypt_bytes_integral_nonce(
+            cipher,
+            self.domain("payload"),
+            Hidden::hide(self.payload.as_bytes().to_vec()),
+        )?
+        .to_hex();
+
+        Ok(self)
+    }
+
+    #[allow(unused_assignments)]
+    fn decrypt(mut self, cipher: &XChaCha20Poly1305) -> Result<Self, String> {
+        let mut decrypted_value = decrypt_bytes_integral_nonce(
+            cipher,
+            self.domain("payload"),
+            &from_hex(self.payload.as_str()).map_err(|e| e.to_string())?,
+        )?;
+
+        self.payload = from_utf8(decrypted_value.as_slice())
+            .map_err(|e| e.to_string())?
+            .to_string();
+
+        // we zeroize the decrypted value
+        decrypted_value.zeroize();
+
+        Ok(self)
+    }
+}
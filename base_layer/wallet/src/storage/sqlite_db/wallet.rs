@@ -24,7 +24,6 @@ use std::{
     convert::TryFrom,
     mem::size_of,
     str::{from_utf8, FromStr},
-    sync::{Arc, RwLock},
 };
 
 use argon2::password_hash::{
@@ -41,7 +40,7 @@ use log::*;
 use tari_common_sqlite::sqlite_connection_pool::PooledDbConnection;
 use tari_common_types::{
     chain_metadata::ChainMetadata,
-    encryption::{decrypt_bytes_integral_nonce, encrypt_bytes_integral_nonce, Encryptable},
+    encryption::{decrypt_bytes_integral_nonce, encrypt_bytes_integral_nonce, Encryptable, SharedCipher},
 };
 use tari_comms::{
     multiaddr::Multiaddr,
@@ -63,10 +62,11 @@ use zeroize::Zeroize;
 
 use crate::{
     error::WalletStorageError,
+    scheduled_payments_service::models::{ScheduledPayment, ScheduledPaymentId},
     schema::{burnt_proofs, client_key_values, wallet_settings},
     storage::{
         database::{DbKey, DbKeyValuePair, DbValue, WalletBackend, WriteOperation},
-        sqlite_db::scanned_blocks::ScannedBlockSql,
+        sqlite_db::{scanned_blocks::ScannedBlockSql, scheduled_payments::ScheduledPaymentSql},
         sqlite_utilities::wallet_db_connection::WalletDbConnection,
     },
     utxo_scanner_service::service::ScannedBlock,
@@ -221,10 +221,11 @@ impl DatabaseEncryptionFields {
 }
 
 /// A Sqlite backend for the Output Manager Service. The Backend is accessed via a connection pool to the Sqlite file.
+/// The main encryption key is only held in memory while the database is unlocked.
 #[derive(Clone)]
 pub struct WalletSqliteDatabase {
     database_connection: WalletDbConnection,
-    cipher: Arc<RwLock<XChaCha20Poly1305>>,
+    cipher: SharedCipher,
 }
 impl WalletSqliteDatabase {
     pub fn new(database_connection: WalletDbConnection, passphrase: SafePassword) -> Result<Self, WalletStorageError> {
@@ -232,12 +233,17 @@ impl WalletSqliteDatabase {
 
         Ok(Self {
             database_connection,
-            cipher: Arc::new(RwLock::new(cipher)),
+            cipher: SharedCipher::new(cipher),
         })
     }
 
+    /// Returns the main database cipher, or an error if the database is locked
+    fn unlocked_cipher(&self) -> Result<XChaCha20Poly1305, WalletStorageError> {
+        self.cipher.get().ok_or(WalletStorageError::DatabaseLocked)
+    }
+
     fn set_master_seed(&self, seed: &CipherSeed, conn: &mut SqliteConnection) -> Result<(), WalletStorageError> {
-        let cipher = acquire_read_lock!(self.cipher);
+        let cipher = self.unlocked_cipher()?;
         if WalletSettingSql::get(&DbKey::WalletBirthday, conn)?.is_none() {
             let birthday = seed.birthday();
             WalletSettingSql::new(DbKey::WalletBirthday, birthday.to_string()).set(conn)?;
@@ -253,7 +259,7 @@ impl WalletSqliteDatabase {
     }
 
     fn get_master_seed(&self, conn: &mut SqliteConnection) -> Result<Option<CipherSeed>, WalletStorageError> {
-        let cipher = acquire_read_lock!(self.cipher);
+        let cipher = self.unlocked_cipher()?;
         if let Some(seed_str) = WalletSettingSql::get(&DbKey::MasterSeed, conn)? {
             let seed = {
                 // Decrypted_key_bytes contains sensitive data regarding decrypted
@@ -276,7 +282,7 @@ impl WalletSqliteDatabase {
     }
 
     fn decrypt_value<T: Encryptable<XChaCha20Poly1305>>(&self, o: T) -> Result<T, WalletStorageError> {
-        let cipher = acquire_read_lock!(self.cipher);
+        let cipher = self.unlocked_cipher()?;
         let o = o
             .decrypt(&cipher)
             .map_err(|e| WalletStorageError::AeadError(format!("Decryption Error:{}", e)))?;
@@ -285,7 +291,7 @@ impl WalletSqliteDatabase {
 
     #[allow(dead_code)]
     fn encrypt_value<T: Encryptable<XChaCha20Poly1305>>(&self, o: T) -> Result<T, WalletStorageError> {
-        let cipher = acquire_read_lock!(self.cipher);
+        let cipher = self.unlocked_cipher()?;
         o.encrypt(&cipher)
             .map_err(|e| WalletStorageError::AeadError(format!("Encryption Error:{}", e)))
     }
@@ -312,7 +318,7 @@ impl WalletSqliteDatabase {
     }
 
     fn set_tor_id(&self, tor: TorIdentity, conn: &mut SqliteConnection) -> Result<(), WalletStorageError> {
-        let cipher = acquire_read_lock!(self.cipher);
+        let cipher = self.unlocked_cipher()?;
 
         let bytes =
             Hidden::hide(bincode::serialize(&tor).map_err(|e| WalletStorageError::ConversionError(e.to_string()))?);
@@ -325,7 +331,7 @@ impl WalletSqliteDatabase {
     }
 
     fn get_tor_id(&self, conn: &mut SqliteConnection) -> Result<Option<DbValue>, WalletStorageError> {
-        let cipher = acquire_read_lock!(self.cipher);
+        let cipher = self.unlocked_cipher()?;
         if let Some(key_str) = WalletSettingSql::get(&DbKey::TorId, conn)? {
             let id = {
                 // we must zeroize decrypted_key_bytes, as this contains sensitive data,
@@ -364,7 +370,6 @@ impl WalletSqliteDatabase {
         let start = Instant::now();
         let mut conn = self.database_connection.get_pooled_connection()?;
         let acquire_lock = start.elapsed();
-        let cipher = acquire_read_lock!(self.cipher);
         let kvp_text;
         match kvp {
             DbKeyValuePair::MasterSeed(seed) => {
@@ -388,6 +393,7 @@ impl WalletSqliteDatabase {
                     None
                 };
 
+                let cipher = self.unlocked_cipher()?;
                 let client_key_value = ClientKeyValueSql::new(k, v, &cipher)?;
 
                 client_key_value.set(&mut conn)?;
@@ -480,9 +486,14 @@ impl WalletSqliteDatabase {
         Ok(None)
     }
 
-    pub fn cipher(&self) -> XChaCha20Poly1305 {
-        let cipher = acquire_read_lock!(self.cipher);
-        (*cipher).clone()
+    pub fn cipher(&self) -> Result<XChaCha20Poly1305, WalletStorageError> {
+        self.unlocked_cipher()
+    }
+
+    /// Returns the handle to the main database cipher. The other backends on this database share it, so that locking
+    /// the wallet database locks them too.
+    pub fn shared_cipher(&self) -> SharedCipher {
+        self.cipher.clone()
     }
 }
 
@@ -625,6 +636,20 @@ impl WalletBackend for WalletSqliteDatabase {
         Ok(())
     }
 
+    fn lock(&self) {
+        self.cipher.clear();
+    }
+
+    fn unlock(&self, passphrase: &SafePassword) -> Result<(), WalletStorageError> {
+        let cipher = get_db_cipher(&self.database_connection, passphrase)?;
+        self.cipher.set(cipher);
+        Ok(())
+    }
+
+    fn is_locked(&self) -> bool {
+        self.cipher.is_cleared()
+    }
+
     fn create_burnt_proof(
         &self,
         id: u32,
@@ -632,7 +657,7 @@ impl WalletBackend for WalletSqliteDatabase {
         payload: String,
     ) -> Result<(), WalletStorageError> {
         let mut conn = self.database_connection.get_pooled_connection()?;
-        let cipher = acquire_read_lock!(self.cipher);
+        let cipher = self.unlocked_cipher()?;
 
         BurntProofSql::new(
             id,
@@ -717,6 +742,35 @@ impl WalletBackend for WalletSqliteDatabase {
         BurntProofSql::delete(id, &mut conn)?;
         Ok(())
     }
+
+    fn save_scheduled_payment(&self, payment: &ScheduledPayment) -> Result<(), WalletStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        let cipher = self.unlocked_cipher()?;
+        ScheduledPaymentSql::new(payment, &cipher)?.set(&mut conn)
+    }
+
+    fn fetch_scheduled_payment(&self, id: ScheduledPaymentId) -> Result<Option<ScheduledPayment>, WalletStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        ScheduledPaymentSql::get(id, &mut conn)?
+            .map(|entry| ScheduledPayment::try_from(self.decrypt_value(entry)?))
+            .transpose()
+    }
+
+    fn fetch_scheduled_payments(&self) -> Result<Vec<ScheduledPayment>, WalletStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        ScheduledPaymentSql::index(&mut conn)?
+            .into_iter()
+            .map(|entry| ScheduledPayment::try_from(self.decrypt_value(entry)?))
+            .collect()
+    }
+
+    fn fetch_due_scheduled_payments(&self, now: NaiveDateTime) -> Result<Vec<ScheduledPayment>, WalletStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        ScheduledPaymentSql::index_due(now, &mut conn)?
+            .into_iter()
+            .map(|entry| ScheduledPayment::try_from(self.decrypt_value(entry)?))
+            .collect()
+    }
 }
 
 /// Derive a secondary database key and associated commitment
@@ -1077,9 +1131,38 @@ impl Encryptable<XChaCha20Poly1305> for BurntProofSql {
 
 #[cfg(test)]
 mod test {
+    use std::{convert::TryFrom, time::Duration};
+
+    use chrono::Utc;
+    use diesel::{Connection, SqliteConnection};
     use tari_common_sqlite::sqlite_connection_pool::PooledDbConnection;
-    use tari_common_types::encryption::{decrypt_bytes_integral_nonce, Encryptable};
-    use tari_key_manager::cipher_seed::CipherSeed;
+    use tari_common_types::{
+        encryption::{decrypt_bytes_integral_nonce, Encryptable},
+        tari_address::TariAddress,
+        transaction::TxId,
+        types::PublicKey,
+    };
+    use tari_comms::peer_manager::PeerFeatures;
+    use tari_contacts::contacts_service::{
+        error::ContactsServiceStorageError,
+        storage::{
+            database::{
+                ContactsBackend,
+                DbKey as ContactsDbKey,
+                DbKeyValuePair as ContactsDbKeyValuePair,
+                DbValue as ContactsDbValue,
+                WriteOperation as ContactsWriteOperation,
+            },
+            types::contacts::ContactSql,
+        },
+        types::Contact,
+    };
+    use tari_core::transactions::{key_manager::TariKeyId, tari_amount::MicroMinotari};
+    use tari_key_manager::{
+        cipher_seed::CipherSeed,
+        key_manager_service::storage::database::{KeyManagerBackend, KeyManagerState},
+    };
+    use tari_script::{script, ExecutionStack};
     use tari_test_utils::random::string;
     use tari_utilities::{
         hex::{from_hex, Hex},
@@ -1088,10 +1171,35 @@ mod test {
     };
     use tempfile::tempdir;
 
-    use crate::storage::{
-        database::{DbKey, DbValue, WalletBackend},
-        sqlite_db::wallet::{ClientKeyValueSql, WalletSettingSql, WalletSqliteDatabase},
-        sqlite_utilities::run_migration_and_create_sqlite_connection,
+    use crate::{
+        error::WalletStorageError,
+        output_manager_service::{
+            error::OutputManagerStorageError,
+            storage::{
+                database::{
+                    DbKey as OutputDbKey,
+                    DbKeyValuePair as OutputDbKeyValuePair,
+                    DbValue as OutputDbValue,
+                    OutputManagerBackend,
+                    WriteOperation as OutputWriteOperation,
+                },
+                models::KnownOneSidedPaymentScript,
+                sqlite_db::KnownOneSidedPaymentScriptSql,
+            },
+        },
+        scheduled_payments_service::models::{Recurrence, ScheduledPayment, ScheduledPaymentStatus},
+        storage::{
+            database::{DbKey, DbKeyValuePair, DbValue, WalletBackend, WriteOperation},
+            sqlite_db::{
+                scheduled_payments::ScheduledPaymentSql,
+                wallet::{ClientKeyValueSql, WalletSettingSql, WalletSqliteDatabase},
+            },
+            sqlite_utilities::{initialize_sqlite_database_backends, run_migration_and_create_sqlite_connection},
+        },
+        transaction_service::{
+            error::TransactionStorageError,
+            storage::database::{DbKey as TransactionDbKey, TransactionBackend},
+        },
     };
     #[test]
     fn test_passphrase() {
@@ -1137,6 +1245,187 @@ mod test {
         assert!(WalletSqliteDatabase::new(connection, "new passphrase".to_string().into()).is_ok());
     }
 
+    #[test]
+    fn test_lock_and_unlock() {
+        // Set up a database
+        let db_name = format!("{}.sqlite3", string(8).as_str());
+        let db_tempdir = tempdir().unwrap();
+        let db_folder = db_tempdir.path().to_str().unwrap().to_string();
+        let db_path = format!("{}/{}", db_folder, db_name);
+        let connection = run_migration_and_create_sqlite_connection(db_path, 16).unwrap();
+
+        let db = WalletSqliteDatabase::new(connection, "passphrase".to_string().into()).unwrap();
+        let seed = CipherSeed::new();
+        db.write(WriteOperation::Insert(DbKeyValuePair::MasterSeed(seed.clone())))
+            .unwrap();
+        assert!(!db.is_locked());
+
+        // Encrypted values cannot be read or written while locked
+        db.lock();
+        assert!(db.is_locked());
+        assert!(matches!(
+            db.fetch(&DbKey::MasterSeed),
+            Err(WalletStorageError::DatabaseLocked)
+        ));
+        assert!(db.cipher().is_err());
+
+        // Unencrypted values are still available
+        db.write(WriteOperation::Insert(DbKeyValuePair::CommsFeatures(
+            PeerFeatures::COMMUNICATION_CLIENT,
+        )))
+        .unwrap();
+
+        // The wrong passphrase does not unlock the database
+        assert!(db.unlock(&"evil passphrase".to_string().into()).is_err());
+        assert!(db.is_locked());
+
+        // The correct passphrase does
+        db.unlock(&"passphrase".to_string().into()).unwrap();
+        assert!(!db.is_locked());
+        match db.fetch(&DbKey::MasterSeed).unwrap() {
+            Some(DbValue::MasterSeed(s)) => assert_eq!(s.entropy(), seed.entropy()),
+            _ => panic!("Master seed should be present"),
+        }
+    }
+
+    fn known_script() -> KnownOneSidedPaymentScript {
+        KnownOneSidedPaymentScript {
+            script_hash: vec![1u8; 32],
+            script_key_id: TariKeyId::Managed {
+                branch: "script".to_string(),
+                index: 7,
+            },
+            script: script!(Nop),
+            input: ExecutionStack::new(vec![]),
+            script_lock_height: 0,
+        }
+    }
+
+    fn fetch_known_scripts<T: OutputManagerBackend>(
+        backend: &T,
+    ) -> Result<Vec<KnownOneSidedPaymentScript>, OutputManagerStorageError> {
+        match backend.fetch(&OutputDbKey::KnownOneSidedPaymentScripts)? {
+            Some(OutputDbValue::KnownOneSidedPaymentScripts(scripts)) => Ok(scripts),
+            _ => panic!("Expected known scripts"),
+        }
+    }
+
+    fn fetch_contacts<T: ContactsBackend>(backend: &T) -> Result<Vec<Contact>, ContactsServiceStorageError> {
+        match backend.fetch(&ContactsDbKey::Contacts)? {
+            Some(ContactsDbValue::Contacts(contacts)) => Ok(contacts),
+            _ => panic!("Expected contacts"),
+        }
+    }
+
+    #[test]
+    fn test_lock_applies_to_all_backends() {
+        let db_tempdir = tempdir().unwrap();
+        let db_path = db_tempdir.path().join(format!("{}.sqlite3", string(8).as_str()));
+        let (wallet_backend, transaction_backend, output_manager_backend, contacts_backend, key_manager_backend) =
+            initialize_sqlite_database_backends(db_path, "passphrase".to_string().into(), 16).unwrap();
+        let key_manager_state = KeyManagerState {
+            branch_seed: "branch".to_string(),
+            primary_key_index: 1,
+        };
+        KeyManagerBackend::<PublicKey>::add_key_manager(&key_manager_backend, key_manager_state.clone()).unwrap();
+        transaction_backend
+            .fetch(&TransactionDbKey::CompletedTransactions)
+            .unwrap();
+        output_manager_backend
+            .write(OutputWriteOperation::Insert(
+                OutputDbKeyValuePair::KnownOneSidedPaymentScripts(known_script()),
+            ))
+            .unwrap();
+        let contact = Contact::new("Alice".to_string(), TariAddress::default(), None, None, false);
+        contacts_backend
+            .write(ContactsWriteOperation::Upsert(Box::new(
+                ContactsDbKeyValuePair::Contact(contact.address.clone(), contact.clone()),
+            )))
+            .unwrap();
+
+        // Locking the wallet database removes the cipher from the backends sharing it, so nothing can be decrypted
+        wallet_backend.lock();
+        assert!(KeyManagerBackend::<PublicKey>::get_key_manager(&key_manager_backend, "branch").is_err());
+        assert!(matches!(
+            transaction_backend.fetch(&TransactionDbKey::CompletedTransactions),
+            Err(TransactionStorageError::DatabaseLocked)
+        ));
+        assert!(matches!(
+            fetch_known_scripts(&output_manager_backend),
+            Err(OutputManagerStorageError::DatabaseLocked)
+        ));
+        assert!(matches!(
+            output_manager_backend.fetch_sorted_unspent_outputs(),
+            Err(OutputManagerStorageError::DatabaseLocked)
+        ));
+        assert!(matches!(
+            fetch_contacts(&contacts_backend),
+            Err(ContactsServiceStorageError::DatabaseLocked)
+        ));
+
+        wallet_backend.unlock(&"passphrase".to_string().into()).unwrap();
+        assert_eq!(
+            KeyManagerBackend::<PublicKey>::get_key_manager(&key_manager_backend, "branch").unwrap(),
+            Some(key_manager_state)
+        );
+        transaction_backend
+            .fetch(&TransactionDbKey::CompletedTransactions)
+            .unwrap();
+        assert_eq!(fetch_known_scripts(&output_manager_backend).unwrap(), vec![
+            known_script()
+        ]);
+        assert_eq!(fetch_contacts(&contacts_backend).unwrap(), vec![contact]);
+    }
+
+    #[test]
+    fn test_rotated_passphrase_protects_all_backends() {
+        let db_tempdir = tempdir().unwrap();
+        let db_path = db_tempdir.path().join(format!("{}.sqlite3", string(8).as_str()));
+        let (wallet_backend, _, output_manager_backend, contacts_backend, _) =
+            initialize_sqlite_database_backends(db_path.clone(), "passphrase".to_string().into(), 16).unwrap();
+        output_manager_backend
+            .write(OutputWriteOperation::Insert(
+                OutputDbKeyValuePair::KnownOneSidedPaymentScripts(known_script()),
+            ))
+            .unwrap();
+        let contact = Contact::new("Alice".to_string(), TariAddress::default(), None, None, false);
+        contacts_backend
+            .write(ContactsWriteOperation::Upsert(Box::new(
+                ContactsDbKeyValuePair::Contact(contact.address.clone(), contact.clone()),
+            )))
+            .unwrap();
+
+        // The rows are encrypted under the main key, which the rotation encrypts under the new passphrase
+        wallet_backend
+            .change_passphrase(&"passphrase".to_string().into(), &"new passphrase".to_string().into())
+            .unwrap();
+        wallet_backend.lock();
+        assert!(wallet_backend.unlock(&"passphrase".to_string().into()).is_err());
+        assert!(matches!(
+            fetch_known_scripts(&output_manager_backend),
+            Err(OutputManagerStorageError::DatabaseLocked)
+        ));
+        assert!(matches!(
+            fetch_contacts(&contacts_backend),
+            Err(ContactsServiceStorageError::DatabaseLocked)
+        ));
+
+        wallet_backend.unlock(&"new passphrase".to_string().into()).unwrap();
+        assert_eq!(fetch_known_scripts(&output_manager_backend).unwrap(), vec![
+            known_script()
+        ]);
+        assert_eq!(fetch_contacts(&contacts_backend).unwrap(), vec![contact.clone()]);
+
+        // The rows are not stored in plaintext
+        let mut conn = SqliteConnection::establish(db_path.to_str().unwrap()).unwrap();
+        let stored_scripts = KnownOneSidedPaymentScriptSql::index(&mut conn).unwrap();
+        assert_eq!(stored_scripts.len(), 1);
+        assert_ne!(stored_scripts[0].private_key, known_script().script_key_id.to_string());
+        let stored_contacts = ContactSql::index(&mut conn).unwrap();
+        assert_eq!(stored_contacts.len(), 1);
+        assert_ne!(stored_contacts[0].alias, contact.alias);
+    }
+
     #[test]
     #[allow(unused_must_use)]
     fn test_malleated_secondary_key_hash() {
@@ -1173,7 +1462,7 @@ mod test {
         let seed = CipherSeed::new();
         let passphrase = "a very very secret key example.".to_string().into();
         let db = WalletSqliteDatabase::new(connection.clone(), passphrase).unwrap();
-        let cipher = db.cipher();
+        let cipher = db.cipher().unwrap();
 
         let mut key_values = vec![
             ClientKeyValueSql::new("key1".to_string(), "value1".to_string(), &cipher).unwrap(),
@@ -1204,7 +1493,7 @@ mod test {
         };
 
         for kv in &mut key_values {
-            *kv = kv.clone().decrypt(&db.cipher()).unwrap();
+            *kv = kv.clone().decrypt(&db.cipher().unwrap()).unwrap();
             match db.fetch(&DbKey::ClientKey(kv.key.clone())).unwrap().unwrap() {
                 DbValue::ClientValue(v) => {
                     assert_eq!(kv.value, v);
@@ -1260,7 +1549,7 @@ mod test {
 
         let passphrase = "a very very secret key example.".to_string().into();
         let db = WalletSqliteDatabase::new(connection, passphrase).unwrap();
-        let cipher = db.cipher();
+        let cipher = db.cipher().unwrap();
 
         ClientKeyValueSql::new(key1.clone(), value1.clone(), &cipher)
             .unwrap()
@@ -1319,7 +1608,7 @@ mod test {
         assert_eq!(db_seed.len(), 146);
 
         let decrypted_db_seed = decrypt_bytes_integral_nonce(
-            &wallet.cipher(),
+            &wallet.cipher().unwrap(),
             b"wallet_setting_master_seed".to_vec(),
             &from_hex(db_seed.as_str()).unwrap(),
         )
@@ -1327,4 +1616,62 @@ mod test {
 
         assert_eq!(decrypted_db_seed, seed_bytes);
     }
+
+    #[test]
+    fn test_scheduled_payment_store() {
+        let db_name = format!("{}.sqlite3", string(8).as_str());
+        let db_tempdir = tempdir().unwrap();
+        let db_folder = db_tempdir.path().to_str().unwrap().to_string();
+        let connection = run_migration_and_create_sqlite_connection(format!("{}{}", db_folder, db_name), 16).unwrap();
+
+        let passphrase = "a very very secret key example.".to_string().into();
+        let db = WalletSqliteDatabase::new(connection.clone(), passphrase).unwrap();
+
+        let now = Utc::now().naive_utc();
+        let mut due = ScheduledPayment {
+            id: 1,
+            destination: TariAddress::default(),
+            amount: MicroMinotari::from(1000),
+            fee_per_gram: MicroMinotari::from(5),
+            message: "monthly salary".to_string(),
+            recurrence: Some(Recurrence {
+                interval: Duration::from_secs(30 * 24 * 60 * 60),
+                max_payments: Some(12),
+            }),
+            next_payment_at: now - chrono::Duration::minutes(1),
+            payments_made: 0,
+            last_tx_id: None,
+            status: ScheduledPaymentStatus::Active,
+            created_at: now,
+        };
+        let future = ScheduledPayment {
+            id: 2,
+            recurrence: None,
+            next_payment_at: now + chrono::Duration::days(1),
+            ..due.clone()
+        };
+        db.save_scheduled_payment(&due).unwrap();
+        db.save_scheduled_payment(&future).unwrap();
+
+        // The payment details must not be stored in the clear
+        let mut conn = connection.get_pooled_connection().unwrap();
+        let stored = ScheduledPaymentSql::get(1, &mut conn).unwrap().unwrap();
+        assert!(ScheduledPayment::try_from(stored).is_err());
+
+        assert_eq!(db.fetch_scheduled_payment(1).unwrap(), Some(due.clone()));
+        assert_eq!(db.fetch_scheduled_payment(3).unwrap(), None);
+        assert_eq!(db.fetch_scheduled_payments().unwrap(), vec![
+            due.clone(),
+            future.clone()
+        ]);
+        assert_eq!(db.fetch_due_scheduled_payments(now).unwrap(), vec![due.clone()]);
+
+        due.record_payment(TxId::from(123u64), now);
+        db.save_scheduled_payment(&due).unwrap();
+        assert_eq!(
+            db.fetch_scheduled_payment(1).unwrap().unwrap().last_tx_id,
+            Some(TxId::from(123u64))
+        );
+        assert!(db.fetch_due_scheduled_payments(now).unwrap().is_empty());
+    }
 }
@@ -25,6 +25,7 @@ use std::{fmt, time::Duration};
 use log::*;
 use serde::{Deserialize, Serialize};
 use tari_common::configuration::serializers;
+use tari_core::transactions::tari_amount::MicroMinotari;
 
 const LOG_TARGET: &str = "wallet::transaction_service::config";
 
@@ -63,12 +64,28 @@ pub struct TransactionServiceConfig {
     /// This option specifies the transaction routing mechanism as being directly between wallets, making use of store
     /// and forward or using any combination of these.
     pub transaction_routing_mechanism: TransactionRoutingMechanism,
+    /// The number of randomly selected relay peers that direct transaction negotiation messages are onion-routed
+    /// through, so that the recipient and network observers cannot link the transaction to this node. A value of zero
+    /// disables onion routing.
+    pub onion_routing_hops: usize,
     /// This is the size of the event channel used to communicate transaction status events to the wallet's UI. A busy
     /// console wallet doing thousands of bulk payments or used for stress testing needs a fairly big size.
     pub transaction_event_channel_size: usize,
     /// This is the timeout period that will be used to re-submit transactions not found in the mempool
     #[serde(with = "serializers::seconds")]
     pub transaction_mempool_resubmission_window: Duration,
+    /// Outbound transactions spending more than this amount are held until they are approved. Unlimited if not set.
+    pub per_transaction_spend_limit: Option<MicroMinotari>,
+    /// Outbound transactions that take the total spent over the last 24 hours above this amount are held until they
+    /// are approved. Unlimited if not set.
+    pub daily_spend_limit: Option<MicroMinotari>,
+    /// This is the time a transaction held for exceeding a spending limit waits to be approved before it is rejected
+    #[serde(with = "serializers::seconds")]
+    pub spend_approval_timeout: Duration,
+    /// This is how long a send with a recipient liveness check waits for the recipient to respond to a ping before
+    /// treating them as offline
+    #[serde(with = "serializers::seconds")]
+    pub recipient_liveness_timeout: Duration,
 }
 
 impl Default for TransactionServiceConfig {
@@ -85,8 +102,13 @@ impl Default for TransactionServiceConfig {
             num_confirmations_required: 3,
             max_tx_query_batch_size: 20,
             transaction_routing_mechanism: TransactionRoutingMechanism::default(),
+            onion_routing_hops: 0,
             transaction_event_channel_size: 1000,
             transaction_mempool_resubmission_window: Duration::from_secs(600),
+            per_transaction_spend_limit: None,
+            daily_spend_limit: None,
+            spend_approval_timeout: Duration::from_secs(3600),
+            recipient_liveness_timeout: Duration::from_secs(10),
         }
     }
 }
@@ -32,6 +32,7 @@ use tari_common_types::{
 use tari_comms::{connectivity::ConnectivityError, peer_manager::node_id::NodeIdError, protocol::rpc::RpcError};
 use tari_comms_dht::outbound::DhtOutboundError;
 use tari_core::transactions::{
+    payment_proof::PaymentProofError,
     transaction_components::{EncryptedDataError, TransactionError},
     transaction_protocol::TransactionProtocolError,
 };
@@ -47,6 +48,7 @@ use crate::{
     error::WalletStorageError,
     output_manager_service::error::OutputManagerError,
     transaction_service::{
+        spending_limits::SpendLimit,
         storage::{database::DbKey, sqlite_db::CompletedTransactionConversionError},
         utc::NegativeDurationError,
     },
@@ -62,6 +64,8 @@ pub enum TransactionServiceError {
     OneSidedTransactionError(String),
     #[error("Transaction Protocol Error: `{0}`")]
     TransactionProtocolError(#[from] TransactionProtocolError),
+    #[error("Payment proof error: `{0}`")]
+    PaymentProofError(#[from] PaymentProofError),
     #[error("The message being processed is not recognized by the Transaction Manager")]
     InvalidMessageTypeError,
     #[error("A message for a specific tx_id has been repeated")]
@@ -189,6 +193,14 @@ pub enum TransactionServiceError {
     InvalidKeyId(String),
     #[error("Invalid key manager data: `{0}`")]
     KeyManagerServiceError(#[from] KeyManagerServiceError),
+    #[error("Transaction exceeds the {0} and was rejected")]
+    TransactionApprovalRejected(SpendLimit),
+    #[error("Transaction exceeds the {0} and was not approved in time")]
+    TransactionApprovalTimedOut(SpendLimit),
+    #[error("No transaction is awaiting approval with id `{0}`")]
+    PendingApprovalNotFound(u64),
+    #[error("The recipient did not respond to a liveness check and appears to be offline")]
+    RecipientOffline,
 }
 
 impl From<RangeProofError> for TransactionServiceError {
@@ -261,6 +273,8 @@ pub enum TransactionStorageError {
     AlreadyEncrypted,
     #[error("Aead error: `{0}`")]
     AeadError(String),
+    #[error("The wallet database is locked")]
+    DatabaseLocked,
     #[error("Transaction (TxId: '{0}') is not mined")]
     TransactionNotMined(TxId),
     #[error("Conversion error: `{0}`")]
@@ -25,6 +25,7 @@ use std::{
     fmt,
     fmt::{Display, Formatter},
     sync::Arc,
+    time::Duration,
 };
 
 use chrono::NaiveDateTime;
@@ -59,10 +60,12 @@ use crate::{
     output_manager_service::UtxoSelectionCriteria,
     transaction_service::{
         error::TransactionServiceError,
+        spending_limits::{PendingTransactionApproval, SpendLimit},
         storage::models::{
             CompletedTransaction,
             InboundTransaction,
             OutboundTransaction,
+            TransactionEventRecord,
             TxCancellationReason,
             WalletTransaction,
         },
@@ -70,6 +73,36 @@ use crate::{
     OperationId,
 };
 
+/// Whether an interactive send first checks that the recipient is online via the liveness protocol, and what to do
+/// if they are not
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RecipientLivenessCheck {
+    /// Start the interactive transaction protocol straight away
+    #[default]
+    Disabled,
+    /// Ping the recipient and send a one-sided payment instead if they do not respond in time
+    FallbackToOneSided,
+    /// Ping the recipient and fail the send with `RecipientOffline` if they do not respond in time
+    Fail,
+}
+
+/// How a transaction sent with a recipient liveness check was ultimately sent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SendTransactionOutcome {
+    /// The recipient was online (or was not checked) and the interactive protocol was started
+    Interactive(TxId),
+    /// The recipient was offline and a one-sided payment was sent instead
+    OneSided(TxId),
+}
+
+impl SendTransactionOutcome {
+    pub fn tx_id(&self) -> TxId {
+        match self {
+            Self::Interactive(tx_id) | Self::OneSided(tx_id) => *tx_id,
+        }
+    }
+}
+
 /// API Request enum
 #[allow(clippy::large_enum_variant)]
 #[derive(Clone)]
@@ -89,6 +122,7 @@ pub enum TransactionServiceRequest {
         output_features: Box<OutputFeatures>,
         fee_per_gram: MicroMinotari,
         message: String,
+        recipient_check: RecipientLivenessCheck,
     },
     BurnTari {
         amount: MicroMinotari,
@@ -134,6 +168,7 @@ pub enum TransactionServiceRequest {
     },
     SendShaAtomicSwapTransaction(TariAddress, MicroMinotari, UtxoSelectionCriteria, MicroMinotari, String),
     CancelTransaction(TxId),
+    CancelAllStaleTransactions(Duration),
     ImportUtxoWithStatus {
         amount: MicroMinotari,
         source_address: TariAddress,
@@ -163,6 +198,18 @@ pub enum TransactionServiceRequest {
     GetFeePerGramStatsPerBlock {
         count: usize,
     },
+    /// Releases an outbound transaction that is held for exceeding a spending limit
+    ApproveTransaction(u64),
+    /// Rejects an outbound transaction that is held for exceeding a spending limit
+    RejectTransaction(u64),
+    GetPendingApprovals,
+    /// Returns up to `limit` transaction event journal entries with a sequence number greater than `since_seq`
+    GetTransactionEventsSince {
+        since_seq: u64,
+        limit: usize,
+    },
+    /// Returns the payment proof receipt the recipient returned for an outbound transaction
+    GetPaymentReceipt(TxId),
 }
 
 impl fmt::Display for TransactionServiceRequest {
@@ -211,6 +258,9 @@ impl fmt::Display for TransactionServiceRequest {
                 write!(f, "SendShaAtomicSwapTransaction (to {}, {}, {})", k, v, msg)
             },
             Self::CancelTransaction(t) => write!(f, "CancelTransaction ({})", t),
+            Self::CancelAllStaleTransactions(older_than) => {
+                write!(f, "CancelAllStaleTransactions (older than {:.0?})", older_than)
+            },
             Self::ImportUtxoWithStatus {
                 amount,
                 source_address,
@@ -251,14 +301,31 @@ impl fmt::Display for TransactionServiceRequest {
             TransactionServiceRequest::RegisterCodeTemplate { template_name, .. } => {
                 write!(f, "RegisterCodeTemplate: {}", template_name)
             },
+            Self::ApproveTransaction(approval_id) => write!(f, "ApproveTransaction ({})", approval_id),
+            Self::RejectTransaction(approval_id) => write!(f, "RejectTransaction ({})", approval_id),
+            Self::GetPendingApprovals => write!(f, "GetPendingApprovals"),
+            Self::GetTransactionEventsSince { since_seq, limit } => {
+                write!(f, "GetTransactionEventsSince (seq: {}, limit: {})", since_seq, limit)
+            },
+            Self::GetPaymentReceipt(tx_id) => write!(f, "GetPaymentReceipt ({})", tx_id),
         }
     }
 }
 
 /// API Response enum
 #[derive(Debug)]
+/// The outcome of cancelling all stale pending transactions
+#[derive(Debug, Clone, Default)]
+pub struct StaleTransactionCancellationSummary {
+    /// Transactions that were cancelled and had their encumbered outputs released
+    pub cancelled: Vec<TxId>,
+    /// Transactions that could not be cancelled and remain pending, with the reason
+    pub failed: Vec<(TxId, String)>,
+}
+
 pub enum TransactionServiceResponse {
     TransactionSent(TxId),
+    OneSidedFallbackTransactionSent(TxId),
     BurntTransactionSent {
         tx_id: TxId,
         proof: Box<BurntProof>,
@@ -268,6 +335,7 @@ pub enum TransactionServiceResponse {
         template_registration: Box<CodeTemplateRegistration>,
     },
     TransactionCancelled,
+    StaleTransactionsCancelled(StaleTransactionCancellationSummary),
     PendingInboundTransactions(HashMap<TxId, InboundTransaction>),
     PendingOutboundTransactions(HashMap<TxId, OutboundTransaction>),
     CompletedTransactions(HashMap<TxId, CompletedTransaction>),
@@ -286,6 +354,11 @@ pub enum TransactionServiceResponse {
     CompletedTransactionValidityChanged,
     ShaAtomicSwapTransactionSent(Box<(TxId, PublicKey, TransactionOutput)>),
     FeePerGramStatsPerBlock(FeePerGramStatsResponse),
+    TransactionApproved,
+    TransactionRejected,
+    PendingApprovals(Vec<PendingTransactionApproval>),
+    TransactionEvents(Vec<TransactionEventRecord>),
+    PaymentReceipt(Option<Signature>),
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq, Default)]
@@ -338,9 +411,17 @@ pub enum TransactionEvent {
         num_confirmations: u64,
         is_valid: bool,
     },
+    /// A previously mined transaction's block was reorged out of the chain
+    TransactionUnmined(TxId),
     TransactionValidationStateChanged(OperationId),
     TransactionValidationCompleted(OperationId),
     TransactionValidationFailed(OperationId, u64),
+    /// An outbound transaction exceeds a spending limit and is held until it is approved or rejected
+    TransactionApprovalRequired {
+        approval_id: u64,
+        amount: MicroMinotari,
+        exceeded_limit: SpendLimit,
+    },
     Error(String),
 }
 
@@ -408,6 +489,9 @@ impl fmt::Display for TransactionEvent {
                      {is_valid}",
                 )
             },
+            TransactionEvent::TransactionUnmined(tx) => {
+                write!(f, "TransactionUnmined for {tx}")
+            },
             TransactionEvent::Error(error) => {
                 write!(f, "Error:{error}")
             },
@@ -423,6 +507,16 @@ impl fmt::Display for TransactionEvent {
             TransactionEvent::NewBlockMined(tx_id) => {
                 write!(f, "New block mined {tx_id}")
             },
+            TransactionEvent::TransactionApprovalRequired {
+                approval_id,
+                amount,
+                exceeded_limit,
+            } => {
+                write!(
+                    f,
+                    "Transaction approval(#{approval_id}) required for {amount}, exceeds the {exceeded_limit}"
+                )
+            },
         }
     }
 }
@@ -435,6 +529,22 @@ pub struct FeePerGramStatsResponse {
     pub stats: Vec<FeePerGramStat>,
 }
 
+impl FeePerGramStatsResponse {
+    /// The number of blocks expected until a transaction paying `fee_per_gram` is mined, i.e. 1 for the next block,
+    /// based on the minimum fee per gram of the mempool transactions expected in each upcoming block. Returns None
+    /// if the fee per gram is too low to be expected within the blocks covered by these stats.
+    pub fn expected_blocks_until_mined(&self, fee_per_gram: MicroMinotari) -> Option<u64> {
+        if self.stats.is_empty() {
+            // The mempool is empty, so the transaction is expected in the next block
+            return Some(1);
+        }
+        self.stats
+            .iter()
+            .find(|stat| fee_per_gram >= stat.min_fee_per_gram)
+            .map(|stat| stat.order + 1)
+    }
+}
+
 impl From<proto::base_node::GetMempoolFeePerGramStatsResponse> for FeePerGramStatsResponse {
     fn from(value: proto::base_node::GetMempoolFeePerGramStatsResponse) -> Self {
         Self {
@@ -484,6 +594,7 @@ impl TransactionServiceHandle {
                 output_features: Box::new(output_features),
                 fee_per_gram,
                 message,
+                recipient_check: RecipientLivenessCheck::Disabled,
             })
             .await??
         {
@@ -492,6 +603,39 @@ impl TransactionServiceHandle {
         }
     }
 
+    /// Sends a transaction, first pinging the recipient if `recipient_check` is enabled so that an offline recipient
+    /// can be paid one-sided (or the send failed) rather than leaving the transaction pending until they come online
+    pub async fn send_transaction_with_recipient_check(
+        &mut self,
+        destination: TariAddress,
+        amount: MicroMinotari,
+        selection_criteria: UtxoSelectionCriteria,
+        output_features: OutputFeatures,
+        fee_per_gram: MicroMinotari,
+        message: String,
+        recipient_check: RecipientLivenessCheck,
+    ) -> Result<SendTransactionOutcome, TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::SendTransaction {
+                destination,
+                amount,
+                selection_criteria,
+                output_features: Box::new(output_features),
+                fee_per_gram,
+                message,
+                recipient_check,
+            })
+            .await??
+        {
+            TransactionServiceResponse::TransactionSent(tx_id) => Ok(SendTransactionOutcome::Interactive(tx_id)),
+            TransactionServiceResponse::OneSidedFallbackTransactionSent(tx_id) => {
+                Ok(SendTransactionOutcome::OneSided(tx_id))
+            },
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
     pub async fn register_validator_node(
         &mut self,
         amount: MicroMinotari,
@@ -638,6 +782,22 @@ impl TransactionServiceHandle {
         }
     }
 
+    /// Cancel every pending transaction created more than `older_than` ago and release its encumbered outputs back to
+    /// the spendable pool.
+    pub async fn cancel_all_stale(
+        &mut self,
+        older_than: Duration,
+    ) -> Result<StaleTransactionCancellationSummary, TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::CancelAllStaleTransactions(older_than))
+            .await??
+        {
+            TransactionServiceResponse::StaleTransactionsCancelled(summary) => Ok(summary),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
     pub async fn get_pending_inbound_transactions(
         &mut self,
     ) -> Result<HashMap<TxId, InboundTransaction>, TransactionServiceError> {
@@ -941,4 +1101,66 @@ impl TransactionServiceHandle {
             _ => Err(TransactionServiceError::UnexpectedApiResponse),
         }
     }
+
+    /// Releases an outbound transaction that is held for exceeding a spending limit so that it can be sent
+    pub async fn approve_transaction(&mut self, approval_id: u64) -> Result<(), TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::ApproveTransaction(approval_id))
+            .await??
+        {
+            TransactionServiceResponse::TransactionApproved => Ok(()),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Rejects an outbound transaction that is held for exceeding a spending limit, it will not be sent
+    pub async fn reject_transaction(&mut self, approval_id: u64) -> Result<(), TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::RejectTransaction(approval_id))
+            .await??
+        {
+            TransactionServiceResponse::TransactionRejected => Ok(()),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    pub async fn get_pending_approvals(&mut self) -> Result<Vec<PendingTransactionApproval>, TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::GetPendingApprovals)
+            .await??
+        {
+            TransactionServiceResponse::PendingApprovals(approvals) => Ok(approvals),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Fetches up to `limit` recorded transaction events with a sequence number greater than `since_seq`, oldest first
+    pub async fn get_transaction_events_since(
+        &mut self,
+        since_seq: u64,
+        limit: usize,
+    ) -> Result<Vec<TransactionEventRecord>, TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::GetTransactionEventsSince { since_seq, limit })
+            .await??
+        {
+            TransactionServiceResponse::TransactionEvents(events) => Ok(events),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    pub async fn get_payment_receipt(&mut self, tx_id: TxId) -> Result<Option<Signature>, TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::GetPaymentReceipt(tx_id))
+            .await??
+        {
+            TransactionServiceResponse::PaymentReceipt(receipt) => Ok(receipt),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
 }
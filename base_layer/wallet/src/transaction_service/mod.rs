@@ -37,7 +37,7 @@ use tari_core::{
 use tari_p2p::{
     comms_connector::SubscriptionFactory,
     domain_message::DomainMessage,
-    services::utils::map_decode,
+    services::{liveness::LivenessHandle, utils::map_decode},
     tari_message::TariMessageType,
 };
 use tari_service_framework::{
@@ -68,6 +68,7 @@ pub mod error;
 pub mod handle;
 pub mod protocols;
 pub mod service;
+pub mod spending_limits;
 pub mod storage;
 pub mod tasks;
 mod utc;
@@ -233,6 +234,7 @@ where
             let core_key_manager_service = handles.expect_handle::<TKeyManagerInterface>();
             let connectivity = handles.expect_handle::<WalletConnectivityHandle>();
             let base_node_service_handle = handles.expect_handle::<BaseNodeServiceHandle>();
+            let liveness = handles.get_handle::<LivenessHandle>();
 
             let result = TransactionService::new(
                 config,
@@ -254,6 +256,7 @@ where
                 factories,
                 handles.get_shutdown_signal(),
                 base_node_service_handle,
+                liveness,
             )
             .start()
             .await;
@@ -28,9 +28,11 @@ use log::*;
 use tari_common_types::{
     tari_address::TariAddress,
     transaction::{TransactionDirection, TransactionStatus, TxId},
+    types::Commitment,
 };
 use tari_core::transactions::{
     key_manager::TransactionKeyManagerInterface,
+    payment_proof::PaymentProof,
     transaction_components::Transaction,
     transaction_protocol::{recipient::RecipientState, sender::TransactionSenderMessage},
 };
@@ -142,13 +144,31 @@ where
 
             let amount = data.amount;
 
-            let rtp = self
+            let mut rtp = self
                 .resources
                 .output_manager_service
                 .get_recipient_transaction(self.sender_message.clone())
                 .await
                 .map_err(|e| TransactionServiceProtocolError::new(self.id, TransactionServiceError::from(e)))?;
 
+            // Acknowledge the payment with our wallet key so that the sender can later prove that we were paid
+            let signed_data = rtp
+                .get_signed_data()
+                .map_err(|e| TransactionServiceProtocolError::new(self.id, TransactionServiceError::from(e)))?;
+            let kernel_excess = Commitment::from_public_key(&(&data.public_excess + &signed_data.public_spend_key));
+            let kernel_public_nonce = &data.public_nonce + signed_data.partial_signature.get_public_nonce();
+            let receipt = PaymentProof::sign_receipt(
+                self.resources.wallet_identity.node_identity.secret_key(),
+                &self.resources.wallet_identity.address,
+                &self.source_address,
+                &kernel_excess,
+                &kernel_public_nonce,
+                amount,
+            )
+            .map_err(|e| TransactionServiceProtocolError::new(self.id, TransactionServiceError::from(e)))?;
+            rtp.add_payment_receipt(receipt)
+                .map_err(|e| TransactionServiceProtocolError::new(self.id, TransactionServiceError::from(e)))?;
+
             let inbound_transaction = InboundTransaction::new(
                 data.tx_id,
                 self.source_address.clone(),
@@ -169,6 +189,7 @@ where
                 self.resources.outbound_message_service.clone(),
                 self.resources.config.direct_send_timeout,
                 self.resources.config.transaction_routing_mechanism,
+                self.resources.config.onion_routing_hops,
             )
             .await
             .map_err(|e| TransactionServiceProtocolError::new(self.id, e))?;
@@ -277,6 +298,7 @@ where
                 self.resources.outbound_message_service.clone(),
                 self.resources.config.direct_send_timeout,
                 self.resources.config.transaction_routing_mechanism,
+                self.resources.config.onion_routing_hops,
             )
             .await
             {
@@ -326,6 +348,7 @@ where
                             self.resources.outbound_message_service.clone(),
                             self.resources.config.direct_send_timeout,
                             self.resources.config.transaction_routing_mechanism,
+                            self.resources.config.onion_routing_hops,
                         )
                         .await {
                             Ok(_) => self.resources
@@ -38,6 +38,7 @@ use tari_core::{
     covenants::Covenant,
     transactions::{
         key_manager::TransactionKeyManagerInterface,
+        payment_proof::PaymentProof,
         tari_amount::MicroMinotari,
         transaction_components::OutputFeatures,
         transaction_protocol::{
@@ -69,6 +70,7 @@ use crate::{
             models::{CompletedTransaction, OutboundTransaction, TxCancellationReason},
         },
         tasks::{
+            send_direct_message::send_direct_message,
             send_finalized_transaction::send_finalized_transaction_message,
             send_transaction_cancelled::send_transaction_cancelled_message,
             wait_on_dial::wait_on_dial,
@@ -483,7 +485,8 @@ where
                         info!(target: LOG_TARGET, "Cancelling Transaction Send Protocol (TxId: {})", self.id);
                         let _ = send_transaction_cancelled_message(
                             self.id,self.dest_address.public_key().clone(),
-                            self.resources.outbound_message_service.clone(), )
+                            self.resources.outbound_message_service.clone(),
+                            self.resources.config.onion_routing_hops, )
                         .await.map_err(|e| {
                             warn!(
                                 target: LOG_TARGET,
@@ -541,6 +544,7 @@ where
         let recipient_reply = reply.ok_or_else(|| {
             TransactionServiceProtocolError::new(self.id, TransactionServiceError::TransactionCancelled)
         })?;
+        let payment_receipt = recipient_reply.payment_receipt.clone();
 
         outbound_tx
             .sender_protocol
@@ -565,6 +569,33 @@ where
             .get_transaction()
             .map_err(|e| TransactionServiceProtocolError::new(self.id, TransactionServiceError::from(e)))?;
 
+        // Keep the recipient's receipt, if it is valid for the final kernel, so that a payment proof can be made later
+        match (payment_receipt, tx.body.kernels().first()) {
+            (Some(receipt), Some(kernel))
+                if PaymentProof::verify_receipt(
+                    &receipt,
+                    &outbound_tx.destination_address,
+                    &self.resources.wallet_identity.address,
+                    &kernel.excess,
+                    kernel.excess_sig.get_public_nonce(),
+                    outbound_tx.amount,
+                ) =>
+            {
+                self.resources
+                    .db
+                    .set_payment_receipt(tx_id, &receipt)
+                    .map_err(|e| TransactionServiceProtocolError::new(self.id, TransactionServiceError::from(e)))?;
+            },
+            (Some(_), _) => warn!(
+                target: LOG_TARGET,
+                "Recipient of transaction (TxId: {}) returned an invalid payment receipt", tx_id
+            ),
+            (None, _) => debug!(
+                target: LOG_TARGET,
+                "Recipient of transaction (TxId: {}) did not return a payment receipt", tx_id
+            ),
+        }
+
         let completed_transaction = CompletedTransaction::new(
             tx_id,
             self.resources.wallet_identity.address.clone(),
@@ -597,6 +628,7 @@ where
             self.resources.outbound_message_service.clone(),
             self.resources.config.direct_send_timeout,
             self.resources.config.transaction_routing_mechanism,
+            self.resources.config.onion_routing_hops,
         )
         .await
         .map_err(|e| TransactionServiceProtocolError::new(self.id, e))?;
@@ -672,15 +704,14 @@ where
             "Attempting to Send Transaction (TxId: {}) to recipient with address: {}", self.id, self.dest_address,
         );
 
-        match self
-            .resources
-            .outbound_message_service
-            .send_direct_unencrypted(
-                self.dest_address.public_key().clone(),
-                OutboundDomainMessage::new(&TariMessageType::SenderPartialTransaction, proto_message.clone()),
-                "transaction send".to_string(),
-            )
-            .await
+        match send_direct_message(
+            &mut self.resources.outbound_message_service,
+            self.dest_address.public_key().clone(),
+            OutboundDomainMessage::new(&TariMessageType::SenderPartialTransaction, proto_message.clone()),
+            self.resources.config.onion_routing_hops,
+            "transaction send",
+        )
+        .await
         {
             Ok(result) => match result {
                 SendMessageResponse::Queued(send_states) => {
@@ -887,6 +918,7 @@ where
             self.id,
             self.dest_address.public_key().clone(),
             self.resources.outbound_message_service.clone(),
+            self.resources.config.onion_routing_hops,
         )
         .await
         .map_err(|e| {
@@ -182,6 +182,10 @@ where
                         );
                         self.update_transaction_as_unmined(unmined_tx.tx_id, &unmined_tx.status)
                             .await?;
+                        if unmined_tx.status == TransactionStatus::MinedUnconfirmed {
+                            // The base node no longer knows of the block this transaction was mined in
+                            self.publish_event(TransactionEvent::TransactionUnmined(unmined_tx.tx_id));
+                        }
                         self.publish_event(TransactionEvent::NewBlockMined(unmined_tx.tx_id));
                     }
                 }
@@ -255,6 +259,7 @@ where
                 );
                 self.update_transaction_as_unmined(last_mined_transaction.tx_id, &last_mined_transaction.status)
                     .await?;
+                self.publish_event(TransactionEvent::TransactionUnmined(last_mined_transaction.tx_id));
                 self.publish_event(TransactionEvent::TransactionValidationStateChanged(op_id));
             } else {
                 debug!(
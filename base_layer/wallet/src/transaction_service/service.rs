@@ -39,7 +39,7 @@ use tari_common_types::{
     transaction::{ImportStatus, TransactionDirection, TransactionStatus, TxId},
     types::{PrivateKey, PublicKey, Signature},
 };
-use tari_comms::types::CommsPublicKey;
+use tari_comms::{peer_manager::NodeId, types::CommsPublicKey};
 use tari_comms_dht::outbound::OutboundMessageRequester;
 use tari_core::{
     consensus::ConsensusManager,
@@ -78,19 +78,21 @@ use tari_crypto::{
     tari_utilities::ByteArray,
 };
 use tari_key_manager::key_manager_service::KeyId;
-use tari_p2p::domain_message::DomainMessage;
+use tari_p2p::{domain_message::DomainMessage, services::liveness::LivenessHandle};
 use tari_script::{inputs, one_sided_payment_script, script, stealth_payment_script, TariScript};
 use tari_service_framework::{reply_channel, reply_channel::Receiver};
 use tari_shutdown::ShutdownSignal;
 use tokio::{
     sync::{mpsc, mpsc::Sender, oneshot, Mutex},
     task::JoinHandle,
+    time::MissedTickBehavior,
 };
 
 use crate::{
     base_node_service::handle::{BaseNodeEvent, BaseNodeServiceHandle},
     connectivity_service::WalletConnectivityInterface,
     output_manager_service::{
+        error::{OutputManagerError, OutputManagerStorageError},
         handle::{OutputManagerEvent, OutputManagerHandle},
         storage::models::SpendingPriority,
         UtxoSelectionCriteria,
@@ -101,6 +103,8 @@ use crate::{
         error::{TransactionServiceError, TransactionServiceProtocolError},
         handle::{
             FeePerGramStatsResponse,
+            RecipientLivenessCheck,
+            StaleTransactionCancellationSummary,
             TransactionEvent,
             TransactionEventSender,
             TransactionServiceRequest,
@@ -112,12 +116,14 @@ use crate::{
             transaction_send_protocol::{TransactionSendProtocol, TransactionSendProtocolStage},
             transaction_validation_protocol::TransactionValidationProtocol,
         },
+        spending_limits::{outbound_spend, PendingTransactionApproval, SpendingLimits},
         storage::{
             database::{TransactionBackend, TransactionDatabase},
             models::{CompletedTransaction, TxCancellationReason},
         },
         tasks::{
             check_faux_transaction_status::check_faux_transactions,
+            check_recipient_liveness::check_recipient_liveness,
             send_finalized_transaction::send_finalized_transaction_message,
             send_transaction_cancelled::send_transaction_cancelled_message,
             send_transaction_reply::send_transaction_reply,
@@ -130,6 +136,18 @@ use crate::{
 };
 
 const LOG_TARGET: &str = "wallet::transaction_service::service";
+/// How often transactions held for approval are checked for having timed out
+const HELD_TRANSACTION_EXPIRY_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+type ServiceReplySender = oneshot::Sender<Result<TransactionServiceResponse, TransactionServiceError>>;
+
+/// An outbound transaction request held for exceeding a spending limit, along with the channel its response is
+/// sent on once it is approved, rejected or times out
+struct HeldTransaction {
+    approval: PendingTransactionApproval,
+    request: TransactionServiceRequest,
+    reply_tx: ServiceReplySender,
+}
 
 /// TransactionService allows for the management of multiple inbound and outbound transaction protocols
 /// which are uniquely identified by a tx_id. The TransactionService generates and accepts the various protocol
@@ -178,7 +196,16 @@ pub struct TransactionService<
     base_node_service: BaseNodeServiceHandle,
     last_seen_tip_height: Option<u64>,
     validation_in_progress: Arc<Mutex<()>>,
+    revalidate_after_reorg: bool,
     consensus_manager: ConsensusManager,
+    spending_limits: SpendingLimits,
+    held_transactions: HashMap<u64, HeldTransaction>,
+    next_approval_id: u64,
+    approved_transactions_sender: mpsc::UnboundedSender<(TransactionServiceRequest, ServiceReplySender)>,
+    approved_transactions_receiver: Option<mpsc::UnboundedReceiver<(TransactionServiceRequest, ServiceReplySender)>>,
+    liveness: Option<LivenessHandle>,
+    recipient_checked_sender: mpsc::UnboundedSender<(TransactionServiceRequest, ServiceReplySender)>,
+    recipient_checked_receiver: Option<mpsc::UnboundedReceiver<(TransactionServiceRequest, ServiceReplySender)>>,
 }
 
 impl<
@@ -238,6 +265,7 @@ where
         factories: CryptoFactories,
         shutdown_signal: ShutdownSignal,
         base_node_service: BaseNodeServiceHandle,
+        liveness: Option<LivenessHandle>,
     ) -> Self {
         // Collect the resources that all protocols will need so that they can be neatly cloned as the protocols are
         // spawned.
@@ -260,6 +288,9 @@ where
             PowerMode::Normal => config.broadcast_monitoring_timeout,
         };
         let timeout_update_watch = Watch::new(timeout);
+        let spending_limits = SpendingLimits::new(&config);
+        let (approved_transactions_sender, approved_transactions_receiver) = mpsc::unbounded_channel();
+        let (recipient_checked_sender, recipient_checked_receiver) = mpsc::unbounded_channel();
 
         Self {
             config,
@@ -283,7 +314,16 @@ where
             wallet_db,
             last_seen_tip_height: None,
             validation_in_progress: Arc::new(Mutex::new(())),
+            revalidate_after_reorg: false,
             consensus_manager,
+            spending_limits,
+            held_transactions: HashMap::new(),
+            next_approval_id: 1,
+            approved_transactions_sender,
+            approved_transactions_receiver: Some(approved_transactions_receiver),
+            liveness,
+            recipient_checked_sender,
+            recipient_checked_receiver: Some(recipient_checked_receiver),
         }
     }
 
@@ -295,6 +335,7 @@ where
             .transaction_key_manager_service
             .import_key(self.resources.wallet_identity.node_identity.secret_key().clone())
             .await?;
+        self.load_spending_history()?;
 
         let request_stream = self
             .request_stream
@@ -332,6 +373,16 @@ where
             .expect("Transaction Service initialized without transaction_cancelled_stream")
             .fuse();
         pin_mut!(transaction_cancelled_stream);
+        let mut approved_transactions = self
+            .approved_transactions_receiver
+            .take()
+            .expect("Transaction Service initialized without approved_transactions_receiver");
+        let mut recipient_checked_transactions = self
+            .recipient_checked_receiver
+            .take()
+            .expect("Transaction Service initialized without recipient_checked_receiver");
+        let mut held_transaction_expiry_interval = tokio::time::interval(HELD_TRANSACTION_EXPIRY_CHECK_INTERVAL);
+        held_transaction_expiry_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
 
         let mut shutdown = self.resources.shutdown_signal.clone();
 
@@ -376,6 +427,46 @@ where
                     let (request, reply_tx) = request_context.split();
                     let event = format!("Handling Service API Request ({})", request);
                     trace!(target: LOG_TARGET, "{}", event);
+                    let checked = self
+                        .apply_spending_limits(request, reply_tx)
+                        .and_then(|(request, reply_tx)| self.apply_recipient_liveness_check(request, reply_tx));
+                    if let Some((request, reply_tx)) = checked {
+                        let _result = self.handle_request(request,
+                            &mut send_transaction_protocol_handles,
+                            &mut receive_transaction_protocol_handles,
+                            &mut transaction_broadcast_protocol_handles,
+                            &mut transaction_validation_protocol_handles,
+                            reply_tx,
+                        ).await.map_err(|e| {
+                            warn!(target: LOG_TARGET, "Error handling request: {:?}", e);
+                            e
+                        });
+                    }
+                    trace!(target: LOG_TARGET,
+                        "{}, processed in {}ms",
+                        event,
+                        start.elapsed().as_millis()
+                    );
+                },
+                // Held transaction released by its approver
+                Some((request, reply_tx)) = approved_transactions.recv() => {
+                    trace!(target: LOG_TARGET, "Handling approved Service API Request ({})", request);
+                    if let Some((request, reply_tx)) = self.apply_recipient_liveness_check(request, reply_tx) {
+                        let _result = self.handle_request(request,
+                            &mut send_transaction_protocol_handles,
+                            &mut receive_transaction_protocol_handles,
+                            &mut transaction_broadcast_protocol_handles,
+                            &mut transaction_validation_protocol_handles,
+                            reply_tx,
+                        ).await.map_err(|e| {
+                            warn!(target: LOG_TARGET, "Error handling approved request: {:?}", e);
+                            e
+                        });
+                    }
+                },
+                // Send whose recipient liveness check has completed
+                Some((request, reply_tx)) = recipient_checked_transactions.recv() => {
+                    trace!(target: LOG_TARGET, "Handling recipient checked Service API Request ({})", request);
                     let _result = self.handle_request(request,
                         &mut send_transaction_protocol_handles,
                         &mut receive_transaction_protocol_handles,
@@ -383,14 +474,12 @@ where
                         &mut transaction_validation_protocol_handles,
                         reply_tx,
                     ).await.map_err(|e| {
-                        warn!(target: LOG_TARGET, "Error handling request: {:?}", e);
+                        warn!(target: LOG_TARGET, "Error handling recipient checked request: {:?}", e);
                         e
                     });
-                    trace!(target: LOG_TARGET,
-                        "{}, processed in {}ms",
-                        event,
-                        start.elapsed().as_millis()
-                    );
+                },
+                _ = held_transaction_expiry_interval.tick() => {
+                    self.expire_held_transactions();
                 },
                 // Incoming Transaction messages from the Comms layer
                 Some(msg) = transaction_stream.next() => {
@@ -549,7 +638,8 @@ where
                         Ok(join_result_inner) => self.complete_transaction_validation_protocol(
                             join_result_inner,
                             &mut transaction_broadcast_protocol_handles,
-                        ),
+                            &mut transaction_validation_protocol_handles,
+                        ).await,
                         Err(e) => error!(target: LOG_TARGET, "Error resolving Transaction Validation protocol: {:?}", e),
                     };
                 }
@@ -593,6 +683,7 @@ where
                 output_features,
                 fee_per_gram,
                 message,
+                ..
             } => {
                 let rp = reply_channel.take().expect("Cannot be missing");
                 self.send_transaction(
@@ -745,6 +836,10 @@ where
                 .cancel_pending_transaction(tx_id)
                 .await
                 .map(|_| TransactionServiceResponse::TransactionCancelled),
+            TransactionServiceRequest::CancelAllStaleTransactions(older_than) => self
+                .cancel_all_stale_transactions(older_than)
+                .await
+                .map(TransactionServiceResponse::StaleTransactionsCancelled),
             TransactionServiceRequest::GetPendingInboundTransactions => Ok(
                 TransactionServiceResponse::PendingInboundTransactions(self.db.get_pending_inbound_transactions()?),
             ),
@@ -846,6 +941,26 @@ where
                 self.handle_get_fee_per_gram_stats_per_block_request(count, reply_channel);
                 return Ok(());
             },
+            TransactionServiceRequest::ApproveTransaction(approval_id) => self
+                .approve_held_transaction(approval_id)
+                .map(|_| TransactionServiceResponse::TransactionApproved),
+            TransactionServiceRequest::RejectTransaction(approval_id) => self
+                .reject_held_transaction(approval_id)
+                .map(|_| TransactionServiceResponse::TransactionRejected),
+            TransactionServiceRequest::GetPendingApprovals => Ok(TransactionServiceResponse::PendingApprovals(
+                self.held_transactions
+                    .values()
+                    .map(|held| held.approval.clone())
+                    .collect(),
+            )),
+            TransactionServiceRequest::GetTransactionEventsSince { since_seq, limit } => {
+                Ok(TransactionServiceResponse::TransactionEvents(
+                    self.db.fetch_transaction_events_since(since_seq, limit)?,
+                ))
+            },
+            TransactionServiceRequest::GetPaymentReceipt(tx_id) => Ok(TransactionServiceResponse::PaymentReceipt(
+                self.db.fetch_payment_receipt(tx_id)?,
+            )),
         };
 
         // If the individual handlers did not already send the API response then do it here.
@@ -858,6 +973,231 @@ where
         Ok(())
     }
 
+    /// Seeds the daily spending limit with the outbound transactions made over the last 24 hours, so that restarting
+    /// the wallet does not reset the amount already spent
+    fn load_spending_history(&mut self) -> Result<(), TransactionServiceError> {
+        if !self.spending_limits.is_daily_limit_enabled() {
+            return Ok(());
+        }
+        let window_start = Utc::now().naive_utc() - chrono::Duration::days(1);
+        for tx in self.db.get_pending_outbound_transactions()?.values() {
+            if tx.timestamp > window_start {
+                self.spending_limits.record_spend(tx.amount, tx.timestamp);
+            }
+        }
+        for tx in self.db.get_completed_transactions()?.values() {
+            if tx.direction == TransactionDirection::Outbound && tx.timestamp > window_start {
+                self.spending_limits.record_spend(tx.amount, tx.timestamp);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the request so that it can be handled if it is within the spending limits, otherwise holds it until
+    /// it is approved or rejected and notifies subscribers that an approval is required. Spends are recorded when
+    /// they are released, so a send that subsequently fails still counts towards the daily limit.
+    fn apply_spending_limits(
+        &mut self,
+        request: TransactionServiceRequest,
+        reply_tx: ServiceReplySender,
+    ) -> Option<(TransactionServiceRequest, ServiceReplySender)> {
+        let (amount, destination, message) = match outbound_spend(&request) {
+            Some(spend) => spend,
+            None => return Some((request, reply_tx)),
+        };
+        let now = Utc::now().naive_utc();
+        let exceeded_limit = match self.spending_limits.exceeded_limit(amount, now) {
+            Some(limit) => limit,
+            None => {
+                self.spending_limits.record_spend(amount, now);
+                return Some((request, reply_tx));
+            },
+        };
+
+        let approval_id = self.next_approval_id;
+        self.next_approval_id += 1;
+        info!(
+            target: LOG_TARGET,
+            "Transaction of {} exceeds the {}, holding it for approval (#{})", amount, exceeded_limit, approval_id
+        );
+        let _size = self
+            .event_publisher
+            .send(Arc::new(TransactionEvent::TransactionApprovalRequired {
+                approval_id,
+                amount,
+                exceeded_limit,
+            }));
+        self.held_transactions.insert(approval_id, HeldTransaction {
+            approval: PendingTransactionApproval {
+                approval_id,
+                amount,
+                destination,
+                message,
+                exceeded_limit,
+                requested_at: now,
+            },
+            request,
+            reply_tx,
+        });
+        None
+    }
+
+    /// Defers an interactive send that asked for a recipient liveness check until the recipient has been pinged.
+    /// Returns the request unchanged if no check is needed.
+    fn apply_recipient_liveness_check(
+        &mut self,
+        request: TransactionServiceRequest,
+        reply_tx: ServiceReplySender,
+    ) -> Option<(TransactionServiceRequest, ServiceReplySender)> {
+        let destination = match &request {
+            TransactionServiceRequest::SendTransaction {
+                destination,
+                recipient_check,
+                ..
+            } if *recipient_check != RecipientLivenessCheck::Disabled => destination.clone(),
+            _ => return Some((request, reply_tx)),
+        };
+        if destination.public_key() == self.resources.wallet_identity.address.public_key() {
+            return Some((request, reply_tx));
+        }
+        let liveness = match self.liveness.clone() {
+            Some(liveness) => liveness,
+            None => {
+                warn!(
+                    target: LOG_TARGET,
+                    "Recipient liveness check requested but the liveness service is not available, sending without it"
+                );
+                return Some((request, reply_tx));
+            },
+        };
+
+        let node_id = NodeId::from_public_key(destination.public_key());
+        let timeout = self.config.recipient_liveness_timeout;
+        let checked_sender = self.recipient_checked_sender.clone();
+        tokio::spawn(async move {
+            let online = check_recipient_liveness(liveness, node_id, timeout).await;
+            let TransactionServiceRequest::SendTransaction {
+                destination,
+                amount,
+                selection_criteria,
+                output_features,
+                fee_per_gram,
+                message,
+                recipient_check,
+            } = request
+            else {
+                let _result = reply_tx.send(Err(TransactionServiceError::UnexpectedResult(
+                    "Only SendTransaction requests can be liveness checked".to_string(),
+                )));
+                return;
+            };
+            if online {
+                debug!(target: LOG_TARGET, "Recipient {} is online, sending interactively", destination);
+                let request = TransactionServiceRequest::SendTransaction {
+                    destination,
+                    amount,
+                    selection_criteria,
+                    output_features,
+                    fee_per_gram,
+                    message,
+                    recipient_check: RecipientLivenessCheck::Disabled,
+                };
+                let _result = checked_sender.send((request, reply_tx));
+                return;
+            }
+
+            if recipient_check == RecipientLivenessCheck::Fail {
+                info!(target: LOG_TARGET, "Recipient {} appears to be offline, not sending", destination);
+                let _result = reply_tx.send(Err(TransactionServiceError::RecipientOffline));
+                return;
+            }
+            info!(
+                target: LOG_TARGET,
+                "Recipient {} appears to be offline, sending a one-sided transaction instead", destination
+            );
+            let request = TransactionServiceRequest::SendOneSidedTransaction {
+                destination,
+                amount,
+                selection_criteria,
+                output_features,
+                fee_per_gram,
+                message,
+            };
+            let (one_sided_reply_tx, one_sided_reply_rx) = oneshot::channel();
+            if checked_sender.send((request, one_sided_reply_tx)).is_err() {
+                let _result = reply_tx.send(Err(TransactionServiceError::ProtocolChannelError));
+                return;
+            }
+            let response = match one_sided_reply_rx.await {
+                Ok(Ok(TransactionServiceResponse::TransactionSent(tx_id))) => {
+                    Ok(TransactionServiceResponse::OneSidedFallbackTransactionSent(tx_id))
+                },
+                Ok(response) => response,
+                Err(_) => Err(TransactionServiceError::ProtocolChannelError),
+            };
+            let _result = reply_tx.send(response);
+        });
+        None
+    }
+
+    fn approve_held_transaction(&mut self, approval_id: u64) -> Result<(), TransactionServiceError> {
+        let held = self
+            .held_transactions
+            .remove(&approval_id)
+            .ok_or(TransactionServiceError::PendingApprovalNotFound(approval_id))?;
+        info!(
+            target: LOG_TARGET,
+            "Transaction of {} held for approval (#{}) was approved", held.approval.amount, approval_id
+        );
+        self.spending_limits
+            .record_spend(held.approval.amount, Utc::now().naive_utc());
+        self.approved_transactions_sender
+            .send((held.request, held.reply_tx))
+            .map_err(|_| TransactionServiceError::ProtocolChannelError)
+    }
+
+    fn reject_held_transaction(&mut self, approval_id: u64) -> Result<(), TransactionServiceError> {
+        let held = self
+            .held_transactions
+            .remove(&approval_id)
+            .ok_or(TransactionServiceError::PendingApprovalNotFound(approval_id))?;
+        info!(
+            target: LOG_TARGET,
+            "Transaction of {} held for approval (#{}) was rejected", held.approval.amount, approval_id
+        );
+        let _result = held
+            .reply_tx
+            .send(Err(TransactionServiceError::TransactionApprovalRejected(
+                held.approval.exceeded_limit,
+            )));
+        Ok(())
+    }
+
+    fn expire_held_transactions(&mut self) {
+        let now = Utc::now().naive_utc();
+        let timeout = chrono::Duration::from_std(self.config.spend_approval_timeout)
+            .unwrap_or_else(|_| chrono::Duration::max_value());
+        let expired = self
+            .held_transactions
+            .iter()
+            .filter(|(_, held)| now - held.approval.requested_at > timeout)
+            .map(|(approval_id, _)| *approval_id)
+            .collect::<Vec<_>>();
+        for approval_id in expired {
+            if let Some(held) = self.held_transactions.remove(&approval_id) {
+                warn!(
+                    target: LOG_TARGET,
+                    "Transaction of {} held for approval (#{}) was not approved in time", held.approval.amount, approval_id
+                );
+                let _result = held
+                    .reply_tx
+                    .send(Err(TransactionServiceError::TransactionApprovalTimedOut(
+                        held.approval.exceeded_limit,
+                    )));
+            }
+        }
+    }
+
     fn handle_get_fee_per_gram_stats_per_block_request(
         &self,
         count: usize,
@@ -922,6 +1262,18 @@ where
 
                 self.last_seen_tip_height = Some(height);
             },
+            BaseNodeEvent::ReorgDetected(height) => {
+                // A validation that started before the reorg may report stale mined state, so it has to be run
+                // again once it completes. The validation for the new tip is started by `NewBlockDetected`.
+                if self.validation_in_progress.try_lock().is_err() {
+                    debug!(
+                        target: LOG_TARGET,
+                        "Reorg detected from height {} while a transaction validation is in progress, will revalidate",
+                        height
+                    );
+                    self.revalidate_after_reorg = true;
+                }
+            },
         }
     }
 
@@ -1907,6 +2259,7 @@ where
                     tx_id,
                     source_pubkey,
                     self.resources.outbound_message_service.clone(),
+                    self.resources.config.onion_routing_hops,
                 ));
             } else {
                 // Resend the reply
@@ -1921,6 +2274,7 @@ where
                     self.resources.outbound_message_service.clone(),
                     self.resources.config.direct_send_timeout,
                     self.resources.config.transaction_routing_mechanism,
+                    self.resources.config.onion_routing_hops,
                 ));
             }
 
@@ -1953,6 +2307,7 @@ where
                 tx_id,
                 source_pubkey,
                 self.resources.outbound_message_service.clone(),
+                self.resources.config.onion_routing_hops,
             ));
 
             if let Err(e) = self.resources.db.increment_send_count(tx_id) {
@@ -2050,15 +2405,7 @@ where
 
         self.resources.output_manager_service.cancel_transaction(tx_id).await?;
 
-        if let Some(cancellation_sender) = self.send_transaction_cancellation_senders.remove(&tx_id) {
-            let _result = cancellation_sender.send(());
-        }
-        let _public_key = self.pending_transaction_reply_senders.remove(&tx_id);
-
-        if let Some(cancellation_sender) = self.receiver_transaction_cancellation_senders.remove(&tx_id) {
-            let _result = cancellation_sender.send(());
-        }
-        let _public_key = self.finalized_transaction_senders.remove(&tx_id);
+        self.stop_pending_transaction_protocols(tx_id);
 
         let _size = self
             .event_publisher
@@ -2080,6 +2427,95 @@ where
         Ok(())
     }
 
+    /// Cancel all pending transactions that were created more than `older_than` ago. Failing to cancel one transaction
+    /// does not stop the others from being cancelled; it is reported in the summary instead.
+    async fn cancel_all_stale_transactions(
+        &mut self,
+        older_than: Duration,
+    ) -> Result<StaleTransactionCancellationSummary, TransactionServiceError> {
+        let mut summary = StaleTransactionCancellationSummary::default();
+        let cutoff = match chrono::Duration::from_std(older_than)
+            .ok()
+            .and_then(|older_than| Utc::now().naive_utc().checked_sub_signed(older_than))
+        {
+            Some(cutoff) => cutoff,
+            None => return Ok(summary),
+        };
+
+        let mut stale_tx_ids = self
+            .db
+            .get_pending_inbound_transactions()?
+            .into_values()
+            .filter(|tx| tx.timestamp < cutoff)
+            .map(|tx| tx.tx_id)
+            .collect::<Vec<_>>();
+        stale_tx_ids.extend(
+            self.db
+                .get_pending_outbound_transactions()?
+                .into_values()
+                .filter(|tx| tx.timestamp < cutoff)
+                .map(|tx| tx.tx_id),
+        );
+
+        for tx_id in stale_tx_ids {
+            match self.cancel_stale_transaction(tx_id).await {
+                Ok(()) => summary.cancelled.push(tx_id),
+                Err(e) => {
+                    warn!(
+                        target: LOG_TARGET,
+                        "Could not cancel stale pending transaction (TxId: {}): {}", tx_id, e
+                    );
+                    summary.failed.push((tx_id, e.to_string()));
+                },
+            }
+        }
+
+        info!(
+            target: LOG_TARGET,
+            "Cancelled {} stale pending transaction(s), {} could not be cancelled",
+            summary.cancelled.len(),
+            summary.failed.len()
+        );
+        Ok(summary)
+    }
+
+    /// Release the encumbered outputs of a stale pending transaction and then cancel it. The outputs are released
+    /// first so that a failure leaves the transaction pending, rather than cancelled with its outputs still encumbered.
+    async fn cancel_stale_transaction(&mut self, tx_id: TxId) -> Result<(), TransactionServiceError> {
+        match self.resources.output_manager_service.cancel_transaction(tx_id).await {
+            // A transaction without encumbered outputs has nothing to release
+            Ok(()) | Err(OutputManagerError::OutputManagerStorageError(OutputManagerStorageError::ValueNotFound)) => {},
+            Err(e) => return Err(e.into()),
+        }
+        self.db.cancel_pending_transaction(tx_id)?;
+
+        self.stop_pending_transaction_protocols(tx_id);
+
+        let _size = self
+            .event_publisher
+            .send(Arc::new(TransactionEvent::TransactionCancelled(
+                tx_id,
+                TxCancellationReason::Timeout,
+            )));
+
+        info!(target: LOG_TARGET, "Stale Pending Transaction (TxId: {}) cancelled", tx_id);
+
+        Ok(())
+    }
+
+    /// Stop any send or receive protocol still running for a cancelled pending transaction
+    fn stop_pending_transaction_protocols(&mut self, tx_id: TxId) {
+        if let Some(cancellation_sender) = self.send_transaction_cancellation_senders.remove(&tx_id) {
+            let _result = cancellation_sender.send(());
+        }
+        let _public_key = self.pending_transaction_reply_senders.remove(&tx_id);
+
+        if let Some(cancellation_sender) = self.receiver_transaction_cancellation_senders.remove(&tx_id) {
+            let _result = cancellation_sender.send(());
+        }
+        let _public_key = self.finalized_transaction_senders.remove(&tx_id);
+    }
+
     /// Handle a Transaction Cancelled message received from the Comms layer
     pub async fn handle_transaction_cancelled_message(
         &mut self,
@@ -2232,6 +2668,7 @@ where
                     tx.tx_id,
                     source_pubkey,
                     self.resources.outbound_message_service.clone(),
+                    self.resources.config.onion_routing_hops,
                 ));
 
                 return Ok(());
@@ -2267,6 +2704,7 @@ where
                     self.resources.outbound_message_service.clone(),
                     self.resources.config.direct_send_timeout,
                     self.resources.config.transaction_routing_mechanism,
+                    self.resources.config.onion_routing_hops,
                 ));
                 if let Err(e) = self.resources.db.increment_send_count(tx_id) {
                     warn!(
@@ -2608,12 +3046,15 @@ where
     }
 
     /// Handle the final clean up after a Transaction Validation protocol completes
-    fn complete_transaction_validation_protocol(
+    async fn complete_transaction_validation_protocol(
         &mut self,
         join_result: Result<OperationId, TransactionServiceProtocolError<OperationId>>,
         transaction_broadcast_join_handles: &mut FuturesUnordered<
             JoinHandle<Result<TxId, TransactionServiceProtocolError<TxId>>>,
         >,
+        transaction_validation_join_handles: &mut FuturesUnordered<
+            JoinHandle<Result<OperationId, TransactionServiceProtocolError<OperationId>>>,
+        >,
     ) {
         match join_result {
             Ok(id) => {
@@ -2621,6 +3062,13 @@ where
                     target: LOG_TARGET,
                     "Transaction Validation Protocol (Id: {}) completed successfully", id
                 );
+                if self.revalidate_after_reorg {
+                    self.revalidate_after_reorg = false;
+                    let _operation_id = self
+                        .start_transaction_validation_protocol(transaction_validation_join_handles)
+                        .await
+                        .map_err(|e| warn!(target: LOG_TARGET, "Error revalidating transactions: {:?}", e));
+                }
                 // Restart broadcast protocols for any transactions that were found to be no longer mined.
                 let _ = self
                     .restart_broadcast_protocols(transaction_broadcast_join_handles)
@@ -0,0 +1,199 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{collections::VecDeque, fmt};
+
+use chrono::{Duration, NaiveDateTime};
+use tari_common_types::tari_address::TariAddress;
+use tari_core::transactions::tari_amount::MicroMinotari;
+
+use crate::transaction_service::{config::TransactionServiceConfig, handle::TransactionServiceRequest};
+
+/// The spending limit that an outbound transaction would exceed
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum SpendLimit {
+    /// The transaction amount is above the per-transaction limit
+    PerTransaction(MicroMinotari),
+    /// The transaction would take the amount spent over the last 24 hours above the daily limit
+    Daily(MicroMinotari),
+}
+
+impl fmt::Display for SpendLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpendLimit::PerTransaction(limit) => write!(f, "per-transaction limit of {}", limit),
+            SpendLimit::Daily(limit) => write!(f, "daily limit of {}", limit),
+        }
+    }
+}
+
+/// An outbound transaction that exceeded a spending limit and is held until it is approved or rejected
+#[derive(Clone, Debug)]
+pub struct PendingTransactionApproval {
+    pub approval_id: u64,
+    pub amount: MicroMinotari,
+    /// The recipient of the transaction, or None if the funds are being burnt
+    pub destination: Option<TariAddress>,
+    pub message: String,
+    pub exceeded_limit: SpendLimit,
+    pub requested_at: NaiveDateTime,
+}
+
+/// Tracks outbound spending against the configured per-transaction and daily spending limits
+pub struct SpendingLimits {
+    per_transaction_limit: Option<MicroMinotari>,
+    daily_limit: Option<MicroMinotari>,
+    spends: VecDeque<(NaiveDateTime, MicroMinotari)>,
+}
+
+impl SpendingLimits {
+    pub fn new(config: &TransactionServiceConfig) -> Self {
+        Self {
+            per_transaction_limit: config.per_transaction_spend_limit,
+            daily_limit: config.daily_spend_limit,
+            spends: VecDeque::new(),
+        }
+    }
+
+    pub fn is_daily_limit_enabled(&self) -> bool {
+        self.daily_limit.is_some()
+    }
+
+    /// Records an outbound spend of `amount` made at `timestamp` against the daily limit
+    pub fn record_spend(&mut self, amount: MicroMinotari, timestamp: NaiveDateTime) {
+        if !self.is_daily_limit_enabled() {
+            return;
+        }
+        self.spends.push_back((timestamp, amount));
+        if self.spends.len() > 1 && self.spends[self.spends.len() - 2].0 > timestamp {
+            self.spends.make_contiguous().sort_by_key(|(timestamp, _)| *timestamp);
+        }
+    }
+
+    /// The total amount spent in the 24 hours before `now`
+    pub fn spent_in_last_day(&mut self, now: NaiveDateTime) -> MicroMinotari {
+        let window_start = now - Duration::days(1);
+        while self
+            .spends
+            .front()
+            .map_or(false, |(timestamp, _)| *timestamp <= window_start)
+        {
+            self.spends.pop_front();
+        }
+        self.spends.iter().map(|(_, amount)| *amount).sum()
+    }
+
+    /// Returns the limit that spending `amount` at `now` would exceed, if any
+    pub fn exceeded_limit(&mut self, amount: MicroMinotari, now: NaiveDateTime) -> Option<SpendLimit> {
+        if let Some(limit) = self.per_transaction_limit {
+            if amount > limit {
+                return Some(SpendLimit::PerTransaction(limit));
+            }
+        }
+        if let Some(limit) = self.daily_limit {
+            if self.spent_in_last_day(now) + amount > limit {
+                return Some(SpendLimit::Daily(limit));
+            }
+        }
+        None
+    }
+}
+
+/// Returns the amount, destination and message of requests that spend funds from the wallet and are therefore subject
+/// to the spending limits
+pub fn outbound_spend(request: &TransactionServiceRequest) -> Option<(MicroMinotari, Option<TariAddress>, String)> {
+    match request {
+        TransactionServiceRequest::SendTransaction {
+            destination,
+            amount,
+            message,
+            ..
+        } |
+        TransactionServiceRequest::SendOneSidedTransaction {
+            destination,
+            amount,
+            message,
+            ..
+        } |
+        TransactionServiceRequest::SendOneSidedToStealthAddressTransaction {
+            destination,
+            amount,
+            message,
+            ..
+        } => Some((*amount, Some(destination.clone()), message.clone())),
+        TransactionServiceRequest::BurnTari { amount, message, .. } => Some((*amount, None, message.clone())),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::Utc;
+
+    use super::*;
+
+    fn limits(per_transaction: Option<u64>, daily: Option<u64>) -> SpendingLimits {
+        SpendingLimits::new(&TransactionServiceConfig {
+            per_transaction_spend_limit: per_transaction.map(MicroMinotari::from),
+            daily_spend_limit: daily.map(MicroMinotari::from),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn it_allows_everything_without_limits() {
+        let mut limits = limits(None, None);
+        let now = Utc::now().naive_utc();
+        limits.record_spend(MicroMinotari::from(u64::MAX / 2), now);
+        assert_eq!(limits.exceeded_limit(MicroMinotari::from(u64::MAX / 2), now), None);
+    }
+
+    #[test]
+    fn it_applies_the_per_transaction_limit() {
+        let mut limits = limits(Some(1000), None);
+        let now = Utc::now().naive_utc();
+        assert_eq!(limits.exceeded_limit(MicroMinotari::from(1000), now), None);
+        assert_eq!(
+            limits.exceeded_limit(MicroMinotari::from(1001), now),
+            Some(SpendLimit::PerTransaction(MicroMinotari::from(1000)))
+        );
+    }
+
+    #[test]
+    fn it_applies_the_daily_limit_over_a_rolling_window() {
+        let mut limits = limits(None, Some(1000));
+        let now = Utc::now().naive_utc();
+        limits.record_spend(MicroMinotari::from(400), now - Duration::hours(25));
+        limits.record_spend(MicroMinotari::from(300), now - Duration::hours(2));
+        limits.record_spend(MicroMinotari::from(500), now - Duration::hours(23));
+        assert_eq!(limits.spent_in_last_day(now), MicroMinotari::from(800));
+        assert_eq!(limits.exceeded_limit(MicroMinotari::from(200), now), None);
+        assert_eq!(
+            limits.exceeded_limit(MicroMinotari::from(201), now),
+            Some(SpendLimit::Daily(MicroMinotari::from(1000)))
+        );
+        // Once the oldest spend in the window rolls off, the allowance is freed up again
+        let later = now + Duration::hours(2);
+        assert_eq!(limits.spent_in_last_day(later), MicroMinotari::from(300));
+        assert_eq!(limits.exceeded_limit(MicroMinotari::from(700), later), None);
+    }
+}
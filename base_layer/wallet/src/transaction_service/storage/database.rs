@@ -33,7 +33,7 @@ use log::*;
 use tari_common_types::{
     tari_address::TariAddress,
     transaction::{ImportStatus, TransactionDirection, TransactionStatus, TxId},
-    types::{BlockHash, PrivateKey},
+    types::{BlockHash, PrivateKey, Signature},
 };
 use tari_core::transactions::{tari_amount::MicroMinotari, transaction_components::Transaction};
 
@@ -44,6 +44,7 @@ use crate::transaction_service::{
             CompletedTransaction,
             InboundTransaction,
             OutboundTransaction,
+            TransactionEventRecord,
             TxCancellationReason,
             WalletTransaction,
         },
@@ -155,6 +156,16 @@ pub trait TransactionBackend: Send + Sync + Clone {
         height: u64,
     ) -> Result<Vec<CompletedTransaction>, TransactionStorageError>;
     fn abandon_coinbase_transaction(&self, tx_id: TxId) -> Result<(), TransactionStorageError>;
+    /// Fetches up to `limit` journal events with a sequence number greater than `since_seq`, in sequence order
+    fn fetch_transaction_events_since(
+        &self,
+        since_seq: u64,
+        limit: usize,
+    ) -> Result<Vec<TransactionEventRecord>, TransactionStorageError>;
+    /// Stores the payment proof receipt the recipient returned for an outbound transaction
+    fn set_payment_receipt(&self, tx_id: TxId, receipt: &Signature) -> Result<(), TransactionStorageError>;
+    /// Fetches the payment proof receipt for an outbound transaction, if the recipient provided one
+    fn fetch_payment_receipt(&self, tx_id: TxId) -> Result<Option<Signature>, TransactionStorageError>;
 }
 
 #[derive(Clone, PartialEq)]
@@ -745,6 +756,22 @@ where T: TransactionBackend + 'static
     pub fn abandon_coinbase_transaction(&self, tx_id: TxId) -> Result<(), TransactionStorageError> {
         self.db.abandon_coinbase_transaction(tx_id)
     }
+
+    pub fn fetch_transaction_events_since(
+        &self,
+        since_seq: u64,
+        limit: usize,
+    ) -> Result<Vec<TransactionEventRecord>, TransactionStorageError> {
+        self.db.fetch_transaction_events_since(since_seq, limit)
+    }
+
+    pub fn set_payment_receipt(&self, tx_id: TxId, receipt: &Signature) -> Result<(), TransactionStorageError> {
+        self.db.set_payment_receipt(tx_id, receipt)
+    }
+
+    pub fn fetch_payment_receipt(&self, tx_id: TxId) -> Result<Option<Signature>, TransactionStorageError> {
+        self.db.fetch_payment_receipt(tx_id)
+    }
 }
 
 impl Display for DbKey {
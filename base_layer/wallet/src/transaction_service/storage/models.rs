@@ -319,6 +319,16 @@ pub enum WalletTransaction {
     Completed(CompletedTransaction),
 }
 
+impl WalletTransaction {
+    pub fn status(&self) -> TransactionStatus {
+        match self {
+            WalletTransaction::PendingInbound(tx) => tx.status.clone(),
+            WalletTransaction::PendingOutbound(tx) => tx.status.clone(),
+            WalletTransaction::Completed(tx) => tx.status.clone(),
+        }
+    }
+}
+
 impl From<WalletTransaction> for CompletedTransaction {
     fn from(tx: WalletTransaction) -> Self {
         match tx {
@@ -376,3 +386,15 @@ impl Display for TxCancellationReason {
         fmt.write_str(response)
     }
 }
+
+/// A persisted state change of one of the wallet's transactions. Sequence numbers are assigned in the order the events
+/// are recorded and never reused, so a consumer can resume from the last sequence number it processed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionEventRecord {
+    pub seq: u64,
+    pub tx_id: TxId,
+    pub event: String,
+    /// The status of the transaction when the event was recorded, if the transaction was known at that time
+    pub status: Option<TransactionStatus>,
+    pub recorded_at: NaiveDateTime,
+}
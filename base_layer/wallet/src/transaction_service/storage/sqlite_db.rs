@@ -24,7 +24,6 @@ use std::{
     collections::HashMap,
     convert::{TryFrom, TryInto},
     str::from_utf8,
-    sync::{Arc, RwLock},
 };
 
 use chacha20poly1305::XChaCha20Poly1305;
@@ -33,7 +32,7 @@ use diesel::{prelude::*, result::Error as DieselError, SqliteConnection};
 use log::*;
 use tari_common_sqlite::{sqlite_connection_pool::PooledDbConnection, util::diesel_ext::ExpectedRowsExtension};
 use tari_common_types::{
-    encryption::{decrypt_bytes_integral_nonce, encrypt_bytes_integral_nonce, Encryptable},
+    encryption::{decrypt_bytes_integral_nonce, encrypt_bytes_integral_nonce, Encryptable, SharedCipher},
     tari_address::TariAddress,
     transaction::{
         TransactionConversionError,
@@ -55,7 +54,13 @@ use tokio::time::Instant;
 use zeroize::Zeroize;
 
 use crate::{
-    schema::{completed_transactions, inbound_transactions, outbound_transactions},
+    schema::{
+        completed_transactions,
+        inbound_transactions,
+        outbound_transactions,
+        payment_receipts,
+        transaction_events,
+    },
     storage::sqlite_utilities::wallet_db_connection::WalletDbConnection,
     transaction_service::{
         error::{TransactionKeyError, TransactionStorageError},
@@ -65,6 +70,7 @@ use crate::{
                 CompletedTransaction,
                 InboundTransaction,
                 OutboundTransaction,
+                TransactionEventRecord,
                 TxCancellationReason,
                 WalletTransaction,
             },
@@ -77,50 +83,71 @@ const LOG_TARGET: &str = "wallet::transaction_service::database::wallet";
 #[derive(Clone)]
 pub struct TransactionServiceSqliteDatabase {
     database_connection: WalletDbConnection,
-    cipher: Arc<RwLock<XChaCha20Poly1305>>,
+    cipher: SharedCipher,
 }
 
 impl TransactionServiceSqliteDatabase {
-    pub fn new(database_connection: WalletDbConnection, cipher: XChaCha20Poly1305) -> Self {
+    pub fn new<C: Into<SharedCipher>>(database_connection: WalletDbConnection, cipher: C) -> Self {
         Self {
             database_connection,
-            cipher: Arc::new(RwLock::new(cipher)),
+            cipher: cipher.into(),
         }
     }
 
+    /// Returns the database cipher, or an error if the wallet database is locked
+    fn unlocked_cipher(&self) -> Result<XChaCha20Poly1305, TransactionStorageError> {
+        self.cipher.get().ok_or(TransactionStorageError::DatabaseLocked)
+    }
+
     fn insert(&self, kvp: DbKeyValuePair, conn: &mut SqliteConnection) -> Result<(), TransactionStorageError> {
-        let cipher = acquire_read_lock!(self.cipher);
+        let cipher = self.unlocked_cipher()?;
 
         match kvp {
             DbKeyValuePair::PendingOutboundTransaction(k, v) => {
                 if OutboundTransactionSql::find_by_cancelled(k, false, conn).is_ok() {
                     return Err(TransactionStorageError::DuplicateOutput);
                 }
+                let status = v.status.clone();
                 let o = OutboundTransactionSql::try_from(*v, &cipher)?;
-                o.commit(conn)?;
+                conn.transaction::<_, TransactionStorageError, _>(|conn| {
+                    o.commit(conn)?;
+                    NewTransactionEventSql::new(k, "transaction_queued", Some(status)).commit(conn)
+                })?;
             },
             DbKeyValuePair::PendingInboundTransaction(k, v) => {
                 if InboundTransactionSql::find_by_cancelled(k, false, conn).is_ok() {
                     return Err(TransactionStorageError::DuplicateOutput);
                 }
+                let status = v.status.clone();
                 let i = InboundTransactionSql::try_from(*v, &cipher)?;
-
-                i.commit(conn)?;
+                conn.transaction::<_, TransactionStorageError, _>(|conn| {
+                    i.commit(conn)?;
+                    NewTransactionEventSql::new(k, "received_transaction", Some(status)).commit(conn)
+                })?;
             },
             DbKeyValuePair::CompletedTransaction(k, v) => {
                 if CompletedTransactionSql::find_by_cancelled(k, false, conn).is_ok() {
                     return Err(TransactionStorageError::DuplicateOutput);
                 }
+                let status = v.status.clone();
+                let event = match status {
+                    TransactionStatus::Imported |
+                    TransactionStatus::FauxUnconfirmed |
+                    TransactionStatus::FauxConfirmed => "transaction_imported",
+                    _ => "transaction_completed",
+                };
                 let c = CompletedTransactionSql::try_from(*v, &cipher)?;
-
-                c.commit(conn)?;
+                conn.transaction::<_, TransactionStorageError, _>(|conn| {
+                    c.commit(conn)?;
+                    NewTransactionEventSql::new(k, event, Some(status)).commit(conn)
+                })?;
             },
         }
         Ok(())
     }
 
     fn remove(&self, key: DbKey, conn: &mut SqliteConnection) -> Result<Option<DbValue>, TransactionStorageError> {
-        let cipher = acquire_read_lock!(self.cipher);
+        let cipher = self.unlocked_cipher()?;
         match key {
             DbKey::PendingOutboundTransaction(k) => {
                 conn.transaction::<_, _, _>(|conn| match OutboundTransactionSql::find_by_cancelled(k, false, conn) {
@@ -211,7 +238,7 @@ impl TransactionBackend for TransactionServiceSqliteDatabase {
         let start = Instant::now();
         let mut conn = self.database_connection.get_pooled_connection()?;
         let acquire_lock = start.elapsed();
-        let cipher = acquire_read_lock!(self.cipher);
+        let cipher = self.unlocked_cipher()?;
 
         let result = match key {
             DbKey::PendingOutboundTransaction(t) => {
@@ -470,7 +497,7 @@ impl TransactionBackend for TransactionServiceSqliteDatabase {
         let start = Instant::now();
         let mut conn = self.database_connection.get_pooled_connection()?;
         let acquire_lock = start.elapsed();
-        let cipher = acquire_read_lock!(self.cipher);
+        let cipher = self.unlocked_cipher()?;
 
         if let Ok(outbound_tx_sql) = OutboundTransactionSql::find_by_cancelled(tx_id, false, &mut conn) {
             let outbound_tx = OutboundTransaction::try_from(outbound_tx_sql, &cipher)?;
@@ -509,7 +536,7 @@ impl TransactionBackend for TransactionServiceSqliteDatabase {
         tx_id: TxId,
     ) -> Result<Option<WalletTransaction>, TransactionStorageError> {
         let mut conn = self.database_connection.get_pooled_connection()?;
-        let cipher = acquire_read_lock!(self.cipher);
+        let cipher = self.unlocked_cipher()?;
 
         match OutboundTransactionSql::find_by_cancelled(tx_id, true, &mut conn) {
             Ok(o) => {
@@ -549,17 +576,21 @@ impl TransactionBackend for TransactionServiceSqliteDatabase {
         let start = Instant::now();
         let mut conn = self.database_connection.get_pooled_connection()?;
         let acquire_lock = start.elapsed();
-        let cipher = acquire_read_lock!(self.cipher);
+        let cipher = self.unlocked_cipher()?;
 
         if CompletedTransactionSql::find_by_cancelled(tx_id, false, &mut conn).is_ok() {
             return Err(TransactionStorageError::TransactionAlreadyExists);
         }
 
+        let status = completed_transaction.status.clone();
         let completed_tx_sql = CompletedTransactionSql::try_from(completed_transaction, &cipher)?;
 
         conn.transaction::<_, _, _>(|conn| {
             match OutboundTransactionSql::complete_outbound_transaction(tx_id, conn) {
-                Ok(_) => completed_tx_sql.commit(conn)?,
+                Ok(_) => {
+                    completed_tx_sql.commit(conn)?;
+                    NewTransactionEventSql::new(tx_id, "received_transaction_reply", Some(status)).commit(conn)?;
+                },
                 Err(TransactionStorageError::DieselError(DieselError::NotFound)) => {
                     return Err(TransactionStorageError::ValueNotFound(
                         DbKey::PendingOutboundTransaction(tx_id),
@@ -590,17 +621,21 @@ impl TransactionBackend for TransactionServiceSqliteDatabase {
         let start = Instant::now();
         let mut conn = self.database_connection.get_pooled_connection()?;
         let acquire_lock = start.elapsed();
-        let cipher = acquire_read_lock!(self.cipher);
+        let cipher = self.unlocked_cipher()?;
 
         if CompletedTransactionSql::find_by_cancelled(tx_id, false, &mut conn).is_ok() {
             return Err(TransactionStorageError::TransactionAlreadyExists);
         }
 
+        let status = completed_transaction.status.clone();
         let completed_tx_sql = CompletedTransactionSql::try_from(completed_transaction, &cipher)?;
 
         conn.transaction::<_, _, _>(|conn| {
             match InboundTransactionSql::complete_inbound_transaction(tx_id, conn) {
-                Ok(_) => completed_tx_sql.commit(conn)?,
+                Ok(_) => {
+                    completed_tx_sql.commit(conn)?;
+                    NewTransactionEventSql::new(tx_id, "received_finalized_transaction", Some(status)).commit(conn)?;
+                },
                 Err(TransactionStorageError::DieselError(DieselError::NotFound)) => {
                     return Err(TransactionStorageError::ValueNotFound(
                         DbKey::PendingInboundTransaction(tx_id),
@@ -641,6 +676,8 @@ impl TransactionBackend for TransactionServiceSqliteDatabase {
                             },
                             conn,
                         )?;
+                        NewTransactionEventSql::new(tx_id, "transaction_broadcast", Some(TransactionStatus::Broadcast))
+                            .commit(conn)?;
                     }
                 },
                 Err(TransactionStorageError::DieselError(DieselError::NotFound)) => {
@@ -674,7 +711,10 @@ impl TransactionBackend for TransactionServiceSqliteDatabase {
         let start = Instant::now();
         let mut conn = self.database_connection.get_pooled_connection()?;
         let acquire_lock = start.elapsed();
-        match CompletedTransactionSql::reject_completed_transaction(tx_id, reason, &mut conn) {
+        match conn.transaction::<_, TransactionStorageError, _>(|conn| {
+            CompletedTransactionSql::reject_completed_transaction(tx_id, reason, conn)?;
+            NewTransactionEventSql::new(tx_id, "transaction_cancelled", Some(TransactionStatus::Rejected)).commit(conn)
+        }) {
             Ok(_) => {},
             Err(TransactionStorageError::DieselError(DieselError::NotFound)) => {
                 return Err(TransactionStorageError::ValueNotFound(DbKey::CompletedTransaction(
@@ -704,18 +744,26 @@ impl TransactionBackend for TransactionServiceSqliteDatabase {
         let mut conn = self.database_connection.get_pooled_connection()?;
         let acquire_lock = start.elapsed();
 
-        match InboundTransactionSql::find_and_set_cancelled(tx_id, cancelled, &mut conn) {
-            Ok(_) => {},
-            Err(_) => {
-                match OutboundTransactionSql::find_and_set_cancelled(tx_id, cancelled, &mut conn) {
-                    Ok(_) => {},
-                    Err(TransactionStorageError::DieselError(DieselError::NotFound)) => {
-                        return Err(TransactionStorageError::ValuesNotFound);
-                    },
-                    Err(e) => return Err(e),
-                };
-            },
-        }
+        let event = if cancelled {
+            "transaction_cancelled"
+        } else {
+            "transaction_restored"
+        };
+        conn.transaction::<_, TransactionStorageError, _>(|conn| {
+            match InboundTransactionSql::find_and_set_cancelled(tx_id, cancelled, conn) {
+                Ok(_) => {},
+                Err(_) => {
+                    match OutboundTransactionSql::find_and_set_cancelled(tx_id, cancelled, conn) {
+                        Ok(_) => {},
+                        Err(TransactionStorageError::DieselError(DieselError::NotFound)) => {
+                            return Err(TransactionStorageError::ValuesNotFound);
+                        },
+                        Err(e) => return Err(e),
+                    };
+                },
+            }
+            NewTransactionEventSql::new(tx_id, event, Some(TransactionStatus::Pending)).commit(conn)
+        })?;
 
         if start.elapsed().as_millis() > 0 {
             trace!(
@@ -734,18 +782,21 @@ impl TransactionBackend for TransactionServiceSqliteDatabase {
         let mut conn = self.database_connection.get_pooled_connection()?;
         let acquire_lock = start.elapsed();
 
-        match InboundTransactionSql::mark_direct_send_success(tx_id, &mut conn) {
-            Ok(_) => {},
-            Err(_) => {
-                match OutboundTransactionSql::mark_direct_send_success(tx_id, &mut conn) {
-                    Ok(_) => {},
-                    Err(TransactionStorageError::DieselError(DieselError::NotFound)) => {
-                        return Err(TransactionStorageError::ValuesNotFound);
-                    },
-                    Err(e) => return Err(e),
-                };
-            },
-        };
+        conn.transaction::<_, TransactionStorageError, _>(|conn| {
+            match InboundTransactionSql::mark_direct_send_success(tx_id, conn) {
+                Ok(_) => {},
+                Err(_) => {
+                    match OutboundTransactionSql::mark_direct_send_success(tx_id, conn) {
+                        Ok(_) => {},
+                        Err(TransactionStorageError::DieselError(DieselError::NotFound)) => {
+                            return Err(TransactionStorageError::ValuesNotFound);
+                        },
+                        Err(e) => return Err(e),
+                    };
+                },
+            };
+            NewTransactionEventSql::new(tx_id, "transaction_sent", Some(TransactionStatus::Pending)).commit(conn)
+        })?;
 
         if start.elapsed().as_millis() > 0 {
             trace!(
@@ -764,11 +815,23 @@ impl TransactionBackend for TransactionServiceSqliteDatabase {
         let mut conn = self.database_connection.get_pooled_connection()?;
         let acquire_lock = start.elapsed();
 
-        CompletedTransactionSql::reject_coinbases_at_block_height(
-            block_height as i64,
-            TxCancellationReason::AbandonedCoinbase,
-            &mut conn,
-        )?;
+        conn.transaction::<_, TransactionStorageError, _>(|conn| {
+            let rejected_tx_ids = CompletedTransactionSql::index_coinbase_at_block_height(block_height as i64, conn)?
+                .into_iter()
+                .filter(|c| c.status == TransactionStatus::Coinbase as i32)
+                .map(|c| TxId::from(c.tx_id as u64))
+                .collect::<Vec<_>>();
+            CompletedTransactionSql::reject_coinbases_at_block_height(
+                block_height as i64,
+                TxCancellationReason::AbandonedCoinbase,
+                conn,
+            )?;
+            for tx_id in rejected_tx_ids {
+                NewTransactionEventSql::new(tx_id, "transaction_cancelled", Some(TransactionStatus::Rejected))
+                    .commit(conn)?;
+            }
+            Ok(())
+        })?;
         if start.elapsed().as_millis() > 0 {
             trace!(
                 target: LOG_TARGET,
@@ -790,7 +853,7 @@ impl TransactionBackend for TransactionServiceSqliteDatabase {
         let start = Instant::now();
         let mut conn = self.database_connection.get_pooled_connection()?;
         let acquire_lock = start.elapsed();
-        let cipher = acquire_read_lock!(self.cipher);
+        let cipher = self.unlocked_cipher()?;
 
         let coinbase_txs = CompletedTransactionSql::index_coinbase_at_block_height(block_height as i64, &mut conn)?;
         for c in coinbase_txs {
@@ -850,27 +913,35 @@ impl TransactionBackend for TransactionServiceSqliteDatabase {
         let start = Instant::now();
         let mut conn = self.database_connection.get_pooled_connection()?;
         let acquire_lock = start.elapsed();
-        let status = if is_confirmed {
+        let (status, event) = if is_confirmed {
             if is_faux {
-                TransactionStatus::FauxConfirmed
+                (TransactionStatus::FauxConfirmed, "faux_transaction_confirmed")
             } else {
-                TransactionStatus::MinedConfirmed
+                (TransactionStatus::MinedConfirmed, "transaction_mined")
             }
         } else if is_faux {
-            TransactionStatus::FauxUnconfirmed
+            (TransactionStatus::FauxUnconfirmed, "faux_transaction_unconfirmed")
         } else {
-            TransactionStatus::MinedUnconfirmed
+            (TransactionStatus::MinedUnconfirmed, "transaction_mined_unconfirmed")
         };
 
-        match CompletedTransactionSql::update_mined_height(
-            tx_id,
-            num_confirmations,
-            status,
-            mined_height,
-            mined_in_block,
-            mined_timestamp,
-            &mut conn,
-        ) {
+        match conn.transaction::<_, TransactionStorageError, _>(|conn| {
+            let previous_status = CompletedTransactionSql::find(tx_id, conn)?.status;
+            CompletedTransactionSql::update_mined_height(
+                tx_id,
+                num_confirmations,
+                status.clone(),
+                mined_height,
+                mined_in_block,
+                mined_timestamp,
+                conn,
+            )?;
+            // Only status changes are journaled, not every confirmation that is counted while the status is unchanged
+            if previous_status != status.clone() as i32 {
+                NewTransactionEventSql::new(tx_id, event, Some(status)).commit(conn)?;
+            }
+            Ok(())
+        }) {
             Ok(_) => {},
             Err(TransactionStorageError::DieselError(DieselError::NotFound)) => {
                 return Err(TransactionStorageError::ValueNotFound(DbKey::CompletedTransaction(
@@ -896,7 +967,7 @@ impl TransactionBackend for TransactionServiceSqliteDatabase {
         let start = Instant::now();
         let mut conn = self.database_connection.get_pooled_connection()?;
         let acquire_lock = start.elapsed();
-        let cipher = acquire_read_lock!(self.cipher);
+        let cipher = self.unlocked_cipher()?;
 
         let tx = completed_transactions::table
             // Note: Check 'mined_in_block' as well as 'mined_height' is populated for faux transactions before it is confirmed
@@ -953,7 +1024,7 @@ impl TransactionBackend for TransactionServiceSqliteDatabase {
         let start = Instant::now();
         let mut conn = self.database_connection.get_pooled_connection()?;
         let acquire_lock = start.elapsed();
-        let cipher = acquire_read_lock!(self.cipher);
+        let cipher = self.unlocked_cipher()?;
 
         let txs = completed_transactions::table
             .filter(
@@ -1016,7 +1087,10 @@ impl TransactionBackend for TransactionServiceSqliteDatabase {
         let start = Instant::now();
         let mut conn = self.database_connection.get_pooled_connection()?;
         let acquire_lock = start.elapsed();
-        match CompletedTransactionSql::set_as_unmined(tx_id, &mut conn) {
+        match conn.transaction::<_, TransactionStorageError, _>(|conn| {
+            CompletedTransactionSql::set_as_unmined(tx_id, conn)?;
+            NewTransactionEventSql::for_completed_transaction(tx_id, "transaction_unmined", conn)?.commit(conn)
+        }) {
             Ok(_) => {},
             Err(TransactionStorageError::DieselError(DieselError::NotFound)) => {
                 return Err(TransactionStorageError::ValueNotFound(DbKey::CompletedTransaction(
@@ -1066,7 +1140,7 @@ impl TransactionBackend for TransactionServiceSqliteDatabase {
 
     fn fetch_imported_transactions(&self) -> Result<Vec<CompletedTransaction>, TransactionStorageError> {
         let mut conn = self.database_connection.get_pooled_connection()?;
-        let cipher = acquire_read_lock!(self.cipher);
+        let cipher = self.unlocked_cipher()?;
 
         CompletedTransactionSql::index_by_status_and_cancelled(TransactionStatus::Imported, false, &mut conn)?
             .into_iter()
@@ -1078,7 +1152,7 @@ impl TransactionBackend for TransactionServiceSqliteDatabase {
 
     fn fetch_unconfirmed_faux_transactions(&self) -> Result<Vec<CompletedTransaction>, TransactionStorageError> {
         let mut conn = self.database_connection.get_pooled_connection()?;
-        let cipher = acquire_read_lock!(self.cipher);
+        let cipher = self.unlocked_cipher()?;
 
         CompletedTransactionSql::index_by_status_and_cancelled(TransactionStatus::FauxUnconfirmed, false, &mut conn)?
             .into_iter()
@@ -1093,7 +1167,7 @@ impl TransactionBackend for TransactionServiceSqliteDatabase {
         height: u64,
     ) -> Result<Vec<CompletedTransaction>, TransactionStorageError> {
         let mut conn = self.database_connection.get_pooled_connection()?;
-        let cipher = acquire_read_lock!(self.cipher);
+        let cipher = self.unlocked_cipher()?;
 
         CompletedTransactionSql::index_by_status_and_cancelled_from_block_height(
             TransactionStatus::FauxConfirmed,
@@ -1110,7 +1184,10 @@ impl TransactionBackend for TransactionServiceSqliteDatabase {
 
     fn abandon_coinbase_transaction(&self, tx_id: TxId) -> Result<(), TransactionStorageError> {
         let mut conn = self.database_connection.get_pooled_connection()?;
-        match CompletedTransactionSql::find_and_abandon_coinbase(tx_id, &mut conn) {
+        match conn.transaction::<_, TransactionStorageError, _>(|conn| {
+            CompletedTransactionSql::find_and_abandon_coinbase(tx_id, conn)?;
+            NewTransactionEventSql::for_completed_transaction(tx_id, "transaction_cancelled", conn)?.commit(conn)
+        }) {
             Ok(_) => {},
             Err(TransactionStorageError::DieselError(DieselError::NotFound)) => {
                 return Err(TransactionStorageError::ValueNotFound(DbKey::CompletedTransaction(
@@ -1122,6 +1199,122 @@ impl TransactionBackend for TransactionServiceSqliteDatabase {
 
         Ok(())
     }
+
+    fn fetch_transaction_events_since(
+        &self,
+        since_seq: u64,
+        limit: usize,
+    ) -> Result<Vec<TransactionEventRecord>, TransactionStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        TransactionEventSql::index_since(since_seq as i64, limit as i64, &mut conn)?
+            .into_iter()
+            .map(TransactionEventRecord::try_from)
+            .collect()
+    }
+
+    fn set_payment_receipt(&self, tx_id: TxId, receipt: &Signature) -> Result<(), TransactionStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        diesel::replace_into(payment_receipts::table)
+            .values((
+                payment_receipts::tx_id.eq(tx_id.as_u64() as i64),
+                payment_receipts::public_nonce.eq(receipt.get_public_nonce().to_vec()),
+                payment_receipts::signature.eq(receipt.get_signature().to_vec()),
+            ))
+            .execute(&mut conn)?;
+        Ok(())
+    }
+
+    fn fetch_payment_receipt(&self, tx_id: TxId) -> Result<Option<Signature>, TransactionStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        let receipt = payment_receipts::table
+            .filter(payment_receipts::tx_id.eq(tx_id.as_u64() as i64))
+            .select((payment_receipts::public_nonce, payment_receipts::signature))
+            .first::<(Vec<u8>, Vec<u8>)>(&mut conn)
+            .optional()?;
+        match receipt {
+            Some((public_nonce, signature)) => Ok(Some(Signature::new(
+                PublicKey::from_vec(&public_nonce)?,
+                PrivateKey::from_vec(&signature)?,
+            ))),
+            None => Ok(None),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Queryable)]
+struct TransactionEventSql {
+    seq: i64,
+    tx_id: i64,
+    event: String,
+    status: Option<i32>,
+    recorded_at: NaiveDateTime,
+}
+
+impl TransactionEventSql {
+    fn index_since(
+        since_seq: i64,
+        limit: i64,
+        conn: &mut SqliteConnection,
+    ) -> Result<Vec<TransactionEventSql>, TransactionStorageError> {
+        Ok(transaction_events::table
+            .filter(transaction_events::seq.gt(since_seq))
+            .order_by(transaction_events::seq.asc())
+            .limit(limit)
+            .load::<TransactionEventSql>(conn)?)
+    }
+}
+
+impl TryFrom<TransactionEventSql> for TransactionEventRecord {
+    type Error = TransactionStorageError;
+
+    fn try_from(e: TransactionEventSql) -> Result<Self, Self::Error> {
+        Ok(Self {
+            seq: e.seq as u64,
+            tx_id: TxId::from(e.tx_id as u64),
+            event: e.event,
+            status: e.status.map(TransactionStatus::try_from).transpose()?,
+            recorded_at: e.recorded_at,
+        })
+    }
+}
+
+/// A transaction event journal entry. Every entry is committed in the same database transaction as the change to the
+/// transaction that it records, so the journal can not miss a change or record one that was rolled back.
+#[derive(Clone, Debug, Insertable)]
+#[diesel(table_name = transaction_events)]
+struct NewTransactionEventSql {
+    tx_id: i64,
+    event: String,
+    status: Option<i32>,
+    recorded_at: NaiveDateTime,
+}
+
+impl NewTransactionEventSql {
+    fn new(tx_id: TxId, event: &str, status: Option<TransactionStatus>) -> Self {
+        Self {
+            tx_id: tx_id.as_u64() as i64,
+            event: event.to_string(),
+            status: status.map(|s| s as i32),
+            recorded_at: Utc::now().naive_utc(),
+        }
+    }
+
+    /// An entry that records the current status of a completed transaction
+    fn for_completed_transaction(
+        tx_id: TxId,
+        event: &str,
+        conn: &mut SqliteConnection,
+    ) -> Result<Self, TransactionStorageError> {
+        let status = TransactionStatus::try_from(CompletedTransactionSql::find(tx_id, conn)?.status)?;
+        Ok(Self::new(tx_id, event, Some(status)))
+    }
+
+    fn commit(&self, conn: &mut SqliteConnection) -> Result<(), TransactionStorageError> {
+        diesel::insert_into(transaction_events::table)
+            .values(self.clone())
+            .execute(conn)?;
+        Ok(())
+    }
 }
 
 #[derive(Debug, PartialEq)]
@@ -2258,7 +2451,7 @@ mod test {
         encryption::Encryptable,
         tari_address::TariAddress,
         transaction::{TransactionDirection, TransactionStatus, TxId},
-        types::{PrivateKey, PublicKey, Signature},
+        types::{BlockHash, PrivateKey, PublicKey, Signature},
     };
     use tari_core::transactions::{
         tari_amount::MicroMinotari,
@@ -2277,7 +2470,7 @@ mod test {
         storage::sqlite_utilities::wallet_db_connection::WalletDbConnection,
         test_utils::create_consensus_constants,
         transaction_service::storage::{
-            database::{DbKey, TransactionBackend},
+            database::{DbKey, DbKeyValuePair, TransactionBackend, WriteOperation},
             models::{CompletedTransaction, InboundTransaction, OutboundTransaction, TxCancellationReason},
             sqlite_db::{
                 CompletedTransactionSql,
@@ -3231,4 +3424,128 @@ mod test {
         assert_eq!(info_list.len(), 941);
         assert_eq!(info_list, info_list_reference);
     }
+
+    #[test]
+    fn test_transaction_event_journal() {
+        let db_name = format!("{}.sqlite3", string(8).as_str());
+        let temp_dir = tempdir().unwrap();
+        let db_folder = temp_dir.path().to_str().unwrap().to_string();
+        let db_path = format!("{}{}", db_folder, db_name);
+
+        const MIGRATIONS: EmbeddedMigrations = embed_migrations!("./migrations");
+        let mut pool = SqliteConnectionPool::new(db_path.clone(), 1, true, true, Duration::from_secs(60));
+        pool.create_pool()
+            .unwrap_or_else(|_| panic!("Error connecting to {}", db_path));
+        pool.get_pooled_connection()
+            .unwrap_or_else(|_| panic!("Error connecting to {}", db_path))
+            .run_pending_migrations(MIGRATIONS)
+            .expect("Migrations failed");
+
+        let mut key = [0u8; size_of::<Key>()];
+        OsRng.fill_bytes(&mut key);
+        let cipher = XChaCha20Poly1305::new(Key::from_slice(&key));
+        let db = TransactionServiceSqliteDatabase::new(WalletDbConnection::new(pool, None), cipher);
+
+        assert!(db.fetch_transaction_events_since(0, 10).unwrap().is_empty());
+
+        let tx_id = TxId::from(1u64);
+        let completed_tx = CompletedTransaction {
+            tx_id,
+            source_address: TariAddress::default(),
+            destination_address: TariAddress::default(),
+            amount: MicroMinotari::from(100),
+            fee: MicroMinotari::from(100),
+            transaction: Transaction::new(
+                vec![],
+                vec![],
+                vec![],
+                PrivateKey::random(&mut OsRng),
+                PrivateKey::random(&mut OsRng),
+            ),
+            status: TransactionStatus::Completed,
+            message: "Yo!".to_string(),
+            timestamp: Utc::now().naive_utc(),
+            cancelled: None,
+            direction: TransactionDirection::Outbound,
+            coinbase_block_height: None,
+            send_count: 0,
+            last_send_timestamp: None,
+            transaction_signature: Signature::default(),
+            confirmations: None,
+            mined_height: None,
+            mined_in_block: None,
+            mined_timestamp: None,
+        };
+        db.write(WriteOperation::Insert(DbKeyValuePair::CompletedTransaction(
+            tx_id,
+            Box::new(completed_tx),
+        )))
+        .unwrap();
+        db.broadcast_completed_transaction(tx_id).unwrap();
+        // A change that fails is not journaled
+        assert!(db.broadcast_completed_transaction(TxId::from(2u64)).is_err());
+        db.update_mined_height(tx_id, 10, BlockHash::zero(), 0, 1, false, false)
+            .unwrap();
+        // Counting another confirmation does not change the status, so it is not journaled
+        db.update_mined_height(tx_id, 10, BlockHash::zero(), 0, 2, false, false)
+            .unwrap();
+        db.update_mined_height(tx_id, 10, BlockHash::zero(), 0, 3, true, false)
+            .unwrap();
+        db.set_transaction_as_unmined(tx_id).unwrap();
+
+        let events = db.fetch_transaction_events_since(0, 10).unwrap();
+        assert_eq!(
+            events
+                .iter()
+                .map(|e| (e.tx_id, e.event.as_str(), e.status.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (tx_id, "transaction_completed", Some(TransactionStatus::Completed)),
+                (tx_id, "transaction_broadcast", Some(TransactionStatus::Broadcast)),
+                (
+                    tx_id,
+                    "transaction_mined_unconfirmed",
+                    Some(TransactionStatus::MinedUnconfirmed)
+                ),
+                (tx_id, "transaction_mined", Some(TransactionStatus::MinedConfirmed)),
+                (tx_id, "transaction_unmined", Some(TransactionStatus::Completed)),
+            ]
+        );
+        assert!(events.windows(2).all(|w| w[0].seq < w[1].seq));
+
+        let since_first = db.fetch_transaction_events_since(events[0].seq, 10).unwrap();
+        assert_eq!(since_first, events[1..].to_vec());
+        let first_only = db.fetch_transaction_events_since(0, 1).unwrap();
+        assert_eq!(first_only, events[..1].to_vec());
+        assert!(db.fetch_transaction_events_since(events[4].seq, 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_payment_receipt_storage() {
+        let db_name = format!("{}.sqlite3", string(8).as_str());
+        let temp_dir = tempdir().unwrap();
+        let db_folder = temp_dir.path().to_str().unwrap().to_string();
+        let db_path = format!("{}{}", db_folder, db_name);
+
+        const MIGRATIONS: EmbeddedMigrations = embed_migrations!("./migrations");
+        let mut pool = SqliteConnectionPool::new(db_path.clone(), 1, true, true, Duration::from_secs(60));
+        pool.create_pool()
+            .unwrap_or_else(|_| panic!("Error connecting to {}", db_path));
+        pool.get_pooled_connection()
+            .unwrap_or_else(|_| panic!("Error connecting to {}", db_path))
+            .run_pending_migrations(MIGRATIONS)
+            .expect("Migrations failed");
+
+        let mut key = [0u8; size_of::<Key>()];
+        OsRng.fill_bytes(&mut key);
+        let cipher = XChaCha20Poly1305::new(Key::from_slice(&key));
+        let db = TransactionServiceSqliteDatabase::new(WalletDbConnection::new(pool, None), cipher);
+
+        let tx_id = TxId::from(1u64);
+        assert_eq!(db.fetch_payment_receipt(tx_id).unwrap(), None);
+        let receipt = Signature::new(PublicKey::random_keypair(&mut OsRng).1, PrivateKey::random(&mut OsRng));
+        db.set_payment_receipt(tx_id, &receipt).unwrap();
+        assert_eq!(db.fetch_payment_receipt(tx_id).unwrap(), Some(receipt));
+        assert_eq!(db.fetch_payment_receipt(TxId::from(2u64)).unwrap(), None);
+    }
 }
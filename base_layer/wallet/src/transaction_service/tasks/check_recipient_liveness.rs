@@ -0,0 +1,69 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+use std::time::Duration;
+
+use log::*;
+use tari_comms::peer_manager::NodeId;
+use tari_p2p::services::liveness::{LivenessEvent, LivenessHandle};
+use tokio::{sync::broadcast::error::RecvError, time};
+
+const LOG_TARGET: &str = "wallet::transaction_service::tasks::check_recipient_liveness";
+
+/// Pings `node_id` via the liveness service and waits up to `timeout` for a pong from it. Returns false if the ping
+/// could not be sent or no pong was received in time.
+pub async fn check_recipient_liveness(mut liveness: LivenessHandle, node_id: NodeId, timeout: Duration) -> bool {
+    // Subscribe before pinging so that a fast pong is not missed
+    let mut event_stream = liveness.get_event_stream();
+    if let Err(e) = liveness.send_ping(node_id.clone()).await {
+        warn!(target: LOG_TARGET, "Failed to ping recipient {}: {}", node_id, e);
+        return false;
+    }
+
+    let wait_for_pong = async {
+        loop {
+            match event_stream.recv().await {
+                Ok(event) => {
+                    if let LivenessEvent::ReceivedPong(pong) = &*event {
+                        if pong.node_id == node_id {
+                            return true;
+                        }
+                    }
+                },
+                Err(RecvError::Lagged(n)) => {
+                    debug!(target: LOG_TARGET, "Liveness event stream lagged by {} events", n);
+                },
+                Err(RecvError::Closed) => return false,
+            }
+        }
+    };
+
+    match time::timeout(timeout, wait_for_pong).await {
+        Ok(online) => online,
+        Err(_) => {
+            debug!(
+                target: LOG_TARGET,
+                "Recipient {} did not respond to a ping within {:.2?}", node_id, timeout
+            );
+            false
+        },
+    }
+}
@@ -21,6 +21,8 @@
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 pub mod check_faux_transaction_status;
+pub mod check_recipient_liveness;
+pub mod send_direct_message;
 pub mod send_finalized_transaction;
 pub mod send_transaction_cancelled;
 pub mod send_transaction_reply;
@@ -0,0 +1,63 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use log::*;
+use tari_comms::types::CommsPublicKey;
+use tari_comms_dht::{
+    domain_message::OutboundDomainMessage,
+    outbound::{DhtOutboundError, OutboundMessageRequester, SendMessageResponse},
+};
+
+const LOG_TARGET: &str = "wallet::transaction_service::tasks";
+
+/// Sends a transaction negotiation message directly to the recipient. If `onion_routing_hops` is non-zero the message
+/// is encrypted for the recipient and onion-routed via that many randomly selected relays, otherwise it is sent over a
+/// direct connection to the recipient.
+pub async fn send_direct_message<T>(
+    outbound_message_service: &mut OutboundMessageRequester,
+    destination_public_key: CommsPublicKey,
+    message: OutboundDomainMessage<T>,
+    onion_routing_hops: usize,
+    source_info: &str,
+) -> Result<SendMessageResponse, DhtOutboundError>
+where
+    T: prost::Message,
+{
+    if onion_routing_hops == 0 {
+        return outbound_message_service
+            .send_direct_unencrypted(destination_public_key, message, source_info.to_string())
+            .await;
+    }
+
+    debug!(
+        target: LOG_TARGET,
+        "Sending {} to {} via {} onion relay(s)", source_info, destination_public_key, onion_routing_hops
+    );
+    outbound_message_service
+        .send_onion_routed(
+            destination_public_key,
+            message,
+            onion_routing_hops,
+            source_info.to_string(),
+        )
+        .await
+}
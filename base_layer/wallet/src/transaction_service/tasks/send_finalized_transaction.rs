@@ -36,7 +36,7 @@ use tari_p2p::tari_message::TariMessageType;
 use crate::transaction_service::{
     config::TransactionRoutingMechanism,
     error::TransactionServiceError,
-    tasks::wait_on_dial::wait_on_dial,
+    tasks::{send_direct_message::send_direct_message, wait_on_dial::wait_on_dial},
 };
 
 const LOG_TARGET: &str = "wallet::transaction_service::tasks::send_finalized_transaction";
@@ -48,6 +48,7 @@ pub async fn send_finalized_transaction_message(
     mut outbound_message_service: OutboundMessageRequester,
     direct_send_timeout: Duration,
     transaction_routing_mechanism: TransactionRoutingMechanism,
+    onion_routing_hops: usize,
 ) -> Result<(), TransactionServiceError> {
     match transaction_routing_mechanism {
         TransactionRoutingMechanism::DirectOnly | TransactionRoutingMechanism::DirectAndStoreAndForward => {
@@ -58,6 +59,7 @@ pub async fn send_finalized_transaction_message(
                 outbound_message_service,
                 direct_send_timeout,
                 transaction_routing_mechanism,
+                onion_routing_hops,
             )
             .await?;
         },
@@ -95,6 +97,7 @@ pub async fn send_finalized_transaction_message_direct(
     mut outbound_message_service: OutboundMessageRequester,
     direct_send_timeout: Duration,
     transaction_routing_mechanism: TransactionRoutingMechanism,
+    onion_routing_hops: usize,
 ) -> Result<(), TransactionServiceError> {
     let finalized_transaction_message = proto::TransactionFinalizedMessage {
         tx_id: tx_id.into(),
@@ -107,16 +110,17 @@ pub async fn send_finalized_transaction_message_direct(
     };
     let mut store_and_forward_send_result = false;
     let mut direct_send_result = false;
-    match outbound_message_service
-        .send_direct_unencrypted(
-            destination_public_key.clone(),
-            OutboundDomainMessage::new(
-                &TariMessageType::TransactionFinalized,
-                finalized_transaction_message.clone(),
-            ),
-            "transaction finalized".to_string(),
-        )
-        .await
+    match send_direct_message(
+        &mut outbound_message_service,
+        destination_public_key.clone(),
+        OutboundDomainMessage::new(
+            &TariMessageType::TransactionFinalized,
+            finalized_transaction_message.clone(),
+        ),
+        onion_routing_hops,
+        "transaction finalized",
+    )
+    .await
     {
         Ok(result) => match result {
             SendMessageResponse::Queued(send_states) => {
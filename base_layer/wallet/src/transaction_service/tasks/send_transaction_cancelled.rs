@@ -28,24 +28,26 @@ use tari_comms_dht::{
 use tari_core::transactions::transaction_protocol::proto::protocol as proto;
 use tari_p2p::tari_message::TariMessageType;
 
-use crate::transaction_service::error::TransactionServiceError;
+use crate::transaction_service::{error::TransactionServiceError, tasks::send_direct_message::send_direct_message};
 
 pub async fn send_transaction_cancelled_message(
     tx_id: TxId,
     destination_public_key: CommsPublicKey,
     mut outbound_message_service: OutboundMessageRequester,
+    onion_routing_hops: usize,
 ) -> Result<(), TransactionServiceError> {
     let proto_message = proto::TransactionCancelledMessage { tx_id: tx_id.into() };
 
     // Send both direct and SAF we are not going to monitor the progress on these messages for potential resend as
     // they are just courtesy messages
-    let _send_message_response = outbound_message_service
-        .send_direct_unencrypted(
-            destination_public_key.clone(),
-            OutboundDomainMessage::new(&TariMessageType::TransactionCancelled, proto_message.clone()),
-            "transaction cancelled".to_string(),
-        )
-        .await?;
+    let _send_message_response = send_direct_message(
+        &mut outbound_message_service,
+        destination_public_key.clone(),
+        OutboundDomainMessage::new(&TariMessageType::TransactionCancelled, proto_message.clone()),
+        onion_routing_hops,
+        "transaction cancelled",
+    )
+    .await?;
 
     let _message_send_state = outbound_message_service
         .closest_broadcast(
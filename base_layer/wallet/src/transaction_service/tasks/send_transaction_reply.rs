@@ -36,7 +36,7 @@ use crate::transaction_service::{
     config::TransactionRoutingMechanism,
     error::TransactionServiceError,
     storage::models::InboundTransaction,
-    tasks::wait_on_dial::wait_on_dial,
+    tasks::{send_direct_message::send_direct_message, wait_on_dial::wait_on_dial},
 };
 
 const LOG_TARGET: &str = "wallet::transaction_service::tasks::send_transaction_reply";
@@ -48,6 +48,7 @@ pub async fn send_transaction_reply(
     mut outbound_message_service: OutboundMessageRequester,
     direct_send_timeout: Duration,
     transaction_routing_mechanism: TransactionRoutingMechanism,
+    onion_routing_hops: usize,
 ) -> Result<bool, TransactionServiceError> {
     let recipient_reply = inbound_transaction.receiver_protocol.get_signed_data()?.clone();
     let proto_message: proto::RecipientSignedMessage = recipient_reply
@@ -61,6 +62,7 @@ pub async fn send_transaction_reply(
                 outbound_message_service,
                 direct_send_timeout,
                 transaction_routing_mechanism,
+                onion_routing_hops,
             )
             .await?
         },
@@ -85,6 +87,7 @@ pub async fn send_transaction_reply_direct(
     mut outbound_message_service: OutboundMessageRequester,
     direct_send_timeout: Duration,
     transaction_routing_mechanism: TransactionRoutingMechanism,
+    onion_routing_hops: usize,
 ) -> Result<bool, TransactionServiceError> {
     let recipient_reply = inbound_transaction.receiver_protocol.get_signed_data()?.clone();
 
@@ -95,13 +98,14 @@ pub async fn send_transaction_reply_direct(
     let proto_message: proto::RecipientSignedMessage = recipient_reply
         .try_into()
         .map_err(TransactionServiceError::ServiceError)?;
-    match outbound_message_service
-        .send_direct_unencrypted(
-            inbound_transaction.source_address.public_key().clone(),
-            OutboundDomainMessage::new(&TariMessageType::ReceiverPartialTransactionReply, proto_message.clone()),
-            "wallet transaction reply".to_string(),
-        )
-        .await
+    match send_direct_message(
+        &mut outbound_message_service,
+        inbound_transaction.source_address.public_key().clone(),
+        OutboundDomainMessage::new(&TariMessageType::ReceiverPartialTransactionReply, proto_message.clone()),
+        onion_routing_hops,
+        "wallet transaction reply",
+    )
+    .await
     {
         Ok(result) => match result {
             SendMessageResponse::Queued(send_states) => {
@@ -0,0 +1,179 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Payment requests are passed between Tari applications, e.g. in a QR code, as the `transactions/send` action of a
+//! [`TariUri`] deep link:
+//! `tari://<network>/transactions/send?tariAddress=<address>&amount=<amount in µT>&message=<message>`, where only the
+//! address is required.
+
+use std::{
+    convert::TryFrom,
+    fmt,
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use tari_common_types::{
+    tari_address::TariAddress,
+    tari_uri::{TariUri, TariUriAction, TariUriError},
+};
+use tari_core::transactions::tari_amount::MicroMinotari;
+use thiserror::Error;
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum PaymentRequestError {
+    #[error("Invalid Tari URI: {0}")]
+    InvalidUri(#[from] TariUriError),
+    #[error("The Tari URI `{0}` is not a payment request")]
+    NotAPaymentRequest(String),
+}
+
+/// A request for a payment to a Tari address, optionally for a specific amount and with a message for the recipient
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaymentRequest {
+    pub address: TariAddress,
+    pub amount: Option<MicroMinotari>,
+    pub message: Option<String>,
+}
+
+impl PaymentRequest {
+    pub fn new(address: TariAddress) -> Self {
+        Self {
+            address,
+            amount: None,
+            message: None,
+        }
+    }
+
+    pub fn with_amount(mut self, amount: MicroMinotari) -> Self {
+        self.amount = Some(amount);
+        self
+    }
+
+    pub fn with_message<T: Into<String>>(mut self, message: T) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+
+    /// The deep link URI for this payment request
+    pub fn to_uri(&self) -> String {
+        TariUri::from(self.clone()).to_string()
+    }
+}
+
+impl From<PaymentRequest> for TariUri {
+    fn from(request: PaymentRequest) -> Self {
+        TariUri::new(request.address.network(), TariUriAction::Send {
+            address: request.address,
+            amount: request.amount.map(|amount| amount.as_u64()),
+            message: request.message,
+        })
+    }
+}
+
+impl TryFrom<TariUri> for PaymentRequest {
+    type Error = PaymentRequestError;
+
+    fn try_from(uri: TariUri) -> Result<Self, Self::Error> {
+        match uri.action {
+            TariUriAction::Send {
+                address,
+                amount,
+                message,
+            } => Ok(Self {
+                address,
+                amount: amount.map(MicroMinotari::from),
+                message,
+            }),
+            _ => Err(PaymentRequestError::NotAPaymentRequest(uri.to_string())),
+        }
+    }
+}
+
+impl Display for PaymentRequest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_uri())
+    }
+}
+
+impl FromStr for PaymentRequest {
+    type Err = PaymentRequestError;
+
+    fn from_str(uri: &str) -> Result<Self, Self::Err> {
+        Self::try_from(TariUri::from_str(uri)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tari_common::configuration::Network;
+    use tari_common_types::types::PublicKey;
+    use tari_crypto::keys::PublicKey as PublicKeyTrait;
+
+    use super::*;
+
+    fn address(network: Network) -> TariAddress {
+        let (_, public_key) = PublicKey::random_keypair(&mut rand::rngs::OsRng);
+        TariAddress::new(public_key, network)
+    }
+
+    #[test]
+    fn it_round_trips_a_payment_request() {
+        let request = PaymentRequest::new(address(Network::Esmeralda))
+            .with_amount(MicroMinotari::from(1_234_567))
+            .with_message("Coffee & cake, 100% 🍰");
+        let uri = request.to_uri();
+        assert!(uri.starts_with("tari://esmeralda/transactions/send?tariAddress="));
+        assert!(!uri.contains(' '));
+        assert_eq!(uri.parse::<PaymentRequest>().unwrap(), request);
+    }
+
+    #[test]
+    fn it_parses_an_address_only_request() {
+        let address = address(Network::LocalNet);
+        let uri = format!("tari://localnet/transactions/send?tariAddress={}", address.to_hex());
+        let request = uri.parse::<PaymentRequest>().unwrap();
+        assert_eq!(request, PaymentRequest::new(address));
+        assert_eq!(request.to_uri(), uri);
+    }
+
+    #[test]
+    fn it_rejects_invalid_requests() {
+        let address = address(Network::LocalNet);
+        assert_eq!(
+            "https://tari.com".parse::<PaymentRequest>(),
+            Err(PaymentRequestError::InvalidUri(TariUriError::InvalidScheme))
+        );
+        let add_contact = format!("tari://localnet/contacts/add?tariAddress={}", address.to_hex());
+        assert_eq!(
+            add_contact.parse::<PaymentRequest>(),
+            Err(PaymentRequestError::NotAPaymentRequest(add_contact))
+        );
+        assert_eq!(
+            format!("tari://esmeralda/transactions/send?tariAddress={}", address.to_hex()).parse::<PaymentRequest>(),
+            Err(PaymentRequestError::InvalidUri(TariUriError::NetworkMismatch {
+                address: Network::LocalNet,
+                uri: Network::Esmeralda
+            }))
+        );
+    }
+}
@@ -20,7 +20,13 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{cmp, marker::PhantomData, sync::Arc};
+use std::{
+    cmp,
+    collections::{HashMap, HashSet},
+    marker::PhantomData,
+    str::FromStr,
+    sync::Arc,
+};
 
 use blake2::Blake2b;
 use digest::consts::U32;
@@ -29,13 +35,13 @@ use rand::rngs::OsRng;
 use tari_common::configuration::bootstrap::ApplicationType;
 use tari_common_types::{
     tari_address::TariAddress,
-    transaction::{ImportStatus, TxId},
+    transaction::{ImportStatus, TransactionDirection, TransactionStatus, TxId},
     types::{ComAndPubSignature, Commitment, PrivateKey, PublicKey, SignatureWithDomain},
 };
 use tari_comms::{
     multiaddr::Multiaddr,
     net_address::{MultiaddressesWithStats, PeerAddressSource},
-    peer_manager::{NodeId, Peer, PeerFeatures, PeerFlags},
+    peer_manager::{NodeId, Peer, PeerFeatures, PeerFlags, PeerQuery, PeerQuerySortBy},
     types::{CommsPublicKey, CommsSecretKey},
     CommsNode,
     NodeIdentity,
@@ -52,6 +58,7 @@ use tari_core::{
     covenants::Covenant,
     transactions::{
         key_manager::{SecretTransactionKeyManagerInterface, TransactionKeyManagerInitializer},
+        payment_proof::PaymentProof,
         tari_amount::MicroMinotari,
         transaction_components::{EncryptedData, OutputFeatures, UnblindedOutput},
         CryptoFactories,
@@ -70,13 +77,14 @@ use tari_p2p::{
     comms_connector::pubsub_connector,
     initialization,
     initialization::P2pInitializer,
+    peer_seeds::SeedPeer,
     services::liveness::{config::LivenessConfig, LivenessInitializer},
     PeerSeedsConfig,
 };
 use tari_script::{one_sided_payment_script, ExecutionStack, TariScript};
 use tari_service_framework::StackBuilder;
 use tari_shutdown::ShutdownSignal;
-use tari_utilities::{hex::Hex, ByteArray};
+use tari_utilities::{hex::Hex, ByteArray, SafePassword};
 
 use crate::{
     base_node_service::{handle::BaseNodeServiceHandle, BaseNodeServiceInitializer},
@@ -87,12 +95,14 @@ use crate::{
     output_manager_service::{
         error::OutputManagerError,
         handle::OutputManagerHandle,
+        service::EncumbranceAudit,
         storage::{
             database::{OutputManagerBackend, OutputManagerDatabase},
             models::KnownOneSidedPaymentScript,
         },
         OutputManagerServiceInitializer,
     },
+    scheduled_payments_service::{handle::ScheduledPaymentsHandle, ScheduledPaymentsServiceInitializer},
     storage::database::{WalletBackend, WalletDatabase},
     transaction_service::{
         handle::TransactionServiceHandle,
@@ -104,8 +114,23 @@ use crate::{
 };
 
 const LOG_TARGET: &str = "wallet";
+
+/// The outcome of auditing the wallet's outputs against its transactions
+#[derive(Debug, Clone, Default)]
+pub struct OutputAuditReport {
+    pub encumbrances: EncumbranceAudit,
+    /// Commitments spent by more than one unmined transaction. At most one of these transactions can be mined, so they
+    /// are reported for the user to decide which to cancel rather than repaired automatically.
+    pub duplicate_commitments: Vec<(Commitment, Vec<TxId>)>,
+}
 /// The minimum buffer size for the wallet pubsub_connector channel
 const WALLET_BUFFER_MIN_SIZE: usize = 300;
+/// Client key under which the base node candidates added with [Wallet::add_base_node_candidate] are stored
+const BASE_NODE_CANDIDATES_KEY: &str = "base_node_candidates";
+/// Client key under which the public key of the pinned base node is stored
+const PINNED_BASE_NODE_KEY: &str = "pinned_base_node";
+/// The maximum number of base nodes from the peer database that are added to the base node candidates on startup
+const MAX_PEER_DB_BASE_NODE_CANDIDATES: usize = 5;
 
 // Domain separator for signing arbitrary messages with a wallet secret key
 hash_domain!(WalletMessageSigningDomain, "com.tari.base_layer.wallet.message_signing");
@@ -125,6 +150,7 @@ pub struct Wallet<T, U, V, W, TKeyManagerInterface> {
     pub contacts_service: ContactsServiceHandle,
     pub base_node_service: BaseNodeServiceHandle,
     pub utxo_scanner_service: UtxoScannerHandle,
+    pub scheduled_payments_service: ScheduledPaymentsHandle,
     pub updater_service: Option<SoftwareUpdaterHandle>,
     pub db: WalletDatabase<T>,
     pub output_db: OutputManagerDatabase<V>,
@@ -230,6 +256,10 @@ where
                 wallet_database.clone(),
                 factories.clone(),
                 wallet_identity.clone(),
+            ))
+            .add_initializer(ScheduledPaymentsServiceInitializer::new(
+                config.scheduled_payments_service_config,
+                wallet_database.clone(),
             ));
 
         // Check if we have update config. FFI wallets don't do this, the update on mobile is done differently.
@@ -252,6 +282,12 @@ where
             .take_handle::<UnspawnedCommsNode>()
             .expect("P2pInitializer was not added to the stack");
         let comms = initialization::spawn_comms_using_transport(comms, config.p2p.transport).await?;
+        let configured_base_nodes = config
+            .custom_base_node
+            .iter()
+            .chain(config.base_node_service_peers.iter())
+            .cloned()
+            .collect::<Vec<_>>();
 
         let mut output_manager_handle = handles.expect_handle::<OutputManagerHandle>();
         let key_manager_handle = handles.expect_handle::<TKeyManagerInterface>();
@@ -262,6 +298,7 @@ where
 
         let base_node_service_handle = handles.expect_handle::<BaseNodeServiceHandle>();
         let utxo_scanner_service_handle = handles.expect_handle::<UtxoScannerHandle>();
+        let scheduled_payments_handle = handles.expect_handle::<ScheduledPaymentsHandle>();
         let wallet_connectivity = handles.expect_handle::<WalletConnectivityHandle>();
         let updater_handle = if auto_update.is_update_enabled() {
             Some(handles.expect_handle::<SoftwareUpdaterHandle>())
@@ -297,7 +334,7 @@ where
             warn!("failed to store network and version: {:#?}", e);
         }
 
-        Ok(Self {
+        let mut wallet = Self {
             network: config.network.into(),
             comms,
             dht_service: dht,
@@ -308,6 +345,7 @@ where
             contacts_service: contacts_handle,
             base_node_service: base_node_service_handle,
             utxo_scanner_service: utxo_scanner_service_handle,
+            scheduled_payments_service: scheduled_payments_handle,
             updater_service: updater_handle,
             wallet_connectivity,
             db: wallet_database,
@@ -318,7 +356,10 @@ where
             _u: PhantomData,
             _v: PhantomData,
             _w: PhantomData,
-        })
+        };
+        wallet.restore_base_node_candidates(&configured_base_nodes).await?;
+
+        Ok(wallet)
     }
 
     /// This method consumes the wallet so that the handles are dropped which will result in the services async loops
@@ -346,6 +387,116 @@ where
                 .await?;
         }
 
+        let peer = self.upsert_base_node_peer(public_key, address).await?;
+        self.wallet_connectivity.set_base_node(peer);
+
+        Ok(())
+    }
+
+    /// Adds a base node that the wallet can fail over to if the active base node becomes unavailable
+    pub async fn add_base_node_candidate(
+        &mut self,
+        public_key: CommsPublicKey,
+        address: Multiaddr,
+    ) -> Result<(), WalletError> {
+        info!(
+            target: LOG_TARGET,
+            "Wallet adding base node candidate, public key: {}, net address: {}.", public_key, address
+        );
+        let peer = self.upsert_base_node_peer(public_key.clone(), address.clone()).await?;
+        self.wallet_connectivity.add_base_node_candidates(Some(peer));
+
+        let mut candidates = self.stored_base_node_candidates()?;
+        candidates.retain(|c| c.public_key != public_key);
+        candidates.push(SeedPeer::new(public_key, vec![address]));
+        let candidates = serde_json::to_string(&candidates).map_err(WalletStorageError::from)?;
+        self.db
+            .set_client_key_value(BASE_NODE_CANDIDATES_KEY.to_string(), candidates)?;
+        Ok(())
+    }
+
+    /// Pins the wallet to the given base node candidate, which is used whenever it is healthy. The pin is kept across
+    /// restarts.
+    pub fn pin_base_node(&mut self, public_key: &CommsPublicKey) -> Result<(), WalletError> {
+        self.wallet_connectivity.pin_base_node(public_key)?;
+        self.db
+            .set_client_key_value(PINNED_BASE_NODE_KEY.to_string(), public_key.to_hex())?;
+        Ok(())
+    }
+
+    /// Removes the base node pin, letting the wallet select the best base node candidate
+    pub fn unpin_base_node(&mut self) -> Result<(), WalletError> {
+        self.wallet_connectivity.unpin_base_node();
+        self.db.clear_client_value(PINNED_BASE_NODE_KEY.to_string())?;
+        Ok(())
+    }
+
+    /// Returns the base node candidates stored by [Self::add_base_node_candidate]
+    fn stored_base_node_candidates(&self) -> Result<Vec<SeedPeer>, WalletError> {
+        let candidates = match self.db.get_client_key_value(BASE_NODE_CANDIDATES_KEY.to_string())? {
+            Some(candidates) => candidates,
+            None => return Ok(Vec::new()),
+        };
+        Ok(serde_json::from_str(&candidates).unwrap_or_else(|e| {
+            warn!(target: LOG_TARGET, "Ignoring malformed stored base node candidates: {}", e);
+            Vec::new()
+        }))
+    }
+
+    /// Fills the base node failover pool from the configured base nodes, the stored base node candidates and the most
+    /// recently connected base nodes in the peer database, then restores the pinned base node
+    async fn restore_base_node_candidates(&mut self, configured_base_nodes: &[String]) -> Result<(), WalletError> {
+        let mut seed_peers = configured_base_nodes
+            .iter()
+            .filter_map(|s| match SeedPeer::from_str(s) {
+                Ok(seed_peer) => Some(seed_peer),
+                Err(e) => {
+                    warn!(target: LOG_TARGET, "Ignoring invalid base node peer '{}': {}", s, e);
+                    None
+                },
+            })
+            .collect::<Vec<_>>();
+        seed_peers.extend(self.stored_base_node_candidates()?);
+        for seed_peer in seed_peers {
+            let address = match seed_peer.addresses.first() {
+                Some(address) => address.clone(),
+                None => continue,
+            };
+            let peer = self.upsert_base_node_peer(seed_peer.public_key, address).await?;
+            self.wallet_connectivity.add_base_node_candidates(Some(peer));
+        }
+
+        let known_base_nodes = self
+            .comms
+            .peer_manager()
+            .perform_query(
+                PeerQuery::new()
+                    .select_where(|p| !p.is_banned() && !p.is_offline() && !p.features.is_client())
+                    .sort_by(PeerQuerySortBy::LastConnected)
+                    .limit(MAX_PEER_DB_BASE_NODE_CANDIDATES),
+            )
+            .await?;
+        self.wallet_connectivity.add_base_node_candidates(known_base_nodes);
+
+        if let Some(public_key) = self.db.get_client_key_value(PINNED_BASE_NODE_KEY.to_string())? {
+            match CommsPublicKey::from_hex(&public_key) {
+                Ok(public_key) => {
+                    if let Err(e) = self.wallet_connectivity.pin_base_node(&public_key) {
+                        warn!(target: LOG_TARGET, "Could not restore the pinned base node: {}", e);
+                    }
+                },
+                Err(e) => warn!(target: LOG_TARGET, "Ignoring malformed pinned base node: {}", e),
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds or updates the base node peer in the peer manager and adds it to the connectivity allow list
+    async fn upsert_base_node_peer(
+        &mut self,
+        public_key: CommsPublicKey,
+        address: Multiaddr,
+    ) -> Result<Peer, WalletError> {
         let peer_manager = self.comms.peer_manager();
         let mut connectivity = self.comms.connectivity();
         if let Some(mut current_peer) = peer_manager.find_by_public_key(&public_key).await? {
@@ -364,7 +515,7 @@ where
             connectivity
                 .add_peer_to_allow_list(current_peer.node_id.clone())
                 .await?;
-            self.wallet_connectivity.set_base_node(current_peer);
+            Ok(current_peer)
         } else {
             let node_id = NodeId::from_key(&public_key);
             let peer = Peer::new(
@@ -378,10 +529,8 @@ where
             );
             peer_manager.add_peer(peer.clone()).await?;
             connectivity.add_peer_to_allow_list(peer.node_id.clone()).await?;
-            self.wallet_connectivity.set_base_node(peer);
+            Ok(peer)
         }
-
-        Ok(())
     }
 
     pub async fn get_base_node_peer(&mut self) -> Option<Peer> {
@@ -517,6 +666,49 @@ where
         signature.verify_message(public_key, message)
     }
 
+    /// Create a payment proof for the outbound transaction `tx_id`, signed with this wallet's key. The proof binds the
+    /// transaction kernel to the recipient address, amount and `message`, includes the receipt the recipient returned
+    /// during the transaction protocol and can be checked by a third party.
+    pub async fn create_payment_proof(&mut self, tx_id: TxId, message: String) -> Result<PaymentProof, WalletError> {
+        let transaction = self.transaction_service.get_completed_transaction(tx_id).await?;
+        if transaction.direction != TransactionDirection::Outbound {
+            return Err(WalletError::ArgumentError {
+                argument: "tx_id".to_string(),
+                value: tx_id.to_string(),
+                message: "Payment proofs can only be created for outbound transactions".to_string(),
+            });
+        }
+        let kernel = transaction
+            .transaction
+            .body
+            .kernels()
+            .first()
+            .ok_or_else(|| WalletError::ArgumentError {
+                argument: "tx_id".to_string(),
+                value: tx_id.to_string(),
+                message: "Transaction has no kernel".to_string(),
+            })?;
+        let receipt = self
+            .transaction_service
+            .get_payment_receipt(tx_id)
+            .await?
+            .ok_or_else(|| WalletError::ArgumentError {
+                argument: "tx_id".to_string(),
+                value: tx_id.to_string(),
+                message: "The recipient did not provide a payment receipt for this transaction".to_string(),
+            })?;
+        let proof = PaymentProof::sign(
+            self.comms.node_identity().secret_key(),
+            kernel,
+            transaction.source_address.clone(),
+            transaction.destination_address.clone(),
+            transaction.amount,
+            message,
+            receipt,
+        )?;
+        Ok(proof)
+    }
+
     /// Appraise the expected outputs and a fee
     pub async fn preview_coin_split_with_commitments_no_amount(
         &mut self,
@@ -654,6 +846,57 @@ where
         }
     }
 
+    /// Cross-reference the encumbered outputs against the wallet's pending and completed transactions. With `repair`
+    /// set, encumbrances that belong to no live transaction are released, and the released outputs are revalidated
+    /// against the base node's UTXO set.
+    pub async fn audit_outputs(&self, repair: bool) -> Result<OutputAuditReport, WalletError> {
+        let mut transaction_service = self.transaction_service.clone();
+        let pending_inbound = transaction_service.get_pending_inbound_transactions().await?;
+        let pending_outbound = transaction_service.get_pending_outbound_transactions().await?;
+        let completed = transaction_service.get_completed_transactions().await?;
+
+        let mut spending_tx_ids = HashMap::<Commitment, Vec<TxId>>::new();
+        for tx in completed
+            .values()
+            .filter(|tx| matches!(tx.status, TransactionStatus::Completed | TransactionStatus::Broadcast))
+        {
+            for input in tx.transaction.body.inputs() {
+                if let Ok(commitment) = input.commitment() {
+                    spending_tx_ids.entry(commitment.clone()).or_default().push(tx.tx_id);
+                }
+            }
+        }
+        let duplicate_commitments = spending_tx_ids
+            .into_iter()
+            .filter(|(_, tx_ids)| tx_ids.len() > 1)
+            .collect::<Vec<_>>();
+        for (commitment, tx_ids) in &duplicate_commitments {
+            warn!(
+                target: LOG_TARGET,
+                "Commitment {} is spent by {} unmined transactions: {:?}",
+                commitment.to_hex(),
+                tx_ids.len(),
+                tx_ids
+            );
+        }
+
+        let live_tx_ids = pending_inbound
+            .into_keys()
+            .chain(pending_outbound.into_keys())
+            .chain(completed.into_keys())
+            .collect::<HashSet<_>>();
+        let encumbrances = self
+            .output_manager_service
+            .clone()
+            .audit_encumbrances(live_tx_ids, repair)
+            .await?;
+
+        Ok(OutputAuditReport {
+            encumbrances,
+            duplicate_commitments,
+        })
+    }
+
     /// Utility function to find out if there is data in the database indicating that there is an incomplete recovery
     /// process in progress
     pub fn is_recovery_in_progress(&self) -> Result<bool, WalletError> {
@@ -670,6 +913,31 @@ where
         let seed_words = master_seed.to_mnemonic(*language, None)?;
         Ok(seed_words)
     }
+
+    /// Re-encrypt the wallet database main key under a new passphrase. The existing passphrase must be provided.
+    pub fn rotate_passphrase(&self, existing: &SafePassword, new: &SafePassword) -> Result<(), WalletError> {
+        self.db.change_passphrase(existing, new)?;
+        info!(target: LOG_TARGET, "Wallet database passphrase rotated");
+        Ok(())
+    }
+
+    /// Remove the wallet database main key from memory. Encrypted wallet data cannot be read or written until the
+    /// wallet is unlocked again.
+    pub fn lock(&self) {
+        self.db.lock();
+        info!(target: LOG_TARGET, "Wallet database locked");
+    }
+
+    /// Restore the wallet database main key using the passphrase
+    pub fn unlock(&self, passphrase: &SafePassword) -> Result<(), WalletError> {
+        self.db.unlock(passphrase)?;
+        info!(target: LOG_TARGET, "Wallet database unlocked");
+        Ok(())
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.db.is_locked()
+    }
 }
 
 pub fn read_or_create_master_seed<T: WalletBackend + 'static>(
@@ -23,6 +23,28 @@
 use std::{mem::size_of, panic, path::Path, sync::Arc, time::Duration};
 
 use chacha20poly1305::{Key, KeyInit, XChaCha20Poly1305};
+use minotari_wallet::{
+    error::{WalletError, WalletStorageError},
+    output_manager_service::{
+        storage::{database::OutputManagerDatabase, sqlite_db::OutputManagerSqliteDatabase},
+        UtxoSelectionCriteria,
+    },
+    storage::{
+        database::{DbKeyValuePair, WalletBackend, WalletDatabase, WriteOperation},
+        sqlite_db::wallet::WalletSqliteDatabase,
+        sqlite_utilities::{initialize_sqlite_database_backends, run_migration_and_create_sqlite_connection},
+    },
+    test_utils::make_wallet_database_connection,
+    transaction_service::{
+        config::TransactionServiceConfig,
+        handle::TransactionEvent,
+        storage::sqlite_db::TransactionServiceSqliteDatabase,
+    },
+    wallet::read_or_create_master_seed,
+    Wallet,
+    WalletConfig,
+    WalletSqlite,
+};
 use rand::{rngs::OsRng, RngCore};
 use support::utils::make_non_recoverable_input;
 use tari_common::configuration::{MultiaddrList, StringList};
@@ -49,6 +71,7 @@ use tari_contacts::contacts_service::{
 use tari_core::{
     consensus::ConsensusManager,
     covenants::Covenant,
+    test_helpers::create_test_core_key_manager_with_memory_db,
     transactions::{
         tari_amount::{uT, MicroMinotari},
         test_helpers::{create_wallet_output_with_data, TestParams},
@@ -78,31 +101,8 @@ use tari_script::{inputs, script};
 use tari_shutdown::{Shutdown, ShutdownSignal};
 use tari_test_utils::{collect_recv, comms_and_services::get_next_memory_address, random};
 use tari_utilities::{Hidden, SafePassword};
-use minotari_wallet::{
-    error::{WalletError, WalletStorageError},
-    output_manager_service::{
-        storage::{database::OutputManagerDatabase, sqlite_db::OutputManagerSqliteDatabase},
-        UtxoSelectionCriteria,
-    },
-    storage::{
-        database::{DbKeyValuePair, WalletBackend, WalletDatabase, WriteOperation},
-        sqlite_db::wallet::WalletSqliteDatabase,
-        sqlite_utilities::{initialize_sqlite_database_backends, run_migration_and_create_sqlite_connection},
-    },
-    test_utils::make_wallet_database_connection,
-    transaction_service::{
-        config::TransactionServiceConfig,
-        handle::TransactionEvent,
-        storage::sqlite_db::TransactionServiceSqliteDatabase,
-    },
-    wallet::read_or_create_master_seed,
-    Wallet,
-    WalletConfig,
-    WalletSqlite,
-};
 use tempfile::tempdir;
 use tokio::{sync::mpsc, time::sleep};
-use tari_core::test_helpers::create_test_core_key_manager_with_memory_db;
 
 use crate::support::utils::make_input;
 
@@ -155,6 +155,9 @@ async fn create_wallet(
         auxiliary_tcp_listener_address: None,
         rpc_max_simultaneous_sessions: 0,
         rpc_max_sessions_per_peer: 0,
+        rpc_compression: Default::default(),
+        max_substreams_per_protocol: 0,
+        max_connections: 0,
         listener_liveness_check_interval: None,
     };
 
@@ -285,7 +288,13 @@ async fn test_wallet() {
 
     let value = MicroMinotari::from(1000);
     let key_manager = create_test_core_key_manager_with_memory_db();
-    let (_utxo, uo1) = make_non_recoverable_input(&mut OsRng, MicroMinotari(2500), &OutputFeatures::default(), &key_manager).await;
+    let (_utxo, uo1) = make_non_recoverable_input(
+        &mut OsRng,
+        MicroMinotari(2500),
+        &OutputFeatures::default(),
+        &key_manager,
+    )
+    .await;
 
     alice_wallet.output_manager_service.add_output(uo1, None).await.unwrap();
 
@@ -591,7 +600,13 @@ async fn test_store_and_forward_send_tx() {
 
     let value = MicroMinotari::from(1000);
     let key_manager = create_test_core_key_manager_with_memory_db();
-    let (_utxo, uo1) = make_non_recoverable_input(&mut OsRng, MicroMinotari(2500), &OutputFeatures::default(), &key_manager).await;
+    let (_utxo, uo1) = make_non_recoverable_input(
+        &mut OsRng,
+        MicroMinotari(2500),
+        &OutputFeatures::default(),
+        &key_manager,
+    )
+    .await;
 
     alice_wallet.output_manager_service.add_output(uo1, None).await.unwrap();
 
@@ -693,6 +708,9 @@ async fn test_import_utxo() {
         auxiliary_tcp_listener_address: None,
         rpc_max_simultaneous_sessions: 0,
         rpc_max_sessions_per_peer: 0,
+        rpc_compression: Default::default(),
+        max_substreams_per_protocol: 0,
+        max_connections: 0,
         listener_liveness_check_interval: None,
     };
     let config = WalletConfig {
@@ -706,7 +724,7 @@ async fn test_import_utxo() {
     let key_ga = Key::from_slice(&key);
     let cipher = XChaCha20Poly1305::new(key_ga);
 
-    let output_manager_backend = OutputManagerSqliteDatabase::new(connection.clone());
+    let output_manager_backend = OutputManagerSqliteDatabase::new(connection.clone(), cipher.clone());
 
     let mut alice_wallet = Wallet::start(
         config,
@@ -721,7 +739,7 @@ async fn test_import_utxo() {
         OutputManagerDatabase::new(output_manager_backend.clone()),
         TransactionServiceSqliteDatabase::new(connection.clone(), cipher.clone()),
         output_manager_backend,
-        ContactsServiceSqliteDatabase::init(connection.clone()),
+        ContactsServiceSqliteDatabase::init(connection.clone(), cipher.clone()),
         KeyManagerSqliteDatabase::init(connection.clone(), cipher.clone()),
         shutdown.to_signal(),
         CipherSeed::new(),
@@ -736,7 +754,9 @@ async fn test_import_utxo() {
 
     let key_manager = create_test_core_key_manager_with_memory_db();
     let p = TestParams::new(&key_manager);
-    let utxo = create_wallet_output_with_data(script.clone(), temp_features, &p, 20000 * uT, &key_manager).await.unwrap();
+    let utxo = create_wallet_output_with_data(script.clone(), temp_features, &p, 20000 * uT, &key_manager)
+        .await
+        .unwrap();
     let output = utxo.as_transaction_output(&key_manager).unwrap();
     let expected_output_hash = output.hash();
     let node_address = TariAddress::new(node_identity.public_key().clone(), network);
@@ -781,7 +801,7 @@ async fn test_import_utxo() {
 
     assert_eq!(completed_tx.amount, 20000 * uT);
     assert_eq!(completed_tx.status, TransactionStatus::Imported);
-    let db = OutputManagerDatabase::new(OutputManagerSqliteDatabase::new(connection));
+    let db = OutputManagerDatabase::new(OutputManagerSqliteDatabase::new(connection, cipher));
     let outputs = db.fetch_outputs_by_tx_id(tx_id).unwrap();
     assert!(outputs.iter().any(|o| { o.hash == expected_output_hash }));
 }
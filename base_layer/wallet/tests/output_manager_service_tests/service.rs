@@ -20,7 +20,12 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{collections::HashMap, convert::TryInto, sync::Arc, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    convert::TryInto,
+    sync::Arc,
+    time::Duration,
+};
 
 use minotari_wallet::{
     base_node_service::handle::{BaseNodeEvent, BaseNodeServiceHandle},
@@ -80,6 +85,7 @@ use tari_key_manager::key_manager_service::KeyManagerInterface;
 use tari_script::{inputs, script, TariScript};
 use tari_service_framework::reply_channel;
 use tari_shutdown::Shutdown;
+use tari_utilities::ByteArray;
 use tokio::{
     sync::{broadcast, broadcast::channel},
     task,
@@ -90,7 +96,7 @@ use crate::support::{
     base_node_service_mock::MockBaseNodeService,
     comms_rpc::{connect_rpc_client, BaseNodeWalletRpcMockService, BaseNodeWalletRpcMockState},
     data::get_temp_sqlite_database_connection,
-    utils::{make_input, make_input_with_features},
+    utils::{make_input, make_input_with_features, random_db_cipher},
 };
 
 fn default_features_and_scripts_size_byte_size() -> std::io::Result<usize> {
@@ -302,7 +308,7 @@ async fn generate_sender_transaction_message(
 #[tokio::test]
 async fn fee_estimate() {
     let (connection, _tempdir) = get_temp_sqlite_database_connection();
-    let backend = OutputManagerSqliteDatabase::new(connection.clone());
+    let backend = OutputManagerSqliteDatabase::new(connection.clone(), random_db_cipher());
     let mut oms = setup_output_manager_service(backend, true).await;
 
     let uo = make_input(
@@ -390,8 +396,12 @@ async fn test_utxo_selection_no_chain_metadata() {
     let server_node_identity = build_node_identity(PeerFeatures::COMMUNICATION_NODE);
 
     // no chain metadata
-    let (mut oms, _shutdown, _, _, _, key_manager) =
-        setup_oms_with_bn_state(OutputManagerSqliteDatabase::new(connection), None, server_node_identity).await;
+    let (mut oms, _shutdown, _, _, _, key_manager) = setup_oms_with_bn_state(
+        OutputManagerSqliteDatabase::new(connection, random_db_cipher()),
+        None,
+        server_node_identity,
+    )
+    .await;
 
     let fee_calc = Fee::new(*create_consensus_constants(0).transaction_weight_params());
     // no utxos - not enough funds
@@ -519,7 +529,7 @@ async fn test_utxo_selection_with_chain_metadata() {
     let server_node_identity = build_node_identity(PeerFeatures::COMMUNICATION_NODE);
     // setup with chain metadata at a height of 6
     let (mut oms, _shutdown, _, _, _, key_manager) = setup_oms_with_bn_state(
-        OutputManagerSqliteDatabase::new(connection),
+        OutputManagerSqliteDatabase::new(connection, random_db_cipher()),
         Some(6),
         server_node_identity,
     )
@@ -672,7 +682,7 @@ async fn test_utxo_selection_with_tx_priority() {
 
     // setup with chain metadata at a height of 6
     let (mut oms, _shutdown, _, _, _, key_manager) = setup_oms_with_bn_state(
-        OutputManagerSqliteDatabase::new(connection),
+        OutputManagerSqliteDatabase::new(connection, random_db_cipher()),
         Some(6),
         server_node_identity,
     )
@@ -733,7 +743,7 @@ async fn test_utxo_selection_with_tx_priority() {
 #[tokio::test]
 async fn send_not_enough_funds() {
     let (connection, _tempdir) = get_temp_sqlite_database_connection();
-    let backend = OutputManagerSqliteDatabase::new(connection.clone());
+    let backend = OutputManagerSqliteDatabase::new(connection.clone(), random_db_cipher());
     let mut oms = setup_output_manager_service(backend, true).await;
 
     let num_outputs = 20;
@@ -772,7 +782,7 @@ async fn send_not_enough_funds() {
 #[tokio::test]
 async fn send_no_change() {
     let (connection, _tempdir) = get_temp_sqlite_database_connection();
-    let backend = OutputManagerSqliteDatabase::new(connection.clone());
+    let backend = OutputManagerSqliteDatabase::new(connection.clone(), random_db_cipher());
     let mut oms = setup_output_manager_service(backend, true).await;
 
     let fee_per_gram = MicroMinotari::from(4);
@@ -851,7 +861,7 @@ async fn send_no_change() {
 #[tokio::test]
 async fn send_not_enough_for_change() {
     let (connection, _tempdir) = get_temp_sqlite_database_connection();
-    let backend = OutputManagerSqliteDatabase::new(connection.clone());
+    let backend = OutputManagerSqliteDatabase::new(connection.clone(), random_db_cipher());
     let mut oms = setup_output_manager_service(backend, true).await;
 
     let fee_per_gram = MicroMinotari::from(4);
@@ -915,7 +925,7 @@ async fn send_not_enough_for_change() {
 #[tokio::test]
 async fn cancel_transaction() {
     let (connection, _tempdir) = get_temp_sqlite_database_connection();
-    let backend = OutputManagerSqliteDatabase::new(connection.clone());
+    let backend = OutputManagerSqliteDatabase::new(connection.clone(), random_db_cipher());
     let mut oms = setup_output_manager_service(backend, true).await;
 
     let num_outputs = 20;
@@ -962,10 +972,87 @@ async fn cancel_transaction() {
     );
 }
 
+#[tokio::test]
+async fn audit_and_repair_orphaned_encumbrances() {
+    let (connection, _tempdir) = get_temp_sqlite_database_connection();
+    let backend = OutputManagerSqliteDatabase::new(connection.clone(), random_db_cipher());
+    let mut oms = setup_output_manager_service(backend, true).await;
+
+    let num_outputs = 5;
+    for _i in 0..num_outputs {
+        let uo = make_input(
+            &mut OsRng.clone(),
+            MicroMinotari::from(1000),
+            &OutputFeatures::default(),
+            &oms.key_manager_handle,
+        )
+        .await;
+        oms.output_manager_handle.add_output(uo, None).await.unwrap();
+    }
+    let tx_id = TxId::new_random();
+    let _stp = oms
+        .output_manager_handle
+        .prepare_transaction_to_send(
+            tx_id,
+            MicroMinotari::from(1000),
+            UtxoSelectionCriteria::default(),
+            OutputFeatures::default(),
+            MicroMinotari::from(4),
+            TransactionMetadata::default(),
+            "".to_string(),
+            script!(Nop),
+            Covenant::default(),
+            MicroMinotari::zero(),
+        )
+        .await
+        .unwrap();
+    oms.output_manager_handle
+        .confirm_pending_transaction(tx_id)
+        .await
+        .unwrap();
+
+    let live_tx_ids = vec![tx_id].into_iter().collect::<HashSet<_>>();
+    let audit = oms
+        .output_manager_handle
+        .audit_encumbrances(live_tx_ids, true)
+        .await
+        .unwrap();
+    assert!(audit.orphaned.is_empty());
+    assert!(audit.repaired_tx_ids.is_empty());
+
+    let audit = oms
+        .output_manager_handle
+        .audit_encumbrances(HashSet::new(), false)
+        .await
+        .unwrap();
+    assert!(!audit.orphaned.is_empty());
+    assert!(audit.orphaned.iter().all(|o| o.tx_id == Some(tx_id)));
+    assert!(audit.repaired_tx_ids.is_empty());
+    assert!(oms.output_manager_handle.get_unspent_outputs().await.unwrap().len() < num_outputs);
+
+    let audit = oms
+        .output_manager_handle
+        .audit_encumbrances(HashSet::new(), true)
+        .await
+        .unwrap();
+    assert_eq!(audit.repaired_tx_ids, vec![tx_id]);
+    assert_eq!(
+        oms.output_manager_handle.get_unspent_outputs().await.unwrap().len(),
+        num_outputs
+    );
+
+    let audit = oms
+        .output_manager_handle
+        .audit_encumbrances(HashSet::new(), false)
+        .await
+        .unwrap();
+    assert!(audit.orphaned.is_empty());
+}
+
 #[tokio::test]
 async fn cancel_transaction_and_reinstate_inbound_tx() {
     let (connection, _tempdir) = get_temp_sqlite_database_connection();
-    let backend = OutputManagerSqliteDatabase::new(connection.clone());
+    let backend = OutputManagerSqliteDatabase::new(connection.clone(), random_db_cipher());
     let mut oms = setup_output_manager_service(backend, true).await;
 
     let value = MicroMinotari::from(5000);
@@ -998,7 +1085,7 @@ async fn cancel_transaction_and_reinstate_inbound_tx() {
 #[tokio::test]
 async fn test_get_balance() {
     let (connection, _tempdir) = get_temp_sqlite_database_connection();
-    let backend = OutputManagerSqliteDatabase::new(connection.clone());
+    let backend = OutputManagerSqliteDatabase::new(connection.clone(), random_db_cipher());
     let mut oms = setup_output_manager_service(backend, true).await;
 
     let balance = oms.output_manager_handle.get_balance().await.unwrap();
@@ -1066,7 +1153,7 @@ async fn test_get_balance() {
 #[tokio::test]
 async fn sending_transaction_persisted_while_offline() {
     let (connection, _tempdir) = get_temp_sqlite_database_connection();
-    let backend = OutputManagerSqliteDatabase::new(connection.clone());
+    let backend = OutputManagerSqliteDatabase::new(connection.clone(), random_db_cipher());
     let mut oms = setup_output_manager_service(backend.clone(), true).await;
 
     let available_balance = 20_000 * uT;
@@ -1160,7 +1247,7 @@ async fn sending_transaction_persisted_while_offline() {
 #[tokio::test]
 async fn coin_split_with_change() {
     let (connection, _tempdir) = get_temp_sqlite_database_connection();
-    let backend = OutputManagerSqliteDatabase::new(connection.clone());
+    let backend = OutputManagerSqliteDatabase::new(connection.clone(), random_db_cipher());
     let mut oms = setup_output_manager_service(backend, true).await;
 
     let val1 = 6_000 * uT;
@@ -1200,7 +1287,7 @@ async fn coin_split_with_change() {
 #[tokio::test]
 async fn coin_split_no_change() {
     let (connection, _tempdir) = get_temp_sqlite_database_connection();
-    let backend = OutputManagerSqliteDatabase::new(connection.clone());
+    let backend = OutputManagerSqliteDatabase::new(connection.clone(), random_db_cipher());
     let mut oms = setup_output_manager_service(backend, true).await;
 
     let fee_per_gram = MicroMinotari::from(5);
@@ -1241,7 +1328,7 @@ async fn coin_split_no_change() {
 #[tokio::test]
 async fn handle_coinbase_with_bulletproofs_rewinding() {
     let (connection, _tempdir) = get_temp_sqlite_database_connection();
-    let backend = OutputManagerSqliteDatabase::new(connection.clone());
+    let backend = OutputManagerSqliteDatabase::new(connection.clone(), random_db_cipher());
     let mut oms = setup_output_manager_service(backend, true).await;
 
     let reward1 = MicroMinotari::from(1000);
@@ -1311,7 +1398,7 @@ async fn handle_coinbase_with_bulletproofs_rewinding() {
 #[allow(clippy::too_many_lines)]
 async fn test_txo_validation() {
     let (connection, _tempdir) = get_temp_sqlite_database_connection();
-    let backend = OutputManagerSqliteDatabase::new(connection.clone());
+    let backend = OutputManagerSqliteDatabase::new(connection.clone(), random_db_cipher());
     let oms_db = backend.clone();
     let mut oms = setup_output_manager_service(backend, true).await;
 
@@ -1419,9 +1506,9 @@ async fn test_txo_validation() {
     oms.base_node_wallet_rpc_mock_state
         .set_query_deleted_response(query_deleted_response.clone());
     oms.output_manager_handle.validate_txos().await.unwrap();
-    let _utxo_query_calls = oms
+    let _utxo_status_query_calls = oms
         .base_node_wallet_rpc_mock_state
-        .wait_pop_utxo_query_calls(1, Duration::from_secs(60))
+        .wait_pop_utxo_status_query_calls(1, Duration::from_secs(60))
         .await
         .unwrap();
     let _query_deleted_calls = oms
@@ -1592,13 +1679,13 @@ async fn test_txo_validation() {
 
     oms.output_manager_handle.validate_txos().await.unwrap();
 
-    let utxo_query_calls = oms
+    let utxo_status_query_calls = oms
         .base_node_wallet_rpc_mock_state
-        .wait_pop_utxo_query_calls(1, Duration::from_secs(60))
+        .wait_pop_utxo_status_query_calls(1, Duration::from_secs(60))
         .await
         .unwrap();
 
-    assert_eq!(utxo_query_calls[0].len(), 4);
+    assert_eq!(utxo_status_query_calls[0].len(), 4);
 
     let query_deleted_calls = oms
         .base_node_wallet_rpc_mock_state
@@ -1638,14 +1725,14 @@ async fn test_txo_validation() {
 
     oms.output_manager_handle.validate_txos().await.unwrap();
 
-    let utxo_query_calls = oms
+    let utxo_status_query_calls = oms
         .base_node_wallet_rpc_mock_state
-        .wait_pop_utxo_query_calls(1, Duration::from_secs(60))
+        .wait_pop_utxo_status_query_calls(1, Duration::from_secs(60))
         .await
         .unwrap();
 
     // The spent transaction is not checked during this second validation
-    assert_eq!(utxo_query_calls[0].len(), 4);
+    assert_eq!(utxo_status_query_calls[0].len(), 4);
 
     let query_deleted_calls = oms
         .base_node_wallet_rpc_mock_state
@@ -1670,20 +1757,20 @@ async fn test_txo_validation() {
     // Trigger another validation and only Output3 should be checked
     oms.output_manager_handle.validate_txos().await.unwrap();
 
-    let utxo_query_calls = oms
+    let utxo_status_query_calls = oms
         .base_node_wallet_rpc_mock_state
-        .wait_pop_utxo_query_calls(1, Duration::from_secs(60))
+        .wait_pop_utxo_status_query_calls(1, Duration::from_secs(60))
         .await
         .unwrap();
-    assert_eq!(utxo_query_calls.len(), 1);
-    assert_eq!(utxo_query_calls[0].len(), 1);
+    assert_eq!(utxo_status_query_calls.len(), 1);
+    assert_eq!(utxo_status_query_calls[0].len(), 1);
     assert_eq!(
-        utxo_query_calls[0][0],
+        utxo_status_query_calls[0][0],
         output3
             .to_transaction_output(&oms.key_manager_handle)
             .await
             .unwrap()
-            .hash()
+            .commitment
             .to_vec()
     );
 
@@ -1766,9 +1853,9 @@ async fn test_txo_validation() {
         .await
         .unwrap();
 
-    let _utxo_query_calls = oms
+    let _utxo_status_query_calls = oms
         .base_node_wallet_rpc_mock_state
-        .wait_pop_utxo_query_calls(1, Duration::from_secs(60))
+        .wait_pop_utxo_status_query_calls(1, Duration::from_secs(60))
         .await
         .unwrap();
 
@@ -1830,9 +1917,9 @@ async fn test_txo_validation() {
 
     let validation_id = oms.output_manager_handle.validate_txos().await.unwrap();
 
-    let _utxo_query_calls = oms
+    let _utxo_status_query_calls = oms
         .base_node_wallet_rpc_mock_state
-        .wait_pop_utxo_query_calls(1, Duration::from_secs(60))
+        .wait_pop_utxo_status_query_calls(1, Duration::from_secs(60))
         .await
         .unwrap();
 
@@ -1877,7 +1964,7 @@ async fn test_txo_validation() {
 #[allow(clippy::too_many_lines)]
 async fn test_txo_revalidation() {
     let (connection, _tempdir) = get_temp_sqlite_database_connection();
-    let backend = OutputManagerSqliteDatabase::new(connection.clone());
+    let backend = OutputManagerSqliteDatabase::new(connection.clone(), random_db_cipher());
 
     let mut oms = setup_output_manager_service(backend, true).await;
 
@@ -1975,9 +2062,9 @@ async fn test_txo_revalidation() {
     oms.base_node_wallet_rpc_mock_state
         .set_query_deleted_response(query_deleted_response.clone());
     oms.output_manager_handle.validate_txos().await.unwrap();
-    let _utxo_query_calls = oms
+    let _utxo_status_query_calls = oms
         .base_node_wallet_rpc_mock_state
-        .wait_pop_utxo_query_calls(1, Duration::from_secs(60))
+        .wait_pop_utxo_status_query_calls(1, Duration::from_secs(60))
         .await
         .unwrap();
     let _query_deleted_calls = oms
@@ -2002,9 +2089,9 @@ async fn test_txo_revalidation() {
     oms.base_node_wallet_rpc_mock_state
         .set_query_deleted_response(query_deleted_response.clone());
     oms.output_manager_handle.revalidate_all_outputs().await.unwrap();
-    let _utxo_query_calls = oms
+    let _utxo_status_query_calls = oms
         .base_node_wallet_rpc_mock_state
-        .wait_pop_utxo_query_calls(1, Duration::from_secs(60))
+        .wait_pop_utxo_status_query_calls(1, Duration::from_secs(60))
         .await
         .unwrap();
     let _query_deleted_calls = oms
@@ -2029,9 +2116,9 @@ async fn test_txo_revalidation() {
     oms.base_node_wallet_rpc_mock_state
         .set_query_deleted_response(query_deleted_response.clone());
     oms.output_manager_handle.revalidate_all_outputs().await.unwrap();
-    let _utxo_query_calls = oms
+    let _utxo_status_query_calls = oms
         .base_node_wallet_rpc_mock_state
-        .wait_pop_utxo_query_calls(1, Duration::from_secs(60))
+        .wait_pop_utxo_status_query_calls(1, Duration::from_secs(60))
         .await
         .unwrap();
     let _query_deleted_calls = oms
@@ -2047,7 +2134,7 @@ async fn test_txo_revalidation() {
 #[tokio::test]
 async fn test_get_status_by_tx_id() {
     let (connection, _tempdir) = get_temp_sqlite_database_connection();
-    let backend = OutputManagerSqliteDatabase::new(connection.clone());
+    let backend = OutputManagerSqliteDatabase::new(connection.clone(), random_db_cipher());
     let mut oms = setup_output_manager_service(backend, true).await;
 
     let uo1 = make_input(
@@ -2091,7 +2178,7 @@ async fn test_get_status_by_tx_id() {
 #[allow(clippy::too_many_lines)]
 async fn scan_for_recovery_test() {
     let (connection, _tempdir) = get_temp_sqlite_database_connection();
-    let backend = OutputManagerSqliteDatabase::new(connection.clone());
+    let backend = OutputManagerSqliteDatabase::new(connection.clone(), random_db_cipher());
     let mut oms = setup_output_manager_service(backend.clone(), true).await;
 
     const NUM_RECOVERABLE: usize = 5;
@@ -2192,7 +2279,7 @@ async fn scan_for_recovery_test() {
 #[tokio::test]
 async fn recovered_output_key_not_in_keychain() {
     let (connection, _tempdir) = get_temp_sqlite_database_connection();
-    let backend = OutputManagerSqliteDatabase::new(connection.clone());
+    let backend = OutputManagerSqliteDatabase::new(connection.clone(), random_db_cipher());
     let mut oms = setup_output_manager_service(backend.clone(), true).await;
     // we need to create a new key manager here as we dont want the input be recoverable from oms key chain
     let key_manager = create_test_core_key_manager_with_memory_db();
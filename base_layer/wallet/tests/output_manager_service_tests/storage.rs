@@ -20,25 +20,29 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use chrono::NaiveDateTime;
 use minotari_wallet::output_manager_service::{
     error::OutputManagerStorageError,
     service::Balance,
     storage::{
         database::{OutputManagerBackend, OutputManagerDatabase},
-        models::DbWalletOutput,
+        models::{BalanceHistoryResolution, BalanceSnapshot, DbWalletOutput},
         sqlite_db::OutputManagerSqliteDatabase,
         OutputSource,
     },
 };
 use rand::{rngs::OsRng, RngCore};
-use tari_common_types::{transaction::TxId, types::FixedHash};
+use tari_common_types::{encryption::SharedCipher, transaction::TxId, types::FixedHash};
 use tari_core::transactions::{
     tari_amount::MicroMinotari,
     test_helpers::create_test_core_key_manager_with_memory_db,
     transaction_components::OutputFeatures,
 };
 
-use crate::support::{data::get_temp_sqlite_database_connection, utils::make_input};
+use crate::support::{
+    data::get_temp_sqlite_database_connection,
+    utils::{make_input, random_db_cipher},
+};
 
 #[allow(clippy::too_many_lines)]
 pub async fn test_db_backend<T: OutputManagerBackend + 'static>(backend: T) {
@@ -333,19 +337,29 @@ pub async fn test_db_backend<T: OutputManagerBackend + 'static>(backend: T) {
 pub async fn test_output_manager_sqlite_db() {
     let (connection, _tempdir) = get_temp_sqlite_database_connection();
 
-    test_db_backend(OutputManagerSqliteDatabase::new(connection)).await;
+    test_db_backend(OutputManagerSqliteDatabase::new(connection, random_db_cipher())).await;
 }
 
 #[tokio::test]
 pub async fn test_output_manager_sqlite_db_encrypted() {
     let (connection, _tempdir) = get_temp_sqlite_database_connection();
-    test_db_backend(OutputManagerSqliteDatabase::new(connection)).await;
+    let cipher = SharedCipher::new(random_db_cipher());
+    let backend = OutputManagerSqliteDatabase::new(connection, cipher.clone());
+    test_db_backend(backend.clone()).await;
+
+    // The outputs can't be read while the cipher is cleared
+    let db = OutputManagerDatabase::new(backend);
+    cipher.clear();
+    assert!(matches!(
+        db.fetch_sorted_unspent_outputs(),
+        Err(OutputManagerStorageError::DatabaseLocked)
+    ));
 }
 
 #[tokio::test]
 pub async fn test_short_term_encumberance() {
     let (connection, _tempdir) = get_temp_sqlite_database_connection();
-    let backend = OutputManagerSqliteDatabase::new(connection);
+    let backend = OutputManagerSqliteDatabase::new(connection, random_db_cipher());
     let db = OutputManagerDatabase::new(backend);
 
     let mut unspent_outputs = Vec::new();
@@ -405,7 +419,7 @@ pub async fn test_short_term_encumberance() {
 #[tokio::test]
 pub async fn test_no_duplicate_outputs() {
     let (connection, _tempdir) = get_temp_sqlite_database_connection();
-    let backend = OutputManagerSqliteDatabase::new(connection);
+    let backend = OutputManagerSqliteDatabase::new(connection, random_db_cipher());
     let db = OutputManagerDatabase::new(backend);
 
     // create an output
@@ -447,7 +461,7 @@ pub async fn test_no_duplicate_outputs() {
 #[tokio::test]
 pub async fn test_mark_as_unmined() {
     let (connection, _tempdir) = get_temp_sqlite_database_connection();
-    let backend = OutputManagerSqliteDatabase::new(connection);
+    let backend = OutputManagerSqliteDatabase::new(connection, random_db_cipher());
     let db = OutputManagerDatabase::new(backend);
 
     // create an output
@@ -476,3 +490,52 @@ pub async fn test_mark_as_unmined() {
     assert!(o.mined_height.is_none());
     assert!(o.mined_in_block.is_none());
 }
+
+#[tokio::test]
+pub async fn test_balance_history() {
+    let (connection, _tempdir) = get_temp_sqlite_database_connection();
+    let backend = OutputManagerSqliteDatabase::new(connection, random_db_cipher());
+    let db = OutputManagerDatabase::new(backend);
+
+    let day = 24 * 60 * 60;
+    let snapshot = |block_height: u64, secs: i64, available: u64| BalanceSnapshot {
+        block_height,
+        recorded_at: NaiveDateTime::from_timestamp_opt(secs, 0).unwrap(),
+        balance: Balance {
+            available_balance: MicroMinotari::from(available),
+            time_locked_balance: Some(MicroMinotari::from(0)),
+            pending_incoming_balance: MicroMinotari::from(0),
+            pending_outgoing_balance: MicroMinotari::from(0),
+        },
+    };
+    let snapshots = vec![
+        snapshot(10, day * 100, 1000),
+        snapshot(11, day * 100 + 60, 2000),
+        snapshot(12, day * 101, 3000),
+    ];
+    for s in &snapshots {
+        db.record_balance_snapshot(s, BalanceHistoryResolution::Daily).unwrap();
+        db.record_balance_snapshot(s, BalanceHistoryResolution::PerBlock)
+            .unwrap();
+    }
+
+    let from = NaiveDateTime::from_timestamp_opt(0, 0).unwrap();
+    let to = NaiveDateTime::from_timestamp_opt(day * 200, 0).unwrap();
+
+    // Only the latest snapshot of each day is kept
+    let daily = db
+        .fetch_balance_history(BalanceHistoryResolution::Daily, from, to)
+        .unwrap();
+    assert_eq!(daily, vec![snapshots[1].clone(), snapshots[2].clone()]);
+
+    let per_block = db
+        .fetch_balance_history(BalanceHistoryResolution::PerBlock, from, to)
+        .unwrap();
+    assert_eq!(per_block, snapshots);
+
+    let to = NaiveDateTime::from_timestamp_opt(day * 100 + 60, 0).unwrap();
+    let per_block = db
+        .fetch_balance_history(BalanceHistoryResolution::PerBlock, from, to)
+        .unwrap();
+    assert_eq!(per_block, snapshots[..2].to_vec());
+}
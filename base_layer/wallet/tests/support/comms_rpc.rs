@@ -52,6 +52,8 @@ use tari_core::{
             GetMempoolFeePerGramStatsResponse,
             QueryDeletedRequest,
             QueryDeletedResponse,
+            QueryUtxoStatusesRequest,
+            QueryUtxoStatusesResponse,
             Signatures as SignaturesProto,
             SyncUtxosByBlockRequest,
             SyncUtxosByBlockResponse,
@@ -61,6 +63,8 @@ use tari_core::{
             TxSubmissionResponse as TxSubmissionResponseProto,
             UtxoQueryRequest,
             UtxoQueryResponses,
+            UtxoStatus,
+            UtxoStatusResponse,
         },
         types::{
             Signature as SignatureProto,
@@ -92,6 +96,7 @@ pub struct BaseNodeWalletRpcMockState {
     transaction_query_calls: Arc<Mutex<Vec<Signature>>>,
     transaction_batch_query_calls: Arc<Mutex<Vec<Vec<Signature>>>>,
     utxo_query_calls: Arc<Mutex<Vec<Vec<Vec<u8>>>>>,
+    utxo_status_query_calls: Arc<Mutex<Vec<Vec<Vec<u8>>>>>,
     query_deleted_calls: Arc<Mutex<Vec<QueryDeletedRequest>>>,
     get_header_by_height_calls: Arc<Mutex<Vec<u64>>>,
     get_height_at_time_calls: Arc<Mutex<Vec<u64>>>,
@@ -122,6 +127,7 @@ impl BaseNodeWalletRpcMockState {
             transaction_query_calls: Arc::new(Mutex::new(Vec::new())),
             transaction_batch_query_calls: Arc::new(Mutex::new(Vec::new())),
             utxo_query_calls: Arc::new(Mutex::new(vec![])),
+            utxo_status_query_calls: Arc::new(Mutex::new(vec![])),
             query_deleted_calls: Arc::new(Mutex::new(vec![])),
             get_header_by_height_calls: Arc::new(Mutex::new(vec![])),
             get_height_at_time_calls: Arc::new(Mutex::new(vec![])),
@@ -412,6 +418,28 @@ impl BaseNodeWalletRpcMockState {
         ))
     }
 
+    pub async fn wait_pop_utxo_status_query_calls(
+        &self,
+        num_calls: usize,
+        timeout: Duration,
+    ) -> Result<Vec<Vec<Vec<u8>>>, String> {
+        let now = Instant::now();
+        let mut count = 0usize;
+        while now.elapsed() < timeout {
+            let mut lock = acquire_lock!(self.utxo_status_query_calls);
+            count = (*lock).len();
+            if (*lock).len() >= num_calls {
+                return Ok((*lock).drain(..num_calls).collect());
+            }
+            drop(lock);
+            sleep(Duration::from_millis(100)).await;
+        }
+        Err(format!(
+            "Did not receive enough calls within the timeout period, received {}, expected {}.",
+            count, num_calls
+        ))
+    }
+
     pub async fn wait_pop_transaction_query_calls(
         &self,
         num_calls: usize,
@@ -712,6 +740,54 @@ impl BaseNodeWalletService for BaseNodeWalletRpcMockService {
         Ok(Response::new(lock.clone()))
     }
 
+    async fn query_utxo_statuses(
+        &self,
+        request: Request<QueryUtxoStatusesRequest>,
+    ) -> Result<Response<QueryUtxoStatusesResponse>, RpcStatus> {
+        let message = request.into_message();
+
+        let mut utxo_status_query_lock = acquire_lock!(self.state.utxo_status_query_calls);
+        (*utxo_status_query_lock).push(message.commitments.clone());
+
+        // The statuses are derived from the utxo query response so that both queries report the same mined outputs
+        let lock = acquire_lock!(self.state.utxo_query_response);
+        let statuses = message
+            .commitments
+            .into_iter()
+            .map(|commitment| {
+                let found = lock.responses.iter().find(|r| {
+                    r.output
+                        .as_ref()
+                        .and_then(|o| o.commitment.as_ref())
+                        .map(|c| c.data == commitment)
+                        .unwrap_or(false)
+                });
+                match found {
+                    Some(r) => UtxoStatusResponse {
+                        commitment,
+                        status: UtxoStatus::Unspent.into(),
+                        output_hash: r.output_hash.clone(),
+                        mmr_position: r.mmr_position,
+                        mined_height: r.mined_height,
+                        mined_in_block: r.mined_in_block.clone(),
+                        mined_timestamp: r.mined_timestamp,
+                    },
+                    None => UtxoStatusResponse {
+                        commitment,
+                        status: UtxoStatus::NotFound.into(),
+                        ..Default::default()
+                    },
+                }
+            })
+            .collect();
+
+        Ok(Response::new(QueryUtxoStatusesResponse {
+            statuses,
+            best_block: lock.best_block.clone(),
+            height_of_longest_chain: lock.height_of_longest_chain,
+        }))
+    }
+
     async fn query_deleted(
         &self,
         request: Request<QueryDeletedRequest>,
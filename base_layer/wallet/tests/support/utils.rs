@@ -20,7 +20,10 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use rand::{CryptoRng, Rng};
+use std::mem::size_of;
+
+use chacha20poly1305::{Key, KeyInit, XChaCha20Poly1305};
+use rand::{rngs::OsRng, CryptoRng, Rng, RngCore};
 use tari_core::{
     covenants::Covenant,
     transactions::{
@@ -40,6 +43,13 @@ use tari_core::{
 use tari_key_manager::key_manager_service::KeyManagerInterface;
 use tari_script::{inputs, script, TariScript};
 
+/// Creates a database cipher from a random key
+pub fn random_db_cipher() -> XChaCha20Poly1305 {
+    let mut key = [0u8; size_of::<Key>()];
+    OsRng.fill_bytes(&mut key);
+    XChaCha20Poly1305::new(Key::from_slice(&key))
+}
+
 pub async fn make_input<R: Rng + CryptoRng>(
     _rng: &mut R,
     val: MicroMinotari,
@@ -214,7 +214,7 @@ async fn setup_transaction_service<P: AsRef<Path>>(
     let cipher = XChaCha20Poly1305::new(key_ga);
 
     let ts_backend = TransactionServiceSqliteDatabase::new(db_connection.clone(), cipher.clone());
-    let oms_backend = OutputManagerSqliteDatabase::new(db_connection.clone());
+    let oms_backend = OutputManagerSqliteDatabase::new(db_connection.clone(), cipher.clone());
     let wallet_identity = WalletIdentity::new(node_identity, Network::LocalNet);
 
     let connection = DbConnection::connect_url(&DbConnectionUrl::MemoryShared(random_string(8))).unwrap();
@@ -368,13 +368,13 @@ async fn setup_transaction_service_no_comms(
     let passphrase = SafePassword::from("My lovely secret passphrase");
     let wallet =
         WalletSqliteDatabase::new(db_connection.clone(), passphrase).expect("Should be able to create wallet database");
-    let cipher = wallet.cipher();
+    let cipher = wallet.cipher().unwrap();
     let wallet_db = WalletDatabase::new(wallet);
 
     let ts_service_db = TransactionServiceSqliteDatabase::new(db_connection.clone(), cipher.clone());
     let ts_db = TransactionDatabase::new(ts_service_db.clone());
     let key_manager = create_test_core_key_manager_with_memory_db();
-    let oms_db = OutputManagerDatabase::new(OutputManagerSqliteDatabase::new(db_connection));
+    let oms_db = OutputManagerDatabase::new(OutputManagerSqliteDatabase::new(db_connection, cipher));
     let wallet_identity = WalletIdentity::new(node_identity.clone(), Network::LocalNet);
     let output_manager_service = OutputManagerService::new(
         OutputManagerServiceConfig::default(),
@@ -429,6 +429,7 @@ async fn setup_transaction_service_no_comms(
         factories,
         shutdown.to_signal(),
         base_node_service_handle,
+        None,
     );
     task::spawn(async move { output_manager_service.start().await.unwrap() });
     task::spawn(async move { ts_service.start().await.unwrap() });
@@ -4943,6 +4944,105 @@ async fn test_resend_on_startup() {
     assert_eq!(reply.tx_id, tx_id);
 }
 
+#[tokio::test]
+async fn test_cancel_all_stale_transactions() {
+    let factories = CryptoFactories::default();
+
+    let bob_node_identity =
+        NodeIdentity::random(&mut OsRng, get_next_memory_address(), PeerFeatures::COMMUNICATION_NODE);
+
+    let (connection, _temp_dir) = make_wallet_database_connection(None);
+
+    let mut alice_ts_interface = setup_transaction_service_no_comms(factories.clone(), connection, None).await;
+
+    let alice_total_available = 2500000 * uT;
+    let uo = make_input(
+        &mut OsRng,
+        alice_total_available,
+        &OutputFeatures::default(),
+        &alice_ts_interface.key_manager_handle,
+    )
+    .await;
+    alice_ts_interface
+        .output_manager_service_handle
+        .add_output(uo, None)
+        .await
+        .unwrap();
+
+    let bob_address = TariAddress::new(bob_node_identity.public_key().clone(), Network::LocalNet);
+    let tx_id = alice_ts_interface
+        .transaction_service_handle
+        .send_transaction(
+            bob_address,
+            100000 * uT,
+            UtxoSelectionCriteria::default(),
+            OutputFeatures::default(),
+            100 * uT,
+            "Testing Message".to_string(),
+        )
+        .await
+        .unwrap();
+
+    for i in 0..=12 {
+        if alice_ts_interface
+            .transaction_service_handle
+            .get_pending_outbound_transactions()
+            .await
+            .unwrap()
+            .contains_key(&tx_id)
+        {
+            break;
+        }
+        sleep(Duration::from_secs(5)).await;
+        if i >= 12 {
+            panic!("Pending outbound transaction should have been added by now");
+        }
+    }
+    assert_eq!(
+        alice_ts_interface
+            .output_manager_service_handle
+            .get_balance()
+            .await
+            .unwrap()
+            .available_balance,
+        MicroMinotari(0)
+    );
+
+    // The transaction is not older than an hour, so nothing is cancelled
+    let summary = alice_ts_interface
+        .transaction_service_handle
+        .cancel_all_stale(Duration::from_secs(3600))
+        .await
+        .unwrap();
+    assert!(summary.cancelled.is_empty());
+    assert!(summary.failed.is_empty());
+
+    sleep(Duration::from_secs(1)).await;
+    let summary = alice_ts_interface
+        .transaction_service_handle
+        .cancel_all_stale(Duration::ZERO)
+        .await
+        .unwrap();
+    assert_eq!(summary.cancelled, vec![tx_id]);
+    assert!(summary.failed.is_empty());
+
+    assert!(alice_ts_interface
+        .transaction_service_handle
+        .get_pending_outbound_transactions()
+        .await
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        alice_ts_interface
+            .output_manager_service_handle
+            .get_balance()
+            .await
+            .unwrap()
+            .available_balance,
+        alice_total_available
+    );
+}
+
 #[tokio::test]
 async fn test_replying_to_cancelled_tx() {
     let factories = CryptoFactories::default();
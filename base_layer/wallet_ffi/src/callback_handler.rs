@@ -21,6 +21,9 @@
 //! `callback_transaction_mined` - This will be called when a Broadcast transaction is detected as mined via a base
 //! node request
 //!
+//! `callback_transaction_unmined` - This will be called when the block a mined transaction was in has been reorged
+//! out of the chain
+//!
 //! `callback_faux_transaction_confirmed` - This will be called when an imported output, recovered output or one-sided
 //!  transaction is detected as mined
 //!
@@ -34,6 +37,12 @@
 //! `callback_base_node_sync_complete` - This is called when a Base Node Sync process is completed or times out. The
 //! request_key is used to identify which request this callback references and a result of true means it was successful
 //! and false that the process timed out and new one will be started
+//!
+//! `callback_scheduled_payment_status` - This is called when a scheduled payment is sent, deferred, fails or completes
+//! its schedule
+//!
+//! `callback_transaction_approval_required` - This is called when an outbound transaction exceeds a configured spending
+//! limit and is held until it is approved or rejected using the provided approval_id
 
 use std::{ops::Deref, sync::Arc};
 
@@ -48,6 +57,10 @@ use minotari_wallet::{
         handle::{OutputManagerEvent, OutputManagerEventReceiver, OutputManagerHandle},
         service::Balance,
     },
+    scheduled_payments_service::{
+        handle::{DeferralReason, ScheduledPaymentEvent, ScheduledPaymentEventReceiver},
+        models::ScheduledPaymentId,
+    },
     transaction_service::{
         handle::{TransactionEvent, TransactionEventReceiver, TransactionSendStatus},
         storage::{
@@ -75,6 +88,7 @@ where TBackend: TransactionBackend + 'static
     callback_transaction_broadcast: unsafe extern "C" fn(*mut CompletedTransaction),
     callback_transaction_mined: unsafe extern "C" fn(*mut CompletedTransaction),
     callback_transaction_mined_unconfirmed: unsafe extern "C" fn(*mut CompletedTransaction, u64),
+    callback_transaction_unmined: unsafe extern "C" fn(*mut CompletedTransaction),
     callback_faux_transaction_confirmed: unsafe extern "C" fn(*mut CompletedTransaction),
     callback_faux_transaction_unconfirmed: unsafe extern "C" fn(*mut CompletedTransaction, u64),
     callback_transaction_send_result: unsafe extern "C" fn(u64, *mut TransactionSendStatus),
@@ -86,6 +100,8 @@ where TBackend: TransactionBackend + 'static
     callback_saf_messages_received: unsafe extern "C" fn(),
     callback_connectivity_status: unsafe extern "C" fn(u64),
     callback_base_node_state: unsafe extern "C" fn(*mut TariBaseNodeState),
+    callback_scheduled_payment_status: unsafe extern "C" fn(u64, u64, u64),
+    callback_transaction_approval_required: unsafe extern "C" fn(u64, u64),
     db: TransactionDatabase<TBackend>,
     base_node_service_event_stream: BaseNodeEventReceiver,
     transaction_service_event_stream: TransactionEventReceiver,
@@ -97,6 +113,7 @@ where TBackend: TransactionBackend + 'static
     balance_cache: Balance,
     connectivity_status_watch: watch::Receiver<OnlineStatus>,
     contacts_liveness_events: broadcast::Receiver<Arc<ContactsLivenessEvent>>,
+    scheduled_payment_events: ScheduledPaymentEventReceiver,
 }
 
 impl<TBackend> CallbackHandler<TBackend>
@@ -114,12 +131,14 @@ where TBackend: TransactionBackend + 'static
         comms_address: TariAddress,
         connectivity_status_watch: watch::Receiver<OnlineStatus>,
         contacts_liveness_events: broadcast::Receiver<Arc<ContactsLivenessEvent>>,
+        scheduled_payment_events: ScheduledPaymentEventReceiver,
         callback_received_transaction: unsafe extern "C" fn(*mut InboundTransaction),
         callback_received_transaction_reply: unsafe extern "C" fn(*mut CompletedTransaction),
         callback_received_finalized_transaction: unsafe extern "C" fn(*mut CompletedTransaction),
         callback_transaction_broadcast: unsafe extern "C" fn(*mut CompletedTransaction),
         callback_transaction_mined: unsafe extern "C" fn(*mut CompletedTransaction),
         callback_transaction_mined_unconfirmed: unsafe extern "C" fn(*mut CompletedTransaction, u64),
+        callback_transaction_unmined: unsafe extern "C" fn(*mut CompletedTransaction),
         callback_faux_transaction_confirmed: unsafe extern "C" fn(*mut CompletedTransaction),
         callback_faux_transaction_unconfirmed: unsafe extern "C" fn(*mut CompletedTransaction, u64),
         callback_transaction_send_result: unsafe extern "C" fn(u64, *mut TransactionSendStatus),
@@ -131,6 +150,8 @@ where TBackend: TransactionBackend + 'static
         callback_saf_messages_received: unsafe extern "C" fn(),
         callback_connectivity_status: unsafe extern "C" fn(u64),
         callback_base_node_state: unsafe extern "C" fn(*mut TariBaseNodeState),
+        callback_scheduled_payment_status: unsafe extern "C" fn(u64, u64, u64),
+        callback_transaction_approval_required: unsafe extern "C" fn(u64, u64),
     ) -> Self {
         info!(
             target: LOG_TARGET,
@@ -156,6 +177,10 @@ where TBackend: TransactionBackend + 'static
             target: LOG_TARGET,
             "TransactionMinedUnconfirmedCallback -> Assigning Fn: {:?}", callback_transaction_mined_unconfirmed
         );
+        info!(
+            target: LOG_TARGET,
+            "TransactionUnminedCallback -> Assigning Fn: {:?}", callback_transaction_unmined
+        );
         info!(
             target: LOG_TARGET,
             "FauxTransactionConfirmedCallback -> Assigning Fn: {:?}", callback_faux_transaction_confirmed
@@ -196,6 +221,14 @@ where TBackend: TransactionBackend + 'static
             target: LOG_TARGET,
             "ConnectivityStatusCallback -> Assigning Fn:  {:?}", callback_connectivity_status
         );
+        info!(
+            target: LOG_TARGET,
+            "ScheduledPaymentStatusCallback -> Assigning Fn:  {:?}", callback_scheduled_payment_status
+        );
+        info!(
+            target: LOG_TARGET,
+            "TransactionApprovalRequiredCallback -> Assigning Fn:  {:?}", callback_transaction_approval_required
+        );
 
         Self {
             callback_received_transaction,
@@ -204,6 +237,7 @@ where TBackend: TransactionBackend + 'static
             callback_transaction_broadcast,
             callback_transaction_mined,
             callback_transaction_mined_unconfirmed,
+            callback_transaction_unmined,
             callback_faux_transaction_confirmed,
             callback_faux_transaction_unconfirmed,
             callback_transaction_send_result,
@@ -215,6 +249,8 @@ where TBackend: TransactionBackend + 'static
             callback_saf_messages_received,
             callback_connectivity_status,
             callback_base_node_state,
+            callback_scheduled_payment_status,
+            callback_transaction_approval_required,
             db,
             base_node_service_event_stream,
             transaction_service_event_stream,
@@ -226,6 +262,7 @@ where TBackend: TransactionBackend + 'static
             balance_cache: Balance::zero(),
             connectivity_status_watch,
             contacts_liveness_events,
+            scheduled_payment_events,
         }
     }
 
@@ -277,6 +314,10 @@ where TBackend: TransactionBackend + 'static
                                     self.receive_transaction_mined_unconfirmed_event(tx_id, num_confirmations);
                                     self.trigger_balance_refresh().await;
                                 },
+                                TransactionEvent::TransactionUnmined(tx_id) => {
+                                    self.receive_transaction_unmined_event(tx_id);
+                                    self.trigger_balance_refresh().await;
+                                },
                                 TransactionEvent::FauxTransactionConfirmed{tx_id, is_valid: _} => {
                                     self.receive_faux_transaction_confirmed_event(tx_id);
                                     self.trigger_balance_refresh().await;
@@ -296,6 +337,9 @@ where TBackend: TransactionBackend + 'static
                                     self.transaction_validation_complete_event(request_key.as_u64(), reason);
                                     self.trigger_balance_refresh().await;
                                 },
+                                TransactionEvent::TransactionApprovalRequired { approval_id, amount, .. } => {
+                                    self.transaction_approval_required_event(approval_id, amount.as_u64());
+                                },
                                 TransactionEvent::TransactionMinedRequestTimedOut(_tx_id) |
                                 TransactionEvent::TransactionImported(_tx_id)|
                                 TransactionEvent::TransactionCompletedImmediately(_tx_id)
@@ -365,6 +409,10 @@ where TBackend: TransactionBackend + 'static
                                 BaseNodeEvent::NewBlockDetected(_hash, _new_block_number) => {
                                     //
                                 },
+
+                                BaseNodeEvent::ReorgDetected(_height) => {
+                                    //
+                                },
                             }
                         },
                         Err(_e) => error!(target: LOG_TARGET, "failed to receive base node state event"),
@@ -390,6 +438,33 @@ where TBackend: TransactionBackend + 'static
                         Err(broadcast::error::RecvError::Closed) => {}
                     }
                 }
+
+                event = self.scheduled_payment_events.recv() => {
+                    match event {
+                        Ok(msg) => {
+                            trace!(target: LOG_TARGET, "Scheduled Payments Service Callback Handler event {:?}", msg);
+                            match (*msg).clone() {
+                                ScheduledPaymentEvent::PaymentSent { payment_id, tx_id } => {
+                                    self.scheduled_payment_status_event(payment_id, tx_id, 0);
+                                },
+                                ScheduledPaymentEvent::PaymentDeferred { payment_id, reason } => {
+                                    let status = match reason {
+                                        DeferralReason::InsufficientBalance => 1,
+                                        DeferralReason::Offline => 2,
+                                    };
+                                    self.scheduled_payment_status_event(payment_id, TxId::from(0u64), status);
+                                },
+                                ScheduledPaymentEvent::PaymentFailed { payment_id, .. } => {
+                                    self.scheduled_payment_status_event(payment_id, TxId::from(0u64), 3);
+                                },
+                                ScheduledPaymentEvent::ScheduleCompleted(payment_id) => {
+                                    self.scheduled_payment_status_event(payment_id, TxId::from(0u64), 4);
+                                },
+                            }
+                        },
+                        Err(_e) => error!(target: LOG_TARGET, "Error reading from Scheduled Payments Service event broadcast channel"),
+                    }
+                },
                  _ = shutdown_signal.wait() => {
                     info!(target: LOG_TARGET, "Transaction Callback Handler shutting down because the shutdown signal was received");
                     break;
@@ -580,6 +655,22 @@ where TBackend: TransactionBackend + 'static
         }
     }
 
+    fn receive_transaction_unmined_event(&mut self, tx_id: TxId) {
+        match self.db.get_completed_transaction(tx_id) {
+            Ok(tx) => {
+                debug!(
+                    target: LOG_TARGET,
+                    "Calling Received Transaction Unmined callback function for TxId: {}", tx_id
+                );
+                let boxing = Box::into_raw(Box::new(tx));
+                unsafe {
+                    (self.callback_transaction_unmined)(boxing);
+                }
+            },
+            Err(e) => error!(target: LOG_TARGET, "Error retrieving Completed Transaction: {:?}", e),
+        }
+    }
+
     fn receive_faux_transaction_confirmed_event(&mut self, tx_id: TxId) {
         match self.db.get_completed_transaction(tx_id) {
             Ok(tx) => {
@@ -635,6 +726,31 @@ where TBackend: TransactionBackend + 'static
         }
     }
 
+    fn scheduled_payment_status_event(&mut self, payment_id: ScheduledPaymentId, tx_id: TxId, status: u64) {
+        debug!(
+            target: LOG_TARGET,
+            "Calling Scheduled Payment Status callback function for scheduled payment {} (TxId: {}) with status = {}",
+            payment_id,
+            tx_id,
+            status,
+        );
+
+        unsafe {
+            (self.callback_scheduled_payment_status)(payment_id, tx_id.as_u64(), status);
+        }
+    }
+
+    fn transaction_approval_required_event(&mut self, approval_id: u64, amount: u64) {
+        debug!(
+            target: LOG_TARGET,
+            "Calling Transaction Approval Required callback function for approval {} of {} µT", approval_id, amount,
+        );
+
+        unsafe {
+            (self.callback_transaction_approval_required)(approval_id, amount);
+        }
+    }
+
     fn saf_messages_received_event(&mut self) {
         debug!(target: LOG_TARGET, "Calling SAF Messages Received callback function");
         unsafe {
@@ -19,9 +19,11 @@ mod test {
             handle::{OutputManagerEvent, OutputManagerHandle},
             service::Balance,
         },
+        scheduled_payments_service::handle::{DeferralReason, ScheduledPaymentEvent},
         test_utils::make_wallet_database_connection,
         transaction_service::{
             handle::{TransactionEvent, TransactionSendStatus},
+            spending_limits::SpendLimit,
             storage::{
                 database::TransactionDatabase,
                 models::{CompletedTransaction, InboundTransaction, OutboundTransaction, TxCancellationReason},
@@ -74,6 +76,7 @@ mod test {
         pub broadcast_tx_callback_called: bool,
         pub mined_tx_callback_called: bool,
         pub mined_tx_unconfirmed_callback_called: u64,
+        pub unmined_tx_callback_called: bool,
         pub faux_tx_confirmed_callback_called: bool,
         pub faux_tx_unconfirmed_callback_called: u64,
         pub direct_send_callback_called: u32,
@@ -92,6 +95,8 @@ mod test {
         pub saf_messages_received: bool,
         pub connectivity_status_callback_called: u64,
         pub base_node_state_changed_callback_invoked: bool,
+        pub scheduled_payment_status_callback_called: u64,
+        pub transaction_approval_required_callback_called: u64,
     }
 
     impl CallbackState {
@@ -103,6 +108,7 @@ mod test {
                 broadcast_tx_callback_called: false,
                 mined_tx_callback_called: false,
                 mined_tx_unconfirmed_callback_called: 0,
+                unmined_tx_callback_called: false,
                 faux_tx_confirmed_callback_called: false,
                 faux_tx_unconfirmed_callback_called: 0,
                 direct_send_callback_called: 0,
@@ -121,6 +127,8 @@ mod test {
                 saf_messages_received: false,
                 connectivity_status_callback_called: 0,
                 base_node_state_changed_callback_invoked: false,
+                scheduled_payment_status_callback_called: 0,
+                transaction_approval_required_callback_called: 0,
             }
         }
     }
@@ -171,6 +179,13 @@ mod test {
         drop(Box::from_raw(tx))
     }
 
+    unsafe extern "C" fn unmined_callback(tx: *mut CompletedTransaction) {
+        let mut lock = CALLBACK_STATE.lock().unwrap();
+        lock.unmined_tx_callback_called = true;
+        drop(lock);
+        drop(Box::from_raw(tx))
+    }
+
     unsafe extern "C" fn faux_confirmed_callback(tx: *mut CompletedTransaction) {
         let mut lock = CALLBACK_STATE.lock().unwrap();
         lock.faux_tx_confirmed_callback_called = true;
@@ -263,6 +278,18 @@ mod test {
         drop(Box::from_raw(state))
     }
 
+    unsafe extern "C" fn scheduled_payment_status_callback(payment_id: u64, tx_id: u64, status: u64) {
+        let mut lock = CALLBACK_STATE.lock().unwrap();
+        lock.scheduled_payment_status_callback_called += payment_id + tx_id + status;
+        drop(lock);
+    }
+
+    unsafe extern "C" fn transaction_approval_required_callback(approval_id: u64, amount: u64) {
+        let mut lock = CALLBACK_STATE.lock().unwrap();
+        lock.transaction_approval_required_callback_called += approval_id + amount;
+        drop(lock);
+    }
+
     #[test]
     // casting casting is okay in tests
     #[allow(clippy::cast_possible_truncation)]
@@ -456,6 +483,7 @@ mod test {
         let (connectivity_tx, connectivity_rx) = watch::channel(OnlineStatus::Offline);
         let (contacts_liveness_events_sender, _) = broadcast::channel(250);
         let contacts_liveness_events = contacts_liveness_events_sender.subscribe();
+        let (scheduled_payment_events_sender, scheduled_payment_events) = broadcast::channel(20);
         let comms_address = TariAddress::new(
             PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng)),
             Network::LocalNet,
@@ -472,12 +500,14 @@ mod test {
             comms_address,
             connectivity_rx,
             contacts_liveness_events,
+            scheduled_payment_events,
             received_tx_callback,
             received_tx_reply_callback,
             received_tx_finalized_callback,
             broadcast_callback,
             mined_callback,
             mined_unconfirmed_callback,
+            unmined_callback,
             faux_confirmed_callback,
             faux_unconfirmed_callback,
             transaction_send_result_callback,
@@ -489,6 +519,8 @@ mod test {
             saf_messages_received_callback,
             connectivity_status_callback,
             base_node_state_changed_callback,
+            scheduled_payment_status_callback,
+            transaction_approval_required_callback,
         );
 
         runtime.spawn(callback_handler.start());
@@ -604,6 +636,10 @@ mod test {
             }))
             .unwrap();
 
+        transaction_event_sender
+            .send(Arc::new(TransactionEvent::TransactionUnmined(2u64.into())))
+            .unwrap();
+
         transaction_event_sender
             .send(Arc::new(TransactionEvent::TransactionSendResult(
                 2u64.into(),
@@ -816,6 +852,30 @@ mod test {
         dht_event_sender
             .send(Arc::new(DhtEvent::StoreAndForwardMessagesReceived))
             .unwrap();
+
+        scheduled_payment_events_sender
+            .send(Arc::new(ScheduledPaymentEvent::PaymentSent {
+                payment_id: 1,
+                tx_id: 10u64.into(),
+            }))
+            .unwrap();
+        scheduled_payment_events_sender
+            .send(Arc::new(ScheduledPaymentEvent::PaymentDeferred {
+                payment_id: 2,
+                reason: DeferralReason::Offline,
+            }))
+            .unwrap();
+        scheduled_payment_events_sender
+            .send(Arc::new(ScheduledPaymentEvent::ScheduleCompleted(1)))
+            .unwrap();
+
+        transaction_event_sender
+            .send(Arc::new(TransactionEvent::TransactionApprovalRequired {
+                approval_id: 3,
+                amount: MicroMinotari::from(5000),
+                exceeded_limit: SpendLimit::PerTransaction(MicroMinotari::from(1000)),
+            }))
+            .unwrap();
         thread::sleep(Duration::from_secs(2));
         connectivity_tx.send(OnlineStatus::Offline).unwrap();
         thread::sleep(Duration::from_secs(2));
@@ -834,6 +894,7 @@ mod test {
         assert!(lock.broadcast_tx_callback_called);
         assert!(lock.mined_tx_callback_called);
         assert_eq!(lock.mined_tx_unconfirmed_callback_called, 22u64);
+        assert!(lock.unmined_tx_callback_called);
         assert!(lock.faux_tx_confirmed_callback_called);
         assert_eq!(lock.faux_tx_unconfirmed_callback_called, 2u64);
         assert_eq!(lock.direct_send_callback_called, 1);
@@ -851,6 +912,9 @@ mod test {
         assert_eq!(lock.callback_balance_updated, 7);
         assert_eq!(lock.callback_transaction_validation_complete, 13);
         assert_eq!(lock.connectivity_status_callback_called, 7);
+        // (1 + 10 + 0) + (2 + 0 + 2) + (1 + 0 + 4)
+        assert_eq!(lock.scheduled_payment_status_callback_called, 20);
+        assert_eq!(lock.transaction_approval_required_callback_called, 5003);
 
         drop(lock);
     }
@@ -23,9 +23,11 @@ use log::*;
 use minotari_wallet::{
     error::{WalletError, WalletStorageError},
     output_manager_service::error::{OutputManagerError, OutputManagerStorageError},
+    scheduled_payments_service::error::ScheduledPaymentsServiceError,
     transaction_service::error::{TransactionServiceError, TransactionStorageError},
+    util::payment_request::PaymentRequestError,
 };
-use tari_common_types::tari_address::TariAddressError;
+use tari_common_types::{tari_address::TariAddressError, tari_uri::TariUriError};
 use tari_comms::multiaddr;
 use tari_comms_dht::store_forward::StoreAndForwardError;
 use tari_contacts::contacts_service::error::{ContactsServiceError, ContactsServiceStorageError};
@@ -231,6 +233,18 @@ impl From<WalletError> for LibWalletError {
                 code: 212,
                 message: format!("{:?}", w),
             },
+            WalletError::TransactionServiceError(TransactionServiceError::TransactionApprovalRejected(_)) => Self {
+                code: 213,
+                message: format!("{:?}", w),
+            },
+            WalletError::TransactionServiceError(TransactionServiceError::TransactionApprovalTimedOut(_)) => Self {
+                code: 214,
+                message: format!("{:?}", w),
+            },
+            WalletError::TransactionServiceError(TransactionServiceError::PendingApprovalNotFound(_)) => Self {
+                code: 215,
+                message: format!("{:?}", w),
+            },
             WalletError::TransactionServiceError(_) => Self {
                 code: 211,
                 message: format!("{:?}", w),
@@ -322,6 +336,31 @@ impl From<WalletError> for LibWalletError {
                 code: 434,
                 message: format!("{:?}", w),
             },
+            WalletError::WalletStorageError(WalletStorageError::DatabaseLocked) => Self {
+                code: 435,
+                message: format!("{:?}", w),
+            },
+            // Scheduled Payments Service errors
+            WalletError::ScheduledPaymentsServiceError(ScheduledPaymentsServiceError::ScheduledPaymentNotFound(_)) => {
+                Self {
+                    code: 440,
+                    message: format!("{:?}", w),
+                }
+            },
+            WalletError::ScheduledPaymentsServiceError(ScheduledPaymentsServiceError::ScheduledPaymentNotActive(_)) => {
+                Self {
+                    code: 441,
+                    message: format!("{:?}", w),
+                }
+            },
+            WalletError::ScheduledPaymentsServiceError(ScheduledPaymentsServiceError::InvalidSchedule(_)) => Self {
+                code: 442,
+                message: format!("{:?}", w),
+            },
+            WalletError::ScheduledPaymentsServiceError(_) => Self {
+                code: 443,
+                message: format!("{:?}", w),
+            },
             // these are general catch errors to try and reduce 999 when we get it with zero additional logging
             WalletError::SetLoggerError(_) => Self {
                 code: 994,
@@ -418,6 +457,44 @@ impl From<TariAddressError> for LibWalletError {
     }
 }
 
+/// This implementation maps the internal TariUriError to a set of LibWalletErrors.
+/// The mapping is explicitly managed here.
+impl From<TariUriError> for LibWalletError {
+    fn from(e: TariUriError) -> Self {
+        error!(target: LOG_TARGET, "{}", format!("{:?}", e));
+        let code = match e {
+            TariUriError::InvalidScheme => 710,
+            TariUriError::UnsupportedAction(_) => 711,
+            TariUriError::InvalidNetwork(_) => 712,
+            TariUriError::MissingParameter(_) => 713,
+            TariUriError::InvalidParameter { .. } => 714,
+            TariUriError::NetworkMismatch { .. } => 715,
+            TariUriError::InvalidEncoding(_) => 716,
+        };
+        Self {
+            code,
+            message: format!("{:?}", e),
+        }
+    }
+}
+
+/// This implementation maps the internal PaymentRequestError to a set of LibWalletErrors.
+/// The mapping is explicitly managed here.
+impl From<PaymentRequestError> for LibWalletError {
+    fn from(e: PaymentRequestError) -> Self {
+        match e {
+            PaymentRequestError::InvalidUri(e) => e.into(),
+            PaymentRequestError::NotAPaymentRequest(_) => {
+                error!(target: LOG_TARGET, "{}", format!("{:?}", e));
+                Self {
+                    code: 717,
+                    message: format!("{:?}", e),
+                }
+            },
+        }
+    }
+}
+
 impl From<multiaddr::Error> for LibWalletError {
     fn from(err: multiaddr::Error) -> Self {
         error!(target: LOG_TARGET, "{}", format!("{:?}", err));
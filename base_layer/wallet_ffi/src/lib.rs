@@ -65,7 +65,7 @@ use std::{
     time::Duration,
 };
 
-use chrono::{DateTime, Local};
+use chrono::{DateTime, Local, NaiveDateTime};
 use error::LibWalletError;
 use ffi_basenode_state::TariBaseNodeState;
 use itertools::Itertools;
@@ -91,11 +91,13 @@ use minotari_wallet::{
         error::OutputManagerError,
         storage::{
             database::{OutputBackendQuery, OutputManagerDatabase, SortDirection},
-            models::DbWalletOutput,
+            models::{BalanceHistoryResolution, BalanceSnapshot, DbWalletOutput},
             OutputStatus,
         },
         UtxoSelectionCriteria,
+        UtxoSelectionOrdering,
     },
+    scheduled_payments_service::models::Recurrence,
     storage::{
         database::WalletDatabase,
         sqlite_db::wallet::WalletSqliteDatabase,
@@ -119,8 +121,10 @@ use num_traits::FromPrimitive;
 use rand::rngs::OsRng;
 use tari_common::configuration::{MultiaddrList, StringList};
 use tari_common_types::{
+    address_utils::{check_emoji_id, EmojiCorrection, EmojiIdCheck},
     emoji::emoji_set,
     tari_address::{TariAddress, TariAddressError},
+    tari_uri::{TariUri, TariUriAction},
     transaction::{TransactionDirection, TransactionStatus, TxId},
     types::{ComAndPubSignature, Commitment, PublicKey, SignatureWithDomain},
 };
@@ -215,14 +219,22 @@ pub type TariCompletedTransaction = minotari_wallet::transaction_service::storag
 pub type TariTransactionSendStatus = minotari_wallet::transaction_service::handle::TransactionSendStatus;
 pub type TariFeePerGramStats = minotari_wallet::transaction_service::handle::FeePerGramStatsResponse;
 pub type TariFeePerGramStat = tari_core::mempool::FeePerGramStat;
+pub type TariBaseNodeCandidate = minotari_wallet::connectivity_service::BaseNodeCandidateInfo;
+
+pub struct TariBaseNodeCandidates(Vec<TariBaseNodeCandidate>);
 pub type TariContactsLivenessData = tari_contacts::contacts_service::handle::ContactsLivenessData;
 pub type TariBalance = minotari_wallet::output_manager_service::service::Balance;
 pub type TariMnemonicLanguage = tari_key_manager::mnemonic::MnemonicLanguage;
 
 pub struct TariCompletedTransactions(Vec<TariCompletedTransaction>);
 
+pub struct TariBalanceHistory(Vec<BalanceSnapshot>);
+
+pub struct TariEmojiIdCorrections(Vec<EmojiCorrection>);
+
 pub type TariPendingInboundTransaction = minotari_wallet::transaction_service::storage::models::InboundTransaction;
 pub type TariPendingOutboundTransaction = minotari_wallet::transaction_service::storage::models::OutboundTransaction;
+pub type TariPaymentRequest = minotari_wallet::util::payment_request::PaymentRequest;
 
 pub struct TariPendingInboundTransactions(Vec<TariPendingInboundTransaction>);
 
@@ -262,6 +274,28 @@ pub enum TariUtxoSort {
     MinedHeightDesc = 3,
 }
 
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[repr(C)]
+pub enum TariUtxoSelectionStrategy {
+    Default = 0,
+    SmallestFirst = 1,
+    LargestFirst = 2,
+    BranchAndBound = 3,
+    PrivacyRandom = 4,
+}
+
+impl From<TariUtxoSelectionStrategy> for UtxoSelectionOrdering {
+    fn from(strategy: TariUtxoSelectionStrategy) -> Self {
+        match strategy {
+            TariUtxoSelectionStrategy::Default => UtxoSelectionOrdering::Default,
+            TariUtxoSelectionStrategy::SmallestFirst => UtxoSelectionOrdering::SmallestFirst,
+            TariUtxoSelectionStrategy::LargestFirst => UtxoSelectionOrdering::LargestFirst,
+            TariUtxoSelectionStrategy::BranchAndBound => UtxoSelectionOrdering::BranchAndBound,
+            TariUtxoSelectionStrategy::PrivacyRandom => UtxoSelectionOrdering::PrivacyRandom,
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 #[repr(C)]
 pub enum TariTypeTag {
@@ -1280,136 +1314,169 @@ pub unsafe extern "C" fn emoji_id_to_tari_address(
     }
 }
 
-/// -------------------------------------------------------------------------------------------- ///
-///
-/// ------------------------------- ComAndPubSignature Signature ---------------------------------------///
-
-/// Creates a TariComAndPubSignature from `u_a`. `u_x`, `u_y`, `ephemeral_pubkey` and `ephemeral_commitment_bytes`
-/// ByteVectors
+/// Checks a char array in emoji format for single-emoji transcription errors
 ///
 /// ## Arguments
-/// `ephemeral_commitment_bytes` - The public ephemeral commitment component as a ByteVector
-/// `ephemeral_pubkey_bytes` - The public ephemeral pubkey component as a ByteVector
-/// `u_a_bytes` - The u_a signature component as a ByteVector
-/// `u_x_bytes` - The u_x signature component as a ByteVector
-/// `u_y_bytes` - The u_y signature component as a ByteVector
+/// `emoji` - The pointer to a char array in emoji format
+/// `network` - an u8 indicating the network
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `TariComAndPubSignature` - Returns a ComAndPubS signature. Note that it will be ptr::null_mut() if any argument is
-/// null or if there was an error with the contents of bytes
+/// `*mut TariEmojiIdCorrections` - Returns a pointer to the single-emoji substitutions that turn the emoji ID into a
+/// valid address, ordered by position. There are no corrections if the emoji ID is already valid, and exactly one if
+/// the mistyped emoji could be pinpointed. Corrections are candidates to show to the user and must not be applied
+/// without confirmation. Note that it returns null if the emoji ID cannot be corrected.
 ///
 /// # Safety
-/// The ```commitment_signature_destroy``` function must be called when finished with a TariComAndPubSignature to
-/// prevent a memory leak
+/// The ```emoji_id_corrections_destroy``` method must be called when finished with a TariEmojiIdCorrections to prevent
+/// a memory leak
+// casting here is network is a u8
+#[allow(clippy::cast_possible_truncation)]
 #[no_mangle]
-pub unsafe extern "C" fn commitment_and_public_signature_create_from_bytes(
-    ephemeral_commitment_bytes: *const ByteVector,
-    ephemeral_pubkey_bytes: *const ByteVector,
-    u_a_bytes: *const ByteVector,
-    u_x_bytes: *const ByteVector,
-    u_y_bytes: *const ByteVector,
+pub unsafe extern "C" fn emoji_id_check(
+    emoji: *const c_char,
+    network: c_uint,
     error_out: *mut c_int,
-) -> *mut TariComAndPubSignature {
+) -> *mut TariEmojiIdCorrections {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
-    if ephemeral_pubkey_bytes.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("ephemeral_pubkey_bytes".to_string())).code;
-        ptr::swap(error_out, &mut error as *mut c_int);
-        return ptr::null_mut();
-    }
-    if ephemeral_commitment_bytes.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("ephemeral_commitment_bytes".to_string())).code;
-        ptr::swap(error_out, &mut error as *mut c_int);
-        return ptr::null_mut();
-    }
-    if u_a_bytes.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("u_a_bytes".to_string())).code;
-        ptr::swap(error_out, &mut error as *mut c_int);
-        return ptr::null_mut();
-    }
-    if u_x_bytes.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("u_x_bytes".to_string())).code;
-        ptr::swap(error_out, &mut error as *mut c_int);
-        return ptr::null_mut();
-    }
-    if u_y_bytes.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("u_y_bytes".to_string())).code;
+    if emoji.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("emoji".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
         return ptr::null_mut();
     }
-
-    let ephemeral_commitment = match Commitment::from_bytes(&(*ephemeral_commitment_bytes).0.clone()) {
-        Ok(ephemeral_commitment) => ephemeral_commitment,
-        Err(e) => {
-            error!(
-                target: LOG_TARGET,
-                "Error creating a ephemeral commitment from bytes: {:?}", e
-            );
-            error = LibWalletError::from(e).code;
-            ptr::swap(error_out, &mut error as *mut c_int);
-            return ptr::null_mut();
-        },
-    };
-    let ephemeral_pubkey = match PublicKey::from_bytes(&(*ephemeral_pubkey_bytes).0.clone()) {
-        Ok(ephemeral_pubkey) => ephemeral_pubkey,
-        Err(e) => {
-            error!(
-                target: LOG_TARGET,
-                "Error creating a ephemeral pubkey from bytes: {:?}", e
-            );
-            error = LibWalletError::from(e).code;
+    let network = match (network as u8).try_into() {
+        Ok(network) => network,
+        Err(_) => {
+            error = LibWalletError::from(InterfaceError::InvalidArgument("network".to_string())).code;
             ptr::swap(error_out, &mut error as *mut c_int);
             return ptr::null_mut();
         },
     };
 
-    let u_a = match TariPrivateKey::from_bytes(&(*u_a_bytes).0.clone()) {
-        Ok(u) => u,
-        Err(e) => {
-            error!(
-                target: LOG_TARGET,
-                "Error creating a Private Key (u_a) from bytes: {:?}", e
-            );
-            error = LibWalletError::from(e).code;
-            ptr::swap(error_out, &mut error as *mut c_int);
-            return ptr::null_mut();
-        },
-    };
-    let u_x = match TariPrivateKey::from_bytes(&(*u_x_bytes).0.clone()) {
-        Ok(u) => u,
-        Err(e) => {
-            error!(
-                target: LOG_TARGET,
-                "Error creating a Private Key (u_x) from bytes: {:?}", e
-            );
-            error = LibWalletError::from(e).code;
+    match CStr::from_ptr(emoji)
+        .to_str()
+        .map_err(|_| TariAddressError::InvalidEmoji)
+        .and_then(|emoji| check_emoji_id(emoji, network))
+    {
+        Ok(EmojiIdCheck::Valid(_)) => Box::into_raw(Box::new(TariEmojiIdCorrections(Vec::new()))),
+        Ok(EmojiIdCheck::Corrections(corrections)) => Box::into_raw(Box::new(TariEmojiIdCorrections(corrections))),
+        Err(_) => {
+            error = LibWalletError::from(InterfaceError::InvalidEmojiId).code;
             ptr::swap(error_out, &mut error as *mut c_int);
-            return ptr::null_mut();
+            ptr::null_mut()
         },
-    };
-    let u_y = match TariPrivateKey::from_bytes(&(*u_y_bytes).0.clone()) {
-        Ok(u) => u,
-        Err(e) => {
-            error!(
-                target: LOG_TARGET,
-                "Error creating a Private Key (u_y) from bytes: {:?}", e
-            );
-            error = LibWalletError::from(e).code;
+    }
+}
+
+/// Gets the number of corrections in a TariEmojiIdCorrections
+///
+/// ## Arguments
+/// `corrections` - The pointer to a TariEmojiIdCorrections
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `c_uint` - Returns the number of corrections, note that it will be zero if corrections is null
+///
+/// # Safety
+/// None
+// casting here is okay as there are at most 33 corrections
+#[allow(clippy::cast_possible_truncation)]
+#[no_mangle]
+pub unsafe extern "C" fn emoji_id_corrections_get_length(
+    corrections: *mut TariEmojiIdCorrections,
+    error_out: *mut c_int,
+) -> c_uint {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    let mut len = 0;
+    if corrections.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("corrections".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+    } else {
+        len = (*corrections).0.len();
+    }
+    len as c_uint
+}
+
+unsafe fn emoji_id_correction_at<'a>(
+    corrections: *mut TariEmojiIdCorrections,
+    position: c_uint,
+    error_out: *mut c_int,
+) -> Option<&'a EmojiCorrection> {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if corrections.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("corrections".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return None;
+    }
+    match (*corrections).0.get(position as usize) {
+        Some(correction) => Some(correction),
+        None => {
+            error = LibWalletError::from(InterfaceError::PositionInvalidError).code;
             ptr::swap(error_out, &mut error as *mut c_int);
-            return ptr::null_mut();
+            None
         },
-    };
+    }
+}
 
-    let sig = ComAndPubSignature::new(ephemeral_commitment, ephemeral_pubkey, u_a, u_x, u_y);
-    Box::into_raw(Box::new(sig))
+/// Gets the zero-based emoji position that the correction at position in a TariEmojiIdCorrections replaces
+///
+/// ## Arguments
+/// `corrections` - The pointer to a TariEmojiIdCorrections
+/// `position` - The integer position
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `c_uint` - Returns the emoji position, note that it will be zero if corrections is null or position is invalid
+///
+/// # Safety
+/// None
+// casting here is okay as an emoji ID has 33 emojis
+#[allow(clippy::cast_possible_truncation)]
+#[no_mangle]
+pub unsafe extern "C" fn emoji_id_corrections_get_emoji_position_at(
+    corrections: *mut TariEmojiIdCorrections,
+    position: c_uint,
+    error_out: *mut c_int,
+) -> c_uint {
+    emoji_id_correction_at(corrections, position, error_out).map_or(0, |correction| correction.position as c_uint)
 }
 
-/// Frees memory for a TariComAndPubSignature
+/// Gets the corrected TariWalletAddress of the correction at position in a TariEmojiIdCorrections
 ///
 /// ## Arguments
-/// `compub_sig` - The pointer to a TariComAndPubSignature
+/// `corrections` - The pointer to a TariEmojiIdCorrections
+/// `position` - The integer position
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `*mut TariWalletAddress` - Returns a pointer to a TariWalletAddress, note that ptr::null_mut() is returned if
+/// corrections is null or position is invalid
+///
+/// # Safety
+/// The ```tari_address_destroy``` method must be called when finished with a TariWalletAddress to prevent a memory leak
+#[no_mangle]
+pub unsafe extern "C" fn emoji_id_corrections_get_address_at(
+    corrections: *mut TariEmojiIdCorrections,
+    position: c_uint,
+    error_out: *mut c_int,
+) -> *mut TariWalletAddress {
+    match emoji_id_correction_at(corrections, position, error_out) {
+        Some(correction) => Box::into_raw(Box::new(correction.address.clone())),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Frees memory for a TariEmojiIdCorrections
+///
+/// ## Arguments
+/// `corrections` - The pointer to a TariEmojiIdCorrections
 ///
 /// ## Returns
 /// `()` - Does not return a value, equivalent to void in C
@@ -1417,357 +1484,449 @@ pub unsafe extern "C" fn commitment_and_public_signature_create_from_bytes(
 /// # Safety
 /// None
 #[no_mangle]
-pub unsafe extern "C" fn commitment_and_public_signature_destroy(compub_sig: *mut TariComAndPubSignature) {
-    if !compub_sig.is_null() {
-        drop(Box::from_raw(compub_sig))
+pub unsafe extern "C" fn emoji_id_corrections_destroy(corrections: *mut TariEmojiIdCorrections) {
+    if !corrections.is_null() {
+        drop(Box::from_raw(corrections))
     }
 }
 
 /// -------------------------------------------------------------------------------------------- ///
+///
+/// ------------------------------- Payment Requests ---------------------------------------------///
 
-/// -------------------------------- Unblinded utxo -------------------------------------------- ///
-
-/// Creates an unblinded output
+/// Creates a payment request URI, e.g. to display as a QR code, that asks for a payment to a TariWalletAddress. The URI
+/// has the form `tari://<network>/transactions/send?tariAddress=<address>&amount=<amount>&message=<message>` and is
+/// shared by all Tari applications.
 ///
 /// ## Arguments
-/// `amount` - The value of the UTXO in MicroMinotari
-/// `spending_key` - The private spending key
-/// `source_address` - The tari address of the source of the transaction
-/// `features` - Options for an output's structure or use
-/// `metadata_signature` - UTXO signature with the script offset private key, k_O
-/// `sender_offset_public_key` - Tari script offset pubkey, K_O
-/// `script_private_key` - Tari script private key, k_S, is used to create the script signature
-/// `covenant` - The covenant that will be executed when spending this output
-/// `message` - The message that the transaction will have
-/// `encrypted_data` - Encrypted data.
-/// `minimum_value_promise` - The minimum value of the commitment that is proven by the range proof
+/// `address` - The pointer to a TariWalletAddress that should receive the payment
+/// `amount` - The requested amount in MicroMinotari, or 0 to leave it to the payer
+/// `message` - The pointer to a char array with a message for the payer, may be null
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// TariUnblindedOutput -  Returns the TransactionID of the generated transaction, note that it will be zero if the
-/// transaction is null
+/// `*mut c_char` - Returns a pointer to a char array. Note that it returns empty if there was an error
 ///
 /// # Safety
-///  The ```tari_unblinded_output_destroy``` function must be called when finished with a TariUnblindedOutput to
-/// prevent a memory leak
+/// The ```string_destroy``` method must be called when finished with a string from rust to prevent a memory leak
 #[no_mangle]
-#[allow(clippy::too_many_lines)]
-pub unsafe extern "C" fn create_tari_unblinded_output(
+pub unsafe extern "C" fn payment_request_create(
+    address: *mut TariWalletAddress,
     amount: c_ulonglong,
-    spending_key: *mut TariPrivateKey,
-    features: *mut TariOutputFeatures,
-    script: *const c_char,
-    input_data: *const c_char,
-    metadata_signature: *mut TariComAndPubSignature,
-    sender_offset_public_key: *mut TariPublicKey,
-    script_private_key: *mut TariPrivateKey,
-    covenant: *mut TariCovenant,
-    encrypted_data: *mut TariEncryptedOpenings,
-    minimum_value_promise: c_ulonglong,
-    script_lock_height: c_ulonglong,
+    message: *const c_char,
     error_out: *mut c_int,
-) -> *mut TariUnblindedOutput {
+) -> *mut c_char {
     let mut error = 0;
+    let mut result = CString::new("").expect("Blank CString will not fail.");
     ptr::swap(error_out, &mut error as *mut c_int);
-
-    if spending_key.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("spending_key".to_string())).code;
+    if address.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("address".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        return ptr::null_mut();
+        return CString::into_raw(result);
     }
 
-    if metadata_signature.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("metadata_signature".to_string())).code;
-        ptr::swap(error_out, &mut error as *mut c_int);
-        return ptr::null_mut();
+    let mut request = TariPaymentRequest::new((*address).clone());
+    if amount > 0 {
+        request = request.with_amount(MicroMinotari::from(amount));
+    }
+    if !message.is_null() {
+        match CStr::from_ptr(message).to_str() {
+            Ok(v) => request = request.with_message(v),
+            _ => {
+                error = LibWalletError::from(InterfaceError::PointerError("message".to_string())).code;
+                ptr::swap(error_out, &mut error as *mut c_int);
+                return CString::into_raw(result);
+            },
+        }
     }
 
-    if sender_offset_public_key.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("sender_offset_public_key".to_string())).code;
-        ptr::swap(error_out, &mut error as *mut c_int);
-        return ptr::null_mut();
+    match CString::new(request.to_uri()) {
+        Ok(v) => result = v,
+        _ => {
+            error = LibWalletError::from(InterfaceError::PointerError("message".to_string())).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+        },
     }
+    CString::into_raw(result)
+}
 
-    if script_private_key.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("script_private_key".to_string())).code;
+/// Parses a payment request URI, e.g. scanned from a QR code
+///
+/// ## Arguments
+/// `uri` - The pointer to a char array containing the payment request URI
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `*mut TariPaymentRequest` - Returns a pointer to a TariPaymentRequest. Note that it returns ptr::null_mut() if uri
+/// is null or is not a valid payment request
+///
+/// # Safety
+/// The ```payment_request_destroy``` method must be called when finished with a TariPaymentRequest to prevent a memory
+/// leak
+#[no_mangle]
+pub unsafe extern "C" fn payment_request_parse(uri: *const c_char, error_out: *mut c_int) -> *mut TariPaymentRequest {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if uri.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("uri".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
         return ptr::null_mut();
     }
 
-    if features.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("features".to_string())).code;
-        ptr::swap(error_out, &mut error as *mut c_int);
-        return ptr::null_mut();
-    }
-    let script_str;
-    if script.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("script".to_string())).code;
-        ptr::swap(error_out, &mut error as *mut c_int);
-        return ptr::null_mut();
-    } else {
-        match CStr::from_ptr(script).to_str() {
-            Ok(v) => {
-                script_str = v.to_owned();
-            },
-            _ => {
-                error = LibWalletError::from(InterfaceError::PointerError("key".to_string())).code;
-                ptr::swap(error_out, &mut error as *mut c_int);
-                return ptr::null_mut();
-            },
-        }
-    }
-    let script = match TariScript::from_hex(&script_str) {
+    let uri = match CStr::from_ptr(uri).to_str() {
         Ok(v) => v,
-        Err(e) => {
-            error = LibWalletError::from(e).code;
+        _ => {
+            error = LibWalletError::from(InterfaceError::PointerError("uri".to_string())).code;
             ptr::swap(error_out, &mut error as *mut c_int);
             return ptr::null_mut();
         },
     };
-    let input_str;
-    if input_data.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("input_data".to_string())).code;
-        ptr::swap(error_out, &mut error as *mut c_int);
-        return ptr::null_mut();
-    } else {
-        match CStr::from_ptr(input_data).to_str() {
-            Ok(v) => {
-                input_str = v.to_owned();
-            },
-            _ => {
-                error = LibWalletError::from(InterfaceError::PointerError("key".to_string())).code;
-                ptr::swap(error_out, &mut error as *mut c_int);
-                return ptr::null_mut();
-            },
-        }
-    }
-    let input_data = match tari_script::ExecutionStack::from_hex(&input_str) {
-        Ok(v) => v,
+
+    match TariPaymentRequest::from_str(uri) {
+        Ok(request) => Box::into_raw(Box::new(request)),
         Err(e) => {
             error = LibWalletError::from(e).code;
             ptr::swap(error_out, &mut error as *mut c_int);
-            return ptr::null_mut();
+            ptr::null_mut()
         },
-    };
-
-    let covenant = if covenant.is_null() {
-        TariCovenant::default()
-    } else {
-        (*covenant).clone()
-    };
-
-    let encrypted_data = if encrypted_data.is_null() {
-        TariEncryptedOpenings::default()
-    } else {
-        *encrypted_data
-    };
-
-    let unblinded_output = UnblindedOutput::new_current_version(
-        amount.into(),
-        (*spending_key).clone(),
-        (*features).clone(),
-        script,
-        input_data,
-        (*script_private_key).clone(),
-        (*sender_offset_public_key).clone(),
-        (*metadata_signature).clone(),
-        script_lock_height,
-        covenant,
-        encrypted_data,
-        minimum_value_promise.into(),
-    );
-
-    Box::into_raw(Box::new(unblinded_output))
+    }
 }
 
-/// Frees memory for a TariUnblindedOutput
+/// Gets the TariWalletAddress that a payment is requested for
 ///
 /// ## Arguments
-/// `output` - The pointer to a TariUnblindedOutput
+/// `request` - The pointer to a TariPaymentRequest
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
 ///
 /// ## Returns
-/// `()` - Does not return a value, equivalent to void in C
+/// `*mut TariWalletAddress` - Returns a pointer to a TariWalletAddress. Note that it returns ptr::null_mut() if
+/// request is null
 ///
 /// # Safety
-/// None
+/// The ```tari_address_destroy``` method must be called when finished with a TariWalletAddress to prevent a memory leak
 #[no_mangle]
-pub unsafe extern "C" fn tari_unblinded_output_destroy(output: *mut TariUnblindedOutput) {
-    if !output.is_null() {
-        drop(Box::from_raw(output))
+pub unsafe extern "C" fn payment_request_get_address(
+    request: *mut TariPaymentRequest,
+    error_out: *mut c_int,
+) -> *mut TariWalletAddress {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if request.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("request".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return ptr::null_mut();
     }
+    Box::into_raw(Box::new((*request).address.clone()))
 }
 
-/// returns the TariUnblindedOutput as a json string
+/// Gets the amount of a TariPaymentRequest
 ///
 /// ## Arguments
-/// `output` - The pointer to a TariUnblindedOutput
+/// `request` - The pointer to a TariPaymentRequest
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
 ///
 /// ## Returns
-/// `*mut c_char` - Returns a pointer to a char array. Note that it returns an empty char array if
-/// TariUnblindedOutput is null or the position is invalid
+/// `c_ulonglong` - Returns the requested amount in MicroMinotari, or 0 if the request does not specify an amount
 ///
 /// # Safety
-///  The ```tari_unblinded_output_destroy``` function must be called when finished with a TariUnblindedOutput to
-/// prevent a memory leak
+/// None
 #[no_mangle]
-pub unsafe extern "C" fn tari_unblinded_output_to_json(
-    output: *mut TariUnblindedOutput,
+pub unsafe extern "C" fn payment_request_get_amount(
+    request: *mut TariPaymentRequest,
     error_out: *mut c_int,
-) -> *mut c_char {
+) -> c_ulonglong {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
-    let mut hex_bytes = CString::new("").expect("Blank CString will not fail.");
-    if output.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("output".to_string())).code;
+    if request.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("request".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-    } else {
-        match serde_json::to_string(&*output) {
-            Ok(json_string) => match CString::new(json_string) {
-                Ok(v) => hex_bytes = v,
-                _ => {
-                    error = LibWalletError::from(InterfaceError::PointerError("output".to_string())).code;
-                    ptr::swap(error_out, &mut error as *mut c_int);
-                },
-            },
-            Err(_) => {
-                error = LibWalletError::from(HexError::HexConversionError {}).code;
-                ptr::swap(error_out, &mut error as *mut c_int);
-            },
-        }
+        return 0;
     }
-    CString::into_raw(hex_bytes)
+    (*request).amount.map(|amount| amount.as_u64()).unwrap_or_default()
 }
 
-/// Creates a TariUnblindedOutput from a char array
+/// Gets the message of a TariPaymentRequest
 ///
 /// ## Arguments
-/// `output_json` - The pointer to a char array which is json of the TariUnblindedOutput
+/// `request` - The pointer to a TariPaymentRequest
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `*mut TariUnblindedOutput` - Returns a pointer to a TariUnblindedOutput. Note that it returns ptr::null_mut()
-/// if key is null or if there was an error creating the TariUnblindedOutput from key
+/// `*mut c_char` - Returns a pointer to a char array. Note that it returns empty if the request does not contain a
+/// message or there was an error
 ///
 /// # Safety
-/// The ```tari_unblinded_output_destroy``` function must be called when finished with a TariUnblindedOutput to
-// /// prevent a memory leak
+/// The ```string_destroy``` method must be called when finished with a string from rust to prevent a memory leak
 #[no_mangle]
-pub unsafe extern "C" fn create_tari_unblinded_output_from_json(
-    output_json: *const c_char,
+pub unsafe extern "C" fn payment_request_get_message(
+    request: *mut TariPaymentRequest,
     error_out: *mut c_int,
-) -> *mut TariUnblindedOutput {
+) -> *mut c_char {
     let mut error = 0;
+    let mut result = CString::new("").expect("Blank CString will not fail.");
     ptr::swap(error_out, &mut error as *mut c_int);
-    let output_json_str;
-    if output_json.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("output_json".to_string())).code;
+    if request.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("request".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        return ptr::null_mut();
-    } else {
-        match CStr::from_ptr(output_json).to_str() {
-            Ok(v) => {
-                output_json_str = v.to_owned();
-            },
+        return CString::into_raw(result);
+    }
+    if let Some(message) = (*request).message.as_ref() {
+        match CString::new(message.as_str()) {
+            Ok(v) => result = v,
             _ => {
-                error = LibWalletError::from(InterfaceError::PointerError("output_json".to_string())).code;
+                error = LibWalletError::from(InterfaceError::PointerError("message".to_string())).code;
                 ptr::swap(error_out, &mut error as *mut c_int);
-                return ptr::null_mut();
             },
-        };
+        }
     }
-    let output: Result<TariUnblindedOutput, _> = serde_json::from_str(&output_json_str);
+    CString::into_raw(result)
+}
 
-    match output {
-        Ok(output) => Box::into_raw(Box::new(output)),
-        Err(e) => {
-            error!(target: LOG_TARGET, "Error creating a output from json: {:?}", e);
+/// Frees memory for a TariPaymentRequest
+///
+/// ## Arguments
+/// `request` - The pointer to a TariPaymentRequest
+///
+/// ## Returns
+/// `()` - Does not return a value, equivalent to void in C
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn payment_request_destroy(request: *mut TariPaymentRequest) {
+    if !request.is_null() {
+        drop(Box::from_raw(request))
+    }
+}
 
-            error = LibWalletError::from(HexError::HexConversionError {}).code;
-            ptr::swap(error_out, &mut error as *mut c_int);
-            ptr::null_mut()
-        },
+/// Reads a Tari URI argument passed over the FFI boundary
+unsafe fn ffi_tari_uri(uri: *const c_char) -> Result<TariUri, LibWalletError> {
+    if uri.is_null() {
+        return Err(InterfaceError::NullError("uri".to_string()).into());
     }
+    let uri = CStr::from_ptr(uri)
+        .to_str()
+        .map_err(|_| LibWalletError::from(InterfaceError::PointerError("uri".to_string())))?;
+    Ok(TariUri::from_str(uri)?)
 }
 
 /// -------------------------------------------------------------------------------------------- ///
+///
+/// ------------------------------- Tari URIs ----------------------------------------------------///
 
-/// ----------------------------------- TariUnblindedOutputs ------------------------------------///
-
-/// Gets the length of TariUnblindedOutputs
+/// Gets the action that a `tari://` URI, e.g. scanned from a QR code, asks the wallet to perform so that the caller can
+/// hand it to the matching function: `payment_request_parse` for payments, `tari_uri_get_contact` for contacts and
+/// `wallet_add_base_node_peer_from_uri` for base nodes
 ///
 /// ## Arguments
-/// `outputs` - The pointer to a TariUnblindedOutputs
+/// `uri` - The pointer to a char array containing the URI
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `c_uint` - Returns number of elements in , zero if outputs is null
+/// `c_int` - Returns 0 for a payment request, 1 to add a contact, 2 to add a base node and 3 to call a template. Note
+/// that it returns -1 if the URI is null or invalid
 ///
 /// # Safety
 /// None
-// casting here is okay the length of the array wont go over u32
-#[allow(clippy::cast_possible_truncation)]
 #[no_mangle]
-pub unsafe extern "C" fn unblinded_outputs_get_length(
-    outputs: *mut TariUnblindedOutputs,
-    error_out: *mut c_int,
-) -> c_uint {
+pub unsafe extern "C" fn tari_uri_get_action(uri: *const c_char, error_out: *mut c_int) -> c_int {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
-    let mut len = 0;
-    if outputs.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("outputs".to_string())).code;
-        ptr::swap(error_out, &mut error as *mut c_int);
-    } else {
-        len = (*outputs).0.len();
+    match ffi_tari_uri(uri) {
+        Ok(uri) => match uri.action {
+            TariUriAction::Send { .. } => 0,
+            TariUriAction::AddContact { .. } => 1,
+            TariUriAction::AddBaseNode { .. } => 2,
+            TariUriAction::CallTemplate { .. } => 3,
+        },
+        Err(e) => {
+            error = e.code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            -1
+        },
     }
-    len as c_uint
 }
 
-/// Gets a TariUnblindedOutput from TariUnblindedOutputs at position
+/// Creates a TariContact from a `tari://<network>/contacts/add` URI
 ///
 /// ## Arguments
-/// `outputs` - The pointer to a TariUnblindedOutputs
-/// `position` - The integer position
+/// `uri` - The pointer to a char array containing the URI
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `*mut TariUnblindedOutput` - Returns a TariUnblindedOutput, note that it returns ptr::null_mut() if
-/// TariUnblindedOutputs is null or position is invalid
+/// `*mut TariContact` - Returns a pointer to a TariContact. Note that it returns ptr::null_mut() if the URI is null,
+/// invalid or does not add a contact
 ///
 /// # Safety
 /// The ```contact_destroy``` method must be called when finished with a TariContact to prevent a memory leak
-// converting between here is fine as its used to clamp the the array to length
-#[allow(clippy::cast_possible_wrap)]
 #[no_mangle]
-pub unsafe extern "C" fn unblinded_outputs_get_at(
-    outputs: *mut TariUnblindedOutputs,
-    position: c_uint,
-    error_out: *mut c_int,
-) -> *mut TariUnblindedOutput {
-    let mut error = 0;
-    ptr::swap(error_out, &mut error as *mut c_int);
-    if outputs.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("outputs".to_string())).code;
-        ptr::swap(error_out, &mut error as *mut c_int);
-        return ptr::null_mut();
+pub unsafe extern "C" fn tari_uri_get_contact(uri: *const c_char, error_out: *mut c_int) -> *mut TariContact {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    match ffi_tari_uri(uri) {
+        Ok(TariUri {
+            action: TariUriAction::AddContact { address, alias },
+            ..
+        }) => Box::into_raw(Box::new(Contact::new(
+            alias.unwrap_or_default(),
+            address,
+            None,
+            None,
+            false,
+        ))),
+        Ok(_) => {
+            error = LibWalletError::from(InterfaceError::InvalidArgument(
+                "uri does not add a contact".to_string(),
+            ))
+            .code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            ptr::null_mut()
+        },
+        Err(e) => {
+            error = e.code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            ptr::null_mut()
+        },
     }
-    let len = unblinded_outputs_get_length(outputs, error_out) as c_int - 1;
-    if len < 0 || position > len as c_uint {
-        error = LibWalletError::from(InterfaceError::PositionInvalidError).code;
+}
+
+/// -------------------------------------------------------------------------------------------- ///
+///
+/// ------------------------------- ComAndPubSignature Signature ---------------------------------------///
+
+/// Creates a TariComAndPubSignature from `u_a`. `u_x`, `u_y`, `ephemeral_pubkey` and `ephemeral_commitment_bytes`
+/// ByteVectors
+///
+/// ## Arguments
+/// `ephemeral_commitment_bytes` - The public ephemeral commitment component as a ByteVector
+/// `ephemeral_pubkey_bytes` - The public ephemeral pubkey component as a ByteVector
+/// `u_a_bytes` - The u_a signature component as a ByteVector
+/// `u_x_bytes` - The u_x signature component as a ByteVector
+/// `u_y_bytes` - The u_y signature component as a ByteVector
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `TariComAndPubSignature` - Returns a ComAndPubS signature. Note that it will be ptr::null_mut() if any argument is
+/// null or if there was an error with the contents of bytes
+///
+/// # Safety
+/// The ```commitment_signature_destroy``` function must be called when finished with a TariComAndPubSignature to
+/// prevent a memory leak
+#[no_mangle]
+pub unsafe extern "C" fn commitment_and_public_signature_create_from_bytes(
+    ephemeral_commitment_bytes: *const ByteVector,
+    ephemeral_pubkey_bytes: *const ByteVector,
+    u_a_bytes: *const ByteVector,
+    u_x_bytes: *const ByteVector,
+    u_y_bytes: *const ByteVector,
+    error_out: *mut c_int,
+) -> *mut TariComAndPubSignature {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if ephemeral_pubkey_bytes.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("ephemeral_pubkey_bytes".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
         return ptr::null_mut();
     }
-    Box::into_raw(Box::new((*outputs).0[position as usize].clone()))
+    if ephemeral_commitment_bytes.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("ephemeral_commitment_bytes".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return ptr::null_mut();
+    }
+    if u_a_bytes.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("u_a_bytes".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return ptr::null_mut();
+    }
+    if u_x_bytes.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("u_x_bytes".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return ptr::null_mut();
+    }
+    if u_y_bytes.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("u_y_bytes".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return ptr::null_mut();
+    }
+
+    let ephemeral_commitment = match Commitment::from_bytes(&(*ephemeral_commitment_bytes).0.clone()) {
+        Ok(ephemeral_commitment) => ephemeral_commitment,
+        Err(e) => {
+            error!(
+                target: LOG_TARGET,
+                "Error creating a ephemeral commitment from bytes: {:?}", e
+            );
+            error = LibWalletError::from(e).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            return ptr::null_mut();
+        },
+    };
+    let ephemeral_pubkey = match PublicKey::from_bytes(&(*ephemeral_pubkey_bytes).0.clone()) {
+        Ok(ephemeral_pubkey) => ephemeral_pubkey,
+        Err(e) => {
+            error!(
+                target: LOG_TARGET,
+                "Error creating a ephemeral pubkey from bytes: {:?}", e
+            );
+            error = LibWalletError::from(e).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            return ptr::null_mut();
+        },
+    };
+
+    let u_a = match TariPrivateKey::from_bytes(&(*u_a_bytes).0.clone()) {
+        Ok(u) => u,
+        Err(e) => {
+            error!(
+                target: LOG_TARGET,
+                "Error creating a Private Key (u_a) from bytes: {:?}", e
+            );
+            error = LibWalletError::from(e).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            return ptr::null_mut();
+        },
+    };
+    let u_x = match TariPrivateKey::from_bytes(&(*u_x_bytes).0.clone()) {
+        Ok(u) => u,
+        Err(e) => {
+            error!(
+                target: LOG_TARGET,
+                "Error creating a Private Key (u_x) from bytes: {:?}", e
+            );
+            error = LibWalletError::from(e).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            return ptr::null_mut();
+        },
+    };
+    let u_y = match TariPrivateKey::from_bytes(&(*u_y_bytes).0.clone()) {
+        Ok(u) => u,
+        Err(e) => {
+            error!(
+                target: LOG_TARGET,
+                "Error creating a Private Key (u_y) from bytes: {:?}", e
+            );
+            error = LibWalletError::from(e).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            return ptr::null_mut();
+        },
+    };
+
+    let sig = ComAndPubSignature::new(ephemeral_commitment, ephemeral_pubkey, u_a, u_x, u_y);
+    Box::into_raw(Box::new(sig))
 }
 
-/// Frees memory for a TariUnblindedOutputs
+/// Frees memory for a TariComAndPubSignature
 ///
 /// ## Arguments
-/// `outputs` - The pointer to a TariUnblindedOutputs
+/// `compub_sig` - The pointer to a TariComAndPubSignature
 ///
 /// ## Returns
 /// `()` - Does not return a value, equivalent to void in C
@@ -1775,17 +1934,19 @@ pub unsafe extern "C" fn unblinded_outputs_get_at(
 /// # Safety
 /// None
 #[no_mangle]
-pub unsafe extern "C" fn unblinded_outputs_destroy(outputs: *mut TariUnblindedOutputs) {
-    if !outputs.is_null() {
-        drop(Box::from_raw(outputs))
+pub unsafe extern "C" fn commitment_and_public_signature_destroy(compub_sig: *mut TariComAndPubSignature) {
+    if !compub_sig.is_null() {
+        drop(Box::from_raw(compub_sig))
     }
 }
 
-/// Import an external UTXO into the wallet as a non-rewindable (i.e. non-recoverable) output. This will add a spendable
-/// UTXO (as EncumberedToBeReceived) and create a faux completed transaction to record the event.
+/// -------------------------------------------------------------------------------------------- ///
+
+/// -------------------------------- Unblinded utxo -------------------------------------------- ///
+
+/// Creates an unblinded output
 ///
 /// ## Arguments
-/// `wallet` - The TariWallet pointer
 /// `amount` - The value of the UTXO in MicroMinotari
 /// `spending_key` - The private spending key
 /// `source_address` - The tari address of the source of the transaction
@@ -1801,173 +1962,146 @@ pub unsafe extern "C" fn unblinded_outputs_destroy(outputs: *mut TariUnblindedOu
 /// as an out parameter.
 ///
 /// ## Returns
-/// `c_ulonglong` -  Returns the TransactionID of the generated transaction, note that it will be zero if the
+/// TariUnblindedOutput -  Returns the TransactionID of the generated transaction, note that it will be zero if the
 /// transaction is null
 ///
 /// # Safety
-/// None
+///  The ```tari_unblinded_output_destroy``` function must be called when finished with a TariUnblindedOutput to
+/// prevent a memory leak
 #[no_mangle]
-pub unsafe extern "C" fn wallet_import_external_utxo_as_non_rewindable(
-    wallet: *mut TariWallet,
-    output: *mut TariUnblindedOutput,
-    source_address: *mut TariWalletAddress,
-    message: *const c_char,
+#[allow(clippy::too_many_lines)]
+pub unsafe extern "C" fn create_tari_unblinded_output(
+    amount: c_ulonglong,
+    spending_key: *mut TariPrivateKey,
+    features: *mut TariOutputFeatures,
+    script: *const c_char,
+    input_data: *const c_char,
+    metadata_signature: *mut TariComAndPubSignature,
+    sender_offset_public_key: *mut TariPublicKey,
+    script_private_key: *mut TariPrivateKey,
+    covenant: *mut TariCovenant,
+    encrypted_data: *mut TariEncryptedOpenings,
+    minimum_value_promise: c_ulonglong,
+    script_lock_height: c_ulonglong,
     error_out: *mut c_int,
-) -> c_ulonglong {
+) -> *mut TariUnblindedOutput {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
-    if wallet.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
+
+    if spending_key.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("spending_key".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        return 0;
+        return ptr::null_mut();
     }
-    let source_address = if source_address.is_null() {
-        TariWalletAddress::default()
-    } else {
-        (*source_address).clone()
-    };
-    let message_string;
-    if message.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("message".to_string())).code;
+
+    if metadata_signature.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("metadata_signature".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        message_string = CString::new("Imported UTXO")
-            .expect("CString will not fail")
-            .to_str()
-            .expect("CString.toStr() will not fail")
-            .to_owned();
+        return ptr::null_mut();
+    }
+
+    if sender_offset_public_key.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("sender_offset_public_key".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return ptr::null_mut();
+    }
+
+    if script_private_key.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("script_private_key".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return ptr::null_mut();
+    }
+
+    if features.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("features".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return ptr::null_mut();
+    }
+    let script_str;
+    if script.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("script".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return ptr::null_mut();
     } else {
-        match CStr::from_ptr(message).to_str() {
+        match CStr::from_ptr(script).to_str() {
             Ok(v) => {
-                message_string = v.to_owned();
+                script_str = v.to_owned();
             },
             _ => {
-                error = LibWalletError::from(InterfaceError::PointerError("message".to_string())).code;
+                error = LibWalletError::from(InterfaceError::PointerError("key".to_string())).code;
                 ptr::swap(error_out, &mut error as *mut c_int);
-                message_string = CString::new("Imported UTXO")
-                    .expect("CString will not fail")
-                    .to_str()
-                    .expect("CString.to_str() will not fail")
-                    .to_owned();
+                return ptr::null_mut();
             },
         }
-    };
-    match (*wallet)
-        .runtime
-        .block_on((*wallet).wallet.import_unblinded_output_as_non_rewindable(
-            (*output).clone(),
-            source_address,
-            message_string,
-        )) {
-        Ok(tx_id) => tx_id.as_u64(),
+    }
+    let script = match TariScript::from_hex(&script_str) {
+        Ok(v) => v,
         Err(e) => {
             error = LibWalletError::from(e).code;
             ptr::swap(error_out, &mut error as *mut c_int);
-            0
+            return ptr::null_mut();
         },
-    }
-}
-
-/// Get the TariUnblindedOutputs from a TariWallet
-///
-/// ## Arguments
-/// `wallet` - The TariWallet pointer
-/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
-/// as an out parameter.
-///
-/// ## Returns
-/// `*mut TariUnblindedOutputs` - returns the unspent unblinded outputs, note that it returns ptr::null_mut() if
-/// wallet is null
-///
-/// # Safety
-/// The ```unblinded_outputs_destroy``` method must be called when finished with a TariUnblindedOutput to prevent a
-/// memory leak
-#[no_mangle]
-pub unsafe extern "C" fn wallet_get_unspent_outputs(
-    wallet: *mut TariWallet,
-    error_out: *mut c_int,
-) -> *mut TariUnblindedOutputs {
-    let mut error = 0;
-    ptr::swap(error_out, &mut error as *mut c_int);
-    let mut outputs = Vec::new();
-    if wallet.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
+    };
+    let input_str;
+    if input_data.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("input_data".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
         return ptr::null_mut();
+    } else {
+        match CStr::from_ptr(input_data).to_str() {
+            Ok(v) => {
+                input_str = v.to_owned();
+            },
+            _ => {
+                error = LibWalletError::from(InterfaceError::PointerError("key".to_string())).code;
+                ptr::swap(error_out, &mut error as *mut c_int);
+                return ptr::null_mut();
+            },
+        }
     }
-
-    let received_outputs = (*wallet)
-        .runtime
-        .block_on((*wallet).wallet.output_manager_service.get_unspent_outputs());
-    match received_outputs {
-        Ok(rec_outputs) => {
-            for output in rec_outputs {
-                let unblinded = (*wallet).runtime.block_on(UnblindedOutput::from_wallet_output(
-                    output.wallet_output,
-                    &(*wallet).wallet.key_manager_service,
-                ));
-                match unblinded {
-                    Ok(uo) => {
-                        outputs.push(uo);
-                    },
-                    Err(e) => {
-                        error = LibWalletError::from(WalletError::TransactionError(e)).code;
-                        ptr::swap(error_out, &mut error as *mut c_int);
-                        return ptr::null_mut();
-                    },
-                }
-            }
-            Box::into_raw(Box::new(TariUnblindedOutputs(outputs)))
-        },
+    let input_data = match tari_script::ExecutionStack::from_hex(&input_str) {
+        Ok(v) => v,
         Err(e) => {
-            error = LibWalletError::from(WalletError::OutputManagerError(e)).code;
+            error = LibWalletError::from(e).code;
             ptr::swap(error_out, &mut error as *mut c_int);
-            ptr::null_mut()
+            return ptr::null_mut();
         },
-    }
-}
+    };
 
-/// -------------------------------------------------------------------------------------------- ///
+    let covenant = if covenant.is_null() {
+        TariCovenant::default()
+    } else {
+        (*covenant).clone()
+    };
 
-/// -------------------------------- Private Key ----------------------------------------------- ///
+    let encrypted_data = if encrypted_data.is_null() {
+        TariEncryptedOpenings::default()
+    } else {
+        *encrypted_data
+    };
 
-/// Creates a TariPrivateKey from a ByteVector
-///
-/// ## Arguments
-/// `bytes` - The pointer to a ByteVector
-/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
-/// as an out parameter.
-///
-/// ## Returns
-/// `*mut TariPrivateKey` - Returns a pointer to a TariPrivateKey. Note that it returns ptr::null_mut()
-/// if bytes is null or if there was an error creating the TariPrivateKey from bytes
-///
-/// # Safety
-/// The ```private_key_destroy``` method must be called when finished with a TariPrivateKey to prevent a memory leak
-#[no_mangle]
-pub unsafe extern "C" fn private_key_create(bytes: *mut ByteVector, error_out: *mut c_int) -> *mut TariPrivateKey {
-    let mut error = 0;
-    ptr::swap(error_out, &mut error as *mut c_int);
-    if bytes.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("bytes".to_string())).code;
-        ptr::swap(error_out, &mut error as *mut c_int);
-        return ptr::null_mut();
-    }
-    let v = (*bytes).0.clone();
-    let pk = TariPrivateKey::from_bytes(&v);
-    match pk {
-        Ok(pk) => Box::into_raw(Box::new(pk)),
-        Err(e) => {
-            error = LibWalletError::from(e).code;
-            ptr::swap(error_out, &mut error as *mut c_int);
-            ptr::null_mut()
-        },
-    }
+    let unblinded_output = UnblindedOutput::new_current_version(
+        amount.into(),
+        (*spending_key).clone(),
+        (*features).clone(),
+        script,
+        input_data,
+        (*script_private_key).clone(),
+        (*sender_offset_public_key).clone(),
+        (*metadata_signature).clone(),
+        script_lock_height,
+        covenant,
+        encrypted_data,
+        minimum_value_promise.into(),
+    );
+
+    Box::into_raw(Box::new(unblinded_output))
 }
 
-/// Frees memory for a TariPrivateKey
+/// Frees memory for a TariUnblindedOutput
 ///
 /// ## Arguments
-/// `pk` - The pointer to a TariPrivateKey
+/// `output` - The pointer to a TariUnblindedOutput
 ///
 /// ## Returns
 /// `()` - Does not return a value, equivalent to void in C
@@ -1975,99 +2109,99 @@ pub unsafe extern "C" fn private_key_create(bytes: *mut ByteVector, error_out: *
 /// # Safety
 /// None
 #[no_mangle]
-pub unsafe extern "C" fn private_key_destroy(pk: *mut TariPrivateKey) {
-    if !pk.is_null() {
-        drop(Box::from_raw(pk))
+pub unsafe extern "C" fn tari_unblinded_output_destroy(output: *mut TariUnblindedOutput) {
+    if !output.is_null() {
+        drop(Box::from_raw(output))
     }
 }
 
-/// Gets a ByteVector from a TariPrivateKey
+/// returns the TariUnblindedOutput as a json string
 ///
 /// ## Arguments
-/// `pk` - The pointer to a TariPrivateKey
-/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
-/// as an out parameter.
+/// `output` - The pointer to a TariUnblindedOutput
 ///
 /// ## Returns
-/// `*mut ByteVectror` - Returns a pointer to a ByteVector. Note that it returns ptr::null_mut()
-/// if pk is null
+/// `*mut c_char` - Returns a pointer to a char array. Note that it returns an empty char array if
+/// TariUnblindedOutput is null or the position is invalid
 ///
 /// # Safety
-/// The ```byte_vector_destroy``` must be called when finished with a ByteVector to prevent a memory leak
+///  The ```tari_unblinded_output_destroy``` function must be called when finished with a TariUnblindedOutput to
+/// prevent a memory leak
 #[no_mangle]
-pub unsafe extern "C" fn private_key_get_bytes(pk: *mut TariPrivateKey, error_out: *mut c_int) -> *mut ByteVector {
+pub unsafe extern "C" fn tari_unblinded_output_to_json(
+    output: *mut TariUnblindedOutput,
+    error_out: *mut c_int,
+) -> *mut c_char {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
-    let mut bytes = ByteVector(Vec::new());
-    if pk.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("pk".to_string())).code;
+    let mut hex_bytes = CString::new("").expect("Blank CString will not fail.");
+    if output.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("output".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        return ptr::null_mut();
     } else {
-        bytes.0 = (*pk).to_vec();
+        match serde_json::to_string(&*output) {
+            Ok(json_string) => match CString::new(json_string) {
+                Ok(v) => hex_bytes = v,
+                _ => {
+                    error = LibWalletError::from(InterfaceError::PointerError("output".to_string())).code;
+                    ptr::swap(error_out, &mut error as *mut c_int);
+                },
+            },
+            Err(_) => {
+                error = LibWalletError::from(HexError::HexConversionError {}).code;
+                ptr::swap(error_out, &mut error as *mut c_int);
+            },
+        }
     }
-    Box::into_raw(Box::new(bytes))
-}
-
-/// Generates a TariPrivateKey
-///
-/// ## Arguments
-/// `()` - Does  not take any arguments
-///
-/// ## Returns
-/// `*mut TariPrivateKey` - Returns a pointer to a TariPrivateKey
-///
-/// # Safety
-/// The ```private_key_destroy``` method must be called when finished with a TariPrivateKey to prevent a memory leak.
-#[no_mangle]
-pub unsafe extern "C" fn private_key_generate() -> *mut TariPrivateKey {
-    let secret_key = TariPrivateKey::random(&mut OsRng);
-    Box::into_raw(Box::new(secret_key))
+    CString::into_raw(hex_bytes)
 }
 
-/// Creates a TariPrivateKey from a char array
+/// Creates a TariUnblindedOutput from a char array
 ///
 /// ## Arguments
-/// `key` - The pointer to a char array which is hex encoded
+/// `output_json` - The pointer to a char array which is json of the TariUnblindedOutput
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `*mut TariPrivateKey` - Returns a pointer to a TariPrivateKey. Note that it returns ptr::null_mut()
-/// if key is null or if there was an error creating the TariPrivateKey from key
+/// `*mut TariUnblindedOutput` - Returns a pointer to a TariUnblindedOutput. Note that it returns ptr::null_mut()
+/// if key is null or if there was an error creating the TariUnblindedOutput from key
 ///
 /// # Safety
-/// The ```private_key_destroy``` method must be called when finished with a TariPrivateKey to prevent a memory leak
+/// The ```tari_unblinded_output_destroy``` function must be called when finished with a TariUnblindedOutput to
+// /// prevent a memory leak
 #[no_mangle]
-pub unsafe extern "C" fn private_key_from_hex(key: *const c_char, error_out: *mut c_int) -> *mut TariPrivateKey {
+pub unsafe extern "C" fn create_tari_unblinded_output_from_json(
+    output_json: *const c_char,
+    error_out: *mut c_int,
+) -> *mut TariUnblindedOutput {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
-    let key_str;
-    if key.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("key".to_string())).code;
+    let output_json_str;
+    if output_json.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("output_json".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
         return ptr::null_mut();
     } else {
-        match CStr::from_ptr(key).to_str() {
+        match CStr::from_ptr(output_json).to_str() {
             Ok(v) => {
-                key_str = v.to_owned();
+                output_json_str = v.to_owned();
             },
             _ => {
-                error = LibWalletError::from(InterfaceError::PointerError("key".to_string())).code;
+                error = LibWalletError::from(InterfaceError::PointerError("output_json".to_string())).code;
                 ptr::swap(error_out, &mut error as *mut c_int);
                 return ptr::null_mut();
             },
         };
     }
+    let output: Result<TariUnblindedOutput, _> = serde_json::from_str(&output_json_str);
 
-    let secret_key = TariPrivateKey::from_hex(key_str.as_str());
-
-    match secret_key {
-        Ok(secret_key) => Box::into_raw(Box::new(secret_key)),
+    match output {
+        Ok(output) => Box::into_raw(Box::new(output)),
         Err(e) => {
-            error!(target: LOG_TARGET, "Error creating a Public Key from Hex: {:?}", e);
+            error!(target: LOG_TARGET, "Error creating a output from json: {:?}", e);
 
-            error = LibWalletError::from(e).code;
+            error = LibWalletError::from(HexError::HexConversionError {}).code;
             ptr::swap(error_out, &mut error as *mut c_int);
             ptr::null_mut()
         },
@@ -2075,49 +2209,82 @@ pub unsafe extern "C" fn private_key_from_hex(key: *const c_char, error_out: *mu
 }
 
 /// -------------------------------------------------------------------------------------------- ///
-/// --------------------------------------- Covenant --------------------------------------------///
 
-/// Creates a TariCovenant from a ByteVector containing the covenant bytes
+/// ----------------------------------- TariUnblindedOutputs ------------------------------------///
+
+/// Gets the length of TariUnblindedOutputs
 ///
 /// ## Arguments
-/// `covenant_bytes` - The covenant bytes as a ByteVector
+/// `outputs` - The pointer to a TariUnblindedOutputs
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
 ///
 /// ## Returns
-/// `TariCovenant` - Returns a commitment signature. Note that it will be ptr::null_mut() if any argument is
-/// null or if there was an error with the contents of bytes
+/// `c_uint` - Returns number of elements in , zero if outputs is null
 ///
 /// # Safety
-/// The ```covenant_destroy``` function must be called when finished with a TariCovenant to prevent a memory leak
+/// None
+// casting here is okay the length of the array wont go over u32
+#[allow(clippy::cast_possible_truncation)]
 #[no_mangle]
-pub unsafe extern "C" fn covenant_create_from_bytes(
-    covenant_bytes: *const ByteVector,
+pub unsafe extern "C" fn unblinded_outputs_get_length(
+    outputs: *mut TariUnblindedOutputs,
     error_out: *mut c_int,
-) -> *mut TariCovenant {
+) -> c_uint {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
-
-    if covenant_bytes.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("covenant_bytes".to_string())).code;
+    let mut len = 0;
+    if outputs.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("outputs".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        return ptr::null_mut();
+    } else {
+        len = (*outputs).0.len();
     }
-    let mut decoded_covenant_bytes = (*covenant_bytes).0.as_bytes();
+    len as c_uint
+}
 
-    match TariCovenant::borsh_from_bytes(&mut decoded_covenant_bytes) {
-        Ok(covenant) => Box::into_raw(Box::new(covenant)),
-        Err(e) => {
-            error!(target: LOG_TARGET, "Error creating a Covenant: {:?}", e);
-            error = LibWalletError::from(InterfaceError::InvalidArgument("covenant_bytes".to_string())).code;
-            ptr::swap(error_out, &mut error as *mut c_int);
-            ptr::null_mut()
-        },
+/// Gets a TariUnblindedOutput from TariUnblindedOutputs at position
+///
+/// ## Arguments
+/// `outputs` - The pointer to a TariUnblindedOutputs
+/// `position` - The integer position
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `*mut TariUnblindedOutput` - Returns a TariUnblindedOutput, note that it returns ptr::null_mut() if
+/// TariUnblindedOutputs is null or position is invalid
+///
+/// # Safety
+/// The ```contact_destroy``` method must be called when finished with a TariContact to prevent a memory leak
+// converting between here is fine as its used to clamp the the array to length
+#[allow(clippy::cast_possible_wrap)]
+#[no_mangle]
+pub unsafe extern "C" fn unblinded_outputs_get_at(
+    outputs: *mut TariUnblindedOutputs,
+    position: c_uint,
+    error_out: *mut c_int,
+) -> *mut TariUnblindedOutput {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if outputs.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("outputs".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return ptr::null_mut();
+    }
+    let len = unblinded_outputs_get_length(outputs, error_out) as c_int - 1;
+    if len < 0 || position > len as c_uint {
+        error = LibWalletError::from(InterfaceError::PositionInvalidError).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return ptr::null_mut();
     }
+    Box::into_raw(Box::new((*outputs).0[position as usize].clone()))
 }
 
-/// Frees memory for a TariCovenant
+/// Frees memory for a TariUnblindedOutputs
 ///
 /// ## Arguments
-/// `covenant` - The pointer to a TariCovenant
+/// `outputs` - The pointer to a TariUnblindedOutputs
 ///
 /// ## Returns
 /// `()` - Does not return a value, equivalent to void in C
@@ -2125,190 +2292,199 @@ pub unsafe extern "C" fn covenant_create_from_bytes(
 /// # Safety
 /// None
 #[no_mangle]
-pub unsafe extern "C" fn covenant_destroy(covenant: *mut TariCovenant) {
-    if !covenant.is_null() {
-        drop(Box::from_raw(covenant))
+pub unsafe extern "C" fn unblinded_outputs_destroy(outputs: *mut TariUnblindedOutputs) {
+    if !outputs.is_null() {
+        drop(Box::from_raw(outputs))
     }
 }
 
-/// -------------------------------------------------------------------------------------------- ///
-/// --------------------------------------- EncryptedOpenings --------------------------------------------///
-
-/// Creates a TariEncryptedOpenings from a ByteVector containing the encrypted_data bytes
+/// Import an external UTXO into the wallet as a non-rewindable (i.e. non-recoverable) output. This will add a spendable
+/// UTXO (as EncumberedToBeReceived) and create a faux completed transaction to record the event.
 ///
 /// ## Arguments
-/// `encrypted_data_bytes` - The encrypted_data bytes as a ByteVector
+/// `wallet` - The TariWallet pointer
+/// `amount` - The value of the UTXO in MicroMinotari
+/// `spending_key` - The private spending key
+/// `source_address` - The tari address of the source of the transaction
+/// `features` - Options for an output's structure or use
+/// `metadata_signature` - UTXO signature with the script offset private key, k_O
+/// `sender_offset_public_key` - Tari script offset pubkey, K_O
+/// `script_private_key` - Tari script private key, k_S, is used to create the script signature
+/// `covenant` - The covenant that will be executed when spending this output
+/// `message` - The message that the transaction will have
+/// `encrypted_data` - Encrypted data.
+/// `minimum_value_promise` - The minimum value of the commitment that is proven by the range proof
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
 ///
 /// ## Returns
-/// `TariEncryptedOpenings` - Returns  encrypted data. Note that it will be ptr::null_mut() if any argument is
-/// null or if there was an error with the contents of bytes
+/// `c_ulonglong` -  Returns the TransactionID of the generated transaction, note that it will be zero if the
+/// transaction is null
 ///
 /// # Safety
-/// The ```encrypted_data_destroy``` function must be called when finished with a TariEncryptedOpenings to prevent a
-/// memory leak
+/// None
 #[no_mangle]
-pub unsafe extern "C" fn encrypted_data_create_from_bytes(
-    encrypted_data_bytes: *const ByteVector,
+pub unsafe extern "C" fn wallet_import_external_utxo_as_non_rewindable(
+    wallet: *mut TariWallet,
+    output: *mut TariUnblindedOutput,
+    source_address: *mut TariWalletAddress,
+    message: *const c_char,
     error_out: *mut c_int,
-) -> *mut TariEncryptedOpenings {
+) -> c_ulonglong {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
-
-    if encrypted_data_bytes.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("encrypted_data_bytes".to_string())).code;
+    if wallet.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        return ptr::null_mut();
+        return 0;
     }
-    let decoded_encrypted_data_bytes = (*encrypted_data_bytes).0.clone();
-
-    match TariEncryptedOpenings::from_bytes(&decoded_encrypted_data_bytes) {
-        Ok(encrypted_data) => Box::into_raw(Box::new(encrypted_data)),
+    let source_address = if source_address.is_null() {
+        TariWalletAddress::default()
+    } else {
+        (*source_address).clone()
+    };
+    let message_string;
+    if message.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("message".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        message_string = CString::new("Imported UTXO")
+            .expect("CString will not fail")
+            .to_str()
+            .expect("CString.toStr() will not fail")
+            .to_owned();
+    } else {
+        match CStr::from_ptr(message).to_str() {
+            Ok(v) => {
+                message_string = v.to_owned();
+            },
+            _ => {
+                error = LibWalletError::from(InterfaceError::PointerError("message".to_string())).code;
+                ptr::swap(error_out, &mut error as *mut c_int);
+                message_string = CString::new("Imported UTXO")
+                    .expect("CString will not fail")
+                    .to_str()
+                    .expect("CString.to_str() will not fail")
+                    .to_owned();
+            },
+        }
+    };
+    match (*wallet)
+        .runtime
+        .block_on((*wallet).wallet.import_unblinded_output_as_non_rewindable(
+            (*output).clone(),
+            source_address,
+            message_string,
+        )) {
+        Ok(tx_id) => tx_id.as_u64(),
         Err(e) => {
-            error!(target: LOG_TARGET, "Error creating an encrypted_data: {:?}", e);
-            error = LibWalletError::from(InterfaceError::InvalidArgument("encrypted_data_bytes".to_string())).code;
+            error = LibWalletError::from(e).code;
             ptr::swap(error_out, &mut error as *mut c_int);
-            ptr::null_mut()
+            0
         },
     }
 }
 
-/// Creates a ByteVector containing the encrypted_data bytes from a TariEncryptedOpenings
+/// Get the TariUnblindedOutputs from a TariWallet
 ///
 /// ## Arguments
-/// `encrypted_data` - The encrypted_data as a TariEncryptedOpenings
+/// `wallet` - The TariWallet pointer
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
 ///
 /// ## Returns
-/// `ByteVector` - Returns a ByteVector containing the encrypted_data bytes. Note that it will be ptr::null_mut() if
-/// any argument is null or if there was an error with the contents of bytes
+/// `*mut TariUnblindedOutputs` - returns the unspent unblinded outputs, note that it returns ptr::null_mut() if
+/// wallet is null
 ///
 /// # Safety
-/// The `encrypted_data_destroy` function must be called when finished with a TariEncryptedOpenings to prevent a
+/// The ```unblinded_outputs_destroy``` method must be called when finished with a TariUnblindedOutput to prevent a
 /// memory leak
 #[no_mangle]
-pub unsafe extern "C" fn encrypted_data_as_bytes(
-    encrypted_data: *const TariEncryptedOpenings,
+pub unsafe extern "C" fn wallet_get_unspent_outputs(
+    wallet: *mut TariWallet,
     error_out: *mut c_int,
-) -> *mut ByteVector {
+) -> *mut TariUnblindedOutputs {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
-
-    if encrypted_data.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("encrypted_data".to_string())).code;
+    let mut outputs = Vec::new();
+    if wallet.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
         return ptr::null_mut();
     }
 
-    let encrypted_data_bytes = TariEncryptedOpenings::to_byte_vec(&(*encrypted_data)).to_vec();
-    let encrypted_byte_vector = ByteVector(encrypted_data_bytes);
-    Box::into_raw(Box::new(encrypted_byte_vector))
-}
-
-/// Frees memory for a TariEncryptedOpenings
-///
-/// ## Arguments
-/// `encrypted_data` - The pointer to a TariEncryptedOpenings
-///
-/// ## Returns
-/// `()` - Does not return a value, equivalent to void in C
-///
-/// # Safety
-/// None
-#[no_mangle]
-pub unsafe extern "C" fn encrypted_data_destroy(encrypted_data: *mut TariEncryptedOpenings) {
-    if !encrypted_data.is_null() {
-        // zeroize the data content of encrypted_data, as to prevent memory leaks
-        (*encrypted_data).zeroize();
+    let received_outputs = (*wallet)
+        .runtime
+        .block_on((*wallet).wallet.output_manager_service.get_unspent_outputs());
+    match received_outputs {
+        Ok(rec_outputs) => {
+            for output in rec_outputs {
+                let unblinded = (*wallet).runtime.block_on(UnblindedOutput::from_wallet_output(
+                    output.wallet_output,
+                    &(*wallet).wallet.key_manager_service,
+                ));
+                match unblinded {
+                    Ok(uo) => {
+                        outputs.push(uo);
+                    },
+                    Err(e) => {
+                        error = LibWalletError::from(WalletError::TransactionError(e)).code;
+                        ptr::swap(error_out, &mut error as *mut c_int);
+                        return ptr::null_mut();
+                    },
+                }
+            }
+            Box::into_raw(Box::new(TariUnblindedOutputs(outputs)))
+        },
+        Err(e) => {
+            error = LibWalletError::from(WalletError::OutputManagerError(e)).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            ptr::null_mut()
+        },
     }
 }
 
 /// -------------------------------------------------------------------------------------------- ///
-/// ---------------------------------- Output Features ------------------------------------------///
 
-/// Creates a TariOutputFeatures from byte values
+/// -------------------------------- Private Key ----------------------------------------------- ///
+
+/// Creates a TariPrivateKey from a ByteVector
 ///
 /// ## Arguments
-/// `version` - The encoded value of the version as a byte
-/// `output_type` - The encoded value of the output type as a byte
-/// `maturity` - The encoded value maturity as bytes
-/// `metadata` - The metadata componenet as a ByteVector. It cannot be null
-/// `encrypted_data` - The encrypted_data component as a ByteVector. It can be null  to model a None value.
+/// `bytes` - The pointer to a ByteVector
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `TariOutputFeatures` - Returns an output features object. Note that it will be ptr::null_mut() if any mandatory
-/// arguments are null or if there was an error with the contents of bytes
+/// `*mut TariPrivateKey` - Returns a pointer to a TariPrivateKey. Note that it returns ptr::null_mut()
+/// if bytes is null or if there was an error creating the TariPrivateKey from bytes
 ///
 /// # Safety
-/// The ```output_features_destroy``` function must be called when finished with a TariOutputFeatures to
-/// prevent a memory leak
+/// The ```private_key_destroy``` method must be called when finished with a TariPrivateKey to prevent a memory leak
 #[no_mangle]
-pub unsafe extern "C" fn output_features_create_from_bytes(
-    version: c_uchar,
-    output_type: c_ushort,
-    maturity: c_ulonglong,
-    metadata: *const ByteVector,
-    range_proof_type: c_ushort,
-    error_out: *mut c_int,
-) -> *mut TariOutputFeatures {
+pub unsafe extern "C" fn private_key_create(bytes: *mut ByteVector, error_out: *mut c_int) -> *mut TariPrivateKey {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
-    if metadata.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("metadata".to_string())).code;
+    if bytes.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("bytes".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
         return ptr::null_mut();
     }
-
-    let decoded_version = match OutputFeaturesVersion::try_from(version) {
-        Ok(v) => v,
-        Err(message) => {
-            error!(
-                target: LOG_TARGET,
-                "Error creating a OutputFeaturesVersion: {:?}", message
-            );
-            error = LibWalletError::from(InterfaceError::InvalidArgument("version".to_string())).code;
-            ptr::swap(error_out, &mut error as *mut c_int);
-            return ptr::null_mut();
-        },
-    };
-
-    let decoded_output_type = match output_type.try_into().ok().and_then(OutputType::from_byte) {
-        Some(val) => val,
-        None => {
-            error!(target: LOG_TARGET, "output_type overflowed",);
-            error = LibWalletError::from(InterfaceError::InvalidArgument("flag".to_string())).code;
-            ptr::swap(error_out, &mut error as *mut c_int);
-            return ptr::null_mut();
-        },
-    };
-
-    let decoded_range_proof_type = match range_proof_type.try_into().ok().and_then(RangeProofType::from_byte) {
-        Some(val) => val,
-        None => {
-            error!(target: LOG_TARGET, "range_proof_type overflowed",);
-            error = LibWalletError::from(InterfaceError::InvalidArgument("flag".to_string())).code;
+    let v = (*bytes).0.clone();
+    let pk = TariPrivateKey::from_bytes(&v);
+    match pk {
+        Ok(pk) => Box::into_raw(Box::new(pk)),
+        Err(e) => {
+            error = LibWalletError::from(e).code;
             ptr::swap(error_out, &mut error as *mut c_int);
-            return ptr::null_mut();
+            ptr::null_mut()
         },
-    };
-
-    let decoded_metadata = (*metadata).0.clone();
-
-    let output_features = TariOutputFeatures::new(
-        decoded_version,
-        decoded_output_type,
-        maturity,
-        decoded_metadata,
-        None,
-        decoded_range_proof_type,
-    );
-    Box::into_raw(Box::new(output_features))
+    }
 }
 
-/// Frees memory for a TariOutputFeatures
+/// Frees memory for a TariPrivateKey
 ///
 /// ## Arguments
-/// `output_features` - The pointer to a TariOutputFeatures
+/// `pk` - The pointer to a TariPrivateKey
 ///
 /// ## Returns
 /// `()` - Does not return a value, equivalent to void in C
@@ -2316,316 +2492,238 @@ pub unsafe extern "C" fn output_features_create_from_bytes(
 /// # Safety
 /// None
 #[no_mangle]
-pub unsafe extern "C" fn output_features_destroy(output_features: *mut TariOutputFeatures) {
-    if !output_features.is_null() {
-        drop(Box::from_raw(output_features))
+pub unsafe extern "C" fn private_key_destroy(pk: *mut TariPrivateKey) {
+    if !pk.is_null() {
+        drop(Box::from_raw(pk))
     }
 }
 
-/// -------------------------------------------------------------------------------------------- ///
-
-/// ----------------------------------- Seed Words ----------------------------------------------///
+/// Gets a ByteVector from a TariPrivateKey
+///
+/// ## Arguments
+/// `pk` - The pointer to a TariPrivateKey
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `*mut ByteVectror` - Returns a pointer to a ByteVector. Note that it returns ptr::null_mut()
+/// if pk is null
+///
+/// # Safety
+/// The ```byte_vector_destroy``` must be called when finished with a ByteVector to prevent a memory leak
+#[no_mangle]
+pub unsafe extern "C" fn private_key_get_bytes(pk: *mut TariPrivateKey, error_out: *mut c_int) -> *mut ByteVector {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    let mut bytes = ByteVector(Vec::new());
+    if pk.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("pk".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return ptr::null_mut();
+    } else {
+        bytes.0 = (*pk).to_vec();
+    }
+    Box::into_raw(Box::new(bytes))
+}
 
-/// Create an empty instance of TariSeedWords
+/// Generates a TariPrivateKey
 ///
 /// ## Arguments
-/// None
+/// `()` - Does  not take any arguments
 ///
 /// ## Returns
-/// `TariSeedWords` - Returns an empty TariSeedWords instance
+/// `*mut TariPrivateKey` - Returns a pointer to a TariPrivateKey
 ///
 /// # Safety
-/// None
+/// The ```private_key_destroy``` method must be called when finished with a TariPrivateKey to prevent a memory leak.
 #[no_mangle]
-pub unsafe extern "C" fn seed_words_create() -> *mut TariSeedWords {
-    let seed_words = SeedWords::new(vec![]);
-    Box::into_raw(Box::new(TariSeedWords(seed_words)))
+pub unsafe extern "C" fn private_key_generate() -> *mut TariPrivateKey {
+    let secret_key = TariPrivateKey::random(&mut OsRng);
+    Box::into_raw(Box::new(secret_key))
 }
 
-/// Create a TariSeedWords instance containing the entire mnemonic wordlist for the requested language
+/// Creates a TariPrivateKey from a char array
 ///
 /// ## Arguments
-/// `language` - The required language as a string
+/// `key` - The pointer to a char array which is hex encoded
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `TariSeedWords` - Returns the TariSeedWords instance containing the entire mnemonic wordlist for the
-/// requested language.
+/// `*mut TariPrivateKey` - Returns a pointer to a TariPrivateKey. Note that it returns ptr::null_mut()
+/// if key is null or if there was an error creating the TariPrivateKey from key
 ///
 /// # Safety
-/// The `seed_words_destroy` method must be called when finished with a TariSeedWords instance from rust to prevent a
-/// memory leak
+/// The ```private_key_destroy``` method must be called when finished with a TariPrivateKey to prevent a memory leak
 #[no_mangle]
-pub unsafe extern "C" fn seed_words_get_mnemonic_word_list_for_language(
-    language: *const c_char,
-    error_out: *mut c_int,
-) -> *mut TariSeedWords {
-    use tari_key_manager::mnemonic_wordlists;
-
+pub unsafe extern "C" fn private_key_from_hex(key: *const c_char, error_out: *mut c_int) -> *mut TariPrivateKey {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
-
-    let mut mnemonic_word_list_vec = SeedWords::new(vec![]);
-    if language.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("mnemonic wordlist".to_string())).code;
+    let key_str;
+    if key.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("key".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
+        return ptr::null_mut();
     } else {
-        let not_supported;
-        let language_string = match CStr::from_ptr(language).to_str() {
-            Ok(str) => str,
-            Err(e) => {
-                not_supported = e.to_string();
-                not_supported.as_str()
-            },
-        };
-        let mnemonic_word_list = match TariMnemonicLanguage::from_str(language_string) {
-            Ok(language) => match language {
-                TariMnemonicLanguage::ChineseSimplified => mnemonic_wordlists::MNEMONIC_CHINESE_SIMPLIFIED_WORDS,
-                TariMnemonicLanguage::English => mnemonic_wordlists::MNEMONIC_ENGLISH_WORDS,
-                TariMnemonicLanguage::French => mnemonic_wordlists::MNEMONIC_FRENCH_WORDS,
-                TariMnemonicLanguage::Italian => mnemonic_wordlists::MNEMONIC_ITALIAN_WORDS,
-                TariMnemonicLanguage::Japanese => mnemonic_wordlists::MNEMONIC_JAPANESE_WORDS,
-                TariMnemonicLanguage::Korean => mnemonic_wordlists::MNEMONIC_KOREAN_WORDS,
-                TariMnemonicLanguage::Spanish => mnemonic_wordlists::MNEMONIC_SPANISH_WORDS,
+        match CStr::from_ptr(key).to_str() {
+            Ok(v) => {
+                key_str = v.to_owned();
             },
-            Err(_) => {
-                error!(
-                    target: LOG_TARGET,
-                    "Mnemonic wordlist - '{}' language not supported", language_string
-                );
-                error = LibWalletError::from(InterfaceError::InvalidArgument(format!(
-                    "mnemonic wordlist - '{}' language not supported",
-                    language_string
-                )))
-                .code;
+            _ => {
+                error = LibWalletError::from(InterfaceError::PointerError("key".to_string())).code;
                 ptr::swap(error_out, &mut error as *mut c_int);
-                [""; 2048]
+                return ptr::null_mut();
             },
         };
-        info!(
-            target: LOG_TARGET,
-            "Retrieved mnemonic wordlist for'{}'", language_string
-        );
-        mnemonic_word_list_vec =
-            SeedWords::new(mnemonic_word_list.iter().map(|s| Hidden::hide(s.to_string())).collect());
     }
 
-    Box::into_raw(Box::new(TariSeedWords(mnemonic_word_list_vec)))
+    let secret_key = TariPrivateKey::from_hex(key_str.as_str());
+
+    match secret_key {
+        Ok(secret_key) => Box::into_raw(Box::new(secret_key)),
+        Err(e) => {
+            error!(target: LOG_TARGET, "Error creating a Public Key from Hex: {:?}", e);
+
+            error = LibWalletError::from(e).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            ptr::null_mut()
+        },
+    }
 }
 
-/// Gets the length of TariSeedWords
+/// -------------------------------------------------------------------------------------------- ///
+/// --------------------------------------- Covenant --------------------------------------------///
+
+/// Creates a TariCovenant from a ByteVector containing the covenant bytes
 ///
 /// ## Arguments
-/// `seed_words` - The pointer to a TariSeedWords
-/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
-/// as an out parameter.
+/// `covenant_bytes` - The covenant bytes as a ByteVector
 ///
 /// ## Returns
-/// `c_uint` - Returns number of elements in seed_words, zero if seed_words is null
+/// `TariCovenant` - Returns a commitment signature. Note that it will be ptr::null_mut() if any argument is
+/// null or if there was an error with the contents of bytes
 ///
 /// # Safety
-/// None
-// casting here is okay as we wont get more than u32 seed words
-#[allow(clippy::cast_possible_truncation)]
+/// The ```covenant_destroy``` function must be called when finished with a TariCovenant to prevent a memory leak
 #[no_mangle]
-pub unsafe extern "C" fn seed_words_get_length(seed_words: *const TariSeedWords, error_out: *mut c_int) -> c_uint {
+pub unsafe extern "C" fn covenant_create_from_bytes(
+    covenant_bytes: *const ByteVector,
+    error_out: *mut c_int,
+) -> *mut TariCovenant {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
-    let mut len = 0;
-    if seed_words.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("seed words".to_string())).code;
+
+    if covenant_bytes.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("covenant_bytes".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-    } else {
-        len = (*seed_words).0.len();
+        return ptr::null_mut();
+    }
+    let mut decoded_covenant_bytes = (*covenant_bytes).0.as_bytes();
+
+    match TariCovenant::borsh_from_bytes(&mut decoded_covenant_bytes) {
+        Ok(covenant) => Box::into_raw(Box::new(covenant)),
+        Err(e) => {
+            error!(target: LOG_TARGET, "Error creating a Covenant: {:?}", e);
+            error = LibWalletError::from(InterfaceError::InvalidArgument("covenant_bytes".to_string())).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            ptr::null_mut()
+        },
     }
-    len as c_uint
 }
 
-/// Gets a seed word from TariSeedWords at position
+/// Frees memory for a TariCovenant
 ///
 /// ## Arguments
-/// `seed_words` - The pointer to a TariSeedWords
-/// `position` - The integer position
-/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
-/// as an out parameter.
+/// `covenant` - The pointer to a TariCovenant
 ///
 /// ## Returns
-/// `*mut c_char` - Returns a pointer to a char array. Note that it returns an empty char array if
-/// TariSeedWords collection is null or the position is invalid
+/// `()` - Does not return a value, equivalent to void in C
 ///
 /// # Safety
-/// The ```string_destroy``` method must be called when finished with a string from rust to prevent a memory leak
-// casting here is okay as there aint more as u32 seed words
-#[allow(clippy::cast_possible_truncation)]
+/// None
 #[no_mangle]
-pub unsafe extern "C" fn seed_words_get_at(
-    seed_words: *mut TariSeedWords,
-    position: c_uint,
-    error_out: *mut c_int,
-) -> *mut c_char {
-    let mut error = 0;
-    ptr::swap(error_out, &mut error as *mut c_int);
-    let mut word = CString::new("").expect("Blank CString will not fail.");
-    if seed_words.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("seed words".to_string())).code;
-        ptr::swap(error_out, &mut error as *mut c_int);
-    } else {
-        let len = (*seed_words).0.len() - 1; // clamp to length
-        if position > len as u32 {
-            error = LibWalletError::from(InterfaceError::PositionInvalidError).code;
-            ptr::swap(error_out, &mut error as *mut c_int);
-        } else if let Ok(v) = CString::new(
-            (*seed_words)
-                .0
-                .get_word(position as usize)
-                .expect("Seed Words position is in bounds")
-                .as_str(),
-        ) {
-            word = v;
-        } else {
-            error = LibWalletError::from(InterfaceError::PointerError("seed_words".to_string())).code;
-            ptr::swap(error_out, &mut error as *mut c_int);
-        }
+pub unsafe extern "C" fn covenant_destroy(covenant: *mut TariCovenant) {
+    if !covenant.is_null() {
+        drop(Box::from_raw(covenant))
     }
-    CString::into_raw(word)
 }
 
-/// Add a word to the provided TariSeedWords instance
+/// -------------------------------------------------------------------------------------------- ///
+/// --------------------------------------- EncryptedOpenings --------------------------------------------///
+
+/// Creates a TariEncryptedOpenings from a ByteVector containing the encrypted_data bytes
 ///
 /// ## Arguments
-/// `seed_words` - The pointer to a TariSeedWords
-/// `word` - Word to add
-/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
-/// as an out parameter.
+/// `encrypted_data_bytes` - The encrypted_data bytes as a ByteVector
 ///
 /// ## Returns
-/// 'c_uchar' - Returns a u8 version of the `SeedWordPushResult` enum indicating whether the word was not a valid seed
-/// word, if the push was successful and whether the push was successful and completed the full Seed Phrase.
-///  `seed_words` is only modified in the event of a `SuccessfulPush`.
-///     '0' -> InvalidSeedWord
-///     '1' -> SuccessfulPush
-///     '2' -> SeedPhraseComplete
-///     '3' -> InvalidSeedPhrase
-///     '4' -> NoLanguageMatch,
+/// `TariEncryptedOpenings` - Returns  encrypted data. Note that it will be ptr::null_mut() if any argument is
+/// null or if there was an error with the contents of bytes
+///
 /// # Safety
-/// The ```string_destroy``` method must be called when finished with a string from rust to prevent a memory leak
+/// The ```encrypted_data_destroy``` function must be called when finished with a TariEncryptedOpenings to prevent a
+/// memory leak
 #[no_mangle]
-pub unsafe extern "C" fn seed_words_push_word(
-    seed_words: *mut TariSeedWords,
-    word: *const c_char,
+pub unsafe extern "C" fn encrypted_data_create_from_bytes(
+    encrypted_data_bytes: *const ByteVector,
     error_out: *mut c_int,
-) -> c_uchar {
-    use tari_key_manager::mnemonic::Mnemonic;
-
+) -> *mut TariEncryptedOpenings {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
-    if seed_words.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("seed words".to_string())).code;
-        ptr::swap(error_out, &mut error as *mut c_int);
-    }
 
-    let word_string;
-    if word.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("word".to_string())).code;
+    if encrypted_data_bytes.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("encrypted_data_bytes".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        return SeedWordPushResult::InvalidSeedWord as u8;
-    } else {
-        match CStr::from_ptr(word).to_str() {
-            Ok(v) => {
-                word_string = v.to_owned();
-            },
-            _ => {
-                error = LibWalletError::from(InterfaceError::PointerError("word".to_string())).code;
-                ptr::swap(error_out, &mut error as *mut c_int);
-                return SeedWordPushResult::InvalidObject as u8;
-            },
-        }
+        return ptr::null_mut();
     }
+    let decoded_encrypted_data_bytes = (*encrypted_data_bytes).0.clone();
 
-    // Check word is from a word list
-    match MnemonicLanguage::from(&word_string) {
-        Ok(language) => {
-            if (*seed_words).0.len() >= MnemonicLanguage::word_count(&language) {
-                let error_msg = "Invalid seed words object, i.e. the entire mnemonic word list, is being used";
-                log::error!(target: LOG_TARGET, "{}", error_msg);
-                error = LibWalletError::from(InterfaceError::InvalidArgument(error_msg.to_string())).code;
-                ptr::swap(error_out, &mut error as *mut c_int);
-                return SeedWordPushResult::InvalidObject as u8;
-            }
-        },
+    match TariEncryptedOpenings::from_bytes(&decoded_encrypted_data_bytes) {
+        Ok(encrypted_data) => Box::into_raw(Box::new(encrypted_data)),
         Err(e) => {
-            log::error!(
-                target: LOG_TARGET,
-                "{} is not a valid mnemonic seed word ({:?})",
-                word_string,
-                e
-            );
-            return SeedWordPushResult::InvalidSeedWord as u8;
+            error!(target: LOG_TARGET, "Error creating an encrypted_data: {:?}", e);
+            error = LibWalletError::from(InterfaceError::InvalidArgument("encrypted_data_bytes".to_string())).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            ptr::null_mut()
         },
     }
+}
 
-    // Seed words is currently empty, this is the first word
-    if (*seed_words).0.is_empty() {
-        (*seed_words).0.push(word_string);
-        return SeedWordPushResult::SuccessfulPush as u8;
-    }
-
-    // Try push to a temporary copy first to prevent existing object becoming invalid
-    if let Ok(language) = MnemonicLanguage::detect_language(&(*seed_words).0) {
-        // Check words in temp are still consistent for a language, note that detected language can change
-        // depending on word added
-        if MnemonicLanguage::detect_language(&(*seed_words).0).is_ok() {
-            if (*seed_words).0.len() >= 24 {
-                if let Err(e) = CipherSeed::from_mnemonic(&(*seed_words).0, None) {
-                    log::error!(
-                        target: LOG_TARGET,
-                        "Problem building valid private seed from seed phrase: {:?}",
-                        e
-                    );
-                    error = LibWalletError::from(WalletError::KeyManagerError(e)).code;
-                    ptr::swap(error_out, &mut error as *mut c_int);
-                    return SeedWordPushResult::InvalidSeedPhrase as u8;
-                };
-            }
-
-            (*seed_words).0.push(word_string);
+/// Creates a ByteVector containing the encrypted_data bytes from a TariEncryptedOpenings
+///
+/// ## Arguments
+/// `encrypted_data` - The encrypted_data as a TariEncryptedOpenings
+///
+/// ## Returns
+/// `ByteVector` - Returns a ByteVector containing the encrypted_data bytes. Note that it will be ptr::null_mut() if
+/// any argument is null or if there was an error with the contents of bytes
+///
+/// # Safety
+/// The `encrypted_data_destroy` function must be called when finished with a TariEncryptedOpenings to prevent a
+/// memory leak
+#[no_mangle]
+pub unsafe extern "C" fn encrypted_data_as_bytes(
+    encrypted_data: *const TariEncryptedOpenings,
+    error_out: *mut c_int,
+) -> *mut ByteVector {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
 
-            // Note: test for a validity was already done so we can just check length here
-            if (*seed_words).0.len() < 24 {
-                SeedWordPushResult::SuccessfulPush as u8
-            } else {
-                SeedWordPushResult::SeedPhraseComplete as u8
-            }
-        } else {
-            log::error!(
-                target: LOG_TARGET,
-                "Words in seed phrase do not match any language after trying to add word: `{:?}`, previously words \
-                 were detected to be in: `{:?}`",
-                word_string,
-                language
-            );
-            SeedWordPushResult::NoLanguageMatch as u8
-        }
-    } else {
-        // Seed words are invalid, shouldn't normally be reachable
-        log::error!(
-            target: LOG_TARGET,
-            "Words in seed phrase do not match any language prior to adding word: `{:?}`",
-            word_string
-        );
-        let error_msg = "Invalid seed words object, no language can be detected.";
-        log::error!(target: LOG_TARGET, "{}", error_msg);
-        error = LibWalletError::from(InterfaceError::InvalidArgument(error_msg.to_string())).code;
+    if encrypted_data.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("encrypted_data".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        SeedWordPushResult::InvalidObject as u8
+        return ptr::null_mut();
     }
+
+    let encrypted_data_bytes = TariEncryptedOpenings::to_byte_vec(&(*encrypted_data)).to_vec();
+    let encrypted_byte_vector = ByteVector(encrypted_data_bytes);
+    Box::into_raw(Box::new(encrypted_byte_vector))
 }
 
-/// Frees memory for a TariSeedWords
+/// Frees memory for a TariEncryptedOpenings
 ///
 /// ## Arguments
-/// `seed_words` - The pointer to a TariSeedWords
+/// `encrypted_data` - The pointer to a TariEncryptedOpenings
 ///
 /// ## Returns
 /// `()` - Does not return a value, equivalent to void in C
@@ -2633,456 +2731,579 @@ pub unsafe extern "C" fn seed_words_push_word(
 /// # Safety
 /// None
 #[no_mangle]
-pub unsafe extern "C" fn seed_words_destroy(seed_words: *mut TariSeedWords) {
-    if !seed_words.is_null() {
-        drop(Box::from_raw(seed_words))
+pub unsafe extern "C" fn encrypted_data_destroy(encrypted_data: *mut TariEncryptedOpenings) {
+    if !encrypted_data.is_null() {
+        // zeroize the data content of encrypted_data, as to prevent memory leaks
+        (*encrypted_data).zeroize();
     }
 }
 
 /// -------------------------------------------------------------------------------------------- ///
+/// ---------------------------------- Output Features ------------------------------------------///
 
-/// ----------------------------------- Contact -------------------------------------------------///
-
-/// Creates a TariContact
+/// Creates a TariOutputFeatures from byte values
 ///
 /// ## Arguments
-/// `alias` - The pointer to a char array
-/// `address` - The pointer to a TariWalletAddress
+/// `version` - The encoded value of the version as a byte
+/// `output_type` - The encoded value of the output type as a byte
+/// `maturity` - The encoded value maturity as bytes
+/// `metadata` - The metadata componenet as a ByteVector. It cannot be null
+/// `encrypted_data` - The encrypted_data component as a ByteVector. It can be null  to model a None value.
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `*mut TariContact` - Returns a pointer to a TariContact. Note that it returns ptr::null_mut()
-/// if alias is null or if pk is null
+/// `TariOutputFeatures` - Returns an output features object. Note that it will be ptr::null_mut() if any mandatory
+/// arguments are null or if there was an error with the contents of bytes
 ///
 /// # Safety
-/// The ```contact_destroy``` method must be called when finished with a TariContact
+/// The ```output_features_destroy``` function must be called when finished with a TariOutputFeatures to
+/// prevent a memory leak
 #[no_mangle]
-pub unsafe extern "C" fn contact_create(
-    alias: *const c_char,
-    address: *mut TariWalletAddress,
-    favourite: bool,
+pub unsafe extern "C" fn output_features_create_from_bytes(
+    version: c_uchar,
+    output_type: c_ushort,
+    maturity: c_ulonglong,
+    metadata: *const ByteVector,
+    range_proof_type: c_ushort,
     error_out: *mut c_int,
-) -> *mut TariContact {
+) -> *mut TariOutputFeatures {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
-    let alias_string;
-    if alias.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("alias".to_string())).code;
-        ptr::swap(error_out, &mut error as *mut c_int);
-        return ptr::null_mut();
-    } else {
-        match CStr::from_ptr(alias).to_str() {
-            Ok(v) => {
-                alias_string = v.to_owned();
-            },
-            _ => {
-                error = LibWalletError::from(InterfaceError::PointerError("alias".to_string())).code;
-                ptr::swap(error_out, &mut error as *mut c_int);
-                return ptr::null_mut();
-            },
-        }
-    }
-
-    if address.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("address".to_string())).code;
+    if metadata.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("metadata".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
         return ptr::null_mut();
     }
 
-    let contact = Contact::new(alias_string, (*address).clone(), None, None, favourite);
-    Box::into_raw(Box::new(contact))
-}
+    let decoded_version = match OutputFeaturesVersion::try_from(version) {
+        Ok(v) => v,
+        Err(message) => {
+            error!(
+                target: LOG_TARGET,
+                "Error creating a OutputFeaturesVersion: {:?}", message
+            );
+            error = LibWalletError::from(InterfaceError::InvalidArgument("version".to_string())).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            return ptr::null_mut();
+        },
+    };
 
-/// Gets the alias of the TariContact
-///
-/// ## Arguments
-/// `contact` - The pointer to a TariContact
-/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
-/// as an out parameter.
+    let decoded_output_type = match output_type.try_into().ok().and_then(OutputType::from_byte) {
+        Some(val) => val,
+        None => {
+            error!(target: LOG_TARGET, "output_type overflowed",);
+            error = LibWalletError::from(InterfaceError::InvalidArgument("flag".to_string())).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            return ptr::null_mut();
+        },
+    };
+
+    let decoded_range_proof_type = match range_proof_type.try_into().ok().and_then(RangeProofType::from_byte) {
+        Some(val) => val,
+        None => {
+            error!(target: LOG_TARGET, "range_proof_type overflowed",);
+            error = LibWalletError::from(InterfaceError::InvalidArgument("flag".to_string())).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            return ptr::null_mut();
+        },
+    };
+
+    let decoded_metadata = (*metadata).0.clone();
+
+    let output_features = TariOutputFeatures::new(
+        decoded_version,
+        decoded_output_type,
+        maturity,
+        decoded_metadata,
+        None,
+        decoded_range_proof_type,
+    );
+    Box::into_raw(Box::new(output_features))
+}
+
+/// Frees memory for a TariOutputFeatures
+///
+/// ## Arguments
+/// `output_features` - The pointer to a TariOutputFeatures
 ///
 /// ## Returns
-/// `*mut c_char` - Returns a pointer to a char array. Note that it returns an empty char array if
-/// contact is null
+/// `()` - Does not return a value, equivalent to void in C
 ///
 /// # Safety
-/// The ```string_destroy``` method must be called when finished with a string from rust to prevent a memory leak
+/// None
 #[no_mangle]
-pub unsafe extern "C" fn contact_get_alias(contact: *mut TariContact, error_out: *mut c_int) -> *mut c_char {
-    let mut error = 0;
-    ptr::swap(error_out, &mut error as *mut c_int);
-    let mut a = CString::new("").expect("Blank CString will not fail.");
-    if contact.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("contact".to_string())).code;
-        ptr::swap(error_out, &mut error as *mut c_int);
-    } else {
-        match CString::new((*contact).alias.clone()) {
-            Ok(v) => a = v,
-            _ => {
-                error = LibWalletError::from(InterfaceError::PointerError("contact".to_string())).code;
-                ptr::swap(error_out, &mut error as *mut c_int);
-            },
-        }
+pub unsafe extern "C" fn output_features_destroy(output_features: *mut TariOutputFeatures) {
+    if !output_features.is_null() {
+        drop(Box::from_raw(output_features))
     }
-    CString::into_raw(a)
 }
 
-/// Gets the favourite status of the TariContact
+/// -------------------------------------------------------------------------------------------- ///
+
+/// ----------------------------------- Seed Words ----------------------------------------------///
+
+/// Create an empty instance of TariSeedWords
 ///
 /// ## Arguments
-/// `contact` - The pointer to a TariContact
-/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
-/// as an out parameter.
+/// None
 ///
 /// ## Returns
-/// `bool` - Returns a bool indicating the favourite status of a contact. NOTE this will return false if the pointer is
-/// null as well.
+/// `TariSeedWords` - Returns an empty TariSeedWords instance
 ///
 /// # Safety
-/// The ```string_destroy``` method must be called when finished with a string from rust to prevent a memory leak
+/// None
 #[no_mangle]
-pub unsafe extern "C" fn contact_get_favourite(contact: *mut TariContact, error_out: *mut c_int) -> bool {
-    let mut error = 0;
-    let mut favourite = false;
-    ptr::swap(error_out, &mut error as *mut c_int);
-    if contact.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("contact".to_string())).code;
-        ptr::swap(error_out, &mut error as *mut c_int);
-    } else {
-        favourite = (*contact).favourite;
-    }
-
-    favourite
+pub unsafe extern "C" fn seed_words_create() -> *mut TariSeedWords {
+    let seed_words = SeedWords::new(vec![]);
+    Box::into_raw(Box::new(TariSeedWords(seed_words)))
 }
 
-/// Gets the TariWalletAddress of the TariContact
+/// Create a TariSeedWords instance containing the entire mnemonic wordlist for the requested language
 ///
 /// ## Arguments
-/// `contact` - The pointer to a TariContact
+/// `language` - The required language as a string
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `*mut TariWalletAddress` - Returns a pointer to a TariWalletAddress. Note that it returns
-/// ptr::null_mut() if contact is null
+/// `TariSeedWords` - Returns the TariSeedWords instance containing the entire mnemonic wordlist for the
+/// requested language.
 ///
 /// # Safety
-/// The ```tari_address_destroy``` method must be called when finished with a TariWalletAddress to prevent a memory leak
+/// The `seed_words_destroy` method must be called when finished with a TariSeedWords instance from rust to prevent a
+/// memory leak
 #[no_mangle]
-pub unsafe extern "C" fn contact_get_tari_address(
-    contact: *mut TariContact,
+pub unsafe extern "C" fn seed_words_get_mnemonic_word_list_for_language(
+    language: *const c_char,
     error_out: *mut c_int,
-) -> *mut TariWalletAddress {
+) -> *mut TariSeedWords {
+    use tari_key_manager::mnemonic_wordlists;
+
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
-    if contact.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("contact".to_string())).code;
+
+    let mut mnemonic_word_list_vec = SeedWords::new(vec![]);
+    if language.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("mnemonic wordlist".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        return ptr::null_mut();
+    } else {
+        let not_supported;
+        let language_string = match CStr::from_ptr(language).to_str() {
+            Ok(str) => str,
+            Err(e) => {
+                not_supported = e.to_string();
+                not_supported.as_str()
+            },
+        };
+        let mnemonic_word_list = match TariMnemonicLanguage::from_str(language_string) {
+            Ok(language) => match language {
+                TariMnemonicLanguage::ChineseSimplified => mnemonic_wordlists::MNEMONIC_CHINESE_SIMPLIFIED_WORDS,
+                TariMnemonicLanguage::English => mnemonic_wordlists::MNEMONIC_ENGLISH_WORDS,
+                TariMnemonicLanguage::French => mnemonic_wordlists::MNEMONIC_FRENCH_WORDS,
+                TariMnemonicLanguage::Italian => mnemonic_wordlists::MNEMONIC_ITALIAN_WORDS,
+                TariMnemonicLanguage::Japanese => mnemonic_wordlists::MNEMONIC_JAPANESE_WORDS,
+                TariMnemonicLanguage::Korean => mnemonic_wordlists::MNEMONIC_KOREAN_WORDS,
+                TariMnemonicLanguage::Spanish => mnemonic_wordlists::MNEMONIC_SPANISH_WORDS,
+            },
+            Err(_) => {
+                error!(
+                    target: LOG_TARGET,
+                    "Mnemonic wordlist - '{}' language not supported", language_string
+                );
+                error = LibWalletError::from(InterfaceError::InvalidArgument(format!(
+                    "mnemonic wordlist - '{}' language not supported",
+                    language_string
+                )))
+                .code;
+                ptr::swap(error_out, &mut error as *mut c_int);
+                [""; 2048]
+            },
+        };
+        info!(
+            target: LOG_TARGET,
+            "Retrieved mnemonic wordlist for'{}'", language_string
+        );
+        mnemonic_word_list_vec =
+            SeedWords::new(mnemonic_word_list.iter().map(|s| Hidden::hide(s.to_string())).collect());
     }
-    Box::into_raw(Box::new((*contact).address.clone()))
-}
 
-/// Frees memory for a TariContact
-///
-/// ## Arguments
-/// `contact` - The pointer to a TariContact
-///
-/// ## Returns
-/// `()` - Does not return a value, equivalent to void in C
-///
-/// # Safety
-/// None
-#[no_mangle]
-pub unsafe extern "C" fn contact_destroy(contact: *mut TariContact) {
-    if !contact.is_null() {
-        drop(Box::from_raw(contact))
-    }
+    Box::into_raw(Box::new(TariSeedWords(mnemonic_word_list_vec)))
 }
 
-/// -------------------------------------------------------------------------------------------- ///
-
-/// ----------------------------------- Contacts -------------------------------------------------///
-
-/// Gets the length of TariContacts
+/// Gets the length of TariSeedWords
 ///
 /// ## Arguments
-/// `contacts` - The pointer to a TariContacts
+/// `seed_words` - The pointer to a TariSeedWords
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `c_uint` - Returns number of elements in , zero if contacts is null
+/// `c_uint` - Returns number of elements in seed_words, zero if seed_words is null
 ///
 /// # Safety
 /// None
-// casting here is okay as we dont have more thant u32 contacts
+// casting here is okay as we wont get more than u32 seed words
 #[allow(clippy::cast_possible_truncation)]
 #[no_mangle]
-pub unsafe extern "C" fn contacts_get_length(contacts: *mut TariContacts, error_out: *mut c_int) -> c_uint {
+pub unsafe extern "C" fn seed_words_get_length(seed_words: *const TariSeedWords, error_out: *mut c_int) -> c_uint {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
     let mut len = 0;
-    if contacts.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("contacts".to_string())).code;
+    if seed_words.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("seed words".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
     } else {
-        len = (*contacts).0.len();
+        len = (*seed_words).0.len();
     }
     len as c_uint
 }
 
-/// Gets a TariContact from TariContacts at position
+/// Gets a seed word from TariSeedWords at position
 ///
 /// ## Arguments
-/// `contacts` - The pointer to a TariContacts
+/// `seed_words` - The pointer to a TariSeedWords
 /// `position` - The integer position
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `*mut TariContact` - Returns a TariContact, note that it returns ptr::null_mut() if contacts is
-/// null or position is invalid
+/// `*mut c_char` - Returns a pointer to a char array. Note that it returns an empty char array if
+/// TariSeedWords collection is null or the position is invalid
 ///
 /// # Safety
-/// The ```contact_destroy``` method must be called when finished with a TariContact to prevent a memory leak
-// converting between here is fine as its used to clamp the the array to length
-#[allow(clippy::cast_possible_wrap)]
+/// The ```string_destroy``` method must be called when finished with a string from rust to prevent a memory leak
+// casting here is okay as there aint more as u32 seed words
+#[allow(clippy::cast_possible_truncation)]
 #[no_mangle]
-pub unsafe extern "C" fn contacts_get_at(
-    contacts: *mut TariContacts,
+pub unsafe extern "C" fn seed_words_get_at(
+    seed_words: *mut TariSeedWords,
     position: c_uint,
     error_out: *mut c_int,
-) -> *mut TariContact {
+) -> *mut c_char {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
-    if contacts.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("contacts".to_string())).code;
-        ptr::swap(error_out, &mut error as *mut c_int);
-        return ptr::null_mut();
-    }
-    let len = contacts_get_length(contacts, error_out) as c_int - 1;
-    if len < 0 || position > len as c_uint {
-        error = LibWalletError::from(InterfaceError::PositionInvalidError).code;
+    let mut word = CString::new("").expect("Blank CString will not fail.");
+    if seed_words.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("seed words".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        return ptr::null_mut();
+    } else {
+        let len = (*seed_words).0.len() - 1; // clamp to length
+        if position > len as u32 {
+            error = LibWalletError::from(InterfaceError::PositionInvalidError).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+        } else if let Ok(v) = CString::new(
+            (*seed_words)
+                .0
+                .get_word(position as usize)
+                .expect("Seed Words position is in bounds")
+                .as_str(),
+        ) {
+            word = v;
+        } else {
+            error = LibWalletError::from(InterfaceError::PointerError("seed_words".to_string())).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+        }
     }
-    Box::into_raw(Box::new((*contacts).0[position as usize].clone()))
+    CString::into_raw(word)
 }
 
-/// Frees memory for a TariContacts
+/// Add a word to the provided TariSeedWords instance
 ///
 /// ## Arguments
-/// `contacts` - The pointer to a TariContacts
+/// `seed_words` - The pointer to a TariSeedWords
+/// `word` - Word to add
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
 ///
 /// ## Returns
-/// `()` - Does not return a value, equivalent to void in C
-///
-/// # Safety
-/// None
+/// 'c_uchar' - Returns a u8 version of the `SeedWordPushResult` enum indicating whether the word was not a valid seed
+/// word, if the push was successful and whether the push was successful and completed the full Seed Phrase.
+///  `seed_words` is only modified in the event of a `SuccessfulPush`.
+///     '0' -> InvalidSeedWord
+///     '1' -> SuccessfulPush
+///     '2' -> SeedPhraseComplete
+///     '3' -> InvalidSeedPhrase
+///     '4' -> NoLanguageMatch,
+/// # Safety
+/// The ```string_destroy``` method must be called when finished with a string from rust to prevent a memory leak
 #[no_mangle]
-pub unsafe extern "C" fn contacts_destroy(contacts: *mut TariContacts) {
-    if !contacts.is_null() {
-        drop(Box::from_raw(contacts))
+pub unsafe extern "C" fn seed_words_push_word(
+    seed_words: *mut TariSeedWords,
+    word: *const c_char,
+    error_out: *mut c_int,
+) -> c_uchar {
+    use tari_key_manager::mnemonic::Mnemonic;
+
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if seed_words.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("seed words".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
     }
-}
 
-/// -------------------------------------------------------------------------------------------- ///
+    let word_string;
+    if word.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("word".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return SeedWordPushResult::InvalidSeedWord as u8;
+    } else {
+        match CStr::from_ptr(word).to_str() {
+            Ok(v) => {
+                word_string = v.to_owned();
+            },
+            _ => {
+                error = LibWalletError::from(InterfaceError::PointerError("word".to_string())).code;
+                ptr::swap(error_out, &mut error as *mut c_int);
+                return SeedWordPushResult::InvalidObject as u8;
+            },
+        }
+    }
 
-/// ----------------------------------- Contacts Liveness Data ----------------------------------///
+    // Check word is from a word list
+    match MnemonicLanguage::from(&word_string) {
+        Ok(language) => {
+            if (*seed_words).0.len() >= MnemonicLanguage::word_count(&language) {
+                let error_msg = "Invalid seed words object, i.e. the entire mnemonic word list, is being used";
+                log::error!(target: LOG_TARGET, "{}", error_msg);
+                error = LibWalletError::from(InterfaceError::InvalidArgument(error_msg.to_string())).code;
+                ptr::swap(error_out, &mut error as *mut c_int);
+                return SeedWordPushResult::InvalidObject as u8;
+            }
+        },
+        Err(e) => {
+            log::error!(
+                target: LOG_TARGET,
+                "{} is not a valid mnemonic seed word ({:?})",
+                word_string,
+                e
+            );
+            return SeedWordPushResult::InvalidSeedWord as u8;
+        },
+    }
 
-/// Gets the public_key from a TariContactsLivenessData
+    // Seed words is currently empty, this is the first word
+    if (*seed_words).0.is_empty() {
+        (*seed_words).0.push(word_string);
+        return SeedWordPushResult::SuccessfulPush as u8;
+    }
+
+    // Try push to a temporary copy first to prevent existing object becoming invalid
+    if let Ok(language) = MnemonicLanguage::detect_language(&(*seed_words).0) {
+        // Check words in temp are still consistent for a language, note that detected language can change
+        // depending on word added
+        if MnemonicLanguage::detect_language(&(*seed_words).0).is_ok() {
+            if (*seed_words).0.len() >= 24 {
+                if let Err(e) = CipherSeed::from_mnemonic(&(*seed_words).0, None) {
+                    log::error!(
+                        target: LOG_TARGET,
+                        "Problem building valid private seed from seed phrase: {:?}",
+                        e
+                    );
+                    error = LibWalletError::from(WalletError::KeyManagerError(e)).code;
+                    ptr::swap(error_out, &mut error as *mut c_int);
+                    return SeedWordPushResult::InvalidSeedPhrase as u8;
+                };
+            }
+
+            (*seed_words).0.push(word_string);
+
+            // Note: test for a validity was already done so we can just check length here
+            if (*seed_words).0.len() < 24 {
+                SeedWordPushResult::SuccessfulPush as u8
+            } else {
+                SeedWordPushResult::SeedPhraseComplete as u8
+            }
+        } else {
+            log::error!(
+                target: LOG_TARGET,
+                "Words in seed phrase do not match any language after trying to add word: `{:?}`, previously words \
+                 were detected to be in: `{:?}`",
+                word_string,
+                language
+            );
+            SeedWordPushResult::NoLanguageMatch as u8
+        }
+    } else {
+        // Seed words are invalid, shouldn't normally be reachable
+        log::error!(
+            target: LOG_TARGET,
+            "Words in seed phrase do not match any language prior to adding word: `{:?}`",
+            word_string
+        );
+        let error_msg = "Invalid seed words object, no language can be detected.";
+        log::error!(target: LOG_TARGET, "{}", error_msg);
+        error = LibWalletError::from(InterfaceError::InvalidArgument(error_msg.to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        SeedWordPushResult::InvalidObject as u8
+    }
+}
+
+/// Frees memory for a TariSeedWords
 ///
 /// ## Arguments
-/// `liveness_data` - The pointer to a TariContactsLivenessData
-/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
-/// as an out parameter.
+/// `seed_words` - The pointer to a TariSeedWords
 ///
 /// ## Returns
-/// `*mut TariWalletAddress` - Returns a pointer to a TariWalletAddress. Note that it returns ptr::null_mut() if
-/// liveness_data is null.
+/// `()` - Does not return a value, equivalent to void in C
 ///
 /// # Safety
-/// The ```liveness_data_destroy``` method must be called when finished with a TariContactsLivenessData to prevent a
-/// memory leak
+/// None
 #[no_mangle]
-pub unsafe extern "C" fn liveness_data_get_public_key(
-    liveness_data: *mut TariContactsLivenessData,
-    error_out: *mut c_int,
-) -> *mut TariWalletAddress {
-    let mut error = 0;
-    ptr::swap(error_out, &mut error as *mut c_int);
-    if liveness_data.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("liveness_data".to_string())).code;
-        ptr::swap(error_out, &mut error as *mut c_int);
-        return ptr::null_mut();
+pub unsafe extern "C" fn seed_words_destroy(seed_words: *mut TariSeedWords) {
+    if !seed_words.is_null() {
+        drop(Box::from_raw(seed_words))
     }
-    Box::into_raw(Box::new((*liveness_data).address().clone()))
 }
 
-/// Gets the latency in milli-seconds (ms) from a TariContactsLivenessData
+/// -------------------------------------------------------------------------------------------- ///
+
+/// ----------------------------------- Contact -------------------------------------------------///
+
+/// Creates a TariContact
 ///
 /// ## Arguments
-/// `liveness_data` - The pointer to a TariContactsLivenessData
+/// `alias` - The pointer to a char array
+/// `address` - The pointer to a TariWalletAddress
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `*mut c_int` - Returns a pointer to a c_int if the optional latency data (in milli-seconds (ms)) exists, with a
-/// value of '0' if it is None. Note that it also returns '0' if liveness_data is null.
+/// `*mut TariContact` - Returns a pointer to a TariContact. Note that it returns ptr::null_mut()
+/// if alias is null or if pk is null
 ///
 /// # Safety
-/// The ```liveness_data_destroy``` method must be called when finished with a TariContactsLivenessData to prevent a
-/// memory leak
-
+/// The ```contact_destroy``` method must be called when finished with a TariContact
 #[no_mangle]
-pub unsafe extern "C" fn liveness_data_get_latency(
-    liveness_data: *mut TariContactsLivenessData,
+pub unsafe extern "C" fn contact_create(
+    alias: *const c_char,
+    address: *mut TariWalletAddress,
+    favourite: bool,
     error_out: *mut c_int,
-) -> c_uint {
+) -> *mut TariContact {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
-    if liveness_data.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("liveness_data".to_string())).code;
+    let alias_string;
+    if alias.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("alias".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        return 0;
-    }
-    if let Some(latency) = (*liveness_data).latency() {
-        latency as c_uint
+        return ptr::null_mut();
     } else {
-        0
+        match CStr::from_ptr(alias).to_str() {
+            Ok(v) => {
+                alias_string = v.to_owned();
+            },
+            _ => {
+                error = LibWalletError::from(InterfaceError::PointerError("alias".to_string())).code;
+                ptr::swap(error_out, &mut error as *mut c_int);
+                return ptr::null_mut();
+            },
+        }
+    }
+
+    if address.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("address".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return ptr::null_mut();
     }
+
+    let contact = Contact::new(alias_string, (*address).clone(), None, None, favourite);
+    Box::into_raw(Box::new(contact))
 }
 
-/// Gets the last_seen time (in local time) from a TariContactsLivenessData
+/// Gets the alias of the TariContact
 ///
 /// ## Arguments
-/// `liveness_data` - The pointer to a TariContactsLivenessData
+/// `contact` - The pointer to a TariContact
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `*mut c_char` - Returns a pointer to a char array if the optional last_seen data exists, with a value of '?' if it
-/// is None. Note that it returns ptr::null_mut() if liveness_data is null.
+/// `*mut c_char` - Returns a pointer to a char array. Note that it returns an empty char array if
+/// contact is null
 ///
 /// # Safety
-/// The ```liveness_data_destroy``` method must be called when finished with a TariContactsLivenessData to prevent a
-/// memory leak
+/// The ```string_destroy``` method must be called when finished with a string from rust to prevent a memory leak
 #[no_mangle]
-pub unsafe extern "C" fn liveness_data_get_last_seen(
-    liveness_data: *mut TariContactsLivenessData,
-    error_out: *mut c_int,
-) -> *mut c_char {
+pub unsafe extern "C" fn contact_get_alias(contact: *mut TariContact, error_out: *mut c_int) -> *mut c_char {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
-    if liveness_data.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("liveness_data".to_string())).code;
+    let mut a = CString::new("").expect("Blank CString will not fail.");
+    if contact.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("contact".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        return ptr::null_mut();
-    }
-    if let Some(last_seen) = (*liveness_data).last_ping_pong_received() {
-        let last_seen_local_time = DateTime::<Local>::from_utc(last_seen, Local::now().offset().to_owned())
-            .format("%FT%T")
-            .to_string();
-        let mut return_value = CString::new("").expect("Blank CString will not fail.");
-        match CString::new(last_seen_local_time) {
-            Ok(val) => {
-                return_value = val;
-            },
+    } else {
+        match CString::new((*contact).alias.clone()) {
+            Ok(v) => a = v,
             _ => {
-                error = LibWalletError::from(InterfaceError::PointerError("liveness_data".to_string())).code;
+                error = LibWalletError::from(InterfaceError::PointerError("contact".to_string())).code;
                 ptr::swap(error_out, &mut error as *mut c_int);
             },
         }
-        CString::into_raw(return_value)
-    } else {
-        CString::into_raw(CString::new("?").expect("Single character CString will not fail."))
     }
+    CString::into_raw(a)
 }
 
-/// Gets the message_type (ContactMessageType enum) from a TariContactsLivenessData
+/// Gets the favourite status of the TariContact
 ///
 /// ## Arguments
-/// `liveness_data` - The pointer to a TariContactsLivenessData
+/// `contact` - The pointer to a TariContact
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `c_int` - Returns the status which corresponds to:
-/// | Value | Interpretation |
-/// |---|---|
-/// |  -1 | NullError        |
-/// |   0 | Ping             |
-/// |   1 | Pong             |
-/// |   2 | NoMessage        |
+/// `bool` - Returns a bool indicating the favourite status of a contact. NOTE this will return false if the pointer is
+/// null as well.
 ///
 /// # Safety
-/// The ```liveness_data_destroy``` method must be called when finished with a TariContactsLivenessData to prevent a
-/// memory leak
+/// The ```string_destroy``` method must be called when finished with a string from rust to prevent a memory leak
 #[no_mangle]
-pub unsafe extern "C" fn liveness_data_get_message_type(
-    liveness_data: *mut TariContactsLivenessData,
-    error_out: *mut c_int,
-) -> c_int {
+pub unsafe extern "C" fn contact_get_favourite(contact: *mut TariContact, error_out: *mut c_int) -> bool {
     let mut error = 0;
+    let mut favourite = false;
     ptr::swap(error_out, &mut error as *mut c_int);
-    if liveness_data.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("liveness_data".to_string())).code;
+    if contact.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("contact".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        return -1;
+    } else {
+        favourite = (*contact).favourite;
     }
-    let status = (*liveness_data).message_type();
-    status as c_int
+
+    favourite
 }
 
-/// Gets the online_status (ContactOnlineStatus enum) from a TariContactsLivenessData
+/// Gets the TariWalletAddress of the TariContact
 ///
 /// ## Arguments
-/// `liveness_data` - The pointer to a TariContactsLivenessData
+/// `contact` - The pointer to a TariContact
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `c_int` - Returns the status which corresponds to:
-/// | Value | Interpretation |
-/// |---|---|
-/// |  -1 | NullError        |
-/// |   0 | Online           |
-/// |   1 | Offline          |
-/// |   2 | NeverSeen        |
-/// |   3 | Banned           |
+/// `*mut TariWalletAddress` - Returns a pointer to a TariWalletAddress. Note that it returns
+/// ptr::null_mut() if contact is null
 ///
 /// # Safety
-/// The ```liveness_data_destroy``` method must be called when finished with a TariContactsLivenessData to prevent a
-/// memory leak
+/// The ```tari_address_destroy``` method must be called when finished with a TariWalletAddress to prevent a memory leak
 #[no_mangle]
-pub unsafe extern "C" fn liveness_data_get_online_status(
-    liveness_data: *mut TariContactsLivenessData,
+pub unsafe extern "C" fn contact_get_tari_address(
+    contact: *mut TariContact,
     error_out: *mut c_int,
-) -> *const c_char {
+) -> *mut TariWalletAddress {
     let mut error = 0;
-    let mut result = CString::new("").expect("Blank CString will not fail.");
     ptr::swap(error_out, &mut error as *mut c_int);
-    if liveness_data.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("liveness_data".to_string())).code;
+    if contact.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("contact".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        return result.into_raw();
-    }
-    let status = (*liveness_data).online_status();
-    match CString::new(status.to_string()) {
-        Ok(v) => result = v,
-        _ => {
-            error = LibWalletError::from(InterfaceError::PointerError("message".to_string())).code;
-            ptr::swap(error_out, &mut error as *mut c_int);
-        },
+        return ptr::null_mut();
     }
-    result.into_raw()
+    Box::into_raw(Box::new((*contact).address.clone()))
 }
 
-/// Frees memory for a TariContactsLivenessData
+/// Frees memory for a TariContact
 ///
 /// ## Arguments
-/// `liveness_data` - The pointer to a TariContactsLivenessData
+/// `contact` - The pointer to a TariContact
 ///
 /// ## Returns
 /// `()` - Does not return a value, equivalent to void in C
@@ -3090,90 +3311,86 @@ pub unsafe extern "C" fn liveness_data_get_online_status(
 /// # Safety
 /// None
 #[no_mangle]
-pub unsafe extern "C" fn liveness_data_destroy(liveness_data: *mut TariContactsLivenessData) {
-    if !liveness_data.is_null() {
-        drop(Box::from_raw(liveness_data))
+pub unsafe extern "C" fn contact_destroy(contact: *mut TariContact) {
+    if !contact.is_null() {
+        drop(Box::from_raw(contact))
     }
 }
+
 /// -------------------------------------------------------------------------------------------- ///
 
-/// ----------------------------------- CompletedTransactions ----------------------------------- ///
+/// ----------------------------------- Contacts -------------------------------------------------///
 
-/// Gets the length of a TariCompletedTransactions
+/// Gets the length of TariContacts
 ///
 /// ## Arguments
-/// `transactions` - The pointer to a TariCompletedTransactions
+/// `contacts` - The pointer to a TariContacts
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `c_uint` - Returns the number of elements in a TariCompletedTransactions, note that it will be
-/// zero if transactions is null
+/// `c_uint` - Returns number of elements in , zero if contacts is null
 ///
 /// # Safety
 /// None
-// casting here is okay as we wont have more than u32 transctions
+// casting here is okay as we dont have more thant u32 contacts
 #[allow(clippy::cast_possible_truncation)]
 #[no_mangle]
-pub unsafe extern "C" fn completed_transactions_get_length(
-    transactions: *mut TariCompletedTransactions,
-    error_out: *mut c_int,
-) -> c_uint {
+pub unsafe extern "C" fn contacts_get_length(contacts: *mut TariContacts, error_out: *mut c_int) -> c_uint {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
     let mut len = 0;
-    if transactions.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("transaction".to_string())).code;
+    if contacts.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("contacts".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
     } else {
-        len = (*transactions).0.len();
+        len = (*contacts).0.len();
     }
     len as c_uint
 }
 
-/// Gets a TariCompletedTransaction from a TariCompletedTransactions at position
+/// Gets a TariContact from TariContacts at position
 ///
 /// ## Arguments
-/// `transactions` - The pointer to a TariCompletedTransactions
+/// `contacts` - The pointer to a TariContacts
 /// `position` - The integer position
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `*mut TariCompletedTransaction` - Returns a pointer to a TariCompletedTransaction,
-/// note that ptr::null_mut() is returned if transactions is null or position is invalid
+/// `*mut TariContact` - Returns a TariContact, note that it returns ptr::null_mut() if contacts is
+/// null or position is invalid
 ///
 /// # Safety
-/// The ```completed_transaction_destroy``` method must be called when finished with a TariCompletedTransaction to
-/// prevent a memory leak
+/// The ```contact_destroy``` method must be called when finished with a TariContact to prevent a memory leak
 // converting between here is fine as its used to clamp the the array to length
 #[allow(clippy::cast_possible_wrap)]
 #[no_mangle]
-pub unsafe extern "C" fn completed_transactions_get_at(
-    transactions: *mut TariCompletedTransactions,
+pub unsafe extern "C" fn contacts_get_at(
+    contacts: *mut TariContacts,
     position: c_uint,
     error_out: *mut c_int,
-) -> *mut TariCompletedTransaction {
+) -> *mut TariContact {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
-    if transactions.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("transactions".to_string())).code;
+    if contacts.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("contacts".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
         return ptr::null_mut();
     }
-    let len = completed_transactions_get_length(transactions, error_out) as c_int - 1;
+    let len = contacts_get_length(contacts, error_out) as c_int - 1;
     if len < 0 || position > len as c_uint {
         error = LibWalletError::from(InterfaceError::PositionInvalidError).code;
         ptr::swap(error_out, &mut error as *mut c_int);
         return ptr::null_mut();
     }
-    Box::into_raw(Box::new((*transactions).0[position as usize].clone()))
+    Box::into_raw(Box::new((*contacts).0[position as usize].clone()))
 }
 
-/// Frees memory for a TariCompletedTransactions
+/// Frees memory for a TariContacts
 ///
 /// ## Arguments
-/// `transactions` - The pointer to a TariCompletedTransaction
+/// `contacts` - The pointer to a TariContacts
 ///
 /// ## Returns
 /// `()` - Does not return a value, equivalent to void in C
@@ -3181,127 +3398,242 @@ pub unsafe extern "C" fn completed_transactions_get_at(
 /// # Safety
 /// None
 #[no_mangle]
-pub unsafe extern "C" fn completed_transactions_destroy(transactions: *mut TariCompletedTransactions) {
-    if !transactions.is_null() {
-        drop(Box::from_raw(transactions))
+pub unsafe extern "C" fn contacts_destroy(contacts: *mut TariContacts) {
+    if !contacts.is_null() {
+        drop(Box::from_raw(contacts))
     }
 }
 
 /// -------------------------------------------------------------------------------------------- ///
 
-/// ----------------------------------- OutboundTransactions ------------------------------------ ///
+/// ----------------------------------- Contacts Liveness Data ----------------------------------///
 
-/// Gets the length of a TariPendingOutboundTransactions
+/// Gets the public_key from a TariContactsLivenessData
 ///
 /// ## Arguments
-/// `transactions` - The pointer to a TariPendingOutboundTransactions
+/// `liveness_data` - The pointer to a TariContactsLivenessData
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `c_uint` - Returns the number of elements in a TariPendingOutboundTransactions, note that it will be
-/// zero if transactions is null
+/// `*mut TariWalletAddress` - Returns a pointer to a TariWalletAddress. Note that it returns ptr::null_mut() if
+/// liveness_data is null.
 ///
 /// # Safety
-/// None
-// casting here is we wont have more than u32 transctions
-#[allow(clippy::cast_possible_truncation)]
+/// The ```liveness_data_destroy``` method must be called when finished with a TariContactsLivenessData to prevent a
+/// memory leak
 #[no_mangle]
-pub unsafe extern "C" fn pending_outbound_transactions_get_length(
-    transactions: *mut TariPendingOutboundTransactions,
+pub unsafe extern "C" fn liveness_data_get_public_key(
+    liveness_data: *mut TariContactsLivenessData,
     error_out: *mut c_int,
-) -> c_uint {
+) -> *mut TariWalletAddress {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
-    let mut len = 0;
-    if transactions.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("transaction".to_string())).code;
+    if liveness_data.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("liveness_data".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-    } else {
-        len = (*transactions).0.len();
+        return ptr::null_mut();
     }
-
-    len as c_uint
+    Box::into_raw(Box::new((*liveness_data).address().clone()))
 }
 
-/// Gets a TariPendingOutboundTransaction of a TariPendingOutboundTransactions
+/// Gets the latency in milli-seconds (ms) from a TariContactsLivenessData
 ///
 /// ## Arguments
-/// `transactions` - The pointer to a TariPendingOutboundTransactions
-/// `position` - The integer position
+/// `liveness_data` - The pointer to a TariContactsLivenessData
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `*mut TariPendingOutboundTransaction` - Returns a pointer to a TariPendingOutboundTransaction,
-/// note that ptr::null_mut() is returned if transactions is null or position is invalid
+/// `*mut c_int` - Returns a pointer to a c_int if the optional latency data (in milli-seconds (ms)) exists, with a
+/// value of '0' if it is None. Note that it also returns '0' if liveness_data is null.
 ///
 /// # Safety
-/// The ```pending_outbound_transaction_destroy``` method must be called when finished with a
-/// TariPendingOutboundTransaction to prevent a memory leak
-// converting between here is fine as its used to clamp the the array to length
-#[allow(clippy::cast_possible_wrap)]
+/// The ```liveness_data_destroy``` method must be called when finished with a TariContactsLivenessData to prevent a
+/// memory leak
+
 #[no_mangle]
-pub unsafe extern "C" fn pending_outbound_transactions_get_at(
-    transactions: *mut TariPendingOutboundTransactions,
-    position: c_uint,
+pub unsafe extern "C" fn liveness_data_get_latency(
+    liveness_data: *mut TariContactsLivenessData,
     error_out: *mut c_int,
-) -> *mut TariPendingOutboundTransaction {
+) -> c_uint {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
-    if transactions.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("transaction".to_string())).code;
+    if liveness_data.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("liveness_data".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        return ptr::null_mut();
+        return 0;
     }
-    let len = pending_outbound_transactions_get_length(transactions, error_out) as c_int - 1;
-    if len < 0 || position > len as c_uint {
-        error = LibWalletError::from(InterfaceError::PositionInvalidError).code;
-        ptr::swap(error_out, &mut error as *mut c_int);
-        return ptr::null_mut();
+    if let Some(latency) = (*liveness_data).latency() {
+        latency as c_uint
+    } else {
+        0
     }
-    Box::into_raw(Box::new((*transactions).0[position as usize].clone()))
 }
 
-/// Frees memory for a TariPendingOutboundTransactions
+/// Gets the last_seen time (in local time) from a TariContactsLivenessData
 ///
 /// ## Arguments
-/// `transactions` - The pointer to a TariPendingOutboundTransactions
+/// `liveness_data` - The pointer to a TariContactsLivenessData
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
 ///
 /// ## Returns
-/// `()` - Does not return a value, equivalent to void in C
+/// `*mut c_char` - Returns a pointer to a char array if the optional last_seen data exists, with a value of '?' if it
+/// is None. Note that it returns ptr::null_mut() if liveness_data is null.
 ///
 /// # Safety
-/// None
+/// The ```liveness_data_destroy``` method must be called when finished with a TariContactsLivenessData to prevent a
+/// memory leak
 #[no_mangle]
-pub unsafe extern "C" fn pending_outbound_transactions_destroy(transactions: *mut TariPendingOutboundTransactions) {
-    if !transactions.is_null() {
-        drop(Box::from_raw(transactions))
+pub unsafe extern "C" fn liveness_data_get_last_seen(
+    liveness_data: *mut TariContactsLivenessData,
+    error_out: *mut c_int,
+) -> *mut c_char {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if liveness_data.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("liveness_data".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return ptr::null_mut();
+    }
+    if let Some(last_seen) = (*liveness_data).last_ping_pong_received() {
+        let last_seen_local_time = DateTime::<Local>::from_utc(last_seen, Local::now().offset().to_owned())
+            .format("%FT%T")
+            .to_string();
+        let mut return_value = CString::new("").expect("Blank CString will not fail.");
+        match CString::new(last_seen_local_time) {
+            Ok(val) => {
+                return_value = val;
+            },
+            _ => {
+                error = LibWalletError::from(InterfaceError::PointerError("liveness_data".to_string())).code;
+                ptr::swap(error_out, &mut error as *mut c_int);
+            },
+        }
+        CString::into_raw(return_value)
+    } else {
+        CString::into_raw(CString::new("?").expect("Single character CString will not fail."))
     }
 }
 
-/// -------------------------------------------------------------------------------------------- ///
-
-/// ----------------------------------- InboundTransactions ------------------------------------- ///
-
-/// Gets the length of a TariPendingInboundTransactions
+/// Gets the message_type (ContactMessageType enum) from a TariContactsLivenessData
 ///
 /// ## Arguments
-/// `transactions` - The pointer to a TariPendingInboundTransactions
+/// `liveness_data` - The pointer to a TariContactsLivenessData
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `c_uint` - Returns the number of elements in a TariPendingInboundTransactions, note that
-/// it will be zero if transactions is null
-///
-/// # Safety
+/// `c_int` - Returns the status which corresponds to:
+/// | Value | Interpretation |
+/// |---|---|
+/// |  -1 | NullError        |
+/// |   0 | Ping             |
+/// |   1 | Pong             |
+/// |   2 | NoMessage        |
+///
+/// # Safety
+/// The ```liveness_data_destroy``` method must be called when finished with a TariContactsLivenessData to prevent a
+/// memory leak
+#[no_mangle]
+pub unsafe extern "C" fn liveness_data_get_message_type(
+    liveness_data: *mut TariContactsLivenessData,
+    error_out: *mut c_int,
+) -> c_int {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if liveness_data.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("liveness_data".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return -1;
+    }
+    let status = (*liveness_data).message_type();
+    status as c_int
+}
+
+/// Gets the online_status (ContactOnlineStatus enum) from a TariContactsLivenessData
+///
+/// ## Arguments
+/// `liveness_data` - The pointer to a TariContactsLivenessData
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `c_int` - Returns the status which corresponds to:
+/// | Value | Interpretation |
+/// |---|---|
+/// |  -1 | NullError        |
+/// |   0 | Online           |
+/// |   1 | Offline          |
+/// |   2 | NeverSeen        |
+/// |   3 | Banned           |
+///
+/// # Safety
+/// The ```liveness_data_destroy``` method must be called when finished with a TariContactsLivenessData to prevent a
+/// memory leak
+#[no_mangle]
+pub unsafe extern "C" fn liveness_data_get_online_status(
+    liveness_data: *mut TariContactsLivenessData,
+    error_out: *mut c_int,
+) -> *const c_char {
+    let mut error = 0;
+    let mut result = CString::new("").expect("Blank CString will not fail.");
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if liveness_data.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("liveness_data".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return result.into_raw();
+    }
+    let status = (*liveness_data).online_status();
+    match CString::new(status.to_string()) {
+        Ok(v) => result = v,
+        _ => {
+            error = LibWalletError::from(InterfaceError::PointerError("message".to_string())).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+        },
+    }
+    result.into_raw()
+}
+
+/// Frees memory for a TariContactsLivenessData
+///
+/// ## Arguments
+/// `liveness_data` - The pointer to a TariContactsLivenessData
+///
+/// ## Returns
+/// `()` - Does not return a value, equivalent to void in C
+///
+/// # Safety
 /// None
-// casting here is okay as we wont have mroe than u32 tranasctions
+#[no_mangle]
+pub unsafe extern "C" fn liveness_data_destroy(liveness_data: *mut TariContactsLivenessData) {
+    if !liveness_data.is_null() {
+        drop(Box::from_raw(liveness_data))
+    }
+}
+/// -------------------------------------------------------------------------------------------- ///
+
+/// ----------------------------------- CompletedTransactions ----------------------------------- ///
+
+/// Gets the length of a TariCompletedTransactions
+///
+/// ## Arguments
+/// `transactions` - The pointer to a TariCompletedTransactions
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `c_uint` - Returns the number of elements in a TariCompletedTransactions, note that it will be
+/// zero if transactions is null
+///
+/// # Safety
+/// None
+// casting here is okay as we wont have more than u32 transctions
 #[allow(clippy::cast_possible_truncation)]
 #[no_mangle]
-pub unsafe extern "C" fn pending_inbound_transactions_get_length(
-    transactions: *mut TariPendingInboundTransactions,
+pub unsafe extern "C" fn completed_transactions_get_length(
+    transactions: *mut TariCompletedTransactions,
     error_out: *mut c_int,
 ) -> c_uint {
     let mut error = 0;
@@ -3316,37 +3648,37 @@ pub unsafe extern "C" fn pending_inbound_transactions_get_length(
     len as c_uint
 }
 
-/// Gets a TariPendingInboundTransaction of a TariPendingInboundTransactions
+/// Gets a TariCompletedTransaction from a TariCompletedTransactions at position
 ///
 /// ## Arguments
-/// `transactions` - The pointer to a TariPendingInboundTransactions
+/// `transactions` - The pointer to a TariCompletedTransactions
 /// `position` - The integer position
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `*mut TariPendingOutboundTransaction` - Returns a pointer to a TariPendingInboundTransaction,
+/// `*mut TariCompletedTransaction` - Returns a pointer to a TariCompletedTransaction,
 /// note that ptr::null_mut() is returned if transactions is null or position is invalid
 ///
 /// # Safety
-/// The ```pending_inbound_transaction_destroy``` method must be called when finished with a
-/// TariPendingOutboundTransaction to prevent a memory leak
+/// The ```completed_transaction_destroy``` method must be called when finished with a TariCompletedTransaction to
+/// prevent a memory leak
 // converting between here is fine as its used to clamp the the array to length
 #[allow(clippy::cast_possible_wrap)]
 #[no_mangle]
-pub unsafe extern "C" fn pending_inbound_transactions_get_at(
-    transactions: *mut TariPendingInboundTransactions,
+pub unsafe extern "C" fn completed_transactions_get_at(
+    transactions: *mut TariCompletedTransactions,
     position: c_uint,
     error_out: *mut c_int,
-) -> *mut TariPendingInboundTransaction {
+) -> *mut TariCompletedTransaction {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
     if transactions.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("transaction".to_string())).code;
+        error = LibWalletError::from(InterfaceError::NullError("transactions".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
         return ptr::null_mut();
     }
-    let len = pending_inbound_transactions_get_length(transactions, error_out) as c_int - 1;
+    let len = completed_transactions_get_length(transactions, error_out) as c_int - 1;
     if len < 0 || position > len as c_uint {
         error = LibWalletError::from(InterfaceError::PositionInvalidError).code;
         ptr::swap(error_out, &mut error as *mut c_int);
@@ -3355,10 +3687,10 @@ pub unsafe extern "C" fn pending_inbound_transactions_get_at(
     Box::into_raw(Box::new((*transactions).0[position as usize].clone()))
 }
 
-/// Frees memory for a TariPendingInboundTransactions
+/// Frees memory for a TariCompletedTransactions
 ///
 /// ## Arguments
-/// `transactions` - The pointer to a TariPendingInboundTransactions
+/// `transactions` - The pointer to a TariCompletedTransaction
 ///
 /// ## Returns
 /// `()` - Does not return a value, equivalent to void in C
@@ -3366,7 +3698,7 @@ pub unsafe extern "C" fn pending_inbound_transactions_get_at(
 /// # Safety
 /// None
 #[no_mangle]
-pub unsafe extern "C" fn pending_inbound_transactions_destroy(transactions: *mut TariPendingInboundTransactions) {
+pub unsafe extern "C" fn completed_transactions_destroy(transactions: *mut TariCompletedTransactions) {
     if !transactions.is_null() {
         drop(Box::from_raw(transactions))
     }
@@ -3374,132 +3706,317 @@ pub unsafe extern "C" fn pending_inbound_transactions_destroy(transactions: *mut
 
 /// -------------------------------------------------------------------------------------------- ///
 
-/// ----------------------------------- CompletedTransaction ------------------------------------- ///
+/// ----------------------------------- OutboundTransactions ------------------------------------ ///
 
-/// Gets the TransactionID of a TariCompletedTransaction
+/// Gets the length of a TariPendingOutboundTransactions
 ///
 /// ## Arguments
-/// `transaction` - The pointer to a TariCompletedTransaction
+/// `transactions` - The pointer to a TariPendingOutboundTransactions
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `c_ulonglong` - Returns the TransactionID, note that it will be zero if transaction is null
+/// `c_uint` - Returns the number of elements in a TariPendingOutboundTransactions, note that it will be
+/// zero if transactions is null
 ///
 /// # Safety
 /// None
+// casting here is we wont have more than u32 transctions
+#[allow(clippy::cast_possible_truncation)]
 #[no_mangle]
-pub unsafe extern "C" fn completed_transaction_get_transaction_id(
-    transaction: *mut TariCompletedTransaction,
+pub unsafe extern "C" fn pending_outbound_transactions_get_length(
+    transactions: *mut TariPendingOutboundTransactions,
     error_out: *mut c_int,
-) -> c_ulonglong {
+) -> c_uint {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
-    if transaction.is_null() {
+    let mut len = 0;
+    if transactions.is_null() {
         error = LibWalletError::from(InterfaceError::NullError("transaction".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        return 0;
+    } else {
+        len = (*transactions).0.len();
     }
-    (*transaction).tx_id.as_u64() as c_ulonglong
+
+    len as c_uint
 }
 
-/// Gets the destination TariWalletAddress of a TariCompletedTransaction
+/// Gets a TariPendingOutboundTransaction of a TariPendingOutboundTransactions
 ///
 /// ## Arguments
-/// `transaction` - The pointer to a TariCompletedTransaction
+/// `transactions` - The pointer to a TariPendingOutboundTransactions
+/// `position` - The integer position
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `*mut TariWalletAddress` - Returns the destination TariWalletAddress, note that it will be
-/// ptr::null_mut() if transaction is null
+/// `*mut TariPendingOutboundTransaction` - Returns a pointer to a TariPendingOutboundTransaction,
+/// note that ptr::null_mut() is returned if transactions is null or position is invalid
 ///
 /// # Safety
-/// The ```tari_address_destroy``` method must be called when finished with a TariWalletAddress to prevent a memory leak
+/// The ```pending_outbound_transaction_destroy``` method must be called when finished with a
+/// TariPendingOutboundTransaction to prevent a memory leak
+// converting between here is fine as its used to clamp the the array to length
+#[allow(clippy::cast_possible_wrap)]
 #[no_mangle]
-pub unsafe extern "C" fn completed_transaction_get_destination_tari_address(
-    transaction: *mut TariCompletedTransaction,
+pub unsafe extern "C" fn pending_outbound_transactions_get_at(
+    transactions: *mut TariPendingOutboundTransactions,
+    position: c_uint,
     error_out: *mut c_int,
-) -> *mut TariWalletAddress {
+) -> *mut TariPendingOutboundTransaction {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
-    if transaction.is_null() {
+    if transactions.is_null() {
         error = LibWalletError::from(InterfaceError::NullError("transaction".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
         return ptr::null_mut();
     }
-    let address = (*transaction).destination_address.clone();
-    Box::into_raw(Box::new(address))
+    let len = pending_outbound_transactions_get_length(transactions, error_out) as c_int - 1;
+    if len < 0 || position > len as c_uint {
+        error = LibWalletError::from(InterfaceError::PositionInvalidError).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return ptr::null_mut();
+    }
+    Box::into_raw(Box::new((*transactions).0[position as usize].clone()))
 }
 
-/// Gets the TariTransactionKernel of a TariCompletedTransaction
+/// Frees memory for a TariPendingOutboundTransactions
 ///
 /// ## Arguments
-/// `transaction` - The pointer to a TariCompletedTransaction
-/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
-/// as an out parameter.
+/// `transactions` - The pointer to a TariPendingOutboundTransactions
 ///
 /// ## Returns
-/// `*mut TariTransactionKernel` - Returns the transaction kernel, note that it will be
-/// ptr::null_mut() if transaction is null, if the transaction status is Pending, or if the number of kernels is not
-/// exactly one.
+/// `()` - Does not return a value, equivalent to void in C
 ///
 /// # Safety
-/// The ```transaction_kernel_destroy``` method must be called when finished with a TariTransactionKernel to prevent a
-/// memory leak
+/// None
 #[no_mangle]
-pub unsafe extern "C" fn completed_transaction_get_transaction_kernel(
-    transaction: *mut TariCompletedTransaction,
-    error_out: *mut c_int,
-) -> *mut TariTransactionKernel {
-    let mut error = 0;
-    ptr::swap(error_out, &mut error as *mut c_int);
-    if transaction.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("transaction".to_string())).code;
-        ptr::swap(error_out, &mut error as *mut c_int);
-        return ptr::null_mut();
-    }
-
-    // check the tx is not in pending state
-    if matches!(
-        (*transaction).status,
-        TransactionStatus::Pending | TransactionStatus::Imported
-    ) {
-        let msg = format!("Incorrect transaction status: {}", (*transaction).status);
-        error = LibWalletError::from(TransactionError::StatusError(msg)).code;
-        ptr::swap(error_out, &mut error as *mut c_int);
-        return ptr::null_mut();
+pub unsafe extern "C" fn pending_outbound_transactions_destroy(transactions: *mut TariPendingOutboundTransactions) {
+    if !transactions.is_null() {
+        drop(Box::from_raw(transactions))
     }
+}
 
-    let kernels = (*transaction).transaction.body().kernels();
-
-    // currently we presume that each CompletedTransaction only has 1 kernel
-    // if that changes this will need to be accounted for
-    if kernels.len() != 1 {
-        let msg = format!("Expected 1 kernel, got {}", kernels.len());
-        error = LibWalletError::from(TransactionError::KernelError(msg)).code;
-        ptr::swap(error_out, &mut error as *mut c_int);
-        return ptr::null_mut();
-    }
+/// -------------------------------------------------------------------------------------------- ///
 
-    let x = kernels[0].clone();
-    Box::into_raw(Box::new(x))
-}
+/// ----------------------------------- InboundTransactions ------------------------------------- ///
 
-/// Gets the source TariWalletAddress of a TariCompletedTransaction
+/// Gets the length of a TariPendingInboundTransactions
 ///
 /// ## Arguments
-/// `transaction` - The pointer to a TariCompletedTransaction
+/// `transactions` - The pointer to a TariPendingInboundTransactions
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `*mut TariWalletAddress` - Returns the source TariWalletAddress, note that it will be
-/// ptr::null_mut() if transaction is null
+/// `c_uint` - Returns the number of elements in a TariPendingInboundTransactions, note that
+/// it will be zero if transactions is null
 ///
 /// # Safety
-/// The ```tari_address_destroy``` method must be called when finished with a TariWalletAddress to prevent a memory leak
-#[no_mangle]
+/// None
+// casting here is okay as we wont have mroe than u32 tranasctions
+#[allow(clippy::cast_possible_truncation)]
+#[no_mangle]
+pub unsafe extern "C" fn pending_inbound_transactions_get_length(
+    transactions: *mut TariPendingInboundTransactions,
+    error_out: *mut c_int,
+) -> c_uint {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    let mut len = 0;
+    if transactions.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("transaction".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+    } else {
+        len = (*transactions).0.len();
+    }
+    len as c_uint
+}
+
+/// Gets a TariPendingInboundTransaction of a TariPendingInboundTransactions
+///
+/// ## Arguments
+/// `transactions` - The pointer to a TariPendingInboundTransactions
+/// `position` - The integer position
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `*mut TariPendingOutboundTransaction` - Returns a pointer to a TariPendingInboundTransaction,
+/// note that ptr::null_mut() is returned if transactions is null or position is invalid
+///
+/// # Safety
+/// The ```pending_inbound_transaction_destroy``` method must be called when finished with a
+/// TariPendingOutboundTransaction to prevent a memory leak
+// converting between here is fine as its used to clamp the the array to length
+#[allow(clippy::cast_possible_wrap)]
+#[no_mangle]
+pub unsafe extern "C" fn pending_inbound_transactions_get_at(
+    transactions: *mut TariPendingInboundTransactions,
+    position: c_uint,
+    error_out: *mut c_int,
+) -> *mut TariPendingInboundTransaction {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if transactions.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("transaction".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return ptr::null_mut();
+    }
+    let len = pending_inbound_transactions_get_length(transactions, error_out) as c_int - 1;
+    if len < 0 || position > len as c_uint {
+        error = LibWalletError::from(InterfaceError::PositionInvalidError).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return ptr::null_mut();
+    }
+    Box::into_raw(Box::new((*transactions).0[position as usize].clone()))
+}
+
+/// Frees memory for a TariPendingInboundTransactions
+///
+/// ## Arguments
+/// `transactions` - The pointer to a TariPendingInboundTransactions
+///
+/// ## Returns
+/// `()` - Does not return a value, equivalent to void in C
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn pending_inbound_transactions_destroy(transactions: *mut TariPendingInboundTransactions) {
+    if !transactions.is_null() {
+        drop(Box::from_raw(transactions))
+    }
+}
+
+/// -------------------------------------------------------------------------------------------- ///
+
+/// ----------------------------------- CompletedTransaction ------------------------------------- ///
+
+/// Gets the TransactionID of a TariCompletedTransaction
+///
+/// ## Arguments
+/// `transaction` - The pointer to a TariCompletedTransaction
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `c_ulonglong` - Returns the TransactionID, note that it will be zero if transaction is null
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn completed_transaction_get_transaction_id(
+    transaction: *mut TariCompletedTransaction,
+    error_out: *mut c_int,
+) -> c_ulonglong {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if transaction.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("transaction".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return 0;
+    }
+    (*transaction).tx_id.as_u64() as c_ulonglong
+}
+
+/// Gets the destination TariWalletAddress of a TariCompletedTransaction
+///
+/// ## Arguments
+/// `transaction` - The pointer to a TariCompletedTransaction
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `*mut TariWalletAddress` - Returns the destination TariWalletAddress, note that it will be
+/// ptr::null_mut() if transaction is null
+///
+/// # Safety
+/// The ```tari_address_destroy``` method must be called when finished with a TariWalletAddress to prevent a memory leak
+#[no_mangle]
+pub unsafe extern "C" fn completed_transaction_get_destination_tari_address(
+    transaction: *mut TariCompletedTransaction,
+    error_out: *mut c_int,
+) -> *mut TariWalletAddress {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if transaction.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("transaction".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return ptr::null_mut();
+    }
+    let address = (*transaction).destination_address.clone();
+    Box::into_raw(Box::new(address))
+}
+
+/// Gets the TariTransactionKernel of a TariCompletedTransaction
+///
+/// ## Arguments
+/// `transaction` - The pointer to a TariCompletedTransaction
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `*mut TariTransactionKernel` - Returns the transaction kernel, note that it will be
+/// ptr::null_mut() if transaction is null, if the transaction status is Pending, or if the number of kernels is not
+/// exactly one.
+///
+/// # Safety
+/// The ```transaction_kernel_destroy``` method must be called when finished with a TariTransactionKernel to prevent a
+/// memory leak
+#[no_mangle]
+pub unsafe extern "C" fn completed_transaction_get_transaction_kernel(
+    transaction: *mut TariCompletedTransaction,
+    error_out: *mut c_int,
+) -> *mut TariTransactionKernel {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if transaction.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("transaction".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return ptr::null_mut();
+    }
+
+    // check the tx is not in pending state
+    if matches!(
+        (*transaction).status,
+        TransactionStatus::Pending | TransactionStatus::Imported
+    ) {
+        let msg = format!("Incorrect transaction status: {}", (*transaction).status);
+        error = LibWalletError::from(TransactionError::StatusError(msg)).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return ptr::null_mut();
+    }
+
+    let kernels = (*transaction).transaction.body().kernels();
+
+    // currently we presume that each CompletedTransaction only has 1 kernel
+    // if that changes this will need to be accounted for
+    if kernels.len() != 1 {
+        let msg = format!("Expected 1 kernel, got {}", kernels.len());
+        error = LibWalletError::from(TransactionError::KernelError(msg)).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return ptr::null_mut();
+    }
+
+    let x = kernels[0].clone();
+    Box::into_raw(Box::new(x))
+}
+
+/// Gets the source TariWalletAddress of a TariCompletedTransaction
+///
+/// ## Arguments
+/// `transaction` - The pointer to a TariCompletedTransaction
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `*mut TariWalletAddress` - Returns the source TariWalletAddress, note that it will be
+/// ptr::null_mut() if transaction is null
+///
+/// # Safety
+/// The ```tari_address_destroy``` method must be called when finished with a TariWalletAddress to prevent a memory leak
+#[no_mangle]
 pub unsafe extern "C" fn completed_transaction_get_source_tari_address(
     transaction: *mut TariCompletedTransaction,
     error_out: *mut c_int,
@@ -4829,6 +5346,9 @@ pub unsafe extern "C" fn comms_config_create(
                 user_agent: format!("tari/mobile_wallet/{}", env!("CARGO_PKG_VERSION")),
                 rpc_max_simultaneous_sessions: 0,
                 rpc_max_sessions_per_peer: 0,
+                rpc_compression: Default::default(),
+                max_substreams_per_protocol: 0,
+                max_connections: 0,
                 listener_liveness_check_interval: None,
             };
 
@@ -5133,6 +5653,8 @@ unsafe fn init_logging(
 /// when a Broadcast transaction is detected as mined AND confirmed.
 /// `callback_transaction_mined_unconfirmed` - The callback function pointer matching the function signature. This will
 /// be called when a Broadcast transaction is detected as mined but not yet confirmed.
+/// `callback_transaction_unmined` - The callback function pointer matching the function signature. This will be called
+/// when the block a mined transaction was in has been reorged out of the chain.
 /// `callback_faux_transaction_confirmed` - The callback function pointer matching the function signature. This will be
 /// called when a one-sided transaction is detected as mined AND confirmed.
 /// `callback_faux_transaction_unconfirmed` - The callback function pointer matching the function signature. This
@@ -5187,6 +5709,19 @@ unsafe fn init_logging(
 ///     Online,         // 1
 ///     Offline,        // 2
 /// }
+/// `callback_scheduled_payment_status` - The callback function pointer matching the function signature. This is called
+/// when a scheduled payment is sent, deferred, fails or completes its schedule. The first parameter is the id of the
+/// scheduled payment, the second the TxId of the sent transaction (0 if no transaction was sent) and the third the
+/// status:
+///     PaymentSent,                    // 0
+///     DeferredInsufficientBalance,    // 1
+///     DeferredOffline,                // 2
+///     PaymentFailed,                  // 3
+///     ScheduleCompleted,              // 4
+/// `callback_transaction_approval_required` - The callback function pointer matching the function signature. This is
+/// called when an outbound transaction exceeds the configured per-transaction or daily spending limit. The transaction
+/// is held until it is approved with `wallet_approve_transaction` or rejected with `wallet_reject_transaction`. The
+/// first parameter is the approval_id and the second the amount of the transaction in MicroMinotari.
 /// `recovery_in_progress` - Pointer to an bool which will be modified to indicate if there is an outstanding recovery
 /// that should be completed or not to an error code should one occur, may not be null. Functions as an out parameter.
 /// `error_out` - Pointer to an int which will be modified
@@ -5214,6 +5749,7 @@ pub unsafe extern "C" fn wallet_create(
     callback_transaction_broadcast: unsafe extern "C" fn(*mut TariCompletedTransaction),
     callback_transaction_mined: unsafe extern "C" fn(*mut TariCompletedTransaction),
     callback_transaction_mined_unconfirmed: unsafe extern "C" fn(*mut TariCompletedTransaction, u64),
+    callback_transaction_unmined: unsafe extern "C" fn(*mut TariCompletedTransaction),
     callback_faux_transaction_confirmed: unsafe extern "C" fn(*mut TariCompletedTransaction),
     callback_faux_transaction_unconfirmed: unsafe extern "C" fn(*mut TariCompletedTransaction, u64),
     callback_transaction_send_result: unsafe extern "C" fn(c_ulonglong, *mut TariTransactionSendStatus),
@@ -5225,6 +5761,8 @@ pub unsafe extern "C" fn wallet_create(
     callback_saf_messages_received: unsafe extern "C" fn(),
     callback_connectivity_status: unsafe extern "C" fn(u64),
     callback_base_node_state: unsafe extern "C" fn(*mut TariBaseNodeState),
+    callback_scheduled_payment_status: unsafe extern "C" fn(c_ulonglong, c_ulonglong, c_ulonglong),
+    callback_transaction_approval_required: unsafe extern "C" fn(c_ulonglong, c_ulonglong),
     recovery_in_progress: *mut bool,
     error_out: *mut c_int,
 ) -> *mut TariWallet {
@@ -5467,12 +6005,14 @@ pub unsafe extern "C" fn wallet_create(
                 wallet_address,
                 w.wallet_connectivity.get_connectivity_status_watch(),
                 w.contacts_service.get_contacts_liveness_event_stream(),
+                w.scheduled_payments_service.get_event_stream(),
                 callback_received_transaction,
                 callback_received_transaction_reply,
                 callback_received_finalized_transaction,
                 callback_transaction_broadcast,
                 callback_transaction_mined,
                 callback_transaction_mined_unconfirmed,
+                callback_transaction_unmined,
                 callback_faux_transaction_confirmed,
                 callback_faux_transaction_unconfirmed,
                 callback_transaction_send_result,
@@ -5484,6 +6024,8 @@ pub unsafe extern "C" fn wallet_create(
                 callback_saf_messages_received,
                 callback_connectivity_status,
                 callback_base_node_state,
+                callback_scheduled_payment_status,
+                callback_transaction_approval_required,
             );
 
             runtime.spawn(callback_handler.start());
@@ -5625,61 +6167,146 @@ pub unsafe extern "C" fn wallet_get_balance(wallet: *mut TariWallet, error_out:
     }
 }
 
-/// This function returns a list of unspent UTXO values and commitments.
+/// Gets the recorded balance history of a TariWallet
 ///
 /// ## Arguments
-/// * `wallet` - The TariWallet pointer,
-/// * `page` - Page offset,
-/// * `page_size` - A number of items per page,
-/// * `sorting` - An enum representing desired sorting,
-/// * `dust_threshold` - A value filtering threshold. Outputs whose values are <= `dust_threshold` are not listed in the
-/// result.
-/// * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null.
-/// Functions as an out parameter.
+/// `wallet` - The TariWallet pointer
+/// `from_timestamp` - The unix timestamp (in seconds) of the start of the range
+/// `to_timestamp` - The unix timestamp (in seconds) of the end of the range, or 0 for the current time
+/// `resolution` - The granularity of the snapshots: 0 for daily, 1 for per block
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
 ///
 /// ## Returns
-/// `*mut TariVector` - Returns a struct with an array pointer, length and capacity (needed for proper destruction
-/// after use).
+/// `*mut TariBalanceHistory` - Returns a pointer to a TariBalanceHistory, ordered from oldest to newest. Note that it
+/// returns ptr::null_mut() if wallet is null, the arguments are invalid or an error is encountered.
 ///
 /// # Safety
-/// `destroy_tari_vector()` must be called after use.
-/// Items that fail to produce `.as_transaction_output()` are omitted from the list and a `warn!()` message is logged to
-/// LOG_TARGET.
-// casting here is okay as we wont have more than u32 utxos
-#[allow(clippy::cast_possible_truncation)]
+/// The ```balance_history_destroy``` method must be called when finished with a TariBalanceHistory to prevent a memory
+/// leak
 #[no_mangle]
-pub unsafe extern "C" fn wallet_get_utxos(
+pub unsafe extern "C" fn wallet_get_balance_history(
     wallet: *mut TariWallet,
-    page: usize,
-    page_size: usize,
-    sorting: TariUtxoSort,
-    states: *mut TariVector,
-    dust_threshold: u64,
-    error_ptr: *mut i32,
-) -> *mut TariVector {
+    from_timestamp: c_ulonglong,
+    to_timestamp: c_ulonglong,
+    resolution: c_uint,
+    error_out: *mut c_int,
+) -> *mut TariBalanceHistory {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
     if wallet.is_null() {
-        error!(target: LOG_TARGET, "wallet pointer is null");
-        ptr::replace(
-            error_ptr,
-            LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code,
-        );
+        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
         return ptr::null_mut();
     }
 
-    let page = i64::from_usize(page).unwrap_or(i64::MAX);
-    let page_size = i64::from_usize(page_size).unwrap_or(i64::MAX);
-    let dust_threshold = i64::from_u64(dust_threshold).unwrap_or(0);
-
-    let status = {
-        if states.is_null() {
-            vec![]
-        } else {
-            Vec::from_raw_parts((*states).ptr as *mut u64, (*states).len, (*states).cap)
-                .into_iter()
-                .map(|x| OutputStatus::try_from(x as i32).unwrap())
-                .collect_vec()
-        }
-    };
+    let resolution = match i32::try_from(resolution)
+        .ok()
+        .and_then(|r| BalanceHistoryResolution::try_from(r).ok())
+    {
+        Some(r) => r,
+        None => {
+            error = LibWalletError::from(InterfaceError::InvalidArgument("resolution".to_string())).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            return ptr::null_mut();
+        },
+    };
+    let to_datetime = |timestamp: c_ulonglong| {
+        i64::try_from(timestamp)
+            .ok()
+            .and_then(|secs| NaiveDateTime::from_timestamp_opt(secs, 0))
+    };
+    let from = match to_datetime(from_timestamp) {
+        Some(from) => from,
+        None => {
+            error = LibWalletError::from(InterfaceError::InvalidArgument("from_timestamp".to_string())).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            return ptr::null_mut();
+        },
+    };
+    let to = if to_timestamp == 0 {
+        Local::now().naive_utc()
+    } else {
+        match to_datetime(to_timestamp) {
+            Some(to) => to,
+            None => {
+                error = LibWalletError::from(InterfaceError::InvalidArgument("to_timestamp".to_string())).code;
+                ptr::swap(error_out, &mut error as *mut c_int);
+                return ptr::null_mut();
+            },
+        }
+    };
+
+    match (*wallet).runtime.block_on(
+        (*wallet)
+            .wallet
+            .output_manager_service
+            .get_balance_history(resolution, from, to),
+    ) {
+        Ok(history) => Box::into_raw(Box::new(TariBalanceHistory(history))),
+        Err(e) => {
+            error = LibWalletError::from(WalletError::OutputManagerError(e)).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            ptr::null_mut()
+        },
+    }
+}
+
+/// This function returns a list of unspent UTXO values and commitments.
+///
+/// ## Arguments
+/// * `wallet` - The TariWallet pointer,
+/// * `page` - Page offset,
+/// * `page_size` - A number of items per page,
+/// * `sorting` - An enum representing desired sorting,
+/// * `dust_threshold` - A value filtering threshold. Outputs whose values are <= `dust_threshold` are not listed in the
+/// result.
+/// * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null.
+/// Functions as an out parameter.
+///
+/// ## Returns
+/// `*mut TariVector` - Returns a struct with an array pointer, length and capacity (needed for proper destruction
+/// after use).
+///
+/// # Safety
+/// `destroy_tari_vector()` must be called after use.
+/// Items that fail to produce `.as_transaction_output()` are omitted from the list and a `warn!()` message is logged to
+/// LOG_TARGET.
+// casting here is okay as we wont have more than u32 utxos
+#[allow(clippy::cast_possible_truncation)]
+#[no_mangle]
+pub unsafe extern "C" fn wallet_get_utxos(
+    wallet: *mut TariWallet,
+    page: usize,
+    page_size: usize,
+    sorting: TariUtxoSort,
+    states: *mut TariVector,
+    dust_threshold: u64,
+    error_ptr: *mut i32,
+) -> *mut TariVector {
+    if wallet.is_null() {
+        error!(target: LOG_TARGET, "wallet pointer is null");
+        ptr::replace(
+            error_ptr,
+            LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code,
+        );
+        return ptr::null_mut();
+    }
+
+    let page = i64::from_usize(page).unwrap_or(i64::MAX);
+    let page_size = i64::from_usize(page_size).unwrap_or(i64::MAX);
+    let dust_threshold = i64::from_u64(dust_threshold).unwrap_or(0);
+
+    let status = {
+        if states.is_null() {
+            vec![]
+        } else {
+            Vec::from_raw_parts((*states).ptr as *mut u64, (*states).len, (*states).cap)
+                .into_iter()
+                .map(|x| OutputStatus::try_from(x as i32).unwrap())
+                .collect_vec()
+        }
+    };
 
     use SortDirection::{Asc, Desc};
     let q = OutputBackendQuery {
@@ -6324,12 +6951,12 @@ pub unsafe extern "C" fn wallet_add_base_node_peer(
     true
 }
 
-/// Upserts a TariContact to the TariWallet. If the contact does not exist it will be Inserted. If it does exist the
-/// Alias will be updated.
+/// Adds the base node peer from a `tari://<network>/base_nodes/add` URI, as shown by a base node's `whoami` command, to
+/// the TariWallet. The first address in the URI is used.
 ///
 /// ## Arguments
 /// `wallet` - The TariWallet pointer
-/// `contact` - The TariContact pointer
+/// `uri` - The pointer to a char array containing the URI
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
@@ -6339,9 +6966,9 @@ pub unsafe extern "C" fn wallet_add_base_node_peer(
 /// # Safety
 /// None
 #[no_mangle]
-pub unsafe extern "C" fn wallet_upsert_contact(
+pub unsafe extern "C" fn wallet_add_base_node_peer_from_uri(
     wallet: *mut TariWallet,
-    contact: *mut TariContact,
+    uri: *const c_char,
     error_out: *mut c_int,
 ) -> bool {
     let mut error = 0;
@@ -6351,30 +6978,54 @@ pub unsafe extern "C" fn wallet_upsert_contact(
         ptr::swap(error_out, &mut error as *mut c_int);
         return false;
     }
-    if contact.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("contact".to_string())).code;
-        ptr::swap(error_out, &mut error as *mut c_int);
-        return false;
-    }
 
-    match (*wallet)
-        .runtime
-        .block_on((*wallet).wallet.contacts_service.upsert_contact((*contact).clone()))
-    {
-        Ok(_) => true,
+    let (public_key, addresses) = match ffi_tari_uri(uri) {
+        Ok(TariUri {
+            action: TariUriAction::AddBaseNode {
+                public_key, addresses, ..
+            },
+            ..
+        }) => (public_key, addresses),
+        Ok(_) => {
+            error = LibWalletError::from(InterfaceError::InvalidArgument(
+                "uri does not add a base node".to_string(),
+            ))
+            .code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            return false;
+        },
         Err(e) => {
-            error = LibWalletError::from(WalletError::ContactsServiceError(e)).code;
+            error = e.code;
             ptr::swap(error_out, &mut error as *mut c_int);
-            false
+            return false;
         },
+    };
+    let address = match addresses.first().map(|a| Multiaddr::from_str(a)) {
+        Some(Ok(address)) => address,
+        _ => {
+            error = LibWalletError::from(InterfaceError::InvalidArgument("address is invalid".to_string())).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            return false;
+        },
+    };
+
+    if let Err(e) = (*wallet)
+        .runtime
+        .block_on((*wallet).wallet.set_base_node_peer(public_key, address))
+    {
+        error = LibWalletError::from(e).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return false;
     }
+    true
 }
 
-/// Removes a TariContact from the TariWallet
+/// Adds a base node candidate that the TariWallet can fail over to if the active base node becomes unavailable
 ///
 /// ## Arguments
 /// `wallet` - The TariWallet pointer
-/// `tx` - The TariPendingInboundTransaction pointer
+/// `public_key` - The TariPublicKey pointer
+/// `address` - The pointer to a char array
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
@@ -6384,9 +7035,10 @@ pub unsafe extern "C" fn wallet_upsert_contact(
 /// # Safety
 /// None
 #[no_mangle]
-pub unsafe extern "C" fn wallet_remove_contact(
+pub unsafe extern "C" fn wallet_add_base_node_candidate(
     wallet: *mut TariWallet,
-    contact: *mut TariContact,
+    public_key: *mut TariPublicKey,
+    address: *const c_char,
     error_out: *mut c_int,
 ) -> bool {
     let mut error = 0;
@@ -6396,110 +7048,301 @@ pub unsafe extern "C" fn wallet_remove_contact(
         ptr::swap(error_out, &mut error as *mut c_int);
         return false;
     }
-    if contact.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("contact".to_string())).code;
+
+    if public_key.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("public_key".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
         return false;
     }
 
-    match (*wallet).runtime.block_on(
+    if address.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("address".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return false;
+    }
+    let parsed_addr = match CStr::from_ptr(address).to_str() {
+        Ok(v) => match Multiaddr::from_str(v) {
+            Ok(v) => v,
+            Err(_) => {
+                error = LibWalletError::from(InterfaceError::InvalidArgument("address is invalid".to_string())).code;
+                ptr::swap(error_out, &mut error as *mut c_int);
+                return false;
+            },
+        },
+        _ => {
+            error = LibWalletError::from(InterfaceError::PointerError("address".to_string())).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            return false;
+        },
+    };
+
+    if let Err(e) = (*wallet).runtime.block_on(
         (*wallet)
             .wallet
-            .contacts_service
-            .remove_contact((*contact).address.clone()),
+            .add_base_node_candidate((*public_key).clone(), parsed_addr),
     ) {
-        Ok(_) => true,
-        Err(e) => {
-            error = LibWalletError::from(WalletError::ContactsServiceError(e)).code;
-            ptr::swap(error_out, &mut error as *mut c_int);
-            false
-        },
+        error = LibWalletError::from(e).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return false;
     }
+    true
 }
 
-/// Gets the available balance from a TariBalance. This is the balance the user can spend.
+/// Pins a base node candidate as the preferred base node of the TariWallet and makes it the active base node. The
+/// wallet only fails over to another base node while the pinned base node is unavailable.
 ///
 /// ## Arguments
-/// `balance` - The TariBalance pointer
+/// `wallet` - The TariWallet pointer
+/// `public_key` - The TariPublicKey pointer of a base node that was previously added to the wallet
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `c_ulonglong` - The available balance, 0 if wallet is null
+/// `bool` - Returns if successful or not
 ///
 /// # Safety
 /// None
 #[no_mangle]
-pub unsafe extern "C" fn balance_get_available(balance: *mut TariBalance, error_out: *mut c_int) -> c_ulonglong {
+pub unsafe extern "C" fn wallet_pin_base_node(
+    wallet: *mut TariWallet,
+    public_key: *mut TariPublicKey,
+    error_out: *mut c_int,
+) -> bool {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
-    if balance.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("balance".to_string())).code;
+    if wallet.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        return 0;
+        return false;
     }
 
-    c_ulonglong::from((*balance).available_balance)
+    if public_key.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("public_key".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return false;
+    }
+
+    match (*wallet).wallet.pin_base_node(&*public_key) {
+        Ok(_) => true,
+        Err(WalletError::WalletConnectivityError(e)) => {
+            error = LibWalletError::from(InterfaceError::InvalidArgument(e.to_string())).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            false
+        },
+        Err(e) => {
+            error = LibWalletError::from(e).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            false
+        },
+    }
 }
 
-/// Gets the time locked balance from a TariBalance. This is the balance the user can spend.
+/// Removes the preferred base node of the TariWallet, if one was pinned
 ///
 /// ## Arguments
-/// `balance` - The TariBalance pointer
+/// `wallet` - The TariWallet pointer
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `c_ulonglong` - The time locked balance, 0 if wallet is null
+/// `bool` - Returns if successful or not
 ///
 /// # Safety
 /// None
 #[no_mangle]
-pub unsafe extern "C" fn balance_get_time_locked(balance: *mut TariBalance, error_out: *mut c_int) -> c_ulonglong {
+pub unsafe extern "C" fn wallet_unpin_base_node(wallet: *mut TariWallet, error_out: *mut c_int) -> bool {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
-    if balance.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("balance".to_string())).code;
+    if wallet.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        return 0;
+        return false;
     }
 
-    let b = if let Some(bal) = (*balance).time_locked_balance {
-        bal
-    } else {
-        MicroMinotari::from(0)
-    };
-    c_ulonglong::from(b)
+    if let Err(e) = (*wallet).wallet.unpin_base_node() {
+        error = LibWalletError::from(e).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return false;
+    }
+    true
 }
 
-/// Gets the pending incoming balance from a TariBalance. This is the balance the user can spend.
+/// Upserts a TariContact to the TariWallet. If the contact does not exist it will be Inserted. If it does exist the
+/// Alias will be updated.
 ///
 /// ## Arguments
-/// `balance` - The TariBalance pointer
+/// `wallet` - The TariWallet pointer
+/// `contact` - The TariContact pointer
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `c_ulonglong` - The pending incoming, 0 if wallet is null
+/// `bool` - Returns if successful or not
 ///
 /// # Safety
 /// None
 #[no_mangle]
-pub unsafe extern "C" fn balance_get_pending_incoming(balance: *mut TariBalance, error_out: *mut c_int) -> c_ulonglong {
+pub unsafe extern "C" fn wallet_upsert_contact(
+    wallet: *mut TariWallet,
+    contact: *mut TariContact,
+    error_out: *mut c_int,
+) -> bool {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
-    if balance.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("balance".to_string())).code;
+    if wallet.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        return 0;
+        return false;
+    }
+    if contact.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("contact".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return false;
     }
 
-    c_ulonglong::from((*balance).pending_incoming_balance)
-}
-
-/// Gets the pending outgoing balance from a TariBalance. This is the balance the user can spend.
-///
-/// ## Arguments
+    match (*wallet)
+        .runtime
+        .block_on((*wallet).wallet.contacts_service.upsert_contact((*contact).clone()))
+    {
+        Ok(_) => true,
+        Err(e) => {
+            error = LibWalletError::from(WalletError::ContactsServiceError(e)).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            false
+        },
+    }
+}
+
+/// Removes a TariContact from the TariWallet
+///
+/// ## Arguments
+/// `wallet` - The TariWallet pointer
+/// `tx` - The TariPendingInboundTransaction pointer
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `bool` - Returns if successful or not
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn wallet_remove_contact(
+    wallet: *mut TariWallet,
+    contact: *mut TariContact,
+    error_out: *mut c_int,
+) -> bool {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if wallet.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return false;
+    }
+    if contact.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("contact".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return false;
+    }
+
+    match (*wallet).runtime.block_on(
+        (*wallet)
+            .wallet
+            .contacts_service
+            .remove_contact((*contact).address.clone()),
+    ) {
+        Ok(_) => true,
+        Err(e) => {
+            error = LibWalletError::from(WalletError::ContactsServiceError(e)).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            false
+        },
+    }
+}
+
+/// Gets the available balance from a TariBalance. This is the balance the user can spend.
+///
+/// ## Arguments
+/// `balance` - The TariBalance pointer
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `c_ulonglong` - The available balance, 0 if wallet is null
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn balance_get_available(balance: *mut TariBalance, error_out: *mut c_int) -> c_ulonglong {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if balance.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("balance".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return 0;
+    }
+
+    c_ulonglong::from((*balance).available_balance)
+}
+
+/// Gets the time locked balance from a TariBalance. This is the balance the user can spend.
+///
+/// ## Arguments
+/// `balance` - The TariBalance pointer
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `c_ulonglong` - The time locked balance, 0 if wallet is null
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn balance_get_time_locked(balance: *mut TariBalance, error_out: *mut c_int) -> c_ulonglong {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if balance.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("balance".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return 0;
+    }
+
+    let b = if let Some(bal) = (*balance).time_locked_balance {
+        bal
+    } else {
+        MicroMinotari::from(0)
+    };
+    c_ulonglong::from(b)
+}
+
+/// Gets the pending incoming balance from a TariBalance. This is the balance the user can spend.
+///
+/// ## Arguments
+/// `balance` - The TariBalance pointer
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `c_ulonglong` - The pending incoming, 0 if wallet is null
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn balance_get_pending_incoming(balance: *mut TariBalance, error_out: *mut c_int) -> c_ulonglong {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if balance.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("balance".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return 0;
+    }
+
+    c_ulonglong::from((*balance).pending_incoming_balance)
+}
+
+/// Gets the pending outgoing balance from a TariBalance. This is the balance the user can spend.
+///
+/// ## Arguments
 /// `balance` - The TariBalance pointer
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
@@ -6539,6 +7382,145 @@ pub unsafe extern "C" fn balance_destroy(balance: *mut TariBalance) {
     }
 }
 
+/// Gets the number of snapshots in a TariBalanceHistory
+///
+/// ## Arguments
+/// `history` - The pointer to a TariBalanceHistory
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `c_uint` - Returns the number of snapshots in a TariBalanceHistory, note that it will be zero if history is null
+///
+/// # Safety
+/// None
+// casting here is okay as we wont have more than u32 snapshots
+#[allow(clippy::cast_possible_truncation)]
+#[no_mangle]
+pub unsafe extern "C" fn balance_history_get_length(history: *mut TariBalanceHistory, error_out: *mut c_int) -> c_uint {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    let mut len = 0;
+    if history.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("history".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+    } else {
+        len = (*history).0.len();
+    }
+    len as c_uint
+}
+
+unsafe fn balance_history_snapshot_at<'a>(
+    history: *mut TariBalanceHistory,
+    position: c_uint,
+    error_out: *mut c_int,
+) -> Option<&'a BalanceSnapshot> {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if history.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("history".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return None;
+    }
+    match (*history).0.get(position as usize) {
+        Some(snapshot) => Some(snapshot),
+        None => {
+            error = LibWalletError::from(InterfaceError::PositionInvalidError).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            None
+        },
+    }
+}
+
+/// Gets the block height at which the snapshot at position in a TariBalanceHistory was recorded
+///
+/// ## Arguments
+/// `history` - The pointer to a TariBalanceHistory
+/// `position` - The integer position
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `c_ulonglong` - Returns the block height, note that it will be zero if history is null or position is invalid
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn balance_history_get_height_at(
+    history: *mut TariBalanceHistory,
+    position: c_uint,
+    error_out: *mut c_int,
+) -> c_ulonglong {
+    balance_history_snapshot_at(history, position, error_out).map_or(0, |snapshot| snapshot.block_height)
+}
+
+/// Gets the unix timestamp (in seconds) at which the snapshot at position in a TariBalanceHistory was recorded
+///
+/// ## Arguments
+/// `history` - The pointer to a TariBalanceHistory
+/// `position` - The integer position
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `c_ulonglong` - Returns the timestamp, note that it will be zero if history is null or position is invalid
+///
+/// # Safety
+/// None
+#[allow(clippy::cast_sign_loss)]
+#[no_mangle]
+pub unsafe extern "C" fn balance_history_get_timestamp_at(
+    history: *mut TariBalanceHistory,
+    position: c_uint,
+    error_out: *mut c_int,
+) -> c_ulonglong {
+    balance_history_snapshot_at(history, position, error_out)
+        .map_or(0, |snapshot| snapshot.recorded_at.timestamp().max(0) as c_ulonglong)
+}
+
+/// Gets the TariBalance of the snapshot at position in a TariBalanceHistory
+///
+/// ## Arguments
+/// `history` - The pointer to a TariBalanceHistory
+/// `position` - The integer position
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `*mut TariBalance` - Returns a pointer to a TariBalance, note that ptr::null_mut() is returned if history is null
+/// or position is invalid
+///
+/// # Safety
+/// The ```balance_destroy``` method must be called when finished with a TariBalance to prevent a memory leak
+#[no_mangle]
+pub unsafe extern "C" fn balance_history_get_balance_at(
+    history: *mut TariBalanceHistory,
+    position: c_uint,
+    error_out: *mut c_int,
+) -> *mut TariBalance {
+    match balance_history_snapshot_at(history, position, error_out) {
+        Some(snapshot) => Box::into_raw(Box::new(snapshot.balance.clone())),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Frees memory for a TariBalanceHistory
+///
+/// ## Arguments
+/// `history` - The pointer to a TariBalanceHistory
+///
+/// ## Returns
+/// `()` - Does not return a value, equivalent to void in C
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn balance_history_destroy(history: *mut TariBalanceHistory) {
+    if !history.is_null() {
+        drop(Box::from_raw(history))
+    }
+}
+
 /// Sends a TariPendingOutboundTransaction
 ///
 /// ## Arguments
@@ -6547,6 +7529,8 @@ pub unsafe extern "C" fn balance_destroy(balance: *mut TariBalance) {
 /// `amount` - The amount
 /// `commitments` - A `TariVector` of "strings", tagged as `TariTypeTag::String`, containing commitment's hex values
 ///   (see `Commitment::to_hex()`)
+/// `selection_strategy` - The strategy used to select the inputs of the transaction. Ignored if `commitments` is
+/// provided.
 /// `fee_per_gram` - The transaction fee
 /// `message` - The pointer to a char array
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
@@ -6563,6 +7547,7 @@ pub unsafe extern "C" fn wallet_send_transaction(
     destination: *mut TariWalletAddress,
     amount: c_ulonglong,
     commitments: *mut TariVector,
+    selection_strategy: TariUtxoSelectionStrategy,
     fee_per_gram: c_ulonglong,
     message: *const c_char,
     one_sided: bool,
@@ -6583,7 +7568,10 @@ pub unsafe extern "C" fn wallet_send_transaction(
     }
 
     let selection_criteria = match commitments.as_ref() {
-        None => UtxoSelectionCriteria::default(),
+        None => UtxoSelectionCriteria {
+            ordering: selection_strategy.into(),
+            ..Default::default()
+        },
         Some(cs) => match cs.to_commitment_vec() {
             Ok(cs) => UtxoSelectionCriteria::specific(cs),
             Err(e) => {
@@ -6662,32 +7650,256 @@ pub unsafe extern "C" fn wallet_send_transaction(
     }
 }
 
-/// Gets a fee estimate for an amount
+/// Schedules a payment to be sent by the wallet once it falls due, optionally repeating at a fixed interval. Due
+/// payments are only sent while the wallet is online and has enough available balance; progress is reported through
+/// `callback_scheduled_payment_status`.
 ///
 /// ## Arguments
 /// `wallet` - The TariWallet pointer
-/// `amount` - The amount
-/// `commitments` - A `TariVector` of "strings", tagged as `TariTypeTag::String`, containing commitment's hex values
-///   (see `Commitment::to_hex()`)
-/// `fee_per_gram` - The fee per gram
-/// `num_kernels` - The number of transaction kernels
-/// `num_outputs` - The number of outputs
+/// `destination` - The TariWalletAddress pointer of the peer
+/// `amount` - The amount of each payment
+/// `fee_per_gram` - The transaction fee
+/// `message` - The pointer to a char array
+/// `first_payment_at` - The time of the first payment as a unix timestamp in seconds
+/// `interval_seconds` - The number of seconds between payments, or 0 for a one-off payment
+/// `max_payments` - The total number of payments to make for a recurring payment, or 0 to repeat until cancelled.
+/// Ignored for one-off payments.
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `unsigned long long` - Returns 0 if unsuccessful or the fee estimate in MicroMinotari if successful
+/// `unsigned long long` - Returns 0 if unsuccessful or the id of the scheduled payment if successful
 ///
 /// # Safety
 /// None
 #[no_mangle]
-pub unsafe extern "C" fn wallet_get_fee_estimate(
+pub unsafe extern "C" fn wallet_schedule_payment(
     wallet: *mut TariWallet,
+    destination: *mut TariWalletAddress,
     amount: c_ulonglong,
-    commitments: *mut TariVector,
     fee_per_gram: c_ulonglong,
-    num_kernels: c_uint,
-    num_outputs: c_uint,
+    message: *const c_char,
+    first_payment_at: c_ulonglong,
+    interval_seconds: c_ulonglong,
+    max_payments: c_ulonglong,
+    error_out: *mut c_int,
+) -> c_ulonglong {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if wallet.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return 0;
+    }
+
+    if destination.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("destination".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return 0;
+    }
+
+    let message_string = if message.is_null() {
+        String::new()
+    } else {
+        match CStr::from_ptr(message).to_str() {
+            Ok(v) => v.to_owned(),
+            _ => {
+                error = LibWalletError::from(InterfaceError::PointerError("message".to_string())).code;
+                ptr::swap(error_out, &mut error as *mut c_int);
+                return 0;
+            },
+        }
+    };
+
+    let first_payment_at = match i64::try_from(first_payment_at)
+        .ok()
+        .and_then(|secs| NaiveDateTime::from_timestamp_opt(secs, 0))
+    {
+        Some(t) => t,
+        None => {
+            error = LibWalletError::from(InterfaceError::InvalidArgument("first_payment_at".to_string())).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            return 0;
+        },
+    };
+
+    let recurrence = if interval_seconds == 0 {
+        None
+    } else {
+        Some(Recurrence {
+            interval: Duration::from_secs(interval_seconds),
+            max_payments: if max_payments == 0 { None } else { Some(max_payments) },
+        })
+    };
+
+    match (*wallet)
+        .runtime
+        .block_on((*wallet).wallet.scheduled_payments_service.schedule_payment(
+            (*destination).clone(),
+            MicroMinotari::from(amount),
+            MicroMinotari::from(fee_per_gram),
+            message_string,
+            first_payment_at,
+            recurrence,
+        )) {
+        Ok(payment_id) => payment_id,
+        Err(e) => {
+            error = LibWalletError::from(WalletError::ScheduledPaymentsServiceError(e)).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            0
+        },
+    }
+}
+
+/// Cancels an active scheduled payment so that no further payments are made for it
+///
+/// ## Arguments
+/// `wallet` - The TariWallet pointer
+/// `payment_id` - The id of the scheduled payment returned by `wallet_schedule_payment`
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `bool` - Returns if the cancellation was successful or not
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn wallet_cancel_scheduled_payment(
+    wallet: *mut TariWallet,
+    payment_id: c_ulonglong,
+    error_out: *mut c_int,
+) -> bool {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if wallet.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return false;
+    }
+
+    match (*wallet).runtime.block_on(
+        (*wallet)
+            .wallet
+            .scheduled_payments_service
+            .cancel_scheduled_payment(payment_id),
+    ) {
+        Ok(_) => true,
+        Err(e) => {
+            error = LibWalletError::from(WalletError::ScheduledPaymentsServiceError(e)).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            false
+        },
+    }
+}
+
+/// Approves an outbound transaction that is held for exceeding a spending limit, the transaction is then sent as
+/// normal
+///
+/// ## Arguments
+/// `wallet` - The TariWallet pointer
+/// `approval_id` - The approval_id provided by `callback_transaction_approval_required`
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `bool` - Returns if the approval was successful or not
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn wallet_approve_transaction(
+    wallet: *mut TariWallet,
+    approval_id: c_ulonglong,
+    error_out: *mut c_int,
+) -> bool {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if wallet.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return false;
+    }
+
+    match (*wallet)
+        .runtime
+        .block_on((*wallet).wallet.transaction_service.approve_transaction(approval_id))
+    {
+        Ok(_) => true,
+        Err(e) => {
+            error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            false
+        },
+    }
+}
+
+/// Rejects an outbound transaction that is held for exceeding a spending limit, the transaction will not be sent
+///
+/// ## Arguments
+/// `wallet` - The TariWallet pointer
+/// `approval_id` - The approval_id provided by `callback_transaction_approval_required`
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `bool` - Returns if the rejection was successful or not
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn wallet_reject_transaction(
+    wallet: *mut TariWallet,
+    approval_id: c_ulonglong,
+    error_out: *mut c_int,
+) -> bool {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if wallet.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return false;
+    }
+
+    match (*wallet)
+        .runtime
+        .block_on((*wallet).wallet.transaction_service.reject_transaction(approval_id))
+    {
+        Ok(_) => true,
+        Err(e) => {
+            error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            false
+        },
+    }
+}
+
+/// Gets a fee estimate for an amount
+///
+/// ## Arguments
+/// `wallet` - The TariWallet pointer
+/// `amount` - The amount
+/// `commitments` - A `TariVector` of "strings", tagged as `TariTypeTag::String`, containing commitment's hex values
+///   (see `Commitment::to_hex()`)
+/// `fee_per_gram` - The fee per gram
+/// `num_kernels` - The number of transaction kernels
+/// `num_outputs` - The number of outputs
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `unsigned long long` - Returns 0 if unsuccessful or the fee estimate in MicroMinotari if successful
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn wallet_get_fee_estimate(
+    wallet: *mut TariWallet,
+    amount: c_ulonglong,
+    commitments: *mut TariVector,
+    fee_per_gram: c_ulonglong,
+    num_kernels: c_uint,
+    num_outputs: c_uint,
     error_out: *mut c_int,
 ) -> c_ulonglong {
     let mut error = 0;
@@ -6708,334 +7920,965 @@ pub unsafe extern "C" fn wallet_get_fee_estimate(
                 return 0;
             },
         },
-    };
-
-    match (*wallet)
-        .runtime
-        .block_on((*wallet).wallet.output_manager_service.fee_estimate(
-            MicroMinotari::from(amount),
-            selection_criteria,
-            MicroMinotari::from(fee_per_gram),
-            num_kernels as usize,
-            num_outputs as usize,
-        )) {
-        Ok(fee) => fee.into(),
+    };
+
+    match (*wallet)
+        .runtime
+        .block_on((*wallet).wallet.output_manager_service.fee_estimate(
+            MicroMinotari::from(amount),
+            selection_criteria,
+            MicroMinotari::from(fee_per_gram),
+            num_kernels as usize,
+            num_outputs as usize,
+        )) {
+        Ok(fee) => fee.into(),
+        Err(e) => {
+            error = LibWalletError::from(WalletError::OutputManagerError(e)).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            0
+        },
+    }
+}
+
+/// Estimates the size of a transaction, i.e. its weight in grams, so that a fee for it can be calculated as the
+/// weight multiplied by the fee per gram
+///
+/// ## Arguments
+/// `wallet` - The TariWallet pointer
+/// `amount` - The amount to send
+/// `fee_per_gram` - The fee per gram, used to select the inputs that would fund the transaction
+/// `num_outputs` - The number of recipient outputs
+/// `output_features` - An array of `num_outputs` TariOutputFeatures pointers, one for each recipient output. The array
+/// or any of its entries may be null, in which case default output features are used.
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `unsigned long long` - Returns 0 if unsuccessful or the estimated transaction weight in grams, including the
+/// selected inputs and a change output if one is required
+///
+/// # Safety
+/// `output_features` must be null or point to at least `num_outputs` TariOutputFeatures pointers
+#[no_mangle]
+pub unsafe extern "C" fn wallet_estimate_tx_size(
+    wallet: *mut TariWallet,
+    amount: c_ulonglong,
+    fee_per_gram: c_ulonglong,
+    num_outputs: c_uint,
+    output_features: *const *mut TariOutputFeatures,
+    error_out: *mut c_int,
+) -> c_ulonglong {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if wallet.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return 0;
+    }
+
+    let features = (0..num_outputs as usize)
+        .map(|i| {
+            if output_features.is_null() {
+                return TariOutputFeatures::default();
+            }
+            (*output_features.add(i)).as_ref().cloned().unwrap_or_default()
+        })
+        .collect();
+
+    match (*wallet)
+        .runtime
+        .block_on((*wallet).wallet.output_manager_service.estimate_transaction_weight(
+            MicroMinotari::from(amount),
+            UtxoSelectionCriteria::default(),
+            MicroMinotari::from(fee_per_gram),
+            features,
+        )) {
+        Ok(estimate) => estimate.weight,
+        Err(e) => {
+            error = LibWalletError::from(WalletError::OutputManagerError(e)).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            0
+        },
+    }
+}
+
+/// Gets the number of mining confirmations required
+///
+/// ## Arguments
+/// `wallet` - The TariWallet pointer
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `unsigned long long` - Returns the number of confirmations required
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn wallet_get_num_confirmations_required(
+    wallet: *mut TariWallet,
+    error_out: *mut c_int,
+) -> c_ulonglong {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if wallet.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return 0;
+    }
+
+    match (*wallet)
+        .runtime
+        .block_on((*wallet).wallet.transaction_service.get_num_confirmations_required())
+    {
+        Ok(num) => num,
+        Err(e) => {
+            error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            0
+        },
+    }
+}
+
+/// Sets the number of mining confirmations required
+///
+/// ## Arguments
+/// `wallet` - The TariWallet pointer
+/// `num` - The number of confirmations to require
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `()` - Does not return a value, equivalent to void in C
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn wallet_set_num_confirmations_required(
+    wallet: *mut TariWallet,
+    num: c_ulonglong,
+    error_out: *mut c_int,
+) {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if wallet.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int)
+    }
+
+    match (*wallet)
+        .runtime
+        .block_on((*wallet).wallet.transaction_service.set_num_confirmations_required(num))
+    {
+        Ok(()) => (),
+        Err(e) => {
+            error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
+            ptr::swap(error_out, &mut error as *mut c_int)
+        },
+    }
+}
+
+/// Get the TariContacts from a TariWallet
+///
+/// ## Arguments
+/// `wallet` - The TariWallet pointer
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `*mut TariContacts` - returns the contacts, note that it returns ptr::null_mut() if
+/// wallet is null
+///
+/// # Safety
+/// The ```contacts_destroy``` method must be called when finished with a TariContacts to prevent a memory leak
+#[no_mangle]
+pub unsafe extern "C" fn wallet_get_contacts(wallet: *mut TariWallet, error_out: *mut c_int) -> *mut TariContacts {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    let mut contacts = Vec::new();
+    if wallet.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return ptr::null_mut();
+    }
+
+    let retrieved_contacts = (*wallet)
+        .runtime
+        .block_on((*wallet).wallet.contacts_service.get_contacts());
+    match retrieved_contacts {
+        Ok(mut retrieved_contacts) => {
+            contacts.append(&mut retrieved_contacts);
+            Box::into_raw(Box::new(TariContacts(contacts)))
+        },
+        Err(e) => {
+            error = LibWalletError::from(WalletError::ContactsServiceError(e)).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            ptr::null_mut()
+        },
+    }
+}
+
+/// Get the TariCompletedTransactions from a TariWallet
+///
+/// ## Arguments
+/// `wallet` - The TariWallet pointer
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `*mut TariCompletedTransactions` - returns the transactions, note that it returns ptr::null_mut() if
+/// wallet is null or an error is encountered
+///
+/// # Safety
+/// The ```completed_transactions_destroy``` method must be called when finished with a TariCompletedTransactions to
+/// prevent a memory leak
+#[no_mangle]
+pub unsafe extern "C" fn wallet_get_completed_transactions(
+    wallet: *mut TariWallet,
+    error_out: *mut c_int,
+) -> *mut TariCompletedTransactions {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    let mut completed = Vec::new();
+    if wallet.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return ptr::null_mut();
+    }
+
+    let completed_transactions = (*wallet)
+        .runtime
+        .block_on((*wallet).wallet.transaction_service.get_completed_transactions());
+    match completed_transactions {
+        Ok(completed_transactions) => {
+            // The frontend specification calls for completed transactions that have not yet been mined to be
+            // classified as Pending Transactions. In order to support this logic without impacting the practical
+            // definitions and storage of a MimbleWimble CompletedTransaction we will remove CompletedTransactions with
+            // the Completed and Broadcast states from the list returned by this FFI function
+            for tx in completed_transactions
+                .values()
+                .filter(|ct| ct.status != TransactionStatus::Completed)
+                .filter(|ct| ct.status != TransactionStatus::Broadcast)
+                .filter(|ct| ct.status != TransactionStatus::Imported)
+            {
+                completed.push(tx.clone());
+            }
+            Box::into_raw(Box::new(TariCompletedTransactions(completed)))
+        },
+        Err(e) => {
+            error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            ptr::null_mut()
+        },
+    }
+}
+
+/// Get the TariPendingInboundTransactions from a TariWallet
+///
+/// Currently a CompletedTransaction with the Status of Completed and Broadcast is considered Pending by the frontend
+///
+/// ## Arguments
+/// `wallet` - The TariWallet pointer
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `*mut TariPendingInboundTransactions` - returns the transactions, note that it returns ptr::null_mut() if
+/// wallet is null or and error is encountered
+///
+/// # Safety
+/// The ```pending_inbound_transactions_destroy``` method must be called when finished with a
+/// TariPendingInboundTransactions to prevent a memory leak
+#[no_mangle]
+pub unsafe extern "C" fn wallet_get_pending_inbound_transactions(
+    wallet: *mut TariWallet,
+    error_out: *mut c_int,
+) -> *mut TariPendingInboundTransactions {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    let mut pending = Vec::new();
+    if wallet.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return ptr::null_mut();
+    }
+
+    let pending_transactions = (*wallet)
+        .runtime
+        .block_on((*wallet).wallet.transaction_service.get_pending_inbound_transactions());
+
+    match pending_transactions {
+        Ok(pending_transactions) => {
+            for tx in pending_transactions.values() {
+                pending.push(tx.clone());
+            }
+
+            if let Ok(completed_txs) = (*wallet)
+                .runtime
+                .block_on((*wallet).wallet.transaction_service.get_completed_transactions())
+            {
+                // The frontend specification calls for completed transactions that have not yet been mined to be
+                // classified as Pending Transactions. In order to support this logic without impacting the practical
+                // definitions and storage of a MimbleWimble CompletedTransaction we will add those transaction to the
+                // list here in the FFI interface
+                for ct in completed_txs
+                    .values()
+                    .filter(|ct| {
+                        ct.status == TransactionStatus::Completed ||
+                            ct.status == TransactionStatus::Broadcast ||
+                            ct.status == TransactionStatus::Imported
+                    })
+                    .filter(|ct| ct.direction == TransactionDirection::Inbound)
+                {
+                    pending.push(InboundTransaction::from(ct.clone()));
+                }
+            }
+
+            Box::into_raw(Box::new(TariPendingInboundTransactions(pending)))
+        },
+        Err(e) => {
+            error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            ptr::null_mut()
+        },
+    }
+}
+
+/// Get the TariPendingOutboundTransactions from a TariWallet
+///
+/// Currently a CompletedTransaction with the Status of Completed and Broadcast is considered Pending by the frontend
+///
+/// ## Arguments
+/// `wallet` - The TariWallet pointer
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `*mut TariPendingOutboundTransactions` - returns the transactions, note that it returns ptr::null_mut() if
+/// wallet is null or and error is encountered
+///
+/// # Safety
+/// The ```pending_outbound_transactions_destroy``` method must be called when finished with a
+/// TariPendingOutboundTransactions to prevent a memory leak
+#[no_mangle]
+pub unsafe extern "C" fn wallet_get_pending_outbound_transactions(
+    wallet: *mut TariWallet,
+    error_out: *mut c_int,
+) -> *mut TariPendingOutboundTransactions {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    let mut pending = Vec::new();
+    if wallet.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return ptr::null_mut();
+    }
+
+    let pending_transactions = (*wallet)
+        .runtime
+        .block_on((*wallet).wallet.transaction_service.get_pending_outbound_transactions());
+    match pending_transactions {
+        Ok(pending_transactions) => {
+            for tx in pending_transactions.values() {
+                pending.push(tx.clone());
+            }
+            if let Ok(completed_txs) = (*wallet)
+                .runtime
+                .block_on((*wallet).wallet.transaction_service.get_completed_transactions())
+            {
+                // The frontend specification calls for completed transactions that have not yet been mined to be
+                // classified as Pending Transactions. In order to support this logic without impacting the practical
+                // definitions and storage of a MimbleWimble CompletedTransaction we will add those transaction to the
+                // list here in the FFI interface
+                for ct in completed_txs
+                    .values()
+                    .filter(|ct| ct.status == TransactionStatus::Completed || ct.status == TransactionStatus::Broadcast)
+                    .filter(|ct| ct.direction == TransactionDirection::Outbound)
+                {
+                    pending.push(OutboundTransaction::from(ct.clone()));
+                }
+            }
+            Box::into_raw(Box::new(TariPendingOutboundTransactions(pending)))
+        },
+        Err(e) => {
+            error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            ptr::null_mut()
+        },
+    }
+}
+
+/// Get the all Cancelled Transactions from a TariWallet. This function will also get cancelled pending inbound and
+/// outbound transaction and include them in this list by converting them to CompletedTransactions
+///
+/// ## Arguments
+/// `wallet` - The TariWallet pointer
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `*mut TariCompletedTransactions` - returns the transactions, note that it returns ptr::null_mut() if
+/// wallet is null or an error is encountered
+///
+/// # Safety
+/// The ```completed_transactions_destroy``` method must be called when finished with a TariCompletedTransactions to
+/// prevent a memory leak
+#[no_mangle]
+pub unsafe extern "C" fn wallet_get_cancelled_transactions(
+    wallet: *mut TariWallet,
+    error_out: *mut c_int,
+) -> *mut TariCompletedTransactions {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+
+    if wallet.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return ptr::null_mut();
+    }
+
+    let completed_transactions = match (*wallet).runtime.block_on(
+        (*wallet)
+            .wallet
+            .transaction_service
+            .get_cancelled_completed_transactions(),
+    ) {
+        Ok(txs) => txs,
+        Err(e) => {
+            error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            return ptr::null_mut();
+        },
+    };
+    let inbound_transactions = match (*wallet).runtime.block_on(
+        (*wallet)
+            .wallet
+            .transaction_service
+            .get_cancelled_pending_inbound_transactions(),
+    ) {
+        Ok(txs) => txs,
+        Err(e) => {
+            error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            return ptr::null_mut();
+        },
+    };
+    let outbound_transactions = match (*wallet).runtime.block_on(
+        (*wallet)
+            .wallet
+            .transaction_service
+            .get_cancelled_pending_outbound_transactions(),
+    ) {
+        Ok(txs) => txs,
+        Err(e) => {
+            error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            return ptr::null_mut();
+        },
+    };
+
+    let mut completed = Vec::new();
+    for tx in completed_transactions.values() {
+        completed.push(tx.clone());
+    }
+    let wallet_address = TariAddress::new(
+        (*wallet).wallet.comms.node_identity().public_key().clone(),
+        (*wallet).wallet.network.as_network(),
+    );
+    for tx in inbound_transactions.values() {
+        let mut inbound_tx = CompletedTransaction::from(tx.clone());
+        inbound_tx.destination_address = wallet_address.clone();
+        completed.push(inbound_tx);
+    }
+    for tx in outbound_transactions.values() {
+        let mut outbound_tx = CompletedTransaction::from(tx.clone());
+        outbound_tx.source_address = wallet_address.clone();
+        completed.push(outbound_tx);
+    }
+
+    Box::into_raw(Box::new(TariCompletedTransactions(completed)))
+}
+
+/// Get the TariCompletedTransaction from a TariWallet by its' TransactionId
+///
+/// ## Arguments
+/// `wallet` - The TariWallet pointer
+/// `transaction_id` - The TransactionId
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `*mut TariCompletedTransaction` - returns the transaction, note that it returns ptr::null_mut() if
+/// wallet is null, an error is encountered or if the transaction is not found
+///
+/// # Safety
+/// The ```completed_transaction_destroy``` method must be called when finished with a TariCompletedTransaction to
+/// prevent a memory leak
+#[no_mangle]
+pub unsafe extern "C" fn wallet_get_completed_transaction_by_id(
+    wallet: *mut TariWallet,
+    transaction_id: c_ulonglong,
+    error_out: *mut c_int,
+) -> *mut TariCompletedTransaction {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if wallet.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return ptr::null_mut();
+    }
+
+    let completed_transactions = (*wallet)
+        .runtime
+        .block_on((*wallet).wallet.transaction_service.get_completed_transactions());
+
+    match completed_transactions {
+        Ok(completed_transactions) => {
+            if let Some(tx) = completed_transactions.get(&TxId::from(transaction_id)) {
+                if tx.status != TransactionStatus::Completed && tx.status != TransactionStatus::Broadcast {
+                    let completed = tx.clone();
+                    return Box::into_raw(Box::new(completed));
+                }
+            }
+            error = 108;
+            ptr::swap(error_out, &mut error as *mut c_int);
+        },
         Err(e) => {
-            error = LibWalletError::from(WalletError::OutputManagerError(e)).code;
+            error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
             ptr::swap(error_out, &mut error as *mut c_int);
-            0
         },
     }
+
+    ptr::null_mut()
 }
 
-/// Gets the number of mining confirmations required
+/// Get the TariPendingInboundTransaction from a TariWallet by its' TransactionId
 ///
 /// ## Arguments
 /// `wallet` - The TariWallet pointer
+/// `transaction_id` - The TransactionId
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `unsigned long long` - Returns the number of confirmations required
+/// `*mut TariPendingInboundTransaction` - returns the transaction, note that it returns ptr::null_mut() if
+/// wallet is null, an error is encountered or if the transaction is not found
 ///
 /// # Safety
-/// None
+/// The ```pending_inbound_transaction_destroy``` method must be called when finished with a
+/// TariPendingInboundTransaction to prevent a memory leak
 #[no_mangle]
-pub unsafe extern "C" fn wallet_get_num_confirmations_required(
+pub unsafe extern "C" fn wallet_get_pending_inbound_transaction_by_id(
     wallet: *mut TariWallet,
+    transaction_id: c_ulonglong,
     error_out: *mut c_int,
-) -> c_ulonglong {
+) -> *mut TariPendingInboundTransaction {
     let mut error = 0;
+    let transaction_id = TxId::from(transaction_id);
     ptr::swap(error_out, &mut error as *mut c_int);
     if wallet.is_null() {
         error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        return 0;
+        return ptr::null_mut();
     }
 
-    match (*wallet)
+    let pending_transactions = (*wallet)
         .runtime
-        .block_on((*wallet).wallet.transaction_service.get_num_confirmations_required())
-    {
-        Ok(num) => num,
+        .block_on((*wallet).wallet.transaction_service.get_pending_inbound_transactions());
+
+    let completed_transactions = (*wallet)
+        .runtime
+        .block_on((*wallet).wallet.transaction_service.get_completed_transactions());
+
+    match completed_transactions {
+        Ok(completed_transactions) => {
+            if let Some(tx) = completed_transactions.get(&transaction_id) {
+                if (tx.status == TransactionStatus::Broadcast || tx.status == TransactionStatus::Completed) &&
+                    tx.direction == TransactionDirection::Inbound
+                {
+                    let completed = tx.clone();
+                    let pending_tx = TariPendingInboundTransaction::from(completed);
+                    return Box::into_raw(Box::new(pending_tx));
+                }
+            }
+        },
+        Err(e) => {
+            error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+        },
+    }
+
+    match pending_transactions {
+        Ok(pending_transactions) => {
+            if let Some(tx) = pending_transactions.get(&transaction_id) {
+                let pending = tx.clone();
+                return Box::into_raw(Box::new(pending));
+            }
+            error = 108;
+            ptr::swap(error_out, &mut error as *mut c_int);
+        },
         Err(e) => {
             error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
             ptr::swap(error_out, &mut error as *mut c_int);
-            0
         },
     }
+
+    ptr::null_mut()
 }
 
-/// Sets the number of mining confirmations required
+/// Get the TariPendingOutboundTransaction from a TariWallet by its' TransactionId
 ///
 /// ## Arguments
 /// `wallet` - The TariWallet pointer
-/// `num` - The number of confirmations to require
+/// `transaction_id` - The TransactionId
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `()` - Does not return a value, equivalent to void in C
+/// `*mut TariPendingOutboundTransaction` - returns the transaction, note that it returns ptr::null_mut() if
+/// wallet is null, an error is encountered or if the transaction is not found
 ///
 /// # Safety
-/// None
+/// The ```pending_outbound_transaction_destroy``` method must be called when finished with a
+/// TariPendingOutboundtransaction to prevent a memory leak
 #[no_mangle]
-pub unsafe extern "C" fn wallet_set_num_confirmations_required(
+pub unsafe extern "C" fn wallet_get_pending_outbound_transaction_by_id(
     wallet: *mut TariWallet,
-    num: c_ulonglong,
+    transaction_id: c_ulonglong,
     error_out: *mut c_int,
-) {
+) -> *mut TariPendingOutboundTransaction {
     let mut error = 0;
+    let transaction_id = TxId::from(transaction_id);
     ptr::swap(error_out, &mut error as *mut c_int);
     if wallet.is_null() {
         error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
-        ptr::swap(error_out, &mut error as *mut c_int)
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return ptr::null_mut();
     }
 
-    match (*wallet)
+    let pending_transactions = (*wallet)
         .runtime
-        .block_on((*wallet).wallet.transaction_service.set_num_confirmations_required(num))
-    {
-        Ok(()) => (),
+        .block_on((*wallet).wallet.transaction_service.get_pending_outbound_transactions());
+
+    let completed_transactions = (*wallet)
+        .runtime
+        .block_on((*wallet).wallet.transaction_service.get_completed_transactions());
+
+    match completed_transactions {
+        Ok(completed_transactions) => {
+            if let Some(tx) = completed_transactions.get(&transaction_id) {
+                if (tx.status == TransactionStatus::Broadcast || tx.status == TransactionStatus::Completed) &&
+                    tx.direction == TransactionDirection::Outbound
+                {
+                    let completed = tx.clone();
+                    let pending_tx = TariPendingOutboundTransaction::from(completed);
+                    return Box::into_raw(Box::new(pending_tx));
+                }
+            }
+        },
         Err(e) => {
             error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
-            ptr::swap(error_out, &mut error as *mut c_int)
+            ptr::swap(error_out, &mut error as *mut c_int);
+        },
+    }
+
+    match pending_transactions {
+        Ok(pending_transactions) => {
+            if let Some(tx) = pending_transactions.get(&transaction_id) {
+                let pending = tx.clone();
+                return Box::into_raw(Box::new(pending));
+            }
+            error = 108;
+            ptr::swap(error_out, &mut error as *mut c_int);
+        },
+        Err(e) => {
+            error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
         },
     }
+
+    ptr::null_mut()
 }
 
-/// Get the TariContacts from a TariWallet
+/// Get a Cancelled transaction from a TariWallet by its TransactionId. Pending Inbound or Outbound transaction will be
+/// converted to a CompletedTransaction
 ///
 /// ## Arguments
 /// `wallet` - The TariWallet pointer
+/// `transaction_id` - The TransactionId
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `*mut TariContacts` - returns the contacts, note that it returns ptr::null_mut() if
-/// wallet is null
+/// `*mut TariCompletedTransaction` - returns the transaction, note that it returns ptr::null_mut() if
+/// wallet is null, an error is encountered or if the transaction is not found
 ///
 /// # Safety
-/// The ```contacts_destroy``` method must be called when finished with a TariContacts to prevent a memory leak
+/// The ```completed_transaction_destroy``` method must be called when finished with a TariCompletedTransaction to
+/// prevent a memory leak
 #[no_mangle]
-pub unsafe extern "C" fn wallet_get_contacts(wallet: *mut TariWallet, error_out: *mut c_int) -> *mut TariContacts {
+pub unsafe extern "C" fn wallet_get_cancelled_transaction_by_id(
+    wallet: *mut TariWallet,
+    transaction_id: c_ulonglong,
+    error_out: *mut c_int,
+) -> *mut TariCompletedTransaction {
     let mut error = 0;
+    let transaction_id = TxId::from(transaction_id);
     ptr::swap(error_out, &mut error as *mut c_int);
-    let mut contacts = Vec::new();
     if wallet.is_null() {
         error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
         return ptr::null_mut();
     }
 
-    let retrieved_contacts = (*wallet)
-        .runtime
-        .block_on((*wallet).wallet.contacts_service.get_contacts());
-    match retrieved_contacts {
-        Ok(mut retrieved_contacts) => {
-            contacts.append(&mut retrieved_contacts);
-            Box::into_raw(Box::new(TariContacts(contacts)))
-        },
+    let mut transaction = None;
+
+    let mut completed_transactions = match (*wallet).runtime.block_on(
+        (*wallet)
+            .wallet
+            .transaction_service
+            .get_cancelled_completed_transactions(),
+    ) {
+        Ok(txs) => txs,
         Err(e) => {
-            error = LibWalletError::from(WalletError::ContactsServiceError(e)).code;
+            error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
             ptr::swap(error_out, &mut error as *mut c_int);
-            ptr::null_mut()
+            return ptr::null_mut();
+        },
+    };
+
+    if let Some(tx) = completed_transactions.remove(&transaction_id) {
+        transaction = Some(tx);
+    } else {
+        let mut outbound_transactions = match (*wallet).runtime.block_on(
+            (*wallet)
+                .wallet
+                .transaction_service
+                .get_cancelled_pending_outbound_transactions(),
+        ) {
+            Ok(txs) => txs,
+            Err(e) => {
+                error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
+                ptr::swap(error_out, &mut error as *mut c_int);
+                return ptr::null_mut();
+            },
+        };
+        let network = (*wallet).wallet.network.as_network();
+        let address = TariWalletAddress::new((*wallet).wallet.comms.node_identity().public_key().clone(), network);
+        if let Some(tx) = outbound_transactions.remove(&transaction_id) {
+            let mut outbound_tx = CompletedTransaction::from(tx);
+            outbound_tx.source_address = address;
+            transaction = Some(outbound_tx);
+        } else {
+            let mut inbound_transactions = match (*wallet).runtime.block_on(
+                (*wallet)
+                    .wallet
+                    .transaction_service
+                    .get_cancelled_pending_inbound_transactions(),
+            ) {
+                Ok(txs) => txs,
+                Err(e) => {
+                    error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
+                    ptr::swap(error_out, &mut error as *mut c_int);
+                    return ptr::null_mut();
+                },
+            };
+            if let Some(tx) = inbound_transactions.remove(&transaction_id) {
+                let mut inbound_tx = CompletedTransaction::from(tx);
+                inbound_tx.destination_address = address;
+                transaction = Some(inbound_tx);
+            }
+        }
+    }
+
+    match transaction {
+        Some(tx) => {
+            return Box::into_raw(Box::new(tx));
         },
+        None => {
+            error = LibWalletError::from(WalletError::TransactionServiceError(
+                TransactionServiceError::TransactionDoesNotExistError,
+            ))
+            .code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+        },
+    }
+
+    ptr::null_mut()
+}
+
+/// Get the TariWalletAddress from a TariWallet
+///
+/// ## Arguments
+/// `wallet` - The TariWallet pointer
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `*mut TariWalletAddress` - returns the address, note that ptr::null_mut() is returned
+/// if wc is null
+///
+/// # Safety
+/// The ```tari_address_destroy``` method must be called when finished with a TariWalletAddress to prevent a memory leak
+#[no_mangle]
+pub unsafe extern "C" fn wallet_get_tari_address(
+    wallet: *mut TariWallet,
+    error_out: *mut c_int,
+) -> *mut TariWalletAddress {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if wallet.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return ptr::null_mut();
     }
+    let network = (*wallet).wallet.network.as_network();
+    let pk = (*wallet).wallet.comms.node_identity().public_key().clone();
+    let address = TariWalletAddress::new(pk, network);
+    Box::into_raw(Box::new(address))
 }
 
-/// Get the TariCompletedTransactions from a TariWallet
+/// Cancel a Pending Transaction
 ///
 /// ## Arguments
 /// `wallet` - The TariWallet pointer
+/// `transaction_id` - The TransactionId
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `*mut TariCompletedTransactions` - returns the transactions, note that it returns ptr::null_mut() if
-/// wallet is null or an error is encountered
+/// `bool` - returns whether the transaction could be cancelled
 ///
 /// # Safety
-/// The ```completed_transactions_destroy``` method must be called when finished with a TariCompletedTransactions to
-/// prevent a memory leak
+/// None
 #[no_mangle]
-pub unsafe extern "C" fn wallet_get_completed_transactions(
+pub unsafe extern "C" fn wallet_cancel_pending_transaction(
     wallet: *mut TariWallet,
+    transaction_id: c_ulonglong,
     error_out: *mut c_int,
-) -> *mut TariCompletedTransactions {
+) -> bool {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
-    let mut completed = Vec::new();
     if wallet.is_null() {
         error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        return ptr::null_mut();
+        return false;
     }
 
-    let completed_transactions = (*wallet)
-        .runtime
-        .block_on((*wallet).wallet.transaction_service.get_completed_transactions());
-    match completed_transactions {
-        Ok(completed_transactions) => {
-            // The frontend specification calls for completed transactions that have not yet been mined to be
-            // classified as Pending Transactions. In order to support this logic without impacting the practical
-            // definitions and storage of a MimbleWimble CompletedTransaction we will remove CompletedTransactions with
-            // the Completed and Broadcast states from the list returned by this FFI function
-            for tx in completed_transactions
-                .values()
-                .filter(|ct| ct.status != TransactionStatus::Completed)
-                .filter(|ct| ct.status != TransactionStatus::Broadcast)
-                .filter(|ct| ct.status != TransactionStatus::Imported)
-            {
-                completed.push(tx.clone());
-            }
-            Box::into_raw(Box::new(TariCompletedTransactions(completed)))
-        },
+    match (*wallet).runtime.block_on(
+        (*wallet)
+            .wallet
+            .transaction_service
+            .cancel_transaction(TxId::from(transaction_id)),
+    ) {
+        Ok(_) => true,
         Err(e) => {
             error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
             ptr::swap(error_out, &mut error as *mut c_int);
-            ptr::null_mut()
+            false
         },
     }
 }
 
-/// Get the TariPendingInboundTransactions from a TariWallet
-///
-/// Currently a CompletedTransaction with the Status of Completed and Broadcast is considered Pending by the frontend
+/// Cancel all Pending Transactions created more than `older_than_secs` seconds ago and release their encumbered outputs
+/// back to the spendable pool
 ///
 /// ## Arguments
 /// `wallet` - The TariWallet pointer
+/// `older_than_secs` - The minimum age in seconds of the pending transactions to cancel
+/// `num_failed_out` - Pointer to an unsigned long long which will be set to the number of stale transactions that could
+/// not be cancelled, may be null. Functions as an out parameter.
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `*mut TariPendingInboundTransactions` - returns the transactions, note that it returns ptr::null_mut() if
-/// wallet is null or and error is encountered
+/// `c_ulonglong` - returns the number of transactions that were cancelled
 ///
 /// # Safety
-/// The ```pending_inbound_transactions_destroy``` method must be called when finished with a
-/// TariPendingInboundTransactions to prevent a memory leak
+/// None
 #[no_mangle]
-pub unsafe extern "C" fn wallet_get_pending_inbound_transactions(
+pub unsafe extern "C" fn wallet_cancel_all_stale_transactions(
     wallet: *mut TariWallet,
+    older_than_secs: c_ulonglong,
+    num_failed_out: *mut c_ulonglong,
     error_out: *mut c_int,
-) -> *mut TariPendingInboundTransactions {
+) -> c_ulonglong {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
-    let mut pending = Vec::new();
     if wallet.is_null() {
         error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        return ptr::null_mut();
+        return 0;
     }
 
-    let pending_transactions = (*wallet)
-        .runtime
-        .block_on((*wallet).wallet.transaction_service.get_pending_inbound_transactions());
-
-    match pending_transactions {
-        Ok(pending_transactions) => {
-            for tx in pending_transactions.values() {
-                pending.push(tx.clone());
-            }
-
-            if let Ok(completed_txs) = (*wallet)
-                .runtime
-                .block_on((*wallet).wallet.transaction_service.get_completed_transactions())
-            {
-                // The frontend specification calls for completed transactions that have not yet been mined to be
-                // classified as Pending Transactions. In order to support this logic without impacting the practical
-                // definitions and storage of a MimbleWimble CompletedTransaction we will add those transaction to the
-                // list here in the FFI interface
-                for ct in completed_txs
-                    .values()
-                    .filter(|ct| {
-                        ct.status == TransactionStatus::Completed ||
-                            ct.status == TransactionStatus::Broadcast ||
-                            ct.status == TransactionStatus::Imported
-                    })
-                    .filter(|ct| ct.direction == TransactionDirection::Inbound)
-                {
-                    pending.push(InboundTransaction::from(ct.clone()));
-                }
+    match (*wallet).runtime.block_on(
+        (*wallet)
+            .wallet
+            .transaction_service
+            .cancel_all_stale(Duration::from_secs(older_than_secs)),
+    ) {
+        Ok(summary) => {
+            if !num_failed_out.is_null() {
+                *num_failed_out = summary.failed.len() as c_ulonglong;
             }
-
-            Box::into_raw(Box::new(TariPendingInboundTransactions(pending)))
+            summary.cancelled.len() as c_ulonglong
         },
         Err(e) => {
             error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
             ptr::swap(error_out, &mut error as *mut c_int);
-            ptr::null_mut()
+            0
         },
     }
 }
 
-/// Get the TariPendingOutboundTransactions from a TariWallet
-///
-/// Currently a CompletedTransaction with the Status of Completed and Broadcast is considered Pending by the frontend
+/// Audit the wallet's encumbered outputs against its pending and completed transactions
 ///
 /// ## Arguments
 /// `wallet` - The TariWallet pointer
+/// `repair` - Release the encumbered outputs that do not belong to any live transaction and revalidate them with the
+/// base node
+/// `num_duplicates_out` - Pointer to an unsigned long long which will be set to the number of commitments spent by
+/// more than one unmined transaction, may be null. Functions as an out parameter.
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `*mut TariPendingOutboundTransactions` - returns the transactions, note that it returns ptr::null_mut() if
-/// wallet is null or and error is encountered
+/// `c_ulonglong` - returns the number of orphaned encumbered outputs that were found
 ///
 /// # Safety
-/// The ```pending_outbound_transactions_destroy``` method must be called when finished with a
-/// TariPendingOutboundTransactions to prevent a memory leak
+/// None
 #[no_mangle]
-pub unsafe extern "C" fn wallet_get_pending_outbound_transactions(
+pub unsafe extern "C" fn wallet_audit_outputs(
     wallet: *mut TariWallet,
+    repair: bool,
+    num_duplicates_out: *mut c_ulonglong,
     error_out: *mut c_int,
-) -> *mut TariPendingOutboundTransactions {
+) -> c_ulonglong {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
-    let mut pending = Vec::new();
     if wallet.is_null() {
         error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        return ptr::null_mut();
+        return 0;
     }
 
-    let pending_transactions = (*wallet)
-        .runtime
-        .block_on((*wallet).wallet.transaction_service.get_pending_outbound_transactions());
-    match pending_transactions {
-        Ok(pending_transactions) => {
-            for tx in pending_transactions.values() {
-                pending.push(tx.clone());
-            }
-            if let Ok(completed_txs) = (*wallet)
-                .runtime
-                .block_on((*wallet).wallet.transaction_service.get_completed_transactions())
-            {
-                // The frontend specification calls for completed transactions that have not yet been mined to be
-                // classified as Pending Transactions. In order to support this logic without impacting the practical
-                // definitions and storage of a MimbleWimble CompletedTransaction we will add those transaction to the
-                // list here in the FFI interface
-                for ct in completed_txs
-                    .values()
-                    .filter(|ct| ct.status == TransactionStatus::Completed || ct.status == TransactionStatus::Broadcast)
-                    .filter(|ct| ct.direction == TransactionDirection::Outbound)
-                {
-                    pending.push(OutboundTransaction::from(ct.clone()));
-                }
+    match (*wallet).runtime.block_on((*wallet).wallet.audit_outputs(repair)) {
+        Ok(report) => {
+            if !num_duplicates_out.is_null() {
+                *num_duplicates_out = report.duplicate_commitments.len() as c_ulonglong;
             }
-            Box::into_raw(Box::new(TariPendingOutboundTransactions(pending)))
+            report.encumbrances.orphaned.len() as c_ulonglong
         },
         Err(e) => {
-            error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
+            error = LibWalletError::from(e).code;
             ptr::swap(error_out, &mut error as *mut c_int);
-            ptr::null_mut()
+            0
         },
     }
 }
 
-/// Get the all Cancelled Transactions from a TariWallet. This function will also get cancelled pending inbound and
-/// outbound transaction and include them in this list by converting them to CompletedTransactions
+/// This function will tell the wallet to query the set base node to confirm the status of transaction outputs
+/// (TXOs).
 ///
 /// ## Arguments
 /// `wallet` - The TariWallet pointer
@@ -7043,471 +8886,305 @@ pub unsafe extern "C" fn wallet_get_pending_outbound_transactions(
 /// as an out parameter.
 ///
 /// ## Returns
-/// `*mut TariCompletedTransactions` - returns the transactions, note that it returns ptr::null_mut() if
-/// wallet is null or an error is encountered
+/// `c_ulonglong` -  Returns a unique Request Key that is used to identify which callbacks refer to this specific sync
+/// request. Note the result will be 0 if there was an error
 ///
 /// # Safety
-/// The ```completed_transactions_destroy``` method must be called when finished with a TariCompletedTransactions to
-/// prevent a memory leak
+/// None
 #[no_mangle]
-pub unsafe extern "C" fn wallet_get_cancelled_transactions(
-    wallet: *mut TariWallet,
-    error_out: *mut c_int,
-) -> *mut TariCompletedTransactions {
+pub unsafe extern "C" fn wallet_start_txo_validation(wallet: *mut TariWallet, error_out: *mut c_int) -> c_ulonglong {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
-
     if wallet.is_null() {
         error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        return ptr::null_mut();
+        return 0;
     }
 
-    let completed_transactions = match (*wallet).runtime.block_on(
-        (*wallet)
-            .wallet
-            .transaction_service
-            .get_cancelled_completed_transactions(),
-    ) {
-        Ok(txs) => txs,
-        Err(e) => {
-            error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
-            ptr::swap(error_out, &mut error as *mut c_int);
-            return ptr::null_mut();
-        },
-    };
-    let inbound_transactions = match (*wallet).runtime.block_on(
-        (*wallet)
-            .wallet
-            .transaction_service
-            .get_cancelled_pending_inbound_transactions(),
-    ) {
-        Ok(txs) => txs,
-        Err(e) => {
-            error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
-            ptr::swap(error_out, &mut error as *mut c_int);
-            return ptr::null_mut();
-        },
-    };
-    let outbound_transactions = match (*wallet).runtime.block_on(
+    if let Err(e) = (*wallet).runtime.block_on(
         (*wallet)
             .wallet
-            .transaction_service
-            .get_cancelled_pending_outbound_transactions(),
+            .store_and_forward_requester
+            .request_saf_messages_from_neighbours(),
     ) {
-        Ok(txs) => txs,
+        error = LibWalletError::from(e).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return 0;
+    }
+
+    match (*wallet)
+        .runtime
+        .block_on((*wallet).wallet.output_manager_service.validate_txos())
+    {
+        Ok(request_key) => request_key,
         Err(e) => {
-            error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
+            error = LibWalletError::from(WalletError::OutputManagerError(e)).code;
             ptr::swap(error_out, &mut error as *mut c_int);
-            return ptr::null_mut();
+            0
         },
-    };
-
-    let mut completed = Vec::new();
-    for tx in completed_transactions.values() {
-        completed.push(tx.clone());
-    }
-    let wallet_address = TariAddress::new(
-        (*wallet).wallet.comms.node_identity().public_key().clone(),
-        (*wallet).wallet.network.as_network(),
-    );
-    for tx in inbound_transactions.values() {
-        let mut inbound_tx = CompletedTransaction::from(tx.clone());
-        inbound_tx.destination_address = wallet_address.clone();
-        completed.push(inbound_tx);
-    }
-    for tx in outbound_transactions.values() {
-        let mut outbound_tx = CompletedTransaction::from(tx.clone());
-        outbound_tx.source_address = wallet_address.clone();
-        completed.push(outbound_tx);
     }
-
-    Box::into_raw(Box::new(TariCompletedTransactions(completed)))
 }
 
-/// Get the TariCompletedTransaction from a TariWallet by its' TransactionId
+/// This function will tell the wallet to query the set base node to confirm the status of mined transactions.
 ///
 /// ## Arguments
 /// `wallet` - The TariWallet pointer
-/// `transaction_id` - The TransactionId
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `*mut TariCompletedTransaction` - returns the transaction, note that it returns ptr::null_mut() if
-/// wallet is null, an error is encountered or if the transaction is not found
+/// `c_ulonglong` -  Returns a unique Request Key that is used to identify which callbacks refer to this specific sync
+/// request. Note the result will be 0 if there was an error
 ///
 /// # Safety
-/// The ```completed_transaction_destroy``` method must be called when finished with a TariCompletedTransaction to
-/// prevent a memory leak
+/// None
 #[no_mangle]
-pub unsafe extern "C" fn wallet_get_completed_transaction_by_id(
+pub unsafe extern "C" fn wallet_start_transaction_validation(
     wallet: *mut TariWallet,
-    transaction_id: c_ulonglong,
     error_out: *mut c_int,
-) -> *mut TariCompletedTransaction {
+) -> c_ulonglong {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
     if wallet.is_null() {
         error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        return ptr::null_mut();
+        return 0;
     }
 
-    let completed_transactions = (*wallet)
-        .runtime
-        .block_on((*wallet).wallet.transaction_service.get_completed_transactions());
+    if let Err(e) = (*wallet).runtime.block_on(
+        (*wallet)
+            .wallet
+            .store_and_forward_requester
+            .request_saf_messages_from_neighbours(),
+    ) {
+        error = LibWalletError::from(e).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return 0;
+    }
 
-    match completed_transactions {
-        Ok(completed_transactions) => {
-            if let Some(tx) = completed_transactions.get(&TxId::from(transaction_id)) {
-                if tx.status != TransactionStatus::Completed && tx.status != TransactionStatus::Broadcast {
-                    let completed = tx.clone();
-                    return Box::into_raw(Box::new(completed));
-                }
-            }
-            error = 108;
-            ptr::swap(error_out, &mut error as *mut c_int);
-        },
+    match (*wallet)
+        .runtime
+        .block_on((*wallet).wallet.transaction_service.validate_transactions())
+    {
+        Ok(request_key) => request_key.as_u64(),
         Err(e) => {
             error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
             ptr::swap(error_out, &mut error as *mut c_int);
+            0
         },
     }
-
-    ptr::null_mut()
 }
 
-/// Get the TariPendingInboundTransaction from a TariWallet by its' TransactionId
+/// This function will tell the wallet retart any broadcast protocols for completed transactions. Ideally this should be
+/// called after a successfuly Transaction Validation is complete
 ///
 /// ## Arguments
 /// `wallet` - The TariWallet pointer
-/// `transaction_id` - The TransactionId
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `*mut TariPendingInboundTransaction` - returns the transaction, note that it returns ptr::null_mut() if
-/// wallet is null, an error is encountered or if the transaction is not found
+/// `bool` -  Returns a boolean value indicating if the launch was success or not.
 ///
 /// # Safety
-/// The ```pending_inbound_transaction_destroy``` method must be called when finished with a
-/// TariPendingInboundTransaction to prevent a memory leak
+/// None
 #[no_mangle]
-pub unsafe extern "C" fn wallet_get_pending_inbound_transaction_by_id(
-    wallet: *mut TariWallet,
-    transaction_id: c_ulonglong,
-    error_out: *mut c_int,
-) -> *mut TariPendingInboundTransaction {
+pub unsafe extern "C" fn wallet_restart_transaction_broadcast(wallet: *mut TariWallet, error_out: *mut c_int) -> bool {
     let mut error = 0;
-    let transaction_id = TxId::from(transaction_id);
     ptr::swap(error_out, &mut error as *mut c_int);
     if wallet.is_null() {
         error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        return ptr::null_mut();
+        return false;
     }
 
-    let pending_transactions = (*wallet)
-        .runtime
-        .block_on((*wallet).wallet.transaction_service.get_pending_inbound_transactions());
-
-    let completed_transactions = (*wallet)
-        .runtime
-        .block_on((*wallet).wallet.transaction_service.get_completed_transactions());
-
-    match completed_transactions {
-        Ok(completed_transactions) => {
-            if let Some(tx) = completed_transactions.get(&transaction_id) {
-                if (tx.status == TransactionStatus::Broadcast || tx.status == TransactionStatus::Completed) &&
-                    tx.direction == TransactionDirection::Inbound
-                {
-                    let completed = tx.clone();
-                    let pending_tx = TariPendingInboundTransaction::from(completed);
-                    return Box::into_raw(Box::new(pending_tx));
-                }
-            }
-        },
-        Err(e) => {
-            error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
-            ptr::swap(error_out, &mut error as *mut c_int);
-        },
+    if let Err(e) = (*wallet).runtime.block_on(
+        (*wallet)
+            .wallet
+            .store_and_forward_requester
+            .request_saf_messages_from_neighbours(),
+    ) {
+        error = LibWalletError::from(e).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return false;
     }
 
-    match pending_transactions {
-        Ok(pending_transactions) => {
-            if let Some(tx) = pending_transactions.get(&transaction_id) {
-                let pending = tx.clone();
-                return Box::into_raw(Box::new(pending));
-            }
-            error = 108;
-            ptr::swap(error_out, &mut error as *mut c_int);
-        },
+    match (*wallet)
+        .runtime
+        .block_on((*wallet).wallet.transaction_service.restart_broadcast_protocols())
+    {
+        Ok(()) => true,
         Err(e) => {
             error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
             ptr::swap(error_out, &mut error as *mut c_int);
+            false
         },
     }
-
-    ptr::null_mut()
 }
 
-/// Get the TariPendingOutboundTransaction from a TariWallet by its' TransactionId
+/// Gets the seed words representing the seed private key of the provided `TariWallet`.
 ///
 /// ## Arguments
 /// `wallet` - The TariWallet pointer
-/// `transaction_id` - The TransactionId
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `*mut TariPendingOutboundTransaction` - returns the transaction, note that it returns ptr::null_mut() if
-/// wallet is null, an error is encountered or if the transaction is not found
+/// `*mut TariSeedWords` - A collection of the seed words
 ///
 /// # Safety
-/// The ```pending_outbound_transaction_destroy``` method must be called when finished with a
-/// TariPendingOutboundtransaction to prevent a memory leak
+/// The ```tari_seed_words_destroy``` method must be called when finished with a
+/// TariSeedWords to prevent a memory leak
 #[no_mangle]
-pub unsafe extern "C" fn wallet_get_pending_outbound_transaction_by_id(
-    wallet: *mut TariWallet,
-    transaction_id: c_ulonglong,
-    error_out: *mut c_int,
-) -> *mut TariPendingOutboundTransaction {
+pub unsafe extern "C" fn wallet_get_seed_words(wallet: *mut TariWallet, error_out: *mut c_int) -> *mut TariSeedWords {
     let mut error = 0;
-    let transaction_id = TxId::from(transaction_id);
     ptr::swap(error_out, &mut error as *mut c_int);
+
     if wallet.is_null() {
         error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
         return ptr::null_mut();
     }
 
-    let pending_transactions = (*wallet)
-        .runtime
-        .block_on((*wallet).wallet.transaction_service.get_pending_outbound_transactions());
-
-    let completed_transactions = (*wallet)
-        .runtime
-        .block_on((*wallet).wallet.transaction_service.get_completed_transactions());
-
-    match completed_transactions {
-        Ok(completed_transactions) => {
-            if let Some(tx) = completed_transactions.get(&transaction_id) {
-                if (tx.status == TransactionStatus::Broadcast || tx.status == TransactionStatus::Completed) &&
-                    tx.direction == TransactionDirection::Outbound
-                {
-                    let completed = tx.clone();
-                    let pending_tx = TariPendingOutboundTransaction::from(completed);
-                    return Box::into_raw(Box::new(pending_tx));
-                }
-            }
-        },
-        Err(e) => {
-            error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
-            ptr::swap(error_out, &mut error as *mut c_int);
-        },
-    }
-
-    match pending_transactions {
-        Ok(pending_transactions) => {
-            if let Some(tx) = pending_transactions.get(&transaction_id) {
-                let pending = tx.clone();
-                return Box::into_raw(Box::new(pending));
-            }
-            error = 108;
-            ptr::swap(error_out, &mut error as *mut c_int);
-        },
+    match (*wallet).wallet.get_seed_words(&MnemonicLanguage::English) {
+        Ok(seed_words) => Box::into_raw(Box::new(TariSeedWords(seed_words))),
         Err(e) => {
-            error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
+            error = LibWalletError::from(e).code;
             ptr::swap(error_out, &mut error as *mut c_int);
+            ptr::null_mut()
         },
     }
-
-    ptr::null_mut()
 }
 
-/// Get a Cancelled transaction from a TariWallet by its TransactionId. Pending Inbound or Outbound transaction will be
-/// converted to a CompletedTransaction
+/// Re-encrypts the wallet database main key under a new passphrase.
 ///
 /// ## Arguments
 /// `wallet` - The TariWallet pointer
-/// `transaction_id` - The TransactionId
+/// `existing_passphrase` - The current wallet passphrase, may not be null
+/// `new_passphrase` - The passphrase to rotate to, may not be null
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `*mut TariCompletedTransaction` - returns the transaction, note that it returns ptr::null_mut() if
-/// wallet is null, an error is encountered or if the transaction is not found
+/// `bool` - Returns true if the passphrase was rotated, false otherwise
 ///
 /// # Safety
-/// The ```completed_transaction_destroy``` method must be called when finished with a TariCompletedTransaction to
-/// prevent a memory leak
+/// None
 #[no_mangle]
-pub unsafe extern "C" fn wallet_get_cancelled_transaction_by_id(
+pub unsafe extern "C" fn wallet_rotate_passphrase(
     wallet: *mut TariWallet,
-    transaction_id: c_ulonglong,
+    existing_passphrase: *const c_char,
+    new_passphrase: *const c_char,
     error_out: *mut c_int,
-) -> *mut TariCompletedTransaction {
+) -> bool {
     let mut error = 0;
-    let transaction_id = TxId::from(transaction_id);
     ptr::swap(error_out, &mut error as *mut c_int);
+
     if wallet.is_null() {
         error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        return ptr::null_mut();
+        return false;
     }
 
-    let mut transaction = None;
-
-    let mut completed_transactions = match (*wallet).runtime.block_on(
-        (*wallet)
-            .wallet
-            .transaction_service
-            .get_cancelled_completed_transactions(),
-    ) {
-        Ok(txs) => txs,
+    let existing = match ffi_passphrase(existing_passphrase, "existing_passphrase") {
+        Ok(p) => p,
         Err(e) => {
-            error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
+            error = LibWalletError::from(e).code;
             ptr::swap(error_out, &mut error as *mut c_int);
-            return ptr::null_mut();
+            return false;
         },
     };
-
-    if let Some(tx) = completed_transactions.remove(&transaction_id) {
-        transaction = Some(tx);
-    } else {
-        let mut outbound_transactions = match (*wallet).runtime.block_on(
-            (*wallet)
-                .wallet
-                .transaction_service
-                .get_cancelled_pending_outbound_transactions(),
-        ) {
-            Ok(txs) => txs,
-            Err(e) => {
-                error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
-                ptr::swap(error_out, &mut error as *mut c_int);
-                return ptr::null_mut();
-            },
-        };
-        let network = (*wallet).wallet.network.as_network();
-        let address = TariWalletAddress::new((*wallet).wallet.comms.node_identity().public_key().clone(), network);
-        if let Some(tx) = outbound_transactions.remove(&transaction_id) {
-            let mut outbound_tx = CompletedTransaction::from(tx);
-            outbound_tx.source_address = address;
-            transaction = Some(outbound_tx);
-        } else {
-            let mut inbound_transactions = match (*wallet).runtime.block_on(
-                (*wallet)
-                    .wallet
-                    .transaction_service
-                    .get_cancelled_pending_inbound_transactions(),
-            ) {
-                Ok(txs) => txs,
-                Err(e) => {
-                    error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
-                    ptr::swap(error_out, &mut error as *mut c_int);
-                    return ptr::null_mut();
-                },
-            };
-            if let Some(tx) = inbound_transactions.remove(&transaction_id) {
-                let mut inbound_tx = CompletedTransaction::from(tx);
-                inbound_tx.destination_address = address;
-                transaction = Some(inbound_tx);
-            }
-        }
-    }
-
-    match transaction {
-        Some(tx) => {
-            return Box::into_raw(Box::new(tx));
+    let new = match ffi_passphrase(new_passphrase, "new_passphrase") {
+        Ok(p) => p,
+        Err(e) => {
+            error = LibWalletError::from(e).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            return false;
         },
-        None => {
-            error = LibWalletError::from(WalletError::TransactionServiceError(
-                TransactionServiceError::TransactionDoesNotExistError,
-            ))
-            .code;
+    };
+
+    match (*wallet).wallet.rotate_passphrase(&existing, &new) {
+        Ok(()) => true,
+        Err(e) => {
+            error = LibWalletError::from(e).code;
             ptr::swap(error_out, &mut error as *mut c_int);
+            false
         },
     }
-
-    ptr::null_mut()
 }
 
-/// Get the TariWalletAddress from a TariWallet
+/// Locks the wallet database by removing its main encryption key from memory. Encrypted wallet data (such as the seed
+/// words) cannot be accessed until `wallet_unlock` is called.
 ///
 /// ## Arguments
 /// `wallet` - The TariWallet pointer
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
-/// ## Returns
-/// `*mut TariWalletAddress` - returns the address, note that ptr::null_mut() is returned
-/// if wc is null
-///
 /// # Safety
-/// The ```tari_address_destroy``` method must be called when finished with a TariWalletAddress to prevent a memory leak
+/// None
 #[no_mangle]
-pub unsafe extern "C" fn wallet_get_tari_address(
-    wallet: *mut TariWallet,
-    error_out: *mut c_int,
-) -> *mut TariWalletAddress {
+pub unsafe extern "C" fn wallet_lock(wallet: *mut TariWallet, error_out: *mut c_int) {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
+
     if wallet.is_null() {
         error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        return ptr::null_mut();
+        return;
     }
-    let network = (*wallet).wallet.network.as_network();
-    let pk = (*wallet).wallet.comms.node_identity().public_key().clone();
-    let address = TariWalletAddress::new(pk, network);
-    Box::into_raw(Box::new(address))
+
+    (*wallet).wallet.lock();
 }
 
-/// Cancel a Pending Transaction
+/// Unlocks the wallet database using the wallet passphrase.
 ///
 /// ## Arguments
 /// `wallet` - The TariWallet pointer
-/// `transaction_id` - The TransactionId
+/// `passphrase` - The wallet passphrase, may not be null
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `bool` - returns whether the transaction could be cancelled
+/// `bool` - Returns true if the wallet was unlocked, false otherwise
 ///
 /// # Safety
 /// None
 #[no_mangle]
-pub unsafe extern "C" fn wallet_cancel_pending_transaction(
+pub unsafe extern "C" fn wallet_unlock(
     wallet: *mut TariWallet,
-    transaction_id: c_ulonglong,
+    passphrase: *const c_char,
     error_out: *mut c_int,
 ) -> bool {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
+
     if wallet.is_null() {
         error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
         return false;
     }
 
-    match (*wallet).runtime.block_on(
-        (*wallet)
-            .wallet
-            .transaction_service
-            .cancel_transaction(TxId::from(transaction_id)),
-    ) {
-        Ok(_) => true,
+    let passphrase = match ffi_passphrase(passphrase, "passphrase") {
+        Ok(p) => p,
         Err(e) => {
-            error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
+            error = LibWalletError::from(e).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            return false;
+        },
+    };
+
+    match (*wallet).wallet.unlock(&passphrase) {
+        Ok(()) => true,
+        Err(e) => {
+            error = LibWalletError::from(e).code;
             ptr::swap(error_out, &mut error as *mut c_int);
             false
         },
     }
 }
 
-/// This function will tell the wallet to query the set base node to confirm the status of transaction outputs
-/// (TXOs).
+/// Checks whether the wallet database is locked.
 ///
 /// ## Arguments
 /// `wallet` - The TariWallet pointer
@@ -7515,237 +9192,437 @@ pub unsafe extern "C" fn wallet_cancel_pending_transaction(
 /// as an out parameter.
 ///
 /// ## Returns
-/// `c_ulonglong` -  Returns a unique Request Key that is used to identify which callbacks refer to this specific sync
-/// request. Note the result will be 0 if there was an error
+/// `bool` - Returns true if the wallet is locked, false otherwise
 ///
 /// # Safety
 /// None
 #[no_mangle]
-pub unsafe extern "C" fn wallet_start_txo_validation(wallet: *mut TariWallet, error_out: *mut c_int) -> c_ulonglong {
+pub unsafe extern "C" fn wallet_is_locked(wallet: *mut TariWallet, error_out: *mut c_int) -> bool {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
+
     if wallet.is_null() {
         error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        return 0;
+        return false;
     }
 
-    if let Err(e) = (*wallet).runtime.block_on(
-        (*wallet)
-            .wallet
-            .store_and_forward_requester
-            .request_saf_messages_from_neighbours(),
-    ) {
-        error = LibWalletError::from(e).code;
+    (*wallet).wallet.is_locked()
+}
+
+/// Reads a passphrase argument passed over the FFI boundary
+unsafe fn ffi_passphrase(passphrase: *const c_char, name: &str) -> Result<SafePassword, InterfaceError> {
+    if passphrase.is_null() {
+        return Err(InterfaceError::NullError(name.to_string()));
+    }
+    CStr::from_ptr(passphrase)
+        .to_str()
+        .map(|p| SafePassword::from(p.to_owned()))
+        .map_err(|_| InterfaceError::PointerError(name.to_string()))
+}
+
+/// Set the power mode of the wallet to Low Power mode which will reduce the amount of network operations the wallet
+/// performs to conserve power
+///
+/// ## Arguments
+/// `wallet` - The TariWallet pointer
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn wallet_set_low_power_mode(wallet: *mut TariWallet, error_out: *mut c_int) {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if wallet.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        return 0;
+        return;
     }
 
-    match (*wallet)
+    if let Err(e) = (*wallet)
         .runtime
-        .block_on((*wallet).wallet.output_manager_service.validate_txos())
+        .block_on((*wallet).wallet.transaction_service.set_low_power_mode())
     {
-        Ok(request_key) => request_key,
-        Err(e) => {
-            error = LibWalletError::from(WalletError::OutputManagerError(e)).code;
-            ptr::swap(error_out, &mut error as *mut c_int);
-            0
-        },
+        error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
     }
 }
 
-/// This function will tell the wallet to query the set base node to confirm the status of mined transactions.
+/// Set the power mode of the wallet to Normal Power mode which will then use the standard level of network traffic
 ///
 /// ## Arguments
 /// `wallet` - The TariWallet pointer
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn wallet_set_normal_power_mode(wallet: *mut TariWallet, error_out: *mut c_int) {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if wallet.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return;
+    }
+
+    if let Err(e) = (*wallet)
+        .runtime
+        .block_on((*wallet).wallet.transaction_service.set_normal_power_mode())
+    {
+        error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+    }
+}
+
+/// Set a Key Value in the Wallet storage used for Client Key Value store
+///
+/// ## Arguments
+/// `wallet` - The TariWallet pointer.
+/// `key` - The pointer to a Utf8 string representing the Key
+/// `value` - The pointer to a Utf8 string representing the Value ot be stored
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
 ///
 /// ## Returns
-/// `c_ulonglong` -  Returns a unique Request Key that is used to identify which callbacks refer to this specific sync
-/// request. Note the result will be 0 if there was an error
+/// `bool` - Return a boolean value indicating the operation's success or failure. The error_ptr will hold the error
+/// code if there was a failure
 ///
 /// # Safety
 /// None
 #[no_mangle]
-pub unsafe extern "C" fn wallet_start_transaction_validation(
+pub unsafe extern "C" fn wallet_set_key_value(
     wallet: *mut TariWallet,
+    key: *const c_char,
+    value: *const c_char,
     error_out: *mut c_int,
-) -> c_ulonglong {
+) -> bool {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
+
     if wallet.is_null() {
         error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        return 0;
+        return false;
     }
 
-    if let Err(e) = (*wallet).runtime.block_on(
-        (*wallet)
-            .wallet
-            .store_and_forward_requester
-            .request_saf_messages_from_neighbours(),
-    ) {
-        error = LibWalletError::from(e).code;
+    let key_string;
+    if key.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("key".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        return 0;
+        return false;
+    } else {
+        match CStr::from_ptr(key).to_str() {
+            Ok(v) => {
+                key_string = v.to_owned();
+            },
+            _ => {
+                error = LibWalletError::from(InterfaceError::PointerError("key".to_string())).code;
+                ptr::swap(error_out, &mut error as *mut c_int);
+                return false;
+            },
+        }
     }
 
-    match (*wallet)
-        .runtime
-        .block_on((*wallet).wallet.transaction_service.validate_transactions())
-    {
-        Ok(request_key) => request_key.as_u64(),
+    let value_string;
+    if value.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("value".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return false;
+    } else {
+        match CStr::from_ptr(value).to_str() {
+            Ok(v) => {
+                value_string = v.to_owned();
+            },
+            _ => {
+                error = LibWalletError::from(InterfaceError::PointerError("value".to_string())).code;
+                ptr::swap(error_out, &mut error as *mut c_int);
+                return false;
+            },
+        }
+    }
+
+    match (*wallet).wallet.db.set_client_key_value(key_string, value_string) {
+        Ok(_) => true,
         Err(e) => {
-            error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
+            error = LibWalletError::from(WalletError::WalletStorageError(e)).code;
             ptr::swap(error_out, &mut error as *mut c_int);
-            0
+            false
         },
     }
 }
 
-/// This function will tell the wallet retart any broadcast protocols for completed transactions. Ideally this should be
-/// called after a successfuly Transaction Validation is complete
+/// get a stored Value that was previously stored in the Wallet storage used for Client Key Value store
 ///
 /// ## Arguments
-/// `wallet` - The TariWallet pointer
+/// `wallet` - The TariWallet pointer.
+/// `key` - The pointer to a Utf8 string representing the Key
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `bool` -  Returns a boolean value indicating if the launch was success or not.
+/// `*mut c_char` - Returns a pointer to a char array of the Value string. Note that it returns an null pointer if an
+/// error occured.
 ///
 /// # Safety
-/// None
+/// The ```string_destroy``` method must be called when finished with a string from rust to prevent a memory leak
 #[no_mangle]
-pub unsafe extern "C" fn wallet_restart_transaction_broadcast(wallet: *mut TariWallet, error_out: *mut c_int) -> bool {
+pub unsafe extern "C" fn wallet_get_value(
+    wallet: *mut TariWallet,
+    key: *const c_char,
+    error_out: *mut c_int,
+) -> *mut c_char {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
+
     if wallet.is_null() {
         error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        return false;
+        return ptr::null_mut();
     }
 
-    if let Err(e) = (*wallet).runtime.block_on(
-        (*wallet)
-            .wallet
-            .store_and_forward_requester
-            .request_saf_messages_from_neighbours(),
-    ) {
-        error = LibWalletError::from(e).code;
+    let key_string;
+    if key.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("key".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        return false;
+        return ptr::null_mut();
+    } else {
+        match CStr::from_ptr(key).to_str() {
+            Ok(v) => {
+                key_string = v.to_owned();
+            },
+            _ => {
+                error = LibWalletError::from(InterfaceError::PointerError("key".to_string())).code;
+                ptr::swap(error_out, &mut error as *mut c_int);
+                return ptr::null_mut();
+            },
+        }
     }
 
-    match (*wallet)
-        .runtime
-        .block_on((*wallet).wallet.transaction_service.restart_broadcast_protocols())
-    {
-        Ok(()) => true,
+    match (*wallet).wallet.db.get_client_key_value(key_string) {
+        Ok(result) => match result {
+            None => {
+                error = LibWalletError::from(WalletError::WalletStorageError(WalletStorageError::ValuesNotFound)).code;
+                ptr::swap(error_out, &mut error as *mut c_int);
+                ptr::null_mut()
+            },
+            Some(value) => {
+                let v = CString::new(value).expect("Should be able to make a CString");
+                CString::into_raw(v)
+            },
+        },
         Err(e) => {
-            error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
+            error = LibWalletError::from(WalletError::WalletStorageError(e)).code;
             ptr::swap(error_out, &mut error as *mut c_int);
-            false
+            ptr::null_mut()
         },
     }
 }
 
-/// Gets the seed words representing the seed private key of the provided `TariWallet`.
+/// Clears a Value for the provided Key Value in the Wallet storage used for Client Key Value store
 ///
 /// ## Arguments
-/// `wallet` - The TariWallet pointer
+/// `wallet` - The TariWallet pointer.
+/// `key` - The pointer to a Utf8 string representing the Key
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `*mut TariSeedWords` - A collection of the seed words
+/// `bool` - Return a boolean value indicating the operation's success or failure. The error_ptr will hold the error
+/// code if there was a failure
 ///
 /// # Safety
-/// The ```tari_seed_words_destroy``` method must be called when finished with a
-/// TariSeedWords to prevent a memory leak
+/// None
 #[no_mangle]
-pub unsafe extern "C" fn wallet_get_seed_words(wallet: *mut TariWallet, error_out: *mut c_int) -> *mut TariSeedWords {
+pub unsafe extern "C" fn wallet_clear_value(
+    wallet: *mut TariWallet,
+    key: *const c_char,
+    error_out: *mut c_int,
+) -> bool {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
 
     if wallet.is_null() {
         error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        return ptr::null_mut();
+        return false;
     }
 
-    match (*wallet).wallet.get_seed_words(&MnemonicLanguage::English) {
-        Ok(seed_words) => Box::into_raw(Box::new(TariSeedWords(seed_words))),
+    let key_string;
+    if key.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("key".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return false;
+    } else {
+        match CStr::from_ptr(key).to_str() {
+            Ok(v) => {
+                key_string = v.to_owned();
+            },
+            _ => {
+                error = LibWalletError::from(InterfaceError::PointerError("key".to_string())).code;
+                ptr::swap(error_out, &mut error as *mut c_int);
+                return false;
+            },
+        }
+    }
+
+    match (*wallet).wallet.db.clear_client_value(key_string) {
+        Ok(result) => result,
         Err(e) => {
-            error = LibWalletError::from(e).code;
+            error = LibWalletError::from(WalletError::WalletStorageError(e)).code;
             ptr::swap(error_out, &mut error as *mut c_int);
-            ptr::null_mut()
+            false
         },
     }
 }
 
-/// Set the power mode of the wallet to Low Power mode which will reduce the amount of network operations the wallet
-/// performs to conserve power
+/// Check if a Wallet has the data of an In Progress Recovery in its database.
 ///
 /// ## Arguments
-/// `wallet` - The TariWallet pointer
+/// `wallet` - The TariWallet pointer.
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
+///
+/// ## Returns
+/// `bool` - Return a boolean value indicating whether there is an in progress recovery or not. An error will also
+/// result in a false result.
+///
 /// # Safety
 /// None
 #[no_mangle]
-pub unsafe extern "C" fn wallet_set_low_power_mode(wallet: *mut TariWallet, error_out: *mut c_int) {
+pub unsafe extern "C" fn wallet_is_recovery_in_progress(wallet: *mut TariWallet, error_out: *mut c_int) -> bool {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
+
     if wallet.is_null() {
         error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        return;
+        return false;
     }
 
-    if let Err(e) = (*wallet)
-        .runtime
-        .block_on((*wallet).wallet.transaction_service.set_low_power_mode())
-    {
-        error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
-        ptr::swap(error_out, &mut error as *mut c_int);
+    match (*wallet).wallet.is_recovery_in_progress() {
+        Ok(result) => result,
+        Err(e) => {
+            error = LibWalletError::from(e).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            false
+        },
     }
 }
 
-/// Set the power mode of the wallet to Normal Power mode which will then use the standard level of network traffic
+/// Starts the Wallet recovery process.
 ///
 /// ## Arguments
-/// `wallet` - The TariWallet pointer
+/// `wallet` - The TariWallet pointer.
+/// `base_node_public_key` - The TariPublicKey pointer of the Base Node the recovery process will use
+/// `recovery_progress_callback` - The callback function pointer that will be used to asynchronously communicate
+/// progress to the client. The first argument of the callback is an event enum encoded as a u8 as follows:
+/// ```
+/// enum RecoveryEvent {
+///     ConnectingToBaseNode,       // 0
+///     ConnectedToBaseNode,        // 1
+///     ConnectionToBaseNodeFailed, // 2
+///     Progress,                   // 3
+///     Completed,                  // 4
+///     ScanningRoundFailed,        // 5
+///     RecoveryFailed,             // 6
+/// }
+/// ```
+/// The second and third arguments are u64 values that will contain different information depending on the event
+/// that triggered the callback. The meaning of the second and third argument for each event are as follows:
+///     - ConnectingToBaseNode, 0, 0
+///     - ConnectedToBaseNode, 0, 1
+///     - ConnectionToBaseNodeFailed, number of retries, retry limit
+///     - Progress, current block, total number of blocks
+///     - Completed, total number of UTXO's recovered, MicroMinotari recovered,
+///     - ScanningRoundFailed, number of retries, retry limit
+///     - RecoveryFailed, 0, 0
+///
+/// If connection to a base node is successful the flow of callbacks should be:
+///     - The process will start with a callback with `ConnectingToBaseNode` showing a connection is being attempted
+///       this could be repeated multiple times until a connection is made.
+///     - The next a callback with `ConnectedToBaseNode` indicate a successful base node connection and process has
+///       started
+///     - In Progress callbacks will be of the form (n, m) where n < m
+///     - If the process completed successfully then the final `Completed` callback will return how many UTXO's were
+///       scanned and how much MicroMinotari was recovered
+///     - If there is an error in the connection process then the `ConnectionToBaseNodeFailed` will be returned
+///     - If there is a minor error in scanning then `ScanningRoundFailed` will be returned and another connection/sync
+///       attempt will be made
+///     - If a unrecoverable error occurs the `RecoveryFailed` event will be returned and the client will need to start
+///       a new process.
+///
+/// `recovered_output_message` - A string that will be used as the message for any recovered outputs. If Null the
+/// default     message will be used
+///
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
+///
+/// ## Returns
+/// `bool` - Return a boolean value indicating whether the process started successfully or not, the process will
+/// continue to run asynchronously and communicate it progress via the callback. An error will also produce a false
+/// result.
+///
 /// # Safety
 /// None
 #[no_mangle]
-pub unsafe extern "C" fn wallet_set_normal_power_mode(wallet: *mut TariWallet, error_out: *mut c_int) {
+pub unsafe extern "C" fn wallet_start_recovery(
+    wallet: *mut TariWallet,
+    base_node_public_key: *mut TariPublicKey,
+    recovery_progress_callback: unsafe extern "C" fn(u8, u64, u64),
+    recovered_output_message: *const c_char,
+    error_out: *mut c_int,
+) -> bool {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
+
     if wallet.is_null() {
         error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        return;
+        return false;
     }
 
-    if let Err(e) = (*wallet)
-        .runtime
-        .block_on((*wallet).wallet.transaction_service.set_normal_power_mode())
-    {
-        error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
-        ptr::swap(error_out, &mut error as *mut c_int);
+    let shutdown_signal = (*wallet).shutdown.to_signal();
+    let peer_public_keys: Vec<TariPublicKey> = vec![(*base_node_public_key).clone()];
+    let mut recovery_task_builder = UtxoScannerService::<WalletSqliteDatabase, WalletConnectivityHandle>::builder();
+
+    if !recovered_output_message.is_null() {
+        let message_str = match CStr::from_ptr(recovered_output_message).to_str() {
+            Ok(v) => v.to_owned(),
+            _ => {
+                error = LibWalletError::from(InterfaceError::PointerError("recovered_output_message".to_string())).code;
+                ptr::swap(error_out, &mut error as *mut c_int);
+                return false;
+            },
+        };
+        recovery_task_builder.with_recovery_message(message_str);
     }
+
+    let mut recovery_task = recovery_task_builder
+        .with_peers(peer_public_keys)
+        .with_retry_limit(10)
+        .build_with_wallet(&(*wallet).wallet, shutdown_signal);
+
+    let event_stream = recovery_task.get_event_receiver();
+    let recovery_join_handle = (*wallet).runtime.spawn(recovery_task.run());
+
+    // Spawn a task to monitor the recovery process events and call the callback appropriately
+    (*wallet).runtime.spawn(recovery_event_monitoring(
+        event_stream,
+        recovery_join_handle,
+        recovery_progress_callback,
+    ));
+
+    true
 }
 
-/// Set a Key Value in the Wallet storage used for Client Key Value store
+/// Set the text message that is applied to a detected One-Side payment transaction when it is scanned from the
+/// blockchain
 ///
 /// ## Arguments
 /// `wallet` - The TariWallet pointer.
-/// `key` - The pointer to a Utf8 string representing the Key
-/// `value` - The pointer to a Utf8 string representing the Value ot be stored
+/// `message` - The pointer to a Utf8 string representing the Message
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
@@ -7756,10 +9633,9 @@ pub unsafe extern "C" fn wallet_set_normal_power_mode(wallet: *mut TariWallet, e
 /// # Safety
 /// None
 #[no_mangle]
-pub unsafe extern "C" fn wallet_set_key_value(
+pub unsafe extern "C" fn wallet_set_one_sided_payment_message(
     wallet: *mut TariWallet,
-    key: *const c_char,
-    value: *const c_char,
+    message: *const c_char,
     error_out: *mut c_int,
 ) -> bool {
     let mut error = 0;
@@ -7771,630 +9647,583 @@ pub unsafe extern "C" fn wallet_set_key_value(
         return false;
     }
 
-    let key_string;
-    if key.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("key".to_string())).code;
+    let message_string;
+    if message.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("message".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
         return false;
     } else {
-        match CStr::from_ptr(key).to_str() {
+        match CStr::from_ptr(message).to_str() {
             Ok(v) => {
-                key_string = v.to_owned();
+                message_string = v.to_owned();
             },
             _ => {
-                error = LibWalletError::from(InterfaceError::PointerError("key".to_string())).code;
+                error = LibWalletError::from(InterfaceError::PointerError("message".to_string())).code;
                 ptr::swap(error_out, &mut error as *mut c_int);
                 return false;
             },
         }
     }
 
-    let value_string;
-    if value.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("value".to_string())).code;
-        ptr::swap(error_out, &mut error as *mut c_int);
-        return false;
-    } else {
-        match CStr::from_ptr(value).to_str() {
-            Ok(v) => {
-                value_string = v.to_owned();
-            },
-            _ => {
-                error = LibWalletError::from(InterfaceError::PointerError("value".to_string())).code;
-                ptr::swap(error_out, &mut error as *mut c_int);
-                return false;
-            },
-        }
+    (*wallet)
+        .wallet
+        .utxo_scanner_service
+        .set_one_sided_payment_message(message_string);
+
+    true
+}
+
+/// Gets the current emoji set
+///
+/// ## Arguments
+/// `()` - Does not take any arguments
+///
+/// ## Returns
+/// `*mut EmojiSet` - Pointer to the created EmojiSet.
+///
+/// # Safety
+/// The ```emoji_set_destroy``` function must be called when finished with a ByteVector to prevent a memory leak
+#[no_mangle]
+pub unsafe extern "C" fn get_emoji_set() -> *mut EmojiSet {
+    let current_emoji_set = emoji_set();
+    let mut emoji_set: Vec<ByteVector> = Vec::with_capacity(current_emoji_set.len());
+    for emoji in current_emoji_set.iter() {
+        let mut b = [0; 4]; // emojis are 4 bytes, unicode character
+        let emoji_char = ByteVector(emoji.encode_utf8(&mut b).as_bytes().to_vec());
+        emoji_set.push(emoji_char);
     }
+    let result = EmojiSet(emoji_set);
+    Box::into_raw(Box::new(result))
+}
 
-    match (*wallet).wallet.db.set_client_key_value(key_string, value_string) {
-        Ok(_) => true,
-        Err(e) => {
-            error = LibWalletError::from(WalletError::WalletStorageError(e)).code;
-            ptr::swap(error_out, &mut error as *mut c_int);
-            false
-        },
+/// Gets the length of the current emoji set
+///
+/// ## Arguments
+/// `*mut EmojiSet` - Pointer to emoji set
+///
+/// ## Returns
+/// `c_int` - Pointer to the created EmojiSet.
+///
+/// # Safety
+/// None
+// casting here is okay as emoji set wont get larger than u32
+#[allow(clippy::cast_possible_truncation)]
+#[no_mangle]
+pub unsafe extern "C" fn emoji_set_get_length(emoji_set: *const EmojiSet, error_out: *mut c_int) -> c_uint {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if emoji_set.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("emoji_set".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return 0;
     }
+    (*emoji_set).0.len() as c_uint
 }
 
-/// get a stored Value that was previously stored in the Wallet storage used for Client Key Value store
+/// Gets a ByteVector at position in a EmojiSet
 ///
 /// ## Arguments
-/// `wallet` - The TariWallet pointer.
-/// `key` - The pointer to a Utf8 string representing the Key
+/// `emoji_set` - The pointer to a EmojiSet
+/// `position` - The integer position
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `*mut c_char` - Returns a pointer to a char array of the Value string. Note that it returns an null pointer if an
-/// error occured.
+/// `ByteVector` - Returns a ByteVector. Note that the ByteVector will be null if ptr
+/// is null or if the position is invalid
 ///
 /// # Safety
-/// The ```string_destroy``` method must be called when finished with a string from rust to prevent a memory leak
+/// The ```byte_vector_destroy``` function must be called when finished with the ByteVector to prevent a memory leak.
 #[no_mangle]
-pub unsafe extern "C" fn wallet_get_value(
-    wallet: *mut TariWallet,
-    key: *const c_char,
+pub unsafe extern "C" fn emoji_set_get_at(
+    emoji_set: *const EmojiSet,
+    position: c_uint,
     error_out: *mut c_int,
-) -> *mut c_char {
+) -> *mut ByteVector {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
-
-    if wallet.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
+    if emoji_set.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("emoji_set".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
         return ptr::null_mut();
     }
-
-    let key_string;
-    if key.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("key".to_string())).code;
+    let last_index = emoji_set_get_length(emoji_set, error_out) - 1;
+    if position > last_index {
+        error = LibWalletError::from(InterfaceError::PositionInvalidError).code;
         ptr::swap(error_out, &mut error as *mut c_int);
         return ptr::null_mut();
-    } else {
-        match CStr::from_ptr(key).to_str() {
-            Ok(v) => {
-                key_string = v.to_owned();
-            },
-            _ => {
-                error = LibWalletError::from(InterfaceError::PointerError("key".to_string())).code;
-                ptr::swap(error_out, &mut error as *mut c_int);
-                return ptr::null_mut();
-            },
-        }
     }
+    let result = (*emoji_set).0[position as usize].clone();
+    Box::into_raw(Box::new(result))
+}
 
-    match (*wallet).wallet.db.get_client_key_value(key_string) {
-        Ok(result) => match result {
-            None => {
-                error = LibWalletError::from(WalletError::WalletStorageError(WalletStorageError::ValuesNotFound)).code;
-                ptr::swap(error_out, &mut error as *mut c_int);
-                ptr::null_mut()
-            },
-            Some(value) => {
-                let v = CString::new(value).expect("Should be able to make a CString");
-                CString::into_raw(v)
-            },
-        },
-        Err(e) => {
-            error = LibWalletError::from(WalletError::WalletStorageError(e)).code;
-            ptr::swap(error_out, &mut error as *mut c_int);
-            ptr::null_mut()
-        },
+/// Frees memory for a EmojiSet
+///
+/// ## Arguments
+/// `emoji_set` - The EmojiSet pointer
+///
+/// ## Returns
+/// `()` - Does not return a value, equivalent to void in C
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn emoji_set_destroy(emoji_set: *mut EmojiSet) {
+    if !emoji_set.is_null() {
+        drop(Box::from_raw(emoji_set))
     }
 }
 
-/// Clears a Value for the provided Key Value in the Wallet storage used for Client Key Value store
+/// Frees memory for a TariWallet
 ///
 /// ## Arguments
-/// `wallet` - The TariWallet pointer.
-/// `key` - The pointer to a Utf8 string representing the Key
-/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
-/// as an out parameter.
+/// `wallet` - The TariWallet pointer
 ///
 /// ## Returns
-/// `bool` - Return a boolean value indicating the operation's success or failure. The error_ptr will hold the error
-/// code if there was a failure
+/// `()` - Does not return a value, equivalent to void in C
 ///
 /// # Safety
 /// None
 #[no_mangle]
-pub unsafe extern "C" fn wallet_clear_value(
-    wallet: *mut TariWallet,
-    key: *const c_char,
-    error_out: *mut c_int,
-) -> bool {
-    let mut error = 0;
-    ptr::swap(error_out, &mut error as *mut c_int);
-
-    if wallet.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
-        ptr::swap(error_out, &mut error as *mut c_int);
-        return false;
+pub unsafe extern "C" fn wallet_destroy(wallet: *mut TariWallet) {
+    if !wallet.is_null() {
+        let mut w = Box::from_raw(wallet);
+        w.shutdown.trigger();
+        w.runtime.block_on(w.wallet.wait_until_shutdown());
     }
+}
 
-    let key_string;
-    if key.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("key".to_string())).code;
-        ptr::swap(error_out, &mut error as *mut c_int);
-        return false;
-    } else {
-        match CStr::from_ptr(key).to_str() {
+/// This function will log the provided string at debug level. To be used to have a client log messages to the LibWallet
+/// logs.
+///
+/// ## Arguments
+/// `msg` - A string that will be logged at the debug level. If msg is null nothing will be done.
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn log_debug_message(msg: *const c_char, error_out: *mut c_int) {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    let message;
+    if !msg.is_null() {
+        match CStr::from_ptr(msg).to_str() {
             Ok(v) => {
-                key_string = v.to_owned();
+                message = v.to_owned();
             },
             _ => {
-                error = LibWalletError::from(InterfaceError::PointerError("key".to_string())).code;
+                error = LibWalletError::from(InterfaceError::PointerError("msg".to_string())).code;
                 ptr::swap(error_out, &mut error as *mut c_int);
-                return false;
+                return;
             },
         }
-    }
-
-    match (*wallet).wallet.db.clear_client_value(key_string) {
-        Ok(result) => result,
-        Err(e) => {
-            error = LibWalletError::from(WalletError::WalletStorageError(e)).code;
-            ptr::swap(error_out, &mut error as *mut c_int);
-            false
-        },
+        debug!(target: LOG_TARGET, "{}", message);
     }
 }
 
-/// Check if a Wallet has the data of an In Progress Recovery in its database.
+/// ------------------------------------- FeePerGramStats ------------------------------------ ///
+
+/// Get the TariFeePerGramStats from a TariWallet.
 ///
 /// ## Arguments
-/// `wallet` - The TariWallet pointer.
+/// `wallet` - The TariWallet pointer
+/// `count` - The maximum number of blocks to be checked
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
-/// as an out parameter.
+/// as an out parameter
 ///
 /// ## Returns
-/// `bool` - Return a boolean value indicating whether there is an in progress recovery or not. An error will also
-/// result in a false result.
+/// `*mut TariCompletedTransactions` - returns the transactions, note that it returns ptr::null_mut() if
+/// wallet is null or an error is encountered.
 ///
 /// # Safety
-/// None
+/// The ```fee_per_gram_stats_destroy``` method must be called when finished with a TariFeePerGramStats to prevent
+/// a memory leak.
 #[no_mangle]
-pub unsafe extern "C" fn wallet_is_recovery_in_progress(wallet: *mut TariWallet, error_out: *mut c_int) -> bool {
+pub unsafe extern "C" fn wallet_get_fee_per_gram_stats(
+    wallet: *mut TariWallet,
+    count: c_uint,
+    error_out: *mut c_int,
+) -> *mut TariFeePerGramStats {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
 
     if wallet.is_null() {
         error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        return false;
+        return ptr::null_mut();
     }
 
-    match (*wallet).wallet.is_recovery_in_progress() {
-        Ok(result) => result,
+    match (*wallet).runtime.block_on(
+        (*wallet)
+            .wallet
+            .transaction_service
+            .get_fee_per_gram_stats_per_block(count as usize),
+    ) {
+        Ok(estimates) => Box::into_raw(Box::new(estimates)),
         Err(e) => {
-            error = LibWalletError::from(e).code;
+            error!(target: LOG_TARGET, "Error getting the fee estimates: {:?}", e);
+            error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
             ptr::swap(error_out, &mut error as *mut c_int);
-            false
+            ptr::null_mut()
         },
     }
 }
 
-/// Starts the Wallet recovery process.
+/// Get length of stats from the TariFeePerGramStats.
 ///
 /// ## Arguments
-/// `wallet` - The TariWallet pointer.
-/// `base_node_public_key` - The TariPublicKey pointer of the Base Node the recovery process will use
-/// `recovery_progress_callback` - The callback function pointer that will be used to asynchronously communicate
-/// progress to the client. The first argument of the callback is an event enum encoded as a u8 as follows:
-/// ```
-/// enum RecoveryEvent {
-///     ConnectingToBaseNode,       // 0
-///     ConnectedToBaseNode,        // 1
-///     ConnectionToBaseNodeFailed, // 2
-///     Progress,                   // 3
-///     Completed,                  // 4
-///     ScanningRoundFailed,        // 5
-///     RecoveryFailed,             // 6
-/// }
-/// ```
-/// The second and third arguments are u64 values that will contain different information depending on the event
-/// that triggered the callback. The meaning of the second and third argument for each event are as follows:
-///     - ConnectingToBaseNode, 0, 0
-///     - ConnectedToBaseNode, 0, 1
-///     - ConnectionToBaseNodeFailed, number of retries, retry limit
-///     - Progress, current block, total number of blocks
-///     - Completed, total number of UTXO's recovered, MicroMinotari recovered,
-///     - ScanningRoundFailed, number of retries, retry limit
-///     - RecoveryFailed, 0, 0
+/// `fee_per_gram_stats` - The pointer to a TariFeePerGramStats
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter
 ///
-/// If connection to a base node is successful the flow of callbacks should be:
-///     - The process will start with a callback with `ConnectingToBaseNode` showing a connection is being attempted
-///       this could be repeated multiple times until a connection is made.
-///     - The next a callback with `ConnectedToBaseNode` indicate a successful base node connection and process has
-///       started
-///     - In Progress callbacks will be of the form (n, m) where n < m
-///     - If the process completed successfully then the final `Completed` callback will return how many UTXO's were
-///       scanned and how much MicroMinotari was recovered
-///     - If there is an error in the connection process then the `ConnectionToBaseNodeFailed` will be returned
-///     - If there is a minor error in scanning then `ScanningRoundFailed` will be returned and another connection/sync
-///       attempt will be made
-///     - If a unrecoverable error occurs the `RecoveryFailed` event will be returned and the client will need to start
-///       a new process.
+/// ## Returns
+/// `c_uint` - length of stats in TariFeePerGramStats
 ///
-/// `recovered_output_message` - A string that will be used as the message for any recovered outputs. If Null the
-/// default     message will be used
+/// # Safety
+/// None
+// casting here is okay as fee per gram stats cannot get larger than u32
+#[allow(clippy::cast_possible_truncation)]
+#[no_mangle]
+pub unsafe extern "C" fn fee_per_gram_stats_get_length(
+    fee_per_gram_stats: *mut TariFeePerGramStats,
+    error_out: *mut c_int,
+) -> c_uint {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    let mut len = 0;
+    if fee_per_gram_stats.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("fee_per_gram_stats".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+    } else {
+        len = (*fee_per_gram_stats).stats.len();
+    }
+    len as c_uint
+}
+
+/// Get TariFeePerGramStat at position from the TariFeePerGramStats.
 ///
+/// ## Arguments
+/// `fee_per_gram_stats` - The pointer to a TariFeePerGramStats.
+/// `position` - The integer position.
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `bool` - Return a boolean value indicating whether the process started successfully or not, the process will
-/// continue to run asynchronously and communicate it progress via the callback. An error will also produce a false
-/// result.
+/// `*mut TariCompletedTransactions` - returns the TariFeePerGramStat, note that it returns ptr::null_mut() if
+/// fee_per_gram_stats is null or an error is encountered.
 ///
 /// # Safety
-/// None
+/// The ```fee_per_gram_stat_destroy``` method must be called when finished with a TariCompletedTransactions to 4prevent
+/// a memory leak.
 #[no_mangle]
-pub unsafe extern "C" fn wallet_start_recovery(
-    wallet: *mut TariWallet,
-    base_node_public_key: *mut TariPublicKey,
-    recovery_progress_callback: unsafe extern "C" fn(u8, u64, u64),
-    recovered_output_message: *const c_char,
+pub unsafe extern "C" fn fee_per_gram_stats_get_at(
+    fee_per_gram_stats: *mut TariFeePerGramStats,
+    position: c_uint,
     error_out: *mut c_int,
-) -> bool {
+) -> *mut TariFeePerGramStat {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
-
-    if wallet.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
+    if fee_per_gram_stats.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("fee_per_gram_stats".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        return false;
+        return ptr::null_mut();
     }
-
-    let shutdown_signal = (*wallet).shutdown.to_signal();
-    let peer_public_keys: Vec<TariPublicKey> = vec![(*base_node_public_key).clone()];
-    let mut recovery_task_builder = UtxoScannerService::<WalletSqliteDatabase, WalletConnectivityHandle>::builder();
-
-    if !recovered_output_message.is_null() {
-        let message_str = match CStr::from_ptr(recovered_output_message).to_str() {
-            Ok(v) => v.to_owned(),
-            _ => {
-                error = LibWalletError::from(InterfaceError::PointerError("recovered_output_message".to_string())).code;
-                ptr::swap(error_out, &mut error as *mut c_int);
-                return false;
-            },
-        };
-        recovery_task_builder.with_recovery_message(message_str);
+    let len = fee_per_gram_stats_get_length(fee_per_gram_stats, error_out);
+    if *error_out != 0 {
+        return ptr::null_mut();
     }
-
-    let mut recovery_task = recovery_task_builder
-        .with_peers(peer_public_keys)
-        .with_retry_limit(10)
-        .build_with_wallet(&(*wallet).wallet, shutdown_signal);
-
-    let event_stream = recovery_task.get_event_receiver();
-    let recovery_join_handle = (*wallet).runtime.spawn(recovery_task.run());
-
-    // Spawn a task to monitor the recovery process events and call the callback appropriately
-    (*wallet).runtime.spawn(recovery_event_monitoring(
-        event_stream,
-        recovery_join_handle,
-        recovery_progress_callback,
-    ));
-
-    true
+    if len == 0 || position > len - 1 {
+        error = LibWalletError::from(InterfaceError::PositionInvalidError).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return ptr::null_mut();
+    }
+    Box::into_raw(Box::new((*fee_per_gram_stats).stats[position as usize].clone()))
 }
 
-/// Set the text message that is applied to a detected One-Side payment transaction when it is scanned from the
-/// blockchain
+/// Get the number of blocks expected until a transaction paying the given fee per gram is mined, based on the
+/// TariFeePerGramStats of the upcoming blocks.
 ///
 /// ## Arguments
-/// `wallet` - The TariWallet pointer.
-/// `message` - The pointer to a Utf8 string representing the Message
+/// `fee_per_gram_stats` - The pointer to a TariFeePerGramStats.
+/// `fee_per_gram` - The fee per gram of the transaction.
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `bool` - Return a boolean value indicating the operation's success or failure. The error_ptr will hold the error
-/// code if there was a failure
+/// `c_ulonglong` - Returns the expected number of blocks, i.e. 1 for the next block, or 0 if the fee per gram is too
+/// low to be expected within the blocks covered by the stats or if an error is encountered.
 ///
 /// # Safety
 /// None
 #[no_mangle]
-pub unsafe extern "C" fn wallet_set_one_sided_payment_message(
-    wallet: *mut TariWallet,
-    message: *const c_char,
+pub unsafe extern "C" fn fee_per_gram_stats_get_expected_blocks(
+    fee_per_gram_stats: *mut TariFeePerGramStats,
+    fee_per_gram: c_ulonglong,
     error_out: *mut c_int,
-) -> bool {
+) -> c_ulonglong {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
-
-    if wallet.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
-        ptr::swap(error_out, &mut error as *mut c_int);
-        return false;
-    }
-
-    let message_string;
-    if message.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("message".to_string())).code;
+    if fee_per_gram_stats.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("fee_per_gram_stats".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        return false;
-    } else {
-        match CStr::from_ptr(message).to_str() {
-            Ok(v) => {
-                message_string = v.to_owned();
-            },
-            _ => {
-                error = LibWalletError::from(InterfaceError::PointerError("message".to_string())).code;
-                ptr::swap(error_out, &mut error as *mut c_int);
-                return false;
-            },
-        }
+        return 0;
     }
-
-    (*wallet)
-        .wallet
-        .utxo_scanner_service
-        .set_one_sided_payment_message(message_string);
-
-    true
+    (*fee_per_gram_stats)
+        .expected_blocks_until_mined(MicroMinotari::from(fee_per_gram))
+        .unwrap_or(0)
 }
 
-/// Gets the current emoji set
+/// Frees memory for a TariFeePerGramStats
 ///
 /// ## Arguments
-/// `()` - Does not take any arguments
+/// `fee_per_gram_stats` - The TariFeePerGramStats pointer
 ///
 /// ## Returns
-/// `*mut EmojiSet` - Pointer to the created EmojiSet.
+/// `()` - Does not return a value, equivalent to void in C
 ///
 /// # Safety
-/// The ```emoji_set_destroy``` function must be called when finished with a ByteVector to prevent a memory leak
+/// None
 #[no_mangle]
-pub unsafe extern "C" fn get_emoji_set() -> *mut EmojiSet {
-    let current_emoji_set = emoji_set();
-    let mut emoji_set: Vec<ByteVector> = Vec::with_capacity(current_emoji_set.len());
-    for emoji in current_emoji_set.iter() {
-        let mut b = [0; 4]; // emojis are 4 bytes, unicode character
-        let emoji_char = ByteVector(emoji.encode_utf8(&mut b).as_bytes().to_vec());
-        emoji_set.push(emoji_char);
+pub unsafe extern "C" fn fee_per_gram_stats_destroy(fee_per_gram_stats: *mut TariFeePerGramStats) {
+    if !fee_per_gram_stats.is_null() {
+        drop(Box::from_raw(fee_per_gram_stats))
     }
-    let result = EmojiSet(emoji_set);
-    Box::into_raw(Box::new(result))
 }
 
-/// Gets the length of the current emoji set
+/// ------------------------------------------------------------------------------------------ ///
+
+/// ------------------------------------- FeePerGramStat ------------------------------------- ///
+
+/// Get the order of TariFeePerGramStat
 ///
 /// ## Arguments
-/// `*mut EmojiSet` - Pointer to emoji set
+/// `fee_per_gram_stats` - The TariFeePerGramStat pointer
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
 ///
 /// ## Returns
-/// `c_int` - Pointer to the created EmojiSet.
+/// `c_ulonglong` - Returns order
 ///
 /// # Safety
 /// None
-// casting here is okay as emoji set wont get larger than u32
-#[allow(clippy::cast_possible_truncation)]
 #[no_mangle]
-pub unsafe extern "C" fn emoji_set_get_length(emoji_set: *const EmojiSet, error_out: *mut c_int) -> c_uint {
+pub unsafe extern "C" fn fee_per_gram_stat_get_order(
+    fee_per_gram_stat: *mut TariFeePerGramStat,
+    error_out: *mut c_int,
+) -> c_ulonglong {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
-    if emoji_set.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("emoji_set".to_string())).code;
+    let mut order = 0;
+    if fee_per_gram_stat.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("fee_per_gram_stat".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        return 0;
+    } else {
+        order = (*fee_per_gram_stat).order;
     }
-    (*emoji_set).0.len() as c_uint
+    order
 }
 
-/// Gets a ByteVector at position in a EmojiSet
+/// Get the minimum fee per gram of TariFeePerGramStat
 ///
 /// ## Arguments
-/// `emoji_set` - The pointer to a EmojiSet
-/// `position` - The integer position
+/// `fee_per_gram_stats` - The TariFeePerGramStat pointer
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `ByteVector` - Returns a ByteVector. Note that the ByteVector will be null if ptr
-/// is null or if the position is invalid
+/// `c_ulonglong` - Returns minimum fee per gram
 ///
 /// # Safety
-/// The ```byte_vector_destroy``` function must be called when finished with the ByteVector to prevent a memory leak.
+/// None
 #[no_mangle]
-pub unsafe extern "C" fn emoji_set_get_at(
-    emoji_set: *const EmojiSet,
-    position: c_uint,
+pub unsafe extern "C" fn fee_per_gram_stat_get_min_fee_per_gram(
+    fee_per_gram_stat: *mut TariFeePerGramStat,
     error_out: *mut c_int,
-) -> *mut ByteVector {
+) -> c_ulonglong {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
-    if emoji_set.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("emoji_set".to_string())).code;
-        ptr::swap(error_out, &mut error as *mut c_int);
-        return ptr::null_mut();
-    }
-    let last_index = emoji_set_get_length(emoji_set, error_out) - 1;
-    if position > last_index {
-        error = LibWalletError::from(InterfaceError::PositionInvalidError).code;
+    let mut fee_per_gram = 0;
+    if fee_per_gram_stat.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("fee_per_gram_stat".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-        return ptr::null_mut();
+    } else {
+        fee_per_gram = (*fee_per_gram_stat).min_fee_per_gram.as_u64();
     }
-    let result = (*emoji_set).0[position as usize].clone();
-    Box::into_raw(Box::new(result))
+    fee_per_gram
 }
 
-/// Frees memory for a EmojiSet
+/// Get the average fee per gram of TariFeePerGramStat
 ///
 /// ## Arguments
-/// `emoji_set` - The EmojiSet pointer
+/// `fee_per_gram_stats` - The TariFeePerGramStat pointer
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
 ///
 /// ## Returns
-/// `()` - Does not return a value, equivalent to void in C
+/// `c_ulonglong` - Returns average fee per gram
 ///
 /// # Safety
 /// None
 #[no_mangle]
-pub unsafe extern "C" fn emoji_set_destroy(emoji_set: *mut EmojiSet) {
-    if !emoji_set.is_null() {
-        drop(Box::from_raw(emoji_set))
+pub unsafe extern "C" fn fee_per_gram_stat_get_avg_fee_per_gram(
+    fee_per_gram_stat: *mut TariFeePerGramStat,
+    error_out: *mut c_int,
+) -> c_ulonglong {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    let mut fee_per_gram = 0;
+    if fee_per_gram_stat.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("fee_per_gram_stat".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+    } else {
+        fee_per_gram = (*fee_per_gram_stat).avg_fee_per_gram.as_u64();
     }
+    fee_per_gram
 }
 
-/// Frees memory for a TariWallet
+/// Get the maximum fee per gram of TariFeePerGramStat
 ///
 /// ## Arguments
-/// `wallet` - The TariWallet pointer
+/// `fee_per_gram_stats` - The TariFeePerGramStat pointer
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
 ///
 /// ## Returns
-/// `()` - Does not return a value, equivalent to void in C
+/// `c_ulonglong` - Returns maximum fee per gram
 ///
 /// # Safety
 /// None
 #[no_mangle]
-pub unsafe extern "C" fn wallet_destroy(wallet: *mut TariWallet) {
-    if !wallet.is_null() {
-        let mut w = Box::from_raw(wallet);
-        w.shutdown.trigger();
-        w.runtime.block_on(w.wallet.wait_until_shutdown());
+pub unsafe extern "C" fn fee_per_gram_stat_get_max_fee_per_gram(
+    fee_per_gram_stat: *mut TariFeePerGramStat,
+    error_out: *mut c_int,
+) -> c_ulonglong {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    let mut fee_per_gram = 0;
+    if fee_per_gram_stat.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("fee_per_gram_stat".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+    } else {
+        fee_per_gram = (*fee_per_gram_stat).max_fee_per_gram.as_u64();
     }
+    fee_per_gram
 }
 
-/// This function will log the provided string at debug level. To be used to have a client log messages to the LibWallet
-/// logs.
+/// Frees memory for a TariFeePerGramStat
 ///
 /// ## Arguments
-/// `msg` - A string that will be logged at the debug level. If msg is null nothing will be done.
-/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
-/// as an out parameter.
+/// `fee_per_gram_stats` - The TariFeePerGramStat pointer
+///
+/// ## Returns
+/// `()` - Does not return a value, equivalent to void in C
 ///
 /// # Safety
 /// None
 #[no_mangle]
-pub unsafe extern "C" fn log_debug_message(msg: *const c_char, error_out: *mut c_int) {
-    let mut error = 0;
-    ptr::swap(error_out, &mut error as *mut c_int);
-    let message;
-    if !msg.is_null() {
-        match CStr::from_ptr(msg).to_str() {
-            Ok(v) => {
-                message = v.to_owned();
-            },
-            _ => {
-                error = LibWalletError::from(InterfaceError::PointerError("msg".to_string())).code;
-                ptr::swap(error_out, &mut error as *mut c_int);
-                return;
-            },
-        }
-        debug!(target: LOG_TARGET, "{}", message);
+pub unsafe extern "C" fn fee_per_gram_stat_destroy(fee_per_gram_stat: *mut TariFeePerGramStat) {
+    if !fee_per_gram_stat.is_null() {
+        drop(Box::from_raw(fee_per_gram_stat))
     }
 }
 
-/// ------------------------------------- FeePerGramStats ------------------------------------ ///
+/// ------------------------------------------------------------------------------------------ ///
 
-/// Get the TariFeePerGramStats from a TariWallet.
+/// ------------------------------------- BaseNodeCandidates --------------------------------- ///
+
+/// Get the base node candidates that the TariWallet can fail over to, including the active base node
 ///
 /// ## Arguments
 /// `wallet` - The TariWallet pointer
-/// `count` - The maximum number of blocks to be checked
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter
 ///
 /// ## Returns
-/// `*mut TariCompletedTransactions` - returns the transactions, note that it returns ptr::null_mut() if
-/// wallet is null or an error is encountered.
+/// `*mut TariBaseNodeCandidates` - returns the base node candidates, note that it returns ptr::null_mut() if wallet is
+/// null
 ///
 /// # Safety
-/// The ```fee_per_gram_stats_destroy``` method must be called when finished with a TariFeePerGramStats to prevent
-/// a memory leak.
+/// The ```base_node_candidates_destroy``` method must be called when finished with a TariBaseNodeCandidates to
+/// prevent a memory leak.
 #[no_mangle]
-pub unsafe extern "C" fn wallet_get_fee_per_gram_stats(
+pub unsafe extern "C" fn wallet_get_base_node_candidates(
     wallet: *mut TariWallet,
-    count: c_uint,
     error_out: *mut c_int,
-) -> *mut TariFeePerGramStats {
+) -> *mut TariBaseNodeCandidates {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
-
     if wallet.is_null() {
         error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
         return ptr::null_mut();
     }
 
-    match (*wallet).runtime.block_on(
-        (*wallet)
-            .wallet
-            .transaction_service
-            .get_fee_per_gram_stats_per_block(count as usize),
-    ) {
-        Ok(estimates) => Box::into_raw(Box::new(estimates)),
-        Err(e) => {
-            error!(target: LOG_TARGET, "Error getting the fee estimates: {:?}", e);
-            error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
-            ptr::swap(error_out, &mut error as *mut c_int);
-            ptr::null_mut()
-        },
-    }
+    let candidates = (*wallet).wallet.wallet_connectivity.get_base_node_candidates();
+    Box::into_raw(Box::new(TariBaseNodeCandidates(candidates)))
 }
 
-/// Get length of stats from the TariFeePerGramStats.
+/// Get length of the TariBaseNodeCandidates.
 ///
 /// ## Arguments
-/// `fee_per_gram_stats` - The pointer to a TariFeePerGramStats
+/// `base_node_candidates` - The pointer to a TariBaseNodeCandidates
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter
 ///
 /// ## Returns
-/// `c_uint` - length of stats in TariFeePerGramStats
+/// `c_uint` - length of the TariBaseNodeCandidates
 ///
 /// # Safety
 /// None
-// casting here is okay as fee per gram stats cannot get larger than u32
+// casting here is okay as the number of base node candidates cannot get larger than u32
 #[allow(clippy::cast_possible_truncation)]
 #[no_mangle]
-pub unsafe extern "C" fn fee_per_gram_stats_get_length(
-    fee_per_gram_stats: *mut TariFeePerGramStats,
+pub unsafe extern "C" fn base_node_candidates_get_length(
+    base_node_candidates: *mut TariBaseNodeCandidates,
     error_out: *mut c_int,
 ) -> c_uint {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
     let mut len = 0;
-    if fee_per_gram_stats.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("fee_per_gram_stats".to_string())).code;
+    if base_node_candidates.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("base_node_candidates".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
     } else {
-        len = (*fee_per_gram_stats).stats.len();
+        len = (*base_node_candidates).0.len();
     }
     len as c_uint
 }
 
-/// Get TariFeePerGramStat at position from the TariFeePerGramStats.
+/// Get TariBaseNodeCandidate at position from the TariBaseNodeCandidates.
 ///
 /// ## Arguments
-/// `fee_per_gram_stats` - The pointer to a TariFeePerGramStats.
+/// `base_node_candidates` - The pointer to a TariBaseNodeCandidates.
 /// `position` - The integer position.
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `*mut TariCompletedTransactions` - returns the TariFeePerGramStat, note that it returns ptr::null_mut() if
-/// fee_per_gram_stats is null or an error is encountered.
+/// `*mut TariBaseNodeCandidate` - returns the TariBaseNodeCandidate, note that it returns ptr::null_mut() if
+/// base_node_candidates is null or position is invalid.
 ///
 /// # Safety
-/// The ```fee_per_gram_stat_destroy``` method must be called when finished with a TariCompletedTransactions to 4prevent
+/// The ```base_node_candidate_destroy``` method must be called when finished with a TariBaseNodeCandidate to prevent
 /// a memory leak.
 #[no_mangle]
-pub unsafe extern "C" fn fee_per_gram_stats_get_at(
-    fee_per_gram_stats: *mut TariFeePerGramStats,
+pub unsafe extern "C" fn base_node_candidates_get_at(
+    base_node_candidates: *mut TariBaseNodeCandidates,
     position: c_uint,
     error_out: *mut c_int,
-) -> *mut TariFeePerGramStat {
+) -> *mut TariBaseNodeCandidate {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
-    if fee_per_gram_stats.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("fee_per_gram_stats".to_string())).code;
+    if base_node_candidates.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("base_node_candidates".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
         return ptr::null_mut();
     }
-    let len = fee_per_gram_stats_get_length(fee_per_gram_stats, error_out);
+    let len = base_node_candidates_get_length(base_node_candidates, error_out);
     if *error_out != 0 {
         return ptr::null_mut();
     }
@@ -8403,13 +10232,13 @@ pub unsafe extern "C" fn fee_per_gram_stats_get_at(
         ptr::swap(error_out, &mut error as *mut c_int);
         return ptr::null_mut();
     }
-    Box::into_raw(Box::new((*fee_per_gram_stats).stats[position as usize].clone()))
+    Box::into_raw(Box::new((*base_node_candidates).0[position as usize].clone()))
 }
 
-/// Frees memory for a TariFeePerGramStats
+/// Frees memory for a TariBaseNodeCandidates
 ///
 /// ## Arguments
-/// `fee_per_gram_stats` - The TariFeePerGramStats pointer
+/// `base_node_candidates` - The TariBaseNodeCandidates pointer
 ///
 /// ## Returns
 /// `()` - Does not return a value, equivalent to void in C
@@ -8417,136 +10246,213 @@ pub unsafe extern "C" fn fee_per_gram_stats_get_at(
 /// # Safety
 /// None
 #[no_mangle]
-pub unsafe extern "C" fn fee_per_gram_stats_destroy(fee_per_gram_stats: *mut TariFeePerGramStats) {
-    if !fee_per_gram_stats.is_null() {
-        drop(Box::from_raw(fee_per_gram_stats))
+pub unsafe extern "C" fn base_node_candidates_destroy(base_node_candidates: *mut TariBaseNodeCandidates) {
+    if !base_node_candidates.is_null() {
+        drop(Box::from_raw(base_node_candidates))
     }
 }
 
 /// ------------------------------------------------------------------------------------------ ///
 
-/// ------------------------------------- FeePerGramStat ------------------------------------- ///
+/// ------------------------------------- BaseNodeCandidate ---------------------------------- ///
 
-/// Get the order of TariFeePerGramStat
+/// Get the public key of a TariBaseNodeCandidate
 ///
 /// ## Arguments
-/// `fee_per_gram_stats` - The TariFeePerGramStat pointer
+/// `base_node_candidate` - The TariBaseNodeCandidate pointer
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `c_ulonglong` - Returns order
+/// `*mut TariPublicKey` - Returns the public key, note that it returns ptr::null_mut() if base_node_candidate is
+/// null
+///
+/// # Safety
+/// The ```public_key_destroy``` method must be called when finished with the TariPublicKey to prevent a memory leak.
+#[no_mangle]
+pub unsafe extern "C" fn base_node_candidate_get_public_key(
+    base_node_candidate: *mut TariBaseNodeCandidate,
+    error_out: *mut c_int,
+) -> *mut TariPublicKey {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if base_node_candidate.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("base_node_candidate".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return ptr::null_mut();
+    }
+    Box::into_raw(Box::new((*base_node_candidate).public_key.clone()))
+}
+
+/// Get the latency in milliseconds of the last successful check of a TariBaseNodeCandidate
+///
+/// ## Arguments
+/// `base_node_candidate` - The TariBaseNodeCandidate pointer
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `c_ulonglong` - Returns the latency, or 0 if the base node has not been checked yet
 ///
 /// # Safety
 /// None
 #[no_mangle]
-pub unsafe extern "C" fn fee_per_gram_stat_get_order(
-    fee_per_gram_stat: *mut TariFeePerGramStat,
+pub unsafe extern "C" fn base_node_candidate_get_latency_ms(
+    base_node_candidate: *mut TariBaseNodeCandidate,
     error_out: *mut c_int,
 ) -> c_ulonglong {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
-    let mut order = 0;
-    if fee_per_gram_stat.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("fee_per_gram_stat".to_string())).code;
+    if base_node_candidate.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("base_node_candidate".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-    } else {
-        order = (*fee_per_gram_stat).order;
+        return 0;
     }
-    order
+    (*base_node_candidate)
+        .latency
+        .map(|d| u64::try_from(d.as_millis()).unwrap_or(u64::MAX))
+        .unwrap_or_default()
 }
 
-/// Get the minimum fee per gram of TariFeePerGramStat
+/// Get the chain height reported by a TariBaseNodeCandidate
 ///
 /// ## Arguments
-/// `fee_per_gram_stats` - The TariFeePerGramStat pointer
+/// `base_node_candidate` - The TariBaseNodeCandidate pointer
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `c_ulonglong` - Returns minimum fee per gram
+/// `c_ulonglong` - Returns the height, or 0 if the base node has not reported a height yet
 ///
 /// # Safety
 /// None
 #[no_mangle]
-pub unsafe extern "C" fn fee_per_gram_stat_get_min_fee_per_gram(
-    fee_per_gram_stat: *mut TariFeePerGramStat,
+pub unsafe extern "C" fn base_node_candidate_get_height(
+    base_node_candidate: *mut TariBaseNodeCandidate,
     error_out: *mut c_int,
 ) -> c_ulonglong {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
-    let mut fee_per_gram = 0;
-    if fee_per_gram_stat.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("fee_per_gram_stat".to_string())).code;
+    if base_node_candidate.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("base_node_candidate".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-    } else {
-        fee_per_gram = (*fee_per_gram_stat).min_fee_per_gram.as_u64();
+        return 0;
     }
-    fee_per_gram
+    (*base_node_candidate).height.unwrap_or_default()
 }
 
-/// Get the average fee per gram of TariFeePerGramStat
+/// Get the number of consecutive failed checks of a TariBaseNodeCandidate
 ///
 /// ## Arguments
-/// `fee_per_gram_stats` - The TariFeePerGramStat pointer
+/// `base_node_candidate` - The TariBaseNodeCandidate pointer
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `c_ulonglong` - Returns average fee per gram
+/// `c_uint` - Returns the number of consecutive failures
 ///
 /// # Safety
 /// None
 #[no_mangle]
-pub unsafe extern "C" fn fee_per_gram_stat_get_avg_fee_per_gram(
-    fee_per_gram_stat: *mut TariFeePerGramStat,
+pub unsafe extern "C" fn base_node_candidate_get_consecutive_failures(
+    base_node_candidate: *mut TariBaseNodeCandidate,
     error_out: *mut c_int,
-) -> c_ulonglong {
+) -> c_uint {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
-    let mut fee_per_gram = 0;
-    if fee_per_gram_stat.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("fee_per_gram_stat".to_string())).code;
+    if base_node_candidate.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("base_node_candidate".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-    } else {
-        fee_per_gram = (*fee_per_gram_stat).avg_fee_per_gram.as_u64();
+        return 0;
     }
-    fee_per_gram
+    (*base_node_candidate).consecutive_failures
 }
 
-/// Get the maximum fee per gram of TariFeePerGramStat
+/// Get whether a TariBaseNodeCandidate is currently considered healthy
 ///
 /// ## Arguments
-/// `fee_per_gram_stats` - The TariFeePerGramStat pointer
+/// `base_node_candidate` - The TariBaseNodeCandidate pointer
 /// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
 /// as an out parameter.
 ///
 /// ## Returns
-/// `c_ulonglong` - Returns maximum fee per gram
+/// `bool` - Returns if the base node is healthy
 ///
 /// # Safety
 /// None
 #[no_mangle]
-pub unsafe extern "C" fn fee_per_gram_stat_get_max_fee_per_gram(
-    fee_per_gram_stat: *mut TariFeePerGramStat,
+pub unsafe extern "C" fn base_node_candidate_is_healthy(
+    base_node_candidate: *mut TariBaseNodeCandidate,
     error_out: *mut c_int,
-) -> c_ulonglong {
+) -> bool {
     let mut error = 0;
     ptr::swap(error_out, &mut error as *mut c_int);
-    let mut fee_per_gram = 0;
-    if fee_per_gram_stat.is_null() {
-        error = LibWalletError::from(InterfaceError::NullError("fee_per_gram_stat".to_string())).code;
+    if base_node_candidate.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("base_node_candidate".to_string())).code;
         ptr::swap(error_out, &mut error as *mut c_int);
-    } else {
-        fee_per_gram = (*fee_per_gram_stat).max_fee_per_gram.as_u64();
+        return false;
     }
-    fee_per_gram
+    (*base_node_candidate).is_healthy
 }
 
-/// Frees memory for a TariFeePerGramStat
+/// Get whether a TariBaseNodeCandidate is the pinned base node
 ///
 /// ## Arguments
-/// `fee_per_gram_stats` - The TariFeePerGramStat pointer
+/// `base_node_candidate` - The TariBaseNodeCandidate pointer
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `bool` - Returns if the base node is pinned
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn base_node_candidate_is_pinned(
+    base_node_candidate: *mut TariBaseNodeCandidate,
+    error_out: *mut c_int,
+) -> bool {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if base_node_candidate.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("base_node_candidate".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return false;
+    }
+    (*base_node_candidate).is_pinned
+}
+
+/// Get whether a TariBaseNodeCandidate is the active base node of the wallet
+///
+/// ## Arguments
+/// `base_node_candidate` - The TariBaseNodeCandidate pointer
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `bool` - Returns if the base node is active
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn base_node_candidate_is_active(
+    base_node_candidate: *mut TariBaseNodeCandidate,
+    error_out: *mut c_int,
+) -> bool {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if base_node_candidate.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("base_node_candidate".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return false;
+    }
+    (*base_node_candidate).is_active
+}
+
+/// Frees memory for a TariBaseNodeCandidate
+///
+/// ## Arguments
+/// `base_node_candidate` - The TariBaseNodeCandidate pointer
 ///
 /// ## Returns
 /// `()` - Does not return a value, equivalent to void in C
@@ -8554,9 +10460,9 @@ pub unsafe extern "C" fn fee_per_gram_stat_get_max_fee_per_gram(
 /// # Safety
 /// None
 #[no_mangle]
-pub unsafe extern "C" fn fee_per_gram_stat_destroy(fee_per_gram_stat: *mut TariFeePerGramStat) {
-    if !fee_per_gram_stat.is_null() {
-        drop(Box::from_raw(fee_per_gram_stat))
+pub unsafe extern "C" fn base_node_candidate_destroy(base_node_candidate: *mut TariBaseNodeCandidate) {
+    if !base_node_candidate.is_null() {
+        drop(Box::from_raw(base_node_candidate))
     }
 }
 
@@ -8712,6 +10618,10 @@ mod test {
         completed_transaction_destroy(tx);
     }
 
+    unsafe extern "C" fn unmined_callback(tx: *mut TariCompletedTransaction) {
+        completed_transaction_destroy(tx);
+    }
+
     unsafe extern "C" fn mined_unconfirmed_callback(tx: *mut TariCompletedTransaction, _confirmations: u64) {
         assert!(!tx.is_null());
         assert_eq!(
@@ -8829,6 +10739,14 @@ mod test {
         // assert!(true); //optimized out by compiler
     }
 
+    unsafe extern "C" fn scheduled_payment_status_callback(_payment_id: u64, _tx_id: u64, _status: u64) {
+        // assert!(true); //optimized out by compiler
+    }
+
+    unsafe extern "C" fn transaction_approval_required_callback(_approval_id: u64, _amount: u64) {
+        // assert!(true); //optimized out by compiler
+    }
+
     const NETWORK_STRING: &str = "localnet";
 
     #[test]
@@ -9078,6 +10996,26 @@ mod test {
             assert!(TariAddress::from_emoji_string(emoji_str).is_ok());
             let address_emoji = emoji_id_to_tari_address(emoji, error_ptr);
             assert_eq!((*address), (*address_emoji));
+            let corrections = emoji_id_check(emoji, 0x26, error_ptr);
+            assert_eq!(error, 0);
+            assert_eq!(emoji_id_corrections_get_length(corrections, error_ptr), 0);
+            let typo = CString::new(emoji_str.chars().skip(1).fold(String::from("🎅"), |mut s, c| {
+                s.push(c);
+                s
+            }))
+            .unwrap();
+            let typo_corrections = emoji_id_check(typo.as_ptr(), 0x26, error_ptr);
+            assert_eq!(error, 0);
+            assert_eq!(emoji_id_corrections_get_length(typo_corrections, error_ptr), 1);
+            assert_eq!(
+                emoji_id_corrections_get_emoji_position_at(typo_corrections, 0, error_ptr),
+                0
+            );
+            let corrected_address = emoji_id_corrections_get_address_at(typo_corrections, 0, error_ptr);
+            assert_eq!((*address), (*corrected_address));
+            emoji_id_corrections_destroy(corrections);
+            emoji_id_corrections_destroy(typo_corrections);
+            tari_address_destroy(corrected_address);
             private_key_destroy(private_key);
             public_key_destroy(public_key);
             tari_address_destroy(address_emoji);
@@ -9088,6 +11026,83 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_payment_request() {
+        unsafe {
+            let mut error = 0;
+            let error_ptr = &mut error as *mut c_int;
+            let private_key = private_key_generate();
+            let address = tari_address_from_private_key(private_key, 0x26, error_ptr);
+            assert_eq!(error, 0);
+            let message = CString::new("Invoice #42").unwrap();
+            let uri = payment_request_create(address, 12345, message.as_ptr(), error_ptr);
+            assert_eq!(error, 0);
+
+            let request = payment_request_parse(uri, error_ptr);
+            assert_eq!(error, 0);
+            let request_address = payment_request_get_address(request, error_ptr);
+            assert_eq!((*address), (*request_address));
+            assert_eq!(payment_request_get_amount(request, error_ptr), 12345);
+            let request_message = payment_request_get_message(request, error_ptr);
+            assert_eq!(CStr::from_ptr(request_message).to_str().unwrap(), "Invoice #42");
+
+            let invalid_uri = CString::new("tari://localnet/transactions/send?amount=1").unwrap();
+            let invalid_request = payment_request_parse(invalid_uri.as_ptr(), error_ptr);
+            assert!(invalid_request.is_null());
+            assert_eq!(error, 713);
+
+            string_destroy(request_message);
+            string_destroy(uri);
+            tari_address_destroy(request_address);
+            payment_request_destroy(request);
+            tari_address_destroy(address);
+            private_key_destroy(private_key);
+        }
+    }
+
+    #[test]
+    fn test_tari_uri() {
+        unsafe {
+            let mut error = 0;
+            let error_ptr = &mut error as *mut c_int;
+            let private_key = private_key_generate();
+            let address = tari_address_from_private_key(private_key, 0x26, error_ptr);
+            assert_eq!(error, 0);
+            let payment_uri = payment_request_create(address, 0, ptr::null(), error_ptr);
+            assert_eq!(tari_uri_get_action(payment_uri, error_ptr), 0);
+            assert_eq!(error, 0);
+
+            let contact_uri = CString::new(
+                TariUri::new((*address).network(), TariUriAction::AddContact {
+                    address: (*address).clone(),
+                    alias: Some("Alice".to_string()),
+                })
+                .to_string(),
+            )
+            .unwrap();
+            assert_eq!(tari_uri_get_action(contact_uri.as_ptr(), error_ptr), 1);
+            let contact = tari_uri_get_contact(contact_uri.as_ptr(), error_ptr);
+            assert_eq!(error, 0);
+            let contact_address = contact_get_tari_address(contact, error_ptr);
+            assert_eq!((*address), (*contact_address));
+            let alias = contact_get_alias(contact, error_ptr);
+            assert_eq!(CStr::from_ptr(alias).to_str().unwrap(), "Alice");
+
+            assert!(tari_uri_get_contact(payment_uri, error_ptr).is_null());
+            assert_eq!(error, 7);
+            let invalid_uri = CString::new("tari://localnet/wallets/delete").unwrap();
+            assert_eq!(tari_uri_get_action(invalid_uri.as_ptr(), error_ptr), -1);
+            assert_eq!(error, 711);
+
+            string_destroy(alias);
+            tari_address_destroy(contact_address);
+            contact_destroy(contact);
+            string_destroy(payment_uri);
+            tari_address_destroy(address);
+            private_key_destroy(private_key);
+        }
+    }
+
     #[test]
     fn test_covenant_create_empty() {
         unsafe {
@@ -9428,6 +11443,7 @@ mod test {
                 broadcast_callback,
                 mined_callback,
                 mined_unconfirmed_callback,
+                unmined_callback,
                 scanned_callback,
                 scanned_unconfirmed_callback,
                 transaction_send_result_callback,
@@ -9439,6 +11455,8 @@ mod test {
                 saf_messages_received_callback,
                 connectivity_status_callback,
                 base_node_state_callback,
+                scheduled_payment_status_callback,
+                transaction_approval_required_callback,
                 recovery_in_progress_ptr,
                 error_ptr,
             );
@@ -9471,6 +11489,7 @@ mod test {
                 broadcast_callback,
                 mined_callback,
                 mined_unconfirmed_callback,
+                unmined_callback,
                 scanned_callback,
                 scanned_unconfirmed_callback,
                 transaction_send_result_callback,
@@ -9482,6 +11501,8 @@ mod test {
                 saf_messages_received_callback,
                 connectivity_status_callback,
                 base_node_state_callback,
+                scheduled_payment_status_callback,
+                transaction_approval_required_callback,
                 recovery_in_progress_ptr,
                 error_ptr,
             );
@@ -9583,6 +11604,7 @@ mod test {
                 broadcast_callback,
                 mined_callback,
                 mined_unconfirmed_callback,
+                unmined_callback,
                 scanned_callback,
                 scanned_unconfirmed_callback,
                 transaction_send_result_callback,
@@ -9594,6 +11616,8 @@ mod test {
                 saf_messages_received_callback,
                 connectivity_status_callback,
                 base_node_state_callback,
+                scheduled_payment_status_callback,
+                transaction_approval_required_callback,
                 recovery_in_progress_ptr,
                 error_ptr,
             );
@@ -9806,6 +11830,7 @@ mod test {
                 broadcast_callback,
                 mined_callback,
                 mined_unconfirmed_callback,
+                unmined_callback,
                 scanned_callback,
                 scanned_unconfirmed_callback,
                 transaction_send_result_callback,
@@ -9817,6 +11842,8 @@ mod test {
                 saf_messages_received_callback,
                 connectivity_status_callback,
                 base_node_state_callback,
+                scheduled_payment_status_callback,
+                transaction_approval_required_callback,
                 recovery_in_progress_ptr,
                 error_ptr,
             );
@@ -9868,6 +11895,7 @@ mod test {
                 broadcast_callback,
                 mined_callback,
                 mined_unconfirmed_callback,
+                unmined_callback,
                 scanned_callback,
                 scanned_unconfirmed_callback,
                 transaction_send_result_callback,
@@ -9879,6 +11907,8 @@ mod test {
                 saf_messages_received_callback,
                 connectivity_status_callback,
                 base_node_state_callback,
+                scheduled_payment_status_callback,
+                transaction_approval_required_callback,
                 recovery_in_progress_ptr,
                 error_ptr,
             );
@@ -9944,6 +11974,7 @@ mod test {
                 broadcast_callback,
                 mined_callback,
                 mined_unconfirmed_callback,
+                unmined_callback,
                 scanned_callback,
                 scanned_unconfirmed_callback,
                 transaction_send_result_callback,
@@ -9955,6 +11986,8 @@ mod test {
                 saf_messages_received_callback,
                 connectivity_status_callback,
                 base_node_state_callback,
+                scheduled_payment_status_callback,
+                transaction_approval_required_callback,
                 recovery_in_progress_ptr,
                 error_ptr,
             );
@@ -10092,6 +12125,7 @@ mod test {
                 broadcast_callback,
                 mined_callback,
                 mined_unconfirmed_callback,
+                unmined_callback,
                 scanned_callback,
                 scanned_unconfirmed_callback,
                 transaction_send_result_callback,
@@ -10103,6 +12137,8 @@ mod test {
                 saf_messages_received_callback,
                 connectivity_status_callback,
                 base_node_state_callback,
+                scheduled_payment_status_callback,
+                transaction_approval_required_callback,
                 recovery_in_progress_ptr,
                 error_ptr,
             );
@@ -10209,6 +12245,7 @@ mod test {
                 broadcast_callback,
                 mined_callback,
                 mined_unconfirmed_callback,
+                unmined_callback,
                 scanned_callback,
                 scanned_unconfirmed_callback,
                 transaction_send_result_callback,
@@ -10220,6 +12257,8 @@ mod test {
                 saf_messages_received_callback,
                 connectivity_status_callback,
                 base_node_state_callback,
+                scheduled_payment_status_callback,
+                transaction_approval_required_callback,
                 recovery_in_progress_ptr,
                 error_ptr,
             );
@@ -10409,6 +12448,7 @@ mod test {
                 broadcast_callback,
                 mined_callback,
                 mined_unconfirmed_callback,
+                unmined_callback,
                 scanned_callback,
                 scanned_unconfirmed_callback,
                 transaction_send_result_callback,
@@ -10420,6 +12460,8 @@ mod test {
                 saf_messages_received_callback,
                 connectivity_status_callback,
                 base_node_state_callback,
+                scheduled_payment_status_callback,
+                transaction_approval_required_callback,
                 recovery_in_progress_ptr,
                 error_ptr,
             );
@@ -10616,6 +12658,7 @@ mod test {
                 broadcast_callback,
                 mined_callback,
                 mined_unconfirmed_callback,
+                unmined_callback,
                 scanned_callback,
                 scanned_unconfirmed_callback,
                 transaction_send_result_callback,
@@ -10627,6 +12670,8 @@ mod test {
                 saf_messages_received_callback,
                 connectivity_status_callback,
                 base_node_state_callback,
+                scheduled_payment_status_callback,
+                transaction_approval_required_callback,
                 recovery_in_progress_ptr,
                 error_ptr,
             );
@@ -10866,6 +12911,7 @@ mod test {
                 broadcast_callback,
                 mined_callback,
                 mined_unconfirmed_callback,
+                unmined_callback,
                 scanned_callback,
                 scanned_unconfirmed_callback,
                 transaction_send_result_callback,
@@ -10877,6 +12923,8 @@ mod test {
                 saf_messages_received_callback,
                 connectivity_status_callback,
                 base_node_state_callback,
+                scheduled_payment_status_callback,
+                transaction_approval_required_callback,
                 recovery_in_progress_ptr,
                 error_ptr,
             );
@@ -11056,4 +13104,37 @@ mod test {
             let _spending_key = Box::from_raw(spending_key_ptr);
         }
     }
+
+    #[test]
+    fn test_fee_per_gram_stats_get_expected_blocks() {
+        unsafe {
+            let mut error = 0;
+            let error_ptr = &mut error as *mut c_int;
+
+            let stat = |order, min_fee_per_gram| TariFeePerGramStat {
+                order,
+                min_fee_per_gram: MicroMinotari(min_fee_per_gram),
+                avg_fee_per_gram: MicroMinotari(min_fee_per_gram + 5),
+                max_fee_per_gram: MicroMinotari(min_fee_per_gram + 10),
+            };
+            let stats = Box::into_raw(Box::new(TariFeePerGramStats {
+                stats: vec![stat(0, 20), stat(1, 10), stat(2, 5)],
+            }));
+
+            assert_eq!(fee_per_gram_stats_get_expected_blocks(stats, 25, error_ptr), 1);
+            assert_eq!(fee_per_gram_stats_get_expected_blocks(stats, 10, error_ptr), 2);
+            assert_eq!(fee_per_gram_stats_get_expected_blocks(stats, 5, error_ptr), 3);
+            assert_eq!(fee_per_gram_stats_get_expected_blocks(stats, 1, error_ptr), 0);
+            assert_eq!(error, 0);
+
+            let empty = Box::into_raw(Box::new(TariFeePerGramStats::default()));
+            assert_eq!(fee_per_gram_stats_get_expected_blocks(empty, 1, error_ptr), 1);
+
+            assert_eq!(fee_per_gram_stats_get_expected_blocks(ptr::null_mut(), 1, error_ptr), 0);
+            assert_ne!(error, 0);
+
+            fee_per_gram_stats_destroy(stats);
+            fee_per_gram_stats_destroy(empty);
+        }
+    }
 }
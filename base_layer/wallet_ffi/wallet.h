@@ -21,6 +21,14 @@ enum TariTypeTag {
   I64 = 4,
 };
 
+enum TariUtxoSelectionStrategy {
+  Default = 0,
+  SmallestFirst = 1,
+  LargestFirst = 2,
+  BranchAndBound = 3,
+  PrivacyRandom = 4,
+};
+
 enum TariUtxoSort {
   ValueAsc = 0,
   ValueDesc = 1,
@@ -100,6 +108,11 @@ struct OutputFeatures;
  */
 struct P2pConfig;
 
+/**
+ * A request for a payment to a Tari address, optionally for a specific amount and with a message for the recipient
+ */
+struct PaymentRequest;
+
 /**
  * The [PublicKey](trait.PublicKey.html) implementation for `ristretto255` is a thin wrapper around the dalek
  * library's [RistrettoPoint](struct.RistrettoPoint.html).
@@ -156,12 +169,18 @@ struct RistrettoSecretKey;
 
 struct TariAddress;
 
+struct TariBalanceHistory;
+
+struct TariBaseNodeCandidates;
+
 struct TariBaseNodeState;
 
 struct TariCompletedTransactions;
 
 struct TariContacts;
 
+struct TariEmojiIdCorrections;
+
 struct TariPendingInboundTransactions;
 
 struct TariPendingOutboundTransactions;
@@ -317,6 +336,8 @@ typedef struct OutboundTransaction TariPendingOutboundTransaction;
 
 typedef struct InboundTransaction TariPendingInboundTransaction;
 
+typedef struct PaymentRequest TariPaymentRequest;
+
 typedef struct TransactionSendStatus TariTransactionSendStatus;
 
 typedef struct TransportConfig TariTransportConfig;
@@ -329,6 +350,8 @@ typedef struct FeePerGramStatsResponse TariFeePerGramStats;
 
 typedef struct FeePerGramStat TariFeePerGramStat;
 
+typedef struct BaseNodeCandidateInfo TariBaseNodeCandidate;
+
 struct TariUtxo {
   const char *commitment;
   uint64_t value;
@@ -774,6 +797,249 @@ char *tari_address_to_emoji_id(TariWalletAddress *address,
 TariWalletAddress *emoji_id_to_tari_address(const char *emoji,
                                             int *error_out);
 
+/**
+ * Checks a char array in emoji format for single-emoji transcription errors
+ *
+ * ## Arguments
+ * `emoji` - The pointer to a char array in emoji format
+ * `network` - an u8 indicating the network
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `*mut TariEmojiIdCorrections` - Returns a pointer to the single-emoji substitutions that turn the emoji ID into a valid
+ * address, ordered by position. There are no corrections if the emoji ID is already valid, and exactly one if the
+ * mistyped emoji could be pinpointed. Corrections are candidates to show to the user and must not be applied without
+ * confirmation. Note that it returns null if the emoji ID cannot be corrected.
+ *
+ * # Safety
+ * The ```emoji_id_corrections_destroy``` method must be called when finished with a TariEmojiIdCorrections to prevent
+ * a memory leak
+ */
+struct TariEmojiIdCorrections *emoji_id_check(const char *emoji,
+                                              unsigned int network,
+                                              int *error_out);
+
+/**
+ * Gets the number of corrections in a TariEmojiIdCorrections
+ *
+ * ## Arguments
+ * `corrections` - The pointer to a TariEmojiIdCorrections
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `c_uint` - Returns the number of corrections, note that it will be zero if corrections is null
+ *
+ * # Safety
+ * None
+ */
+unsigned int emoji_id_corrections_get_length(struct TariEmojiIdCorrections *corrections,
+                                             int *error_out);
+
+/**
+ * Gets the zero-based emoji position that the correction at position in a TariEmojiIdCorrections replaces
+ *
+ * ## Arguments
+ * `corrections` - The pointer to a TariEmojiIdCorrections
+ * `position` - The integer position
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `c_uint` - Returns the emoji position, note that it will be zero if corrections is null or position is invalid
+ *
+ * # Safety
+ * None
+ */
+unsigned int emoji_id_corrections_get_emoji_position_at(struct TariEmojiIdCorrections *corrections,
+                                                        unsigned int position,
+                                                        int *error_out);
+
+/**
+ * Gets the corrected TariWalletAddress of the correction at position in a TariEmojiIdCorrections
+ *
+ * ## Arguments
+ * `corrections` - The pointer to a TariEmojiIdCorrections
+ * `position` - The integer position
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `*mut TariWalletAddress` - Returns a pointer to a TariWalletAddress, note that ptr::null_mut() is returned if
+ * corrections is null or position is invalid
+ *
+ * # Safety
+ * The ```tari_address_destroy``` method must be called when finished with a TariWalletAddress to prevent a memory leak
+ */
+TariWalletAddress *emoji_id_corrections_get_address_at(struct TariEmojiIdCorrections *corrections,
+                                                       unsigned int position,
+                                                       int *error_out);
+
+/**
+ * Frees memory for a TariEmojiIdCorrections
+ *
+ * ## Arguments
+ * `corrections` - The pointer to a TariEmojiIdCorrections
+ *
+ * ## Returns
+ * `()` - Does not return a value, equivalent to void in C
+ *
+ * # Safety
+ * None
+ */
+void emoji_id_corrections_destroy(struct TariEmojiIdCorrections *corrections);
+
+/**
+ * Creates a payment request URI, e.g. to display as a QR code, that asks for a payment to a TariWalletAddress. The URI
+ * has the form `tari://<network>/transactions/send?tariAddress=<address>&amount=<amount>&message=<message>` and is
+ * shared by all Tari applications.
+ *
+ * ## Arguments
+ * `address` - The pointer to a TariWalletAddress that should receive the payment
+ * `amount` - The requested amount in MicroMinotari, or 0 to leave it to the payer
+ * `message` - The pointer to a char array with a message for the payer, may be null
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `*mut c_char` - Returns a pointer to a char array. Note that it returns empty if there was an error
+ *
+ * # Safety
+ * The ```string_destroy``` method must be called when finished with a string from rust to prevent a memory leak
+ */
+char *payment_request_create(TariWalletAddress *address,
+                             unsigned long long amount,
+                             const char *message,
+                             int *error_out);
+
+/**
+ * Parses a payment request URI, e.g. scanned from a QR code
+ *
+ * ## Arguments
+ * `uri` - The pointer to a char array containing the payment request URI
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `*mut TariPaymentRequest` - Returns a pointer to a TariPaymentRequest. Note that it returns ptr::null_mut() if uri
+ * is null or is not a valid payment request
+ *
+ * # Safety
+ * The ```payment_request_destroy``` method must be called when finished with a TariPaymentRequest to prevent a memory
+ * leak
+ */
+TariPaymentRequest *payment_request_parse(const char *uri,
+                                          int *error_out);
+
+/**
+ * Gets the TariWalletAddress that a payment is requested for
+ *
+ * ## Arguments
+ * `request` - The pointer to a TariPaymentRequest
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `*mut TariWalletAddress` - Returns a pointer to a TariWalletAddress. Note that it returns ptr::null_mut() if
+ * request is null
+ *
+ * # Safety
+ * The ```tari_address_destroy``` method must be called when finished with a TariWalletAddress to prevent a memory leak
+ */
+TariWalletAddress *payment_request_get_address(TariPaymentRequest *request,
+                                               int *error_out);
+
+/**
+ * Gets the amount of a TariPaymentRequest
+ *
+ * ## Arguments
+ * `request` - The pointer to a TariPaymentRequest
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `c_ulonglong` - Returns the requested amount in MicroMinotari, or 0 if the request does not specify an amount
+ *
+ * # Safety
+ * None
+ */
+unsigned long long payment_request_get_amount(TariPaymentRequest *request,
+                                              int *error_out);
+
+/**
+ * Gets the message of a TariPaymentRequest
+ *
+ * ## Arguments
+ * `request` - The pointer to a TariPaymentRequest
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `*mut c_char` - Returns a pointer to a char array. Note that it returns empty if the request does not contain a
+ * message or there was an error
+ *
+ * # Safety
+ * The ```string_destroy``` method must be called when finished with a string from rust to prevent a memory leak
+ */
+char *payment_request_get_message(TariPaymentRequest *request,
+                                  int *error_out);
+
+/**
+ * Frees memory for a TariPaymentRequest
+ *
+ * ## Arguments
+ * `request` - The pointer to a TariPaymentRequest
+ *
+ * ## Returns
+ * `()` - Does not return a value, equivalent to void in C
+ *
+ * # Safety
+ * None
+ */
+void payment_request_destroy(TariPaymentRequest *request);
+
+/**
+ * -------------------------------------------------------------------------------------------- ///
+ *
+ * ------------------------------- Tari URIs ----------------------------------------------------///
+ * Gets the action that a `tari://` URI, e.g. scanned from a QR code, asks the wallet to perform so that the caller can
+ * hand it to the matching function: `payment_request_parse` for payments, `tari_uri_get_contact` for contacts and
+ * `wallet_add_base_node_peer_from_uri` for base nodes
+ *
+ * ## Arguments
+ * `uri` - The pointer to a char array containing the URI
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `c_int` - Returns 0 for a payment request, 1 to add a contact, 2 to add a base node and 3 to call a template. Note
+ * that it returns -1 if the URI is null or invalid
+ *
+ * # Safety
+ * None
+ */
+int tari_uri_get_action(const char *uri,
+                        int *error_out);
+
+/**
+ * Creates a TariContact from a `tari://<network>/contacts/add` URI
+ *
+ * ## Arguments
+ * `uri` - The pointer to a char array containing the URI
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `*mut TariContact` - Returns a pointer to a TariContact. Note that it returns ptr::null_mut() if the URI is null,
+ * invalid or does not add a contact
+ *
+ * # Safety
+ * The ```contact_destroy``` method must be called when finished with a TariContact to prevent a memory leak
+ */
+TariContact *tari_uri_get_contact(const char *uri,
+                                  int *error_out);
+
 /**
  * -------------------------------------------------------------------------------------------- ///
  *
@@ -2597,6 +2863,8 @@ TariPublicKey *public_keys_get_at(const struct TariPublicKeys *public_keys,
  * when a Broadcast transaction is detected as mined AND confirmed.
  * `callback_transaction_mined_unconfirmed` - The callback function pointer matching the function signature. This will
  * be called when a Broadcast transaction is detected as mined but not yet confirmed.
+ * `callback_transaction_unmined` - The callback function pointer matching the function signature. This will be called
+ * when the block a mined transaction was in has been reorged out of the chain.
  * `callback_faux_transaction_confirmed` - The callback function pointer matching the function signature. This will be
  * called when a one-sided transaction is detected as mined AND confirmed.
  * `callback_faux_transaction_unconfirmed` - The callback function pointer matching the function signature. This
@@ -2651,6 +2919,19 @@ TariPublicKey *public_keys_get_at(const struct TariPublicKeys *public_keys,
  *     Online,         // 1
  *     Offline,        // 2
  * }
+ * `callback_scheduled_payment_status` - The callback function pointer matching the function signature. This is called
+ * when a scheduled payment is sent, deferred, fails or completes its schedule. The first parameter is the id of the
+ * scheduled payment, the second the TxId of the sent transaction (0 if no transaction was sent) and the third the
+ * status:
+ *     PaymentSent,                    // 0
+ *     DeferredInsufficientBalance,    // 1
+ *     DeferredOffline,                // 2
+ *     PaymentFailed,                  // 3
+ *     ScheduleCompleted,              // 4
+ * `callback_transaction_approval_required` - The callback function pointer matching the function signature. This is
+ * called when an outbound transaction exceeds the configured per-transaction or daily spending limit. The transaction
+ * is held until it is approved with `wallet_approve_transaction` or rejected with `wallet_reject_transaction`. The
+ * first parameter is the approval_id and the second the amount of the transaction in MicroMinotari.
  * `recovery_in_progress` - Pointer to an bool which will be modified to indicate if there is an outstanding recovery
  * that should be completed or not to an error code should one occur, may not be null. Functions as an out parameter.
  * `error_out` - Pointer to an int which will be modified
@@ -2676,6 +2957,7 @@ struct TariWallet *wallet_create(TariCommsConfig *config,
                                  void (*callback_transaction_mined)(TariCompletedTransaction*),
                                  void (*callback_transaction_mined_unconfirmed)(TariCompletedTransaction*,
                                                                                 uint64_t),
+                                 void (*callback_transaction_unmined)(TariCompletedTransaction*),
                                  void (*callback_faux_transaction_confirmed)(TariCompletedTransaction*),
                                  void (*callback_faux_transaction_unconfirmed)(TariCompletedTransaction*,
                                                                                uint64_t),
@@ -2690,6 +2972,11 @@ struct TariWallet *wallet_create(TariCommsConfig *config,
                                  void (*callback_saf_messages_received)(void),
                                  void (*callback_connectivity_status)(uint64_t),
                                  void (*callback_base_node_state)(struct TariBaseNodeState*),
+                                 void (*callback_scheduled_payment_status)(unsigned long long,
+                                                                           unsigned long long,
+                                                                           unsigned long long),
+                                 void (*callback_transaction_approval_required)(unsigned long long,
+                                                                                unsigned long long),
                                  bool *recovery_in_progress,
                                  int *error_out);
 
@@ -2741,6 +3028,31 @@ char *wallet_get_last_network(TariCommsConfig *config,
 TariBalance *wallet_get_balance(struct TariWallet *wallet,
                                 int *error_out);
 
+/**
+ * Gets the recorded balance history of a TariWallet
+ *
+ * ## Arguments
+ * `wallet` - The TariWallet pointer
+ * `from_timestamp` - The unix timestamp (in seconds) of the start of the range
+ * `to_timestamp` - The unix timestamp (in seconds) of the end of the range, or 0 for the current time
+ * `resolution` - The granularity of the snapshots: 0 for daily, 1 for per block
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `*mut TariBalanceHistory` - Returns a pointer to a TariBalanceHistory, ordered from oldest to newest. Note that it
+ * returns ptr::null_mut() if wallet is null, the arguments are invalid or an error is encountered.
+ *
+ * # Safety
+ * The ```balance_history_destroy``` method must be called when finished with a TariBalanceHistory to prevent a memory
+ * leak
+ */
+struct TariBalanceHistory *wallet_get_balance_history(struct TariWallet *wallet,
+                                                      unsigned long long from_timestamp,
+                                                      unsigned long long to_timestamp,
+                                                      unsigned int resolution,
+                                                      int *error_out);
+
 /**
  * This function returns a list of unspent UTXO values and commitments.
  *
@@ -2961,6 +3273,84 @@ bool wallet_add_base_node_peer(struct TariWallet *wallet,
                                const char *address,
                                int *error_out);
 
+/**
+ * Adds the base node peer from a `tari://<network>/base_nodes/add` URI, as shown by a base node's `whoami` command, to
+ * the TariWallet. The first address in the URI is used.
+ *
+ * ## Arguments
+ * `wallet` - The TariWallet pointer
+ * `uri` - The pointer to a char array containing the URI
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `bool` - Returns if successful or not
+ *
+ * # Safety
+ * None
+ */
+bool wallet_add_base_node_peer_from_uri(struct TariWallet *wallet,
+                                        const char *uri,
+                                        int *error_out);
+
+/**
+ * Adds a base node candidate that the TariWallet can fail over to if the active base node becomes unavailable
+ *
+ * ## Arguments
+ * `wallet` - The TariWallet pointer
+ * `public_key` - The TariPublicKey pointer
+ * `address` - The pointer to a char array
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `bool` - Returns if successful or not
+ *
+ * # Safety
+ * None
+ */
+bool wallet_add_base_node_candidate(struct TariWallet *wallet,
+                                    TariPublicKey *public_key,
+                                    const char *address,
+                                    int *error_out);
+
+/**
+ * Pins a base node candidate as the preferred base node of the TariWallet and makes it the active base node. The
+ * wallet only fails over to another base node while the pinned base node is unavailable.
+ *
+ * ## Arguments
+ * `wallet` - The TariWallet pointer
+ * `public_key` - The TariPublicKey pointer of a base node that was previously added to the wallet
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `bool` - Returns if successful or not
+ *
+ * # Safety
+ * None
+ */
+bool wallet_pin_base_node(struct TariWallet *wallet,
+                          TariPublicKey *public_key,
+                          int *error_out);
+
+/**
+ * Removes the preferred base node of the TariWallet, if one was pinned
+ *
+ * ## Arguments
+ * `wallet` - The TariWallet pointer
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `bool` - Returns if successful or not
+ *
+ * # Safety
+ * None
+ */
+bool wallet_unpin_base_node(struct TariWallet *wallet,
+                            int *error_out);
+
 /**
  * Upserts a TariContact to the TariWallet. If the contact does not exist it will be Inserted. If it does exist the
  * Alias will be updated.
@@ -3082,6 +3472,95 @@ unsigned long long balance_get_pending_outgoing(TariBalance *balance,
  */
 void balance_destroy(TariBalance *balance);
 
+/**
+ * Gets the number of snapshots in a TariBalanceHistory
+ *
+ * ## Arguments
+ * `history` - The pointer to a TariBalanceHistory
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `c_uint` - Returns the number of snapshots in a TariBalanceHistory, note that it will be zero if history is null
+ *
+ * # Safety
+ * None
+ */
+unsigned int balance_history_get_length(struct TariBalanceHistory *history,
+                                        int *error_out);
+
+/**
+ * Gets the block height at which the snapshot at position in a TariBalanceHistory was recorded
+ *
+ * ## Arguments
+ * `history` - The pointer to a TariBalanceHistory
+ * `position` - The integer position
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `c_ulonglong` - Returns the block height, note that it will be zero if history is null or position is invalid
+ *
+ * # Safety
+ * None
+ */
+unsigned long long balance_history_get_height_at(struct TariBalanceHistory *history,
+                                                 unsigned int position,
+                                                 int *error_out);
+
+/**
+ * Gets the unix timestamp (in seconds) at which the snapshot at position in a TariBalanceHistory was recorded
+ *
+ * ## Arguments
+ * `history` - The pointer to a TariBalanceHistory
+ * `position` - The integer position
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `c_ulonglong` - Returns the timestamp, note that it will be zero if history is null or position is invalid
+ *
+ * # Safety
+ * None
+ */
+unsigned long long balance_history_get_timestamp_at(struct TariBalanceHistory *history,
+                                                    unsigned int position,
+                                                    int *error_out);
+
+/**
+ * Gets the TariBalance of the snapshot at position in a TariBalanceHistory
+ *
+ * ## Arguments
+ * `history` - The pointer to a TariBalanceHistory
+ * `position` - The integer position
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `*mut TariBalance` - Returns a pointer to a TariBalance, note that ptr::null_mut() is returned if history is null
+ * or position is invalid
+ *
+ * # Safety
+ * The ```balance_destroy``` method must be called when finished with a TariBalance to prevent a memory leak
+ */
+TariBalance *balance_history_get_balance_at(struct TariBalanceHistory *history,
+                                            unsigned int position,
+                                            int *error_out);
+
+/**
+ * Frees memory for a TariBalanceHistory
+ *
+ * ## Arguments
+ * `history` - The pointer to a TariBalanceHistory
+ *
+ * ## Returns
+ * `()` - Does not return a value, equivalent to void in C
+ *
+ * # Safety
+ * None
+ */
+void balance_history_destroy(struct TariBalanceHistory *history);
+
 /**
  * Sends a TariPendingOutboundTransaction
  *
@@ -3091,6 +3570,8 @@ void balance_destroy(TariBalance *balance);
  * `amount` - The amount
  * `commitments` - A `TariVector` of "strings", tagged as `TariTypeTag::String`, containing commitment's hex values
  *   (see `Commitment::to_hex()`)
+ * `selection_strategy` - The strategy used to select the inputs of the transaction. Ignored if `commitments` is
+ * provided.
  * `fee_per_gram` - The transaction fee
  * `message` - The pointer to a char array
  * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
@@ -3106,11 +3587,104 @@ unsigned long long wallet_send_transaction(struct TariWallet *wallet,
                                            TariWalletAddress *destination,
                                            unsigned long long amount,
                                            struct TariVector *commitments,
+                                           enum TariUtxoSelectionStrategy selection_strategy,
                                            unsigned long long fee_per_gram,
                                            const char *message,
                                            bool one_sided,
                                            int *error_out);
 
+/**
+ * Schedules a payment to be sent by the wallet once it falls due, optionally repeating at a fixed interval. Due
+ * payments are only sent while the wallet is online and has enough available balance; progress is reported through
+ * `callback_scheduled_payment_status`.
+ *
+ * ## Arguments
+ * `wallet` - The TariWallet pointer
+ * `destination` - The TariWalletAddress pointer of the peer
+ * `amount` - The amount of each payment
+ * `fee_per_gram` - The transaction fee
+ * `message` - The pointer to a char array
+ * `first_payment_at` - The time of the first payment as a unix timestamp in seconds
+ * `interval_seconds` - The number of seconds between payments, or 0 for a one-off payment
+ * `max_payments` - The total number of payments to make for a recurring payment, or 0 to repeat until cancelled.
+ * Ignored for one-off payments.
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `unsigned long long` - Returns 0 if unsuccessful or the id of the scheduled payment if successful
+ *
+ * # Safety
+ * None
+ */
+unsigned long long wallet_schedule_payment(struct TariWallet *wallet,
+                                           TariWalletAddress *destination,
+                                           unsigned long long amount,
+                                           unsigned long long fee_per_gram,
+                                           const char *message,
+                                           unsigned long long first_payment_at,
+                                           unsigned long long interval_seconds,
+                                           unsigned long long max_payments,
+                                           int *error_out);
+
+/**
+ * Cancels an active scheduled payment so that no further payments are made for it
+ *
+ * ## Arguments
+ * `wallet` - The TariWallet pointer
+ * `payment_id` - The id of the scheduled payment returned by `wallet_schedule_payment`
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `bool` - Returns if the cancellation was successful or not
+ *
+ * # Safety
+ * None
+ */
+bool wallet_cancel_scheduled_payment(struct TariWallet *wallet,
+                                     unsigned long long payment_id,
+                                     int *error_out);
+
+/**
+ * Approves an outbound transaction that is held for exceeding a spending limit, the transaction is then sent as
+ * normal
+ *
+ * ## Arguments
+ * `wallet` - The TariWallet pointer
+ * `approval_id` - The approval_id provided by `callback_transaction_approval_required`
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `bool` - Returns if the approval was successful or not
+ *
+ * # Safety
+ * None
+ */
+bool wallet_approve_transaction(struct TariWallet *wallet,
+                                unsigned long long approval_id,
+                                int *error_out);
+
+/**
+ * Rejects an outbound transaction that is held for exceeding a spending limit, the transaction will not be sent
+ *
+ * ## Arguments
+ * `wallet` - The TariWallet pointer
+ * `approval_id` - The approval_id provided by `callback_transaction_approval_required`
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `bool` - Returns if the rejection was successful or not
+ *
+ * # Safety
+ * None
+ */
+bool wallet_reject_transaction(struct TariWallet *wallet,
+                               unsigned long long approval_id,
+                               int *error_out);
+
 /**
  * Gets a fee estimate for an amount
  *
@@ -3139,6 +3713,34 @@ unsigned long long wallet_get_fee_estimate(struct TariWallet *wallet,
                                            unsigned int num_outputs,
                                            int *error_out);
 
+/**
+ * Estimates the size of a transaction, i.e. its weight in grams, so that a fee for it can be calculated as the
+ * weight multiplied by the fee per gram
+ *
+ * ## Arguments
+ * `wallet` - The TariWallet pointer
+ * `amount` - The amount to send
+ * `fee_per_gram` - The fee per gram, used to select the inputs that would fund the transaction
+ * `num_outputs` - The number of recipient outputs
+ * `output_features` - An array of `num_outputs` TariOutputFeatures pointers, one for each recipient output. The array
+ * or any of its entries may be null, in which case default output features are used.
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `unsigned long long` - Returns 0 if unsuccessful or the estimated transaction weight in grams, including the
+ * selected inputs and a change output if one is required
+ *
+ * # Safety
+ * `output_features` must be null or point to at least `num_outputs` TariOutputFeatures pointers
+ */
+unsigned long long wallet_estimate_tx_size(struct TariWallet *wallet,
+                                           unsigned long long amount,
+                                           unsigned long long fee_per_gram,
+                                           unsigned int num_outputs,
+                                           TariOutputFeatures *const *output_features,
+                                           int *error_out);
+
 /**
  * Gets the number of mining confirmations required
  *
@@ -3396,6 +3998,52 @@ bool wallet_cancel_pending_transaction(struct TariWallet *wallet,
                                        unsigned long long transaction_id,
                                        int *error_out);
 
+/**
+ * Cancel all Pending Transactions created more than `older_than_secs` seconds ago and release their encumbered outputs
+ * back to the spendable pool
+ *
+ * ## Arguments
+ * `wallet` - The TariWallet pointer
+ * `older_than_secs` - The minimum age in seconds of the pending transactions to cancel
+ * `num_failed_out` - Pointer to an unsigned long long which will be set to the number of stale transactions that could
+ * not be cancelled, may be null. Functions as an out parameter.
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `c_ulonglong` - returns the number of transactions that were cancelled
+ *
+ * # Safety
+ * None
+ */
+unsigned long long wallet_cancel_all_stale_transactions(struct TariWallet *wallet,
+                                                        unsigned long long older_than_secs,
+                                                        unsigned long long *num_failed_out,
+                                                        int *error_out);
+
+/**
+ * Audit the wallet's encumbered outputs against its pending and completed transactions
+ *
+ * ## Arguments
+ * `wallet` - The TariWallet pointer
+ * `repair` - Release the encumbered outputs that do not belong to any live transaction and revalidate them with the
+ * base node
+ * `num_duplicates_out` - Pointer to an unsigned long long which will be set to the number of commitments spent by
+ * more than one unmined transaction, may be null. Functions as an out parameter.
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `c_ulonglong` - returns the number of orphaned encumbered outputs that were found
+ *
+ * # Safety
+ * None
+ */
+unsigned long long wallet_audit_outputs(struct TariWallet *wallet,
+                                        bool repair,
+                                        unsigned long long *num_duplicates_out,
+                                        int *error_out);
+
 /**
  * This function will tell the wallet to query the set base node to confirm the status of transaction outputs
  * (TXOs).
@@ -3469,6 +4117,78 @@ bool wallet_restart_transaction_broadcast(struct TariWallet *wallet,
 struct TariSeedWords *wallet_get_seed_words(struct TariWallet *wallet,
                                             int *error_out);
 
+/**
+ * Re-encrypts the wallet database main key under a new passphrase.
+ *
+ * ## Arguments
+ * `wallet` - The TariWallet pointer
+ * `existing_passphrase` - The current wallet passphrase, may not be null
+ * `new_passphrase` - The passphrase to rotate to, may not be null
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `bool` - Returns true if the passphrase was rotated, false otherwise
+ *
+ * # Safety
+ * None
+ */
+bool wallet_rotate_passphrase(struct TariWallet *wallet,
+                              const char *existing_passphrase,
+                              const char *new_passphrase,
+                              int *error_out);
+
+/**
+ * Locks the wallet database by removing its main encryption key from memory. Encrypted wallet data (such as the seed
+ * words) cannot be accessed until `wallet_unlock` is called.
+ *
+ * ## Arguments
+ * `wallet` - The TariWallet pointer
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * # Safety
+ * None
+ */
+void wallet_lock(struct TariWallet *wallet,
+                 int *error_out);
+
+/**
+ * Unlocks the wallet database using the wallet passphrase.
+ *
+ * ## Arguments
+ * `wallet` - The TariWallet pointer
+ * `passphrase` - The wallet passphrase, may not be null
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `bool` - Returns true if the wallet was unlocked, false otherwise
+ *
+ * # Safety
+ * None
+ */
+bool wallet_unlock(struct TariWallet *wallet,
+                   const char *passphrase,
+                   int *error_out);
+
+/**
+ * Checks whether the wallet database is locked.
+ *
+ * ## Arguments
+ * `wallet` - The TariWallet pointer
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `bool` - Returns true if the wallet is locked, false otherwise
+ *
+ * # Safety
+ * None
+ */
+bool wallet_is_locked(struct TariWallet *wallet,
+                      int *error_out);
+
 /**
  * Set the power mode of the wallet to Low Power mode which will reduce the amount of network operations the wallet
  * performs to conserve power
@@ -3811,6 +4531,27 @@ TariFeePerGramStat *fee_per_gram_stats_get_at(TariFeePerGramStats *fee_per_gram_
                                               unsigned int position,
                                               int *error_out);
 
+/**
+ * Get the number of blocks expected until a transaction paying the given fee per gram is mined, based on the
+ * TariFeePerGramStats of the upcoming blocks.
+ *
+ * ## Arguments
+ * `fee_per_gram_stats` - The pointer to a TariFeePerGramStats.
+ * `fee_per_gram` - The fee per gram of the transaction.
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `c_ulonglong` - Returns the expected number of blocks, i.e. 1 for the next block, or 0 if the fee per gram is too
+ * low to be expected within the blocks covered by the stats or if an error is encountered.
+ *
+ * # Safety
+ * None
+ */
+unsigned long long fee_per_gram_stats_get_expected_blocks(TariFeePerGramStats *fee_per_gram_stats,
+                                                          unsigned long long fee_per_gram,
+                                                          int *error_out);
+
 /**
  * Frees memory for a TariFeePerGramStats
  *
@@ -3909,6 +4650,211 @@ unsigned long long fee_per_gram_stat_get_max_fee_per_gram(TariFeePerGramStat *fe
  */
 void fee_per_gram_stat_destroy(TariFeePerGramStat *fee_per_gram_stat);
 
+/**
+ * Get the base node candidates that the TariWallet can fail over to, including the active base node
+ *
+ * ## Arguments
+ * `wallet` - The TariWallet pointer
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter
+ *
+ * ## Returns
+ * `*mut TariBaseNodeCandidates` - returns the base node candidates, note that it returns ptr::null_mut() if wallet is
+ * null
+ *
+ * # Safety
+ * The ```base_node_candidates_destroy``` method must be called when finished with a TariBaseNodeCandidates to
+ * prevent a memory leak.
+ */
+struct TariBaseNodeCandidates *wallet_get_base_node_candidates(struct TariWallet *wallet,
+                                                               int *error_out);
+
+/**
+ * Get length of the TariBaseNodeCandidates.
+ *
+ * ## Arguments
+ * `base_node_candidates` - The pointer to a TariBaseNodeCandidates
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter
+ *
+ * ## Returns
+ * `c_uint` - length of the TariBaseNodeCandidates
+ *
+ * # Safety
+ * None
+ */
+unsigned int base_node_candidates_get_length(struct TariBaseNodeCandidates *base_node_candidates,
+                                             int *error_out);
+
+/**
+ * Get TariBaseNodeCandidate at position from the TariBaseNodeCandidates.
+ *
+ * ## Arguments
+ * `base_node_candidates` - The pointer to a TariBaseNodeCandidates.
+ * `position` - The integer position.
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `*mut TariBaseNodeCandidate` - returns the TariBaseNodeCandidate, note that it returns ptr::null_mut() if
+ * base_node_candidates is null or position is invalid.
+ *
+ * # Safety
+ * The ```base_node_candidate_destroy``` method must be called when finished with a TariBaseNodeCandidate to prevent
+ * a memory leak.
+ */
+TariBaseNodeCandidate *base_node_candidates_get_at(struct TariBaseNodeCandidates *base_node_candidates,
+                                                   unsigned int position,
+                                                   int *error_out);
+
+/**
+ * Frees memory for a TariBaseNodeCandidates
+ *
+ * ## Arguments
+ * `base_node_candidates` - The TariBaseNodeCandidates pointer
+ *
+ * ## Returns
+ * `()` - Does not return a value, equivalent to void in C
+ *
+ * # Safety
+ * None
+ */
+void base_node_candidates_destroy(struct TariBaseNodeCandidates *base_node_candidates);
+
+/**
+ * Get the public key of a TariBaseNodeCandidate
+ *
+ * ## Arguments
+ * `base_node_candidate` - The TariBaseNodeCandidate pointer
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `*mut TariPublicKey` - Returns the public key, note that it returns ptr::null_mut() if base_node_candidate is
+ * null
+ *
+ * # Safety
+ * The ```public_key_destroy``` method must be called when finished with the TariPublicKey to prevent a memory leak.
+ */
+TariPublicKey *base_node_candidate_get_public_key(TariBaseNodeCandidate *base_node_candidate,
+                                                  int *error_out);
+
+/**
+ * Get the latency in milliseconds of the last successful check of a TariBaseNodeCandidate
+ *
+ * ## Arguments
+ * `base_node_candidate` - The TariBaseNodeCandidate pointer
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `c_ulonglong` - Returns the latency, or 0 if the base node has not been checked yet
+ *
+ * # Safety
+ * None
+ */
+unsigned long long base_node_candidate_get_latency_ms(TariBaseNodeCandidate *base_node_candidate,
+                                                      int *error_out);
+
+/**
+ * Get the chain height reported by a TariBaseNodeCandidate
+ *
+ * ## Arguments
+ * `base_node_candidate` - The TariBaseNodeCandidate pointer
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `c_ulonglong` - Returns the height, or 0 if the base node has not reported a height yet
+ *
+ * # Safety
+ * None
+ */
+unsigned long long base_node_candidate_get_height(TariBaseNodeCandidate *base_node_candidate,
+                                                  int *error_out);
+
+/**
+ * Get the number of consecutive failed checks of a TariBaseNodeCandidate
+ *
+ * ## Arguments
+ * `base_node_candidate` - The TariBaseNodeCandidate pointer
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `c_uint` - Returns the number of consecutive failures
+ *
+ * # Safety
+ * None
+ */
+unsigned int base_node_candidate_get_consecutive_failures(TariBaseNodeCandidate *base_node_candidate,
+                                                          int *error_out);
+
+/**
+ * Get whether a TariBaseNodeCandidate is currently considered healthy
+ *
+ * ## Arguments
+ * `base_node_candidate` - The TariBaseNodeCandidate pointer
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `bool` - Returns if the base node is healthy
+ *
+ * # Safety
+ * None
+ */
+bool base_node_candidate_is_healthy(TariBaseNodeCandidate *base_node_candidate,
+                                    int *error_out);
+
+/**
+ * Get whether a TariBaseNodeCandidate is the pinned base node
+ *
+ * ## Arguments
+ * `base_node_candidate` - The TariBaseNodeCandidate pointer
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `bool` - Returns if the base node is pinned
+ *
+ * # Safety
+ * None
+ */
+bool base_node_candidate_is_pinned(TariBaseNodeCandidate *base_node_candidate,
+                                   int *error_out);
+
+/**
+ * Get whether a TariBaseNodeCandidate is the active base node of the wallet
+ *
+ * ## Arguments
+ * `base_node_candidate` - The TariBaseNodeCandidate pointer
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `bool` - Returns if the base node is active
+ *
+ * # Safety
+ * None
+ */
+bool base_node_candidate_is_active(TariBaseNodeCandidate *base_node_candidate,
+                                   int *error_out);
+
+/**
+ * Frees memory for a TariBaseNodeCandidate
+ *
+ * ## Arguments
+ * `base_node_candidate` - The TariBaseNodeCandidate pointer
+ *
+ * ## Returns
+ * `()` - Does not return a value, equivalent to void in C
+ *
+ * # Safety
+ * None
+ */
+void base_node_candidate_destroy(TariBaseNodeCandidate *base_node_candidate);
+
 /**
  * Extracts a `NodeId` represented as a vector of bytes wrapped into a `ByteVector`
  *
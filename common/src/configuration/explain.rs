@@ -0,0 +1,360 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Tracks which source each configuration value was taken from, so that the effective configuration of an
+//! application can be printed along with the origin of every setting.
+//!
+//! Configuration is layered, in increasing order of precedence:
+//! 1. the defaults of the application's config structs,
+//! 2. the configuration file,
+//! 3. `TARI_*` environment variables, e.g. `TARI_BASE_NODE__GRPC_ADDRESS` for `base_node.grpc_address`,
+//! 4. command line overrides, e.g. `-p base_node.grpc_address=/ip4/127.0.0.1/tcp/18142`.
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt,
+    fmt::{Display, Formatter, Write},
+};
+
+use config::{Config, Source, Value, ValueKind};
+use serde::Serialize;
+
+use crate::configuration::loader::{ConfigPath, ConfigurationError};
+
+/// Key segments that mark a setting as sensitive, so that its value is never printed
+const REDACTED_KEY_PATTERNS: &[&str] = &["password", "passphrase", "secret", "auth"];
+
+/// A source a configuration value was set by, other than the defaults
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigOrigin {
+    /// The value was set in the configuration file at this path
+    File(String),
+    /// The value was set by this environment variable
+    Environment(String),
+    /// The value was set by a command line argument
+    CommandLine,
+}
+
+impl ConfigOrigin {
+    /// The name of the environment variable that sets the given configuration key
+    pub fn environment_variable(key: &str) -> Self {
+        ConfigOrigin::Environment(format!("TARI_{}", key.to_uppercase().replace('.', "__")))
+    }
+}
+
+impl Display for ConfigOrigin {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigOrigin::File(path) => write!(f, "file ({})", path),
+            ConfigOrigin::Environment(var) => write!(f, "environment ({})", var),
+            ConfigOrigin::CommandLine => write!(f, "command line"),
+        }
+    }
+}
+
+/// The sources that set each configuration key, in increasing order of precedence
+#[derive(Debug, Clone, Default)]
+pub struct ConfigOrigins {
+    origins: BTreeMap<String, Vec<ConfigOrigin>>,
+}
+
+impl ConfigOrigins {
+    /// Returns the sources that set the key, the last of which supplied the value. Empty if the key was not set by
+    /// any source, i.e. the default value is used.
+    pub fn get(&self, key: &str) -> &[ConfigOrigin] {
+        self.origins
+            .get(&key.to_lowercase())
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn record_key(&mut self, key: &str, origin: ConfigOrigin) {
+        self.origins.entry(key.to_lowercase()).or_default().push(origin);
+    }
+
+    /// Records `origin` for every value in `config`
+    pub(crate) fn record_config<F>(&mut self, config: &Config, origin: F) -> Result<(), config::ConfigError>
+    where F: Fn(&str) -> ConfigOrigin {
+        let mut keys = Vec::new();
+        for (key, value) in Source::collect(config)? {
+            flatten_config_value(key, value, &mut keys);
+        }
+        for key in keys {
+            let origin = origin(&key);
+            self.record_key(&key, origin);
+        }
+        Ok(())
+    }
+}
+
+fn flatten_config_value(key: String, value: Value, keys: &mut Vec<String>) {
+    match value.kind {
+        ValueKind::Table(table) => {
+            for (sub_key, sub_value) in table {
+                flatten_config_value(format!("{}.{}", key, sub_key), sub_value, keys);
+            }
+        },
+        _ => keys.push(key),
+    }
+}
+
+#[derive(Debug, Clone)]
+struct EffectiveSetting {
+    key: String,
+    value: String,
+    origins: Vec<ConfigOrigin>,
+}
+
+/// The effective configuration of an application, built from the config sections it loaded
+pub struct EffectiveConfig<'a> {
+    config: &'a Config,
+    origins: &'a ConfigOrigins,
+    settings: Vec<EffectiveSetting>,
+    /// The configuration keys that can set one of the settings
+    setting_keys: HashSet<String>,
+}
+
+impl<'a> EffectiveConfig<'a> {
+    pub fn new(config: &'a Config, origins: &'a ConfigOrigins) -> Self {
+        Self {
+            config,
+            origins,
+            settings: Vec::new(),
+            setting_keys: HashSet::new(),
+        }
+    }
+
+    /// Adds the settings of a loaded config section
+    pub fn add_section<T: ConfigPath + Serialize>(&mut self, section: &T) -> Result<(), ConfigurationError> {
+        let main_prefix = T::main_key_prefix();
+        let overload_prefix = T::overload_key_prefix(self.config)?;
+        let mut leaves = Vec::new();
+        flatten_json_value(String::new(), serde_json::to_value(section)?, &mut leaves);
+
+        for (path, value) in leaves {
+            let key = join_key(main_prefix, &path);
+            // Values under the `override_from` section replace those of the main section
+            let mut candidates = Vec::with_capacity(2);
+            if let Some(ref overload_prefix) = overload_prefix {
+                candidates.push(join_key(overload_prefix, &path).to_lowercase());
+            }
+            candidates.push(key.to_lowercase());
+
+            let origins = candidates
+                .iter()
+                .map(|candidate| self.origins.get(candidate))
+                .find(|origins| !origins.is_empty())
+                .unwrap_or_default()
+                .to_vec();
+            let value = if is_sensitive(&key) && !value.is_null() {
+                "\"<redacted>\"".to_string()
+            } else {
+                value.to_string()
+            };
+            self.setting_keys.extend(candidates);
+            self.settings.push(EffectiveSetting { key, value, origins });
+        }
+        Ok(())
+    }
+
+    /// Renders one `key = value` line per setting. If `explain` is set, each line is annotated with the source the
+    /// value was taken from and any lower precedence sources it overrides, followed by the environment variables and
+    /// command line overrides that do not set any of the settings.
+    pub fn render(&self, explain: bool) -> String {
+        let mut out = String::new();
+        for setting in &self.settings {
+            let _ = write!(out, "{} = {}", setting.key, setting.value);
+            if explain {
+                match setting.origins.split_last() {
+                    Some((origin, overridden)) => {
+                        let _ = write!(out, "  # {}", origin);
+                        if !overridden.is_empty() {
+                            let overridden = overridden.iter().rev().map(ToString::to_string).collect::<Vec<_>>();
+                            let _ = write!(out, ", overrides {}", overridden.join(", "));
+                        }
+                    },
+                    None => out.push_str("  # default"),
+                }
+            }
+            out.push('\n');
+        }
+
+        if explain {
+            let unused = self.unused_overrides();
+            if !unused.is_empty() {
+                out.push_str("\n# The following overrides do not set any setting and are ignored:\n");
+                for (key, origin) in unused {
+                    let _ = writeln!(out, "#   {} from {}", key, origin);
+                }
+            }
+        }
+        out
+    }
+
+    /// The keys set by the environment or on the command line that do not set any of the settings, which usually
+    /// means they are misspelled or meant for another application
+    fn unused_overrides(&self) -> Vec<(&str, &ConfigOrigin)> {
+        self.origins
+            .origins
+            .iter()
+            // `override_from` keys select a section rather than setting a value
+            .filter(|(key, _)| !key.ends_with("override_from"))
+            .filter(|(key, _)| !self.is_setting_key(key))
+            .flat_map(|(key, origins)| {
+                origins
+                    .iter()
+                    .filter(|origin| !matches!(origin, ConfigOrigin::File(_)))
+                    .map(move |origin| (key.as_str(), origin))
+            })
+            .collect()
+    }
+
+    fn is_setting_key(&self, key: &str) -> bool {
+        if self.setting_keys.contains(key) {
+            return true;
+        }
+        // A key may set a whole table of settings
+        let prefix = format!("{}.", key);
+        self.setting_keys.iter().any(|k| k.starts_with(&prefix))
+    }
+}
+
+fn join_key(prefix: &str, path: &str) -> String {
+    match (prefix.is_empty(), path.is_empty()) {
+        (_, true) => prefix.to_string(),
+        (true, false) => path.to_string(),
+        (false, false) => format!("{}.{}", prefix, path),
+    }
+}
+
+fn flatten_json_value(path: String, value: serde_json::Value, leaves: &mut Vec<(String, serde_json::Value)>) {
+    match value {
+        serde_json::Value::Object(map) if !map.is_empty() => {
+            for (key, value) in map {
+                flatten_json_value(join_key(&path, &key), value, leaves);
+            }
+        },
+        value => leaves.push((path, value)),
+    }
+}
+
+fn is_sensitive(key: &str) -> bool {
+    key.split('.').any(|segment| {
+        let segment = segment.to_lowercase();
+        REDACTED_KEY_PATTERNS.iter().any(|pattern| segment.contains(pattern))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use config::{File, FileFormat};
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+    use crate::configuration::loader::SubConfigPath;
+
+    #[derive(Default, Serialize, Deserialize)]
+    struct TestConfig {
+        override_from: Option<String>,
+        address: String,
+        port: u16,
+        password: Option<String>,
+        limits: TestLimits,
+    }
+
+    #[derive(Default, Serialize, Deserialize)]
+    struct TestLimits {
+        max_peers: u32,
+    }
+
+    impl SubConfigPath for TestConfig {
+        fn main_key_prefix() -> &'static str {
+            "test"
+        }
+    }
+
+    fn effective_config_lines(config: &Config, origins: &ConfigOrigins, explain: bool) -> Vec<String> {
+        let section = TestConfig {
+            override_from: config.get_string("test.override_from").ok(),
+            address: "localhost".to_string(),
+            port: 1234,
+            password: Some("hunter2".to_string()),
+            limits: TestLimits { max_peers: 8 },
+        };
+        let mut effective = EffectiveConfig::new(config, origins);
+        effective.add_section(&section).unwrap();
+        effective.render(explain).lines().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn it_explains_the_origin_of_each_setting() {
+        let file = Config::builder()
+            .add_source(File::from_str(
+                "[test]\naddress = \"example.com\"\npassword = \"hunter2\"\n",
+                FileFormat::Toml,
+            ))
+            .build()
+            .unwrap();
+        let mut origins = ConfigOrigins::default();
+        origins
+            .record_config(&file, |_| ConfigOrigin::File("config.toml".to_string()))
+            .unwrap();
+        origins.record_key("test.address", ConfigOrigin::environment_variable("test.address"));
+        origins.record_key("test.limits.max_peers", ConfigOrigin::CommandLine);
+        origins.record_key("test.misspelled", ConfigOrigin::CommandLine);
+
+        let lines = effective_config_lines(&file, &origins, true);
+        assert!(lines.contains(
+            &"test.address = \"localhost\"  # environment (TARI_TEST__ADDRESS), overrides file (config.toml)"
+                .to_string()
+        ));
+        assert!(lines.contains(&"test.port = 1234  # default".to_string()));
+        assert!(lines.contains(&"test.password = \"<redacted>\"  # file (config.toml)".to_string()));
+        assert!(lines.contains(&"test.limits.max_peers = 8  # command line".to_string()));
+        assert!(lines.contains(&"#   test.misspelled from command line".to_string()));
+
+        let lines = effective_config_lines(&file, &origins, false);
+        assert!(lines.contains(&"test.port = 1234".to_string()));
+        assert!(lines.iter().all(|line| !line.contains('#')));
+    }
+
+    #[test]
+    fn it_attributes_settings_to_the_override_from_section() {
+        let file = Config::builder()
+            .add_source(File::from_str(
+                "[test]\noverride_from = \"custom\"\nport = 1\n[custom.test]\nport = 1234\n",
+                FileFormat::Toml,
+            ))
+            .build()
+            .unwrap();
+        let mut origins = ConfigOrigins::default();
+        origins
+            .record_config(&file, |_| ConfigOrigin::File("config.toml".to_string()))
+            .unwrap();
+        origins.record_key("custom.test.port", ConfigOrigin::CommandLine);
+
+        let lines = effective_config_lines(&file, &origins, true);
+        assert!(lines.contains(&"test.port = 1234  # command line, overrides file (config.toml)".to_string()));
+        assert!(lines.contains(&"test.override_from = \"custom\"  # file (config.toml)".to_string()));
+        assert!(lines.iter().all(|line| !line.starts_with("#   ")));
+    }
+}
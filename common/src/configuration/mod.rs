@@ -39,6 +39,7 @@
 
 pub mod bootstrap;
 pub mod error;
+pub mod explain;
 pub mod loader;
 mod network;
 pub use network::Network;
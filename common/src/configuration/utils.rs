@@ -13,7 +13,11 @@ use serde::{
 };
 
 use crate::{
-    configuration::{ConfigOverrideProvider, Network},
+    configuration::{
+        explain::{ConfigOrigin, ConfigOrigins},
+        ConfigOverrideProvider,
+        Network,
+    },
     ConfigError,
     LOG_TARGET,
 };
@@ -25,6 +29,17 @@ pub fn load_configuration<P: AsRef<Path>, TOverride: ConfigOverrideProvider>(
     create_if_not_exists: bool,
     overrides: &TOverride,
 ) -> Result<Config, ConfigError> {
+    load_configuration_with_origins(config_path, create_if_not_exists, overrides).map(|(cfg, _)| cfg)
+}
+
+/// Loads the configuration from the configuration file, `TARI_*` environment variables and the command line
+/// overrides, in increasing order of precedence, and returns it along with the sources that set each key. Values not
+/// set by any of these are taken from the defaults of the application's config structs.
+pub fn load_configuration_with_origins<P: AsRef<Path>, TOverride: ConfigOverrideProvider>(
+    config_path: P,
+    create_if_not_exists: bool,
+    overrides: &TOverride,
+) -> Result<(Config, ConfigOrigins), ConfigError> {
     debug!(
         target: LOG_TARGET,
         "Loading configuration file from  {}",
@@ -38,8 +53,11 @@ pub fn load_configuration<P: AsRef<Path>, TOverride: ConfigOverrideProvider>(
         .as_ref()
         .to_str()
         .ok_or_else(|| ConfigError::new("Invalid config file path", None))?;
-    let cfg = Config::builder()
+    let file_cfg = Config::builder()
         .add_source(config::File::with_name(filename))
+        .build()
+        .map_err(|ce| ConfigError::new("Could not build config", Some(ce.to_string())))?;
+    let env_cfg = Config::builder()
         .add_source(
             config::Environment::with_prefix("TARI")
                 .prefix_separator("_")
@@ -48,6 +66,20 @@ pub fn load_configuration<P: AsRef<Path>, TOverride: ConfigOverrideProvider>(
         .build()
         .map_err(|ce| ConfigError::new("Could not build config", Some(ce.to_string())))?;
 
+    let mut origins = ConfigOrigins::default();
+    origins
+        .record_config(&file_cfg, |_| ConfigOrigin::File(filename.to_string()))
+        .map_err(|ce| ConfigError::new("Could not read config file", Some(ce.to_string())))?;
+    origins
+        .record_config(&env_cfg, ConfigOrigin::environment_variable)
+        .map_err(|ce| ConfigError::new("Could not read config environment variables", Some(ce.to_string())))?;
+
+    let cfg = Config::builder()
+        .add_source(file_cfg)
+        .add_source(env_cfg)
+        .build()
+        .map_err(|ce| ConfigError::new("Could not build config", Some(ce.to_string())))?;
+
     let network = match cfg.get_string("network") {
         Ok(network) => {
             Network::from_str(&network).map_err(|e| ConfigError::new("Invalid network", Some(e.to_string())))?
@@ -67,11 +99,12 @@ pub fn load_configuration<P: AsRef<Path>, TOverride: ConfigOverrideProvider>(
     info!(target: LOG_TARGET, "Configuration file loaded.");
     let overrides = overrides.get_config_property_overrides(network);
     if overrides.is_empty() {
-        return Ok(cfg);
+        return Ok((cfg, origins));
     }
 
     let mut cfg = Config::builder().add_source(cfg);
     for (key, value) in overrides {
+        origins.record_key(&key, ConfigOrigin::CommandLine);
         cfg = cfg
             .set_override(key.as_str(), value.as_str())
             .map_err(|ce| ConfigError::new("Could not override config property", Some(ce.to_string())))?;
@@ -80,7 +113,7 @@ pub fn load_configuration<P: AsRef<Path>, TOverride: ConfigOverrideProvider>(
         .build()
         .map_err(|ce| ConfigError::new("Could not build config", Some(ce.to_string())))?;
 
-    Ok(cfg)
+    Ok((cfg, origins))
 }
 
 /// Installs a new configuration file template, copied from the application type's preset and written to the given path.
@@ -61,7 +61,7 @@ pub use configuration::{
     error::ConfigError,
     loader::{ConfigLoader, ConfigPath, ConfigurationError, DefaultConfigLoader, SubConfigPath},
     name_server::DnsNameServer,
-    utils::load_configuration,
+    utils::{load_configuration, load_configuration_with_origins},
 };
 pub mod dir_utils;
 pub use logging::initialize_logging;
@@ -129,6 +129,7 @@ pub async fn create(
                 proxy_address: TOR_SOCKS_ADDR.parse().unwrap(),
                 authentication: Default::default(),
                 proxy_bypass_predicate: Arc::new(FalsePredicate::new()),
+                isolate_streams: false,
             }))
             .await
             .unwrap()
@@ -45,13 +45,19 @@ use crate::{
     connection_manager::{ConnectionManagerConfig, ConnectionManagerRequester},
     connectivity::{ConnectivityConfig, ConnectivityRequester},
     multiaddr::Multiaddr,
-    peer_manager::{NodeIdentity, PeerManager},
+    peer_manager::{NodeIdentity, PeerManager, PeerStorage},
     peer_validator::PeerValidatorConfig,
     protocol::{NodeNetworkInfo, ProtocolExtensions},
     tor,
     types::CommsDatabase,
 };
 
+/// The backend the [PeerManager] persists peers to.
+enum PeerStorageBackend {
+    Lmdb(CommsDatabase),
+    Custom(Box<dyn PeerStorage>),
+}
+
 /// # CommsBuilder
 ///
 /// [CommsBuilder] is used to customize and spawn Tari comms core.
@@ -118,7 +124,7 @@ use crate::{
 ///
 /// [CommsBuilder]: crate::CommsBuilder
 pub struct CommsBuilder {
-    peer_storage: Option<CommsDatabase>,
+    peer_storage: Option<PeerStorageBackend>,
     peer_storage_file_lock: Option<File>,
     node_identity: Option<Arc<NodeIdentity>>,
     dial_backoff: BoxedBackoff,
@@ -253,6 +259,20 @@ impl CommsBuilder {
         self
     }
 
+    /// The maximum number of inbound substreams a peer may open for a single protocol on a connection. Set to 0 for
+    /// no limit.
+    pub fn with_max_substreams_per_protocol(mut self, max_substreams_per_protocol: usize) -> Self {
+        self.connection_manager_config.max_substreams_per_protocol = max_substreams_per_protocol;
+        self
+    }
+
+    /// The maximum number of connections allowed in the connection pool. Once this limit is reached, the least
+    /// recently used idle connections are disconnected to make room for new connections. Set to 0 for no limit.
+    pub fn with_max_connections(mut self, max_connections: usize) -> Self {
+        self.connectivity_config.max_connections = max_connections;
+        self
+    }
+
     /// The number of dial attempts to make before giving up.
     pub fn with_max_dial_attempts(mut self, max_dial_attempts: usize) -> Self {
         self.connection_manager_config.max_dial_attempts = max_dial_attempts;
@@ -274,7 +294,16 @@ impl CommsBuilder {
 
     /// Set the peer storage database to use.
     pub fn with_peer_storage(mut self, peer_storage: CommsDatabase, file_lock: Option<File>) -> Self {
-        self.peer_storage = Some(peer_storage);
+        self.peer_storage = Some(PeerStorageBackend::Lmdb(peer_storage));
+        self.peer_storage_file_lock = file_lock;
+        self
+    }
+
+    /// Set a custom [PeerStorage] backend to use instead of the LMDB peer database e.g.
+    /// [InMemoryPeerStorage](crate::peer_manager::InMemoryPeerStorage).
+    pub fn with_custom_peer_storage<T>(mut self, peer_storage: T, file_lock: Option<File>) -> Self
+    where T: PeerStorage + 'static {
+        self.peer_storage = Some(PeerStorageBackend::Custom(Box::new(peer_storage)));
         self.peer_storage_file_lock = file_lock;
         self
     }
@@ -297,13 +326,18 @@ impl CommsBuilder {
         let file_lock = self.peer_storage_file_lock.take();
 
         match self.peer_storage.take() {
-            Some(storage) => {
+            Some(PeerStorageBackend::Lmdb(storage)) => {
                 #[cfg(not(test))]
                 PeerManager::migrate_lmdb(&storage.inner())?;
 
                 let peer_manager = PeerManager::new(storage, file_lock).map_err(CommsBuilderError::PeerManagerError)?;
                 Ok(Arc::new(peer_manager))
             },
+            Some(PeerStorageBackend::Custom(storage)) => {
+                let peer_manager =
+                    PeerManager::with_storage(storage, file_lock).map_err(CommsBuilderError::PeerManagerError)?;
+                Ok(Arc::new(peer_manager))
+            },
             None => Err(CommsBuilderError::PeerStorageNotProvided),
         }
     }
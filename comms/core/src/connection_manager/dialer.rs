@@ -475,6 +475,7 @@ where
             conn_man_notifier,
             our_supported_protocols,
             peer_identity.metadata.supported_protocols.clone(),
+            config.max_substreams_per_protocol,
         );
 
         Ok((peer_connection, peer_identity))
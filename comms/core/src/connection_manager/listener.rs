@@ -410,6 +410,7 @@ where
             conn_man_notifier,
             our_supported_protocols,
             valid_peer_identity.metadata.supported_protocols,
+            config.max_substreams_per_protocol,
         );
 
         peer_manager.add_peer(peer).await?;
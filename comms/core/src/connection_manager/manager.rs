@@ -126,6 +126,10 @@ pub struct ConnectionManagerConfig {
     pub auxiliary_tcp_listener_address: Option<Multiaddr>,
     /// Peer validation configuration. See [PeerValidatorConfig]
     pub peer_validation_config: PeerValidatorConfig,
+    /// The maximum number of inbound substreams a peer may have open for a single protocol on a connection. Further
+    /// substreams for that protocol are rejected until existing ones are closed. Set to 0 for no limit.
+    /// Default: 50
+    pub max_substreams_per_protocol: usize,
 }
 
 impl Default for ConnectionManagerConfig {
@@ -147,6 +151,7 @@ impl Default for ConnectionManagerConfig {
             auxiliary_tcp_listener_address: None,
             peer_validation_config: PeerValidatorConfig::default(),
             noise_handshake_recv_timeout: Duration::from_secs(6),
+            max_substreams_per_protocol: 50,
         }
     }
 }
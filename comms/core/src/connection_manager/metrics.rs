@@ -81,3 +81,29 @@ pub fn inbound_substream_counter(peer: &NodeId, protocol: &ProtocolId) -> IntCou
 
     METER.with_label_values(&[peer.to_string().as_str(), String::from_utf8_lossy(protocol).as_ref()])
 }
+
+pub fn active_inbound_substreams(protocol: &ProtocolId) -> IntGauge {
+    static METER: Lazy<IntGaugeVec> = Lazy::new(|| {
+        tari_metrics::register_int_gauge_vec(
+            "comms::connections::active_inbound_substreams",
+            "Number of open inbound substreams by protocol",
+            &["protocol"],
+        )
+        .unwrap()
+    });
+
+    METER.with_label_values(&[String::from_utf8_lossy(protocol).as_ref()])
+}
+
+pub fn rejected_substream_counter(peer: &NodeId, protocol: &ProtocolId) -> IntCounter {
+    static METER: Lazy<IntCounterVec> = Lazy::new(|| {
+        tari_metrics::register_int_counter_vec(
+            "comms::connections::rejected_inbound_substreams",
+            "Number of inbound substreams rejected because the per-protocol limit was reached",
+            &["peer_id", "protocol"],
+        )
+        .unwrap()
+    });
+
+    METER.with_label_values(&[peer.to_string().as_str(), String::from_utf8_lossy(protocol).as_ref()])
+}
@@ -48,6 +48,7 @@ mod error;
 pub use error::{ConnectionManagerError, PeerConnectionError};
 
 mod peer_connection;
+pub(crate) use peer_connection::InboundSubstreamGuard;
 pub use peer_connection::{ConnectionId, NegotiatedSubstream, PeerConnection, PeerConnectionRequest};
 
 mod liveness;
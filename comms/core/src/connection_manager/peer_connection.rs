@@ -21,10 +21,12 @@
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use std::{
+    collections::HashMap,
+    convert::TryFrom,
     fmt,
     future::Future,
     sync::{
-        atomic::{AtomicUsize, Ordering},
+        atomic::{AtomicU64, AtomicUsize, Ordering},
         Arc,
     },
     time::{Duration, Instant},
@@ -33,6 +35,7 @@ use std::{
 use futures::{future::BoxFuture, stream::FuturesUnordered};
 use log::*;
 use multiaddr::Multiaddr;
+use tari_metrics::IntGauge;
 use tokio::{
     sync::{mpsc, oneshot},
     time,
@@ -40,7 +43,7 @@ use tokio::{
 use tokio_stream::StreamExt;
 use tracing::{self, span, Instrument, Level};
 
-use super::{direction::ConnectionDirection, error::PeerConnectionError, manager::ConnectionManagerEvent};
+use super::{direction::ConnectionDirection, error::PeerConnectionError, manager::ConnectionManagerEvent, metrics};
 #[cfg(feature = "rpc")]
 use crate::protocol::rpc::{
     pool::RpcClientPool,
@@ -57,7 +60,7 @@ use crate::{
     multiplexing::{Control, IncomingSubstreams, Substream, Yamux},
     peer_manager::{NodeId, PeerFeatures},
     protocol::{ProtocolId, ProtocolNegotiation},
-    utils::atomic_ref_counter::AtomicRefCounter,
+    utils::atomic_ref_counter::{AtomicRefCounter, AtomicRefCounterGuard},
 };
 
 const LOG_TARGET: &str = "comms::connection_manager::peer_connection";
@@ -75,6 +78,7 @@ pub fn create(
     event_notifier: mpsc::Sender<ConnectionManagerEvent>,
     our_supported_protocols: Arc<Vec<ProtocolId>>,
     their_supported_protocols: Vec<ProtocolId>,
+    max_substreams_per_protocol: usize,
 ) -> PeerConnection {
     trace!(
         target: LOG_TARGET,
@@ -103,6 +107,8 @@ pub fn create(
         event_notifier,
         our_supported_protocols,
         their_supported_protocols,
+        max_substreams_per_protocol,
+        peer_conn.last_activity.clone(),
     );
     tokio::spawn(peer_actor.run());
 
@@ -134,6 +140,7 @@ pub struct PeerConnection {
     address: Arc<Multiaddr>,
     direction: ConnectionDirection,
     started_at: Instant,
+    last_activity: ActivityTracker,
     substream_counter: AtomicRefCounter,
     handle_counter: Arc<()>,
 }
@@ -148,6 +155,7 @@ impl PeerConnection {
         direction: ConnectionDirection,
         substream_counter: AtomicRefCounter,
     ) -> Self {
+        let started_at = Instant::now();
         Self {
             id,
             request_tx,
@@ -155,7 +163,8 @@ impl PeerConnection {
             peer_features,
             address: Arc::new(address),
             direction,
-            started_at: Instant::now(),
+            started_at,
+            last_activity: ActivityTracker::new(started_at),
             substream_counter,
             handle_counter: Arc::new(()),
         }
@@ -203,6 +212,12 @@ impl PeerConnection {
         self.started_at.elapsed()
     }
 
+    /// The time elapsed since a substream was last opened on this connection, or since the connection was
+    /// established if no substreams have been opened.
+    pub fn idle_time(&self) -> Duration {
+        self.last_activity.elapsed()
+    }
+
     pub fn substream_count(&self) -> usize {
         self.substream_counter.get()
     }
@@ -332,6 +347,9 @@ struct PeerConnectionActor {
     inbound_protocol_negotiations:
         FuturesUnordered<BoxFuture<'static, Result<(ProtocolId, Substream), PeerConnectionError>>>,
     their_supported_protocols: Vec<ProtocolId>,
+    inbound_substream_counters: HashMap<ProtocolId, AtomicRefCounter>,
+    max_substreams_per_protocol: usize,
+    last_activity: ActivityTracker,
 }
 
 impl PeerConnectionActor {
@@ -344,6 +362,8 @@ impl PeerConnectionActor {
         event_notifier: mpsc::Sender<ConnectionManagerEvent>,
         our_supported_protocols: Arc<Vec<ProtocolId>>,
         their_supported_protocols: Vec<ProtocolId>,
+        max_substreams_per_protocol: usize,
+        last_activity: ActivityTracker,
     ) -> Self {
         Self {
             id,
@@ -356,6 +376,9 @@ impl PeerConnectionActor {
             our_supported_protocols,
             inbound_protocol_negotiations: FuturesUnordered::new(),
             their_supported_protocols,
+            inbound_substream_counters: HashMap::new(),
+            max_substreams_per_protocol,
+            last_activity,
         }
     }
 
@@ -447,7 +470,32 @@ impl PeerConnectionActor {
         result: Result<(ProtocolId, Substream), PeerConnectionError>,
     ) {
         match result {
-            Ok((selected_protocol, stream)) => {
+            Ok((selected_protocol, mut stream)) => {
+                let counter = self
+                    .inbound_substream_counters
+                    .entry(selected_protocol.clone())
+                    .or_default()
+                    .clone();
+                if self.max_substreams_per_protocol > 0 && counter.get() >= self.max_substreams_per_protocol {
+                    warn!(
+                        target: LOG_TARGET,
+                        "[{}] Rejecting inbound substream for protocol '{}' from peer '{}' because the limit of {} \
+                         substreams for this protocol has been reached",
+                        self,
+                        String::from_utf8_lossy(&selected_protocol),
+                        self.peer_node_id.short_str(),
+                        self.max_substreams_per_protocol
+                    );
+                    metrics::rejected_substream_counter(&self.peer_node_id, &selected_protocol).inc();
+                    // Dropping the substream resets it
+                    return;
+                }
+                stream.set_protocol_guard(InboundSubstreamGuard::new(
+                    counter.new_guard(),
+                    metrics::active_inbound_substreams(&selected_protocol),
+                ));
+                self.last_activity.touch();
+
                 self.notify_event(ConnectionManagerEvent::NewInboundSubstream(
                     self.peer_node_id.clone(),
                     selected_protocol,
@@ -494,6 +542,7 @@ impl PeerConnectionActor {
             self.peer_node_id.short_str()
         );
         let mut stream = self.control.open_stream().await?;
+        self.last_activity.touch();
 
         let mut negotiation = ProtocolNegotiation::new(&mut stream);
 
@@ -564,6 +613,57 @@ impl fmt::Display for PeerConnectionActor {
     }
 }
 
+/// Records the time that a substream was last opened on a peer connection. This is shared between the
+/// [PeerConnection] handles and the actor.
+#[derive(Debug, Clone)]
+struct ActivityTracker {
+    started_at: Instant,
+    last_active_millis: Arc<AtomicU64>,
+}
+
+impl ActivityTracker {
+    fn new(started_at: Instant) -> Self {
+        Self {
+            started_at,
+            last_active_millis: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    fn touch(&self) {
+        let millis = u64::try_from(self.started_at.elapsed().as_millis()).unwrap_or(u64::MAX);
+        self.last_active_millis.store(millis, Ordering::Relaxed);
+    }
+
+    fn elapsed(&self) -> Duration {
+        let last_active = Duration::from_millis(self.last_active_millis.load(Ordering::Relaxed));
+        self.started_at.elapsed().saturating_sub(last_active)
+    }
+}
+
+/// Counts an inbound substream towards the per-protocol substream limit of a peer connection and the active
+/// substreams gauge for as long as the substream is held.
+#[derive(Debug)]
+pub(crate) struct InboundSubstreamGuard {
+    _counter_guard: AtomicRefCounterGuard,
+    gauge: IntGauge,
+}
+
+impl InboundSubstreamGuard {
+    fn new(counter_guard: AtomicRefCounterGuard, gauge: IntGauge) -> Self {
+        gauge.inc();
+        Self {
+            _counter_guard: counter_guard,
+            gauge,
+        }
+    }
+}
+
+impl Drop for InboundSubstreamGuard {
+    fn drop(&mut self) {
+        self.gauge.dec();
+    }
+}
+
 /// Contains the substream and the ProtocolId that was successfully negotiated.
 pub struct NegotiatedSubstream<TSubstream> {
     pub protocol: ProtocolId,
@@ -49,6 +49,11 @@ pub struct ConnectivityConfig {
     /// next connection attempt.
     /// Default: 24 hours
     pub expire_peer_last_seen_duration: Duration,
+    /// The maximum number of connections allowed in the connection pool. When a new connection exceeds this limit,
+    /// the least recently used idle connections are disconnected. If no idle connection can be disconnected, the new
+    /// connection is rejected. Set to 0 for no limit.
+    /// Default: 0
+    pub max_connections: usize,
 }
 
 impl Default for ConnectivityConfig {
@@ -62,6 +67,7 @@ impl Default for ConnectivityConfig {
             max_failures_mark_offline: 1,
             connection_tie_break_linger: Duration::from_secs(2),
             expire_peer_last_seen_duration: Duration::from_secs(24 * 60 * 60),
+            max_connections: 0,
         }
     }
 }
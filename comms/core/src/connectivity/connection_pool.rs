@@ -167,6 +167,11 @@ impl ConnectionPool {
         })
     }
 
+    /// Returns connected connections that are not referenced outside of the connection pool
+    pub fn get_idle_connections_mut(&mut self) -> Vec<&mut PeerConnection> {
+        self.filter_connections_mut(|conn| conn.is_connected() && conn.handle_count() <= 1)
+    }
+
     pub(in crate::connectivity) fn filter_drain<P>(&mut self, mut predicate: P) -> Vec<PeerConnectionState>
     where P: FnMut(&PeerConnectionState) -> bool {
         let (keep, remove) = self
@@ -20,6 +20,7 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 use std::{
+    cmp::Reverse,
     collections::HashMap,
     fmt,
     sync::Arc,
@@ -430,6 +431,49 @@ impl ConnectivityManagerActor {
         }
     }
 
+    /// Ensures that there is room in the pool for the new connection by disconnecting the least recently used idle
+    /// connections. Peers in the allow list are never disconnected. Returns false if the connection limit would still
+    /// be exceeded by adding the new connection.
+    async fn make_room_for_connection(&mut self, new_conn: &PeerConnection) -> bool {
+        if self.config.max_connections == 0 {
+            return true;
+        }
+        let excess_connections = (self.pool.count_connected() + 1).saturating_sub(self.config.max_connections);
+        if excess_connections == 0 {
+            return true;
+        }
+
+        let allow_list = &self.allow_list;
+        let mut connections = self.pool.get_idle_connections_mut();
+        connections.retain(|conn| conn.id() != new_conn.id() && !allow_list.contains(conn.peer_node_id()));
+        // Least recently used first
+        connections.sort_by_key(|conn| Reverse(conn.idle_time()));
+        connections.truncate(excess_connections);
+        let num_evicted = connections.len();
+        for conn in connections {
+            debug!(
+                target: LOG_TARGET,
+                "Disconnecting '{}' to make room for a new connection (idle for {:.0?})",
+                conn.peer_node_id().short_str(),
+                conn.idle_time()
+            );
+            if let Err(err) = conn.disconnect().await {
+                // Already disconnected
+                debug!(
+                    target: LOG_TARGET,
+                    "Peer '{}' already disconnected. Error: {:?}",
+                    conn.peer_node_id().short_str(),
+                    err
+                );
+            }
+        }
+
+        #[cfg(feature = "metrics")]
+        super::metrics::evicted_connections_counter().inc_by(num_evicted as u64);
+
+        num_evicted >= excess_connections
+    }
+
     fn clean_connection_pool(&mut self) {
         let cleared_states = self.pool.filter_drain(|state| {
             state.status() == ConnectionStatus::Failed || state.status() == ConnectionStatus::Disconnected
@@ -557,7 +601,20 @@ impl ConnectivityManagerActor {
                         // Ignore event, we discarded the new connection and keeping the current one
                         return Ok(());
                     },
-                    TieBreak::UseNew | TieBreak::None => {},
+                    TieBreak::None => {
+                        if !self.make_room_for_connection(new_conn).await {
+                            warn!(
+                                target: LOG_TARGET,
+                                "Rejecting new connection to peer '{}' because the maximum of {} connections has been \
+                                 reached and no idle connections could be disconnected",
+                                new_conn.peer_node_id().short_str(),
+                                self.config.max_connections
+                            );
+                            let _result = new_conn.clone().disconnect_silent().await;
+                            return Ok(());
+                        }
+                    },
+                    TieBreak::UseNew => {},
                 }
             },
             PeerDisconnected(id, node_id) => {
@@ -795,6 +852,13 @@ impl ConnectivityManagerActor {
 
         metrics::connections(ConnectionDirection::Inbound).set(num_inbound);
         metrics::connections(ConnectionDirection::Outbound).set(total - num_inbound);
+        metrics::idle_connections().set(self.pool.count_filtered(|state| {
+            state
+                .connection()
+                .map(|conn| conn.is_connected() && conn.handle_count() <= 1)
+                .unwrap_or(false)
+        }) as i64);
+        metrics::max_connections().set(i64::try_from(self.config.max_connections).unwrap_or(i64::MAX));
 
         let uptime = self
             .uptime
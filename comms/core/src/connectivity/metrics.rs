@@ -57,3 +57,39 @@ pub fn banned_peers_counter(peer: &NodeId) -> IntCounter {
 
     METER.with_label_values(&[peer.to_string().as_str()])
 }
+
+pub fn max_connections() -> IntGauge {
+    static METER: Lazy<IntGauge> = Lazy::new(|| {
+        tari_metrics::register_int_gauge(
+            "comms::connectivity::max_connections",
+            "The configured maximum number of connections (0 if unlimited)",
+        )
+        .unwrap()
+    });
+
+    METER.clone()
+}
+
+pub fn idle_connections() -> IntGauge {
+    static METER: Lazy<IntGauge> = Lazy::new(|| {
+        tari_metrics::register_int_gauge(
+            "comms::connectivity::num_idle_connections",
+            "Number of active connections that are not in use",
+        )
+        .unwrap()
+    });
+
+    METER.clone()
+}
+
+pub fn evicted_connections_counter() -> IntCounter {
+    static METER: Lazy<IntCounter> = Lazy::new(|| {
+        tari_metrics::register_int_counter(
+            "comms::connectivity::evicted_connections",
+            "Number of connections closed because the maximum number of connections was reached",
+        )
+        .unwrap()
+    });
+
+    METER.clone()
+}
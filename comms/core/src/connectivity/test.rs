@@ -452,3 +452,58 @@ async fn pool_management() {
     let conns = connectivity.get_active_connections().await.unwrap();
     assert!(conns.is_empty());
 }
+
+#[tokio::test]
+async fn max_connections() {
+    let config = ConnectivityConfig {
+        min_connectivity: 1,
+        max_connections: 3,
+        ..Default::default()
+    };
+    let (mut connectivity, mut event_stream, node_identity, peer_manager, cm_mock_state, _shutdown) =
+        setup_connectivity_manager(config);
+    let peers = add_test_peers(&peer_manager, 5).await;
+
+    let mut connections = future::join_all(
+        peers
+            .iter()
+            .cloned()
+            .map(|peer| create_peer_connection_mock_pair(peer, node_identity.to_peer())),
+    )
+    .await
+    .into_iter()
+    .map(|(_, _, conn, _)| conn)
+    .collect::<Vec<_>>();
+
+    let mut events = collect_try_recv!(event_stream, take = 1, timeout = Duration::from_secs(10));
+    unpack_enum!(ConnectivityEvent::ConnectivityStateInitialized = events.remove(0));
+
+    for conn in connections.iter().take(3) {
+        cm_mock_state.publish_event(ConnectionManagerEvent::PeerConnected(conn.clone().into()));
+    }
+    // 3 x PeerConnected + ConnectivityStateOnline
+    collect_try_recv!(event_stream, take = 4, timeout = Duration::from_secs(10));
+
+    // Only the pool references the second connection, so it is the only idle connection
+    let idle_conn = connections.remove(1);
+    let idle_conn_disconnected = idle_conn.on_disconnect();
+    drop(idle_conn);
+
+    cm_mock_state.publish_event(ConnectionManagerEvent::PeerConnected(connections[2].clone().into()));
+    tokio::time::timeout(Duration::from_secs(10), idle_conn_disconnected)
+        .await
+        .unwrap();
+    let mut events = collect_try_recv!(event_stream, take = 1, timeout = Duration::from_secs(10));
+    unpack_enum!(ConnectivityEvent::PeerConnected(_conn) = events.remove(0));
+    assert!(connections[2].is_connected());
+
+    // No idle connections remain, so the new connection is rejected
+    cm_mock_state.publish_event(ConnectionManagerEvent::PeerConnected(connections[3].clone().into()));
+    tokio::time::timeout(Duration::from_secs(10), connections[3].on_disconnect())
+        .await
+        .unwrap();
+
+    let conns = connectivity.get_active_connections().await.unwrap();
+    assert_eq!(conns.len(), 3);
+    assert!(conns.iter().all(|c| c.peer_node_id() != connections[3].peer_node_id()));
+}
@@ -34,7 +34,7 @@ pub use yamux::ConnectionError;
 use yamux::Mode;
 
 use crate::{
-    connection_manager::ConnectionDirection,
+    connection_manager::{ConnectionDirection, InboundSubstreamGuard},
     stream_id,
     stream_id::StreamId,
     utils::atomic_ref_counter::{AtomicRefCounter, AtomicRefCounterGuard},
@@ -143,6 +143,7 @@ impl Control {
         Ok(Substream {
             stream: stream.compat(),
             _counter_guard: counter_guard,
+            _protocol_guard: None,
         })
     }
 
@@ -186,6 +187,7 @@ impl Stream for IncomingSubstreams {
             Some(stream) => Poll::Ready(Some(Substream {
                 stream: stream.compat(),
                 _counter_guard: self.substream_counter.new_guard(),
+                _protocol_guard: None,
             })),
             None => Poll::Ready(None),
         }
@@ -197,6 +199,15 @@ impl Stream for IncomingSubstreams {
 pub struct Substream {
     stream: Compat<yamux::Stream>,
     _counter_guard: AtomicRefCounterGuard,
+    _protocol_guard: Option<InboundSubstreamGuard>,
+}
+
+impl Substream {
+    /// Attaches a guard that counts this substream towards the limit for its negotiated protocol. The guard is
+    /// released when the substream is dropped.
+    pub(crate) fn set_protocol_guard(&mut self, guard: InboundSubstreamGuard) {
+        self._protocol_guard = Some(guard);
+    }
 }
 
 impl StreamId for Substream {
@@ -34,7 +34,8 @@ use crate::{
         migrations,
         peer::{Peer, PeerFlags},
         peer_id::PeerId,
-        peer_storage::PeerStorage,
+        peer_storage::IndexedPeerStorage,
+        storage::PeerStorage,
         wrapper::KeyValueWrapper,
         NodeDistance,
         NodeId,
@@ -49,14 +50,22 @@ use crate::{
 /// It also provides functionality to add, find and delete peers.
 pub struct PeerManager {
     // yo dawg, I heard you like wrappers, so I wrapped your wrapper in a wrapper so you can wrap while you wrap
-    peer_storage: RwLock<PeerStorage<CachedStore<PeerId, Peer, KeyValueWrapper<CommsDatabase>>>>,
+    peer_storage: RwLock<IndexedPeerStorage<CachedStore<PeerId, Peer, Box<dyn PeerStorage>>>>,
     _file_lock: Option<File>,
 }
 
 impl PeerManager {
     /// Constructs a new empty PeerManager
     pub fn new(database: CommsDatabase, file_lock: Option<File>) -> Result<PeerManager, PeerManagerError> {
-        let storage = PeerStorage::new_indexed(CachedStore::new(KeyValueWrapper::new(database)))?;
+        Self::with_storage(Box::new(KeyValueWrapper::new(database)), file_lock)
+    }
+
+    /// Constructs a new PeerManager that persists peers to the given [PeerStorage] backend
+    pub fn with_storage(
+        storage: Box<dyn PeerStorage>,
+        file_lock: Option<File>,
+    ) -> Result<PeerManager, PeerManagerError> {
+        let storage = IndexedPeerStorage::new_indexed(CachedStore::new(storage))?;
         Ok(Self {
             peer_storage: RwLock::new(storage),
             _file_lock: file_lock,
@@ -378,6 +387,7 @@ mod test {
         peer_manager::{
             node_id::NodeId,
             peer::{Peer, PeerFlags},
+            InMemoryPeerStorage,
             PeerFeatures,
         },
     };
@@ -653,4 +663,22 @@ mod test {
 
         assert!(!peer.is_offline());
     }
+
+    #[tokio::test]
+    async fn test_with_storage() {
+        let storage = InMemoryPeerStorage::new();
+        let mut existing = create_test_peer(false, PeerFeatures::COMMUNICATION_NODE);
+        existing.set_id(1);
+        PeerStorage::insert(&storage, 1, existing.clone()).unwrap();
+
+        let peer_manager = PeerManager::with_storage(Box::new(storage), None).unwrap();
+        assert_eq!(peer_manager.count().await, 1);
+        let found = peer_manager.find_by_node_id(&existing.node_id).await.unwrap().unwrap();
+        assert_eq!(found.public_key, existing.public_key);
+
+        let peer = create_test_peer(false, PeerFeatures::COMMUNICATION_NODE);
+        peer_manager.add_peer(peer.clone()).await.unwrap();
+        assert!(peer_manager.exists(&peer.public_key).await);
+        assert_eq!(peer_manager.count().await, 2);
+    }
 }
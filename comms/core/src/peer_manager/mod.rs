@@ -94,7 +94,7 @@ mod peer_features;
 pub use peer_features::PeerFeatures;
 
 mod peer_id;
-pub(crate) use peer_id::PeerId;
+pub use peer_id::PeerId;
 
 mod manager;
 pub use manager::PeerManager;
@@ -103,7 +103,12 @@ mod peer_query;
 pub use peer_query::{PeerQuery, PeerQuerySortBy};
 
 mod peer_storage;
-pub use peer_storage::PeerStorage;
+pub use peer_storage::IndexedPeerStorage;
+
+mod storage;
+#[cfg(feature = "sqlite")]
+pub use storage::SqlitePeerStorage;
+pub use storage::{InMemoryPeerStorage, PeerStorage};
 
 mod peer_identity_claim;
 pub use peer_identity_claim::PeerIdentityClaim;
@@ -47,19 +47,19 @@ const LOG_TARGET: &str = "comms::peer_manager::peer_storage";
 const PEER_MANAGER_SYNC_PEERS: usize = 100;
 const PEER_ACTIVE_WITHIN_DURATION: u64 = 7 * 24 * 60 * 60; // 7 days, 24h, 60m, 60s = 1 week
 
-/// PeerStorage provides a mechanism to keep a datastore and a local copy of all peers in sync and allow fast searches
-/// using the node_id, public key or net_address of a peer.
-pub struct PeerStorage<DS> {
+/// IndexedPeerStorage provides a mechanism to keep a datastore and a local copy of all peers in sync and allow fast
+/// searches using the node_id, public key or net_address of a peer.
+pub struct IndexedPeerStorage<DS> {
     peer_db: DS,
     public_key_index: HashMap<CommsPublicKey, PeerId>,
     node_id_index: HashMap<NodeId, PeerId>,
 }
 
-impl<DS> PeerStorage<DS>
+impl<DS> IndexedPeerStorage<DS>
 where DS: KeyValueStore<PeerId, Peer>
 {
-    /// Constructs a new PeerStorage, with indexes populated from the given datastore
-    pub fn new_indexed(database: DS) -> Result<PeerStorage<DS>, PeerManagerError> {
+    /// Constructs a new IndexedPeerStorage, with indexes populated from the given datastore
+    pub fn new_indexed(database: DS) -> Result<IndexedPeerStorage<DS>, PeerManagerError> {
         // mutable_key_type: CommsPublicKey uses interior mutability to lazily compress the key, but is otherwise
         // immutable so the Hashmap order can never change.
         #[allow(clippy::mutable_key_type)]
@@ -82,7 +82,7 @@ where DS: KeyValueStore<PeerId, Peer>
             total_entries,
         );
 
-        Ok(PeerStorage {
+        Ok(IndexedPeerStorage {
             peer_db: database,
             public_key_index,
             node_id_index,
@@ -510,7 +510,7 @@ where DS: KeyValueStore<PeerId, Peer>
 }
 
 #[allow(clippy::from_over_into)]
-impl Into<CommsDatabase> for PeerStorage<CommsDatabase> {
+impl Into<CommsDatabase> for IndexedPeerStorage<CommsDatabase> {
     fn into(self) -> CommsDatabase {
         self.peer_db
     }
@@ -590,7 +590,7 @@ mod test {
         // Create new datastore with a peer database
         let mut db = Some(HashmapDatabase::new());
         {
-            let mut peer_storage = PeerStorage::new_indexed(db.take().unwrap()).unwrap();
+            let mut peer_storage = IndexedPeerStorage::new_indexed(db.take().unwrap()).unwrap();
 
             // Test adding and searching for peers
             assert!(peer_storage.add_peer(peer1.clone()).is_ok());
@@ -604,7 +604,7 @@ mod test {
             db = Some(peer_storage.peer_db);
         }
         // Restore from existing database
-        let peer_storage = PeerStorage::new_indexed(db.take().unwrap()).unwrap();
+        let peer_storage = IndexedPeerStorage::new_indexed(db.take().unwrap()).unwrap();
 
         assert_eq!(peer_storage.peer_db.size().unwrap(), 3);
         assert!(peer_storage.find_by_public_key(&peer1.public_key).is_ok());
@@ -615,7 +615,7 @@ mod test {
     #[allow(clippy::too_many_lines)]
     #[test]
     fn test_add_delete_find_peer() {
-        let mut peer_storage = PeerStorage::new_indexed(HashmapDatabase::new()).unwrap();
+        let mut peer_storage = IndexedPeerStorage::new_indexed(HashmapDatabase::new()).unwrap();
 
         // Create Peers
         let mut rng = rand::rngs::OsRng;
@@ -802,7 +802,7 @@ mod test {
 
     #[test]
     fn test_in_network_region() {
-        let mut peer_storage = PeerStorage::new_indexed(HashmapDatabase::new()).unwrap();
+        let mut peer_storage = IndexedPeerStorage::new_indexed(HashmapDatabase::new()).unwrap();
 
         let mut nodes = repeat_with(|| create_test_peer(PeerFeatures::COMMUNICATION_NODE, false))
             .take(5)
@@ -843,7 +843,7 @@ mod test {
 
     #[test]
     fn discovery_syncing_returns_correct_peers() {
-        let mut peer_storage = PeerStorage::new_indexed(HashmapDatabase::new()).unwrap();
+        let mut peer_storage = IndexedPeerStorage::new_indexed(HashmapDatabase::new()).unwrap();
         #[allow(clippy::cast_possible_wrap)] // Won't wrap around, numbers are static
         let a_week_ago = Utc::now().timestamp() - (PEER_ACTIVE_WITHIN_DURATION + 60) as i64; // A week ago + a minute
 
@@ -0,0 +1,149 @@
+//  Copyright 2023, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Pluggable persistence backends for the [PeerManager](crate::peer_manager::PeerManager).
+//!
+//! The peer manager keeps an in-memory index of all peers and writes every change through to a [PeerStorage]
+//! backend. LMDB is used by default, but an [InMemoryPeerStorage] or (with the `sqlite` feature) a
+//! [SqlitePeerStorage] can be used instead, e.g. by embedded or mobile applications that would rather not open an LMDB
+//! environment or that want to keep their peers in an existing sqlite database.
+
+use tari_storage::{HashmapDatabase, IterationResult, KeyValStoreError, KeyValueStore};
+
+use crate::peer_manager::{wrapper::KeyValueWrapper, Peer, PeerId};
+
+#[cfg(feature = "sqlite")]
+mod sqlite;
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqlitePeerStorage;
+
+/// A peer storage backend that keeps peers in memory only. Peers are lost when the backend is dropped.
+pub type InMemoryPeerStorage = HashmapDatabase<PeerId, Peer>;
+
+/// The persistence backend used by the peer manager.
+///
+/// Implementations must be thread-safe. The peer manager serialises access to the backend and caches peers in memory,
+/// so implementations do not need to be particularly fast to read from.
+pub trait PeerStorage: Send + Sync {
+    /// Inserts or replaces the peer stored under `peer_id`.
+    fn insert(&self, peer_id: PeerId, peer: Peer) -> Result<(), KeyValStoreError>;
+
+    /// Returns the peer stored under `peer_id`, if any.
+    fn get(&self, peer_id: &PeerId) -> Result<Option<Peer>, KeyValStoreError>;
+
+    /// Returns the peers stored under the given `peer_ids`. Ids that are not found are skipped.
+    fn get_many(&self, peer_ids: &[PeerId]) -> Result<Vec<Peer>, KeyValStoreError>;
+
+    /// Returns the number of stored peers.
+    fn size(&self) -> Result<usize, KeyValStoreError>;
+
+    /// Returns all stored peers along with their ids.
+    fn all(&self) -> Result<Vec<(PeerId, Peer)>, KeyValStoreError>;
+
+    /// Returns true if a peer is stored under `peer_id`, otherwise false.
+    fn exists(&self, peer_id: &PeerId) -> Result<bool, KeyValStoreError>;
+
+    /// Removes the peer stored under `peer_id`.
+    fn delete(&self, peer_id: &PeerId) -> Result<(), KeyValStoreError>;
+}
+
+macro_rules! impl_peer_storage_for_key_value_store {
+    ($ty:ty $(, $param:ident)?) => {
+        impl$(<$param: KeyValueStore<PeerId, Peer> + Send + Sync>)? PeerStorage for $ty {
+            fn insert(&self, peer_id: PeerId, peer: Peer) -> Result<(), KeyValStoreError> {
+                KeyValueStore::insert(self, peer_id, peer)
+            }
+
+            fn get(&self, peer_id: &PeerId) -> Result<Option<Peer>, KeyValStoreError> {
+                KeyValueStore::get(self, peer_id)
+            }
+
+            fn get_many(&self, peer_ids: &[PeerId]) -> Result<Vec<Peer>, KeyValStoreError> {
+                KeyValueStore::get_many(self, peer_ids)
+            }
+
+            fn size(&self) -> Result<usize, KeyValStoreError> {
+                KeyValueStore::size(self)
+            }
+
+            fn all(&self) -> Result<Vec<(PeerId, Peer)>, KeyValStoreError> {
+                let mut peers = Vec::new();
+                self.for_each_ok(|pair| {
+                    peers.push(pair);
+                    IterationResult::Continue
+                })?;
+                Ok(peers)
+            }
+
+            fn exists(&self, peer_id: &PeerId) -> Result<bool, KeyValStoreError> {
+                KeyValueStore::exists(self, peer_id)
+            }
+
+            fn delete(&self, peer_id: &PeerId) -> Result<(), KeyValStoreError> {
+                KeyValueStore::delete(self, peer_id)
+            }
+        }
+    };
+}
+
+impl_peer_storage_for_key_value_store!(InMemoryPeerStorage);
+impl_peer_storage_for_key_value_store!(KeyValueWrapper<T>, T);
+
+/// Allows a boxed backend to be used wherever a `KeyValueStore` is expected (e.g. `CachedStore`).
+impl KeyValueStore<PeerId, Peer> for Box<dyn PeerStorage> {
+    fn insert(&self, key: PeerId, value: Peer) -> Result<(), KeyValStoreError> {
+        PeerStorage::insert(&**self, key, value)
+    }
+
+    fn get(&self, key: &PeerId) -> Result<Option<Peer>, KeyValStoreError> {
+        PeerStorage::get(&**self, key)
+    }
+
+    fn get_many(&self, keys: &[PeerId]) -> Result<Vec<Peer>, KeyValStoreError> {
+        PeerStorage::get_many(&**self, keys)
+    }
+
+    fn size(&self) -> Result<usize, KeyValStoreError> {
+        PeerStorage::size(&**self)
+    }
+
+    fn for_each<F>(&self, mut f: F) -> Result<(), KeyValStoreError>
+    where
+        Self: Sized,
+        F: FnMut(Result<(PeerId, Peer), KeyValStoreError>) -> IterationResult,
+    {
+        for pair in PeerStorage::all(&**self)? {
+            if let IterationResult::Break = f(Ok(pair)) {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    fn exists(&self, key: &PeerId) -> Result<bool, KeyValStoreError> {
+        PeerStorage::exists(&**self, key)
+    }
+
+    fn delete(&self, key: &PeerId) -> Result<(), KeyValStoreError> {
+        PeerStorage::delete(&**self, key)
+    }
+}
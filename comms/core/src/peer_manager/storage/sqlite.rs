@@ -0,0 +1,209 @@
+//  Copyright 2023, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::convert::TryFrom;
+
+use diesel::{
+    dsl::exists,
+    prelude::*,
+    r2d2::{ConnectionManager, PooledConnection},
+    sql_query,
+};
+use tari_common_sqlite::{error::SqliteStorageError, sqlite_connection_pool::PooledDbConnection};
+use tari_storage::KeyValStoreError;
+
+use crate::peer_manager::{storage::PeerStorage, Peer, PeerId};
+
+diesel::table! {
+    comms_peers (peer_id) {
+        peer_id -> BigInt,
+        peer -> Binary,
+    }
+}
+
+/// The peer table is created on demand so that the backend can live alongside other tables (e.g. in a wallet
+/// database) without requiring a migration in that database.
+const CREATE_PEERS_TABLE: &str =
+    "CREATE TABLE IF NOT EXISTS comms_peers (peer_id BIGINT PRIMARY KEY NOT NULL, peer BLOB NOT NULL)";
+
+/// A peer storage backend that stores peers in a sqlite database.
+#[derive(Clone)]
+pub struct SqlitePeerStorage<TConn> {
+    connection: TConn,
+}
+
+impl<TConn> SqlitePeerStorage<TConn>
+where TConn: PooledDbConnection<Error = SqliteStorageError>
+{
+    /// Creates a new backend using the given connection, creating the `comms_peers` table if it does not exist.
+    pub fn new(connection: TConn) -> Result<Self, KeyValStoreError> {
+        let mut conn = connection.get_pooled_connection().map_err(to_database_error)?;
+        sql_query(CREATE_PEERS_TABLE)
+            .execute(&mut conn)
+            .map_err(to_database_error)?;
+        Ok(Self { connection })
+    }
+
+    fn connection(&self) -> Result<PooledConnection<ConnectionManager<SqliteConnection>>, KeyValStoreError> {
+        self.connection.get_pooled_connection().map_err(to_database_error)
+    }
+}
+
+impl<TConn> PeerStorage for SqlitePeerStorage<TConn>
+where TConn: PooledDbConnection<Error = SqliteStorageError>
+{
+    fn insert(&self, peer_id: PeerId, peer: Peer) -> Result<(), KeyValStoreError> {
+        let peer = bincode::serialize(&peer).map_err(|e| KeyValStoreError::SerializationError(e.to_string()))?;
+        diesel::replace_into(comms_peers::table)
+            .values((comms_peers::peer_id.eq(to_sql_id(peer_id)), comms_peers::peer.eq(peer)))
+            .execute(&mut *self.connection()?)
+            .map_err(to_database_error)?;
+        Ok(())
+    }
+
+    fn get(&self, peer_id: &PeerId) -> Result<Option<Peer>, KeyValStoreError> {
+        comms_peers::table
+            .find(to_sql_id(*peer_id))
+            .select(comms_peers::peer)
+            .first::<Vec<u8>>(&mut *self.connection()?)
+            .optional()
+            .map_err(to_database_error)?
+            .map(|peer| deserialize_peer(&peer))
+            .transpose()
+    }
+
+    fn get_many(&self, peer_ids: &[PeerId]) -> Result<Vec<Peer>, KeyValStoreError> {
+        comms_peers::table
+            .filter(comms_peers::peer_id.eq_any(peer_ids.iter().copied().map(to_sql_id)))
+            .select(comms_peers::peer)
+            .load::<Vec<u8>>(&mut *self.connection()?)
+            .map_err(to_database_error)?
+            .into_iter()
+            .map(|peer| deserialize_peer(&peer))
+            .collect()
+    }
+
+    fn size(&self) -> Result<usize, KeyValStoreError> {
+        let count = comms_peers::table
+            .count()
+            .get_result::<i64>(&mut *self.connection()?)
+            .map_err(to_database_error)?;
+        usize::try_from(count).map_err(|e| KeyValStoreError::DatabaseError(e.to_string()))
+    }
+
+    fn all(&self) -> Result<Vec<(PeerId, Peer)>, KeyValStoreError> {
+        comms_peers::table
+            .load::<(i64, Vec<u8>)>(&mut *self.connection()?)
+            .map_err(to_database_error)?
+            .into_iter()
+            .map(|(peer_id, peer)| Ok((from_sql_id(peer_id), deserialize_peer(&peer)?)))
+            .collect()
+    }
+
+    fn exists(&self, peer_id: &PeerId) -> Result<bool, KeyValStoreError> {
+        diesel::select(exists(comms_peers::table.find(to_sql_id(*peer_id))))
+            .get_result(&mut *self.connection()?)
+            .map_err(to_database_error)
+    }
+
+    fn delete(&self, peer_id: &PeerId) -> Result<(), KeyValStoreError> {
+        diesel::delete(comms_peers::table.find(to_sql_id(*peer_id)))
+            .execute(&mut *self.connection()?)
+            .map_err(to_database_error)?;
+        Ok(())
+    }
+}
+
+/// Peer ids span the full u64 range, so they are stored bit-for-bit in sqlite's signed 64-bit integer.
+fn to_sql_id(peer_id: PeerId) -> i64 {
+    i64::from_le_bytes(peer_id.to_le_bytes())
+}
+
+fn from_sql_id(peer_id: i64) -> PeerId {
+    PeerId::from_le_bytes(peer_id.to_le_bytes())
+}
+
+fn deserialize_peer(bytes: &[u8]) -> Result<Peer, KeyValStoreError> {
+    bincode::deserialize(bytes).map_err(|e| KeyValStoreError::DeserializationError(e.to_string()))
+}
+
+fn to_database_error<E: ToString>(err: E) -> KeyValStoreError {
+    KeyValStoreError::DatabaseError(err.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use tari_common_sqlite::connection::DbConnection;
+    use tari_crypto::keys::PublicKey;
+    use tari_test_utils::random;
+
+    use super::*;
+    use crate::{
+        net_address::{MultiaddressesWithStats, PeerAddressSource},
+        peer_manager::{NodeId, PeerFeatures, PeerFlags},
+        types::CommsPublicKey,
+    };
+
+    fn create_test_peer() -> Peer {
+        let (_sk, pk) = CommsPublicKey::random_keypair(&mut rand::rngs::OsRng);
+        let node_id = NodeId::from_key(&pk);
+        let addresses = MultiaddressesWithStats::from_addresses_with_source(
+            vec!["/ip4/1.2.3.4/tcp/8000".parse().unwrap()],
+            &PeerAddressSource::Config,
+        );
+        Peer::new(
+            pk,
+            node_id,
+            addresses,
+            PeerFlags::default(),
+            PeerFeatures::COMMUNICATION_NODE,
+            Default::default(),
+            Default::default(),
+        )
+    }
+
+    #[test]
+    fn it_stores_and_retrieves_peers() {
+        let connection = DbConnection::connect_memory(random::string(8)).unwrap();
+        let storage = SqlitePeerStorage::new(connection.clone()).unwrap();
+        let peer1 = create_test_peer();
+        let peer2 = create_test_peer();
+        storage.insert(1, peer1.clone()).unwrap();
+        storage.insert(u64::MAX - 1, peer2.clone()).unwrap();
+
+        assert_eq!(storage.size().unwrap(), 2);
+        assert!(storage.exists(&(u64::MAX - 1)).unwrap());
+        assert_eq!(storage.get(&1).unwrap().unwrap().node_id, peer1.node_id);
+        assert_eq!(storage.get_many(&[1, 2, u64::MAX - 1]).unwrap().len(), 2);
+
+        // Re-opening the database must not drop existing peers
+        let storage = SqlitePeerStorage::new(connection).unwrap();
+        let mut all = storage.all().unwrap();
+        all.sort_by_key(|(id, _)| *id);
+        assert_eq!(all[1].0, u64::MAX - 1);
+        assert_eq!(all[1].1.node_id, peer2.node_id);
+
+        storage.delete(&1).unwrap();
+        assert!(storage.get(&1).unwrap().is_none());
+        assert_eq!(storage.size().unwrap(), 1);
+    }
+}
@@ -128,3 +128,16 @@ pub fn inbound_response_bytes(peer: &NodeId, protocol: &ProtocolId) -> Histogram
 
     METER.with_label_values(&[peer.to_string().as_str(), String::from_utf8_lossy(protocol).as_ref()])
 }
+
+pub fn decompressed_responses(peer: &NodeId, protocol: &ProtocolId) -> IntCounter {
+    static METER: Lazy<IntCounterVec> = Lazy::new(|| {
+        tari_metrics::register_int_counter_vec(
+            "comms::rpc::client::decompressed_response_count",
+            "The number of compressed responses received per peer per protocol",
+            &["peer_id", "protocol"],
+        )
+        .unwrap()
+    });
+
+    METER.with_label_values(&[peer.to_string().as_str(), String::from_utf8_lossy(protocol).as_ref()])
+}
@@ -71,6 +71,7 @@ use crate::{
             Handshake,
             NamedProtocolService,
             Response,
+            RpcCompression,
             RpcError,
             RpcServerError,
             RpcStatus,
@@ -255,6 +256,13 @@ impl<TClient> RpcClientBuilder<TClient> {
         self
     }
 
+    /// Do not advertise support for compressed responses in the RPC handshake. The server will then send all
+    /// responses uncompressed.
+    pub fn disable_compression(mut self) -> Self {
+        self.config.compression_enabled = false;
+        self
+    }
+
     /// Set the protocol ID associated with this client. This is used for logging purposes only.
     pub fn with_protocol_id(mut self, protocol_id: ProtocolId) -> Self {
         self.protocol_id = Some(protocol_id);
@@ -293,6 +301,7 @@ pub struct RpcClientConfig {
     pub deadline: Option<Duration>,
     pub deadline_grace_period: Duration,
     pub handshake_timeout: Duration,
+    pub compression_enabled: bool,
 }
 
 impl RpcClientConfig {
@@ -313,6 +322,7 @@ impl Default for RpcClientConfig {
             deadline: Some(Duration::from_secs(120)),
             deadline_grace_period: Duration::from_secs(60),
             handshake_timeout: Duration::from_secs(90),
+            compression_enabled: true,
         }
     }
 }
@@ -394,6 +404,7 @@ impl Service<BaseRequest<Bytes>> for ClientConnector {
 
 struct RpcClientWorker<TSubstream> {
     config: RpcClientConfig,
+    compression: RpcCompression,
     node_id: NodeId,
     request_rx: mpsc::Receiver<ClientRequest>,
     last_request_latency_tx: watch::Sender<Option<Duration>>,
@@ -421,6 +432,7 @@ where TSubstream: AsyncRead + AsyncWrite + Unpin + Send + StreamId
     ) -> Self {
         Self {
             config,
+            compression: RpcCompression::None,
             node_id,
             request_rx,
             framed,
@@ -448,15 +460,24 @@ where TSubstream: AsyncRead + AsyncWrite + Unpin + Send + StreamId
             self.protocol_name()
         );
         let start = Instant::now();
-        let mut handshake = Handshake::new(&mut self.framed).with_timeout(self.config.handshake_timeout());
+        let compression = if self.config.compression_enabled {
+            RpcCompression::SUPPORTED[0]
+        } else {
+            RpcCompression::None
+        };
+        let mut handshake = Handshake::new(&mut self.framed)
+            .with_timeout(self.config.handshake_timeout())
+            .with_compression(compression);
         match handshake.perform_client_handshake().await {
-            Ok(_) => {
+            Ok(session) => {
+                self.compression = session.compression;
                 let latency = start.elapsed();
                 debug!(
                     target: LOG_TARGET,
-                    "(stream={}) RPC Session ({}) negotiation completed. Latency: {:.0?}",
+                    "(stream={}) RPC Session ({}) negotiation completed. Compression: {}, Latency: {:.0?}",
                     self.stream_id(),
                     self.protocol_name(),
+                    self.compression,
                     latency
                 );
                 let _ = self.last_request_latency_tx.send(Some(latency));
@@ -551,7 +572,7 @@ where TSubstream: AsyncRead + AsyncWrite + Unpin + Send + StreamId
             self.protocol_name(),
             start.elapsed()
         );
-        let mut reader = RpcResponseReader::new(&mut self.framed, self.config, 0);
+        let mut reader = RpcResponseReader::new(&mut self.framed, self.config, self.compression, 0);
         let resp = match reader.read_ack().await {
             Ok(resp) => resp,
             Err(RpcError::ReplyTimeout) => {
@@ -574,14 +595,7 @@ where TSubstream: AsyncRead + AsyncWrite + Unpin + Send + StreamId
             return Err(status.into());
         }
 
-        let resp_flags =
-            RpcMessageFlags::from_bits(u8::try_from(resp.flags).map_err(|_| {
-                RpcStatus::protocol_error(&format!("invalid message flag: must be less than {}", u8::MAX))
-            })?)
-            .ok_or(RpcStatus::protocol_error(&format!(
-                "invalid message flag, does not match any flags ({})",
-                resp.flags
-            )))?;
+        let resp_flags = resp.flags().map_err(|err| RpcStatus::protocol_error(&err))?;
         if !resp_flags.contains(RpcMessageFlags::ACK) {
             warn!(
                 target: LOG_TARGET,
@@ -807,7 +821,7 @@ where TSubstream: AsyncRead + AsyncWrite + Unpin + Send + StreamId
         let stream_id = self.stream_id();
         let protocol_name = self.protocol_name().to_string();
 
-        let mut reader = RpcResponseReader::new(&mut self.framed, self.config, request_id);
+        let mut reader = RpcResponseReader::new(&mut self.framed, self.config, self.compression, request_id);
         let mut num_ignored = 0;
         let resp = loop {
             match reader.read_response().await {
@@ -821,6 +835,9 @@ where TSubstream: AsyncRead + AsyncWrite + Unpin + Send + StreamId
                     );
                     metrics::inbound_response_bytes(&self.node_id, &self.protocol_id)
                         .observe(reader.bytes_read() as f64);
+                    if reader.was_decompressed() {
+                        metrics::decompressed_responses(&self.node_id, &self.protocol_id).inc();
+                    }
                     let time_to_first_msg = reader.time_to_first_msg();
                     break (resp, time_to_first_msg);
                 },
@@ -889,21 +906,30 @@ pub enum ClientRequest {
 struct RpcResponseReader<'a, TSubstream> {
     framed: &'a mut CanonicalFraming<TSubstream>,
     config: RpcClientConfig,
+    compression: RpcCompression,
     request_id: u16,
     bytes_read: usize,
     time_to_first_msg: Option<Duration>,
+    was_decompressed: bool,
 }
 
 impl<'a, TSubstream> RpcResponseReader<'a, TSubstream>
 where TSubstream: AsyncRead + AsyncWrite + Unpin
 {
-    pub fn new(framed: &'a mut CanonicalFraming<TSubstream>, config: RpcClientConfig, request_id: u16) -> Self {
+    pub fn new(
+        framed: &'a mut CanonicalFraming<TSubstream>,
+        config: RpcClientConfig,
+        compression: RpcCompression,
+        request_id: u16,
+    ) -> Self {
         Self {
             framed,
             config,
+            compression,
             request_id,
             bytes_read: 0,
             time_to_first_msg: None,
+            was_decompressed: false,
         }
     }
 
@@ -915,20 +941,18 @@ where TSubstream: AsyncRead + AsyncWrite + Unpin
         self.time_to_first_msg
     }
 
+    pub fn was_decompressed(&self) -> bool {
+        self.was_decompressed
+    }
+
     pub async fn read_response(&mut self) -> Result<proto::rpc::RpcResponse, RpcError> {
         let timer = Instant::now();
         let mut resp = self.next().await?;
         self.time_to_first_msg = Some(timer.elapsed());
         self.check_response(&resp)?;
         let mut chunk_count = 1;
-        let mut last_chunk_flags =
-            RpcMessageFlags::from_bits(u8::try_from(resp.flags).map_err(|_| {
-                RpcStatus::protocol_error(&format!("invalid message flag: must be less than {}", u8::MAX))
-            })?)
-            .ok_or(RpcStatus::protocol_error(&format!(
-                "invalid message flag, does not match any flags ({})",
-                resp.flags
-            )))?;
+        let mut last_chunk_flags = resp.flags().map_err(|err| RpcStatus::protocol_error(&err))?;
+        let first_chunk_flags = last_chunk_flags;
         let mut last_chunk_size = resp.payload.len();
         self.bytes_read += last_chunk_size;
         loop {
@@ -941,6 +965,9 @@ where TSubstream: AsyncRead + AsyncWrite + Unpin
                 resp.payload.len()
             );
             if !last_chunk_flags.is_more() {
+                if first_chunk_flags.is_compressed() {
+                    self.decompress(&mut resp, first_chunk_flags)?;
+                }
                 return Ok(resp);
             }
 
@@ -951,13 +978,7 @@ where TSubstream: AsyncRead + AsyncWrite + Unpin
             }
 
             let msg = self.next().await?;
-            last_chunk_flags = RpcMessageFlags::from_bits(u8::try_from(msg.flags).map_err(|_| {
-                RpcStatus::protocol_error(&format!("invalid message flag: must be less than {}", u8::MAX))
-            })?)
-            .ok_or(RpcStatus::protocol_error(&format!(
-                "invalid message flag, does not match any flags ({})",
-                resp.flags
-            )))?;
+            last_chunk_flags = msg.flags().map_err(|err| RpcStatus::protocol_error(&err))?;
             last_chunk_size = msg.payload.len();
             self.bytes_read += last_chunk_size;
             self.check_response(&resp)?;
@@ -966,6 +987,30 @@ where TSubstream: AsyncRead + AsyncWrite + Unpin
         }
     }
 
+    fn decompress(&mut self, resp: &mut proto::rpc::RpcResponse, flags: RpcMessageFlags) -> Result<(), RpcError> {
+        if self.compression.is_none() {
+            return Err(RpcStatus::protocol_error(
+                &"received a compressed response but compression was not negotiated",
+            )
+            .into());
+        }
+        let payload = self
+            .compression
+            .decompress(&resp.payload, rpc::max_response_payload_size())
+            .map_err(|err| RpcError::DecompressionFailed(err.to_string()))?;
+        trace!(
+            target: LOG_TARGET,
+            "Decompressed response ({}, {} bytes -> {} bytes)",
+            self.compression,
+            resp.payload.len(),
+            payload.len()
+        );
+        resp.payload = payload;
+        resp.flags = u32::from((flags - RpcMessageFlags::COMPRESSED).bits());
+        self.was_decompressed = true;
+        Ok(())
+    }
+
     pub async fn read_ack(&mut self) -> Result<proto::rpc::RpcResponse, RpcError> {
         let resp = self.next().await?;
         Ok(resp)
@@ -975,14 +1020,7 @@ where TSubstream: AsyncRead + AsyncWrite + Unpin
         let resp_id = u16::try_from(resp.request_id)
             .map_err(|_| RpcStatus::protocol_error(&format!("invalid request_id: must be less than {}", u16::MAX)))?;
 
-        let flags =
-            RpcMessageFlags::from_bits(u8::try_from(resp.flags).map_err(|_| {
-                RpcStatus::protocol_error(&format!("invalid message flag: must be less than {}", u8::MAX))
-            })?)
-            .ok_or(RpcStatus::protocol_error(&format!(
-                "invalid message flag, does not match any flags ({})",
-                resp.flags
-            )))?;
+        let flags = resp.flags().map_err(|err| RpcStatus::protocol_error(&err))?;
         if flags.contains(RpcMessageFlags::ACK) {
             return Err(RpcError::UnexpectedAckResponse);
         }
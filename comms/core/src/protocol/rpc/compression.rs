@@ -0,0 +1,212 @@
+//  Copyright 2023, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{fmt, io};
+
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    proto,
+    protocol::{rpc, rpc::message::RpcMessageFlags},
+};
+
+/// Responses with a payload smaller than this are never compressed, the overhead is not worth it.
+pub const DEFAULT_COMPRESSION_THRESHOLD: usize = 4 * 1024;
+
+/// Compression algorithms that can be negotiated for an RPC session. The server decides which algorithm (if any) to
+/// use from the algorithms advertised by the client in the RPC handshake.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RpcCompression {
+    #[default]
+    None,
+    Lz4,
+    Zstd,
+}
+
+impl RpcCompression {
+    /// All compression algorithms supported by this node in order of preference.
+    pub const SUPPORTED: &'static [RpcCompression] = &[RpcCompression::Lz4, RpcCompression::Zstd];
+    const ZSTD_LEVEL: i32 = 3;
+
+    pub fn is_none(self) -> bool {
+        matches!(self, RpcCompression::None)
+    }
+
+    pub(super) fn as_i32(self) -> i32 {
+        let compression = match self {
+            RpcCompression::None => proto::rpc::RpcCompression::None,
+            RpcCompression::Lz4 => proto::rpc::RpcCompression::Lz4,
+            RpcCompression::Zstd => proto::rpc::RpcCompression::Zstd,
+        };
+        compression as i32
+    }
+
+    /// Returns the compression for the given protobuf value or None if it is not recognised.
+    pub(super) fn from_i32(value: i32) -> Option<Self> {
+        match proto::rpc::RpcCompression::from_i32(value)? {
+            proto::rpc::RpcCompression::None => Some(RpcCompression::None),
+            proto::rpc::RpcCompression::Lz4 => Some(RpcCompression::Lz4),
+            proto::rpc::RpcCompression::Zstd => Some(RpcCompression::Zstd),
+        }
+    }
+
+    /// Server-side negotiation: use the `preferred` compression if the client supports it, otherwise the first client
+    /// supported algorithm that this node also supports. If `preferred` is `None`, compression is disabled.
+    pub(super) fn negotiate(preferred: RpcCompression, client_supported: &[i32]) -> RpcCompression {
+        if preferred.is_none() {
+            return RpcCompression::None;
+        }
+        let client_supported = client_supported
+            .iter()
+            .filter_map(|c| Self::from_i32(*c))
+            .filter(|c| !c.is_none())
+            .collect::<Vec<_>>();
+        if client_supported.contains(&preferred) {
+            return preferred;
+        }
+        client_supported
+            .into_iter()
+            .find(|c| Self::SUPPORTED.contains(c))
+            .unwrap_or(RpcCompression::None)
+    }
+
+    pub(super) fn compress(self, payload: &[u8]) -> io::Result<Vec<u8>> {
+        match self {
+            RpcCompression::None => Ok(payload.to_vec()),
+            RpcCompression::Lz4 => Ok(lz4_flex::compress_prepend_size(payload)),
+            RpcCompression::Zstd => zstd::bulk::compress(payload, Self::ZSTD_LEVEL),
+        }
+    }
+
+    /// Decompresses the payload. An error is returned if the decompressed payload would exceed `max_size` bytes.
+    pub(super) fn decompress(self, payload: &[u8], max_size: usize) -> io::Result<Vec<u8>> {
+        match self {
+            RpcCompression::None => Ok(payload.to_vec()),
+            RpcCompression::Lz4 => {
+                let (size, _) = lz4_flex::block::uncompressed_size(payload)
+                    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+                if size > max_size {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("decompressed size {} exceeds maximum of {} bytes", size, max_size),
+                    ));
+                }
+                lz4_flex::decompress_size_prepended(payload)
+                    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+            },
+            RpcCompression::Zstd => zstd::bulk::decompress(payload, max_size),
+        }
+    }
+}
+
+impl fmt::Display for RpcCompression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RpcCompression::None => write!(f, "none"),
+            RpcCompression::Lz4 => write!(f, "lz4"),
+            RpcCompression::Zstd => write!(f, "zstd"),
+        }
+    }
+}
+
+/// Compresses the payload if it is at least `threshold` bytes and compression actually reduces its size. Returns the
+/// flags and payload to send along with the number of bytes saved. Payloads that exceed the maximum response size are
+/// never compressed, so that the client can always decompress within that limit.
+pub(super) fn compress_payload(
+    compression: RpcCompression,
+    threshold: usize,
+    flags: RpcMessageFlags,
+    payload: Bytes,
+) -> (RpcMessageFlags, Bytes, usize) {
+    if compression.is_none() || payload.len() < threshold || payload.len() > rpc::max_response_payload_size() {
+        return (flags, payload, 0);
+    }
+    match compression.compress(&payload) {
+        Ok(compressed) if compressed.len() < payload.len() => {
+            let saved = payload.len() - compressed.len();
+            (flags | RpcMessageFlags::COMPRESSED, compressed.into(), saved)
+        },
+        // Not worth it (or failed), send the payload as is
+        _ => (flags, payload, 0),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::iter;
+
+    use super::*;
+
+    #[test]
+    fn it_negotiates_compression() {
+        let lz4 = RpcCompression::Lz4.as_i32();
+        let zstd = RpcCompression::Zstd.as_i32();
+        assert_eq!(
+            RpcCompression::negotiate(RpcCompression::Zstd, &[lz4, zstd]),
+            RpcCompression::Zstd
+        );
+        assert_eq!(
+            RpcCompression::negotiate(RpcCompression::Zstd, &[lz4]),
+            RpcCompression::Lz4
+        );
+        assert_eq!(
+            RpcCompression::negotiate(RpcCompression::None, &[lz4]),
+            RpcCompression::None
+        );
+        // Older clients do not advertise any compression
+        assert_eq!(
+            RpcCompression::negotiate(RpcCompression::Lz4, &[]),
+            RpcCompression::None
+        );
+        assert_eq!(
+            RpcCompression::negotiate(RpcCompression::Lz4, &[123]),
+            RpcCompression::None
+        );
+    }
+
+    #[test]
+    fn it_compresses_and_decompresses() {
+        let payload = iter::repeat(b"tari").take(2048).flatten().copied().collect::<Bytes>();
+        for compression in RpcCompression::SUPPORTED {
+            let (flags, compressed, saved) =
+                compress_payload(*compression, 1024, RpcMessageFlags::FIN, payload.clone());
+            assert!(flags.is_compressed());
+            assert!(flags.is_fin());
+            assert_eq!(saved, payload.len() - compressed.len());
+            let decompressed = compression.decompress(&compressed, payload.len()).unwrap();
+            assert_eq!(decompressed, payload);
+            compression.decompress(&compressed, payload.len() - 1).unwrap_err();
+        }
+    }
+
+    #[test]
+    fn it_does_not_compress_small_payloads() {
+        let payload = Bytes::from_static(&[0u8; 100]);
+        let (flags, compressed, saved) =
+            compress_payload(RpcCompression::Lz4, 1024, RpcMessageFlags::empty(), payload.clone());
+        assert!(!flags.is_compressed());
+        assert_eq!(compressed, payload);
+        assert_eq!(saved, 0);
+    }
+}
@@ -72,6 +72,8 @@ pub enum RpcError {
     RemotePeerExceededMaxChunkCount { expected: usize },
     #[error("Request body was too large. Expected <= {expected} but got {got}")]
     MaxRequestSizeExceeded { got: usize, expected: usize },
+    #[error("Failed to decompress response payload: {0}")]
+    DecompressionFailed(String),
     #[error(transparent)]
     UnknownError(#[from] anyhow::Error),
 }
@@ -91,6 +93,8 @@ impl RpcError {
             RpcError::HandshakeError(RpcHandshakeError::ServerClosedRequest) |
             RpcError::HandshakeError(RpcHandshakeError::Rejected(_)) |
             RpcError::HandshakeError(RpcHandshakeError::TimedOut) |
+            RpcError::HandshakeError(RpcHandshakeError::UnsupportedCompression(_)) |
+            RpcError::DecompressionFailed(_) |
             RpcError::ServerClosedRequest |
             RpcError::UnexpectedAckResponse |
             RpcError::ResponseIdDidNotMatchRequest { .. } => true,
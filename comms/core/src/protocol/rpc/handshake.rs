@@ -31,7 +31,12 @@ use tokio::{
 };
 use tracing::{debug, error, span, warn, Instrument, Level};
 
-use crate::{framing::CanonicalFraming, message::MessageExt, proto, protocol::rpc::error::HandshakeRejectReason};
+use crate::{
+    framing::CanonicalFraming,
+    message::MessageExt,
+    proto,
+    protocol::rpc::{error::HandshakeRejectReason, RpcCompression},
+};
 
 const LOG_TARGET: &str = "comms::rpc::handshake";
 
@@ -55,12 +60,22 @@ pub enum RpcHandshakeError {
     Rejected(#[from] HandshakeRejectReason),
     #[error("The client connection is closed")]
     ClientClosed,
+    #[error("The server selected an unsupported compression algorithm ({0})")]
+    UnsupportedCompression(i32),
+}
+
+/// The session parameters agreed upon in the handshake
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NegotiatedSession {
+    pub version: u32,
+    pub compression: RpcCompression,
 }
 
 /// Handshake protocol
 pub struct Handshake<'a, T> {
     framed: &'a mut CanonicalFraming<T>,
     timeout: Option<Duration>,
+    compression: RpcCompression,
 }
 
 impl<'a, T> Handshake<'a, T>
@@ -68,7 +83,11 @@ where T: AsyncRead + AsyncWrite + Unpin
 {
     /// Create a Handshake using the given framing and no timeout. To set a timeout, use `with_timeout`.
     pub fn new(framed: &'a mut CanonicalFraming<T>) -> Self {
-        Self { framed, timeout: None }
+        Self {
+            framed,
+            timeout: None,
+            compression: RpcCompression::None,
+        }
     }
 
     /// Set the length of time that a client/server should wait for the other side to respond before timing out.
@@ -77,8 +96,16 @@ where T: AsyncRead + AsyncWrite + Unpin
         self
     }
 
+    /// Set the response compression for the session. For the server, this is the preferred compression which is used
+    /// if the client supports it. For the client, any value other than `RpcCompression::None` advertises support for
+    /// all compression algorithms supported by this node. Defaults to `RpcCompression::None`.
+    pub fn with_compression(mut self, compression: RpcCompression) -> Self {
+        self.compression = compression;
+        self
+    }
+
     /// Server-side handshake protocol
-    pub async fn perform_server_handshake(&mut self) -> Result<u32, RpcHandshakeError> {
+    pub async fn perform_server_handshake(&mut self) -> Result<NegotiatedSession, RpcHandshakeError> {
         match self.recv_next_frame().await {
             Ok(Some(Ok(msg))) => {
                 let msg = proto::rpc::RpcSession::decode(&mut msg.freeze())?;
@@ -86,9 +113,14 @@ where T: AsyncRead + AsyncWrite + Unpin
                     .iter()
                     .find(|v| msg.supported_versions.contains(v));
                 if let Some(version) = version {
-                    debug!(target: LOG_TARGET, "Server accepted version: {}", version);
+                    let compression = RpcCompression::negotiate(self.compression, &msg.supported_compression);
+                    debug!(
+                        target: LOG_TARGET,
+                        "Server accepted version: {}, compression: {}", version, compression
+                    );
                     let reply = proto::rpc::RpcSessionReply {
                         session_result: Some(proto::rpc::rpc_session_reply::SessionResult::AcceptedVersion(*version)),
+                        compression: compression.as_i32(),
                         ..Default::default()
                     };
                     let span = span!(Level::INFO, "rpc::server::handshake::send_accept_version_reply");
@@ -96,7 +128,10 @@ where T: AsyncRead + AsyncWrite + Unpin
                         .send(reply.to_encoded_bytes().into())
                         .instrument(span)
                         .await?;
-                    return Ok(*version);
+                    return Ok(NegotiatedSession {
+                        version: *version,
+                        compression,
+                    });
                 }
 
                 let span = span!(Level::INFO, "rpc::server::handshake::send_rejection");
@@ -125,6 +160,7 @@ where T: AsyncRead + AsyncWrite + Unpin
         let reply = proto::rpc::RpcSessionReply {
             session_result: Some(proto::rpc::rpc_session_reply::SessionResult::Rejected(true)),
             reject_reason: reject_reason.as_i32(),
+            ..Default::default()
         };
         self.framed.send(reply.to_encoded_bytes().into()).await?;
         self.framed.close().await?;
@@ -132,9 +168,15 @@ where T: AsyncRead + AsyncWrite + Unpin
     }
 
     /// Client-side handshake protocol
-    pub async fn perform_client_handshake(&mut self) -> Result<(), RpcHandshakeError> {
+    pub async fn perform_client_handshake(&mut self) -> Result<NegotiatedSession, RpcHandshakeError> {
+        let supported_compression = if self.compression.is_none() {
+            vec![]
+        } else {
+            RpcCompression::SUPPORTED.iter().map(|c| c.as_i32()).collect()
+        };
         let msg = proto::rpc::RpcSession {
             supported_versions: SUPPORTED_RPC_VERSIONS.to_vec(),
+            supported_compression,
         };
         let payload = msg.to_encoded_bytes();
         debug!(target: LOG_TARGET, "Sending client handshake ({} bytes)", payload.len());
@@ -152,8 +194,15 @@ where T: AsyncRead + AsyncWrite + Unpin
             Ok(Some(Ok(msg))) => {
                 let msg = proto::rpc::RpcSessionReply::decode(&mut msg.freeze())?;
                 let version = msg.result()?;
-                debug!(target: LOG_TARGET, "Server accepted version {}", version);
-                Ok(())
+                let compression = msg.compression()?;
+                if !compression.is_none() && self.compression.is_none() {
+                    return Err(RpcHandshakeError::UnsupportedCompression(compression.as_i32()));
+                }
+                debug!(
+                    target: LOG_TARGET,
+                    "Server accepted version {}, compression: {}", version, compression
+                );
+                Ok(NegotiatedSession { version, compression })
             },
             Ok(Some(Err(err))) => {
                 error!(target: LOG_TARGET, "Error during handshake: {}", err);
@@ -24,6 +24,7 @@ use std::{convert::TryFrom, fmt, time::Duration};
 
 use bitflags::bitflags;
 use bytes::Bytes;
+use prost::Message;
 
 use super::RpcError;
 use crate::{
@@ -33,6 +34,9 @@ use crate::{
         body::{Body, IntoBody},
         context::RequestContext,
         error::HandshakeRejectReason,
+        RpcCompression,
+        RpcHandshakeError,
+        RpcStatus,
         RpcStatusCode,
     },
 };
@@ -204,6 +208,8 @@ bitflags! {
         const ACK = 0x02;
         /// Another chunk to be received
         const MORE = 0x04;
+        /// The payload is compressed using the compression negotiated for the session
+        const COMPRESSED = 0x08;
     }
 }
 impl RpcMessageFlags {
@@ -218,6 +224,10 @@ impl RpcMessageFlags {
     pub fn is_more(self) -> bool {
         self.contains(Self::MORE)
     }
+
+    pub fn is_compressed(self) -> bool {
+        self.contains(Self::COMPRESSED)
+    }
 }
 
 impl Default for RpcMessageFlags {
@@ -244,6 +254,14 @@ impl proto::rpc::RpcRequest {
     }
 }
 
+/// Decodes an RPC request frame received from a remote peer and validates its flags. Malformed frames result in an
+/// error and never panic.
+pub fn decode_request_frame(mut frame: &[u8]) -> Result<(proto::rpc::RpcRequest, RpcMessageFlags), RpcError> {
+    let request = proto::rpc::RpcRequest::decode(&mut frame)?;
+    let flags = request.flags().map_err(|err| RpcStatus::protocol_error(&err))?;
+    Ok((request, flags))
+}
+
 impl fmt::Display for proto::rpc::RpcRequest {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -299,11 +317,20 @@ impl proto::rpc::RpcResponse {
         ))
     }
 
+    /// Returns true if the FIN flag is set. Invalid flags are never considered FIN.
     pub fn is_fin(&self) -> bool {
-        u8::try_from(self.flags).unwrap() & RpcMessageFlags::FIN.bits() != 0
+        self.flags().map(RpcMessageFlags::is_fin).unwrap_or(false)
     }
 }
 
+/// Decodes an RPC response frame received from a remote peer and validates its flags. Malformed frames result in an
+/// error and never panic.
+pub fn decode_response_frame(mut frame: &[u8]) -> Result<(proto::rpc::RpcResponse, RpcMessageFlags), RpcError> {
+    let response = proto::rpc::RpcResponse::decode(&mut frame)?;
+    let flags = response.flags().map_err(|err| RpcStatus::protocol_error(&err))?;
+    Ok((response, flags))
+}
+
 impl fmt::Display for proto::rpc::RpcResponse {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -334,4 +361,49 @@ impl proto::rpc::RpcSessionReply {
             )),
         }
     }
+
+    /// Returns the compression selected by the server. Servers that do not support compression leave this unset,
+    /// which is interpreted as `RpcCompression::None`.
+    pub fn compression(&self) -> Result<RpcCompression, RpcHandshakeError> {
+        RpcCompression::from_i32(self.compression).ok_or(RpcHandshakeError::UnsupportedCompression(self.compression))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::message::MessageExt;
+
+    #[test]
+    fn it_does_not_panic_on_out_of_range_flags() {
+        let resp = proto::rpc::RpcResponse {
+            flags: u32::MAX,
+            ..Default::default()
+        };
+        assert!(!resp.is_fin());
+        resp.flags().unwrap_err();
+        decode_response_frame(&resp.to_encoded_bytes()).unwrap_err();
+
+        let req = proto::rpc::RpcRequest {
+            flags: 0x80,
+            ..Default::default()
+        };
+        decode_request_frame(&req.to_encoded_bytes()).unwrap_err();
+    }
+
+    #[test]
+    fn it_decodes_valid_frames() {
+        let resp = proto::rpc::RpcResponse {
+            request_id: 1,
+            flags: RpcMessageFlags::FIN.bits().into(),
+            payload: b"tari".to_vec(),
+            ..Default::default()
+        };
+        assert!(resp.is_fin());
+        let (decoded, flags) = decode_response_frame(&resp.to_encoded_bytes()).unwrap();
+        assert!(flags.is_fin());
+        assert_eq!(decoded, resp);
+
+        decode_request_frame(&[0xff, 0xff]).unwrap_err();
+    }
 }
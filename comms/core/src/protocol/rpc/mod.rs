@@ -77,13 +77,16 @@ pub use client::{
 mod either;
 
 mod message;
-pub use message::{Request, Response};
+pub use message::{decode_request_frame, decode_response_frame, Request, Response, RpcMessageFlags};
 
 mod error;
 pub use error::RpcError;
 
 mod handshake;
-pub use handshake::{Handshake, RpcHandshakeError};
+pub use handshake::{Handshake, NegotiatedSession, RpcHandshakeError};
+
+mod compression;
+pub use compression::{RpcCompression, DEFAULT_COMPRESSION_THRESHOLD};
 
 mod status;
 pub use status::{RpcStatus, RpcStatusCode, RpcStatusResultExt};
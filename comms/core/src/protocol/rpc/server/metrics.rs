@@ -26,7 +26,7 @@ use tari_metrics::{Histogram, HistogramVec, IntCounter, IntCounterVec, IntGauge,
 use crate::{
     peer_manager::NodeId,
     protocol::{
-        rpc::{RpcServerError, RpcStatusCode},
+        rpc::{message::RpcMethod, RpcServerError, RpcStatusCode},
         ProtocolId,
     },
 };
@@ -91,6 +91,23 @@ pub fn status_error_counter(node_id: &NodeId, protocol: &ProtocolId, status_code
     ])
 }
 
+pub fn deadline_exceeded_counter(node_id: &NodeId, protocol: &ProtocolId, method: RpcMethod) -> IntCounter {
+    static METER: Lazy<IntCounterVec> = Lazy::new(|| {
+        tari_metrics::register_int_counter_vec(
+            "comms::rpc::server::deadline_exceeded_count",
+            "The number of requests that exceeded their deadline per peer per protocol method",
+            &["peer_id", "protocol", "method"],
+        )
+        .unwrap()
+    });
+
+    METER.with_label_values(&[
+        node_id.to_string().as_str(),
+        String::from_utf8_lossy(protocol).as_ref(),
+        method.id().to_string().as_str(),
+    ])
+}
+
 pub fn inbound_requests_bytes(node_id: &NodeId, protocol: &ProtocolId) -> Histogram {
     static METER: Lazy<HistogramVec> = Lazy::new(|| {
         tari_metrics::register_histogram_vec(
@@ -116,3 +133,29 @@ pub fn outbound_response_bytes(node_id: &NodeId, protocol: &ProtocolId) -> Histo
 
     METER.with_label_values(&[node_id.to_string().as_str(), String::from_utf8_lossy(protocol).as_ref()])
 }
+
+pub fn compressed_responses(node_id: &NodeId, protocol: &ProtocolId) -> IntCounter {
+    static METER: Lazy<IntCounterVec> = Lazy::new(|| {
+        tari_metrics::register_int_counter_vec(
+            "comms::rpc::server::compressed_response_count",
+            "The number of compressed responses per peer per protocol",
+            &["peer_id", "protocol"],
+        )
+        .unwrap()
+    });
+
+    METER.with_label_values(&[node_id.to_string().as_str(), String::from_utf8_lossy(protocol).as_ref()])
+}
+
+pub fn compression_bytes_saved(node_id: &NodeId, protocol: &ProtocolId) -> IntCounter {
+    static METER: Lazy<IntCounterVec> = Lazy::new(|| {
+        tari_metrics::register_int_counter_vec(
+            "comms::rpc::server::compression_bytes_saved",
+            "The number of response bytes saved by compression per peer per protocol",
+            &["peer_id", "protocol"],
+        )
+        .unwrap()
+    });
+
+    METER.with_label_values(&[node_id.to_string().as_str(), String::from_utf8_lossy(protocol).as_ref()])
+}
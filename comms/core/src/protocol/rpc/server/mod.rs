@@ -41,7 +41,7 @@ use std::{
     borrow::Cow,
     cmp,
     collections::HashMap,
-    convert::TryFrom,
+    fmt,
     future::Future,
     io,
     io::ErrorKind,
@@ -62,12 +62,15 @@ use tracing::{debug, error, instrument, span, trace, warn, Instrument, Level};
 
 use super::{
     body::Body,
+    compression,
     context::{RequestContext, RpcCommsProvider},
     error::HandshakeRejectReason,
     message::{Request, Response, RpcMessageFlags},
     not_found::ProtocolServiceNotFound,
     status::RpcStatus,
     Handshake,
+    RpcCompression,
+    DEFAULT_COMPRESSION_THRESHOLD,
     RPC_MAX_FRAME_SIZE,
 };
 use crate::{
@@ -177,7 +180,11 @@ pub struct RpcServerBuilder {
     maximum_simultaneous_sessions: Option<usize>,
     maximum_sessions_per_client: Option<usize>,
     minimum_client_deadline: Duration,
+    maximum_deadline: Option<Duration>,
+    method_deadlines: HashMap<(ProtocolId, u32), Duration>,
     handshake_timeout: Duration,
+    compression: RpcCompression,
+    compression_threshold: usize,
 }
 
 impl RpcServerBuilder {
@@ -210,6 +217,45 @@ impl RpcServerBuilder {
         self
     }
 
+    /// The maximum length of time that any request may run on this server, regardless of the deadline requested by
+    /// the client. If a request exceeds this deadline, it is aborted and the client receives a timeout status.
+    /// Default: no maximum, the client deadline is used
+    pub fn with_maximum_deadline(mut self, deadline: Duration) -> Self {
+        self.maximum_deadline = Some(deadline);
+        self
+    }
+
+    /// Set the maximum length of time that the given method for the given protocol may run. This takes precedence
+    /// over the maximum deadline set with [with_maximum_deadline](Self::with_maximum_deadline). The client deadline
+    /// is used if it is shorter.
+    pub fn with_method_deadline(mut self, protocol: ProtocolId, method: u32, deadline: Duration) -> Self {
+        self.method_deadlines.insert((protocol, method), deadline);
+        self
+    }
+
+    /// Returns the server-side deadline for the given protocol method, if any
+    fn server_deadline(&self, protocol: &ProtocolId, method: RpcMethod) -> Option<Duration> {
+        self.method_deadlines
+            .get(&(protocol.clone(), method.id()))
+            .copied()
+            .or(self.maximum_deadline)
+    }
+
+    /// Compress response payloads using the given compression for sessions with clients that support it. If the
+    /// client does not support it, another compression supported by the client is used. Compression is disabled by
+    /// default.
+    pub fn with_compression(mut self, compression: RpcCompression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Only response payloads of at least `threshold` bytes are compressed.
+    /// Default: [DEFAULT_COMPRESSION_THRESHOLD](crate::protocol::rpc::DEFAULT_COMPRESSION_THRESHOLD)
+    pub fn with_compression_threshold(mut self, threshold: usize) -> Self {
+        self.compression_threshold = threshold;
+        self
+    }
+
     pub fn finish(self) -> RpcServer {
         let (request_tx, request_rx) = mpsc::channel(10);
         RpcServer {
@@ -226,7 +272,11 @@ impl Default for RpcServerBuilder {
             maximum_simultaneous_sessions: None,
             maximum_sessions_per_client: None,
             minimum_client_deadline: Duration::from_secs(1),
+            maximum_deadline: None,
+            method_deadlines: HashMap::new(),
             handshake_timeout: Duration::from_secs(15),
+            compression: RpcCompression::None,
+            compression_threshold: DEFAULT_COMPRESSION_THRESHOLD,
         }
     }
 }
@@ -398,7 +448,9 @@ where
         node_id: &NodeId,
         mut framed: CanonicalFraming<Substream>,
     ) -> Result<(), RpcServerError> {
-        let mut handshake = Handshake::new(&mut framed).with_timeout(self.config.handshake_timeout);
+        let mut handshake = Handshake::new(&mut framed)
+            .with_timeout(self.config.handshake_timeout)
+            .with_compression(self.config.compression);
 
         if !self.executor.can_spawn() {
             debug!(
@@ -445,14 +497,18 @@ where
             },
         }
 
-        let version = handshake.perform_server_handshake().await?;
+        let session = handshake.perform_server_handshake().await?;
         debug!(
             target: LOG_TARGET,
-            "Server negotiated RPC v{} with client node `{}`", version, node_id
+            "Server negotiated RPC v{} (compression: {}) with client node `{}`",
+            session.version,
+            session.compression,
+            node_id
         );
 
         let service = ActivePeerRpcService::new(
             self.config.clone(),
+            session.compression,
             protocol,
             node_id.clone(),
             service,
@@ -482,6 +538,7 @@ where
 
 struct ActivePeerRpcService<TSvc, TCommsProvider> {
     config: RpcServerBuilder,
+    compression: RpcCompression,
     protocol: ProtocolId,
     node_id: NodeId,
     service: TSvc,
@@ -497,6 +554,7 @@ where
 {
     pub(self) fn new(
         config: RpcServerBuilder,
+        compression: RpcCompression,
         protocol: ProtocolId,
         node_id: NodeId,
         service: TSvc,
@@ -512,6 +570,7 @@ where
             )),
 
             config,
+            compression,
             protocol,
             node_id,
             service,
@@ -603,10 +662,10 @@ where
 
         let request_id = decoded_msg.request_id;
         let method = RpcMethod::from(decoded_msg.method);
-        let deadline = Duration::from_secs(decoded_msg.deadline);
+        let client_deadline = Duration::from_secs(decoded_msg.deadline);
 
         // The client side deadline MUST be greater or equal to the minimum_client_deadline
-        if deadline < self.config.minimum_client_deadline {
+        if client_deadline < self.config.minimum_client_deadline {
             debug!(
                 target: LOG_TARGET,
                 "({}) Client has an invalid deadline. {}", self.logging_context_string, decoded_msg
@@ -614,7 +673,7 @@ where
             // Let the client know that they have disobeyed the spec
             let status = RpcStatus::bad_request(&format!(
                 "Invalid deadline ({:.0?}). The deadline MUST be greater than {:.0?}.",
-                self.node_id, deadline,
+                client_deadline, self.config.minimum_client_deadline,
             ));
             let bad_request = proto::rpc::RpcResponse {
                 request_id,
@@ -627,13 +686,7 @@ where
             return Ok(());
         }
 
-        let msg_flags = RpcMessageFlags::from_bits(u8::try_from(decoded_msg.flags).map_err(|_| {
-            RpcServerError::ProtocolError(format!("invalid message flag: must be less than {}", u8::MAX))
-        })?)
-        .ok_or(RpcServerError::ProtocolError(format!(
-            "invalid message flag, does not match any flags ({})",
-            decoded_msg.flags
-        )))?;
+        let msg_flags = decoded_msg.flags().map_err(RpcServerError::ProtocolError)?;
 
         if msg_flags.contains(RpcMessageFlags::FIN) {
             debug!(target: LOG_TARGET, "({}) Client sent FIN.", self.logging_context_string);
@@ -662,6 +715,15 @@ where
             method.id()
         );
 
+        // The server may limit the time a method is permitted to run to less than the client deadline
+        let deadline = match self.config.server_deadline(&self.protocol, method) {
+            Some(duration) if duration < client_deadline => Deadline::Server {
+                duration,
+                expires_at: time::Instant::now() + duration,
+            },
+            _ => Deadline::Client(client_deadline),
+        };
+
         let req = Request::with_context(
             self.create_request_context(request_id),
             method,
@@ -674,13 +736,13 @@ where
             "service call",
             self.service.call(req),
         );
-        let service_result = time::timeout(deadline, service_call).await;
+        let service_result = time::timeout(deadline.duration(), service_call).await;
         let service_result = match service_result {
             Ok(v) => v,
             Err(_) => {
                 warn!(
                     target: LOG_TARGET,
-                    "{} RPC service was not able to complete within the deadline ({:.0?}). Request aborted",
+                    "{} RPC service was not able to complete within the {}. Request aborted",
                     self.logging_context_string,
                     deadline,
                 );
@@ -691,13 +753,15 @@ where
                     &RpcServerError::ServiceCallExceededDeadline,
                 )
                 .inc();
+                metrics::deadline_exceeded_counter(&self.node_id, &self.protocol, method).inc();
+                self.send_deadline_exceeded(request_id, deadline).await?;
                 return Ok(());
             },
         };
 
         match service_result {
             Ok(body) => {
-                self.process_body(request_id, deadline, body).await?;
+                self.process_body(request_id, method, deadline, body).await?;
             },
             Err(err) => {
                 debug!(
@@ -723,10 +787,28 @@ where
         String::from_utf8_lossy(&self.protocol)
     }
 
+    /// Lets the client know that the request was aborted because the server deadline was exceeded. Nothing is sent if
+    /// the client deadline was exceeded, because the client has already given up on the request.
+    async fn send_deadline_exceeded(&mut self, request_id: u32, deadline: Deadline) -> Result<(), RpcServerError> {
+        if let Deadline::Server { duration, .. } = deadline {
+            let status = RpcStatus::timed_out(&format!("Request exceeded the server deadline of {:.0?}", duration));
+            let resp = proto::rpc::RpcResponse {
+                request_id,
+                status: status.as_code(),
+                flags: RpcMessageFlags::FIN.bits().into(),
+                payload: status.to_details_bytes(),
+            };
+            metrics::status_error_counter(&self.node_id, &self.protocol, status.as_status_code()).inc();
+            self.framed.send(resp.to_encoded_bytes().into()).await?;
+        }
+        Ok(())
+    }
+
     async fn process_body(
         &mut self,
         request_id: u32,
-        deadline: Duration,
+        method: RpcMethod,
+        deadline: Deadline,
         body: Response<Body>,
     ) -> Result<(), RpcServerError> {
         let response_bytes = metrics::outbound_response_bytes(&self.node_id, &self.protocol);
@@ -734,11 +816,26 @@ where
 
         let node_id = self.node_id.clone();
         let protocol = self.protocol.clone();
+        let compression = self.compression;
+        let compression_threshold = self.config.compression_threshold;
         let mut stream = body
             .into_message()
             .map(|result| into_response(request_id, result))
-            .flat_map(move |message| {
-                if !message.status.is_ok() {
+            .flat_map(move |mut message| {
+                if message.status.is_ok() {
+                    let (flags, payload, saved) = compression::compress_payload(
+                        compression,
+                        compression_threshold,
+                        message.flags,
+                        message.payload,
+                    );
+                    if saved > 0 {
+                        metrics::compressed_responses(&node_id, &protocol).inc();
+                        metrics::compression_bytes_saved(&node_id, &protocol).inc_by(saved as u64);
+                    }
+                    message.flags = flags;
+                    message.payload = payload;
+                } else {
                     metrics::status_error_counter(&node_id, &protocol, message.status).inc();
                 }
                 stream::iter(ChunkedResponseIter::new(message))
@@ -752,7 +849,10 @@ where
                 "message read",
                 stream.next(),
             );
-            let timeout = time::sleep(deadline);
+            let timeout = match deadline {
+                Deadline::Client(d) => time::sleep(d),
+                Deadline::Server { expires_at, .. } => time::sleep_until(expires_at),
+            };
 
             tokio::select! {
                 // Check if the client interrupted the outgoing stream
@@ -791,7 +891,7 @@ where
                 _ = timeout => {
                      debug!(
                         target: LOG_TARGET,
-                        "({}) Failed to return result within client deadline ({:.0?})",
+                        "({}) Failed to return result within the {}",
                         self.logging_context_string,
                         deadline
                     );
@@ -802,6 +902,8 @@ where
                         &RpcServerError::ReadStreamExceededDeadline,
                     )
                     .inc();
+                    metrics::deadline_exceeded_counter(&self.node_id, &self.protocol, method).inc();
+                    self.send_deadline_exceeded(request_id, deadline).await?;
                     break;
                 }
             } // end select!
@@ -819,25 +921,11 @@ where
                         return Poll::Ready(Some(RpcServerError::UnexpectedIncomingMessageMalformed));
                     },
                 };
-                let u8_bits = match u8::try_from(decoded_msg.flags) {
-                    Ok(bits) => bits,
+                let msg_flags = match decoded_msg.flags() {
+                    Ok(flags) => flags,
                     Err(err) => {
                         error!(target: LOG_TARGET, "Client send MALFORMED flags: {}", err);
-                        return Poll::Ready(Some(RpcServerError::ProtocolError(format!(
-                            "invalid message flag: must be less than {}",
-                            u8::MAX
-                        ))));
-                    },
-                };
-
-                let msg_flags = match RpcMessageFlags::from_bits(u8_bits) {
-                    Some(flags) => flags,
-                    None => {
-                        error!(target: LOG_TARGET, "Client send MALFORMED flags: {}", u8_bits);
-                        return Poll::Ready(Some(RpcServerError::ProtocolError(format!(
-                            "invalid message flag, does not match any flags ({})",
-                            u8_bits
-                        ))));
+                        return Poll::Ready(Some(RpcServerError::ProtocolError(err)));
                     },
                 };
                 if msg_flags.is_fin() {
@@ -863,6 +951,34 @@ where
     }
 }
 
+/// The deadline applied to a request. Client deadlines apply to each message sent back to the client, server deadlines
+/// apply to the request as a whole.
+#[derive(Debug, Clone, Copy)]
+enum Deadline {
+    Client(Duration),
+    Server {
+        duration: Duration,
+        expires_at: time::Instant,
+    },
+}
+
+impl Deadline {
+    fn duration(self) -> Duration {
+        match self {
+            Deadline::Client(d) | Deadline::Server { duration: d, .. } => d,
+        }
+    }
+}
+
+impl fmt::Display for Deadline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Deadline::Client(d) => write!(f, "client deadline ({:.0?})", d),
+            Deadline::Server { duration, .. } => write!(f, "server deadline ({:.0?})", duration),
+        }
+    }
+}
+
 async fn log_timing<R, F: Future<Output = R>>(context_str: Arc<String>, request_id: u32, tag: &str, fut: F) -> R {
     let t = Instant::now();
     let span = span!(Level::TRACE, "rpc::internal::timing", request_id, tag);
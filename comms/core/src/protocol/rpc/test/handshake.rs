@@ -30,6 +30,7 @@ use crate::{
         error::HandshakeRejectReason,
         handshake::{RpcHandshakeError, SUPPORTED_RPC_VERSIONS},
         Handshake,
+        RpcCompression,
     },
 };
 
@@ -46,9 +47,49 @@ async fn it_performs_the_handshake() {
     let mut client_framed = framing::canonical(client, 1024);
     let mut handshake_client = Handshake::new(&mut client_framed);
 
-    handshake_client.perform_client_handshake().await.unwrap();
-    let v = handshake_result.await.unwrap().unwrap();
-    assert!(SUPPORTED_RPC_VERSIONS.contains(&v));
+    let client_session = handshake_client.perform_client_handshake().await.unwrap();
+    let server_session = handshake_result.await.unwrap().unwrap();
+    assert!(SUPPORTED_RPC_VERSIONS.contains(&server_session.version));
+    assert_eq!(client_session, server_session);
+    assert_eq!(server_session.compression, RpcCompression::None);
+}
+
+#[tokio::test]
+async fn it_negotiates_compression() {
+    let (client, server) = MemorySocket::new_pair();
+
+    let handshake_result = task::spawn(async move {
+        let mut server_framed = framing::canonical(server, 1024);
+        let mut handshake_server = Handshake::new(&mut server_framed).with_compression(RpcCompression::Zstd);
+        handshake_server.perform_server_handshake().await
+    });
+
+    let mut client_framed = framing::canonical(client, 1024);
+    let mut handshake_client = Handshake::new(&mut client_framed).with_compression(RpcCompression::Lz4);
+
+    let client_session = handshake_client.perform_client_handshake().await.unwrap();
+    let server_session = handshake_result.await.unwrap().unwrap();
+    assert_eq!(client_session.compression, RpcCompression::Zstd);
+    assert_eq!(server_session.compression, RpcCompression::Zstd);
+}
+
+#[tokio::test]
+async fn it_does_not_compress_if_the_client_does_not_support_it() {
+    let (client, server) = MemorySocket::new_pair();
+
+    let handshake_result = task::spawn(async move {
+        let mut server_framed = framing::canonical(server, 1024);
+        let mut handshake_server = Handshake::new(&mut server_framed).with_compression(RpcCompression::Lz4);
+        handshake_server.perform_server_handshake().await
+    });
+
+    let mut client_framed = framing::canonical(client, 1024);
+    let mut handshake_client = Handshake::new(&mut client_framed);
+
+    let client_session = handshake_client.perform_client_handshake().await.unwrap();
+    let server_session = handshake_result.await.unwrap().unwrap();
+    assert_eq!(client_session.compression, RpcCompression::None);
+    assert_eq!(server_session.compression, RpcCompression::None);
 }
 
 #[tokio::test]
@@ -53,6 +53,7 @@ use crate::{
                 },
                 mock::create_mocked_rpc_context,
             },
+            RpcCompression,
             RpcError,
             RpcServer,
             RpcServerBuilder,
@@ -286,6 +287,51 @@ async fn response_too_big() {
         .unwrap();
 }
 
+#[tokio::test]
+async fn compressed_responses() {
+    for compression in RpcCompression::SUPPORTED {
+        let builder = RpcServer::builder()
+            .with_minimum_client_deadline(Duration::from_secs(0))
+            .with_compression(*compression);
+        let (notif_tx, _, context, _shutdown) = setup_service_with_builder(GreetingService::default(), builder).await;
+        let (_, mut inbound, outbound) = build_multiplexed_connections().await;
+        let node_identity = build_node_identity(Default::default());
+        context.peer_manager().add_peer(node_identity.to_peer()).await.unwrap();
+        let substream = outbound.get_yamux_control().open_stream().await.unwrap();
+        notif_tx
+            .send(ProtocolNotification::new(
+                ProtocolId::from_static(b"/test/greeting/1.0"),
+                ProtocolEvent::NewInboundSubstream(node_identity.node_id().clone(), substream),
+            ))
+            .await
+            .unwrap();
+
+        let socket = inbound.incoming_mut().next().await.unwrap();
+        let framed = framing::canonical(socket, rpc::max_response_size());
+        let mut client = GreetingClient::builder()
+            .with_deadline(Duration::from_secs(5))
+            .connect(framed)
+            .await
+            .unwrap();
+
+        let msg = client.reply_with_msg_of_size(1024 * 1024).await.unwrap();
+        assert_eq!(msg.len(), 1024 * 1024);
+        assert!(msg.iter().all(|b| *b == 0));
+
+        // Small responses are sent uncompressed
+        let msg = client.reply_with_msg_of_size(10).await.unwrap();
+        assert_eq!(msg.len(), 10);
+
+        // Responses that are too large are still rejected
+        let err = client
+            .reply_with_msg_of_size(rpc::max_response_payload_size() as u64 + 1)
+            .await
+            .unwrap_err();
+        unpack_enum!(RpcError::RequestFailed(status) = err);
+        unpack_enum!(RpcStatusCode::MalformedResponse = status.as_status_code());
+    }
+}
+
 #[tokio::test]
 async fn ping_latency() {
     let (mut muxer, _outbound, _, _, _shutdown) = setup(GreetingService::new(&[]), 1).await;
@@ -339,6 +385,52 @@ async fn timeout() {
     assert_eq!(resp.greeting, "took a while to load");
 }
 
+#[tokio::test]
+async fn server_method_deadline() {
+    let delay = Arc::new(RwLock::new(Duration::from_secs(10)));
+    let builder = RpcServer::builder()
+        .with_minimum_client_deadline(Duration::from_secs(0))
+        .with_method_deadline(
+            ProtocolId::from_static(b"/test/greeting/1.0"),
+            1,
+            Duration::from_secs(1),
+        );
+    let (notif_tx, _, context, _shutdown) =
+        setup_service_with_builder(SlowGreetingService::new(delay.clone()), builder).await;
+    let (_, mut inbound, outbound) = build_multiplexed_connections().await;
+    let node_identity = build_node_identity(Default::default());
+    context.peer_manager().add_peer(node_identity.to_peer()).await.unwrap();
+    let substream = outbound.get_yamux_control().open_stream().await.unwrap();
+    notif_tx
+        .send(ProtocolNotification::new(
+            ProtocolId::from_static(b"/test/greeting/1.0"),
+            ProtocolEvent::NewInboundSubstream(node_identity.node_id().clone(), substream),
+        ))
+        .await
+        .unwrap();
+
+    let socket = inbound.incoming_mut().next().await.unwrap();
+    let framed = framing::canonical(socket, 1024);
+    let mut client = GreetingClient::builder()
+        .with_deadline(Duration::from_secs(30))
+        .connect(framed)
+        .await
+        .unwrap();
+
+    // The server aborts the request after its own deadline and tells the client, rather than waiting for the (much
+    // longer) client deadline to expire
+    let err = time::timeout(Duration::from_secs(10), client.say_hello(Default::default()))
+        .await
+        .unwrap()
+        .unwrap_err();
+    unpack_enum!(RpcError::RequestFailed(status) = err);
+    assert_eq!(status.as_status_code(), RpcStatusCode::Timeout);
+
+    *delay.write().await = Duration::from_secs(0);
+    let resp = client.say_hello(Default::default()).await.unwrap();
+    assert_eq!(resp.greeting, "took a while to load");
+}
+
 #[tokio::test]
 async fn unknown_protocol() {
     let (notif_tx, _, _, _shutdown) = setup_service(GreetingService::new(&[]), 1).await;
@@ -30,6 +30,8 @@ pub mod mocks;
 pub mod node_id;
 pub mod node_identity;
 
+pub mod network_simulator;
+
 pub mod peer_manager;
 pub use peer_manager::build_peer_manager;
 
@@ -0,0 +1,545 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! # Network simulator
+//!
+//! An in-process [Transport] for tests that run many comms nodes. Every node gets its own [SimulatedTransport] from a
+//! shared [SimulatedNetwork], which delivers the bytes written on one end of a connection to the other end after the
+//! latency of the link between the two nodes. Links can drop packets, which (as with TCP) delays delivery by a
+//! retransmission timeout rather than corrupting the stream, and nodes can be partitioned from each other.
+//!
+//! All randomness is drawn from a generator seeded when the network is created, so a test that is driven in a
+//! deterministic order (e.g. on a current-thread runtime) sees the same delays for the same seed.
+//!
+//! Simulated addresses are `/memory/<port>` addresses but are independent of
+//! [MemoryTransport](crate::transports::MemoryTransport) ports.
+
+use std::{
+    collections::{HashMap, HashSet},
+    io,
+    pin::Pin,
+    sync::{Arc, Mutex, MutexGuard},
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use bytes::Bytes;
+use futures::{ready, Future, Stream};
+use multiaddr::{Multiaddr, Protocol};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use tokio::{
+    io::{AsyncRead, AsyncWrite, ReadBuf},
+    sync::mpsc,
+    time::{sleep_until, Instant, Sleep},
+};
+
+use crate::transports::Transport;
+
+/// The maximum number of times a lost packet is retransmitted before the connection attempt or write fails
+const MAX_RETRANSMISSIONS: u32 = 8;
+
+/// The conditions of the link from one node to another
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LinkConditions {
+    /// The time taken for a packet to cross the link
+    pub latency: Duration,
+    /// The maximum random delay added to the latency of each packet
+    pub jitter: Duration,
+    /// The probability, between 0 and 1, that a packet is lost and has to be retransmitted
+    pub packet_loss: f64,
+    /// The time after which a lost packet is retransmitted. This doubles with every retransmission of the same packet.
+    pub retransmission_timeout: Duration,
+}
+
+impl LinkConditions {
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    pub fn with_jitter(mut self, jitter: Duration) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    pub fn with_packet_loss(mut self, packet_loss: f64) -> Self {
+        self.packet_loss = packet_loss.clamp(0.0, 1.0);
+        self
+    }
+}
+
+impl Default for LinkConditions {
+    fn default() -> Self {
+        Self {
+            latency: Duration::ZERO,
+            jitter: Duration::ZERO,
+            packet_loss: 0.0,
+            retransmission_timeout: Duration::from_millis(200),
+        }
+    }
+}
+
+/// Counters of the traffic on a [SimulatedNetwork]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NetworkStats {
+    pub connections: usize,
+    pub refused_connections: usize,
+    pub packets_sent: usize,
+    pub packets_lost: usize,
+    pub bytes_sent: usize,
+}
+
+struct NetworkState {
+    rng: StdRng,
+    default_conditions: LinkConditions,
+    links: HashMap<(u64, u64), LinkConditions>,
+    partitions: HashSet<(u64, u64)>,
+    listeners: HashMap<u64, mpsc::UnboundedSender<(SimulatedSocket, Multiaddr)>>,
+    next_port: u64,
+    stats: NetworkStats,
+}
+
+impl NetworkState {
+    fn conditions(&self, from: u64, to: u64) -> LinkConditions {
+        self.links.get(&(from, to)).copied().unwrap_or(self.default_conditions)
+    }
+
+    fn is_partitioned(&self, a: u64, b: u64) -> bool {
+        self.partitions.contains(&partition_key(a, b))
+    }
+
+    /// Returns the time taken for a packet to cross the link from `from` to `to`, or None if the packet was lost on
+    /// every retransmission
+    fn transmission_delay(&mut self, from: u64, to: u64) -> Option<Duration> {
+        let conditions = self.conditions(from, to);
+        let mut delay = conditions.latency;
+        if !conditions.jitter.is_zero() {
+            delay += conditions.jitter.mul_f64(self.rng.gen::<f64>());
+        }
+        self.stats.packets_sent += 1;
+        let mut timeout = conditions.retransmission_timeout;
+        for _ in 0..MAX_RETRANSMISSIONS {
+            if !self.rng.gen_bool(conditions.packet_loss) {
+                return Some(delay);
+            }
+            self.stats.packets_lost += 1;
+            delay += timeout;
+            timeout *= 2;
+        }
+        None
+    }
+}
+
+fn partition_key(a: u64, b: u64) -> (u64, u64) {
+    (a.min(b), a.max(b))
+}
+
+/// A simulated network shared by the [SimulatedTransport]s of the nodes in a test
+#[derive(Clone)]
+pub struct SimulatedNetwork {
+    state: Arc<Mutex<NetworkState>>,
+}
+
+impl SimulatedNetwork {
+    /// Creates a network with perfect links, whose random packet loss and jitter are drawn from `seed`
+    pub fn new(seed: u64) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(NetworkState {
+                rng: StdRng::seed_from_u64(seed),
+                default_conditions: LinkConditions::default(),
+                links: HashMap::new(),
+                partitions: HashSet::new(),
+                listeners: HashMap::new(),
+                next_port: 1,
+                stats: NetworkStats::default(),
+            })),
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, NetworkState> {
+        self.state.lock().expect("simulated network lock poisoned")
+    }
+
+    /// Creates the transport for a new node, with its own address on this network
+    pub fn create_transport(&self) -> SimulatedTransport {
+        let mut state = self.state();
+        let port = state.next_port;
+        state.next_port += 1;
+        SimulatedTransport {
+            network: self.clone(),
+            port,
+        }
+    }
+
+    /// Sets the conditions of every link that does not have its own conditions
+    pub fn set_default_conditions(&self, conditions: LinkConditions) {
+        self.state().default_conditions = conditions;
+    }
+
+    /// Sets the conditions of the links in both directions between the nodes with addresses `a` and `b`
+    ///
+    /// # Panics
+    /// Panics if either address is not a simulated address
+    pub fn set_link_conditions(&self, a: &Multiaddr, b: &Multiaddr, conditions: LinkConditions) {
+        let (a, b) = (expect_port(a), expect_port(b));
+        let mut state = self.state();
+        state.links.insert((a, b), conditions);
+        state.links.insert((b, a), conditions);
+    }
+
+    /// Cuts the link between the nodes with addresses `a` and `b`. Dials between them are refused and writes to
+    /// existing connections between them fail, closing the connection.
+    ///
+    /// # Panics
+    /// Panics if either address is not a simulated address
+    pub fn partition(&self, a: &Multiaddr, b: &Multiaddr) {
+        self.state()
+            .partitions
+            .insert(partition_key(expect_port(a), expect_port(b)));
+    }
+
+    /// Restores the link between the nodes with addresses `a` and `b`
+    ///
+    /// # Panics
+    /// Panics if either address is not a simulated address
+    pub fn heal(&self, a: &Multiaddr, b: &Multiaddr) {
+        self.state()
+            .partitions
+            .remove(&partition_key(expect_port(a), expect_port(b)));
+    }
+
+    /// Returns the traffic counters for this network
+    pub fn stats(&self) -> NetworkStats {
+        self.state().stats
+    }
+}
+
+/// The [Transport] of one node on a [SimulatedNetwork]
+#[derive(Clone)]
+pub struct SimulatedTransport {
+    network: SimulatedNetwork,
+    port: u64,
+}
+
+impl SimulatedTransport {
+    /// The address this node listens on when asked to listen on `/memory/0`
+    pub fn address(&self) -> Multiaddr {
+        Protocol::Memory(self.port).into()
+    }
+
+    fn refused(&self, to: u64) -> io::Error {
+        self.network.state().stats.refused_connections += 1;
+        io::Error::new(
+            io::ErrorKind::ConnectionRefused,
+            format!("Connection from /memory/{} to /memory/{} refused", self.port, to),
+        )
+    }
+}
+
+#[crate::async_trait]
+impl Transport for SimulatedTransport {
+    type Error = io::Error;
+    type Listener = SimulatedListener;
+    type Output = SimulatedSocket;
+
+    async fn listen(&self, addr: &Multiaddr) -> Result<(Self::Listener, Multiaddr), Self::Error> {
+        let port = match parse_port(addr)? {
+            0 => self.port,
+            port => port,
+        };
+        let mut state = self.network.state();
+        if state.listeners.get(&port).map_or(false, |l| !l.is_closed()) {
+            return Err(io::Error::new(
+                io::ErrorKind::AddrInUse,
+                format!("/memory/{} is already in use", port),
+            ));
+        }
+        let (tx, rx) = mpsc::unbounded_channel();
+        state.listeners.insert(port, tx);
+        Ok((SimulatedListener { rx }, Protocol::Memory(port).into()))
+    }
+
+    async fn dial(&self, addr: &Multiaddr) -> Result<Self::Output, Self::Error> {
+        let to = parse_port(addr)?;
+        let (outbound_delay, inbound_delay, listener) = {
+            let mut state = self.network.state();
+            let listener = state
+                .listeners
+                .get(&to)
+                .filter(|_| !state.is_partitioned(self.port, to))
+                .cloned();
+            let listener = match listener {
+                Some(listener) => listener,
+                None => {
+                    drop(state);
+                    return Err(self.refused(to));
+                },
+            };
+            let outbound_delay = state.transmission_delay(self.port, to);
+            let inbound_delay = state.transmission_delay(to, self.port);
+            (outbound_delay, inbound_delay, listener)
+        };
+        let (outbound_delay, inbound_delay) = match (outbound_delay, inbound_delay) {
+            (Some(outbound), Some(inbound)) => (outbound, inbound),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    format!("Connection from /memory/{} to /memory/{} timed out", self.port, to),
+                ))
+            },
+        };
+
+        tokio::time::sleep(outbound_delay).await;
+        let (dialer, listener_socket) = SimulatedSocket::pair(&self.network, self.port, to);
+        if listener.send((listener_socket, self.address())).is_err() {
+            return Err(self.refused(to));
+        }
+        self.network.state().stats.connections += 1;
+        tokio::time::sleep(inbound_delay).await;
+        Ok(dialer)
+    }
+}
+
+/// Inbound connections to a [SimulatedTransport]
+#[must_use = "streams do nothing unless polled"]
+pub struct SimulatedListener {
+    rx: mpsc::UnboundedReceiver<(SimulatedSocket, Multiaddr)>,
+}
+
+impl Stream for SimulatedListener {
+    type Item = io::Result<(SimulatedSocket, Multiaddr)>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_recv(cx).map(|item| item.map(Ok))
+    }
+}
+
+struct Packet {
+    deliver_at: Instant,
+    data: Bytes,
+}
+
+/// One end of a connection on a [SimulatedNetwork]
+pub struct SimulatedSocket {
+    network: SimulatedNetwork,
+    local: u64,
+    remote: u64,
+    tx: Option<mpsc::UnboundedSender<Packet>>,
+    rx: mpsc::UnboundedReceiver<Packet>,
+    /// The packet that is being read or waited for
+    pending: Option<Packet>,
+    delay: Option<Pin<Box<Sleep>>>,
+    /// When the last packet written to this socket arrives, so that packets never overtake each other
+    last_delivery: Instant,
+}
+
+impl SimulatedSocket {
+    fn pair(network: &SimulatedNetwork, a: u64, b: u64) -> (Self, Self) {
+        let (a_tx, b_rx) = mpsc::unbounded_channel();
+        let (b_tx, a_rx) = mpsc::unbounded_channel();
+        let socket = |local, remote, tx, rx| Self {
+            network: network.clone(),
+            local,
+            remote,
+            tx: Some(tx),
+            rx,
+            pending: None,
+            delay: None,
+            last_delivery: Instant::now(),
+        };
+        (socket(a, b, a_tx, a_rx), socket(b, a, b_tx, b_rx))
+    }
+}
+
+impl AsyncRead for SimulatedSocket {
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<io::Result<()>> {
+        loop {
+            if let Some(deliver_at) = self.pending.as_ref().map(|p| p.deliver_at) {
+                if deliver_at > Instant::now() {
+                    let delay = self.delay.get_or_insert_with(|| Box::pin(sleep_until(deliver_at)));
+                    delay.as_mut().reset(deliver_at);
+                    ready!(delay.as_mut().poll(cx));
+                }
+                let packet = self.pending.as_mut().expect("checked above");
+                let len = packet.data.len().min(buf.remaining());
+                buf.put_slice(&packet.data.split_to(len));
+                if packet.data.is_empty() {
+                    self.pending = None;
+                }
+                return Poll::Ready(Ok(()));
+            }
+            match ready!(self.rx.poll_recv(cx)) {
+                Some(packet) => self.pending = Some(packet),
+                // The remote end has closed the connection
+                None => return Poll::Ready(Ok(())),
+            }
+        }
+    }
+}
+
+impl AsyncWrite for SimulatedSocket {
+    fn poll_write(mut self: Pin<&mut Self>, _cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        if self.tx.is_none() {
+            return Poll::Ready(Err(io::ErrorKind::BrokenPipe.into()));
+        }
+        let delay = {
+            let mut state = self.network.state();
+            if state.is_partitioned(self.local, self.remote) {
+                None
+            } else {
+                state.stats.bytes_sent += buf.len();
+                state.transmission_delay(self.local, self.remote)
+            }
+        };
+        let delay = match delay {
+            Some(delay) => delay,
+            None => {
+                self.tx = None;
+                return Poll::Ready(Err(io::ErrorKind::ConnectionReset.into()));
+            },
+        };
+        let deliver_at = (Instant::now() + delay).max(self.last_delivery);
+        self.last_delivery = deliver_at;
+        let packet = Packet {
+            deliver_at,
+            data: Bytes::copy_from_slice(buf),
+        };
+        match self.tx.as_ref().map(|tx| tx.send(packet)) {
+            Some(Ok(())) => Poll::Ready(Ok(buf.len())),
+            _ => {
+                self.tx = None;
+                Poll::Ready(Err(io::ErrorKind::BrokenPipe.into()))
+            },
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.tx = None;
+        Poll::Ready(Ok(()))
+    }
+}
+
+fn parse_port(addr: &Multiaddr) -> io::Result<u64> {
+    let mut iter = addr.iter();
+    match (iter.next(), iter.next()) {
+        (Some(Protocol::Memory(port)), None) => Ok(port),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("Invalid simulated network address '{}'", addr),
+        )),
+    }
+}
+
+fn expect_port(addr: &Multiaddr) -> u64 {
+    parse_port(addr).expect("not a simulated network address")
+}
+
+#[cfg(test)]
+mod test {
+    use futures::StreamExt;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    use super::*;
+
+    async fn connect(network: &SimulatedNetwork) -> (SimulatedTransport, SimulatedTransport, SimulatedListener) {
+        let (a, b) = (network.create_transport(), network.create_transport());
+        let (listener, addr) = b.listen(&"/memory/0".parse().unwrap()).await.unwrap();
+        assert_eq!(addr, b.address());
+        (a, b, listener)
+    }
+
+    #[tokio::test]
+    async fn it_delivers_bytes_in_order_after_the_link_latency() {
+        let network = SimulatedNetwork::new(1);
+        network.set_default_conditions(
+            LinkConditions::default()
+                .with_latency(Duration::from_millis(20))
+                .with_jitter(Duration::from_millis(10)),
+        );
+        let (a, b, mut listener) = connect(&network).await;
+
+        let start = Instant::now();
+        let mut outbound = a.dial(&b.address()).await.unwrap();
+        let (mut inbound, dialer_addr) = listener.next().await.unwrap().unwrap();
+        assert_eq!(dialer_addr, a.address());
+        assert!(start.elapsed() >= Duration::from_millis(40));
+
+        let start = Instant::now();
+        for chunk in [&b"hello"[..], b" ", b"world"] {
+            outbound.write_all(chunk).await.unwrap();
+        }
+        outbound.shutdown().await.unwrap();
+        let mut buf = Vec::new();
+        inbound.read_to_end(&mut buf).await.unwrap();
+        assert_eq!(buf, b"hello world");
+        assert!(start.elapsed() >= Duration::from_millis(20));
+    }
+
+    #[tokio::test]
+    async fn it_refuses_dials_across_a_partition() {
+        let network = SimulatedNetwork::new(1);
+        let (a, b, mut listener) = connect(&network).await;
+        let mut outbound = a.dial(&b.address()).await.unwrap();
+        let (mut inbound, _) = listener.next().await.unwrap().unwrap();
+
+        network.partition(&a.address(), &b.address());
+        let err = a.dial(&b.address()).await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::ConnectionRefused);
+        let err = outbound.write_all(b"lost").await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::ConnectionReset);
+        let mut buf = Vec::new();
+        inbound.read_to_end(&mut buf).await.unwrap();
+        assert!(buf.is_empty());
+
+        network.heal(&a.address(), &b.address());
+        a.dial(&b.address()).await.unwrap();
+        assert_eq!(network.stats().refused_connections, 1);
+        assert_eq!(network.stats().connections, 2);
+    }
+
+    #[test]
+    fn it_loses_the_same_packets_for_the_same_seed() {
+        let delays = |seed| {
+            let network = SimulatedNetwork::new(seed);
+            network.set_default_conditions(
+                LinkConditions::default()
+                    .with_jitter(Duration::from_millis(50))
+                    .with_packet_loss(0.3),
+            );
+            let mut state = network.state();
+            (0..100).map(|_| state.transmission_delay(1, 2)).collect::<Vec<_>>()
+        };
+        assert_eq!(delays(42), delays(42));
+        assert_ne!(delays(42), delays(43));
+
+        let network = SimulatedNetwork::new(42);
+        network.set_default_conditions(LinkConditions::default().with_packet_loss(1.0));
+        assert_eq!(network.state().transmission_delay(1, 2), None);
+    }
+}
@@ -27,7 +27,10 @@ use log::*;
 use tari_shutdown::OptionalShutdownSignal;
 use tari_utilities::hex::Hex;
 use thiserror::Error;
-use tokio::{sync::broadcast, time};
+use tokio::{
+    sync::{broadcast, mpsc},
+    time,
+};
 
 use crate::{
     multiaddr::Multiaddr,
@@ -37,7 +40,7 @@ use crate::{
             commands::{AddOnionFlag, AddOnionResponse},
             TorControlEvent,
         },
-        hidden_service::TorProxyOpts,
+        hidden_service::{HiddenServiceRequest, TorProxyOpts},
         Authentication,
         HiddenService,
         HsFlags,
@@ -74,6 +77,8 @@ pub enum HiddenServiceControllerError {
     UnrecognizedAuthenticationMethod(String),
     #[error("Failed to load tor cookie file: {0}")]
     FailedToLoadCookieFile(io::Error),
+    #[error("The hidden service controller is no longer running")]
+    ControllerNotRunning,
 }
 
 pub struct HiddenServiceController {
@@ -130,6 +135,7 @@ impl HiddenServiceController {
             proxy_address: socks_addr,
             authentication: self.socks_auth.clone(),
             proxy_bypass_predicate: Arc::new(self.proxy_opts.to_bypass_predicate()),
+            isolate_streams: false,
         }))
     }
 
@@ -140,44 +146,55 @@ impl HiddenServiceController {
         self.connect_and_auth().await?;
         self.set_events().await?;
 
-        let hidden_service = self.create_hidden_service_from_identity().await?;
+        let identity = self.create_hidden_service_from_identity().await?;
+        let (request_tx, mut request_rx) = mpsc::channel(1);
+        let hidden_service = HiddenService {
+            identity,
+            proxied_addr: socketaddr_to_multiaddr(self.proxied_port_mapping.proxied_address()),
+            shutdown_signal: self.shutdown_signal.clone(),
+            request_tx,
+        };
         let mut shutdown_signal = hidden_service.shutdown_signal.clone();
         let mut event_stream = self.client.as_ref().unwrap().get_event_stream();
 
         tokio::spawn({
             async move {
                 loop {
-                    let either = future::select(&mut shutdown_signal, event_stream.next()).await;
-                    match either {
-                        Either::Left(_) => {
+                    tokio::select! {
+                        _ = &mut shutdown_signal => {
                             debug!(
                                 target: LOG_TARGET,
                                 "Tor controller shut down because the shutdown signal was received"
                             );
                             break;
                         },
-                        Either::Right((Some(Ok(TorControlEvent::TorControlDisconnected)), shutdown_signal)) => {
-                            let event_tx = self
-                                .client
-                                .as_ref()
-                                .map(|c| c.event_sender().clone())
-                                .expect("HiddenServiceController::client was None");
-                            warn!(
-                                target: LOG_TARGET,
-                                "Tor control server disconnected. Attempting to reestablish connection..."
-                            );
-                            if let Err(err) = self.reestablish_hidden_service(event_tx, shutdown_signal).await {
-                                error!(
+                        Some(request) = request_rx.recv() => {
+                            self.handle_request(request).await;
+                        },
+                        event = event_stream.next() => match event {
+                            Some(Ok(TorControlEvent::TorControlDisconnected)) => {
+                                let event_tx = self
+                                    .client
+                                    .as_ref()
+                                    .map(|c| c.event_sender().clone())
+                                    .expect("HiddenServiceController::client was None");
+                                warn!(
                                     target: LOG_TARGET,
-                                    "Failed to reestablish connection to tor control server because '{:?}'", err
+                                    "Tor control server disconnected. Attempting to reestablish connection..."
                                 );
-                                break;
-                            }
+                                if let Err(err) = self.reestablish_hidden_service(event_tx, &mut shutdown_signal).await {
+                                    error!(
+                                        target: LOG_TARGET,
+                                        "Failed to reestablish connection to tor control server because '{:?}'", err
+                                    );
+                                    break;
+                                }
+                            },
+                            Some(Ok(evt)) => {
+                                trace!(target: LOG_TARGET, "Tor control event: {:?}", evt);
+                            },
+                            _ => {},
                         },
-                        Either::Right((Some(Ok(evt)), _)) => {
-                            trace!(target: LOG_TARGET, "Tor control event: {:?}", evt);
-                        },
-                        _ => {},
                     }
                 }
             }
@@ -186,6 +203,42 @@ impl HiddenServiceController {
         Ok(hidden_service)
     }
 
+    async fn handle_request(&mut self, request: HiddenServiceRequest) {
+        match request {
+            HiddenServiceRequest::RotateIdentity(reply) => {
+                let _result = reply.send(self.rotate_identity().await);
+            },
+        }
+    }
+
+    /// Replaces the onion service with one using a newly generated key. The previous onion service is removed once the
+    /// new one has been published.
+    async fn rotate_identity(&mut self) -> Result<TorIdentity, HiddenServiceControllerError> {
+        let old_identity = self.identity.take();
+        let identity = match self.create_hidden_service_from_identity().await {
+            Ok(identity) => identity,
+            Err(err) => {
+                self.identity = old_identity;
+                return Err(err);
+            },
+        };
+
+        if let Some(old_identity) = old_identity {
+            if let Err(err) = self.client_mut()?.del_onion(&old_identity.service_id).await {
+                warn!(
+                    target: LOG_TARGET,
+                    "Failed to remove previous onion service '{}': {}", old_identity.service_id, err
+                );
+            }
+        }
+        info!(
+            target: LOG_TARGET,
+            "Rotated onion service. New service id is '{}'", identity.service_id
+        );
+
+        Ok(identity)
+    }
+
     pub async fn connect_and_auth(&mut self) -> Result<(), HiddenServiceControllerError> {
         if !self.is_authenticated {
             self.connect().await?;
@@ -335,7 +388,7 @@ impl HiddenServiceController {
         }
     }
 
-    async fn create_hidden_service_from_identity(&mut self) -> Result<HiddenService, HiddenServiceControllerError> {
+    async fn create_hidden_service_from_identity(&mut self) -> Result<TorIdentity, HiddenServiceControllerError> {
         let socks_addr = self.get_socks_address().await?;
         debug!(target: LOG_TARGET, "Tor SOCKS address is '{}'", socks_addr);
 
@@ -370,13 +423,7 @@ impl HiddenServiceController {
             "Added hidden service with service id '{}' on port '{}'", identity.service_id, identity.onion_port
         );
 
-        let proxied_addr = socketaddr_to_multiaddr(self.proxied_port_mapping.proxied_address());
-
-        Ok(HiddenService {
-            identity,
-            proxied_addr,
-            shutdown_signal: self.shutdown_signal.clone(),
-        })
+        Ok(identity)
     }
 
     pub fn set_proxied_addr(&mut self, addr: &Multiaddr) {
@@ -33,12 +33,18 @@ use derivative::Derivative;
 pub use proxy_opts::TorProxyOpts;
 use serde_derive::{Deserialize, Serialize};
 use tari_shutdown::OptionalShutdownSignal;
+use tokio::sync::{mpsc, oneshot};
 
 use crate::{
     multiaddr::Multiaddr,
     tor::{PrivateKey, TorClientError},
 };
 
+/// Requests handled by the task that maintains the hidden service
+pub(super) enum HiddenServiceRequest {
+    RotateIdentity(oneshot::Sender<Result<TorIdentity, HiddenServiceControllerError>>),
+}
+
 /// Handle for a Tor Hidden Service. This handle keeps the session to the Tor control port alive.
 /// Once this is dropped, the hidden service will cease to be accessible.
 #[derive(Clone)]
@@ -49,6 +55,8 @@ pub struct HiddenService {
     pub(super) proxied_addr: Multiaddr,
     /// Shutdown signal for hidden service
     pub(super) shutdown_signal: OptionalShutdownSignal,
+    /// Channel to the task that maintains the hidden service
+    pub(super) request_tx: mpsc::Sender<HiddenServiceRequest>,
 }
 
 impl HiddenService {
@@ -66,9 +74,23 @@ impl HiddenService {
         &self.proxied_addr
     }
 
+    /// The identity the hidden service was created with. This is not updated by `rotate_identity`.
     pub fn tor_identity(&self) -> &TorIdentity {
         &self.identity
     }
+
+    /// Publishes a new onion service with a freshly generated key and removes the current one. The new identity is
+    /// returned so that it can be persisted.
+    pub async fn rotate_identity(&self) -> Result<TorIdentity, HiddenServiceControllerError> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.request_tx
+            .send(HiddenServiceRequest::RotateIdentity(reply_tx))
+            .await
+            .map_err(|_| HiddenServiceControllerError::ControllerNotRunning)?;
+        reply_rx
+            .await
+            .map_err(|_| HiddenServiceControllerError::ControllerNotRunning)?
+    }
 }
 
 fn multiaddr_from_service_id_and_port(service_id: &str, onion_port: u16) -> Result<Multiaddr, TorClientError> {
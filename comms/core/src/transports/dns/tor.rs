@@ -100,6 +100,7 @@ mod test {
             proxy_address: "/ip4/127.0.0.1/tcp/9050".parse().unwrap(),
             authentication: Default::default(),
             proxy_bypass_predicate: Arc::new(FalsePredicate::new()),
+            isolate_streams: false,
         });
 
         let addr = resolver
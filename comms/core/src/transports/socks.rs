@@ -26,7 +26,12 @@ use std::{
     sync::Arc,
 };
 
+use blake2::Blake2b;
+use digest::consts::U32;
 use log::debug;
+use rand::RngCore;
+use tari_crypto::hashing::DomainSeparatedHasher;
+use tari_utilities::hex::Hex;
 use tokio::net::TcpStream;
 
 use crate::{
@@ -34,6 +39,7 @@ use crate::{
     socks,
     socks::Socks5Client,
     transports::{dns::SystemDnsResolver, predicate::Predicate, tcp::TcpTransport, Transport},
+    types::{CommsCoreHashDomain, CommsRng},
 };
 
 const LOG_TARGET: &str = "comms::transports::socks";
@@ -44,6 +50,10 @@ pub struct SocksConfig {
     pub proxy_address: Multiaddr,
     pub authentication: socks::Authentication,
     pub proxy_bypass_predicate: Arc<dyn Predicate<Multiaddr> + Send + Sync>,
+    /// When true, each destination is dialed using distinct SOCKS credentials derived for that destination. Tor
+    /// isolates circuits by SOCKS credentials (`IsolateSOCKSAuth`), so connections to different peers do not share a
+    /// circuit. The configured `authentication` is not used in this mode, so the proxy must accept any credentials.
+    pub isolate_streams: bool,
 }
 
 impl Debug for SocksConfig {
@@ -52,6 +62,7 @@ impl Debug for SocksConfig {
             .field("proxy_address", &self.proxy_address)
             .field("authentication", &self.authentication)
             .field("proxy_bypass_predicate", &"...")
+            .field("isolate_streams", &self.isolate_streams)
             .finish()
     }
 }
@@ -61,13 +72,17 @@ impl Debug for SocksConfig {
 pub struct SocksTransport {
     socks_config: SocksConfig,
     tcp_transport: TcpTransport,
+    isolation_secret: Arc<[u8; 32]>,
 }
 
 impl SocksTransport {
     pub fn new(socks_config: SocksConfig) -> Self {
+        let mut isolation_secret = [0u8; 32];
+        CommsRng::default().fill_bytes(&mut isolation_secret);
         Self {
             socks_config,
             tcp_transport: Self::create_socks_tcp_transport(),
+            isolation_secret: Arc::new(isolation_secret),
         }
     }
 
@@ -77,9 +92,28 @@ impl SocksTransport {
         tcp_transport
     }
 
+    /// Returns the SOCKS credentials to use when dialing `dest_addr`. If stream isolation is enabled, credentials are
+    /// unique to the destination for the lifetime of this transport.
+    fn authentication_for(&self, dest_addr: &Multiaddr) -> socks::Authentication {
+        if !self.socks_config.isolate_streams {
+            return self.socks_config.authentication.clone();
+        }
+
+        let hash = DomainSeparatedHasher::<Blake2b<U32>, CommsCoreHashDomain>::new_with_label("socks.stream_isolation")
+            .chain(&self.isolation_secret[..])
+            .chain(dest_addr.to_vec())
+            .finalize();
+        let (username, password) = hash.as_ref().split_at(16);
+        socks::Authentication::Password {
+            username: username.to_hex(),
+            password: password.to_hex(),
+        }
+    }
+
     async fn socks_connect(
         tcp: TcpTransport,
         socks_config: &SocksConfig,
+        authentication: socks::Authentication,
         dest_addr: &Multiaddr,
     ) -> io::Result<TcpStream> {
         // Create a new connection to the SOCKS proxy
@@ -87,7 +121,7 @@ impl SocksTransport {
         let mut client = Socks5Client::new(socks_conn);
 
         client
-            .with_authentication(socks_config.authentication.clone())
+            .with_authentication(authentication)
             .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
 
         client
@@ -115,7 +149,8 @@ impl Transport for SocksTransport {
             return self.tcp_transport.dial(addr).await;
         }
 
-        let socket = Self::socks_connect(self.tcp_transport.clone(), &self.socks_config, addr).await?;
+        let authentication = self.authentication_for(addr);
+        let socket = Self::socks_connect(self.tcp_transport.clone(), &self.socks_config, authentication, addr).await?;
         Ok(socket)
     }
 }
@@ -132,9 +167,32 @@ mod test {
             proxy_address: proxy_address.clone(),
             authentication: Default::default(),
             proxy_bypass_predicate: Arc::new(FalsePredicate::new()),
+            isolate_streams: false,
         });
 
         assert_eq!(transport.socks_config.proxy_address, proxy_address);
         assert_eq!(transport.socks_config.authentication, Authentication::None);
     }
+
+    #[test]
+    fn stream_isolation() {
+        let transport = SocksTransport::new(SocksConfig {
+            proxy_address: "/ip4/127.0.0.1/tcp/9050".parse().unwrap(),
+            authentication: Default::default(),
+            proxy_bypass_predicate: Arc::new(FalsePredicate::new()),
+            isolate_streams: true,
+        });
+        let addr1 = "/ip4/1.2.3.4/tcp/18189".parse::<Multiaddr>().unwrap();
+        let addr2 = "/ip4/5.6.7.8/tcp/18189".parse::<Multiaddr>().unwrap();
+
+        let auth1 = transport.authentication_for(&addr1);
+        assert!(matches!(auth1, Authentication::Password { .. }));
+        assert_eq!(auth1, transport.authentication_for(&addr1));
+        assert_eq!(auth1, transport.clone().authentication_for(&addr1));
+        assert_ne!(auth1, transport.authentication_for(&addr2));
+
+        // A new transport does not reuse the credentials of a previous one
+        let other = SocksTransport::new(transport.socks_config.clone());
+        assert_ne!(auth1, other.authentication_for(&addr1));
+    }
 }
@@ -36,7 +36,7 @@ use tari_comms::{
     backoff::ConstantBackoff,
     connection_manager::{ConnectionDirection, ConnectionManagerEvent},
     connectivity::ConnectivitySelection,
-    peer_manager::{NodeId, NodeIdentity, Peer, PeerFeatures, PeerStorage},
+    peer_manager::{IndexedPeerStorage, NodeId, NodeIdentity, Peer, PeerFeatures},
     pipeline,
     pipeline::SinkService,
     protocol::{
@@ -832,7 +832,7 @@ fn create_peer_storage() -> CommsDatabase {
 
     let peer_database = datastore.get_handle(&database_name).unwrap();
     let peer_database = LMDBWrapper::new(Arc::new(peer_database));
-    PeerStorage::new_indexed(peer_database).unwrap().into()
+    IndexedPeerStorage::new_indexed(peer_database).unwrap().into()
 }
 
 pub async fn make_node(
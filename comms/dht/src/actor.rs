@@ -35,7 +35,16 @@ use log::*;
 use tari_comms::{
     connection_manager::ConnectionManagerError,
     connectivity::{ConnectivityError, ConnectivityRequester, ConnectivitySelection},
-    peer_manager::{NodeId, NodeIdentity, PeerFeatures, PeerManager, PeerManagerError, PeerQuery, PeerQuerySortBy},
+    peer_manager::{
+        NodeId,
+        NodeIdentity,
+        Peer,
+        PeerFeatures,
+        PeerManager,
+        PeerManagerError,
+        PeerQuery,
+        PeerQuerySortBy,
+    },
     types::CommsPublicKey,
     PeerConnection,
 };
@@ -112,6 +121,12 @@ pub enum DhtRequest {
     GetMsgHashHitCount(Vec<u8>, oneshot::Sender<u32>),
     /// Fetch selected peers according to the broadcast strategy
     SelectPeers(BroadcastStrategy, oneshot::Sender<Vec<NodeId>>),
+    /// Select random communication nodes that will relay an onion-routed message
+    SelectOnionRelays {
+        num_relays: usize,
+        excluded: Vec<NodeId>,
+        reply_tx: oneshot::Sender<Result<Vec<Peer>, DhtActorError>>,
+    },
     GetMetadata(DhtMetadataKey, oneshot::Sender<Result<Option<Vec<u8>>, DhtActorError>>),
     SetMetadata(DhtMetadataKey, Vec<u8>, oneshot::Sender<Result<(), DhtActorError>>),
     DialDiscoverPeer {
@@ -150,6 +165,14 @@ impl Display for DhtRequest {
             ),
             GetMsgHashHitCount(hash, _) => write!(f, "GetMsgHashHitCount({})", hash.to_hex()),
             SelectPeers(s, _) => write!(f, "SelectPeers (Strategy={})", s),
+            SelectOnionRelays {
+                num_relays, excluded, ..
+            } => write!(
+                f,
+                "SelectOnionRelays (num_relays={}, excluded={} peer(s))",
+                num_relays,
+                excluded.len()
+            ),
             GetMetadata(key, _) => write!(f, "GetMetadata (key={})", key),
             SetMetadata(key, value, _) => {
                 write!(f, "SetMetadata (key={}, value={} bytes)", key, value.len())
@@ -193,6 +216,24 @@ impl DhtRequester {
         reply_rx.await.map_err(|_| DhtActorError::ReplyCanceled)
     }
 
+    /// Selects up to `num_relays` random communication nodes, excluding the given peers, that can be used to relay an
+    /// onion-routed message.
+    pub async fn select_onion_relays(
+        &mut self,
+        num_relays: usize,
+        excluded: Vec<NodeId>,
+    ) -> Result<Vec<Peer>, DhtActorError> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.sender
+            .send(DhtRequest::SelectOnionRelays {
+                num_relays,
+                excluded,
+                reply_tx,
+            })
+            .await?;
+        reply_rx.await.map_err(|_| DhtActorError::ReplyCanceled)?
+    }
+
     /// Adds a message hash to the dedup cache.
     pub async fn add_message_to_dedup_cache(
         &mut self,
@@ -438,6 +479,22 @@ impl DhtActor {
                     }
                 })
             },
+            SelectOnionRelays {
+                num_relays,
+                excluded,
+                reply_tx,
+            } => {
+                let peer_manager = Arc::clone(&self.peer_manager);
+                Box::pin(async move {
+                    let _result = reply_tx.send(
+                        peer_manager
+                            .random_peers(num_relays, &excluded)
+                            .await
+                            .map_err(Into::into),
+                    );
+                    Ok(())
+                })
+            },
             GetMetadata(key, reply_tx) => {
                 let db = self.database.clone();
                 Box::pin(async move {
@@ -28,6 +28,8 @@ use tari_comms::peer_validator::PeerValidatorConfig;
 
 use crate::{
     actor::OffenceSeverity,
+    dandelion::DandelionConfig,
+    mdns::MdnsConfig,
     network_discovery::NetworkDiscoveryConfig,
     storage::DbConnectionUrl,
     store_forward::SafConfig,
@@ -83,6 +85,10 @@ pub struct DhtConfig {
     pub connectivity: DhtConnectivityConfig,
     /// Network discovery config
     pub network_discovery: NetworkDiscoveryConfig,
+    /// Dandelion++ stem/fluff routing config for broadcast transactions
+    pub dandelion: DandelionConfig,
+    /// Local network peer discovery using mDNS (requires the `mdns` feature)
+    pub mdns: MdnsConfig,
     /// Length of time to ban a peer if the peer misbehaves at the DHT-level.
     /// Default: 2 hrs
     #[serde(with = "serializers::seconds")]
@@ -181,6 +187,8 @@ impl Default for DhtConfig {
             auto_join: false,
             join_cooldown_interval: Duration::from_secs(10 * 60),
             network_discovery: Default::default(),
+            dandelion: Default::default(),
+            mdns: Default::default(),
             ban_duration: Duration::from_secs(2 * 60 * 60),
             ban_duration_short: Duration::from_secs(10 * 60),
             flood_ban_max_msg_count: 100_000,
@@ -0,0 +1,62 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tari_common::configuration::serializers;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DandelionConfig {
+    /// True to relay new messages along a stem before fluffing them, false to flood them immediately.
+    /// Default: false
+    pub enabled: bool,
+    /// The probability (0.0 to 1.0) that this node acts as a diffuser for an epoch, fluffing every stem message it
+    /// receives from peers.
+    /// Default: 0.1
+    pub fluff_probability: f64,
+    /// The number of stem relays selected for each epoch. Each inbound peer is mapped to one of these relays.
+    /// Default: 2
+    pub num_stem_relays: usize,
+    /// The length of an epoch, after which stem relays and the diffuser role are selected again.
+    /// Default: 10 mins
+    #[serde(with = "serializers::seconds")]
+    pub epoch_duration: Duration,
+    /// The minimum time to wait for a stemmed message to be seen on the network before fluffing it ourselves. A random
+    /// delay of up to half this value is added to each embargo.
+    /// Default: 30 secs
+    #[serde(with = "serializers::seconds")]
+    pub embargo_timeout: Duration,
+}
+
+impl Default for DandelionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            fluff_probability: 0.1,
+            num_stem_relays: 2,
+            epoch_duration: Duration::from_secs(10 * 60),
+            embargo_timeout: Duration::from_secs(30),
+        }
+    }
+}
@@ -0,0 +1,265 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{collections::HashMap, time::Instant};
+
+use log::*;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use tari_comms::peer_manager::NodeId;
+
+use super::DandelionConfig;
+
+const LOG_TARGET: &str = "comms::dht::dandelion";
+
+/// The propagation phase of a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DandelionPhase {
+    /// The message is relayed to a single peer
+    Stem,
+    /// The message is flooded to the network
+    Fluff,
+}
+
+/// Where a stem-phase message should be sent next.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DandelionRoute {
+    /// Relay the message to this peer, keeping it in the stem phase
+    Stem(NodeId),
+    /// Flood the message to the network
+    Fluff,
+}
+
+struct Epoch {
+    started_at: Instant,
+    is_diffuser: bool,
+    relays: Vec<NodeId>,
+    /// Maps the peer a message was received from (`None` for our own messages) to the relay it is forwarded to
+    routes: HashMap<Option<NodeId>, NodeId>,
+}
+
+/// Decides how stem-phase messages are routed. Relays and the diffuser role are fixed for the duration of an epoch so
+/// that messages from the same peer keep following the same path, which limits what an observer can learn from many
+/// messages.
+pub struct DandelionRouter {
+    config: DandelionConfig,
+    rng: StdRng,
+    epoch: Option<Epoch>,
+}
+
+impl DandelionRouter {
+    pub fn new(config: DandelionConfig) -> Self {
+        Self::with_rng(config, StdRng::from_entropy())
+    }
+
+    /// Create a router that uses the given RNG. A seeded RNG makes routing decisions reproducible.
+    pub fn with_rng(config: DandelionConfig, rng: StdRng) -> Self {
+        Self {
+            config,
+            rng,
+            epoch: None,
+        }
+    }
+
+    pub fn config(&self) -> &DandelionConfig {
+        &self.config
+    }
+
+    /// Returns the route for a stem-phase message received from `source` (`None` for messages originating from this
+    /// node). `candidates` are the peers that are currently available to act as relays.
+    pub fn route(&mut self, source: Option<&NodeId>, candidates: &[NodeId]) -> DandelionRoute {
+        if !self.config.enabled {
+            return DandelionRoute::Fluff;
+        }
+
+        self.update_epoch(candidates);
+        let Self { epoch, rng, .. } = self;
+        let Some(epoch) = epoch.as_mut() else {
+            return DandelionRoute::Fluff;
+        };
+        // Our own messages are always stemmed, otherwise a diffuser would reveal itself as the origin of the
+        // messages it fluffs.
+        if epoch.is_diffuser && source.is_some() {
+            return DandelionRoute::Fluff;
+        }
+
+        let is_usable = |relay: &NodeId| Some(relay) != source && candidates.contains(relay);
+        let key = source.cloned();
+        if let Some(relay) = epoch.routes.get(&key).filter(|r| is_usable(*r)) {
+            return DandelionRoute::Stem(relay.clone());
+        }
+
+        let usable = epoch.relays.iter().filter(|r| is_usable(*r)).collect::<Vec<_>>();
+        match usable.choose(rng) {
+            Some(relay) => {
+                let relay = (*relay).clone();
+                epoch.routes.insert(key, relay.clone());
+                DandelionRoute::Stem(relay)
+            },
+            None => {
+                debug!(target: LOG_TARGET, "No stem relay available, fluffing message");
+                DandelionRoute::Fluff
+            },
+        }
+    }
+
+    /// Removes a relay that failed to accept a stem message so that later messages are routed elsewhere. A new epoch
+    /// is started once every relay has failed.
+    pub fn record_failure(&mut self, relay: &NodeId) {
+        if let Some(epoch) = self.epoch.as_mut() {
+            debug!(target: LOG_TARGET, "Removing failed stem relay {}", relay);
+            epoch.relays.retain(|r| r != relay);
+            epoch.routes.retain(|_, r| r != relay);
+        }
+    }
+
+    /// Returns the time at which a message that is stemmed now should be fluffed if it has not yet been seen on the
+    /// network.
+    pub fn embargo_expiry(&mut self) -> Instant {
+        let jitter = (self.config.embargo_timeout / 2).mul_f64(self.rng.gen::<f64>());
+        Instant::now() + self.config.embargo_timeout + jitter
+    }
+
+    fn update_epoch(&mut self, candidates: &[NodeId]) {
+        let is_expired = self.epoch.as_ref().map_or(true, |e| {
+            e.relays.is_empty() || e.started_at.elapsed() >= self.config.epoch_duration
+        });
+        if !is_expired {
+            return;
+        }
+
+        let relays = candidates
+            .choose_multiple(&mut self.rng, self.config.num_stem_relays)
+            .cloned()
+            .collect::<Vec<_>>();
+        let is_diffuser = self.rng.gen_bool(self.config.fluff_probability.clamp(0.0, 1.0));
+        debug!(
+            target: LOG_TARGET,
+            "Starting new dandelion epoch as a {} with {} stem relay(s)",
+            if is_diffuser { "diffuser" } else { "relay" },
+            relays.len()
+        );
+        self.epoch = Some(Epoch {
+            started_at: Instant::now(),
+            is_diffuser,
+            relays,
+            routes: HashMap::new(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::HashSet, time::Duration};
+
+    use super::*;
+    use crate::test_utils::make_node_identity;
+
+    fn make_peers(n: usize) -> Vec<NodeId> {
+        (0..n).map(|_| make_node_identity().node_id().clone()).collect()
+    }
+
+    fn make_router(fluff_probability: f64) -> DandelionRouter {
+        let config = DandelionConfig {
+            enabled: true,
+            fluff_probability,
+            ..Default::default()
+        };
+        DandelionRouter::with_rng(config, StdRng::seed_from_u64(123))
+    }
+
+    #[test]
+    fn it_fluffs_when_disabled_or_without_relays() {
+        let peers = make_peers(4);
+        let mut router = DandelionRouter::with_rng(
+            DandelionConfig {
+                enabled: false,
+                ..Default::default()
+            },
+            StdRng::seed_from_u64(1),
+        );
+        assert_eq!(router.route(None, &peers), DandelionRoute::Fluff);
+
+        let mut router = make_router(0.0);
+        assert_eq!(router.route(None, &[]), DandelionRoute::Fluff);
+    }
+
+    #[test]
+    fn it_keeps_routes_stable_within_an_epoch() {
+        let peers = make_peers(8);
+        let mut router = make_router(0.0);
+        for source in &peers {
+            let first = router.route(Some(source), &peers);
+            let relay = match &first {
+                DandelionRoute::Stem(relay) => relay.clone(),
+                DandelionRoute::Fluff => panic!("expected stem route"),
+            };
+            assert_ne!(&relay, source);
+            for _ in 0..5 {
+                assert_eq!(router.route(Some(source), &peers), first);
+            }
+        }
+        let relays = peers
+            .iter()
+            .filter_map(|p| match router.route(Some(p), &peers) {
+                DandelionRoute::Stem(relay) => Some(relay),
+                DandelionRoute::Fluff => None,
+            })
+            .collect::<HashSet<_>>();
+        assert!(relays.len() <= 2);
+    }
+
+    #[test]
+    fn it_stems_own_messages_as_a_diffuser() {
+        let peers = make_peers(4);
+        let mut router = make_router(1.0);
+        assert_eq!(router.route(Some(&peers[0]), &peers), DandelionRoute::Fluff);
+        assert!(matches!(router.route(None, &peers), DandelionRoute::Stem(_)));
+    }
+
+    #[test]
+    fn it_reroutes_after_relay_failure() {
+        let peers = make_peers(4);
+        let mut router = make_router(0.0);
+        let DandelionRoute::Stem(first) = router.route(None, &peers) else {
+            panic!("expected stem route");
+        };
+        router.record_failure(&first);
+        let DandelionRoute::Stem(second) = router.route(None, &peers) else {
+            panic!("expected stem route");
+        };
+        assert_ne!(first, second);
+
+        // Once every relay has failed a new epoch selects new relays
+        router.record_failure(&second);
+        assert!(matches!(router.route(None, &peers), DandelionRoute::Stem(_)));
+    }
+
+    #[test]
+    fn it_adds_jitter_to_embargoes() {
+        let mut router = make_router(0.0);
+        let timeout = router.config().embargo_timeout;
+        let now = Instant::now();
+        let expiry = router.embargo_expiry();
+        assert!(expiry >= now + timeout);
+        assert!(expiry <= Instant::now() + timeout + timeout / 2 + Duration::from_millis(1));
+    }
+}
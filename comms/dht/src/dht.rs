@@ -37,9 +37,12 @@ use tokio::sync::{broadcast, mpsc};
 use tower::{layer::Layer, Service, ServiceBuilder};
 
 use self::outbound::OutboundMessageRequester;
+#[cfg(feature = "mdns")]
+use crate::mdns::MdnsDiscovery;
 use crate::{
     actor::{DhtActor, DhtRequest, DhtRequester},
     connectivity::{DhtConnectivity, MetricsCollector, MetricsCollectorHandle},
+    dandelion::DandelionRouter,
     discovery::{DhtDiscoveryRequest, DhtDiscoveryRequester, DhtDiscoveryService},
     event::{DhtEventReceiver, DhtEventSender},
     filter,
@@ -142,6 +145,9 @@ impl Dht {
             .map_err(DhtInitializationError::DatabaseMigrationFailed)?;
 
         dht.network_discovery_service(shutdown_signal.clone()).spawn();
+        if dht.config.mdns.enabled {
+            dht.spawn_mdns_discovery(shutdown_signal.clone());
+        }
         dht.connectivity_service(shutdown_signal.clone()).spawn();
         dht.store_and_forward_service(
             conn.clone(),
@@ -229,6 +235,28 @@ impl Dht {
         )
     }
 
+    /// Spawn the mDNS local network discovery service
+    #[cfg(feature = "mdns")]
+    fn spawn_mdns_discovery(&self, shutdown_signal: ShutdownSignal) {
+        MdnsDiscovery::new(
+            self.config.clone(),
+            Arc::clone(&self.node_identity),
+            Arc::clone(&self.peer_manager),
+            self.connectivity.clone(),
+            self.event_publisher.clone(),
+            shutdown_signal,
+        )
+        .spawn();
+    }
+
+    #[cfg(not(feature = "mdns"))]
+    fn spawn_mdns_discovery(&self, _shutdown_signal: ShutdownSignal) {
+        warn!(
+            target: LOG_TARGET,
+            "mDNS discovery is enabled in the config but this node was built without the `mdns` feature"
+        );
+    }
+
     fn store_and_forward_service(
         &self,
         conn: DbConnection,
@@ -270,6 +298,11 @@ impl Dht {
         StoreAndForwardRequester::new(self.saf_sender.clone())
     }
 
+    /// Returns a new Dandelion++ router using this instance's configuration
+    pub fn dandelion_router(&self) -> DandelionRouter {
+        DandelionRouter::new(self.config.dandelion)
+    }
+
     /// Get a subscription to `DhtEvents`
     pub fn subscribe_dht_events(&self) -> DhtEventReceiver {
         self.event_publisher.subscribe()
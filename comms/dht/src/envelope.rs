@@ -29,6 +29,7 @@ use std::{
 
 use bitflags::bitflags;
 use chrono::{DateTime, NaiveDateTime, Utc};
+use prost::Message;
 use prost_types::Timestamp;
 use serde::{Deserialize, Serialize};
 use tari_comms::{message::MessageTag, peer_manager::NodeId, types::CommsPublicKey, NodeIdentity};
@@ -50,14 +51,14 @@ pub(crate) fn datetime_to_timestamp(datetime: DateTime<Utc>) -> Timestamp {
 /// Utility function that converts a `prost::Timestamp` to a `chrono::DateTime<Utc>`
 pub(crate) fn timestamp_to_datetime(timestamp: Timestamp) -> Option<DateTime<Utc>> {
     let naive =
-        NaiveDateTime::from_timestamp_opt(timestamp.seconds, u32::try_from(cmp::max(0, timestamp.nanos)).unwrap())?;
+        NaiveDateTime::from_timestamp_opt(timestamp.seconds, u32::try_from(cmp::max(0, timestamp.nanos)).ok()?)?;
     Some(DateTime::from_utc(naive, Utc))
 }
 
-/// Utility function that converts a `chrono::DateTime` to a `EpochTime`
+/// Utility function that converts a `chrono::DateTime` to a `EpochTime`. Times before the unix epoch are clamped to
+/// the epoch.
 pub(crate) fn datetime_to_epochtime(datetime: DateTime<Utc>) -> EpochTime {
-    #[allow(clippy::cast_sign_loss)]
-    EpochTime::from_secs_since_epoch(datetime.timestamp() as u64)
+    EpochTime::from_secs_since_epoch(u64::try_from(datetime.timestamp()).unwrap_or(0))
 }
 
 /// Utility function that converts a `EpochTime` to a `chrono::DateTime`
@@ -88,6 +89,16 @@ pub enum DhtMessageError {
     HeaderOmitted,
     #[error("Message Body is empty")]
     BodyEmpty,
+    #[error("Failed to decode DHT envelope: {0}")]
+    InvalidEnvelope(#[from] prost::DecodeError),
+}
+
+/// Decodes a [DhtEnvelope] received from a peer and validates its header. This is the entry point for all untrusted
+/// DHT envelope bytes; malformed input results in a `DhtMessageError` and never panics.
+pub fn decode_dht_envelope(mut bytes: &[u8]) -> Result<(DhtMessageHeader, Vec<u8>), DhtMessageError> {
+    let envelope = DhtEnvelope::decode(&mut bytes)?;
+    let header = envelope.header.try_into()?;
+    Ok((header, envelope.body))
 }
 
 impl fmt::Display for DhtMessageType {
@@ -120,7 +131,7 @@ impl DhtMessageType {
     }
 
     pub fn is_dht_message(self) -> bool {
-        self.is_dht_discovery() || self.is_dht_discovery_response() || self.is_dht_join()
+        self.is_dht_discovery() || self.is_dht_discovery_response() || self.is_dht_join() || self.is_onion_relay()
     }
 
     pub fn is_forwardable(self) -> bool {
@@ -139,6 +150,10 @@ impl DhtMessageType {
         matches!(self, DhtMessageType::Join)
     }
 
+    pub fn is_onion_relay(self) -> bool {
+        matches!(self, DhtMessageType::OnionRelay)
+    }
+
     pub fn is_saf_message(self) -> bool {
         use DhtMessageType::{SafRequestMessages, SafStoredMessages};
         matches!(self, SafRequestMessages | SafStoredMessages)
@@ -408,4 +423,30 @@ mod tests {
             assert!(to_hex(&NodeDestination::PublicKey(Box::new(pk.clone())).to_inner_bytes()).contains(&pk.to_hex()));
         }
     }
+
+    mod decode_dht_envelope {
+        use super::*;
+
+        #[test]
+        fn it_rejects_malformed_envelopes() {
+            assert!(matches!(
+                decode_dht_envelope(&[0xff, 0xff, 0xff]),
+                Err(DhtMessageError::InvalidEnvelope(_))
+            ));
+            let envelope = DhtEnvelope {
+                header: None,
+                body: vec![1, 2, 3],
+            };
+            assert!(matches!(
+                decode_dht_envelope(&envelope.encode_to_vec()),
+                Err(DhtMessageError::HeaderOmitted)
+            ));
+        }
+    }
+
+    #[test]
+    fn it_clamps_pre_epoch_times() {
+        let dt = timestamp_to_datetime(Timestamp { seconds: -10, nanos: 0 }).unwrap();
+        assert_eq!(datetime_to_epochtime(dt).as_u64(), 0);
+    }
 }
@@ -20,15 +20,14 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{convert::TryInto, sync::Arc, task::Poll};
+use std::{sync::Arc, task::Poll};
 
 use futures::{future::BoxFuture, task::Context};
 use log::*;
-use prost::Message;
 use tari_comms::{message::InboundMessage, pipeline::PipelineError, OrNotFound, PeerManager};
 use tower::{layer::Layer, Service, ServiceExt};
 
-use crate::{inbound::DhtInboundMessage, proto::envelope::DhtEnvelope};
+use crate::{envelope::decode_dht_envelope, inbound::DhtInboundMessage};
 
 const LOG_TARGET: &str = "comms::dht::deserialize";
 
@@ -72,26 +71,22 @@ where
             trace!(target: LOG_TARGET, "Deserializing InboundMessage {}", message.tag);
 
             let InboundMessage {
-                source_peer,
-                mut body,
-                tag,
-                ..
+                source_peer, body, tag, ..
             } = message;
 
             if body.is_empty() {
                 return Err(anyhow::anyhow!("Received empty message from peer '{}'", source_peer));
             }
 
-            match DhtEnvelope::decode(&mut body) {
-                Ok(dht_envelope) => {
+            match decode_dht_envelope(&body) {
+                Ok((dht_header, dht_body)) => {
                     let source_peer = peer_manager
                         .find_by_node_id(&source_peer)
                         .await
                         .or_not_found()
                         .map(Arc::new)?;
 
-                    let inbound_msg =
-                        DhtInboundMessage::new(tag, dht_envelope.header.try_into()?, source_peer, dht_envelope.body);
+                    let inbound_msg = DhtInboundMessage::new(tag, dht_header, source_peer, dht_body);
                     trace!(
                         target: LOG_TARGET,
                         "Deserialization succeeded. Passing message {} onto next service (Trace: {})",
@@ -131,6 +126,8 @@ impl<S> Layer<S> for DeserializeLayer {
 
 #[cfg(test)]
 mod test {
+    use std::convert::TryInto;
+
     use tari_comms::message::{MessageExt, MessageTag};
 
     use super::*;
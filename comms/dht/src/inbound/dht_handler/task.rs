@@ -23,6 +23,7 @@
 use std::{convert::TryInto, sync::Arc};
 
 use log::*;
+use prost::Message;
 use tari_comms::{
     message::MessageExt,
     peer_manager::{NodeId, NodeIdentity, PeerManager},
@@ -36,13 +37,13 @@ use tower::{Service, ServiceExt};
 use crate::{
     actor::OffenceSeverity,
     discovery::DhtDiscoveryRequester,
-    envelope::NodeDestination,
+    envelope::{DhtMessageError, DhtMessageHeader, NodeDestination},
     inbound::{error::DhtInboundError, message::DecryptedDhtMessage},
     outbound::{OutboundMessageRequester, SendMessageParams},
     peer_validator::{DhtPeerValidatorError, PeerValidator},
     proto::{
-        dht::{DiscoveryMessage, DiscoveryResponseMessage, JoinMessage},
-        envelope::DhtMessageType,
+        dht::{DiscoveryMessage, DiscoveryResponseMessage, JoinMessage, OnionRelayMessage},
+        envelope::{DhtEnvelope, DhtMessageType},
     },
     rpc::UnvalidatedPeerInfo,
     DhtConfig,
@@ -128,6 +129,7 @@ where S: Service<DecryptedDhtMessage, Response = (), Error = PipelineError>
             DhtMessageType::Join => self.handle_join(message).await?,
             DhtMessageType::Discovery => self.handle_discover(message).await?,
             DhtMessageType::DiscoveryResponse => self.handle_discover_response(message).await?,
+            DhtMessageType::OnionRelay => self.handle_onion_relay(message).await?,
             // Not a DHT message, call downstream middleware
             _ => {
                 trace!(
@@ -417,6 +419,64 @@ where S: Service<DecryptedDhtMessage, Response = (), Error = PipelineError>
         Ok(())
     }
 
+    /// Relay a single onion layer to the next hop. The wrapped envelope is sent unmodified, so this node is only able
+    /// to determine the previous and next hop of the message.
+    async fn handle_onion_relay(&mut self, message: DecryptedDhtMessage) -> Result<(), DhtInboundError> {
+        // Onion layers are always encrypted for the relay. A cleartext layer would reveal the route to observers.
+        if !message.dht_header.flags.is_encrypted() {
+            warn!(
+                target: LOG_TARGET,
+                "Discarding unencrypted onion relay message from peer '{}'",
+                message.source_peer.node_id.short_str()
+            );
+            return Err(DhtInboundError::InvalidOnionRelayMessage(
+                "onion relay message was not encrypted".to_string(),
+            ));
+        }
+
+        let msg = message
+            .success()
+            .expect("already checked that this message decrypted successfully");
+
+        let relay_msg = msg
+            .decode_part::<OnionRelayMessage>(0)?
+            .ok_or(DhtInboundError::InvalidMessageBody)?;
+        let next_hop_public_key = CommsPublicKey::from_bytes(&relay_msg.next_hop_public_key)
+            .map_err(|e| DhtInboundError::InvalidOnionRelayMessage(e.to_string()))?;
+        let envelope = DhtEnvelope::decode(relay_msg.envelope.as_slice())
+            .map_err(|e| DhtInboundError::InvalidOnionRelayMessage(e.to_string()))?;
+        let header: DhtMessageHeader = envelope
+            .header
+            .try_into()
+            .map_err(|e: DhtMessageError| DhtInboundError::InvalidOnionRelayMessage(e.to_string()))?;
+        if !header.is_semantically_valid() {
+            return Err(DhtInboundError::InvalidOnionRelayMessage(
+                "wrapped envelope header is not semantically valid".to_string(),
+            ));
+        }
+
+        debug!(
+            target: LOG_TARGET,
+            "Relaying onion-routed message from peer '{}' to '{}' (Trace: {})",
+            message.source_peer.node_id.short_str(),
+            next_hop_public_key,
+            message.dht_header.message_tag,
+        );
+        self.outbound_service
+            .send_raw_no_wait(
+                SendMessageParams::new()
+                    .direct_public_key(next_hop_public_key)
+                    .with_discovery(true)
+                    .with_dht_header(header)
+                    .with_debug_info("Relaying onion-routed message".to_string())
+                    .finish(),
+                envelope.body.as_slice().into(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
     /// Send a `DiscoveryResponseMessage` in response to a `DiscoveryMessage` to the given public key
     /// using the given nonce which should come from the `DiscoveryMessage`
     async fn send_discovery_response(
@@ -487,6 +547,7 @@ where S: Service<DecryptedDhtMessage, Response = (), Error = PipelineError>
                             .await;
                     },
                     DhtInboundError::ConnectivityError(_) => {},
+                    DhtInboundError::InvalidOnionRelayMessage(_) => {},
                 }
                 Err(err)
             },
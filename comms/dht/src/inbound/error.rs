@@ -45,4 +45,6 @@ pub enum DhtInboundError {
     InvalidDiscoveryMessage(#[from] anyhow::Error),
     #[error("ConnectivityError: {0}")]
     ConnectivityError(#[from] ConnectivityError),
+    #[error("Invalid onion relay message: {0}")]
+    InvalidOnionRelayMessage(String),
 }
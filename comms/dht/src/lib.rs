@@ -97,6 +97,9 @@ pub use error::DhtEncryptError;
 mod network_discovery;
 pub use network_discovery::NetworkDiscoveryConfig;
 
+mod mdns;
+pub use mdns::MdnsConfig;
+
 mod storage;
 pub use storage::DbConnectionUrl;
 
@@ -115,6 +118,7 @@ mod version;
 pub use version::DhtProtocolVersion;
 
 pub mod broadcast_strategy;
+pub mod dandelion;
 pub mod domain_message;
 pub mod envelope;
 pub mod event;
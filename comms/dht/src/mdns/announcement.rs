@@ -0,0 +1,121 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{collections::HashMap, convert::TryInto};
+
+use prost::Message;
+use tari_comms::message::MessageExt;
+use tari_utilities::hex::{from_hex, to_hex};
+
+use super::MdnsDiscoveryError;
+use crate::{proto::rpc, rpc::UnvalidatedPeerInfo};
+
+const ANNOUNCEMENT_VERSION: &str = "1";
+const VERSION_KEY: &str = "v";
+const NUM_CHUNKS_KEY: &str = "n";
+/// A TXT record string may not exceed 255 bytes, including the key
+const MAX_CHUNK_LEN: usize = 200;
+/// Announcements are a single peer identity claim, which comfortably fits in this many chunks
+const MAX_NUM_CHUNKS: usize = 16;
+
+/// Encodes the peer info as TXT record properties. The hex encoded `PeerInfo` message is split into numbered chunks
+/// to stay within the TXT record size limit.
+pub fn encode_announcement(peer_info: UnvalidatedPeerInfo) -> HashMap<String, String> {
+    let encoded = to_hex(&rpc::PeerInfo::from(peer_info).to_encoded_bytes());
+    let mut properties = HashMap::new();
+    properties.insert(VERSION_KEY.to_string(), ANNOUNCEMENT_VERSION.to_string());
+    let mut num_chunks = 0usize;
+    for (i, chunk) in encoded.as_bytes().chunks(MAX_CHUNK_LEN).enumerate() {
+        properties.insert(chunk_key(i), String::from_utf8_lossy(chunk).into_owned());
+        num_chunks += 1;
+    }
+    properties.insert(NUM_CHUNKS_KEY.to_string(), num_chunks.to_string());
+    properties
+}
+
+/// Decodes peer info from the TXT record properties of an announcement, using `get_property` to look up each
+/// property value.
+pub fn decode_announcement<'a, F>(get_property: F) -> Result<UnvalidatedPeerInfo, MdnsDiscoveryError>
+where F: Fn(&str) -> Option<&'a str> {
+    let version = get_property(VERSION_KEY).ok_or_else(|| invalid("missing version"))?;
+    if version != ANNOUNCEMENT_VERSION {
+        return Err(invalid(&format!("unsupported version '{}'", version)));
+    }
+    let num_chunks = get_property(NUM_CHUNKS_KEY)
+        .and_then(|n| n.parse::<usize>().ok())
+        .ok_or_else(|| invalid("missing or invalid chunk count"))?;
+    if num_chunks == 0 || num_chunks > MAX_NUM_CHUNKS {
+        return Err(invalid(&format!("invalid chunk count {}", num_chunks)));
+    }
+
+    let mut encoded = String::with_capacity(num_chunks * MAX_CHUNK_LEN);
+    for i in 0..num_chunks {
+        let chunk = get_property(&chunk_key(i)).ok_or_else(|| invalid(&format!("missing chunk {}", i)))?;
+        encoded.push_str(chunk);
+    }
+
+    let bytes = from_hex(&encoded).map_err(|err| invalid(&err.to_string()))?;
+    let peer_info = rpc::PeerInfo::decode(bytes.as_slice()).map_err(|err| invalid(&err.to_string()))?;
+    peer_info
+        .try_into()
+        .map_err(|err: anyhow::Error| invalid(&err.to_string()))
+}
+
+fn chunk_key(i: usize) -> String {
+    format!("p{}", i)
+}
+
+fn invalid(details: &str) -> MdnsDiscoveryError {
+    MdnsDiscoveryError::InvalidAnnouncement(details.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::make_node_identity;
+
+    #[test]
+    fn it_encodes_and_decodes_the_peer_info() {
+        let node_identity = make_node_identity();
+        let peer_info = UnvalidatedPeerInfo::from_peer_limited_claims(node_identity.to_peer(), 1, 5);
+        let addresses = peer_info.claims[0].addresses.clone();
+        let properties = encode_announcement(peer_info);
+        assert!(properties.values().all(|v| v.len() <= MAX_CHUNK_LEN));
+
+        let decoded = decode_announcement(|key| properties.get(key).map(String::as_str)).unwrap();
+        assert_eq!(decoded.public_key, *node_identity.public_key());
+        assert_eq!(decoded.claims.len(), 1);
+        assert_eq!(decoded.claims[0].addresses, addresses);
+        assert!(decoded.claims[0].is_valid(node_identity.public_key()));
+    }
+
+    #[test]
+    fn it_rejects_incomplete_announcements() {
+        let node_identity = make_node_identity();
+        let peer_info = UnvalidatedPeerInfo::from_peer_limited_claims(node_identity.to_peer(), 1, 5);
+        let mut properties = encode_announcement(peer_info);
+        properties.remove(&chunk_key(0));
+
+        let err = decode_announcement(|key| properties.get(key).map(String::as_str)).unwrap_err();
+        assert!(matches!(err, MdnsDiscoveryError::InvalidAnnouncement(_)));
+    }
+}
@@ -0,0 +1,46 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! # mDNS Local Peer Discovery
+//!
+//! Discovers peers on the local network using multicast DNS, so that nodes on the same LAN can find each other
+//! without Tor or public seed peers (e.g. workshops and air-gapped lab networks).
+//!
+//! Each communication node registers an mDNS service whose TXT records carry its signed peer identity claim. Peers
+//! resolved on the local network are validated in the same way as peers received through peer sync before being
+//! added to the peer manager. Discovery requires the `mdns` feature and is disabled by default.
+
+mod config;
+pub use config::MdnsConfig;
+
+#[cfg(feature = "mdns")]
+mod announcement;
+
+#[cfg(feature = "mdns")]
+mod error;
+#[cfg(feature = "mdns")]
+pub use error::MdnsDiscoveryError;
+
+#[cfg(feature = "mdns")]
+mod service;
+#[cfg(feature = "mdns")]
+pub use service::MdnsDiscovery;
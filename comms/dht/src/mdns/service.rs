@@ -0,0 +1,205 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::sync::Arc;
+
+use log::*;
+use mdns_sd::{ServiceDaemon, ServiceEvent, ServiceInfo};
+use tari_comms::{
+    connectivity::ConnectivityRequester,
+    multiaddr::Protocol,
+    peer_manager::{NodeIdentity, PeerManager},
+};
+use tari_shutdown::ShutdownSignal;
+use tari_utilities::hex::Hex;
+use tokio::task;
+
+use super::{
+    announcement::{decode_announcement, encode_announcement},
+    MdnsDiscoveryError,
+};
+use crate::{
+    event::{DhtEvent, DhtEventSender},
+    network_discovery::DhtNetworkDiscoveryRoundInfo,
+    peer_validator::PeerValidator,
+    rpc::UnvalidatedPeerInfo,
+    DhtConfig,
+};
+
+const LOG_TARGET: &str = "comms::dht::mdns";
+
+/// Announces this node and discovers peers on the local network using mDNS.
+pub struct MdnsDiscovery {
+    config: Arc<DhtConfig>,
+    node_identity: Arc<NodeIdentity>,
+    peer_manager: Arc<PeerManager>,
+    connectivity: ConnectivityRequester,
+    event_tx: DhtEventSender,
+    shutdown_signal: ShutdownSignal,
+}
+
+impl MdnsDiscovery {
+    pub fn new(
+        config: Arc<DhtConfig>,
+        node_identity: Arc<NodeIdentity>,
+        peer_manager: Arc<PeerManager>,
+        connectivity: ConnectivityRequester,
+        event_tx: DhtEventSender,
+        shutdown_signal: ShutdownSignal,
+    ) -> Self {
+        Self {
+            config,
+            node_identity,
+            peer_manager,
+            connectivity,
+            event_tx,
+            shutdown_signal,
+        }
+    }
+
+    pub fn spawn(self) -> task::JoinHandle<()> {
+        task::spawn(async move {
+            if let Err(err) = self.run().await {
+                error!(target: LOG_TARGET, "mDNS discovery failed: {}", err);
+            }
+        })
+    }
+
+    async fn run(self) -> Result<(), MdnsDiscoveryError> {
+        let mut shutdown_signal = self.shutdown_signal.clone();
+        let daemon = ServiceDaemon::new()?;
+        let service_type = self.config.mdns.service_type.clone();
+
+        let own_fullname = match self.own_service_info(&service_type)? {
+            Some(info) => {
+                let fullname = info.get_fullname().to_string();
+                daemon.register(info)?;
+                info!(target: LOG_TARGET, "Announcing this node on the local network as '{}'", fullname);
+                Some(fullname)
+            },
+            None => {
+                debug!(
+                    target: LOG_TARGET,
+                    "This node has no public addresses or is a client, not announcing on the local network"
+                );
+                None
+            },
+        };
+
+        let events = daemon.browse(&service_type)?;
+        loop {
+            tokio::select! {
+                _ = shutdown_signal.wait() => {
+                    break;
+                },
+                event = events.recv_async() => match event {
+                    Ok(ServiceEvent::ServiceResolved(info)) => {
+                        if own_fullname.as_deref() == Some(info.get_fullname()) {
+                            continue;
+                        }
+                        if let Err(err) = self.handle_resolved_service(&info).await {
+                            debug!(
+                                target: LOG_TARGET,
+                                "Ignoring local peer '{}': {}",
+                                info.get_fullname(),
+                                err
+                            );
+                        }
+                    },
+                    Ok(_) => {},
+                    Err(_) => {
+                        warn!(target: LOG_TARGET, "mDNS daemon stopped unexpectedly");
+                        break;
+                    },
+                },
+            }
+        }
+
+        if let Some(fullname) = own_fullname {
+            let _result = daemon.unregister(&fullname);
+        }
+        let _result = daemon.shutdown();
+        Ok(())
+    }
+
+    fn own_service_info(&self, service_type: &str) -> Result<Option<ServiceInfo>, MdnsDiscoveryError> {
+        let addresses = self.node_identity.public_addresses();
+        if self.node_identity.features().is_client() || addresses.is_empty() {
+            return Ok(None);
+        }
+
+        let port = addresses
+            .iter()
+            .flat_map(|addr| addr.iter())
+            .find_map(|proto| match proto {
+                Protocol::Tcp(port) => Some(port),
+                _ => None,
+            })
+            .unwrap_or(0);
+        let peer_info = UnvalidatedPeerInfo::from_peer_limited_claims(
+            self.node_identity.to_peer(),
+            1,
+            self.config.peer_validator_config.max_permitted_peer_addresses_per_claim,
+        );
+        let instance_name = self.node_identity.node_id().to_hex();
+        let host_name = format!("{}.local.", instance_name);
+        let info = ServiceInfo::new(
+            service_type,
+            &instance_name,
+            &host_name,
+            "",
+            port,
+            encode_announcement(peer_info),
+        )?
+        .enable_addr_auto();
+
+        Ok(Some(info))
+    }
+
+    async fn handle_resolved_service(&self, info: &ServiceInfo) -> Result<(), MdnsDiscoveryError> {
+        let peer_info = decode_announcement(|key| info.get_property_val_str(key))?;
+        if peer_info.public_key == *self.node_identity.public_key() {
+            return Ok(());
+        }
+
+        let existing_peer = self.peer_manager.find_by_public_key(&peer_info.public_key).await?;
+        let is_new_peer = existing_peer.is_none();
+        let peer = PeerValidator::new(&self.config).validate_peer(peer_info, existing_peer)?;
+        let node_id = peer.node_id.clone();
+        self.peer_manager.add_peer(peer).await?;
+
+        if is_new_peer {
+            info!(target: LOG_TARGET, "Discovered local peer `{}`", node_id);
+            let _result = self.event_tx.send(Arc::new(DhtEvent::NetworkDiscoveryPeersAdded(
+                DhtNetworkDiscoveryRoundInfo {
+                    num_new_peers: 1,
+                    num_duplicate_peers: 0,
+                    num_succeeded: 1,
+                    sync_peers: vec![],
+                },
+            )));
+            self.connectivity.request_many_dials([node_id]).await?;
+        }
+
+        Ok(())
+    }
+}
@@ -35,7 +35,8 @@ use tari_comms::{
     message::{MessageExt, MessageTag},
     peer_manager::{NodeId, NodeIdentity, Peer},
     pipeline::PipelineError,
-    types::{CommsDHKE, CommsPublicKey},
+    types::{CommsDHKE, CommsPublicKey, CommsSecretKey},
+    wrap_in_envelope_body,
     Bytes,
     BytesMut,
 };
@@ -59,7 +60,10 @@ use crate::{
         message_send_state::MessageSendState,
         SendMessageResponse,
     },
-    proto::envelope::DhtMessageType,
+    proto::{
+        dht::OnionRelayMessage,
+        envelope::{DhtEnvelope, DhtHeader, DhtMessageType},
+    },
     version::DhtProtocolVersion,
     DhtConfig,
 };
@@ -254,6 +258,10 @@ where S: Service<DhtOutboundMessage, Response = (), Error = PipelineError>
             return Err(DhtOutboundError::SendToOurselves);
         }
 
+        if params.onion_hops > 0 {
+            return self.handle_onion_routed_message(params, body, reply_tx).await;
+        }
+
         let FinalSendMessageParams {
             broadcast_strategy,
             destination,
@@ -265,6 +273,7 @@ where S: Service<DhtOutboundMessage, Response = (), Error = PipelineError>
             dht_header,
             debug_info: _,
             tag,
+            onion_hops: _,
         } = params;
 
         match self.select_peers(broadcast_strategy.clone()).await {
@@ -456,6 +465,226 @@ where S: Service<DhtOutboundMessage, Response = (), Error = PipelineError>
         Ok(messages.unzip())
     }
 
+    async fn handle_onion_routed_message(
+        &mut self,
+        params: FinalSendMessageParams,
+        body: BytesMut,
+        reply_tx: oneshot::Sender<SendMessageResponse>,
+    ) -> Result<Vec<DhtOutboundMessage>, DhtOutboundError> {
+        if params.dht_header.is_some() {
+            let err = DhtOutboundError::OnionRoutingNotSupported("custom DHT headers cannot be onion routed");
+            let _result = reply_tx.send(SendMessageResponse::Failed(SendFailure::General(err.to_string())));
+            return Err(err);
+        }
+
+        let expires = Utc::now() + self.message_validity_window;
+        match self.generate_onion_routed_message(params, body, expires).await {
+            Ok((msg, send_state)) => {
+                let _result = reply_tx.send(SendMessageResponse::Queued(vec![send_state].into()));
+                Ok(vec![msg])
+            },
+            Err(err) => {
+                let _result = reply_tx.send(SendMessageResponse::Failed(SendFailure::FailedToGenerateMessages(
+                    err.to_string(),
+                )));
+                Err(err)
+            },
+        }
+    }
+
+    /// Wraps the message in `num_hops` layers of encryption, one for each randomly selected relay, and returns the
+    /// outermost layer addressed to the first relay. Each layer is signed by a throwaway identity so that relays
+    /// cannot determine the origin of the message. Only the innermost message, encrypted for the recipient, is
+    /// signed by this node.
+    async fn generate_onion_routed_message(
+        &mut self,
+        params: FinalSendMessageParams,
+        body: BytesMut,
+        expires: DateTime<Utc>,
+    ) -> Result<(DhtOutboundMessage, MessageSendState), DhtOutboundError> {
+        let FinalSendMessageParams {
+            broadcast_strategy,
+            destination,
+            dht_message_type,
+            dht_message_flags,
+            encryption,
+            force_origin,
+            tag,
+            onion_hops: num_hops,
+            ..
+        } = params;
+
+        let recipient_public_key = match &encryption {
+            OutboundEncryption::EncryptFor(pk) if broadcast_strategy.direct_public_key() == Some(&**pk) => {
+                (**pk).clone()
+            },
+            _ => {
+                return Err(DhtOutboundError::OnionRoutingNotSupported(
+                    "the message must be encrypted for a direct public key recipient",
+                ))
+            },
+        };
+
+        let mut relays = self.select_onion_relays(num_hops, &recipient_public_key).await?;
+        let first_relay = relays.remove(0);
+
+        let expires_epochtime = Some(datetime_to_epochtime(expires));
+        let expires = Some(datetime_to_timestamp(expires));
+
+        // The innermost message is encrypted for the recipient and signed by this node
+        let dht_flags = encryption.flags() | dht_message_flags;
+        let parts = self.process_encryption(
+            &encryption,
+            force_origin,
+            &destination,
+            dht_message_type,
+            dht_flags,
+            expires_epochtime,
+            body,
+        )?;
+        let mut envelope = self.create_envelope(parts, destination, dht_message_type, dht_flags, expires.clone());
+        let mut next_hop_public_key = recipient_public_key;
+
+        // Wrap the message for each relay, starting with the relay closest to the recipient
+        for relay in relays.into_iter().rev() {
+            let relay_destination = NodeDestination::PublicKey(Box::new(relay.public_key.clone()));
+            let parts = self.encrypt_onion_layer(
+                &relay.public_key,
+                &relay_destination,
+                &next_hop_public_key,
+                &envelope,
+                expires_epochtime,
+            )?;
+            envelope = self.create_envelope(
+                parts,
+                relay_destination,
+                DhtMessageType::OnionRelay,
+                DhtMessageFlags::ENCRYPTED,
+                expires.clone(),
+            );
+            next_hop_public_key = relay.public_key;
+        }
+
+        // The outermost layer is sent directly to the first relay
+        let relay_destination = NodeDestination::PublicKey(Box::new(first_relay.public_key.clone()));
+        let (ephemeral_public_key, message_signature, encrypted_layer) = self.encrypt_onion_layer(
+            &first_relay.public_key,
+            &relay_destination,
+            &next_hop_public_key,
+            &envelope,
+            expires_epochtime,
+        )?;
+        trace!(
+            target: LOG_TARGET,
+            "Sending onion-routed message via {} relay(s) starting with peer {}",
+            num_hops,
+            first_relay.node_id.short_str()
+        );
+
+        let (reply_tx, reply_rx) = oneshot::channel();
+        let tag = tag.unwrap_or_else(MessageTag::new);
+        let send_state = MessageSendState::new(tag, reply_rx);
+        Ok((
+            DhtOutboundMessage {
+                protocol_version: self.protocol_version,
+                tag,
+                destination_node_id: first_relay.node_id,
+                destination: relay_destination,
+                dht_message_type: DhtMessageType::OnionRelay,
+                dht_flags: DhtMessageFlags::ENCRYPTED,
+                custom_header: None,
+                body: encrypted_layer,
+                reply: reply_tx.into(),
+                ephemeral_public_key,
+                message_signature,
+                is_broadcast: false,
+                expires,
+            },
+            send_state,
+        ))
+    }
+
+    /// Selects exactly `num_hops` relays, excluding this node and the recipient
+    async fn select_onion_relays(
+        &mut self,
+        num_hops: usize,
+        recipient_public_key: &CommsPublicKey,
+    ) -> Result<Vec<Peer>, DhtOutboundError> {
+        let excluded = vec![
+            self.node_identity.node_id().clone(),
+            NodeId::from_public_key(recipient_public_key),
+        ];
+        let mut relays = self
+            .dht_requester
+            .select_onion_relays(num_hops, excluded)
+            .await
+            .map_err(|err| {
+                error!(target: LOG_TARGET, "{}", err);
+                DhtOutboundError::PeerSelectionFailed
+            })?;
+        if relays.len() < num_hops {
+            return Err(DhtOutboundError::InsufficientOnionRelays {
+                required: num_hops,
+                available: relays.len(),
+            });
+        }
+        relays.truncate(num_hops);
+        Ok(relays)
+    }
+
+    fn create_envelope(
+        &self,
+        (ephemeral_public_key, message_signature, body): FinalMessageParts,
+        destination: NodeDestination,
+        message_type: DhtMessageType,
+        flags: DhtMessageFlags,
+        expires: Option<prost_types::Timestamp>,
+    ) -> DhtEnvelope {
+        DhtEnvelope::new(
+            DhtHeader {
+                major: self.protocol_version.as_major(),
+                message_signature: message_signature.map(|b| b.to_vec()).unwrap_or_default(),
+                ephemeral_public_key: ephemeral_public_key.map(|e| e.to_vec()).unwrap_or_default(),
+                message_type: message_type.into(),
+                flags: flags.bits(),
+                destination: Some(destination.into()),
+                // Each layer has a new message tag so that layers cannot be correlated
+                message_tag: MessageTag::new().as_value(),
+                expires,
+            },
+            body.to_vec(),
+        )
+    }
+
+    /// Encrypts a single onion layer for a relay. The layer is signed by a throwaway identity so that the relay cannot
+    /// determine the origin of the message.
+    fn encrypt_onion_layer(
+        &self,
+        relay_public_key: &CommsPublicKey,
+        relay_destination: &NodeDestination,
+        next_hop_public_key: &CommsPublicKey,
+        envelope: &DhtEnvelope,
+        expires: Option<EpochTime>,
+    ) -> Result<FinalMessageParts, DhtOutboundError> {
+        let layer = wrap_in_envelope_body!(OnionRelayMessage {
+            next_hop_public_key: next_hop_public_key.to_vec(),
+            envelope: envelope.to_encoded_bytes(),
+        });
+        let body = crypt::prepare_message(true, &layer)?;
+        let (throwaway_secret_key, throwaway_public_key) = CommsPublicKey::random_keypair(&mut OsRng);
+        encrypt_for_recipient(
+            self.protocol_version,
+            &throwaway_secret_key,
+            &throwaway_public_key,
+            relay_public_key,
+            relay_destination,
+            DhtMessageType::OnionRelay,
+            DhtMessageFlags::ENCRYPTED,
+            expires,
+            body,
+        )
+    }
+
     async fn add_to_dedup_cache(&mut self, hash: [u8; 32]) -> Result<(), DhtOutboundError> {
         trace!(
             target: LOG_TARGET,
@@ -486,47 +715,23 @@ where S: Service<DhtOutboundMessage, Response = (), Error = PipelineError>
         message_type: DhtMessageType,
         flags: DhtMessageFlags,
         expires: Option<EpochTime>,
-        mut body: BytesMut,
+        body: BytesMut,
     ) -> Result<FinalMessageParts, DhtOutboundError> {
         match encryption {
             // Encrypt the message, protecting the sender identity
             OutboundEncryption::EncryptFor(recipient_public_key) => {
                 trace!(target: LOG_TARGET, "Encrypting message for {}", recipient_public_key);
-
-                // Perform an ephemeral ECDH exchange against the recipient public key
-                let (ephemeral_secret_key, ephemeral_public_key) = CommsPublicKey::random_keypair(&mut OsRng);
-                let shared_ephemeral_secret = CommsDHKE::new(&ephemeral_secret_key, &**recipient_public_key);
-
-                // Produce a masked sender public key using an offset mask derived from the ECDH exchange
-                let mask = crypt::generate_key_mask(&shared_ephemeral_secret)
-                    .map_err(|e| DhtOutboundError::CipherError(e.to_string()))?;
-                let masked_sender_public_key = &mask * self.node_identity.public_key();
-
-                // Pad and encrypt the message using the masked sender public key
-                let key_message = crypt::generate_key_message(&shared_ephemeral_secret);
-                crypt::encrypt_message(&key_message, &mut body, masked_sender_public_key.as_bytes())?;
-                let encrypted_body = body.freeze();
-
-                // Produce a hash that binds the message and metadata
-                let binding_hash = crypt::create_message_domain_separated_hash_parts(
+                encrypt_for_recipient(
                     self.protocol_version,
+                    self.node_identity.secret_key(),
+                    self.node_identity.public_key(),
+                    recipient_public_key,
                     destination,
                     message_type,
                     flags,
                     expires,
-                    Some(&ephemeral_public_key),
-                    &encrypted_body,
-                );
-
-                // Sign the encrypted message using the masked sender key
-                let masked_sender_secret_key = mask * self.node_identity.secret_key();
-                let signature = MessageSignature::new_signed(masked_sender_secret_key, &binding_hash).to_proto();
-
-                Ok((
-                    Some(Arc::new(ephemeral_public_key)),
-                    Some(signature.to_encoded_bytes().into()), // this includes the masked signer public key
-                    encrypted_body,
-                ))
+                    body,
+                )
             },
             // Keep the message unencrypted
             OutboundEncryption::ClearText => {
@@ -555,6 +760,55 @@ where S: Service<DhtOutboundMessage, Response = (), Error = PipelineError>
     }
 }
 
+/// Encrypts the message for the recipient, protecting the sender identity. The message is signed by a masked version of
+/// the given sender key that only the recipient is able to unmask.
+fn encrypt_for_recipient(
+    protocol_version: DhtProtocolVersion,
+    sender_secret_key: &CommsSecretKey,
+    sender_public_key: &CommsPublicKey,
+    recipient_public_key: &CommsPublicKey,
+    destination: &NodeDestination,
+    message_type: DhtMessageType,
+    flags: DhtMessageFlags,
+    expires: Option<EpochTime>,
+    mut body: BytesMut,
+) -> Result<FinalMessageParts, DhtOutboundError> {
+    // Perform an ephemeral ECDH exchange against the recipient public key
+    let (ephemeral_secret_key, ephemeral_public_key) = CommsPublicKey::random_keypair(&mut OsRng);
+    let shared_ephemeral_secret = CommsDHKE::new(&ephemeral_secret_key, recipient_public_key);
+
+    // Produce a masked sender public key using an offset mask derived from the ECDH exchange
+    let mask =
+        crypt::generate_key_mask(&shared_ephemeral_secret).map_err(|e| DhtOutboundError::CipherError(e.to_string()))?;
+    let masked_sender_public_key = &mask * sender_public_key;
+
+    // Pad and encrypt the message using the masked sender public key
+    let key_message = crypt::generate_key_message(&shared_ephemeral_secret);
+    crypt::encrypt_message(&key_message, &mut body, masked_sender_public_key.as_bytes())?;
+    let encrypted_body = body.freeze();
+
+    // Produce a hash that binds the message and metadata
+    let binding_hash = crypt::create_message_domain_separated_hash_parts(
+        protocol_version,
+        destination,
+        message_type,
+        flags,
+        expires,
+        Some(&ephemeral_public_key),
+        &encrypted_body,
+    );
+
+    // Sign the encrypted message using the masked sender key
+    let masked_sender_secret_key = mask * sender_secret_key;
+    let signature = MessageSignature::new_signed(masked_sender_secret_key, &binding_hash).to_proto();
+
+    Ok((
+        Some(Arc::new(ephemeral_public_key)),
+        Some(signature.to_encoded_bytes().into()), // this includes the masked signer public key
+        encrypted_body,
+    ))
+}
+
 #[cfg(test)]
 mod test {
     use std::time::Duration;
@@ -746,4 +1000,75 @@ mod test {
         assert_eq!(tags.len(), 1);
         assert_eq!(spy.call_count(), 1);
     }
+
+    #[tokio::test]
+    async fn test_send_message_onion_routed() {
+        let node_identity = NodeIdentity::random(
+            &mut OsRng,
+            "/ip4/127.0.0.1/tcp/9000".parse().unwrap(),
+            PeerFeatures::COMMUNICATION_NODE,
+        );
+        let (dht_requester, dht_mock) = create_dht_actor_mock(10);
+        let mock_state = dht_mock.get_shared_state();
+        let relays = vec![make_peer(), make_peer()];
+        let recipient = make_peer();
+        mock_state.set_select_peers_response(vec![relays[0].clone(), recipient.clone(), relays[1].clone()]);
+        task::spawn(dht_mock.run());
+        let (dht_discover_requester, _) = create_dht_discovery_mock(Duration::from_secs(10));
+        let spy = service_spy();
+
+        let mut service = BroadcastMiddleware::new(
+            spy.to_service::<PipelineError>(),
+            Arc::new(node_identity),
+            dht_requester,
+            dht_discover_requester,
+            chrono::Duration::seconds(10800),
+            DhtProtocolVersion::latest(),
+        );
+
+        let params = SendMessageParams::new()
+            .direct_public_key(recipient.public_key.clone())
+            .with_encryption(OutboundEncryption::encrypt_for(recipient.public_key.clone()))
+            .with_destination(recipient.public_key.clone().into())
+            .with_onion_routing(2)
+            .finish();
+        let (reply_tx, reply_rx) = oneshot::channel();
+        service
+            .call(DhtOutboundRequest::SendMessage(
+                Box::new(params.clone()),
+                b"custom_msg".as_slice().into(),
+                reply_tx,
+            ))
+            .await
+            .unwrap();
+
+        unpack_enum!(SendMessageResponse::Queued(tags) = reply_rx.await.unwrap());
+        assert_eq!(tags.len(), 1);
+        assert_eq!(spy.call_count(), 1);
+        let msg = spy.take_requests().pop().unwrap();
+        let relay = relays
+            .iter()
+            .find(|p| p.node_id == msg.destination_node_id)
+            .expect("message was not sent to a relay");
+        assert_eq!(msg.dht_message_type, DhtMessageType::OnionRelay);
+        assert!(msg.dht_flags.is_encrypted());
+        assert_eq!(msg.destination.public_key(), Some(&relay.public_key));
+        assert!(msg.ephemeral_public_key.is_some());
+        assert!(msg.message_signature.is_some());
+
+        // There are not enough relays for 3 hops
+        let mut params = params;
+        params.onion_hops = 3;
+        let (reply_tx, reply_rx) = oneshot::channel();
+        service
+            .call(DhtOutboundRequest::SendMessage(
+                Box::new(params),
+                b"custom_msg".as_slice().into(),
+                reply_tx,
+            ))
+            .await
+            .unwrap_err();
+        unpack_enum!(SendMessageResponse::Failed(_err) = reply_rx.await.unwrap());
+        assert!(spy.take_requests().is_empty());
+    }
 }
@@ -61,6 +61,10 @@ pub enum DhtOutboundError {
     CipherError(String),
     #[error("Padding error: `{0}`")]
     PaddingError(String),
+    #[error("Onion routing is not supported for this message: {0}")]
+    OnionRoutingNotSupported(&'static str),
+    #[error("Not enough onion relays available (required: {required}, available: {available})")]
+    InsufficientOnionRelays { required: usize, available: usize },
 }
 
 impl From<SchnorrSignatureError> for DhtOutboundError {
@@ -70,6 +70,7 @@ pub struct FinalSendMessageParams {
     pub dht_header: Option<DhtMessageHeader>,
     pub debug_info: Option<String>,
     pub tag: Option<MessageTag>,
+    pub onion_hops: usize,
 }
 
 impl Default for FinalSendMessageParams {
@@ -85,6 +86,7 @@ impl Default for FinalSendMessageParams {
             dht_header: None,
             debug_info: None,
             tag: None,
+            onion_hops: 0,
         }
     }
 }
@@ -238,6 +240,15 @@ impl SendMessageParams {
         self
     }
 
+    /// Route the message through `num_hops` randomly selected relay peers. Each relay is only able to determine the
+    /// previous and next hop, so the recipient and network observers cannot link the message to this node. This
+    /// requires the message to be encrypted for a direct public key recipient. A value of zero disables onion
+    /// routing.
+    pub fn with_onion_routing(&mut self, num_hops: usize) -> &mut Self {
+        self.params_mut().onion_hops = num_hops;
+        self
+    }
+
     /// Return the final SendMessageParams
     pub fn finish(&mut self) -> FinalSendMessageParams {
         self.params.take().expect("cannot be None")
@@ -98,6 +98,36 @@ impl OutboundMessageRequester {
         .await
     }
 
+    /// Send to a peer via `num_hops` randomly selected relays. The message is encrypted for the recipient and each
+    /// relay is only able to determine the previous and next hop, so neither the recipient nor network observers
+    /// can link the message to this node's network location. This node does not perform discovery, the final relay
+    /// will attempt discovery of the recipient if required.
+    pub async fn send_onion_routed<T>(
+        &mut self,
+        dest_public_key: CommsPublicKey,
+        message: OutboundDomainMessage<T>,
+        num_hops: usize,
+        source_info: String,
+    ) -> Result<SendMessageResponse, DhtOutboundError>
+    where
+        T: prost::Message,
+    {
+        self.send_message(
+            SendMessageParams::new()
+                .with_debug_info(format!(
+                    "Send onion-routed to {} from {}",
+                    &dest_public_key, source_info
+                ))
+                .direct_public_key(dest_public_key.clone())
+                .with_encryption(OutboundEncryption::encrypt_for(dest_public_key.clone()))
+                .with_destination(dest_public_key.into())
+                .with_onion_routing(num_hops)
+                .finish(),
+            message,
+        )
+        .await
+    }
+
     /// Send directly to a peer.
     pub async fn send_direct_node_id<T>(
         &mut self,
@@ -123,6 +123,21 @@ impl DhtActorMock {
                     .send(lock.iter().cloned().map(|p| p.node_id).collect())
                     .unwrap();
             },
+            SelectOnionRelays {
+                num_relays,
+                excluded,
+                reply_tx,
+            } => {
+                let lock = self.state.select_peers.read().unwrap();
+                reply_tx
+                    .send(Ok(lock
+                        .iter()
+                        .filter(|p| !excluded.contains(&p.node_id))
+                        .take(num_relays)
+                        .cloned()
+                        .collect()))
+                    .unwrap();
+            },
             GetMetadata(key, reply_tx) => {
                 let _result = reply_tx.send(Ok(self
                     .state
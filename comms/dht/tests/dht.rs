@@ -908,6 +908,93 @@ async fn test_dht_header_not_malleable() {
     node_C.shutdown().await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[allow(non_snake_case)]
+async fn test_dht_onion_routed_message() {
+    let config = dht_config();
+    // Node D (the recipient) knows no one
+    let mut node_D = make_node("node_D", PeerFeatures::COMMUNICATION_NODE, config.clone(), None).await;
+    // Node B and C (the relays) know about each other and Node D
+    let mut node_C = make_node(
+        "node_C",
+        PeerFeatures::COMMUNICATION_NODE,
+        config.clone(),
+        Some(node_D.to_peer()),
+    )
+    .await;
+    let node_B = make_node("node_B", PeerFeatures::COMMUNICATION_NODE, config.clone(), vec![
+        node_C.to_peer(),
+        node_D.to_peer(),
+    ])
+    .await;
+    node_C.comms.peer_manager().add_peer(node_B.to_peer()).await.unwrap();
+    // Node A (the sender) knows about Node B and C, but not Node D
+    let node_A = make_node("node_A", PeerFeatures::COMMUNICATION_NODE, config, vec![
+        node_B.to_peer(),
+        node_C.to_peer(),
+    ])
+    .await;
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    struct Person {
+        #[prost(string, tag = "1")]
+        name: String,
+        #[prost(uint32, tag = "2")]
+        age: u32,
+    }
+
+    let out_msg = OutboundDomainMessage::new(&123, Person {
+        name: "John Conway".into(),
+        age: 82,
+    });
+    let node_D_public_key = node_D.node_identity().public_key().clone();
+    node_A
+        .dht
+        .outbound_requester()
+        .send_message(
+            SendMessageParams::new()
+                .direct_public_key(node_D_public_key.clone())
+                .with_encryption(OutboundEncryption::encrypt_for(node_D_public_key.clone()))
+                .with_destination(node_D_public_key.into())
+                .with_onion_routing(2)
+                .finish(),
+            out_msg,
+        )
+        .await
+        .unwrap()
+        .resolve()
+        .await
+        .unwrap();
+
+    let msg = node_D
+        .next_inbound_message(Duration::from_secs(20))
+        .await
+        .expect("Node D expected an inbound message but it never arrived");
+    assert!(msg.decryption_succeeded());
+    assert_eq!(msg.dht_header.message_type, DhtMessageType::None);
+    // The recipient is able to authenticate the sender, but the message was delivered by one of the relays
+    assert_eq!(
+        msg.authenticated_origin.as_ref(),
+        Some(node_A.node_identity().public_key())
+    );
+    assert!(
+        msg.source_peer.node_id == *node_B.node_identity().node_id() ||
+            msg.source_peer.node_id == *node_C.node_identity().node_id()
+    );
+    let person = msg
+        .decryption_result
+        .unwrap()
+        .decode_part::<Person>(1)
+        .unwrap()
+        .unwrap();
+    assert_eq!(person.name, "John Conway");
+
+    node_A.shutdown().await;
+    node_B.shutdown().await;
+    node_C.shutdown().await;
+    node_D.shutdown().await;
+}
+
 fn filter_received(events: Vec<MessagingEvent>) -> Vec<MessagingEvent> {
     events
         .into_iter()
@@ -0,0 +1,44 @@
+// Copyright 2023. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+#![no_main]
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use libfuzzer_sys::fuzz_target;
+use tari_script::{ExecutionStack, TariScript};
+
+// Scripts and input data are received as part of transactions and blocks
+fuzz_target!(|data: &[u8]| {
+    if let Ok(script) = TariScript::from_bytes(data) {
+        assert_eq!(TariScript::from_bytes(&script.to_bytes()).unwrap(), script);
+    }
+    if let Ok(stack) = ExecutionStack::from_bytes(data) {
+        assert_eq!(ExecutionStack::from_bytes(&stack.to_bytes()).unwrap(), stack);
+    }
+    if let Ok(script) = TariScript::try_from_slice(data) {
+        assert_eq!(
+            TariScript::try_from_slice(&script.try_to_vec().unwrap()).unwrap(),
+            script
+        );
+    }
+    let _ = ExecutionStack::try_from_slice(data);
+});
@@ -42,7 +42,7 @@ use tari_comms::{
     NodeIdentity,
 };
 use tari_contacts::contacts_service::{
-    service::ContactOnlineStatus,
+    service::{ContactOnlineStatus, LivenessPrivacyMode},
     types::{Message, MessageMetadataType},
 };
 
@@ -103,6 +103,26 @@ extern "C" {
         error_our: *const c_int,
     ) -> *mut c_void;
     pub fn send_read_confirmation_for_message(client: *mut ClientFFI, message: *mut c_void, error_out: *const c_int);
+    pub fn set_chat_liveness_privacy_mode(client: *mut ClientFFI, mode: c_int, error_out: *const c_int);
+    pub fn set_chat_contact_liveness_privacy_mode(
+        client: *mut ClientFFI,
+        address: *mut c_void,
+        mode: c_int,
+        error_out: *const c_int,
+    );
+    pub fn request_chat_device_link(client: *mut ClientFFI, identity: *mut c_void, error_out: *const c_int);
+    pub fn approve_chat_device_link(client: *mut ClientFFI, device: *mut c_void, error_out: *const c_int);
+    pub fn unlink_chat_device(client: *mut ClientFFI, device: *mut c_void, error_out: *const c_int);
+    pub fn set_chat_message_requests_enabled(client: *mut ClientFFI, enabled: bool, error_out: *const c_int);
+    pub fn accept_chat_message_request(client: *mut ClientFFI, address: *mut c_void, error_out: *const c_int);
+    pub fn reject_chat_message_request(
+        client: *mut ClientFFI,
+        address: *mut c_void,
+        block: bool,
+        error_out: *const c_int,
+    );
+    pub fn block_chat_address(client: *mut ClientFFI, address: *mut c_void, error_out: *const c_int);
+    pub fn unblock_chat_address(client: *mut ClientFFI, address: *mut c_void, error_out: *const c_int);
 }
 
 #[derive(Debug)]
@@ -211,6 +231,105 @@ impl ChatClient for ChatFFI {
         }
     }
 
+    async fn set_liveness_privacy_mode(&self, mode: LivenessPrivacyMode) {
+        let client = self.ptr.lock().unwrap();
+        let error_out = Box::into_raw(Box::new(0));
+
+        unsafe {
+            set_chat_liveness_privacy_mode(client.0, c_int::from(mode.as_u8()), error_out);
+        }
+    }
+
+    async fn set_contact_liveness_privacy_mode(&self, address: &TariAddress, mode: Option<LivenessPrivacyMode>) {
+        let client = self.ptr.lock().unwrap();
+        let address_ptr = Box::into_raw(Box::new(address.clone())) as *mut c_void;
+        let mode = mode.map(|m| c_int::from(m.as_u8())).unwrap_or(0);
+        let error_out = Box::into_raw(Box::new(0));
+
+        unsafe {
+            set_chat_contact_liveness_privacy_mode(client.0, address_ptr, mode, error_out);
+        }
+    }
+
+    async fn request_device_link(&self, identity: &TariAddress) {
+        let client = self.ptr.lock().unwrap();
+        let address_ptr = Box::into_raw(Box::new(identity.clone())) as *mut c_void;
+        let error_out = Box::into_raw(Box::new(0));
+
+        unsafe {
+            request_chat_device_link(client.0, address_ptr, error_out);
+        }
+    }
+
+    async fn approve_device_link(&self, device: &TariAddress) {
+        let client = self.ptr.lock().unwrap();
+        let address_ptr = Box::into_raw(Box::new(device.clone())) as *mut c_void;
+        let error_out = Box::into_raw(Box::new(0));
+
+        unsafe {
+            approve_chat_device_link(client.0, address_ptr, error_out);
+        }
+    }
+
+    async fn unlink_device(&self, device: &TariAddress) {
+        let client = self.ptr.lock().unwrap();
+        let address_ptr = Box::into_raw(Box::new(device.clone())) as *mut c_void;
+        let error_out = Box::into_raw(Box::new(0));
+
+        unsafe {
+            unlink_chat_device(client.0, address_ptr, error_out);
+        }
+    }
+
+    async fn set_message_requests_enabled(&self, enabled: bool) {
+        let client = self.ptr.lock().unwrap();
+        let error_out = Box::into_raw(Box::new(0));
+
+        unsafe {
+            set_chat_message_requests_enabled(client.0, enabled, error_out);
+        }
+    }
+
+    async fn accept_message_request(&self, address: &TariAddress) {
+        let client = self.ptr.lock().unwrap();
+        let address_ptr = Box::into_raw(Box::new(address.clone())) as *mut c_void;
+        let error_out = Box::into_raw(Box::new(0));
+
+        unsafe {
+            accept_chat_message_request(client.0, address_ptr, error_out);
+        }
+    }
+
+    async fn reject_message_request(&self, address: &TariAddress, block: bool) {
+        let client = self.ptr.lock().unwrap();
+        let address_ptr = Box::into_raw(Box::new(address.clone())) as *mut c_void;
+        let error_out = Box::into_raw(Box::new(0));
+
+        unsafe {
+            reject_chat_message_request(client.0, address_ptr, block, error_out);
+        }
+    }
+
+    async fn block_address(&self, address: &TariAddress) {
+        let client = self.ptr.lock().unwrap();
+        let address_ptr = Box::into_raw(Box::new(address.clone())) as *mut c_void;
+        let error_out = Box::into_raw(Box::new(0));
+
+        unsafe {
+            block_chat_address(client.0, address_ptr, error_out);
+        }
+    }
+
+    async fn unblock_address(&self, address: &TariAddress) {
+        let client = self.ptr.lock().unwrap();
+        let address_ptr = Box::into_raw(Box::new(address.clone())) as *mut c_void;
+        let error_out = Box::into_raw(Box::new(0));
+
+        unsafe {
+            unblock_chat_address(client.0, address_ptr, error_out);
+        }
+    }
+
     fn identity(&self) -> &NodeIdentity {
         &self.identity
     }
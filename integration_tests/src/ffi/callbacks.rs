@@ -35,6 +35,7 @@ pub struct Callbacks {
     transaction_broadcast: Mutex<u64>,
     transaction_mined: Mutex<u64>,
     transaction_mined_unconfirmed: Mutex<u64>,
+    transaction_unmined: Mutex<u64>,
     transaction_faux_confirmed: Mutex<u64>,
     transaction_faux_unconfirmed: Mutex<u64>,
     transaction_cancelled: Mutex<u64>,
@@ -78,6 +79,11 @@ impl Callbacks {
         *self.transaction_mined_unconfirmed.lock().unwrap()
     }
 
+    #[allow(dead_code)]
+    pub fn get_transaction_unmined(&self) -> u64 {
+        *self.transaction_unmined.lock().unwrap()
+    }
+
     pub fn get_transaction_faux_confirmed(&self) -> u64 {
         *self.transaction_faux_confirmed.lock().unwrap()
     }
@@ -180,6 +186,16 @@ impl Callbacks {
         *self.transaction_mined_unconfirmed.lock().unwrap() += 1;
     }
 
+    pub fn on_transaction_unmined(&mut self, ptr: *mut c_void) {
+        let completed_transaction = CompletedTransaction::from_ptr(ptr);
+        println!(
+            "{} Transaction with txID {} was reorged out of the chain.",
+            chrono::Local::now().format("%Y/%m/%d %H:%M:%S"),
+            completed_transaction.get_transaction_id()
+        );
+        *self.transaction_unmined.lock().unwrap() += 1;
+    }
+
     pub fn on_faux_transaction_confirmed(&mut self, ptr: *mut c_void) {
         let completed_transaction = CompletedTransaction::from_ptr(ptr);
         println!(
@@ -296,6 +312,25 @@ impl Callbacks {
         );
     }
 
+    pub fn on_scheduled_payment_status(&mut self, payment_id: u64, tx_id: u64, status: u64) {
+        println!(
+            "{} Scheduled payment {} changed status to {} (TxId: {}).",
+            chrono::Local::now().format("%Y/%m/%d %H:%M:%S"),
+            payment_id,
+            status,
+            tx_id
+        );
+    }
+
+    pub fn on_transaction_approval_required(&mut self, approval_id: u64, amount: u64) {
+        println!(
+            "{} Transaction of {} uT requires approval (#{}).",
+            chrono::Local::now().format("%Y/%m/%d %H:%M:%S"),
+            amount,
+            approval_id
+        );
+    }
+
     pub fn on_basenode_state_update(&mut self, state: *mut c_void) {
         *self.basenode_state_updated.lock().unwrap() += 1;
         println!(
@@ -312,6 +347,7 @@ impl Callbacks {
         *self.transaction_broadcast.lock().unwrap() = 0;
         *self.transaction_mined.lock().unwrap() = 0;
         *self.transaction_mined_unconfirmed.lock().unwrap() = 0;
+        *self.transaction_unmined.lock().unwrap() = 0;
         *self.transaction_faux_confirmed.lock().unwrap() = 0;
         *self.transaction_faux_unconfirmed.lock().unwrap() = 0;
         *self.transaction_cancelled.lock().unwrap() = 0;
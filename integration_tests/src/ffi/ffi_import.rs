@@ -391,6 +391,7 @@ extern "C" {
         callback_transaction_broadcast: unsafe extern "C" fn(*mut TariCompletedTransaction),
         callback_transaction_mined: unsafe extern "C" fn(*mut TariCompletedTransaction),
         callback_transaction_mined_unconfirmed: unsafe extern "C" fn(*mut TariCompletedTransaction, u64),
+        callback_transaction_unmined: unsafe extern "C" fn(*mut TariCompletedTransaction),
         callback_faux_transaction_confirmed: unsafe extern "C" fn(*mut TariCompletedTransaction),
         callback_faux_transaction_unconfirmed: unsafe extern "C" fn(*mut TariCompletedTransaction, u64),
         callback_transaction_send_result: unsafe extern "C" fn(c_ulonglong, *mut TariTransactionSendStatus),
@@ -402,6 +403,8 @@ extern "C" {
         callback_saf_messages_received: unsafe extern "C" fn(),
         callback_connectivity_status: unsafe extern "C" fn(u64),
         callback_base_node_state_updated: unsafe extern "C" fn(*mut TariBaseNodeState),
+        callback_scheduled_payment_status: unsafe extern "C" fn(u64, u64, u64),
+        callback_transaction_approval_required: unsafe extern "C" fn(u64, u64),
         recovery_in_progress: *mut bool,
         error_out: *mut c_int,
     ) -> *mut TariWallet;
@@ -468,6 +471,7 @@ extern "C" {
         destination: *mut TariWalletAddress,
         amount: c_ulonglong,
         commitments: *mut TariVector,
+        selection_strategy: c_uint,
         fee_per_gram: c_ulonglong,
         message: *const c_char,
         one_sided: bool,
@@ -86,6 +86,11 @@ extern "C" fn callback_transaction_mined_unconfirmed(ptr: *mut TariCompletedTran
     callbacks.on_transaction_mined_unconfirmed(ptr, confirmations);
     // println!("callback_transaction_mined_unconfirmed");
 }
+extern "C" fn callback_transaction_unmined(ptr: *mut TariCompletedTransaction) {
+    let callbacks = Callbacks::instance();
+    callbacks.on_transaction_unmined(ptr);
+    // println!("callback_transaction_unmined");
+}
 extern "C" fn callback_faux_transaction_confirmed(ptr: *mut TariCompletedTransaction) {
     let callbacks = Callbacks::instance();
     callbacks.on_faux_transaction_confirmed(ptr);
@@ -140,6 +145,14 @@ extern "C" fn callback_base_node_state(state: *mut TariBaseNodeState) {
     let callbacks = Callbacks::instance();
     callbacks.on_basenode_state_update(state);
 }
+extern "C" fn callback_scheduled_payment_status(payment_id: u64, tx_id: u64, status: u64) {
+    let callbacks = Callbacks::instance();
+    callbacks.on_scheduled_payment_status(payment_id, tx_id, status);
+}
+extern "C" fn callback_transaction_approval_required(approval_id: u64, amount: u64) {
+    let callbacks = Callbacks::instance();
+    callbacks.on_transaction_approval_required(approval_id, amount);
+}
 
 #[derive(Default, Debug)]
 struct CachedBalance {
@@ -183,6 +196,7 @@ impl Wallet {
                 callback_transaction_broadcast,
                 callback_transaction_mined,
                 callback_transaction_mined_unconfirmed,
+                callback_transaction_unmined,
                 callback_faux_transaction_confirmed,
                 callback_faux_transaction_unconfirmed,
                 callback_transaction_send_result,
@@ -194,6 +208,8 @@ impl Wallet {
                 callback_saf_messages_received,
                 callback_connectivity_status,
                 callback_base_node_state,
+                callback_scheduled_payment_status,
+                callback_transaction_approval_required,
                 &mut recovery_in_progress,
                 &mut error,
             );
@@ -332,6 +348,7 @@ impl Wallet {
                 WalletAddress::from_hex(dest).get_ptr(),
                 amount,
                 null_mut(),
+                0,
                 fee_per_gram,
                 CString::new(message).unwrap().into_raw(),
                 one_sided,
@@ -115,6 +115,8 @@ impl MergeMiningProxyProcess {
                             origin_submission.to_string(),
                         ),
                     ],
+                    print_config: false,
+                    explain: false,
                 },
             };
             let rt = runtime::Builder::new_multi_thread().enable_all().build().unwrap();
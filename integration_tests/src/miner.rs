@@ -120,6 +120,8 @@ impl MinerProcess {
                     ("miner.mine_on_tip_only".to_string(), "false".to_string()),
                 ],
                 network: Some(Network::LocalNet),
+                print_config: false,
+                explain: false,
             },
             mine_until_height: None,
             miner_max_blocks: blocks,
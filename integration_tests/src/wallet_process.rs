@@ -209,9 +209,12 @@ pub fn get_default_cli() -> Cli {
             log_level: None,
             network: None,
             config_property_overrides: vec![],
+            print_config: false,
+            explain: false,
         },
         password: None,
         change_password: false,
+        use_keyring: false,
         recovery: false,
         seed_words: None,
         seed_words_file_name: None,
@@ -33,6 +33,7 @@ use minotari_console_wallet::{
     ExportUtxosArgs,
     MakeItRainArgs,
     SendMinotariArgs,
+    SendMinotariInteractiveArgs,
     SetBaseNodeArgs,
     WhoisArgs,
 };
@@ -155,7 +156,10 @@ async fn send_from_cli(world: &mut TariWorld, amount: u64, wallet_a: String, wal
         message: format!("Send amount {} from {} to {}", amount, wallet_a, wallet_b),
         destination: wallet_b_address,
     };
-    cli.command2 = Some(CliCommands::SendMinotari(args));
+    cli.command2 = Some(CliCommands::SendMinotari(SendMinotariInteractiveArgs {
+        args,
+        recipient_check: None,
+    }));
 
     let base_node = world.wallet_connected_to_base_node.get(&wallet_a).unwrap();
     let seed_nodes = world.base_nodes.get(base_node).unwrap().seed_nodes.clone();
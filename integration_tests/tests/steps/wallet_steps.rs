@@ -687,6 +687,7 @@ async fn send_amount_from_source_wallet_to_dest_wallet_without_broadcast(
     };
     let transfer_req = TransferRequest {
         recipients: vec![payment_recipient],
+        utxo_selection_strategy: 0, // default selection strategy
     };
     let tx_res = source_client.transfer(transfer_req).await.unwrap().into_inner();
     let tx_res = tx_res.results;
@@ -747,6 +748,7 @@ async fn send_one_sided_transaction_from_source_wallet_to_dest_wallt(
     };
     let transfer_req = TransferRequest {
         recipients: vec![payment_recipient],
+        utxo_selection_strategy: 0, // default selection strategy
     };
     let tx_res = source_client.transfer(transfer_req).await.unwrap().into_inner();
     let tx_res = tx_res.results;
@@ -846,6 +848,7 @@ async fn send_amount_from_wallet_to_wallet_at_fee(
     };
     let transfer_req = TransferRequest {
         recipients: vec![payment_recipient],
+        utxo_selection_strategy: 0, // default selection strategy
     };
     let tx_res = sender_wallet_client.transfer(transfer_req).await.unwrap().into_inner();
     let tx_res = tx_res.results;
@@ -1263,6 +1266,7 @@ async fn send_num_transactions_to_wallets_at_fee(
         };
         let transfer_req = TransferRequest {
             recipients: vec![payment_recipient],
+            utxo_selection_strategy: 0, // default selection strategy
         };
         let transfer_res = sender_wallet_client.transfer(transfer_req).await.unwrap().into_inner();
         let transfer_res = transfer_res.results.first().unwrap();
@@ -1404,6 +1408,7 @@ async fn transfer_tari_from_wallet_to_receiver(world: &mut TariWorld, amount: u6
     };
     let transfer_req = TransferRequest {
         recipients: vec![payment_recipient],
+        utxo_selection_strategy: 0, // default selection strategy
     };
     let tx_res = sender_wallet_client.transfer(transfer_req).await.unwrap().into_inner();
     let tx_res = tx_res.results;
@@ -1610,6 +1615,7 @@ async fn transfer_from_wallet_to_two_recipients_at_fee(
     };
     let transfer_req = TransferRequest {
         recipients: vec![payment_recipient1, payment_recipient2],
+        utxo_selection_strategy: 0, // default selection strategy
     };
     let tx_res = sender_client.transfer(transfer_req).await.unwrap().into_inner();
     let tx_res = tx_res.results;
@@ -1721,6 +1727,7 @@ async fn transfer_tari_to_self(world: &mut TariWorld, amount: u64, sender: Strin
     };
     let transfer_req = TransferRequest {
         recipients: vec![payment_recipient],
+        utxo_selection_strategy: 0, // default selection strategy
     };
     let tx_res = sender_wallet_client.transfer(transfer_req).await.unwrap().into_inner();
     let tx_res = tx_res.results;
@@ -2094,6 +2101,7 @@ async fn send_one_sided_stealth_transaction(
     };
     let transfer_req = TransferRequest {
         recipients: vec![payment_recipient],
+        utxo_selection_strategy: 0, // default selection strategy
     };
     let tx_res = sender_client.transfer(transfer_req).await.unwrap().into_inner();
     let tx_res = tx_res.results;
@@ -2576,6 +2584,7 @@ async fn multi_send_txs_from_wallet(
 
         let transfer_req = TransferRequest {
             recipients: vec![payment_recipient],
+            utxo_selection_strategy: 0, // default selection strategy
         };
         let tx_res = sender_wallet_client.transfer(transfer_req).await.unwrap().into_inner();
         let tx_res = tx_res.results;